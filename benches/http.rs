@@ -48,7 +48,7 @@ fn benchmark_http(c: &mut Criterion) {
                             "out",
                             &["in"],
                             sinks::http::HttpSinkConfig {
-                                uri: out_addr.to_string().parse::<http::Uri>().unwrap().into(),
+                                uri: out_addr.to_string(),
                                 compression: *compression,
                                 method: Default::default(),
                                 auth: Default::default(),
@@ -57,6 +57,7 @@ fn benchmark_http(c: &mut Criterion) {
                                 encoding: (None::<FramingConfig>, TextSerializerConfig::new())
                                     .into(),
                                 request: Default::default(),
+                                request_per_event: false,
                                 tls: Default::default(),
                                 acknowledgements: Default::default(),
                             },
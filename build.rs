@@ -93,6 +93,37 @@ impl BuildConstants {
     }
 }
 
+/// Compiles the BPF programs of the `ebpf` source, which are embedded in Vector.
+#[cfg(feature = "sources-ebpf")]
+fn compile_bpf() {
+    const SOURCE: &str = "src/sources/ebpf/bpf/events.bpf.c";
+    println!("cargo:rerun-if-changed={}", SOURCE);
+    println!("cargo:rerun-if-env-changed=CLANG");
+
+    if env::var("CARGO_CFG_TARGET_OS").as_deref() != Ok("linux") {
+        return;
+    }
+    let arch = match env::var("CARGO_CFG_TARGET_ARCH").as_deref() {
+        Ok("x86_64") => "x86",
+        Ok("aarch64") => "arm64",
+        arch => panic!(
+            "The `ebpf` source does not support target architecture {:?}",
+            arch
+        ),
+    };
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR not present in build script!");
+    let clang = env::var("CLANG").unwrap_or_else(|_| "clang".to_string());
+    let status = std::process::Command::new(&clang)
+        .args(["-O2", "-g", "-target", "bpf"])
+        .arg(format!("-D__TARGET_ARCH_{}", arch))
+        .args(["-c", SOURCE, "-o"])
+        .arg(Path::new(&out_dir).join("events.bpf.o"))
+        .status()
+        .unwrap_or_else(|error| panic!("Failed to run {}: {}", clang, error));
+    assert!(status.success(), "Failed to compile {}", SOURCE);
+}
+
 fn main() {
     // Always rerun if the build script itself changes.
     println!("cargo:rerun-if-changed=build.rs");
@@ -103,6 +134,8 @@ fn main() {
         println!("cargo:rerun-if-changed=proto/dnstap.proto");
         println!("cargo:rerun-if-changed=proto/ddsketch_full.proto");
         println!("cargo:rerun-if-changed=proto/dd_metric.proto");
+        println!("cargo:rerun-if-changed=proto/kinesis_aggregation.proto");
+        println!("cargo:rerun-if-changed=proto/google/cloud/bigquery/storage/v1/storage.proto");
         println!("cargo:rerun-if-changed=proto/google/pubsub/v1/pubsub.proto");
         println!("cargo:rerun-if-changed=proto/google/rpc/status.proto");
         println!("cargo:rerun-if-changed=proto/vector.proto");
@@ -125,6 +158,8 @@ fn main() {
                     "proto/ddsketch_full.proto",
                     "proto/dd_metric.proto",
                     "proto/dd_trace.proto",
+                    "proto/kinesis_aggregation.proto",
+                    "proto/google/cloud/bigquery/storage/v1/storage.proto",
                     "proto/google/pubsub/v1/pubsub.proto",
                     "proto/google/rpc/status.proto",
                     "proto/vector.proto",
@@ -135,6 +170,9 @@ fn main() {
             .unwrap();
     }
 
+    #[cfg(feature = "sources-ebpf")]
+    compile_bpf();
+
     // We keep track of which environment variables we slurp in, and then emit stanzas at the end to
     // inform Cargo when it needs to rerun this build script.  This allows us to avoid rerunning it
     // every single time unless something _actually_ changes.
@@ -2,9 +2,11 @@ use std::convert::TryInto;
 
 use bytes::Bytes;
 use chrono::Utc;
+use derivative::Derivative;
 use serde::{Deserialize, Serialize};
 use smallvec::{smallvec, SmallVec};
 use value::Kind;
+use vector_config::configurable_component;
 use vector_core::{
     config::{log_schema, DataType, LogNamespace},
     event::Event,
@@ -15,7 +17,11 @@ use super::Deserializer;
 
 /// Config used to build a `JsonDeserializer`.
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
-pub struct JsonDeserializerConfig;
+pub struct JsonDeserializerConfig {
+    /// Options for the JSON deserializer.
+    #[serde(default)]
+    pub json: JsonDeserializerOptions,
+}
 
 impl JsonDeserializerConfig {
     /// Build the `JsonDeserializer` from this configuration.
@@ -54,15 +60,69 @@ impl JsonDeserializerConfig {
     }
 }
 
+/// JSON deserializer options.
+#[configurable_component]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct JsonDeserializerOptions {
+    /// The engine used to parse JSON.
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    pub engine: JsonEngine,
+}
+
+/// The engine used to parse JSON.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, PartialEq, Eq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum JsonEngine {
+    /// Parse with `serde_json`.
+    #[derivative(Default)]
+    Serde,
+
+    /// Parse with `simd-json`, which uses SIMD instructions to parse large inputs significantly
+    /// faster.
+    ///
+    /// SIMD instructions are only used when Vector is built for a CPU supporting AVX2 or SSE 4.2
+    /// on x86, or NEON on ARM. Otherwise, `simd-json` falls back to a scalar implementation.
+    ///
+    /// Inputs that `simd-json` can't parse, such as those containing invalid UTF-8, are parsed
+    /// again with `serde_json`, so that the same events, or errors, are produced as with the
+    /// `serde` engine.
+    Simd,
+}
+
 /// Deserializer that builds `Event`s from a byte frame containing JSON.
 #[derive(Debug, Clone, Default)]
-pub struct JsonDeserializer;
+pub struct JsonDeserializer {
+    engine: JsonEngine,
+}
 
 impl JsonDeserializer {
     /// Creates a new `JsonDeserializer`.
     pub fn new() -> Self {
         Default::default()
     }
+
+    /// Creates a new `JsonDeserializer` parsing JSON with the given engine.
+    pub const fn with_engine(engine: JsonEngine) -> Self {
+        Self { engine }
+    }
+
+    fn parse_json(&self, bytes: &[u8]) -> vector_common::Result<serde_json::Value> {
+        if self.engine == JsonEngine::Simd {
+            // `simd-json` parses in place, so it needs its own copy of the input.
+            let mut buffer = bytes.to_vec();
+            if let Ok(json) = simd_json::serde::from_slice(&mut buffer) {
+                return Ok(json);
+            }
+        }
+
+        serde_json::from_slice(bytes)
+            .map_err(|error| format!("Error parsing JSON: {:?}", error).into())
+    }
 }
 
 impl Deserializer for JsonDeserializer {
@@ -77,8 +137,7 @@ impl Deserializer for JsonDeserializer {
             return Ok(smallvec![]);
         }
 
-        let json: serde_json::Value = serde_json::from_slice(&bytes)
-            .map_err(|error| format!("Error parsing JSON: {:?}", error))?;
+        let json = self.parse_json(&bytes)?;
 
         // If the root is an Array, split it into multiple events
         let mut events = match json {
@@ -111,8 +170,8 @@ impl Deserializer for JsonDeserializer {
 }
 
 impl From<&JsonDeserializerConfig> for JsonDeserializer {
-    fn from(_: &JsonDeserializerConfig) -> Self {
-        Self
+    fn from(config: &JsonDeserializerConfig) -> Self {
+        Self::with_engine(config.json.engine)
     }
 }
 
@@ -197,4 +256,32 @@ mod tests {
             assert!(deserializer.parse(input.clone(), namespace).is_err());
         }
     }
+
+    #[test]
+    fn deserialize_json_simd() {
+        let input = Bytes::from(
+            r#"[{ "foo": 123, "bar": { "baz": [true, null, 1.5] } }, { "qux": "quux" }]"#,
+        );
+        let serde = JsonDeserializer::with_engine(JsonEngine::Serde);
+        let simd = JsonDeserializer::with_engine(JsonEngine::Simd);
+
+        let events = simd.parse(input.clone(), LogNamespace::Vector).unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].as_log()["foo"], 123.into());
+        assert_eq!(events[0].as_log()["bar.baz[2]"], 1.5.into());
+        assert_eq!(events[1].as_log()["qux"], "quux".into());
+        assert_eq!(events, serde.parse(input, LogNamespace::Vector).unwrap());
+    }
+
+    #[test]
+    fn deserialize_json_simd_falls_back_to_serde() {
+        let deserializer = JsonDeserializer::with_engine(JsonEngine::Simd);
+
+        for namespace in [LogNamespace::Legacy, LogNamespace::Vector] {
+            assert!(deserializer
+                .parse(Bytes::from(&b"{ \"foo\": \"\xff\" }"[..]), namespace)
+                .is_err());
+            assert!(deserializer.parse(Bytes::from("{ foo"), namespace).is_err());
+        }
+    }
 }
@@ -101,9 +101,9 @@ mod tests {
             .with_namespace(Some("vector"))
             .with_tags(Some(
                 vec![
-                    ("key2".to_owned(), "value2".to_owned()),
-                    ("key1".to_owned(), "value1".to_owned()),
-                    ("Key3".to_owned(), "Value3".to_owned()),
+                    ("key2".into(), "value2".to_owned()),
+                    ("key1".into(), "value1".to_owned()),
+                    ("Key3".into(), "Value3".to_owned()),
                 ]
                 .into_iter()
                 .collect(),
@@ -31,7 +31,10 @@
 
 use std::{
     collections::{BTreeMap, HashMap},
-    sync::{Arc, Mutex},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
 };
 
 use arc_swap::ArcSwap;
@@ -47,6 +50,7 @@ type TableMap = HashMap<String, Box<dyn Table + Send + Sync>>;
 pub struct TableRegistry {
     loading: Arc<Mutex<Option<TableMap>>>,
     tables: Arc<ArcSwap<Option<TableMap>>>,
+    generation: Arc<AtomicUsize>,
 }
 
 impl TableRegistry {
@@ -109,6 +113,9 @@ impl TableRegistry {
         let mut tables_lock = self.loading.lock().unwrap();
         let tables = tables_lock.take();
         self.tables.swap(Arc::new(tables));
+        // Only once the tables are swapped, so that the results of searches of the previous tables
+        // are never taken for results of the current ones.
+        self.generation.fetch_add(1, Ordering::Release);
     }
 
     /// Return a list of the available tables that we can write to.
@@ -154,7 +161,7 @@ impl TableRegistry {
     /// Returns a cheaply clonable struct through that provides lock free read
     /// access to the enrichment tables.
     pub fn as_readonly(&self) -> TableSearch {
-        TableSearch(self.tables.clone())
+        TableSearch(self.tables.clone(), self.generation.clone())
     }
 
     /// Returns the indexes that have been applied to the given table.
@@ -192,9 +199,15 @@ impl std::fmt::Debug for TableRegistry {
 /// `vrl::EnrichmentTableSearch` trait. Cloning this object is designed to be
 /// cheap. The underlying data will be shared by all clones.
 #[derive(Clone, Default)]
-pub struct TableSearch(Arc<ArcSwap<Option<TableMap>>>);
+pub struct TableSearch(Arc<ArcSwap<Option<TableMap>>>, Arc<AtomicUsize>);
 
 impl TableSearch {
+    /// Returns the generation of the loaded tables, which changes every time they are loaded, so
+    /// that the results of searches can be cached until the tables are reloaded.
+    pub fn generation(&self) -> usize {
+        self.1.load(Ordering::Acquire)
+    }
+
     /// Search the given table to find the data.
     ///
     /// If we are in the writing stage, this function will return an error.
@@ -372,6 +385,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn generation_changes_on_reload() {
+        let mut tables: TableMap = HashMap::new();
+        tables.insert("dummy1".to_string(), Box::new(DummyEnrichmentTable::new()));
+
+        let registry = super::TableRegistry::default();
+        let tables_search = registry.as_readonly();
+        registry.load(tables);
+        registry.finish_load();
+        let generation = tables_search.generation();
+
+        registry.load(HashMap::new());
+        assert_eq!(generation, tables_search.generation());
+
+        registry.finish_load();
+        assert_ne!(generation, tables_search.generation());
+    }
+
     #[test]
     fn can_reload() {
         let mut tables: TableMap = HashMap::new();
@@ -1,10 +1,14 @@
+//! Generators of random log lines in common formats.
+//!
+//! All the random parts of the lines are drawn from the given random number generator, so that a
+//! seeded generator always yields the same lines. The timestamps are those of the current time.
+
 use chrono::{
     format::{DelayedFormat, StrftimeItems},
-    prelude::Local,
+    prelude::{Local, Utc},
     SecondsFormat,
 };
-use fakedata_generator::{gen_domain, gen_ipv4, gen_username};
-use rand::{thread_rng, Rng};
+use rand::Rng;
 
 static APPLICATION_NAMES: [&str; 10] = [
     "auth", "data", "deploy", "etl", "scraper", "cron", "ingress", "egress", "alerter", "fwd",
@@ -45,71 +49,193 @@ static ERROR_MESSAGES: [&str; 9] = [
     "Pretty pretty pretty good",
 ];
 
+static WORDS: [&str; 16] = [
+    "booper",
+    "chief",
+    "ubiquitous",
+    "deleniti",
+    "hoppe",
+    "schoen",
+    "totam",
+    "leadworld",
+    "scalable",
+    "quantum",
+    "synergy",
+    "wireless",
+    "magnetic",
+    "velvet",
+    "ember",
+    "harbor",
+];
+
+static TOP_LEVEL_DOMAINS: [&str; 6] = ["com", "io", "net", "org", "dev", "biz"];
+
+static USER_AGENTS: [&str; 5] = [
+    "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/107.0.0.0 Safari/537.36",
+    "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/16.1 Safari/605.1.15",
+    "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:106.0) Gecko/20100101 Firefox/106.0",
+    "curl/7.86.0",
+    "kube-probe/1.25",
+];
+
+static KUBERNETES_VERBS: [&str; 7] = [
+    "get", "list", "watch", "create", "update", "patch", "delete",
+];
+
+static KUBERNETES_RESOURCES: [&str; 8] = [
+    "pods",
+    "services",
+    "deployments",
+    "configmaps",
+    "secrets",
+    "namespaces",
+    "nodes",
+    "leases",
+];
+
+static KUBERNETES_NAMESPACES: [&str; 5] = [
+    "default",
+    "kube-system",
+    "monitoring",
+    "ingress-nginx",
+    "production",
+];
+
+static KUBERNETES_USERS: [&str; 5] = [
+    "system:kube-scheduler",
+    "system:kube-controller-manager",
+    "system:serviceaccount:kube-system:coredns",
+    "system:node:worker-1",
+    "admin",
+];
+
+static KUBERNETES_STAGES: [&str; 3] = ["RequestReceived", "ResponseStarted", "ResponseComplete"];
+
+static KUBERNETES_LEVELS: [&str; 3] = ["Metadata", "Request", "RequestResponse"];
+
+static KUBERNETES_CODES: [usize; 6] = [200, 201, 403, 404, 409, 500];
+
+static ALB_TYPES: [&str; 4] = ["http", "https", "h2", "ws"];
+
+static WINDOWS_CHANNELS: [&str; 3] = ["Security", "System", "Application"];
+
+/// The events of the Windows event log, as their ID, provider, level and message.
+static WINDOWS_EVENTS: [(usize, &str, &str, &str); 8] = [
+    (
+        4624,
+        "Microsoft-Windows-Security-Auditing",
+        "Information",
+        "An account was successfully logged on.",
+    ),
+    (
+        4625,
+        "Microsoft-Windows-Security-Auditing",
+        "Information",
+        "An account failed to log on.",
+    ),
+    (
+        4634,
+        "Microsoft-Windows-Security-Auditing",
+        "Information",
+        "An account was logged off.",
+    ),
+    (
+        4688,
+        "Microsoft-Windows-Security-Auditing",
+        "Information",
+        "A new process has been created.",
+    ),
+    (
+        7036,
+        "Service Control Manager",
+        "Information",
+        "The Windows Update service entered the running state.",
+    ),
+    (
+        7034,
+        "Service Control Manager",
+        "Error",
+        "The Print Spooler service terminated unexpectedly.",
+    ),
+    (
+        1000,
+        "Application Error",
+        "Error",
+        "Faulting application name: vector.exe.",
+    ),
+    (
+        41,
+        "Microsoft-Windows-Kernel-Power",
+        "Critical",
+        "The system has rebooted without cleanly shutting down first.",
+    ),
+];
+
 const APACHE_COMMON_TIME_FORMAT: &str = "%d/%b/%Y:%T %z";
 const APACHE_ERROR_TIME_FORMAT: &str = "%a %b %d %T %Y";
 const SYSLOG_3164_FORMAT: &str = "%b %d %T";
 const JSON_TIME_FORMAT: &str = "%d/%b/%Y:%T";
 
-pub fn apache_common_log_line() -> String {
+pub fn apache_common_log_line<R: Rng + ?Sized>(rng: &mut R) -> String {
     // Example log line:
     // 173.159.239.159 - schoen1464 [31/Oct/2020:19:06:10 -0700] "POST /wireless HTTP/2.0" 100 20815
     format!(
         "{} - {} [{}] \"{} {} {}\" {} {}",
-        ipv4_address(),
-        username(),
+        ipv4_address(rng),
+        username(rng),
         timestamp_apache_common(),
-        http_method(),
-        http_endpoint(),
-        http_version(),
-        http_code(),
-        byte_size(),
+        http_method(rng),
+        http_endpoint(rng),
+        http_version(rng),
+        http_code(rng),
+        byte_size(rng),
     )
 }
 
-pub fn apache_error_log_line() -> String {
+pub fn apache_error_log_line<R: Rng + ?Sized>(rng: &mut R) -> String {
     // Example log line:
     // [Sat Oct 31 19:27:55 2020] [deleniti:crit] [pid 879:tid 9607] [client 169.198.228.174:1364] Something bad happened
     format!(
         "[{}] [{}:{}] [pid {}:tid] [client {}:{}] {}",
         timestamp_apache_error(),
-        username(),
-        error_level(),
-        pid(),
-        ipv4_address(),
-        port(),
-        error_message(),
+        username(rng),
+        error_level(rng),
+        pid(rng),
+        ipv4_address(rng),
+        port(rng),
+        error_message(rng),
     )
 }
 
-pub fn syslog_3164_log_line() -> String {
+pub fn syslog_3164_log_line<R: Rng + ?Sized>(rng: &mut R) -> String {
     format!(
         "<{}>{} {} {}[{}]: {}",
-        priority(),
+        priority(rng),
         timestamp_syslog_3164(),
-        domain(),
-        application(),
-        pid(),
-        error_message()
+        domain(rng),
+        application(rng),
+        pid(rng),
+        error_message(rng)
     )
 }
 
-pub fn syslog_5424_log_line() -> String {
+pub fn syslog_5424_log_line<R: Rng + ?Sized>(rng: &mut R) -> String {
     // Example log line:
     // <65>2 2020-11-05T18:11:43.975Z chiefubiquitous.io totam 6899 ID44 - Something bad happened
     format!(
         "<{}>{} {} {} {} {} ID{} - {}",
-        priority(),
-        syslog_version(),
+        priority(rng),
+        syslog_version(rng),
         timestamp_syslog_5424(),
-        domain(),
-        username(),
-        random_in_range(100, 9999),
-        random_in_range(1, 999),
-        error_message(),
+        domain(rng),
+        username(rng),
+        random_in_range(rng, 100, 9999),
+        random_in_range(rng, 1, 999),
+        error_message(rng),
     )
 }
 
-pub fn json_log_line() -> String {
+pub fn json_log_line<R: Rng + ?Sized>(rng: &mut R) -> String {
     // Borrowed from Flog: https://github.com/mingrammer/flog/blob/master/log.go#L24
     // Example log line:
     // {"host":"208.171.64.160", "user-identifier":"hoppe7055", "datetime":" -0800", "method": \
@@ -117,15 +243,147 @@ pub fn json_log_line() -> String {
     //   "status":403, "bytes":25926, "referer": "https://www.leadworld-class.org/revolutionize/applications"}
     format!(
         "{{\"host\":\"{}\",\"user-identifier\":\"{}\",\"datetime\":\"{}\",\"method\":\"{}\",\"request\":\"{}\",\"protocol\":\"{}\",\"status\":\"{}\",\"bytes\":{},\"referer\":\"{}\"}}",
-        ipv4_address(),
-        username(),
+        ipv4_address(rng),
+        username(rng),
         timestamp_json(),
-        http_method(),
-        http_endpoint(),
-        http_version(),
-        http_code(),
-        random_in_range(1000, 50000),
-        referer(),
+        http_method(rng),
+        http_endpoint(rng),
+        http_version(rng),
+        http_code(rng),
+        random_in_range(rng, 1000, 50000),
+        referer(rng),
+    )
+}
+
+pub fn kubernetes_audit_log_line<R: Rng + ?Sized>(rng: &mut R) -> String {
+    // Example log line:
+    // {"kind":"Event","apiVersion":"audit.k8s.io/v1","level":"Metadata","auditID":"0c7d2c0e-...", \
+    //   "stage":"ResponseComplete","requestURI":"/api/v1/namespaces/default/pods","verb":"list", \
+    //   "user":{"username":"admin","groups":["system:authenticated"]},"sourceIPs":["10.0.0.12"], \
+    //   "userAgent":"kubectl/v1.25.4","objectRef":{"resource":"pods","namespace":"default", \
+    //   "apiVersion":"v1"},"responseStatus":{"metadata":{},"code":200}, \
+    //   "requestReceivedTimestamp":"2022-11-21T10:31:07.123456Z","stageTimestamp":"2022-11-21T10:31:07.125678Z"}
+    let verb = random_from_array(rng, &KUBERNETES_VERBS);
+    let resource = random_from_array(rng, &KUBERNETES_RESOURCES);
+    let namespace = random_from_array(rng, &KUBERNETES_NAMESPACES);
+    let timestamp = Utc::now().to_rfc3339_opts(SecondsFormat::Micros, true);
+    format!(
+        "{{\"kind\":\"Event\",\"apiVersion\":\"audit.k8s.io/v1\",\"level\":\"{}\",\"auditID\":\"{}\",\"stage\":\"{}\",\"requestURI\":\"/api/v1/namespaces/{}/{}\",\"verb\":\"{}\",\"user\":{{\"username\":\"{}\",\"groups\":[\"system:authenticated\"]}},\"sourceIPs\":[\"{}\"],\"userAgent\":\"kubectl/v1.25.{}\",\"objectRef\":{{\"resource\":\"{}\",\"namespace\":\"{}\",\"apiVersion\":\"v1\"}},\"responseStatus\":{{\"metadata\":{{}},\"code\":{}}},\"requestReceivedTimestamp\":\"{}\",\"stageTimestamp\":\"{}\"}}",
+        random_from_array(rng, &KUBERNETES_LEVELS),
+        uuid(rng),
+        random_from_array(rng, &KUBERNETES_STAGES),
+        namespace,
+        resource,
+        verb,
+        random_from_array(rng, &KUBERNETES_USERS),
+        ipv4_address(rng),
+        random_in_range(rng, 0, 10),
+        resource,
+        namespace,
+        random_from_array_copied(rng, &KUBERNETES_CODES),
+        timestamp,
+        timestamp,
+    )
+}
+
+pub fn nginx_ingress_log_line<R: Rng + ?Sized>(rng: &mut R) -> String {
+    // Example log line, in the default format of the ingress-nginx controller:
+    // 10.0.0.12 - - [21/Nov/2022:10:31:07 +0000] "GET /apps/deploy HTTP/1.1" 200 612 "-" \
+    //   "curl/7.86.0" 237 0.003 [default-web-80] [] 10.244.1.7:8080 612 0.004 200 5c4a0ba8e5e9f27c0ed7b4c1a5d3e9f0
+    format!(
+        "{} - - [{}] \"{} {} {}\" {} {} \"{}\" \"{}\" {} {:.3} [{}-{}-{}] [] {}:{} {} {:.3} {} {:032x}",
+        ipv4_address(rng),
+        timestamp_apache_common(),
+        http_method(rng),
+        http_endpoint(rng),
+        http_version(rng),
+        http_code(rng),
+        byte_size(rng),
+        referer(rng),
+        random_from_array(rng, &USER_AGENTS),
+        random_in_range(rng, 100, 2000),
+        latency_secs(rng),
+        random_from_array(rng, &KUBERNETES_NAMESPACES),
+        application(rng),
+        random_from_array_copied(rng, &[80, 443, 8080]),
+        ipv4_address(rng),
+        random_from_array_copied(rng, &[80, 8080, 9000]),
+        byte_size(rng),
+        latency_secs(rng),
+        http_code(rng),
+        rng.gen::<u128>(),
+    )
+}
+
+pub fn aws_alb_log_line<R: Rng + ?Sized>(rng: &mut R) -> String {
+    // Example log line:
+    // https 2022-11-21T10:31:07.123456Z app/my-loadbalancer/50dc6c495c0c9188 192.168.131.39:2817 \
+    //   10.0.0.1:80 0.000 0.001 0.000 200 200 34 366 "GET https://www.example.com:443/ HTTP/1.1" \
+    //   "curl/7.86.0" ECDHE-RSA-AES128-GCM-SHA256 TLSv1.2 \
+    //   arn:aws:elasticloadbalancing:us-east-2:123456789012:targetgroup/my-targets/73e2d6bc24d8a067 \
+    //   "Root=1-58337262-36d228ad5d99923122bbe354" "www.example.com" "-" 0 2022-11-21T10:31:07.122000Z \
+    //   "forward" "-" "-" "10.0.0.1:80" "200" "-" "-"
+    let status = http_code(rng);
+    let target = format!("{}:{}", ipv4_address(rng), port(rng));
+    let domain = domain(rng);
+    let timestamp = Utc::now().to_rfc3339_opts(SecondsFormat::Micros, true);
+    format!(
+        "{} {} app/{}-lb/{:016x} {}:{} {} {:.3} {:.3} {:.3} {} {} {} {} \"{} https://{}:443{} {}\" \"{}\" ECDHE-RSA-AES128-GCM-SHA256 TLSv1.2 arn:aws:elasticloadbalancing:us-east-2:{:012}:targetgroup/{}-targets/{:016x} \"Root=1-{:08x}-{:024x}\" \"{}\" \"-\" 0 {} \"forward\" \"-\" \"-\" \"{}\" \"{}\" \"-\" \"-\"",
+        random_from_array(rng, &ALB_TYPES),
+        timestamp,
+        application(rng),
+        rng.gen::<u64>(),
+        ipv4_address(rng),
+        port(rng),
+        target,
+        latency_secs(rng),
+        latency_secs(rng),
+        latency_secs(rng),
+        status,
+        status,
+        random_in_range(rng, 50, 2000),
+        byte_size(rng),
+        http_method(rng),
+        domain,
+        http_endpoint(rng),
+        http_version(rng),
+        random_from_array(rng, &USER_AGENTS),
+        rng.gen_range(0..1_000_000_000_000_u64),
+        application(rng),
+        rng.gen::<u64>(),
+        rng.gen::<u32>(),
+        rng.gen::<u128>() >> 32,
+        domain,
+        timestamp,
+        target,
+        status,
+    )
+}
+
+pub fn windows_event_log_line<R: Rng + ?Sized>(rng: &mut R) -> String {
+    // Example log line:
+    // {"EventID":4624,"Channel":"Security","Provider":"Microsoft-Windows-Security-Auditing", \
+    //   "Computer":"harbor-ember.corp.local","Level":"Information","RecordNumber":482910, \
+    //   "ProcessID":632,"Message":"An account was successfully logged on.", \
+    //   "TimeCreated":"2022-11-21T10:31:07.1234567Z"}
+    let (event_id, provider, level, message) = random_from_array_copied(rng, &WINDOWS_EVENTS);
+    let channel = if provider == "Microsoft-Windows-Security-Auditing" {
+        WINDOWS_CHANNELS[0]
+    } else {
+        random_from_array(rng, &WINDOWS_CHANNELS[1..])
+    };
+    format!(
+        "{{\"EventID\":{},\"Channel\":\"{}\",\"Provider\":\"{}\",\"Computer\":\"{}-{}.corp.local\",\"Level\":\"{}\",\"RecordNumber\":{},\"ProcessID\":{},\"Message\":\"{}\",\"TimeCreated\":\"{}\"}}",
+        event_id,
+        channel,
+        provider,
+        random_from_array(rng, &WORDS),
+        random_from_array(rng, &WORDS),
+        level,
+        random_in_range(rng, 1, 1_000_000),
+        pid(rng),
+        message,
+        Utc::now().to_rfc3339_opts(SecondsFormat::Nanos, true),
     )
 }
 
@@ -151,79 +409,119 @@ fn timestamp_json() -> DelayedFormat<StrftimeItems<'static>> {
 }
 
 // Other random strings
-fn application() -> &'static str {
-    random_from_array(&APPLICATION_NAMES)
+fn application<R: Rng + ?Sized>(rng: &mut R) -> &'static str {
+    random_from_array(rng, &APPLICATION_NAMES)
+}
+
+fn domain<R: Rng + ?Sized>(rng: &mut R) -> String {
+    format!(
+        "{}{}.{}",
+        random_from_array(rng, &WORDS),
+        random_from_array(rng, &WORDS),
+        random_from_array(rng, &TOP_LEVEL_DOMAINS)
+    )
 }
 
-fn domain() -> String {
-    gen_domain()
+fn error_level<R: Rng + ?Sized>(rng: &mut R) -> &'static str {
+    random_from_array(rng, &ERROR_LEVELS)
 }
 
-fn error_level() -> &'static str {
-    random_from_array(&ERROR_LEVELS)
+fn error_message<R: Rng + ?Sized>(rng: &mut R) -> &'static str {
+    random_from_array(rng, &ERROR_MESSAGES)
 }
 
-fn error_message() -> &'static str {
-    random_from_array(&ERROR_MESSAGES)
+fn http_code<R: Rng + ?Sized>(rng: &mut R) -> usize {
+    random_from_array_copied(rng, &HTTP_CODES)
 }
 
-fn http_code() -> usize {
-    random_from_array_copied(&HTTP_CODES)
+fn byte_size<R: Rng + ?Sized>(rng: &mut R) -> usize {
+    random_in_range(rng, 50, 50000)
 }
 
-fn byte_size() -> usize {
-    random_in_range(50, 50000)
+fn http_endpoint<R: Rng + ?Sized>(rng: &mut R) -> &'static str {
+    random_from_array(rng, &HTTP_ENDPOINTS)
 }
 
-fn http_endpoint() -> &'static str {
-    random_from_array(&HTTP_ENDPOINTS)
+fn http_method<R: Rng + ?Sized>(rng: &mut R) -> &'static str {
+    random_from_array(rng, &HTTP_METHODS)
 }
 
-fn http_method() -> &'static str {
-    random_from_array(&HTTP_METHODS)
+fn http_version<R: Rng + ?Sized>(rng: &mut R) -> &'static str {
+    random_from_array(rng, &HTTP_VERSIONS)
 }
 
-fn http_version() -> &'static str {
-    random_from_array(&HTTP_VERSIONS)
+fn ipv4_address<R: Rng + ?Sized>(rng: &mut R) -> String {
+    let [a, b, c, d] = rng.gen::<[u8; 4]>();
+    format!("{}.{}.{}.{}", a, b, c, d)
 }
 
-fn ipv4_address() -> String {
-    gen_ipv4()
+fn latency_secs<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    rng.gen_range(0.0..2.0)
 }
 
-fn pid() -> usize {
-    random_in_range(1, 9999)
+fn pid<R: Rng + ?Sized>(rng: &mut R) -> usize {
+    random_in_range(rng, 1, 9999)
 }
 
-fn port() -> usize {
-    random_in_range(1024, 65535)
+fn port<R: Rng + ?Sized>(rng: &mut R) -> usize {
+    random_in_range(rng, 1024, 65535)
 }
 
-fn priority() -> usize {
-    random_in_range(0, 191)
+fn priority<R: Rng + ?Sized>(rng: &mut R) -> usize {
+    random_in_range(rng, 0, 191)
 }
 
-fn referer() -> String {
-    format!("https://{}{}", domain(), http_endpoint())
+fn referer<R: Rng + ?Sized>(rng: &mut R) -> String {
+    format!("https://{}{}", domain(rng), http_endpoint(rng))
 }
 
-fn username() -> String {
-    gen_username()
+fn username<R: Rng + ?Sized>(rng: &mut R) -> String {
+    format!(
+        "{}{}",
+        random_from_array(rng, &WORDS),
+        random_in_range(rng, 1, 9999)
+    )
+}
+
+fn uuid<R: Rng + ?Sized>(rng: &mut R) -> String {
+    let bytes = rng.gen::<u128>().to_be_bytes();
+    format!(
+        "{:02x}{:02x}{:02x}{:02x}-{:02x}{:02x}-4{:01x}{:02x}-{:02x}{:02x}-{:02x}{:02x}{:02x}{:02x}{:02x}{:02x}",
+        bytes[0],
+        bytes[1],
+        bytes[2],
+        bytes[3],
+        bytes[4],
+        bytes[5],
+        bytes[6] & 0x0f,
+        bytes[7],
+        (bytes[8] & 0x3f) | 0x80,
+        bytes[9],
+        bytes[10],
+        bytes[11],
+        bytes[12],
+        bytes[13],
+        bytes[14],
+        bytes[15],
+    )
 }
 
-fn syslog_version() -> usize {
-    random_in_range(1, 3)
+fn syslog_version<R: Rng + ?Sized>(rng: &mut R) -> usize {
+    random_in_range(rng, 1, 3)
 }
 
 // Helper functions
-fn random_in_range(min: usize, max: usize) -> usize {
-    thread_rng().gen_range(min..max)
+fn random_in_range<R: Rng + ?Sized>(rng: &mut R, min: usize, max: usize) -> usize {
+    rng.gen_range(min..max)
 }
 
-fn random_from_array<T: ?Sized>(v: &'static [&'static T]) -> &'static T {
-    v[thread_rng().gen_range(0..v.len())]
+fn random_from_array<R: Rng + ?Sized, T: ?Sized>(
+    rng: &mut R,
+    v: &'static [&'static T],
+) -> &'static T {
+    v[rng.gen_range(0..v.len())]
 }
 
-fn random_from_array_copied<T: Copy>(v: &[T]) -> T {
-    v[thread_rng().gen_range(0..v.len())]
+fn random_from_array_copied<R: Rng + ?Sized, T: Copy>(rng: &mut R, v: &[T]) -> T {
+    v[rng.gen_range(0..v.len())]
 }
@@ -10,19 +10,20 @@ use std::{
 use bytes::Bytes;
 use chrono::{DateTime, Utc};
 use futures::{
+    channel::mpsc::UnboundedReceiver,
     future::{select, Either},
     Future, Sink, SinkExt,
 };
 use indexmap::IndexMap;
 use tokio::time::sleep;
-use tracing::{debug, error, info, trace};
+use tracing::{debug, error, info, trace, warn};
 
 use crate::{
     checkpointer::{Checkpointer, CheckpointsView},
     file_watcher::FileWatcher,
     fingerprinter::{FileFingerprint, Fingerprinter},
     paths_provider::PathsProvider,
-    FileSourceInternalEvents, ReadFrom,
+    FilePosition, FileSourceInternalEvents, ReadFrom,
 };
 
 /// `FileServer` is a Source which cooperatively schedules reads over files,
@@ -50,10 +51,23 @@ where
     pub fingerprinter: Fingerprinter,
     pub oldest_first: bool,
     pub remove_after: Option<Duration>,
+    /// Whether to stop once all the files have been read to their end, rather than waiting for
+    /// more lines to be written.
+    pub once: bool,
+    /// The files to read again from an earlier position, such as for lines that failed to be
+    /// delivered.
+    pub rewinds: Option<UnboundedReceiver<Rewind>>,
     pub emitter: E,
     pub handle: tokio::runtime::Handle,
 }
 
+/// A request to read a file again from an earlier position.
+#[derive(Debug)]
+pub struct Rewind {
+    pub file_id: FileFingerprint,
+    pub position: FilePosition,
+}
+
 /// `FileServer` as Source
 ///
 /// The 'run' of `FileServer` performs the cooperative scheduling of reads over
@@ -78,7 +92,7 @@ where
     // checkpoint writer task, which has to wait for all
     // acknowledgements to be completed.
     pub fn run<C, S1, S2>(
-        self,
+        mut self,
         mut chans: C,
         mut shutdown_data: S1,
         shutdown_checkpointer: S2,
@@ -96,6 +110,7 @@ where
 
         let mut backoff_cap: usize = 1;
         let mut lines = Vec::new();
+        let mut rewinds = self.rewinds.take();
 
         checkpointer.read_checkpoints(self.ignore_before);
 
@@ -229,6 +244,13 @@ where
                 stats.record("discovery", start.elapsed());
             }
 
+            // Read files again from where it was asked to.
+            if let Some(rewinds) = rewinds.as_mut() {
+                while let Ok(Some(rewind)) = rewinds.try_next() {
+                    self.rewind_file(&mut fp_map, rewind);
+                }
+            }
+
             // Collect lines by polling files.
             let mut global_bytes_read: usize = 0;
             let mut maxed_out_reading_single_file = false;
@@ -256,6 +278,7 @@ where
                         file_id,
                         start_offset: line.offset,
                         end_offset: watcher.get_file_position(),
+                        generation: watcher.generation(),
                     });
 
                     if bytes_read > self.max_read_bytes {
@@ -317,6 +340,21 @@ where
             }
             stats.record("sending", start.elapsed());
 
+            // Running once, the server stops as soon as nothing more is read from the files. The
+            // lines are all sent by then, so the channel is closed before waiting for their
+            // checkpoints.
+            if self.once && global_bytes_read == 0 {
+                drop(chans);
+                let checkpointer = self
+                    .handle
+                    .block_on(checkpoint_task_handle)
+                    .expect("checkpoint task has panicked");
+                if let Err(error) = checkpointer.write_checkpoints() {
+                    error!(?error, "Error writing checkpoints after reading the files");
+                }
+                return Ok(Shutdown);
+            }
+
             let start = time::Instant::now();
             // When no lines have been read we kick the backup_cap up by twice,
             // limited by the hard-coded cap. Else, we set the backup_cap to its
@@ -357,6 +395,32 @@ where
         }
     }
 
+    fn rewind_file(&self, fp_map: &mut IndexMap<FileFingerprint, FileWatcher>, rewind: Rewind) {
+        // The file may have been unwatched since.
+        let watcher = match fp_map.get_mut(&rewind.file_id) {
+            Some(watcher) => watcher,
+            None => return,
+        };
+        match watcher.rewind(rewind.position) {
+            Ok(true) => debug!(
+                message = "Reading file again.",
+                path = ?watcher.path,
+                position = %rewind.position,
+            ),
+            Ok(false) => warn!(
+                message = "Can't read file again, it will be read again from its last checkpoint when restarted.",
+                path = ?watcher.path,
+                position = %rewind.position,
+            ),
+            Err(error) => warn!(
+                message = "Failed to read file again, it will be read again from its last checkpoint when restarted.",
+                path = ?watcher.path,
+                position = %rewind.position,
+                %error,
+            ),
+        }
+    }
+
     fn watch_new_file(
         &self,
         path: PathBuf,
@@ -518,4 +582,7 @@ pub struct Line {
     pub file_id: FileFingerprint,
     pub start_offset: u64,
     pub end_offset: u64,
+    /// The generation of the watcher of the file when the line was read, which changes whenever
+    /// the file is read again from an earlier position.
+    pub generation: u64,
 }
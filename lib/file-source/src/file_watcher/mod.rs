@@ -2,6 +2,7 @@ use std::{
     fs::{self, File},
     io::{self, BufRead, Seek},
     path::PathBuf,
+    sync::atomic::{AtomicU64, Ordering},
     time::{Duration, Instant},
 };
 
@@ -16,6 +17,13 @@ use crate::{
 #[cfg(test)]
 mod tests;
 
+/// The source of the generations of the watchers, which are unique across all of them.
+static NEXT_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+fn next_generation() -> u64 {
+    NEXT_GENERATION.fetch_add(1, Ordering::Relaxed)
+}
+
 /// The `RawLine` struct is a thin wrapper around the bytes that have been read
 /// in order to retain the context of where in the file they have been read from.
 ///
@@ -47,6 +55,7 @@ pub struct FileWatcher {
     max_line_bytes: usize,
     line_delimiter: Bytes,
     buf: BytesMut,
+    generation: u64,
 }
 
 impl FileWatcher {
@@ -148,6 +157,7 @@ impl FileWatcher {
             max_line_bytes,
             line_delimiter,
             buf: BytesMut::new(),
+            generation: next_generation(),
         })
     }
 
@@ -194,6 +204,39 @@ impl FileWatcher {
         self.file_position
     }
 
+    /// Returns the generation of the lines read, which changes whenever the file is read again
+    /// from an earlier position.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Read the file again from an earlier position, such as from a line that failed to be
+    /// delivered.
+    ///
+    /// Returns `false` when the file can't be read again from there, because it is compressed or
+    /// has been rotated away from the path of the watcher.
+    pub fn rewind(&mut self, position: FilePosition) -> io::Result<bool> {
+        if position > self.file_position {
+            return Ok(false);
+        }
+
+        let file_handle = File::open(&self.path)?;
+        if (file_handle.portable_dev()?, file_handle.portable_ino()?) != (self.devno, self.inode) {
+            return Ok(false);
+        }
+        let mut reader = io::BufReader::new(file_handle);
+        if is_gzipped(&mut reader)? {
+            return Ok(false);
+        }
+
+        reader.seek(io::SeekFrom::Start(position))?;
+        self.reader = Box::new(reader);
+        self.file_position = position;
+        self.buf.clear();
+        self.generation = next_generation();
+        Ok(true)
+    }
+
     /// Read a single line from the underlying file
     ///
     /// This function will attempt to read a new line from its file, blocking,
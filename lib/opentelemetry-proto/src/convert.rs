@@ -1,6 +1,7 @@
 use super::proto::{
     common::v1::{any_value::Value as PBValue, KeyValue},
     logs::v1::{LogRecord, ResourceLogs, SeverityNumber},
+    metrics::v1::{exponential_histogram_data_point::Buckets, ExponentialHistogramDataPoint},
     resource::v1::Resource,
 };
 use bytes::Bytes;
@@ -10,7 +11,10 @@ use std::collections::BTreeMap;
 use value::Value;
 use vector_core::{
     config::log_schema,
-    event::{Event, LogEvent},
+    event::{
+        metric::{ExponentialBuckets, ExponentialHistogram, MAX_EXPONENTIAL_SCALE},
+        Event, LogEvent,
+    },
 };
 
 const RESOURCE_KEY: &str = "resources";
@@ -144,3 +148,53 @@ impl From<ResourceLog> for Event {
         le.into()
     }
 }
+
+impl From<Buckets> for ExponentialBuckets {
+    fn from(buckets: Buckets) -> Self {
+        Self {
+            offset: buckets.offset,
+            counts: buckets.bucket_counts,
+        }
+    }
+}
+
+impl From<ExponentialBuckets> for Buckets {
+    fn from(buckets: ExponentialBuckets) -> Self {
+        Self {
+            offset: buckets.offset,
+            bucket_counts: buckets.counts,
+        }
+    }
+}
+
+// OTLP and Vector use the same bucket layout, so only the scale may need adjusting.
+impl From<ExponentialHistogramDataPoint> for ExponentialHistogram {
+    fn from(point: ExponentialHistogramDataPoint) -> Self {
+        let mut histogram = ExponentialHistogram {
+            scale: point.scale,
+            zero_count: point.zero_count,
+            zero_threshold: point.zero_threshold,
+            positive: point.positive.map(Into::into).unwrap_or_default(),
+            negative: point.negative.map(Into::into).unwrap_or_default(),
+            count: point.count,
+            sum: point.sum.unwrap_or_default(),
+        };
+        histogram.downscale(MAX_EXPONENTIAL_SCALE);
+        histogram
+    }
+}
+
+impl From<ExponentialHistogram> for ExponentialHistogramDataPoint {
+    fn from(histogram: ExponentialHistogram) -> Self {
+        Self {
+            count: histogram.count,
+            sum: Some(histogram.sum),
+            scale: histogram.scale,
+            zero_count: histogram.zero_count,
+            zero_threshold: histogram.zero_threshold,
+            positive: (!histogram.positive.is_empty()).then(|| histogram.positive.into()),
+            negative: (!histogram.negative.is_empty()).then(|| histogram.negative.into()),
+            ..Default::default()
+        }
+    }
+}
@@ -107,6 +107,7 @@ type MetricMap<T> = IndexMap<GroupKey, T>;
 pub enum GroupKind {
     Summary(MetricMap<SummaryMetric>),
     Histogram(MetricMap<HistogramMetric>),
+    NativeHistogram(MetricMap<proto::Histogram>),
     Gauge(MetricMap<SimpleMetric>),
     Counter(MetricMap<SimpleMetric>),
     Untyped(MetricMap<SimpleMetric>),
@@ -133,7 +134,7 @@ impl GroupKind {
         match self {
             Self::Counter { .. } => kind == MetricKind::Counter,
             Self::Gauge { .. } => kind == MetricKind::Gauge,
-            Self::Histogram { .. } => kind == MetricKind::Histogram,
+            Self::Histogram { .. } | Self::NativeHistogram { .. } => kind == MetricKind::Histogram,
             Self::Summary { .. } => kind == MetricKind::Summary,
             Self::Untyped { .. } => true,
         }
@@ -196,6 +197,15 @@ impl GroupKind {
                     }))
                 }
             },
+            // Native histograms only come from remote write requests, never as text samples.
+            Self::NativeHistogram(_) => {
+                return Ok(Some(Metric {
+                    name: metric.name,
+                    timestamp: key.timestamp,
+                    labels: key.labels,
+                    value,
+                }))
+            }
             Self::Summary(ref mut metrics) => match suffix {
                 "" => {
                     let quantile = key
@@ -387,6 +397,37 @@ impl MetricGroupSet {
         Ok(())
     }
 
+    fn insert_histogram(
+        &mut self,
+        name: &str,
+        labels: &BTreeMap<String, String>,
+        histogram: proto::Histogram,
+    ) -> Result<(), ParserError> {
+        let group = self
+            .0
+            .entry(name.into())
+            .or_insert_with(|| GroupKind::NativeHistogram(IndexMap::default()));
+        // The metadata of native histograms declares them as regular histograms, so take over
+        // that group as long as no classic histogram samples were put in it.
+        if matches!(group, GroupKind::Histogram(metrics) if metrics.is_empty())
+            || matches!(group, GroupKind::Untyped(metrics) if metrics.is_empty())
+        {
+            *group = GroupKind::NativeHistogram(IndexMap::default());
+        }
+
+        match group {
+            GroupKind::NativeHistogram(metrics) => {
+                let key = GroupKey {
+                    timestamp: Some(histogram.timestamp),
+                    labels: labels.clone(),
+                };
+                metrics.insert(key, histogram);
+                Ok(())
+            }
+            _ => Err(ParserError::MultipleMetricKinds { name: name.into() }),
+        }
+    }
+
     fn finish(self) -> Vec<MetricGroup> {
         self.0
             .into_iter()
@@ -422,6 +463,9 @@ pub fn parse_request(request: proto::WriteRequest) -> Result<Vec<MetricGroup>, P
         for sample in timeseries.samples {
             groups.insert_sample(&name, &labels, sample)?;
         }
+        for histogram in timeseries.histograms {
+            groups.insert_histogram(&name, &labels, histogram)?;
+        }
     }
 
     Ok(groups.finish())
@@ -800,6 +844,7 @@ mod test {
                     samples: vec![
                         $( proto::Sample { value: $sample as f64, timestamp: $timestamp as i64 }, )*
                     ],
+                    histograms: vec![],
                 }, )* ],
             }
         };
@@ -950,4 +995,46 @@ mod test {
             assert_eq!(metrics.get_index(0).unwrap(), simple_metric!(Some(1395066367700), labels!(), 24.0));
         });
     }
+
+    #[test]
+    fn parse_request_native_histogram() {
+        let mut request = write_request!(["one" = Histogram], []);
+        let histogram = proto::Histogram {
+            count: Some(proto::histogram::Count::CountInt(3)),
+            sum: 4.5,
+            schema: 0,
+            zero_count: Some(proto::histogram::ZeroCount::ZeroCountInt(1)),
+            positive_spans: vec![proto::BucketSpan {
+                offset: 1,
+                length: 2,
+            }],
+            positive_deltas: vec![1, 0],
+            timestamp: 1395066367700,
+            ..Default::default()
+        };
+        request.timeseries.push(proto::TimeSeries {
+            labels: vec![proto::Label {
+                name: METRIC_NAME_LABEL.into(),
+                value: "one".into(),
+            }],
+            samples: vec![],
+            histograms: vec![histogram.clone()],
+        });
+
+        let parsed = parse_request(request).unwrap();
+        assert_eq!(parsed.len(), 1);
+        match_group!(parsed[0], "one", NativeHistogram => |metrics: &MetricMap<proto::Histogram>| {
+            assert_eq!(metrics.len(), 1);
+            assert_eq!(
+                metrics.get_index(0).unwrap(),
+                (
+                    &GroupKey {
+                        timestamp: Some(1395066367700),
+                        labels: labels!(),
+                    },
+                    &histogram
+                )
+            );
+        });
+    }
 }
@@ -3,6 +3,8 @@ use graphql_client::GraphQLQuery;
 use indoc::indoc;
 use url::Url;
 
+use crate::ConnectOptions;
+
 /// Wrapped `Result` type, that returns deserialized GraphQL response data.
 pub type QueryResult<T> =
     anyhow::Result<graphql_client::Response<<T as GraphQLQuery>::ResponseData>>;
@@ -11,21 +13,48 @@ pub type QueryResult<T> =
 #[derive(Debug)]
 pub struct Client {
     url: Url,
+    http: reqwest::Client,
+    authorization: Option<String>,
 }
 
 impl Client {
     /// Returns a new GraphQL query client, bound to the provided URL.
     pub fn new(url: Url) -> Self {
-        Self { url }
+        Self {
+            url,
+            http: reqwest::Client::new(),
+            authorization: None,
+        }
     }
 
-    pub async fn new_with_healthcheck(url: Url) -> Option<Self> {
+    /// Returns a new GraphQL query client, bound to the provided URL, that connects with the
+    /// provided TLS and authentication options.
+    pub fn with_options(url: Url, options: &ConnectOptions) -> anyhow::Result<Self> {
+        let http = reqwest::Client::builder()
+            .use_preconfigured_tls(options.tls_connector()?)
+            .build()
+            .context("Couldn't build the HTTP client")?;
+
+        Ok(Self {
+            url,
+            http,
+            authorization: options.authorization(),
+        })
+    }
+
+    pub async fn new_with_healthcheck(url: Url, options: &ConnectOptions) -> Option<Self> {
         #![allow(clippy::print_stderr)]
 
         use crate::gql::HealthQueryExt;
 
         // Create a new API client for connecting to the local/remote Vector instance.
-        let client = Self::new(url.clone());
+        let client = match Self::with_options(url.clone(), options) {
+            Ok(client) => client,
+            Err(error) => {
+                eprintln!("Couldn't create the Vector API client: {:#}", error);
+                return None;
+            }
+        };
 
         // Check that the GraphQL server is reachable
         match client.health_query().await {
@@ -54,11 +83,12 @@ impl Client {
         &self,
         request_body: &graphql_client::QueryBody<T::Variables>,
     ) -> QueryResult<T> {
-        let client = reqwest::Client::new();
+        let mut request = self.http.post(self.url.clone()).json(request_body);
+        if let Some(authorization) = &self.authorization {
+            request = request.header(reqwest::header::AUTHORIZATION, authorization);
+        }
 
-        client
-            .post(self.url.clone())
-            .json(request_body)
+        request
             .send()
             .await
             .with_context(|| {
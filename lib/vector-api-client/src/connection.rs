@@ -0,0 +1,75 @@
+use std::{fmt, fs, path::PathBuf};
+
+use anyhow::Context;
+
+/// Options for connecting to a Vector API server that requires TLS or authentication.
+#[derive(clap::Args, Clone, Default)]
+pub struct ConnectOptions {
+    /// Bearer token to authenticate with, if the API server sets `api.auth_token`
+    #[arg(long, env = "VECTOR_API_TOKEN", hide_env_values = true)]
+    pub token: Option<String>,
+
+    /// Absolute path to an additional CA certificate file, in PEM format, to verify the API server certificate with
+    #[arg(long)]
+    pub tls_ca_file: Option<PathBuf>,
+
+    /// Absolute path to a client certificate file, in PEM format, to identify with if the API server verifies certificates
+    #[arg(long, requires = "tls_key_file")]
+    pub tls_crt_file: Option<PathBuf>,
+
+    /// Absolute path to the private key of the client certificate, in PEM (PKCS#8) format
+    #[arg(long, requires = "tls_crt_file")]
+    pub tls_key_file: Option<PathBuf>,
+
+    /// Disable the verification of the API server certificate. Do NOT use this unless you understand the risks
+    #[arg(long)]
+    pub tls_insecure: bool,
+}
+
+impl fmt::Debug for ConnectOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConnectOptions")
+            .field("token", &self.token.as_ref().map(|_| "**REDACTED**"))
+            .field("tls_ca_file", &self.tls_ca_file)
+            .field("tls_crt_file", &self.tls_crt_file)
+            .field("tls_key_file", &self.tls_key_file)
+            .field("tls_insecure", &self.tls_insecure)
+            .finish()
+    }
+}
+
+impl ConnectOptions {
+    /// Returns the value of the `Authorization` header to send, if a token is set.
+    pub(crate) fn authorization(&self) -> Option<String> {
+        self.token.as_ref().map(|token| format!("Bearer {}", token))
+    }
+
+    /// Builds the TLS connector used for both HTTPS and secure WebSockets connections.
+    pub(crate) fn tls_connector(&self) -> anyhow::Result<native_tls::TlsConnector> {
+        let mut builder = native_tls::TlsConnector::builder();
+
+        if let Some(path) = &self.tls_ca_file {
+            let pem = fs::read(path)
+                .with_context(|| format!("Couldn't read CA certificate file {:?}", path))?;
+            let certificate = native_tls::Certificate::from_pem(&pem)
+                .with_context(|| format!("Couldn't parse CA certificate file {:?}", path))?;
+            builder.add_root_certificate(certificate);
+        }
+
+        if let (Some(crt_file), Some(key_file)) = (&self.tls_crt_file, &self.tls_key_file) {
+            let crt = fs::read(crt_file)
+                .with_context(|| format!("Couldn't read certificate file {:?}", crt_file))?;
+            let key = fs::read(key_file)
+                .with_context(|| format!("Couldn't read key file {:?}", key_file))?;
+            let identity = native_tls::Identity::from_pkcs8(&crt, &key)
+                .context("Couldn't build the client identity from the certificate and key")?;
+            builder.identity(identity);
+        }
+
+        if self.tls_insecure {
+            builder.danger_accept_invalid_certs(true);
+        }
+
+        builder.build().context("Couldn't build the TLS connector")
+    }
+}
@@ -167,6 +167,26 @@ impl component_sent_events_throughputs_subscription::ComponentSentEventsThroughp
 )]
 pub struct ComponentErrorsTotalsSubscription;
 
+/// ComponentErrorTypesSubscription contains metrics on the number of errors, split by error
+/// type and stage, against specific components.
+#[derive(GraphQLQuery, Debug, Copy, Clone)]
+#[graphql(
+    schema_path = "graphql/schema.json",
+    query_path = "graphql/subscriptions/component_error_types.graphql",
+    response_derives = "Debug"
+)]
+pub struct ComponentErrorTypesSubscription;
+
+/// ComponentUtilizationsSubscription contains the utilization of specific components, as the
+/// fraction of time they spend processing events.
+#[derive(GraphQLQuery, Debug, Copy, Clone)]
+#[graphql(
+    schema_path = "graphql/schema.json",
+    query_path = "graphql/subscriptions/component_utilizations.graphql",
+    response_derives = "Debug"
+)]
+pub struct ComponentUtilizationsSubscription;
+
 /// Extension methods for metrics subscriptions
 pub trait MetricsSubscriptionExt {
     /// Executes an uptime metrics subscription.
@@ -242,6 +262,18 @@ pub trait MetricsSubscriptionExt {
         &self,
         interval: i64,
     ) -> crate::BoxedSubscription<ComponentErrorsTotalsSubscription>;
+
+    /// Executes a component error types subscription.
+    fn component_error_types_subscription(
+        &self,
+        interval: i64,
+    ) -> crate::BoxedSubscription<ComponentErrorTypesSubscription>;
+
+    /// Executes a component utilizations subscription.
+    fn component_utilizations_subscription(
+        &self,
+        interval: i64,
+    ) -> crate::BoxedSubscription<ComponentUtilizationsSubscription>;
 }
 
 impl MetricsSubscriptionExt for crate::SubscriptionClient {
@@ -394,4 +426,28 @@ impl MetricsSubscriptionExt for crate::SubscriptionClient {
 
         self.start::<ComponentErrorsTotalsSubscription>(&request_body)
     }
+
+    /// Executes a component error types subscription.
+    fn component_error_types_subscription(
+        &self,
+        interval: i64,
+    ) -> BoxedSubscription<ComponentErrorTypesSubscription> {
+        let request_body = ComponentErrorTypesSubscription::build_query(
+            component_error_types_subscription::Variables { interval },
+        );
+
+        self.start::<ComponentErrorTypesSubscription>(&request_body)
+    }
+
+    /// Executes a component utilizations subscription.
+    fn component_utilizations_subscription(
+        &self,
+        interval: i64,
+    ) -> BoxedSubscription<ComponentUtilizationsSubscription> {
+        let request_body = ComponentUtilizationsSubscription::build_query(
+            component_utilizations_subscription::Variables { interval },
+        );
+
+        self.start::<ComponentUtilizationsSubscription>(&request_body)
+    }
 }
@@ -7,16 +7,19 @@
 //! 3. GraphQL queries/mutations/subscriptions, defined in `graphql/**/*.graphql` files
 //! 4. Extension methods for each client, for executing queries/subscriptions, and returning
 //! deserialized JSON responses
+//! 5. Connection options, for connecting to API servers that require TLS or authentication
 //!
 
 #![deny(warnings)]
 #![deny(missing_debug_implementations, missing_copy_implementations)]
 
 mod client;
+mod connection;
 /// GraphQL queries
 pub mod gql;
 mod subscription;
 pub mod test;
 
 pub use client::*;
+pub use connection::*;
 pub use subscription::*;
@@ -13,10 +13,20 @@ use tokio::sync::{
     mpsc, oneshot,
 };
 use tokio_stream::{wrappers::BroadcastStream, Stream, StreamExt};
-use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tokio_tungstenite::{
+    connect_async_tls_with_config,
+    tungstenite::{
+        client::IntoClientRequest,
+        http::{header::AUTHORIZATION, HeaderValue},
+        Message,
+    },
+    Connector,
+};
 use url::Url;
 use uuid::Uuid;
 
+use crate::ConnectOptions;
+
 /// Subscription GraphQL response, returned from an active stream.
 pub type BoxedSubscription<T> = Pin<
     Box<
@@ -172,11 +182,21 @@ impl SubscriptionClient {
 
 /// Connect to a new WebSocket GraphQL server endpoint, and return a `SubscriptionClient`.
 /// This method will a) connect to a ws(s):// endpoint, and perform the initial handshake, and b)
-/// set up channel forwarding to expose just the returned `Payload`s to the client.
+/// set up channel forwarding to expose just the returned `Payload`s to the client. The provided
+/// options are used for wss:// endpoints, and to authenticate with a bearer token.
 pub async fn connect_subscription_client(
     url: Url,
-) -> Result<SubscriptionClient, tokio_tungstenite::tungstenite::Error> {
-    let (ws, _) = connect_async(url).await?;
+    options: &ConnectOptions,
+) -> anyhow::Result<SubscriptionClient> {
+    let mut request = url.into_client_request()?;
+    if let Some(authorization) = options.authorization() {
+        request
+            .headers_mut()
+            .insert(AUTHORIZATION, HeaderValue::from_str(&authorization)?);
+    }
+
+    let connector = Connector::NativeTls(options.tls_connector()?);
+    let (ws, _) = connect_async_tls_with_config(request, None, Some(connector)).await?;
     let (mut ws_tx, mut ws_rx) = futures::StreamExt::split(ws);
 
     let (send_tx, mut send_rx) = mpsc::unbounded_channel::<Payload>();
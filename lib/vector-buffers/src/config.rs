@@ -189,6 +189,26 @@ impl DiskUsage {
     }
 }
 
+/// Gets the paths of the directories that the disk buffers of a component may store their files in,
+/// whatever their type.
+pub fn buffer_data_dir_paths(global_data_dir: &Path, id: &str) -> Vec<PathBuf> {
+    let mut paths = crate::variants::disk_v1::get_buffer_dir_paths(global_data_dir, id);
+    paths.push(crate::variants::disk_v2::get_disk_v2_data_dir_path(
+        global_data_dir,
+        id,
+    ));
+    paths
+}
+
+/// Gets the paths of the directories that hold the directories of the disk buffers of all of the
+/// components.
+pub fn buffer_data_dir_roots(global_data_dir: &Path) -> Vec<PathBuf> {
+    vec![
+        global_data_dir.to_path_buf(),
+        crate::variants::disk_v2::get_disk_v2_buffers_dir_path(global_data_dir),
+    ]
+}
+
 /// A specific type of buffer stage.
 #[configurable_component(no_deser)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -26,6 +26,8 @@ pub mod encoding;
 
 mod internal_events;
 
+pub mod snapshot;
+
 #[cfg(test)]
 pub mod test;
 pub mod topology;
@@ -0,0 +1,178 @@
+//! Snapshots of disk buffers.
+//!
+//! A snapshot is a copy of the files of the disk buffer of a component, laid out as they are in the
+//! data directory, so that the records stranded in the buffer of a decommissioned sink can be read
+//! again later, through another configuration.
+
+use std::{
+    error::Error,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use fslock::LockFile;
+use snafu::{ResultExt, Snafu};
+
+use crate::{
+    buffer_usage_data::BufferUsageHandle,
+    topology::channel::{BufferReceiver, BufferReceiverStream},
+    variants::disk_v2::{get_disk_v2_data_dir_path, Buffer, DiskBufferConfigBuilder},
+    Bufferable,
+};
+
+const LOCK_FILE_NAME: &str = "buffer.lock";
+
+/// Error that occurred when exporting or opening a snapshot.
+#[derive(Debug, Snafu)]
+pub enum SnapshotError {
+    /// There is no disk buffer to snapshot, or no snapshot to open.
+    #[snafu(display("no disk buffer found at {:?}", path))]
+    NotFound { path: PathBuf },
+
+    /// The buffer is opened by a running Vector process.
+    #[snafu(display(
+        "failed to lock {:?}; is another Vector process running and using this buffer?",
+        path
+    ))]
+    InUse { path: PathBuf },
+
+    /// Failed to copy the files of the buffer.
+    #[snafu(display("failed to copy the buffer: {}", source))]
+    Io { source: io::Error },
+
+    /// Failed to open the buffer of the snapshot.
+    #[snafu(display("failed to open the snapshot: {}", source))]
+    Open {
+        source: Box<dyn Error + Send + Sync>,
+    },
+}
+
+/// Copies the disk buffer of a component into a snapshot directory.
+///
+/// The buffer is locked while it is copied, so that it can't be exported while a Vector process is
+/// using it. Returns the number of bytes copied.
+///
+/// # Errors
+///
+/// If the component has no disk buffer, if the buffer is in use, or if its files can't be copied,
+/// an error variant will be returned describing the error.
+pub fn export_disk_buffer(
+    global_data_dir: &Path,
+    id: &str,
+    snapshot_dir: &Path,
+) -> Result<u64, SnapshotError> {
+    let buffer_path = get_disk_v2_data_dir_path(global_data_dir, id);
+    if !buffer_path.is_dir() {
+        return Err(SnapshotError::NotFound { path: buffer_path });
+    }
+
+    let lock_path = buffer_path.join(LOCK_FILE_NAME);
+    let mut lock = LockFile::open(&lock_path).context(IoSnafu)?;
+    if !lock.try_lock().context(IoSnafu)? {
+        return Err(SnapshotError::InUse { path: lock_path });
+    }
+
+    let snapshot_path = get_disk_v2_data_dir_path(snapshot_dir, id);
+    fs::create_dir_all(&snapshot_path).context(IoSnafu)?;
+
+    let mut bytes_copied = 0;
+    for entry in fs::read_dir(&buffer_path).context(IoSnafu)? {
+        let entry = entry.context(IoSnafu)?;
+        if entry.file_name() == LOCK_FILE_NAME || !entry.file_type().context(IoSnafu)?.is_file() {
+            continue;
+        }
+        bytes_copied +=
+            fs::copy(entry.path(), snapshot_path.join(entry.file_name())).context(IoSnafu)?;
+    }
+
+    Ok(bytes_copied)
+}
+
+/// Opens the buffer of a snapshot, as a stream of its records.
+///
+/// The stream ends once all of the records have been read and acknowledged. As the records are
+/// removed from the snapshot once acknowledged, an interrupted replay resumes where it left off.
+///
+/// # Errors
+///
+/// If the snapshot has no buffer for the component, or if the buffer can't be opened, an error
+/// variant will be returned describing the error.
+pub async fn open_snapshot<T>(
+    snapshot_dir: &Path,
+    id: &str,
+) -> Result<BufferReceiverStream<T>, SnapshotError>
+where
+    T: Bufferable + Clone,
+{
+    let snapshot_path = get_disk_v2_data_dir_path(snapshot_dir, id);
+    if !snapshot_path.is_dir() {
+        return Err(SnapshotError::NotFound {
+            path: snapshot_path,
+        });
+    }
+
+    let config = DiskBufferConfigBuilder::from_path(snapshot_path)
+        .build()
+        .map_err(|error| SnapshotError::Open {
+            source: error.into(),
+        })?;
+    let (writer, reader) = Buffer::<T>::from_config(config, BufferUsageHandle::noop())
+        .await
+        .map_err(|error| SnapshotError::Open {
+            source: error.into(),
+        })?;
+
+    // Nothing is written to a snapshot, and with its writer closed, the reader ends once the
+    // buffer is empty rather than waiting for more records.
+    drop(writer);
+
+    Ok(BufferReceiver::new(reader.into()).into_stream())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::with_temp_dir;
+
+    #[tokio::test]
+    async fn exports_buffer_files() {
+        with_temp_dir(|dir| {
+            let dir = dir.to_path_buf();
+
+            async move {
+                let data_dir = dir.join("data");
+                let buffer_path = get_disk_v2_data_dir_path(&data_dir, "decommissioned");
+                fs::create_dir_all(&buffer_path).unwrap();
+                fs::write(buffer_path.join("buffer.db"), b"ledger").unwrap();
+                fs::write(buffer_path.join("buffer-data-1.dat"), b"records").unwrap();
+                fs::write(buffer_path.join(LOCK_FILE_NAME), b"").unwrap();
+
+                let snapshot_dir = dir.join("snapshot");
+                let bytes_copied =
+                    export_disk_buffer(&data_dir, "decommissioned", &snapshot_dir).unwrap();
+                assert_eq!(bytes_copied, 13);
+
+                let snapshot_path = get_disk_v2_data_dir_path(&snapshot_dir, "decommissioned");
+                assert_eq!(
+                    fs::read(snapshot_path.join("buffer-data-1.dat")).unwrap(),
+                    b"records"
+                );
+                assert!(!snapshot_path.join(LOCK_FILE_NAME).exists());
+
+                // A buffer in use can't be exported.
+                let mut lock = LockFile::open(&buffer_path.join(LOCK_FILE_NAME)).unwrap();
+                assert!(lock.try_lock().unwrap());
+                assert!(matches!(
+                    export_disk_buffer(&data_dir, "decommissioned", &dir.join("other")),
+                    Err(SnapshotError::InUse { .. })
+                ));
+
+                assert!(matches!(
+                    export_disk_buffer(&data_dir, "missing", &dir.join("other")),
+                    Err(SnapshotError::NotFound { .. })
+                ));
+            }
+        })
+        .await;
+    }
+}
@@ -503,6 +503,15 @@ pub(crate) fn get_new_style_buffer_dir_path(base: &Path, id: &str) -> PathBuf {
     base.join(buffer_id)
 }
 
+/// Gets the paths of the directories, of any style, that the buffer of a component may use.
+pub(crate) fn get_buffer_dir_paths(base: &Path, id: &str) -> Vec<PathBuf> {
+    vec![
+        base.join(get_new_style_buffer_dir_name(id)),
+        base.join(get_old_style_buffer_dir_name(id)),
+        base.join(get_sidelined_old_style_buffer_dir_name(id)),
+    ]
+}
+
 fn get_old_style_buffer_dir_path(base: &Path, id: &str) -> PathBuf {
     let buffer_id = get_old_style_buffer_dir_name(id);
     base.join(buffer_id)
@@ -336,6 +336,10 @@ where
         .map_err(Into::into)
 }
 
+pub(crate) fn get_disk_v2_buffers_dir_path(base_dir: &Path) -> PathBuf {
+    base_dir.join("buffer").join("v2")
+}
+
 pub(crate) fn get_disk_v2_data_dir_path(base_dir: &Path, buffer_id: &str) -> PathBuf {
-    base_dir.join("buffer").join("v2").join(buffer_id)
+    get_disk_v2_buffers_dir_path(base_dir).join(buffer_id)
 }
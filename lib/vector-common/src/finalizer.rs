@@ -1,11 +1,12 @@
 #![allow(clippy::module_name_repetitions)]
 
 use std::marker::{PhantomData, Unpin};
-use std::{fmt::Debug, future::Future, pin::Pin, task::Context, task::Poll};
+use std::{fmt::Debug, future::Future, pin::Pin, task::Context, task::Poll, time::Duration};
 
 use futures::stream::{BoxStream, FuturesOrdered, FuturesUnordered};
 use futures::{FutureExt, Stream, StreamExt};
 use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio::time::{sleep, Sleep};
 
 use crate::finalization::{BatchStatus, BatchStatusReceiver};
 use crate::shutdown::ShutdownSignal;
@@ -45,13 +46,26 @@ where
     /// received acknowledged batch identifiers.
     #[must_use]
     pub fn new(shutdown: ShutdownSignal) -> (Self, BoxStream<'static, (BatchStatus, T)>) {
+        Self::with_timeout(shutdown, None)
+    }
+
+    /// Produce a finalizer set along with the output stream of
+    /// received acknowledged batch identifiers, where the batches that
+    /// aren't acknowledged within `timeout` are given the
+    /// `BatchStatus::Errored` status, so that the source can deliver
+    /// them again.
+    #[must_use]
+    pub fn with_timeout(
+        shutdown: ShutdownSignal,
+        timeout: Option<Duration>,
+    ) -> (Self, BoxStream<'static, (BatchStatus, T)>) {
         let (todo_tx, todo_rx) = mpsc::unbounded_channel();
         (
             Self {
                 sender: Some(todo_tx),
                 _phantom: PhantomData::default(),
             },
-            finalizer_stream(shutdown, todo_rx, S::default()).boxed(),
+            finalizer_stream(shutdown, todo_rx, S::default(), timeout).boxed(),
         )
     }
 
@@ -63,9 +77,20 @@ where
     pub fn maybe_new(
         maybe: bool,
         shutdown: ShutdownSignal,
+    ) -> (Option<Self>, BoxStream<'static, (BatchStatus, T)>) {
+        Self::maybe_with_timeout(maybe, shutdown, None)
+    }
+
+    /// This returns an optional finalizer set, as `maybe_new` does,
+    /// with the timeout of `with_timeout`.
+    #[must_use]
+    pub fn maybe_with_timeout(
+        maybe: bool,
+        shutdown: ShutdownSignal,
+        timeout: Option<Duration>,
     ) -> (Option<Self>, BoxStream<'static, (BatchStatus, T)>) {
         if maybe {
-            let (finalizer, stream) = Self::new(shutdown);
+            let (finalizer, stream) = Self::with_timeout(shutdown, timeout);
             (Some(finalizer), stream)
         } else {
             (None, EmptyStream::default().boxed())
@@ -85,6 +110,7 @@ fn finalizer_stream<T, S>(
     mut shutdown: ShutdownSignal,
     mut new_entries: UnboundedReceiver<(BatchStatusReceiver, T)>,
     mut status_receivers: S,
+    timeout: Option<Duration>,
 ) -> impl Stream<Item = (BatchStatus, T)>
 where
     S: FuturesSet<FinalizerFuture<T>> + Unpin,
@@ -101,6 +127,7 @@ where
                         status_receivers.push(FinalizerFuture {
                             receiver,
                             entry: Some(entry),
+                            deadline: timeout.map(|timeout| Box::pin(sleep(timeout))),
                         });
                     }
                     // The new entry sender went away before shutdown, count it as a shutdown too.
@@ -156,12 +183,23 @@ impl<Fut: Future> FuturesSet<Fut> for FuturesUnordered<Fut> {
 pub struct FinalizerFuture<T> {
     receiver: BatchStatusReceiver,
     entry: Option<T>,
+    deadline: Option<Pin<Box<Sleep>>>,
 }
 
 impl<T> Future for FinalizerFuture<T> {
     type Output = (<BatchStatusReceiver as Future>::Output, T);
     fn poll(mut self: Pin<&mut Self>, ctx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
-        let status = std::task::ready!(self.receiver.poll_unpin(ctx));
+        let status = match self.receiver.poll_unpin(ctx) {
+            Poll::Ready(status) => status,
+            Poll::Pending => match self.deadline.as_mut() {
+                Some(deadline) => {
+                    std::task::ready!(deadline.as_mut().poll(ctx));
+                    debug!(message = "Timed out waiting for the batch to be acknowledged.");
+                    BatchStatus::Errored
+                }
+                None => return Poll::Pending,
+            },
+        };
         // The use of this above in a `Futures{Ordered|Unordered|`
         // will only take this once before dropping the future.
         Poll::Ready((status, self.entry.take().unwrap_or_else(|| unreachable!())))
@@ -188,3 +226,34 @@ impl<T> Stream for EmptyStream<T> {
         (0, Some(0))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::finalization::BatchNotifier;
+
+    #[tokio::test]
+    async fn acknowledged_before_timeout() {
+        let (finalizer, mut stream) = UnorderedFinalizer::<u32>::with_timeout(
+            ShutdownSignal::noop(),
+            Some(Duration::from_secs(60)),
+        );
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        finalizer.add(1, receiver);
+        drop(batch);
+
+        assert_eq!(stream.next().await, Some((BatchStatus::Delivered, 1)));
+    }
+
+    #[tokio::test]
+    async fn errored_after_timeout() {
+        let (finalizer, mut stream) = UnorderedFinalizer::<u32>::with_timeout(
+            ShutdownSignal::noop(),
+            Some(Duration::from_millis(10)),
+        );
+        let (_batch, receiver) = BatchNotifier::new_with_receiver();
+        finalizer.add(1, receiver);
+
+        assert_eq!(stream.next().await, Some((BatchStatus::Errored, 1)));
+    }
+}
@@ -0,0 +1,46 @@
+use metrics::counter;
+use tracing::trace;
+
+use crate::internal_event::InternalEvent;
+
+/// The reason a batch was flushed by a sink.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BatchFlushReason {
+    /// The batch reached its size limit, in bytes or in events.
+    Size,
+    /// The batch reached its timeout.
+    Timeout,
+    /// The batches of all the partitions together reached their size limit, and this one was the
+    /// largest of them.
+    TotalSize,
+    /// The input of the sink ended.
+    EndOfStream,
+}
+
+impl BatchFlushReason {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Size => "size",
+            Self::Timeout => "timeout",
+            Self::TotalSize => "total_size",
+            Self::EndOfStream => "end_of_stream",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct BatchFlushed {
+    pub reason: BatchFlushReason,
+    pub count: usize,
+}
+
+impl InternalEvent for BatchFlushed {
+    fn emit(self) {
+        trace!(message = "Batch flushed.", reason = self.reason.as_str(), count = %self.count);
+        counter!("component_batches_flushed_total", 1, "reason" => self.reason.as_str());
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("BatchFlushed")
+    }
+}
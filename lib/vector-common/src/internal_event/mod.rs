@@ -1,3 +1,4 @@
+mod batch_flushed;
 mod bytes_received;
 mod bytes_sent;
 mod events_received;
@@ -7,6 +8,7 @@ pub mod service;
 
 pub use metrics::SharedString;
 
+pub use batch_flushed::{BatchFlushReason, BatchFlushed};
 pub use bytes_received::BytesReceived;
 pub use bytes_sent::BytesSent;
 pub use events_received::EventsReceived;
@@ -0,0 +1,79 @@
+use std::{num::NonZeroUsize, time::Duration};
+
+use vector_config::configurable_component;
+
+/// The default maximum number of events in a batch sent between components.
+pub const INTER_COMPONENT_BATCH_DEFAULT_MAX_EVENTS: NonZeroUsize = match NonZeroUsize::new(1000) {
+    Some(max_events) => max_events,
+    None => panic!("the default maximum number of events must not be zero"),
+};
+
+/// Batching options.
+#[configurable_component]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct BatchOptions {
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub inter_component: InterComponentBatchConfig,
+}
+
+impl BatchOptions {
+    /// Merges the batching options of another configuration into these, returning an error if
+    /// both set a different value.
+    pub(super) fn merge(&self, with: &Self) -> Result<Self, String> {
+        let default = InterComponentBatchConfig::default();
+        if self.inter_component == default {
+            Ok(with.clone())
+        } else if with.inter_component == default || self.inter_component == with.inter_component {
+            Ok(self.clone())
+        } else {
+            Err("conflicting values for 'batch.inter_component' found".to_owned())
+        }
+    }
+}
+
+/// Batching of the events sent between components.
+///
+/// Sources and transforms send events to the next components in batches. The events that are ready to be sent at
+/// once are merged into batches of up to `max_events` events, so that fewer, larger batches are sent.
+#[configurable_component]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct InterComponentBatchConfig {
+    /// The maximum number of events in a batch.
+    ///
+    /// Batches larger than this, as sent by some sources, are not split.
+    #[serde(default = "default_max_events")]
+    pub max_events: NonZeroUsize,
+
+    /// The maximum amount of time, in milliseconds, to wait for a batch to fill up.
+    ///
+    /// By default, only the events that are ready to be sent are batched together, so that no latency is added. A
+    /// small timeout can help with sources that send events one by one at high rates.
+    #[serde(default)]
+    pub timeout_ms: u64,
+}
+
+impl Default for InterComponentBatchConfig {
+    fn default() -> Self {
+        Self {
+            max_events: default_max_events(),
+            timeout_ms: 0,
+        }
+    }
+}
+
+impl InterComponentBatchConfig {
+    /// The maximum amount of time to wait for a batch to fill up, if any.
+    pub fn timeout(&self) -> Option<Duration> {
+        (self.timeout_ms > 0).then(|| Duration::from_millis(self.timeout_ms))
+    }
+}
+
+const fn default_max_events() -> NonZeroUsize {
+    INTER_COMPONENT_BATCH_DEFAULT_MAX_EVENTS
+}
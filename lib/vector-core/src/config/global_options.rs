@@ -5,7 +5,10 @@ use vector_common::TimeZone;
 use vector_config::configurable_component;
 
 use super::super::default_data_dir;
-use super::{proxy::ProxyConfig, AcknowledgementsConfig, LogSchema};
+use super::{
+    proxy::ProxyConfig, AcknowledgementsConfig, BatchOptions, LogSchema, MemoryOptions,
+    StorageOptions, TenancyOptions, UtilizationOptions,
+};
 use crate::serde::bool_or_struct;
 
 #[derive(Debug, Snafu)]
@@ -74,6 +77,26 @@ pub struct GlobalOptions {
     )]
     pub acknowledgements: AcknowledgementsConfig,
 
+    #[configurable(derived)]
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    pub batch: BatchOptions,
+
+    #[configurable(derived)]
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    pub memory: MemoryOptions,
+
+    #[configurable(derived)]
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    pub storage: StorageOptions,
+
+    #[configurable(derived)]
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    pub tenancy: TenancyOptions,
+
+    #[configurable(derived)]
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    pub utilization: UtilizationOptions,
+
     /// The amount of time, in seconds, that internal metrics will persist after having not been
     /// updated before they expire and are removed.
     ///
@@ -192,11 +215,77 @@ impl GlobalOptions {
             errors.push("conflicting values for 'timezone' found".to_owned());
         }
 
+        if conflicts(&self.memory.limit_bytes, &with.memory.limit_bytes) {
+            errors.push("conflicting values for 'memory.limit_bytes' found".to_owned());
+        }
+
+        if conflicts(
+            &self.storage.component_quota_bytes,
+            &with.storage.component_quota_bytes,
+        ) {
+            errors.push("conflicting values for 'storage.component_quota_bytes' found".to_owned());
+        }
+
+        if conflicts(
+            &self.storage.usage_interval_secs,
+            &with.storage.usage_interval_secs,
+        ) {
+            errors.push("conflicting values for 'storage.usage_interval_secs' found".to_owned());
+        }
+
+        if conflicts(
+            &self.utilization.report_interval_secs,
+            &with.utilization.report_interval_secs,
+        ) {
+            errors
+                .push("conflicting values for 'utilization.report_interval_secs' found".to_owned());
+        }
+
+        if conflicts(&self.utilization.ewma_alpha, &with.utilization.ewma_alpha) {
+            errors.push("conflicting values for 'utilization.ewma_alpha' found".to_owned());
+        }
+
+        let tenancy = if self.tenancy == TenancyOptions::default() {
+            with.tenancy
+        } else if with.tenancy == TenancyOptions::default() || self.tenancy == with.tenancy {
+            self.tenancy.clone()
+        } else {
+            errors.push("conflicting values for 'tenancy' found".to_owned());
+            self.tenancy.clone()
+        };
+
+        let batch = self.batch.merge(&with.batch).unwrap_or_else(|error| {
+            errors.push(error);
+            self.batch.clone()
+        });
+
         if errors.is_empty() {
             Ok(Self {
                 data_dir,
                 log_schema,
                 acknowledgements: self.acknowledgements.merge_default(&with.acknowledgements),
+                batch,
+                memory: MemoryOptions {
+                    limit_bytes: self.memory.limit_bytes.or(with.memory.limit_bytes),
+                },
+                storage: StorageOptions {
+                    component_quota_bytes: self
+                        .storage
+                        .component_quota_bytes
+                        .or(with.storage.component_quota_bytes),
+                    usage_interval_secs: self
+                        .storage
+                        .usage_interval_secs
+                        .or(with.storage.usage_interval_secs),
+                },
+                tenancy,
+                utilization: UtilizationOptions {
+                    report_interval_secs: self
+                        .utilization
+                        .report_interval_secs
+                        .or(with.utilization.report_interval_secs),
+                    ewma_alpha: self.utilization.ewma_alpha.or(with.utilization.ewma_alpha),
+                },
                 timezone: self.timezone,
                 proxy: self.proxy.merge(&with.proxy),
                 expire_metrics: self.expire_metrics.or(with.expire_metrics),
@@ -1,6 +1,8 @@
 use once_cell::sync::{Lazy, OnceCell};
 use vector_config::configurable_component;
 
+use crate::event::InternedString;
+
 static LOG_SCHEMA: OnceCell<LogSchema> = OnceCell::new();
 static LOG_SCHEMA_DEFAULT: Lazy<LogSchema> = Lazy::new(LogSchema::default);
 
@@ -39,6 +41,9 @@ pub fn log_schema() -> &'static LogSchema {
 /// A log schema is used by Vector not only to uniformly process the fields of an event, but also to
 /// specify which fields should hold speicifc data that is also set by Vector once an event is
 /// flowing through a topology.
+///
+/// The keys are interned, as they're the most common keys of log events, and also end up as metric
+/// tag keys, such as the host key.
 #[configurable_component]
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[serde(default)]
@@ -47,31 +52,31 @@ pub struct LogSchema {
     ///
     /// This would be the field that holds the raw message, such as a raw log line.
     #[serde(default = "LogSchema::default_message_key")]
-    message_key: String,
+    message_key: InternedString,
 
     /// The name of the event field to treat as the event timestamp.
     #[serde(default = "LogSchema::default_timestamp_key")]
-    timestamp_key: String,
+    timestamp_key: InternedString,
 
     /// The name of the event field to treat as the host which sent the message.
     ///
     /// This field will generally represent a real host, or container, that generated the message,
     /// but is somewhat source-dependent.
     #[serde(default = "LogSchema::default_host_key")]
-    host_key: String,
+    host_key: InternedString,
 
     /// The name of the event field to set the source identifier in.
     ///
     /// This field will be set by the Vector source that the event was created in.
     #[serde(default = "LogSchema::default_source_type_key")]
-    source_type_key: String,
+    source_type_key: InternedString,
 
     /// The name of the event field to set the event metadata in.
     ///
     /// Generally, this field will be set by Vector to hold event-specific metadata, such as
     /// annotations by the `remap` transform when an error or abort is encountered.
     #[serde(default = "LogSchema::default_metadata_key")]
-    metadata_key: String,
+    metadata_key: InternedString,
 }
 
 impl Default for LogSchema {
@@ -87,24 +92,24 @@ impl Default for LogSchema {
 }
 
 impl LogSchema {
-    fn default_message_key() -> String {
-        String::from("message")
+    fn default_message_key() -> InternedString {
+        InternedString::new("message")
     }
 
-    fn default_timestamp_key() -> String {
-        String::from("timestamp")
+    fn default_timestamp_key() -> InternedString {
+        InternedString::new("timestamp")
     }
 
-    fn default_host_key() -> String {
-        String::from("host")
+    fn default_host_key() -> InternedString {
+        InternedString::new("host")
     }
 
-    fn default_source_type_key() -> String {
-        String::from("source_type")
+    fn default_source_type_key() -> InternedString {
+        InternedString::new("source_type")
     }
 
-    fn default_metadata_key() -> String {
-        String::from("metadata")
+    fn default_metadata_key() -> InternedString {
+        InternedString::new("metadata")
     }
 
     pub fn message_key(&self) -> &str {
@@ -127,24 +132,29 @@ impl LogSchema {
         &self.metadata_key
     }
 
+    /// Gets the host key, as the interned tag key that metrics are tagged with.
+    pub fn interned_host_key(&self) -> &InternedString {
+        &self.host_key
+    }
+
     pub fn set_message_key(&mut self, v: String) {
-        self.message_key = v;
+        self.message_key = v.into();
     }
 
     pub fn set_timestamp_key(&mut self, v: String) {
-        self.timestamp_key = v;
+        self.timestamp_key = v.into();
     }
 
     pub fn set_host_key(&mut self, v: String) {
-        self.host_key = v;
+        self.host_key = v.into();
     }
 
     pub fn set_source_type_key(&mut self, v: String) {
-        self.source_type_key = v;
+        self.source_type_key = v.into();
     }
 
     pub fn set_metadata_key(&mut self, v: String) {
-        self.metadata_key = v;
+        self.metadata_key = v.into();
     }
 
     /// Merge two `LogSchema` instances together.
@@ -215,4 +225,22 @@ mod test {
         "#;
         toml::from_str::<LogSchema>(toml).unwrap();
     }
+
+    #[test]
+    fn interns_keys() {
+        let toml = r#"
+            host_key = "hostname"
+        "#;
+        let schema = toml::from_str::<LogSchema>(toml).unwrap();
+
+        assert_eq!(schema.host_key(), "hostname");
+        assert_eq!(
+            schema.interned_host_key().as_ptr(),
+            InternedString::new("hostname").as_ptr()
+        );
+        assert_eq!(
+            LogSchema::default().message_key().as_ptr(),
+            InternedString::new("message").as_ptr()
+        );
+    }
 }
@@ -0,0 +1,21 @@
+use std::num::NonZeroUsize;
+
+use vector_config::configurable_component;
+
+/// Memory options.
+#[configurable_component]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct MemoryOptions {
+    /// The maximum number of bytes of events in flight within the topology.
+    ///
+    /// Sources reserve the size of the events they send against this budget, and the reservation is
+    /// released once all of the events, or their copies, have been dropped, whether they were sent by
+    /// a sink, written to a disk buffer, or discarded. Once the budget is exhausted, sources wait for
+    /// events to be released before sending more, applying backpressure to their upstream clients.
+    ///
+    /// Not set by default, in which case the events in flight are only bounded by the buffers of the
+    /// components.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit_bytes: Option<NonZeroUsize>,
+}
@@ -1,14 +1,26 @@
-use std::{fmt, num::NonZeroUsize};
+use std::{fmt, num::NonZeroUsize, time::Duration};
 
 use bitmask_enum::bitmask;
 
+mod batch;
 mod global_options;
 mod log_schema;
+mod memory;
 pub mod proxy;
+mod storage;
+mod tenancy;
+mod utilization;
 
 use crate::event::LogEvent;
+pub use batch::{
+    BatchOptions, InterComponentBatchConfig, INTER_COMPONENT_BATCH_DEFAULT_MAX_EVENTS,
+};
 pub use global_options::GlobalOptions;
 pub use log_schema::{init_log_schema, log_schema, LogSchema};
+pub use memory::MemoryOptions;
+pub use storage::StorageOptions;
+pub use tenancy::{TenancyOptions, TenantLimits};
+pub use utilization::UtilizationOptions;
 use lookup::lookup_v2::ValuePath;
 use lookup::{path, PathPrefix};
 use serde::{Deserialize, Serialize};
@@ -151,25 +163,45 @@ impl Output {
 pub struct AcknowledgementsConfig {
     /// Enables end-to-end acknowledgements.
     enabled: Option<bool>,
+
+    /// The maximum time, in seconds, a source waits for its events to be acknowledged.
+    ///
+    /// The events that aren't acknowledged in time are considered to have failed, and the sources
+    /// that can replay them, such as `kafka`, `file`, and `aws_sqs`, deliver them again.
+    timeout_secs: Option<u64>,
 }
 
 impl AcknowledgementsConfig {
-    pub const DEFAULT: Self = Self { enabled: None };
+    pub const DEFAULT: Self = Self {
+        enabled: None,
+        timeout_secs: None,
+    };
 
     #[must_use]
     pub fn merge_default(&self, other: &Self) -> Self {
         let enabled = self.enabled.or(other.enabled);
-        Self { enabled }
+        let timeout_secs = self.timeout_secs.or(other.timeout_secs);
+        Self {
+            enabled,
+            timeout_secs,
+        }
     }
 
     pub fn enabled(&self) -> bool {
         self.enabled.unwrap_or(false)
     }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout_secs.map(Duration::from_secs)
+    }
 }
 
 impl From<Option<bool>> for AcknowledgementsConfig {
     fn from(enabled: Option<bool>) -> Self {
-        Self { enabled }
+        Self {
+            enabled,
+            timeout_secs: None,
+        }
     }
 }
 
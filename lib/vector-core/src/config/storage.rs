@@ -0,0 +1,34 @@
+use std::num::NonZeroU64;
+
+use vector_config::configurable_component;
+
+/// Storage options.
+///
+/// The data of each component, such as its checkpoints and disk buffers, is stored in directories
+/// of the data directory namespaced by the ID of the component.
+#[configurable_component]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct StorageOptions {
+    /// The maximum number of bytes each component should store in the data directory.
+    ///
+    /// The disk usage of each component is measured every `usage_interval_secs`, and a warning is
+    /// logged for the components that exceed this quota.
+    ///
+    /// Not set by default, in which case the disk usage of the components is only measured.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub component_quota_bytes: Option<NonZeroU64>,
+
+    /// The interval, in seconds, between measurements of the disk usage of each component.
+    ///
+    /// Defaults to 60 seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage_interval_secs: Option<NonZeroU64>,
+}
+
+impl StorageOptions {
+    /// Gets the interval, in seconds, between measurements of the disk usage of each component.
+    pub fn usage_interval_secs(&self) -> u64 {
+        self.usage_interval_secs.map_or(60, NonZeroU64::get)
+    }
+}
@@ -0,0 +1,80 @@
+use std::{collections::HashMap, num::NonZeroU64};
+
+use vector_config::configurable_component;
+
+/// Multi-tenancy options.
+///
+/// Each event belongs to a tenant, named by the value of `tenant_field` or, when the event has no
+/// such field, by the ID of the source that received it. The events of each tenant are counted as
+/// they are received by the sources, across the whole topology, and the events exceeding the limits
+/// of their tenant are discarded, so that a single tenant can't exhaust a shared aggregator.
+#[configurable_component]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TenancyOptions {
+    /// The field of log and trace events, or the tag of metrics, holding the name of their tenant.
+    ///
+    /// Not set by default, in which case the tenant of all events is the source that received them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant_field: Option<String>,
+
+    /// The limits of the tenants that have no limits of their own in `tenants`.
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub default_limits: TenantLimits,
+
+    /// The limits of specific tenants, by name.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub tenants: HashMap<String, TenantLimits>,
+
+    /// The length, in seconds, of the windows over which the byte quotas of the tenants are counted.
+    ///
+    /// Defaults to 3600 seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quota_window_secs: Option<NonZeroU64>,
+}
+
+impl TenancyOptions {
+    /// Whether any tenant is limited.
+    pub fn is_enabled(&self) -> bool {
+        self.default_limits.is_limited() || self.tenants.values().any(TenantLimits::is_limited)
+    }
+
+    /// Gets the limits of the given tenant.
+    pub fn limits(&self, tenant: &str) -> &TenantLimits {
+        self.tenants.get(tenant).unwrap_or(&self.default_limits)
+    }
+
+    /// Gets the length, in seconds, of the windows over which the byte quotas are counted.
+    pub fn quota_window_secs(&self) -> u64 {
+        self.quota_window_secs.map_or(3600, NonZeroU64::get)
+    }
+}
+
+/// The limits of a tenant.
+#[configurable_component]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TenantLimits {
+    /// The maximum number of events per second the tenant can send.
+    ///
+    /// Not set by default, in which case the rate of the events of the tenant isn't limited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub events_per_sec: Option<NonZeroU64>,
+
+    /// The maximum number of bytes of events the tenant can send within each quota window.
+    ///
+    /// Not set by default, in which case the bytes of the events of the tenant aren't limited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quota_bytes: Option<NonZeroU64>,
+}
+
+impl TenantLimits {
+    /// Whether any limit is set.
+    pub fn is_limited(&self) -> bool {
+        self.events_per_sec.is_some() || self.quota_bytes.is_some()
+    }
+}
@@ -0,0 +1,55 @@
+use std::{num::NonZeroU64, time::Duration};
+
+use vector_config::configurable_component;
+
+/// Utilization options.
+///
+/// The utilization of each transform and sink is the fraction of time it spends processing events,
+/// rather than waiting for more, and is reported as the `utilization` gauge. It is measured over
+/// each reporting interval, and smoothed with an exponentially weighted moving average.
+#[configurable_component]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct UtilizationOptions {
+    /// The interval, in seconds, between reports of the utilization of each component.
+    ///
+    /// Defaults to 5 seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub report_interval_secs: Option<NonZeroU64>,
+
+    /// The weight of the latest measurement in the reported utilization, between 0 and 1.
+    ///
+    /// Lower values smooth the utilization more, making it steadier but slower to follow changes,
+    /// and a value of 1 reports each measurement as is.
+    ///
+    /// Defaults to 0.9.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ewma_alpha: Option<f64>,
+}
+
+impl UtilizationOptions {
+    /// Gets the interval between reports of the utilization of each component.
+    pub fn report_interval(&self) -> Duration {
+        Duration::from_secs(self.report_interval_secs.map_or(5, NonZeroU64::get))
+    }
+
+    /// Gets the weight of the latest measurement in the reported utilization.
+    pub fn ewma_alpha(&self) -> f64 {
+        self.ewma_alpha.unwrap_or(0.9)
+    }
+
+    /// Validates the options.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `ewma_alpha` isn't a weight greater than 0, and up to 1.
+    pub fn validate(&self) -> Result<(), String> {
+        match self.ewma_alpha {
+            Some(alpha) if !(alpha > 0.0 && alpha <= 1.0) => Err(format!(
+                "'utilization.ewma_alpha' must be greater than 0, and up to 1, found {}",
+                alpha
+            )),
+            _ => Ok(()),
+        }
+    }
+}
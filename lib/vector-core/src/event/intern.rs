@@ -0,0 +1,290 @@
+//! Interning of the strings used as keys in events.
+//!
+//! Metric-heavy pipelines see the same few tag keys, such as `host` or `code`, over and over again.
+//! Interning those keys means that each distinct key is only allocated once, after which creating
+//! or cloning a key is a reference count increment, and comparing two keys is usually a pointer
+//! comparison.
+//!
+//! The keys of the log schema, such as `message` or `host`, are interned as well, so that the host
+//! key tagging metrics created from logs shares its allocation with the schema.
+
+use std::{
+    borrow::Borrow,
+    cmp::Ordering,
+    collections::HashSet,
+    fmt,
+    hash::{Hash, Hasher},
+    ops::Deref,
+    sync::Arc,
+};
+
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use vector_common::byte_size_of::ByteSizeOf;
+use vector_config::{
+    schema::generate_string_schema,
+    schemars::{gen::SchemaGenerator, schema::SchemaObject},
+    Configurable, ConfigurableString, GenerateError,
+};
+
+/// The maximum number of distinct strings held by the interner.
+///
+/// Keys are expected to be drawn from a small set, so this is only reached when keys are
+/// effectively unbounded, such as when they are derived from event data. Past this limit, new
+/// strings are still usable as keys, but are allocated separately instead of being interned.
+const MAX_INTERNED_STRINGS: usize = 16_384;
+
+static INTERNER: Lazy<RwLock<HashSet<InternedString>>> = Lazy::new(Default::default);
+
+/// An interned string.
+///
+/// Behaves like an immutable `String`: it dereferences to `String`, and compares, orders and hashes
+/// like one, so that maps keyed by `InternedString` can be looked up by `&str` or `&String`.
+#[derive(Clone)]
+pub struct InternedString(Arc<String>);
+
+impl InternedString {
+    /// Interns the given string.
+    pub fn new(value: &str) -> Self {
+        if let Some(interned) = INTERNER.read().get(value) {
+            return interned.clone();
+        }
+
+        let mut interner = INTERNER.write();
+        if let Some(interned) = interner.get(value) {
+            return interned.clone();
+        }
+        let interned = Self(Arc::new(value.to_owned()));
+        if interner.len() < MAX_INTERNED_STRINGS {
+            interner.insert(interned.clone());
+        }
+        interned
+    }
+
+    /// Gets the string as a `&str`.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Default for InternedString {
+    fn default() -> Self {
+        Self::new("")
+    }
+}
+
+impl Deref for InternedString {
+    type Target = String;
+
+    fn deref(&self) -> &String {
+        &self.0
+    }
+}
+
+impl AsRef<str> for InternedString {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for InternedString {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<String> for InternedString {
+    fn borrow(&self) -> &String {
+        &self.0
+    }
+}
+
+impl From<&str> for InternedString {
+    fn from(value: &str) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<&String> for InternedString {
+    fn from(value: &String) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<String> for InternedString {
+    fn from(value: String) -> Self {
+        Self::new(&value)
+    }
+}
+
+impl From<&InternedString> for String {
+    fn from(value: &InternedString) -> Self {
+        value.as_str().to_owned()
+    }
+}
+
+impl From<&InternedString> for InternedString {
+    fn from(value: &InternedString) -> Self {
+        value.clone()
+    }
+}
+
+impl From<InternedString> for String {
+    fn from(value: InternedString) -> Self {
+        value.as_str().to_owned()
+    }
+}
+
+impl PartialEq for InternedString {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0) || self.0 == other.0
+    }
+}
+
+impl Eq for InternedString {}
+
+impl PartialEq<str> for InternedString {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for InternedString {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<String> for InternedString {
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<InternedString> for &str {
+    fn eq(&self, other: &InternedString) -> bool {
+        *self == other.as_str()
+    }
+}
+
+impl PartialEq<InternedString> for String {
+    fn eq(&self, other: &InternedString) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl Ord for InternedString {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if Arc::ptr_eq(&self.0, &other.0) {
+            Ordering::Equal
+        } else {
+            self.0.cmp(&other.0)
+        }
+    }
+}
+
+impl PartialOrd for InternedString {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Hash for InternedString {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Must hash like `str`, since maps keyed by `InternedString` are looked up by `&str`.
+        self.as_str().hash(state);
+    }
+}
+
+impl fmt::Debug for InternedString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for InternedString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+impl ByteSizeOf for InternedString {
+    fn allocated_bytes(&self) -> usize {
+        // Accounted like a `String`, so that the estimated size of an event doesn't depend on
+        // whether its keys were interned.
+        self.len()
+    }
+}
+
+impl Configurable for InternedString {
+    fn generate_schema(_: &mut SchemaGenerator) -> Result<SchemaObject, GenerateError> {
+        Ok(generate_string_schema())
+    }
+}
+
+impl ConfigurableString for InternedString {}
+
+impl Serialize for InternedString {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for InternedString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct InternedStringVisitor;
+
+        impl<'de> de::Visitor<'de> for InternedStringVisitor {
+            type Value = InternedString;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a string")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                Ok(InternedString::new(value))
+            }
+        }
+
+        deserializer.deserialize_str(InternedStringVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    #[test]
+    fn shares_allocations() {
+        let first = InternedString::new("host");
+        let second = InternedString::from(String::from("host"));
+        assert!(Arc::ptr_eq(&first.0, &second.0));
+        assert_eq!(first, second);
+        assert_ne!(first, InternedString::new("hostname"));
+    }
+
+    #[test]
+    fn behaves_like_a_string_key() {
+        let mut map = BTreeMap::new();
+        map.insert(InternedString::new("b"), 2);
+        map.insert(InternedString::new("a"), 1);
+        map.insert(InternedString::new("b"), 3);
+
+        assert_eq!(map.get("b"), Some(&3));
+        assert_eq!(
+            map.keys().map(InternedString::as_str).collect::<Vec<_>>(),
+            vec!["a", "b"]
+        );
+    }
+
+    #[test]
+    fn serializes_as_a_string() {
+        let key = InternedString::new("code");
+        let json = serde_json::to_string(&key).unwrap();
+        assert_eq!(json, r#""code""#);
+        assert_eq!(serde_json::from_str::<InternedString>(&json).unwrap(), key);
+    }
+}
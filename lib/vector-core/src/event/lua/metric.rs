@@ -5,7 +5,7 @@ use mlua::prelude::*;
 use super::util::{table_to_timestamp, timestamp_to_table};
 use crate::{
     event::{
-        metric::{self, MetricSketch},
+        metric::{self, ExponentialBuckets, ExponentialHistogram, MetricSketch},
         Metric, MetricKind, MetricValue, StatisticKind,
     },
     metrics::AgentDDSketch,
@@ -80,6 +80,10 @@ impl<'a> ToLua<'a> for Metric {
             tbl.raw_set("interval_ms", i.get())?;
         }
         if let Some(tags) = self.series.tags {
+            let tags = tags
+                .into_iter()
+                .map(|(key, value)| (String::from(key), value))
+                .collect::<BTreeMap<_, _>>();
             tbl.raw_set("tags", tags)?;
         }
         tbl.raw_set("kind", self.data.kind)?;
@@ -157,6 +161,19 @@ impl<'a> ToLua<'a> for Metric {
 
                 tbl.raw_set("sketch", sketch_tbl)?;
             }
+            MetricValue::ExponentialHistogram { histogram } => {
+                let exponential_histogram = lua.create_table()?;
+                exponential_histogram.raw_set("scale", histogram.scale)?;
+                exponential_histogram.raw_set("zero_count", histogram.zero_count)?;
+                exponential_histogram.raw_set("zero_threshold", histogram.zero_threshold)?;
+                exponential_histogram.raw_set("positive_offset", histogram.positive.offset)?;
+                exponential_histogram.raw_set("positive_counts", histogram.positive.counts)?;
+                exponential_histogram.raw_set("negative_offset", histogram.negative.offset)?;
+                exponential_histogram.raw_set("negative_counts", histogram.negative.counts)?;
+                exponential_histogram.raw_set("count", histogram.count)?;
+                exponential_histogram.raw_set("sum", histogram.sum)?;
+                tbl.raw_set("exponential_histogram", exponential_histogram)?;
+            }
         }
 
         Ok(LuaValue::Table(tbl))
@@ -261,17 +278,49 @@ impl<'a> FromLua<'a> for Metric {
                     })
                 }
             }
+        } else if let Some(exponential_histogram) =
+            table.raw_get::<_, Option<LuaTable>>("exponential_histogram")?
+        {
+            let positive_counts: Vec<u64> = exponential_histogram.raw_get("positive_counts")?;
+            let negative_counts: Vec<u64> = exponential_histogram.raw_get("negative_counts")?;
+            let zero_count: u64 = exponential_histogram.raw_get("zero_count")?;
+            let count = zero_count
+                + positive_counts.iter().sum::<u64>()
+                + negative_counts.iter().sum::<u64>();
+            MetricValue::ExponentialHistogram {
+                histogram: ExponentialHistogram {
+                    scale: exponential_histogram.raw_get("scale")?,
+                    zero_count,
+                    zero_threshold: exponential_histogram
+                        .raw_get::<_, Option<f64>>("zero_threshold")?
+                        .unwrap_or_default(),
+                    positive: ExponentialBuckets {
+                        offset: exponential_histogram.raw_get("positive_offset")?,
+                        counts: positive_counts,
+                    },
+                    negative: ExponentialBuckets {
+                        offset: exponential_histogram.raw_get("negative_offset")?,
+                        counts: negative_counts,
+                    },
+                    count,
+                    sum: exponential_histogram.raw_get("sum")?,
+                },
+            }
         } else {
             return Err(LuaError::FromLuaConversionError {
                 from: value.type_name(),
                 to: "Metric",
-                message: Some("Cannot find metric value, expected presence one of \"counter\", \"gauge\", \"set\", \"distribution\", \"aggregated_histogram\", \"aggregated_summary\"".to_string()),
+                message: Some("Cannot find metric value, expected presence one of \"counter\", \"gauge\", \"set\", \"distribution\", \"aggregated_histogram\", \"aggregated_summary\", \"exponential_histogram\"".to_string()),
             });
         };
 
         Ok(Metric::new(name, kind, value)
             .with_namespace(namespace)
-            .with_tags(tags)
+            .with_tags(tags.map(|tags| {
+                tags.into_iter()
+                    .map(|(key, value)| (key.into(), value))
+                    .collect()
+            }))
             .with_timestamp(timestamp)
             .with_interval_ms(interval_ms.and_then(std::num::NonZeroU32::new)))
     }
@@ -305,7 +354,7 @@ mod test {
         )
         .with_namespace(Some("namespace_example"))
         .with_tags(Some(
-            vec![("example tag".to_string(), "example value".to_string())]
+            vec![("example tag".into(), "example value".to_string())]
                 .into_iter()
                 .collect(),
         ))
@@ -498,7 +547,7 @@ mod test {
         )
         .with_namespace(Some("example_namespace"))
         .with_tags(Some(
-            vec![("example tag".to_string(), "example value".to_string())]
+            vec![("example tag".into(), "example value".to_string())]
                 .into_iter()
                 .collect(),
         ))
@@ -585,6 +634,44 @@ mod test {
         assert_event_data_eq!(Lua::new().load(value).eval::<Metric>().unwrap(), expected);
     }
 
+    #[test]
+    fn from_lua_exponential_histogram() {
+        let value = r#"{
+            name = "example histogram",
+            exponential_histogram = {
+                scale = 2,
+                zero_count = 1,
+                positive_offset = -1,
+                positive_counts = { 3, 0, 2 },
+                negative_offset = 4,
+                negative_counts = { 1 },
+                sum = 12.5
+            }
+        }"#;
+        let expected = Metric::new(
+            "example histogram",
+            MetricKind::Absolute,
+            MetricValue::ExponentialHistogram {
+                histogram: ExponentialHistogram {
+                    scale: 2,
+                    zero_count: 1,
+                    zero_threshold: 0.0,
+                    positive: ExponentialBuckets {
+                        offset: -1,
+                        counts: vec![3, 0, 2],
+                    },
+                    negative: ExponentialBuckets {
+                        offset: 4,
+                        counts: vec![1],
+                    },
+                    count: 7,
+                    sum: 12.5,
+                },
+            },
+        );
+        assert_event_data_eq!(Lua::new().load(value).eval::<Metric>().unwrap(), expected);
+    }
+
     #[test]
     fn from_lua_aggregated_summary() {
         let value = r#"{
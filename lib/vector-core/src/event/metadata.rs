@@ -9,6 +9,7 @@ use vector_common::EventDataEq;
 
 use super::{BatchNotifier, EventFinalizer, EventFinalizers, EventStatus};
 use crate::config::LogNamespace;
+use crate::memory::SharedReservation;
 use crate::{schema, ByteSizeOf};
 
 const DATADOG_API_KEY: &str = "datadog_api_key";
@@ -35,6 +36,10 @@ pub struct EventMetadata {
     /// TODO(Jean): must not skip serialization to track schemas across restarts.
     #[serde(default = "default_schema_definition", skip)]
     schema_definition: Arc<schema::Definition>,
+
+    /// The reservation of the memory budget held by the event, if any.
+    #[serde(default, skip)]
+    memory_reservation: SharedReservation,
 }
 
 fn default_metadata_value() -> Value {
@@ -91,6 +96,7 @@ impl Default for EventMetadata {
             secrets: Secrets::new(),
             finalizers: Default::default(),
             schema_definition: default_schema_definition(),
+            memory_reservation: SharedReservation::default(),
         }
     }
 }
@@ -151,9 +157,17 @@ impl EventMetadata {
     /// Merge the other `EventMetadata` into this.
     /// If a Datadog API key is not set in `self`, the one from `other` will be used.
     /// If a Splunk HEC token is not set in `self`, the one from `other` will be used.
+    /// The top-level metadata fields not set in `self` are taken from `other`.
     pub fn merge(&mut self, other: Self) {
+        if let (Value::Object(fields), Value::Object(other_fields)) = (&mut self.value, other.value)
+        {
+            for (key, value) in other_fields {
+                fields.entry(key).or_insert(value);
+            }
+        }
         self.finalizers.merge(other.finalizers);
         self.secrets.merge(other.secrets);
+        self.memory_reservation.merge(other.memory_reservation);
     }
 
     /// Update the finalizer(s) status.
@@ -191,10 +205,20 @@ impl EventMetadata {
         self.schema_definition.as_ref()
     }
 
+    /// Get the shared schema definition, which is cheaper to hold on to than a copy.
+    pub fn shared_schema_definition(&self) -> &Arc<schema::Definition> {
+        &self.schema_definition
+    }
+
     /// Set the schema definition.
     pub fn set_schema_definition(&mut self, definition: &Arc<schema::Definition>) {
         self.schema_definition = Arc::clone(definition);
     }
+
+    /// Set the reservation of the memory budget held by the event.
+    pub fn set_memory_reservation(&mut self, reservation: SharedReservation) {
+        self.memory_reservation = reservation;
+    }
 }
 
 impl EventDataEq for EventMetadata {
@@ -242,4 +266,23 @@ mod test {
         assert_eq!(metadata.datadog_api_key().unwrap().as_ref(), SECRET);
         assert_eq!(metadata.splunk_hec_token().unwrap().as_ref(), SECRET2);
     }
+
+    #[test]
+    fn merge_keeps_fields() {
+        let mut metadata = EventMetadata::default();
+        metadata.value_mut().insert("tenant", "acme");
+        metadata.value_mut().insert("source", "first");
+        let mut other = EventMetadata::default();
+        other.value_mut().insert("source", "second");
+        other.value_mut().insert("ingest_timestamp", 42);
+
+        metadata.merge(other);
+
+        assert_eq!(metadata.value().get("tenant"), Some(&Value::from("acme")));
+        assert_eq!(metadata.value().get("source"), Some(&Value::from("first")));
+        assert_eq!(
+            metadata.value().get("ingest_timestamp"),
+            Some(&Value::from(42))
+        );
+    }
 }
@@ -3,7 +3,8 @@ use proptest::{collection::btree_set, prelude::*};
 use crate::metrics::AgentDDSketch;
 
 use super::{
-    samples_to_buckets, Bucket, MetricSketch, MetricValue, Quantile, Sample, StatisticKind,
+    samples_to_buckets, Bucket, ExponentialHistogram, MetricSketch, MetricValue, Quantile, Sample,
+    StatisticKind,
 };
 
 fn realistic_float() -> proptest::num::f64::Any {
@@ -52,6 +53,13 @@ impl Arbitrary for MetricValue {
                 }
             }),
             any::<MetricSketch>().prop_map(|sketch| MetricValue::Sketch { sketch }),
+            (-4i32..=8, any::<Vec<Sample>>()).prop_map(|(scale, samples)| {
+                let mut histogram = ExponentialHistogram::new(scale);
+                for sample in samples {
+                    histogram.record(sample.value, u64::from(sample.rate));
+                }
+                MetricValue::ExponentialHistogram { histogram }
+            }),
         ];
         strategy.boxed()
     }
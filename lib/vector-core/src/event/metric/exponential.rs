@@ -0,0 +1,618 @@
+use vector_common::byte_size_of::ByteSizeOf;
+use vector_config::configurable_component;
+
+use super::Bucket;
+
+/// The highest scale supported by exponential histograms.
+///
+/// This matches the highest scale allowed by OpenTelemetry, where the relative error of a bucket is
+/// already far below the precision of any reasonable measurement.
+pub const MAX_EXPONENTIAL_SCALE: i32 = 20;
+
+/// The lowest scale supported by exponential histograms.
+///
+/// At this scale, a single bucket covers the entire range of `f64`.
+pub const MIN_EXPONENTIAL_SCALE: i32 = -10;
+
+/// The maximum number of buckets on each side of the zero bucket.
+///
+/// This matches the default of the OpenTelemetry SDKs. When recording or merging would exceed it,
+/// the histogram is downscaled until its buckets fit.
+pub const MAX_EXPONENTIAL_BUCKETS: i32 = 160;
+
+/// A histogram with exponentially sized buckets.
+///
+/// This follows the [OpenTelemetry exponential histogram][otlp] data model, which Prometheus native
+/// histograms share: bucket boundaries are powers of `base = 2^(2^-scale)`, and the bucket at
+/// `index` holds the observations whose magnitude is within `(base^index, base^(index + 1)]`.
+/// Negative observations are tracked in a mirrored set of buckets, and observations whose magnitude
+/// doesn't exceed `zero_threshold` are counted in a dedicated zero bucket.
+///
+/// Unlike aggregated histograms, two exponential histograms can always be merged: the one with
+/// the finer resolution is downscaled to the scale of the other first.
+///
+/// [otlp]: https://opentelemetry.io/docs/specs/otel/metrics/data-model/#exponentialhistogram
+#[configurable_component]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ExponentialHistogram {
+    /// The resolution of the histogram.
+    ///
+    /// Each increment of the scale halves the width of the buckets, in logarithmic terms.
+    pub scale: i32,
+
+    /// The number of observations whose magnitude doesn't exceed `zero_threshold`.
+    pub zero_count: u64,
+
+    /// The upper bound of the magnitude of observations counted in the zero bucket.
+    pub zero_threshold: f64,
+
+    /// The buckets of positive observations.
+    pub positive: ExponentialBuckets,
+
+    /// The buckets of negative observations, indexed by their magnitude.
+    pub negative: ExponentialBuckets,
+
+    /// The total number of observations contained within this histogram.
+    pub count: u64,
+
+    /// The sum of all observations contained within this histogram.
+    pub sum: f64,
+}
+
+impl ExponentialHistogram {
+    /// Creates an empty histogram at the given scale.
+    pub fn new(scale: i32) -> Self {
+        Self {
+            scale: scale.clamp(MIN_EXPONENTIAL_SCALE, MAX_EXPONENTIAL_SCALE),
+            ..Self::default()
+        }
+    }
+
+    /// Returns `true` if the histogram holds no observations.
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Gets the lower bound of the bucket at `index`, which is also the upper bound of the bucket
+    /// at `index - 1`.
+    pub fn lower_bound(&self, index: i32) -> f64 {
+        (f64::from(index) * (-f64::from(self.scale)).exp2()).exp2()
+    }
+
+    /// Gets the index of the bucket the given magnitude falls in.
+    pub fn index_of(&self, magnitude: f64) -> i32 {
+        // SAFETY: `log2` of any finite `f64` is within (-1075, 1024], so even at the highest scale
+        // the result fits in an `i32`.
+        #[allow(clippy::cast_possible_truncation)]
+        let index = (magnitude.log2() * f64::from(self.scale).exp2()).ceil() as i32;
+        index - 1
+    }
+
+    /// Records `count` observations of `value`.
+    pub fn record(&mut self, value: f64, count: u64) {
+        // Infinities have no bucket, so they can't be represented any more than NaN.
+        if count == 0 || !value.is_finite() {
+            return;
+        }
+
+        self.count += count;
+        #[allow(clippy::cast_precision_loss)]
+        let total = value * count as f64;
+        self.sum += total;
+
+        let magnitude = value.abs();
+        if magnitude <= self.zero_threshold {
+            self.zero_count += count;
+        } else {
+            let index = self.index_of(magnitude);
+            let buckets = if value.is_sign_positive() {
+                &self.positive
+            } else {
+                &self.negative
+            };
+            let by = self.reduction_to_fit(buckets.range(), Some((index, index)));
+            self.downscale(self.scale - by);
+
+            if value.is_sign_positive() {
+                self.positive.increment(index >> by, count);
+            } else {
+                self.negative.increment(index >> by, count);
+            }
+        }
+    }
+
+    /// Lowers the resolution of the histogram to the given scale, merging adjacent buckets.
+    ///
+    /// Does nothing if the histogram already is at that scale, or at a lower one.
+    pub fn downscale(&mut self, scale: i32) {
+        if scale >= self.scale {
+            return;
+        }
+
+        // SAFETY: The difference is positive, since the target scale is lower.
+        let by = (self.scale - scale) as u32;
+        self.positive.downscale(by);
+        self.negative.downscale(by);
+        self.scale = scale;
+    }
+
+    /// Raises the zero threshold to the given value.
+    ///
+    /// Buckets that are entirely covered by the new threshold are folded into the zero bucket.
+    /// Does nothing if the threshold is already at least that high.
+    pub fn widen_zero_bucket(&mut self, threshold: f64) {
+        if threshold <= self.zero_threshold {
+            return;
+        }
+
+        // Bucket `index` is covered when its upper bound, `base^(index + 1)`, is within the
+        // threshold, which is only the case for the bucket holding the threshold if it is exactly
+        // that bucket's upper bound.
+        let index = self.index_of(threshold);
+        let last_covered = if self.lower_bound(index + 1) <= threshold {
+            index
+        } else {
+            index - 1
+        };
+        self.zero_count += self.positive.take_up_to(last_covered);
+        self.zero_count += self.negative.take_up_to(last_covered);
+        self.zero_threshold = threshold;
+    }
+
+    /// Merges another histogram into this one.
+    ///
+    /// The resulting histogram is at the lower of both scales, and has the higher of both zero
+    /// thresholds.
+    pub fn merge(&mut self, other: &Self) {
+        let mut other = other.clone();
+        let scale = self.scale.min(other.scale);
+        self.downscale(scale);
+        other.downscale(scale);
+
+        let by = self
+            .reduction_to_fit(self.positive.range(), other.positive.range())
+            .max(self.reduction_to_fit(self.negative.range(), other.negative.range()));
+        self.downscale(scale - by);
+        other.downscale(scale - by);
+
+        let threshold = self.zero_threshold.max(other.zero_threshold);
+        self.widen_zero_bucket(threshold);
+        other.widen_zero_bucket(threshold);
+
+        for (index, count) in other.positive.iter() {
+            self.positive.increment(index, count);
+        }
+        for (index, count) in other.negative.iter() {
+            self.negative.increment(index, count);
+        }
+        self.zero_count += other.zero_count;
+        self.count += other.count;
+        self.sum += other.sum;
+    }
+
+    /// Subtracts another histogram from this one.
+    ///
+    /// This only succeeds if both histograms have the same layout, and no bucket of `other` holds
+    /// more observations than the same bucket in this one. Otherwise, `false` is returned and this
+    /// histogram is left unmodified.
+    #[must_use]
+    pub fn subtract(&mut self, other: &Self) -> bool {
+        if self.scale != other.scale
+            || self.zero_threshold != other.zero_threshold
+            || self.count < other.count
+            || self.zero_count < other.zero_count
+            || !self.positive.contains(&other.positive)
+            || !self.negative.contains(&other.negative)
+        {
+            return false;
+        }
+
+        for (index, count) in other.positive.iter() {
+            self.positive.decrement(index, count);
+        }
+        for (index, count) in other.negative.iter() {
+            self.negative.decrement(index, count);
+        }
+        self.zero_count -= other.zero_count;
+        self.count -= other.count;
+        self.sum -= other.sum;
+        true
+    }
+
+    /// Gets by how much the scale must be lowered for the union of both index ranges to fit in
+    /// [`MAX_EXPONENTIAL_BUCKETS`].
+    fn reduction_to_fit(&self, a: Option<(i32, i32)>, b: Option<(i32, i32)>) -> i32 {
+        let (low, high) = match (a, b) {
+            (Some((low_a, high_a)), Some((low_b, high_b))) => {
+                (low_a.min(low_b), high_a.max(high_b))
+            }
+            (Some(range), None) | (None, Some(range)) => range,
+            (None, None) => return 0,
+        };
+
+        let mut by = 0;
+        while self.scale - by > MIN_EXPONENTIAL_SCALE
+            && (high >> by) - (low >> by) >= MAX_EXPONENTIAL_BUCKETS
+        {
+            by += 1;
+        }
+        by
+    }
+
+    /// Zeroes out all counts, while keeping the layout of the histogram.
+    pub fn clear(&mut self) {
+        self.positive.counts.iter_mut().for_each(|count| *count = 0);
+        self.negative.counts.iter_mut().for_each(|count| *count = 0);
+        self.zero_count = 0;
+        self.count = 0;
+        self.sum = 0.0;
+    }
+
+    /// Converts the histogram to the buckets of an aggregated histogram.
+    ///
+    /// This is lossless in terms of counts, but the bucket boundaries can no longer be rescaled, so
+    /// this should only be used for destinations that don't support exponential histograms.
+    pub fn to_buckets(&self) -> Vec<Bucket> {
+        let negative = self.negative.iter().rev().map(|(index, count)| Bucket {
+            upper_limit: -self.lower_bound(index),
+            count,
+        });
+        let zero = Bucket {
+            upper_limit: self.zero_threshold,
+            count: self.zero_count,
+        };
+        let positive = self.positive.iter().map(|(index, count)| Bucket {
+            upper_limit: self.lower_bound(index + 1),
+            count,
+        });
+
+        negative
+            .chain(std::iter::once(zero))
+            .chain(positive)
+            .collect()
+    }
+}
+
+impl ByteSizeOf for ExponentialHistogram {
+    fn allocated_bytes(&self) -> usize {
+        self.positive.allocated_bytes() + self.negative.allocated_bytes()
+    }
+}
+
+/// A dense range of exponential histogram buckets.
+#[configurable_component]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ExponentialBuckets {
+    /// The index of the first bucket.
+    pub offset: i32,
+
+    /// The number of observations in each bucket, starting at the bucket at `offset`.
+    pub counts: Vec<u64>,
+}
+
+impl ExponentialBuckets {
+    /// Returns `true` if there are no buckets.
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Gets the indexes of the first and last buckets, if there are any.
+    #[allow(clippy::cast_possible_truncation)] // the number of buckets is far below `i32::MAX`
+    pub fn range(&self) -> Option<(i32, i32)> {
+        (!self.counts.is_empty()).then(|| (self.offset, self.offset + self.counts.len() as i32 - 1))
+    }
+
+    /// Iterates over the index and count of each bucket.
+    #[allow(clippy::cast_possible_truncation)] // the number of buckets is far below `i32::MAX`
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (i32, u64)> + '_ {
+        self.counts
+            .iter()
+            .enumerate()
+            .map(|(position, count)| (self.offset + position as i32, *count))
+    }
+
+    /// Gets the number of observations in the bucket at `index`.
+    pub fn get(&self, index: i32) -> u64 {
+        usize::try_from(index - self.offset)
+            .ok()
+            .and_then(|position| self.counts.get(position))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Adds `count` observations to the bucket at `index`, growing the range as needed.
+    pub fn increment(&mut self, index: i32, count: u64) {
+        if self.counts.is_empty() {
+            self.offset = index;
+        } else if index < self.offset {
+            // SAFETY: `index` is lower than the offset, so the difference is positive.
+            let missing = (self.offset - index) as usize;
+            self.counts.splice(0..0, std::iter::repeat(0).take(missing));
+            self.offset = index;
+        }
+
+        // SAFETY: `index` is at least the offset at this point.
+        let position = (index - self.offset) as usize;
+        if position >= self.counts.len() {
+            self.counts.resize(position + 1, 0);
+        }
+        self.counts[position] += count;
+    }
+
+    fn decrement(&mut self, index: i32, count: u64) {
+        if let Ok(position) = usize::try_from(index - self.offset) {
+            if let Some(bucket) = self.counts.get_mut(position) {
+                *bucket = bucket.saturating_sub(count);
+            }
+        }
+    }
+
+    /// Returns `true` if every bucket of `other` is matched by a bucket with at least as many
+    /// observations in these buckets.
+    fn contains(&self, other: &Self) -> bool {
+        other
+            .iter()
+            .all(|(index, count)| count == 0 || self.get(index) >= count)
+    }
+
+    /// Merges every `2^by` adjacent buckets into one.
+    fn downscale(&mut self, by: u32) {
+        if by == 0 || self.counts.is_empty() {
+            return;
+        }
+
+        let buckets = std::mem::take(self);
+        for (index, count) in buckets.iter() {
+            // Shifting right rounds towards negative infinity, as the bucket layout requires.
+            self.increment(index >> by, count);
+        }
+    }
+
+    /// Removes the buckets up to and including `last`, returning the observations they held.
+    fn take_up_to(&mut self, last: i32) -> u64 {
+        if self.counts.is_empty() || last < self.offset {
+            return 0;
+        }
+
+        let end = usize::try_from(last - self.offset + 1)
+            .unwrap_or(0)
+            .min(self.counts.len());
+        let taken = self.counts.drain(..end).sum();
+        // SAFETY: `end` is bounded by the number of buckets, which is far below `i32::MAX`.
+        #[allow(clippy::cast_possible_truncation)]
+        let end = end as i32;
+        self.offset += end;
+        taken
+    }
+
+    /// Encodes the buckets in the sparse layout used by Prometheus native histograms.
+    ///
+    /// Empty buckets are skipped. Each span is an `(offset, length)` pair covering consecutive
+    /// non-empty buckets: the offset of the first span is the index of its first bucket, while the
+    /// offset of the others is the number of buckets skipped since the end of the previous span.
+    /// The counts are encoded as the difference from the previous bucket, or from zero for the
+    /// first one.
+    pub fn to_spans(&self) -> (Vec<(i32, u32)>, Vec<i64>) {
+        let mut spans: Vec<(i32, u32)> = Vec::new();
+        let mut deltas = Vec::new();
+        let mut previous_count = 0;
+        let mut next_index = None;
+
+        for (index, count) in self.iter().filter(|(_, count)| *count > 0) {
+            match next_index {
+                Some(next) if next == index => {
+                    if let Some(span) = spans.last_mut() {
+                        span.1 += 1;
+                    }
+                }
+                Some(next) => spans.push((index - next, 1)),
+                None => spans.push((index, 1)),
+            }
+            next_index = Some(index + 1);
+
+            // SAFETY: Counts beyond `i64::MAX` aren't representable in Prometheus either.
+            deltas.push(count as i64 - previous_count as i64);
+            previous_count = count;
+        }
+
+        (spans, deltas)
+    }
+
+    /// Decodes buckets from the sparse layout used by Prometheus native histograms.
+    ///
+    /// Returns `None` if the spans and the deltas don't match, or if a bucket would end up with a
+    /// negative count.
+    pub fn from_spans(spans: &[(i32, u32)], deltas: &[i64]) -> Option<Self> {
+        let mut buckets = Self::default();
+        let mut deltas = deltas.iter();
+        let mut count: i64 = 0;
+        let mut index = 0;
+
+        for (position, (offset, length)) in spans.iter().enumerate() {
+            index = if position == 0 {
+                *offset
+            } else {
+                index + offset
+            };
+            for _ in 0..*length {
+                count += deltas.next()?;
+                buckets.increment(index, u64::try_from(count).ok()?);
+                index += 1;
+            }
+        }
+
+        deltas.next().is_none().then_some(buckets)
+    }
+}
+
+impl ByteSizeOf for ExponentialBuckets {
+    fn allocated_bytes(&self) -> usize {
+        self.counts.allocated_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_into_buckets() {
+        let mut histogram = ExponentialHistogram::new(0);
+        histogram.record(1.0, 1);
+        histogram.record(3.0, 2);
+        histogram.record(4.0, 1);
+        histogram.record(-3.0, 1);
+        histogram.record(0.0, 1);
+
+        assert_eq!(histogram.count, 6);
+        assert_eq!(histogram.sum, 8.0);
+        assert_eq!(histogram.zero_count, 1);
+        // At scale 0, bucket `index` covers `(2^index, 2^(index + 1)]`.
+        assert_eq!(histogram.positive.offset, -1);
+        assert_eq!(histogram.positive.counts, vec![1, 0, 3]);
+        assert_eq!(histogram.negative.offset, 1);
+        assert_eq!(histogram.negative.counts, vec![1]);
+    }
+
+    #[test]
+    fn record_downscales_to_fit() {
+        let mut histogram = ExponentialHistogram::new(MAX_EXPONENTIAL_SCALE);
+        histogram.record(1.0e-100, 1);
+        histogram.record(1.0e100, 1);
+        histogram.record(f64::INFINITY, 1);
+
+        assert_eq!(histogram.count, 2);
+        assert!(histogram.scale < MAX_EXPONENTIAL_SCALE);
+        let (low, high) = histogram.positive.range().unwrap();
+        assert!(high - low < MAX_EXPONENTIAL_BUCKETS);
+        assert_eq!(histogram.positive.get(low), 1);
+        assert_eq!(histogram.positive.get(high), 1);
+    }
+
+    #[test]
+    fn downscale_merges_buckets() {
+        let mut histogram = ExponentialHistogram::new(1);
+        for value in [1.2, 1.5, 2.5, 3.5, 0.6] {
+            histogram.record(value, 1);
+        }
+        let before = histogram
+            .positive
+            .iter()
+            .map(|(_, count)| count)
+            .sum::<u64>();
+
+        histogram.downscale(0);
+        assert_eq!(histogram.scale, 0);
+        assert_eq!(histogram.positive.offset, -1);
+        assert_eq!(histogram.positive.counts, vec![1, 2, 2]);
+        assert_eq!(
+            histogram
+                .positive
+                .iter()
+                .map(|(_, count)| count)
+                .sum::<u64>(),
+            before
+        );
+    }
+
+    #[test]
+    fn merge_downscales_to_the_coarsest_scale() {
+        let mut fine = ExponentialHistogram::new(3);
+        fine.record(5.0, 2);
+        let mut coarse = ExponentialHistogram::new(0);
+        coarse.record(6.0, 1);
+
+        fine.merge(&coarse);
+        assert_eq!(fine.scale, 0);
+        assert_eq!(fine.count, 3);
+        assert_eq!(fine.sum, 16.0);
+        assert_eq!(fine.positive.offset, 2);
+        assert_eq!(fine.positive.counts, vec![3]);
+    }
+
+    #[test]
+    fn merge_widens_the_zero_bucket() {
+        let mut a = ExponentialHistogram::new(0);
+        a.record(0.1, 1);
+        a.record(5.0, 1);
+        let mut b = ExponentialHistogram::new(0);
+        b.zero_threshold = 1.0;
+        b.record(0.5, 1);
+
+        a.merge(&b);
+        assert_eq!(a.zero_threshold, 1.0);
+        assert_eq!(a.zero_count, 2);
+        assert_eq!(a.positive.iter().filter(|(_, c)| *c > 0).count(), 1);
+    }
+
+    #[test]
+    fn subtract_requires_matching_layout() {
+        let mut a = ExponentialHistogram::new(0);
+        a.record(3.0, 3);
+        let mut b = ExponentialHistogram::new(0);
+        b.record(3.0, 1);
+
+        assert!(a.subtract(&b));
+        assert_eq!(a.count, 2);
+        assert_eq!(a.positive.get(1), 2);
+
+        let mut c = ExponentialHistogram::new(1);
+        c.record(3.0, 1);
+        assert!(!a.subtract(&c));
+        assert!(!b.subtract(&a));
+    }
+
+    #[test]
+    fn to_buckets_is_sorted_and_complete() {
+        let mut histogram = ExponentialHistogram::new(0);
+        histogram.record(-3.0, 1);
+        histogram.record(0.0, 2);
+        histogram.record(3.0, 4);
+
+        let buckets = histogram.to_buckets();
+        assert_eq!(
+            buckets,
+            vec![
+                Bucket {
+                    upper_limit: -2.0,
+                    count: 1
+                },
+                Bucket {
+                    upper_limit: 0.0,
+                    count: 2
+                },
+                Bucket {
+                    upper_limit: 4.0,
+                    count: 4
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn spans_round_trip() {
+        let buckets = ExponentialBuckets {
+            offset: -2,
+            counts: vec![1, 3, 0, 0, 2, 0, 5],
+        };
+
+        let (spans, deltas) = buckets.to_spans();
+        assert_eq!(spans, vec![(-2, 2), (2, 1), (1, 1)]);
+        assert_eq!(deltas, vec![1, 2, -1, 3]);
+
+        let decoded = ExponentialBuckets::from_spans(&spans, &deltas).unwrap();
+        assert_eq!(
+            decoded
+                .iter()
+                .filter(|(_, count)| *count > 0)
+                .collect::<Vec<_>>(),
+            buckets
+                .iter()
+                .filter(|(_, count)| *count > 0)
+                .collect::<Vec<_>>()
+        );
+
+        assert!(ExponentialBuckets::from_spans(&spans, &deltas[..3]).is_none());
+        assert!(ExponentialBuckets::from_spans(&[(0, 1)], &[-1]).is_none());
+    }
+}
@@ -14,7 +14,9 @@ use vector_config::configurable_component;
 use vrl_lib::prelude::VrlValueConvert;
 
 use crate::{
-    event::{BatchNotifier, EventFinalizer, EventFinalizers, EventMetadata, Finalizable},
+    event::{
+        BatchNotifier, EventFinalizer, EventFinalizers, EventMetadata, Finalizable, InternedString,
+    },
     ByteSizeOf,
 };
 
@@ -24,13 +26,18 @@ mod arbitrary;
 mod data;
 pub use self::data::*;
 
+mod exponential;
+pub use self::exponential::*;
+
 mod series;
 pub use self::series::*;
 
 mod value;
 pub use self::value::*;
 
-pub type MetricTags = BTreeMap<String, String>;
+/// The tags of a metric, keyed by interned tag keys since the same few keys are repeated across
+/// most metrics.
+pub type MetricTags = BTreeMap<InternedString, String>;
 
 /// A metric.
 #[configurable_component]
@@ -286,7 +293,12 @@ impl Metric {
     ) -> Self {
         let labels = key
             .labels()
-            .map(|label| (String::from(label.key()), String::from(label.value())))
+            .map(|label| {
+                (
+                    InternedString::new(label.key()),
+                    String::from(label.value()),
+                )
+            })
             .collect::<MetricTags>();
 
         Self::new(key.name().to_string(), MetricKind::Absolute, value)
@@ -323,14 +335,17 @@ impl Metric {
     /// containing the previous value of the tag.
     ///
     /// *Note:* This will create the tags map if it is not present.
-    pub fn insert_tag(&mut self, name: String, value: String) -> Option<String> {
+    pub fn insert_tag(&mut self, name: impl Into<InternedString>, value: String) -> Option<String> {
         self.series.insert_tag(name, value)
     }
 
     /// Gets the given tag's corresponding entry in this metric.
     ///
     /// *Note:* This will create the tags map if it is not present, even if nothing is later inserted.
-    pub fn tag_entry(&mut self, key: String) -> btree_map::Entry<String, String> {
+    pub fn tag_entry(
+        &mut self,
+        key: impl Into<InternedString>,
+    ) -> btree_map::Entry<InternedString, String> {
         self.series.tag_entry(key)
     }
 
@@ -609,9 +624,9 @@ mod test {
 
     fn tags() -> MetricTags {
         vec![
-            ("normal_tag".to_owned(), "value".to_owned()),
-            ("true_tag".to_owned(), "true".to_owned()),
-            ("empty_tag".to_owned(), "".to_owned()),
+            ("normal_tag".into(), "value".to_owned()),
+            ("true_tag".into(), "true".to_owned()),
+            ("empty_tag".into(), "".to_owned()),
         ]
         .into_iter()
         .collect()
@@ -944,6 +959,16 @@ mod test {
             ),
             r#"six{} = count=2 sum=127 1@63 2@64"#
         );
+
+        let mut histogram = ExponentialHistogram::new(0);
+        histogram.record(3.0, 2);
+        assert_eq!(
+            format!(
+                "{}",
+                Metric::new("seven", MetricKind::Absolute, histogram.into())
+            ),
+            r#"seven{} = count=2 sum=6 scale=0 2@4"#
+        );
     }
 
     #[test]
@@ -5,6 +5,7 @@ use vector_common::byte_size_of::ByteSizeOf;
 use vector_config::configurable_component;
 
 use super::{write_list, write_word, MetricTags};
+use crate::event::InternedString;
 
 /// Metrics series.
 #[configurable_component]
@@ -42,8 +43,8 @@ impl MetricSeries {
     /// Sets or updates the string value of a tag.
     ///
     /// *Note:* This will create the tags map if it is not present.
-    pub fn insert_tag(&mut self, key: String, value: String) -> Option<String> {
-        (self.tags.get_or_insert_with(Default::default)).insert(key, value)
+    pub fn insert_tag(&mut self, key: impl Into<InternedString>, value: String) -> Option<String> {
+        (self.tags.get_or_insert_with(Default::default)).insert(key.into(), value)
     }
 
     /// Removes all the tags.
@@ -70,8 +71,13 @@ impl MetricSeries {
     /// Get the tag entry for the named key. *Note:* This will create
     /// the tags map if it is not present, even if nothing is later
     /// inserted.
-    pub fn tag_entry(&mut self, key: String) -> btree_map::Entry<String, String> {
-        self.tags.get_or_insert_with(Default::default).entry(key)
+    pub fn tag_entry(
+        &mut self,
+        key: impl Into<InternedString>,
+    ) -> btree_map::Entry<InternedString, String> {
+        self.tags
+            .get_or_insert_with(Default::default)
+            .entry(key.into())
     }
 }
 
@@ -5,7 +5,7 @@ use float_eq::FloatEq;
 use vector_common::byte_size_of::ByteSizeOf;
 use vector_config::configurable_component;
 
-use super::{samples_to_buckets, write_list, write_word};
+use super::{samples_to_buckets, write_list, write_word, ExponentialHistogram};
 use crate::metrics::AgentDDSketch;
 
 /// Metric value.
@@ -80,6 +80,16 @@ pub enum MetricValue {
         #[configurable(derived)]
         sketch: MetricSketch,
     },
+
+    /// A set of observations which are counted into exponentially sized buckets.
+    ///
+    /// Unlike aggregated histograms, the bucket layout is fully determined by the scale of the histogram, so these can
+    /// be merged with each other without loss, and be sent as-is to destinations with native support for them, such as
+    /// OpenTelemetry exponential histograms or Prometheus native histograms.
+    ExponentialHistogram {
+        #[configurable(derived)]
+        histogram: ExponentialHistogram,
+    },
 }
 
 impl MetricValue {
@@ -95,6 +105,7 @@ impl MetricValue {
             MetricValue::AggregatedSummary { count, .. }
             | MetricValue::AggregatedHistogram { count, .. } => *count == 0,
             MetricValue::Sketch { sketch } => sketch.is_empty(),
+            MetricValue::ExponentialHistogram { histogram } => histogram.is_empty(),
         }
     }
 
@@ -110,6 +121,7 @@ impl MetricValue {
             Self::AggregatedHistogram { .. } => "aggregated histogram",
             Self::AggregatedSummary { .. } => "aggregated summary",
             Self::Sketch { sketch } => sketch.as_name(),
+            Self::ExponentialHistogram { .. } => "exponential histogram",
         }
     }
 
@@ -136,6 +148,26 @@ impl MetricValue {
         }
     }
 
+    /// Converts an exponential histogram to an aggregated histogram.
+    ///
+    /// The buckets of the aggregated histogram are the non-empty buckets of the exponential histogram, so no
+    /// observation is lost, but the result can no longer be merged with histograms of a different scale.
+    ///
+    /// If this value is not an exponential histogram, then `None` is returned.  Otherwise,
+    /// `Some(MetricValue::AggregatedHistogram)` is returned.
+    pub fn exponential_to_agg_histogram(&self) -> Option<MetricValue> {
+        match self {
+            MetricValue::ExponentialHistogram { histogram } => {
+                Some(MetricValue::AggregatedHistogram {
+                    buckets: histogram.to_buckets(),
+                    count: histogram.count,
+                    sum: histogram.sum,
+                })
+            }
+            _ => None,
+        }
+    }
+
     /// Converts a distribution to a sketch.
     ///
     /// This conversion specifically use the `AgentDDSketch` sketch variant, in the default configuration that matches
@@ -195,6 +227,7 @@ impl MetricValue {
                     ddsketch.clear();
                 }
             },
+            Self::ExponentialHistogram { histogram } => histogram.clear(),
         }
     }
 
@@ -260,6 +293,16 @@ impl MetricValue {
                     ) => ddsketch.merge(ddsketch2).is_ok(),
                 }
             }
+            // Exponential histograms of different scales can always be merged, by downscaling the finer one.
+            (
+                Self::ExponentialHistogram { histogram },
+                Self::ExponentialHistogram {
+                    histogram: histogram2,
+                },
+            ) => {
+                histogram.merge(histogram2);
+                true
+            }
             _ => false,
         }
     }
@@ -350,6 +393,14 @@ impl MetricValue {
                 *sum -= sum2;
                 true
             }
+            // Same as aggregated histograms: a histogram with fewer observations in any bucket forces the metric to be
+            // reinitialized.
+            (
+                Self::ExponentialHistogram { histogram },
+                Self::ExponentialHistogram {
+                    histogram: histogram2,
+                },
+            ) => histogram.subtract(histogram2),
             _ => false,
         }
     }
@@ -364,6 +415,7 @@ impl ByteSizeOf for MetricValue {
             Self::AggregatedHistogram { buckets, .. } => buckets.allocated_bytes(),
             Self::AggregatedSummary { quantiles, .. } => quantiles.allocated_bytes(),
             Self::Sketch { sketch } => sketch.allocated_bytes(),
+            Self::ExponentialHistogram { histogram } => histogram.allocated_bytes(),
         }
     }
 }
@@ -415,6 +467,22 @@ impl PartialEq for MetricValue {
             (Self::Sketch { sketch: l_sketch }, Self::Sketch { sketch: r_sketch }) => {
                 l_sketch == r_sketch
             }
+            (
+                Self::ExponentialHistogram {
+                    histogram: l_histogram,
+                },
+                Self::ExponentialHistogram {
+                    histogram: r_histogram,
+                },
+            ) => {
+                l_histogram.scale == r_histogram.scale
+                    && l_histogram.zero_count == r_histogram.zero_count
+                    && l_histogram.zero_threshold == r_histogram.zero_threshold
+                    && l_histogram.positive == r_histogram.positive
+                    && l_histogram.negative == r_histogram.negative
+                    && l_histogram.count == r_histogram.count
+                    && l_histogram.sum.eq_ulps(&r_histogram.sum, &1)
+            }
             _ => false,
         }
     }
@@ -493,10 +561,30 @@ impl fmt::Display for MetricValue {
                     }
                 }
             }
+            MetricValue::ExponentialHistogram { histogram } => {
+                write!(
+                    fmt,
+                    "count={} sum={} scale={} ",
+                    histogram.count, histogram.sum, histogram.scale
+                )?;
+                let buckets = histogram.to_buckets();
+                write_list(
+                    fmt,
+                    " ",
+                    buckets.iter().filter(|bucket| bucket.count > 0),
+                    |fmt, bucket| write!(fmt, "{}@{}", bucket.count, bucket.upper_limit),
+                )
+            }
         }
     }
 }
 
+impl From<ExponentialHistogram> for MetricValue {
+    fn from(histogram: ExponentialHistogram) -> Self {
+        MetricValue::ExponentialHistogram { histogram }
+    }
+}
+
 impl From<AgentDDSketch> for MetricValue {
     fn from(ddsketch: AgentDDSketch) -> Self {
         MetricValue::Sketch {
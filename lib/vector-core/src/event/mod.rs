@@ -11,6 +11,7 @@ pub use finalization::{
     BatchNotifier, BatchStatus, BatchStatusReceiver, EventFinalizer, EventFinalizers, EventStatus,
     Finalizable,
 };
+pub use intern::InternedString;
 pub use log_event::LogEvent;
 pub use metadata::{EventMetadata, WithMetadata};
 pub use metric::{Metric, MetricKind, MetricValue, StatisticKind};
@@ -25,6 +26,7 @@ pub use vrl_target::{TargetEvents, VrlTarget};
 pub mod array;
 pub mod discriminant;
 pub mod error;
+pub mod intern;
 mod log_event;
 #[cfg(feature = "lua")]
 pub mod lua;
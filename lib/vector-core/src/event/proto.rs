@@ -154,7 +154,13 @@ impl From<Metric> for event::Metric {
         let tags = if metric.tags.is_empty() {
             None
         } else {
-            Some(metric.tags)
+            Some(
+                metric
+                    .tags
+                    .into_iter()
+                    .map(|(key, value)| (key.into(), value))
+                    .collect(),
+            )
         };
 
         let value = match metric.value.unwrap() {
@@ -211,6 +217,11 @@ impl From<Metric> for event::Metric {
                     sketch: ddsketch.into(),
                 },
             },
+            MetricValue::ExponentialHistogram(histogram) => {
+                event::MetricValue::ExponentialHistogram {
+                    histogram: histogram.into(),
+                }
+            }
         };
 
         let mut metadata = event::EventMetadata::default();
@@ -325,7 +336,12 @@ impl From<event::Metric> for WithMetadata<Metric> {
 
         let interval_ms = data.time.interval_ms.map_or(0, std::num::NonZeroU32::get);
 
-        let tags = series.tags.unwrap_or_default();
+        let tags = series
+            .tags
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(key, value)| (key.into(), value))
+            .collect();
 
         let kind = match data.kind {
             event::MetricKind::Incremental => metric::Kind::Incremental,
@@ -387,6 +403,9 @@ impl From<event::Metric> for WithMetadata<Metric> {
                     })
                 }
             },
+            event::MetricValue::ExponentialHistogram { histogram } => {
+                MetricValue::ExponentialHistogram(histogram.into())
+            }
         };
 
         let data = Metric {
@@ -431,6 +450,52 @@ impl From<event::Event> for WithMetadata<EventWrapper> {
     }
 }
 
+impl From<event::metric::ExponentialHistogram> for ExponentialHistogram {
+    fn from(histogram: event::metric::ExponentialHistogram) -> Self {
+        Self {
+            scale: histogram.scale,
+            zero_count: histogram.zero_count,
+            zero_threshold: histogram.zero_threshold,
+            positive: Some(histogram.positive.into()),
+            negative: Some(histogram.negative.into()),
+            count: histogram.count,
+            sum: histogram.sum,
+        }
+    }
+}
+
+impl From<ExponentialHistogram> for event::metric::ExponentialHistogram {
+    fn from(histogram: ExponentialHistogram) -> Self {
+        Self {
+            scale: histogram.scale,
+            zero_count: histogram.zero_count,
+            zero_threshold: histogram.zero_threshold,
+            positive: histogram.positive.map(Into::into).unwrap_or_default(),
+            negative: histogram.negative.map(Into::into).unwrap_or_default(),
+            count: histogram.count,
+            sum: histogram.sum,
+        }
+    }
+}
+
+impl From<event::metric::ExponentialBuckets> for ExponentialBuckets {
+    fn from(buckets: event::metric::ExponentialBuckets) -> Self {
+        Self {
+            offset: buckets.offset,
+            counts: buckets.counts,
+        }
+    }
+}
+
+impl From<ExponentialBuckets> for event::metric::ExponentialBuckets {
+    fn from(buckets: ExponentialBuckets) -> Self {
+        Self {
+            offset: buckets.offset,
+            counts: buckets.counts,
+        }
+    }
+}
+
 impl From<AgentDDSketch> for Sketch {
     fn from(ddsketch: AgentDDSketch) -> Self {
         let bin_map = ddsketch.bin_map();
@@ -9,11 +9,11 @@ use quickcheck::{empty_shrinker, Arbitrary, Gen};
 use crate::{
     event::{
         metric::{
-            Bucket, MetricData, MetricName, MetricSeries, MetricSketch, MetricTime, Quantile,
-            Sample,
+            Bucket, ExponentialHistogram, MetricData, MetricName, MetricSeries, MetricSketch,
+            MetricTags, MetricTime, Quantile, Sample,
         },
-        Event, EventMetadata, LogEvent, Metric, MetricKind, MetricValue, StatisticKind, TraceEvent,
-        Value,
+        Event, EventMetadata, InternedString, LogEvent, Metric, MetricKind, MetricValue,
+        StatisticKind, TraceEvent, Value,
     },
     metrics::AgentDDSketch,
 };
@@ -58,6 +58,16 @@ fn datetime(g: &mut Gen) -> DateTime<Utc> {
     DateTime::<Utc>::from_utc(NaiveDateTime::from_timestamp(secs, nanosecs), Utc)
 }
 
+impl Arbitrary for InternedString {
+    fn arbitrary(g: &mut Gen) -> Self {
+        String::from(Name::arbitrary(g)).into()
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        Box::new(self.as_str().to_owned().shrink().map(Into::into))
+    }
+}
+
 impl Arbitrary for Event {
     fn arbitrary(g: &mut Gen) -> Self {
         let choice: u8 = u8::arbitrary(g);
@@ -178,7 +188,7 @@ impl Arbitrary for MetricValue {
         // constant here are the number of fields in `MetricValue`. Because the
         // field total is not a power of two we introduce a bias into choice
         // here toward `MetricValue::Counter` and `MetricValue::Gauge`.
-        match u8::arbitrary(g) % 7 {
+        match u8::arbitrary(g) % 8 {
             0 => MetricValue::Counter {
                 value: f64::arbitrary(g) % MAX_F64_SIZE,
             },
@@ -223,6 +233,16 @@ impl Arbitrary for MetricValue {
                     ),
                 }
             }
+            7 => {
+                let mut histogram = ExponentialHistogram::new(i32::from(i8::arbitrary(g) % 8));
+                for _ in 0..u8::arbitrary(g) % 16 {
+                    histogram.record(
+                        f64::arbitrary(g) % MAX_F64_SIZE,
+                        u64::from(u8::arbitrary(g)),
+                    );
+                }
+                MetricValue::ExponentialHistogram { histogram }
+            }
 
             _ => unreachable!(),
         }
@@ -360,6 +380,13 @@ impl Arbitrary for MetricValue {
             MetricValue::Sketch { sketch } => Box::new(iter::once(MetricValue::Sketch {
                 sketch: sketch.clone(),
             })),
+            // Same as sketches: the buckets are derived from the recorded values, so shrinking them
+            // independently would only produce inconsistent histograms.
+            MetricValue::ExponentialHistogram { histogram } => {
+                Box::new(iter::once(MetricValue::ExponentialHistogram {
+                    histogram: histogram.clone(),
+                }))
+            }
         }
     }
 }
@@ -474,9 +501,9 @@ impl Arbitrary for StatisticKind {
 impl Arbitrary for MetricSeries {
     fn arbitrary(g: &mut Gen) -> Self {
         let tags = if bool::arbitrary(g) {
-            let mut map: BTreeMap<String, String> = BTreeMap::new();
+            let mut map = MetricTags::new();
             for _ in 0..(usize::arbitrary(g) % MAX_MAP_SIZE) {
-                let key = String::from(Name::arbitrary(g));
+                let key = InternedString::from(String::from(Name::arbitrary(g)));
                 let value = String::from(Name::arbitrary(g));
                 map.insert(key, value);
             }
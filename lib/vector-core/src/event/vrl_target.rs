@@ -194,7 +194,7 @@ impl vrl_lib::Target for VrlTarget {
                                 metric.remove_tags();
                                 for (field, value) in &value {
                                     metric.insert_tag(
-                                        field.as_str().to_owned(),
+                                        field.as_str(),
                                         value
                                             .try_bytes_utf8_lossy()
                                             .map_err(|e| e.to_string())?
@@ -205,7 +205,7 @@ impl vrl_lib::Target for VrlTarget {
                             ["tags", field] => {
                                 let value = value.clone().try_bytes().map_err(|e| e.to_string())?;
                                 metric.insert_tag(
-                                    (*field).to_owned(),
+                                    *field,
                                     String::from_utf8_lossy(&value).into_owned(),
                                 );
                             }
@@ -313,7 +313,7 @@ impl vrl_lib::Target for VrlTarget {
                             ["timestamp"] => metric.data.time.timestamp.take().map(Into::into),
                             ["tags"] => metric.series.tags.take().map(|map| {
                                 map.into_iter()
-                                    .map(|(k, v)| (k, v.into()))
+                                    .map(|(k, v)| (String::from(k), v.into()))
                                     .collect::<::value::Value>()
                             }),
                             ["tags", field] => metric.remove_tag(field).map(Into::into),
@@ -479,7 +479,7 @@ fn precompute_metric_value(metric: &Metric, info: &ProgramInfo) -> Value {
                     map.insert(
                         "tags".to_owned(),
                         tags.into_iter()
-                            .map(|(tag, value)| (tag, value.into()))
+                            .map(|(tag, value)| (String::from(tag), value.into()))
                             .collect::<BTreeMap<_, _>>()
                             .into(),
                     );
@@ -525,7 +525,7 @@ fn precompute_metric_value(metric: &Metric, info: &ProgramInfo) -> Value {
                             .cloned()
                             .unwrap()
                             .into_iter()
-                            .map(|(tag, value)| (tag, value.into()))
+                            .map(|(tag, value)| (String::from(tag), value.into()))
                             .collect::<BTreeMap<_, _>>()
                             .into(),
                     );
@@ -902,7 +902,7 @@ mod test {
         .with_namespace(Some("zoob"))
         .with_tags(Some({
             let mut map = MetricTags::new();
-            map.insert("tig".to_string(), "tog".to_string());
+            map.insert("tig".into(), "tog".to_string());
             map
         }))
         .with_timestamp(Some(Utc.ymd(2020, 12, 10).and_hms(12, 0, 0)));
@@ -949,7 +949,7 @@ mod test {
         )
         .with_tags(Some({
             let mut map = MetricTags::new();
-            map.insert("tig".to_string(), "tog".to_string());
+            map.insert("tig".into(), "tog".to_string());
             map
         }));
 
@@ -1031,7 +1031,7 @@ mod test {
         )
         .with_tags(Some({
             let mut map = MetricTags::new();
-            map.insert("tig".to_string(), "tog".to_string());
+            map.insert("tig".into(), "tog".to_string());
             map
         }));
 
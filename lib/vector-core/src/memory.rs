@@ -0,0 +1,198 @@
+//! Accounting of the memory held by the events in flight within a topology.
+//!
+//! When a memory budget is configured, sources reserve the size of the events they send against
+//! it before sending them. The reservation travels with the events, in their metadata, and is
+//! released once all of them, and all of their copies, have been dropped. Sources therefore slow
+//! down to the pace at which the rest of the topology is able to release events, instead of
+//! accumulating them in memory until the process runs out of it.
+
+use std::{cmp::Ordering, fmt, num::NonZeroUsize, sync::Arc};
+
+use metrics::Gauge;
+use once_cell::sync::OnceCell;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::config::MemoryOptions;
+
+/// The granularity of the budget, to which reservations are rounded up.
+const UNIT_BYTES: usize = 1024;
+
+/// The largest budget, in units, that can be tracked.
+const MAX_UNITS: u32 = u32::MAX >> 3;
+
+static MEMORY_BUDGET: OnceCell<MemoryBudget> = OnceCell::new();
+
+/// A budget of bytes shared by the events in flight.
+#[derive(Clone, Debug)]
+pub struct MemoryBudget {
+    semaphore: Arc<Semaphore>,
+    units: u32,
+}
+
+impl MemoryBudget {
+    /// Creates a budget of the given number of bytes.
+    pub fn new(limit_bytes: NonZeroUsize) -> Self {
+        let units = units(limit_bytes.get());
+        Self {
+            semaphore: Arc::new(Semaphore::new(units as usize)),
+            units,
+        }
+    }
+
+    /// Gets the global budget, if the given options set one.
+    ///
+    /// The global budget is created the first time it is requested, and then shared by all of the
+    /// topologies built afterwards, since the global options can't change when reloading.
+    pub fn global(options: &MemoryOptions) -> Option<Self> {
+        options
+            .limit_bytes
+            .map(|limit_bytes| MEMORY_BUDGET.get_or_init(|| Self::new(limit_bytes)).clone())
+    }
+
+    /// Gets the number of bytes left in the budget.
+    pub fn available_bytes(&self) -> usize {
+        self.semaphore.available_permits() * UNIT_BYTES
+    }
+
+    /// Reserves the given number of bytes, first waiting for enough of the budget to be released
+    /// if needed.
+    ///
+    /// Reservations larger than the whole budget are capped to it, so that they can eventually be
+    /// made. If given, the gauge is increased by the number of bytes for as long as the reservation
+    /// is held.
+    pub async fn reserve(&self, bytes: usize, gauge: Option<Gauge>) -> MemoryReservation {
+        let permit = Arc::clone(&self.semaphore)
+            .acquire_many_owned(units(bytes).min(self.units))
+            .await
+            .expect("the semaphore of a memory budget is never closed");
+        if let Some(gauge) = &gauge {
+            #[allow(clippy::cast_precision_loss)]
+            gauge.increment(bytes as f64);
+        }
+        MemoryReservation {
+            _permit: permit,
+            bytes,
+            gauge,
+        }
+    }
+}
+
+fn units(bytes: usize) -> u32 {
+    let units = bytes / UNIT_BYTES + usize::from(bytes % UNIT_BYTES != 0);
+    u32::try_from(units).unwrap_or(MAX_UNITS).min(MAX_UNITS)
+}
+
+/// Bytes reserved from a memory budget, released when dropped.
+pub struct MemoryReservation {
+    _permit: OwnedSemaphorePermit,
+    bytes: usize,
+    gauge: Option<Gauge>,
+}
+
+impl MemoryReservation {
+    /// Gets the number of bytes reserved.
+    pub fn bytes(&self) -> usize {
+        self.bytes
+    }
+
+    /// Shares the reservation between the events it was made for.
+    #[must_use]
+    pub fn share(self) -> SharedReservation {
+        SharedReservation(Some(Arc::new(self)))
+    }
+}
+
+impl Drop for MemoryReservation {
+    fn drop(&mut self) {
+        if let Some(gauge) = &self.gauge {
+            #[allow(clippy::cast_precision_loss)]
+            gauge.decrement(self.bytes as f64);
+        }
+    }
+}
+
+impl fmt::Debug for MemoryReservation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // `metrics::Gauge` is missing `impl Debug`
+        f.debug_struct("MemoryReservation")
+            .field("bytes", &self.bytes)
+            .finish()
+    }
+}
+
+/// A reservation held by the metadata of each of the events it was made for.
+///
+/// Like event finalizers, this is not event data, so all shared reservations compare as equal.
+#[derive(Clone, Debug, Default)]
+pub struct SharedReservation(Option<Arc<MemoryReservation>>);
+
+impl SharedReservation {
+    /// Merges another reservation into this one.
+    ///
+    /// Only one reservation is kept, so if both are set, the other one is released early, once its
+    /// remaining events are dropped.
+    pub fn merge(&mut self, other: Self) {
+        if self.0.is_none() {
+            *self = other;
+        }
+    }
+}
+
+impl PartialEq for SharedReservation {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl PartialOrd for SharedReservation {
+    fn partial_cmp(&self, _other: &Self) -> Option<Ordering> {
+        Some(Ordering::Equal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn budget(limit_bytes: usize) -> MemoryBudget {
+        MemoryBudget::new(NonZeroUsize::new(limit_bytes).unwrap())
+    }
+
+    #[tokio::test]
+    async fn releases_on_drop() {
+        let budget = budget(4 * UNIT_BYTES);
+
+        let first = budget.reserve(UNIT_BYTES + 1, None).await.share();
+        let copy = first.clone();
+        assert_eq!(budget.available_bytes(), 2 * UNIT_BYTES);
+
+        drop(first);
+        assert_eq!(budget.available_bytes(), 2 * UNIT_BYTES);
+        drop(copy);
+        assert_eq!(budget.available_bytes(), 4 * UNIT_BYTES);
+    }
+
+    #[tokio::test]
+    async fn waits_for_budget() {
+        let budget = budget(2 * UNIT_BYTES);
+        let held = budget.reserve(2 * UNIT_BYTES, None).await;
+
+        let waiting = tokio::time::timeout(Duration::from_millis(10), budget.reserve(1, None));
+        assert!(waiting.await.is_err());
+
+        drop(held);
+        let reservation = budget.reserve(1, None).await;
+        assert_eq!(reservation.bytes(), 1);
+        assert_eq!(budget.available_bytes(), UNIT_BYTES);
+    }
+
+    #[tokio::test]
+    async fn caps_reservations_to_budget() {
+        let budget = budget(UNIT_BYTES);
+        let reservation = budget.reserve(10 * UNIT_BYTES, None).await;
+        assert_eq!(reservation.bytes(), 10 * UNIT_BYTES);
+        assert_eq!(budget.available_bytes(), 0);
+    }
+}
@@ -11,7 +11,10 @@ use snafu::Snafu;
 use vector_common::byte_size_of::ByteSizeOf;
 use vector_config::configurable_component;
 
-use crate::event::{metric::Bucket, Metric, MetricValue};
+use crate::event::{
+    metric::{Bucket, ExponentialHistogram},
+    Metric, MetricValue,
+};
 
 const AGENT_DEFAULT_BIN_LIMIT: u16 = 4096;
 const AGENT_DEFAULT_EPS: f64 = 1.0 / 128.0;
@@ -21,6 +24,12 @@ const UV_INF: i16 = i16::MAX;
 const MAX_KEY: i16 = UV_INF;
 
 const INITIAL_BINS: u16 = 128;
+
+/// The exponential histogram scale closest to the resolution of the agent configuration.
+///
+/// With a relative accuracy of 1/128, sketch bins grow by a factor of about 1.0157, while buckets at
+/// scale 5 grow by a factor of about 1.0219.
+pub const AGENT_EXPONENTIAL_SCALE: i32 = 5;
 const MAX_BIN_WIDTH: u16 = u16::MAX;
 
 #[inline]
@@ -650,6 +659,64 @@ impl AgentDDSketch {
         Ok(())
     }
 
+    /// Inserts the observations of an exponential histogram.
+    ///
+    /// The observations of each bucket are interpolated across the sketch bins that the bucket spans,
+    /// in the same way as for aggregated histograms.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if a bucket size is greater that `u32::MAX`.
+    pub fn insert_exponential_histogram(
+        &mut self,
+        histogram: &ExponentialHistogram,
+    ) -> Result<(), &'static str> {
+        let too_large =
+            |count: u64| u32::try_from(count).map_err(|_| "bucket size greater than u32::MAX");
+
+        let zero_count = too_large(histogram.zero_count)?;
+        if zero_count > 0 {
+            self.insert_n(0.0, zero_count);
+        }
+        for (index, count) in histogram.negative.iter() {
+            let count = too_large(count)?;
+            if count > 0 {
+                self.insert_interpolate_bucket(
+                    -histogram.lower_bound(index + 1),
+                    -histogram.lower_bound(index),
+                    count,
+                );
+            }
+        }
+        for (index, count) in histogram.positive.iter() {
+            let count = too_large(count)?;
+            if count > 0 {
+                self.insert_interpolate_bucket(
+                    histogram.lower_bound(index),
+                    histogram.lower_bound(index + 1),
+                    count,
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Converts the sketch to an exponential histogram at the given scale.
+    ///
+    /// The observations of each bin are recorded at the lower bound of the bin, while the count and
+    /// the sum are carried over as-is. Using [`AGENT_EXPONENTIAL_SCALE`] gives a resolution close
+    /// to the one of the sketch.
+    pub fn to_exponential_histogram(&self, scale: i32) -> ExponentialHistogram {
+        let mut histogram = ExponentialHistogram::new(scale);
+        for bin in &self.bins {
+            histogram.record(self.config.bin_lower_bound(bin.k), u64::from(bin.n));
+        }
+        histogram.count = u64::from(self.count);
+        histogram.sum = self.sum().unwrap_or(0.0);
+        histogram
+    }
+
     /// Adds a bin directly into the sketch.
     ///
     /// Used only for unit testing so that we can create a sketch with an exact layout, which allows
@@ -793,6 +860,11 @@ impl AgentDDSketch {
                 sketch.insert_interpolate_buckets(delta_buckets)?;
                 Some(sketch)
             }
+            MetricValue::ExponentialHistogram { histogram } => {
+                let mut sketch = AgentDDSketch::with_agent_defaults();
+                sketch.insert_exponential_histogram(histogram)?;
+                Some(sketch)
+            }
             // We can't convert from any other metric value.
             _ => None,
         };
@@ -1095,8 +1167,10 @@ fn round_to_even(v: f64) -> f64 {
 
 #[cfg(test)]
 mod tests {
-    use super::{round_to_even, AgentDDSketch, Config, AGENT_DEFAULT_EPS, MAX_KEY};
-    use crate::event::metric::Bucket;
+    use super::{
+        round_to_even, AgentDDSketch, Config, AGENT_DEFAULT_EPS, AGENT_EXPONENTIAL_SCALE, MAX_KEY,
+    };
+    use crate::event::metric::{Bucket, ExponentialHistogram};
 
     const FLOATING_POINT_ACCEPTABLE_ERROR: f64 = 1.0e-10;
 
@@ -1233,6 +1307,29 @@ mod tests {
         assert_eq!(sketch, AgentDDSketch::with_agent_defaults());
     }
 
+    #[test]
+    fn test_exponential_histogram_round_trip() {
+        let mut histogram = ExponentialHistogram::new(AGENT_EXPONENTIAL_SCALE);
+        for value in [-4.0, 0.0, 1.5, 3.0, 10.0, 250.0] {
+            histogram.record(value, 10);
+        }
+
+        let mut sketch = AgentDDSketch::with_agent_defaults();
+        sketch
+            .insert_exponential_histogram(&histogram)
+            .expect("should not fail to insert histogram");
+        assert_eq!(sketch.count(), 60);
+        let p99 = sketch.quantile(0.99).expect("sketch should not be empty");
+        assert!((p99 - 250.0).abs() / 250.0 < 0.05, "p99 was {}", p99);
+
+        let converted = sketch.to_exponential_histogram(AGENT_EXPONENTIAL_SCALE);
+        assert_eq!(converted.scale, AGENT_EXPONENTIAL_SCALE);
+        assert_eq!(converted.count, 60);
+        assert_eq!(converted.zero_count, 10);
+        assert_eq!(converted.negative.iter().map(|(_, n)| n).sum::<u64>(), 10);
+        assert_eq!(converted.positive.iter().map(|(_, n)| n).sum::<u64>(), 40);
+    }
+
     #[test]
     fn test_merge() {
         let mut all_values = AgentDDSketch::with_agent_defaults();
@@ -13,7 +13,7 @@ use metrics_util::layers::Layer;
 use once_cell::sync::OnceCell;
 use snafu::Snafu;
 
-pub use self::ddsketch::{AgentDDSketch, BinMap, Config};
+pub use self::ddsketch::{AgentDDSketch, BinMap, Config, AGENT_EXPONENTIAL_SCALE};
 use self::{label_filter::VectorLabelFilter, recorder::Registry, recorder::VectorRecorder};
 use crate::event::{Metric, MetricValue};
 
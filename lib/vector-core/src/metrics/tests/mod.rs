@@ -33,9 +33,9 @@ fn test_labels_injection() {
 
     let expected_tags = Some(
         vec![
-            ("component_id".to_owned(), "my_component_id".to_owned()),
-            ("component_type".to_owned(), "my_component_type".to_owned()),
-            ("component_kind".to_owned(), "my_component_kind".to_owned()),
+            ("component_id".into(), "my_component_id".to_owned()),
+            ("component_type".into(), "my_component_type".to_owned()),
+            ("component_kind".into(), "my_component_kind".to_owned()),
         ]
         .into_iter()
         .collect(),
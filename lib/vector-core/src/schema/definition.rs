@@ -320,6 +320,16 @@ impl Definition {
         self
     }
 
+    /// Add type information for an optional metadata field.
+    ///
+    /// # Panics
+    ///
+    /// See `Definition::with_metadata_field`.
+    #[must_use]
+    pub fn optional_metadata_field(self, path: impl Into<LookupBuf>, kind: Kind) -> Self {
+        self.with_metadata_field(path, kind.or_undefined())
+    }
+
     /// Add type information for an optional event field.
     ///
     /// # Panics
@@ -368,6 +378,15 @@ impl Definition {
         self
     }
 
+    /// Set the kind for all unknown metadata fields.
+    #[must_use]
+    pub fn unknown_metadata_fields(mut self, unknown: impl Into<Kind>) -> Self {
+        if let Some(object) = self.metadata_kind.as_object_mut() {
+            object.set_unknown(unknown.into());
+        }
+        self
+    }
+
     /// Merge `other` definition into `self`.
     ///
     /// This just takes the union of both definitions.
@@ -416,11 +435,51 @@ impl Definition {
         &self.event_kind
     }
 
+    /// Returns whether the event field at the path is present in every event, even if it may be
+    /// `null`.
+    ///
+    /// A field added with `optional_field`, or only added by some of the merged definitions, may be
+    /// absent instead, which is tracked separately from whether its value may be `null`.
+    pub fn is_required(&self, path: impl Into<LookupBuf>) -> bool {
+        is_required(&self.event_kind.at_path(&path.into()))
+    }
+
+    /// Returns whether the event field at the path may be absent from some events, but present in
+    /// others.
+    pub fn is_optional(&self, path: impl Into<LookupBuf>) -> bool {
+        is_optional(&self.event_kind.at_path(&path.into()))
+    }
+
+    /// Returns whether the value of the event field at the path may be `null`, when present.
+    pub fn is_nullable(&self, path: impl Into<LookupBuf>) -> bool {
+        self.event_kind.at_path(&path.into()).contains_null()
+    }
+
+    /// Returns whether the metadata field at the path is present in every event, even if it may be
+    /// `null`.
+    pub fn is_metadata_required(&self, path: impl Into<LookupBuf>) -> bool {
+        is_required(&self.metadata_kind.at_path(&path.into()))
+    }
+
+    /// Returns whether the metadata field at the path may be absent from some events, but present
+    /// in others.
+    pub fn is_metadata_optional(&self, path: impl Into<LookupBuf>) -> bool {
+        is_optional(&self.metadata_kind.at_path(&path.into()))
+    }
+
     pub fn metadata_kind(&self) -> &Kind {
         &self.metadata_kind
     }
 }
 
+fn is_required(kind: &Kind) -> bool {
+    kind.contains_any_defined() && !kind.contains_undefined()
+}
+
+fn is_optional(kind: &Kind) -> bool {
+    kind.contains_any_defined() && kind.contains_undefined()
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::{BTreeMap, HashMap};
@@ -613,6 +672,102 @@ mod tests {
         assert_eq!(got, want);
     }
 
+    #[test]
+    fn test_metadata_fields() {
+        let want = Definition {
+            event_kind: Kind::object(Collection::empty()),
+            metadata_kind: Kind::object(
+                Collection::from(BTreeMap::from([
+                    (
+                        "vector".into(),
+                        Kind::object(BTreeMap::from([("source_type".into(), Kind::bytes())])),
+                    ),
+                    ("tenant".into(), Kind::bytes().or_undefined()),
+                ]))
+                .with_unknown(Kind::integer()),
+            ),
+            meaning: BTreeMap::default(),
+            log_namespaces: BTreeSet::from([LogNamespace::Vector]),
+        };
+
+        let got = Definition::new_with_default_metadata(
+            Kind::object(Collection::empty()),
+            [LogNamespace::Vector],
+        )
+        .with_vector_metadata(None::<LookupBuf>, "source_type", Kind::bytes(), None)
+        .optional_metadata_field("tenant", Kind::bytes())
+        .unknown_metadata_fields(Kind::integer());
+
+        assert_eq!(got, want);
+    }
+
+    #[test]
+    fn test_required_and_nullable() {
+        let definition = Definition::new_with_default_metadata(
+            Kind::object(Collection::empty()),
+            [LogNamespace::Legacy],
+        )
+        .with_field("required", Kind::bytes(), None)
+        .with_field("nullable", Kind::bytes().or_null(), None)
+        .optional_field("optional", Kind::bytes(), None)
+        .optional_field("optional_nullable", Kind::bytes().or_null(), None);
+
+        assert!(definition.is_required("required"));
+        assert!(!definition.is_nullable("required"));
+        assert!(definition.is_required("nullable"));
+        assert!(definition.is_nullable("nullable"));
+        assert!(definition.is_optional("optional"));
+        assert!(!definition.is_nullable("optional"));
+        assert!(definition.is_optional("optional_nullable"));
+        assert!(definition.is_nullable("optional_nullable"));
+        assert!(!definition.is_required("missing"));
+        assert!(!definition.is_optional("missing"));
+    }
+
+    #[test]
+    fn test_merge_required() {
+        let this = Definition::new_with_default_metadata(
+            Kind::object(Collection::empty()),
+            [LogNamespace::Legacy],
+        )
+        .with_field("both", Kind::bytes(), None)
+        .with_field("this", Kind::bytes(), None)
+        .with_metadata_field("tenant", Kind::bytes());
+        let other = Definition::new_with_default_metadata(
+            Kind::object(Collection::empty()),
+            [LogNamespace::Legacy],
+        )
+        .with_field("both", Kind::null(), None);
+
+        let merged = this.merge(other);
+
+        assert!(merged.is_required("both"));
+        assert!(merged.is_nullable("both"));
+        assert!(merged.is_optional("this"));
+        assert!(!merged.is_nullable("this"));
+        assert!(merged.is_metadata_optional("tenant"));
+        assert!(!merged.is_metadata_required("tenant"));
+    }
+
+    #[test]
+    fn test_merge_metadata() {
+        let this =
+            Definition::new_with_default_metadata(Kind::any_object(), [LogNamespace::Vector])
+                .with_metadata_field("tenant", Kind::bytes());
+        let other =
+            Definition::new_with_default_metadata(Kind::any_object(), [LogNamespace::Vector])
+                .with_metadata_field("tenant", Kind::integer())
+                .with_metadata_field("region", Kind::bytes());
+
+        assert_eq!(
+            this.merge(other).metadata_kind(),
+            &Kind::object(BTreeMap::from([
+                ("tenant".into(), Kind::bytes().or_integer()),
+                ("region".into(), Kind::bytes().or_undefined()),
+            ]))
+        );
+    }
+
     #[test]
     #[allow(clippy::too_many_lines)]
     fn test_merge() {
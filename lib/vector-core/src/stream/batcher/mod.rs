@@ -15,6 +15,8 @@ use futures::{
 use pin_project::pin_project;
 use tokio::time::Sleep;
 
+use crate::internal_event::{emit, BatchFlushReason, BatchFlushed};
+
 #[pin_project]
 pub struct Batcher<S, C> {
     state: C,
@@ -64,7 +66,7 @@ where
                         if this.state.len() == 0 {
                             Poll::Ready(None)
                         } else {
-                            Poll::Ready(Some(this.state.take_batch()))
+                            Poll::Ready(Some(take_batch(this.state, BatchFlushReason::EndOfStream)))
                         }
                     }
                 }
@@ -74,13 +76,17 @@ where
                         this.state.push(item, item_metadata);
                         if this.state.is_batch_full() {
                             this.timer.set(Maybe::None);
-                            return Poll::Ready(Some(this.state.take_batch()));
+                            return Poll::Ready(Some(take_batch(
+                                this.state,
+                                BatchFlushReason::Size,
+                            )));
                         } else if this.state.len() == 1 {
                             this.timer
                                 .set(Maybe::Some(tokio::time::sleep(this.state.timeout())));
                         }
                     } else {
-                        let output = Poll::Ready(Some(this.state.take_batch()));
+                        let output =
+                            Poll::Ready(Some(take_batch(this.state, BatchFlushReason::Size)));
                         this.state.push(item, item_metadata);
                         this.timer
                             .set(Maybe::Some(tokio::time::sleep(this.state.timeout())));
@@ -96,7 +102,7 @@ where
                                 this.state.len() != 0,
                                 "timer should have been cancelled"
                             );
-                            Poll::Ready(Some(this.state.take_batch()))
+                            Poll::Ready(Some(take_batch(this.state, BatchFlushReason::Timeout)))
                         } else {
                             Poll::Pending
                         }
@@ -111,6 +117,15 @@ where
     }
 }
 
+/// Takes the batch out of the state, recording why it was flushed.
+fn take_batch<T, C: BatchConfig<T>>(state: &mut C, reason: BatchFlushReason) -> C::Batch {
+    emit(BatchFlushed {
+        reason,
+        count: state.len(),
+    });
+    state.take_batch()
+}
+
 #[cfg(test)]
 #[allow(clippy::similar_names)]
 mod test {
@@ -15,6 +15,7 @@ use tokio_util::time::{delay_queue::Key, DelayQueue};
 use twox_hash::XxHash64;
 
 use crate::{
+    internal_event::{emit, BatchFlushReason, BatchFlushed},
     partition::Partitioner,
     stream::batcher::{
         config::BatchConfigParts,
@@ -86,6 +87,12 @@ where
         }
     }
 
+    fn remove(&mut self, item_key: &K) {
+        if let Some(expiration_key) = self.expiration_map.remove(item_key) {
+            self.expirations.remove(&expiration_key);
+        }
+    }
+
     fn poll_expired(&mut self, cx: &mut Context) -> Poll<Option<K>> {
         match ready!(self.expirations.poll_expired(cx)) {
             // No expirations yet.
@@ -210,6 +217,9 @@ where
 
 /// Controls the behavior of the batcher in terms of batch size and flush interval.
 ///
+/// The limits apply to each batch, so to each partition of a partitioned batcher, except for
+/// `total_size_limit`, that applies to the batches of all the partitions together.
+///
 /// This is a temporary solution for pushing in a fixed settings structure so we don't have to worry
 /// about misordering parameters and what not.  At some point, we will pull
 /// `BatchConfig`/`BatchSettings`/`BatchSize` out of `vector` and move them into `vector_core`, and
@@ -220,6 +230,7 @@ pub struct BatcherSettings {
     pub timeout: Duration,
     pub size_limit: usize,
     pub item_limit: usize,
+    pub total_size_limit: usize,
 }
 
 impl BatcherSettings {
@@ -232,6 +243,19 @@ impl BatcherSettings {
             timeout,
             size_limit: size_limit.get(),
             item_limit: item_limit.get(),
+            total_size_limit: usize::MAX,
+        }
+    }
+
+    /// Limits the total size of the batches of a partitioned batcher.
+    ///
+    /// When the batches of all the partitions together exceed this limit, the largest of them are
+    /// flushed until they fit within it again.
+    #[must_use]
+    pub const fn with_total_size_limit(self, total_size_limit: NonZeroUsize) -> Self {
+        BatcherSettings {
+            total_size_limit: total_size_limit.get(),
+            ..self
         }
     }
 
@@ -296,6 +320,11 @@ where
     batch_allocation_limit: usize,
     /// The maximum number of items that are allowed per-batch
     batch_item_limit: usize,
+    /// The total number of bytes all the batches in this struct are allowed to
+    /// hold together.
+    total_allocation_limit: usize,
+    /// The total number of bytes held by the live batches.
+    total_allocated_bytes: usize,
     /// The store of live batches. Note that the key here is an option type,
     /// on account of the interface of `Prt`.
     batches: HashMap<Prt::Key, Batch<Prt::Item>, BuildHasherDefault<XxHash64>>,
@@ -323,6 +352,8 @@ where
         Self {
             batch_allocation_limit: settings.size_limit,
             batch_item_limit: settings.item_limit,
+            total_allocation_limit: settings.total_size_limit,
+            total_allocated_bytes: 0,
             batches: HashMap::default(),
             closed_batches: Vec::default(),
             timer: ExpirationQueue::new(settings.timeout),
@@ -350,6 +381,8 @@ where
             batch_allocation_limit: batch_allocation_limit
                 .map_or(usize::max_value(), NonZeroUsize::get),
             batch_item_limit: batch_item_limit.get(),
+            total_allocation_limit: usize::max_value(),
+            total_allocated_bytes: 0,
             batches: HashMap::default(),
             closed_batches: Vec::default(),
             timer,
@@ -389,7 +422,13 @@ where
                             .batches
                             .remove(&item_key)
                             .expect("batch should exist if it is set to expire");
-                        this.closed_batches.push((item_key, batch.into_inner()));
+                        close_batch(
+                            this.closed_batches,
+                            this.total_allocated_bytes,
+                            item_key,
+                            batch,
+                            BatchFlushReason::Timeout,
+                        );
 
                         continue;
                     }
@@ -402,11 +441,15 @@ where
                     // we finish.
                     if !this.batches.is_empty() {
                         this.timer.clear();
-                        this.closed_batches.extend(
-                            this.batches
-                                .drain()
-                                .map(|(key, batch)| (key, batch.into_inner())),
-                        );
+                        for (key, batch) in this.batches.drain() {
+                            close_batch(
+                                this.closed_batches,
+                                this.total_allocated_bytes,
+                                key,
+                                batch,
+                                BatchFlushReason::EndOfStream,
+                            );
+                        }
                         continue;
                     }
                     return Poll::Ready(None);
@@ -415,6 +458,7 @@ where
                     let item_key = this.partitioner.partition(&item);
                     let item_limit: usize = *this.batch_item_limit;
                     let alloc_limit: usize = *this.batch_allocation_limit;
+                    *this.total_allocated_bytes += item.size_of();
 
                     if let Some(batch) = this.batches.get_mut(&item_key) {
                         if batch.has_space(&item) {
@@ -430,7 +474,13 @@ where
                             // pushed out, so now we reset the batch timeout.
                             this.timer.insert(item_key.clone());
 
-                            this.closed_batches.push((item_key, batch.into_inner()));
+                            close_batch(
+                                this.closed_batches,
+                                this.total_allocated_bytes,
+                                item_key,
+                                batch,
+                                BatchFlushReason::Size,
+                            );
                         }
                     } else {
                         // We have no batch yet for this partition key, so
@@ -441,12 +491,53 @@ where
                         this.batches.insert(item_key.clone(), batch);
                         this.timer.insert(item_key);
                     }
+
+                    // The largest batches are flushed early when all of them together hold more
+                    // than allowed, so that a sink with many partitions doesn't hold too much.
+                    while *this.total_allocated_bytes > *this.total_allocation_limit {
+                        let item_key = this
+                            .batches
+                            .iter()
+                            .max_by_key(|(_, batch)| batch.allocated_bytes)
+                            .map(|(key, _)| key.clone())
+                            .expect("batches should exist if they hold bytes");
+                        let batch = this
+                            .batches
+                            .remove(&item_key)
+                            .expect("batch should exist if it is the largest");
+                        this.timer.remove(&item_key);
+                        close_batch(
+                            this.closed_batches,
+                            this.total_allocated_bytes,
+                            item_key,
+                            batch,
+                            BatchFlushReason::TotalSize,
+                        );
+                    }
                 }
             }
         }
     }
 }
 
+/// Closes the batch, so that it's flushed before any new item is taken from the stream.
+fn close_batch<K, I>(
+    closed_batches: &mut Vec<(K, Vec<I>)>,
+    total_allocated_bytes: &mut usize,
+    key: K,
+    batch: Batch<I>,
+    reason: BatchFlushReason,
+) where
+    I: ByteSizeOf,
+{
+    *total_allocated_bytes -= batch.allocated_bytes;
+    emit(BatchFlushed {
+        reason,
+        count: batch.elements.len(),
+    });
+    closed_batches.push((key, batch.into_inner()));
+}
+
 #[cfg(test)]
 mod test {
     use std::{
@@ -457,14 +548,14 @@ mod test {
         time::Duration,
     };
 
-    use futures::{stream, Stream};
+    use futures::{stream, Stream, StreamExt};
     use pin_project::pin_project;
     use proptest::prelude::*;
     use tokio::{pin, time::advance};
 
     use crate::{
         partition::Partitioner,
-        stream::partitioned_batcher::{ExpirationQueue, PartitionedBatcher},
+        stream::partitioned_batcher::{BatcherSettings, ExpirationQueue, PartitionedBatcher},
         time::KeyedTimer,
     };
 
@@ -498,6 +589,10 @@ mod test {
             self.valid_keys.insert(item_key);
         }
 
+        fn remove(&mut self, item_key: &u8) {
+            self.valid_keys.remove(item_key);
+        }
+
         fn poll_expired(&mut self, _cx: &mut Context) -> Poll<Option<u8>> {
             match self.responses.pop() {
                 Some(Poll::Pending) => unreachable!(),
@@ -735,6 +830,29 @@ mod test {
         }
     }
 
+    #[tokio::test]
+    async fn total_size_limit_flushes_largest_batch() {
+        // Asserts that once the batches of all the partitions together hold
+        // more than the total size limit, the largest of them is flushed
+        // before it reaches its own limits.
+        let item_size = std::mem::size_of::<u64>();
+        let settings = BatcherSettings::new(
+            Duration::from_secs(60),
+            NonZeroUsize::new(item_size * 100).unwrap(),
+            NonZeroUsize::new(100).unwrap(),
+        )
+        .with_total_size_limit(NonZeroUsize::new(item_size * 3).unwrap());
+        let partitioner = TestPartitioner {
+            key_space: NonZeroU8::new(2).unwrap(),
+        };
+
+        let batcher =
+            PartitionedBatcher::new(stream::iter(vec![0, 2, 1, 4]), partitioner, settings);
+        let batches: Vec<_> = batcher.collect().await;
+
+        assert_eq!(batches, vec![(0, vec![0, 2, 4]), (1, vec![1])]);
+    }
+
     #[tokio::test(start_paused = true)]
     #[allow(clippy::semicolon_if_nothing_returned)] // https://github.com/rust-lang/rust-clippy/issues/7438
     async fn expiration_queue_impl_keyed_timer() {
@@ -21,6 +21,11 @@ pub trait KeyedTimer<K> {
     /// If the given key already exists in the timer, the underlying subtimer is reset.
     fn insert(&mut self, item_key: K);
 
+    /// Removes the subtimer keyed by `K`, if any.
+    ///
+    /// Future calls to `poll_expired` will not return the given key, until it is inserted again.
+    fn remove(&mut self, item_key: &K);
+
     /// Attempts to pull out the next expired subtimer in the queue.
     ///
     /// The key of the subtimer is returned if it has expired, otherwise, returns `None` if the
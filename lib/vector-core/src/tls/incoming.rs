@@ -1,7 +1,7 @@
 use std::{
     collections::HashMap,
     future::Future,
-    net::SocketAddr,
+    net::{IpAddr, SocketAddr},
     pin::Pin,
     sync::Arc,
     task::{Context, Poll},
@@ -42,7 +42,12 @@ impl TlsSettings {
 impl MaybeTlsSettings {
     pub async fn bind(&self, addr: &SocketAddr) -> crate::tls::Result<MaybeTlsListener> {
         let listener = TcpListener::bind(addr).await.context(TcpBindSnafu)?;
+        self.listen(listener)
+    }
 
+    /// Accepts connections on a listener that is already bound, such as one passed by the process
+    /// that started Vector.
+    pub fn listen(&self, listener: TcpListener) -> crate::tls::Result<MaybeTlsListener> {
         let acceptor = match self {
             Self::Tls(tls) => Some(tls.acceptor()?),
             Self::Raw(()) => None,
@@ -343,6 +348,7 @@ pub struct CertificateMetadata {
     pub organization_name: Option<String>,
     pub organizational_unit_name: Option<String>,
     pub common_name: Option<String>,
+    pub subject_alt_names: Vec<String>,
 }
 
 impl CertificateMetadata {
@@ -387,10 +393,40 @@ impl From<X509> for CertificateMetadata {
             organization_name: subject_metadata.get("organizationName").cloned(),
             organizational_unit_name: subject_metadata.get("organizationalUnitName").cloned(),
             common_name: subject_metadata.get("commonName").cloned(),
+            subject_alt_names: subject_alt_names(&cert),
         }
     }
 }
 
+/// Gets the DNS names, email addresses, URIs and IP addresses of the subject alternative names of
+/// the certificate.
+fn subject_alt_names(cert: &X509) -> Vec<String> {
+    cert.subject_alt_names()
+        .map(|names| {
+            names
+                .iter()
+                .filter_map(|name| {
+                    name.dnsname()
+                        .or_else(|| name.email())
+                        .or_else(|| name.uri())
+                        .map(ToOwned::to_owned)
+                        .or_else(|| name.ipaddress().and_then(ip_address_to_string))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Formats the octets of an IPv4 or IPv6 address.
+fn ip_address_to_string(octets: &[u8]) -> Option<String> {
+    match <[u8; 4]>::try_from(octets) {
+        Ok(octets) => Some(IpAddr::from(octets).to_string()),
+        Err(_) => <[u8; 16]>::try_from(octets)
+            .ok()
+            .map(|octets| IpAddr::from(octets).to_string()),
+    }
+}
+
 #[derive(Clone)]
 pub struct MaybeTlsConnectInfo {
     pub remote_addr: SocketAddr,
@@ -1,4 +1,9 @@
-use std::{collections::HashMap, error, pin::Pin};
+use std::{
+    collections::HashMap,
+    error,
+    pin::Pin,
+    time::{Duration, Instant},
+};
 
 use futures::{Stream, StreamExt};
 use vector_common::internal_event::{emit, EventsSent, DEFAULT_OUTPUT};
@@ -22,6 +27,7 @@ pub enum Transform {
     Function(Box<dyn FunctionTransform>),
     Synchronous(Box<dyn SyncTransform>),
     Task(Box<dyn TaskTransform<EventArray>>),
+    OutputsTask(Box<dyn OutputsTaskTransform>),
 }
 
 impl Transform {
@@ -100,6 +106,15 @@ impl Transform {
         Transform::Task(Box::new(WrapEventTask(v)))
     }
 
+    /// Create a new task transform writing to multiple outputs.
+    ///
+    /// These tasks are coordinated like the other task transforms, but write to multiple outputs
+    /// like synchronous transforms do. Those outputs must be known in advanced and returned via
+    /// `TransformConfig::outputs`.
+    pub fn outputs_task(v: impl OutputsTaskTransform + 'static) -> Self {
+        Transform::OutputsTask(Box::new(v))
+    }
+
     /// Mutably borrow the inner transform as a task transform.
     ///
     /// # Panics
@@ -172,6 +187,18 @@ pub trait TaskTransform<T: EventContainer + 'static>: Send + 'static {
     }
 }
 
+/// Broader than [`TaskTransform`], this trait allows task transforms to write to multiple outputs.
+///
+/// Each item of the returned stream holds the events the transform sends at once, in buffers created
+/// with [`TransformOutputsBuf::new_with_capacity`] for the outputs returned via
+/// `TransformConfig::outputs`.
+pub trait OutputsTaskTransform: Send + 'static {
+    fn transform(
+        self: Box<Self>,
+        task: Pin<Box<dyn Stream<Item = EventArray> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = TransformOutputsBuf> + Send>>;
+}
+
 /// Broader than the simple [`FunctionTransform`], this trait allows transforms to write to
 /// multiple outputs. Those outputs must be known in advanced and returned via
 /// `TransformConfig::outputs`. Attempting to send to any output not registered in advance is
@@ -216,6 +243,8 @@ pub struct TransformOutputs {
     outputs_spec: Vec<Output>,
     primary_output: Option<Fanout>,
     named_outputs: HashMap<String, Fanout>,
+    primary_send_duration: Duration,
+    named_send_durations: HashMap<String, Duration>,
 }
 
 impl TransformOutputs {
@@ -223,6 +252,7 @@ impl TransformOutputs {
         let outputs_spec = outputs_in.clone();
         let mut primary_output = None;
         let mut named_outputs = HashMap::new();
+        let mut named_send_durations = HashMap::new();
         let mut controls = HashMap::new();
 
         for output in outputs_in {
@@ -234,6 +264,7 @@ impl TransformOutputs {
                 }
                 Some(name) => {
                     named_outputs.insert(name.clone(), fanout);
+                    named_send_durations.insert(name.clone(), Duration::ZERO);
                     controls.insert(Some(name.clone()), control);
                 }
             }
@@ -243,11 +274,18 @@ impl TransformOutputs {
             outputs_spec,
             primary_output,
             named_outputs,
+            primary_send_duration: Duration::ZERO,
+            named_send_durations,
         };
 
         (me, controls)
     }
 
+    /// Gets the number of outputs, including the default one.
+    pub fn output_count(&self) -> usize {
+        self.outputs_spec.len()
+    }
+
     pub fn new_buf_with_capacity(&self, capacity: usize) -> TransformOutputsBuf {
         TransformOutputsBuf::new_with_capacity(self.outputs_spec.clone(), capacity)
     }
@@ -265,11 +303,13 @@ impl TransformOutputs {
         if let Some(primary) = self.primary_output.as_mut() {
             let count = buf.primary_buffer.as_ref().map_or(0, OutputBuffer::len);
             let byte_size = buf.primary_buffer.as_ref().map_or(0, ByteSizeOf::size_of);
+            let start = Instant::now();
             buf.primary_buffer
                 .as_mut()
                 .expect("mismatched outputs")
                 .send(primary)
                 .await?;
+            self.primary_send_duration += start.elapsed();
             emit(EventsSent {
                 count,
                 byte_size,
@@ -280,8 +320,13 @@ impl TransformOutputs {
         for (key, buf) in &mut buf.named_buffers {
             let count = buf.len();
             let byte_size = buf.size_of();
+            let start = Instant::now();
             buf.send(self.named_outputs.get_mut(key).expect("unknown output"))
                 .await?;
+            *self
+                .named_send_durations
+                .get_mut(key)
+                .expect("unknown output") += start.elapsed();
             emit(EventsSent {
                 count,
                 byte_size,
@@ -291,6 +336,26 @@ impl TransformOutputs {
 
         Ok(())
     }
+
+    /// Takes the time spent sending events to each output since the last call, the default output
+    /// being `None`.
+    ///
+    /// Sending is only slow when the downstream components apply backpressure, so this tells which
+    /// of the outputs hold the transform back.
+    pub fn take_send_durations(&mut self) -> Vec<(Option<&str>, Duration)> {
+        let primary = self
+            .primary_output
+            .is_some()
+            .then(|| (None, std::mem::take(&mut self.primary_send_duration)));
+        primary
+            .into_iter()
+            .chain(
+                self.named_send_durations
+                    .iter_mut()
+                    .map(|(name, duration)| (Some(name.as_str()), std::mem::take(duration))),
+            )
+            .collect()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -7,6 +7,7 @@ pub mod remove_secret;
 pub mod set_metadata_field;
 pub mod set_secret;
 pub mod set_semantic_meaning;
+pub mod state;
 
 use ::value::Value;
 use lookup::OwnedTargetPath;
@@ -37,6 +38,8 @@ pub fn vrl_functions() -> Vec<Box<dyn vrl::Function>> {
         Box::new(get_secret::GetSecret) as _,
         Box::new(remove_secret::RemoveSecret) as _,
         Box::new(set_secret::SetSecret) as _,
+        Box::new(state::GetState) as _,
+        Box::new(state::SetState) as _,
     ]
 }
 
@@ -0,0 +1,306 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime},
+};
+
+use ::value::Value;
+use vrl::state::TypeState;
+use vrl::{diagnostic::Label, prelude::*};
+
+/// The number of keys from which the expired keys are removed when setting a value.
+///
+/// It doubles with the number of keys left afterwards, so that removing them is amortized.
+const MIN_PURGE_LEN: usize = 1024;
+
+/// The values of a key, along with when it expires.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StateEntry {
+    pub value: Value,
+    pub expires_at: Option<SystemTime>,
+}
+
+impl StateEntry {
+    fn is_expired(&self, now: SystemTime) -> bool {
+        self.expires_at
+            .map_or(false, |expires_at| expires_at <= now)
+    }
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    entries: HashMap<String, StateEntry>,
+    purge_len: usize,
+}
+
+impl Inner {
+    fn remove_expired(&mut self, now: SystemTime) {
+        self.entries.retain(|_, entry| !entry.is_expired(now));
+        self.purge_len = MIN_PURGE_LEN.max(self.entries.len() * 2);
+    }
+}
+
+/// The state of a component, as values by key, shared by all the events it processes.
+///
+/// It is set as the external context of the programs of the component, where the `get_state` and
+/// `set_state` functions read and write it. The clones of the state all share the same values.
+#[derive(Clone, Debug, Default)]
+pub struct ComponentState(Arc<Mutex<Inner>>);
+
+impl ComponentState {
+    /// Creates the state from the entries of a snapshot, leaving out the expired ones.
+    pub fn from_entries(entries: impl IntoIterator<Item = (String, StateEntry)>) -> Self {
+        let state = Self::default();
+        {
+            let mut inner = state.0.lock().expect("poisoned lock");
+            inner.entries.extend(entries);
+            inner.remove_expired(SystemTime::now());
+        }
+        state
+    }
+
+    /// Gets the value of the given key, if it has one that hasn't expired.
+    pub fn get(&self, key: &str) -> Option<Value> {
+        let inner = self.0.lock().expect("poisoned lock");
+        inner
+            .entries
+            .get(key)
+            .filter(|entry| !entry.is_expired(SystemTime::now()))
+            .map(|entry| entry.value.clone())
+    }
+
+    /// Sets the value of the given key, which expires after `ttl` if there is one.
+    ///
+    /// Setting the value to `null` removes the key.
+    pub fn set(&self, key: String, value: Value, ttl: Option<Duration>) {
+        let now = SystemTime::now();
+        let mut inner = self.0.lock().expect("poisoned lock");
+        if value.is_null() {
+            inner.entries.remove(&key);
+            return;
+        }
+
+        let expires_at = ttl.and_then(|ttl| now.checked_add(ttl));
+        inner.entries.insert(key, StateEntry { value, expires_at });
+        if inner.entries.len() >= inner.purge_len {
+            inner.remove_expired(now);
+        }
+    }
+
+    /// Takes a snapshot of the entries that haven't expired.
+    pub fn entries(&self) -> Vec<(String, StateEntry)> {
+        let mut inner = self.0.lock().expect("poisoned lock");
+        inner.remove_expired(SystemTime::now());
+        inner
+            .entries
+            .iter()
+            .map(|(key, entry)| (key.clone(), entry.clone()))
+            .collect()
+    }
+}
+
+/// Gets the state of the component the program is compiled for.
+fn component_state(
+    ctx: &FunctionCompileContext,
+) -> std::result::Result<ComponentState, ExpressionError> {
+    ctx.get_external_context::<ComponentState>()
+        .cloned()
+        .ok_or_else(|| ExpressionError::Error {
+            message: "state not available".to_owned(),
+            labels: vec![Label::primary(
+                "the state of the component is only available in the `remap` transform",
+                ctx.span(),
+            )],
+            notes: vec![],
+        })
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct GetState;
+
+impl Function for GetState {
+    fn identifier(&self) -> &'static str {
+        "get_state"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "key",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "Get a value of the state",
+            source: r#"get_state("last_seen")"#,
+            result: Ok("null"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &TypeState,
+        ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let state = component_state(ctx).map_err(|error| Box::new(error) as Box<_>)?;
+        let key = arguments.required("key");
+        Ok(GetStateFn { state, key }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct GetStateFn {
+    state: ComponentState,
+    key: Box<dyn Expression>,
+}
+
+impl FunctionExpression for GetStateFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let key = self.key.resolve(ctx)?;
+        let key = key.try_bytes_utf8_lossy()?;
+        Ok(self.state.get(&key).unwrap_or(Value::Null))
+    }
+
+    fn type_def(&self, _: &TypeState) -> TypeDef {
+        TypeDef::any().infallible()
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct SetState;
+
+impl Function for SetState {
+    fn identifier(&self) -> &'static str {
+        "set_state"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "key",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "value",
+                kind: kind::ANY,
+                required: true,
+            },
+            Parameter {
+                keyword: "ttl",
+                kind: kind::INTEGER | kind::FLOAT,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "Set a value of the state for an hour",
+            source: r#"set_state!("last_seen", "2022-11-02T10:30:00Z", ttl: 3600)"#,
+            result: Ok("null"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &TypeState,
+        ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let state = component_state(ctx).map_err(|error| Box::new(error) as Box<_>)?;
+        let key = arguments.required("key");
+        let value = arguments.required("value");
+        let ttl = arguments.optional("ttl");
+        Ok(SetStateFn {
+            state,
+            key,
+            value,
+            ttl,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SetStateFn {
+    state: ComponentState,
+    key: Box<dyn Expression>,
+    value: Box<dyn Expression>,
+    ttl: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for SetStateFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let key = self.key.resolve(ctx)?;
+        let key = key.try_bytes_utf8_lossy()?.into_owned();
+        let value = self.value.resolve(ctx)?;
+        let ttl = match &self.ttl {
+            Some(ttl) => {
+                let ttl = match ttl.resolve(ctx)? {
+                    Value::Integer(ttl) => ttl as f64,
+                    Value::Float(ttl) => ttl.into_inner(),
+                    _ => unreachable!("ttl is an integer or a float"),
+                };
+                if !ttl.is_finite() || ttl <= 0.0 {
+                    return Err(
+                        format!("ttl must be a positive number of seconds, got {}", ttl).into(),
+                    );
+                }
+                Some(Duration::from_secs_f64(ttl))
+            }
+            None => None,
+        };
+
+        self.state.set(key, value, ttl);
+        Ok(Value::Null)
+    }
+
+    fn type_def(&self, _: &TypeState) -> TypeDef {
+        TypeDef::null().with_fallibility(self.ttl.is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sets_and_gets_values() {
+        let state = ComponentState::default();
+        assert_eq!(state.get("count"), None);
+
+        state.set("count".to_owned(), 1.into(), None);
+        assert_eq!(state.clone().get("count"), Some(1.into()));
+
+        state.set("count".to_owned(), Value::Null, None);
+        assert_eq!(state.get("count"), None);
+    }
+
+    #[test]
+    fn expires_values() {
+        let state = ComponentState::from_entries([
+            (
+                "expired".to_owned(),
+                StateEntry {
+                    value: true.into(),
+                    expires_at: Some(SystemTime::now() - Duration::from_secs(1)),
+                },
+            ),
+            (
+                "kept".to_owned(),
+                StateEntry {
+                    value: true.into(),
+                    expires_at: Some(SystemTime::now() + Duration::from_secs(3600)),
+                },
+            ),
+        ]);
+
+        assert_eq!(state.get("expired"), None);
+        assert_eq!(state.get("kept"), Some(true.into()));
+        assert_eq!(state.entries().len(), 1);
+    }
+}
@@ -1,4 +1,4 @@
-use core::TargetValueRef;
+use core::{TargetValue, TargetValueRef};
 use std::{
     collections::BTreeMap,
     fs::File,
@@ -18,7 +18,7 @@ use vrl::{CompilationResult, CompileConfig};
 
 #[cfg(feature = "repl")]
 use super::repl;
-use super::Error;
+use super::{profile, Error};
 
 #[derive(Parser, Debug)]
 #[command(name = "VRL", about = "Vector Remap Language CLI")]
@@ -52,6 +52,11 @@ pub struct Opts {
     // Should the CLI emit warnings
     #[arg(long = "print-warnings")]
     print_warnings: bool,
+
+    /// Run the program against all of the events, then report the time spent and the memory
+    /// allocated by each of its top-level expressions, along with their types.
+    #[arg(long)]
+    profile: bool,
 }
 
 impl Opts {
@@ -82,10 +87,7 @@ impl Opts {
 
         match input.as_str() {
             "" => Ok(vec![Value::Object(BTreeMap::default())]),
-            _ => input
-                .lines()
-                .map(|line| Ok(serde_to_vrl(serde_json::from_str(line)?)))
-                .collect::<Result<Vec<Value>, Error>>(),
+            _ => parse_objects(&input),
         }
     }
 
@@ -121,6 +123,11 @@ fn run(opts: &Opts) -> Result<(), Error> {
         };
 
         repl(repl_objects, tz, opts.runtime)
+    } else if opts.profile {
+        let objects = opts.read_into_objects()?;
+        let source = opts.read_program()?;
+
+        run_profile(&source, objects, tz, opts.print_object)
     } else {
         let objects = opts.read_into_objects()?;
         let source = opts.read_program()?;
@@ -177,11 +184,69 @@ fn run(opts: &Opts) -> Result<(), Error> {
     }
 }
 
+fn run_profile(
+    source: &str,
+    objects: Vec<Value>,
+    timezone: TimeZone,
+    print_object: bool,
+) -> Result<(), Error> {
+    let mut objects = objects
+        .into_iter()
+        .map(|value| TargetValue {
+            value,
+            metadata: Value::Object(BTreeMap::new()),
+            secrets: Secrets::new(),
+        })
+        .collect::<Vec<_>>();
+
+    let profile = profile::run(
+        source,
+        &stdlib::all(),
+        &TypeState::default(),
+        compile_config,
+        &mut objects,
+        timezone,
+    )
+    .map_err(Error::Parse)?;
+
+    #[allow(clippy::print_stdout)]
+    #[allow(clippy::print_stderr)]
+    {
+        for (object, result) in objects.iter().zip(&profile.results) {
+            match result {
+                Ok(_) if print_object => println!("{}", object.value),
+                Ok(value) => println!("{}", value),
+                Err(err) => eprintln!("{}", err),
+            }
+        }
+
+        eprintln!("\n{}", profile);
+    }
+
+    Ok(())
+}
+
+/// Returns the configuration VRL programs are compiled with.
+pub(crate) fn compile_config() -> CompileConfig {
+    let mut config = CompileConfig::default();
+    // The CLI should be moved out of the "vrl" module, and then it can use the `vector-core::compile_vrl` function which includes this automatically
+    config.set_read_only_path(OwnedTargetPath::metadata(owned_value_path!("vector")), true);
+    config.set_custom(vector_vrl_functions::state::ComponentState::default());
+    config
+}
+
+/// Parses events from newline delimited JSON, one event per line.
+pub(crate) fn parse_objects(input: &str) -> Result<Vec<Value>, Error> {
+    input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| Ok(serde_to_vrl(serde_json::from_str(line)?)))
+        .collect()
+}
+
 #[cfg(feature = "repl")]
 #[allow(clippy::unnecessary_wraps)]
 fn repl(objects: Vec<Value>, timezone: TimeZone, vrl_runtime: VrlRuntime) -> Result<(), Error> {
-    use core::TargetValue;
-
     let objects = objects
         .into_iter()
         .map(|value| TargetValue {
@@ -13,6 +13,7 @@
 )]
 
 pub mod cmd;
+pub mod profile;
 #[cfg(feature = "repl")]
 mod repl;
 
@@ -1,7 +1,13 @@
 extern crate vrl_cli;
 
+use std::alloc::System;
+
 use clap::Parser;
-use vrl_cli::{cmd::cmd, Opts};
+use vrl_cli::{cmd::cmd, profile::ProfilingAllocator, Opts};
+
+// Allows `--profile` and the `profile` command of the REPL to count allocations.
+#[global_allocator]
+static ALLOC: ProfilingAllocator<System> = ProfilingAllocator(System);
 
 fn main() {
     std::process::exit(cmd(&Opts::parse()));
@@ -0,0 +1,335 @@
+//! Profiling of VRL programs against a set of sample events.
+//!
+//! The program is split into its top-level expressions, which are compiled one after the other,
+//! threading the type state through them, so that each of them can be timed on its own while the
+//! program runs against every event. Allocations are only counted if the [`ProfilingAllocator`]
+//! is installed as the global allocator, as the `vrl` binary does.
+
+use std::{
+    alloc::{GlobalAlloc, Layout},
+    fmt,
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+use ::value::{Kind, Value};
+use core::TargetValue;
+use vector_common::TimeZone;
+use vrl::{
+    diagnostic::Formatter,
+    prelude::TypeInfo,
+    state::{self, TypeState},
+    CompileConfig, Function, Program, Runtime,
+};
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+static TRACKING: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// The maximum number of characters of an expression shown in a report.
+const MAX_EXPRESSION_WIDTH: usize = 60;
+
+/// A global allocator wrapping another one, counting the allocations made while an expression is
+/// being profiled.
+#[derive(Debug)]
+pub struct ProfilingAllocator<A>(pub A);
+
+impl<A> ProfilingAllocator<A> {
+    fn track(size: usize) {
+        if !INSTALLED.load(Ordering::Relaxed) {
+            INSTALLED.store(true, Ordering::Relaxed);
+        }
+
+        if TRACKING.load(Ordering::Relaxed) {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            ALLOCATED_BYTES.fetch_add(size as u64, Ordering::Relaxed);
+        }
+    }
+}
+
+unsafe impl<A: GlobalAlloc> GlobalAlloc for ProfilingAllocator<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        Self::track(layout.size());
+        self.0.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        Self::track(layout.size());
+        self.0.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        Self::track(new_size);
+        self.0.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.0.dealloc(ptr, layout);
+    }
+}
+
+/// Returns the number of allocations, and allocated bytes, counted so far.
+fn allocation_counts() -> (u64, u64) {
+    (
+        ALLOCATIONS.load(Ordering::Relaxed),
+        ALLOCATED_BYTES.load(Ordering::Relaxed),
+    )
+}
+
+/// The measurements of a top-level expression of a profiled program.
+struct ExpressionProfile {
+    source: String,
+    program: Program,
+    kind: Kind,
+    duration: Duration,
+    allocations: u64,
+    allocated_bytes: u64,
+}
+
+/// The measurements of a program run against a set of events.
+pub(crate) struct Profile {
+    expressions: Vec<ExpressionProfile>,
+    errors: usize,
+    target_kind: Kind,
+
+    /// The result of the program for each of the events.
+    pub(crate) results: Vec<Result<Value, String>>,
+}
+
+/// Runs a program against all of the given events, measuring each of its top-level expressions.
+///
+/// The events are modified by the program. The diagnostics of the program are returned as an
+/// error if it doesn't compile.
+pub(crate) fn run(
+    source: &str,
+    functions: &[Box<dyn Function>],
+    state: &TypeState,
+    config: impl Fn() -> CompileConfig,
+    objects: &mut [TargetValue],
+    timezone: TimeZone,
+) -> Result<Profile, String> {
+    let compile = |source: &str, state: &TypeState| {
+        vrl::compile_with_state(source, functions, state, config())
+            .map(|result| result.program)
+            .map_err(|diagnostics| Formatter::new(source, diagnostics).colored().to_string())
+    };
+
+    // Compile the whole program first, so that its diagnostics are reported as a whole.
+    compile(source, state)?;
+
+    let mut state = state.clone();
+    let mut expressions = Vec::new();
+    for node in parser::parse(source)
+        .map_err(|error| error.to_string())?
+        .iter()
+    {
+        let source = &source[node.span().range()];
+        let program = compile(source, &state)?;
+        let TypeInfo {
+            state: next,
+            result,
+        } = program.final_type_info();
+        state = next;
+
+        expressions.push(ExpressionProfile {
+            source: source.trim().to_owned(),
+            program,
+            kind: result.kind().clone(),
+            duration: Duration::ZERO,
+            allocations: 0,
+            allocated_bytes: 0,
+        });
+    }
+
+    let mut errors = 0;
+    let mut results = Vec::with_capacity(objects.len());
+    for object in objects {
+        let mut runtime = Runtime::new(state::Runtime::default());
+        let mut result = Ok(Value::Null);
+
+        for expression in &mut expressions {
+            let (allocations, allocated_bytes) = allocation_counts();
+            TRACKING.store(true, Ordering::Relaxed);
+            let start = Instant::now();
+
+            result = runtime.resolve(object, &expression.program, &timezone);
+
+            expression.duration += start.elapsed();
+            TRACKING.store(false, Ordering::Relaxed);
+            let (end_allocations, end_allocated_bytes) = allocation_counts();
+            expression.allocations += end_allocations - allocations;
+            expression.allocated_bytes += end_allocated_bytes - allocated_bytes;
+
+            if result.is_err() {
+                errors += 1;
+                break;
+            }
+        }
+
+        results.push(result.map_err(|error| error.to_string()));
+    }
+
+    Ok(Profile {
+        expressions,
+        errors,
+        target_kind: state.external.target_kind().clone(),
+        results,
+    })
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn per_event(total: u64, events: usize) -> f64 {
+    total as f64 / events.max(1) as f64
+}
+
+fn truncate(source: &str) -> String {
+    let line = source.lines().next().unwrap_or_default();
+    if line.chars().count() > MAX_EXPRESSION_WIDTH || line.len() < source.len() {
+        let truncated = line
+            .chars()
+            .take(MAX_EXPRESSION_WIDTH - 1)
+            .collect::<String>();
+        format!("{}…", truncated)
+    } else {
+        line.to_owned()
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let events = self.results.len();
+        let total = self
+            .expressions
+            .iter()
+            .map(|expression| expression.duration)
+            .sum::<Duration>();
+        let tracked = INSTALLED.load(Ordering::Relaxed);
+
+        writeln!(
+            f,
+            "Profiled {} expression(s) against {} event(s), {} of which failed, in {:.2?}.\n",
+            self.expressions.len(),
+            events,
+            self.errors,
+            total
+        )?;
+        writeln!(
+            f,
+            "{:>10}  {:>10}  {:>6}  {:>8}  {:>10}  {:<24}  expression",
+            "total", "per event", "share", "allocs", "bytes", "type"
+        )?;
+
+        for expression in &self.expressions {
+            let duration = expression.duration.as_secs_f64();
+            let share = if total.is_zero() {
+                0.0
+            } else {
+                100.0 * duration / total.as_secs_f64()
+            };
+            let (allocations, allocated_bytes) = if tracked {
+                (
+                    format!("{:.1}", per_event(expression.allocations, events)),
+                    format!("{:.0}", per_event(expression.allocated_bytes, events)),
+                )
+            } else {
+                ("n/a".to_owned(), "n/a".to_owned())
+            };
+
+            #[allow(clippy::cast_precision_loss)]
+            let average = Duration::from_secs_f64(duration / events.max(1) as f64);
+            // Formatted ahead of time, as the padding is only applied to strings.
+            let duration = format!("{:.2?}", expression.duration);
+            let average = format!("{:.2?}", average);
+            let kind = expression.kind.to_string();
+            writeln!(
+                f,
+                "{:>10}  {:>10}  {:>5.1}%  {:>8}  {:>10}  {:<24}  {}",
+                duration,
+                average,
+                share,
+                allocations,
+                allocated_bytes,
+                kind,
+                truncate(&expression.source)
+            )?;
+        }
+
+        if !tracked {
+            writeln!(
+                f,
+                "\nAllocations are only counted by the standalone `vrl` binary."
+            )?;
+        }
+
+        write!(f, "\nResulting event type: {}", self.target_kind)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use value::Secrets;
+
+    use super::*;
+
+    fn object(value: Value) -> TargetValue {
+        TargetValue {
+            value,
+            metadata: Value::Object(BTreeMap::new()),
+            secrets: Secrets::new(),
+        }
+    }
+
+    #[test]
+    fn profiles_each_expression() {
+        let mut objects = vec![
+            object(BTreeMap::from([("message".to_owned(), Value::from("foo"))]).into()),
+            object(BTreeMap::from([("message".to_owned(), Value::from(1))]).into()),
+        ];
+
+        let profile = run(
+            ".message = upcase!(.message)\n.length = strlen(.message)",
+            &stdlib::all(),
+            &TypeState::default(),
+            CompileConfig::default,
+            &mut objects,
+            TimeZone::default(),
+        )
+        .unwrap();
+
+        assert_eq!(profile.expressions.len(), 2);
+        assert_eq!(
+            profile.expressions[0].source,
+            ".message = upcase!(.message)"
+        );
+        assert!(profile.expressions[1].kind.is_integer());
+        assert_eq!(profile.errors, 1);
+        assert_eq!(profile.results[0], Ok(Value::from(3)));
+        assert!(profile.results[1].is_err());
+        assert_eq!(
+            objects[0].value,
+            Value::from(BTreeMap::from([
+                ("message".to_owned(), Value::from("FOO")),
+                ("length".to_owned(), Value::from(3)),
+            ]))
+        );
+    }
+
+    #[test]
+    fn reports_diagnostics() {
+        let error = run(
+            ".message = upcase(.message)",
+            &stdlib::all(),
+            &TypeState::default(),
+            CompileConfig::default,
+            &mut [],
+            TimeZone::default(),
+        )
+        .err()
+        .unwrap();
+
+        assert!(error.contains("unhandled fallible assignment"));
+    }
+}
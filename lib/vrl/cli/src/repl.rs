@@ -1,9 +1,11 @@
 use core::TargetValue;
-use std::borrow::Cow::{self, Borrowed, Owned};
+use std::{
+    borrow::Cow::{self, Borrowed, Owned},
+    fs,
+};
 
 use ::value::Value;
 use indoc::indoc;
-use lookup::{owned_value_path, OwnedTargetPath};
 use once_cell::sync::Lazy;
 use prettytable::{format, Cell, Row, Table};
 use regex::Regex;
@@ -18,9 +20,12 @@ use rustyline::{
 use value::Secrets;
 use vector_common::TimeZone;
 use vector_vrl_functions::vrl_functions;
-use vrl::state::TypeState;
-use vrl::{
-    diagnostic::Formatter, prelude::BTreeMap, state, CompileConfig, Runtime, Target, VrlRuntime,
+use vrl::state::{TypeInfo, TypeState};
+use vrl::{diagnostic::Formatter, prelude::BTreeMap, state, Function, Runtime, Target, VrlRuntime};
+
+use crate::{
+    cmd::{compile_config, parse_objects},
+    profile,
 };
 
 // Create a list of all possible error values for potential docs lookup
@@ -47,6 +52,9 @@ const RESERVED_TERMS: &[&str] = &[
     "help funcs",
     "help fs",
     "help docs",
+    "load",
+    "replay",
+    "profile",
 ];
 
 pub(crate) fn run(
@@ -57,6 +65,9 @@ pub(crate) fn run(
     let mut index = 0;
     let func_docs_regex = Regex::new(r"^help\sdocs\s(\w{1,})$").unwrap();
     let error_docs_regex = Regex::new(r"^help\serror\s(\w{1,})$").unwrap();
+    let load_regex = Regex::new(r"^load\s+(.+)$").unwrap();
+    let replay_regex = Regex::new(r"(?s)^replay\s+(.+)$").unwrap();
+    let profile_regex = Regex::new(r"(?s)^profile\s+(.+)$").unwrap();
 
     let mut state = TypeState::default();
 
@@ -82,6 +93,31 @@ pub(crate) fn run(
             Ok(line) if error_docs_regex.is_match(line) => show_error_docs(line, &error_docs_regex),
             // Capture "help docs <func_name>"
             Ok(line) if func_docs_regex.is_match(line) => show_func_docs(line, &func_docs_regex),
+            // Capture "load <path>"
+            Ok(line) if load_regex.is_match(line) => {
+                rl.add_history_entry(line);
+                if let Some(loaded) = load_objects(capture(line, &load_regex)) {
+                    objects = loaded;
+                    index = 0;
+                }
+            }
+            // Capture "replay <program>"
+            Ok(line) if replay_regex.is_match(line) => {
+                rl.add_history_entry(line);
+                replay(
+                    &mut objects,
+                    &mut rt,
+                    capture(line, &replay_regex),
+                    &mut state,
+                    timezone,
+                    vrl_runtime,
+                );
+            }
+            // Capture "profile <program>"
+            Ok(line) if profile_regex.is_match(line) => {
+                rl.add_history_entry(line);
+                profile(&objects, capture(line, &profile_regex), &state, timezone);
+            }
             Ok(line) => {
                 rl.add_history_entry(line);
 
@@ -151,6 +187,18 @@ pub(crate) fn run(
     Ok(())
 }
 
+fn functions() -> Vec<Box<dyn Function>> {
+    let mut functions = stdlib::all();
+    functions.extend(vector_vrl_functions::vrl_functions());
+    functions
+}
+
+fn compile(program: &str, state: &TypeState) -> Result<vrl::Program, String> {
+    vrl::compile_with_state(program, &functions(), state, compile_config())
+        .map(|result| result.program)
+        .map_err(|diagnostics| Formatter::new(program, diagnostics).colored().to_string())
+}
+
 fn resolve(
     target: &mut TargetValue,
     runtime: &mut Runtime,
@@ -159,22 +207,122 @@ fn resolve(
     timezone: TimeZone,
     vrl_runtime: VrlRuntime,
 ) -> Result<Value, String> {
-    let mut functions = stdlib::all();
-    functions.extend(vector_vrl_functions::vrl_functions());
+    let program = compile(program, state)?;
+
+    *state = program.final_type_state();
+    execute(runtime, &program, target, timezone, vrl_runtime)
+}
+
+fn capture<'a>(line: &'a str, pattern: &Regex) -> &'a str {
+    // Unwrap is okay here, as the pattern has already matched, with a single capture group.
+    pattern
+        .captures(line)
+        .unwrap()
+        .get(1)
+        .unwrap()
+        .as_str()
+        .trim()
+}
+
+fn new_object(value: Value) -> TargetValue {
+    TargetValue {
+        value,
+        metadata: Value::Object(BTreeMap::new()),
+        secrets: Secrets::new(),
+    }
+}
 
-    let mut config = CompileConfig::default();
-    // The CLI should be moved out of the "vrl" module, and then it can use the `vector-core::compile_vrl` function which includes this automatically
-    config.set_read_only_path(OwnedTargetPath::metadata(owned_value_path!("vector")), true);
+/// Loads the events of a newline delimited JSON file, to replace the current objects.
+fn load_objects(path: &str) -> Option<Vec<TargetValue>> {
+    let result = fs::read_to_string(path)
+        .map_err(crate::Error::from)
+        .and_then(|input| parse_objects(&input));
+
+    #[allow(clippy::print_stdout)]
+    match result {
+        Ok(values) if values.is_empty() => {
+            println!("no events found in {}\n", path);
+            None
+        }
+        Ok(values) => {
+            println!("loaded {} event(s) from {}\n", values.len(), path);
+            Some(values.into_iter().map(new_object).collect())
+        }
+        Err(err) => {
+            println!("unable to load events from {}: {}\n", path, err);
+            None
+        }
+    }
+}
+
+/// Runs a program against all of the objects, printing the result for each of them, followed by
+/// the types of the result and of the objects.
+fn replay(
+    objects: &mut [TargetValue],
+    runtime: &mut Runtime,
+    program: &str,
+    state: &mut TypeState,
+    timezone: TimeZone,
+    vrl_runtime: VrlRuntime,
+) {
+    #![allow(clippy::print_stdout)]
 
-    let program = match vrl::compile_with_state(program, &functions, state, config) {
-        Ok(result) => result.program,
+    let program = match compile(program, state) {
+        Ok(program) => program,
         Err(diagnostics) => {
-            return Err(Formatter::new(program, diagnostics).colored().to_string());
+            println!("{}\n", diagnostics);
+            return;
         }
     };
 
-    *state = program.final_type_state();
-    execute(runtime, &program, target, timezone, vrl_runtime)
+    let TypeInfo {
+        state: next,
+        result,
+    } = program.final_type_info();
+    *state = next;
+
+    for (index, object) in objects.iter_mut().enumerate() {
+        let output = match execute(runtime, &program, object, timezone, vrl_runtime) {
+            Ok(value) => value.to_string(),
+            Err(err) => err,
+        };
+        println!("[{}] {}", index, output);
+    }
+
+    println!(
+        "\nresult type: {}\nobject type: {}\n",
+        result.kind(),
+        state.external.target_kind()
+    );
+}
+
+/// Profiles a program against copies of all of the objects, leaving them unchanged.
+fn profile(objects: &[TargetValue], program: &str, state: &TypeState, timezone: TimeZone) {
+    let mut objects = objects
+        .iter()
+        .map(|object| TargetValue {
+            value: object.value.clone(),
+            metadata: object.metadata.clone(),
+            secrets: object.secrets.clone(),
+        })
+        .collect::<Vec<_>>();
+
+    let output = match profile::run(
+        program,
+        &functions(),
+        state,
+        compile_config,
+        &mut objects,
+        timezone,
+    ) {
+        Ok(profile) => profile.to_string(),
+        Err(diagnostics) => diagnostics,
+    };
+
+    #[allow(clippy::print_stdout)]
+    {
+        println!("{}\n", output);
+    }
 }
 
 fn execute(
@@ -416,6 +564,10 @@ const HELP_TEXT: &str = indoc! {r#"
       help error <code>  Navigate to the docs for a specific error code
       next               Load the next object or create a new one
       prev               Load the previous object
+      load <path>        Replace the objects with the events of a file, one JSON event per line
+      replay <program>   Run a program against all of the objects, and show the resulting types
+      profile <program>  Report the time spent, memory allocated and type of each expression of a
+                         program, run against copies of all of the objects
       exit               Terminate the program
 "#};
 
@@ -450,6 +602,9 @@ const BANNER_TEXT: &str = indoc! {r#"
     >   help              Learn more about VRL
     >   next              Load the next object or create a new one
     >   prev              Load the previous object
+    >   load <path>       Load the objects from a file, one JSON event per line
+    >   replay <program>  Run a program against all of the objects
+    >   profile <program> Profile a program against all of the objects
     >   exit              Terminate the program
     >
     > Any other value is resolved to a VRL expression.
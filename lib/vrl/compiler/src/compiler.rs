@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use core::Value;
 use diagnostic::{DiagnosticList, DiagnosticMessage, Note, Severity, Span};
 use lookup::{OwnedTargetPath, OwnedValuePath, PathPrefix};
@@ -8,9 +10,10 @@ use crate::state::TypeState;
 use crate::value::VrlValueConvert;
 use crate::{
     expression::{
-        assignment, function_call, literal, predicate, query, Abort, Array, Assignment, Block,
-        Container, Error, Expr, Expression, FunctionArgument, FunctionCall, Group, IfStatement,
-        Literal, Noop, Not, Object, Op, Predicate, Query, Target, Unary, Variable,
+        assignment, function_call, literal, match_expression, predicate, query, Abort, Array,
+        Assignment, Block, Container, Error, Expr, Expression, FunctionArgument, FunctionCall,
+        Group, IfStatement, Literal, Match, MatchArm, Noop, Not, Object, Op, Pattern, Predicate,
+        Query, Target, Unary, Variable,
     },
     parser::ast::RootExpr,
     program::ProgramInfo,
@@ -118,8 +121,8 @@ impl<'a> Compiler<'a> {
 
     fn compile_expr(&mut self, node: Node<ast::Expr>, state: &mut TypeState) -> Option<Expr> {
         use ast::Expr::{
-            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Op, Query, Unary,
-            Variable,
+            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Match, Op, Query,
+            Unary, Variable,
         };
         let original_state = state.clone();
 
@@ -136,6 +139,7 @@ impl<'a> Compiler<'a> {
             Variable(node) => self.compile_variable(node, state).map(Into::into),
             Unary(node) => self.compile_unary(node, state).map(Into::into),
             Abort(node) => self.compile_abort(node, state).map(Into::into),
+            Match(node) => self.compile_match(node, state).map(Into::into),
         }?;
 
         // If the previously compiled expression is fallible, _and_ we are
@@ -900,6 +904,141 @@ impl<'a> Compiler<'a> {
         self.handle_missing_feature_error(node.span(), "expr-abort")
     }
 
+    #[cfg(feature = "expr-match")]
+    fn compile_match(&mut self, node: Node<ast::Match>, state: &mut TypeState) -> Option<Match> {
+        use match_expression::{bind, unbind, Coverage};
+
+        let (span, ast::Match { expr, arms }) = node.take();
+        let original_state = state.clone();
+
+        let expr = self.compile_expr(*expr, state)?;
+        let after_expr_state = state.clone();
+
+        // Arms are only reached by the values not matched by the previous ones, which narrows
+        // down the type of their bindings.
+        let mut coverage = Coverage::new(expr.type_def(&original_state).kind().clone());
+        let mut compiled_arms = Vec::with_capacity(arms.len());
+
+        for arm in arms {
+            let (arm_span, arm) = arm.take();
+            let ast::MatchArm {
+                pattern,
+                guard,
+                body,
+            } = arm;
+
+            if coverage.is_exhaustive() {
+                self.diagnostics
+                    .push(Box::new(match_expression::Error::UnreachableArm {
+                        span: arm_span,
+                    }));
+            }
+
+            let pattern_span = pattern.span();
+            let pattern = self.compile_pattern(pattern, state)?;
+
+            let mut bindings = vec![];
+            pattern.binding_kinds(coverage.remaining(), &mut bindings);
+            let mut idents = HashSet::new();
+            if let Some((ident, _)) = bindings.iter().find(|(ident, _)| !idents.insert(ident)) {
+                self.diagnostics
+                    .push(Box::new(match_expression::Error::DuplicateBinding {
+                        ident: ident.clone(),
+                        span: pattern_span,
+                    }));
+                return None;
+            }
+
+            *state = after_expr_state.clone();
+            let shadowed = bind(&mut state.local, bindings);
+
+            let guard = match guard {
+                Some(guard) => Some(
+                    self.compile_predicate(guard, state)?
+                        .map_err(|err| self.diagnostics.push(Box::new(err)))
+                        .ok()?,
+                ),
+                None => None,
+            };
+            let body = self.compile_expr(*body, state)?;
+
+            unbind(&mut state.local, shadowed);
+
+            if guard.is_none() {
+                coverage.add(&pattern);
+            }
+
+            compiled_arms.push(MatchArm {
+                pattern,
+                guard,
+                body: Box::new(body),
+            });
+        }
+
+        if !coverage.is_exhaustive() {
+            self.diagnostics
+                .push(Box::new(match_expression::Error::NonExhaustive {
+                    kind: coverage.remaining().clone(),
+                    span,
+                }));
+        }
+
+        let r#match = Match {
+            expr: Box::new(expr),
+            arms: compiled_arms,
+        };
+
+        // The current state is from one of the arms. Restore it and calculate
+        // the type state from the full "match" expression.
+        *state = original_state;
+        r#match.apply_type_info(state);
+        Some(r#match)
+    }
+
+    #[cfg(not(feature = "expr-match"))]
+    fn compile_match(&mut self, node: Node<ast::Match>, _: &mut ExternalEnv) -> Option<Expr> {
+        self.handle_missing_feature_error(node.span(), "expr-match")
+    }
+
+    #[cfg(feature = "expr-match")]
+    fn compile_pattern(
+        &mut self,
+        node: Node<ast::MatchPattern>,
+        state: &mut TypeState,
+    ) -> Option<Pattern> {
+        let (span, pattern) = node.take();
+
+        let pattern = match pattern {
+            ast::MatchPattern::Wildcard => Pattern::Wildcard,
+            ast::MatchPattern::Binding(ident) => Pattern::Binding(ident),
+            ast::MatchPattern::Literal(node) => {
+                match self.compile_literal(node, state)?.as_value() {
+                    Some(value) => Pattern::Literal(value),
+                    None => {
+                        self.diagnostics.push(Box::new(
+                            match_expression::Error::NonLiteralPattern { span },
+                        ));
+                        return None;
+                    }
+                }
+            }
+            ast::MatchPattern::Array(nodes) => Pattern::Array(
+                nodes
+                    .into_iter()
+                    .map(|node| self.compile_pattern(node, state))
+                    .collect::<Option<_>>()?,
+            ),
+            ast::MatchPattern::Object(fields) => Pattern::Object(
+                fields
+                    .into_iter()
+                    .map(|(key, node)| Some((key.into_inner(), self.compile_pattern(node, state)?)))
+                    .collect::<Option<_>>()?,
+            ),
+        };
+
+        Some(pattern)
+    }
+
     fn handle_parser_error(&mut self, error: parser::Error) {
         self.diagnostics.push(Box::new(error));
     }
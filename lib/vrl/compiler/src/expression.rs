@@ -34,6 +34,8 @@ pub(crate) mod function;
 pub(crate) mod function_call;
 #[cfg(feature = "expr-literal")]
 pub(crate) mod literal;
+#[cfg(feature = "expr-match")]
+pub(crate) mod match_expression;
 #[cfg(feature = "expr-if_statement")]
 pub(crate) mod predicate;
 #[cfg(feature = "expr-query")]
@@ -59,6 +61,8 @@ pub use group::Group;
 pub use if_statement::IfStatement;
 #[cfg(feature = "expr-literal")]
 pub use literal::Literal;
+#[cfg(feature = "expr-match")]
+pub use match_expression::{Match, MatchArm, Pattern};
 pub use noop::Noop;
 #[cfg(feature = "expr-unary")]
 pub use not::Not;
@@ -145,14 +149,16 @@ pub enum Expr {
     Unary(Unary),
     #[cfg(feature = "expr-abort")]
     Abort(Abort),
+    #[cfg(feature = "expr-match")]
+    Match(Match),
 }
 
 impl Expr {
     pub fn as_str(&self) -> &str {
         use container::Variant::{Array, Block, Group, Object};
         use Expr::{
-            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Noop, Op, Query,
-            Unary, Variable,
+            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Match, Noop, Op,
+            Query, Unary, Variable,
         };
 
         match self {
@@ -180,6 +186,8 @@ impl Expr {
             Unary(..) => "unary operation",
             #[cfg(feature = "expr-abort")]
             Abort(..) => "abort operation",
+            #[cfg(feature = "expr-match")]
+            Match(..) => "match expression",
         }
     }
 
@@ -243,8 +251,8 @@ impl Expr {
 impl Expression for Expr {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
         use Expr::{
-            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Noop, Op, Query,
-            Unary, Variable,
+            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Match, Noop, Op,
+            Query, Unary, Variable,
         };
 
         match self {
@@ -267,13 +275,15 @@ impl Expression for Expr {
             Unary(v) => v.resolve(ctx),
             #[cfg(feature = "expr-abort")]
             Abort(v) => v.resolve(ctx),
+            #[cfg(feature = "expr-match")]
+            Match(v) => v.resolve(ctx),
         }
     }
 
     fn as_value(&self) -> Option<Value> {
         use Expr::{
-            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Noop, Op, Query,
-            Unary, Variable,
+            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Match, Noop, Op,
+            Query, Unary, Variable,
         };
 
         match self {
@@ -296,13 +306,15 @@ impl Expression for Expr {
             Unary(v) => Expression::as_value(v),
             #[cfg(feature = "expr-abort")]
             Abort(v) => Expression::as_value(v),
+            #[cfg(feature = "expr-match")]
+            Match(v) => Expression::as_value(v),
         }
     }
 
     fn type_info(&self, state: &TypeState) -> TypeInfo {
         use Expr::{
-            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Noop, Op, Query,
-            Unary, Variable,
+            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Match, Noop, Op,
+            Query, Unary, Variable,
         };
 
         match self {
@@ -325,6 +337,8 @@ impl Expression for Expr {
             Unary(v) => v.type_info(state),
             #[cfg(feature = "expr-abort")]
             Abort(v) => v.type_info(state),
+            #[cfg(feature = "expr-match")]
+            Match(v) => v.type_info(state),
         }
     }
 }
@@ -332,8 +346,8 @@ impl Expression for Expr {
 impl fmt::Display for Expr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use Expr::{
-            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Noop, Op, Query,
-            Unary, Variable,
+            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Match, Noop, Op,
+            Query, Unary, Variable,
         };
 
         match self {
@@ -356,6 +370,8 @@ impl fmt::Display for Expr {
             Unary(v) => v.fmt(f),
             #[cfg(feature = "expr-abort")]
             Abort(v) => v.fmt(f),
+            #[cfg(feature = "expr-match")]
+            Match(v) => v.fmt(f),
         }
     }
 }
@@ -436,6 +452,13 @@ impl From<Abort> for Expr {
     }
 }
 
+#[cfg(feature = "expr-match")]
+impl From<Match> for Expr {
+    fn from(r#match: Match) -> Self {
+        Expr::Match(r#match)
+    }
+}
+
 #[cfg(feature = "expr-literal")]
 impl From<Value> for Expr {
     fn from(value: Value) -> Self {
@@ -0,0 +1,483 @@
+use std::fmt;
+
+use diagnostic::{DiagnosticMessage, Label, Note, Severity, Urls};
+use value::{
+    kind::{Collection, Field, Index},
+    Value,
+};
+
+use crate::{
+    expression::{Expr, ExpressionError, Predicate, Resolved},
+    parser::ast::Ident,
+    state::{LocalEnv, TypeInfo, TypeState},
+    type_def::Details,
+    value::{Kind, VrlValueConvert},
+    Context, Expression, Span, TypeDef,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Match {
+    pub(crate) expr: Box<Expr>,
+    pub(crate) arms: Vec<MatchArm>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchArm {
+    pub(crate) pattern: Pattern,
+    pub(crate) guard: Option<Predicate>,
+    pub(crate) body: Box<Expr>,
+}
+
+/// A pattern the value of a match expression is tested against.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Pattern {
+    Wildcard,
+    Binding(Ident),
+    Literal(Value),
+    Array(Vec<Pattern>),
+    Object(Vec<(String, Pattern)>),
+}
+
+impl Pattern {
+    /// Tests the value against the pattern, collecting the values of its bindings.
+    fn matches(&self, value: &Value, bindings: &mut Vec<(Ident, Value)>) -> bool {
+        match (self, value) {
+            (Pattern::Wildcard, _) => true,
+            (Pattern::Binding(ident), value) => {
+                bindings.push((ident.clone(), value.clone()));
+                true
+            }
+            (Pattern::Literal(Value::Regex(regex)), Value::Bytes(bytes)) => {
+                regex.is_match(&String::from_utf8_lossy(bytes))
+            }
+            (Pattern::Literal(literal), value) => literal == value,
+            (Pattern::Array(patterns), Value::Array(values)) => {
+                patterns.len() == values.len()
+                    && patterns
+                        .iter()
+                        .zip(values)
+                        .all(|(pattern, value)| pattern.matches(value, bindings))
+            }
+            (Pattern::Object(fields), Value::Object(object)) => {
+                fields.iter().all(|(key, pattern)| {
+                    object
+                        .get(key)
+                        .map_or(false, |value| pattern.matches(value, bindings))
+                })
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns whether any value of the given kind matches the pattern.
+    fn covers(&self, kind: &Kind) -> bool {
+        match self {
+            Pattern::Wildcard | Pattern::Binding(_) => true,
+            Pattern::Literal(Value::Null) => kind.is_null(),
+            Pattern::Literal(_) => false,
+            Pattern::Array(patterns) => kind.as_array().map_or(false, |array| {
+                kind.is_array()
+                    && array.exact_length() == Some(patterns.len())
+                    && patterns
+                        .iter()
+                        .enumerate()
+                        .all(|(index, pattern)| pattern.covers(&element_kind(array, index)))
+            }),
+            Pattern::Object(fields) => kind.as_object().map_or(false, |object| {
+                kind.is_object()
+                    && fields.iter().all(|(key, pattern)| {
+                        let kind = field_kind(object, key);
+                        !kind.contains_undefined() && pattern.covers(&kind)
+                    })
+            }),
+        }
+    }
+
+    /// Collects the bindings of the pattern, along with the kind of the values they are bound
+    /// to when matching a value of the given kind.
+    pub(crate) fn binding_kinds(&self, kind: &Kind, bindings: &mut Vec<(Ident, Kind)>) {
+        match self {
+            Pattern::Wildcard | Pattern::Literal(_) => {}
+            Pattern::Binding(ident) => bindings.push((ident.clone(), kind.clone())),
+            Pattern::Array(patterns) => {
+                for (index, pattern) in patterns.iter().enumerate() {
+                    let kind = kind.as_array().map_or_else(Kind::never, |array| {
+                        element_kind(array, index).without_undefined()
+                    });
+                    pattern.binding_kinds(&kind, bindings);
+                }
+            }
+            Pattern::Object(fields) => {
+                for (key, pattern) in fields {
+                    let kind = kind.as_object().map_or_else(Kind::never, |object| {
+                        field_kind(object, key).without_undefined()
+                    });
+                    pattern.binding_kinds(&kind, bindings);
+                }
+            }
+        }
+    }
+}
+
+fn element_kind(array: &Collection<Index>, index: usize) -> Kind {
+    array
+        .known()
+        .get(&index.into())
+        .cloned()
+        .unwrap_or_else(|| array.unknown_kind())
+}
+
+fn field_kind(object: &Collection<Field>, key: &str) -> Kind {
+    object
+        .known()
+        .get(&key.into())
+        .cloned()
+        .unwrap_or_else(|| object.unknown_kind())
+}
+
+/// Tracks the kind of the values that aren't matched by any of the arms seen so far.
+#[derive(Debug, Clone)]
+pub(crate) struct Coverage {
+    remaining: Kind,
+    matches_true: bool,
+    matches_false: bool,
+}
+
+impl Coverage {
+    pub(crate) fn new(kind: Kind) -> Self {
+        Self {
+            remaining: kind.without_undefined(),
+            matches_true: false,
+            matches_false: false,
+        }
+    }
+
+    /// The kind of the values that aren't matched yet.
+    pub(crate) fn remaining(&self) -> &Kind {
+        &self.remaining
+    }
+
+    pub(crate) fn is_exhaustive(&self) -> bool {
+        self.remaining.is_never()
+    }
+
+    /// Records an arm without a guard, whose pattern matches the values it covers.
+    pub(crate) fn add(&mut self, pattern: &Pattern) {
+        let remaining = &mut self.remaining;
+
+        match pattern {
+            Pattern::Literal(Value::Boolean(true)) => self.matches_true = true,
+            Pattern::Literal(Value::Boolean(false)) => self.matches_false = true,
+            _ => {}
+        }
+        if self.matches_true && self.matches_false {
+            remaining.remove_boolean();
+        }
+
+        if remaining.contains_bytes() && pattern.covers(&Kind::bytes()) {
+            remaining.remove_bytes();
+        }
+        if remaining.contains_integer() && pattern.covers(&Kind::integer()) {
+            remaining.remove_integer();
+        }
+        if remaining.contains_float() && pattern.covers(&Kind::float()) {
+            remaining.remove_float();
+        }
+        if remaining.contains_boolean() && pattern.covers(&Kind::boolean()) {
+            remaining.remove_boolean();
+        }
+        if remaining.contains_timestamp() && pattern.covers(&Kind::timestamp()) {
+            remaining.remove_timestamp();
+        }
+        if remaining.contains_regex() && pattern.covers(&Kind::regex()) {
+            remaining.remove_regex();
+        }
+        if remaining.contains_null() && pattern.covers(&Kind::null()) {
+            remaining.remove_null();
+        }
+        if let Some(array) = remaining.as_array().cloned() {
+            if pattern.covers(&Kind::array(array)) {
+                remaining.remove_array();
+            }
+        }
+        if let Some(object) = remaining.as_object().cloned() {
+            if pattern.covers(&Kind::object(object)) {
+                remaining.remove_object();
+            }
+        }
+    }
+}
+
+/// Adds the bindings of a pattern to the local variables, returning the variables they shadow.
+pub(crate) fn bind(
+    local: &mut LocalEnv,
+    bindings: Vec<(Ident, Kind)>,
+) -> Vec<(Ident, Option<Details>)> {
+    bindings
+        .into_iter()
+        .map(|(ident, kind)| {
+            let previous = local.remove_variable(&ident);
+            let details = Details {
+                type_def: TypeDef::from(kind),
+                value: None,
+            };
+            local.insert_variable(ident.clone(), details);
+            (ident, previous)
+        })
+        .collect()
+}
+
+/// Removes the bindings of a pattern from the local variables, restoring the ones they shadowed.
+pub(crate) fn unbind(local: &mut LocalEnv, shadowed: Vec<(Ident, Option<Details>)>) {
+    for (ident, previous) in shadowed.into_iter().rev() {
+        match previous {
+            Some(details) => local.insert_variable(ident, details),
+            None => {
+                local.remove_variable(&ident);
+            }
+        }
+    }
+}
+
+impl MatchArm {
+    /// Resolves the arm once its pattern matched, returning `None` if the guard rejects the value.
+    fn resolve(&self, ctx: &mut Context) -> Result<Option<Value>, ExpressionError> {
+        if let Some(guard) = &self.guard {
+            if !guard.resolve(ctx)?.try_boolean()? {
+                return Ok(None);
+            }
+        }
+
+        self.body.resolve(ctx).map(Some)
+    }
+}
+
+impl Expression for Match {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.expr.resolve(ctx)?;
+
+        let mut bindings = Vec::new();
+        for arm in &self.arms {
+            bindings.clear();
+            if !arm.pattern.matches(&value, &mut bindings) {
+                continue;
+            }
+
+            let shadowed = bindings
+                .drain(..)
+                .map(|(ident, value)| {
+                    let previous = ctx.state_mut().swap_variable(ident.clone(), value);
+                    (ident, previous)
+                })
+                .collect::<Vec<_>>();
+
+            let result = arm.resolve(ctx);
+
+            for (ident, previous) in shadowed.into_iter().rev() {
+                match previous {
+                    Some(value) => ctx.state_mut().insert_variable(ident, value),
+                    None => ctx.state_mut().remove_variable(&ident),
+                }
+            }
+
+            if let Some(value) = result? {
+                return Ok(value);
+            }
+        }
+
+        Ok(Value::Null)
+    }
+
+    fn type_info(&self, state: &TypeState) -> TypeInfo {
+        let mut state = state.clone();
+        let expr_def = self.expr.apply_type_info(&mut state);
+
+        let mut coverage = Coverage::new(expr_def.kind().clone());
+        let mut result: Option<TypeDef> = None;
+        let mut final_state: Option<TypeState> = None;
+
+        for arm in &self.arms {
+            let mut arm_state = state.clone();
+
+            let mut bindings = vec![];
+            arm.pattern
+                .binding_kinds(coverage.remaining(), &mut bindings);
+            let shadowed = bind(&mut arm_state.local, bindings);
+
+            if let Some(guard) = &arm.guard {
+                guard.apply_type_info(&mut arm_state);
+            }
+            let arm_result = arm.body.apply_type_info(&mut arm_state);
+            unbind(&mut arm_state.local, shadowed);
+
+            result = Some(match result {
+                Some(result) => result.union(arm_result),
+                None => arm_result,
+            });
+            final_state = Some(match final_state {
+                Some(final_state) => final_state.merge(arm_state),
+                None => arm_state,
+            });
+
+            if arm.guard.is_none() {
+                coverage.add(&arm.pattern);
+            }
+        }
+
+        // If no arm matches, "null" is returned, without any other state change.
+        let (final_state, result) = match (final_state, result) {
+            (Some(final_state), Some(result)) if coverage.is_exhaustive() => (final_state, result),
+            (Some(final_state), Some(result)) => (final_state.merge(state), result.or_null()),
+            _ => (state, TypeDef::null()),
+        };
+
+        let result = result.with_fallibility(result.is_fallible() || expr_def.is_fallible());
+
+        TypeInfo::new(final_state, result)
+    }
+}
+
+impl fmt::Display for Match {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "match {} {{ ", self.expr)?;
+
+        let mut iter = self.arms.iter().peekable();
+        while let Some(arm) = iter.next() {
+            arm.fmt(f)?;
+
+            if iter.peek().is_some() {
+                f.write_str(", ")?;
+            }
+        }
+
+        f.write_str(" }")
+    }
+}
+
+impl fmt::Display for MatchArm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.pattern.fmt(f)?;
+
+        if let Some(guard) = &self.guard {
+            write!(f, " if {}", guard)?;
+        }
+
+        write!(f, " => {}", self.body)
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Pattern::Wildcard => f.write_str("_"),
+            Pattern::Binding(ident) => ident.fmt(f),
+            Pattern::Literal(value) => value.fmt(f),
+            Pattern::Array(patterns) => {
+                let patterns = patterns
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                write!(f, "[{}]", patterns)
+            }
+            Pattern::Object(fields) => {
+                let fields = fields
+                    .iter()
+                    .map(|(key, pattern)| format!(r#""{}": {}"#, key, pattern))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                write!(f, "{{ {} }}", fields)
+            }
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+#[derive(thiserror::Error, Debug)]
+pub(crate) enum Error {
+    #[error("non-literal match pattern")]
+    NonLiteralPattern { span: Span },
+
+    #[error("duplicate match binding")]
+    DuplicateBinding { ident: Ident, span: Span },
+
+    #[error("non-exhaustive match")]
+    NonExhaustive { kind: Kind, span: Span },
+
+    #[error("unreachable match arm")]
+    UnreachableArm { span: Span },
+}
+
+impl DiagnosticMessage for Error {
+    fn code(&self) -> usize {
+        use Error::{DuplicateBinding, NonExhaustive, NonLiteralPattern, UnreachableArm};
+
+        match self {
+            NonLiteralPattern { .. } => 670,
+            DuplicateBinding { .. } => 671,
+            NonExhaustive { .. } => 672,
+            UnreachableArm { .. } => 673,
+        }
+    }
+
+    fn labels(&self) -> Vec<Label> {
+        use Error::{DuplicateBinding, NonExhaustive, NonLiteralPattern, UnreachableArm};
+
+        match self {
+            NonLiteralPattern { span } => vec![
+                Label::primary("this pattern must be a literal", span),
+                Label::context("templated strings can't be matched against", span),
+            ],
+            DuplicateBinding { ident, span } => vec![
+                Label::primary(
+                    format!("variable `{}` is bound more than once", ident),
+                    span,
+                ),
+                Label::context("use a different name for each binding", span),
+            ],
+            NonExhaustive { kind, span } => vec![
+                Label::primary("this match doesn't handle all possible values", span),
+                Label::context(
+                    format!("values of type {} aren't matched, resulting in null", kind),
+                    span,
+                ),
+            ],
+            UnreachableArm { span } => vec![
+                Label::primary("this arm is never reached", span),
+                Label::context("the previous arms already match all possible values", span),
+            ],
+        }
+    }
+
+    fn notes(&self) -> Vec<Note> {
+        use Error::{DuplicateBinding, NonExhaustive, NonLiteralPattern, UnreachableArm};
+
+        match self {
+            NonLiteralPattern { .. } | DuplicateBinding { .. } | UnreachableArm { .. } => {
+                vec![Note::SeeDocs(
+                    "match expressions".to_owned(),
+                    Urls::expression_docs_url("#match"),
+                )]
+            }
+            NonExhaustive { .. } => vec![
+                Note::Hint("add a `_` arm to handle the remaining values".to_owned()),
+                Note::SeeDocs(
+                    "match expressions".to_owned(),
+                    Urls::expression_docs_url("#match"),
+                ),
+            ],
+        }
+    }
+
+    fn severity(&self) -> Severity {
+        use Error::{DuplicateBinding, NonExhaustive, NonLiteralPattern, UnreachableArm};
+
+        match self {
+            NonLiteralPattern { .. } | DuplicateBinding { .. } => Severity::Error,
+            NonExhaustive { .. } | UnreachableArm { .. } => Severity::Warning,
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use lookup::OwnedTargetPath;
 
-use crate::state::TypeState;
+use crate::state::{TypeInfo, TypeState};
 use crate::{
     expression::{Block, Resolved},
     Context, Expression,
@@ -27,6 +27,13 @@ impl Program {
         self.expressions.type_info(&self.initial_state).state
     }
 
+    /// Retrieves the type of the value the program resolves to, along with the state of the type
+    /// system after the program runs.
+    #[must_use]
+    pub fn final_type_info(&self) -> TypeInfo {
+        self.expressions.type_info(&self.initial_state)
+    }
+
     /// Get detailed information about the program, as collected by the VRL
     /// compiler.
     #[must_use]
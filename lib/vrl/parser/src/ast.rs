@@ -233,13 +233,14 @@ pub enum Expr {
     Variable(Node<Ident>),
     Unary(Node<Unary>),
     Abort(Node<Abort>),
+    Match(Node<Match>),
 }
 
 impl fmt::Debug for Expr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use Expr::{
-            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Op, Query, Unary,
-            Variable,
+            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Match, Op, Query,
+            Unary, Variable,
         };
 
         let value = match self {
@@ -253,6 +254,7 @@ impl fmt::Debug for Expr {
             Variable(v) => format!("{:?}", v),
             Unary(v) => format!("{:?}", v),
             Abort(v) => format!("{:?}", v),
+            Match(v) => format!("{:?}", v),
         };
 
         write!(f, "Expr({})", value)
@@ -262,8 +264,8 @@ impl fmt::Debug for Expr {
 impl fmt::Display for Expr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use Expr::{
-            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Op, Query, Unary,
-            Variable,
+            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Match, Op, Query,
+            Unary, Variable,
         };
 
         match self {
@@ -277,6 +279,7 @@ impl fmt::Display for Expr {
             Variable(v) => v.fmt(f),
             Unary(v) => v.fmt(f),
             Abort(v) => v.fmt(f),
+            Match(v) => v.fmt(f),
         }
     }
 }
@@ -663,6 +666,125 @@ impl fmt::Debug for Predicate {
     }
 }
 
+// -----------------------------------------------------------------------------
+// match
+// -----------------------------------------------------------------------------
+
+#[derive(Clone, PartialEq)]
+pub struct Match {
+    pub expr: Box<Node<Expr>>,
+    pub arms: Vec<Node<MatchArm>>,
+}
+
+impl fmt::Display for Match {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "match {} {{ ", self.expr)?;
+
+        let mut iter = self.arms.iter().peekable();
+        while let Some(arm) = iter.next() {
+            arm.fmt(f)?;
+
+            if iter.peek().is_some() {
+                f.write_str(", ")?;
+            }
+        }
+
+        f.write_str(" }")
+    }
+}
+
+impl fmt::Debug for Match {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Match({:?}, {:?})", self.expr, self.arms)
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct MatchArm {
+    pub pattern: Node<MatchPattern>,
+    pub guard: Option<Node<Predicate>>,
+    pub body: Box<Node<Expr>>,
+}
+
+impl fmt::Display for MatchArm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.pattern.fmt(f)?;
+
+        if let Some(guard) = &self.guard {
+            write!(f, " if {}", guard)?;
+        }
+
+        write!(f, " => {}", self.body)
+    }
+}
+
+impl fmt::Debug for MatchArm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.guard {
+            Some(guard) => write!(
+                f,
+                "MatchArm({:?} if {:?} => {:?})",
+                self.pattern, guard, self.body
+            ),
+            None => write!(f, "MatchArm({:?} => {:?})", self.pattern, self.body),
+        }
+    }
+}
+
+/// A pattern a value is matched against, binding parts of it to variables.
+#[derive(Clone, PartialEq)]
+pub enum MatchPattern {
+    /// Matches any value, without binding it.
+    Wildcard,
+
+    /// Matches any value, binding it to a variable.
+    Binding(Ident),
+
+    /// Matches a value equal to the literal, or a string matching a regex literal.
+    Literal(Node<Literal>),
+
+    /// Matches an array of the same length, with each element matching its pattern.
+    Array(Vec<Node<MatchPattern>>),
+
+    /// Matches an object having all of the given fields, with each of them matching its
+    /// pattern. Other fields are ignored.
+    Object(Vec<(Node<String>, Node<MatchPattern>)>),
+}
+
+impl fmt::Display for MatchPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MatchPattern::Wildcard => f.write_str("_"),
+            MatchPattern::Binding(ident) => ident.fmt(f),
+            MatchPattern::Literal(literal) => literal.fmt(f),
+            MatchPattern::Array(patterns) => {
+                let patterns = patterns
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                write!(f, "[{}]", patterns)
+            }
+            MatchPattern::Object(fields) => {
+                let fields = fields
+                    .iter()
+                    .map(|(k, v)| format!(r#""{}": {}"#, k, v))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                write!(f, "{{ {} }}", fields)
+            }
+        }
+    }
+}
+
+impl fmt::Debug for MatchPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MatchPattern({})", self)
+    }
+}
+
 // -----------------------------------------------------------------------------
 // operation
 // -----------------------------------------------------------------------------
@@ -371,6 +371,7 @@ pub enum Token<S> {
     False,
     True,
     Abort,
+    Match,
 
     // tokens
     Colon,
@@ -387,6 +388,7 @@ pub enum Token<S> {
     Underscore,
     Escape,
     Arrow,
+    FatArrow,
     Ampersand,
     Percent,
 
@@ -433,10 +435,11 @@ impl<S> Token<S> {
     pub(crate) fn map<R>(self, f: impl Fn(S) -> R) -> Token<R> {
         use self::Token::{
             Abort, Ampersand, Arrow, Bang, Colon, Comma, Dot, Else, Equals, Escape, False,
-            FloatLiteral, FunctionCall, Identifier, If, IntegerLiteral, InvalidToken, LBrace,
-            LBracket, LParen, LQuery, MergeEquals, Newline, Null, Operator, PathField, Percent,
-            Question, RBrace, RBracket, RParen, RQuery, RawStringLiteral, RegexLiteral,
-            ReservedIdentifier, SemiColon, StringLiteral, TimestampLiteral, True, Underscore,
+            FatArrow, FloatLiteral, FunctionCall, Identifier, If, IntegerLiteral, InvalidToken,
+            LBrace, LBracket, LParen, LQuery, Match, MergeEquals, Newline, Null, Operator,
+            PathField, Percent, Question, RBrace, RBracket, RParen, RQuery, RawStringLiteral,
+            RegexLiteral, ReservedIdentifier, SemiColon, StringLiteral, TimestampLiteral, True,
+            Underscore,
         };
 
         match self {
@@ -465,6 +468,7 @@ impl<S> Token<S> {
             Null => Null,
             True => True,
             Abort => Abort,
+            Match => Match,
 
             // tokens
             Colon => Colon,
@@ -481,6 +485,7 @@ impl<S> Token<S> {
             Underscore => Underscore,
             Escape => Escape,
             Arrow => Arrow,
+            FatArrow => FatArrow,
             Ampersand => Ampersand,
             Percent => Percent,
 
@@ -502,10 +507,11 @@ where
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         use self::Token::{
             Abort, Ampersand, Arrow, Bang, Colon, Comma, Dot, Else, Equals, Escape, False,
-            FloatLiteral, FunctionCall, Identifier, If, IntegerLiteral, InvalidToken, LBrace,
-            LBracket, LParen, LQuery, MergeEquals, Newline, Null, Operator, PathField, Percent,
-            Question, RBrace, RBracket, RParen, RQuery, RawStringLiteral, RegexLiteral,
-            ReservedIdentifier, SemiColon, StringLiteral, TimestampLiteral, True, Underscore,
+            FatArrow, FloatLiteral, FunctionCall, Identifier, If, IntegerLiteral, InvalidToken,
+            LBrace, LBracket, LParen, LQuery, Match, MergeEquals, Newline, Null, Operator,
+            PathField, Percent, Question, RBrace, RBracket, RParen, RQuery, RawStringLiteral,
+            RegexLiteral, ReservedIdentifier, SemiColon, StringLiteral, TimestampLiteral, True,
+            Underscore,
         };
 
         let s = match *self {
@@ -528,6 +534,7 @@ where
             Null => "Null",
             True => "True",
             Abort => "Abort",
+            Match => "Match",
 
             // tokens
             Colon => "Colon",
@@ -544,6 +551,7 @@ where
             Underscore => "Underscore",
             Escape => "Escape",
             Arrow => "Arrow",
+            FatArrow => "FatArrow",
             Ampersand => "Ampersand",
             Percent => "Percent",
 
@@ -564,7 +572,7 @@ impl<'input> Token<&'input str> {
     /// Returns either a literal, reserved, or generic identifier.
     fn ident(s: &'input str) -> Self {
         use Token::{
-            Abort, Else, False, Identifier, If, Null, PathField, ReservedIdentifier, True,
+            Abort, Else, False, Identifier, If, Match, Null, PathField, ReservedIdentifier, True,
         };
 
         match s {
@@ -574,6 +582,7 @@ impl<'input> Token<&'input str> {
             "false" => False,
             "null" => Null,
             "abort" => Abort,
+            "match" => Match,
 
             // reserved identifiers
             "array" | "bool" | "boolean" | "break" | "continue" | "do" | "emit" | "float"
@@ -1126,6 +1135,10 @@ impl<'input> Lexer<'input> {
 
         let token = if self.test_peek(|ch| ch == '(' || ch == '!') {
             Token::FunctionCall(ident)
+        } else if ident == "match" && !self.match_subject_follows(end) {
+            // `match` is only a keyword when it starts a match expression, so that it can still be
+            // used as a variable name.
+            Token::Identifier(ident)
         } else {
             Token::ident(ident)
         };
@@ -1133,12 +1146,37 @@ impl<'input> Lexer<'input> {
         (start, token, end)
     }
 
+    /// Returns whether the input starting at `end` is the subject of a match expression.
+    ///
+    /// The subject has to be on the same line as the `match` keyword, separated by whitespace, and
+    /// can't start with an operator or a brace, so that `match = 1`, `match == 1` or `if match {`
+    /// still refer to a variable.
+    fn match_subject_follows(&self, end: usize) -> bool {
+        let rest = &self.input[end..];
+        let subject = rest.trim_start_matches(|ch| ch == ' ' || ch == '\t');
+        if subject.len() == rest.len() {
+            return false;
+        }
+
+        let mut chars = subject.chars();
+        match chars.next() {
+            Some('!') => chars.next() != Some('='),
+            Some(ch) => {
+                matches!(ch, '.' | '%' | '(' | '[' | '"' | '\'')
+                    || is_digit(ch)
+                    || is_ident_start(ch)
+            }
+            None => false,
+        }
+    }
+
     fn operator(&mut self, start: usize) -> Spanned<'input, usize> {
         let (end, op) = self.take_while(start, is_operator);
 
         let token = match op {
             "=" => Token::Equals,
             "|=" => Token::MergeEquals,
+            "=>" => Token::FatArrow,
             "?" => Token::Question,
             op => Token::Operator(op),
         };
@@ -1328,10 +1366,10 @@ mod test {
 
     use super::*;
     use crate::lex::Token::{
-        Arrow, Bang, Colon, Comma, Dot, Else, Equals, FloatLiteral, FunctionCall, Identifier, If,
-        IntegerLiteral, LBrace, LBracket, LParen, LQuery, Newline, Operator, PathField, Percent,
-        RBrace, RBracket, RParen, RQuery, RawStringLiteral, RegexLiteral, StringLiteral,
-        TimestampLiteral, True,
+        Arrow, Bang, Colon, Comma, Dot, Else, Equals, FatArrow, FloatLiteral, FunctionCall,
+        Identifier, If, IntegerLiteral, LBrace, LBracket, LParen, LQuery, Match, Newline, Operator,
+        PathField, Percent, RBrace, RBracket, RParen, RQuery, RawStringLiteral, RegexLiteral,
+        SemiColon, StringLiteral, TimestampLiteral, True, Underscore,
     };
 
     fn lexer(input: &str) -> impl Iterator<Item = SpannedResult<'_, usize>> + '_ {
@@ -2183,6 +2221,58 @@ mod test {
         );
     }
 
+    #[test]
+    fn match_variable() {
+        test(
+            data("match = 1; match == 1 || match"),
+            vec![
+                ("~~~~~                         ", Identifier("match")),
+                ("      ~                       ", Equals),
+                ("        ~                     ", IntegerLiteral(1)),
+                ("         ~                    ", SemiColon),
+                ("           ~~~~~              ", Identifier("match")),
+                ("                 ~~           ", Operator("==")),
+                ("                    ~         ", IntegerLiteral(1)),
+                ("                      ~~      ", Operator("||")),
+                ("                         ~~~~~", Identifier("match")),
+            ],
+        );
+    }
+
+    #[test]
+    fn match_expression() {
+        test(
+            data("match x { 1 => true, _ => match(x, r'') }"),
+            vec![
+                ("~~~~~                                    ", Match),
+                ("      ~                                  ", Identifier("x")),
+                ("        ~                                ", LBrace),
+                (
+                    "          ~                              ",
+                    IntegerLiteral(1),
+                ),
+                ("            ~~                           ", FatArrow),
+                ("               ~~~~                      ", True),
+                ("                   ~                     ", Comma),
+                ("                     ~                   ", Underscore),
+                ("                       ~~                ", FatArrow),
+                (
+                    "                          ~~~~~          ",
+                    FunctionCall("match"),
+                ),
+                ("                               ~         ", LParen),
+                ("                                ~        ", Identifier("x")),
+                ("                                 ~       ", Comma),
+                (
+                    "                                   ~~~   ",
+                    RegexLiteral(""),
+                ),
+                ("                                      ~  ", RParen),
+                ("                                        ~", RBrace),
+            ],
+        );
+    }
+
     #[test]
     fn function_closure_no_arg() {
         test(
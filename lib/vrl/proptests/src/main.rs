@@ -21,6 +21,7 @@ static RESERVED: &[&str] = &[
     "false",
     "null",
     "abort",
+    "match",
     "array",
     "bool",
     "boolean",
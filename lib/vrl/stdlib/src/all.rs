@@ -0,0 +1,124 @@
+use ::value::{kind::Collection, Value};
+use vrl::prelude::*;
+
+fn all<T>(value: Value, ctx: &mut Context, runner: closure::Runner<T>) -> Resolved
+where
+    T: Fn(&mut Context) -> Resolved,
+{
+    for item in value.into_iter(false) {
+        let matched = match item {
+            IterItem::KeyValue(key, value) => runner.run_key_value(ctx, key, value)?,
+            IterItem::IndexValue(index, value) => runner.run_index_value(ctx, index, value)?,
+            IterItem::Value(_) => continue,
+        };
+
+        // Stop iterating as soon as one of the elements doesn't match.
+        if !matched
+            .as_boolean()
+            .expect("compiler guarantees boolean return type")
+        {
+            return Ok(false.into());
+        }
+    }
+
+    Ok(true.into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct All;
+
+impl Function for All {
+    fn identifier(&self) -> &'static str {
+        "all"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::OBJECT | kind::ARRAY,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "all object elements",
+                source: r#"all({ "a": 1, "b": 2 }) -> |_key, value| { value > 1 }"#,
+                result: Ok("false"),
+            },
+            Example {
+                title: "all array elements",
+                source: r#"all([1, 2]) -> |_index, value| { value > 0 }"#,
+                result: Ok("true"),
+            },
+            Example {
+                title: "all array of objects",
+                source: r#"all([{ "a": 3 }, { "a": 2 }, { "a": 4 }]) -> |_index, value| { value.a > 2 }"#,
+                result: Ok("false"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let closure = arguments.required_closure()?;
+
+        Ok(AllFn { value, closure }.as_expr())
+    }
+
+    fn closure(&self) -> Option<closure::Definition> {
+        use closure::{Definition, Input, Output, Variable, VariableKind};
+
+        Some(Definition {
+            inputs: vec![Input {
+                parameter_keyword: "value",
+                kind: Kind::object(Collection::any()).or_array(Collection::any()),
+                variables: vec![
+                    Variable {
+                        kind: VariableKind::TargetInnerKey,
+                    },
+                    Variable {
+                        kind: VariableKind::TargetInnerValue,
+                    },
+                ],
+                output: Output::Kind(Kind::boolean()),
+                example: Example {
+                    title: "all array elements",
+                    source: r#"all([1, 2]) -> |index, _value| { index < 2 }"#,
+                    result: Ok("true"),
+                },
+            }],
+            is_iterator: true,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct AllFn {
+    value: Box<dyn Expression>,
+    closure: FunctionClosure,
+}
+
+impl FunctionExpression for AllFn {
+    fn resolve(&self, ctx: &mut Context) -> Result<Value> {
+        let value = self.value.resolve(ctx)?;
+        let FunctionClosure {
+            variables,
+            block,
+            block_type_def: _,
+        } = &self.closure;
+        let runner = closure::Runner::new(variables, |ctx| block.resolve(ctx));
+
+        all(value, ctx, runner)
+    }
+
+    fn type_def(&self, _ctx: &state::TypeState) -> TypeDef {
+        TypeDef::boolean()
+    }
+}
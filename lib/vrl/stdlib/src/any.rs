@@ -0,0 +1,124 @@
+use ::value::{kind::Collection, Value};
+use vrl::prelude::*;
+
+fn any<T>(value: Value, ctx: &mut Context, runner: closure::Runner<T>) -> Resolved
+where
+    T: Fn(&mut Context) -> Resolved,
+{
+    for item in value.into_iter(false) {
+        let matched = match item {
+            IterItem::KeyValue(key, value) => runner.run_key_value(ctx, key, value)?,
+            IterItem::IndexValue(index, value) => runner.run_index_value(ctx, index, value)?,
+            IterItem::Value(_) => continue,
+        };
+
+        // Stop iterating as soon as one of the elements matches.
+        if matched
+            .as_boolean()
+            .expect("compiler guarantees boolean return type")
+        {
+            return Ok(true.into());
+        }
+    }
+
+    Ok(false.into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Any;
+
+impl Function for Any {
+    fn identifier(&self) -> &'static str {
+        "any"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::OBJECT | kind::ARRAY,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "any object element",
+                source: r#"any({ "a": 1, "b": 2 }) -> |_key, value| { value > 1 }"#,
+                result: Ok("true"),
+            },
+            Example {
+                title: "any array element",
+                source: r#"any([1, 2]) -> |_index, value| { value > 2 }"#,
+                result: Ok("false"),
+            },
+            Example {
+                title: "any array of objects",
+                source: r#"any([{ "a": 3 }, { "a": 2 }, { "a": 4 }]) -> |_index, value| { value.a == 2 }"#,
+                result: Ok("true"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let closure = arguments.required_closure()?;
+
+        Ok(AnyFn { value, closure }.as_expr())
+    }
+
+    fn closure(&self) -> Option<closure::Definition> {
+        use closure::{Definition, Input, Output, Variable, VariableKind};
+
+        Some(Definition {
+            inputs: vec![Input {
+                parameter_keyword: "value",
+                kind: Kind::object(Collection::any()).or_array(Collection::any()),
+                variables: vec![
+                    Variable {
+                        kind: VariableKind::TargetInnerKey,
+                    },
+                    Variable {
+                        kind: VariableKind::TargetInnerValue,
+                    },
+                ],
+                output: Output::Kind(Kind::boolean()),
+                example: Example {
+                    title: "any array element",
+                    source: r#"any([1, 2]) -> |index, _value| { index == 1 }"#,
+                    result: Ok("true"),
+                },
+            }],
+            is_iterator: true,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct AnyFn {
+    value: Box<dyn Expression>,
+    closure: FunctionClosure,
+}
+
+impl FunctionExpression for AnyFn {
+    fn resolve(&self, ctx: &mut Context) -> Result<Value> {
+        let value = self.value.resolve(ctx)?;
+        let FunctionClosure {
+            variables,
+            block,
+            block_type_def: _,
+        } = &self.closure;
+        let runner = closure::Runner::new(variables, |ctx| block.resolve(ctx));
+
+        any(value, ctx, runner)
+    }
+
+    fn type_def(&self, _ctx: &state::TypeState) -> TypeDef {
+        TypeDef::boolean()
+    }
+}
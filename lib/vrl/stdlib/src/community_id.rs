@@ -0,0 +1,344 @@
+use std::net::IpAddr;
+
+use ::sha1::{Digest, Sha1};
+use ::value::Value;
+use vrl::prelude::*;
+
+const ICMP: u8 = 1;
+const TCP: u8 = 6;
+const UDP: u8 = 17;
+const ICMP6: u8 = 58;
+const SCTP: u8 = 132;
+
+/// Used to keep Clippy's `too_many_argument` check happy.
+#[derive(Debug)]
+struct Flow {
+    source_ip: Value,
+    destination_ip: Value,
+    protocol: Value,
+    source_port: Option<Value>,
+    destination_port: Option<Value>,
+    seed: Option<Value>,
+}
+
+fn community_id(flow: Flow) -> Resolved {
+    let source_ip = parse_ip(flow.source_ip)?;
+    let destination_ip = parse_ip(flow.destination_ip)?;
+    let protocol = to_int::<u8>(flow.protocol, "protocol")?;
+    let seed = flow
+        .seed
+        .map(|seed| to_int::<u16>(seed, "seed"))
+        .transpose()?
+        .unwrap_or_default();
+
+    let (source_ip, destination_ip) = match (source_ip, destination_ip) {
+        (IpAddr::V4(source), IpAddr::V4(destination)) => {
+            (source.octets().to_vec(), destination.octets().to_vec())
+        }
+        (IpAddr::V6(source), IpAddr::V6(destination)) => {
+            (source.octets().to_vec(), destination.octets().to_vec())
+        }
+        _ => {
+            return Err("source_ip and destination_ip must be of the same IP address family".into())
+        }
+    };
+
+    let ports = match (protocol, flow.source_port, flow.destination_port) {
+        (ICMP | TCP | UDP | ICMP6 | SCTP, Some(source), Some(destination)) => Some((
+            to_int::<u16>(source, "source_port")?,
+            to_int::<u16>(destination, "destination_port")?,
+        )),
+        (ICMP | TCP | UDP | ICMP6 | SCTP, _, _) => {
+            return Err(format!(
+                "source_port and destination_port are required for protocol {}",
+                protocol
+            )
+            .into())
+        }
+        // The ports of other protocols aren't part of the flow.
+        _ => None,
+    };
+
+    Ok(hash(seed, source_ip, destination_ip, protocol, ports).into())
+}
+
+/// Hashes a flow, as specified by version 1 of the Community ID.
+///
+/// The ICMP types and codes are used as the ports of the flow, where the type of the reply to a
+/// request, or the other way around, replaces the code so that both directions of the flow hash
+/// the same.
+fn hash(
+    seed: u16,
+    mut source_ip: Vec<u8>,
+    mut destination_ip: Vec<u8>,
+    protocol: u8,
+    mut ports: Option<(u16, u16)>,
+) -> String {
+    let mut one_way = false;
+    if let Some((icmp_type, _)) = ports.filter(|_| matches!(protocol, ICMP | ICMP6)) {
+        match icmp_counterpart(protocol, icmp_type) {
+            Some(counterpart) => ports = Some((icmp_type, counterpart)),
+            None => one_way = true,
+        }
+    }
+
+    // Flows are hashed from their lowest endpoint, unless they are one-way.
+    let (source_port, destination_port) = ports.unwrap_or_default();
+    let ordered = source_ip < destination_ip
+        || (source_ip == destination_ip && source_port < destination_port);
+    if !one_way && !ordered {
+        std::mem::swap(&mut source_ip, &mut destination_ip);
+        ports = ports.map(|(source, destination)| (destination, source));
+    }
+
+    let mut hasher = Sha1::new();
+    hasher.update(seed.to_be_bytes());
+    hasher.update(&source_ip);
+    hasher.update(&destination_ip);
+    hasher.update([protocol, 0]);
+    if let Some((source_port, destination_port)) = ports {
+        hasher.update(source_port.to_be_bytes());
+        hasher.update(destination_port.to_be_bytes());
+    }
+
+    format!("1:{}", base64::encode(hasher.finalize()))
+}
+
+/// Returns the ICMP type of the reply to a request type, or of the request of a reply type.
+const fn icmp_counterpart(protocol: u8, icmp_type: u16) -> Option<u16> {
+    let counterpart = match (protocol, icmp_type) {
+        // Echo, timestamp, information, router solicitation and address mask.
+        (ICMP, 8) => 0,
+        (ICMP, 0) => 8,
+        (ICMP, 13) => 14,
+        (ICMP, 14) => 13,
+        (ICMP, 15) => 16,
+        (ICMP, 16) => 15,
+        (ICMP, 10) => 9,
+        (ICMP, 9) => 10,
+        (ICMP, 17) => 18,
+        (ICMP, 18) => 17,
+        // Echo, router solicitation, neighbor solicitation, multicast listener, who are you and
+        // home agent address discovery.
+        (ICMP6, 128) => 129,
+        (ICMP6, 129) => 128,
+        (ICMP6, 133) => 134,
+        (ICMP6, 134) => 133,
+        (ICMP6, 135) => 136,
+        (ICMP6, 136) => 135,
+        (ICMP6, 130) => 131,
+        (ICMP6, 131) => 130,
+        (ICMP6, 139) => 140,
+        (ICMP6, 140) => 139,
+        (ICMP6, 144) => 145,
+        (ICMP6, 145) => 144,
+        _ => return None,
+    };
+
+    Some(counterpart)
+}
+
+fn parse_ip(value: Value) -> std::result::Result<IpAddr, ExpressionError> {
+    value
+        .try_bytes_utf8_lossy()?
+        .parse()
+        .map_err(|err| format!("unable to parse IP address: {}", err).into())
+}
+
+fn to_int<T: TryFrom<i64>>(value: Value, name: &str) -> std::result::Result<T, ExpressionError> {
+    let value = value.try_integer()?;
+    T::try_from(value).map_err(|_| format!("{} is out of range: {}", name, value).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct CommunityId;
+
+impl Function for CommunityId {
+    fn identifier(&self) -> &'static str {
+        "community_id"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "source_ip",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "destination_ip",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "protocol",
+                kind: kind::INTEGER,
+                required: true,
+            },
+            Parameter {
+                keyword: "source_port",
+                kind: kind::INTEGER,
+                required: false,
+            },
+            Parameter {
+                keyword: "destination_port",
+                kind: kind::INTEGER,
+                required: false,
+            },
+            Parameter {
+                keyword: "seed",
+                kind: kind::INTEGER,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "TCP",
+                source: r#"community_id!(source_ip: "1.2.3.4", destination_ip: "5.6.7.8", source_port: 1122, destination_port: 3344, protocol: 6)"#,
+                result: Ok("1:wCb3OG7yAFWelaUydu0D+125CLM="),
+            },
+            Example {
+                title: "ICMP",
+                source: r#"community_id!(source_ip: "192.168.0.89", destination_ip: "192.168.0.1", source_port: 8, destination_port: 0, protocol: 1)"#,
+                result: Ok("1:X0snYXpgwiv9TZtqg64sgzUn6Dk="),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let source_ip = arguments.required("source_ip");
+        let destination_ip = arguments.required("destination_ip");
+        let protocol = arguments.required("protocol");
+        let source_port = arguments.optional("source_port");
+        let destination_port = arguments.optional("destination_port");
+        let seed = arguments.optional("seed");
+
+        Ok(CommunityIdFn {
+            source_ip,
+            destination_ip,
+            protocol,
+            source_port,
+            destination_port,
+            seed,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CommunityIdFn {
+    source_ip: Box<dyn Expression>,
+    destination_ip: Box<dyn Expression>,
+    protocol: Box<dyn Expression>,
+    source_port: Option<Box<dyn Expression>>,
+    destination_port: Option<Box<dyn Expression>>,
+    seed: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for CommunityIdFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let flow = Flow {
+            source_ip: self.source_ip.resolve(ctx)?,
+            destination_ip: self.destination_ip.resolve(ctx)?,
+            protocol: self.protocol.resolve(ctx)?,
+            source_port: self
+                .source_port
+                .as_ref()
+                .map(|expr| expr.resolve(ctx))
+                .transpose()?,
+            destination_port: self
+                .destination_port
+                .as_ref()
+                .map(|expr| expr.resolve(ctx))
+                .transpose()?,
+            seed: self
+                .seed
+                .as_ref()
+                .map(|expr| expr.resolve(ctx))
+                .transpose()?,
+        };
+
+        community_id(flow)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        community_id => CommunityId;
+
+        tcp {
+            args: func_args![source_ip: "128.232.110.120", destination_ip: "66.35.250.204", protocol: 6, source_port: 34855, destination_port: 80],
+            want: Ok("1:LQU9qZlK+B5F3KDmev6m5PMibrg="),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        tcp_reverse {
+            args: func_args![source_ip: "66.35.250.204", destination_ip: "128.232.110.120", protocol: 6, source_port: 80, destination_port: 34855],
+            want: Ok("1:LQU9qZlK+B5F3KDmev6m5PMibrg="),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        seed {
+            args: func_args![source_ip: "128.232.110.120", destination_ip: "66.35.250.204", protocol: 6, source_port: 34855, destination_port: 80, seed: 1],
+            want: Ok("1:3V71V58M3Ksw/yuFALMcW0LAHvc="),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        udp {
+            args: func_args![source_ip: "192.168.1.52", destination_ip: "8.8.8.8", protocol: 17, source_port: 54585, destination_port: 53],
+            want: Ok("1:d/FP5EW3wiY1vCndhwleRRKHowQ="),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        icmp_reply {
+            args: func_args![source_ip: "192.168.0.1", destination_ip: "192.168.0.89", protocol: 1, source_port: 0, destination_port: 0],
+            want: Ok("1:X0snYXpgwiv9TZtqg64sgzUn6Dk="),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        icmp_one_way {
+            args: func_args![source_ip: "1.2.3.4", destination_ip: "5.6.7.8", protocol: 1, source_port: 3, destination_port: 1],
+            want: Ok("1:W4aFotGvd33My5d10oV5afM5O+E="),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        icmp6 {
+            args: func_args![source_ip: "fe80::200:86ff:fe05:80da", destination_ip: "fe80::260:97ff:fe07:69ea", protocol: 58, source_port: 135, destination_port: 0],
+            want: Ok("1:dGHyGvjMfljg6Bppwm3bg0LO8TY="),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        missing_ports {
+            args: func_args![source_ip: "1.2.3.4", destination_ip: "5.6.7.8", protocol: 6],
+            want: Err("source_port and destination_port are required for protocol 6"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        mixed_families {
+            args: func_args![source_ip: "1.2.3.4", destination_ip: "::1", protocol: 6, source_port: 1, destination_port: 2],
+            want: Err("source_ip and destination_ip must be of the same IP address family"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        port_out_of_range {
+            args: func_args![source_ip: "1.2.3.4", destination_ip: "5.6.7.8", protocol: 6, source_port: 65536, destination_port: 2],
+            want: Err("source_port is out of range: 65536"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
@@ -0,0 +1,135 @@
+use ::value::Value;
+use rmpv::Value as MsgpackValue;
+use vrl::prelude::*;
+
+fn encode_msgpack(value: Value) -> Resolved {
+    let mut buf = Vec::new();
+    // Writing to a `Vec` can't fail.
+    match rmpv::encode::write_value(&mut buf, &to_msgpack(value)) {
+        Ok(()) => Ok(Bytes::from(buf).into()),
+        Err(error) => unreachable!("unable to encode to msgpack: {}", error),
+    }
+}
+
+fn to_msgpack(value: Value) -> MsgpackValue {
+    match value {
+        Value::Bytes(bytes) => match String::from_utf8(bytes.to_vec()) {
+            Ok(string) => MsgpackValue::from(string),
+            Err(error) => MsgpackValue::Binary(error.into_bytes()),
+        },
+        Value::Regex(regex) => MsgpackValue::from(regex.as_str()),
+        Value::Integer(integer) => MsgpackValue::from(integer),
+        Value::Float(float) => MsgpackValue::F64(float.into_inner()),
+        Value::Boolean(boolean) => MsgpackValue::Boolean(boolean),
+        Value::Timestamp(timestamp) => {
+            MsgpackValue::from(::value::value::timestamp_to_string(&timestamp))
+        }
+        Value::Object(object) => MsgpackValue::Map(
+            object
+                .into_iter()
+                .map(|(key, value)| (MsgpackValue::from(key), to_msgpack(value)))
+                .collect(),
+        ),
+        Value::Array(array) => MsgpackValue::Array(array.into_iter().map(to_msgpack).collect()),
+        Value::Null => MsgpackValue::Nil,
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct EncodeMsgpack;
+
+impl Function for EncodeMsgpack {
+    fn identifier(&self) -> &'static str {
+        "encode_msgpack"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::ANY,
+            required: true,
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(EncodeMsgpackFn { value }.as_expr())
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "encode object",
+            source: r#"encode_base64(encode_msgpack({"foo": "bar"}))"#,
+            result: Ok("gaNmb2+jYmFy"),
+        }]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct EncodeMsgpackFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for EncodeMsgpackFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        encode_msgpack(value)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    test_function![
+        encode_msgpack => EncodeMsgpack;
+
+        string {
+            args: func_args![value: "foo"],
+            want: Ok(Bytes::from_static(b"\xa3foo")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        binary {
+            args: func_args![value: Bytes::from_static(b"\xff\x00")],
+            want: Ok(Bytes::from_static(b"\xc4\x02\xff\x00")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        integer {
+            args: func_args![value: -128],
+            want: Ok(Bytes::from_static(b"\xd0\x80")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        float {
+            args: func_args![value: 1.5],
+            want: Ok(Bytes::from_static(b"\xcb\x3f\xf8\x00\x00\x00\x00\x00\x00")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        timestamp {
+            args: func_args![value: Utc.timestamp(1_500_000_000, 0)],
+            want: Ok(Bytes::from_static(b"\xb42017-07-14T02:40:00Z")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        object {
+            args: func_args![value: value!({ a: [1, true, null] })],
+            want: Ok(Bytes::from_static(b"\x81\xa1a\x93\x01\xc3\xc0")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+    ];
+}
@@ -0,0 +1,422 @@
+use std::{borrow::Cow, fmt::Write};
+
+use ::value::Value;
+use vrl::prelude::*;
+
+/// Used to keep Clippy's `too_many_argument` check happy.
+#[derive(Debug)]
+struct EncodeOptions {
+    attr_prefix: Option<Value>,
+    text_key: Option<Value>,
+    namespaces: Option<Value>,
+    include_declaration: Option<Value>,
+}
+
+fn encode_xml(value: Value, options: EncodeOptions) -> Resolved {
+    let object = value.try_object()?;
+    let attr_prefix = match options.attr_prefix {
+        Some(value) => Cow::from(value.try_bytes_utf8_lossy()?.into_owned()),
+        None => Cow::from("@"),
+    };
+    let text_key = match options.text_key {
+        Some(value) => Cow::from(value.try_bytes_utf8_lossy()?.into_owned()),
+        None => Cow::from("text"),
+    };
+    let namespaces = match options.namespaces {
+        Some(value) => value.try_object()?,
+        None => BTreeMap::new(),
+    };
+    let include_declaration = match options.include_declaration {
+        Some(value) => value.try_boolean()?,
+        None => false,
+    };
+    let config = EncodeXmlConfig {
+        attr_prefix,
+        text_key,
+    };
+
+    let mut root = object.into_iter();
+    let (name, mut value) = match (root.next(), root.next()) {
+        (Some(root), None) => root,
+        _ => return Err("unable to encode xml: object must have exactly one root element".into()),
+    };
+
+    // Namespaces are declared on the root element.
+    if !namespaces.is_empty() {
+        let mut element = match value {
+            Value::Object(object) => object,
+            Value::Null => BTreeMap::new(),
+            value => BTreeMap::from([(config.text_key.to_string(), value)]),
+        };
+        for (prefix, uri) in namespaces {
+            let name = if prefix.is_empty() {
+                format!("{}xmlns", config.attr_prefix)
+            } else {
+                format!("{}xmlns:{}", config.attr_prefix, prefix)
+            };
+            element.insert(name, uri);
+        }
+        value = Value::Object(element);
+    }
+
+    let mut xml = String::new();
+    if include_declaration {
+        xml.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    }
+    write_element(&mut xml, &name, &value, &config)?;
+
+    Ok(xml.into())
+}
+
+struct EncodeXmlConfig<'a> {
+    /// Prefix of the keys encoded as attributes. Default: "@".
+    attr_prefix: Cow<'a, str>,
+    /// Key of the text content of elements with attributes or children. Default: "text".
+    text_key: Cow<'a, str>,
+}
+
+/// Writes an element, or one element per item if the value is an array.
+fn write_element(
+    xml: &mut String,
+    name: &str,
+    value: &Value,
+    config: &EncodeXmlConfig<'_>,
+) -> std::result::Result<(), ExpressionError> {
+    validate_name(name)?;
+
+    match value {
+        Value::Array(array) => {
+            for value in array {
+                write_element(xml, name, value, config)?;
+            }
+        }
+        Value::Object(object) => {
+            write!(xml, "<{}", name).expect("writing to a string never fails");
+
+            let mut content = Vec::new();
+            for (key, value) in object {
+                match key.strip_prefix(&*config.attr_prefix) {
+                    Some(attr) if !config.attr_prefix.is_empty() => {
+                        validate_name(attr)?;
+                        write!(xml, r#" {}="{}""#, attr, escape(&to_text(value)?, true))
+                            .expect("writing to a string never fails");
+                    }
+                    _ => content.push((key, value)),
+                }
+            }
+
+            if content.is_empty() {
+                xml.push_str("/>");
+                return Ok(());
+            }
+
+            xml.push('>');
+            for (key, value) in content {
+                if *key == config.text_key {
+                    write_text(xml, value)?;
+                } else {
+                    write_element(xml, key, value, config)?;
+                }
+            }
+            write!(xml, "</{}>", name).expect("writing to a string never fails");
+        }
+        Value::Null => write!(xml, "<{}/>", name).expect("writing to a string never fails"),
+        value => {
+            write!(xml, "<{}>", name).expect("writing to a string never fails");
+            xml.push_str(&escape(&to_text(value)?, false));
+            write!(xml, "</{}>", name).expect("writing to a string never fails");
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes the text content of an element, where an array holds text split around children.
+fn write_text(xml: &mut String, value: &Value) -> std::result::Result<(), ExpressionError> {
+    match value {
+        Value::Array(array) => {
+            for value in array {
+                xml.push_str(&escape(&to_text(value)?, false));
+            }
+        }
+        value => xml.push_str(&escape(&to_text(value)?, false)),
+    }
+
+    Ok(())
+}
+
+fn to_text(value: &Value) -> std::result::Result<Cow<'_, str>, ExpressionError> {
+    Ok(match value {
+        Value::Bytes(bytes) => String::from_utf8_lossy(bytes),
+        Value::Null => Cow::from(""),
+        Value::Timestamp(timestamp) => Cow::from(::value::value::timestamp_to_string(timestamp)),
+        Value::Regex(regex) => Cow::from(regex.as_str()),
+        Value::Integer(_) | Value::Float(_) | Value::Boolean(_) => Cow::from(value.to_string()),
+        Value::Object(_) | Value::Array(_) => {
+            return Err(format!(
+                "unable to encode xml: {} can't be encoded as text",
+                value.kind_str()
+            )
+            .into())
+        }
+    })
+}
+
+/// Returns an error for names that would produce invalid XML, such as empty names or names
+/// containing whitespace or markup characters.
+fn validate_name(name: &str) -> std::result::Result<(), ExpressionError> {
+    let invalid = name.is_empty()
+        || name.starts_with(|c: char| c.is_ascii_digit() || matches!(c, '-' | '.'))
+        || name.contains(|c: char| {
+            c.is_whitespace() || matches!(c, '<' | '>' | '&' | '"' | '\'' | '/' | '=')
+        });
+
+    if invalid {
+        Err(format!("unable to encode xml: invalid name {:?}", name).into())
+    } else {
+        Ok(())
+    }
+}
+
+fn escape(text: &str, attribute: bool) -> Cow<'_, str> {
+    if !text.contains(|c| matches!(c, '&' | '<' | '>' | '"')) {
+        return Cow::from(text);
+    }
+
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' if attribute => escaped.push_str("&quot;"),
+            c => escaped.push(c),
+        }
+    }
+
+    Cow::from(escaped)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct EncodeXml;
+
+impl Function for EncodeXml {
+    fn identifier(&self) -> &'static str {
+        "encode_xml"
+    }
+
+    fn summary(&self) -> &'static str {
+        "encode an object to XML"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {r#"
+            Encodes the provided object, which must have a single key naming the root element, to
+            XML.
+
+            This is the counterpart of `parse_xml`: keys starting with `attr_prefix` are encoded as
+            attributes, the `text_key` key as the text of the element, and any other key as a
+            child element, repeated for each item of an array. Namespaces can be declared on the
+            root element with `namespaces`, or as `xmlns` attributes.
+        "#}
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::OBJECT,
+                required: true,
+            },
+            Parameter {
+                keyword: "attr_prefix",
+                kind: kind::BYTES,
+                required: false,
+            },
+            Parameter {
+                keyword: "text_key",
+                kind: kind::BYTES,
+                required: false,
+            },
+            Parameter {
+                keyword: "namespaces",
+                kind: kind::OBJECT,
+                required: false,
+            },
+            Parameter {
+                keyword: "include_declaration",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "encode object",
+                source: r#"encode_xml!({"book": {"@category": "CHILDREN", "title": {"@lang": "en", "text": "Harry Potter"}, "year": 2005}})"#,
+                result: Ok(
+                    r#"s'<book category="CHILDREN"><title lang="en">Harry Potter</title><year>2005</year></book>'"#,
+                ),
+            },
+            Example {
+                title: "encode with namespaces",
+                source: r#"encode_xml!({"soap:Envelope": {"soap:Body": {"m:GetPrice": {"m:Item": "Apples"}}}}, namespaces: {"soap": "http://www.w3.org/2003/05/soap-envelope", "m": "urn:prices"})"#,
+                result: Ok(
+                    r#"s'<soap:Envelope xmlns:m="urn:prices" xmlns:soap="http://www.w3.org/2003/05/soap-envelope"><soap:Body><m:GetPrice><m:Item>Apples</m:Item></m:GetPrice></soap:Body></soap:Envelope>'"#,
+                ),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let attr_prefix = arguments.optional("attr_prefix");
+        let text_key = arguments.optional("text_key");
+        let namespaces = arguments.optional("namespaces");
+        let include_declaration = arguments.optional("include_declaration");
+
+        Ok(EncodeXmlFn {
+            value,
+            attr_prefix,
+            text_key,
+            namespaces,
+            include_declaration,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct EncodeXmlFn {
+    value: Box<dyn Expression>,
+
+    attr_prefix: Option<Box<dyn Expression>>,
+    text_key: Option<Box<dyn Expression>>,
+    namespaces: Option<Box<dyn Expression>>,
+    include_declaration: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for EncodeXmlFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        let options = EncodeOptions {
+            attr_prefix: self
+                .attr_prefix
+                .as_ref()
+                .map(|expr| expr.resolve(ctx))
+                .transpose()?,
+
+            text_key: self
+                .text_key
+                .as_ref()
+                .map(|expr| expr.resolve(ctx))
+                .transpose()?,
+
+            namespaces: self
+                .namespaces
+                .as_ref()
+                .map(|expr| expr.resolve(ctx))
+                .transpose()?,
+
+            include_declaration: self
+                .include_declaration
+                .as_ref()
+                .map(|expr| expr.resolve(ctx))
+                .transpose()?,
+        };
+
+        encode_xml(value, options)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        encode_xml => EncodeXml;
+
+        simple_text {
+            args: func_args![value: value!({ "a": "test" })],
+            want: Ok("<a>test</a>"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        attributes_and_text {
+            args: func_args![value: value!({ "a": { "@href": "https://vector.dev", "text": "test" } })],
+            want: Ok(r#"<a href="https://vector.dev">test</a>"#),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        custom_keys {
+            args: func_args![value: value!({ "a": { "_href": "https://vector.dev", "@value": "test" } }), attr_prefix: "_", text_key: "@value"],
+            want: Ok(r#"<a href="https://vector.dev">test</a>"#),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        arrays {
+            args: func_args![value: value!({ "data": { "item": [true, 1, 1.5, null, { "@id": 2 }] } })],
+            want: Ok(r#"<data><item>true</item><item>1</item><item>1.5</item><item/><item id="2"/></data>"#),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        escaping {
+            args: func_args![value: value!({ "a": { "@title": r#"say "<hi>""#, "text": "fish & chips" } })],
+            want: Ok(r#"<a title="say &quot;&lt;hi&gt;&quot;">fish &amp; chips</a>"#),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        namespaces {
+            args: func_args![value: value!({ "Envelope": { "Body": "test" } }), namespaces: value!({ "": "urn:example" }), include_declaration: true],
+            want: Ok(r#"<?xml version="1.0" encoding="UTF-8"?><Envelope xmlns="urn:example"><Body>test</Body></Envelope>"#),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        parse_xml_round_trip {
+            args: func_args![value: value!({
+                "soap:Envelope": {
+                    "@xmlns:soap": "http://www.w3.org/2003/05/soap-envelope",
+                    "soap:Body": {
+                        "m:GetPrice": {
+                            "@xmlns:m": "urn:prices",
+                            "@m:currency": "EUR",
+                            "Item": "Apples"
+                        }
+                    }
+                }
+            })],
+            want: Ok(r#"<soap:Envelope xmlns:soap="http://www.w3.org/2003/05/soap-envelope"><soap:Body><m:GetPrice m:currency="EUR" xmlns:m="urn:prices"><Item>Apples</Item></m:GetPrice></soap:Body></soap:Envelope>"#),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        multiple_roots {
+            args: func_args![value: value!({ "a": 1, "b": 2 })],
+            want: Err("unable to encode xml: object must have exactly one root element"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        invalid_name {
+            args: func_args![value: value!({ "a": { "b c": 1 } })],
+            want: Err(r#"unable to encode xml: invalid name "b c""#),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        nested_array_text {
+            args: func_args![value: value!({ "a": { "@b": [1] } })],
+            want: Err("unable to encode xml: array can't be encoded as text"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
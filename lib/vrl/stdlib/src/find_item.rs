@@ -0,0 +1,137 @@
+use ::value::{kind::Collection, Value};
+use vrl::prelude::*;
+
+fn find_item<T>(value: Value, ctx: &mut Context, runner: closure::Runner<T>) -> Resolved
+where
+    T: Fn(&mut Context) -> Resolved,
+{
+    for item in value.into_iter(false) {
+        let (matched, value) = match item {
+            IterItem::KeyValue(key, value) => (runner.run_key_value(ctx, key, value)?, value),
+            IterItem::IndexValue(index, value) => {
+                (runner.run_index_value(ctx, index, value)?, value)
+            }
+            IterItem::Value(_) => continue,
+        };
+
+        // Stop iterating as soon as one of the elements matches.
+        if matched
+            .as_boolean()
+            .expect("compiler guarantees boolean return type")
+        {
+            return Ok(value.clone());
+        }
+    }
+
+    Ok(Value::Null)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct FindItem;
+
+impl Function for FindItem {
+    fn identifier(&self) -> &'static str {
+        "find_item"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::OBJECT | kind::ARRAY,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "find object element",
+                source: r#"find_item({ "a": 1, "b": 2 }) -> |key, _value| { key == "b" }"#,
+                result: Ok("2"),
+            },
+            Example {
+                title: "find array element",
+                source: r#"find_item([{ "a": 3 }, { "a": 2 }]) -> |_index, value| { value.a == 2 }"#,
+                result: Ok(r#"{ "a": 2 }"#),
+            },
+            Example {
+                title: "no matching element",
+                source: r#"find_item([1, 2]) -> |_index, value| { value > 2 }"#,
+                result: Ok("null"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let closure = arguments.required_closure()?;
+
+        Ok(FindItemFn { value, closure }.as_expr())
+    }
+
+    fn closure(&self) -> Option<closure::Definition> {
+        use closure::{Definition, Input, Output, Variable, VariableKind};
+
+        Some(Definition {
+            inputs: vec![Input {
+                parameter_keyword: "value",
+                kind: Kind::object(Collection::any()).or_array(Collection::any()),
+                variables: vec![
+                    Variable {
+                        kind: VariableKind::TargetInnerKey,
+                    },
+                    Variable {
+                        kind: VariableKind::TargetInnerValue,
+                    },
+                ],
+                output: Output::Kind(Kind::boolean()),
+                example: Example {
+                    title: "find array element",
+                    source: r#"find_item(["a", "b"]) -> |index, _value| { index == 1 }"#,
+                    result: Ok(r#""b""#),
+                },
+            }],
+            is_iterator: true,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct FindItemFn {
+    value: Box<dyn Expression>,
+    closure: FunctionClosure,
+}
+
+impl FunctionExpression for FindItemFn {
+    fn resolve(&self, ctx: &mut Context) -> Result<Value> {
+        let value = self.value.resolve(ctx)?;
+        let FunctionClosure {
+            variables,
+            block,
+            block_type_def: _,
+        } = &self.closure;
+        let runner = closure::Runner::new(variables, |ctx| block.resolve(ctx));
+
+        find_item(value, ctx, runner)
+    }
+
+    fn type_def(&self, ctx: &state::TypeState) -> TypeDef {
+        let type_def = self.value.type_def(ctx);
+
+        // The element is either one of the collection's elements, or `null` if none of them match.
+        let mut kind = Kind::null();
+        if let Some(array) = type_def.kind().as_array() {
+            kind = kind.union(array.reduced_kind());
+        }
+        if let Some(object) = type_def.kind().as_object() {
+            kind = kind.union(object.reduced_kind());
+        }
+
+        TypeDef::from(kind)
+    }
+}
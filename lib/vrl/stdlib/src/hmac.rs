@@ -0,0 +1,161 @@
+use ::hmac::{digest::KeyInit, Hmac as HmacDigest, Mac};
+use ::sha1::Sha1;
+use ::value::Value;
+use sha_2::{Sha224, Sha256, Sha384, Sha512};
+use vrl::prelude::*;
+
+fn hmac(value: Value, key: Value, algorithm: &Bytes) -> Resolved {
+    let value = value.try_bytes()?;
+    let key = key.try_bytes()?;
+    let code = match algorithm.as_ref() {
+        b"SHA1" => sign::<HmacDigest<Sha1>>(&key, &value),
+        b"SHA-224" => sign::<HmacDigest<Sha224>>(&key, &value),
+        b"SHA-256" => sign::<HmacDigest<Sha256>>(&key, &value),
+        b"SHA-384" => sign::<HmacDigest<Sha384>>(&key, &value),
+        b"SHA-512" => sign::<HmacDigest<Sha512>>(&key, &value),
+        _ => unreachable!("enum invariant"),
+    };
+    Ok(Bytes::from(code).into())
+}
+
+#[inline]
+fn sign<T: Mac + KeyInit>(key: &[u8], value: &[u8]) -> Vec<u8> {
+    let mut mac = <T as Mac>::new_from_slice(key).expect("HMAC accepts keys of any size");
+    mac.update(value);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn algorithms() -> Vec<Value> {
+    vec![
+        value!("SHA1"),
+        value!("SHA-224"),
+        value!("SHA-256"),
+        value!("SHA-384"),
+        value!("SHA-512"),
+    ]
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Hmac;
+
+impl Function for Hmac {
+    fn identifier(&self) -> &'static str {
+        "hmac"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "key",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "algorithm",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "default algorithm",
+                source: r#"encode_base64(hmac("Hello there", "super-secret-key"))"#,
+                result: Ok("eLGE8YMviv85NPXgISRUZxstBNSU47JQdcXkUWcClmI="),
+            },
+            Example {
+                title: "custom algorithm",
+                source: r#"encode_base64(hmac("Hello there", "super-secret-key", algorithm: "SHA1"))"#,
+                result: Ok("MiyBIHO8Set9+6crALiwkS0yFPE="),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let key = arguments.required("key");
+        let algorithm = arguments
+            .optional_enum("algorithm", &algorithms())?
+            .unwrap_or_else(|| value!("SHA-256"))
+            .try_bytes()
+            .expect("algorithm not bytes");
+
+        Ok(HmacFn {
+            value,
+            key,
+            algorithm,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct HmacFn {
+    value: Box<dyn Expression>,
+    key: Box<dyn Expression>,
+    algorithm: Bytes,
+}
+
+impl FunctionExpression for HmacFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let key = self.key.resolve(ctx)?;
+
+        hmac(value, key, &self.algorithm)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        hmac => Hmac;
+
+        default_algorithm {
+            args: func_args![value: "Hello there", key: "super-secret-key"],
+            want: Ok(Bytes::from_static(b"\x78\xb1\x84\xf1\x83\x2f\x8a\xff\x39\x34\xf5\xe0\x21\x24\x54\x67\x1b\x2d\x04\xd4\x94\xe3\xb2\x50\x75\xc5\xe4\x51\x67\x02\x96\x62")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        sha1 {
+            args: func_args![value: "Hello there", key: "super-secret-key", algorithm: "SHA1"],
+            want: Ok(Bytes::from_static(b"\x32\x2c\x81\x20\x73\xbc\x49\xeb\x7d\xfb\xa7\x2b\x00\xb8\xb0\x91\x2d\x32\x14\xf1")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        sha224 {
+            args: func_args![value: "Hello there", key: "super-secret-key", algorithm: "SHA-224"],
+            want: Ok(Bytes::from_static(b"\x42\xfc\xcb\xc2\xb7\xd2\x2a\x14\x3b\x92\xf2\x65\xa8\x04\x61\x87\x55\x8a\x94\xd1\x1d\xdb\xb3\x06\x22\x20\x7e\x90")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        sha384 {
+            args: func_args![value: "Hello there", key: "super-secret-key", algorithm: "SHA-384"],
+            want: Ok(Bytes::from_static(b"\xe2\x51\x37\xc4\xd7\xde\xa2\xcc\xb9\x26\x23\x60\xf5\x73\x88\x4d\x5b\x81\x8f\x3d\x0d\xb7\x92\x97\x36\x3f\x66\x42\x94\xf3\x88\xf0\xf9\xb5\x8c\x04\xc1\x1d\x88\x06\xb5\x60\xb8\x0d\xe0\x3f\xed\x0d")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        sha512 {
+            args: func_args![value: "Hello there", key: "super-secret-key", algorithm: "SHA-512"],
+            want: Ok(Bytes::from_static(b"\x20\xc9\x2a\x07\x6b\x22\xf3\x43\x2b\xfe\x91\x8d\xfe\x43\x14\xd0\x24\x3c\x85\x08\x64\x3a\xb1\xd7\xd7\x79\xa5\x65\x84\x81\xce\x2f\xd4\x08\x21\x04\x40\x10\xe9\x78\xc1\x36\x51\x7f\x58\xff\xc8\xe6\xc1\xf2\x58\x30\x73\x88\x58\x30\x3c\xf0\xa7\x10\x73\xc6\x0e\x96")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+    ];
+}
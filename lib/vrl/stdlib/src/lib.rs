@@ -28,6 +28,10 @@
 
 mod util;
 
+#[cfg(feature = "all")]
+mod all;
+#[cfg(feature = "any")]
+mod any;
 #[cfg(feature = "append")]
 mod append;
 #[cfg(feature = "array")]
@@ -42,6 +46,8 @@ mod boolean;
 mod ceil;
 #[cfg(feature = "chunks")]
 mod chunks;
+#[cfg(feature = "community_id")]
+mod community_id;
 #[cfg(feature = "compact")]
 mod compact;
 #[cfg(feature = "contains")]
@@ -64,8 +70,12 @@ mod encode_json;
 mod encode_key_value;
 #[cfg(feature = "encode_logfmt")]
 mod encode_logfmt;
+#[cfg(feature = "encode_msgpack")]
+mod encode_msgpack;
 #[cfg(feature = "encode_percent")]
 mod encode_percent;
+#[cfg(feature = "encode_xml")]
+mod encode_xml;
 #[cfg(feature = "encrypt")]
 mod encrypt;
 #[cfg(feature = "ends_with")]
@@ -76,6 +86,8 @@ mod exists;
 mod filter;
 #[cfg(feature = "find")]
 mod find;
+#[cfg(feature = "find_item")]
+mod find_item;
 #[cfg(feature = "flatten")]
 mod flatten;
 #[cfg(feature = "float")]
@@ -96,6 +108,8 @@ mod get;
 mod get_env_var;
 #[cfg(feature = "get_hostname")]
 mod get_hostname;
+#[cfg(feature = "hmac")]
+mod hmac;
 #[cfg(feature = "includes")]
 mod includes;
 #[cfg(feature = "integer")]
@@ -162,6 +176,8 @@ mod log_util;
 mod map_keys;
 #[cfg(feature = "map_values")]
 mod map_values;
+#[cfg(feature = "map_while")]
+mod map_while;
 #[cfg(feature = "match")]
 mod r#match;
 #[cfg(feature = "match_any")]
@@ -216,6 +232,8 @@ mod parse_klog;
 mod parse_linux_authorization;
 #[cfg(feature = "parse_logfmt")]
 mod parse_logfmt;
+#[cfg(feature = "parse_msgpack")]
+mod parse_msgpack;
 #[cfg(feature = "parse_nginx_log")]
 mod parse_nginx_log;
 #[cfg(feature = "parse_query_string")]
@@ -260,6 +278,8 @@ mod sha1;
 mod sha2;
 #[cfg(feature = "sha3")]
 mod sha3;
+#[cfg(feature = "sign_jwt")]
+mod sign_jwt;
 #[cfg(feature = "slice")]
 mod slice;
 #[cfg(feature = "split")]
@@ -316,9 +336,17 @@ mod upcase;
 mod uuid_v4;
 #[cfg(feature = "values")]
 mod values;
+#[cfg(feature = "verify_jwt")]
+mod verify_jwt;
+#[cfg(feature = "xpath")]
+mod xpath;
 
 // -----------------------------------------------------------------------------
 
+#[cfg(feature = "all")]
+pub use all::All;
+#[cfg(feature = "any")]
+pub use any::Any;
 #[cfg(feature = "append")]
 pub use append::Append;
 #[cfg(feature = "assert")]
@@ -331,6 +359,8 @@ pub use boolean::Boolean;
 pub use ceil::Ceil;
 #[cfg(feature = "chunks")]
 pub use chunks::Chunks;
+#[cfg(feature = "community_id")]
+pub use community_id::CommunityId;
 #[cfg(feature = "compact")]
 pub use compact::Compact;
 #[cfg(feature = "contains")]
@@ -353,8 +383,12 @@ pub use encode_json::EncodeJson;
 pub use encode_key_value::EncodeKeyValue;
 #[cfg(feature = "encode_logfmt")]
 pub use encode_logfmt::EncodeLogfmt;
+#[cfg(feature = "encode_msgpack")]
+pub use encode_msgpack::EncodeMsgpack;
 #[cfg(feature = "encode_percent")]
 pub use encode_percent::EncodePercent;
+#[cfg(feature = "encode_xml")]
+pub use encode_xml::EncodeXml;
 #[cfg(feature = "encrypt")]
 pub use encrypt::Encrypt;
 #[cfg(feature = "ends_with")]
@@ -365,6 +399,8 @@ pub use exists::Exists;
 pub use filter::Filter;
 #[cfg(feature = "find")]
 pub use find::Find;
+#[cfg(feature = "find_item")]
+pub use find_item::FindItem;
 #[cfg(feature = "flatten")]
 pub use flatten::Flatten;
 #[cfg(feature = "float")]
@@ -445,6 +481,8 @@ pub use log::Log;
 pub use map_keys::MapKeys;
 #[cfg(feature = "map_values")]
 pub use map_values::MapValues;
+#[cfg(feature = "map_while")]
+pub use map_while::MapWhile;
 #[cfg(feature = "match_any")]
 pub use match_any::MatchAny;
 #[cfg(feature = "match_array")]
@@ -495,6 +533,8 @@ pub use parse_klog::ParseKlog;
 pub use parse_linux_authorization::ParseLinuxAuthorization;
 #[cfg(feature = "parse_logfmt")]
 pub use parse_logfmt::ParseLogFmt;
+#[cfg(feature = "parse_msgpack")]
+pub use parse_msgpack::ParseMsgpack;
 #[cfg(feature = "parse_nginx_log")]
 pub use parse_nginx_log::ParseNginxLog;
 #[cfg(feature = "parse_query_string")]
@@ -539,6 +579,8 @@ pub use set::Set;
 pub use sha2::Sha2;
 #[cfg(feature = "sha3")]
 pub use sha3::Sha3;
+#[cfg(feature = "sign_jwt")]
+pub use sign_jwt::SignJwt;
 #[cfg(feature = "slice")]
 pub use slice::Slice;
 #[cfg(feature = "split")]
@@ -595,9 +637,15 @@ pub use upcase::Upcase;
 pub use uuid_v4::UuidV4;
 #[cfg(feature = "values")]
 pub use values::Values;
+#[cfg(feature = "verify_jwt")]
+pub use verify_jwt::VerifyJwt;
+#[cfg(feature = "xpath")]
+pub use xpath::Xpath;
 
 #[cfg(feature = "array")]
 pub use crate::array::Array;
+#[cfg(feature = "hmac")]
+pub use crate::hmac::Hmac;
 #[cfg(feature = "md5")]
 pub use crate::md5::Md5;
 #[cfg(feature = "sha1")]
@@ -606,6 +654,10 @@ pub use crate::sha1::Sha1;
 #[must_use]
 pub fn all() -> Vec<Box<dyn vrl::Function>> {
     vec![
+        #[cfg(feature = "all")]
+        Box::new(All),
+        #[cfg(feature = "any")]
+        Box::new(Any),
         #[cfg(feature = "append")]
         Box::new(Append),
         #[cfg(feature = "array")]
@@ -620,6 +672,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(Ceil),
         #[cfg(feature = "chunks")]
         Box::new(Chunks),
+        #[cfg(feature = "community_id")]
+        Box::new(CommunityId),
         #[cfg(feature = "compact")]
         Box::new(Compact),
         #[cfg(feature = "contains")]
@@ -642,8 +696,12 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(EncodeKeyValue),
         #[cfg(feature = "encode_logfmt")]
         Box::new(EncodeLogfmt),
+        #[cfg(feature = "encode_msgpack")]
+        Box::new(EncodeMsgpack),
         #[cfg(feature = "encode_percent")]
         Box::new(EncodePercent),
+        #[cfg(feature = "encode_xml")]
+        Box::new(EncodeXml),
         #[cfg(feature = "encrypt")]
         Box::new(Encrypt),
         #[cfg(feature = "ends_with")]
@@ -654,6 +712,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(Filter),
         #[cfg(feature = "find")]
         Box::new(Find),
+        #[cfg(feature = "find_item")]
+        Box::new(FindItem),
         #[cfg(feature = "flatten")]
         Box::new(Flatten),
         #[cfg(feature = "float")]
@@ -674,6 +734,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(GetEnvVar),
         #[cfg(feature = "get_hostname")]
         Box::new(GetHostname),
+        #[cfg(feature = "hmac")]
+        Box::new(Hmac),
         #[cfg(feature = "includes")]
         Box::new(Includes),
         #[cfg(feature = "integer")]
@@ -734,6 +796,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(MapKeys),
         #[cfg(feature = "map_values")]
         Box::new(MapValues),
+        #[cfg(feature = "map_while")]
+        Box::new(MapWhile),
         #[cfg(feature = "match")]
         Box::new(Match),
         #[cfg(feature = "match_any")]
@@ -790,6 +854,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(ParseLinuxAuthorization),
         #[cfg(feature = "parse_logfmt")]
         Box::new(ParseLogFmt),
+        #[cfg(feature = "parse_msgpack")]
+        Box::new(ParseMsgpack),
         #[cfg(feature = "parse_nginx_log")]
         Box::new(ParseNginxLog),
         #[cfg(feature = "parse_query_string")]
@@ -834,6 +900,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(Sha2),
         #[cfg(feature = "sha3")]
         Box::new(Sha3),
+        #[cfg(feature = "sign_jwt")]
+        Box::new(SignJwt),
         #[cfg(feature = "slice")]
         Box::new(Slice),
         #[cfg(feature = "split")]
@@ -890,5 +958,9 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(UuidV4),
         #[cfg(feature = "values")]
         Box::new(Values),
+        #[cfg(feature = "verify_jwt")]
+        Box::new(VerifyJwt),
+        #[cfg(feature = "xpath")]
+        Box::new(Xpath),
     ]
 }
@@ -0,0 +1,149 @@
+use ::value::{kind::Collection, Value};
+use vrl::prelude::*;
+
+fn map_while<T>(value: Value, ctx: &mut Context, runner: closure::Runner<T>) -> Resolved
+where
+    T: Fn(&mut Context) -> Resolved,
+{
+    // Mapping stops at the first element for which the closure returns `null`.
+    match value {
+        Value::Object(object) => {
+            let mut mapped = BTreeMap::new();
+            for (key, value) in object {
+                match runner.run_key_value(ctx, &key, &value)? {
+                    Value::Null => break,
+                    value => mapped.insert(key, value),
+                };
+            }
+
+            Ok(mapped.into())
+        }
+
+        Value::Array(array) => {
+            let mut mapped = Vec::with_capacity(array.len());
+            for (index, value) in array.iter().enumerate() {
+                match runner.run_index_value(ctx, index, value)? {
+                    Value::Null => break,
+                    value => mapped.push(value),
+                }
+            }
+
+            Ok(mapped.into())
+        }
+
+        _ => unreachable!("function requires collection types as input"),
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct MapWhile;
+
+impl Function for MapWhile {
+    fn identifier(&self) -> &'static str {
+        "map_while"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::OBJECT | kind::ARRAY,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "map object elements",
+                source: r#"map_while({ "a": 1, "b": 2, "c": 3 }) -> |key, value| { if key != "c" { value * 10 } }"#,
+                result: Ok(r#"{ "a": 10, "b": 20 }"#),
+            },
+            Example {
+                title: "map array elements",
+                source: r#"map_while([1, 2, -1, 3]) -> |index, value| { if value > 0 { value + index } }"#,
+                result: Ok("[1, 3]"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let closure = arguments.required_closure()?;
+
+        Ok(MapWhileFn { value, closure }.as_expr())
+    }
+
+    fn closure(&self) -> Option<closure::Definition> {
+        use closure::{Definition, Input, Output, Variable, VariableKind};
+
+        Some(Definition {
+            inputs: vec![Input {
+                parameter_keyword: "value",
+                kind: Kind::object(Collection::any()).or_array(Collection::any()),
+                variables: vec![
+                    Variable {
+                        kind: VariableKind::TargetInnerKey,
+                    },
+                    Variable {
+                        kind: VariableKind::TargetInnerValue,
+                    },
+                ],
+                output: Output::Kind(Kind::any()),
+                example: Example {
+                    title: "map array elements",
+                    source: r#"map_while(["a", "b", ""]) -> |_index, value| { if value != "" { upcase(value) } }"#,
+                    result: Ok(r#"["A", "B"]"#),
+                },
+            }],
+            is_iterator: true,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MapWhileFn {
+    value: Box<dyn Expression>,
+    closure: FunctionClosure,
+}
+
+impl FunctionExpression for MapWhileFn {
+    fn resolve(&self, ctx: &mut Context) -> Result<Value> {
+        let value = self.value.resolve(ctx)?;
+        let FunctionClosure {
+            variables,
+            block,
+            block_type_def: _,
+        } = &self.closure;
+        let runner = closure::Runner::new(variables, |ctx| block.resolve(ctx));
+
+        map_while(value, ctx, runner)
+    }
+
+    fn type_def(&self, ctx: &state::TypeState) -> TypeDef {
+        let mut type_def = self.value.type_def(ctx);
+
+        // The elements are replaced by the non-null results of the closure, and we can't know how
+        // many of them are kept at runtime.
+        let mut element = self.closure.block_type_def.kind().clone();
+        element.remove_null();
+
+        if type_def.contains_array() {
+            type_def
+                .kind_mut()
+                .add_array(Collection::empty().with_unknown(element.clone()));
+        }
+
+        if type_def.contains_object() {
+            type_def
+                .kind_mut()
+                .add_object(Collection::empty().with_unknown(element));
+        }
+
+        type_def
+    }
+}
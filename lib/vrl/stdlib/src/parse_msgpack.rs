@@ -0,0 +1,247 @@
+use ::value::Value;
+use chrono::{TimeZone, Utc};
+use rmpv::Value as MsgpackValue;
+use vrl::prelude::*;
+
+/// The extension type of the timestamps defined by the MessagePack specification.
+const TIMESTAMP_EXT: i8 = -1;
+
+/// The extension type of the `EventTime` timestamps sent by fluentd.
+const EVENT_TIME_EXT: i8 = 0;
+
+fn parse_msgpack(value: Value) -> Resolved {
+    let bytes = value.try_bytes()?;
+    let mut reader = &bytes[..];
+    let value = rmpv::decode::read_value(&mut reader)
+        .map_err(|e| format!("unable to parse msgpack: {}", e))?;
+
+    if !reader.is_empty() {
+        return Err(format!(
+            "unable to parse msgpack: {} trailing byte(s) after the value",
+            reader.len()
+        )
+        .into());
+    }
+
+    from_msgpack(value).map_err(Into::into)
+}
+
+fn from_msgpack(value: MsgpackValue) -> std::result::Result<Value, String> {
+    let value = match value {
+        MsgpackValue::Nil => Value::Null,
+        MsgpackValue::Boolean(boolean) => boolean.into(),
+        MsgpackValue::Integer(integer) => integer
+            .as_i64()
+            .ok_or_else(|| format!("unable to parse msgpack: integer {} out of range", integer))?
+            .into(),
+        MsgpackValue::F32(float) => Value::from_f64_or_zero(float.into()),
+        MsgpackValue::F64(float) => Value::from_f64_or_zero(float),
+        MsgpackValue::String(string) => Bytes::from(string.into_bytes()).into(),
+        MsgpackValue::Binary(bytes) => Bytes::from(bytes).into(),
+        MsgpackValue::Array(array) => array
+            .into_iter()
+            .map(from_msgpack)
+            .collect::<std::result::Result<Vec<_>, _>>()?
+            .into(),
+        MsgpackValue::Map(map) => map
+            .into_iter()
+            .map(|(key, value)| Ok((key_to_string(key), from_msgpack(value)?)))
+            .collect::<std::result::Result<BTreeMap<_, _>, String>>()?
+            .into(),
+        MsgpackValue::Ext(kind, data) => match timestamp(kind, &data) {
+            Some(timestamp) => timestamp.into(),
+            None => Bytes::from(data).into(),
+        },
+    };
+
+    Ok(value)
+}
+
+/// Object keys are usually strings, other keys are converted to their textual representation.
+fn key_to_string(key: MsgpackValue) -> String {
+    match key {
+        MsgpackValue::String(string) => String::from_utf8_lossy(string.as_bytes()).into_owned(),
+        MsgpackValue::Binary(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+        key => key.to_string(),
+    }
+}
+
+/// Decodes the timestamp extensions, returning `None` for any other extension.
+fn timestamp(kind: i8, data: &[u8]) -> Option<chrono::DateTime<Utc>> {
+    let (seconds, nanoseconds) = match (kind, data.len()) {
+        (EVENT_TIME_EXT, 8) => (
+            i64::from(u32::from_be_bytes(data[..4].try_into().ok()?)),
+            u32::from_be_bytes(data[4..].try_into().ok()?),
+        ),
+        (TIMESTAMP_EXT, 4) => (i64::from(u32::from_be_bytes(data.try_into().ok()?)), 0),
+        (TIMESTAMP_EXT, 8) => {
+            let value = u64::from_be_bytes(data.try_into().ok()?);
+            ((value & 0x3_ffff_ffff) as i64, (value >> 34) as u32)
+        }
+        (TIMESTAMP_EXT, 12) => (
+            i64::from_be_bytes(data[4..].try_into().ok()?),
+            u32::from_be_bytes(data[..4].try_into().ok()?),
+        ),
+        _ => return None,
+    };
+
+    Utc.timestamp_opt(seconds, nanoseconds).single()
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseMsgpack;
+
+impl Function for ParseMsgpack {
+    fn identifier(&self) -> &'static str {
+        "parse_msgpack"
+    }
+
+    fn summary(&self) -> &'static str {
+        "parse a MessagePack payload"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {r#"
+            Parses the provided `value` as a single MessagePack value.
+
+            Strings and binaries are both returned as `string`s. The timestamp extension, and the
+            `EventTime` extension used by fluentd, are returned as `timestamp`s, while the data of
+            other extensions is returned as a `string`.
+        "#}
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "object",
+                source: r#"parse_msgpack!(decode_base64!("gaNmb2+jYmFy"))"#,
+                result: Ok(r#"{ "foo": "bar" }"#),
+            },
+            Example {
+                title: "array",
+                source: r#"parse_msgpack!(decode_base64!("kwHDwA=="))"#,
+                result: Ok("[1, true, null]"),
+            },
+            Example {
+                title: "fluentd event time",
+                source: r#"parse_msgpack!(decode_base64!("1wBZaC8AAAAABQ=="))"#,
+                result: Ok("t'2017-07-14T02:40:00.000000005Z'"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(ParseMsgpackFn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseMsgpackFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for ParseMsgpackFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        parse_msgpack(value)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        type_def()
+    }
+}
+
+fn inner_kind() -> Kind {
+    Kind::null()
+        | Kind::bytes()
+        | Kind::integer()
+        | Kind::float()
+        | Kind::boolean()
+        | Kind::timestamp()
+        | Kind::array(Collection::any())
+        | Kind::object(Collection::any())
+}
+
+fn type_def() -> TypeDef {
+    TypeDef::bytes()
+        .fallible()
+        .or_boolean()
+        .or_integer()
+        .or_float()
+        .or_timestamp()
+        .add_null()
+        .or_array(Collection::from_unknown(inner_kind()))
+        .or_object(Collection::from_unknown(inner_kind()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        parse_msgpack => ParseMsgpack;
+
+        object {
+            args: func_args![value: Bytes::from_static(b"\x82\xa1a\x01\xa1b\x92\xcb\x3f\xf8\x00\x00\x00\x00\x00\x00\xc4\x02\xff\x00")],
+            want: Ok(value!({ a: 1, b: [1.5, (Bytes::from_static(b"\xff\x00"))] })),
+            tdef: type_def(),
+        }
+
+        negative_integer {
+            args: func_args![value: Bytes::from_static(b"\xd0\x80")],
+            want: Ok(value!(-128)),
+            tdef: type_def(),
+        }
+
+        integer_out_of_range {
+            args: func_args![value: Bytes::from_static(b"\xcf\xff\xff\xff\xff\xff\xff\xff\xff")],
+            want: Err("unable to parse msgpack: integer 18446744073709551615 out of range"),
+            tdef: type_def(),
+        }
+
+        non_string_keys {
+            args: func_args![value: Bytes::from_static(b"\x81\x01\xc3")],
+            want: Ok(value!({ "1": true })),
+            tdef: type_def(),
+        }
+
+        event_time {
+            args: func_args![value: Bytes::from_static(b"\xd7\x00\x59\x68\x2f\x00\x00\x00\x00\x05")],
+            want: Ok(Value::from(Utc.timestamp(1_500_000_000, 5))),
+            tdef: type_def(),
+        }
+
+        timestamp_32 {
+            args: func_args![value: Bytes::from_static(b"\xd6\xff\x59\x68\x2f\x00")],
+            want: Ok(Value::from(Utc.timestamp(1_500_000_000, 0))),
+            tdef: type_def(),
+        }
+
+        other_extension {
+            args: func_args![value: Bytes::from_static(b"\xd4\x05\x2a")],
+            want: Ok(value!(Bytes::from_static(b"\x2a"))),
+            tdef: type_def(),
+        }
+
+        trailing_bytes {
+            args: func_args![value: Bytes::from_static(b"\x01\x02")],
+            want: Err("unable to parse msgpack: 1 trailing byte(s) after the value"),
+            tdef: type_def(),
+        }
+    ];
+}
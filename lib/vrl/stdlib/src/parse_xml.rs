@@ -14,6 +14,7 @@ use vrl::prelude::*;
 struct ParseOptions {
     trim: Option<Value>,
     include_attr: Option<Value>,
+    include_namespaces: Option<Value>,
     attr_prefix: Option<Value>,
     text_key: Option<Value>,
     always_use_text_key: Option<Value>,
@@ -32,6 +33,10 @@ fn parse_xml(value: Value, options: ParseOptions) -> Resolved {
         Some(value) => value.try_boolean()?,
         None => true,
     };
+    let include_namespaces = match options.include_namespaces {
+        Some(value) => value.try_boolean()?,
+        None => false,
+    };
     let attr_prefix = match options.attr_prefix {
         Some(value) => Cow::from(value.try_bytes_utf8_lossy()?.into_owned()),
         None => Cow::from("@"),
@@ -58,6 +63,7 @@ fn parse_xml(value: Value, options: ParseOptions) -> Resolved {
     };
     let config = ParseXmlConfig {
         include_attr,
+        include_namespaces,
         attr_prefix,
         text_key,
         always_use_text_key,
@@ -75,6 +81,9 @@ fn parse_xml(value: Value, options: ParseOptions) -> Resolved {
 struct ParseXmlConfig<'a> {
     /// Include XML attributes. Default: true,
     include_attr: bool,
+    /// Keep the namespace prefixes of names, and include namespace declarations as attributes.
+    /// Default: false.
+    include_namespaces: bool,
     /// XML attribute prefix, e.g. `<a href="test">` -> `{a: { "@href": "test }}`. Default: "@".
     attr_prefix: Cow<'a, str>,
     /// Key to use for text nodes when attributes are included. Default: "text".
@@ -121,6 +130,7 @@ impl Function for ParseXml {
 
         let trim = arguments.optional("trim");
         let include_attr = arguments.optional("include_attr");
+        let include_namespaces = arguments.optional("include_namespaces");
         let attr_prefix = arguments.optional("attr_prefix");
         let text_key = arguments.optional("text_key");
         let always_use_text_key = arguments.optional("always_use_text_key");
@@ -132,6 +142,7 @@ impl Function for ParseXml {
             value,
             trim,
             include_attr,
+            include_namespaces,
             attr_prefix,
             text_key,
             always_use_text_key,
@@ -159,6 +170,11 @@ impl Function for ParseXml {
                 kind: kind::BOOLEAN,
                 required: false,
             },
+            Parameter {
+                keyword: "include_namespaces",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
             Parameter {
                 keyword: "attr_prefix",
                 kind: kind::BYTES,
@@ -199,6 +215,7 @@ struct ParseXmlFn {
 
     trim: Option<Box<dyn Expression>>,
     include_attr: Option<Box<dyn Expression>>,
+    include_namespaces: Option<Box<dyn Expression>>,
     attr_prefix: Option<Box<dyn Expression>>,
     text_key: Option<Box<dyn Expression>>,
     always_use_text_key: Option<Box<dyn Expression>>,
@@ -224,6 +241,12 @@ impl FunctionExpression for ParseXmlFn {
                 .map(|expr| expr.resolve(ctx))
                 .transpose()?,
 
+            include_namespaces: self
+                .include_namespaces
+                .as_ref()
+                .map(|expr| expr.resolve(ctx))
+                .transpose()?,
+
             attr_prefix: self
                 .attr_prefix
                 .as_ref()
@@ -287,9 +310,16 @@ fn process_node<'a>(node: Node, config: &ParseXmlConfig<'a>) -> Value {
 
         // Expand attributes, if required.
         if config.include_attr {
+            if config.include_namespaces {
+                for (name, uri) in namespace_declarations(node) {
+                    map.insert(format!("{}{}", config.attr_prefix, name), uri.into());
+                }
+            }
+
             for attr in node.attributes() {
+                let name = qualified_name(node, attr.namespace(), attr.name(), config);
                 map.insert(
-                    format!("{}{}", config.attr_prefix, attr.name()),
+                    format!("{}{}", config.attr_prefix, name),
                     attr.value().into(),
                 );
             }
@@ -301,7 +331,7 @@ fn process_node<'a>(node: Node, config: &ParseXmlConfig<'a>) -> Value {
             .filter(|n| n.is_element() || n.is_text())
         {
             let name = match n.node_type() {
-                NodeType::Element => n.tag_name().name().to_string(),
+                NodeType::Element => element_name(n, config),
                 NodeType::Text => config.text_key.to_string(),
                 _ => unreachable!("shouldn't be other XML nodes"),
             };
@@ -338,7 +368,10 @@ fn process_node<'a>(node: Node, config: &ParseXmlConfig<'a>) -> Value {
         NodeType::Root => Value::Object(recurse(node)),
 
         NodeType::Element => {
-            match (config.always_use_text_key, node.attributes().is_empty()) {
+            let no_attributes = node.attributes().is_empty()
+                && !(config.include_namespaces && !namespace_declarations(node).is_empty());
+
+            match (config.always_use_text_key, no_attributes) {
                 // If the node has attributes, *always* recurse to expand default keys.
                 (_, false) if config.include_attr => Value::Object(recurse(node)),
                 // If a text key should be used, always recurse.
@@ -358,10 +391,7 @@ fn process_node<'a>(node: Node, config: &ParseXmlConfig<'a>) -> Value {
                         if node.is_element() {
                             let mut map = BTreeMap::new();
 
-                            map.insert(
-                                node.tag_name().name().to_string(),
-                                Value::Object(recurse(node)),
-                            );
+                            map.insert(element_name(node, config), Value::Object(recurse(node)));
 
                             Value::Object(map)
                         } else {
@@ -379,6 +409,56 @@ fn process_node<'a>(node: Node, config: &ParseXmlConfig<'a>) -> Value {
     }
 }
 
+/// Returns the name of an element or attribute, with its namespace prefix if namespaces are
+/// included.
+fn qualified_name(
+    node: Node,
+    namespace: Option<&str>,
+    name: &str,
+    config: &ParseXmlConfig<'_>,
+) -> String {
+    let prefix = namespace
+        .filter(|_| config.include_namespaces)
+        .and_then(|uri| node.lookup_prefix(uri));
+
+    match prefix {
+        Some(prefix) => format!("{}:{}", prefix, name),
+        None => name.to_owned(),
+    }
+}
+
+fn element_name(node: Node, config: &ParseXmlConfig<'_>) -> String {
+    let name = node.tag_name();
+    qualified_name(node, name.namespace(), name.name(), config)
+}
+
+/// Returns the namespaces declared by an element, as `xmlns` attribute names and URIs.
+///
+/// The namespaces in scope of an element include the ones declared by its ancestors, which are
+/// skipped.
+fn namespace_declarations(node: Node) -> Vec<(String, String)> {
+    let parent = node.parent_element();
+
+    node.namespaces()
+        .iter()
+        .filter(|namespace| namespace.name() != Some("xml"))
+        .filter(|namespace| {
+            parent.map_or(true, |parent| {
+                !parent.namespaces().iter().any(|inherited| {
+                    inherited.name() == namespace.name() && inherited.uri() == namespace.uri()
+                })
+            })
+        })
+        .map(|namespace| {
+            let name = match namespace.name() {
+                Some(prefix) => format!("xmlns:{}", prefix),
+                None => "xmlns".to_owned(),
+            };
+            (name, namespace.uri().to_owned())
+        })
+        .collect()
+}
+
 /// Process a text node, and return the correct `Value` type based on config.
 fn process_text<'a>(text: &'a str, config: &ParseXmlConfig<'a>) -> Value {
     match text {
@@ -599,6 +679,30 @@ mod tests {
             tdef: type_def(),
         }
 
+        include_namespaces {
+            args: func_args![ value: r#"<soap:Envelope xmlns:soap="http://www.w3.org/2003/05/soap-envelope" xmlns="urn:example"><soap:Body><m:GetPrice xmlns:m="urn:prices" m:currency="EUR"><Item>Apples</Item></m:GetPrice></soap:Body></soap:Envelope>"#, include_namespaces: true ],
+            want: Ok(value!({
+                "soap:Envelope": {
+                    "@xmlns": "urn:example",
+                    "@xmlns:soap": "http://www.w3.org/2003/05/soap-envelope",
+                    "soap:Body": {
+                        "m:GetPrice": {
+                            "@xmlns:m": "urn:prices",
+                            "@m:currency": "EUR",
+                            "Item": "Apples"
+                        }
+                    }
+                }
+            })),
+            tdef: type_def(),
+        }
+
+        exclude_namespaces {
+            args: func_args![ value: r#"<soap:Envelope xmlns:soap="http://www.w3.org/2003/05/soap-envelope"><soap:Body>test</soap:Body></soap:Envelope>"# ],
+            want: Ok(value!({ "Envelope": { "Body": "test" } })),
+            tdef: type_def(),
+        }
+
         invalid_token {
             args: func_args![ value: "true" ],
             want: Err("unable to parse xml: unknown token at 1:1"),
@@ -704,6 +808,7 @@ mod tests {
             value: value!(true).into_expression(),
             trim: None,
             include_attr: None,
+            include_namespaces: None,
             attr_prefix: None,
             text_key: None,
             always_use_text_key: None,
@@ -0,0 +1,630 @@
+use ::value::Value;
+use roxmltree::{Document, Node};
+use vrl::prelude::*;
+
+fn xpath(value: Value, path: Value, namespaces: Option<Value>) -> Resolved {
+    let string = value.try_bytes_utf8_lossy()?;
+    let path = path.try_bytes_utf8_lossy()?;
+    let namespaces = match namespaces {
+        Some(value) => value
+            .try_object()?
+            .into_iter()
+            .map(|(prefix, uri)| Ok((prefix, uri.try_bytes_utf8_lossy()?.into_owned())))
+            .collect::<std::result::Result<BTreeMap<_, _>, ExpressionError>>()?,
+        None => BTreeMap::new(),
+    };
+
+    let steps = parse_path(&path).map_err(|e| format!("invalid xpath: {}", e))?;
+    let doc = Document::parse(&string).map_err(|e| format!("unable to parse xml: {}", e))?;
+
+    Ok(select(&doc, &steps, &namespaces).into())
+}
+
+#[derive(Debug, PartialEq)]
+enum Axis {
+    /// `/`, selecting the children of the context nodes.
+    Child,
+    /// `//`, selecting the descendants of the context nodes.
+    Descendant,
+}
+
+/// A name, optionally qualified by a namespace prefix, where `None` matches any name.
+#[derive(Debug, PartialEq)]
+struct NameTest {
+    prefix: Option<String>,
+    local: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+enum NodeTest {
+    /// `.`
+    Current,
+    /// `name`, `prefix:name` or `*`.
+    Element(NameTest),
+    /// `@name`, only allowed as the last step.
+    Attribute(NameTest),
+    /// `text()`, only allowed as the last step.
+    Text,
+}
+
+#[derive(Debug, PartialEq)]
+enum Predicate {
+    /// `[1]`, the one-based position of the element among the matching siblings.
+    Position(usize),
+    /// `[@name]` or `[@name='value']`.
+    Attribute(NameTest, Option<String>),
+    /// `[name='value']`, matching the text of a child element.
+    Child(NameTest, String),
+}
+
+#[derive(Debug, PartialEq)]
+struct Step {
+    axis: Axis,
+    test: NodeTest,
+    predicates: Vec<Predicate>,
+}
+
+fn parse_path(path: &str) -> std::result::Result<Vec<Step>, String> {
+    let mut rest = path.trim();
+    if !rest.starts_with('/') {
+        return Err(r#"path must start with "/""#.to_owned());
+    }
+
+    let mut steps: Vec<Step> = Vec::new();
+    while !rest.is_empty() {
+        if let Some(Step {
+            test: NodeTest::Attribute(_) | NodeTest::Text,
+            ..
+        }) = steps.last()
+        {
+            return Err("attributes and text can only be selected by the last step".to_owned());
+        }
+
+        let axis = if let Some(stripped) = rest.strip_prefix("//") {
+            rest = stripped;
+            Axis::Descendant
+        } else if let Some(stripped) = rest.strip_prefix('/') {
+            rest = stripped;
+            Axis::Child
+        } else {
+            unreachable!("steps are separated by slashes")
+        };
+
+        let end = step_end(rest)?;
+        let (test, predicates) = parse_step(&rest[..end])?;
+        rest = &rest[end..];
+
+        steps.push(Step {
+            axis,
+            test,
+            predicates,
+        });
+    }
+
+    Ok(steps)
+}
+
+/// Returns the end of the step at the start of the path, which is the next slash outside of
+/// predicates.
+fn step_end(path: &str) -> std::result::Result<usize, String> {
+    let mut depth = 0;
+    let mut quote = None;
+
+    for (i, c) in path.char_indices() {
+        match (c, quote) {
+            ('\'' | '"', None) => quote = Some(c),
+            (c, Some(q)) if c == q => quote = None,
+            (_, Some(_)) => {}
+            ('[', None) => depth += 1,
+            (']', None) if depth == 0 => return Err("unexpected \"]\"".to_owned()),
+            (']', None) => depth -= 1,
+            ('/', None) if depth == 0 => return Ok(i),
+            _ => {}
+        }
+    }
+
+    if quote.is_some() {
+        Err("unterminated string".to_owned())
+    } else if depth > 0 {
+        Err(r#"missing "]""#.to_owned())
+    } else {
+        Ok(path.len())
+    }
+}
+
+fn parse_step(step: &str) -> std::result::Result<(NodeTest, Vec<Predicate>), String> {
+    let (test, mut rest) = match step.find('[') {
+        Some(start) => (&step[..start], &step[start..]),
+        None => (step, ""),
+    };
+
+    let test = match test.trim() {
+        "" => return Err("empty step".to_owned()),
+        "." => NodeTest::Current,
+        "text()" => NodeTest::Text,
+        test => match test.strip_prefix('@') {
+            Some(name) => NodeTest::Attribute(parse_name(name)?),
+            None => NodeTest::Element(parse_name(test)?),
+        },
+    };
+
+    let mut predicates = Vec::new();
+    while let Some(predicate) = rest.strip_prefix('[') {
+        let end = predicate_end(predicate)?;
+        predicates.push(parse_predicate(&predicate[..end])?);
+        rest = predicate[end + 1..].trim_start();
+    }
+
+    if !rest.is_empty() {
+        return Err(format!("unexpected {:?}", rest));
+    }
+    if !predicates.is_empty() && !matches!(test, NodeTest::Element(_)) {
+        return Err("predicates can only be applied to elements".to_owned());
+    }
+
+    Ok((test, predicates))
+}
+
+/// Returns the position of the `]` closing a predicate, skipping over quoted strings.
+fn predicate_end(predicate: &str) -> std::result::Result<usize, String> {
+    let mut quote = None;
+
+    for (i, c) in predicate.char_indices() {
+        match (c, quote) {
+            ('\'' | '"', None) => quote = Some(c),
+            (c, Some(q)) if c == q => quote = None,
+            (']', None) => return Ok(i),
+            _ => {}
+        }
+    }
+
+    Err(r#"missing "]""#.to_owned())
+}
+
+fn parse_predicate(predicate: &str) -> std::result::Result<Predicate, String> {
+    let predicate = predicate.trim();
+
+    if let Ok(position) = predicate.parse::<usize>() {
+        return if position == 0 {
+            Err("positions start at 1".to_owned())
+        } else {
+            Ok(Predicate::Position(position))
+        };
+    }
+
+    let (name, value) = match predicate.split_once('=') {
+        Some((name, value)) => (name.trim(), Some(parse_literal(value.trim())?)),
+        None => (predicate, None),
+    };
+
+    match (name.strip_prefix('@'), value) {
+        (Some(name), value) => Ok(Predicate::Attribute(parse_name(name)?, value)),
+        (None, Some(value)) => Ok(Predicate::Child(parse_name(name)?, value)),
+        (None, None) => Err(format!("unsupported predicate {:?}", predicate)),
+    }
+}
+
+fn parse_literal(literal: &str) -> std::result::Result<String, String> {
+    let unquoted = literal
+        .strip_prefix('\'')
+        .and_then(|literal| literal.strip_suffix('\''))
+        .or_else(|| {
+            literal
+                .strip_prefix('"')
+                .and_then(|literal| literal.strip_suffix('"'))
+        });
+
+    unquoted
+        .map(ToOwned::to_owned)
+        .ok_or_else(|| format!("expected a quoted string, got {:?}", literal))
+}
+
+fn parse_name(name: &str) -> std::result::Result<NameTest, String> {
+    let valid = |part: &str| {
+        !part.is_empty()
+            && part
+                .chars()
+                .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.'))
+    };
+
+    let (prefix, local) = match name.split_once(':') {
+        Some((prefix, local)) => (Some(prefix), local),
+        None => (None, name),
+    };
+
+    if !prefix.map_or(true, valid) || !(local == "*" || valid(local)) {
+        return Err(format!("invalid name {:?}", name));
+    }
+
+    Ok(NameTest {
+        prefix: prefix.map(ToOwned::to_owned),
+        local: (local != "*").then(|| local.to_owned()),
+    })
+}
+
+/// Returns whether a name matches a name test.
+///
+/// Unprefixed names match regardless of their namespace, so that documents using a default
+/// namespace can be queried without declaring it. Prefixes are resolved against the given
+/// namespaces first, then against the namespaces declared in the document.
+fn name_matches(
+    test: &NameTest,
+    node: Node,
+    namespace: Option<&str>,
+    name: &str,
+    namespaces: &BTreeMap<String, String>,
+) -> bool {
+    if test.local.as_deref().map_or(false, |local| local != name) {
+        return false;
+    }
+
+    match &test.prefix {
+        Some(prefix) => {
+            let uri = namespaces
+                .get(prefix)
+                .map(String::as_str)
+                .or_else(|| node.lookup_namespace_uri(Some(prefix.as_str())));
+            uri.is_some() && uri == namespace
+        }
+        None => true,
+    }
+}
+
+fn element_matches(test: &NameTest, node: Node, namespaces: &BTreeMap<String, String>) -> bool {
+    let name = node.tag_name();
+    node.is_element() && name_matches(test, node, name.namespace(), name.name(), namespaces)
+}
+
+fn attributes<'a>(
+    test: &NameTest,
+    node: Node<'a, '_>,
+    namespaces: &BTreeMap<String, String>,
+) -> Vec<&'a str> {
+    node.attributes()
+        .iter()
+        .filter(|attr| name_matches(test, node, attr.namespace(), attr.name(), namespaces))
+        .map(|attr| attr.value())
+        .collect()
+}
+
+fn text_content(node: Node) -> String {
+    node.descendants()
+        .filter(|node| node.is_text())
+        .filter_map(|node| node.text())
+        .collect()
+}
+
+fn predicate_matches(
+    predicate: &Predicate,
+    node: Node,
+    namespaces: &BTreeMap<String, String>,
+) -> bool {
+    match predicate {
+        Predicate::Position(_) => unreachable!("positions are applied to the matching siblings"),
+        Predicate::Attribute(test, value) => attributes(test, node, namespaces)
+            .into_iter()
+            .any(|attr| value.as_deref().map_or(true, |value| attr == value)),
+        Predicate::Child(test, value) => node
+            .children()
+            .any(|child| element_matches(test, child, namespaces) && text_content(child) == *value),
+    }
+}
+
+/// Selects the nodes matching a path, returning the text of the matching elements, or the
+/// matching attribute values.
+fn select(doc: &Document, steps: &[Step], namespaces: &BTreeMap<String, String>) -> Vec<Value> {
+    let mut nodes = vec![doc.root()];
+
+    for step in steps {
+        // The nodes the step applies to, including the descendants of the context nodes when
+        // the step is preceded by `//`.
+        let contexts = match step.axis {
+            Axis::Child => nodes,
+            Axis::Descendant => nodes
+                .into_iter()
+                .flat_map(|node| node.descendants())
+                .collect(),
+        };
+
+        nodes = match &step.test {
+            NodeTest::Current => contexts,
+            NodeTest::Element(test) => contexts
+                .into_iter()
+                .flat_map(|context| {
+                    let mut children = context
+                        .children()
+                        .filter(|child| element_matches(test, *child, namespaces))
+                        .collect::<Vec<_>>();
+
+                    for predicate in &step.predicates {
+                        children = match predicate {
+                            Predicate::Position(position) => {
+                                children.into_iter().skip(position - 1).take(1).collect()
+                            }
+                            predicate => children
+                                .into_iter()
+                                .filter(|child| predicate_matches(predicate, *child, namespaces))
+                                .collect(),
+                        };
+                    }
+
+                    children
+                })
+                .collect(),
+            NodeTest::Attribute(test) => {
+                return dedup(contexts)
+                    .into_iter()
+                    .flat_map(|node| attributes(test, node, namespaces))
+                    .map(Value::from)
+                    .collect();
+            }
+            NodeTest::Text => {
+                return dedup(contexts)
+                    .into_iter()
+                    .flat_map(|node| node.children().filter(|child| child.is_text()))
+                    .filter_map(|node| node.text())
+                    .map(Value::from)
+                    .collect();
+            }
+        };
+        nodes = dedup(nodes);
+    }
+
+    nodes
+        .into_iter()
+        .filter(|node| !node.is_root())
+        .map(|node| text_content(node).into())
+        .collect()
+}
+
+/// Sorts nodes in document order, removing the duplicates selected through several contexts.
+fn dedup(mut nodes: Vec<Node>) -> Vec<Node> {
+    // An element starts at the same position as its first ancestors when they have no content of
+    // their own, such as the root node, which the depth tells apart.
+    nodes.sort_by_key(|node| (node.range().start, node.ancestors().count()));
+    nodes.dedup();
+    nodes
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Xpath;
+
+impl Function for Xpath {
+    fn identifier(&self) -> &'static str {
+        "xpath"
+    }
+
+    fn summary(&self) -> &'static str {
+        "select values from an XML document"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {r#"
+            Selects the values matching an XPath-like `path` in the XML document `value`.
+
+            Returns the text of the matching elements, or the matching attribute values, in document
+            order. A subset of XPath is supported: absolute paths made of `/` and `//` steps, `*`,
+            `.`, `@name` and `text()` steps, and the `[1]`, `[@name]`, `[@name='value']` and
+            `[name='value']` predicates.
+
+            Unprefixed names match elements and attributes regardless of their namespace. Prefixed
+            names are resolved against `namespaces`, then against the namespaces declared in the
+            document.
+        "#}
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "path",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "namespaces",
+                kind: kind::OBJECT,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "select elements",
+                source: r#"xpath!(s'<books><book><title>Dune</title></book><book><title>Emma</title></book></books>', "//book/title")"#,
+                result: Ok(r#"["Dune", "Emma"]"#),
+            },
+            Example {
+                title: "select attributes",
+                source: r#"xpath!(s'<book category="SF"><title lang="en">Dune</title></book>', "/book/title/@lang")"#,
+                result: Ok(r#"["en"]"#),
+            },
+            Example {
+                title: "select with a predicate and namespaces",
+                source: r#"xpath!(s'<s:Envelope xmlns:s="urn:soap"><s:Body><item id="1">a</item><item id="2">b</item></s:Body></s:Envelope>', "/soap:Envelope/soap:Body/item[@id='2']", namespaces: {"soap": "urn:soap"})"#,
+                result: Ok(r#"["b"]"#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let path = arguments.required("path");
+        let namespaces = arguments.optional("namespaces");
+
+        Ok(XpathFn {
+            value,
+            path,
+            namespaces,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct XpathFn {
+    value: Box<dyn Expression>,
+    path: Box<dyn Expression>,
+    namespaces: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for XpathFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let path = self.path.resolve(ctx)?;
+        let namespaces = self
+            .namespaces
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+
+        xpath(value, path, namespaces)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::array(Collection::from_unknown(Kind::bytes())).fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOOKS: &str = r#"<library xmlns:dc="http://purl.org/dc/elements/1.1/">
+        <shelf name="fiction">
+            <book id="1" lang="en"><dc:title>Dune</dc:title><year>1965</year></book>
+            <book id="2" lang="fr"><dc:title>Candide</dc:title><year>1759</year></book>
+        </shelf>
+        <shelf name="poetry">
+            <book id="3" lang="en"><dc:title>Leaves of Grass</dc:title><year>1855</year></book>
+        </shelf>
+    </library>"#;
+
+    test_function![
+        xpath => Xpath;
+
+        child {
+            args: func_args![value: BOOKS, path: "/library/shelf/book/year"],
+            want: Ok(value!(["1965", "1759", "1855"])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::bytes())).fallible(),
+        }
+
+        descendant {
+            args: func_args![value: BOOKS, path: "//year"],
+            want: Ok(value!(["1965", "1759", "1855"])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::bytes())).fallible(),
+        }
+
+        nested_descendants_are_not_duplicated {
+            args: func_args![value: BOOKS, path: "//*//year"],
+            want: Ok(value!(["1965", "1759", "1855"])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::bytes())).fallible(),
+        }
+
+        attribute {
+            args: func_args![value: BOOKS, path: "//book/@id"],
+            want: Ok(value!(["1", "2", "3"])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::bytes())).fallible(),
+        }
+
+        position {
+            args: func_args![value: BOOKS, path: "//book[1]/year"],
+            want: Ok(value!(["1965", "1855"])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::bytes())).fallible(),
+        }
+
+        attribute_predicate {
+            args: func_args![value: BOOKS, path: "/library/shelf[@name='fiction']/book[@lang=\"fr\"]/year"],
+            want: Ok(value!(["1759"])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::bytes())).fallible(),
+        }
+
+        child_predicate {
+            args: func_args![value: BOOKS, path: "//book[year='1855']/@id"],
+            want: Ok(value!(["3"])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::bytes())).fallible(),
+        }
+
+        document_prefix {
+            args: func_args![value: BOOKS, path: "//dc:title/text()"],
+            want: Ok(value!(["Dune", "Candide", "Leaves of Grass"])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::bytes())).fallible(),
+        }
+
+        custom_prefix {
+            args: func_args![value: BOOKS, path: "//book[@id='2']/meta:title", namespaces: value!({ "meta": "http://purl.org/dc/elements/1.1/" })],
+            want: Ok(value!(["Candide"])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::bytes())).fallible(),
+        }
+
+        unknown_prefix {
+            args: func_args![value: BOOKS, path: "//unknown:title"],
+            want: Ok(value!([])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::bytes())).fallible(),
+        }
+
+        relative_path {
+            args: func_args![value: BOOKS, path: "book"],
+            want: Err(r#"invalid xpath: path must start with "/""#),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::bytes())).fallible(),
+        }
+
+        attribute_not_last {
+            args: func_args![value: BOOKS, path: "//book/@id/year"],
+            want: Err("invalid xpath: attributes and text can only be selected by the last step"),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::bytes())).fallible(),
+        }
+
+        missing_bracket {
+            args: func_args![value: BOOKS, path: "//book[@id='1'/year"],
+            want: Err(r#"invalid xpath: missing "]""#),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::bytes())).fallible(),
+        }
+
+    ];
+
+    #[test]
+    fn parses_steps() {
+        assert_eq!(
+            parse_path("//a:b[2][@c]/@d"),
+            Ok(vec![
+                Step {
+                    axis: Axis::Descendant,
+                    test: NodeTest::Element(NameTest {
+                        prefix: Some("a".to_owned()),
+                        local: Some("b".to_owned()),
+                    }),
+                    predicates: vec![
+                        Predicate::Position(2),
+                        Predicate::Attribute(
+                            NameTest {
+                                prefix: None,
+                                local: Some("c".to_owned()),
+                            },
+                            None
+                        ),
+                    ],
+                },
+                Step {
+                    axis: Axis::Child,
+                    test: NodeTest::Attribute(NameTest {
+                        prefix: None,
+                        local: Some("d".to_owned()),
+                    }),
+                    predicates: vec![],
+                },
+            ])
+        );
+    }
+}
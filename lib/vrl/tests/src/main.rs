@@ -184,6 +184,7 @@ fn main() {
         let external_env = vrl::state::ExternalEnv::default();
         let mut config = CompileConfig::default();
         config.set_custom(test_enrichment.clone());
+        config.set_custom(vector_vrl_functions::state::ComponentState::default());
 
         // Set some read-only paths that can be tested
         for (path, recursive) in &test.read_only_paths {
@@ -5,7 +5,7 @@ use async_graphql::{Enum, InputObject, Object};
 use super::{source, state, transform, Component};
 use crate::{
     api::schema::{
-        filter,
+        control, filter,
         metrics::{self, IntoSinkMetrics},
         sort,
     },
@@ -118,6 +118,11 @@ impl Sink {
         metrics::by_component_key(self.get_component_key())
             .into_sink_metrics(self.get_component_type())
     }
+
+    /// Whether the sink is paused, and has stopped delivering events
+    pub async fn paused(&self) -> bool {
+        control::is_paused(self.get_component_key())
+    }
 }
 
 #[cfg(test)]
@@ -5,7 +5,7 @@ use async_graphql::{Enum, InputObject, Object};
 use super::{sink, state, transform, Component};
 use crate::{
     api::schema::{
-        filter,
+        control, filter,
         metrics::{self, outputs_by_component_key, IntoSourceMetrics, Output},
         sort,
     },
@@ -138,6 +138,11 @@ impl Source {
         metrics::by_component_key(&self.0.component_key)
             .into_source_metrics(self.get_component_type())
     }
+
+    /// Whether the source is paused, and has stopped ingesting events
+    pub async fn paused(&self) -> bool {
+        control::is_paused(self.get_component_key())
+    }
 }
 
 #[derive(Default, InputObject)]
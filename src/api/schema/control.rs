@@ -0,0 +1,103 @@
+use async_graphql::{Context, Guard, Object, SimpleObject};
+use vector_common::sensitive_string::SensitiveString;
+
+use crate::{config::ComponentKey, topology::pause};
+
+/// The token enabling mutations, as set by `api.control_token`.
+#[derive(Clone)]
+pub struct ControlToken(pub SensitiveString);
+
+/// The bearer token sent along with a request, if any.
+#[derive(Clone)]
+pub struct RequestToken(pub String);
+
+impl RequestToken {
+    /// Parses the token from the value of an `Authorization` header.
+    pub fn from_header(value: &str) -> Option<Self> {
+        value
+            .strip_prefix("Bearer ")
+            .map(|token| Self(token.trim().to_owned()))
+    }
+}
+
+/// Rejects the requests that aren't sent with the control token.
+struct ControlGuard;
+
+#[async_trait::async_trait]
+impl Guard for ControlGuard {
+    async fn check(&self, ctx: &Context<'_>) -> async_graphql::Result<()> {
+        let expected = match ctx.data_opt::<ControlToken>() {
+            Some(ControlToken(token)) => token.inner(),
+            None => {
+                return Err(
+                    "Mutations are disabled. Set `api.control_token` to enable them.".into(),
+                )
+            }
+        };
+
+        match ctx.data_opt::<RequestToken>() {
+            Some(RequestToken(token)) if token == expected => Ok(()),
+            _ => Err("Unauthorized: a valid bearer token is required.".into()),
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct PausedComponent {
+    /// Component id
+    component_id: String,
+
+    /// Component kind, either `source` or `sink`
+    kind: String,
+
+    /// Whether the component is paused
+    paused: bool,
+}
+
+fn set_paused(component_id: String, paused: bool) -> async_graphql::Result<PausedComponent> {
+    let key = ComponentKey::from(component_id.as_str());
+    let kind = pause::set_paused(&key, paused)?;
+
+    info!(
+        message = "Component paused state changed through the API.",
+        component_kind = %kind,
+        component_id = %key,
+        paused,
+    );
+
+    Ok(PausedComponent {
+        component_id,
+        kind: kind.to_string(),
+        paused,
+    })
+}
+
+#[derive(Default)]
+pub struct ControlMutation;
+
+#[Object]
+impl ControlMutation {
+    /// Pauses a source or a sink. A paused source stops ingesting events, and a paused sink stops
+    /// delivering events, which keep accumulating in its buffer.
+    #[graphql(guard = "ControlGuard")]
+    async fn pause_component(
+        &self,
+        component_id: String,
+    ) -> async_graphql::Result<PausedComponent> {
+        set_paused(component_id, true)
+    }
+
+    /// Resumes a paused source or sink.
+    #[graphql(guard = "ControlGuard")]
+    async fn resume_component(
+        &self,
+        component_id: String,
+    ) -> async_graphql::Result<PausedComponent> {
+        set_paused(component_id, false)
+    }
+}
+
+/// Returns whether a source or a sink is paused.
+pub fn is_paused(key: &ComponentKey) -> bool {
+    pause::is_paused(key).unwrap_or(false)
+}
@@ -111,7 +111,7 @@ impl Metric {
         self.event.tags().map(|tags| {
             tags.iter()
                 .map(|(key, value)| MetricTag {
-                    key: key.to_owned(),
+                    key: key.to_string(),
                     value: value.to_owned(),
                 })
                 .collect()
@@ -69,3 +69,62 @@ impl ComponentErrorsTotal {
         ErrorsTotal::new(self.metric.clone())
     }
 }
+
+pub struct ComponentErrorType {
+    component_key: ComponentKey,
+    metric: Metric,
+    throughput: f64,
+}
+
+impl ComponentErrorType {
+    /// Returns a new `ComponentErrorType` struct, which is a GraphQL type, from an errors metric
+    /// summed by error type and stage, and the number of errors since the previous sample.
+    pub fn new(metric: Metric, throughput: f64) -> Self {
+        let component_key = metric.tag_value("component_id").expect(
+            "Returned a metric without a `component_id`, which shouldn't happen. Please report.",
+        );
+        let component_key = ComponentKey::from(component_key);
+
+        Self {
+            component_key,
+            metric,
+            throughput,
+        }
+    }
+}
+
+#[Object]
+impl ComponentErrorType {
+    /// Component id
+    async fn component_id(&self) -> &str {
+        self.component_key.id()
+    }
+
+    /// Name of the errors metric
+    async fn name(&self) -> &str {
+        self.metric.name()
+    }
+
+    /// Type of the errors, if reported
+    async fn error_type(&self) -> Option<String> {
+        self.metric.tag_value("error_type")
+    }
+
+    /// Stage of the component at which the errors occurred, if reported
+    async fn stage(&self) -> Option<String> {
+        self.metric.tag_value("stage")
+    }
+
+    /// Total error count
+    async fn errors_total(&self) -> f64 {
+        match self.metric.value() {
+            MetricValue::Counter { value } => *value,
+            _ => 0.00,
+        }
+    }
+
+    /// Number of errors since the previous sample
+    async fn throughput(&self) -> f64 {
+        self.throughput
+    }
+}
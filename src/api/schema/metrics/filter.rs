@@ -369,6 +369,74 @@ pub fn component_sent_events_total_throughputs_with_outputs(
         .skip(1)
 }
 
+/// Returns a stream of the `_errors_total` metrics of each component, summed by metric name,
+/// `error_type` and `stage` tags, along with the number of errors since the previous sample.
+/// Unlike `component_counter_metrics`, every error type is returned on each sample, so that
+/// the client can tell when errors stop occurring.
+pub fn component_error_type_metrics(interval: i32) -> impl Stream<Item = Vec<(Metric, f64)>> {
+    let mut cache = BTreeMap::new();
+
+    component_to_filtered_metrics(interval, &|m| m.name().ends_with("_errors_total")).map(
+        move |map| {
+            let mut error_types = Vec::new();
+            for (id, metrics) in map {
+                let by_type = metrics.into_iter().fold(BTreeMap::new(), |mut by_type, m| {
+                    let key = (
+                        m.name().to_owned(),
+                        m.tag_value("error_type"),
+                        m.tag_value("stage"),
+                    );
+                    by_type.entry(key).or_insert_with(Vec::new).push(m);
+                    by_type
+                });
+
+                for ((name, error_type, stage), metrics) in by_type {
+                    let m = match sum_metrics_owned(metrics) {
+                        Some(m) => m,
+                        None => continue,
+                    };
+                    if let MetricValue::Counter { value } = m.value() {
+                        let key = format!(
+                            "{}.{}.{}.{}",
+                            id,
+                            name,
+                            error_type.unwrap_or_default(),
+                            stage.unwrap_or_default()
+                        );
+                        // Errors counted before the first sample aren't recent.
+                        let throughput = cache
+                            .insert(key, *value)
+                            .map_or(0.00, |last| (value - last).max(0.00));
+                        error_types.push((m, throughput));
+                    }
+                }
+            }
+            error_types
+        },
+    )
+}
+
+/// Returns a stream of the `utilization` metric of each component, along with the
+/// `output_utilization` metrics of its outputs, for the transforms with several outputs.
+/// Unlike counters, these gauges are returned on each sample, whether they changed or not.
+pub fn component_utilization_metrics(
+    interval: i32,
+) -> impl Stream<Item = Vec<(Metric, Vec<Metric>)>> {
+    component_to_filtered_metrics(interval, &|m| {
+        matches!(m.name(), "utilization" | "output_utilization")
+    })
+    .map(|map| {
+        map.into_values()
+            .filter_map(|metrics| {
+                let (utilization, outputs): (Vec<_>, Vec<_>) =
+                    metrics.into_iter().partition(|m| m.name() == "utilization");
+                let utilization = utilization.into_iter().next()?;
+                Some((utilization, outputs))
+            })
+            .collect()
+    })
+}
+
 /// Returns a map of Component ID to list of metrics where metrics have been
 /// filtered by `filter_fn`
 fn component_to_filtered_metrics(
@@ -11,13 +11,14 @@ mod sink;
 pub mod source;
 mod transform;
 mod uptime;
+mod utilization;
 
 #[cfg(feature = "sources-host_metrics")]
 mod host;
 
 use async_graphql::{Interface, Object, Subscription};
 use chrono::{DateTime, Utc};
-pub use errors::{ComponentErrorsTotal, ErrorsTotal};
+pub use errors::{ComponentErrorType, ComponentErrorsTotal, ErrorsTotal};
 pub use events_in::EventsInTotal;
 pub use events_out::EventsOutTotal;
 pub use filter::*;
@@ -37,6 +38,7 @@ pub use source::{IntoSourceMetrics, SourceMetrics};
 use tokio_stream::{Stream, StreamExt};
 pub use transform::{IntoTransformMetrics, TransformMetrics};
 pub use uptime::Uptime;
+pub use utilization::{ComponentUtilization, OutputUtilization};
 
 use crate::config::ComponentKey;
 
@@ -297,6 +299,31 @@ impl MetricsSubscription {
             .map(|m| m.into_iter().map(ComponentErrorsTotal::new).collect())
     }
 
+    /// Component error metrics, split by error type and stage, sampled over `interval`.
+    async fn component_error_types(
+        &self,
+        #[graphql(default = 1000, validator(minimum = 10, maximum = 60_000))] interval: i32,
+    ) -> impl Stream<Item = Vec<ComponentErrorType>> {
+        component_error_type_metrics(interval).map(|m| {
+            m.into_iter()
+                .map(|(m, throughput)| ComponentErrorType::new(m, throughput))
+                .collect()
+        })
+    }
+
+    /// Component utilization metrics, with the utilization of each output of the transforms with
+    /// several, sampled over `interval`.
+    async fn component_utilizations(
+        &self,
+        #[graphql(default = 1000, validator(minimum = 10, maximum = 60_000))] interval: i32,
+    ) -> impl Stream<Item = Vec<ComponentUtilization>> {
+        component_utilization_metrics(interval).map(|m| {
+            m.into_iter()
+                .map(|(m, outputs)| ComponentUtilization::new(m, outputs))
+                .collect()
+        })
+    }
+
     /// All metrics.
     async fn metrics(
         &self,
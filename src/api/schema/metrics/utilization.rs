@@ -0,0 +1,84 @@
+use async_graphql::Object;
+
+use crate::{
+    config::ComponentKey,
+    event::{Metric, MetricValue},
+};
+
+fn gauge_value(metric: &Metric) -> f64 {
+    match metric.value() {
+        MetricValue::Gauge { value } => *value,
+        _ => 0.00,
+    }
+}
+
+pub struct OutputUtilization {
+    output_id: String,
+    utilization: f64,
+}
+
+impl OutputUtilization {
+    /// Returns a new `OutputUtilization` struct, which is a GraphQL type, from an
+    /// `output_utilization` metric.
+    pub fn new(metric: &Metric) -> Self {
+        Self {
+            output_id: metric.tag_value("output").unwrap_or_default(),
+            utilization: gauge_value(metric),
+        }
+    }
+}
+
+#[Object]
+impl OutputUtilization {
+    /// Id of the output stream
+    async fn output_id(&self) -> &str {
+        self.output_id.as_ref()
+    }
+
+    /// Fraction of time, from 0 to 1, the component spends sending events to the output stream
+    async fn utilization(&self) -> f64 {
+        self.utilization
+    }
+}
+
+pub struct ComponentUtilization {
+    component_key: ComponentKey,
+    utilization: f64,
+    outputs: Vec<OutputUtilization>,
+}
+
+impl ComponentUtilization {
+    /// Returns a new `ComponentUtilization` struct, which is a GraphQL type, from the
+    /// `utilization` metric of a component and the `output_utilization` metrics of its outputs.
+    pub fn new(metric: Metric, outputs: Vec<Metric>) -> Self {
+        let component_key = metric.tag_value("component_id").expect(
+            "Returned a metric without a `component_id`, which shouldn't happen. Please report.",
+        );
+        let component_key = ComponentKey::from(component_key);
+
+        Self {
+            component_key,
+            utilization: gauge_value(&metric),
+            outputs: outputs.iter().map(OutputUtilization::new).collect(),
+        }
+    }
+}
+
+#[Object]
+impl ComponentUtilization {
+    /// Component id
+    async fn component_id(&self) -> &str {
+        self.component_key.id()
+    }
+
+    /// Fraction of time, from 0 to 1, the component spends processing events rather than waiting
+    /// for more, smoothed over time
+    async fn utilization(&self) -> f64 {
+        self.utilization
+    }
+
+    /// Utilization of each output stream, for the transforms with several outputs
+    async fn outputs(&self) -> &Vec<OutputUtilization> {
+        &self.outputs
+    }
+}
@@ -10,10 +10,23 @@ use async_graphql::{
 };
 use async_graphql_warp::{graphql_protocol, GraphQLResponse, GraphQLWebSocket};
 use tokio::sync::oneshot;
-use warp::{filters::BoxedFilter, http::Response, ws::Ws, Filter, Reply};
+use vector_common::sensitive_string::SensitiveString;
+use warp::{
+    filters::BoxedFilter,
+    http::{Response, StatusCode},
+    ws::Ws,
+    Filter, Rejection, Reply,
+};
 
-use super::{handler, schema, ShutdownTx};
-use crate::{config, topology};
+use super::{
+    handler,
+    schema::{
+        self,
+        control::{ControlToken, RequestToken},
+    },
+    ShutdownTx,
+};
+use crate::{config, tls::MaybeTlsSettings, topology};
 
 pub struct Server {
     _shutdown: ShutdownTx,
@@ -21,18 +34,31 @@ pub struct Server {
 }
 
 impl Server {
-    /// Start the API server. This creates the routes and spawns a Warp server. The server is
-    /// gracefully shut down when Self falls out of scope by way of the oneshot sender closing.
-    pub fn start(
+    /// Start the API server. This creates the routes and spawns a Warp server, terminating TLS
+    /// if it is enabled. The server is gracefully shut down when Self falls out of scope by way
+    /// of the oneshot sender closing.
+    pub async fn start(
         config: &config::Config,
         watch_rx: topology::WatchRx,
         running: Arc<AtomicBool>,
-    ) -> Self {
-        let routes = make_routes(config.api.playground, watch_rx, running);
+    ) -> crate::Result<Self> {
+        let routes = make_routes(
+            config.api.playground,
+            config.api.control_token.clone(),
+            config.api.auth_token.clone(),
+            watch_rx,
+            running,
+        );
+
+        let tls = MaybeTlsSettings::from_config(&config.api.tls, true)?;
+        let listener = tls
+            .bind(&config.api.address.expect("No socket address"))
+            .await?;
+        let addr = listener.local_addr()?;
 
         let (_shutdown, rx) = oneshot::channel();
-        let (addr, server) = warp::serve(routes).bind_with_graceful_shutdown(
-            config.api.address.expect("No socket address"),
+        let server = warp::serve(routes).serve_incoming_with_graceful_shutdown(
+            listener.accept_stream(),
             async {
                 rx.await.ok();
             },
@@ -44,7 +70,7 @@ impl Server {
         // Spawn the server in the background.
         tokio::spawn(server);
 
-        Self { _shutdown, addr }
+        Ok(Self { _shutdown, addr })
     }
 
     /// Returns a copy of the SocketAddr that the server was started on.
@@ -60,8 +86,76 @@ impl Server {
     }
 }
 
+/// Builds the GraphQL schema, enabling mutations if a control token is set.
+fn build_schema(
+    control_token: Option<SensitiveString>,
+) -> Schema<schema::Query, schema::Mutation, schema::Subscription> {
+    let builder = schema::build_schema();
+    match control_token {
+        Some(token) => builder.data(ControlToken(token)),
+        None => builder,
+    }
+    .finish()
+}
+
+/// Rejection for the requests that aren't sent with the auth token.
+#[derive(Debug)]
+struct Unauthorized;
+
+impl warp::reject::Reject for Unauthorized {}
+
+/// Returns whether a request is authorized, given the auth token set on the API, if any.
+fn is_authorized(auth_token: Option<&SensitiveString>, token: Option<&RequestToken>) -> bool {
+    match (auth_token, token) {
+        (None, _) => true,
+        (Some(expected), Some(RequestToken(token))) => token == expected.inner(),
+        (Some(_), None) => false,
+    }
+}
+
+/// Rejects the requests that aren't sent with the auth token as a bearer token.
+fn with_auth(
+    auth_token: Option<SensitiveString>,
+) -> impl Filter<Extract = (), Error = Rejection> + Clone {
+    warp::header::optional::<String>("authorization")
+        .and_then(move |authorization: Option<String>| {
+            let authorized = is_authorized(
+                auth_token.as_ref(),
+                authorization
+                    .as_deref()
+                    .and_then(RequestToken::from_header)
+                    .as_ref(),
+            );
+            async move {
+                if authorized {
+                    Ok(())
+                } else {
+                    Err(warp::reject::custom(Unauthorized))
+                }
+            }
+        })
+        .untuple_one()
+}
+
+async fn handle_rejection(rejection: Rejection) -> Result<impl Reply, Rejection> {
+    if rejection.find::<Unauthorized>().is_some() {
+        Ok(warp::reply::with_header(
+            warp::reply::with_status(
+                "Unauthorized: a valid bearer token is required.",
+                StatusCode::UNAUTHORIZED,
+            ),
+            "WWW-Authenticate",
+            "Bearer",
+        ))
+    } else {
+        Err(rejection)
+    }
+}
+
 fn make_routes(
     playground: bool,
+    control_token: Option<SensitiveString>,
+    auth_token: Option<SensitiveString>,
     watch_tx: topology::WatchRx,
     running: Arc<AtomicBool>,
 ) -> BoxedFilter<(impl Reply,)> {
@@ -78,20 +172,45 @@ fn make_routes(
     // GraphQL subscription handler. Creates a Warp WebSocket handler and for each connection,
     // parses the required headers for GraphQL and builds per-connection context based on the
     // provided `WatchTx` channel sender. This allows GraphQL resolvers to subscribe to
-    // topology changes.
-    let graphql_subscription_handler =
-        warp::ws()
-            .and(graphql_protocol())
-            .map(move |ws: Ws, protocol: WebSocketProtocols| {
-                let schema = schema::build_schema().finish();
+    // topology changes. As browsers can't set headers on WebSockets, the bearer token can also
+    // be sent as the `token` field of the `connection_init` payload.
+    let subscription_control_token = control_token.clone();
+    let subscription_auth_token = auth_token.clone();
+    let graphql_subscription_handler = warp::ws()
+        .and(graphql_protocol())
+        .and(warp::header::optional::<String>("authorization"))
+        .map(
+            move |ws: Ws, protocol: WebSocketProtocols, authorization: Option<String>| {
+                let schema = build_schema(subscription_control_token.clone());
                 let watch_tx = watch_tx.clone();
+                let auth_token = subscription_auth_token.clone();
 
                 let reply = ws.on_upgrade(move |socket| {
                     let mut data = Data::default();
                     data.insert(watch_tx);
+                    let header_token = authorization.as_deref().and_then(RequestToken::from_header);
 
                     GraphQLWebSocket::new(socket, schema, protocol)
                         .with_data(data)
+                        .on_connection_init(move |payload| async move {
+                            let token = header_token.or_else(|| {
+                                payload
+                                    .get("token")
+                                    .and_then(serde_json::Value::as_str)
+                                    .map(|token| RequestToken(token.to_owned()))
+                            });
+                            if !is_authorized(auth_token.as_ref(), token.as_ref()) {
+                                return Err(
+                                    "Unauthorized: a valid bearer token is required.".into()
+                                );
+                            }
+
+                            let mut data = Data::default();
+                            if let Some(token) = token {
+                                data.insert(token);
+                            }
+                            Ok(data)
+                        })
                         .serve()
                 });
 
@@ -100,18 +219,29 @@ fn make_routes(
                     "Sec-WebSocket-Protocol",
                     protocol.sec_websocket_protocol(),
                 )
-            });
+            },
+        );
 
     // Handle GraphQL queries. Headers will first be parsed to determine whether the query is
     // a subscription and if so, an attempt will be made to upgrade the connection to WebSockets.
-    // All other queries will fall back to the default HTTP handler.
-    let graphql_handler = warp::path("graphql").and(graphql_subscription_handler.or(
-        async_graphql_warp::graphql(schema::build_schema().finish()).and_then(
-            |(schema, request): (Schema<_, _, _>, Request)| async move {
+    // All other queries will fall back to the default HTTP handler, which rejects the queries sent
+    // without the auth token, if set. The bearer token of the request, if any, is passed along to
+    // authorize mutations.
+    let graphql_query_handler = with_auth(auth_token)
+        .and(async_graphql_warp::graphql(build_schema(control_token)))
+        .and(warp::header::optional::<String>("authorization"))
+        .and_then(
+            |(schema, request): (Schema<_, _, _>, Request),
+             authorization: Option<String>| async move {
+                let request = match authorization.as_deref().and_then(RequestToken::from_header) {
+                    Some(token) => request.data(token),
+                    None => request,
+                };
                 Ok::<_, Infallible>(GraphQLResponse::from(schema.execute(request).await))
             },
-        ),
-    ));
+        );
+    let graphql_handler =
+        warp::path("graphql").and(graphql_subscription_handler.or(graphql_query_handler));
 
     // Provide a playground for executing GraphQL queries/mutations/subscriptions.
     let graphql_playground = if playground {
@@ -134,6 +264,7 @@ fn make_routes(
         .or(graphql_handler)
         .or(graphql_playground)
         .or(not_found)
+        .recover(handle_rejection)
         .with(
             warp::cors()
                 .allow_any_origin()
@@ -146,6 +277,7 @@ fn make_routes(
                     "Access-Control-Allow-Origin",
                     "Access-Control-Request-Headers",
                     "Content-Type",
+                    "Authorization",
                     "X-Apollo-Tracing", // for Apollo GraphQL clients
                     "Pragma",
                     "Host",
@@ -585,7 +585,7 @@ mod tests {
             BlackholeConfig {
                 print_interval_secs: 1,
                 rate: None,
-                acknowledgements: Default::default(),
+                ..Default::default()
             },
         );
 
@@ -642,7 +642,7 @@ mod tests {
             BlackholeConfig {
                 print_interval_secs: 1,
                 rate: None,
-                acknowledgements: Default::default(),
+                ..Default::default()
             },
         );
 
@@ -692,7 +692,7 @@ mod tests {
             BlackholeConfig {
                 print_interval_secs: 1,
                 rate: None,
-                acknowledgements: Default::default(),
+                ..Default::default()
             },
         );
 
@@ -745,7 +745,7 @@ mod tests {
             BlackholeConfig {
                 print_interval_secs: 1,
                 rate: None,
-                acknowledgements: Default::default(),
+                ..Default::default()
             },
         );
 
@@ -819,7 +819,7 @@ mod tests {
             BlackholeConfig {
                 print_interval_secs: 1,
                 rate: None,
-                acknowledgements: Default::default(),
+                ..Default::default()
             },
         );
 
@@ -879,7 +879,7 @@ mod tests {
             BlackholeConfig {
                 print_interval_secs: 1,
                 rate: None,
-                acknowledgements: Default::default(),
+                ..Default::default()
             },
         );
 
@@ -955,7 +955,7 @@ mod tests {
             BlackholeConfig {
                 print_interval_secs: 1,
                 rate: None,
-                acknowledgements: Default::default(),
+                ..Default::default()
             },
         );
 
@@ -22,9 +22,10 @@ use crate::service;
 #[cfg(feature = "api")]
 use crate::{api, internal_events::ApiStarted};
 use crate::{
+    benchmark, buffer_snapshot, clean_data,
     cli::{handle_config_errors, Color, LogFormat, Opts, RootOpts, SubCommand},
     config::{self},
-    generate, generate_schema, graph, heartbeat, list,
+    convert_config, generate, generate_schema, graph, heartbeat, list,
     signal::{self, SignalTo},
     topology::{self, RunningTopology},
     trace, unit_test, validate,
@@ -135,9 +136,19 @@ impl Application {
             let config_paths = root_opts.config_paths_with_formats();
             let watch_config = root_opts.watch_config;
             let require_healthy = root_opts.require_healthy;
+            let once = root_opts.once;
+            let internal_tracing_otlp_endpoint = root_opts.internal_tracing_otlp_endpoint.clone();
+            #[cfg(unix)]
+            let upgrade = root_opts.upgrade;
 
             rt.block_on(async move {
-                trace::init(color, json, &level, root_opts.internal_log_rate_limit);
+                trace::init(
+                    color,
+                    json,
+                    &level,
+                    root_opts.internal_log_rate_limit,
+                    internal_tracing_otlp_endpoint,
+                );
                 info!(
                     message = "Internal log rate limit configured.",
                     internal_log_rate_secs = root_opts.internal_log_rate_limit
@@ -151,7 +162,11 @@ impl Application {
                         SubCommand::Generate(g) => generate::cmd(&g),
                         SubCommand::GenerateSchema => generate_schema::cmd(),
                         SubCommand::Graph(g) => graph::cmd(&g),
+                        SubCommand::CleanData(c) => clean_data::cmd(&c),
+                        SubCommand::Benchmark(b) => benchmark::cmd(&b).await,
+                        SubCommand::Buffer(b) => buffer_snapshot::cmd(&b, signal_rx).await,
                         SubCommand::Config(c) => config::cmd(&c),
+                        SubCommand::ConvertConfig(c) => convert_config::cmd(&c),
                         SubCommand::List(l) => list::cmd(&l),
                         SubCommand::Test(t) => unit_test::cmd(&t, &mut signal_handler).await,
                         #[cfg(windows)]
@@ -202,6 +217,29 @@ impl Application {
                 }
                 config.healthchecks.set_require_healthy(require_healthy);
 
+                if once {
+                    config::check_run_once(&config).map_err(handle_config_errors)?;
+                    config.once = true;
+                }
+
+                #[cfg(unix)]
+                let upgrade_path = config
+                    .global
+                    .data_dir
+                    .as_deref()
+                    .map(crate::upgrade::socket_path);
+                #[cfg(unix)]
+                if upgrade {
+                    let path = upgrade_path.clone().ok_or_else(|| {
+                        error!("Upgrading requires a data directory, set with `data_dir`.");
+                        exitcode::CONFIG
+                    })?;
+                    crate::upgrade::take_over(path).await.map_err(|error| {
+                        error!(message = "Failed to take over from the running Vector.", %error);
+                        exitcode::UNAVAILABLE
+                    })?;
+                }
+
                 #[cfg(feature = "enterprise")]
                 // Enable enterprise features, if applicable.
                 let enterprise = match EnterpriseMetadata::try_from(&config) {
@@ -226,11 +264,21 @@ impl Application {
                     .ok_or(exitcode::CONFIG)?;
 
                 #[cfg(feature = "api")]
-                let api = config.api;
+                let api = config.api.clone();
 
                 let result = topology::start_validated(config, diff, pieces).await;
                 let (topology, graceful_crash) = result.ok_or(exitcode::CONFIG)?;
 
+                #[cfg(unix)]
+                {
+                    crate::upgrade::close_unused();
+                    if let Some(path) = upgrade_path {
+                        if let Err(error) = crate::upgrade::serve(path, signal_handler.clone_tx()) {
+                            debug!(message = "Not listening for upgrades.", %error);
+                        }
+                    }
+                }
+
                 Ok(ApplicationConfig {
                     config_paths,
                     topology,
@@ -252,7 +300,8 @@ impl Application {
         })
     }
 
-    pub fn run(self) {
+    /// Runs the topology until Vector is shut down, returning the exit status of the process.
+    pub fn run(self) -> exitcode::ExitCode {
         let rt = self.runtime;
 
         let mut graceful_crash = UnboundedReceiverStream::new(self.config.graceful_crash);
@@ -276,6 +325,7 @@ impl Application {
         crate::trace::stop_early_buffering();
 
         rt.block_on(async move {
+            let mut crashed = false;
             emit!(VectorStarted);
             tokio::spawn(heartbeat::heartbeat());
 
@@ -284,12 +334,20 @@ impl Application {
             // Assigned to prevent the API terminating when falling out of scope.
             let api_server = if api_config.enabled {
                 use std::sync::{Arc, atomic::AtomicBool};
-                emit!(ApiStarted {
-                    addr: api_config.address.unwrap(),
-                    playground: api_config.playground
-                });
-
-                Some(api::Server::start(topology.config(), topology.watch(), Arc::<AtomicBool>::clone(&topology.running)))
+                match api::Server::start(topology.config(), topology.watch(), Arc::<AtomicBool>::clone(&topology.running)).await {
+                    Ok(api_server) => {
+                        emit!(ApiStarted {
+                            addr: api_server.addr(),
+                            playground: api_config.playground,
+                            tls: api_config.tls.as_ref().and_then(|tls| tls.enabled).unwrap_or(false),
+                        });
+                        Some(api_server)
+                    }
+                    Err(error) => {
+                        error!(message = "Failed to start the API server.", %error);
+                        None
+                    }
+                }
             } else {
                 info!(message="API is disabled, enable by setting `api.enabled` to `true` and use commands like `vector top`.");
                 None
@@ -305,6 +363,7 @@ impl Application {
                                 match config_builder.build().map_err(handle_config_errors) {
                                     Ok(mut new_config) => {
                                         new_config.healthchecks.set_require_healthy(opts.require_healthy);
+                                        new_config.once = opts.once;
 
                                         #[cfg(feature = "enterprise")]
                                         // Augment config to enable observability within Datadog, if applicable.
@@ -361,6 +420,7 @@ impl Application {
 
                                 if let Some(mut new_config) = new_config {
                                     new_config.healthchecks.set_require_healthy(opts.require_healthy);
+                                    new_config.once = opts.once;
 
                                     #[cfg(feature = "enterprise")]
                                     match EnterpriseMetadata::try_from(&new_config) {
@@ -409,7 +469,10 @@ impl Application {
                         }
                     }
                     // Trigger graceful shutdown if a component crashed, or all sources have ended.
-                    _ = graceful_crash.next() => break SignalTo::Shutdown,
+                    _ = graceful_crash.next() => {
+                        crashed = true;
+                        break SignalTo::Shutdown;
+                    }
                     _ = &mut sources_finished => break SignalTo::Shutdown,
                     else => unreachable!("Signal streams never end"),
                 }
@@ -434,6 +497,13 @@ impl Application {
                 }
                 _ => unreachable!(),
             }
-        });
+
+            // Running once, the exit status tells whether the events were processed.
+            if opts.once && crashed {
+                exitcode::SOFTWARE
+            } else {
+                exitcode::OK
+            }
+        })
     }
 }
@@ -0,0 +1,489 @@
+//! The `vector benchmark` subcommand, which runs a configuration with synthetic load in place of
+//! its sources, and reports the throughput of its components.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    num::NonZeroU64,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use async_stream::stream;
+use chrono::{SecondsFormat, Utc};
+use clap::Parser;
+use futures::{stream::FuturesUnordered, Stream, StreamExt};
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+use serde_json::json;
+use tokio::sync::mpsc;
+use vector_core::event::{BatchNotifier, BatchStatusReceiver};
+
+use crate::{
+    cli::handle_config_errors,
+    config::{self, unit_test::UnitTestStreamSourceConfig, SourceOuter},
+    event::{Event, LogEvent, Metric, MetricKind, MetricValue},
+    metrics::Controller,
+    topology,
+};
+
+/// The number of events generated at once, which share their acknowledgement.
+const BATCH_SIZE: u64 = 100;
+
+const HOSTS: [&str; 4] = ["web-1", "web-2", "api-1", "worker-1"];
+const METHODS: [&str; 4] = ["GET", "GET", "POST", "PUT"];
+const PATHS: [&str; 5] = ["/", "/login", "/api/v1/users", "/api/v1/orders", "/health"];
+const STATUSES: [u16; 5] = [200, 200, 201, 404, 500];
+
+#[derive(Parser, Debug)]
+#[command(rename_all = "kebab-case")]
+pub struct Opts {
+    /// Read configuration from one or more files. Wildcard paths are supported.
+    /// File format is detected from the file name.
+    /// If zero files are specified the default config path
+    /// `/etc/vector/vector.toml` will be targeted.
+    #[arg(
+        id = "config",
+        short,
+        long,
+        env = "VECTOR_CONFIG",
+        value_delimiter(',')
+    )]
+    paths: Vec<PathBuf>,
+
+    /// Vector config files in TOML format.
+    #[arg(id = "config-toml", long, value_delimiter(','))]
+    paths_toml: Vec<PathBuf>,
+
+    /// Vector config files in JSON format.
+    #[arg(id = "config-json", long, value_delimiter(','))]
+    paths_json: Vec<PathBuf>,
+
+    /// Vector config files in YAML format.
+    #[arg(id = "config-yaml", long, value_delimiter(','))]
+    paths_yaml: Vec<PathBuf>,
+
+    /// Read configuration from files in one or more directories.
+    /// File format is detected from the file name.
+    ///
+    /// Files not ending in .toml, .json, .yaml, or .yml will be ignored.
+    #[arg(
+        id = "config-dir",
+        short = 'C',
+        long,
+        env = "VECTOR_CONFIG_DIR",
+        value_delimiter(',')
+    )]
+    pub config_dirs: Vec<PathBuf>,
+
+    /// The shape of the events generated in place of each source.
+    #[arg(long, value_enum, default_value = "json")]
+    shape: Shape,
+
+    /// How long to generate events for, in seconds.
+    #[arg(long, default_value = "60")]
+    duration_secs: u64,
+
+    /// The number of events generated per second in place of each source. By default, events
+    /// are generated as fast as the topology accepts them.
+    #[arg(long)]
+    rate: Option<NonZeroU64>,
+}
+
+impl Opts {
+    fn paths_with_formats(&self) -> Vec<config::ConfigPath> {
+        config::merge_path_lists(vec![
+            (&self.paths, None),
+            (&self.paths_toml, Some(config::Format::Toml)),
+            (&self.paths_json, Some(config::Format::Json)),
+            (&self.paths_yaml, Some(config::Format::Yaml)),
+        ])
+        .map(|(path, hint)| config::ConfigPath::File(path, hint))
+        .chain(
+            self.config_dirs
+                .iter()
+                .map(|dir| config::ConfigPath::Dir(dir.to_path_buf())),
+        )
+        .collect()
+    }
+}
+
+/// The shape of the generated events.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shape {
+    /// Logs whose message is a JSON object, as written by a web server.
+    Json,
+
+    /// Logs whose message is a RFC 5424 Syslog line.
+    Syslog,
+
+    /// Counters and gauges of a web server.
+    Metrics,
+}
+
+impl Shape {
+    fn event(self, rng: &mut SmallRng, index: u64) -> Event {
+        let host = HOSTS[rng.gen_range(0..HOSTS.len())];
+        let method = METHODS[rng.gen_range(0..METHODS.len())];
+        let path = PATHS[rng.gen_range(0..PATHS.len())];
+        let status = STATUSES[rng.gen_range(0..STATUSES.len())];
+        let now = Utc::now();
+        match self {
+            Self::Json => {
+                let message = json!({
+                    "timestamp": now.to_rfc3339_opts(SecondsFormat::Millis, true),
+                    "host": host,
+                    "method": method,
+                    "path": path,
+                    "status": status,
+                    "bytes": rng.gen_range(100..50_000),
+                    "duration_ms": rng.gen_range(1..2_000),
+                    "request_id": format!("{:016x}", rng.gen::<u64>()),
+                });
+                let mut log = LogEvent::from(message.to_string());
+                log.insert(crate::config::log_schema().timestamp_key(), now);
+                log.into()
+            }
+            Self::Syslog => {
+                let message = format!(
+                    "<{}>1 {} {} nginx {} ID{} - {} {} {}",
+                    rng.gen_range(128..192),
+                    now.to_rfc3339_opts(SecondsFormat::Millis, true),
+                    host,
+                    rng.gen_range(1000..65_535),
+                    index,
+                    method,
+                    path,
+                    status,
+                );
+                let mut log = LogEvent::from(message);
+                log.insert(crate::config::log_schema().timestamp_key(), now);
+                log.into()
+            }
+            Self::Metrics => {
+                let tags = BTreeMap::from([
+                    ("host".into(), host.to_owned()),
+                    ("method".into(), method.to_owned()),
+                    ("status".into(), status.to_string()),
+                ]);
+                let metric = if index % 2 == 0 {
+                    Metric::new(
+                        "http_requests_total",
+                        MetricKind::Incremental,
+                        MetricValue::Counter { value: 1.0 },
+                    )
+                } else {
+                    Metric::new(
+                        "http_request_duration_seconds",
+                        MetricKind::Absolute,
+                        MetricValue::Gauge {
+                            value: rng.gen_range(0.001..2.0),
+                        },
+                    )
+                };
+                metric
+                    .with_tags(Some(tags))
+                    .with_timestamp(Some(now))
+                    .into()
+            }
+        }
+    }
+}
+
+/// Generates events of the shape until the deadline, sending the time each batch is generated
+/// at and its acknowledgement.
+fn generate(
+    shape: Shape,
+    rate: Option<NonZeroU64>,
+    deadline: Instant,
+    batches: mpsc::UnboundedSender<(Instant, BatchStatusReceiver)>,
+) -> impl Stream<Item = Event> {
+    let batch_size = rate.map_or(BATCH_SIZE, |rate| rate.get().min(BATCH_SIZE));
+    let mut interval = rate.map(|rate| {
+        tokio::time::interval(Duration::from_secs_f64(
+            batch_size as f64 / rate.get() as f64,
+        ))
+    });
+
+    stream! {
+        let mut rng = SmallRng::from_entropy();
+        let mut index = 0;
+        while Instant::now() < deadline {
+            if let Some(interval) = &mut interval {
+                interval.tick().await;
+            }
+
+            let (batch, receiver) = BatchNotifier::new_with_receiver();
+            let events = (0..batch_size)
+                .map(|offset| shape.event(&mut rng, index + offset).with_batch_notifier(&batch))
+                .collect::<Vec<_>>();
+            drop(batch);
+            index += batch_size;
+            let _ = batches.send((Instant::now(), receiver));
+
+            for event in events {
+                yield event;
+            }
+        }
+    }
+}
+
+/// Collects the latency of the batches, from their generation to the delivery, or drop, of all
+/// of their events.
+async fn collect_latencies(
+    mut batches: mpsc::UnboundedReceiver<(Instant, BatchStatusReceiver)>,
+) -> Vec<Duration> {
+    let mut pending = FuturesUnordered::new();
+    let mut latencies = Vec::new();
+    let mut generating = true;
+    loop {
+        tokio::select! {
+            batch = batches.recv(), if generating => match batch {
+                Some((generated_at, receiver)) => pending.push(async move {
+                    receiver.await;
+                    generated_at.elapsed()
+                }),
+                None => generating = false,
+            },
+            Some(latency) = pending.next(), if !pending.is_empty() => latencies.push(latency),
+            else => break,
+        }
+    }
+    latencies
+}
+
+/// The counters and gauges of a component, as captured from the internal metrics.
+#[derive(Debug, Default)]
+struct ComponentMetrics {
+    kind: String,
+    received_events: f64,
+    sent_events: f64,
+    utilization: Option<f64>,
+}
+
+fn capture_component_metrics() -> BTreeMap<String, ComponentMetrics> {
+    let mut components = BTreeMap::<String, ComponentMetrics>::new();
+    let metrics = Controller::get()
+        .map(Controller::capture_metrics)
+        .unwrap_or_default();
+    for metric in metrics {
+        let component_id = match metric.tag_value("component_id") {
+            Some(component_id) => component_id,
+            None => continue,
+        };
+        let component = components.entry(component_id).or_default();
+        if let Some(kind) = metric.tag_value("component_kind") {
+            component.kind = kind;
+        }
+        match (metric.name(), metric.value()) {
+            ("component_received_events_total", MetricValue::Counter { value }) => {
+                component.received_events += value;
+            }
+            // The events sent to each output are counted separately.
+            ("component_sent_events_total", MetricValue::Counter { value }) => {
+                component.sent_events += value;
+            }
+            ("utilization", MetricValue::Gauge { value }) => component.utilization = Some(*value),
+            _ => {}
+        }
+    }
+    components
+}
+
+/// The CPU time, user and system, and the peak resident memory, in bytes, of the process.
+#[cfg(unix)]
+fn resource_usage() -> Option<(Duration, Duration, u64)> {
+    let mut usage = std::mem::MaybeUninit::<libc::rusage>::uninit();
+    // SAFETY: `getrusage` initializes the structure when it succeeds.
+    let usage = unsafe {
+        if libc::getrusage(libc::RUSAGE_SELF, usage.as_mut_ptr()) != 0 {
+            return None;
+        }
+        usage.assume_init()
+    };
+    let duration = |time: libc::timeval| {
+        Duration::from_secs(time.tv_sec as u64) + Duration::from_micros(time.tv_usec as u64)
+    };
+    // The maximum resident set size is in kilobytes, but on macOS where it is in bytes.
+    let max_rss = if cfg!(target_os = "macos") {
+        usage.ru_maxrss as u64
+    } else {
+        usage.ru_maxrss as u64 * 1024
+    };
+    Some((duration(usage.ru_utime), duration(usage.ru_stime), max_rss))
+}
+
+#[cfg(not(unix))]
+const fn resource_usage() -> Option<(Duration, Duration, u64)> {
+    None
+}
+
+/// Gets the percentile of the sorted durations.
+fn percentile(sorted: &[Duration], percentile: f64) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+    let rank = (percentile / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+pub async fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    let paths = opts.paths_with_formats();
+    let paths = match config::process_paths(&paths) {
+        Some(paths) => paths,
+        None => return exitcode::CONFIG,
+    };
+
+    let mut builder = match config::load_builder_from_paths(&paths) {
+        Ok((builder, _)) => builder,
+        Err(errors) => return handle_config_errors(errors),
+    };
+
+    // Each source is replaced by a generator of events, which stops at the deadline.
+    let deadline = Instant::now() + Duration::from_secs(opts.duration_secs);
+    let (batches_tx, batches_rx) = mpsc::unbounded_channel();
+    let source_count = builder.sources.len();
+    for source in builder.sources.values_mut() {
+        let events = generate(opts.shape, opts.rate, deadline, batches_tx.clone());
+        *source = SourceOuter::new(UnitTestStreamSourceConfig::new(events));
+    }
+    drop(batches_tx);
+    let latencies = tokio::spawn(collect_latencies(batches_rx));
+
+    let config = match builder.build() {
+        Ok(config) => config,
+        Err(errors) => return handle_config_errors(errors),
+    };
+    let diff = config::ConfigDiff::initial(&config);
+    let pieces = match topology::build_or_log_errors(&config, &diff, HashMap::new()).await {
+        Some(pieces) => pieces,
+        None => return exitcode::CONFIG,
+    };
+
+    #[allow(clippy::print_stdout)]
+    {
+        println!(
+            "Generating {:?} events in place of {} source(s) for {}s...",
+            opts.shape, source_count, opts.duration_secs
+        );
+    }
+    let before = capture_component_metrics();
+    let usage_before = resource_usage();
+    let started_at = Instant::now();
+
+    let topology = match topology::start_validated(config, diff, pieces).await {
+        Some((topology, _)) => topology,
+        None => return exitcode::CONFIG,
+    };
+    topology.sources_finished().await;
+    // The events still in flight are drained before the topology stops.
+    topology.stop().await;
+
+    let elapsed = started_at.elapsed();
+    let after = capture_component_metrics();
+    let usage_after = resource_usage();
+    let mut latencies = latencies.await.unwrap_or_default();
+    latencies.sort_unstable();
+
+    report(
+        &before,
+        &after,
+        elapsed,
+        &latencies,
+        usage_before.zip(usage_after),
+    );
+    exitcode::OK
+}
+
+#[allow(clippy::print_stdout)]
+fn report(
+    before: &BTreeMap<String, ComponentMetrics>,
+    after: &BTreeMap<String, ComponentMetrics>,
+    elapsed: Duration,
+    latencies: &[Duration],
+    usage: Option<((Duration, Duration, u64), (Duration, Duration, u64))>,
+) {
+    let secs = elapsed.as_secs_f64().max(f64::EPSILON);
+    println!();
+    println!(
+        "{:<32} {:<12} {:>14} {:>14} {:>12}",
+        "Component", "Kind", "Received/s", "Sent/s", "Utilization"
+    );
+    for (id, metrics) in after {
+        let previous = before.get(id);
+        let received =
+            metrics.received_events - previous.map_or(0.0, |previous| previous.received_events);
+        let sent = metrics.sent_events - previous.map_or(0.0, |previous| previous.sent_events);
+        let utilization = metrics.utilization.map_or_else(
+            || "-".to_owned(),
+            |utilization| format!("{:.1}%", utilization * 100.0),
+        );
+        println!(
+            "{:<32} {:<12} {:>14.0} {:>14.0} {:>12}",
+            id,
+            metrics.kind,
+            received / secs,
+            sent / secs,
+            utilization
+        );
+    }
+
+    println!();
+    println!(
+        "Latency ({} batches of up to {} events): p50 {:?}, p90 {:?}, p99 {:?}, max {:?}",
+        latencies.len(),
+        BATCH_SIZE,
+        percentile(latencies, 50.0),
+        percentile(latencies, 90.0),
+        percentile(latencies, 99.0),
+        latencies.last().copied().unwrap_or_default(),
+    );
+
+    if let Some(((user_before, system_before, _), (user_after, system_after, max_rss))) = usage {
+        let user = user_after.saturating_sub(user_before);
+        let system = system_after.saturating_sub(system_before);
+        println!(
+            "CPU: {:.2} cores (user {:.1}s, system {:.1}s)",
+            (user + system).as_secs_f64() / secs,
+            user.as_secs_f64(),
+            system.as_secs_f64(),
+        );
+        println!(
+            "Memory: {:.1} MiB peak resident",
+            max_rss as f64 / 1_048_576.0
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn computes_percentiles() {
+        let sorted = (1..=100).map(Duration::from_millis).collect::<Vec<_>>();
+        assert_eq!(percentile(&sorted, 50.0), Duration::from_millis(50));
+        assert_eq!(percentile(&sorted, 99.0), Duration::from_millis(99));
+        assert_eq!(percentile(&sorted, 100.0), Duration::from_millis(100));
+        assert_eq!(percentile(&[], 50.0), Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn generates_batches_until_deadline() {
+        let (batches_tx, batches_rx) = mpsc::unbounded_channel();
+        let deadline = Instant::now() + Duration::from_millis(50);
+        // At 1000 events per second, a batch is generated every 100ms, the first one at once.
+        let events = generate(Shape::Syslog, NonZeroU64::new(1000), deadline, batches_tx)
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(events.len() as u64, BATCH_SIZE);
+        let message = events[0]
+            .as_log()
+            .get(config::log_schema().message_key())
+            .unwrap()
+            .to_string_lossy();
+        assert!(message.contains(" nginx "));
+
+        drop(events);
+        let latencies = collect_latencies(batches_rx).await;
+        assert_eq!(latencies.len(), 1);
+    }
+}
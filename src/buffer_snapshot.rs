@@ -0,0 +1,382 @@
+//! The `vector buffer` subcommands, which export the disk buffer of a sink to a snapshot, and
+//! replay a snapshot through another configuration, to recover the events stranded in the buffer
+//! of a sink that was decommissioned.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use chrono::{DateTime, Utc};
+use clap::Parser;
+use futures::{stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use vector_buffers::snapshot::{export_disk_buffer, open_snapshot};
+
+use crate::{
+    cli::handle_config_errors,
+    config::{self, unit_test::UnitTestStreamSourceConfig, ComponentKey, SourceOuter},
+    event::{EventArray, EventContainer},
+    get_version,
+    signal::{SignalRx, SignalTo},
+    topology,
+};
+
+/// The name of the file describing the snapshot, at its root.
+const MANIFEST_FILE_NAME: &str = "snapshot.json";
+
+#[derive(Parser, Debug)]
+#[command(rename_all = "kebab-case")]
+pub struct Opts {
+    #[command(subcommand)]
+    sub_command: SubCommand,
+}
+
+#[derive(Parser, Debug)]
+#[command(rename_all = "kebab-case")]
+enum SubCommand {
+    /// Copy the disk buffer of a sink to a snapshot directory.
+    ///
+    /// Vector must not be running with the buffer, which is locked while it is copied.
+    Export(ExportOpts),
+
+    /// Send the events of a snapshot in place of a source of the configuration, then exit once
+    /// they have all been delivered.
+    ///
+    /// The events are removed from the snapshot as they are delivered, so that an interrupted
+    /// replay resumes where it left off.
+    Replay(ReplayOpts),
+}
+
+#[derive(Parser, Debug)]
+#[command(rename_all = "kebab-case")]
+struct ConfigOpts {
+    /// Read configuration from one or more files. Wildcard paths are supported.
+    /// File format is detected from the file name.
+    /// If zero files are specified the default config path
+    /// `/etc/vector/vector.toml` will be targeted.
+    #[arg(
+        id = "config",
+        short,
+        long,
+        env = "VECTOR_CONFIG",
+        value_delimiter(',')
+    )]
+    paths: Vec<PathBuf>,
+
+    /// Vector config files in TOML format.
+    #[arg(id = "config-toml", long, value_delimiter(','))]
+    paths_toml: Vec<PathBuf>,
+
+    /// Vector config files in JSON format.
+    #[arg(id = "config-json", long, value_delimiter(','))]
+    paths_json: Vec<PathBuf>,
+
+    /// Vector config files in YAML format.
+    #[arg(id = "config-yaml", long, value_delimiter(','))]
+    paths_yaml: Vec<PathBuf>,
+
+    /// Read configuration from files in one or more directories.
+    /// File format is detected from the file name.
+    ///
+    /// Files not ending in .toml, .json, .yaml, or .yml will be ignored.
+    #[arg(
+        id = "config-dir",
+        short = 'C',
+        long,
+        env = "VECTOR_CONFIG_DIR",
+        value_delimiter(',')
+    )]
+    config_dirs: Vec<PathBuf>,
+}
+
+impl ConfigOpts {
+    fn paths_with_formats(&self) -> Vec<config::ConfigPath> {
+        config::merge_path_lists(vec![
+            (&self.paths, None),
+            (&self.paths_toml, Some(config::Format::Toml)),
+            (&self.paths_json, Some(config::Format::Json)),
+            (&self.paths_yaml, Some(config::Format::Yaml)),
+        ])
+        .map(|(path, hint)| config::ConfigPath::File(path, hint))
+        .chain(
+            self.config_dirs
+                .iter()
+                .map(|dir| config::ConfigPath::Dir(dir.to_path_buf())),
+        )
+        .collect()
+    }
+
+    fn load_builder(&self) -> Result<config::ConfigBuilder, exitcode::ExitCode> {
+        let paths = self.paths_with_formats();
+        let paths = config::process_paths(&paths).ok_or(exitcode::CONFIG)?;
+        config::load_builder_from_paths(&paths)
+            .map(|(builder, _)| builder)
+            .map_err(handle_config_errors)
+    }
+}
+
+#[derive(Parser, Debug)]
+#[command(rename_all = "kebab-case")]
+struct ExportOpts {
+    /// The ID of the sink whose disk buffer is exported. The sink doesn't need to be part of the
+    /// configuration anymore.
+    sink: String,
+
+    /// The directory the snapshot is written to. It must not exist, or be empty.
+    #[arg(short, long)]
+    output: PathBuf,
+
+    /// The data directory of the buffer. By default, the `data_dir` of the configuration is used.
+    #[arg(long)]
+    data_dir: Option<PathBuf>,
+
+    #[command(flatten)]
+    config: ConfigOpts,
+}
+
+#[derive(Parser, Debug)]
+#[command(rename_all = "kebab-case")]
+struct ReplayOpts {
+    /// The directory of the snapshot to replay.
+    snapshot: PathBuf,
+
+    /// The ID of the source whose events are replaced by those of the snapshot. It may be omitted
+    /// when the configuration has a single source.
+    ///
+    /// The events of the snapshot were already processed before they reached the buffer, so the
+    /// source is best connected to the sink directly. The other sources are replaced by sources
+    /// without events.
+    #[arg(long)]
+    source: Option<String>,
+
+    #[command(flatten)]
+    config: ConfigOpts,
+}
+
+/// The description of a snapshot, written at its root.
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct Manifest {
+    /// The ID of the sink the buffer was exported from.
+    component_id: String,
+    exported_at: DateTime<Utc>,
+    vector_version: String,
+    /// The size of the files of the buffer, in bytes.
+    size: u64,
+}
+
+impl Manifest {
+    fn read(snapshot: &Path) -> Result<Self, String> {
+        let path = snapshot.join(MANIFEST_FILE_NAME);
+        let manifest =
+            fs::read(&path).map_err(|error| format!("Failed to read {:?}: {}", path, error))?;
+        serde_json::from_slice(&manifest)
+            .map_err(|error| format!("Failed to parse {:?}: {}", path, error))
+    }
+
+    fn write(&self, snapshot: &Path) -> Result<(), String> {
+        let path = snapshot.join(MANIFEST_FILE_NAME);
+        let manifest = serde_json::to_vec_pretty(self).expect("manifest is serializable");
+        fs::write(&path, manifest).map_err(|error| format!("Failed to write {:?}: {}", path, error))
+    }
+}
+
+pub(crate) async fn cmd(opts: &Opts, signal_rx: SignalRx) -> exitcode::ExitCode {
+    match &opts.sub_command {
+        SubCommand::Export(opts) => export(opts),
+        SubCommand::Replay(opts) => replay(opts, signal_rx).await,
+    }
+}
+
+fn export(opts: &ExportOpts) -> exitcode::ExitCode {
+    let data_dir = match &opts.data_dir {
+        Some(data_dir) => data_dir.clone(),
+        None => match opts.config.load_builder() {
+            Ok(builder) => match builder.global.data_dir {
+                Some(data_dir) => data_dir,
+                None => {
+                    #[allow(clippy::print_stderr)]
+                    {
+                        eprintln!("The configuration has no `data_dir`, so there is no buffer to export. Use `--data-dir` to set it.");
+                    }
+                    return exitcode::CONFIG;
+                }
+            },
+            Err(code) => return code,
+        },
+    };
+
+    let is_empty = fs::read_dir(&opts.output).map_or(true, |mut entries| entries.next().is_none());
+    if !is_empty {
+        #[allow(clippy::print_stderr)]
+        {
+            eprintln!("The snapshot directory {:?} is not empty.", opts.output);
+        }
+        return exitcode::CANTCREAT;
+    }
+
+    let size = match export_disk_buffer(&data_dir, &opts.sink, &opts.output) {
+        Ok(size) => size,
+        Err(error) => {
+            #[allow(clippy::print_stderr)]
+            {
+                eprintln!("Failed to export the buffer of {:?}: {}", opts.sink, error);
+            }
+            return exitcode::IOERR;
+        }
+    };
+
+    let manifest = Manifest {
+        component_id: opts.sink.clone(),
+        exported_at: Utc::now(),
+        vector_version: get_version(),
+        size,
+    };
+    if let Err(error) = manifest.write(&opts.output) {
+        #[allow(clippy::print_stderr)]
+        {
+            eprintln!("{}", error);
+        }
+        return exitcode::IOERR;
+    }
+
+    #[allow(clippy::print_stdout)]
+    {
+        println!(
+            "Exported the buffer of {:?} ({} bytes) to {:?}",
+            opts.sink, size, opts.output
+        );
+    }
+    exitcode::OK
+}
+
+async fn replay(opts: &ReplayOpts, mut signal_rx: SignalRx) -> exitcode::ExitCode {
+    let manifest = match Manifest::read(&opts.snapshot) {
+        Ok(manifest) => manifest,
+        Err(error) => {
+            #[allow(clippy::print_stderr)]
+            {
+                eprintln!("{}", error);
+            }
+            return exitcode::NOINPUT;
+        }
+    };
+
+    let mut builder = match opts.config.load_builder() {
+        Ok(builder) => builder,
+        Err(code) => return code,
+    };
+    let source = match opts.source.as_deref().map(ComponentKey::from) {
+        Some(source) if builder.sources.contains_key(&source) => source,
+        Some(source) => {
+            return handle_config_errors(vec![format!(
+                "Source {:?} is not part of the configuration.",
+                source.id()
+            )])
+        }
+        None if builder.sources.len() == 1 => builder.sources.keys().next().unwrap().clone(),
+        None => {
+            return handle_config_errors(vec![
+                "The configuration has several sources, use `--source` to choose the one to replay the snapshot through.".to_owned(),
+            ])
+        }
+    };
+
+    let events = match open_snapshot::<EventArray>(&opts.snapshot, &manifest.component_id).await {
+        Ok(events) => events,
+        Err(error) => {
+            #[allow(clippy::print_stderr)]
+            {
+                eprintln!("Failed to open the snapshot {:?}: {}", opts.snapshot, error);
+            }
+            return exitcode::IOERR;
+        }
+    };
+    let replayed = Arc::new(AtomicU64::new(0));
+    let events = {
+        let replayed = Arc::clone(&replayed);
+        events.flat_map(move |events| {
+            replayed.fetch_add(events.len() as u64, Ordering::Relaxed);
+            stream::iter(events.into_events())
+        })
+    };
+
+    let mut events = Some(events);
+    for (key, outer) in builder.sources.iter_mut() {
+        *outer = SourceOuter::new(if *key == source {
+            UnitTestStreamSourceConfig::new(events.take().expect("source keys are unique"))
+        } else {
+            UnitTestStreamSourceConfig::default()
+        });
+    }
+
+    let config = match builder.build() {
+        Ok(config) => config,
+        Err(errors) => return handle_config_errors(errors),
+    };
+    let diff = config::ConfigDiff::initial(&config);
+    let pieces = match topology::build_or_log_errors(&config, &diff, HashMap::new()).await {
+        Some(pieces) => pieces,
+        None => return exitcode::CONFIG,
+    };
+
+    #[allow(clippy::print_stdout)]
+    {
+        println!(
+            "Replaying the buffer of {:?}, exported at {}, through {:?}...",
+            manifest.component_id,
+            manifest.exported_at,
+            source.id()
+        );
+    }
+
+    let topology = match topology::start_validated(config, diff, pieces).await {
+        Some((topology, _)) => topology,
+        None => return exitcode::CONFIG,
+    };
+    let interrupted = tokio::select! {
+        _ = topology.sources_finished() => false,
+        Ok(SignalTo::Shutdown | SignalTo::Quit) = signal_rx.recv() => true,
+    };
+    // The events still in flight are drained before the topology stops.
+    topology.stop().await;
+
+    #[allow(clippy::print_stdout)]
+    {
+        let replayed = replayed.load(Ordering::Relaxed);
+        if interrupted {
+            println!(
+                "Interrupted after replaying {} event(s). Run the replay again to resume it.",
+                replayed
+            );
+        } else {
+            println!("Replayed {} event(s).", replayed);
+        }
+    }
+    exitcode::OK
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_and_reads_manifest() {
+        let snapshot = tempfile::tempdir().unwrap();
+        let manifest = Manifest {
+            component_id: "decommissioned".to_owned(),
+            exported_at: Utc::now(),
+            vector_version: get_version(),
+            size: 1024,
+        };
+        manifest.write(snapshot.path()).unwrap();
+        assert_eq!(Manifest::read(snapshot.path()).unwrap(), manifest);
+
+        assert!(Manifest::read(&snapshot.path().join("missing")).is_err());
+    }
+}
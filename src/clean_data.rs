@@ -0,0 +1,141 @@
+use std::{fs, path::PathBuf};
+
+use clap::Parser;
+
+use crate::{config, data_dir};
+
+#[derive(Parser, Debug)]
+#[command(rename_all = "kebab-case")]
+pub struct Opts {
+    /// Read configuration from one or more files. Wildcard paths are supported.
+    /// File format is detected from the file name.
+    /// If zero files are specified the default config path
+    /// `/etc/vector/vector.toml` will be targeted.
+    #[arg(
+        id = "config",
+        short,
+        long,
+        env = "VECTOR_CONFIG",
+        value_delimiter(',')
+    )]
+    paths: Vec<PathBuf>,
+
+    /// Vector config files in TOML format.
+    #[arg(id = "config-toml", long, value_delimiter(','))]
+    paths_toml: Vec<PathBuf>,
+
+    /// Vector config files in JSON format.
+    #[arg(id = "config-json", long, value_delimiter(','))]
+    paths_json: Vec<PathBuf>,
+
+    /// Vector config files in YAML format.
+    #[arg(id = "config-yaml", long, value_delimiter(','))]
+    paths_yaml: Vec<PathBuf>,
+
+    /// Read configuration from files in one or more directories.
+    /// File format is detected from the file name.
+    ///
+    /// Files not ending in .toml, .json, .yaml, or .yml will be ignored.
+    #[arg(
+        id = "config-dir",
+        short = 'C',
+        long,
+        env = "VECTOR_CONFIG_DIR",
+        value_delimiter(',')
+    )]
+    pub config_dirs: Vec<PathBuf>,
+
+    /// Only print the paths that would be removed, without removing them.
+    #[arg(long)]
+    dry_run: bool,
+}
+
+impl Opts {
+    fn paths_with_formats(&self) -> Vec<config::ConfigPath> {
+        config::merge_path_lists(vec![
+            (&self.paths, None),
+            (&self.paths_toml, Some(config::Format::Toml)),
+            (&self.paths_json, Some(config::Format::Json)),
+            (&self.paths_yaml, Some(config::Format::Yaml)),
+        ])
+        .map(|(path, hint)| config::ConfigPath::File(path, hint))
+        .chain(
+            self.config_dirs
+                .iter()
+                .map(|dir| config::ConfigPath::Dir(dir.to_path_buf())),
+        )
+        .collect()
+    }
+}
+
+pub(crate) fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    let paths = opts.paths_with_formats();
+    let paths = match config::process_paths(&paths) {
+        Some(paths) => paths,
+        None => return exitcode::CONFIG,
+    };
+
+    let config = match config::load_from_paths(&paths) {
+        Ok(config) => config,
+        Err(errs) => {
+            #[allow(clippy::print_stderr)]
+            for err in errs {
+                eprintln!("{}", err);
+            }
+            return exitcode::CONFIG;
+        }
+    };
+
+    let data_dir = match config.global.data_dir.as_ref() {
+        Some(data_dir) => data_dir,
+        None => {
+            #[allow(clippy::print_stderr)]
+            {
+                eprintln!("The configuration has no `data_dir`, so there is no data to clean.");
+            }
+            return exitcode::CONFIG;
+        }
+    };
+
+    let component_ids = config
+        .sources()
+        .map(|(key, _)| key.id())
+        .chain(config.transforms().map(|(key, _)| key.id()))
+        .chain(config.sinks().map(|(key, _)| key.id()));
+    let orphaned = match data_dir::orphaned_paths(data_dir, component_ids) {
+        Ok(orphaned) => orphaned,
+        Err(error) => {
+            #[allow(clippy::print_stderr)]
+            {
+                eprintln!(
+                    "Failed to read the data directory {:?}: {}",
+                    data_dir, error
+                );
+            }
+            return exitcode::IOERR;
+        }
+    };
+
+    let mut code = exitcode::OK;
+    for path in orphaned {
+        if opts.dry_run {
+            #[allow(clippy::print_stdout)]
+            {
+                println!("Would remove {:?}", path);
+            }
+        } else if let Err(error) = fs::remove_dir_all(&path) {
+            #[allow(clippy::print_stderr)]
+            {
+                eprintln!("Failed to remove {:?}: {}", path, error);
+            }
+            code = exitcode::IOERR;
+        } else {
+            #[allow(clippy::print_stdout)]
+            {
+                println!("Removed {:?}", path);
+            }
+        }
+    }
+
+    code
+}
@@ -8,7 +8,10 @@ use crate::service;
 use crate::tap;
 #[cfg(feature = "api-client")]
 use crate::top;
-use crate::{config, generate, get_version, graph, list, unit_test, validate};
+use crate::{
+    benchmark, buffer_snapshot, clean_data, config, convert_config, generate, get_version, graph,
+    list, unit_test, validate,
+};
 
 #[derive(Parser, Debug)]
 #[command(rename_all = "kebab-case")]
@@ -30,7 +33,11 @@ impl Opts {
     pub const fn log_level(&self) -> &'static str {
         let (quiet_level, verbose_level) = match self.sub_command {
             Some(SubCommand::Validate(_))
+            | Some(SubCommand::Benchmark(_))
+            | Some(SubCommand::Buffer(_))
             | Some(SubCommand::Graph(_))
+            | Some(SubCommand::CleanData(_))
+            | Some(SubCommand::ConvertConfig(_))
             | Some(SubCommand::Generate(_))
             | Some(SubCommand::List(_))
             | Some(SubCommand::Test(_)) => {
@@ -148,6 +155,15 @@ pub struct RootOpts {
     #[arg(short, long, env = "VECTOR_WATCH_CONFIG")]
     pub watch_config: bool,
 
+    /// Run the configuration once, then exit.
+    ///
+    /// The sources end once they have read what is available, rather than waiting for more, and
+    /// Vector exits once their events have been processed by the sinks. All the sources must be
+    /// able to run once, such as the `file`, `exec`, `http_scrape` and `stdin` sources, and the
+    /// exit status is non-zero if a component failed.
+    #[arg(long, env = "VECTOR_ONCE")]
+    pub once: bool,
+
     /// Set the internal log rate limit
     #[arg(
         short,
@@ -156,6 +172,20 @@ pub struct RootOpts {
         default_value = "10"
     )]
     pub internal_log_rate_limit: u64,
+
+    /// Export the internal tracing spans of Vector, such as those of the build of the components,
+    /// of the requests of the sinks and of their retries, to this OpenTelemetry collector, over
+    /// OTLP/gRPC.
+    #[arg(long, env = "VECTOR_INTERNAL_TRACING_OTLP_ENDPOINT")]
+    pub internal_tracing_otlp_endpoint: Option<http::Uri>,
+
+    /// Take over from the Vector running with the same data directory, without downtime.
+    ///
+    /// The listening sockets of the running Vector are handed over to this one, which starts once
+    /// the running Vector has shut down and released its disk buffers.
+    #[cfg(unix)]
+    #[arg(long)]
+    pub upgrade: bool,
 }
 
 impl RootOpts {
@@ -194,6 +224,10 @@ pub enum SubCommand {
     /// only when concatentated together.
     GenerateSchema,
 
+    /// Convert a config file from one supported format to another, optionally merging a config
+    /// directory into a single canonical file.
+    ConvertConfig(convert_config::Opts),
+
     /// Output a provided Vector configuration file/dir as a single JSON object, useful for checking in to version control.
     #[command(hide = true)]
     Config(config::Opts),
@@ -208,6 +242,18 @@ pub enum SubCommand {
     /// Output the topology as visual representation using the DOT language which can be rendered by GraphViz
     Graph(graph::Opts),
 
+    /// Remove the data of components that are no longer part of the configuration, such as their
+    /// disk buffers and checkpoints.
+    CleanData(clean_data::Opts),
+
+    /// Run the configuration with synthetic events generated in place of its sources, then report
+    /// the throughput of its components, the latency of the events, and the CPU and memory used.
+    Benchmark(benchmark::Opts),
+
+    /// Export the disk buffer of a sink to a snapshot, or replay a snapshot through another
+    /// configuration, to recover the events stranded in the buffer of a decommissioned sink.
+    Buffer(buffer_snapshot::Opts),
+
     /// Display topology and metrics in the console, for a local or remote Vector instance
     #[cfg(feature = "api-client")]
     Top(top::Opts),
@@ -2,8 +2,17 @@
 // Datadog component type, whether it's used in integration tests, etc.
 #![allow(dead_code)]
 #![allow(unreachable_pub)]
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 use vector_config::configurable_component;
+use vector_core::event::{EventMetadata, Value};
+
+/// The path of the unit of a Datadog metric in its event metadata.
+const METRIC_UNIT_PATH: &str = "datadog.unit";
+
+/// The path of the origin of a Datadog metric in its event metadata.
+const METRIC_ORIGIN_PATH: &str = "datadog.origin";
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub(crate) struct DatadogSeriesMetric {
@@ -31,6 +40,76 @@ pub(crate) enum DatadogMetricType {
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub(crate) struct DatadogPoint<T>(pub(crate) i64, pub(crate) T);
 
+/// The properties of a Datadog metric that have no counterpart in the Vector metric model.
+///
+/// They are stored in the event metadata, under `%datadog`, so that they are carried from the
+/// `datadog_agent` source to the `datadog_metrics` sink, and can be set or changed with VRL.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct DatadogMetricMetadata {
+    /// The unit of the metric, such as `byte` or `second`.
+    pub(crate) unit: Option<String>,
+
+    /// Where the metric originates from within Datadog.
+    pub(crate) origin: Option<DatadogMetricOrigin>,
+}
+
+/// The codes of the product, category and service a Datadog metric originates from.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct DatadogMetricOrigin {
+    pub(crate) product: u32,
+    pub(crate) category: u32,
+    pub(crate) service: u32,
+}
+
+impl DatadogMetricMetadata {
+    /// Reads the Datadog properties of a metric from its event metadata.
+    ///
+    /// Properties that are missing or of the wrong type are ignored.
+    pub(crate) fn from_event_metadata(metadata: &EventMetadata) -> Self {
+        let value = metadata.value();
+        let unit = value
+            .get(METRIC_UNIT_PATH)
+            .and_then(Value::as_str)
+            .filter(|unit| !unit.is_empty())
+            .map(|unit| unit.into_owned());
+        let origin = value
+            .get(METRIC_ORIGIN_PATH)
+            .filter(|origin| origin.is_object())
+            .map(|origin| {
+                let code = |field: &str| {
+                    origin
+                        .get(field)
+                        .and_then(Value::as_integer)
+                        .and_then(|code| u32::try_from(code).ok())
+                        .unwrap_or_default()
+                };
+                DatadogMetricOrigin {
+                    product: code("product"),
+                    category: code("category"),
+                    service: code("service"),
+                }
+            });
+
+        Self { unit, origin }
+    }
+
+    /// Writes the Datadog properties of a metric to its event metadata.
+    pub(crate) fn insert_into(self, metadata: &mut EventMetadata) {
+        let value = metadata.value_mut();
+        if let Some(unit) = self.unit {
+            value.insert(METRIC_UNIT_PATH, unit);
+        }
+        if let Some(origin) = self.origin {
+            let origin = BTreeMap::from([
+                ("product".to_string(), Value::from(origin.product)),
+                ("category".to_string(), Value::from(origin.category)),
+                ("service".to_string(), Value::from(origin.service)),
+            ]);
+            value.insert(METRIC_ORIGIN_PATH, Value::Object(origin));
+        }
+    }
+}
+
 /// A Datadog region.
 #[configurable_component]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -0,0 +1,17 @@
+use crate::aws::ClientBuilder;
+
+pub(crate) struct KinesisClientBuilder;
+
+impl ClientBuilder for KinesisClientBuilder {
+    type Config = aws_sdk_kinesis::config::Config;
+    type Client = aws_sdk_kinesis::client::Client;
+    type DefaultMiddleware = aws_sdk_kinesis::middleware::DefaultMiddleware;
+
+    fn default_middleware() -> Self::DefaultMiddleware {
+        aws_sdk_kinesis::middleware::DefaultMiddleware::new()
+    }
+
+    fn build(client: aws_smithy_client::Client, config: &aws_types::SdkConfig) -> Self::Client {
+        aws_sdk_kinesis::client::Client::with_config(client, config.into())
+    }
+}
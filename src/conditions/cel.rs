@@ -0,0 +1,245 @@
+use std::{
+    cell::RefCell,
+    collections::{hash_map::Entry, HashMap},
+    sync::Arc,
+};
+
+use cel_interpreter::{Context, Program, Value as CelValue};
+use vector_config::configurable_component;
+use vector_core::event::{metric::MetricKind, Event, Value};
+
+use crate::{
+    conditions::{Condition, Conditional, ConditionalConfig},
+    emit,
+    internal_events::CelConditionExecutionError,
+};
+
+/// A condition that uses a [Common Expression Language](https://github.com/google/cel-spec) (CEL) expression against an event.
+#[configurable_component]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CelConfig {
+    /// The CEL boolean expression.
+    ///
+    /// The top-level fields of logs and traces are available as variables. Metrics expose their
+    /// `name`, `namespace`, `kind` and `tags`.
+    source: String,
+}
+
+impl_generate_config_from_default!(CelConfig);
+
+impl ConditionalConfig for CelConfig {
+    fn build(&self, _enrichment_tables: &enrichment::TableRegistry) -> crate::Result<Condition> {
+        // Compiled once here, so that invalid expressions are reported when the configuration is
+        // loaded.
+        compile(&self.source)?;
+
+        Ok(Condition::Cel(Cel {
+            source: Arc::from(self.source.as_str()),
+        }))
+    }
+}
+
+fn compile(source: &str) -> Result<Program, String> {
+    Program::compile(source).map_err(|error| format!("invalid CEL expression: {}", error))
+}
+
+thread_local! {
+    // Compiled programs can't be shared between threads, so they are compiled once per thread,
+    // keyed by their source.
+    static PROGRAMS: RefCell<HashMap<String, Program>> = RefCell::new(HashMap::new());
+}
+
+#[derive(Debug, Clone)]
+pub struct Cel {
+    source: Arc<str>,
+}
+
+impl Cel {
+    fn run(&self, event: &Event) -> Result<bool, String> {
+        PROGRAMS.with(|programs| {
+            let mut programs = programs.borrow_mut();
+            let program = match programs.entry(self.source.to_string()) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(compile(&self.source)?),
+            };
+
+            match program.execute(&context(event)) {
+                Ok(CelValue::Bool(result)) => Ok(result),
+                Ok(_) => Err("source execution resolved to a non-boolean value".to_string()),
+                Err(error) => Err(format!("source execution failed: {}", error)),
+            }
+        })
+    }
+}
+
+/// Builds the variables available to expressions.
+fn context(event: &Event) -> Context {
+    let mut context = Context::default();
+    match event {
+        Event::Log(log) => {
+            if let Some(fields) = log.as_map() {
+                for (name, value) in fields {
+                    context.add_variable(name.clone(), to_cel(value));
+                }
+            }
+        }
+        Event::Trace(trace) => {
+            for (name, value) in trace.as_map() {
+                context.add_variable(name.clone(), to_cel(value));
+            }
+        }
+        Event::Metric(metric) => {
+            context.add_variable("name", metric.name().to_string());
+            context.add_variable(
+                "namespace",
+                metric
+                    .namespace()
+                    .map_or(CelValue::Null, |namespace| namespace.to_string().into()),
+            );
+            context.add_variable(
+                "kind",
+                match metric.kind() {
+                    MetricKind::Incremental => "incremental",
+                    MetricKind::Absolute => "absolute",
+                }
+                .to_string(),
+            );
+            let tags = metric
+                .tags()
+                .map(|tags| {
+                    tags.iter()
+                        .map(|(key, value)| (key.to_string(), CelValue::from(value.clone())))
+                        .collect::<HashMap<_, _>>()
+                })
+                .unwrap_or_default();
+            context.add_variable("tags", tags);
+        }
+    }
+    context
+}
+
+fn to_cel(value: &Value) -> CelValue {
+    match value {
+        Value::Bytes(bytes) => String::from_utf8_lossy(bytes).into_owned().into(),
+        Value::Regex(regex) => regex.as_str().to_string().into(),
+        Value::Integer(integer) => (*integer).into(),
+        Value::Float(float) => float.into_inner().into(),
+        Value::Boolean(boolean) => (*boolean).into(),
+        Value::Timestamp(timestamp) => timestamp.to_rfc3339().into(),
+        Value::Object(map) => map
+            .iter()
+            .map(|(key, value)| (key.clone(), to_cel(value)))
+            .collect::<HashMap<_, _>>()
+            .into(),
+        Value::Array(array) => array.iter().map(to_cel).collect::<Vec<_>>().into(),
+        Value::Null => CelValue::Null,
+    }
+}
+
+impl Conditional for Cel {
+    fn check(&self, event: Event) -> (bool, Event) {
+        let result = self.run(&event).unwrap_or_else(|error| {
+            emit!(CelConditionExecutionError { error: &error });
+            false
+        });
+        (result, event)
+    }
+
+    fn check_with_context(&self, event: Event) -> (Result<(), String>, Event) {
+        let result = match self.run(&event) {
+            Ok(true) => Ok(()),
+            Ok(false) => Err("source execution resolved to false".to_string()),
+            Err(error) => Err(error),
+        };
+        (result, event)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        event::{Metric, MetricValue},
+        log_event,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<CelConfig>();
+    }
+
+    fn check(source: &str, event: Event) -> Result<(), String> {
+        let config = CelConfig {
+            source: source.to_string(),
+        };
+        let condition = config.build(&Default::default()).unwrap();
+        condition.check_with_context(event).0
+    }
+
+    #[test]
+    fn check_logs() {
+        let event = log_event![
+            "level" => "error",
+            "status" => 503,
+            "request" => serde_json::json!({ "method": "GET", "path": "/health" }),
+            "tags" => vec!["canary", "eu"],
+        ];
+
+        assert_eq!(
+            check(r#"level == "error" && status >= 500"#, event.clone()),
+            Ok(())
+        );
+        assert_eq!(
+            check(
+                r#"request.method == "GET" && "canary" in tags"#,
+                event.clone()
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            check(r#"request.path == "/api""#, event.clone()),
+            Err("source execution resolved to false".to_string())
+        );
+        assert_eq!(
+            check("status", event),
+            Err("source execution resolved to a non-boolean value".to_string())
+        );
+    }
+
+    #[test]
+    fn check_metrics() {
+        let event = Event::from(
+            Metric::new(
+                "requests_total",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 1.0 },
+            )
+            .with_namespace(Some("app"))
+            .with_tags(Some(
+                [("host".into(), "a".to_string())].into_iter().collect(),
+            )),
+        );
+
+        assert_eq!(
+            check(
+                r#"name == "requests_total" && namespace == "app" && kind == "incremental" && tags.host == "a""#,
+                event
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn check_undeclared_variable() {
+        assert!(check("missing == 1", log_event!["level" => "info"]).is_err());
+    }
+
+    #[test]
+    fn invalid_expression() {
+        let config = CelConfig {
+            source: "level ==".to_string(),
+        };
+
+        assert!(config.build(&Default::default()).is_err());
+    }
+}
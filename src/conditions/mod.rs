@@ -2,6 +2,7 @@ use vector_config::configurable_component;
 
 use crate::event::Event;
 
+mod cel;
 mod check_fields;
 pub(self) mod datadog_search;
 pub(crate) mod is_log;
@@ -11,6 +12,7 @@ mod vrl;
 
 pub use self::vrl::VrlConfig;
 use self::{
+    cel::{Cel, CelConfig},
     check_fields::{CheckFields, CheckFieldsConfig},
     datadog_search::{DatadogSearchConfig, DatadogSearchRunner},
     is_log::{check_is_log, check_is_log_with_context},
@@ -40,6 +42,9 @@ pub enum Condition {
     /// Matches an event with a [Datadog Search](https://docs.datadoghq.com/logs/explorer/search_syntax/) query.
     DatadogSearch(DatadogSearchRunner),
 
+    /// Matches an event with a [Common Expression Language](https://github.com/google/cel-spec) (CEL) boolean expression.
+    Cel(Cel),
+
     /// Matches any event.
     ///
     /// Used only for internal testing.
@@ -64,6 +69,7 @@ impl Condition {
             Condition::Vrl(x) => x.check(e),
             Condition::CheckFields(x) => x.check(e),
             Condition::DatadogSearch(x) => x.check(e),
+            Condition::Cel(x) => x.check(e),
             Condition::AlwaysPass => (true, e),
             Condition::AlwaysFail => (false, e),
         }
@@ -81,6 +87,7 @@ impl Condition {
             Condition::Vrl(x) => x.check_with_context(e),
             Condition::CheckFields(x) => x.check_with_context(e),
             Condition::DatadogSearch(x) => x.check_with_context(e),
+            Condition::Cel(x) => x.check_with_context(e),
             Condition::AlwaysPass => (Ok(()), e),
             Condition::AlwaysFail => (Ok(()), e),
         }
@@ -119,6 +126,9 @@ pub enum ConditionConfig {
 
     /// Matches an event with a [Datadog Search](https://docs.datadoghq.com/logs/explorer/search_syntax/) query.
     DatadogSearch(#[configurable(derived)] DatadogSearchConfig),
+
+    /// Matches an event with a [Common Expression Language](https://github.com/google/cel-spec) (CEL) boolean expression.
+    Cel(#[configurable(derived)] CelConfig),
 }
 
 impl ConditionConfig {
@@ -130,6 +140,7 @@ impl ConditionConfig {
             ConditionConfig::Vrl(x) => x.build(enrichment_tables),
             ConditionConfig::CheckFields(x) => x.build(enrichment_tables),
             ConditionConfig::DatadogSearch(x) => x.build(enrichment_tables),
+            ConditionConfig::Cel(x) => x.build(enrichment_tables),
         }
     }
 }
@@ -1,10 +1,13 @@
 use std::net::{Ipv4Addr, SocketAddr};
 
+use vector_common::sensitive_string::SensitiveString;
 use vector_config::configurable_component;
 
+use crate::tls::TlsEnableableConfig;
+
 /// API options.
 #[configurable_component]
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 #[serde(default, deny_unknown_fields)]
 pub struct Options {
     /// Whether or not the API endpoint is available.
@@ -18,6 +21,30 @@ pub struct Options {
     /// Whether or not to expose the GraphQL playground on the API endpoint.
     #[serde(default = "default_playground")]
     pub playground: bool,
+
+    /// A token that enables the mutations of the API, such as pausing and resuming components.
+    ///
+    /// Mutations are rejected unless they are sent with this token as a bearer token, in the
+    /// `Authorization` header. Without a token, the mutations are disabled.
+    #[serde(default)]
+    pub control_token: Option<SensitiveString>,
+
+    /// A token that is required to access the GraphQL endpoint.
+    ///
+    /// Requests to the GraphQL endpoint are rejected unless they are sent with this token as a
+    /// bearer token, in the `Authorization` header. Subscriptions can also send it as the `token`
+    /// field of the payload of their `connection_init` message. The health endpoint and the
+    /// playground remain accessible without a token.
+    #[serde(default)]
+    pub auth_token: Option<SensitiveString>,
+
+    /// TLS options for the API endpoint.
+    ///
+    /// Clients are authenticated with mutual TLS by enabling `verify_certificate`, along with a
+    /// `ca_file` to verify their certificates against.
+    #[configurable(derived)]
+    #[serde(default)]
+    pub tls: Option<TlsEnableableConfig>,
 }
 
 impl Default for Options {
@@ -26,6 +53,9 @@ impl Default for Options {
             enabled: default_enabled(),
             playground: default_playground(),
             address: default_address(),
+            control_token: None,
+            auth_token: None,
+            tls: None,
         }
     }
 }
@@ -66,10 +96,35 @@ impl Options {
             }
         };
 
+        // Try to merge control token
+        let control_token = match (&self.control_token, other.control_token) {
+            (Some(a), Some(b)) if *a != b => {
+                return Err("Conflicting `api` control token.".to_owned())
+            }
+            (a, b) => a.clone().or(b),
+        };
+
+        // Try to merge auth token
+        let auth_token = match (&self.auth_token, other.auth_token) {
+            (Some(a), Some(b)) if *a != b => return Err("Conflicting `api` auth token.".to_owned()),
+            (a, b) => a.clone().or(b),
+        };
+
+        // Try to merge TLS options
+        let tls = match (&self.tls, other.tls) {
+            (Some(a), Some(b)) if *a != b => {
+                return Err("Conflicting `api` TLS options.".to_owned())
+            }
+            (a, b) => a.clone().or(b),
+        };
+
         let options = Options {
             address,
             enabled: self.enabled | other.enabled,
             playground: self.playground & other.playground,
+            control_token,
+            auth_token,
+            tls,
         };
 
         *self = options;
@@ -83,6 +138,9 @@ fn bool_merge() {
         enabled: true,
         address: None,
         playground: false,
+        control_token: None,
+        auth_token: None,
+        tls: None,
     };
 
     a.merge(Options::default()).unwrap();
@@ -93,6 +151,9 @@ fn bool_merge() {
             enabled: true,
             address: default_address(),
             playground: false,
+            control_token: None,
+            auth_token: None,
+            tls: None,
         }
     );
 }
@@ -104,6 +165,9 @@ fn bind_merge() {
         enabled: true,
         address: Some(address),
         playground: true,
+        control_token: None,
+        auth_token: None,
+        tls: None,
     };
 
     a.merge(Options::default()).unwrap();
@@ -114,6 +178,9 @@ fn bind_merge() {
             enabled: true,
             address: Some(address),
             playground: true,
+            control_token: None,
+            auth_token: None,
+            tls: None,
         }
     );
 }
@@ -132,3 +199,57 @@ fn bind_conflict() {
 
     assert!(a.merge(b).is_err());
 }
+
+#[test]
+fn control_token_conflict() {
+    let mut a = Options {
+        control_token: Some("a".to_owned().into()),
+        ..Options::default()
+    };
+
+    a.merge(Options::default()).unwrap();
+    assert_eq!(a.control_token, Some("a".to_owned().into()));
+
+    let b = Options {
+        control_token: Some("b".to_owned().into()),
+        ..Options::default()
+    };
+
+    assert!(a.merge(b).is_err());
+}
+
+#[test]
+fn auth_token_conflict() {
+    let mut a = Options {
+        auth_token: Some("a".to_owned().into()),
+        ..Options::default()
+    };
+
+    a.merge(Options::default()).unwrap();
+    assert_eq!(a.auth_token, Some("a".to_owned().into()));
+
+    let b = Options {
+        auth_token: Some("b".to_owned().into()),
+        ..Options::default()
+    };
+
+    assert!(a.merge(b).is_err());
+}
+
+#[test]
+fn tls_merge() {
+    let mut a = Options {
+        tls: Some(TlsEnableableConfig::enabled()),
+        ..Options::default()
+    };
+
+    a.merge(Options::default()).unwrap();
+    assert_eq!(a.tls, Some(TlsEnableableConfig::enabled()));
+
+    let b = Options {
+        tls: Some(TlsEnableableConfig::test_config()),
+        ..Options::default()
+    };
+
+    assert!(a.merge(b).is_err());
+}
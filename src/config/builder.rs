@@ -18,8 +18,10 @@ use super::api;
 #[cfg(feature = "enterprise")]
 use super::enterprise;
 use super::{
-    compiler, schema, ComponentKey, Config, EnrichmentTableOuter, HealthcheckOptions, SinkOuter,
-    SourceOuter, TestDefinition, TransformOuter,
+    compiler,
+    pipeline::{PipelineOuter, PipelineTemplate},
+    schema, ComponentKey, Config, EnrichmentTableOuter, HealthcheckOptions, SinkOuter, SourceOuter,
+    TestDefinition, TransformOuter,
 };
 
 /// A complete Vector configuration.
@@ -64,6 +66,20 @@ pub struct ConfigBuilder {
     #[serde(default)]
     pub transforms: IndexMap<ComponentKey, TransformOuter<String>>,
 
+    /// All configured pipeline templates.
+    ///
+    /// A template is a reusable, parameterized group of transforms that can be instantiated any
+    /// number of times from `pipelines`.
+    #[serde(default)]
+    pub templates: IndexMap<String, PipelineTemplate>,
+
+    /// All configured pipelines.
+    ///
+    /// Each pipeline is an instance of a template, and is expanded into the transforms of that
+    /// template when the configuration is built.
+    #[serde(default)]
+    pub pipelines: IndexMap<ComponentKey, PipelineOuter>,
+
     /// All configured unit tests.
     #[serde(default)]
     pub tests: Vec<TestDefinition<String>>,
@@ -92,6 +108,10 @@ struct ConfigBuilderHash<'a> {
     sources: BTreeMap<&'a ComponentKey, &'a SourceOuter>,
     sinks: BTreeMap<&'a ComponentKey, &'a SinkOuter<String>>,
     transforms: BTreeMap<&'a ComponentKey, &'a TransformOuter<String>>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    templates: BTreeMap<&'a String, &'a PipelineTemplate>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pipelines: BTreeMap<&'a ComponentKey, &'a PipelineOuter>,
     tests: &'a Vec<TestDefinition<String>>,
     provider: &'a Option<Providers>,
     secret: BTreeMap<&'a ComponentKey, &'a SecretBackends>,
@@ -171,6 +191,8 @@ impl<'a> From<&'a ConfigBuilder> for ConfigBuilderHash<'a> {
             sources: value.sources.iter().collect(),
             sinks: value.sinks.iter().collect(),
             transforms: value.transforms.iter().collect(),
+            templates: value.templates.iter().collect(),
+            pipelines: value.pipelines.iter().collect(),
             tests: &value.tests,
             provider: &value.provider,
             secret: value.secret.iter().collect(),
@@ -196,6 +218,7 @@ impl From<Config> for ConfigBuilder {
             secret,
             hash: _,
             expansions: _,
+            once: _,
         } = config;
 
         let transforms = transforms
@@ -222,6 +245,8 @@ impl From<Config> for ConfigBuilder {
             sources,
             sinks,
             transforms,
+            templates: IndexMap::new(),
+            pipelines: IndexMap::new(),
             provider: None,
             tests,
             secret,
@@ -351,6 +376,16 @@ impl ConfigBuilder {
                 errors.push(format!("duplicate transform id found: {}", k));
             }
         });
+        with.templates.keys().for_each(|k| {
+            if self.templates.contains_key(k) {
+                errors.push(format!("duplicate template name found: {}", k));
+            }
+        });
+        with.pipelines.keys().for_each(|k| {
+            if self.pipelines.contains_key(k) {
+                errors.push(format!("duplicate pipeline id found: {}", k));
+            }
+        });
         with.tests.iter().for_each(|wt| {
             if self.tests.iter().any(|t| t.name == wt.name) {
                 errors.push(format!("duplicate test name found: {}", wt.name));
@@ -369,6 +404,8 @@ impl ConfigBuilder {
         self.sources.extend(with.sources);
         self.sinks.extend(with.sinks);
         self.transforms.extend(with.transforms);
+        self.templates.extend(with.templates);
+        self.pipelines.extend(with.pipelines);
         self.tests.extend(with.tests);
         self.secret.extend(with.secret);
 
@@ -3,8 +3,8 @@ use std::collections::HashSet;
 use indexmap::{IndexMap, IndexSet};
 
 use super::{
-    builder::ConfigBuilder, graph::Graph, schema, validation, ComponentKey, Config, OutputId,
-    SourceConfig, TransformConfig,
+    builder::ConfigBuilder, graph::Graph, pipeline, schema, validation, ComponentKey, Config,
+    OutputId, SourceConfig, TransformConfig,
 };
 
 /// to handle the expansions when building the graph we need to be able to get the list of inputs
@@ -33,12 +33,20 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
             .transforms
             .keys()
             .chain(builder.sources.keys())
-            .chain(builder.sinks.keys()),
+            .chain(builder.sinks.keys())
+            .chain(builder.pipelines.keys()),
     ) {
         errors.extend(name_errors);
     }
 
-    let expansions = expand_macros(&mut builder)?;
+    let mut expansions = match pipeline::expand_pipelines(&mut builder) {
+        Ok(expansions) => expansions,
+        Err(pipeline_errors) => {
+            errors.extend(pipeline_errors);
+            return Err(errors);
+        }
+    };
+    expansions.extend(expand_macros(&mut builder)?);
 
     expand_globs(&mut builder);
 
@@ -54,6 +62,10 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
         errors.extend(output_errors);
     }
 
+    if let Err(error) = builder.global.utilization.validate() {
+        errors.push(error);
+    }
+
     #[cfg(feature = "enterprise")]
     let hash = Some(builder.sha256_hash());
 
@@ -73,6 +85,8 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
         sinks,
         transforms,
         tests,
+        templates: _,
+        pipelines: _,
         provider: _,
         secret,
     } = builder;
@@ -132,6 +146,7 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
             tests,
             expansions,
             secret,
+            once: false,
         };
 
         config.propagate_acknowledgements()?;
@@ -2,9 +2,9 @@
 
 #![deny(missing_docs, missing_debug_implementations)]
 
-use std::path::Path;
+use std::{path::Path, str::FromStr};
 
-use serde::de;
+use serde::{de, Serialize};
 
 /// A type alias to better capture the semantics.
 pub type FormatHint = Option<Format>;
@@ -36,6 +36,31 @@ impl Format {
             _ => Err(path),
         }
     }
+
+    /// Returns the canonical file extension for the format.
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Format::Toml => "toml",
+            Format::Json => "json",
+            Format::Yaml => "yaml",
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "toml" => Ok(Format::Toml),
+            "yaml" | "yml" => Ok(Format::Yaml),
+            "json" => Ok(Format::Json),
+            _ => Err(format!(
+                "Invalid format: {}. Expected one of: toml, yaml, json.",
+                s
+            )),
+        }
+    }
 }
 
 /// Parse the string represented in the specified format.
@@ -52,6 +77,18 @@ where
     }
 }
 
+/// Serialize the value into the specified format.
+pub fn serialize<T>(value: &T, format: Format) -> Result<String, String>
+where
+    T: Serialize,
+{
+    match format {
+        Format::Toml => toml::to_string_pretty(value).map_err(|e| e.to_string()),
+        Format::Yaml => serde_yaml::to_string(value).map_err(|e| e.to_string()),
+        Format::Json => serde_json::to_string_pretty(value).map_err(|e| e.to_string()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -116,6 +153,41 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_from_str() {
+        let cases = vec![
+            ("toml", Some(Format::Toml)),
+            ("TOML", Some(Format::Toml)),
+            ("yaml", Some(Format::Yaml)),
+            ("yml", Some(Format::Yaml)),
+            ("json", Some(Format::Json)),
+            ("Json", Some(Format::Json)),
+            ("", None),
+            ("xml", None),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(expected, input.parse::<Format>().ok(), "{}", input)
+        }
+    }
+
+    #[test]
+    fn test_serialize_round_trip() {
+        let mut table = toml::value::Table::new();
+        table.insert("data_dir".into(), "/var/lib/vector".into());
+        let mut source = toml::value::Table::new();
+        source.insert("type".into(), "stdin".into());
+        let mut sources = toml::value::Table::new();
+        sources.insert("in".into(), source.into());
+        table.insert("sources".into(), sources.into());
+
+        for format in [Format::Toml, Format::Yaml, Format::Json] {
+            let serialized = serialize(&table, format).unwrap();
+            let deserialized: toml::value::Table = deserialize(&serialized, format).unwrap();
+            assert_eq!(table, deserialized, "{:?}", format);
+        }
+    }
+
     // Here we test that the deserializations from various formats match
     // the TOML format.
     #[cfg(all(
@@ -1,7 +1,10 @@
 use std::io::Read;
 
 use serde_toml_merge::merge_into_table;
-use toml::{map::Map, value::Table};
+use toml::{
+    map::Map,
+    value::{Table, Value},
+};
 
 use super::{ComponentHint, Loader, Process};
 
@@ -27,8 +30,25 @@ impl Process for SourceLoader {
         Ok((source_string, vec![]))
     }
 
-    /// Merge values by combining with the internal TOML `Table`.
-    fn merge(&mut self, table: Table, _hint: Option<ComponentHint>) -> Result<(), Vec<String>> {
+    /// Merge values by combining with the internal TOML `Table`. Component tables loaded from
+    /// a component sub-folder are nested under their component type first.
+    fn merge(&mut self, table: Table, hint: Option<ComponentHint>) -> Result<(), Vec<String>> {
+        let table = match hint {
+            Some(hint) => {
+                let value = match hint {
+                    // Tests are represented as an array, so the file names are discarded.
+                    ComponentHint::Test => {
+                        Value::Array(table.into_iter().map(|(_, v)| v).collect())
+                    }
+                    _ => Value::Table(table),
+                };
+                let mut wrapper = Map::new();
+                wrapper.insert(hint.as_component_field().to_owned(), value);
+                wrapper
+            }
+            None => table,
+        };
+
         merge_into_table(&mut self.table, table).map_err(|e| vec![e.to_string()])
     }
 }
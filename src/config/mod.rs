@@ -25,6 +25,7 @@ pub mod format;
 mod graph;
 mod id;
 pub(crate) mod loading;
+mod pipeline;
 pub mod provider;
 mod schema;
 mod secret;
@@ -47,15 +48,17 @@ pub use loading::{
     load, load_builder_from_paths, load_from_paths, load_from_paths_with_provider_and_secrets,
     load_from_str, load_source_from_paths, merge_path_lists, process_paths, CONFIG_PATHS,
 };
+pub use pipeline::{PipelineOuter, PipelineTemplate, TemplateParameter};
 pub use provider::ProviderConfig;
 pub use secret::SecretBackend;
 pub use sink::{SinkConfig, SinkContext, SinkHealthcheckOptions, SinkOuter};
 pub use source::{SourceConfig, SourceContext, SourceOuter};
 pub use transform::{
     InnerTopology, InnerTopologyTransform, TransformConfig, TransformContext, TransformOuter,
+    TransformPartitioningOptions, TransformSchedulingOptions,
 };
 pub use unit_test::{build_unit_tests, build_unit_tests_main, UnitTestResult};
-pub use validation::warnings;
+pub use validation::{check_run_once, warnings};
 pub use vector_core::config::{log_schema, proxy::ProxyConfig, LogSchema};
 
 /// Loads Log Schema from configurations and sets global schema.
@@ -113,6 +116,10 @@ pub struct Config {
     tests: Vec<TestDefinition>,
     expansions: IndexMap<ComponentKey, Vec<ComponentKey>>,
     secret: IndexMap<ComponentKey, SecretBackends>,
+
+    /// Whether Vector runs once, as set by the `--once` command-line flag, in which case the
+    /// sources end once they have read what is available.
+    pub once: bool,
 }
 
 impl Config {
@@ -567,7 +574,7 @@ pub struct TestOutput<T = OutputId> {
 
 #[cfg(all(test, feature = "sources-file", feature = "sinks-console"))]
 mod tests {
-    use std::{collections::HashMap, path::PathBuf};
+    use std::{collections::HashMap, path::PathBuf, time::Duration};
 
     use crate::{config, topology};
     use indoc::indoc;
@@ -844,6 +851,53 @@ mod tests {
         )
     }
 
+    #[test]
+    fn utilization_options() {
+        let config = load_from_str(
+            indoc! {r#"
+                [utilization]
+                report_interval_secs = 1
+                ewma_alpha = 0.3
+
+                [sources.in]
+                type = "test_basic"
+
+                [sinks.out]
+                type = "test_basic"
+                inputs = ["in"]
+            "#},
+            Format::Toml,
+        )
+        .unwrap();
+
+        assert_eq!(
+            Duration::from_secs(1),
+            config.global.utilization.report_interval()
+        );
+        assert_eq!(0.3, config.global.utilization.ewma_alpha());
+
+        let errors = load_from_str(
+            indoc! {r#"
+                [utilization]
+                ewma_alpha = 0.0
+
+                [sources.in]
+                type = "test_basic"
+
+                [sinks.out]
+                type = "test_basic"
+                inputs = ["in"]
+            "#},
+            Format::Toml,
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            vec!["'utilization.ewma_alpha' must be greater than 0, and up to 1, found 0"],
+            errors
+        );
+    }
+
     #[test]
     fn default_schema() {
         let config = load_from_str(
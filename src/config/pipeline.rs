@@ -0,0 +1,558 @@
+//! Reusable, parameterized groups of transforms.
+//!
+//! A template is defined once under `templates` and can be instantiated any number of times under
+//! `pipelines`, each instance providing its own inputs and parameter values:
+//!
+//! ```toml
+//! [templates.parse_nginx.params]
+//! service = {}
+//! field = { default = "message" }
+//!
+//! [templates.parse_nginx.transforms.parse]
+//! type = "remap"
+//! source = '. = parse_nginx_log!(.{{ params.field }}, "combined")'
+//!
+//! [templates.parse_nginx.transforms.tag]
+//! type = "remap"
+//! inputs = ["parse"]
+//! source = '.service = "{{ params.service }}"'
+//!
+//! [pipelines.team_a]
+//! template = "parse_nginx"
+//! inputs = ["team_a_logs"]
+//! params.service = "team-a"
+//! ```
+//!
+//! Each pipeline expands into one transform per template transform, named
+//! `<pipeline>_<transform>` (here `team_a_parse` and `team_a_tag`). Template transforms without
+//! `inputs` receive the inputs of the pipeline, and inputs referring to another transform of the
+//! same template are rewritten to point at the expanded transform. Downstream components use the
+//! name of the pipeline (`team_a`) as an input, which resolves to the outputs of the template.
+
+use indexmap::IndexMap;
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use vector_config::configurable_component;
+
+use super::{builder::ConfigBuilder, ComponentKey, TransformOuter};
+
+static PARAM_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\{\{\s*params\.(\w+)\s*\}\}").expect("invalid regex"));
+
+/// The transforms a pipeline expands into, along with the keys of its outputs.
+type Expansion = (
+    Vec<(ComponentKey, TransformOuter<String>)>,
+    Vec<ComponentKey>,
+);
+
+/// A parameter accepted by a pipeline template.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct TemplateParameter {
+    /// The value used when a pipeline does not set the parameter.
+    ///
+    /// Parameters without a default value must be set by every pipeline using the template.
+    pub default: Option<String>,
+}
+
+/// A reusable, parameterized group of transforms.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct PipelineTemplate {
+    /// The parameters accepted by the template.
+    ///
+    /// Parameters can be referenced from any string value of the template transforms as
+    /// `{{ params.<name> }}`.
+    #[serde(default)]
+    pub params: IndexMap<String, TemplateParameter>,
+
+    /// The transforms making up the template, keyed by their name within the template.
+    ///
+    /// Transforms are only parsed once the template is instantiated, as their configuration may
+    /// contain parameter references.
+    #[serde(default)]
+    pub transforms: IndexMap<String, toml::Value>,
+
+    /// The transforms of the template whose output is exposed as the output of the pipeline.
+    ///
+    /// A specific output of a transform can be selected with `<transform>.<output>`. By default,
+    /// every transform that is not used as an input by another transform of the template is an
+    /// output.
+    pub outputs: Option<Vec<String>>,
+}
+
+/// An instance of a pipeline template.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct PipelineOuter {
+    /// The name of the template to instantiate.
+    pub template: String,
+
+    /// Inputs to the pipeline.
+    ///
+    /// These are used as the inputs of every template transform that does not declare its own.
+    #[serde(default)]
+    pub inputs: Vec<String>,
+
+    /// The values of the template parameters.
+    #[serde(default)]
+    pub params: IndexMap<String, String>,
+}
+
+impl PipelineOuter {
+    /// Expands the pipeline into the transforms of the given template, returning them along with
+    /// the keys of the template outputs.
+    fn expand(
+        &self,
+        key: &ComponentKey,
+        template: &PipelineTemplate,
+    ) -> Result<Expansion, Vec<String>> {
+        let params = self.resolve_params(template)?;
+        let mut errors = Vec::new();
+
+        let expanded_input = |input: &str| -> String {
+            let (name, port) = match input.split_once('.') {
+                Some((name, port)) => (name, Some(port)),
+                None => (input, None),
+            };
+            if !template.transforms.contains_key(name) {
+                return input.to_string();
+            }
+            match port {
+                Some(port) => format!("{}_{}.{}", key.id(), name, port),
+                None => format!("{}_{}", key.id(), name),
+            }
+        };
+
+        let mut transforms = Vec::new();
+        for (name, value) in &template.transforms {
+            let value = match interpolate_params(value.clone(), &params) {
+                Ok(value) => value,
+                Err(errs) => {
+                    errors.extend(
+                        errs.into_iter()
+                            .map(|error| format!("Transform \"{}\": {}", name, error)),
+                    );
+                    continue;
+                }
+            };
+
+            match value.try_into::<TransformOuter<String>>() {
+                Ok(mut transform) => {
+                    transform.inputs = if transform.inputs.is_empty() {
+                        self.inputs.clone()
+                    } else {
+                        transform
+                            .inputs
+                            .iter()
+                            .map(|input| expanded_input(input))
+                            .collect()
+                    };
+                    let expanded_key = ComponentKey::from(format!("{}_{}", key.id(), name));
+                    transforms.push((expanded_key, transform));
+                }
+                Err(error) => errors.push(format!("Transform \"{}\": {}", name, error)),
+            }
+        }
+
+        let outputs = match &template.outputs {
+            Some(outputs) => {
+                for output in outputs {
+                    let name = output.split_once('.').map_or(output.as_str(), |(n, _)| n);
+                    if !template.transforms.contains_key(name) {
+                        errors.push(format!(
+                            "Output \"{}\" doesn't match any transform of the template.",
+                            output
+                        ));
+                    }
+                }
+                outputs
+                    .iter()
+                    .map(|output| ComponentKey::from(expanded_input(output)))
+                    .collect()
+            }
+            None => transforms
+                .iter()
+                .map(|(key, _)| key)
+                .filter(|candidate| {
+                    !transforms.iter().any(|(_, transform)| {
+                        transform.inputs.iter().any(|input| {
+                            input == candidate.id()
+                                || input.starts_with(&format!("{}.", candidate.id()))
+                        })
+                    })
+                })
+                .cloned()
+                .collect(),
+        };
+
+        if errors.is_empty() {
+            Ok((transforms, outputs))
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Merges the parameters set by the pipeline with the template defaults.
+    fn resolve_params(
+        &self,
+        template: &PipelineTemplate,
+    ) -> Result<IndexMap<String, String>, Vec<String>> {
+        let mut errors = Vec::new();
+
+        for name in self.params.keys() {
+            if !template.params.contains_key(name) {
+                errors.push(format!("Unknown parameter \"{}\".", name));
+            }
+        }
+
+        let mut params = IndexMap::new();
+        for (name, param) in &template.params {
+            match self.params.get(name).or(param.default.as_ref()) {
+                Some(value) => {
+                    params.insert(name.clone(), value.clone());
+                }
+                None => errors.push(format!("Missing required parameter \"{}\".", name)),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(params)
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Replaces every `{{ params.<name> }}` reference found in the string values of `value`.
+fn interpolate_params(
+    value: toml::Value,
+    params: &IndexMap<String, String>,
+) -> Result<toml::Value, Vec<String>> {
+    let mut errors = Vec::new();
+    let value = interpolate_value(value, params, &mut errors);
+    if errors.is_empty() {
+        Ok(value)
+    } else {
+        Err(errors)
+    }
+}
+
+fn interpolate_value(
+    value: toml::Value,
+    params: &IndexMap<String, String>,
+    errors: &mut Vec<String>,
+) -> toml::Value {
+    match value {
+        toml::Value::String(s) => toml::Value::String(
+            PARAM_REGEX
+                .replace_all(&s, |caps: &Captures<'_>| match params.get(&caps[1]) {
+                    Some(value) => value.clone(),
+                    None => {
+                        errors.push(format!("Unknown parameter \"{}\" referenced.", &caps[1]));
+                        caps[0].to_string()
+                    }
+                })
+                .into_owned(),
+        ),
+        toml::Value::Array(values) => toml::Value::Array(
+            values
+                .into_iter()
+                .map(|value| interpolate_value(value, params, errors))
+                .collect(),
+        ),
+        toml::Value::Table(table) => toml::Value::Table(
+            table
+                .into_iter()
+                .map(|(key, value)| (key, interpolate_value(value, params, errors)))
+                .collect(),
+        ),
+        value => value,
+    }
+}
+
+/// Expands every pipeline of the builder into the transforms of its template.
+///
+/// Returns the expansions mapping each pipeline to the outputs of its template, so that the
+/// pipeline name can be used as an input by downstream components.
+pub(super) fn expand_pipelines(
+    builder: &mut ConfigBuilder,
+) -> Result<IndexMap<ComponentKey, Vec<ComponentKey>>, Vec<String>> {
+    let mut expansions = IndexMap::new();
+    let mut errors = Vec::new();
+
+    for (key, pipeline) in std::mem::take(&mut builder.pipelines) {
+        if builder.sources.contains_key(&key)
+            || builder.transforms.contains_key(&key)
+            || builder.sinks.contains_key(&key)
+        {
+            errors.push(format!(
+                "Pipeline \"{}\" has the same name as an existing component.",
+                key
+            ));
+            continue;
+        }
+
+        let template = match builder.templates.get(&pipeline.template) {
+            Some(template) => template,
+            None => {
+                errors.push(format!(
+                    "Pipeline \"{}\" references unknown template \"{}\".",
+                    key, pipeline.template
+                ));
+                continue;
+            }
+        };
+
+        match pipeline.expand(&key, template) {
+            Ok((transforms, outputs)) => {
+                for (transform_key, transform) in transforms {
+                    if builder.sources.contains_key(&transform_key)
+                        || builder.transforms.contains_key(&transform_key)
+                        || builder.sinks.contains_key(&transform_key)
+                    {
+                        errors.push(format!(
+                            "Pipeline \"{}\" expands to transform \"{}\", which has the same name as an existing component.",
+                            key, transform_key
+                        ));
+                    } else {
+                        builder.transforms.insert(transform_key, transform);
+                    }
+                }
+                expansions.insert(key, outputs);
+            }
+            Err(errs) => errors.extend(
+                errs.into_iter()
+                    .map(|error| format!("Pipeline \"{}\": {}", key, error)),
+            ),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(expansions)
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+    use crate::config::{format, load_from_str, Format, OutputId};
+
+    fn output_id(id: &str) -> OutputId {
+        OutputId::from(ComponentKey::from(id))
+    }
+
+    const TEMPLATE: &str = indoc! {r#"
+        [sources.in_a]
+        type = "test_basic"
+
+        [sources.in_b]
+        type = "test_basic"
+
+        [templates.suffix.params]
+        first = {}
+        second = { default = "-default" }
+
+        [templates.suffix.transforms.one]
+        type = "test_basic"
+        suffix = "{{ params.first }}"
+        increase = 1.0
+
+        [templates.suffix.transforms.two]
+        type = "test_basic"
+        inputs = ["one"]
+        suffix = "{{params.second}}"
+        increase = 2.0
+    "#};
+
+    #[test]
+    fn expands_pipelines() {
+        let config = load_from_str(
+            &format!(
+                "{}{}",
+                TEMPLATE,
+                indoc! {r#"
+                    [pipelines.a]
+                    template = "suffix"
+                    inputs = ["in_a"]
+                    params.first = "-a"
+
+                    [pipelines.b]
+                    template = "suffix"
+                    inputs = ["in_b"]
+                    params.first = "-b"
+                    params.second = "-bb"
+
+                    [sinks.out]
+                    type = "test_basic"
+                    inputs = ["a", "b"]
+                "#}
+            ),
+            Format::Toml,
+        )
+        .unwrap();
+
+        for (pipeline, input) in [("a", "in_a"), ("b", "in_b")] {
+            let one = config
+                .transform(&ComponentKey::from(format!("{}_one", pipeline)))
+                .expect("transform should exist");
+            assert_eq!(one.inputs, vec![output_id(input)]);
+
+            let two = config
+                .transform(&ComponentKey::from(format!("{}_two", pipeline)))
+                .expect("transform should exist");
+            assert_eq!(two.inputs, vec![output_id(&format!("{}_one", pipeline))]);
+        }
+
+        let sink = config.sink(&ComponentKey::from("out")).unwrap();
+        assert_eq!(sink.inputs, vec![output_id("a_two"), output_id("b_two")]);
+    }
+
+    #[test]
+    fn interpolates_params() {
+        let mut builder: ConfigBuilder = format::deserialize(
+            &format!(
+                "{}{}",
+                TEMPLATE,
+                indoc! {r#"
+                    [pipelines.a]
+                    template = "suffix"
+                    inputs = ["in_a"]
+                    params.first = "-a"
+                "#}
+            ),
+            Format::Toml,
+        )
+        .unwrap();
+
+        let expansions = expand_pipelines(&mut builder).unwrap();
+        assert_eq!(
+            expansions[&ComponentKey::from("a")],
+            vec![ComponentKey::from("a_two")]
+        );
+
+        let suffixes = ["a_one", "a_two"]
+            .iter()
+            .map(|key| {
+                let transform = &builder.transforms[&ComponentKey::from(*key)];
+                serde_json::to_value(&transform.inner).unwrap()["suffix"].clone()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(suffixes, vec!["-a", "-default"]);
+    }
+
+    #[test]
+    fn rejects_missing_and_unknown_params() {
+        let errors = load_from_str(
+            &format!(
+                "{}{}",
+                TEMPLATE,
+                indoc! {r#"
+                    [pipelines.a]
+                    template = "suffix"
+                    inputs = ["in_a"]
+                    params.third = "-a"
+
+                    [sinks.out]
+                    type = "test_basic"
+                    inputs = ["a"]
+                "#}
+            ),
+            Format::Toml,
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            errors,
+            vec![
+                "Pipeline \"a\": Unknown parameter \"third\".",
+                "Pipeline \"a\": Missing required parameter \"first\".",
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_template() {
+        let errors = load_from_str(
+            indoc! {r#"
+                [sources.in]
+                type = "test_basic"
+
+                [pipelines.a]
+                template = "missing"
+                inputs = ["in"]
+
+                [sinks.out]
+                type = "test_basic"
+                inputs = ["a"]
+            "#},
+            Format::Toml,
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            errors,
+            vec!["Pipeline \"a\" references unknown template \"missing\"."]
+        );
+    }
+
+    #[test]
+    fn reports_name_and_pipeline_errors() {
+        let errors = load_from_str(
+            indoc! {r#"
+                [sources."in.a"]
+                type = "test_basic"
+
+                [pipelines.a]
+                template = "missing"
+                inputs = ["in.a"]
+
+                [sinks.out]
+                type = "test_basic"
+                inputs = ["a"]
+            "#},
+            Format::Toml,
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            errors,
+            vec![
+                "Component name \"in.a\" should not contain a \".\"",
+                "Pipeline \"a\" references unknown template \"missing\".",
+            ]
+        );
+    }
+
+    #[test]
+    fn explicit_outputs() {
+        let mut builder: ConfigBuilder = format::deserialize(
+            &format!(
+                "{}{}",
+                TEMPLATE,
+                indoc! {r#"
+                    [pipelines.a]
+                    template = "suffix"
+                    inputs = ["in_a"]
+                    params.first = "-a"
+                "#}
+            ),
+            Format::Toml,
+        )
+        .unwrap();
+        builder.templates["suffix"].outputs = Some(vec!["one".into()]);
+
+        let expansions = expand_pipelines(&mut builder).unwrap();
+        assert_eq!(
+            expansions[&ComponentKey::from("a")],
+            vec![ComponentKey::from("a_one")]
+        );
+    }
+}
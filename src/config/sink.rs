@@ -204,6 +204,13 @@ pub struct SinkContext {
     pub globals: GlobalOptions,
     pub proxy: ProxyConfig,
     pub schema: schema::Options,
+
+    /// The schema definition of the events received by the sink, merged from those of all of its
+    /// inputs.
+    ///
+    /// This information can be used by sinks whose destination has a schema of its own, such as
+    /// the `gcp_bigquery` sink, which derives the schema of the rows it writes from it.
+    pub merged_schema_definition: schema::Definition,
 }
 
 impl SinkContext {
@@ -214,6 +221,7 @@ impl SinkContext {
             globals: GlobalOptions::default(),
             proxy: ProxyConfig::default(),
             schema: schema::Options::default(),
+            merged_schema_definition: schema::Definition::any(),
         }
     }
 
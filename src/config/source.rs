@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, time::Duration};
 
 use async_trait::async_trait;
 use enum_dispatch::enum_dispatch;
@@ -80,6 +80,15 @@ pub trait SourceConfig: NamedComponent + core::fmt::Debug + Send + Sync {
     /// well as emit contextual warnings when end-to-end acknowledgements are enabled, but the
     /// topology as configured does not actually support the use of end-to-end acknowledgements.
     fn can_acknowledge(&self) -> bool;
+
+    /// Whether or not this source can run once, when Vector is started with `--once`.
+    ///
+    /// Such a source ends by itself once it has read what is available, rather than waiting for
+    /// more, so that Vector exits once the events read have been processed. Sources that run
+    /// until they are shut down, such as servers, can't run once.
+    fn can_run_once(&self) -> bool {
+        false
+    }
 }
 
 pub struct SourceContext {
@@ -91,6 +100,10 @@ pub struct SourceContext {
     pub acknowledgements: bool,
     pub schema: schema::Options,
 
+    /// Whether Vector runs once, in which case the source ends once it has read what is
+    /// available.
+    pub once: bool,
+
     /// Tracks the schema IDs assigned to schemas exposed by the source.
     ///
     /// Given a source can expose multiple [`Output`] channels, the ID is tied to the identifier of
@@ -116,6 +129,7 @@ impl SourceContext {
                 acknowledgements: false,
                 schema_definitions: HashMap::default(),
                 schema: Default::default(),
+                once: false,
             },
             shutdown,
         )
@@ -135,6 +149,7 @@ impl SourceContext {
             acknowledgements: false,
             schema_definitions: schema_definitions.unwrap_or_default(),
             schema: Default::default(),
+            once: false,
         }
     }
 
@@ -152,6 +167,13 @@ impl SourceContext {
             .enabled()
     }
 
+    /// Gets the time to wait for the events to be acknowledged, before considering they failed.
+    pub fn acknowledgements_timeout(&self, config: &AcknowledgementsConfig) -> Option<Duration> {
+        config
+            .merge_default(&self.globals.acknowledgements)
+            .timeout()
+    }
+
     /// Gets the log namespacing to use. The passed in value is from the source itself
     /// and will override any global default if it's set.
     pub fn log_namespace(&self, namespace: Option<bool>) -> LogNamespace {
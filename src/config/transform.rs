@@ -1,4 +1,7 @@
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    num::NonZeroUsize,
+};
 
 use async_trait::async_trait;
 use enum_dispatch::enum_dispatch;
@@ -26,6 +29,27 @@ where
     #[serde(default = "Default::default")] // https://github.com/serde-rs/serde/issues/1541
     pub inputs: Vec<T>,
 
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    pub scheduling: TransformSchedulingOptions,
+
+    /// The number of copies of the transform to run in parallel.
+    ///
+    /// The events received by the transform are partitioned across the copies according to `partitioning`. Each copy
+    /// has its own state, so stateful transforms should partition events by a key field.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parallelism: Option<NonZeroUsize>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    pub partitioning: TransformPartitioningOptions,
+
     #[serde(flatten)]
     pub inner: Transforms,
 }
@@ -37,6 +61,9 @@ where
     pub(crate) fn new<I: Into<Transforms>>(inputs: Vec<T>, inner: I) -> Self {
         TransformOuter {
             inputs,
+            scheduling: Default::default(),
+            parallelism: None,
+            partitioning: Default::default(),
             inner: inner.into(),
         }
     }
@@ -55,6 +82,9 @@ where
     {
         TransformOuter {
             inputs,
+            scheduling: self.scheduling,
+            parallelism: self.parallelism,
+            partitioning: self.partitioning,
             inner: self.inner,
         }
     }
@@ -99,6 +129,9 @@ impl TransformOuter<String> {
             for (inner_name, inner_transform) in inner_topology.inner {
                 let child = TransformOuter {
                     inputs: inner_transform.inputs,
+                    scheduling: self.scheduling.clone(),
+                    parallelism: self.parallelism,
+                    partitioning: self.partitioning.clone(),
                     inner: inner_transform.inner,
                 };
                 children.push(inner_name.clone());
@@ -111,6 +144,56 @@ impl TransformOuter<String> {
     }
 }
 
+/// Scheduling options of a transform.
+///
+/// By default, transforms run on the worker threads shared by all components.
+#[configurable_component]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TransformSchedulingOptions {
+    /// The number of worker threads dedicated to the transform.
+    ///
+    /// When set, the transform runs on its own worker threads rather than on the shared ones, so that an expensive
+    /// transform cannot starve the other components, and transforms that process events concurrently, such as
+    /// `remap`, scale across this many cores.
+    pub worker_threads: Option<NonZeroUsize>,
+
+    /// The CPUs to pin the dedicated worker threads of the transform to.
+    ///
+    /// This is a hint: it is only supported on Linux, and CPUs that are not available to Vector are ignored. Only
+    /// relevant when `worker_threads` is set.
+    #[serde(default)]
+    pub cpu_affinity: Vec<usize>,
+
+    /// The maximum number of events the transform processes at once.
+    ///
+    /// Only relevant for transforms that process events concurrently. By default, the transform processes up to one
+    /// batch of events per worker thread at once.
+    pub max_in_flight_events: Option<NonZeroUsize>,
+}
+
+/// Partitioning options of a transform with `parallelism`.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct TransformPartitioningOptions {
+    /// The field to partition events by.
+    ///
+    /// Events are assigned to a copy of the transform by the hash of the value of this field, which is read from the
+    /// tags of metrics, such that the events with the same value are processed, in order, by the same copy. By
+    /// default, the batches of events received by the transform are assigned to the copies in a round-robin fashion.
+    pub key_field: Option<String>,
+
+    /// Whether the events sent by the transform keep the order of the batches of events it receives.
+    ///
+    /// When disabled, the events of a copy are sent as soon as it processes them. Transforms that are not
+    /// synchronous, such as `reduce`, always send the events of their copies as soon as they are processed.
+    #[serde(default = "crate::serde::default_true")]
+    #[derivative(Default(value = "true"))]
+    pub ordered: bool,
+}
+
 #[derive(Debug)]
 pub struct TransformContext {
     // This is optional because currently there are a lot of places we use `TransformContext` that
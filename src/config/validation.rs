@@ -4,6 +4,7 @@ use futures_util::{stream, FutureExt, StreamExt, TryFutureExt, TryStreamExt};
 use heim::{disk::Partition, units::information::byte};
 use indexmap::IndexMap;
 use std::{collections::HashMap, path::PathBuf};
+use vector_config::NamedComponent;
 use vector_core::internal_event::DEFAULT_OUTPUT;
 
 use super::{
@@ -149,6 +150,27 @@ pub fn check_resources(config: &ConfigBuilder) -> Result<(), Vec<String>> {
 
 /// To avoid collisions between `output` metric tags, check that a component
 /// does not have a named output with the name [`DEFAULT_OUTPUT`]
+/// Check that all the sources can run once, as they must when Vector is started with `--once`.
+pub fn check_run_once(config: &Config) -> Result<(), Vec<String>> {
+    let errors = config
+        .sources()
+        .filter(|(_, source)| !source.inner.can_run_once())
+        .map(|(key, source)| {
+            format!(
+                "Source \"{}\" of type `{}` doesn't end by itself, so it can't run once.",
+                key,
+                source.inner.get_component_name()
+            )
+        })
+        .collect::<Vec<_>>();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
 pub fn check_outputs(config: &ConfigBuilder) -> Result<(), Vec<String>> {
     let mut errors = Vec::new();
     for (key, source) in config.sources.iter() {
@@ -356,6 +378,23 @@ pub fn warnings(config: &Config) -> Vec<String> {
             .collect::<Vec<_>>()
     });
 
+    for (key, transform) in config.transforms.iter() {
+        if transform.scheduling.worker_threads.is_none()
+            && !transform.scheduling.cpu_affinity.is_empty()
+        {
+            warnings.push(format!(
+                "Transform \"{}\" has `scheduling.cpu_affinity` set without `scheduling.worker_threads`, so it is ignored",
+                key
+            ));
+        }
+        if transform.parallelism.is_none() && transform.partitioning.key_field.is_some() {
+            warnings.push(format!(
+                "Transform \"{}\" has `partitioning.key_field` set without `parallelism`, so it is ignored",
+                key
+            ));
+        }
+    }
+
     for (input_type, id) in transform_ids.chain(source_ids) {
         if !config
             .transforms
@@ -0,0 +1,379 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use clap::Parser;
+
+use crate::config::{self, format, ConfigPath, Format};
+
+#[derive(Parser, Debug)]
+#[command(rename_all = "kebab-case")]
+pub struct Opts {
+    /// The input path. It can be a single file or a directory. If this points to a directory,
+    /// all files with a "toml", "yaml" or "json" extension will be converted.
+    pub(crate) input_path: PathBuf,
+
+    /// The output file or directory to be created. This command will fail if the output path
+    /// already exists.
+    pub(crate) output_path: PathBuf,
+
+    /// The target format to which the existing config files will be converted.
+    #[arg(long, default_value = "yaml")]
+    pub(crate) output_format: Format,
+
+    /// Merge every input file, including component files found in `sources`, `transforms`,
+    /// `sinks` and `tests` sub-directories, into a single output file.
+    ///
+    /// The result is a canonical, fully-merged configuration suitable for auditing. Comments
+    /// are not preserved when merging.
+    #[arg(long)]
+    pub(crate) merge: bool,
+}
+
+fn check_paths(opts: &Opts) -> Result<(), String> {
+    if !opts.input_path.exists() {
+        return Err(format!(
+            "Input path {:?} does not exist.",
+            opts.input_path.as_path()
+        ));
+    }
+
+    if opts.output_path.exists() {
+        return Err(format!(
+            "Output path {:?} already exists. Please provide a non-existing output path.",
+            opts.output_path.as_path()
+        ));
+    }
+
+    if opts.input_path.is_dir() && !opts.merge && opts.output_path.extension().is_some() {
+        return Err(format!(
+            "Input path {:?} is a directory, the output path {:?} must be a directory as well. \
+            Use `--merge` to combine the directory into a single file.",
+            opts.input_path.as_path(),
+            opts.output_path.as_path()
+        ));
+    }
+
+    Ok(())
+}
+
+pub(crate) fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    if let Err(error) = check_paths(opts) {
+        #[allow(clippy::print_stderr)]
+        {
+            eprintln!("{}", error);
+        }
+        return exitcode::SOFTWARE;
+    }
+
+    let result = if opts.merge {
+        merge_and_convert(&opts.input_path, &opts.output_path, opts.output_format)
+    } else if opts.input_path.is_file() {
+        convert_config_file(&opts.input_path, &opts.output_path, opts.output_format)
+    } else {
+        walk_dir_and_convert(&opts.input_path, &opts.output_path, opts.output_format)
+    };
+
+    match result {
+        Ok(()) => exitcode::OK,
+        Err(errors) => {
+            #[allow(clippy::print_stderr)]
+            for error in errors {
+                eprintln!("{}", error);
+            }
+            exitcode::SOFTWARE
+        }
+    }
+}
+
+/// Converts a single config file.
+///
+/// When both the input and output formats are TOML, the original text is written back untouched
+/// once it has been validated, so that comments and formatting survive the conversion.
+fn convert_config_file(
+    input_path: &Path,
+    output_path: &Path,
+    output_format: Format,
+) -> Result<(), Vec<String>> {
+    let input_format = Format::from_path(input_path)
+        .map_err(|path| vec![format!("Could not infer the format of {:?}.", path)])?;
+
+    let contents = fs::read_to_string(input_path)
+        .map_err(|error| vec![format!("Failed to read {:?}: {}.", input_path, error)])?;
+
+    let table: toml::value::Table = format::deserialize(&contents, input_format)
+        .map_err(|errors| prefix_errors(input_path, errors))?;
+
+    let output = if input_format == Format::Toml && output_format == Format::Toml {
+        contents
+    } else {
+        format::serialize(&table, output_format).map_err(|error| {
+            vec![format!(
+                "Failed to convert {:?} to {:?}: {}.",
+                input_path, output_format, error
+            )]
+        })?
+    };
+
+    write_output(output_path, &output)?;
+
+    #[allow(clippy::print_stdout)]
+    {
+        println!(
+            "Converted {:?} to {:?} at {:?}.",
+            input_path, output_format, output_path
+        );
+    }
+
+    Ok(())
+}
+
+/// Converts every config file found under `input_dir`, mirroring the directory layout under
+/// `output_dir`.
+fn walk_dir_and_convert(
+    input_dir: &Path,
+    output_dir: &Path,
+    output_format: Format,
+) -> Result<(), Vec<String>> {
+    let mut errors = Vec::new();
+
+    let entries = fs::read_dir(input_dir)
+        .map_err(|error| vec![format!("Could not read dir {:?}: {}.", input_dir, error)])?;
+
+    for entry in entries {
+        let entry_path = match entry {
+            Ok(entry) => entry.path(),
+            Err(error) => {
+                errors.push(format!(
+                    "Could not read entry in dir {:?}: {}.",
+                    input_dir, error
+                ));
+                continue;
+            }
+        };
+
+        let file_name = match entry_path.file_name() {
+            Some(file_name) => file_name,
+            None => continue,
+        };
+
+        if entry_path.is_dir() {
+            if let Err(errs) =
+                walk_dir_and_convert(&entry_path, &output_dir.join(file_name), output_format)
+            {
+                errors.extend(errs);
+            }
+        } else if Format::from_path(&entry_path).is_ok() {
+            let output_path = output_dir
+                .join(file_name)
+                .with_extension(output_format.extension());
+            if let Err(errs) = convert_config_file(&entry_path, &output_path, output_format) {
+                errors.extend(errs);
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Loads the input path the same way `vector` would, merging component directories, and
+/// writes the combined configuration as a single file.
+fn merge_and_convert(
+    input_path: &Path,
+    output_path: &Path,
+    output_format: Format,
+) -> Result<(), Vec<String>> {
+    let config_path = if input_path.is_dir() {
+        ConfigPath::Dir(input_path.to_path_buf())
+    } else {
+        ConfigPath::File(input_path.to_path_buf(), None)
+    };
+
+    let (table, warnings) = config::load_source_from_paths(&[config_path])?;
+    for warning in warnings {
+        warn!("{}", warning);
+    }
+
+    let output = format::serialize(&table, output_format).map_err(|error| {
+        vec![format!(
+            "Failed to convert merged config to {:?}: {}.",
+            output_format, error
+        )]
+    })?;
+
+    write_output(output_path, &output)?;
+
+    #[allow(clippy::print_stdout)]
+    {
+        println!(
+            "Merged {:?} into {:?} at {:?}.",
+            input_path, output_format, output_path
+        );
+    }
+
+    Ok(())
+}
+
+fn write_output(output_path: &Path, output: &str) -> Result<(), Vec<String>> {
+    if let Some(parent) = output_path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|error| vec![format!("Failed to create dir {:?}: {}.", parent, error)])?;
+    }
+
+    fs::write(output_path, output)
+        .map_err(|error| vec![format!("Failed to write {:?}: {}.", output_path, error)])
+}
+
+fn prefix_errors(path: &Path, errors: Vec<String>) -> Vec<String> {
+    errors
+        .into_iter()
+        .map(|error| format!("{:?}: {}", path, error))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    use super::*;
+
+    const SAMPLE_TOML: &str = r#"
+        # The data directory.
+        data_dir = "/var/lib/vector"
+
+        [sources.in]
+        type = "stdin"
+
+        [sinks.out]
+        type = "console"
+        inputs = ["in"]
+        encoding.codec = "json"
+    "#;
+
+    fn opts(input_path: PathBuf, output_path: PathBuf, output_format: Format) -> Opts {
+        Opts {
+            input_path,
+            output_path,
+            output_format,
+            merge: false,
+        }
+    }
+
+    fn read_table(path: &Path) -> toml::value::Table {
+        let format = Format::from_path(path).unwrap();
+        format::deserialize(&fs::read_to_string(path).unwrap(), format).unwrap()
+    }
+
+    #[test]
+    fn convert_toml_to_yaml_and_json() {
+        let dir = tempdir().unwrap();
+        let input_path = dir.path().join("vector.toml");
+        fs::write(&input_path, SAMPLE_TOML).unwrap();
+        let expected: toml::value::Table = toml::from_str(SAMPLE_TOML).unwrap();
+
+        for format in [Format::Yaml, Format::Json] {
+            let output_path = dir.path().join(format!("out.{}", format.extension()));
+            let opts = opts(input_path.clone(), output_path.clone(), format);
+
+            assert_eq!(cmd(&opts), exitcode::OK);
+            assert_eq!(read_table(&output_path), expected);
+        }
+    }
+
+    #[test]
+    fn convert_toml_to_toml_preserves_comments() {
+        let dir = tempdir().unwrap();
+        let input_path = dir.path().join("vector.toml");
+        let output_path = dir.path().join("out.toml");
+        fs::write(&input_path, SAMPLE_TOML).unwrap();
+
+        let opts = opts(input_path, output_path.clone(), Format::Toml);
+        assert_eq!(cmd(&opts), exitcode::OK);
+
+        let output = fs::read_to_string(output_path).unwrap();
+        assert_eq!(output, SAMPLE_TOML);
+        assert!(output.contains("# The data directory."));
+    }
+
+    #[test]
+    fn convert_dir_mirrors_layout() {
+        let input_dir = tempdir().unwrap();
+        let output_dir = tempdir().unwrap();
+        let output_path = output_dir.path().join("converted");
+
+        fs::create_dir(input_dir.path().join("sources")).unwrap();
+        fs::write(
+            input_dir.path().join("sources").join("in.toml"),
+            r#"type = "stdin""#,
+        )
+        .unwrap();
+        fs::write(input_dir.path().join("README.md"), "not a config").unwrap();
+
+        let opts = opts(
+            input_dir.path().to_path_buf(),
+            output_path.clone(),
+            Format::Json,
+        );
+        assert_eq!(cmd(&opts), exitcode::OK);
+
+        let converted = output_path.join("sources").join("in.json");
+        assert_eq!(
+            read_table(&converted).get("type"),
+            Some(&toml::Value::String("stdin".into()))
+        );
+        assert!(!output_path.join("README.md").exists());
+    }
+
+    #[test]
+    fn merge_dir_into_single_file() {
+        let input_dir = tempdir().unwrap();
+        let output_dir = tempdir().unwrap();
+        let output_path = output_dir.path().join("merged.yaml");
+
+        fs::write(
+            input_dir.path().join("vector.toml"),
+            r#"data_dir = "/var/lib/vector""#,
+        )
+        .unwrap();
+        fs::create_dir(input_dir.path().join("sinks")).unwrap();
+        fs::write(
+            input_dir.path().join("sinks").join("out.yaml"),
+            "type: console\ninputs: [\"in\"]\n",
+        )
+        .unwrap();
+
+        let mut opts = opts(
+            input_dir.path().to_path_buf(),
+            output_path.clone(),
+            Format::Yaml,
+        );
+        opts.merge = true;
+        assert_eq!(cmd(&opts), exitcode::OK);
+
+        let merged = read_table(&output_path);
+        assert_eq!(
+            merged.get("data_dir"),
+            Some(&toml::Value::String("/var/lib/vector".into()))
+        );
+        assert!(merged["sinks"]["out"].get("inputs").is_some());
+    }
+
+    #[test]
+    fn refuses_existing_output_path() {
+        let dir = tempdir().unwrap();
+        let input_path = dir.path().join("vector.toml");
+        let output_path = dir.path().join("vector.yaml");
+        fs::write(&input_path, SAMPLE_TOML).unwrap();
+        fs::write(&output_path, "").unwrap();
+
+        let opts = opts(input_path, output_path, Format::Yaml);
+        assert_eq!(cmd(&opts), exitcode::SOFTWARE);
+    }
+}
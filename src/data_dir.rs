@@ -0,0 +1,211 @@
+//! The layout of the data directory, in which each component stores its data in directories
+//! namespaced by its ID:
+//!   - `<data_dir>/<component_id>`, for its state, such as the checkpoints of sources.
+//!   - The directories of its disk buffer, such as `<data_dir>/buffer/v2/<component_id>`.
+//!
+//! The disk usage of each component is measured at an interval, and the data of the components
+//! that are no longer part of the configuration can be removed with `vector clean-data`.
+
+use std::{
+    collections::HashSet,
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use tokio::sync::watch;
+use vector_buffers::config::{buffer_data_dir_paths, buffer_data_dir_roots};
+use vector_core::config::StorageOptions;
+
+use crate::{
+    config::{ComponentKey, Config},
+    internal_events::{DataDirQuotaExceeded, DataDirUsage, DataDirUsageError},
+};
+
+/// The entries of the data directory that don't belong to a component.
+const RESERVED_ENTRIES: &[&str] = &["buffer", "upgrade.sock", "validate_tmp"];
+
+/// Gets the paths that a component may store its data in.
+pub fn component_data_paths(data_dir: &Path, id: &str) -> Vec<PathBuf> {
+    let mut paths = vec![data_dir.join(id)];
+    paths.extend(buffer_data_dir_paths(data_dir, id));
+    paths
+}
+
+/// Gets the number of bytes of the files under a path, which is zero if it doesn't exist.
+pub fn disk_usage(path: &Path) -> io::Result<u64> {
+    let metadata = match fs::symlink_metadata(path) {
+        Ok(metadata) => metadata,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(0),
+        Err(error) => return Err(error),
+    };
+    if metadata.is_dir() {
+        let mut size = 0;
+        for entry in fs::read_dir(path)? {
+            size += disk_usage(&entry?.path())?;
+        }
+        Ok(size)
+    } else {
+        Ok(metadata.len())
+    }
+}
+
+/// Finds the directories of the data directory that belong to none of the given components.
+pub fn orphaned_paths<'a>(
+    data_dir: &Path,
+    component_ids: impl IntoIterator<Item = &'a str>,
+) -> io::Result<Vec<PathBuf>> {
+    let used = component_ids
+        .into_iter()
+        .flat_map(|id| component_data_paths(data_dir, id))
+        .collect::<HashSet<_>>();
+
+    let mut orphaned = Vec::new();
+    for root in buffer_data_dir_roots(data_dir) {
+        let entries = match fs::read_dir(&root) {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => continue,
+            Err(error) => return Err(error),
+        };
+        for entry in entries {
+            let entry = entry?;
+            let path = entry.path();
+            let reserved = root == data_dir
+                && RESERVED_ENTRIES
+                    .iter()
+                    .any(|reserved| entry.file_name() == *reserved);
+            if entry.file_type()?.is_dir() && !reserved && !used.contains(&path) {
+                orphaned.push(path);
+            }
+        }
+    }
+    orphaned.sort();
+    Ok(orphaned)
+}
+
+/// The components whose disk usage is measured.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct UsageTargets {
+    data_dir: Option<PathBuf>,
+    options: StorageOptions,
+    components: Vec<ComponentKey>,
+}
+
+impl UsageTargets {
+    pub(crate) fn new(config: &Config) -> Self {
+        let components = config
+            .sources()
+            .map(|(key, _)| key)
+            .chain(config.transforms().map(|(key, _)| key))
+            .chain(config.sinks().map(|(key, _)| key))
+            .cloned()
+            .collect();
+        Self {
+            data_dir: config.global.data_dir.clone(),
+            options: config.global.storage.clone(),
+            components,
+        }
+    }
+
+    /// Measures and reports the disk usage of each component.
+    fn measure(&self) {
+        let data_dir = match &self.data_dir {
+            Some(data_dir) => data_dir,
+            None => return,
+        };
+        for key in &self.components {
+            let component_id = key.id();
+            let byte_size = component_data_paths(data_dir, component_id)
+                .iter()
+                .map(|path| disk_usage(path.as_path()))
+                .sum::<io::Result<u64>>();
+            match byte_size {
+                Ok(byte_size) => {
+                    emit!(DataDirUsage {
+                        component_id,
+                        byte_size,
+                    });
+                    if let Some(quota) = self.options.component_quota_bytes {
+                        if byte_size > quota.get() {
+                            emit!(DataDirQuotaExceeded {
+                                component_id,
+                                byte_size,
+                                quota: quota.get(),
+                            });
+                        }
+                    }
+                }
+                Err(error) => emit!(DataDirUsageError {
+                    component_id,
+                    error
+                }),
+            }
+        }
+    }
+}
+
+/// Measures the disk usage of the components at an interval, until the targets are dropped.
+pub(crate) async fn monitor_usage(mut targets: watch::Receiver<UsageTargets>) {
+    loop {
+        let current = targets.borrow_and_update().clone();
+        let interval = Duration::from_secs(current.options.usage_interval_secs());
+        // Walking the directories is blocking, and can take a while for large disk buffers.
+        if tokio::task::spawn_blocking(move || current.measure())
+            .await
+            .is_err()
+        {
+            return;
+        }
+
+        tokio::select! {
+            _ = tokio::time::sleep(interval) => {}
+            changed = targets.changed() => if changed.is_err() {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_orphaned_paths() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let data_dir = data_dir.path();
+        for path in [
+            "in",
+            "old_in",
+            "out_id",
+            "old_out_id",
+            "buffer/v2/out",
+            "buffer/v2/old_out",
+            "validate_tmp",
+        ] {
+            fs::create_dir_all(data_dir.join(path)).unwrap();
+        }
+        fs::write(data_dir.join("upgrade.sock"), "").unwrap();
+
+        assert_eq!(
+            orphaned_paths(data_dir, ["in", "out"]).unwrap(),
+            vec![
+                data_dir.join("buffer/v2/old_out"),
+                data_dir.join("old_in"),
+                data_dir.join("old_out_id"),
+            ]
+        );
+    }
+
+    #[test]
+    fn measures_disk_usage() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let data_dir = data_dir.path();
+        fs::create_dir_all(data_dir.join("in/nested")).unwrap();
+        fs::write(data_dir.join("in/checkpoints.json"), "{}").unwrap();
+        fs::write(data_dir.join("in/nested/file"), "1234").unwrap();
+
+        assert_eq!(disk_usage(&data_dir.join("in")).unwrap(), 6);
+        assert_eq!(disk_usage(&data_dir.join("missing")).unwrap(), 0);
+    }
+}
@@ -2,7 +2,7 @@ use std::{collections::BTreeMap, fs, net::IpAddr, sync::Arc, time::SystemTime};
 
 use enrichment::{Case, Condition, IndexHandle, Table};
 use maxminddb::{
-    geoip2::{City, ConnectionType, Isp},
+    geoip2::{AnonymousIp, City, ConnectionType, Isp},
     MaxMindDBError, Reader,
 };
 use value::Value;
@@ -18,6 +18,7 @@ pub enum DatabaseKind {
     Asn,
     Isp,
     ConnectionType,
+    AnonymousIp,
     City,
 }
 
@@ -27,6 +28,7 @@ impl From<&str> for DatabaseKind {
             "GeoLite2-ASN" => Self::Asn,
             "GeoIP2-ISP" => Self::Isp,
             "GeoIP2-Connection-Type" => Self::ConnectionType,
+            "GeoIP2-Anonymous-IP" => Self::AnonymousIp,
             _ => Self::City,
         }
     }
@@ -39,7 +41,9 @@ pub struct GeoipConfig {
     /// Path to the [MaxMind GeoIP2][geoip2] or [GeoLite2 binary city database file][geolite2]
     /// (**GeoLite2-City.mmdb**).
     ///
-    /// Other databases, such as the country database, are not supported.
+    /// The GeoIP2 ISP, GeoLite2 ASN, GeoIP2 Connection Type, and GeoIP2 Anonymous IP databases are
+    /// supported too. Other databases, such as the country database, are not supported, but can be
+    /// read with the `mmdb` enrichment table.
     ///
     /// [geoip2]: https://dev.maxmind.com/geoip/geoip2/downloadable
     /// [geolite2]: https://dev.maxmind.com/geoip/geoip2/geolite2/#Download_Access
@@ -109,6 +113,7 @@ impl Geoip {
         let result = match dbkind {
             DatabaseKind::Asn | DatabaseKind::Isp => dbreader.lookup::<Isp>(ip).map(|_| ()),
             DatabaseKind::ConnectionType => dbreader.lookup::<ConnectionType>(ip).map(|_| ()),
+            DatabaseKind::AnonymousIp => dbreader.lookup::<AnonymousIp>(ip).map(|_| ()),
             DatabaseKind::City => dbreader.lookup::<City>(ip).map(|_| ()),
         };
 
@@ -198,6 +203,16 @@ impl Geoip {
 
                 add_field!("connection_type", data.connection_type);
             }
+            DatabaseKind::AnonymousIp => {
+                let data = self.dbreader.lookup::<AnonymousIp>(ip).ok()?;
+
+                add_field!("is_anonymous", data.is_anonymous);
+                add_field!("is_anonymous_vpn", data.is_anonymous_vpn);
+                add_field!("is_hosting_provider", data.is_hosting_provider);
+                add_field!("is_public_proxy", data.is_public_proxy);
+                add_field!("is_residential_proxy", data.is_residential_proxy);
+                add_field!("is_tor_exit_node", data.is_tor_exit_node);
+            }
         }
 
         Some(map)
@@ -0,0 +1,238 @@
+use std::{collections::BTreeMap, fs, net::IpAddr, sync::Arc, time::SystemTime};
+
+use enrichment::{Case, Condition, IndexHandle, Table};
+use maxminddb::{MaxMindDBError, Reader};
+use value::Value;
+use vector_config::configurable_component;
+
+use crate::config::{EnrichmentTableConfig, GenerateConfig};
+
+/// Configuration for the `mmdb` enrichment table.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[configurable_component(enrichment_table("mmdb"))]
+pub struct MmdbConfig {
+    /// Path to a [MaxMind DB][mmdb] database file.
+    ///
+    /// Any database in the MaxMind DB format can be read, such as the MaxMind country or
+    /// anonymous IP databases, or databases built in-house. The records of the database are
+    /// returned as they are stored, their top-level fields being the fields of the table.
+    ///
+    /// [mmdb]: https://maxmind.github.io/MaxMind-DB/
+    pub path: String,
+}
+
+impl GenerateConfig for MmdbConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            path: "/path/to/GeoLite2-Country.mmdb".to_string(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl EnrichmentTableConfig for MmdbConfig {
+    async fn build(
+        &self,
+        _: &crate::config::GlobalOptions,
+    ) -> crate::Result<Box<dyn Table + Send + Sync>> {
+        Ok(Box::new(Mmdb::new(self.clone())?))
+    }
+}
+
+#[derive(Clone)]
+pub struct Mmdb {
+    config: MmdbConfig,
+    dbreader: Arc<maxminddb::Reader<Vec<u8>>>,
+    last_modified: SystemTime,
+}
+
+impl Mmdb {
+    pub fn new(config: MmdbConfig) -> crate::Result<Self> {
+        let dbreader = Arc::new(Reader::open_readfile(config.path.clone())?);
+
+        // Check if we can read database with dummy Ip.
+        let ip = IpAddr::V4(std::net::Ipv4Addr::new(0, 0, 0, 0));
+        match dbreader.lookup::<Value>(ip) {
+            Ok(_) | Err(MaxMindDBError::AddressNotFoundError(_)) => Ok(Mmdb {
+                last_modified: fs::metadata(&config.path)?.modified()?,
+                dbreader,
+                config,
+            }),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    fn lookup(
+        &self,
+        ip: IpAddr,
+        select: Option<&[String]>,
+    ) -> Result<Option<BTreeMap<String, Value>>, String> {
+        let data = match self.dbreader.lookup::<Value>(ip) {
+            Ok(data) => data,
+            Err(MaxMindDBError::AddressNotFoundError(_)) => return Ok(None),
+            Err(error) => return Err(error.to_string()),
+        };
+
+        // Records are usually maps, but a database may as well store a single value per network.
+        let map = match data {
+            Value::Object(map) => map,
+            value => BTreeMap::from([("value".to_string(), value)]),
+        };
+
+        Ok(Some(match select {
+            Some(fields) => map
+                .into_iter()
+                .filter(|(key, _)| fields.iter().any(|field| field == key))
+                .collect(),
+            None => map,
+        }))
+    }
+}
+
+impl Table for Mmdb {
+    /// Search the enrichment table data with the given condition.
+    /// All conditions must match (AND).
+    ///
+    /// # Errors
+    /// Errors if no rows, or more than 1 row is found.
+    fn find_table_row<'a>(
+        &self,
+        case: Case,
+        condition: &'a [Condition<'a>],
+        select: Option<&[String]>,
+        index: Option<IndexHandle>,
+    ) -> Result<BTreeMap<String, Value>, String> {
+        let mut rows = self.find_table_rows(case, condition, select, index)?;
+
+        match rows.pop() {
+            Some(row) if rows.is_empty() => Ok(row),
+            Some(_) => Err("More than 1 row found".to_string()),
+            None => Err("IP not found".to_string()),
+        }
+    }
+
+    /// Search the enrichment table data with the given condition.
+    /// All conditions must match (AND).
+    /// Can return multiple matched records
+    fn find_table_rows<'a>(
+        &self,
+        _: Case,
+        condition: &'a [Condition<'a>],
+        select: Option<&[String]>,
+        _: Option<IndexHandle>,
+    ) -> Result<Vec<BTreeMap<String, Value>>, String> {
+        match condition.get(0) {
+            Some(_) if condition.len() > 1 => Err("Only one condition is allowed".to_string()),
+            Some(Condition::Equals { value, .. }) => {
+                let ip = value
+                    .to_string_lossy()
+                    .parse::<IpAddr>()
+                    .map_err(|_| "Invalid IP address".to_string())?;
+                Ok(self
+                    .lookup(ip, select)?
+                    .map(|values| vec![values])
+                    .unwrap_or_default())
+            }
+            Some(_) => Err("Only equality condition is allowed".to_string()),
+            None => Err("IP condition must be specified".to_string()),
+        }
+    }
+
+    /// Hints to the enrichment table what data is going to be searched to allow it to index the
+    /// data in advance.
+    ///
+    /// # Errors
+    /// Errors if the fields are not in the table.
+    fn add_index(&mut self, _: Case, fields: &[&str]) -> Result<IndexHandle, String> {
+        match fields.len() {
+            0 => Err("IP field is required".to_string()),
+            1 => Ok(IndexHandle(0)),
+            _ => Err("Only one field is allowed".to_string()),
+        }
+    }
+
+    /// Returns a list of the field names that are in each index
+    fn index_fields(&self) -> Vec<(Case, Vec<String>)> {
+        Vec::new()
+    }
+
+    /// Returns true if the underlying data has changed and the table needs reloading.
+    fn needs_reload(&self) -> bool {
+        matches!(fs::metadata(&self.config.path)
+            .and_then(|metadata| metadata.modified()),
+            Ok(modified) if modified > self.last_modified)
+    }
+}
+
+impl std::fmt::Debug for Mmdb {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Mmdb database {})", self.config.path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookup() {
+        let values = find_select(
+            "2.125.160.216",
+            "tests/data/GeoIP2-City-Test.mmdb",
+            Some(&["country".to_string(), "postal".to_string()]),
+        )
+        .unwrap();
+
+        assert_eq!(values.len(), 2);
+        assert_eq!(
+            values["country"]
+                .as_object()
+                .and_then(|country| country.get("iso_code")),
+            Some(&Value::from("GB"))
+        );
+        assert_eq!(
+            values["postal"]
+                .as_object()
+                .and_then(|postal| postal.get("code")),
+            Some(&Value::from("OX1"))
+        );
+    }
+
+    #[test]
+    fn lookup_other_database() {
+        let values = find_select("208.192.1.2", "tests/data/GeoIP2-ISP-Test.mmdb", None).unwrap();
+
+        assert_eq!(values["isp"], Value::from("Verizon Business"));
+        assert_eq!(values["autonomous_system_number"], Value::from(701));
+    }
+
+    #[test]
+    fn lookup_no_results() {
+        let values = find_select("10.1.12.1", "tests/data/GeoIP2-City-Test.mmdb", None);
+
+        assert!(values.is_none());
+    }
+
+    fn find_select(
+        ip: &str,
+        database: &str,
+        select: Option<&[String]>,
+    ) -> Option<BTreeMap<String, Value>> {
+        Mmdb::new(MmdbConfig {
+            path: database.to_string(),
+        })
+        .unwrap()
+        .find_table_rows(
+            Case::Insensitive,
+            &[Condition::Equals {
+                field: "ip",
+                value: ip.into(),
+            }],
+            select,
+            None,
+        )
+        .unwrap()
+        .pop()
+    }
+}
@@ -9,6 +9,9 @@ pub mod file;
 #[cfg(feature = "enrichment-tables-geoip")]
 pub mod geoip;
 
+#[cfg(feature = "enrichment-tables-mmdb")]
+pub mod mmdb;
+
 /// Configurable enrichment tables in Vector.
 #[configurable_component]
 #[derive(Clone, Debug)]
@@ -21,6 +24,10 @@ pub enum EnrichmentTables {
     /// GeoIP.
     #[cfg(feature = "enrichment-tables-geoip")]
     Geoip(#[configurable(derived)] geoip::GeoipConfig),
+
+    /// MaxMind DB.
+    #[cfg(feature = "enrichment-tables-mmdb")]
+    Mmdb(#[configurable(derived)] mmdb::MmdbConfig),
 }
 
 // We can't use `enum_dispatch` here because it doesn't support associated constants.
@@ -32,6 +39,8 @@ impl NamedComponent for EnrichmentTables {
             Self::File(config) => config.get_component_name(),
             #[cfg(feature = "enrichment-tables-geoip")]
             Self::Geoip(config) => config.get_component_name(),
+            #[cfg(feature = "enrichment-tables-mmdb")]
+            Self::Mmdb(config) => config.get_component_name(),
             #[allow(unreachable_patterns)]
             _ => unimplemented!(),
         }
@@ -0,0 +1,89 @@
+//! The parts of the forward protocol of Fluentd shared by the `fluent` source and sink.
+//!
+//! See <https://github.com/fluent/fluentd/wiki/Forward-Protocol-Specification-v1>.
+
+use std::io;
+
+use bytes::{Buf, BytesMut};
+use rmpv::Value;
+use sha2::{Digest, Sha512};
+use snafu::{ResultExt, Snafu};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+#[derive(Debug, Snafu)]
+pub enum ProtocolError {
+    #[snafu(display("Failed to read or write the message: {}", source))]
+    Io { source: io::Error },
+
+    #[snafu(display("Failed to decode the message: {}", source))]
+    Decode { source: rmpv::decode::Error },
+
+    #[snafu(display("The message is larger than {} bytes.", max_size))]
+    TooLarge { max_size: usize },
+}
+
+/// Gets the bytes of a string, which the peers send either as `str` or `bin`.
+pub fn value_bytes(value: &Value) -> Option<&[u8]> {
+    match value {
+        Value::String(s) => Some(s.as_bytes()),
+        Value::Binary(b) => Some(b),
+        _ => None,
+    }
+}
+
+/// Gets the hex-encoded SHA-512 digest of the concatenation of the parts, with which the peers
+/// prove they know the shared key and passwords during the handshake.
+pub fn digest(parts: &[&[u8]]) -> String {
+    let mut hasher = Sha512::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    hex::encode(hasher.finalize())
+}
+
+pub async fn write_message<S>(socket: &mut S, message: &Value) -> Result<(), ProtocolError>
+where
+    S: AsyncWrite + Unpin,
+{
+    let mut bytes = Vec::new();
+    rmpv::encode::write_value(&mut bytes, message).expect("writing to a Vec never fails");
+    socket.write_all(&bytes).await.context(IoSnafu)
+}
+
+/// Reads the next message from the socket, leaving the bytes read past its end in `buf`.
+///
+/// The messages read this way, for the handshake and the acknowledgements, are small, so those
+/// larger than `max_size` are rejected.
+pub async fn read_message<S>(
+    socket: &mut S,
+    buf: &mut BytesMut,
+    max_size: usize,
+) -> Result<Value, ProtocolError>
+where
+    S: AsyncRead + Unpin,
+{
+    loop {
+        let mut cursor = io::Cursor::new(&buf[..]);
+        match rmpv::decode::read_value(&mut cursor) {
+            Ok(value) => {
+                let byte_size = cursor.position() as usize;
+                buf.advance(byte_size);
+                return Ok(value);
+            }
+            Err(
+                rmpv::decode::Error::InvalidMarkerRead(ref error)
+                | rmpv::decode::Error::InvalidDataRead(ref error),
+            ) if error.kind() == io::ErrorKind::UnexpectedEof => {}
+            Err(error) => return Err(ProtocolError::Decode { source: error }),
+        }
+
+        if buf.len() > max_size {
+            return Err(ProtocolError::TooLarge { max_size });
+        }
+        if socket.read_buf(buf).await.context(IoSnafu)? == 0 {
+            return Err(ProtocolError::Io {
+                source: io::ErrorKind::UnexpectedEof.into(),
+            });
+        }
+    }
+}
@@ -19,12 +19,16 @@ use tracing::Instrument;
 use vector_common::sensitive_string::SensitiveString;
 use vector_config::configurable_component;
 
+use self::socks::Socks5Proxy;
+pub use self::socks::{AddressPin, ProxyTcpConnector};
 use crate::{
     config::ProxyConfig,
     internal_events::http_client,
     tls::{tls_connector_builder, MaybeTlsSettings, TlsError},
 };
 
+mod socks;
+
 #[derive(Debug, Snafu)]
 #[snafu(visibility(pub(crate)))]
 pub enum HttpError {
@@ -54,7 +58,7 @@ impl HttpError {
 pub type HttpClientFuture = <HttpClient as Service<http::Request<Body>>>::Future;
 
 pub struct HttpClient<B = Body> {
-    client: Client<ProxyConnector<HttpsConnector<HttpConnector>>, B>,
+    client: Client<ProxyConnector<HttpsConnector<ProxyTcpConnector>>, B>,
     user_agent: HeaderValue,
 }
 
@@ -139,12 +143,36 @@ where
 pub fn build_proxy_connector(
     tls_settings: MaybeTlsSettings,
     proxy_config: &ProxyConfig,
-) -> Result<ProxyConnector<HttpsConnector<HttpConnector>>, HttpError> {
+) -> Result<ProxyConnector<HttpsConnector<ProxyTcpConnector>>, HttpError> {
+    build_proxy_connector_with_pin(tls_settings, proxy_config, None)
+}
+
+/// Builds a proxy connector whose direct connections are pinned to the given address.
+///
+/// See [`ProxyTcpConnector::with_address_pin`].
+pub fn build_pinned_proxy_connector(
+    tls_settings: MaybeTlsSettings,
+    proxy_config: &ProxyConfig,
+    address_pin: AddressPin,
+) -> Result<ProxyConnector<HttpsConnector<ProxyTcpConnector>>, HttpError> {
+    build_proxy_connector_with_pin(tls_settings, proxy_config, Some(address_pin))
+}
+
+fn build_proxy_connector_with_pin(
+    tls_settings: MaybeTlsSettings,
+    proxy_config: &ProxyConfig,
+    address_pin: Option<AddressPin>,
+) -> Result<ProxyConnector<HttpsConnector<ProxyTcpConnector>>, HttpError> {
     // Create dedicated TLS connector for the proxied connection with user TLS settings.
     let tls = tls_connector_builder(&tls_settings)
         .context(BuildTlsConnectorSnafu)?
         .build();
-    let https = build_tls_connector(tls_settings)?;
+    let socks5 = Socks5Proxy::from_config(proxy_config).context(MakeProxyConnectorSnafu)?;
+    let mut connector = ProxyTcpConnector::new(build_http_connector(), socks5);
+    if let Some(pin) = address_pin {
+        connector = connector.with_address_pin(pin);
+    }
+    let https = build_https_connector(connector, tls_settings)?;
     let mut proxy = ProxyConnector::new(https).unwrap();
     // Make proxy connector aware of user TLS settings by setting the TLS connector:
     // https://github.com/vectordotdev/vector/issues/13683
@@ -158,11 +186,35 @@ pub fn build_proxy_connector(
 pub fn build_tls_connector(
     tls_settings: MaybeTlsSettings,
 ) -> Result<HttpsConnector<HttpConnector>, HttpError> {
+    build_https_connector(build_http_connector(), tls_settings)
+}
+
+fn build_http_connector() -> HttpConnector {
     let mut http = HttpConnector::new();
     http.enforce_http(false);
+    http
+}
 
+fn build_https_connector<C, T>(
+    connector: C,
+    tls_settings: MaybeTlsSettings,
+) -> Result<HttpsConnector<C>, HttpError>
+where
+    C: Service<Uri, Response = T> + Send,
+    C::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+    C::Future: Unpin + Send + 'static,
+    T: tokio::io::AsyncRead
+        + tokio::io::AsyncWrite
+        + hyper::client::connect::Connection
+        + Unpin
+        + fmt::Debug
+        + Sync
+        + Send
+        + 'static,
+{
     let tls = tls_connector_builder(&tls_settings).context(BuildTlsConnectorSnafu)?;
-    let mut https = HttpsConnector::with_connector(http, tls).context(MakeHttpsConnectorSnafu)?;
+    let mut https =
+        HttpsConnector::with_connector(connector, tls).context(MakeHttpsConnectorSnafu)?;
 
     let settings = tls_settings.tls().cloned();
     https.set_callback(move |c, _uri| {
@@ -0,0 +1,93 @@
+use std::time::Duration;
+
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct AuditLogRequestError {
+    pub provider: &'static str,
+    pub error: crate::Error,
+}
+
+impl InternalEvent for AuditLogRequestError {
+    fn emit(self) {
+        error!(
+            message = "Failed to pull audit logs.",
+            provider = self.provider,
+            error = %self.error,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct AuditLogResponseParseError {
+    pub provider: &'static str,
+    pub error: serde_json::Error,
+}
+
+impl InternalEvent for AuditLogResponseParseError {
+    fn emit(self) {
+        error!(
+            message = "Failed to parse audit log response.",
+            provider = self.provider,
+            error = %self.error,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct AuditLogRateLimited {
+    pub provider: &'static str,
+    pub delay: Duration,
+}
+
+impl InternalEvent for AuditLogRateLimited {
+    fn emit(self) {
+        debug!(
+            message = "Rate limited, waiting before the next request.",
+            provider = self.provider,
+            delay_secs = self.delay.as_secs_f64(),
+            internal_log_rate_limit = true,
+        );
+        counter!("audit_log_rate_limited_total", 1, "provider" => self.provider);
+    }
+}
+
+#[derive(Debug)]
+pub struct AuditLogCheckpointError {
+    pub error: crate::Error,
+}
+
+impl InternalEvent for AuditLogCheckpointError {
+    fn emit(self) {
+        error!(
+            message = "Failed to persist cursors.",
+            error = %self.error,
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
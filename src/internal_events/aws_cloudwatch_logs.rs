@@ -1,3 +1,5 @@
+use std::fmt::Display;
+
 use metrics::counter;
 use vector_common::internal_event::{error_stage, error_type};
 use vector_core::internal_event::InternalEvent;
@@ -33,3 +35,57 @@ impl InternalEvent for AwsCloudwatchLogsMessageSizeError {
         emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
     }
 }
+
+#[derive(Debug)]
+pub struct AwsCloudwatchLogsRequestError<'a, E> {
+    pub operation: &'static str,
+    pub error: E,
+    pub log_group: Option<&'a str>,
+}
+
+impl<'a, E: Display> InternalEvent for AwsCloudwatchLogsRequestError<'a, E> {
+    fn emit(self) {
+        error!(
+            message = "Request to AWS CloudWatch Logs failed.",
+            operation = %self.operation,
+            error = %self.error,
+            log_group = %self.log_group.unwrap_or_default(),
+            error_code = "failed_cloudwatch_logs_request",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_cloudwatch_logs_request",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct AwsCloudwatchLogsCheckpointError<'a> {
+    pub error: crate::Error,
+    pub log_group: &'a str,
+}
+
+impl<'a> InternalEvent for AwsCloudwatchLogsCheckpointError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to checkpoint log group.",
+            error = %self.error,
+            log_group = %self.log_group,
+            error_code = "failed_checkpointing_log_group",
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_checkpointing_log_group",
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
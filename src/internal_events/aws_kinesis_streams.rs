@@ -0,0 +1,59 @@
+use std::fmt::Display;
+
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct AwsKinesisStreamsRequestError<'a, E> {
+    pub operation: &'static str,
+    pub error: E,
+    pub shard_id: Option<&'a str>,
+}
+
+impl<'a, E: Display> InternalEvent for AwsKinesisStreamsRequestError<'a, E> {
+    fn emit(self) {
+        error!(
+            message = "Request to AWS Kinesis failed.",
+            operation = %self.operation,
+            error = %self.error,
+            shard_id = %self.shard_id.unwrap_or_default(),
+            error_code = "failed_kinesis_request",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_kinesis_request",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct AwsKinesisStreamsCheckpointError<'a> {
+    pub error: crate::Error,
+    pub shard_id: &'a str,
+}
+
+impl<'a> InternalEvent for AwsKinesisStreamsCheckpointError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to checkpoint shard.",
+            error = %self.error,
+            shard_id = %self.shard_id,
+            error_code = "failed_checkpointing_shard",
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_checkpointing_shard",
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
@@ -122,6 +122,93 @@ mod s3 {
             counter!("sqs_message_delete_batch_failed_total", 1);
         }
     }
+
+    #[derive(Debug)]
+    pub struct S3BackfillObjectRead<'a> {
+        pub key: &'a str,
+        pub objects_read: u64,
+    }
+
+    impl<'a> InternalEvent for S3BackfillObjectRead<'a> {
+        fn emit(self) {
+            debug!(
+                message = "Backfilled S3 object.",
+                key = %self.key,
+                objects_read = %self.objects_read,
+            );
+            counter!("s3_backfill_objects_read_total", 1);
+        }
+    }
+
+    #[derive(Debug)]
+    pub struct S3BackfillObjectError<'a> {
+        pub error: &'a ProcessingError,
+    }
+
+    impl<'a> InternalEvent for S3BackfillObjectError<'a> {
+        fn emit(self) {
+            error!(
+                message = "Failed to backfill S3 object, stopping the backfill.",
+                error = %self.error,
+                error_code = "failed_backfilling_s3_object",
+                error_type = error_type::READER_FAILED,
+                stage = error_stage::RECEIVING,
+            );
+            counter!(
+                "component_errors_total", 1,
+                "error_code" => "failed_backfilling_s3_object",
+                "error_type" => error_type::READER_FAILED,
+                "stage" => error_stage::RECEIVING,
+            );
+        }
+    }
+
+    #[derive(Debug)]
+    pub struct S3BackfillListError<'a, E> {
+        pub bucket: &'a str,
+        pub error: &'a E,
+    }
+
+    impl<'a, E: std::fmt::Display> InternalEvent for S3BackfillListError<'a, E> {
+        fn emit(self) {
+            error!(
+                message = "Failed to list S3 objects, stopping the backfill.",
+                bucket = %self.bucket,
+                error = %self.error,
+                error_code = "failed_listing_s3_objects",
+                error_type = error_type::REQUEST_FAILED,
+                stage = error_stage::RECEIVING,
+            );
+            counter!(
+                "component_errors_total", 1,
+                "error_code" => "failed_listing_s3_objects",
+                "error_type" => error_type::REQUEST_FAILED,
+                "stage" => error_stage::RECEIVING,
+            );
+        }
+    }
+
+    #[derive(Debug)]
+    pub struct S3BackfillCheckpointError {
+        pub error: crate::Error,
+    }
+
+    impl InternalEvent for S3BackfillCheckpointError {
+        fn emit(self) {
+            error!(
+                message = "Failed to persist the progress of the backfill.",
+                error = %self.error,
+                error_type = error_type::WRITER_FAILED,
+                stage = error_stage::PROCESSING,
+                internal_log_rate_limit = true,
+            );
+            counter!(
+                "component_errors_total", 1,
+                "error_type" => error_type::WRITER_FAILED,
+                "stage" => error_stage::PROCESSING,
+            );
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -203,6 +290,30 @@ impl<'a, E: std::fmt::Display> InternalEvent for SqsMessageDeleteError<'a, E> {
     }
 }
 
+#[cfg(feature = "sources-aws_sqs")]
+#[derive(Debug)]
+pub struct SqsMessageVisibilityError<'a, E> {
+    pub error: &'a E,
+}
+
+#[cfg(feature = "sources-aws_sqs")]
+impl<'a, E: std::fmt::Display> InternalEvent for SqsMessageVisibilityError<'a, E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to make the failed SQS events visible again.",
+            error = %self.error,
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
 // AWS s3 source
 
 #[derive(Debug)]
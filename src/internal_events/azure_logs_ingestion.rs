@@ -0,0 +1,22 @@
+use vector_core::internal_event::InternalEvent;
+
+use crate::sinks::azure_logs_ingestion::ColumnType;
+
+#[derive(Debug)]
+pub struct AzureLogsIngestionColumnConversionError<'a> {
+    pub column: &'a str,
+    pub field: &'a str,
+    pub type_: ColumnType,
+}
+
+impl<'a> InternalEvent for AzureLogsIngestionColumnConversionError<'a> {
+    fn emit(self) {
+        warn!(
+            message = "Field couldn't be converted to the type of its column; column left out of the record.",
+            column = %self.column,
+            field = %self.field,
+            column_type = ?self.type_,
+            internal_log_rate_limit = true,
+        );
+    }
+}
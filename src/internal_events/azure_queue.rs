@@ -0,0 +1,113 @@
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+use crate::sources::azure_blob::queue::ProcessingError;
+
+#[derive(Debug)]
+pub struct AzureQueueMessageReceiveError<'a, E> {
+    pub error: &'a E,
+}
+
+impl<'a, E: std::fmt::Display> InternalEvent for AzureQueueMessageReceiveError<'a, E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to fetch Azure Queue messages.",
+            error = %self.error,
+            error_code = "failed_fetching_azure_queue_messages",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_fetching_azure_queue_messages",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct AzureQueueMessageReceiveSucceeded {
+    pub count: usize,
+}
+
+impl InternalEvent for AzureQueueMessageReceiveSucceeded {
+    fn emit(self) {
+        trace!(message = "Received Azure Queue messages.", count = %self.count);
+        counter!(
+            "azure_queue_message_received_messages_total",
+            self.count as u64
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct AzureQueueMessageProcessingError<'a> {
+    pub message_id: &'a str,
+    pub error: &'a ProcessingError,
+}
+
+impl<'a> InternalEvent for AzureQueueMessageProcessingError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to process Azure Queue message.",
+            message_id = %self.message_id,
+            error = %self.error,
+            error_code = "failed_processing_azure_queue_message",
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_processing_azure_queue_message",
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct AzureQueueMessageDeleteError<'a, E> {
+    pub message_id: &'a str,
+    pub error: &'a E,
+}
+
+impl<'a, E: std::fmt::Display> InternalEvent for AzureQueueMessageDeleteError<'a, E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to delete Azure Queue message.",
+            message_id = %self.message_id,
+            error = %self.error,
+            error_code = "failed_deleting_azure_queue_message",
+            error_type = error_type::ACKNOWLEDGMENT_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_deleting_azure_queue_message",
+            "error_type" => error_type::ACKNOWLEDGMENT_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct AzureBlobEventIgnored<'a> {
+    pub event_type: &'a str,
+    pub subject: &'a str,
+}
+
+impl<'a> InternalEvent for AzureBlobEventIgnored<'a> {
+    fn emit(self) {
+        debug!(
+            message = "Ignored Event Grid event that was not a blob creation.",
+            event_type = %self.event_type,
+            subject = %self.subject,
+        );
+        counter!("azure_queue_event_ignored_total", 1, "ignore_type" => "invalid_event_type");
+    }
+}
@@ -0,0 +1,40 @@
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+use crate::{
+    emit,
+    internal_events::{ComponentEventsDropped, INTENTIONAL},
+};
+
+#[derive(Debug)]
+pub struct BlackholeInjectedError {
+    pub count: usize,
+    pub status: &'static str,
+}
+
+impl InternalEvent for BlackholeInjectedError {
+    fn emit(self) {
+        let reason = "Injected error.";
+        debug!(
+            message = reason,
+            count = %self.count,
+            status = %self.status,
+            error_code = "injected_error",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "injected_error",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+
+        emit!(ComponentEventsDropped::<INTENTIONAL> {
+            count: self.count as u64,
+            reason,
+        });
+    }
+}
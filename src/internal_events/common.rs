@@ -208,6 +208,24 @@ impl<E: std::fmt::Display> InternalEvent for SinkRequestBuildError<E> {
     }
 }
 
+#[derive(Debug)]
+pub struct SinkRequestSplit {
+    pub byte_size: usize,
+    pub max_byte_size: usize,
+}
+
+impl InternalEvent for SinkRequestSplit {
+    fn emit(self) {
+        debug!(
+            message = "Encoded payload is larger than the limit; splitting the request in half.",
+            byte_size = %self.byte_size,
+            max_byte_size = %self.max_byte_size,
+            internal_log_rate_limit = true,
+        );
+        counter!("component_request_splits_total", 1);
+    }
+}
+
 #[derive(Debug)]
 pub struct SinkSendError<E> {
     pub message: &'static str,
@@ -26,3 +26,25 @@ impl<'a> InternalEvent for VrlConditionExecutionError<'a> {
         counter!("processing_errors_total", 1);
     }
 }
+
+#[derive(Debug, Copy, Clone)]
+pub struct CelConditionExecutionError<'a> {
+    pub error: &'a str,
+}
+
+impl<'a> InternalEvent for CelConditionExecutionError<'a> {
+    fn emit(self) {
+        error!(
+            message = "CEL condition execution failed.",
+            error = %self.error,
+            internal_log_rate_limit = true,
+            error_type = error_type::SCRIPT_FAILED,
+            stage = error_stage::PROCESSING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::SCRIPT_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
@@ -0,0 +1,61 @@
+use metrics::gauge;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct DataDirUsage<'a> {
+    pub component_id: &'a str,
+    pub byte_size: u64,
+}
+
+impl<'a> InternalEvent for DataDirUsage<'a> {
+    fn emit(self) {
+        trace!(
+            message = "Measured disk usage of component.",
+            component_id = %self.component_id,
+            byte_size = %self.byte_size,
+        );
+        gauge!(
+            "component_data_dir_size_bytes", self.byte_size as f64,
+            "component_id" => self.component_id.to_owned(),
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct DataDirQuotaExceeded<'a> {
+    pub component_id: &'a str,
+    pub byte_size: u64,
+    pub quota: u64,
+}
+
+impl<'a> InternalEvent for DataDirQuotaExceeded<'a> {
+    fn emit(self) {
+        warn!(
+            message = "Component exceeds its quota of the data directory.",
+            component_id = %self.component_id,
+            byte_size = %self.byte_size,
+            quota = %self.quota,
+            internal_log_rate_limit = true,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct DataDirUsageError<'a> {
+    pub component_id: &'a str,
+    pub error: std::io::Error,
+}
+
+impl<'a> InternalEvent for DataDirUsageError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to measure disk usage of component.",
+            component_id = %self.component_id,
+            error = %self.error,
+            error_type = error_type::IO_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+    }
+}
@@ -0,0 +1,39 @@
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+use crate::{
+    emit,
+    internal_events::{ComponentEventsDropped, UNINTENTIONAL},
+};
+
+#[derive(Debug)]
+pub struct DorisRowsFiltered<'a> {
+    pub count: u64,
+    pub table: &'a str,
+    pub error_url: Option<&'a str>,
+}
+
+impl<'a> InternalEvent for DorisRowsFiltered<'a> {
+    fn emit(self) {
+        let reason = "Rows filtered out by the stream load.";
+        error!(
+            message = reason,
+            count = self.count,
+            table = %self.table,
+            error_url = ?self.error_url,
+            error_type = error_type::CONVERSION_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::CONVERSION_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+            count: self.count,
+            reason,
+        });
+    }
+}
@@ -0,0 +1,58 @@
+use aya::maps::perf::PerfBufferError;
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+use crate::{
+    emit,
+    internal_events::{ComponentEventsDropped, UNINTENTIONAL},
+};
+
+#[derive(Debug)]
+pub struct EbpfReadError {
+    pub error: PerfBufferError,
+}
+
+impl InternalEvent for EbpfReadError {
+    fn emit(self) {
+        error!(
+            message = "Error reading events from BPF programs.",
+            error = %self.error,
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct EbpfEventsLost {
+    pub count: usize,
+}
+
+impl InternalEvent for EbpfEventsLost {
+    fn emit(self) {
+        let reason = "Events buffer of BPF programs is full.";
+        error!(
+            message = reason,
+            count = self.count,
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+            count: self.count,
+            reason,
+        });
+    }
+}
@@ -0,0 +1,58 @@
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+use crate::{
+    emit,
+    internal_events::{ComponentEventsDropped, UNINTENTIONAL},
+};
+
+#[derive(Debug)]
+pub struct EncryptFieldsKeyError {
+    pub error: crate::Error,
+}
+
+impl InternalEvent for EncryptFieldsKeyError {
+    fn emit(self) {
+        let reason = "Failed to wrap data key.";
+        error!(
+            message = reason,
+            error = %self.error,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
+
+#[derive(Debug)]
+pub struct EncryptFieldsEncryptionError<'a> {
+    pub field: &'a str,
+    pub error: crate::Error,
+}
+
+impl<'a> InternalEvent for EncryptFieldsEncryptionError<'a> {
+    fn emit(self) {
+        let reason = "Failed to encrypt field.";
+        error!(
+            message = reason,
+            field = %self.field,
+            error = %self.error,
+            error_type = error_type::ENCODER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::ENCODER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
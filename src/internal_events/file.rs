@@ -375,6 +375,30 @@ mod source {
         }
     }
 
+    #[derive(Debug)]
+    pub struct FileCheckpointStoreError {
+        pub error: crate::Error,
+    }
+
+    impl InternalEvent for FileCheckpointStoreError {
+        fn emit(self) {
+            error!(
+                message = "Failed copying checkpoints to the state store.",
+                error = %self.error,
+                error_code = "storing_checkpoints",
+                error_type = error_type::WRITER_FAILED,
+                stage = error_stage::RECEIVING,
+                internal_log_rate_limit = true,
+            );
+            counter!(
+                "component_errors_total", 1,
+                "error_code" => "storing_checkpoints",
+                "error_type" => error_type::WRITER_FAILED,
+                "stage" => error_stage::RECEIVING,
+            );
+        }
+    }
+
     #[derive(Debug)]
     pub struct PathGlobbingError<'a> {
         pub path: &'a Path,
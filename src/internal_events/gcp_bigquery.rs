@@ -0,0 +1,18 @@
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct GcpBigqueryFieldConversionError<'a> {
+    pub field: &'a str,
+    pub column_type: &'static str,
+}
+
+impl<'a> InternalEvent for GcpBigqueryFieldConversionError<'a> {
+    fn emit(self) {
+        warn!(
+            message = "Field couldn't be converted to the type of its column; column left out of the row.",
+            field = %self.field,
+            column_type = %self.column_type,
+            internal_log_rate_limit = true,
+        );
+    }
+}
@@ -48,3 +48,27 @@ where
         );
     }
 }
+
+#[derive(Debug)]
+pub struct GrpcHttpFallbackError<'a> {
+    pub error: &'a str,
+    pub http_code: u16,
+}
+
+impl InternalEvent for GrpcHttpFallbackError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed decoding the plain HTTP request.",
+            error = %self.error,
+            http_code = %self.http_code,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
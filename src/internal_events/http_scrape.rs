@@ -89,3 +89,88 @@ impl InternalEvent for HttpScrapeHttpError {
         counter!("http_request_errors_total", 1);
     }
 }
+
+#[derive(Debug)]
+pub struct HttpScrapeResponseParseError<E> {
+    pub error: E,
+    pub url: String,
+}
+
+impl<E: std::fmt::Display> InternalEvent for HttpScrapeResponseParseError<E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to parse HTTP response.",
+            url = %self.url,
+            error = %self.error,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct HttpScrapeMaxPagesReached {
+    pub max_pages: usize,
+    pub url: String,
+}
+
+impl InternalEvent for HttpScrapeMaxPagesReached {
+    fn emit(self) {
+        warn!(
+            message = "Maximum number of pages reached, ending scrape early.",
+            max_pages = self.max_pages,
+            url = %self.url,
+            internal_log_rate_limit = true,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct HttpScrapeCheckpointError {
+    pub error: crate::Error,
+}
+
+impl InternalEvent for HttpScrapeCheckpointError {
+    fn emit(self) {
+        error!(
+            message = "Failed to persist checkpoint.",
+            error = %self.error,
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct HttpScrapeHeadersError {
+    pub error: String,
+}
+
+impl InternalEvent for HttpScrapeHeadersError {
+    fn emit(self) {
+        error!(
+            message = "Failed to compute request headers, skipping request.",
+            error = %self.error,
+            error_type = error_type::SCRIPT_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::SCRIPT_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
@@ -0,0 +1,34 @@
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+use crate::{
+    emit,
+    internal_events::{ComponentEventsDropped, UNINTENTIONAL},
+};
+
+#[derive(Debug)]
+pub struct HttpSinkInvalidRequestPart<'a> {
+    pub part: &'static str,
+    pub value: &'a str,
+}
+
+impl<'a> InternalEvent for HttpSinkInvalidRequestPart<'a> {
+    fn emit(self) {
+        let reason = "Rendered request part is invalid.";
+        error!(
+            message = reason,
+            part = self.part,
+            value = %self.value,
+            error_type = error_type::ENCODER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::ENCODER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
@@ -0,0 +1,56 @@
+use std::net::AddrParseError;
+
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct IpEnrichmentAddressParseError<'a> {
+    pub error: AddrParseError,
+    pub address: &'a str,
+}
+
+impl<'a> InternalEvent for IpEnrichmentAddressParseError<'a> {
+    fn emit(self) {
+        error!(
+            message = format!("IP Address not parsed correctly: {:?}.", self.error),
+            error_code = "invalid_ip_address",
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            address = %self.address,
+            internal_log_rate_limit = true
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "invalid_ip_address",
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct IpEnrichmentLookupError<'a> {
+    pub table: &'a str,
+    pub error: String,
+}
+
+impl<'a> InternalEvent for IpEnrichmentLookupError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to look up IP address in enrichment table.",
+            table = %self.table,
+            error = %self.error,
+            error_code = "enrichment_table_lookup",
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "enrichment_table_lookup",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
@@ -84,6 +84,79 @@ impl InternalEvent for KafkaOffsetUpdateError {
     }
 }
 
+#[derive(Debug)]
+pub struct KafkaAbortTransactionError {
+    pub error: rdkafka::error::KafkaError,
+}
+
+impl InternalEvent for KafkaAbortTransactionError {
+    fn emit(self) {
+        error!(
+            message = "Unable to abort transaction.",
+            error = %self.error,
+            error_code = "kafka_abort_transaction",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::SENDING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "kafka_abort_transaction",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct KafkaPauseResumeError {
+    pub error: rdkafka::error::KafkaError,
+    pub action: &'static str,
+}
+
+impl InternalEvent for KafkaPauseResumeError {
+    fn emit(self) {
+        error!(
+            message = "Unable to pause or resume partitions.",
+            error = %self.error,
+            action = %self.action,
+            error_code = "kafka_pause_resume",
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "kafka_pause_resume",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct KafkaSeekError {
+    pub error: rdkafka::error::KafkaError,
+}
+
+impl InternalEvent for KafkaSeekError {
+    fn emit(self) {
+        error!(
+            message = "Unable to seek back to the failed message.",
+            error = %self.error,
+            error_code = "kafka_seek",
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "kafka_seek",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
 #[derive(Debug)]
 pub struct KafkaReadError {
     pub error: rdkafka::error::KafkaError,
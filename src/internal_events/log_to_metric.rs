@@ -93,3 +93,31 @@ impl InternalEvent for LogToMetricTemplateParseError {
         );
     }
 }
+
+pub struct LogToMetricVrlError<'a> {
+    pub error: &'a str,
+}
+
+impl<'a> InternalEvent for LogToMetricVrlError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to compute metrics from VRL program.",
+            error = %self.error,
+            error_code = "failed_computing_metrics",
+            error_type = error_type::SCRIPT_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_computing_metrics",
+            "error_type" => error_type::SCRIPT_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        // deprecated
+        counter!(
+            "processing_errors_total", 1,
+            "error_type" => "vrl_error",
+        );
+    }
+}
@@ -56,3 +56,20 @@ impl InternalEvent for LokiOutOfOrderEventRewritten {
                 "error_type" => "out_of_order");
     }
 }
+
+#[derive(Debug)]
+pub struct LokiLabelCardinalityExceeded<'a> {
+    pub label: &'a str,
+}
+
+impl<'a> InternalEvent for LokiLabelCardinalityExceeded<'a> {
+    fn emit(self) {
+        warn!(
+            message = "Label cardinality limit reached, sending value as structured metadata.",
+            label = %self.label,
+            internal_log_rate_limit = true,
+        );
+        counter!("label_cardinality_exceeded_total", 1,
+                "label" => self.label.to_owned());
+    }
+}
@@ -8,9 +8,14 @@ mod amqp;
 mod apache_metrics;
 #[cfg(feature = "api")]
 mod api;
+#[cfg(feature = "sources-utils-audit-log")]
+mod audit_log;
 #[cfg(feature = "aws-core")]
 mod aws;
-#[cfg(feature = "sinks-aws_cloudwatch_logs")]
+#[cfg(any(
+    feature = "sources-aws_cloudwatch_logs",
+    feature = "sinks-aws_cloudwatch_logs"
+))]
 mod aws_cloudwatch_logs;
 #[cfg(feature = "transforms-aws_ec2_metadata")]
 mod aws_ec2_metadata;
@@ -18,12 +23,21 @@ mod aws_ec2_metadata;
 mod aws_ecs_metrics;
 #[cfg(feature = "sources-aws_kinesis_firehose")]
 mod aws_kinesis_firehose;
+#[cfg(feature = "sources-aws_kinesis_streams")]
+mod aws_kinesis_streams;
 #[cfg(any(feature = "sources-aws_s3", feature = "sources-aws_sqs",))]
 mod aws_sqs;
+#[cfg(feature = "sinks-azure_logs_ingestion")]
+mod azure_logs_ingestion;
+#[cfg(feature = "sources-azure_blob")]
+mod azure_queue;
 mod batch;
+#[cfg(feature = "sinks-blackhole")]
+mod blackhole;
 mod codecs;
 mod common;
 mod conditions;
+mod data_dir;
 #[cfg(feature = "sinks-datadog_metrics")]
 mod datadog_metrics;
 #[cfg(feature = "sinks-datadog_traces")]
@@ -36,7 +50,13 @@ mod demo_logs;
 mod dnstap;
 #[cfg(feature = "sources-docker_logs")]
 mod docker_logs;
+#[cfg(feature = "sinks-doris")]
+mod doris;
+#[cfg(all(target_os = "linux", feature = "sources-ebpf"))]
+mod ebpf;
 mod encoding_transcode;
+#[cfg(feature = "transforms-encrypt_fields")]
+mod encrypt_fields;
 #[cfg(feature = "sources-eventstoredb_metrics")]
 mod eventstoredb_metrics;
 #[cfg(feature = "sources-exec")]
@@ -47,6 +67,8 @@ mod file_descriptor;
 mod filter;
 #[cfg(feature = "sources-fluent")]
 mod fluent;
+#[cfg(feature = "sinks-gcp_bigquery")]
+mod gcp_bigquery;
 #[cfg(feature = "sources-gcp_pubsub")]
 mod gcp_pubsub;
 #[cfg(feature = "transforms-geoip")]
@@ -54,18 +76,24 @@ mod geoip;
 #[cfg(any(feature = "sources-vector", feature = "sources-opentelemetry"))]
 mod grpc;
 mod heartbeat;
+#[cfg(feature = "sinks-honeycomb")]
+mod honeycomb;
 #[cfg(feature = "sources-host_metrics")]
 mod host_metrics;
 mod http;
 pub mod http_client;
 #[cfg(feature = "sources-utils-http-scrape")]
 mod http_scrape;
+#[cfg(feature = "sinks-http")]
+mod http_sink;
 #[cfg(feature = "sinks-influxdb")]
 mod influxdb;
 #[cfg(feature = "sources-internal_logs")]
 mod internal_logs;
 #[cfg(feature = "sources-internal_metrics")]
 mod internal_metrics;
+#[cfg(feature = "transforms-ip_enrichment")]
+mod ip_enrichment;
 #[cfg(all(unix, feature = "sources-journald"))]
 mod journald;
 #[cfg(any(feature = "sources-kafka", feature = "sinks-kafka"))]
@@ -82,12 +110,20 @@ mod lua;
 mod metric_to_log;
 #[cfg(feature = "sources-mongodb_metrics")]
 mod mongodb_metrics;
+#[cfg(feature = "sources-mysql_cdc")]
+mod mysql_cdc;
 #[cfg(feature = "sinks-nats")]
 mod nats;
+#[cfg(feature = "sources-netflow")]
+mod netflow;
 #[cfg(feature = "sources-nginx_metrics")]
 mod nginx_metrics;
 mod open;
+#[cfg(feature = "sinks-pagerduty")]
+mod pagerduty;
 mod parser;
+#[cfg(feature = "sources-postgres_cdc")]
+mod postgres_cdc;
 #[cfg(feature = "sources-postgresql_metrics")]
 mod postgresql_metrics;
 mod process;
@@ -95,17 +131,27 @@ mod process;
 mod prometheus;
 #[cfg(feature = "sinks-pulsar")]
 mod pulsar;
+#[cfg(feature = "sinks-quickwit")]
+mod quickwit;
 #[cfg(feature = "sources-redis")]
 mod redis;
+#[cfg(feature = "transforms-redact_pii")]
+mod redact_pii;
 #[cfg(feature = "transforms-reduce")]
 mod reduce;
 mod remap;
 mod sample;
 #[cfg(feature = "sinks-sematext")]
 mod sematext_metrics;
+#[cfg(feature = "sinks-smtp")]
+mod smtp;
+#[cfg(feature = "sources-snmp")]
+mod snmp;
 mod socket;
 #[cfg(any(feature = "sources-splunk_hec", feature = "sinks-splunk_hec"))]
 mod splunk_hec;
+#[cfg(feature = "sinks-splunk_signalfx")]
+mod splunk_signalfx;
 #[cfg(feature = "sinks-statsd")]
 mod statsd_sink;
 #[cfg(feature = "sources-statsd")]
@@ -116,10 +162,13 @@ mod syslog;
 mod tag_cardinality_limit;
 mod tcp;
 mod template;
+mod tenancy;
 #[cfg(feature = "transforms-throttle")]
 mod throttle;
 mod udp;
 mod unix;
+#[cfg(feature = "sinks-vector")]
+mod vector;
 #[cfg(feature = "sinks-websocket")]
 mod websocket;
 
@@ -142,9 +191,14 @@ pub(crate) use self::amqp::*;
 pub(crate) use self::apache_metrics::*;
 #[cfg(feature = "api")]
 pub(crate) use self::api::*;
+#[cfg(feature = "sources-utils-audit-log")]
+pub(crate) use self::audit_log::*;
 #[cfg(feature = "aws-core")]
 pub(crate) use self::aws::*;
-#[cfg(feature = "sinks-aws_cloudwatch_logs")]
+#[cfg(any(
+    feature = "sources-aws_cloudwatch_logs",
+    feature = "sinks-aws_cloudwatch_logs"
+))]
 pub(crate) use self::aws_cloudwatch_logs::*;
 #[cfg(feature = "transforms-aws_ec2_metadata")]
 pub(crate) use self::aws_ec2_metadata::*;
@@ -152,8 +206,16 @@ pub(crate) use self::aws_ec2_metadata::*;
 pub(crate) use self::aws_ecs_metrics::*;
 #[cfg(feature = "sources-aws_kinesis_firehose")]
 pub(crate) use self::aws_kinesis_firehose::*;
+#[cfg(feature = "sources-aws_kinesis_streams")]
+pub(crate) use self::aws_kinesis_streams::*;
 #[cfg(any(feature = "sources-aws_s3", feature = "sources-aws_sqs",))]
 pub(crate) use self::aws_sqs::*;
+#[cfg(feature = "sinks-azure_logs_ingestion")]
+pub(crate) use self::azure_logs_ingestion::*;
+#[cfg(feature = "sources-azure_blob")]
+pub(crate) use self::azure_queue::*;
+#[cfg(feature = "sinks-blackhole")]
+pub(crate) use self::blackhole::*;
 pub(crate) use self::codecs::*;
 #[cfg(feature = "sinks-datadog_metrics")]
 pub(crate) use self::datadog_metrics::*;
@@ -167,6 +229,12 @@ pub(crate) use self::demo_logs::*;
 pub(crate) use self::dnstap::*;
 #[cfg(feature = "sources-docker_logs")]
 pub(crate) use self::docker_logs::*;
+#[cfg(feature = "sinks-doris")]
+pub(crate) use self::doris::*;
+#[cfg(all(target_os = "linux", feature = "sources-ebpf"))]
+pub(crate) use self::ebpf::*;
+#[cfg(feature = "transforms-encrypt_fields")]
+pub(crate) use self::encrypt_fields::*;
 #[cfg(feature = "sources-eventstoredb_metrics")]
 pub(crate) use self::eventstoredb_metrics::*;
 #[cfg(feature = "sources-exec")]
@@ -183,12 +251,16 @@ pub(crate) use self::file_descriptor::*;
 pub(crate) use self::filter::*;
 #[cfg(feature = "sources-fluent")]
 pub(crate) use self::fluent::*;
+#[cfg(feature = "sinks-gcp_bigquery")]
+pub(crate) use self::gcp_bigquery::*;
 #[cfg(feature = "sources-gcp_pubsub")]
 pub(crate) use self::gcp_pubsub::*;
 #[cfg(feature = "transforms-geoip")]
 pub(crate) use self::geoip::*;
 #[cfg(any(feature = "sources-vector", feature = "sources-opentelemetry"))]
 pub(crate) use self::grpc::*;
+#[cfg(feature = "sinks-honeycomb")]
+pub(crate) use self::honeycomb::*;
 #[cfg(feature = "sources-host_metrics")]
 pub(crate) use self::host_metrics::*;
 #[cfg(any(
@@ -200,12 +272,16 @@ pub(crate) use self::host_metrics::*;
 pub(crate) use self::http::*;
 #[cfg(feature = "sources-utils-http-scrape")]
 pub(crate) use self::http_scrape::*;
+#[cfg(feature = "sinks-http")]
+pub(crate) use self::http_sink::*;
 #[cfg(feature = "sinks-influxdb")]
 pub(crate) use self::influxdb::*;
 #[cfg(feature = "sources-internal_logs")]
 pub(crate) use self::internal_logs::*;
 #[cfg(feature = "sources-internal_metrics")]
 pub(crate) use self::internal_metrics::*;
+#[cfg(feature = "transforms-ip_enrichment")]
+pub(crate) use self::ip_enrichment::*;
 #[cfg(all(unix, feature = "sources-journald"))]
 pub(crate) use self::journald::*;
 #[cfg(any(feature = "sources-kafka", feature = "sinks-kafka"))]
@@ -221,19 +297,31 @@ pub(crate) use self::loki::*;
 pub(crate) use self::lua::*;
 #[cfg(feature = "transforms-metric_to_log")]
 pub(crate) use self::metric_to_log::*;
+#[cfg(feature = "sources-mysql_cdc")]
+pub(crate) use self::mysql_cdc::*;
 #[cfg(feature = "sinks-nats")]
 pub(crate) use self::nats::*;
+#[cfg(feature = "sources-netflow")]
+pub(crate) use self::netflow::*;
 #[cfg(feature = "sources-nginx_metrics")]
 pub(crate) use self::nginx_metrics::*;
+#[cfg(feature = "sinks-pagerduty")]
+pub(crate) use self::pagerduty::*;
 pub(crate) use self::parser::*;
+#[cfg(feature = "sources-postgres_cdc")]
+pub(crate) use self::postgres_cdc::*;
 #[cfg(feature = "sources-postgresql_metrics")]
 pub(crate) use self::postgresql_metrics::*;
 #[cfg(any(feature = "sources-prometheus", feature = "sinks-prometheus"))]
 pub(crate) use self::prometheus::*;
 #[cfg(feature = "sinks-pulsar")]
 pub(crate) use self::pulsar::*;
+#[cfg(feature = "sinks-quickwit")]
+pub(crate) use self::quickwit::*;
 #[cfg(feature = "sources-redis")]
 pub(crate) use self::redis::*;
+#[cfg(feature = "transforms-redact_pii")]
+pub(crate) use self::redact_pii::*;
 #[cfg(feature = "transforms-reduce")]
 pub(crate) use self::reduce::*;
 #[cfg(feature = "transforms-remap")]
@@ -242,8 +330,14 @@ pub(crate) use self::remap::*;
 pub(crate) use self::sample::*;
 #[cfg(feature = "sinks-sematext")]
 pub(crate) use self::sematext_metrics::*;
+#[cfg(feature = "sinks-smtp")]
+pub(crate) use self::smtp::*;
+#[cfg(feature = "sources-snmp")]
+pub(crate) use self::snmp::*;
 #[cfg(any(feature = "sources-splunk_hec", feature = "sinks-splunk_hec"))]
 pub(crate) use self::splunk_hec::*;
+#[cfg(feature = "sinks-splunk_signalfx")]
+pub(crate) use self::splunk_signalfx::*;
 #[cfg(feature = "sinks-statsd")]
 pub(crate) use self::statsd_sink::*;
 #[cfg(feature = "sources-statsd")]
@@ -267,13 +361,16 @@ pub(crate) use self::throttle::*;
     unix
 ))]
 pub(crate) use self::unix::*;
+#[cfg(feature = "sinks-vector")]
+pub(crate) use self::vector::*;
 #[cfg(feature = "sinks-websocket")]
 pub(crate) use self::websocket::*;
 #[cfg(windows)]
 pub(crate) use self::windows::*;
 pub(crate) use self::{
-    adaptive_concurrency::*, batch::*, common::*, conditions::*, encoding_transcode::*,
-    heartbeat::*, open::*, process::*, socket::*, tcp::*, template::*, udp::*,
+    adaptive_concurrency::*, batch::*, common::*, conditions::*, data_dir::*,
+    encoding_transcode::*, heartbeat::*, open::*, process::*, socket::*, tcp::*, template::*,
+    tenancy::*, udp::*,
 };
 
 // this version won't be needed once all `InternalEvent`s implement `name()`
@@ -0,0 +1,47 @@
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct MysqlCdcStreamError<E> {
+    pub error: E,
+}
+
+impl<E: std::fmt::Display> InternalEvent for MysqlCdcStreamError<E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to stream the changes of the binlog.",
+            error = %self.error,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct MysqlCdcCheckpointError {
+    pub error: crate::Error,
+}
+
+impl InternalEvent for MysqlCdcCheckpointError {
+    fn emit(self) {
+        error!(
+            message = "Failed to persist the GTID set.",
+            error = %self.error,
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
@@ -0,0 +1,51 @@
+use std::net::SocketAddr;
+
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct NetflowSocketError {
+    pub error: std::io::Error,
+}
+
+impl InternalEvent for NetflowSocketError {
+    fn emit(self) {
+        error!(
+            message = "Error receiving flows.",
+            error = %self.error,
+            error_type = error_type::CONNECTION_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::CONNECTION_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct NetflowDecodeError<E> {
+    pub error: E,
+    pub peer: SocketAddr,
+}
+
+impl<E: std::fmt::Display> InternalEvent for NetflowDecodeError<E> {
+    fn emit(self) {
+        error!(
+            message = "Could not decode flows.",
+            error = %self.error,
+            peer = %self.peer,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
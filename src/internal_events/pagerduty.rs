@@ -0,0 +1,54 @@
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+use crate::{
+    emit,
+    internal_events::{ComponentEventsDropped, INTENTIONAL, UNINTENTIONAL},
+};
+
+#[derive(Debug)]
+pub struct PagerDutyInvalidEvent {
+    pub reason: &'static str,
+}
+
+impl InternalEvent for PagerDutyInvalidEvent {
+    fn emit(self) {
+        error!(
+            message = "Invalid PagerDuty event.",
+            reason = self.reason,
+            error_type = error_type::CONVERSION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::CONVERSION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+            count: 1,
+            reason: self.reason,
+        });
+    }
+}
+
+#[derive(Debug)]
+pub struct PagerDutyRateLimited {
+    pub count: usize,
+}
+
+impl InternalEvent for PagerDutyRateLimited {
+    fn emit(self) {
+        // The routing key is a secret, so it isn't logged.
+        debug!(
+            message = "Rate limit of routing key exceeded, dropping event.",
+            count = self.count,
+            internal_log_rate_limit = true,
+        );
+        emit!(ComponentEventsDropped::<INTENTIONAL> {
+            count: self.count as u64,
+            reason: "Rate limit exceeded.",
+        });
+    }
+}
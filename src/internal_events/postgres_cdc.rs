@@ -0,0 +1,27 @@
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct PostgresCdcPollError<'a, E> {
+    pub error: E,
+    pub slot: &'a str,
+}
+
+impl<'a, E: std::fmt::Display> InternalEvent for PostgresCdcPollError<'a, E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to poll the changes of the replication slot.",
+            error = %self.error,
+            slot = %self.slot,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
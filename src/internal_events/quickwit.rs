@@ -0,0 +1,37 @@
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+use crate::{
+    emit,
+    internal_events::{ComponentEventsDropped, UNINTENTIONAL},
+};
+
+#[derive(Debug)]
+pub struct QuickwitDocsRejected<'a> {
+    pub count: u64,
+    pub index: &'a str,
+}
+
+impl<'a> InternalEvent for QuickwitDocsRejected<'a> {
+    fn emit(self) {
+        let reason = "Documents rejected by the index.";
+        error!(
+            message = reason,
+            count = self.count,
+            index = %self.index,
+            error_type = error_type::CONVERSION_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::CONVERSION_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+            count: self.count,
+            reason,
+        });
+    }
+}
@@ -0,0 +1,22 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct RedactPiiRedacted {
+    pub detector: &'static str,
+    pub count: usize,
+}
+
+impl InternalEvent for RedactPiiRedacted {
+    fn emit(self) {
+        trace!(
+            message = "Redacted personally identifiable information.",
+            detector = %self.detector,
+            count = %self.count,
+        );
+        counter!(
+            "pii_redactions_total", self.count as u64,
+            "detector" => self.detector,
+        );
+    }
+}
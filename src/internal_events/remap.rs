@@ -62,3 +62,29 @@ impl InternalEvent for RemapMappingAbort {
         }
     }
 }
+
+#[derive(Debug)]
+pub struct RemapStateSnapshotError<'a> {
+    pub error: std::io::Error,
+    pub path: &'a std::path::Path,
+}
+
+impl<'a> InternalEvent for RemapStateSnapshotError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to snapshot the state.",
+            error = %self.error,
+            path = ?self.path,
+            error_code = "state_snapshot",
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "state_snapshot",
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
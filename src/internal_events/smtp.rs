@@ -0,0 +1,57 @@
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+use crate::{
+    emit,
+    internal_events::{ComponentEventsDropped, INTENTIONAL, UNINTENTIONAL},
+};
+
+#[derive(Debug)]
+pub struct SmtpSendError<E> {
+    pub error: E,
+    pub count: usize,
+}
+
+impl<E: std::fmt::Display> InternalEvent for SmtpSendError<E> {
+    fn emit(self) {
+        let reason = "Failed to send email.";
+        error!(
+            message = reason,
+            error = %self.error,
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+            count: self.count as u64,
+            reason,
+        });
+    }
+}
+
+#[derive(Debug)]
+pub struct SmtpRateLimited<'a> {
+    pub key: &'a str,
+    pub count: usize,
+}
+
+impl<'a> InternalEvent for SmtpRateLimited<'a> {
+    fn emit(self) {
+        debug!(
+            message = "Rate limit exceeded, dropping email.",
+            key = %self.key,
+            count = self.count,
+            internal_log_rate_limit = true,
+        );
+        emit!(ComponentEventsDropped::<INTENTIONAL> {
+            count: self.count as u64,
+            reason: "Rate limit exceeded.",
+        });
+    }
+}
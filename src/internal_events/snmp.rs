@@ -0,0 +1,75 @@
+use std::net::SocketAddr;
+
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct SnmpSocketError {
+    pub error: std::io::Error,
+}
+
+impl InternalEvent for SnmpSocketError {
+    fn emit(self) {
+        error!(
+            message = "Error receiving SNMP messages.",
+            error = %self.error,
+            error_type = error_type::CONNECTION_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::CONNECTION_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct SnmpMessageError<E> {
+    pub error: E,
+    pub peer: SocketAddr,
+}
+
+impl<E: std::fmt::Display> InternalEvent for SnmpMessageError<E> {
+    fn emit(self) {
+        error!(
+            message = "Invalid SNMP message.",
+            error = %self.error,
+            peer = %self.peer,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct SnmpPollError<'a, E> {
+    pub error: E,
+    pub target: &'a str,
+}
+
+impl<'a, E: std::fmt::Display> InternalEvent for SnmpPollError<'a, E> {
+    fn emit(self) {
+        error!(
+            message = "SNMP poll failed.",
+            error = %self.error,
+            target = %self.target,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
@@ -0,0 +1,37 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use crate::{
+    emit,
+    event::metric::Metric,
+    internal_events::{ComponentEventsDropped, UNINTENTIONAL},
+};
+use vector_common::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct SignalFxInvalidMetricError<'a> {
+    pub metric: &'a Metric,
+}
+
+impl<'a> InternalEvent for SignalFxInvalidMetricError<'a> {
+    fn emit(self) {
+        let reason = "Unsupported metric type.";
+        error!(
+            message = reason,
+            error_code = "unsupported_metric_type",
+            error_type = error_type::ENCODER_FAILED,
+            stage = error_stage::PROCESSING,
+            value = ?self.metric.value(),
+            kind = ?self.metric.kind(),
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "unsupported_metric_type",
+            "error_type" => error_type::ENCODER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
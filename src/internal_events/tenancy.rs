@@ -0,0 +1,61 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use crate::{
+    emit,
+    internal_events::{ComponentEventsDropped, INTENTIONAL},
+};
+
+#[derive(Debug)]
+pub struct TenantEventsReceived<'a> {
+    pub tenant: &'a str,
+    pub count: usize,
+    pub byte_size: usize,
+}
+
+impl<'a> InternalEvent for TenantEventsReceived<'a> {
+    fn emit(self) {
+        trace!(
+            message = "Events received from tenant.",
+            tenant = %self.tenant,
+            count = %self.count,
+            byte_size = %self.byte_size,
+        );
+        counter!(
+            "tenant_received_events_total", self.count as u64,
+            "tenant" => self.tenant.to_owned(),
+        );
+        counter!(
+            "tenant_received_event_bytes_total", self.byte_size as u64,
+            "tenant" => self.tenant.to_owned(),
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct TenantEventsDiscarded<'a> {
+    pub tenant: &'a str,
+    pub count: usize,
+    pub reason: &'static str,
+}
+
+impl<'a> InternalEvent for TenantEventsDiscarded<'a> {
+    fn emit(self) {
+        debug!(
+            message = "Limit of tenant exceeded, dropping events.",
+            tenant = %self.tenant,
+            count = %self.count,
+            reason = self.reason,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "tenant_discarded_events_total", self.count as u64,
+            "tenant" => self.tenant.to_owned(),
+            "reason" => self.reason,
+        );
+        emit!(ComponentEventsDropped::<INTENTIONAL> {
+            count: self.count as u64,
+            reason: self.reason,
+        });
+    }
+}
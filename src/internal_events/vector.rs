@@ -0,0 +1,46 @@
+use std::time::Duration;
+
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct VectorEndpointEjected<'a> {
+    pub endpoint: &'a str,
+    pub consecutive_errors: usize,
+    pub duration: Duration,
+}
+
+impl InternalEvent for VectorEndpointEjected<'_> {
+    fn emit(self) {
+        warn!(
+            message = "Ejecting endpoint after consecutive failed requests.",
+            endpoint = %self.endpoint,
+            consecutive_errors = %self.consecutive_errors,
+            duration_secs = %self.duration.as_secs(),
+        );
+        counter!("endpoint_ejections_total", 1, "endpoint" => self.endpoint.to_owned());
+    }
+}
+
+#[derive(Debug)]
+pub struct VectorEndpointResolveError {
+    pub error: crate::Error,
+}
+
+impl InternalEvent for VectorEndpointResolveError {
+    fn emit(self) {
+        error!(
+            message = "Failed to resolve the addresses of the endpoints, keeping the previous ones.",
+            error = %self.error,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
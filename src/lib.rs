@@ -49,11 +49,18 @@ pub mod app;
 pub mod async_read;
 #[cfg(feature = "aws-config")]
 pub mod aws;
+pub(crate) mod benchmark;
+pub(crate) mod buffer_snapshot;
 #[allow(unreachable_pub)]
 pub mod codecs;
+pub(crate) mod clean_data;
 pub(crate) mod common;
+pub mod convert_config;
+pub mod data_dir;
 pub mod encoding_transcode;
 pub mod enrichment_tables;
+#[cfg(any(feature = "sources-fluent", feature = "sinks-fluent"))]
+pub mod fluent;
 #[cfg(feature = "gcp")]
 pub mod gcp;
 pub(crate) mod graph;
@@ -82,11 +89,13 @@ pub mod sinks;
 pub mod source_sender;
 #[allow(unreachable_pub)]
 pub mod sources;
+pub mod state_store;
 pub mod stats;
 #[cfg(feature = "api-client")]
 #[allow(unreachable_pub)]
 mod tap;
 pub mod template;
+pub(crate) mod tenancy;
 pub mod test_util;
 #[cfg(feature = "api-client")]
 #[allow(unreachable_pub)]
@@ -99,6 +108,8 @@ pub mod transforms;
 pub mod types;
 pub mod udp;
 pub mod unit_test;
+#[cfg(unix)]
+pub mod upgrade;
 pub(crate) mod utilization;
 pub mod validate;
 #[cfg(windows)]
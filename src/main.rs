@@ -9,7 +9,7 @@ fn main() {
         std::process::exit(code);
     });
 
-    app.run();
+    std::process::exit(app.run());
 }
 
 #[cfg(windows)]
@@ -23,6 +23,6 @@ pub fn main() {
             std::process::exit(code);
         });
 
-        app.run();
+        std::process::exit(app.run());
     });
 }
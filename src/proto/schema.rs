@@ -0,0 +1,237 @@
+//! Conversions between schema definitions and their representation in the Vector protocol.
+//!
+//! The `vector` sink sends the schema definition of the events of a request along with it, so
+//! that the `vector` source can attach it to the events it receives, instead of falling back to a
+//! definition of `any` type.
+
+use std::{collections::BTreeMap, str::FromStr};
+
+use lookup::LookupBuf;
+use tonic::metadata::{BinaryMetadataValue, MetadataMap};
+use value::{
+    kind::{Collection, Field, Index},
+    Kind,
+};
+use vector_core::{config::LogNamespace, schema::Definition};
+
+use super::vector::{schema_collection::Unknown, SchemaCollection, SchemaDefinition, SchemaKind};
+
+/// The metadata key the schema definition is sent under.
+pub const SCHEMA_DEFINITION_METADATA_KEY: &str = "vector-schema-definition-bin";
+
+/// Adds the schema definition to the metadata of a request.
+pub fn insert_schema_definition(metadata: &mut MetadataMap, definition: &Definition) {
+    let encoded = prost::Message::encode_to_vec(&SchemaDefinition::from(definition));
+    metadata.insert_bin(
+        SCHEMA_DEFINITION_METADATA_KEY,
+        BinaryMetadataValue::from_bytes(&encoded),
+    );
+}
+
+/// Gets the schema definition from the metadata of a request, if it has a valid one.
+pub fn get_schema_definition(metadata: &MetadataMap) -> Option<Definition> {
+    let value = metadata.get_bin(SCHEMA_DEFINITION_METADATA_KEY)?;
+    let bytes = value.to_bytes().ok()?;
+    let definition: SchemaDefinition = prost::Message::decode(bytes).ok()?;
+    Some(definition.into())
+}
+
+impl From<&Definition> for SchemaDefinition {
+    fn from(definition: &Definition) -> Self {
+        Self {
+            event_kind: Some(kind_to_proto(definition.event_kind())),
+            metadata_kind: Some(kind_to_proto(definition.metadata_kind())),
+            meanings: definition
+                .meanings()
+                .map(|(meaning, path)| (meaning.clone(), path.to_string()))
+                .collect(),
+            legacy_namespace: definition.log_namespaces().contains(&LogNamespace::Legacy),
+            vector_namespace: definition.log_namespaces().contains(&LogNamespace::Vector),
+        }
+    }
+}
+
+impl From<SchemaDefinition> for Definition {
+    fn from(definition: SchemaDefinition) -> Self {
+        let mut log_namespaces = Vec::new();
+        if definition.legacy_namespace {
+            log_namespaces.push(LogNamespace::Legacy);
+        }
+        if definition.vector_namespace {
+            log_namespaces.push(LogNamespace::Vector);
+        }
+
+        let event_kind = definition
+            .event_kind
+            .as_ref()
+            .map_or_else(Kind::any, kind_from_proto);
+        let metadata_kind = definition
+            .metadata_kind
+            .as_ref()
+            .map_or_else(Kind::any, kind_from_proto);
+        let mut output = Definition::new(event_kind, metadata_kind, log_namespaces);
+
+        for (meaning, path) in definition.meanings {
+            // Meanings pointing to paths that do not exist in the event would be rejected by
+            // `with_meaning`, so they are dropped.
+            if let Ok(path) = LookupBuf::from_str(&path) {
+                if output.event_kind().at_path(&path).contains_any_defined() {
+                    output = output.with_meaning(path, &meaning);
+                }
+            }
+        }
+
+        output
+    }
+}
+
+fn kind_to_proto(kind: &Kind) -> SchemaKind {
+    SchemaKind {
+        bytes: kind.contains_bytes(),
+        integer: kind.contains_integer(),
+        float: kind.contains_float(),
+        boolean: kind.contains_boolean(),
+        timestamp: kind.contains_timestamp(),
+        regex: kind.contains_regex(),
+        null: kind.contains_null(),
+        undefined: kind.contains_undefined(),
+        array: kind.as_array().map(|array| {
+            let mut collection = collection_to_proto(array);
+            collection.indices = array
+                .known()
+                .iter()
+                .map(|(index, kind)| (index.to_usize() as u64, kind_to_proto(kind)))
+                .collect();
+            collection.into()
+        }),
+        object: kind.as_object().map(|object| {
+            let mut collection = collection_to_proto(object);
+            collection.fields = object
+                .known()
+                .iter()
+                .map(|(field, kind)| (field.as_str().to_owned(), kind_to_proto(kind)))
+                .collect();
+            collection.into()
+        }),
+    }
+}
+
+/// Converts the unknown kind of the collection, leaving the known kinds to the caller.
+fn collection_to_proto<T: Ord + Clone>(collection: &Collection<T>) -> SchemaCollection {
+    let unknown = collection.unknown_kind();
+    let unknown = if collection.is_unknown_exact() {
+        Unknown::Exact(kind_to_proto(&unknown).into())
+    } else if unknown.is_json() {
+        // Infinite unknown kinds are recursive, so only their two common forms are sent as is,
+        // and the others are widened to `any`.
+        Unknown::Json(true)
+    } else {
+        Unknown::Any(true)
+    };
+
+    SchemaCollection {
+        fields: BTreeMap::new(),
+        indices: BTreeMap::new(),
+        unknown: Some(unknown),
+    }
+}
+
+fn kind_from_proto(kind: &SchemaKind) -> Kind {
+    let mut output = Kind::never();
+    if kind.bytes {
+        output.add_bytes();
+    }
+    if kind.integer {
+        output.add_integer();
+    }
+    if kind.float {
+        output.add_float();
+    }
+    if kind.boolean {
+        output.add_boolean();
+    }
+    if kind.timestamp {
+        output.add_timestamp();
+    }
+    if kind.regex {
+        output.add_regex();
+    }
+    if kind.null {
+        output.add_null();
+    }
+    if kind.undefined {
+        output.add_undefined();
+    }
+    if let Some(array) = &kind.array {
+        let known = array
+            .indices
+            .iter()
+            .map(|(index, kind)| (Index::from(*index as usize), kind_from_proto(kind)))
+            .collect();
+        output.add_array(Collection::from_parts(known, unknown_from_proto(array)));
+    }
+    if let Some(object) = &kind.object {
+        let known = object
+            .fields
+            .iter()
+            .map(|(field, kind)| (Field::from(field.as_str()), kind_from_proto(kind)))
+            .collect();
+        output.add_object(Collection::from_parts(known, unknown_from_proto(object)));
+    }
+    output
+}
+
+fn unknown_from_proto(collection: &SchemaCollection) -> Kind {
+    match &collection.unknown {
+        Some(Unknown::Exact(kind)) => kind_from_proto(kind),
+        Some(Unknown::Json(_)) => Kind::json(),
+        Some(Unknown::Any(_)) | None => Kind::any(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use value::kind::Collection;
+
+    use super::*;
+
+    #[test]
+    fn schema_definition_roundtrip() {
+        let definition = Definition::new(
+            Kind::object(Collection::json()),
+            Kind::object(Collection::empty()),
+            [LogNamespace::Vector],
+        )
+        .with_field("message", Kind::bytes(), Some("message"))
+        .with_field("timestamp", Kind::timestamp().or_undefined(), None)
+        .with_field(
+            "tags",
+            Kind::array(Collection::from_unknown(Kind::bytes())),
+            None,
+        );
+
+        let mut metadata = MetadataMap::new();
+        insert_schema_definition(&mut metadata, &definition);
+        let decoded = get_schema_definition(&metadata).unwrap();
+
+        assert_eq!(decoded.event_kind(), definition.event_kind());
+        assert_eq!(decoded.metadata_kind(), definition.metadata_kind());
+        assert_eq!(decoded.log_namespaces(), definition.log_namespaces());
+        assert_eq!(
+            decoded.meaning_path("message").map(ToString::to_string),
+            Some("message".to_owned())
+        );
+    }
+
+    #[test]
+    fn any_definition_roundtrip() {
+        let definition = Definition::any();
+        let decoded = Definition::from(SchemaDefinition::from(&definition));
+        assert_eq!(decoded, definition);
+    }
+
+    #[test]
+    fn missing_schema_definition() {
+        assert!(get_schema_definition(&MetadataMap::new()).is_none());
+    }
+}
@@ -1,14 +1,23 @@
+use std::path::PathBuf;
+
 use async_stream::stream;
-use bytes::Buf;
+use bytes::{Buf, Bytes};
 use futures::Stream;
 use hyper::Body;
 use indexmap::IndexMap;
+use openssl::{
+    hash::MessageDigest,
+    memcmp,
+    pkey::PKey,
+    sign::{Signer, Verifier},
+};
 use tokio::time;
 use url::Url;
+use vector_common::sensitive_string::SensitiveString;
 use vector_config::configurable_component;
 
 use crate::{
-    config::{self, provider::ProviderConfig, ProxyConfig},
+    config::{self, provider::ProviderConfig, ConfigBuilder, ProxyConfig},
     http::HttpClient,
     signal,
     tls::{TlsConfig, TlsSettings},
@@ -33,6 +42,45 @@ impl Default for RequestConfig {
     }
 }
 
+/// Signature verification settings.
+///
+/// When configured, the provider expects every configuration it retrieves to be signed, and
+/// rejects any configuration whose signature is missing or invalid.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SignatureConfig {
+    /// The response header holding the hex-encoded signature of the response body.
+    #[serde(default = "default_signature_header")]
+    pub header: String,
+
+    #[configurable(derived)]
+    #[serde(flatten)]
+    pub key: SignatureKey,
+}
+
+fn default_signature_header() -> String {
+    "X-Vector-Signature".to_owned()
+}
+
+/// The key used to verify the signature of the configuration.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields, rename_all = "snake_case", tag = "algorithm")]
+pub enum SignatureKey {
+    /// HMAC-SHA256, keyed with a shared secret.
+    HmacSha256 {
+        /// The shared secret.
+        secret: SensitiveString,
+    },
+
+    /// RSA (PKCS #1 v1.5) or ECDSA signature of the SHA-256 digest of the response body.
+    Sha256 {
+        /// The path to the PEM-encoded public key.
+        public_key_file: PathBuf,
+    },
+}
+
 /// Configuration for the `http` provider.
 #[configurable_component(provider("http"))]
 #[derive(Clone, Debug)]
@@ -47,6 +95,9 @@ pub struct HttpConfig {
     /// How often to poll the provider, in seconds.
     poll_interval_secs: u64,
 
+    #[configurable(derived)]
+    signature: Option<SignatureConfig>,
+
     #[serde(flatten)]
     tls_options: Option<TlsConfig>,
 
@@ -64,32 +115,86 @@ impl Default for HttpConfig {
             url: None,
             request: RequestConfig::default(),
             poll_interval_secs: 30,
+            signature: None,
             tls_options: None,
             proxy: Default::default(),
         }
     }
 }
 
-/// Makes an HTTP request to the provided endpoint, returning the String body.
-async fn http_request(
-    url: &Url,
-    tls_options: &Option<TlsConfig>,
-    headers: &IndexMap<String, String>,
-    proxy: &ProxyConfig,
-) -> Result<bytes::Bytes, &'static str> {
-    let tls_settings = TlsSettings::from_options(tls_options).map_err(|_| "Invalid TLS options")?;
-    let http_client =
-        HttpClient::<Body>::new(tls_settings, proxy).map_err(|_| "Invalid TLS settings")?;
+/// The outcome of a request to the provider endpoint.
+#[derive(Debug)]
+enum Fetched {
+    /// The endpoint returned a configuration.
+    Config {
+        body: Bytes,
+        etag: Option<String>,
+        signature: Option<String>,
+    },
+
+    /// The configuration hasn't changed since the last request.
+    NotModified,
+}
+
+/// Everything needed to retrieve the configuration from the endpoint.
+#[derive(Clone, Debug)]
+struct Endpoint {
+    url: Url,
+    tls_options: Option<TlsConfig>,
+    headers: IndexMap<String, String>,
+    proxy: ProxyConfig,
+    signature: Option<SignatureConfig>,
+}
+
+/// Tracks the last configuration retrieved from the endpoint, so that a reload is only
+/// triggered when the configuration actually changes.
+#[derive(Debug, Default)]
+struct PollState {
+    etag: Option<String>,
+    digest: Option<[u8; 32]>,
+}
+
+impl PollState {
+    /// Gets the digest of the retrieved configuration if it differs from the last valid one.
+    ///
+    /// Not every server supports conditional requests, so the digest of the body is compared as
+    /// well.
+    fn changed_digest(&self, body: &[u8]) -> Option<[u8; 32]> {
+        let digest = openssl::sha::sha256(body);
+        (self.digest != Some(digest)).then(|| digest)
+    }
+
+    /// Records the last valid configuration, which is only reloaded again once it changes.
+    fn record(&mut self, digest: [u8; 32], etag: Option<String>) {
+        self.digest = Some(digest);
+        self.etag = etag;
+    }
+}
+
+/// Makes an HTTP request to the provided endpoint, returning the body of the response.
+///
+/// If an entity tag is provided, the request is made conditional on the configuration having
+/// changed since it was retrieved.
+async fn http_request(endpoint: &Endpoint, etag: Option<&str>) -> Result<Fetched, &'static str> {
+    let url = &endpoint.url;
+    let tls_settings =
+        TlsSettings::from_options(&endpoint.tls_options).map_err(|_| "Invalid TLS options")?;
+    let http_client = HttpClient::<Body>::new(tls_settings, &endpoint.proxy)
+        .map_err(|_| "Invalid TLS settings")?;
 
     // Build HTTP request.
     let mut builder = http::request::Builder::new().uri(url.to_string());
 
     // Augment with headers. These may be required e.g. for authentication to
     // private endpoints.
-    for (header, value) in headers.iter() {
+    for (header, value) in endpoint.headers.iter() {
         builder = builder.header(header.as_str(), value.as_str());
     }
 
+    if let Some(etag) = etag {
+        builder = builder.header(http::header::IF_NONE_MATCH, etag);
+    }
+
     let request = builder
         .body(Body::empty())
         .map_err(|_| "Couldn't create HTTP request")?;
@@ -108,9 +213,33 @@ async fn http_request(
         message
     })?;
 
-    info!(message = "Response received.", url = ?url.as_str());
+    let status = response.status();
+    info!(message = "Response received.", url = ?url.as_str(), %status);
+
+    if status == http::StatusCode::NOT_MODIFIED {
+        return Ok(Fetched::NotModified);
+    }
+
+    if !status.is_success() {
+        let message = "Unexpected HTTP status.";
+        error!(message = ?message, %status, url = ?url.as_str());
+        return Err(message);
+    }
 
-    hyper::body::to_bytes(response.into_body())
+    let header_value = |name: &str| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_owned)
+    };
+    let etag = header_value(http::header::ETAG.as_str());
+    let signature = endpoint
+        .signature
+        .as_ref()
+        .and_then(|signature| header_value(&signature.header));
+
+    let body = hyper::body::to_bytes(response.into_body())
         .await
         .map_err(|err| {
             let message = "Error interpreting response.";
@@ -120,37 +249,110 @@ async fn http_request(
                     error = ?cause);
 
             message
-        })
+        })?;
+
+    Ok(Fetched::Config {
+        body,
+        etag,
+        signature,
+    })
+}
+
+/// Verifies the signature of the configuration retrieved from the endpoint.
+fn verify_signature(
+    config: &SignatureConfig,
+    body: &[u8],
+    signature: Option<&str>,
+) -> Result<(), String> {
+    let signature = signature
+        .ok_or_else(|| format!("Missing signature header `{}`.", config.header))
+        .and_then(|signature| {
+            hex::decode(signature.trim())
+                .map_err(|_| "Signature is not a valid hex string.".to_owned())
+        })?;
+
+    let valid = match &config.key {
+        SignatureKey::HmacSha256 { secret } => {
+            let key = PKey::hmac(secret.inner().as_bytes()).map_err(|e| e.to_string())?;
+            let mut signer =
+                Signer::new(MessageDigest::sha256(), &key).map_err(|e| e.to_string())?;
+            let expected = signer
+                .sign_oneshot_to_vec(body)
+                .map_err(|e| e.to_string())?;
+            expected.len() == signature.len() && memcmp::eq(&expected, &signature)
+        }
+        SignatureKey::Sha256 { public_key_file } => {
+            let pem = std::fs::read(public_key_file)
+                .map_err(|e| format!("Could not read public key {:?}: {}.", public_key_file, e))?;
+            let key = PKey::public_key_from_pem(&pem).map_err(|e| e.to_string())?;
+            let mut verifier =
+                Verifier::new(MessageDigest::sha256(), &key).map_err(|e| e.to_string())?;
+            verifier
+                .verify_oneshot(&signature, body)
+                .map_err(|e| e.to_string())?
+        }
+    };
+
+    if valid {
+        Ok(())
+    } else {
+        Err("Invalid configuration signature.".to_owned())
+    }
 }
 
 /// Calls `http_request`, serializing the result to a `ConfigBuilder`.
+///
+/// Returns `None` if the configuration hasn't changed since the last valid one was retrieved.
 async fn http_request_to_config_builder(
-    url: &Url,
-    tls_options: &Option<TlsConfig>,
-    headers: &IndexMap<String, String>,
-    proxy: &ProxyConfig,
-) -> BuildResult {
-    let config_str = http_request(url, tls_options, headers, proxy)
+    endpoint: &Endpoint,
+    state: &mut PollState,
+) -> Result<Option<ConfigBuilder>, Vec<String>> {
+    let (body, etag, signature) = match http_request(endpoint, state.etag.as_deref())
         .await
-        .map_err(|e| vec![e.to_owned()])?;
+        .map_err(|e| vec![e.to_owned()])?
+    {
+        Fetched::NotModified => return Ok(None),
+        Fetched::Config {
+            body,
+            etag,
+            signature,
+        } => (body, etag, signature),
+    };
+
+    if let Some(config) = &endpoint.signature {
+        verify_signature(config, &body, signature.as_deref()).map_err(|e| vec![e])?;
+    }
+
+    let digest = match state.changed_digest(&body) {
+        Some(digest) => digest,
+        None => {
+            state.etag = etag;
+            return Ok(None);
+        }
+    };
 
     let (config_builder, warnings) =
-        config::load(config_str.chunk(), crate::config::format::Format::Toml)?;
+        config::load(body.chunk(), crate::config::format::Format::Toml)?;
 
     for warning in warnings.into_iter() {
         warn!("{}", warning);
     }
 
-    Ok(config_builder)
+    // Invalid configurations aren't recorded, so that they keep being retrieved and reported until
+    // they're fixed, and so that going back to the last valid one doesn't trigger a reload.
+    config_builder.clone().build()?;
+    state.record(digest, etag);
+
+    Ok(Some(config_builder))
 }
 
 /// Polls the HTTP endpoint after/every `poll_interval_secs`, returning a stream of `ConfigBuilder`.
+///
+/// Only configurations that changed since the last request and that are valid are emitted.
 fn poll_http(
     poll_interval_secs: u64,
-    url: Url,
-    tls_options: Option<TlsConfig>,
-    headers: IndexMap<String, String>,
-    proxy: ProxyConfig,
+    endpoint: Endpoint,
+    mut state: PollState,
 ) -> impl Stream<Item = signal::SignalTo> {
     let duration = time::Duration::from_secs(poll_interval_secs);
     let mut interval = time::interval_at(time::Instant::now() + duration, duration);
@@ -159,15 +361,27 @@ fn poll_http(
         loop {
             interval.tick().await;
 
-            match http_request_to_config_builder(&url, &tls_options, &headers, &proxy).await {
-                Ok(config_builder) => yield signal::SignalTo::ReloadFromConfigBuilder(config_builder),
-                Err(_) => {},
+            match http_request_to_config_builder(&endpoint, &mut state).await {
+                Ok(Some(config_builder)) => {
+                    yield signal::SignalTo::ReloadFromConfigBuilder(config_builder)
+                }
+                Ok(None) => debug!(
+                    message = "Configuration is unchanged.",
+                    url = ?endpoint.url.as_str()),
+                Err(errors) => {
+                    for error in errors {
+                        error!(
+                            message = "Failed to retrieve configuration.",
+                            %error,
+                            url = ?endpoint.url.as_str());
+                    }
+                }
             };
 
             info!(
                 message = "HTTP provider is waiting.",
                 poll_interval_secs = ?poll_interval_secs,
-                url = ?url.as_str());
+                url = ?endpoint.url.as_str());
         }
     }
 }
@@ -180,25 +394,197 @@ impl ProviderConfig for HttpConfig {
             .take()
             .ok_or_else(|| vec!["URL is required for the `http` provider.".to_owned()])?;
 
-        let tls_options = self.tls_options.take();
-        let poll_interval_secs = self.poll_interval_secs;
-        let request = self.request.clone();
-
-        let proxy = ProxyConfig::from_env().merge(&self.proxy);
-        let config_builder =
-            http_request_to_config_builder(&url, &tls_options, &request.headers, &proxy).await?;
+        let endpoint = Endpoint {
+            url,
+            tls_options: self.tls_options.take(),
+            headers: self.request.headers.clone(),
+            proxy: ProxyConfig::from_env().merge(&self.proxy),
+            signature: self.signature.clone(),
+        };
+
+        let mut state = PollState::default();
+        let config_builder = http_request_to_config_builder(&endpoint, &mut state)
+            .await?
+            .ok_or_else(
+                || vec!["No configuration received from the `http` provider.".to_owned()],
+            )?;
 
         // Poll for changes to remote configuration.
-        signal_handler.add(poll_http(
-            poll_interval_secs,
-            url,
-            tls_options,
-            request.headers.clone(),
-            proxy.clone(),
-        ));
+        signal_handler.add(poll_http(self.poll_interval_secs, endpoint, state));
 
         Ok(config_builder)
     }
 }
 
 impl_generate_config_from_default!(HttpConfig);
+
+#[cfg(test)]
+mod tests {
+    use openssl::{ec, nid::Nid};
+    use wiremock::{
+        matchers::{header, method},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use super::*;
+
+    const CONFIG: &str = r#"
+        [sources.in]
+        type = "test_basic"
+
+        [sinks.out]
+        type = "test_basic"
+        inputs = ["in"]
+    "#;
+
+    fn hmac_signature(secret: &str, body: &[u8]) -> String {
+        let key = PKey::hmac(secret.as_bytes()).unwrap();
+        let mut signer = Signer::new(MessageDigest::sha256(), &key).unwrap();
+        hex::encode(signer.sign_oneshot_to_vec(body).unwrap())
+    }
+
+    fn hmac_config(secret: &str) -> SignatureConfig {
+        SignatureConfig {
+            header: default_signature_header(),
+            key: SignatureKey::HmacSha256 {
+                secret: secret.to_owned().into(),
+            },
+        }
+    }
+
+    fn endpoint(url: &str) -> Endpoint {
+        Endpoint {
+            url: url.parse().unwrap(),
+            tls_options: None,
+            headers: IndexMap::new(),
+            proxy: ProxyConfig::default(),
+            signature: None,
+        }
+    }
+
+    #[test]
+    fn poll_state_detects_changes() {
+        let mut state = PollState::default();
+        let digest = state.changed_digest(b"a").unwrap();
+        state.record(digest, Some("\"1\"".into()));
+        assert!(state.changed_digest(b"a").is_none());
+        assert_eq!(state.etag.as_deref(), Some("\"1\""));
+        assert!(state.changed_digest(b"b").is_some());
+    }
+
+    #[test]
+    fn verifies_hmac_signature() {
+        let config = hmac_config("secret");
+        let signature = hmac_signature("secret", CONFIG.as_bytes());
+
+        assert!(verify_signature(&config, CONFIG.as_bytes(), Some(&signature)).is_ok());
+        assert!(verify_signature(&config, b"tampered", Some(&signature)).is_err());
+        assert!(verify_signature(&config, CONFIG.as_bytes(), Some("zz")).is_err());
+        assert!(verify_signature(&config, CONFIG.as_bytes(), None).is_err());
+    }
+
+    #[test]
+    fn verifies_public_key_signature() {
+        let group = ec::EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+        let private_key = PKey::from_ec_key(ec::EcKey::generate(&group).unwrap()).unwrap();
+        let mut signer = Signer::new(MessageDigest::sha256(), &private_key).unwrap();
+        let signature = hex::encode(signer.sign_oneshot_to_vec(CONFIG.as_bytes()).unwrap());
+
+        let dir = tempfile::tempdir().unwrap();
+        let public_key_file = dir.path().join("key.pem");
+        std::fs::write(&public_key_file, private_key.public_key_to_pem().unwrap()).unwrap();
+
+        let config = SignatureConfig {
+            header: default_signature_header(),
+            key: SignatureKey::Sha256 { public_key_file },
+        };
+
+        assert!(verify_signature(&config, CONFIG.as_bytes(), Some(&signature)).is_ok());
+        assert!(verify_signature(&config, b"tampered", Some(&signature)).is_err());
+    }
+
+    #[tokio::test]
+    async fn reloads_only_on_change() {
+        let server = MockServer::start().await;
+
+        // Mocks are matched in the order they're mounted, so the conditional request has to
+        // come first.
+        Mock::given(method("GET"))
+            .and(header("If-None-Match", "\"v1\""))
+            .respond_with(ResponseTemplate::new(304))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("ETag", "\"v1\"")
+                    .set_body_string(CONFIG),
+            )
+            .mount(&server)
+            .await;
+
+        let endpoint = endpoint(&server.uri());
+        let mut state = PollState::default();
+
+        let first = http_request_to_config_builder(&endpoint, &mut state)
+            .await
+            .unwrap();
+        assert!(first.is_some());
+        assert_eq!(state.etag.as_deref(), Some("\"v1\""));
+
+        let second = http_request_to_config_builder(&endpoint, &mut state)
+            .await
+            .unwrap();
+        assert!(second.is_none());
+    }
+
+    #[tokio::test]
+    async fn rejects_unsigned_config() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(CONFIG))
+            .mount(&server)
+            .await;
+
+        let mut endpoint = endpoint(&server.uri());
+        endpoint.signature = Some(hmac_config("secret"));
+
+        let errors = http_request_to_config_builder(&endpoint, &mut PollState::default())
+            .await
+            .unwrap_err();
+        assert_eq!(
+            errors,
+            vec!["Missing signature header `X-Vector-Signature`.".to_owned()]
+        );
+    }
+
+    #[tokio::test]
+    async fn does_not_record_invalid_config() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("ETag", "\"v1\"")
+                    .set_body_string(
+                        r#"
+                            [sinks.out]
+                            type = "test_basic"
+                            inputs = ["missing"]
+                        "#,
+                    ),
+            )
+            .mount(&server)
+            .await;
+
+        let endpoint = endpoint(&server.uri());
+        let mut state = PollState::default();
+
+        assert!(http_request_to_config_builder(&endpoint, &mut state)
+            .await
+            .is_err());
+        assert!(state.digest.is_none());
+        assert!(state.etag.is_none());
+    }
+}
@@ -0,0 +1,565 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use async_stream::stream;
+use futures::{Stream, StreamExt};
+use k8s_openapi::api::core::v1::ConfigMap;
+use kube::{
+    api::{Api, ApiResource, DynamicObject, GroupVersionKind, ListParams},
+    config::{self as kube_config, KubeConfigOptions},
+    runtime::watcher,
+    Client, Config as ClientConfig,
+};
+use vector_config::configurable_component;
+
+use crate::{
+    config::{self, format::Format, provider::ProviderConfig, ConfigBuilder},
+    signal,
+};
+
+use super::BuildResult;
+
+const PIPELINE_GROUP: &str = "vector.dev";
+const PIPELINE_VERSION: &str = "v1alpha1";
+const PIPELINE_KIND: &str = "VectorPipeline";
+
+/// Configuration for the `kubernetes` provider.
+///
+/// Every matching ConfigMap (and, optionally, every `VectorPipeline` custom resource) holds one or
+/// more configuration fragments. The fragments are merged into a single configuration, which is
+/// reloaded whenever one of them changes.
+#[configurable_component(provider("kubernetes"))]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields, default)]
+pub struct KubernetesConfig {
+    /// The namespace to watch.
+    ///
+    /// Defaults to the namespace of the service account Vector runs as, or `default`.
+    namespace: Option<String>,
+
+    /// The name of a single ConfigMap to load.
+    ///
+    /// Mutually exclusive with `label_selector`.
+    config_map: Option<String>,
+
+    /// A label selector for the ConfigMaps to load.
+    ///
+    /// All matching ConfigMaps are merged, in name order.
+    label_selector: Option<String>,
+
+    /// Whether to also load `VectorPipeline` (`vector.dev/v1alpha1`) custom resources.
+    ///
+    /// Each resource holds a configuration fragment in `spec.config`, in the format given by
+    /// `spec.format` (`toml` by default). Resources are filtered by `label_selector` as well.
+    pipelines: bool,
+
+    /// Optional path to a readable kubeconfig file.
+    ///
+    /// If not set, a connection to Kubernetes is made using the in-cluster configuration.
+    kube_config_file: Option<PathBuf>,
+}
+
+/// A single configuration fragment.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Fragment {
+    format: Format,
+    contents: String,
+}
+
+/// The key fragments are stored under: the kind and name of the object holding them.
+type ObjectKey = (&'static str, String);
+
+/// The set of fragments currently known to the provider.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct Fragments(BTreeMap<ObjectKey, Vec<Fragment>>);
+
+impl Fragments {
+    fn apply(&mut self, key: ObjectKey, fragments: Vec<Fragment>) {
+        if fragments.is_empty() {
+            self.0.remove(&key);
+        } else {
+            self.0.insert(key, fragments);
+        }
+    }
+
+    fn remove(&mut self, key: &ObjectKey) {
+        self.0.remove(key);
+    }
+
+    /// Replaces all the fragments of the given kind, as happens when a watch is restarted.
+    fn restart(&mut self, kind: &'static str, objects: Vec<(ObjectKey, Vec<Fragment>)>) {
+        self.0.retain(|(object_kind, _), _| *object_kind != kind);
+        for (key, fragments) in objects {
+            self.apply(key, fragments);
+        }
+    }
+
+    /// Merges every fragment into a single `ConfigBuilder`.
+    fn merge(&self) -> BuildResult {
+        let mut builder = ConfigBuilder::default();
+        let mut errors = Vec::new();
+
+        for ((kind, name), fragments) in &self.0 {
+            for fragment in fragments {
+                match config::load::<_, ConfigBuilder>(
+                    fragment.contents.as_bytes(),
+                    fragment.format,
+                ) {
+                    Ok((loaded, warnings)) => {
+                        for warning in warnings {
+                            warn!(message = "Configuration fragment warning.", %kind, %name, %warning);
+                        }
+                        if let Err(errs) = builder.append(loaded) {
+                            errors.extend(
+                                errs.into_iter()
+                                    .map(|e| format!("{} {}: {}", kind, name, e)),
+                            );
+                        }
+                    }
+                    Err(errs) => errors.extend(
+                        errs.into_iter()
+                            .map(|e| format!("{} {}: {}", kind, name, e)),
+                    ),
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(builder)
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Extracts the configuration fragments of a ConfigMap.
+///
+/// Only keys with a `toml`, `yaml` or `json` extension are considered fragments.
+fn config_map_fragments(config_map: &ConfigMap) -> (ObjectKey, Vec<Fragment>) {
+    let name = config_map.metadata.name.clone().unwrap_or_default();
+    let fragments = config_map
+        .data
+        .iter()
+        .flatten()
+        .filter_map(|(key, contents)| {
+            Format::from_path(key).ok().map(|format| Fragment {
+                format,
+                contents: contents.clone(),
+            })
+        })
+        .collect();
+
+    (("ConfigMap", name), fragments)
+}
+
+/// Extracts the configuration fragment of a `VectorPipeline` custom resource.
+fn pipeline_fragments(pipeline: &DynamicObject) -> (ObjectKey, Vec<Fragment>) {
+    let name = pipeline.metadata.name.clone().unwrap_or_default();
+    let spec = &pipeline.data["spec"];
+
+    let fragment = spec["config"].as_str().and_then(|contents| {
+        let format = match spec["format"].as_str() {
+            Some(format) => match format.parse::<Format>() {
+                Ok(format) => format,
+                Err(error) => {
+                    warn!(message = "Ignoring pipeline with an invalid format.", %name, %error);
+                    return None;
+                }
+            },
+            None => Format::Toml,
+        };
+        Some(Fragment {
+            format,
+            contents: contents.to_owned(),
+        })
+    });
+
+    ((PIPELINE_KIND, name), fragment.into_iter().collect())
+}
+
+/// A change observed by one of the watchers.
+enum Change {
+    Applied(ObjectKey, Vec<Fragment>),
+    Deleted(ObjectKey),
+    Restarted(&'static str, Vec<(ObjectKey, Vec<Fragment>)>),
+}
+
+impl Change {
+    fn from_event<K>(
+        kind: &'static str,
+        event: watcher::Event<K>,
+        extract: fn(&K) -> (ObjectKey, Vec<Fragment>),
+    ) -> Self {
+        match event {
+            watcher::Event::Applied(object) => {
+                let (key, fragments) = extract(&object);
+                Self::Applied(key, fragments)
+            }
+            watcher::Event::Deleted(object) => Self::Deleted(extract(&object).0),
+            watcher::Event::Restarted(objects) => {
+                Self::Restarted(kind, objects.iter().map(extract).collect())
+            }
+        }
+    }
+
+    fn apply_to(self, fragments: &mut Fragments) {
+        match self {
+            Self::Applied(key, object_fragments) => fragments.apply(key, object_fragments),
+            Self::Deleted(key) => fragments.remove(&key),
+            Self::Restarted(kind, objects) => fragments.restart(kind, objects),
+        }
+    }
+}
+
+/// The Kubernetes APIs watched by the provider.
+struct Apis {
+    config_maps: Option<(Api<ConfigMap>, ListParams)>,
+    pipelines: Option<(Api<DynamicObject>, ListParams)>,
+}
+
+impl Apis {
+    /// Lists the current fragments.
+    async fn list(&self) -> Result<Fragments, Vec<String>> {
+        let mut fragments = Fragments::default();
+
+        if let Some((api, params)) = &self.config_maps {
+            let config_maps = api
+                .list(params)
+                .await
+                .map_err(|error| vec![format!("Failed to list ConfigMaps: {}.", error)])?;
+            for config_map in &config_maps {
+                let (key, object_fragments) = config_map_fragments(config_map);
+                fragments.apply(key, object_fragments);
+            }
+        }
+
+        if let Some((api, params)) = &self.pipelines {
+            let pipelines = api.list(params).await.map_err(|error| {
+                vec![format!(
+                    "Failed to list {} resources: {}.",
+                    PIPELINE_KIND, error
+                )]
+            })?;
+            for pipeline in &pipelines {
+                let (key, object_fragments) = pipeline_fragments(pipeline);
+                fragments.apply(key, object_fragments);
+            }
+        }
+
+        Ok(fragments)
+    }
+
+    /// Watches every API, merging the changes into a single stream.
+    fn watch(self) -> impl Stream<Item = Result<Change, watcher::Error>> + Send {
+        let mut streams = Vec::new();
+
+        if let Some((api, params)) = self.config_maps {
+            streams.push(
+                watcher(api, params)
+                    .map(|event| {
+                        event.map(|e| Change::from_event("ConfigMap", e, config_map_fragments))
+                    })
+                    .boxed(),
+            );
+        }
+
+        if let Some((api, params)) = self.pipelines {
+            streams.push(
+                watcher(api, params)
+                    .map(|event| {
+                        event.map(|e| Change::from_event(PIPELINE_KIND, e, pipeline_fragments))
+                    })
+                    .boxed(),
+            );
+        }
+
+        futures::stream::select_all(streams)
+    }
+}
+
+/// Watches the Kubernetes APIs, returning a stream of `ConfigBuilder`.
+///
+/// Only merged configurations that changed and that are valid are emitted.
+fn watch_fragments(apis: Apis, mut fragments: Fragments) -> impl Stream<Item = signal::SignalTo> {
+    stream! {
+        let changes = apis.watch();
+        futures::pin_mut!(changes);
+
+        while let Some(change) = changes.next().await {
+            let change = match change {
+                Ok(change) => change,
+                Err(error) => {
+                    error!(message = "Kubernetes watcher error.", %error);
+                    continue;
+                }
+            };
+
+            let previous = fragments.clone();
+            change.apply_to(&mut fragments);
+            if fragments == previous {
+                debug!(message = "Configuration is unchanged.");
+                continue;
+            }
+
+            match fragments.merge() {
+                Ok(config_builder) => match config_builder.clone().build() {
+                    Ok(_) => {
+                        info!(message = "Configuration fragments changed, reloading.");
+                        yield signal::SignalTo::ReloadFromConfigBuilder(config_builder);
+                    }
+                    Err(errors) => {
+                        for error in errors {
+                            error!(
+                                message = "Configuration retrieved from the Kubernetes provider is invalid.",
+                                %error);
+                        }
+                    }
+                },
+                Err(errors) => {
+                    for error in errors {
+                        error!(message = "Failed to load configuration fragment.", %error);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl KubernetesConfig {
+    async fn client(&self) -> Result<Client, String> {
+        let client_config = match &self.kube_config_file {
+            Some(kc) => ClientConfig::from_custom_kubeconfig(
+                kube_config::Kubeconfig::read_from(kc).map_err(|e| e.to_string())?,
+                &KubeConfigOptions::default(),
+            )
+            .await
+            .map_err(|e| e.to_string())?,
+            None => ClientConfig::infer().await.map_err(|e| e.to_string())?,
+        };
+
+        Client::try_from(client_config).map_err(|e| e.to_string())
+    }
+
+    /// Returns the list parameters for the ConfigMaps and the custom resources, if they are to
+    /// be loaded.
+    fn list_params(&self) -> Result<(Option<ListParams>, Option<ListParams>), String> {
+        let config_map_params = match (&self.config_map, &self.label_selector) {
+            (Some(_), Some(_)) => {
+                return Err("`config_map` and `label_selector` are mutually exclusive.".to_owned())
+            }
+            (Some(name), None) => {
+                Some(ListParams::default().fields(&format!("metadata.name={}", name)))
+            }
+            (None, Some(selector)) => Some(ListParams::default().labels(selector)),
+            (None, None) => None,
+        };
+
+        let pipeline_params = self.pipelines.then(|| match &self.label_selector {
+            Some(selector) => ListParams::default().labels(selector),
+            None => ListParams::default(),
+        });
+
+        if config_map_params.is_none() && pipeline_params.is_none() {
+            return Err(
+                "One of `config_map`, `label_selector` or `pipelines` is required for the `kubernetes` provider."
+                    .to_owned(),
+            );
+        }
+
+        Ok((config_map_params, pipeline_params))
+    }
+
+    async fn apis(&self) -> Result<Apis, Vec<String>> {
+        let (config_map_params, pipeline_params) = self.list_params().map_err(|e| vec![e])?;
+        let client = self
+            .client()
+            .await
+            .map_err(|error| vec![format!("Failed to create a Kubernetes client: {}.", error)])?;
+
+        let resource = ApiResource::from_gvk(&GroupVersionKind::gvk(
+            PIPELINE_GROUP,
+            PIPELINE_VERSION,
+            PIPELINE_KIND,
+        ));
+        let (config_maps, pipelines) = match &self.namespace {
+            Some(namespace) => (
+                Api::namespaced(client.clone(), namespace),
+                Api::namespaced_with(client, namespace, &resource),
+            ),
+            None => (
+                Api::default_namespaced(client.clone()),
+                Api::default_namespaced_with(client, &resource),
+            ),
+        };
+
+        Ok(Apis {
+            config_maps: config_map_params.map(|params| (config_maps, params)),
+            pipelines: pipeline_params.map(|params| (pipelines, params)),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl ProviderConfig for KubernetesConfig {
+    async fn build(&mut self, signal_handler: &mut signal::SignalHandler) -> BuildResult {
+        let apis = self.apis().await?;
+
+        let fragments = apis.list().await?;
+        let config_builder = fragments.merge()?;
+
+        // Watch for changes, emitting a reload whenever the merged configuration changes.
+        signal_handler.add(watch_fragments(apis, fragments));
+
+        Ok(config_builder)
+    }
+}
+
+impl_generate_config_from_default!(KubernetesConfig);
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+    use serde_json::json;
+
+    use super::*;
+
+    fn config_map(name: &str, data: &[(&str, &str)]) -> ConfigMap {
+        ConfigMap {
+            metadata: ObjectMeta {
+                name: Some(name.to_owned()),
+                ..ObjectMeta::default()
+            },
+            data: Some(
+                data.iter()
+                    .map(|(key, value)| (key.to_string(), value.to_string()))
+                    .collect::<BTreeMap<_, _>>(),
+            ),
+            ..ConfigMap::default()
+        }
+    }
+
+    fn pipeline(name: &str, spec: serde_json::Value) -> DynamicObject {
+        let resource = ApiResource::from_gvk(&GroupVersionKind::gvk(
+            PIPELINE_GROUP,
+            PIPELINE_VERSION,
+            PIPELINE_KIND,
+        ));
+        DynamicObject::new(name, &resource).data(json!({ "spec": spec }))
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<KubernetesConfig>();
+    }
+
+    #[test]
+    fn config_map_fragments_by_extension() {
+        let (key, fragments) = config_map_fragments(&config_map(
+            "team-a",
+            &[
+                ("sources.toml", "[sources.in]\ntype = \"test_basic\""),
+                ("sinks.yaml", "sinks: {}"),
+                ("README", "not a config"),
+            ],
+        ));
+
+        assert_eq!(key, ("ConfigMap", "team-a".to_owned()));
+        assert_eq!(
+            fragments.iter().map(|f| f.format).collect::<Vec<_>>(),
+            vec![Format::Yaml, Format::Toml]
+        );
+    }
+
+    #[test]
+    fn pipeline_fragment_format() {
+        let (_, fragments) = pipeline_fragments(&pipeline(
+            "team-b",
+            json!({ "config": "{}", "format": "json" }),
+        ));
+        assert_eq!(fragments[0].format, Format::Json);
+
+        let (_, fragments) = pipeline_fragments(&pipeline("team-b", json!({ "config": "" })));
+        assert_eq!(fragments[0].format, Format::Toml);
+
+        let (_, fragments) = pipeline_fragments(&pipeline("team-b", json!({})));
+        assert!(fragments.is_empty());
+    }
+
+    #[test]
+    fn merges_fragments() {
+        let mut fragments = Fragments::default();
+        let (key, object_fragments) = config_map_fragments(&config_map(
+            "team-a",
+            &[(
+                "pipeline.toml",
+                indoc::indoc! {r#"
+                    [sources.in]
+                    type = "test_basic"
+
+                    [sinks.out]
+                    type = "test_basic"
+                    inputs = ["in"]
+                "#},
+            )],
+        ));
+        fragments.apply(key, object_fragments);
+        let (key, object_fragments) = pipeline_fragments(&pipeline(
+            "team-b",
+            json!({ "config": "[transforms.t]\ntype = \"test_basic\"\ninputs = [\"in\"]" }),
+        ));
+        fragments.apply(key, object_fragments);
+
+        let builder = fragments.merge().unwrap();
+        assert_eq!(builder.sources.len(), 1);
+        assert_eq!(builder.transforms.len(), 1);
+        assert_eq!(builder.sinks.len(), 1);
+    }
+
+    #[test]
+    fn merge_reports_conflicts() {
+        let mut fragments = Fragments::default();
+        for name in ["team-a", "team-b"] {
+            let (key, object_fragments) = config_map_fragments(&config_map(
+                name,
+                &[("in.toml", "[sources.in]\ntype = \"test_basic\"")],
+            ));
+            fragments.apply(key, object_fragments);
+        }
+
+        let errors = fragments.merge().unwrap_err();
+        assert!(errors[0].starts_with("ConfigMap team-b"), "{:?}", errors);
+    }
+
+    #[test]
+    fn restart_replaces_kind() {
+        let mut fragments = Fragments::default();
+        let a = config_map_fragments(&config_map("a", &[("a.toml", "")]));
+        let b = config_map_fragments(&config_map("b", &[("b.toml", "")]));
+        let p = pipeline_fragments(&pipeline("p", json!({ "config": "" })));
+        fragments.apply(a.0, a.1);
+        fragments.apply(p.0.clone(), p.1);
+
+        fragments.restart("ConfigMap", vec![b.clone()]);
+
+        let keys = fragments.0.keys().cloned().collect::<Vec<_>>();
+        assert_eq!(keys, vec![b.0, p.0]);
+    }
+
+    #[test]
+    fn list_params_validation() {
+        let config: KubernetesConfig = toml::from_str(
+            r#"
+            config_map = "vector"
+            label_selector = "team=a"
+            "#,
+        )
+        .unwrap();
+        assert!(config.list_params().is_err());
+
+        assert!(KubernetesConfig::default().list_params().is_err());
+
+        let config: KubernetesConfig = toml::from_str("pipelines = true").unwrap();
+        assert!(config.list_params().is_ok());
+    }
+}
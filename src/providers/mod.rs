@@ -7,6 +7,8 @@ use crate::{
 };
 
 pub mod http;
+#[cfg(feature = "kubernetes")]
+pub mod kubernetes;
 
 pub type BuildResult = std::result::Result<ConfigBuilder, Vec<String>>;
 
@@ -18,6 +20,10 @@ pub type BuildResult = std::result::Result<ConfigBuilder, Vec<String>>;
 pub enum Providers {
     /// HTTP.
     Http(#[configurable(derived)] http::HttpConfig),
+
+    /// Kubernetes.
+    #[cfg(feature = "kubernetes")]
+    Kubernetes(#[configurable(derived)] kubernetes::KubernetesConfig),
 }
 
 // We can't use `enum_dispatch` here because it doesn't support associated constants.
@@ -27,6 +33,8 @@ impl NamedComponent for Providers {
     fn get_component_name(&self) -> &'static str {
         match self {
             Self::Http(config) => config.get_component_name(),
+            #[cfg(feature = "kubernetes")]
+            Self::Kubernetes(config) => config.get_component_name(),
         }
     }
 }
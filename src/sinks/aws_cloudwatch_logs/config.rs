@@ -7,10 +7,10 @@ use vector_config::configurable_component;
 
 use crate::{
     aws::{
-        create_client, create_smithy_client, resolve_region, AwsAuthentication, ClientBuilder,
-        RegionOrEndpoint,
+        create_client, create_smithy_client, resolve_region, AwsAuthentication, RegionOrEndpoint,
     },
     codecs::{Encoder, EncodingConfig},
+    common::cloudwatch_logs::CloudwatchLogsClientBuilder,
     config::{
         log_schema, AcknowledgementsConfig, DataType, GenerateConfig, Input, ProxyConfig,
         SinkConfig, SinkContext,
@@ -30,22 +30,6 @@ use crate::{
     tls::TlsConfig,
 };
 
-pub struct CloudwatchLogsClientBuilder;
-
-impl ClientBuilder for CloudwatchLogsClientBuilder {
-    type Config = aws_sdk_cloudwatchlogs::config::Config;
-    type Client = aws_sdk_cloudwatchlogs::client::Client;
-    type DefaultMiddleware = aws_sdk_cloudwatchlogs::middleware::DefaultMiddleware;
-
-    fn default_middleware() -> Self::DefaultMiddleware {
-        aws_sdk_cloudwatchlogs::middleware::DefaultMiddleware::new()
-    }
-
-    fn build(client: aws_smithy_client::Client, config: &aws_types::SdkConfig) -> Self::Client {
-        aws_sdk_cloudwatchlogs::client::Client::with_config(client, config.into())
-    }
-}
-
 /// Configuration for the `aws_cloudwatch_logs` sink.
 #[configurable_component(sink("aws_cloudwatch_logs"))]
 #[derive(Clone, Debug)]
@@ -15,7 +15,7 @@ use pretty_assertions::assert_eq;
 use super::*;
 use crate::aws::create_client;
 use crate::aws::{AwsAuthentication, RegionOrEndpoint};
-use crate::sinks::aws_cloudwatch_logs::config::CloudwatchLogsClientBuilder;
+use crate::common::cloudwatch_logs::CloudwatchLogsClientBuilder;
 use crate::{
     config::{log_schema, ProxyConfig, SinkConfig, SinkContext},
     event::{Event, LogEvent, Value},
@@ -53,9 +53,9 @@ async fn cloudwatch_metrics_put_data() {
             )
             .with_tags(Some(
                 vec![
-                    ("region".to_owned(), "us-west-1".to_owned()),
-                    ("production".to_owned(), "true".to_owned()),
-                    ("e".to_owned(), "".to_owned()),
+                    ("region".into(), "us-west-1".to_owned()),
+                    ("production".into(), "true".to_owned()),
+                    ("e".into(), "".to_owned()),
                 ]
                 .into_iter()
                 .collect(),
@@ -59,7 +59,7 @@ async fn encode_events_basic_counter() {
             MetricValue::Counter { value: 1.0 },
         )
         .with_tags(Some(
-            vec![("region".to_owned(), "local".to_owned())]
+            vec![("region".into(), "local".to_owned())]
                 .into_iter()
                 .collect(),
         ))
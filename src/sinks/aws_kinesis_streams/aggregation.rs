@@ -0,0 +1,320 @@
+//! Record aggregation in the format of the Kinesis Producer Library (KPL).
+//!
+//! Kinesis bills and throttles by record, so packing many small events into a single record cuts
+//! costs and raises the throughput of each shard. Consumers built on the Kinesis Client Library,
+//! or using one of the deaggregation libraries, unpack the events of aggregated records
+//! transparently, along with the partition key of each of them.
+
+use std::collections::HashMap;
+
+use aws_sdk_kinesis::{model::PutRecordsRequestEntry, types::Blob};
+use md5::{Digest, Md5};
+use prost::Message;
+use vector_config::configurable_component;
+
+use super::request_builder::KinesisRequest;
+use crate::event::{EventFinalizers, Finalizable};
+
+mod proto {
+    include!(concat!(env!("OUT_DIR"), "/kinesis_aggregation.rs"));
+}
+
+use proto::{AggregatedRecord, Record};
+
+/// The magic bytes aggregated records start with.
+const MAGIC: [u8; 4] = [0xF3, 0x89, 0x9A, 0xC2];
+
+/// The length of the MD5 digest aggregated records end with.
+const DIGEST_LEN: usize = 16;
+
+/// Kinesis rejects records larger than 1 MiB.
+const MAX_RECORD_BYTES: usize = 1_048_576;
+
+const fn default_max_bytes() -> usize {
+    51_200
+}
+
+/// Record aggregation configuration.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct AggregationConfig {
+    /// Whether or not to aggregate the events of a batch into KPL aggregated records.
+    ///
+    /// Each event is still sent with its own partition key inside of the aggregated record, but
+    /// the aggregated record is routed to a shard by the partition key of its first event. If
+    /// `partition_key_field` or `partition_key` is set, only events sharing the same partition key
+    /// are aggregated together, so that they all land on the shard they would have been routed to otherwise.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// The maximum size, in bytes, of an aggregated record.
+    ///
+    /// Events are added to an aggregated record until it would exceed this size. An event larger
+    /// than this size is sent in an aggregated record of its own.
+    #[serde(default = "default_max_bytes")]
+    #[configurable(validation(range(min = 1024, max = 1048576)))]
+    pub max_bytes: usize,
+}
+
+impl Default for AggregationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_bytes: default_max_bytes(),
+        }
+    }
+}
+
+/// Aggregates the requests of a batch into as few requests as the maximum record size allows.
+///
+/// If `group_by_partition_key` is set, only requests with the same partition key are aggregated
+/// together.
+pub fn aggregate(
+    requests: Vec<KinesisRequest>,
+    max_bytes: usize,
+    group_by_partition_key: bool,
+) -> Vec<KinesisRequest> {
+    let max_bytes = max_bytes.min(MAX_RECORD_BYTES) - MAGIC.len() - DIGEST_LEN;
+
+    let mut groups: Vec<Vec<KinesisRequest>> = Vec::new();
+    if group_by_partition_key {
+        let mut indices = HashMap::new();
+        for request in requests {
+            let key = partition_key(&request).to_owned();
+            let index = *indices.entry(key).or_insert_with(|| {
+                groups.push(Vec::new());
+                groups.len() - 1
+            });
+            groups[index].push(request);
+        }
+    } else {
+        groups.push(requests);
+    }
+
+    let mut aggregated = Vec::new();
+    for group in groups {
+        let mut aggregator = Aggregator::default();
+        for request in group {
+            if !aggregator.is_empty() && aggregator.encoded_len_with(&request) > max_bytes {
+                aggregated.push(aggregator.finish());
+                aggregator = Aggregator::default();
+            }
+            aggregator.push(request);
+        }
+        if !aggregator.is_empty() {
+            aggregated.push(aggregator.finish());
+        }
+    }
+    aggregated
+}
+
+fn partition_key(request: &KinesisRequest) -> &str {
+    request
+        .put_records_request
+        .partition_key
+        .as_deref()
+        .unwrap_or_default()
+}
+
+fn data(request: &KinesisRequest) -> &[u8] {
+    request
+        .put_records_request
+        .data
+        .as_ref()
+        .map(|data| data.as_ref())
+        .unwrap_or_default()
+}
+
+/// Builds a single aggregated record, keeping track of its encoded length as records are added.
+#[derive(Default)]
+struct Aggregator {
+    record: AggregatedRecord,
+    partition_keys: HashMap<String, u64>,
+    encoded_len: usize,
+    finalizers: EventFinalizers,
+    event_byte_size: usize,
+    event_count: usize,
+    /// The explicit hash key of the first record, which routes the aggregated record.
+    explicit_hash_key: Option<String>,
+}
+
+impl Aggregator {
+    fn is_empty(&self) -> bool {
+        self.record.records.is_empty()
+    }
+
+    /// Gets the encoded length of the aggregated record, if the given request was added to it.
+    fn encoded_len_with(&self, request: &KinesisRequest) -> usize {
+        let key = partition_key(request);
+        let (key_index, key_len) = match self.partition_keys.get(key) {
+            Some(index) => (*index, 0),
+            None => (
+                self.partition_keys.len() as u64,
+                prost::encoding::string::encoded_len(1, &key.to_owned()),
+            ),
+        };
+        let data_len = data(request).len();
+        let record_len = prost::encoding::uint64::encoded_len(1, &key_index)
+            + prost::encoding::key_len(3)
+            + prost::encoding::encoded_len_varint(data_len as u64)
+            + data_len;
+
+        self.encoded_len
+            + key_len
+            + prost::encoding::key_len(3)
+            + prost::encoding::encoded_len_varint(record_len as u64)
+            + record_len
+    }
+
+    fn push(&mut self, mut request: KinesisRequest) {
+        self.encoded_len = self.encoded_len_with(&request);
+        if self.is_empty() {
+            self.explicit_hash_key = request.put_records_request.explicit_hash_key.clone();
+        }
+
+        let key = partition_key(&request).to_owned();
+        let next_index = self.partition_keys.len() as u64;
+        let partition_key_index = *self.partition_keys.entry(key.clone()).or_insert_with(|| {
+            self.record.partition_key_table.push(key);
+            next_index
+        });
+
+        self.record.records.push(Record {
+            partition_key_index,
+            explicit_hash_key_index: None,
+            data: data(&request).to_vec(),
+            tags: Vec::new(),
+        });
+        self.finalizers.merge(request.take_finalizers());
+        self.event_byte_size += request.event_byte_size;
+        self.event_count += request.event_count;
+    }
+
+    fn finish(self) -> KinesisRequest {
+        // The aggregated record is routed by the partition key, or explicit hash key, of its first
+        // record.
+        let partition_key = self.record.partition_key_table[0].clone();
+
+        KinesisRequest {
+            put_records_request: PutRecordsRequestEntry::builder()
+                .data(Blob::new(frame(&self.record)))
+                .partition_key(partition_key)
+                .set_explicit_hash_key(self.explicit_hash_key)
+                .build(),
+            finalizers: self.finalizers,
+            event_byte_size: self.event_byte_size,
+            event_count: self.event_count,
+        }
+    }
+}
+
+/// Frames the aggregated record as the magic bytes, the encoded message and its MD5 digest.
+fn frame(record: &AggregatedRecord) -> Vec<u8> {
+    let message = record.encode_to_vec();
+    let mut framed = Vec::with_capacity(MAGIC.len() + message.len() + DIGEST_LEN);
+    framed.extend_from_slice(&MAGIC);
+    framed.extend_from_slice(&message);
+    framed.extend_from_slice(&Md5::digest(&message));
+    framed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(partition_key: &str, data: &str) -> KinesisRequest {
+        KinesisRequest {
+            put_records_request: PutRecordsRequestEntry::builder()
+                .data(Blob::new(data.as_bytes()))
+                .partition_key(partition_key)
+                .build(),
+            finalizers: EventFinalizers::default(),
+            event_byte_size: data.len(),
+            event_count: 1,
+        }
+    }
+
+    fn deaggregate(request: &KinesisRequest) -> AggregatedRecord {
+        let framed = data(request);
+        assert_eq!(&framed[..MAGIC.len()], &MAGIC);
+        let (message, digest) =
+            framed[MAGIC.len()..].split_at(framed.len() - MAGIC.len() - DIGEST_LEN);
+        assert_eq!(digest, Md5::digest(message).as_slice());
+        AggregatedRecord::decode(message).unwrap()
+    }
+
+    #[test]
+    fn aggregates_with_deaggregation_compatible_framing() {
+        let requests = vec![
+            request("a", "one"),
+            request("b", "two"),
+            request("a", "three"),
+        ];
+
+        let aggregated = aggregate(requests, default_max_bytes(), false);
+        assert_eq!(aggregated.len(), 1);
+        assert_eq!(aggregated[0].event_count, 3);
+        assert_eq!(aggregated[0].event_byte_size, 11);
+        assert_eq!(partition_key(&aggregated[0]), "a");
+
+        let record = deaggregate(&aggregated[0]);
+        assert_eq!(record.partition_key_table, vec!["a", "b"]);
+        let records = record
+            .records
+            .iter()
+            .map(|record| {
+                (
+                    record.partition_key_index,
+                    String::from_utf8(record.data.clone()).unwrap(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            records,
+            vec![
+                (0, "one".to_owned()),
+                (1, "two".to_owned()),
+                (0, "three".to_owned())
+            ]
+        );
+    }
+
+    #[test]
+    fn groups_by_partition_key() {
+        let requests = vec![
+            request("a", "one"),
+            request("b", "two"),
+            request("a", "three"),
+        ];
+
+        let aggregated = aggregate(requests, default_max_bytes(), true);
+        assert_eq!(aggregated.len(), 2);
+        assert_eq!(partition_key(&aggregated[0]), "a");
+        assert_eq!(deaggregate(&aggregated[0]).records.len(), 2);
+        assert_eq!(partition_key(&aggregated[1]), "b");
+        assert_eq!(deaggregate(&aggregated[1]).records.len(), 1);
+    }
+
+    #[test]
+    fn splits_at_max_bytes() {
+        let payload = "x".repeat(400);
+        let requests = (0..10).map(|_| request("a", &payload)).collect();
+
+        let aggregated = aggregate(requests, 1024, false);
+        assert_eq!(aggregated.len(), 5);
+        for request in &aggregated {
+            assert!(data(request).len() <= 1024);
+            assert_eq!(request.event_count, 2);
+        }
+
+        // The encoded length is tracked exactly.
+        let aggregator = {
+            let mut aggregator = Aggregator::default();
+            aggregator.push(request("a", "one"));
+            aggregator.push(request("b", "two"));
+            aggregator
+        };
+        assert_eq!(aggregator.encoded_len, aggregator.record.encoded_len());
+    }
+}
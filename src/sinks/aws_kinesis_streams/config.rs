@@ -9,23 +9,28 @@ use tower::ServiceBuilder;
 use vector_config::configurable_component;
 
 use super::{
-    request_builder::KinesisRequestBuilder, service::KinesisResponse, service::KinesisService,
-    sink::KinesisSink,
+    aggregation::AggregationConfig,
+    request_builder::KinesisRequestBuilder,
+    service::KinesisResponse,
+    service::KinesisService,
+    sink::{KinesisSink, PartitionKeySource},
 };
 use crate::{
-    aws::{create_client, is_retriable_error, AwsAuthentication, ClientBuilder, RegionOrEndpoint},
+    aws::{create_client, is_retriable_error, AwsAuthentication, RegionOrEndpoint},
     codecs::{Encoder, EncodingConfig},
+    common::kinesis::KinesisClientBuilder,
     config::{
         AcknowledgementsConfig, DataType, GenerateConfig, Input, ProxyConfig, SinkConfig,
         SinkContext,
     },
     sinks::{
         util::{
-            retries::RetryLogic, BatchConfig, Compression, ServiceBuilderExt, SinkBatchSettings,
-            TowerRequestConfig,
+            record_partitioner::RecordPartitioner, retries::RetryLogic, BatchConfig, Compression,
+            ServiceBuilderExt, SinkBatchSettings, TowerRequestConfig,
         },
         Healthcheck, VectorSink,
     },
+    template::Template,
     tls::TlsConfig,
 };
 
@@ -45,22 +50,6 @@ enum HealthcheckError {
     NoMatchingStreamName { stream_name: String },
 }
 
-pub struct KinesisClientBuilder;
-
-impl ClientBuilder for KinesisClientBuilder {
-    type Config = aws_sdk_kinesis::config::Config;
-    type Client = aws_sdk_kinesis::client::Client;
-    type DefaultMiddleware = aws_sdk_kinesis::middleware::DefaultMiddleware;
-
-    fn default_middleware() -> Self::DefaultMiddleware {
-        aws_sdk_kinesis::middleware::DefaultMiddleware::new()
-    }
-
-    fn build(client: aws_smithy_client::Client, config: &aws_types::SdkConfig) -> Self::Client {
-        aws_sdk_kinesis::client::Client::with_config(client, config.into())
-    }
-}
-
 #[derive(Clone, Copy, Debug, Default)]
 pub struct KinesisDefaultBatchSettings;
 
@@ -85,6 +74,22 @@ pub struct KinesisSinkConfig {
     /// If not specified, a unique partition key will be generated for each Kinesis record.
     pub partition_key_field: Option<String>,
 
+    /// The template of the Kinesis record’s partition key value, such as `{{ tenant }}-{{ host }}`.
+    ///
+    /// Events whose partition key can't be rendered are dropped. Can't be set along with
+    /// `partition_key_field`.
+    #[configurable(metadata(templateable))]
+    pub partition_key: Option<Template>,
+
+    /// How the Kinesis records are assigned to the shards of the stream.
+    ///
+    /// By default, Kinesis routes each record by the MD5 hash of its partition key. The `murmur2`
+    /// and `fnv` partitioners set the explicit hash key of the records from the hash of their
+    /// partition key instead, while the `random` and `sticky` partitioners ignore the partition
+    /// key of the events.
+    #[configurable(derived)]
+    pub partitioner: Option<RecordPartitioner>,
+
     #[serde(flatten)]
     pub region: RegionOrEndpoint,
 
@@ -99,6 +104,10 @@ pub struct KinesisSinkConfig {
     #[serde(default)]
     pub batch: BatchConfig<KinesisDefaultBatchSettings>,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub aggregation: AggregationConfig,
+
     #[configurable(derived)]
     #[serde(default)]
     pub request: TowerRequestConfig,
@@ -188,12 +197,23 @@ impl SinkConfig for KinesisSinkConfig {
             encoder: (transformer, encoder),
         };
 
+        let partition_key = match (&self.partition_key_field, &self.partition_key) {
+            (Some(_), Some(_)) => {
+                return Err("`partition_key_field` and `partition_key` can't both be set.".into())
+            }
+            (Some(field), None) => Some(PartitionKeySource::Field(field.clone())),
+            (None, Some(template)) => Some(PartitionKeySource::Template(template.clone())),
+            (None, None) => None,
+        };
+
         let sink = KinesisSink {
             batch_settings,
 
             service,
             request_builder,
-            partition_key_field: self.partition_key_field.clone(),
+            partition_key,
+            partitioner: self.partitioner,
+            aggregation: self.aggregation,
         };
         Ok((VectorSink::from_event_streamsink(sink), healthcheck))
     }
@@ -8,9 +8,10 @@ use aws_sdk_kinesis::{
 use codecs::TextSerializerConfig;
 use tokio::time::{sleep, Duration};
 
-use super::{config::KinesisClientBuilder, *};
+use super::*;
 use crate::{
     aws::{create_client, AwsAuthentication, RegionOrEndpoint},
+    common::kinesis::KinesisClientBuilder,
     config::{ProxyConfig, SinkConfig, SinkContext},
     sinks::util::{BatchConfig, Compression},
     test_util::{
@@ -35,10 +36,13 @@ async fn kinesis_put_records() {
     let config = KinesisSinkConfig {
         stream_name: stream.clone(),
         partition_key_field: None,
+        partition_key: None,
+        partitioner: None,
         region: RegionOrEndpoint::with_both("localstack", kinesis_address().as_str()),
         encoding: TextSerializerConfig::new().into(),
         compression: Compression::None,
         batch,
+        aggregation: Default::default(),
         request: Default::default(),
         tls: Default::default(),
         auth: Default::default(),
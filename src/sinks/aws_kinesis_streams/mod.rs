@@ -1,3 +1,4 @@
+mod aggregation;
 mod config;
 mod integration_tests;
 mod request_builder;
@@ -19,6 +19,7 @@ pub struct KinesisRequestBuilder {
 pub struct Metadata {
     pub finalizers: EventFinalizers,
     pub partition_key: String,
+    pub explicit_hash_key: Option<String>,
     pub event_byte_size: usize,
 }
 
@@ -27,6 +28,7 @@ pub struct KinesisRequest {
     pub put_records_request: PutRecordsRequestEntry,
     pub finalizers: EventFinalizers,
     pub event_byte_size: usize,
+    pub event_count: usize,
 }
 
 impl Finalizable for KinesisRequest {
@@ -96,6 +98,7 @@ impl RequestBuilder<KinesisProcessedEvent> for KinesisRequestBuilder {
         let metadata = Metadata {
             finalizers: event.event.take_finalizers(),
             partition_key: event.metadata.partition_key,
+            explicit_hash_key: event.metadata.explicit_hash_key,
             event_byte_size: event.event.size_of(),
         };
         (metadata, Event::from(event.event))
@@ -111,9 +114,11 @@ impl RequestBuilder<KinesisProcessedEvent> for KinesisRequestBuilder {
             put_records_request: PutRecordsRequestEntry::builder()
                 .data(Blob::new(&payload[..]))
                 .partition_key(metadata.partition_key)
+                .set_explicit_hash_key(metadata.explicit_hash_key)
                 .build(),
             finalizers: metadata.finalizers,
             event_byte_size: metadata.event_byte_size,
+            event_count: 1,
         }
     }
 }
@@ -49,7 +49,7 @@ impl Service<Vec<KinesisRequest>> for KinesisService {
         // Emission of Error internal event is handled upstream by the caller
 
         let events_byte_size = requests.iter().map(|req| req.event_byte_size).sum();
-        let count = requests.len();
+        let count = requests.iter().map(|req| req.event_count).sum();
 
         let records = requests
             .into_iter()
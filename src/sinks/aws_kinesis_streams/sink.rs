@@ -1,4 +1,4 @@
-use std::{fmt, num::NonZeroUsize};
+use std::{fmt, num::NonZeroUsize, time::Duration};
 
 use async_trait::async_trait;
 use futures::{future, stream::BoxStream, StreamExt};
@@ -8,24 +8,45 @@ use vector_core::stream::{BatcherSettings, DriverResponse};
 
 use crate::{
     event::{Event, LogEvent},
-    internal_events::SinkRequestBuildError,
+    internal_events::{SinkRequestBuildError, TemplateRenderingError},
     sinks::{
-        aws_kinesis_streams::request_builder::{KinesisRequest, KinesisRequestBuilder},
-        util::{processed_event::ProcessedEvent, SinkBuilderExt, StreamSink},
+        aws_kinesis_streams::{
+            aggregation::{aggregate, AggregationConfig},
+            request_builder::{KinesisRequest, KinesisRequestBuilder},
+        },
+        util::{
+            processed_event::ProcessedEvent,
+            record_partitioner::{RecordPartitioner, Sticky},
+            SinkBuilderExt, StreamSink,
+        },
     },
+    template::Template,
 };
 
+/// How long the `sticky` partitioner keeps a partition key before picking another.
+const STICKY_LINGER: Duration = Duration::from_secs(1);
+
 pub type KinesisProcessedEvent = ProcessedEvent<LogEvent, KinesisMetadata>;
 
 pub struct KinesisMetadata {
     pub partition_key: String,
+    pub explicit_hash_key: Option<String>,
+}
+
+/// Where the partition key of the records is extracted from.
+#[derive(Clone)]
+pub enum PartitionKeySource {
+    Field(String),
+    Template(Template),
 }
 
 pub struct KinesisSink<S> {
     pub batch_settings: BatcherSettings,
     pub service: S,
     pub request_builder: KinesisRequestBuilder,
-    pub partition_key_field: Option<String>,
+    pub partition_key: Option<PartitionKeySource>,
+    pub partitioner: Option<RecordPartitioner>,
+    pub aggregation: AggregationConfig,
 }
 
 impl<S> KinesisSink<S>
@@ -38,13 +59,19 @@ where
     async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
         let request_builder_concurrency_limit = NonZeroUsize::new(50);
 
-        let partition_key_field = self.partition_key_field.clone();
+        let partition_key = self.partition_key.clone();
+        let partitioner = self.partitioner;
+        let mut sticky = Sticky::new(STICKY_LINGER);
+        let aggregation = self.aggregation;
+        let group_by_partition_key = self.partition_key.is_some();
         let sink = input
             .map(|event| {
                 // Panic: This sink only accepts Logs, so this should never panic
                 event.into_log()
             })
-            .filter_map(move |log| future::ready(process_log(log, &partition_key_field)))
+            .filter_map(move |log| {
+                future::ready(process_log(log, &partition_key, partitioner, &mut sticky))
+            })
             .request_builder(request_builder_concurrency_limit, self.request_builder)
             .filter_map(|request| async move {
                 match request {
@@ -56,6 +83,13 @@ where
                 }
             })
             .batched(self.batch_settings.into_byte_size_config())
+            .map(move |requests| {
+                if aggregation.enabled {
+                    aggregate(requests, aggregation.max_bytes, group_by_partition_key)
+                } else {
+                    requests
+                }
+            })
             .into_driver(self.service);
 
         sink.run().await
@@ -77,34 +111,62 @@ where
 
 pub fn process_log(
     log: LogEvent,
-    partition_key_field: &Option<String>,
+    partition_key: &Option<PartitionKeySource>,
+    partitioner: Option<RecordPartitioner>,
+    sticky: &mut Sticky<String>,
 ) -> Option<KinesisProcessedEvent> {
-    let partition_key = if let Some(partition_key_field) = partition_key_field {
-        if let Some(v) = log.get(partition_key_field.as_str()) {
-            v.to_string_lossy()
-        } else {
-            warn!(
-                message = "Partition key does not exist; dropping event.",
-                %partition_key_field,
-                internal_log_rate_limit = true,
-            );
-            return None;
+    let partition_key = match (partitioner, partition_key) {
+        // These partitioners ignore the partition key of the events.
+        (Some(RecordPartitioner::Random), _) => gen_partition_key(),
+        (Some(RecordPartitioner::Sticky), _) => sticky.get(gen_partition_key),
+        (_, Some(PartitionKeySource::Field(partition_key_field))) => {
+            if let Some(v) = log.get(partition_key_field.as_str()) {
+                v.to_string_lossy()
+            } else {
+                warn!(
+                    message = "Partition key does not exist; dropping event.",
+                    %partition_key_field,
+                    internal_log_rate_limit = true,
+                );
+                return None;
+            }
         }
-    } else {
-        gen_partition_key()
+        (_, Some(PartitionKeySource::Template(template))) => template
+            .render_string(&log)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("partition_key"),
+                    drop_event: true,
+                });
+            })
+            .ok()?,
+        (_, None) => gen_partition_key(),
     };
     let partition_key = if partition_key.len() >= 256 {
         partition_key[..256].to_string()
     } else {
         partition_key
     };
+    let explicit_hash_key = partitioner
+        .and_then(|partitioner| partitioner.hash(partition_key.as_bytes()))
+        .map(explicit_hash_key);
 
     Some(KinesisProcessedEvent {
         event: log,
-        metadata: KinesisMetadata { partition_key },
+        metadata: KinesisMetadata {
+            partition_key,
+            explicit_hash_key,
+        },
     })
 }
 
+/// Spreads the positive 31-bit hash of a partition key over the 128-bit range of the hash keys of
+/// the shards, so that the records of a key are routed to the same shard.
+fn explicit_hash_key(hash: u32) -> String {
+    (u128::from(hash) << 97).to_string()
+}
+
 fn gen_partition_key() -> String {
     random::<[char; 16]>()
         .iter()
@@ -113,3 +175,73 @@ fn gen_partition_key() -> String {
             s
         })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn partition(
+        partition_key: Option<PartitionKeySource>,
+        partitioner: Option<RecordPartitioner>,
+        log: LogEvent,
+    ) -> Option<KinesisMetadata> {
+        let mut sticky = Sticky::new(STICKY_LINGER);
+        process_log(log, &partition_key, partitioner, &mut sticky).map(|event| event.metadata)
+    }
+
+    #[test]
+    fn extracts_partition_key() {
+        let mut log = LogEvent::from("hello");
+        log.insert("user", "alice");
+
+        let field = Some(PartitionKeySource::Field("user".to_owned()));
+        let metadata = partition(field.clone(), None, log.clone()).unwrap();
+        assert_eq!(metadata.partition_key, "alice");
+        assert_eq!(metadata.explicit_hash_key, None);
+        assert!(partition(field, None, LogEvent::from("hello")).is_none());
+
+        let template = Some(PartitionKeySource::Template(
+            Template::try_from("{{ user }}-{{ message }}").unwrap(),
+        ));
+        let metadata = partition(template.clone(), None, log).unwrap();
+        assert_eq!(metadata.partition_key, "alice-hello");
+        assert!(partition(template, None, LogEvent::from("hello")).is_none());
+    }
+
+    #[test]
+    fn hashes_partition_key() {
+        let mut log = LogEvent::from("hello");
+        log.insert("user", "21");
+        let field = Some(PartitionKeySource::Field("user".to_owned()));
+
+        let metadata = partition(field.clone(), Some(RecordPartitioner::Murmur2), log.clone());
+        assert_eq!(
+            metadata.unwrap().explicit_hash_key,
+            Some((1_173_551_340_u128 << 97).to_string())
+        );
+
+        let metadata = partition(field, Some(RecordPartitioner::Random), log).unwrap();
+        assert_ne!(metadata.partition_key, "21");
+        assert_eq!(metadata.explicit_hash_key, None);
+    }
+
+    #[test]
+    fn sticks_to_partition_key() {
+        let mut sticky = Sticky::new(STICKY_LINGER);
+        let keys = (0..3)
+            .map(|_| {
+                process_log(
+                    LogEvent::from("hello"),
+                    &None,
+                    Some(RecordPartitioner::Sticky),
+                    &mut sticky,
+                )
+                .unwrap()
+                .metadata
+                .partition_key
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(keys[0], keys[1]);
+        assert_eq!(keys[1], keys[2]);
+    }
+}
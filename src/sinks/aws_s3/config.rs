@@ -9,7 +9,10 @@ use tower::ServiceBuilder;
 use vector_config::configurable_component;
 use vector_core::sink::VectorSink;
 
-use super::sink::S3RequestOptions;
+use super::{
+    partitioner::{EventTimePartitioningConfig, S3KeyPartitioner},
+    sink::S3RequestOptions,
+};
 use crate::{
     aws::{AwsAuthentication, RegionOrEndpoint},
     codecs::{Encoder, EncodingConfigWithFraming, SinkType},
@@ -25,8 +28,8 @@ use crate::{
             sink::S3Sink,
         },
         util::{
-            partitioner::KeyPartitioner, BatchConfig, BulkSizeBasedDefaultBatchSettings,
-            Compression, ServiceBuilderExt, TowerRequestConfig,
+            BatchConfig, BulkSizeBasedDefaultBatchSettings, Compression, ServiceBuilderExt,
+            TowerRequestConfig,
         },
         Healthcheck,
     },
@@ -55,6 +58,10 @@ pub struct S3SinkConfig {
     #[configurable(metadata(templateable))]
     pub key_prefix: Option<String>,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub event_time_partitioning: EventTimePartitioningConfig,
+
     /// The timestamp format for the time component of the object key.
     ///
     /// By default, object keys are appended with a timestamp that reflects when the objects are
@@ -128,6 +135,7 @@ impl GenerateConfig for S3SinkConfig {
         toml::Value::try_from(Self {
             bucket: "".to_owned(),
             key_prefix: None,
+            event_time_partitioning: EventTimePartitioningConfig::default(),
             filename_time_format: None,
             filename_append_uuid: None,
             filename_extension: None,
@@ -174,6 +182,8 @@ impl S3SinkConfig {
             .settings(request_limits, S3RetryLogic)
             .service(service);
 
+        self.options.multipart_upload.validate()?;
+
         // Configure our partitioning/batching.
         let batch_settings = self.batch.into_batcher_settings()?;
         let key_prefix = self
@@ -182,7 +192,7 @@ impl S3SinkConfig {
             .cloned()
             .unwrap_or_else(|| DEFAULT_KEY_PREFIX.into())
             .try_into()?;
-        let partitioner = KeyPartitioner::new(key_prefix);
+        let partitioner = S3KeyPartitioner::new(key_prefix, &self.event_time_partitioning);
 
         // And now collect all of the S3-specific options and configuration knobs.
         let filename_time_format = self
@@ -346,6 +346,7 @@ fn config(bucket: &str, batch_size: usize) -> S3SinkConfig {
     S3SinkConfig {
         bucket: bucket.to_string(),
         key_prefix: Some(random_string(10) + "/date=%F"),
+        event_time_partitioning: Default::default(),
         filename_time_format: None,
         filename_append_uuid: None,
         filename_extension: None,
@@ -1,4 +1,5 @@
 mod config;
+mod partitioner;
 mod sink;
 
 mod integration_tests;
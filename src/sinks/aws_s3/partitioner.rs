@@ -0,0 +1,186 @@
+use chrono::{DateTime, Duration, Utc};
+use vector_config::configurable_component;
+use vector_core::{event::Event, partition::Partitioner};
+
+use crate::{config::log_schema, internal_events::TemplateRenderingError, template::Template};
+
+const fn default_allowed_lateness_secs() -> u64 {
+    3600
+}
+
+fn default_late_key_prefix() -> String {
+    "late/".to_owned()
+}
+
+/// Event-time partitioning configuration.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct EventTimePartitioningConfig {
+    /// Whether or not to render the time specifiers of `key_prefix` strictly from the timestamp of each event.
+    ///
+    /// By default, the time specifiers are rendered from the `timestamp` field of the event if it has one, and from
+    /// the time the event is processed otherwise. When enabled, the timestamp of the event is read from the field with
+    /// the `timestamp` semantic meaning, or from the `timestamp` field of the global log schema, and events without a
+    /// timestamp are handled as late events.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// The maximum lateness, in seconds, of an event.
+    ///
+    /// Events whose timestamp is further in the past than this when they are processed are late: their object key is
+    /// prefixed with `late_key_prefix`, so that the partitions of the `key_prefix` are not updated once they are
+    /// complete.
+    #[serde(default = "default_allowed_lateness_secs")]
+    #[derivative(Default(value = "default_allowed_lateness_secs()"))]
+    pub allowed_lateness_secs: u64,
+
+    /// The prefix of the object keys of late events.
+    ///
+    /// The prefix is prepended to the rendered `key_prefix`, such that a late event with a `key_prefix` of
+    /// `date=%F/hour=%H/` is written under `late/date=2022-07-18/hour=20/`.
+    #[serde(default = "default_late_key_prefix")]
+    #[derivative(Default(value = "default_late_key_prefix()"))]
+    pub late_key_prefix: String,
+}
+
+/// Partitions events by their rendered object key prefix, optionally using their event time.
+pub struct S3KeyPartitioner {
+    key_prefix: Template,
+    event_time: Option<EventTimePartitioning>,
+}
+
+struct EventTimePartitioning {
+    allowed_lateness: Duration,
+    late_key_prefix: String,
+}
+
+impl S3KeyPartitioner {
+    pub fn new(key_prefix: Template, event_time: &EventTimePartitioningConfig) -> Self {
+        let event_time = event_time.enabled.then(|| EventTimePartitioning {
+            allowed_lateness: Duration::seconds(event_time.allowed_lateness_secs as i64),
+            late_key_prefix: event_time.late_key_prefix.clone(),
+        });
+        Self {
+            key_prefix,
+            event_time,
+        }
+    }
+
+    fn partition_at(&self, item: &Event, now: DateTime<Utc>) -> Option<String> {
+        let rendered = match &self.event_time {
+            None => self.key_prefix.render_string(item),
+            Some(event_time) => match event_timestamp(item) {
+                Some(timestamp) if now - timestamp <= event_time.allowed_lateness => {
+                    self.key_prefix.render_string_at(item, timestamp)
+                }
+                timestamp => self
+                    .key_prefix
+                    .render_string_at(item, timestamp.unwrap_or(now))
+                    .map(|key| format!("{}{}", event_time.late_key_prefix, key)),
+            },
+        };
+
+        rendered
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("key_prefix"),
+                    drop_event: true,
+                });
+            })
+            .ok()
+    }
+}
+
+impl Partitioner for S3KeyPartitioner {
+    type Item = Event;
+    type Key = Option<String>;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        self.partition_at(item, Utc::now())
+    }
+}
+
+/// Gets the timestamp of the event, from the field with the `timestamp` meaning if there is one.
+fn event_timestamp(event: &Event) -> Option<DateTime<Utc>> {
+    match event {
+        Event::Log(log) => log
+            .get_by_meaning("timestamp")
+            .or_else(|| log.get(log_schema().timestamp_key()))
+            .and_then(|value| value.as_timestamp())
+            .copied(),
+        Event::Metric(metric) => metric.timestamp(),
+        Event::Trace(trace) => trace
+            .get(log_schema().timestamp_key())
+            .and_then(|value| value.as_timestamp())
+            .copied(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::event::LogEvent;
+
+    fn partitioner() -> S3KeyPartitioner {
+        S3KeyPartitioner::new(
+            Template::try_from("date=%F/hour=%H/").unwrap(),
+            &EventTimePartitioningConfig {
+                enabled: true,
+                ..Default::default()
+            },
+        )
+    }
+
+    fn event(timestamp: Option<DateTime<Utc>>) -> Event {
+        let mut log = LogEvent::from("hello world");
+        if let Some(timestamp) = timestamp {
+            log.insert(log_schema().timestamp_key(), timestamp);
+        }
+        log.into()
+    }
+
+    #[test]
+    fn partitions_by_event_time() {
+        let now = Utc.ymd(2022, 7, 18).and_hms(20, 30, 0);
+        let partitioner = partitioner();
+
+        assert_eq!(
+            partitioner.partition_at(&event(Some(Utc.ymd(2022, 7, 18).and_hms(19, 45, 0))), now),
+            Some("date=2022-07-18/hour=19/".to_owned())
+        );
+    }
+
+    #[test]
+    fn routes_late_events() {
+        let now = Utc.ymd(2022, 7, 18).and_hms(20, 30, 0);
+        let partitioner = partitioner();
+
+        assert_eq!(
+            partitioner.partition_at(&event(Some(Utc.ymd(2022, 7, 18).and_hms(18, 0, 0))), now),
+            Some("late/date=2022-07-18/hour=18/".to_owned())
+        );
+        assert_eq!(
+            partitioner.partition_at(&event(None), now),
+            Some("late/date=2022-07-18/hour=20/".to_owned())
+        );
+    }
+
+    #[test]
+    fn renders_key_prefix_as_is_when_disabled() {
+        let partitioner = S3KeyPartitioner::new(
+            Template::try_from("date=%F/").unwrap(),
+            &EventTimePartitioningConfig::default(),
+        );
+        let now = Utc.ymd(2022, 7, 18).and_hms(20, 30, 0);
+
+        assert_eq!(
+            partitioner.partition_at(&event(Some(Utc.ymd(2020, 1, 1).and_hms(0, 0, 0))), now),
+            Some("date=2020-01-01/".to_owned())
+        );
+    }
+}
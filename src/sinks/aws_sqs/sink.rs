@@ -32,7 +32,7 @@ pub(crate) struct SqsSink {
 
 impl SqsSink {
     pub fn new(config: SqsSinkConfig, client: SqsClient) -> crate::Result<Self> {
-        let request = config.request;
+        let request = config.request.clone();
         Ok(SqsSink {
             request_builder: SqsRequestBuilder::new(config)?,
             service: SqsService::new(client),
@@ -12,7 +12,13 @@ use crate::{
     config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
     sinks::{
         azure_common::{
-            self, config::AzureBlobRetryLogic, service::AzureBlobService, sink::AzureBlobSink,
+            self,
+            config::{
+                AzureAuthentication, AzureBlobRetryLogic, AzureBlobType, MAX_APPEND_BLOCK_SIZE,
+                MAX_BLOCK_SIZE,
+            },
+            service::AzureBlobService,
+            sink::AzureBlobSink,
         },
         util::{
             partitioner::KeyPartitioner, BatchConfig, BulkSizeBasedDefaultBatchSettings,
@@ -43,6 +49,8 @@ pub struct AzureBlobSinkConfig {
     /// - looks for a [Managed Identity][managed_ident_docs]
     /// - uses the `az` CLI tool to get an access token ([more information][az_cli_docs])
     ///
+    /// A user-assigned managed identity, or workload identity federation, can be used instead with `auth`.
+    ///
     /// Either `connection_string`, or this field, must be specified.
     ///
     /// [env_cred_docs]: https://docs.rs/azure_identity/latest/azure_identity/struct.EnvironmentCredential.html
@@ -50,6 +58,10 @@ pub struct AzureBlobSinkConfig {
     /// [az_cli_docs]: https://docs.microsoft.com/en-us/cli/azure/account?view=azure-cli-latest#az-account-get-access-token
     pub storage_account: Option<String>,
 
+    /// The strategy used to authenticate with the storage account, when `storage_account` is set.
+    #[serde(default)]
+    pub auth: AzureAuthentication,
+
     /// The Azure Blob Storage Account container name.
     pub(super) container_name: String,
 
@@ -75,6 +87,8 @@ pub struct AzureBlobSinkConfig {
     ///
     /// When set to an empty string, no timestamp will be appended to the blob prefix.
     ///
+    /// With the `append` blob type, defaults to `%H`, such that a new append blob is created each hour.
+    ///
     /// [chrono_strftime_specifiers]: https://docs.rs/chrono/latest/chrono/format/strftime/index.html#specifiers
     pub blob_time_format: Option<String>,
 
@@ -87,8 +101,22 @@ pub struct AzureBlobSinkConfig {
     ///
     /// This ensures there are no name collisions, and can be useful in high-volume workloads where
     /// blob keys must be unique.
+    ///
+    /// With the `append` blob type, defaults to `false`.
     pub blob_append_uuid: Option<bool>,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub blob_type: AzureBlobType,
+
+    /// The size, in bytes, of the blocks the blobs are written in.
+    ///
+    /// With the `block` blob type, batches larger than this are staged in blocks of this size, which are then
+    /// committed at once. With the `append` blob type, batches are appended in blocks of this size, which must be no
+    /// larger than 4 MiB.
+    #[serde(default = "default_block_size")]
+    pub block_size: usize,
+
     #[serde(flatten)]
     pub encoding: EncodingConfigWithFraming,
 
@@ -118,10 +146,13 @@ impl GenerateConfig for AzureBlobSinkConfig {
         toml::Value::try_from(Self {
             connection_string: Some(String::from("DefaultEndpointsProtocol=https;AccountName=some-account-name;AccountKey=some-account-key;").into()),
             storage_account: Some(String::from("some-account-name")),
+            auth: Default::default(),
             container_name: String::from("logs"),
             blob_prefix: Some(String::from("blob")),
             blob_time_format: Some(String::from("%s")),
             blob_append_uuid: Some(true),
+            blob_type: Default::default(),
+            block_size: default_block_size(),
             encoding: (Some(NewlineDelimitedEncoderConfig::new()), JsonSerializerConfig::new()).into(),
             compression: Compression::gzip_default(),
             batch: BatchConfig::default(),
@@ -138,6 +169,7 @@ impl SinkConfig for AzureBlobSinkConfig {
         let client = azure_common::config::build_client(
             self.connection_string.as_ref().map(|v| v.to_string()),
             self.storage_account.as_ref().map(|v| v.to_string()),
+            &self.auth,
             self.container_name.clone(),
         )?;
 
@@ -158,31 +190,60 @@ impl SinkConfig for AzureBlobSinkConfig {
     }
 }
 
+pub(super) const fn default_block_size() -> usize {
+    4 * 1024 * 1024
+}
+
 const DEFAULT_REQUEST_LIMITS: TowerRequestConfig =
     TowerRequestConfig::const_default().rate_limit_num(250);
 
 const DEFAULT_KEY_PREFIX: &str = "blob/%F/";
 const DEFAULT_FILENAME_TIME_FORMAT: &str = "%s";
 const DEFAULT_FILENAME_APPEND_UUID: bool = true;
+const DEFAULT_APPEND_FILENAME_TIME_FORMAT: &str = "%H";
+const DEFAULT_APPEND_FILENAME_APPEND_UUID: bool = false;
 
 impl AzureBlobSinkConfig {
     pub fn build_processor(&self, client: Arc<ContainerClient>) -> crate::Result<VectorSink> {
+        let max_block_size = match self.blob_type {
+            AzureBlobType::Block => MAX_BLOCK_SIZE,
+            AzureBlobType::Append => MAX_APPEND_BLOCK_SIZE,
+        };
+        if self.block_size == 0 || self.block_size > max_block_size {
+            return Err(format!(
+                "`block_size` must be between 1 and {} bytes for {:?} blobs",
+                max_block_size, self.blob_type
+            )
+            .into());
+        }
+
         let request_limits = self.request.unwrap_with(&DEFAULT_REQUEST_LIMITS);
         let service = ServiceBuilder::new()
             .settings(request_limits, AzureBlobRetryLogic)
-            .service(AzureBlobService::new(client));
+            .service(AzureBlobService::new(
+                client,
+                self.blob_type,
+                self.block_size,
+            ));
 
         // Configure our partitioning/batching.
         let batcher_settings = self.batch.into_batcher_settings()?;
 
+        // Append blobs are appended to by many batches, so their names are only unique per hour by
+        // default.
+        let (default_time_format, default_append_uuid) = match self.blob_type {
+            AzureBlobType::Block => (DEFAULT_FILENAME_TIME_FORMAT, DEFAULT_FILENAME_APPEND_UUID),
+            AzureBlobType::Append => (
+                DEFAULT_APPEND_FILENAME_TIME_FORMAT,
+                DEFAULT_APPEND_FILENAME_APPEND_UUID,
+            ),
+        };
         let blob_time_format = self
             .blob_time_format
             .as_ref()
             .cloned()
-            .unwrap_or_else(|| DEFAULT_FILENAME_TIME_FORMAT.into());
-        let blob_append_uuid = self
-            .blob_append_uuid
-            .unwrap_or(DEFAULT_FILENAME_APPEND_UUID);
+            .unwrap_or_else(|| default_time_format.into());
+        let blob_append_uuid = self.blob_append_uuid.unwrap_or(default_append_uuid);
 
         let transformer = self.encoding.transformer();
         let (framer, serializer) = self.encoding.build(SinkType::MessageBased)?;
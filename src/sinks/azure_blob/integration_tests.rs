@@ -15,11 +15,11 @@ use futures::{stream, Stream, StreamExt};
 use http::StatusCode;
 use vector_core::ByteSizeOf;
 
-use super::config::AzureBlobSinkConfig;
+use super::config::{default_block_size, AzureBlobSinkConfig};
 use crate::{
     event::{Event, EventArray, LogEvent},
     sinks::{
-        azure_common,
+        azure_common::{self, config::AzureBlobType},
         util::{Compression, TowerRequestConfig},
         VectorSink,
     },
@@ -35,6 +35,7 @@ async fn azure_blob_healthcheck_passed() {
     let client = azure_common::config::build_client(
         config.connection_string.map(Into::into),
         None,
+        &config.auth,
         config.container_name.clone(),
     )
     .expect("Failed to create client");
@@ -54,6 +55,7 @@ async fn azure_blob_healthcheck_unknown_container() {
     let client = azure_common::config::build_client(
         config.connection_string.map(Into::into),
         config.storage_account.map(Into::into),
+        &config.auth,
         config.container_name.clone(),
     )
     .expect("Failed to create client");
@@ -88,6 +90,29 @@ async fn azure_blob_insert_lines_into_blob() {
     assert_eq!(lines, blob_lines);
 }
 
+#[tokio::test]
+async fn azure_blob_append_lines_into_blob() {
+    let blob_prefix = format!("lines/append/blob/{}", random_string(10));
+    let config = AzureBlobSinkConfig::new_emulator().await;
+    let config = AzureBlobSinkConfig {
+        blob_prefix: Some(blob_prefix.clone()),
+        blob_type: AzureBlobType::Append,
+        block_size: 1024,
+        ..config
+    };
+    let (first_lines, input) = random_lines_with_stream(100, 10, None);
+    config.run_assert(input).await;
+    let (second_lines, input) = random_lines_with_stream(100, 10, None);
+    config.run_assert(input).await;
+
+    let blobs = config.list_blobs(blob_prefix).await;
+    assert_eq!(blobs.len(), 1);
+    assert!(blobs[0].clone().ends_with(".log"));
+    let (blob, blob_lines) = config.get_blob(blobs[0].clone()).await;
+    assert_eq!(blob.properties.content_type, String::from("text/plain"));
+    assert_eq!([first_lines, second_lines].concat(), blob_lines);
+}
+
 #[tokio::test]
 async fn azure_blob_insert_json_into_blob() {
     let blob_prefix = format!("json/into/blob/{}", random_string(10));
@@ -219,10 +244,13 @@ impl AzureBlobSinkConfig {
         let config = AzureBlobSinkConfig {
                 connection_string: Some(format!("UseDevelopmentStorage=true;DefaultEndpointsProtocol=http;AccountName=devstoreaccount1;AccountKey=Eby8vdM02xNOcqFlqUwJPLlmEtlCDXJ1OUzFT50uSRZ6IFsuFq2UVErCz4I6tq/K1SZFPTOtr/KBHBeksoGMGw==;BlobEndpoint=http://{}:10000/devstoreaccount1;QueueEndpoint=http://{}:10001/devstoreaccount1;TableEndpoint=http://{}:10002/devstoreaccount1;", address, address, address).into()),
                 storage_account: None,
+                auth: Default::default(),
                 container_name: "logs".to_string(),
                 blob_prefix: None,
                 blob_time_format: None,
                 blob_append_uuid: None,
+                blob_type: Default::default(),
+                block_size: default_block_size(),
                 encoding: (None::<FramingConfig>, TextSerializerConfig::new()).into(),
                 compression: Compression::None,
                 batch: Default::default(),
@@ -239,6 +267,7 @@ impl AzureBlobSinkConfig {
         let client = azure_common::config::build_client(
             self.connection_string.clone().map(Into::into),
             self.storage_account.clone().map(Into::into),
+            &self.auth,
             self.container_name.clone(),
         )
         .expect("Failed to create client");
@@ -257,6 +286,7 @@ impl AzureBlobSinkConfig {
         let client = azure_common::config::build_client(
             self.connection_string.clone().map(Into::into),
             self.storage_account.clone().map(Into::into),
+            &self.auth,
             self.container_name.clone(),
         )
         .unwrap();
@@ -285,6 +315,7 @@ impl AzureBlobSinkConfig {
         let client = azure_common::config::build_client(
             self.connection_string.clone().map(Into::into),
             self.storage_account.clone().map(Into::into),
+            &self.auth,
             self.container_name.clone(),
         )
         .unwrap();
@@ -321,6 +352,7 @@ impl AzureBlobSinkConfig {
         let client = azure_common::config::build_client(
             self.connection_string.clone().map(Into::into),
             self.storage_account.clone().map(Into::into),
+            &self.auth,
             self.container_name.clone(),
         )
         .unwrap();
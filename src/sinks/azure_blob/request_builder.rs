@@ -92,6 +92,8 @@ impl Compression {
             Self::None => "text/plain",
             Self::Gzip(_) => "application/gzip",
             Self::Zlib(_) => "application/zlib",
+            Self::Zstd { .. } => "application/zstd",
+            Self::Snappy => "application/x-snappy-framed",
         }
     }
 }
@@ -6,21 +6,27 @@ use codecs::{
 };
 use vector_core::partition::Partitioner;
 
-use super::config::AzureBlobSinkConfig;
+use super::config::{default_block_size, AzureBlobSinkConfig};
 use super::request_builder::AzureBlobRequestOptions;
 use crate::codecs::EncodingConfigWithFraming;
 use crate::event::{Event, LogEvent};
-use crate::sinks::util::{request_builder::RequestBuilder, Compression};
+use crate::sinks::{
+    azure_common::{self, config::AzureBlobType},
+    util::{request_builder::RequestBuilder, Compression},
+};
 use crate::{codecs::Encoder, sinks::util::request_builder::EncodeResult};
 
 fn default_config(encoding: EncodingConfigWithFraming) -> AzureBlobSinkConfig {
     AzureBlobSinkConfig {
         connection_string: Default::default(),
         storage_account: Default::default(),
+        auth: Default::default(),
         container_name: Default::default(),
         blob_prefix: Default::default(),
         blob_time_format: Default::default(),
         blob_append_uuid: Default::default(),
+        blob_type: Default::default(),
+        block_size: default_block_size(),
         encoding,
         compression: Compression::gzip_default(),
         batch: Default::default(),
@@ -200,3 +206,24 @@ fn azure_blob_build_request_with_uuid() {
     assert_eq!(request.content_encoding, None);
     assert_eq!(request.content_type, "text/plain");
 }
+
+#[tokio::test]
+async fn azure_blob_append_rejects_large_block_size() {
+    let client = azure_common::config::build_client(
+        Some("DefaultEndpointsProtocol=https;AccountName=some-account-name;AccountKey=some-account-key;".to_owned()),
+        None,
+        &Default::default(),
+        "logs".to_owned(),
+    )
+    .unwrap();
+    let sink_config = AzureBlobSinkConfig {
+        blob_type: AzureBlobType::Append,
+        block_size: 8 * 1024 * 1024,
+        ..default_config((None::<FramingConfig>, TextSerializerConfig::new()).into())
+    };
+
+    assert_eq!(
+        sink_config.build_processor(client).unwrap_err().to_string(),
+        "`block_size` must be between 1 and 4194304 bytes for Append blobs"
+    );
+}
@@ -0,0 +1,118 @@
+use std::{path::PathBuf, sync::Arc};
+
+use azure_core::{
+    auth::{TokenCredential, TokenResponse},
+    error::{Error as AzureError, ErrorKind},
+    HttpClient,
+};
+use azure_identity::{
+    federated_credentials_flow, DefaultAzureCredential, ImdsManagedIdentityCredential,
+};
+use chrono::{Duration, Utc};
+use vector_config::configurable_component;
+
+/// The strategy used to authenticate with Azure.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields, rename_all = "snake_case", tag = "strategy")]
+pub enum AzureAuthentication {
+    /// Loads credentials from environment variables, from the managed identity of the host, or from the `az` CLI
+    /// tool, in that order.
+    #[derivative(Default)]
+    Default,
+
+    /// Authenticates as a user-assigned [managed identity][managed_ident_docs].
+    ///
+    /// [managed_ident_docs]: https://docs.microsoft.com/en-us/azure/active-directory/managed-identities-azure-resources/overview
+    ManagedIdentity {
+        /// The client ID of the user-assigned managed identity.
+        client_id: String,
+    },
+
+    /// Authenticates as an application with [workload identity federation][workload_ident_docs], such as with Azure
+    /// AD Workload Identity on Kubernetes.
+    ///
+    /// [workload_ident_docs]: https://docs.microsoft.com/en-us/azure/active-directory/develop/workload-identity-federation
+    WorkloadIdentity {
+        /// The client ID of the application.
+        ///
+        /// By default, the `AZURE_CLIENT_ID` environment variable is used.
+        client_id: Option<String>,
+
+        /// The ID of the tenant of the application.
+        ///
+        /// By default, the `AZURE_TENANT_ID` environment variable is used.
+        tenant_id: Option<String>,
+
+        /// The path of the file holding the token exchanged for an access token.
+        ///
+        /// By default, the `AZURE_FEDERATED_TOKEN_FILE` environment variable is used.
+        token_file: Option<PathBuf>,
+    },
+}
+
+impl AzureAuthentication {
+    pub(crate) fn credential(&self) -> crate::Result<Arc<dyn TokenCredential>> {
+        Ok(match self {
+            Self::Default => Arc::new(DefaultAzureCredential::default()),
+            Self::ManagedIdentity { client_id } => {
+                Arc::new(ImdsManagedIdentityCredential::default().with_client_id(client_id))
+            }
+            Self::WorkloadIdentity {
+                client_id,
+                tenant_id,
+                token_file,
+            } => Arc::new(WorkloadIdentityCredential {
+                http_client: azure_core::new_http_client(),
+                client_id: from_env_or(client_id, "AZURE_CLIENT_ID")?,
+                tenant_id: from_env_or(tenant_id, "AZURE_TENANT_ID")?,
+                token_file: match token_file {
+                    Some(token_file) => token_file.clone(),
+                    None => from_env_or(&None, "AZURE_FEDERATED_TOKEN_FILE")?.into(),
+                },
+            }),
+        })
+    }
+}
+
+fn from_env_or(value: &Option<String>, variable: &str) -> crate::Result<String> {
+    match value {
+        Some(value) => Ok(value.clone()),
+        None => std::env::var(variable)
+            .map_err(|_| format!("`{}` must be set for workload identity", variable).into()),
+    }
+}
+
+/// Exchanges the token of the token file, which is rotated by the orchestrator, for an access
+/// token of the application.
+struct WorkloadIdentityCredential {
+    http_client: Arc<dyn HttpClient>,
+    client_id: String,
+    tenant_id: String,
+    token_file: PathBuf,
+}
+
+#[async_trait::async_trait]
+impl TokenCredential for WorkloadIdentityCredential {
+    async fn get_token(&self, resource: &str) -> azure_core::Result<TokenResponse> {
+        let assertion = tokio::fs::read_to_string(&self.token_file)
+            .await
+            .map_err(|error| AzureError::new(ErrorKind::Credential, error))?;
+        let scope = format!("{}/.default", resource.trim_end_matches('/'));
+        let response = federated_credentials_flow::perform(
+            Arc::clone(&self.http_client),
+            &self.client_id,
+            assertion.trim(),
+            &[&scope],
+            &self.tenant_id,
+        )
+        .await?;
+        Ok(TokenResponse::new(
+            response.access_token().clone(),
+            response
+                .expires_on
+                .unwrap_or_else(|| Utc::now() + Duration::minutes(5)),
+        ))
+    }
+}
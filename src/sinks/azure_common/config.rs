@@ -1,13 +1,14 @@
 use std::sync::Arc;
 
 use azure_core::{error::HttpError, RetryOptions};
-use azure_identity::{AutoRefreshingTokenCredential, DefaultAzureCredential};
+use azure_identity::AutoRefreshingTokenCredential;
 use azure_storage::{prelude::*, CloudLocation, ConnectionString};
-use azure_storage_blobs::{blob::operations::PutBlockBlobResponse, prelude::*};
+use azure_storage_blobs::prelude::*;
 use bytes::Bytes;
 use futures::FutureExt;
 use http::StatusCode;
 use snafu::Snafu;
+use vector_config::configurable_component;
 use vector_core::{internal_event::CountByteSize, stream::DriverResponse};
 
 use crate::{
@@ -15,6 +16,8 @@ use crate::{
     sinks::{util::retries::RetryLogic, Healthcheck},
 };
 
+pub use super::auth::AzureAuthentication;
+
 #[derive(Debug, Clone)]
 pub struct AzureBlobRequest {
     pub blob_data: Bytes,
@@ -48,11 +51,14 @@ impl RetryLogic for AzureBlobRetryLogic {
         error.status().is_server_error()
             || StatusCode::TOO_MANY_REQUESTS.as_u16() == Into::<u16>::into(error.status())
     }
+
+    fn error_status(&self, error: &Self::Error) -> Option<StatusCode> {
+        StatusCode::from_u16(error.status().into()).ok()
+    }
 }
 
 #[derive(Debug)]
 pub struct AzureBlobResponse {
-    pub inner: PutBlockBlobResponse,
     pub count: usize,
     pub events_byte_size: usize,
     pub byte_size: usize,
@@ -72,6 +78,31 @@ impl DriverResponse for AzureBlobResponse {
     }
 }
 
+/// The largest block allowed by Azure Blob Storage in an append blob.
+pub const MAX_APPEND_BLOCK_SIZE: usize = 4 * 1024 * 1024;
+
+/// The largest block allowed by Azure Blob Storage in a block blob.
+pub const MAX_BLOCK_SIZE: usize = 100 * 1024 * 1024;
+
+/// The type of the created blobs.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AzureBlobType {
+    /// Each batch of events is written to a new block blob.
+    ///
+    /// Batches larger than `block_size` are staged in blocks, which are then committed at once.
+    #[derivative(Default)]
+    Block,
+
+    /// Each batch of events is appended to an append blob, which is created if it doesn't exist.
+    ///
+    /// The batches are appended in blocks of at most `block_size`, which must be no larger than 4 MiB, such that the
+    /// blob can be tailed while it is written to.
+    Append,
+}
+
 #[derive(Debug, Snafu)]
 pub enum HealthcheckError {
     #[snafu(display("Invalid connection string specified"))]
@@ -109,48 +140,72 @@ pub fn build_healthcheck(
     Ok(healthcheck.boxed())
 }
 
-pub fn build_client(
+/// The name, credentials and custom endpoints of a storage account.
+pub struct StorageAccount {
+    pub name: String,
+    pub credentials: StorageCredentials,
+    pub blob_endpoint: Option<String>,
+    pub queue_endpoint: Option<String>,
+}
+
+pub fn resolve_storage_account(
     connection_string: Option<String>,
     storage_account: Option<String>,
-    container_name: String,
-) -> crate::Result<Arc<ContainerClient>> {
-    let client;
+    auth: &AzureAuthentication,
+) -> crate::Result<StorageAccount> {
     match (connection_string, storage_account) {
         (Some(connection_string_p), None) => {
             let connection_string = ConnectionString::new(&connection_string_p)?;
-            let mut builder = ClientBuilder::new(
-                connection_string
+            Ok(StorageAccount {
+                name: connection_string
                     .account_name
-                    .ok_or("Account name missing in connection string")?,
-                connection_string.storage_credentials()?,
-            )
-            .retry(RetryOptions::none());
-            if let Some(uri) = connection_string.blob_endpoint {
-                builder = builder.cloud_location(CloudLocation::Custom {
-                    uri: uri.to_string(),
-                    credentials: connection_string.storage_credentials()?,
-                });
-            }
-            client = builder.container_client(container_name);
+                    .ok_or("Account name missing in connection string")?
+                    .to_owned(),
+                credentials: connection_string.storage_credentials()?,
+                blob_endpoint: connection_string.blob_endpoint.map(|uri| uri.to_string()),
+                queue_endpoint: connection_string.queue_endpoint.map(|uri| uri.to_string()),
+            })
         }
         (None, Some(storage_account_p)) => {
-            let creds = std::sync::Arc::new(DefaultAzureCredential::default());
+            let creds = auth.credential()?;
             let auto_creds = std::sync::Arc::new(AutoRefreshingTokenCredential::new(creds));
-            let storage_credentials = StorageCredentials::TokenCredential(auto_creds);
 
-            client = ClientBuilder::new(storage_account_p, storage_credentials)
-                .retry(RetryOptions::none())
-                .container_client(container_name)
+            Ok(StorageAccount {
+                name: storage_account_p,
+                credentials: StorageCredentials::TokenCredential(auto_creds),
+                blob_endpoint: None,
+                queue_endpoint: None,
+            })
         }
         (None, None) => {
-            return Err("Either `connection_string` or `storage_account` has to be provided".into())
-        }
-        (Some(_), Some(_)) => {
-            return Err(
-                "`connection_string` and `storage_account` can't be provided at the same time"
-                    .into(),
-            )
+            Err("Either `connection_string` or `storage_account` has to be provided".into())
         }
+        (Some(_), Some(_)) => Err(
+            "`connection_string` and `storage_account` can't be provided at the same time".into(),
+        ),
     }
-    Ok(std::sync::Arc::new(client))
+}
+
+pub fn build_blob_service_client(account: StorageAccount) -> BlobServiceClient {
+    let mut builder =
+        ClientBuilder::new(account.name, account.credentials.clone()).retry(RetryOptions::none());
+    if let Some(uri) = account.blob_endpoint {
+        builder = builder.cloud_location(CloudLocation::Custom {
+            uri,
+            credentials: account.credentials,
+        });
+    }
+    builder.blob_service_client()
+}
+
+pub fn build_client(
+    connection_string: Option<String>,
+    storage_account: Option<String>,
+    auth: &AzureAuthentication,
+    container_name: String,
+) -> crate::Result<Arc<ContainerClient>> {
+    let account = resolve_storage_account(connection_string, storage_account, auth)?;
+    Ok(Arc::new(
+        build_blob_service_client(account).container_client(container_name),
+    ))
 }
@@ -1,3 +1,11 @@
+pub(crate) mod auth;
+#[cfg(any(
+    feature = "sinks-azure_blob",
+    feature = "sinks-datadog_archives",
+    feature = "sources-azure_blob"
+))]
 pub(crate) mod config;
+#[cfg(any(feature = "sinks-azure_blob", feature = "sinks-datadog_archives"))]
 pub(crate) mod service;
+#[cfg(any(feature = "sinks-azure_blob", feature = "sinks-datadog_archives"))]
 pub(crate) mod sink;
@@ -4,21 +4,37 @@ use std::{
     task::{Context, Poll},
 };
 
-use azure_storage_blobs::prelude::*;
+use azure_core::{error::HttpError, prelude::IfMatchCondition};
+use azure_storage_blobs::{
+    blob::{BlobBlockType, BlockList},
+    prelude::*,
+};
+use bytes::Bytes;
 use futures::future::BoxFuture;
+use http::StatusCode;
 use tower::Service;
 use tracing::Instrument;
 
-use crate::sinks::azure_common::config::{AzureBlobRequest, AzureBlobResponse};
+use crate::sinks::azure_common::config::{AzureBlobRequest, AzureBlobResponse, AzureBlobType};
 
 #[derive(Clone)]
 pub(crate) struct AzureBlobService {
     client: Arc<ContainerClient>,
+    blob_type: AzureBlobType,
+    block_size: usize,
 }
 
 impl AzureBlobService {
-    pub fn new(client: Arc<ContainerClient>) -> AzureBlobService {
-        AzureBlobService { client }
+    pub fn new(
+        client: Arc<ContainerClient>,
+        blob_type: AzureBlobType,
+        block_size: usize,
+    ) -> AzureBlobService {
+        AzureBlobService {
+            client,
+            blob_type,
+            block_size,
+        }
     }
 }
 
@@ -41,22 +57,17 @@ impl Service<AzureBlobRequest> for AzureBlobService {
                 .client
                 .blob_client(request.metadata.partition_key.as_str());
             let byte_size = request.blob_data.len();
-            let blob = client
-                .put_block_blob(request.blob_data)
-                .content_type(request.content_type);
-            let blob = match request.content_encoding {
-                Some(encoding) => blob.content_encoding(encoding),
-                None => blob,
-            };
-
-            let result = blob
-                .into_future()
-                .instrument(info_span!("request").or_current())
-                .await
-                .map_err(|err| err.into());
 
-            result.map(|inner| AzureBlobResponse {
-                inner,
+            let result = match this.blob_type {
+                AzureBlobType::Block if byte_size > this.block_size => {
+                    put_staged_block_blob(&client, &request, this.block_size).await
+                }
+                AzureBlobType::Block => put_block_blob(&client, &request).await,
+                AzureBlobType::Append => append_blob(&client, &request, this.block_size).await,
+            }
+            .map_err(|err| err.into());
+
+            result.map(|()| AzureBlobResponse {
                 count: request.metadata.count,
                 events_byte_size: request.metadata.byte_size,
                 byte_size,
@@ -64,3 +75,143 @@ impl Service<AzureBlobRequest> for AzureBlobService {
         })
     }
 }
+
+async fn put_block_blob(client: &BlobClient, request: &AzureBlobRequest) -> azure_core::Result<()> {
+    let blob = client
+        .put_block_blob(request.blob_data.clone())
+        .content_type(request.content_type);
+    let blob = match request.content_encoding {
+        Some(encoding) => blob.content_encoding(encoding),
+        None => blob,
+    };
+
+    blob.into_future()
+        .instrument(info_span!("request").or_current())
+        .await
+        .map(|_| ())
+}
+
+/// Stages the blob data in blocks, then commits all of the blocks at once, such that the blob is
+/// only visible once all of its data is written.
+async fn put_staged_block_blob(
+    client: &BlobClient,
+    request: &AzureBlobRequest,
+    block_size: usize,
+) -> azure_core::Result<()> {
+    let mut block_list = BlockList::default();
+    for (index, block) in split_blocks(&request.blob_data, block_size)
+        .into_iter()
+        .enumerate()
+    {
+        // Block IDs must all have the same length within a blob.
+        let block_id = BlockId::new(format!("{:08}", index));
+        client
+            .put_block(block_id.clone(), block)
+            .into_future()
+            .instrument(info_span!("request").or_current())
+            .await?;
+        block_list
+            .blocks
+            .push(BlobBlockType::new_uncommitted(block_id));
+    }
+
+    let blob = client
+        .put_block_list(block_list)
+        .content_type(request.content_type);
+    let blob = match request.content_encoding {
+        Some(encoding) => blob.content_encoding(encoding),
+        None => blob,
+    };
+
+    blob.into_future()
+        .instrument(info_span!("request").or_current())
+        .await
+        .map(|_| ())
+}
+
+/// Appends the blob data to the append blob, creating it first if it doesn't exist.
+async fn append_blob(
+    client: &BlobClient,
+    request: &AzureBlobRequest,
+    block_size: usize,
+) -> azure_core::Result<()> {
+    let mut created = false;
+    for block in split_blocks(&request.blob_data, block_size) {
+        loop {
+            match client
+                .append_block(block.clone())
+                .into_future()
+                .instrument(info_span!("request").or_current())
+                .await
+            {
+                Ok(_) => break,
+                Err(error) if !created && is_not_found(&error) => {
+                    create_append_blob(client, request).await?;
+                    created = true;
+                }
+                Err(error) => return Err(error),
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn create_append_blob(
+    client: &BlobClient,
+    request: &AzureBlobRequest,
+) -> azure_core::Result<()> {
+    let blob = client
+        .put_append_blob()
+        .if_match(IfMatchCondition::NotMatch("*".into()))
+        .content_type(request.content_type);
+    let blob = match request.content_encoding {
+        Some(encoding) => blob.content_encoding(encoding),
+        None => blob,
+    };
+
+    match blob
+        .into_future()
+        .instrument(info_span!("request").or_current())
+        .await
+    {
+        // The blob was created concurrently, by another request for the same blob.
+        Err(error) if is_status(&error, StatusCode::CONFLICT) => Ok(()),
+        result => result.map(|_| ()),
+    }
+}
+
+fn is_not_found(error: &azure_core::Error) -> bool {
+    is_status(error, StatusCode::NOT_FOUND)
+}
+
+fn is_status(error: &azure_core::Error, status: StatusCode) -> bool {
+    error
+        .downcast_ref::<HttpError>()
+        .map_or(false, |error| u16::from(error.status()) == status.as_u16())
+}
+
+/// Splits the blob data into blocks of at most the given size.
+fn split_blocks(data: &Bytes, block_size: usize) -> Vec<Bytes> {
+    (0..data.len())
+        .step_by(block_size)
+        .map(|start| data.slice(start..data.len().min(start + block_size)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_blob_data_into_blocks() {
+        let data = Bytes::from(vec![0u8; 25]);
+
+        let blocks = split_blocks(&data, 10);
+        assert_eq!(
+            blocks.iter().map(Bytes::len).collect::<Vec<_>>(),
+            vec![10, 10, 5]
+        );
+        assert_eq!(blocks.concat(), data);
+        assert!(split_blocks(&Bytes::new(), 10).is_empty());
+    }
+}
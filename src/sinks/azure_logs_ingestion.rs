@@ -0,0 +1,538 @@
+use std::{collections::HashMap, sync::Arc};
+
+use azure_core::auth::TokenCredential;
+use azure_identity::AutoRefreshingTokenCredential;
+use bytes::Bytes;
+use chrono::{DateTime, SecondsFormat, TimeZone, Utc};
+use futures::{FutureExt, SinkExt};
+use http::{header, Request, Uri};
+use serde_json::{Map, Value as JsonValue};
+use vector_config::configurable_component;
+
+use crate::{
+    codecs::Transformer,
+    config::{log_schema, AcknowledgementsConfig, Input, SinkConfig, SinkContext},
+    event::{Event, LogEvent, Value},
+    http::HttpClient,
+    internal_events::AzureLogsIngestionColumnConversionError,
+    sinks::{
+        azure_common::auth::AzureAuthentication,
+        util::{
+            http::{BatchedHttpSink, HttpEventEncoder, HttpSink},
+            BatchConfig, BoxedRawValue, JsonArrayBuffer, SinkBatchSettings, TowerRequestConfig,
+        },
+        Healthcheck, VectorSink,
+    },
+    tls::{TlsConfig, TlsSettings},
+};
+
+/// The largest request body accepted by the Logs Ingestion API is 1 MB. The batches are kept a
+/// little below it, as the limit applies to the whole body rather than to the events alone.
+const MAX_BATCH_SIZE: usize = 1_000_000;
+
+/// The resource the access tokens are requested for.
+const RESOURCE: &str = "https://monitor.azure.com";
+
+/// API version
+const API_VERSION: &str = "2023-01-01";
+
+/// The column every table of Azure Monitor Logs has, holding the time of the record.
+const TIME_GENERATED: &str = "TimeGenerated";
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AzureLogsIngestionDefaultBatchSettings;
+
+impl SinkBatchSettings for AzureLogsIngestionDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = None;
+    const MAX_BYTES: Option<usize> = Some(MAX_BATCH_SIZE);
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+/// Configuration for the `azure_logs_ingestion` sink.
+#[configurable_component(sink("azure_logs_ingestion"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct AzureLogsIngestionConfig {
+    /// The logs ingestion endpoint of the [Data Collection Endpoint][dce], or of the Data Collection Rule.
+    ///
+    /// For example, `https://my-dce-a1b2.eastus-1.ingest.monitor.azure.com`.
+    ///
+    /// [dce]: https://learn.microsoft.com/en-us/azure/azure-monitor/essentials/data-collection-endpoint-overview
+    pub endpoint: String,
+
+    /// The immutable ID of the [Data Collection Rule][dcr], such as `dcr-000a00a000a00000a000000aa000a0aa`.
+    ///
+    /// [dcr]: https://learn.microsoft.com/en-us/azure/azure-monitor/essentials/data-collection-rule-overview
+    pub dcr_immutable_id: String,
+
+    /// The name of the stream of the Data Collection Rule the events are sent to, such as `Custom-MyTable_CL`.
+    pub stream_name: String,
+
+    /// The strategy used to authenticate with Microsoft Entra ID.
+    ///
+    /// The identity must be granted the `Monitoring Metrics Publisher` role on the Data Collection Rule.
+    #[serde(default)]
+    pub auth: AzureAuthentication,
+
+    /// The columns of the stream, and the fields of the events they are taken from.
+    ///
+    /// Each value is converted to the type of its column. Fields that are missing, or whose value can't be converted,
+    /// are left out of the record.
+    ///
+    /// When no columns are set, all the fields of the events are sent as is. In both cases, the `TimeGenerated`
+    /// column is set to the timestamp of the event, unless it is explicitly configured.
+    #[serde(default)]
+    pub columns: HashMap<String, ColumnConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub encoding: Transformer,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<AzureLogsIngestionDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+/// A column of the stream.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ColumnConfig {
+    /// The field of the event the column is taken from.
+    ///
+    /// By default, the field named after the column is used.
+    pub field: Option<String>,
+
+    /// The type of the column.
+    #[serde(rename = "type")]
+    pub type_: ColumnType,
+}
+
+/// The type of a column.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ColumnType {
+    /// A string.
+    ///
+    /// Values of other types are converted to their string representation.
+    String,
+
+    /// A 32-bit integer.
+    Int,
+
+    /// A 64-bit integer.
+    Long,
+
+    /// A floating point number.
+    Real,
+
+    /// A boolean.
+    Boolean,
+
+    /// A timestamp.
+    ///
+    /// Strings are parsed as RFC 3339 timestamps, and integers as Unix timestamps in seconds.
+    Datetime,
+
+    /// Any value, sent as JSON.
+    Dynamic,
+
+    /// A GUID, as a string.
+    Guid,
+}
+
+impl Default for AzureLogsIngestionConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: "https://my-dce-a1b2.eastus-1.ingest.monitor.azure.com".to_string(),
+            dcr_immutable_id: "dcr-000a00a000a00000a000000aa000a0aa".to_string(),
+            stream_name: "Custom-MyTable_CL".to_string(),
+            auth: AzureAuthentication::default(),
+            columns: HashMap::new(),
+            encoding: Transformer::default(),
+            batch: BatchConfig::default(),
+            request: TowerRequestConfig::default(),
+            tls: None,
+            acknowledgements: AcknowledgementsConfig::default(),
+        }
+    }
+}
+
+impl_generate_config_from_default!(AzureLogsIngestionConfig);
+
+#[async_trait::async_trait]
+impl SinkConfig for AzureLogsIngestionConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let batch_settings = self
+            .batch
+            .validate()?
+            .limit_max_bytes(MAX_BATCH_SIZE)?
+            .into_batch_settings()?;
+
+        let tls_settings = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(Some(tls_settings), &cx.proxy)?;
+
+        let sink = AzureLogsIngestionSink::new(self)?;
+        let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
+
+        let healthcheck = healthcheck(Arc::clone(&sink.credential)).boxed();
+
+        let sink = BatchedHttpSink::new(
+            sink,
+            JsonArrayBuffer::new(batch_settings.size),
+            request_settings,
+            batch_settings.timeout,
+            client,
+        )
+        .sink_map_err(|error| error!(message = "Fatal azure_logs_ingestion sink error.", %error));
+
+        Ok((VectorSink::from_event_sink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+#[derive(Clone)]
+struct AzureLogsIngestionSink {
+    uri: Uri,
+    credential: Arc<AutoRefreshingTokenCredential>,
+    transformer: Transformer,
+    columns: Arc<HashMap<String, ColumnConfig>>,
+}
+
+struct AzureLogsIngestionEventEncoder {
+    transformer: Transformer,
+    columns: Arc<HashMap<String, ColumnConfig>>,
+}
+
+impl HttpEventEncoder<JsonValue> for AzureLogsIngestionEventEncoder {
+    fn encode_event(&mut self, mut event: Event) -> Option<JsonValue> {
+        self.transformer.transform(&mut event);
+        Some(JsonValue::Object(to_record(
+            event.into_log(),
+            &self.columns,
+        )))
+    }
+}
+
+#[async_trait::async_trait]
+impl HttpSink for AzureLogsIngestionSink {
+    type Input = JsonValue;
+    type Output = Vec<BoxedRawValue>;
+    type Encoder = AzureLogsIngestionEventEncoder;
+
+    fn build_encoder(&self) -> Self::Encoder {
+        AzureLogsIngestionEventEncoder {
+            transformer: self.transformer.clone(),
+            columns: Arc::clone(&self.columns),
+        }
+    }
+
+    async fn build_request(&self, events: Self::Output) -> crate::Result<Request<Bytes>> {
+        let token = self.credential.get_token(RESOURCE).await?;
+        self.build_request_sync(events, token.token.secret())
+    }
+}
+
+impl AzureLogsIngestionSink {
+    fn new(config: &AzureLogsIngestionConfig) -> crate::Result<Self> {
+        if config.dcr_immutable_id.is_empty() {
+            return Err("dcr_immutable_id can't be an empty string".into());
+        }
+        if config.stream_name.is_empty() {
+            return Err("stream_name can't be an empty string".into());
+        }
+
+        let uri = format!(
+            "{}/dataCollectionRules/{}/streams/{}?api-version={}",
+            config.endpoint.trim_end_matches('/'),
+            config.dcr_immutable_id,
+            config.stream_name,
+            API_VERSION
+        )
+        .parse()?;
+        let credential = Arc::new(AutoRefreshingTokenCredential::new(
+            config.auth.credential()?,
+        ));
+
+        Ok(Self {
+            uri,
+            credential,
+            transformer: config.encoding.clone(),
+            columns: Arc::new(config.columns.clone()),
+        })
+    }
+
+    fn build_request_sync(
+        &self,
+        events: Vec<BoxedRawValue>,
+        token: &str,
+    ) -> crate::Result<Request<Bytes>> {
+        let body = crate::serde::json::to_bytes(&events)?.freeze();
+
+        Request::post(self.uri.clone())
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(header::AUTHORIZATION, format!("Bearer {}", token))
+            .body(body)
+            .map_err(Into::into)
+    }
+}
+
+/// Builds the record of the stream of an event.
+fn to_record(mut log: LogEvent, columns: &HashMap<String, ColumnConfig>) -> Map<String, JsonValue> {
+    let timestamp = match log.remove(log_schema().timestamp_key()) {
+        Some(Value::Timestamp(timestamp)) => timestamp,
+        _ => Utc::now(),
+    };
+
+    let mut record = if columns.is_empty() {
+        match serde_json::json!(&log) {
+            JsonValue::Object(record) => record,
+            _ => Map::new(),
+        }
+    } else {
+        columns
+            .iter()
+            .filter_map(|(column, config)| {
+                let field = config.field.as_deref().unwrap_or(column);
+                let value = log.get(field)?;
+                match convert(value, config.type_) {
+                    Some(value) => Some((column.clone(), value)),
+                    None => {
+                        emit!(AzureLogsIngestionColumnConversionError {
+                            column,
+                            field,
+                            type_: config.type_,
+                        });
+                        None
+                    }
+                }
+            })
+            .collect()
+    };
+
+    record
+        .entry(TIME_GENERATED)
+        .or_insert_with(|| format_datetime(timestamp));
+    record
+}
+
+/// Converts a value to the type of a column, returning `None` if it can't be.
+fn convert(value: &Value, type_: ColumnType) -> Option<JsonValue> {
+    match (type_, value) {
+        (_, Value::Null) => Some(JsonValue::Null),
+
+        (ColumnType::String | ColumnType::Guid, Value::Bytes(bytes)) => {
+            Some(String::from_utf8_lossy(bytes).into_owned().into())
+        }
+        (ColumnType::String, Value::Timestamp(timestamp)) => Some(format_datetime(*timestamp)),
+        (ColumnType::String, Value::Object(_) | Value::Array(_)) => {
+            serde_json::to_string(value).ok().map(Into::into)
+        }
+        (ColumnType::String, value) => Some(value.to_string_lossy().into()),
+
+        (ColumnType::Int, value) => {
+            to_i64(value).and_then(|int| i32::try_from(int).ok().map(Into::into))
+        }
+        (ColumnType::Long, value) => to_i64(value).map(Into::into),
+
+        (ColumnType::Real, Value::Integer(int)) => Some((*int as f64).into()),
+        (ColumnType::Real, Value::Float(float)) => Some(float.into_inner().into()),
+        (ColumnType::Real, Value::Bytes(bytes)) => std::str::from_utf8(bytes)
+            .ok()?
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|float| float.is_finite())
+            .map(Into::into),
+
+        (ColumnType::Boolean, Value::Boolean(boolean)) => Some((*boolean).into()),
+        (ColumnType::Boolean, Value::Bytes(bytes)) => {
+            match std::str::from_utf8(bytes).ok()?.trim() {
+                "true" | "True" | "TRUE" => Some(true.into()),
+                "false" | "False" | "FALSE" => Some(false.into()),
+                _ => None,
+            }
+        }
+
+        (ColumnType::Datetime, Value::Timestamp(timestamp)) => Some(format_datetime(*timestamp)),
+        (ColumnType::Datetime, Value::Bytes(bytes)) => {
+            DateTime::parse_from_rfc3339(std::str::from_utf8(bytes).ok()?.trim())
+                .ok()
+                .map(|timestamp| format_datetime(timestamp.with_timezone(&Utc)))
+        }
+        (ColumnType::Datetime, Value::Integer(seconds)) => {
+            Utc.timestamp_opt(*seconds, 0).single().map(format_datetime)
+        }
+
+        (ColumnType::Dynamic, value) => serde_json::to_value(value).ok(),
+
+        _ => None,
+    }
+}
+
+fn to_i64(value: &Value) -> Option<i64> {
+    match value {
+        Value::Integer(int) => Some(*int),
+        Value::Float(float) if float.fract() == 0.0 => {
+            let float = float.into_inner();
+            (float >= i64::MIN as f64 && float <= i64::MAX as f64).then(|| float as i64)
+        }
+        Value::Bytes(bytes) => std::str::from_utf8(bytes).ok()?.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+// Azure Monitor doesn't support the full nanosecond precision of the timestamps, so they are sent
+// with milliseconds.
+fn format_datetime(timestamp: DateTime<Utc>) -> JsonValue {
+    timestamp
+        .to_rfc3339_opts(SecondsFormat::Millis, true)
+        .into()
+}
+
+async fn healthcheck(credential: Arc<AutoRefreshingTokenCredential>) -> crate::Result<()> {
+    credential
+        .get_token(RESOURCE)
+        .await
+        .map(|_| ())
+        .map_err(|error| format!("Failed to get an access token: {}", error).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AzureLogsIngestionConfig>();
+    }
+
+    fn column(field: Option<&str>, type_: ColumnType) -> ColumnConfig {
+        ColumnConfig {
+            field: field.map(ToString::to_string),
+            type_,
+        }
+    }
+
+    #[test]
+    fn sends_all_fields_without_columns() {
+        let mut log = LogEvent::from("hello");
+        log.insert("host", "web-1");
+        log.insert(
+            log_schema().timestamp_key(),
+            DateTime::parse_from_rfc3339("2022-10-01T12:30:00.123456Z")
+                .unwrap()
+                .with_timezone(&Utc),
+        );
+
+        let record = to_record(log, &HashMap::new());
+
+        assert_eq!(
+            JsonValue::Object(record),
+            json!({
+                "message": "hello",
+                "host": "web-1",
+                "TimeGenerated": "2022-10-01T12:30:00.123Z",
+            })
+        );
+    }
+
+    #[test]
+    fn converts_fields_to_columns() {
+        let mut log = LogEvent::from("hello");
+        log.insert("status", "404");
+        log.insert("duration", 12);
+        log.insert("ok", "false");
+        log.insert("request.path", "/index.html");
+        log.insert("when", "2022-10-01T12:30:00+02:00");
+        log.insert("count", "not a number");
+
+        let columns = HashMap::from([
+            (
+                "Message".to_string(),
+                column(Some("message"), ColumnType::String),
+            ),
+            ("status".to_string(), column(None, ColumnType::Int)),
+            ("duration".to_string(), column(None, ColumnType::Real)),
+            ("ok".to_string(), column(None, ColumnType::Boolean)),
+            ("request".to_string(), column(None, ColumnType::Dynamic)),
+            (
+                "When".to_string(),
+                column(Some("when"), ColumnType::Datetime),
+            ),
+            ("Count".to_string(), column(Some("count"), ColumnType::Long)),
+            ("Missing".to_string(), column(None, ColumnType::String)),
+        ]);
+        let mut record = to_record(log, &columns);
+        assert!(record.remove(TIME_GENERATED).is_some());
+
+        assert_eq!(
+            JsonValue::Object(record),
+            json!({
+                "Message": "hello",
+                "status": 404,
+                "duration": 12.0,
+                "ok": false,
+                "request": {"path": "/index.html"},
+                "When": "2022-10-01T10:30:00.000Z",
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_out_of_range_ints() {
+        assert_eq!(convert(&Value::from(1_i64 << 40), ColumnType::Int), None);
+        assert_eq!(
+            convert(&Value::from(1_i64 << 40), ColumnType::Long),
+            Some(json!(1_i64 << 40))
+        );
+    }
+
+    #[test]
+    fn builds_requests() {
+        let config = AzureLogsIngestionConfig {
+            endpoint: "https://my-dce.eastus-1.ingest.monitor.azure.com/".to_string(),
+            ..Default::default()
+        };
+        let sink = AzureLogsIngestionSink::new(&config).unwrap();
+
+        let event = serde_json::value::to_raw_value(&json!({"message": "hello"})).unwrap();
+        let request = sink.build_request_sync(vec![event], "token").unwrap();
+
+        assert_eq!(
+            request.uri(),
+            "https://my-dce.eastus-1.ingest.monitor.azure.com/dataCollectionRules/dcr-000a00a000a00000a000000aa000a0aa/streams/Custom-MyTable_CL?api-version=2023-01-01"
+        );
+        assert_eq!(request.headers()[header::AUTHORIZATION], "Bearer token");
+        assert_eq!(request.headers()[header::CONTENT_TYPE], "application/json");
+        assert_eq!(request.body().as_ref(), br#"[{"message":"hello"}]"#);
+    }
+}
@@ -1,4 +1,9 @@
+use std::time::Duration;
+
 use futures::{future, FutureExt};
+use rand::Rng;
+use rand_distr::{Distribution, Exp, Normal};
+use vector_common::finalization::EventStatus;
 use vector_config::configurable_component;
 
 use crate::{
@@ -28,6 +33,23 @@ pub struct BlackholeConfig {
     /// By default, there is no limit.
     pub rate: Option<usize>,
 
+    /// The number of bytes, per second, that the sink is allowed to consume.
+    ///
+    /// The size of the events is their size in memory. By default, there is no limit.
+    pub rate_bytes: Option<usize>,
+
+    #[configurable(derived)]
+    pub latency: Option<LatencyDistribution>,
+
+    /// The ratio of the batches of events that the sink fails to consume, between `0.0` and `1.0`.
+    ///
+    /// The events of these batches are acknowledged with the `error_status`, so that the sources that support end-to-end
+    /// acknowledgements report the failure upstream.
+    pub error_rate: f64,
+
+    #[configurable(derived)]
+    pub error_status: InjectedErrorStatus,
+
     #[configurable(derived)]
     #[serde(
         default,
@@ -37,9 +59,118 @@ pub struct BlackholeConfig {
     pub acknowledgements: AcknowledgementsConfig,
 }
 
+/// The latency of the sink, as the distribution of the delays before each batch of events is consumed.
+///
+/// The batches are consumed one at a time, so the latency also slows the sink down.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[serde(tag = "distribution", rename_all = "snake_case")]
+pub enum LatencyDistribution {
+    /// Each batch is delayed by the same latency.
+    Constant {
+        /// The latency, in milliseconds.
+        latency_ms: u64,
+    },
+
+    /// The latency is uniformly distributed between a minimum and a maximum.
+    Uniform {
+        /// The minimum latency, in milliseconds.
+        min_ms: u64,
+
+        /// The maximum latency, in milliseconds.
+        max_ms: u64,
+    },
+
+    /// The latency is normally distributed, and never less than zero.
+    Normal {
+        /// The mean of the latency, in milliseconds.
+        mean_ms: f64,
+
+        /// The standard deviation of the latency, in milliseconds.
+        stddev_ms: f64,
+    },
+
+    /// The latency is exponentially distributed, as the delays between events occurring at random.
+    Exponential {
+        /// The mean of the latency, in milliseconds.
+        mean_ms: f64,
+    },
+}
+
+impl LatencyDistribution {
+    fn validate(&self) -> crate::Result<()> {
+        match *self {
+            Self::Constant { .. } => Ok(()),
+            Self::Uniform { min_ms, max_ms } if min_ms > max_ms => {
+                Err("The minimum latency must not be greater than the maximum latency.".into())
+            }
+            Self::Uniform { .. } => Ok(()),
+            Self::Normal { mean_ms, stddev_ms } => Normal::new(mean_ms, stddev_ms)
+                .map(|_| ())
+                .map_err(|_| "The standard deviation of the latency must not be negative.".into()),
+            Self::Exponential { mean_ms } if mean_ms > 0.0 => Ok(()),
+            Self::Exponential { .. } => Err("The mean of the latency must be positive.".into()),
+        }
+    }
+
+    /// Draws the latency of a batch of events.
+    pub(super) fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Duration {
+        let latency_ms = match *self {
+            Self::Constant { latency_ms } => latency_ms as f64,
+            Self::Uniform { min_ms, max_ms } => rng.gen_range(min_ms..=max_ms) as f64,
+            Self::Normal { mean_ms, stddev_ms } => {
+                Normal::new(mean_ms, stddev_ms).map_or(mean_ms, |normal| normal.sample(rng))
+            }
+            Self::Exponential { mean_ms } => {
+                Exp::new(1.0 / mean_ms).map_or(mean_ms, |exp| exp.sample(rng))
+            }
+        };
+        Duration::from_secs_f64(latency_ms.max(0.0) / 1000.0)
+    }
+}
+
+/// The status the events of the batches that the sink fails to consume are acknowledged with.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum InjectedErrorStatus {
+    /// The events encountered a retriable error.
+    #[derivative(Default)]
+    Errored,
+
+    /// The events were rejected, permanently.
+    Rejected,
+}
+
+impl InjectedErrorStatus {
+    pub(super) const fn as_str(self) -> &'static str {
+        match self {
+            Self::Errored => "errored",
+            Self::Rejected => "rejected",
+        }
+    }
+}
+
+impl From<InjectedErrorStatus> for EventStatus {
+    fn from(status: InjectedErrorStatus) -> Self {
+        match status {
+            InjectedErrorStatus::Errored => Self::Errored,
+            InjectedErrorStatus::Rejected => Self::Rejected,
+        }
+    }
+}
+
 #[async_trait::async_trait]
 impl SinkConfig for BlackholeConfig {
     async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        if !(0.0..=1.0).contains(&self.error_rate) {
+            return Err("The error rate must be between 0.0 and 1.0.".into());
+        }
+        if let Some(latency) = &self.latency {
+            latency.validate()?;
+        }
+
         let sink = BlackholeSink::new(self.clone());
         let healthcheck = future::ok(()).boxed();
 
@@ -63,10 +194,94 @@ impl GenerateConfig for BlackholeConfig {
 
 #[cfg(test)]
 mod tests {
-    use crate::sinks::blackhole::config::BlackholeConfig;
+    use rand::{rngs::SmallRng, SeedableRng};
+
+    use super::*;
 
     #[test]
     fn generate_config() {
         crate::test_util::test_generate_config::<BlackholeConfig>();
     }
+
+    #[test]
+    fn samples_latencies() {
+        let mut rng = SmallRng::seed_from_u64(0);
+
+        let constant = LatencyDistribution::Constant { latency_ms: 25 };
+        assert_eq!(constant.sample(&mut rng), Duration::from_millis(25));
+
+        let uniform = LatencyDistribution::Uniform {
+            min_ms: 10,
+            max_ms: 20,
+        };
+        for _ in 0..100 {
+            let latency = uniform.sample(&mut rng);
+            assert!(latency >= Duration::from_millis(10) && latency <= Duration::from_millis(20));
+        }
+
+        // The latencies are never negative, however wide the distribution.
+        let normal = LatencyDistribution::Normal {
+            mean_ms: 1.0,
+            stddev_ms: 100.0,
+        };
+        for _ in 0..100 {
+            let _ = normal.sample(&mut rng);
+        }
+
+        let exponential = LatencyDistribution::Exponential { mean_ms: 50.0 };
+        let mean = (0..10_000)
+            .map(|_| exponential.sample(&mut rng).as_secs_f64())
+            .sum::<f64>()
+            / 10_000.0;
+        assert!((0.045..0.055).contains(&mean));
+    }
+
+    #[test]
+    fn validates_latencies() {
+        assert!(LatencyDistribution::Uniform {
+            min_ms: 20,
+            max_ms: 10
+        }
+        .validate()
+        .is_err());
+        assert!(LatencyDistribution::Normal {
+            mean_ms: 10.0,
+            stddev_ms: -1.0
+        }
+        .validate()
+        .is_err());
+        assert!(LatencyDistribution::Exponential { mean_ms: 0.0 }
+            .validate()
+            .is_err());
+        assert!(LatencyDistribution::Exponential { mean_ms: 5.0 }
+            .validate()
+            .is_ok());
+    }
+
+    #[test]
+    fn parses_fault_injection() {
+        let config: BlackholeConfig = toml::from_str(
+            r#"
+            rate_bytes = 1048576
+            error_rate = 0.1
+            error_status = "rejected"
+
+            [latency]
+            distribution = "normal"
+            mean_ms = 50.0
+            stddev_ms = 10.0
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.rate_bytes, Some(1_048_576));
+        assert_eq!(config.error_rate, 0.1);
+        assert_eq!(config.error_status, InjectedErrorStatus::Rejected);
+        assert_eq!(
+            config.latency,
+            Some(LatencyDistribution::Normal {
+                mean_ms: 50.0,
+                stddev_ms: 10.0
+            })
+        );
+    }
 }
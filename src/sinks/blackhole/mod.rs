@@ -7,12 +7,20 @@ pub use config::BlackholeConfig;
 mod tests {
 
     use crate::{
+        event::{BatchNotifier, BatchStatus},
         sinks::{
-            blackhole::{config::BlackholeConfig, sink::BlackholeSink},
+            blackhole::{
+                config::{BlackholeConfig, InjectedErrorStatus},
+                sink::BlackholeSink,
+            },
             VectorSink,
         },
         test_util::{
-            components::run_and_assert_nonsending_sink_compliance, random_events_with_stream,
+            components::{
+                run_and_assert_nonsending_sink_compliance, run_and_assert_sink_error,
+                COMPONENT_ERROR_TAGS,
+            },
+            random_events_with_stream,
         },
     };
 
@@ -21,7 +29,7 @@ mod tests {
         let config = BlackholeConfig {
             print_interval_secs: 10,
             rate: None,
-            acknowledgements: Default::default(),
+            ..Default::default()
         };
         let sink = BlackholeSink::new(config);
         let sink = VectorSink::Stream(Box::new(sink));
@@ -29,4 +37,21 @@ mod tests {
         let (_input_lines, events) = random_events_with_stream(100, 10, None);
         run_and_assert_nonsending_sink_compliance(sink, events, &[]).await;
     }
+
+    #[tokio::test]
+    async fn blackhole_injects_errors() {
+        let config = BlackholeConfig {
+            print_interval_secs: 0,
+            error_rate: 1.0,
+            error_status: InjectedErrorStatus::Rejected,
+            ..Default::default()
+        };
+        let sink = BlackholeSink::new(config);
+        let sink = VectorSink::Stream(Box::new(sink));
+
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let (_input_lines, events) = random_events_with_stream(100, 10, Some(batch));
+        run_and_assert_sink_error(sink, events, &COMPONENT_ERROR_TAGS).await;
+        assert_eq!(receiver.await, BatchStatus::Rejected);
+    }
 }
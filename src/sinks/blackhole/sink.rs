@@ -8,15 +8,17 @@ use std::{
 
 use async_trait::async_trait;
 use futures::{stream::BoxStream, StreamExt};
+use rand::{rngs::SmallRng, Rng, SeedableRng};
 use tokio::{
     select,
     sync::watch,
-    time::{interval, sleep_until},
+    time::{interval, sleep, sleep_until},
 };
 use vector_core::{internal_event::EventsSent, ByteSizeOf};
 
 use crate::{
-    event::{EventArray, EventContainer},
+    event::{EventArray, EventContainer, EventStatus, Finalizable},
+    internal_events::BlackholeInjectedError,
     sinks::{blackhole::config::BlackholeConfig, util::StreamSink},
 };
 
@@ -25,6 +27,7 @@ pub struct BlackholeSink {
     total_raw_bytes: Arc<AtomicUsize>,
     config: BlackholeConfig,
     last: Option<Instant>,
+    rng: SmallRng,
 }
 
 impl BlackholeSink {
@@ -34,6 +37,7 @@ impl BlackholeSink {
             total_events: Arc::new(AtomicUsize::new(0)),
             total_raw_bytes: Arc::new(AtomicUsize::new(0)),
             last: None,
+            rng: SmallRng::from_entropy(),
         }
     }
 }
@@ -73,16 +77,41 @@ impl StreamSink<EventArray> for BlackholeSink {
             });
         }
 
-        while let Some(events) = input.next().await {
-            if let Some(rate) = self.config.rate {
-                let factor: f32 = 1.0 / rate as f32;
-                let secs: f32 = factor * (events.len() as f32);
+        while let Some(mut events) = input.next().await {
+            let message_len = events.size_of();
+
+            // The sink is held back by whichever of the caps takes the longest to consume the events.
+            let throttle_secs = self
+                .config
+                .rate
+                .map(|rate| events.len() as f32 / rate as f32)
+                .into_iter()
+                .chain(
+                    self.config
+                        .rate_bytes
+                        .map(|rate_bytes| message_len as f32 / rate_bytes as f32),
+                )
+                .reduce(f32::max);
+            if let Some(secs) = throttle_secs {
                 let until = self.last.unwrap_or_else(Instant::now) + Duration::from_secs_f32(secs);
                 sleep_until(until.into()).await;
                 self.last = Some(until);
             }
 
-            let message_len = events.size_of();
+            if let Some(latency) = &self.config.latency {
+                sleep(latency.sample(&mut self.rng)).await;
+            }
+
+            let finalizers = events.take_finalizers();
+            if self.config.error_rate > 0.0 && self.rng.gen_bool(self.config.error_rate) {
+                finalizers.update_status(self.config.error_status.into());
+                emit!(BlackholeInjectedError {
+                    count: events.len(),
+                    status: self.config.error_status.as_str(),
+                });
+                continue;
+            }
+            finalizers.update_status(EventStatus::Delivered);
 
             let _ = self.total_events.fetch_add(events.len(), Ordering::AcqRel);
             let _ = self
@@ -191,6 +191,10 @@ impl RetryLogic for ClickhouseRetryLogic {
             _ => self.inner.should_retry_response(response),
         }
     }
+
+    fn response_status(&self, response: &Self::Response) -> Option<StatusCode> {
+        Some(response.status())
+    }
 }
 
 #[cfg(test)]
@@ -7,7 +7,7 @@ use bytes::Bytes;
 use futures::future::BoxFuture;
 use http::{
     header::{CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE},
-    Request, Uri,
+    Request, StatusCode, Uri,
 };
 use hyper::Body;
 use tower::Service;
@@ -34,6 +34,10 @@ impl RetryLogic for LogApiRetry {
     fn is_retriable_error(&self, error: &Self::Error) -> bool {
         error.is_retriable()
     }
+
+    fn error_status(&self, error: &Self::Error) -> Option<StatusCode> {
+        error.status()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -3,7 +3,10 @@ use std::{fmt::Debug, io, num::NonZeroUsize, sync::Arc};
 use async_trait::async_trait;
 use bytes::Bytes;
 use codecs::{encoding::Framer, CharacterDelimitedEncoder, JsonSerializer};
-use futures::{stream::BoxStream, StreamExt};
+use futures::{
+    stream::{self, BoxStream},
+    StreamExt,
+};
 use lookup::event_path;
 use snafu::Snafu;
 use tower::Service;
@@ -179,8 +182,6 @@ impl crate::sinks::util::encoding::Encoder<Vec<Event>> for SemanticJsonEncoding
 
 #[derive(Debug, Snafu)]
 pub enum RequestBuildError {
-    #[snafu(display("Encoded payload is greater than the max limit."))]
-    PayloadTooBig,
     #[snafu(display("Failed to build payload with error: {}", error))]
     Io { error: std::io::Error },
 }
@@ -230,20 +231,9 @@ impl RequestBuilder<(Option<Arc<str>>, Vec<Event>)> for LogRequestBuilder {
         // We need to first serialize the payload separately so that we can figure out how big it is
         // before compression.  The Datadog Logs API has a limit on uncompressed data, so we can't
         // use the default implementation of this method.
-        //
-        // TODO: We should probably make `build_request` fallible itself, because then this override of `encode_events`
-        // wouldn't even need to exist, and we could handle it in `build_request` which is required by all implementors.
-        //
-        // On the flip side, it would mean that we'd potentially be compressing payloads that we would inevitably end up
-        // rejecting anyways, which is meh. This might be a signal that the true "right" fix is to actually switch this
-        // sink to incremental encoding and simply put up with suboptimal batch sizes if we need to end up splitting due
-        // to (un)compressed size limitations.
         let mut buf = Vec::new();
         let n_events = events.len();
         let uncompressed_size = self.encoder().encode_input(events, &mut buf)?;
-        if uncompressed_size > MAX_PAYLOAD_BYTES {
-            return Err(RequestBuildError::PayloadTooBig);
-        }
 
         // Now just compress it like normal.
         let mut compressor = Compressor::from(self.compression);
@@ -274,6 +264,10 @@ impl RequestBuilder<(Option<Arc<str>>, Vec<Event>)> for LogRequestBuilder {
             uncompressed_size,
         }
     }
+
+    fn max_payload_bytes(&self) -> Option<usize> {
+        Some(MAX_PAYLOAD_BYTES)
+    }
 }
 
 struct SemanticLogRequestBuilder {
@@ -315,20 +309,9 @@ impl RequestBuilder<(Option<Arc<str>>, Vec<Event>)> for SemanticLogRequestBuilde
         // We need to first serialize the payload separately so that we can figure out how big it is
         // before compression.  The Datadog Logs API has a limit on uncompressed data, so we can't
         // use the default implementation of this method.
-        //
-        // TODO: We should probably make `build_request` fallible itself, because then this override of `encode_events`
-        // wouldn't even need to exist, and we could handle it in `build_request` which is required by all implementors.
-        //
-        // On the flip side, it would mean that we'd potentially be compressing payloads that we would inevitably end up
-        // rejecting anyways, which is meh. This might be a signal that the true "right" fix is to actually switch this
-        // sink to incremental encoding and simply put up with suboptimal batch sizes if we need to end up splitting due
-        // to (un)compressed size limitations.
         let mut buf = Vec::new();
         let n_events = events.len();
         let uncompressed_size = self.encoder().encode_input(events, &mut buf)?;
-        if uncompressed_size > MAX_PAYLOAD_BYTES {
-            return Err(RequestBuildError::PayloadTooBig);
-        }
 
         // Now just compress it like normal.
         let mut compressor = Compressor::from(self.compression);
@@ -359,6 +342,10 @@ impl RequestBuilder<(Option<Arc<str>>, Vec<Event>)> for SemanticLogRequestBuilde
             uncompressed_size,
         }
     }
+
+    fn max_payload_bytes(&self) -> Option<usize> {
+        Some(MAX_PAYLOAD_BYTES)
+    }
 }
 
 impl<S> LogSink<S>
@@ -377,7 +364,7 @@ where
         if self.schema_enabled {
             let sink = input
                 .batched_partitioned(partitioner, self.batch_settings)
-                .request_builder(
+                .splitting_request_builder(
                     builder_limit,
                     SemanticLogRequestBuilder {
                         default_api_key,
@@ -388,6 +375,7 @@ where
                         compression: self.compression,
                     },
                 )
+                .flat_map(stream::iter)
                 .filter_map(|request| async move {
                     match request {
                         Err(error) => {
@@ -403,7 +391,7 @@ where
         } else {
             let sink = input
                 .batched_partitioned(partitioner, self.batch_settings)
-                .request_builder(
+                .splitting_request_builder(
                     builder_limit,
                     LogRequestBuilder {
                         default_api_key,
@@ -411,6 +399,7 @@ where
                         compression: self.compression,
                     },
                 )
+                .flat_map(stream::iter)
                 .filter_map(|request| async move {
                     match request {
                         Err(error) => {
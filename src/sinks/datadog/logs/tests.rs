@@ -485,7 +485,9 @@ async fn error_is_retriable() {
     }));
 
     // retry-able
-    assert!(retry.is_retriable_error(&DatadogApiError::ServerError));
+    assert!(retry.is_retriable_error(&DatadogApiError::ServerError {
+        status: StatusCode::INTERNAL_SERVER_ERROR
+    }));
     assert!(retry.is_retriable_error(&DatadogApiError::Forbidden));
     assert!(retry.is_retriable_error(&DatadogApiError::HttpError {
         error: HttpError::BuildTlsConnector {
@@ -48,21 +48,58 @@ impl SinkBatchSettings for DatadogMetricsDefaultBatchSettings {
     const TIMEOUT_SECS: f64 = 2.0;
 }
 
+/// The version of the Series API that series metrics are sent to.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, Hash, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SeriesApiVersion {
+    /// Series metrics are sent as JSON to the v1 API.
+    #[derivative(Default)]
+    V1,
+
+    /// Series metrics are sent as Protocol Buffers to the v2 API.
+    ///
+    /// Unlike the v1 API, the v2 API preserves the unit and the origin of the metrics received
+    /// from the Datadog Agent.
+    V2,
+}
+
+/// Where distributions are aggregated.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "lowercase")]
+pub enum DistributionAggregation {
+    /// Distributions are aggregated by Vector into sketches, which are sent to the Sketches API.
+    #[derivative(Default)]
+    Client,
+
+    /// The samples of distributions are sent to the Distribution Points API, and aggregated by
+    /// Datadog.
+    ///
+    /// Aggregated histograms are sent as sketches regardless, as their samples are unknown.
+    Server,
+}
+
 /// Various metric type-specific API types.
 ///
 /// Each of these corresponds to a specific request path when making a request to the agent API.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum DatadogMetricsEndpoint {
-    Series,
+    Series(SeriesApiVersion),
     Sketches,
+    Distributions,
 }
 
 impl DatadogMetricsEndpoint {
     /// Gets the content type associated with the specific encoder for a given metric endpoint.
     pub const fn content_type(self) -> &'static str {
         match self {
-            DatadogMetricsEndpoint::Series => "application/json",
+            DatadogMetricsEndpoint::Series(SeriesApiVersion::V1) => "application/json",
+            DatadogMetricsEndpoint::Series(SeriesApiVersion::V2) => "application/x-protobuf",
             DatadogMetricsEndpoint::Sketches => "application/x-protobuf",
+            DatadogMetricsEndpoint::Distributions => "application/json",
         }
     }
 }
@@ -71,22 +108,29 @@ impl DatadogMetricsEndpoint {
 pub struct DatadogMetricsEndpointConfiguration {
     series_endpoint: Uri,
     sketches_endpoint: Uri,
+    distributions_endpoint: Uri,
 }
 
 impl DatadogMetricsEndpointConfiguration {
     /// Creates a new `DatadogMEtricsEndpointConfiguration`.
-    pub const fn new(series_endpoint: Uri, sketches_endpoint: Uri) -> Self {
+    pub const fn new(
+        series_endpoint: Uri,
+        sketches_endpoint: Uri,
+        distributions_endpoint: Uri,
+    ) -> Self {
         Self {
             series_endpoint,
             sketches_endpoint,
+            distributions_endpoint,
         }
     }
 
     /// Gets the URI for the given Datadog metrics endpoint.
     pub fn get_uri_for_endpoint(&self, endpoint: DatadogMetricsEndpoint) -> Uri {
         match endpoint {
-            DatadogMetricsEndpoint::Series => self.series_endpoint.clone(),
+            DatadogMetricsEndpoint::Series(_) => self.series_endpoint.clone(),
             DatadogMetricsEndpoint::Sketches => self.sketches_endpoint.clone(),
+            DatadogMetricsEndpoint::Distributions => self.distributions_endpoint.clone(),
         }
     }
 }
@@ -125,6 +169,14 @@ pub struct DatadogMetricsConfig {
     #[serde(alias = "api_key")]
     pub default_api_key: SensitiveString,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub series_api_version: SeriesApiVersion,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub distribution_aggregation: DistributionAggregation,
+
     #[configurable(derived)]
     #[serde(default)]
     pub batch: BatchConfig<DatadogMetricsDefaultBatchSettings>,
@@ -191,12 +243,18 @@ impl DatadogMetricsConfig {
         &self,
     ) -> crate::Result<DatadogMetricsEndpointConfiguration> {
         let base_uri = self.get_base_agent_endpoint();
-        let series_endpoint = build_uri(&base_uri, "/api/v1/series")?;
+        let series_path = match self.series_api_version {
+            SeriesApiVersion::V1 => "/api/v1/series",
+            SeriesApiVersion::V2 => "/api/v2/series",
+        };
+        let series_endpoint = build_uri(&base_uri, series_path)?;
         let sketches_endpoint = build_uri(&base_uri, "/api/beta/sketches")?;
+        let distributions_endpoint = build_uri(&base_uri, "/api/v1/distribution_points")?;
 
         Ok(DatadogMetricsEndpointConfiguration::new(
             series_endpoint,
             sketches_endpoint,
+            distributions_endpoint,
         ))
     }
 
@@ -239,9 +297,16 @@ impl DatadogMetricsConfig {
         let request_builder = DatadogMetricsRequestBuilder::new(
             endpoint_configuration,
             self.default_namespace.clone(),
+            self.series_api_version,
         )?;
 
-        let sink = DatadogMetricsSink::new(service, request_builder, batcher_settings);
+        let sink = DatadogMetricsSink::new(
+            service,
+            request_builder,
+            batcher_settings,
+            self.series_api_version,
+            self.distribution_aggregation,
+        );
 
         Ok(VectorSink::from_event_streamsink(sink))
     }
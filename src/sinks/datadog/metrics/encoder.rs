@@ -1,25 +1,30 @@
 use std::{
     cmp,
-    collections::BTreeMap,
     io::{self, Write},
     mem,
     sync::Arc,
 };
 
-use bytes::{BufMut, Bytes};
+use bytes::Bytes;
 use chrono::{DateTime, Utc};
 use prost::Message;
+use serde::Serialize;
 use snafu::{ResultExt, Snafu};
 use vector_core::{
     config::{log_schema, LogSchema},
-    event::{metric::MetricSketch, Metric, MetricValue},
+    event::{
+        metric::{MetricSketch, MetricTags},
+        Metric, MetricValue,
+    },
 };
 
 use super::config::{
-    DatadogMetricsEndpoint, MAXIMUM_PAYLOAD_COMPRESSED_SIZE, MAXIMUM_PAYLOAD_SIZE,
+    DatadogMetricsEndpoint, SeriesApiVersion, MAXIMUM_PAYLOAD_COMPRESSED_SIZE, MAXIMUM_PAYLOAD_SIZE,
 };
 use crate::{
-    common::datadog::{DatadogMetricType, DatadogPoint, DatadogSeriesMetric},
+    common::datadog::{
+        DatadogMetricMetadata, DatadogMetricType, DatadogPoint, DatadogSeriesMetric,
+    },
     sinks::util::{encode_namespace, Compression, Compressor},
 };
 
@@ -32,6 +37,19 @@ mod ddmetric_proto {
     include!(concat!(env!("OUT_DIR"), "/datadog.agentpayload.rs"));
 }
 
+/// A distribution sent to the Distribution Points API, which aggregates its samples.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct DatadogDistributionMetric {
+    metric: String,
+    points: Vec<DatadogPoint<Vec<f64>>>,
+    tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    host: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    device: Option<String>,
+    r#type: &'static str,
+}
+
 #[derive(Debug, Snafu)]
 pub enum CreateError {
     #[snafu(display("Invalid compressed/uncompressed payload size limits were given"))]
@@ -181,7 +199,7 @@ impl DatadogMetricsEncoder {
 
         match self.endpoint {
             // Series metrics are encoded via JSON, in an incremental fashion.
-            DatadogMetricsEndpoint::Series => {
+            DatadogMetricsEndpoint::Series(SeriesApiVersion::V1) => {
                 // A single `Metric` might generate multiple Datadog series metrics.
                 let all_series =
                     generate_series_metrics(&metric, &self.default_namespace, self.log_schema)?;
@@ -200,6 +218,35 @@ impl DatadogMetricsEncoder {
                         .context(JsonEncodingFailedSnafu)?;
                 }
             }
+            // Like sketches, series metrics sent to the v2 API are encoded via Protocol Buffers in
+            // a single operation in `try_encode_pending`, so we only check that they are series.
+            DatadogMetricsEndpoint::Series(SeriesApiVersion::V2) => match metric.value() {
+                MetricValue::Counter { .. }
+                | MetricValue::Gauge { .. }
+                | MetricValue::Set { .. } => {}
+                value => {
+                    return Err(EncoderError::InvalidMetric {
+                        expected: "series",
+                        metric_value: value.as_name(),
+                    })
+                }
+            },
+            // Distributions are encoded via JSON, in an incremental fashion, like series metrics.
+            DatadogMetricsEndpoint::Distributions => {
+                let distribution = generate_distribution_metric(
+                    &metric,
+                    &self.default_namespace,
+                    self.log_schema,
+                )?;
+
+                if !self.state.processed.is_empty()
+                    && write_payload_delimiter(self.endpoint, &mut self.state.buf).is_err()
+                {
+                    return Ok(Some(metric));
+                }
+                serde_json::to_writer(&mut self.state.buf, &distribution)
+                    .context(JsonEncodingFailedSnafu)?;
+            }
             // We can't encode sketches incrementally (yet), so we don't do any encoding here.  We
             // simply store it for later, and in `try_encode_pending`, any such pending metrics will be
             // encoded in a single operation.
@@ -308,14 +355,16 @@ impl DatadogMetricsEncoder {
         // problem for the caller to figure out.  Presently, the only usage of this encoder will
         // naively attempt to split the batch into two and try again.
 
-        // Only go through this if we're targeting the sketch endpoint.
-        if !(matches!(self.endpoint, DatadogMetricsEndpoint::Sketches)) {
-            return Ok(());
-        }
+        // Only go through this if we're targeting the sketch endpoint, or the v2 series endpoint.
+        let write_pending = match self.endpoint {
+            DatadogMetricsEndpoint::Sketches => write_sketches,
+            DatadogMetricsEndpoint::Series(SeriesApiVersion::V2) => write_series_v2,
+            _ => return Ok(()),
+        };
 
-        // Consume of all of the "pending" metrics and try to write them out as sketches.
+        // Consume of all of the "pending" metrics and try to write them out.
         let pending = mem::take(&mut self.state.pending);
-        write_sketches(
+        write_pending(
             &pending,
             &self.default_namespace,
             self.log_schema,
@@ -392,7 +441,7 @@ fn get_namespaced_name(metric: &Metric, default_namespace: &Option<Arc<str>>) ->
     )
 }
 
-fn encode_tags(tags: &BTreeMap<String, String>) -> Vec<String> {
+fn encode_tags(tags: &MetricTags) -> Vec<String> {
     let mut pairs: Vec<_> = tags
         .iter()
         .map(|(name, value)| format!("{}:{}", name, value))
@@ -448,20 +497,20 @@ fn generate_series_metrics(
                 device,
             }]
         }
-        (MetricValue::Set { values }, _) => vec![DatadogSeriesMetric {
+        (MetricValue::Set { values }, maybe_interval_ms) => vec![DatadogSeriesMetric {
             metric: name,
             r#type: DatadogMetricType::Gauge,
-            interval: None,
+            interval: maybe_interval_ms.map(|interval_ms| interval_ms.get() / 1000),
             points: vec![DatadogPoint(ts, values.len() as f64)],
             tags,
             host,
             source_type_name,
             device,
         }],
-        (MetricValue::Gauge { value }, _) => vec![DatadogSeriesMetric {
+        (MetricValue::Gauge { value }, maybe_interval_ms) => vec![DatadogSeriesMetric {
             metric: name,
             r#type: DatadogMetricType::Gauge,
-            interval: None,
+            interval: maybe_interval_ms.map(|interval_ms| interval_ms.get() / 1000),
             points: vec![DatadogPoint(ts, *value)],
             tags,
             host,
@@ -479,15 +528,107 @@ fn generate_series_metrics(
     Ok(results)
 }
 
-fn write_sketches<B>(
+fn generate_distribution_metric(
+    metric: &Metric,
+    default_namespace: &Option<Arc<str>>,
+    log_schema: &'static LogSchema,
+) -> Result<DatadogDistributionMetric, EncoderError> {
+    let samples = match metric.value() {
+        MetricValue::Distribution { samples, .. } => samples,
+        value => {
+            return Err(EncoderError::InvalidMetric {
+                expected: "distribution",
+                metric_value: value.as_name(),
+            })
+        }
+    };
+
+    // The intake has no notion of sample rates, so each sample is repeated as many times as it
+    // was sampled.
+    let values = samples
+        .iter()
+        .flat_map(|sample| std::iter::repeat(sample.value).take(sample.rate as usize))
+        .collect();
+
+    let mut tags = metric.tags().cloned().unwrap_or_default();
+    let host = tags.remove(log_schema.host_key());
+    let device = tags.remove("device");
+
+    Ok(DatadogDistributionMetric {
+        metric: get_namespaced_name(metric, default_namespace),
+        points: vec![DatadogPoint(encode_timestamp(metric.timestamp()), values)],
+        tags: encode_tags(&tags),
+        host,
+        device,
+        r#type: "distribution",
+    })
+}
+
+fn write_series_v2(
+    metrics: &[Metric],
+    default_namespace: &Option<Arc<str>>,
+    log_schema: &'static LogSchema,
+    buf: &mut Vec<u8>,
+) -> Result<(), EncoderError> {
+    use ddmetric_proto::metric_payload::{MetricPoint, MetricSeries, MetricType, Resource};
+
+    let mut series = Vec::new();
+    for metric in metrics {
+        // Unlike the v1 API, the v2 API takes the unit and the origin of the metrics, which we
+        // carry in the event metadata when they come from the Datadog Agent.
+        let dd_metadata = DatadogMetricMetadata::from_event_metadata(metric.metadata());
+        for serie in generate_series_metrics(metric, default_namespace, log_schema)? {
+            let mut tags = serie.tags.unwrap_or_default();
+            if let Some(device) = serie.device {
+                tags.push(format!("device:{}", device));
+            }
+            let r#type = match serie.r#type {
+                DatadogMetricType::Gauge => MetricType::Gauge,
+                DatadogMetricType::Count => MetricType::Count,
+                DatadogMetricType::Rate => MetricType::Rate,
+            };
+
+            series.push(MetricSeries {
+                resources: serie
+                    .host
+                    .map(|host| Resource {
+                        r#type: "host".to_string(),
+                        name: host,
+                    })
+                    .into_iter()
+                    .collect(),
+                metric: serie.metric,
+                tags,
+                points: serie
+                    .points
+                    .into_iter()
+                    .map(|DatadogPoint(timestamp, value)| MetricPoint { value, timestamp })
+                    .collect(),
+                r#type: r#type as i32,
+                unit: dd_metadata.unit.clone().unwrap_or_default(),
+                source_type_name: serie.source_type_name.unwrap_or_default(),
+                interval: serie.interval.map(i64::from).unwrap_or_default(),
+                metadata: dd_metadata.origin.map(|origin| ddmetric_proto::Metadata {
+                    origin: Some(ddmetric_proto::Origin {
+                        origin_product: origin.product,
+                        origin_category: origin.category,
+                        origin_service: origin.service,
+                    }),
+                }),
+            });
+        }
+    }
+
+    let series_payload = ddmetric_proto::MetricPayload { series };
+    series_payload.encode(buf).context(ProtoEncodingFailedSnafu)
+}
+
+fn write_sketches(
     metrics: &[Metric],
     default_namespace: &Option<Arc<str>>,
     log_schema: &'static LogSchema,
-    buf: &mut B,
-) -> Result<(), EncoderError>
-where
-    B: BufMut,
-{
+    buf: &mut Vec<u8>,
+) -> Result<(), EncoderError> {
     let mut sketches = Vec::new();
     for metric in metrics {
         match metric.value() {
@@ -621,7 +762,8 @@ fn write_payload_header(
     writer: &mut dyn io::Write,
 ) -> io::Result<usize> {
     match endpoint {
-        DatadogMetricsEndpoint::Series => writer
+        DatadogMetricsEndpoint::Series(SeriesApiVersion::V1)
+        | DatadogMetricsEndpoint::Distributions => writer
             .write_all(SERIES_PAYLOAD_HEADER)
             .map(|_| SERIES_PAYLOAD_HEADER.len()),
         _ => Ok(0),
@@ -633,7 +775,8 @@ fn write_payload_delimiter(
     writer: &mut dyn io::Write,
 ) -> io::Result<usize> {
     match endpoint {
-        DatadogMetricsEndpoint::Series => writer
+        DatadogMetricsEndpoint::Series(SeriesApiVersion::V1)
+        | DatadogMetricsEndpoint::Distributions => writer
             .write_all(SERIES_PAYLOAD_DELIMITER)
             .map(|_| SERIES_PAYLOAD_DELIMITER.len()),
         _ => Ok(0),
@@ -645,7 +788,8 @@ fn write_payload_footer(
     writer: &mut dyn io::Write,
 ) -> io::Result<usize> {
     match endpoint {
-        DatadogMetricsEndpoint::Series => writer
+        DatadogMetricsEndpoint::Series(SeriesApiVersion::V1)
+        | DatadogMetricsEndpoint::Distributions => writer
             .write_all(SERIES_PAYLOAD_FOOTER)
             .map(|_| SERIES_PAYLOAD_FOOTER.len()),
         _ => Ok(0),
@@ -655,7 +799,6 @@ fn write_payload_footer(
 #[cfg(test)]
 mod tests {
     use std::{
-        collections::BTreeMap,
         io::{self, copy},
         num::NonZeroU32,
     };
@@ -667,19 +810,23 @@ mod tests {
         arbitrary::any, collection::btree_map, num::f64::POSITIVE as ARB_POSITIVE_F64, prop_assert,
         proptest, strategy::Strategy, string::string_regex,
     };
+    use prost::Message;
     use vector_core::{
         config::log_schema,
-        event::{Metric, MetricKind, MetricValue},
+        event::{
+            metric::MetricTags, InternedString, Metric, MetricKind, MetricValue, StatisticKind,
+        },
         metrics::AgentDDSketch,
     };
 
     use super::{
-        encode_tags, encode_timestamp, generate_series_metrics, get_compressor,
+        ddmetric_proto, encode_tags, encode_timestamp, generate_series_metrics, get_compressor,
         max_compression_overhead_len, max_uncompressed_header_len, validate_payload_size_limits,
         write_payload_footer, write_payload_header, DatadogMetricsEncoder, EncoderError,
     };
     use crate::{
-        common::datadog::DatadogMetricType, sinks::datadog::metrics::config::DatadogMetricsEndpoint,
+        common::datadog::{DatadogMetricMetadata, DatadogMetricOrigin, DatadogMetricType},
+        sinks::datadog::metrics::config::{DatadogMetricsEndpoint, SeriesApiVersion},
     };
 
     fn get_simple_counter() -> Metric {
@@ -704,10 +851,16 @@ mod tests {
     fn get_compressed_empty_series_payload() -> Bytes {
         let mut compressor = get_compressor();
 
-        let _ = write_payload_header(DatadogMetricsEndpoint::Series, &mut compressor)
-            .expect("should not fail");
-        let _ = write_payload_footer(DatadogMetricsEndpoint::Series, &mut compressor)
-            .expect("should not fail");
+        let _ = write_payload_header(
+            DatadogMetricsEndpoint::Series(SeriesApiVersion::V1),
+            &mut compressor,
+        )
+        .expect("should not fail");
+        let _ = write_payload_footer(
+            DatadogMetricsEndpoint::Series(SeriesApiVersion::V1),
+            &mut compressor,
+        )
+        .expect("should not fail");
 
         compressor.finish().expect("should not fail").freeze()
     }
@@ -723,11 +876,11 @@ mod tests {
         Utc.ymd(2018, 11, 14).and_hms_nano(8, 9, 10, 11)
     }
 
-    fn tags() -> BTreeMap<String, String> {
+    fn tags() -> MetricTags {
         vec![
-            ("normal_tag".to_owned(), "value".to_owned()),
-            ("true_tag".to_owned(), "true".to_owned()),
-            ("empty_tag".to_owned(), "".to_owned()),
+            ("normal_tag".into(), "value".to_owned()),
+            ("true_tag".into(), "true".to_owned()),
+            ("empty_tag".into(), "".to_owned()),
         ]
         .into_iter()
         .collect()
@@ -760,8 +913,9 @@ mod tests {
 
         // And sketches can't go to the series endpoint.
         // Series metrics can't gbo to the sketches endpoint.
-        let mut series_encoder = DatadogMetricsEncoder::new(DatadogMetricsEndpoint::Series, None)
-            .expect("default payload size limits should be valid");
+        let mut series_encoder =
+            DatadogMetricsEncoder::new(DatadogMetricsEndpoint::Series(SeriesApiVersion::V1), None)
+                .expect("default payload size limits should be valid");
         let sketch_result = series_encoder.try_encode(get_simple_sketch());
         assert!(matches!(
             sketch_result.err(),
@@ -800,8 +954,9 @@ mod tests {
     fn encode_single_series_metric_with_default_limits() {
         // This is a simple test where we ensure that a single metric, with the default limits, can
         // be encoded without hitting any errors.
-        let mut encoder = DatadogMetricsEncoder::new(DatadogMetricsEndpoint::Series, None)
-            .expect("default payload size limits should be valid");
+        let mut encoder =
+            DatadogMetricsEncoder::new(DatadogMetricsEndpoint::Series(SeriesApiVersion::V1), None)
+                .expect("default payload size limits should be valid");
         let counter = get_simple_counter();
         let expected = counter.clone();
 
@@ -852,6 +1007,83 @@ mod tests {
         assert_eq!(70, raw_bytes);
     }
 
+    #[test]
+    fn encode_series_v2_with_unit_and_origin() {
+        let mut gauge = Metric::new(
+            "basic_gauge",
+            MetricKind::Absolute,
+            MetricValue::Gauge { value: 3.14 },
+        )
+        .with_timestamp(Some(ts()))
+        .with_interval_ms(NonZeroU32::new(10000))
+        .with_tags(Some(
+            vec![("host".into(), "a_host".to_owned())]
+                .into_iter()
+                .collect(),
+        ));
+        DatadogMetricMetadata {
+            unit: Some("byte".to_string()),
+            origin: Some(DatadogMetricOrigin {
+                product: 10,
+                category: 11,
+                service: 12,
+            }),
+        }
+        .insert_into(gauge.metadata_mut());
+
+        let mut encoder =
+            DatadogMetricsEncoder::new(DatadogMetricsEndpoint::Series(SeriesApiVersion::V2), None)
+                .expect("default payload size limits should be valid");
+        assert_eq!(encoder.try_encode(gauge).unwrap(), None);
+        let (payload, processed, _) = encoder.finish().unwrap();
+        assert_eq!(processed.len(), 1);
+
+        let payload = decompress_payload(payload).unwrap();
+        let series = ddmetric_proto::MetricPayload::decode(payload)
+            .unwrap()
+            .series;
+        assert_eq!(series.len(), 1);
+        assert_eq!(series[0].metric, "basic_gauge");
+        assert_eq!(series[0].unit, "byte");
+        assert_eq!(series[0].interval, 10);
+        assert_eq!(series[0].resources[0].name, "a_host");
+        assert_eq!(
+            series[0].metadata,
+            Some(ddmetric_proto::Metadata {
+                origin: Some(ddmetric_proto::Origin {
+                    origin_product: 10,
+                    origin_category: 11,
+                    origin_service: 12,
+                }),
+            })
+        );
+    }
+
+    #[test]
+    fn encode_distribution_points() {
+        let distribution = Metric::new(
+            "basic_distribution",
+            MetricKind::Incremental,
+            MetricValue::Distribution {
+                samples: vector_core::samples![1.0 => 2, 2.5 => 1],
+                statistic: StatisticKind::Histogram,
+            },
+        )
+        .with_timestamp(Some(ts()));
+
+        let mut encoder = DatadogMetricsEncoder::new(DatadogMetricsEndpoint::Distributions, None)
+            .expect("default payload size limits should be valid");
+        assert_eq!(encoder.try_encode(distribution).unwrap(), None);
+        let (payload, processed, _) = encoder.finish().unwrap();
+        assert_eq!(processed.len(), 1);
+
+        let payload = decompress_payload(payload).unwrap();
+        assert_eq!(
+            String::from_utf8(payload.to_vec()).unwrap(),
+            r#"{"series":[{"metric":"basic_distribution","points":[[1542182950,[1.0,1.0,2.5]]],"tags":[],"type":"distribution"}]}"#
+        );
+    }
+
     #[test]
     fn payload_size_limits() {
         // Get the maximum length of the header/trailer data.
@@ -886,7 +1118,7 @@ mod tests {
         // uncompressed payload would exceed the limit.
         let header_len = max_uncompressed_header_len();
         let mut encoder = DatadogMetricsEncoder::with_payload_limits(
-            DatadogMetricsEndpoint::Series,
+            DatadogMetricsEndpoint::Series(SeriesApiVersion::V1),
             None,
             header_len + 1,
             usize::MAX,
@@ -923,7 +1155,7 @@ mod tests {
         let uncompressed_limit = 128;
         let compressed_limit = 32;
         let mut encoder = DatadogMetricsEncoder::with_payload_limits(
-            DatadogMetricsEndpoint::Series,
+            DatadogMetricsEndpoint::Series(SeriesApiVersion::V1),
             None,
             uncompressed_limit,
             compressed_limit,
@@ -956,7 +1188,7 @@ mod tests {
         let name = string_regex("[a-zA-Z][a-zA-Z0-9_]{8,96}").expect("regex should not be invalid");
         let value = ARB_POSITIVE_F64;
         let tags = btree_map(
-            any::<u64>().prop_map(|v| v.to_string()),
+            any::<u64>().prop_map(|v| InternedString::from(v.to_string())),
             any::<u64>().prop_map(|v| v.to_string()),
             0..64,
         )
@@ -984,7 +1216,7 @@ mod tests {
             // We check this with targeted unit tests as well but this is some cheap insurance to
             // show that we're hopefully not missing any particular corner cases.
             let result = DatadogMetricsEncoder::with_payload_limits(
-                DatadogMetricsEndpoint::Series,
+                DatadogMetricsEndpoint::Series(SeriesApiVersion::V1),
                 None,
                 uncompressed_limit,
                 compressed_limit,
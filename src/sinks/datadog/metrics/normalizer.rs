@@ -3,10 +3,21 @@ use vector_core::{
     metrics::AgentDDSketch,
 };
 
+use super::config::DistributionAggregation;
 use crate::sinks::util::buffer::metrics::{MetricNormalize, MetricSet};
 
 #[derive(Default)]
-pub(crate) struct DatadogMetricsNormalizer;
+pub(crate) struct DatadogMetricsNormalizer {
+    distribution_aggregation: DistributionAggregation,
+}
+
+impl DatadogMetricsNormalizer {
+    pub(crate) const fn new(distribution_aggregation: DistributionAggregation) -> Self {
+        Self {
+            distribution_aggregation,
+        }
+    }
+}
 
 impl MetricNormalize for DatadogMetricsNormalizer {
     fn normalize(&mut self, state: &mut MetricSet, metric: Metric) -> Option<Metric> {
@@ -17,8 +28,18 @@ impl MetricNormalize for DatadogMetricsNormalizer {
             // system sending an incremental gauge update is kind of doing it wrong, but alas.
             MetricValue::Counter { .. } => state.make_incremental(metric),
             MetricValue::Gauge { .. } => state.make_absolute(metric),
-            // We convert distributions and aggregated histograms to sketches internally. We can't
-            // send absolute sketches to Datadog, though, so we incrementalize them first.
+            // When they are aggregated server-side, the samples of distributions are sent as is,
+            // which only works for incremental distributions.
+            MetricValue::Distribution { .. }
+                if self.distribution_aggregation == DistributionAggregation::Server =>
+            {
+                state
+                    .make_incremental(metric)
+                    .filter(|metric| !metric.value().is_empty())
+            }
+            // We convert distributions and aggregated and exponential histograms to sketches
+            // internally. We can't send absolute sketches to Datadog, though, so we incrementalize
+            // them first.
             MetricValue::Distribution { .. } => state
                 .make_incremental(metric)
                 .filter(|metric| !metric.value().is_empty())
@@ -27,6 +48,10 @@ impl MetricNormalize for DatadogMetricsNormalizer {
                 .make_incremental(metric)
                 .filter(|metric| !metric.value().is_empty())
                 .and_then(|metric| AgentDDSketch::transform_to_sketch(metric).ok()),
+            MetricValue::ExponentialHistogram { .. } => state
+                .make_incremental(metric)
+                .filter(|metric| !metric.value().is_empty())
+                .and_then(|metric| AgentDDSketch::transform_to_sketch(metric).ok()),
             // Sketches cannot be subtracted from one another, so we treat them as implicitly
             // incremental, and just update the metric type.
             MetricValue::Sketch { .. } => Some(metric.into_incremental()),
@@ -48,7 +73,7 @@ mod tests {
         metrics::AgentDDSketch,
     };
 
-    use super::DatadogMetricsNormalizer;
+    use super::{DatadogMetricsNormalizer, DistributionAggregation};
     use crate::sinks::util::buffer::metrics::{MetricNormalize, MetricSet};
 
     fn buckets_from_samples(values: &[f64]) -> (Vec<Bucket>, f64, u64) {
@@ -432,6 +457,21 @@ mod tests {
         run_comparisons(distributions, expected_sketches);
     }
 
+    #[test]
+    fn distribution_aggregated_server_side() {
+        let mut metric_set = MetricSet::default();
+        let mut normalizer = DatadogMetricsNormalizer::new(DistributionAggregation::Server);
+
+        // Distributions are kept as is, rather than converted to sketches.
+        let distribution = get_distribution(generate_f64s(1, 100), MetricKind::Incremental);
+        let result = normalizer.normalize(&mut metric_set, distribution.clone());
+        assert_eq!(result, Some(distribution));
+
+        // Empty distributions are still dropped.
+        let empty = get_distribution(Vec::<f64>::new(), MetricKind::Incremental);
+        assert_eq!(normalizer.normalize(&mut metric_set, empty), None);
+    }
+
     #[test]
     fn mixed_distribution() {
         let samples1 = generate_f64s(1, 100);
@@ -5,7 +5,7 @@ use std::sync::Arc;
 use vector_core::event::{EventFinalizers, Finalizable, Metric};
 
 use super::{
-    config::{DatadogMetricsEndpoint, DatadogMetricsEndpointConfiguration},
+    config::{DatadogMetricsEndpoint, DatadogMetricsEndpointConfiguration, SeriesApiVersion},
     encoder::{CreateError, DatadogMetricsEncoder, EncoderError, FinishError},
     service::DatadogMetricsRequest,
 };
@@ -123,21 +123,27 @@ pub struct DatadogMetricsRequestBuilder {
     endpoint_configuration: DatadogMetricsEndpointConfiguration,
     series_encoder: DatadogMetricsEncoder,
     sketches_encoder: DatadogMetricsEncoder,
+    distributions_encoder: DatadogMetricsEncoder,
 }
 
 impl DatadogMetricsRequestBuilder {
     pub fn new(
         endpoint_configuration: DatadogMetricsEndpointConfiguration,
         default_namespace: Option<String>,
+        series_api_version: SeriesApiVersion,
     ) -> Result<Self, RequestBuilderError> {
         Ok(Self {
             endpoint_configuration,
             series_encoder: DatadogMetricsEncoder::new(
-                DatadogMetricsEndpoint::Series,
+                DatadogMetricsEndpoint::Series(series_api_version),
                 default_namespace.clone(),
             )?,
             sketches_encoder: DatadogMetricsEncoder::new(
                 DatadogMetricsEndpoint::Sketches,
+                default_namespace.clone(),
+            )?,
+            distributions_encoder: DatadogMetricsEncoder::new(
+                DatadogMetricsEndpoint::Distributions,
                 default_namespace,
             )?,
         })
@@ -145,8 +151,9 @@ impl DatadogMetricsRequestBuilder {
 
     fn get_encoder(&mut self, endpoint: DatadogMetricsEndpoint) -> &mut DatadogMetricsEncoder {
         match endpoint {
-            DatadogMetricsEndpoint::Series => &mut self.series_encoder,
+            DatadogMetricsEndpoint::Series(_) => &mut self.series_encoder,
             DatadogMetricsEndpoint::Sketches => &mut self.sketches_encoder,
+            DatadogMetricsEndpoint::Distributions => &mut self.distributions_encoder,
         }
     }
 }
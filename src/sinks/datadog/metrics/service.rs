@@ -34,6 +34,10 @@ impl RetryLogic for DatadogMetricsRetryLogic {
         error.is_retriable()
     }
 
+    fn error_status(&self, error: &Self::Error) -> Option<StatusCode> {
+        error.status()
+    }
+
     fn should_retry_response(&self, response: &Self::Response) -> RetryAction {
         let status = response.status_code;
 
@@ -17,8 +17,10 @@ use vector_core::{
 };
 
 use super::{
-    config::DatadogMetricsEndpoint, normalizer::DatadogMetricsNormalizer,
-    request_builder::DatadogMetricsRequestBuilder, service::DatadogMetricsRequest,
+    config::{DatadogMetricsEndpoint, DistributionAggregation, SeriesApiVersion},
+    normalizer::DatadogMetricsNormalizer,
+    request_builder::DatadogMetricsRequestBuilder,
+    service::DatadogMetricsRequest,
 };
 use crate::{
     internal_events::DatadogMetricsEncodingError,
@@ -33,21 +35,28 @@ use crate::{
 ///
 /// Generally speaking, all "basic" metrics -- counter, gauge, set, aggregated summary-- are sent to
 /// the Series API, while distributions, aggregated histograms, and sketches (hehe) are sent to the
-/// Sketches API.
-struct DatadogMetricsTypePartitioner;
+/// Sketches API. When distributions are aggregated server-side, they are sent to the Distribution
+/// Points API instead.
+struct DatadogMetricsTypePartitioner {
+    series_api_version: SeriesApiVersion,
+}
 
 impl Partitioner for DatadogMetricsTypePartitioner {
     type Item = Metric;
     type Key = (Option<Arc<str>>, DatadogMetricsEndpoint);
 
     fn partition(&self, item: &Self::Item) -> Self::Key {
+        let series = DatadogMetricsEndpoint::Series(self.series_api_version);
         let endpoint = match item.data().value() {
-            MetricValue::Counter { .. } => DatadogMetricsEndpoint::Series,
-            MetricValue::Gauge { .. } => DatadogMetricsEndpoint::Series,
-            MetricValue::Set { .. } => DatadogMetricsEndpoint::Series,
-            MetricValue::Distribution { .. } => DatadogMetricsEndpoint::Sketches,
+            MetricValue::Counter { .. } => series,
+            MetricValue::Gauge { .. } => series,
+            MetricValue::Set { .. } => series,
+            // Distributions only make it this far when they are aggregated server-side, as the
+            // normalizer otherwise converts them to sketches.
+            MetricValue::Distribution { .. } => DatadogMetricsEndpoint::Distributions,
             MetricValue::AggregatedHistogram { .. } => DatadogMetricsEndpoint::Sketches,
-            MetricValue::AggregatedSummary { .. } => DatadogMetricsEndpoint::Series,
+            MetricValue::ExponentialHistogram { .. } => DatadogMetricsEndpoint::Sketches,
+            MetricValue::AggregatedSummary { .. } => series,
             MetricValue::Sketch { .. } => DatadogMetricsEndpoint::Sketches,
         };
         (item.metadata().datadog_api_key(), endpoint)
@@ -58,6 +67,8 @@ pub(crate) struct DatadogMetricsSink<S> {
     service: S,
     request_builder: DatadogMetricsRequestBuilder,
     batch_settings: BatcherSettings,
+    series_api_version: SeriesApiVersion,
+    distribution_aggregation: DistributionAggregation,
 }
 
 impl<S> DatadogMetricsSink<S>
@@ -72,16 +83,24 @@ where
         service: S,
         request_builder: DatadogMetricsRequestBuilder,
         batch_settings: BatcherSettings,
+        series_api_version: SeriesApiVersion,
+        distribution_aggregation: DistributionAggregation,
     ) -> Self {
         DatadogMetricsSink {
             service,
             request_builder,
             batch_settings,
+            series_api_version,
+            distribution_aggregation,
         }
     }
 
     async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
         let mut splitter: MetricSplitter<AggregatedSummarySplitter> = MetricSplitter::default();
+        let normalizer = DatadogMetricsNormalizer::new(self.distribution_aggregation);
+        let partitioner = DatadogMetricsTypePartitioner {
+            series_api_version: self.series_api_version,
+        };
 
         let sink = input
             // Convert `Event` to `Metric` so we don't have to deal with constant conversions.
@@ -93,10 +112,10 @@ where
             // Converts "absolute" metrics to "incremental", and converts distributions and aggregated histograms into
             // sketches so that we can send them in a more DD-native format and thus avoid needing to directly specify
             // what quantiles to generate, etc.
-            .normalized_with_default::<DatadogMetricsNormalizer>()
+            .normalized(normalizer)
             // We batch metrics by their endpoint: series endpoint for counters, gauge, and sets vs sketch endpoint for
             // distributions, aggregated histograms, and sketches.
-            .batched_partitioned(DatadogMetricsTypePartitioner, self.batch_settings)
+            .batched_partitioned(partitioner, self.batch_settings)
             // Aggregate counters with identical timestamps, otherwise identical counters (same
             // series and same timestamp, when rounded to whole seconds) will be dropped in a
             // last-write-wins situation when they hit the DD metrics intake.
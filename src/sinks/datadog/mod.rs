@@ -51,8 +51,8 @@ async fn healthcheck(
 
 #[derive(Debug, Snafu)]
 pub enum DatadogApiError {
-    #[snafu(display("Server responded with an error."))]
-    ServerError,
+    #[snafu(display("Server responded with an error: {}", status))]
+    ServerError { status: StatusCode },
     #[snafu(display("Failed to make HTTP(S) request: {}", error))]
     HttpError { error: HttpError },
     #[snafu(display("Client sent a payload that is too large."))]
@@ -87,7 +87,7 @@ impl DatadogApiError {
                     StatusCode::FORBIDDEN => Err(DatadogApiError::Forbidden),
                     StatusCode::OK | StatusCode::ACCEPTED => Ok(response),
                     StatusCode::PAYLOAD_TOO_LARGE => Err(DatadogApiError::PayloadTooLarge),
-                    _ => Err(DatadogApiError::ServerError),
+                    status => Err(DatadogApiError::ServerError { status }),
                 }
             }
             Err(error) => Err(DatadogApiError::HttpError { error }),
@@ -104,7 +104,18 @@ impl DatadogApiError {
             // https://github.com/vectordotdev/vector/issues/12220
             DatadogApiError::HttpError { error } => error.is_retriable(),
             DatadogApiError::BadRequest | DatadogApiError::PayloadTooLarge => false,
-            DatadogApiError::ServerError | DatadogApiError::Forbidden => true,
+            DatadogApiError::ServerError { .. } | DatadogApiError::Forbidden => true,
+        }
+    }
+
+    /// The HTTP status code the server responded with, if there was a response.
+    pub const fn status(&self) -> Option<StatusCode> {
+        match self {
+            DatadogApiError::ServerError { status } => Some(*status),
+            DatadogApiError::PayloadTooLarge => Some(StatusCode::PAYLOAD_TOO_LARGE),
+            DatadogApiError::BadRequest => Some(StatusCode::BAD_REQUEST),
+            DatadogApiError::Forbidden => Some(StatusCode::FORBIDDEN),
+            DatadogApiError::HttpError { .. } => None,
         }
     }
 }
@@ -41,7 +41,10 @@ use crate::{
     sinks::{
         azure_common::{
             self,
-            config::{AzureBlobMetadata, AzureBlobRequest, AzureBlobRetryLogic},
+            config::{
+                AzureBlobMetadata, AzureBlobRequest, AzureBlobRetryLogic, AzureBlobType,
+                MAX_BLOCK_SIZE,
+            },
             service::AzureBlobService,
             sink::AzureBlobSink,
         },
@@ -308,6 +311,7 @@ impl DatadogArchivesSinkConfig {
                 let client = azure_common::config::build_client(
                     Some(azure_config.connection_string.clone()),
                     None,
+                    &Default::default(),
                     self.bucket.clone(),
                 )?;
                 let svc = self
@@ -446,7 +450,11 @@ impl DatadogArchivesSinkConfig {
         let request_limits = self.request.unwrap_with(&Default::default());
         let service = ServiceBuilder::new()
             .settings(request_limits, AzureBlobRetryLogic)
-            .service(AzureBlobService::new(client));
+            .service(AzureBlobService::new(
+                client,
+                AzureBlobType::Block,
+                MAX_BLOCK_SIZE,
+            ));
 
         let batcher_settings = BatchConfig::<DatadogArchivesDefaultBatchSettings>::default()
             .into_batcher_settings()
@@ -662,6 +670,8 @@ impl RequestBuilder<(String, Vec<Event>)> for DatadogS3RequestBuilder {
                 tags: s3_options.tags,
                 content_encoding: None,
                 content_type: None,
+                checksum_algorithm: None,
+                multipart_upload: Default::default(),
             },
         }
     }
@@ -0,0 +1,299 @@
+use futures::FutureExt;
+use http::{Request, StatusCode};
+use hyper::Body;
+use indoc::indoc;
+use snafu::Snafu;
+use tower::ServiceBuilder;
+use vector_config::configurable_component;
+
+use super::{
+    service::{DorisRetryLogic, DorisService},
+    sink::DorisSink,
+};
+use crate::{
+    codecs::Transformer,
+    config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
+    http::{Auth, HttpClient, MaybeAuth},
+    sinks::{
+        util::{
+            BatchConfig, RealtimeSizeBasedDefaultBatchSettings, ServiceBuilderExt,
+            TowerRequestConfig, UriSerde,
+        },
+        Healthcheck, HealthcheckError, VectorSink,
+    },
+    template::Template,
+    tls::{TlsConfig, TlsSettings},
+};
+
+// Labels are limited to 128 characters, of which the generated suffix takes 33.
+const MAX_LABEL_PREFIX_LENGTH: usize = 95;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("`columns` must be set when the format is `csv`"))]
+    MissingColumns,
+    #[snafu(display(
+        "`label_prefix` must be at most {} letters, digits, `-`, `_` or `:`",
+        MAX_LABEL_PREFIX_LENGTH
+    ))]
+    InvalidLabelPrefix,
+}
+
+/// Configuration for the `doris` sink.
+#[configurable_component(sink("doris"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct DorisConfig {
+    /// The endpoint of the HTTP server of a frontend (FE) of the cluster.
+    ///
+    /// The frontend redirects the loads to the backends, which must be reachable at the addresses
+    /// they are known by in the cluster.
+    pub endpoint: UriSerde,
+
+    /// The database that contains the tables that data will be loaded into.
+    pub database: String,
+
+    /// The table that data will be loaded into.
+    ///
+    /// The table must already exist. The events are batched by table, so that each request loads
+    /// a single one.
+    pub table: Template,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub format: DorisFormat,
+
+    /// The columns of the table the fields of the events are loaded into.
+    ///
+    /// With the `csv` format, these are also the fields of the events written, in order, to each
+    /// row. With the `json` format, the columns default to those of the table, and the fields of
+    /// the events are matched to them by name.
+    #[serde(default)]
+    pub columns: Vec<String>,
+
+    /// The separator between the columns of the rows, with the `csv` format.
+    ///
+    /// Neither the separator nor newlines may appear in the values of the fields.
+    #[serde(default = "default_column_separator")]
+    pub column_separator: String,
+
+    /// The prefix of the labels of the loads.
+    ///
+    /// Each load is labeled with the prefix followed by a unique identifier, which the cluster uses
+    /// to reject the retries of a load that already succeeded.
+    #[serde(default = "default_label_prefix")]
+    pub label_prefix: String,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub encoding: Transformer,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<RealtimeSizeBasedDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    pub auth: Option<Auth>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+/// The format of the data loaded.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DorisFormat {
+    /// Each event is a JSON object, whose fields are loaded into the columns of the same name.
+    #[derivative(Default)]
+    Json,
+
+    /// Each event is a row of delimited values, of the fields named by `columns`.
+    ///
+    /// Missing and null fields are written as `\N`, which is loaded as `NULL`, and timestamps as
+    /// `YYYY-MM-DD HH:MM:SS.ffffff`, in UTC.
+    Csv,
+}
+
+fn default_column_separator() -> String {
+    "\t".to_owned()
+}
+
+fn default_label_prefix() -> String {
+    "vector".to_owned()
+}
+
+impl GenerateConfig for DorisConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(indoc! {r#"
+            endpoint = "http://localhost:8030"
+            database = "my_database"
+            table = "my_table"
+        "#})
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for DorisConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        self.validate()?;
+
+        let batch_settings = self.batch.into_batcher_settings()?;
+        let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
+
+        let endpoint = self.endpoint.with_default_parts();
+        let auth = self.auth.choose_one(&endpoint.auth)?;
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls, cx.proxy())?;
+
+        let healthcheck = healthcheck(client.clone(), endpoint.clone(), auth.clone()).boxed();
+
+        let service = DorisService::new(
+            client,
+            endpoint,
+            self.database.clone(),
+            auth,
+            self.headers(),
+        );
+        let service = ServiceBuilder::new()
+            .settings(request_settings, DorisRetryLogic)
+            .service(service);
+
+        let sink = DorisSink::new(self, batch_settings, service);
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+impl DorisConfig {
+    fn validate(&self) -> Result<(), BuildError> {
+        if self.format == DorisFormat::Csv && self.columns.is_empty() {
+            return Err(BuildError::MissingColumns);
+        }
+        if self.label_prefix.is_empty()
+            || self.label_prefix.len() > MAX_LABEL_PREFIX_LENGTH
+            || !self
+                .label_prefix
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ':'))
+        {
+            return Err(BuildError::InvalidLabelPrefix);
+        }
+        Ok(())
+    }
+
+    /// Gets the headers describing the format of the data to the cluster, sent with each load.
+    fn headers(&self) -> Vec<(&'static str, String)> {
+        let mut headers = match self.format {
+            DorisFormat::Json => vec![
+                ("format", "json".to_owned()),
+                // The events of a load are sent as a single JSON array.
+                ("strip_outer_array", "true".to_owned()),
+            ],
+            DorisFormat::Csv => vec![
+                ("format", "csv".to_owned()),
+                ("column_separator", self.column_separator.clone()),
+            ],
+        };
+        if !self.columns.is_empty() {
+            headers.push(("columns", self.columns.join(",")));
+        }
+        headers
+    }
+}
+
+async fn healthcheck(
+    client: HttpClient,
+    endpoint: UriSerde,
+    auth: Option<Auth>,
+) -> crate::Result<()> {
+    let uri = endpoint.append_path("api/health")?.uri;
+    let mut request = Request::get(uri).body(Body::empty()).unwrap();
+
+    if let Some(auth) = &auth {
+        auth.apply(&mut request);
+    }
+
+    let response = client.send(request).await?;
+
+    match response.status() {
+        StatusCode::OK => Ok(()),
+        status => Err(HealthcheckError::UnexpectedStatus { status }.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<DorisConfig>();
+    }
+
+    #[test]
+    fn csv_requires_columns() {
+        let config: DorisConfig = toml::from_str(indoc! {r#"
+            endpoint = "http://localhost:8030"
+            database = "my_database"
+            table = "my_table"
+            format = "csv"
+        "#})
+        .unwrap();
+        assert!(matches!(config.validate(), Err(BuildError::MissingColumns)));
+
+        let config = DorisConfig {
+            columns: vec!["host".to_owned(), "message".to_owned()],
+            ..config
+        };
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            config.headers(),
+            vec![
+                ("format", "csv".to_owned()),
+                ("column_separator", "\t".to_owned()),
+                ("columns", "host,message".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_label_prefix() {
+        let config: DorisConfig = toml::from_str(indoc! {r#"
+            endpoint = "http://localhost:8030"
+            database = "my_database"
+            table = "my_table"
+            label_prefix = "vector logs"
+        "#})
+        .unwrap();
+        assert!(matches!(
+            config.validate(),
+            Err(BuildError::InvalidLabelPrefix)
+        ));
+    }
+}
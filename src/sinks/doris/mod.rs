@@ -0,0 +1,17 @@
+//! The `doris` sink.
+//!
+//! Loads the events into the tables of an [Apache Doris][doris] or [StarRocks][starrocks] cluster,
+//! through the [Stream Load][stream_load] HTTP protocol of their frontends.
+//!
+//! Each request is given a label, kept by its retries, so that the cluster loads the rows of a
+//! request at most once even when the response to a previous attempt was lost.
+//!
+//! [doris]: https://doris.apache.org/
+//! [starrocks]: https://www.starrocks.io/
+//! [stream_load]: https://doris.apache.org/docs/data-operate/import/import-way/stream-load-manual
+
+mod config;
+mod service;
+mod sink;
+
+pub use self::config::DorisConfig;
@@ -0,0 +1,378 @@
+use std::{
+    convert::Infallible,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use futures::{future::BoxFuture, stream};
+use http::{header, Request, StatusCode, Uri};
+use hyper::Body;
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use serde::Deserialize;
+use snafu::{ResultExt, Snafu};
+use tower::Service;
+use tracing::Instrument;
+use vector_core::{
+    event::{EventFinalizers, EventStatus, Finalizable},
+    internal_event::CountByteSize,
+    stream::DriverResponse,
+};
+
+use crate::{
+    http::{get_http_scheme_from_uri, Auth, HttpClient, HttpError},
+    internal_events::DorisRowsFiltered,
+    sinks::util::{metadata::RequestMetadata, retries::RetryLogic, UriSerde},
+};
+
+/// The number of redirects followed by a load, from the frontend to a backend.
+const MAX_REDIRECTS: usize = 3;
+
+/// The size of the chunks the body of a load is sent in.
+const CHUNK_SIZE: usize = 1024 * 1024;
+
+/// The characters escaped in the path segments, leaving the separators of names as they are.
+const PATH_SEGMENT: &AsciiSet = &NON_ALPHANUMERIC.remove(b'-').remove(b'_').remove(b'.');
+
+#[derive(Debug, Snafu)]
+pub enum DorisError {
+    #[snafu(display("Failed to make HTTP(S) request: {}", source))]
+    Http { source: HttpError },
+
+    #[snafu(display("Failed to read the response: {}", source))]
+    ReadResponse { source: hyper::Error },
+
+    #[snafu(display("Server responded with an error: {}", status))]
+    ServerError { status: StatusCode },
+
+    #[snafu(display("Redirect without a valid location"))]
+    InvalidRedirect,
+
+    #[snafu(display("Too many redirects"))]
+    TooManyRedirects,
+
+    #[snafu(display("Invalid stream load response: {}", source))]
+    InvalidResponse { source: serde_json::Error },
+
+    #[snafu(display("Stream load failed with status {:?}: {}", status, message))]
+    LoadFailed { status: String, message: String },
+
+    #[snafu(display("A stream load with the same label is {}", job_status))]
+    LabelInProgress { job_status: String },
+}
+
+#[derive(Clone, Debug)]
+pub struct DorisRetryLogic;
+
+impl RetryLogic for DorisRetryLogic {
+    type Error = DorisError;
+    type Response = DorisResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            DorisError::ServerError { status } => {
+                *status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+            }
+            // The load may have succeeded, which the label of the retry lets the cluster tell.
+            DorisError::Http { .. }
+            | DorisError::ReadResponse { .. }
+            | DorisError::InvalidResponse { .. }
+            | DorisError::LabelInProgress { .. } => true,
+            DorisError::InvalidRedirect
+            | DorisError::TooManyRedirects
+            | DorisError::LoadFailed { .. } => false,
+        }
+    }
+
+    fn error_status(&self, error: &Self::Error) -> Option<StatusCode> {
+        match error {
+            DorisError::ServerError { status } => Some(*status),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct DorisRequest {
+    pub table: String,
+    pub label: String,
+    pub payload: Bytes,
+    pub finalizers: EventFinalizers,
+    pub metadata: RequestMetadata,
+}
+
+impl Finalizable for DorisRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        self.finalizers.take_finalizers()
+    }
+}
+
+pub struct DorisResponse {
+    protocol: &'static str,
+    metadata: RequestMetadata,
+}
+
+impl DriverResponse for DorisResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> CountByteSize {
+        CountByteSize(
+            self.metadata.event_count(),
+            self.metadata.events_byte_size(),
+        )
+    }
+
+    fn bytes_sent(&self) -> Option<(usize, &str)> {
+        Some((self.metadata.request_encoded_size(), self.protocol))
+    }
+}
+
+/// The result of a stream load, as returned by the backend that ran it.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct StreamLoadResult {
+    status: String,
+    #[serde(default)]
+    message: String,
+    /// The status of the load that already has the label, when the status is
+    /// `Label Already Exists`.
+    #[serde(default)]
+    existing_job_status: Option<String>,
+    #[serde(default)]
+    number_filtered_rows: u64,
+    #[serde(default, rename = "ErrorURL")]
+    error_url: Option<String>,
+}
+
+impl StreamLoadResult {
+    fn check(&self) -> Result<(), DorisError> {
+        match self.status.as_str() {
+            // The data of a load whose publication timed out is committed, and will be visible.
+            "Success" | "Publish Timeout" => Ok(()),
+            // The load is a retry of one that already has the label, and whose response was lost.
+            "Label Already Exists" => match self.existing_job_status.as_deref() {
+                Some("FINISHED" | "VISIBLE" | "COMMITTED") => Ok(()),
+                job_status => Err(DorisError::LabelInProgress {
+                    job_status: job_status.unwrap_or("unknown").to_lowercase(),
+                }),
+            },
+            _ => Err(DorisError::LoadFailed {
+                status: self.status.clone(),
+                message: self.message.clone(),
+            }),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DorisService {
+    client: HttpClient,
+    endpoint: UriSerde,
+    database: String,
+    auth: Option<Auth>,
+    headers: Vec<(&'static str, String)>,
+}
+
+impl DorisService {
+    pub const fn new(
+        client: HttpClient,
+        endpoint: UriSerde,
+        database: String,
+        auth: Option<Auth>,
+        headers: Vec<(&'static str, String)>,
+    ) -> Self {
+        Self {
+            client,
+            endpoint,
+            database,
+            auth,
+            headers,
+        }
+    }
+
+    async fn load(self, request: DorisRequest) -> Result<DorisResponse, DorisError> {
+        let mut uri = load_uri(&self.endpoint.uri, &self.database, &request.table);
+        let protocol = get_http_scheme_from_uri(&uri);
+
+        // The frontend redirects the load to a backend, to which it is sent again.
+        for _ in 0..=MAX_REDIRECTS {
+            let response = self
+                .client
+                .send(self.build_request(uri.clone(), &request))
+                .in_current_span()
+                .await
+                .context(HttpSnafu)?;
+            let status = response.status();
+
+            if status.is_redirection() {
+                uri = redirect_location(&uri, response.headers())?;
+                continue;
+            }
+            if !status.is_success() {
+                return Err(DorisError::ServerError { status });
+            }
+
+            let body = hyper::body::to_bytes(response.into_body())
+                .await
+                .context(ReadResponseSnafu)?;
+            let result: StreamLoadResult =
+                serde_json::from_slice(&body).context(InvalidResponseSnafu)?;
+            result.check()?;
+
+            if result.number_filtered_rows > 0 {
+                emit!(DorisRowsFiltered {
+                    count: result.number_filtered_rows,
+                    table: &request.table,
+                    error_url: result.error_url.as_deref(),
+                });
+            }
+
+            return Ok(DorisResponse {
+                protocol,
+                metadata: request.metadata,
+            });
+        }
+
+        Err(DorisError::TooManyRedirects)
+    }
+
+    fn build_request(&self, uri: Uri, request: &DorisRequest) -> Request<Body> {
+        // The body is sent in chunks, as the stream load protocol expects.
+        let payload = &request.payload;
+        let chunks = (0..payload.len())
+            .step_by(CHUNK_SIZE)
+            .map(|start| {
+                Ok::<_, Infallible>(payload.slice(start..payload.len().min(start + CHUNK_SIZE)))
+            })
+            .collect::<Vec<_>>();
+
+        let mut builder = Request::put(uri)
+            .header(header::EXPECT, "100-continue")
+            .header("label", &request.label);
+        for (name, value) in &self.headers {
+            builder = builder.header(*name, value);
+        }
+        let mut http_request = builder
+            .body(Body::wrap_stream(stream::iter(chunks)))
+            .expect("Building request never fails.");
+
+        // The credentials are sent to the backends too, as they are part of the same cluster.
+        if let Some(auth) = &self.auth {
+            auth.apply(&mut http_request);
+        }
+
+        http_request
+    }
+}
+
+impl Service<DorisRequest> for DorisService {
+    type Response = DorisResponse;
+    type Error = DorisError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: DorisRequest) -> Self::Future {
+        let service = self.clone();
+        Box::pin(service.load(request))
+    }
+}
+
+/// Gets the URI of the stream loads of the table.
+fn load_uri(endpoint: &Uri, database: &str, table: &str) -> Uri {
+    format!(
+        "{}/api/{}/{}/_stream_load",
+        endpoint.to_string().trim_end_matches('/'),
+        utf8_percent_encode(database, PATH_SEGMENT),
+        utf8_percent_encode(table, PATH_SEGMENT),
+    )
+    .parse()
+    .expect("percent-encoded path is a valid URI")
+}
+
+/// Gets the location a load is redirected to, relative to the URI it was sent to.
+fn redirect_location(uri: &Uri, headers: &http::HeaderMap) -> Result<Uri, DorisError> {
+    let location: Uri = headers
+        .get(header::LOCATION)
+        .and_then(|location| location.to_str().ok())
+        .and_then(|location| location.parse().ok())
+        .ok_or(DorisError::InvalidRedirect)?;
+    if location.authority().is_some() {
+        return Ok(location);
+    }
+
+    let mut parts = uri.clone().into_parts();
+    parts.path_and_query = location.path_and_query().cloned();
+    Uri::from_parts(parts).map_err(|_| DorisError::InvalidRedirect)
+}
+
+#[cfg(test)]
+mod tests {
+    use http::HeaderMap;
+
+    use super::*;
+
+    #[test]
+    fn builds_load_uri() {
+        let endpoint: Uri = "http://fe:8030/".parse().unwrap();
+        assert_eq!(
+            load_uri(&endpoint, "logs", "app events").to_string(),
+            "http://fe:8030/api/logs/app%20events/_stream_load"
+        );
+    }
+
+    #[test]
+    fn follows_redirect_location() {
+        let uri: Uri = "http://fe:8030/api/logs/app/_stream_load".parse().unwrap();
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::LOCATION,
+            "http://be:8040/api/logs/app/_stream_load".parse().unwrap(),
+        );
+        assert_eq!(
+            redirect_location(&uri, &headers).unwrap().to_string(),
+            "http://be:8040/api/logs/app/_stream_load"
+        );
+
+        headers.insert(header::LOCATION, "/api/other/_stream_load".parse().unwrap());
+        assert_eq!(
+            redirect_location(&uri, &headers).unwrap().to_string(),
+            "http://fe:8030/api/other/_stream_load"
+        );
+
+        assert!(matches!(
+            redirect_location(&uri, &HeaderMap::new()),
+            Err(DorisError::InvalidRedirect)
+        ));
+    }
+
+    fn check(result: &str) -> Result<(), DorisError> {
+        serde_json::from_str::<StreamLoadResult>(result)
+            .unwrap()
+            .check()
+    }
+
+    #[test]
+    fn checks_load_results() {
+        assert!(check(r#"{"Status": "Success", "Message": "OK"}"#).is_ok());
+        assert!(check(r#"{"Status": "Publish Timeout", "Message": ""}"#).is_ok());
+        assert!(
+            check(r#"{"Status": "Label Already Exists", "ExistingJobStatus": "FINISHED"}"#).is_ok()
+        );
+
+        let running =
+            check(r#"{"Status": "Label Already Exists", "ExistingJobStatus": "RUNNING"}"#)
+                .unwrap_err();
+        assert!(matches!(running, DorisError::LabelInProgress { .. }));
+        assert!(DorisRetryLogic.is_retriable_error(&running));
+
+        let failed =
+            check(r#"{"Status": "Fail", "Message": "too many filtered rows"}"#).unwrap_err();
+        assert!(matches!(failed, DorisError::LoadFailed { .. }));
+        assert!(!DorisRetryLogic.is_retriable_error(&failed));
+    }
+}
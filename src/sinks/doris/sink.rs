@@ -0,0 +1,283 @@
+use std::{io, num::NonZeroUsize};
+
+use bytes::Bytes;
+use futures::{stream::BoxStream, StreamExt};
+use uuid::Uuid;
+use vector_core::{
+    event::{Event, EventFinalizers, Finalizable, LogEvent, Value},
+    partition::Partitioner,
+    sink::StreamSink,
+    stream::BatcherSettings,
+};
+
+use super::{
+    config::{DorisConfig, DorisFormat},
+    service::{DorisRequest, DorisRetryLogic, DorisService},
+};
+use crate::{
+    codecs::Transformer,
+    internal_events::{SinkRequestBuildError, TemplateRenderingError},
+    sinks::util::{
+        encoding::{write_all, Encoder},
+        metadata::{RequestMetadata, RequestMetadataBuilder},
+        request_builder::EncodeResult,
+        service::Svc,
+        Compression, RequestBuilder, SinkBuilderExt,
+    },
+    template::Template,
+};
+
+/// Partitions the events by the table they are loaded into.
+struct TablePartitioner(Template);
+
+impl Partitioner for TablePartitioner {
+    type Item = Event;
+    type Key = Option<String>;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        self.0
+            .render_string(item)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("table"),
+                    drop_event: true,
+                });
+            })
+            .ok()
+    }
+}
+
+/// Encodes the events of a load in the format announced by its headers.
+#[derive(Clone)]
+struct StreamLoadEncoder {
+    transformer: Transformer,
+    format: DorisFormat,
+    columns: Vec<String>,
+    column_separator: String,
+}
+
+impl Encoder<Vec<Event>> for StreamLoadEncoder {
+    fn encode_input(&self, events: Vec<Event>, writer: &mut dyn io::Write) -> io::Result<usize> {
+        let n_events = events.len();
+        let mut body = Vec::new();
+        match self.format {
+            DorisFormat::Json => {
+                body.push(b'[');
+                for (i, mut event) in events.into_iter().enumerate() {
+                    if i > 0 {
+                        body.push(b',');
+                    }
+                    self.transformer.transform(&mut event);
+                    serde_json::to_writer(&mut body, event.as_log())?;
+                }
+                body.push(b']');
+            }
+            DorisFormat::Csv => {
+                for mut event in events {
+                    self.transformer.transform(&mut event);
+                    self.write_row(event.as_log(), &mut body);
+                }
+            }
+        }
+
+        write_all(writer, n_events, &body)?;
+        Ok(body.len())
+    }
+}
+
+impl StreamLoadEncoder {
+    fn write_row(&self, log: &LogEvent, body: &mut Vec<u8>) {
+        for (i, column) in self.columns.iter().enumerate() {
+            if i > 0 {
+                body.extend_from_slice(self.column_separator.as_bytes());
+            }
+            match log.get(column.as_str()) {
+                None | Some(Value::Null) => body.extend_from_slice(b"\\N"),
+                Some(Value::Bytes(bytes)) => body.extend_from_slice(bytes),
+                Some(Value::Timestamp(timestamp)) => body.extend_from_slice(
+                    timestamp
+                        .format("%Y-%m-%d %H:%M:%S%.6f")
+                        .to_string()
+                        .as_bytes(),
+                ),
+                Some(value @ (Value::Object(_) | Value::Array(_))) => {
+                    serde_json::to_writer(&mut *body, value).expect("values are valid JSON");
+                }
+                Some(value) => body.extend_from_slice(value.to_string_lossy().as_bytes()),
+            }
+        }
+        body.push(b'\n');
+    }
+}
+
+#[derive(Clone)]
+struct DorisRequestBuilder {
+    encoder: StreamLoadEncoder,
+    label_prefix: String,
+}
+
+impl RequestBuilder<(String, Vec<Event>)> for DorisRequestBuilder {
+    type Metadata = (String, EventFinalizers, RequestMetadataBuilder);
+    type Events = Vec<Event>;
+    type Encoder = StreamLoadEncoder;
+    type Payload = Bytes;
+    type Request = DorisRequest;
+    type Error = io::Error;
+
+    fn compression(&self) -> Compression {
+        Compression::None
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        &self.encoder
+    }
+
+    fn split_input(&self, input: (String, Vec<Event>)) -> (Self::Metadata, Self::Events) {
+        let (table, mut events) = input;
+        let metadata_builder = RequestMetadata::builder(&events);
+        let finalizers = events.take_finalizers();
+
+        ((table, finalizers, metadata_builder), events)
+    }
+
+    fn build_request(
+        &self,
+        metadata: Self::Metadata,
+        payload: EncodeResult<Self::Payload>,
+    ) -> Self::Request {
+        let (table, finalizers, metadata_builder) = metadata;
+        let metadata = metadata_builder.build(&payload);
+
+        DorisRequest {
+            table,
+            // The label is chosen once per request, rather than per attempt, so that the retries
+            // of a load that succeeded are rejected by the cluster.
+            label: format!("{}_{}", self.label_prefix, Uuid::new_v4().simple()),
+            payload: payload.into_payload(),
+            finalizers,
+            metadata,
+        }
+    }
+}
+
+pub struct DorisSink {
+    partitioner: TablePartitioner,
+    request_builder: DorisRequestBuilder,
+    batch_settings: BatcherSettings,
+    service: Svc<DorisService, DorisRetryLogic>,
+}
+
+impl DorisSink {
+    pub(super) fn new(
+        config: &DorisConfig,
+        batch_settings: BatcherSettings,
+        service: Svc<DorisService, DorisRetryLogic>,
+    ) -> Self {
+        Self {
+            partitioner: TablePartitioner(config.table.clone()),
+            request_builder: DorisRequestBuilder {
+                encoder: StreamLoadEncoder {
+                    transformer: config.encoding.clone(),
+                    format: config.format,
+                    columns: config.columns.clone(),
+                    column_separator: config.column_separator.clone(),
+                },
+                label_prefix: config.label_prefix.clone(),
+            },
+            batch_settings,
+            service,
+        }
+    }
+
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let sink = input
+            .batched_partitioned(self.partitioner, self.batch_settings)
+            .filter_map(|(table, batch)| async move {
+                // A `TemplateRenderingError` is already emitted for the events whose table couldn't
+                // be rendered.
+                table.map(move |table| (table, batch))
+            })
+            .request_builder(NonZeroUsize::new(64), self.request_builder)
+            .filter_map(|request| async move {
+                match request {
+                    Err(error) => {
+                        emit!(SinkRequestBuildError { error });
+                        None
+                    }
+                    Ok(req) => Some(req),
+                }
+            })
+            .into_driver(self.service);
+
+        sink.run().await
+    }
+}
+
+#[async_trait::async_trait]
+impl StreamSink<Event> for DorisSink {
+    async fn run(mut self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    fn encode(encoder: &StreamLoadEncoder, events: Vec<Event>) -> String {
+        let mut body = Vec::new();
+        encoder.encode_input(events, &mut body).unwrap();
+        String::from_utf8(body).unwrap()
+    }
+
+    fn events() -> Vec<Event> {
+        let mut first = LogEvent::default();
+        first.insert("message", "first");
+        first.insert("host", "a");
+        first.insert("status", 200);
+        first.insert(
+            "timestamp",
+            Utc.ymd(2022, 10, 1).and_hms_milli(12, 30, 5, 250),
+        );
+        let mut second = LogEvent::default();
+        second.insert("message", "second");
+        second.insert("tags", vec!["x", "y"]);
+        vec![first.into(), second.into()]
+    }
+
+    #[test]
+    fn encodes_json_array() {
+        let encoder = StreamLoadEncoder {
+            transformer: Transformer::new(None, Some(vec!["timestamp".into()]), None).unwrap(),
+            format: DorisFormat::Json,
+            columns: Vec::new(),
+            column_separator: "\t".to_owned(),
+        };
+
+        assert_eq!(
+            encode(&encoder, events()),
+            r#"[{"host":"a","message":"first","status":200},{"message":"second","tags":["x","y"]}]"#
+        );
+    }
+
+    #[test]
+    fn encodes_csv_rows() {
+        let encoder = StreamLoadEncoder {
+            transformer: Transformer::default(),
+            format: DorisFormat::Csv,
+            columns: ["timestamp", "host", "status", "tags", "message"]
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            column_separator: "|".to_owned(),
+        };
+
+        assert_eq!(
+            encode(&encoder, events()),
+            "2022-10-01 12:30:05.250000|a|200|\\N|first\n\\N|\\N|\\N|[\"x\",\"y\"]|second\n"
+        );
+    }
+}
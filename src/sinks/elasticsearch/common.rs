@@ -112,7 +112,7 @@ impl ElasticsearchCommon {
 
         let tls_settings = TlsSettings::from_options(&config.tls)?;
         let config = config.clone();
-        let request = config.request;
+        let request = config.request.clone();
 
         let metric_config = config.metrics.clone().unwrap_or_default();
         let metric_to_log = MetricToLog::new(
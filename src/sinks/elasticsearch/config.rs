@@ -378,6 +378,9 @@ impl DataStreamConfig {
 #[async_trait::async_trait]
 impl SinkConfig for ElasticsearchConfig {
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        self.compression
+            .check_supported(Compression::FLATE_ALGORITHMS)?;
+
         let commons = ElasticsearchCommon::parse_many(self).await?;
         let common = commons[0].clone();
 
@@ -0,0 +1,234 @@
+use futures::FutureExt;
+use indoc::indoc;
+use snafu::Snafu;
+use tower::ServiceBuilder;
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+
+use super::{
+    connection::{ClientSecurity, FluentConnector},
+    service::{FluentRetryLogic, FluentService},
+    sink::FluentSink,
+};
+use crate::{
+    codecs::Transformer,
+    config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
+    sinks::{
+        util::{BatchConfig, ServiceBuilderExt, SinkBatchSettings, TowerRequestConfig},
+        Healthcheck, VectorSink,
+    },
+    tcp::TcpKeepaliveConfig,
+    template::Template,
+    tls::{MaybeTlsSettings, TlsEnableableConfig},
+};
+
+// Fluentd limits the chunks of its buffers to 8MB by default, which the chunks forwarded to it are
+// best kept under.
+const MAX_CHUNK_SIZE: usize = 8_000_000;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Missing host in address field"))]
+    MissingHost,
+    #[snafu(display("Missing port in address field"))]
+    MissingPort,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FluentDefaultBatchSettings;
+
+impl SinkBatchSettings for FluentDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = None;
+    const MAX_BYTES: Option<usize> = Some(MAX_CHUNK_SIZE);
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+/// Configuration for the `fluent` sink.
+#[configurable_component(sink("fluent"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct FluentSinkConfig {
+    /// The address of the Fluentd, or Fluent Bit, server to forward the events to.
+    ///
+    /// The address _must_ include a port.
+    pub address: String,
+
+    /// The tag of the events.
+    ///
+    /// The events are batched by tag, so that each chunk forwarded holds a single one.
+    pub tag: Template,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub compression: FluentCompression,
+
+    /// Whether to wait for the server to acknowledge each chunk, before considering it forwarded.
+    ///
+    /// The chunks that aren't acknowledged before the request times out are forwarded again.
+    #[serde(default = "crate::serde::default_true")]
+    pub require_ack_response: bool,
+
+    #[configurable(derived)]
+    pub security: Option<FluentSinkSecurityConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub encoding: Transformer,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<FluentDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    pub keepalive: Option<TcpKeepaliveConfig>,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+/// The compression of the chunks forwarded.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum FluentCompression {
+    /// The chunks are not compressed.
+    #[derivative(Default)]
+    None,
+
+    /// The entries of the chunks are compressed with gzip, as Fluentd does with `compress gzip`.
+    Gzip,
+}
+
+/// Authentication with the server.
+///
+/// When set, the sink authenticates with the shared key, and optionally a username and password,
+/// with the handshake of the forward protocol on each connection.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct FluentSinkSecurityConfig {
+    /// The hostname the sink identifies itself with to the server.
+    ///
+    /// Defaults to the hostname of the machine.
+    pub self_hostname: Option<String>,
+
+    /// The key shared with the server.
+    pub shared_key: SensitiveString,
+
+    /// The username to authenticate with, when the server requires it.
+    pub username: Option<String>,
+
+    /// The password to authenticate with, when the server requires it.
+    pub password: Option<SensitiveString>,
+}
+
+impl GenerateConfig for FluentSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(indoc! {r#"
+            address = "127.0.0.1:24224"
+            tag = "vector.{{ service }}"
+        "#})
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for FluentSinkConfig {
+    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let batch_settings = self
+            .batch
+            .validate()?
+            .limit_max_bytes(MAX_CHUNK_SIZE)?
+            .into_batcher_settings()?;
+        let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
+
+        let connector = self.build_connector()?;
+        let healthcheck = connector.clone().healthcheck().boxed();
+
+        let service = FluentService::new(connector, self.require_ack_response);
+        let service = ServiceBuilder::new()
+            .settings(request_settings, FluentRetryLogic)
+            .service(service);
+
+        let sink = FluentSink::new(self, batch_settings, service);
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+impl FluentSinkConfig {
+    fn build_connector(&self) -> crate::Result<FluentConnector> {
+        let uri = self.address.parse::<http::Uri>()?;
+        let host = uri.host().ok_or(BuildError::MissingHost)?.to_string();
+        let port = uri.port_u16().ok_or(BuildError::MissingPort)?;
+        let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
+
+        let security = match &self.security {
+            Some(security) => Some(ClientSecurity {
+                self_hostname: match &security.self_hostname {
+                    Some(hostname) => hostname.clone(),
+                    None => crate::get_hostname()?,
+                },
+                shared_key: security.shared_key.clone(),
+                username: security.username.clone().unwrap_or_default(),
+                password: security.password.clone().unwrap_or_default(),
+            }),
+            None => None,
+        };
+
+        Ok(FluentConnector::new(
+            host,
+            port,
+            self.keepalive,
+            tls,
+            security,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<FluentSinkConfig>();
+    }
+
+    #[test]
+    fn requires_port() {
+        let config: FluentSinkConfig = toml::from_str(indoc! {r#"
+            address = "fluentd.local"
+            tag = "vector"
+        "#})
+        .unwrap();
+
+        assert!(config.require_ack_response);
+        assert_eq!(config.compression, FluentCompression::None);
+        assert!(config.build_connector().is_err());
+    }
+}
@@ -0,0 +1,299 @@
+use std::net::SocketAddr;
+
+use bytes::BytesMut;
+use rmpv::Value;
+use snafu::{ResultExt, Snafu};
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    net::TcpStream,
+};
+use vector_common::sensitive_string::SensitiveString;
+
+use crate::{
+    dns,
+    fluent::{digest, read_message, value_bytes, write_message, ProtocolError},
+    internal_events::TcpSocketConnectionEstablished,
+    tcp::TcpKeepaliveConfig,
+    tls::{MaybeTlsSettings, MaybeTlsStream, TlsError},
+};
+
+/// The largest handshake message accepted from the server.
+const MAX_HANDSHAKE_SIZE: usize = 16 * 1024;
+
+#[derive(Debug, Snafu)]
+pub enum ConnectError {
+    #[snafu(display("Unable to resolve DNS: {}", source))]
+    Dns { source: dns::DnsError },
+
+    #[snafu(display("No addresses returned."))]
+    NoAddresses,
+
+    #[snafu(display("Connect error: {}", source))]
+    Connect { source: TlsError },
+
+    #[snafu(display("Failed to exchange the handshake messages: {}", source))]
+    Handshake { source: ProtocolError },
+
+    #[snafu(display("Expected a {} message from the server.", expected))]
+    UnexpectedMessage { expected: &'static str },
+
+    #[snafu(display("Server refused the authentication: {}", reason))]
+    Unauthorized { reason: String },
+
+    #[snafu(display("Server failed to prove it knows the shared key."))]
+    SharedKeyMismatch,
+}
+
+/// The credentials the sink authenticates with.
+#[derive(Clone, Debug)]
+pub(super) struct ClientSecurity {
+    pub(super) self_hostname: String,
+    pub(super) shared_key: SensitiveString,
+    pub(super) username: String,
+    pub(super) password: SensitiveString,
+}
+
+/// Opens the connections to the server, authenticating them when needed.
+#[derive(Clone, Debug)]
+pub(super) struct FluentConnector {
+    host: String,
+    port: u16,
+    keepalive: Option<TcpKeepaliveConfig>,
+    tls: MaybeTlsSettings,
+    security: Option<ClientSecurity>,
+}
+
+impl FluentConnector {
+    pub(super) const fn new(
+        host: String,
+        port: u16,
+        keepalive: Option<TcpKeepaliveConfig>,
+        tls: MaybeTlsSettings,
+        security: Option<ClientSecurity>,
+    ) -> Self {
+        Self {
+            host,
+            port,
+            keepalive,
+            tls,
+            security,
+        }
+    }
+
+    pub(super) async fn connect(&self) -> Result<MaybeTlsStream<TcpStream>, ConnectError> {
+        let ip = dns::Resolver
+            .lookup_ip(self.host.clone())
+            .await
+            .context(DnsSnafu)?
+            .next()
+            .ok_or(ConnectError::NoAddresses)?;
+
+        let addr = SocketAddr::new(ip, self.port);
+        let mut socket = self
+            .tls
+            .connect(&self.host, &addr)
+            .await
+            .context(ConnectSnafu)?;
+        if let Some(keepalive) = self.keepalive {
+            if let Err(error) = socket.set_keepalive(keepalive) {
+                warn!(message = "Failed configuring TCP keepalive.", %error);
+            }
+        }
+
+        if let Some(security) = &self.security {
+            handshake(&mut socket, security).await?;
+        }
+
+        emit!(TcpSocketConnectionEstablished {
+            peer_addr: socket.peer_addr().ok(),
+        });
+        Ok(socket)
+    }
+
+    pub(super) async fn healthcheck(self) -> crate::Result<()> {
+        self.connect().await.map(|_| ()).map_err(Into::into)
+    }
+}
+
+/// Replies to the `HELO` message of the server with a `PING` message, and checks the `PONG`
+/// message the server answers with.
+///
+/// See <https://github.com/fluent/fluentd/wiki/Forward-Protocol-Specification-v1#handshake-messages>.
+async fn handshake<S>(socket: &mut S, security: &ClientSecurity) -> Result<(), ConnectError>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let mut buf = BytesMut::new();
+    let helo = read_message(socket, &mut buf, MAX_HANDSHAKE_SIZE)
+        .await
+        .context(HandshakeSnafu)?;
+    let (nonce, auth_salt) =
+        parse_helo(&helo).ok_or(ConnectError::UnexpectedMessage { expected: "HELO" })?;
+
+    let shared_key = security.shared_key.inner().as_bytes();
+    let shared_key_salt = hex::encode(rand::random::<[u8; 16]>());
+    // The username and password are only sent when the server asks for them.
+    let (username, password_digest) = if auth_salt.is_empty() {
+        ("", String::new())
+    } else {
+        (
+            security.username.as_str(),
+            digest(&[
+                auth_salt,
+                security.username.as_bytes(),
+                security.password.inner().as_bytes(),
+            ]),
+        )
+    };
+    let ping = Value::Array(vec![
+        "PING".into(),
+        security.self_hostname.as_str().into(),
+        shared_key_salt.as_str().into(),
+        digest(&[
+            shared_key_salt.as_bytes(),
+            security.self_hostname.as_bytes(),
+            nonce,
+            shared_key,
+        ])
+        .into(),
+        username.into(),
+        password_digest.into(),
+    ]);
+    write_message(socket, &ping).await.context(HandshakeSnafu)?;
+
+    let pong = read_message(socket, &mut buf, MAX_HANDSHAKE_SIZE)
+        .await
+        .context(HandshakeSnafu)?;
+    let pong = pong.as_array().map(Vec::as_slice);
+    match pong {
+        Some([kind, ok, reason, hostname, server_digest]) if kind.as_str() == Some("PONG") => {
+            if ok.as_bool() != Some(true) {
+                return Err(ConnectError::Unauthorized {
+                    reason: value_bytes(reason)
+                        .map(|reason| String::from_utf8_lossy(reason).into_owned())
+                        .unwrap_or_default(),
+                });
+            }
+
+            let hostname = value_bytes(hostname).unwrap_or_default();
+            let expected = digest(&[shared_key_salt.as_bytes(), hostname, nonce, shared_key]);
+            if value_bytes(server_digest) != Some(expected.as_bytes()) {
+                return Err(ConnectError::SharedKeyMismatch);
+            }
+            Ok(())
+        }
+        _ => Err(ConnectError::UnexpectedMessage { expected: "PONG" }),
+    }
+}
+
+/// Gets the nonce and the salt of the authentication from the `HELO` message.
+///
+/// The salt is empty when the server doesn't authenticate the users.
+fn parse_helo(helo: &Value) -> Option<(&[u8], &[u8])> {
+    match helo.as_array()?.as_slice() {
+        [kind, options] if kind.as_str() == Some("HELO") => {
+            let option = |name: &str| {
+                options
+                    .as_map()?
+                    .iter()
+                    .find(|(key, _)| key.as_str() == Some(name))
+                    .and_then(|(_, value)| value_bytes(value))
+            };
+            Some((option("nonce")?, option("auth").unwrap_or_default()))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::duplex;
+
+    use super::*;
+
+    fn security() -> ClientSecurity {
+        ClientSecurity {
+            self_hostname: "vector.local".to_owned(),
+            shared_key: "secret".to_owned().into(),
+            username: "alice".to_owned(),
+            password: "hunter2".to_owned().into(),
+        }
+    }
+
+    /// Runs the handshake against a server that answers with the given shared key, and asks for
+    /// a username and password when `auth_salt` is set.
+    ///
+    /// Returns the result of the handshake, and the `PING` message sent by the client.
+    async fn run_handshake(
+        server_key: &'static str,
+        auth_salt: &'static [u8],
+    ) -> (Result<(), ConnectError>, Vec<Value>) {
+        let (mut client, mut server) = duplex(4096);
+        let client = tokio::spawn(async move { handshake(&mut client, &security()).await });
+
+        let nonce = b"nonce".to_vec();
+        let helo = Value::Array(vec![
+            "HELO".into(),
+            Value::Map(vec![
+                ("nonce".into(), Value::Binary(nonce.clone())),
+                ("auth".into(), Value::Binary(auth_salt.to_vec())),
+                ("keepalive".into(), true.into()),
+            ]),
+        ]);
+        write_message(&mut server, &helo).await.unwrap();
+
+        let mut buf = BytesMut::new();
+        let ping = read_message(&mut server, &mut buf, MAX_HANDSHAKE_SIZE)
+            .await
+            .unwrap();
+        let ping = ping.as_array().unwrap().clone();
+
+        let salt = value_bytes(&ping[2]).unwrap();
+        let pong = Value::Array(vec![
+            "PONG".into(),
+            true.into(),
+            "".into(),
+            "fluentd.local".into(),
+            digest(&[salt, b"fluentd.local", &nonce, server_key.as_bytes()]).into(),
+        ]);
+        write_message(&mut server, &pong).await.unwrap();
+
+        (client.await.unwrap(), ping)
+    }
+
+    #[tokio::test]
+    async fn authenticates_with_shared_key() {
+        let (result, ping) = run_handshake("secret", b"").await;
+
+        assert!(result.is_ok());
+        assert_eq!(ping[0].as_str(), Some("PING"));
+        assert_eq!(ping[1].as_str(), Some("vector.local"));
+        let salt = value_bytes(&ping[2]).unwrap();
+        assert_eq!(
+            ping[3].as_str().unwrap(),
+            digest(&[salt, b"vector.local", b"nonce", b"secret"])
+        );
+        // The username and password are left out when the server doesn't ask for them.
+        assert_eq!(ping[4].as_str(), Some(""));
+        assert_eq!(ping[5].as_str(), Some(""));
+    }
+
+    #[tokio::test]
+    async fn authenticates_with_password() {
+        let (result, ping) = run_handshake("secret", b"salt").await;
+
+        assert!(result.is_ok());
+        assert_eq!(ping[4].as_str(), Some("alice"));
+        assert_eq!(
+            ping[5].as_str().unwrap(),
+            digest(&[b"salt", b"alice", b"hunter2"])
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_server_without_shared_key() {
+        let (result, _) = run_handshake("wrong", b"").await;
+
+        assert!(matches!(result, Err(ConnectError::SharedKeyMismatch)));
+    }
+}
@@ -0,0 +1,15 @@
+//! The `fluent` sink.
+//!
+//! Forwards the events to [Fluentd][fluentd], or [Fluent Bit][fluent_bit], servers with the
+//! [forward protocol][forward_protocol], as `PackedForward` messages that the servers acknowledge.
+//!
+//! [fluentd]: https://www.fluentd.org/
+//! [fluent_bit]: https://fluentbit.io/
+//! [forward_protocol]: https://github.com/fluent/fluentd/wiki/Forward-Protocol-Specification-v1
+
+mod config;
+mod connection;
+mod service;
+mod sink;
+
+pub use self::config::FluentSinkConfig;
@@ -0,0 +1,190 @@
+use std::{
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+use bytes::{Bytes, BytesMut};
+use futures::{future::BoxFuture, task::noop_waker_ref};
+use snafu::{ResultExt, Snafu};
+use tokio::{
+    io::{AsyncRead, AsyncWriteExt, ReadBuf},
+    net::TcpStream,
+};
+use tower::Service;
+use vector_core::{
+    event::{EventFinalizers, EventStatus, Finalizable},
+    internal_event::CountByteSize,
+    stream::DriverResponse,
+};
+
+use super::connection::{ConnectError, FluentConnector};
+use crate::{
+    fluent::{read_message, ProtocolError},
+    sinks::util::{metadata::RequestMetadata, retries::RetryLogic},
+    tls::MaybeTlsStream,
+};
+
+/// The largest acknowledgement accepted from the server, which only holds the chunk ID.
+const MAX_ACK_SIZE: usize = 1024;
+
+#[derive(Debug, Snafu)]
+pub enum FluentError {
+    #[snafu(display("Failed to connect: {}", source))]
+    Connect { source: ConnectError },
+
+    #[snafu(display("Failed to send the chunk: {}", source))]
+    Send { source: std::io::Error },
+
+    #[snafu(display("Failed to read the acknowledgement: {}", source))]
+    ReadAck { source: ProtocolError },
+
+    #[snafu(display("Server acknowledged another chunk than {}.", chunk))]
+    AckMismatch { chunk: String },
+}
+
+#[derive(Clone, Debug)]
+pub struct FluentRetryLogic;
+
+impl RetryLogic for FluentRetryLogic {
+    type Error = FluentError;
+    type Response = FluentResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            // Retrying with the same credentials would fail the same way.
+            FluentError::Connect {
+                source: ConnectError::Unauthorized { .. } | ConnectError::SharedKeyMismatch,
+            } => false,
+            _ => true,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct FluentRequest {
+    /// The ID of the chunk, that the server acknowledges it with.
+    pub chunk: Option<String>,
+    pub payload: Bytes,
+    pub finalizers: EventFinalizers,
+    pub metadata: RequestMetadata,
+}
+
+impl Finalizable for FluentRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        self.finalizers.take_finalizers()
+    }
+}
+
+pub struct FluentResponse {
+    metadata: RequestMetadata,
+}
+
+impl DriverResponse for FluentResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> CountByteSize {
+        CountByteSize(
+            self.metadata.event_count(),
+            self.metadata.events_byte_size(),
+        )
+    }
+
+    fn bytes_sent(&self) -> Option<(usize, &str)> {
+        Some((self.metadata.request_wire_size(), "tcp"))
+    }
+}
+
+/// Forwards the chunks to the server, over connections that are kept open between requests.
+///
+/// Each connection carries a single request at a time, so that the acknowledgements can be told
+/// apart, and new connections are opened as more requests are in flight.
+#[derive(Clone)]
+pub struct FluentService {
+    connector: Arc<FluentConnector>,
+    idle: Arc<Mutex<Vec<MaybeTlsStream<TcpStream>>>>,
+    require_ack_response: bool,
+}
+
+impl FluentService {
+    pub(super) fn new(connector: FluentConnector, require_ack_response: bool) -> Self {
+        Self {
+            connector: Arc::new(connector),
+            idle: Arc::default(),
+            require_ack_response,
+        }
+    }
+
+    async fn forward(self, request: FluentRequest) -> Result<FluentResponse, FluentError> {
+        let mut socket = match self.take_idle() {
+            Some(socket) => socket,
+            None => self.connector.connect().await.context(ConnectSnafu)?,
+        };
+
+        socket
+            .write_all(&request.payload)
+            .await
+            .context(SendSnafu)?;
+
+        if let Some(chunk) = request.chunk.filter(|_| self.require_ack_response) {
+            let mut buf = BytesMut::new();
+            let ack = read_message(&mut socket, &mut buf, MAX_ACK_SIZE)
+                .await
+                .context(ReadAckSnafu)?;
+            let acked = ack
+                .as_map()
+                .and_then(|ack| ack.iter().find(|(key, _)| key.as_str() == Some("ack")))
+                .and_then(|(_, value)| value.as_str());
+            if acked != Some(chunk.as_str()) {
+                return Err(FluentError::AckMismatch { chunk });
+            }
+        }
+
+        // The connection is only reused once the request succeeded, as the server may still send
+        // the acknowledgement of a failed one.
+        self.idle.lock().expect("poisoned lock").push(socket);
+
+        Ok(FluentResponse {
+            metadata: request.metadata,
+        })
+    }
+
+    /// Takes one of the idle connections that the server hasn't closed yet.
+    fn take_idle(&self) -> Option<MaybeTlsStream<TcpStream>> {
+        let mut idle = self.idle.lock().expect("poisoned lock");
+        while let Some(mut socket) = idle.pop() {
+            if !is_closed(&mut socket) {
+                return Some(socket);
+            }
+        }
+        None
+    }
+}
+
+/// Tests if the server closed the connection, by reading from it without waiting.
+///
+/// Nothing is expected from the server between the requests, so a connection that has something
+/// to read is considered closed as well.
+fn is_closed(socket: &mut MaybeTlsStream<TcpStream>) -> bool {
+    let mut cx = Context::from_waker(noop_waker_ref());
+    let mut buf = [0u8; 1];
+    let mut buf = ReadBuf::new(&mut buf);
+    !matches!(Pin::new(socket).poll_read(&mut cx, &mut buf), Poll::Pending)
+}
+
+impl Service<FluentRequest> for FluentService {
+    type Response = FluentResponse;
+    type Error = FluentError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: FluentRequest) -> Self::Future {
+        let service = self.clone();
+        Box::pin(service.forward(request))
+    }
+}
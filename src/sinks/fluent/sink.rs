@@ -0,0 +1,350 @@
+use std::{io, num::NonZeroUsize};
+
+use bytes::Bytes;
+use chrono::{DateTime, SecondsFormat, Utc};
+use flate2::write::GzEncoder;
+use futures::{stream::BoxStream, StreamExt};
+use vector_core::{
+    config::log_schema,
+    event::{Event, EventFinalizers, Finalizable, Value},
+    partition::Partitioner,
+    sink::StreamSink,
+    stream::BatcherSettings,
+};
+
+use super::{
+    config::{FluentCompression, FluentSinkConfig},
+    service::{FluentRequest, FluentRetryLogic, FluentService},
+};
+use crate::{
+    codecs::Transformer,
+    internal_events::{SinkRequestBuildError, TemplateRenderingError},
+    sinks::util::{
+        encoding::{write_all, Encoder},
+        metadata::{RequestMetadata, RequestMetadataBuilder},
+        request_builder::EncodeResult,
+        service::Svc,
+        Compression, RequestBuilder, SinkBuilderExt,
+    },
+    template::Template,
+};
+
+/// Partitions the events by their tag.
+struct TagPartitioner(Template);
+
+impl Partitioner for TagPartitioner {
+    type Item = Event;
+    type Key = Option<String>;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        self.0
+            .render_string(item)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("tag"),
+                    drop_event: true,
+                });
+            })
+            .ok()
+    }
+}
+
+/// The events of a chunk, forwarded in a single `PackedForward` message.
+struct FluentChunk {
+    tag: String,
+    chunk: Option<String>,
+    events: Vec<Event>,
+}
+
+/// Encodes the chunks as `PackedForward` messages.
+///
+/// See <https://github.com/fluent/fluentd/wiki/Forward-Protocol-Specification-v1#packedforward-mode>.
+struct FluentEncoder {
+    transformer: Transformer,
+    compression: FluentCompression,
+}
+
+impl Encoder<FluentChunk> for FluentEncoder {
+    fn encode_input(&self, input: FluentChunk, writer: &mut dyn io::Write) -> io::Result<usize> {
+        let count = input.events.len();
+
+        let mut entries = Vec::new();
+        for mut event in input.events {
+            self.transformer.transform(&mut event);
+            let mut log = event.into_log();
+            // The timestamp of the event is that of the entry, rather than a field of its record.
+            let timestamp = match log.remove(log_schema().timestamp_key()) {
+                Some(Value::Timestamp(timestamp)) => timestamp,
+                _ => Utc::now(),
+            };
+
+            let entry = rmpv::Value::Array(vec![event_time(timestamp), to_msgpack(log.value())]);
+            rmpv::encode::write_value(&mut entries, &entry)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        }
+
+        let mut options = vec![("size".into(), (count as u64).into())];
+        if let Some(chunk) = input.chunk {
+            options.push(("chunk".into(), chunk.into()));
+        }
+        if self.compression == FluentCompression::Gzip {
+            let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+            io::Write::write_all(&mut encoder, &entries)?;
+            entries = encoder.finish()?;
+            options.push(("compressed".into(), "gzip".into()));
+        }
+
+        let message = rmpv::Value::Array(vec![
+            input.tag.into(),
+            rmpv::Value::Binary(entries),
+            rmpv::Value::Map(options),
+        ]);
+        let mut bytes = Vec::new();
+        rmpv::encode::write_value(&mut bytes, &message)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        write_all(writer, count, &bytes)?;
+
+        Ok(bytes.len())
+    }
+}
+
+/// Encodes the timestamp as the `EventTime` extension, which keeps its nanoseconds.
+///
+/// See <https://github.com/fluent/fluentd/wiki/Forward-Protocol-Specification-v1#eventtime-ext-format>.
+fn event_time(timestamp: DateTime<Utc>) -> rmpv::Value {
+    let mut bytes = Vec::with_capacity(8);
+    bytes.extend_from_slice(&(timestamp.timestamp() as u32).to_be_bytes());
+    bytes.extend_from_slice(&timestamp.timestamp_subsec_nanos().to_be_bytes());
+    rmpv::Value::Ext(0, bytes)
+}
+
+fn to_msgpack(value: &Value) -> rmpv::Value {
+    match value {
+        Value::Bytes(bytes) => match std::str::from_utf8(bytes) {
+            Ok(string) => string.into(),
+            Err(_) => rmpv::Value::Binary(bytes.to_vec()),
+        },
+        Value::Regex(regex) => regex.as_str().into(),
+        Value::Integer(integer) => (*integer).into(),
+        Value::Float(float) => float.into_inner().into(),
+        Value::Boolean(boolean) => (*boolean).into(),
+        Value::Timestamp(timestamp) => timestamp
+            .to_rfc3339_opts(SecondsFormat::AutoSi, true)
+            .into(),
+        Value::Object(fields) => rmpv::Value::Map(
+            fields
+                .iter()
+                .map(|(key, value)| (key.as_str().into(), to_msgpack(value)))
+                .collect(),
+        ),
+        Value::Array(values) => rmpv::Value::Array(values.iter().map(to_msgpack).collect()),
+        Value::Null => rmpv::Value::Nil,
+    }
+}
+
+struct FluentRequestBuilder {
+    encoder: FluentEncoder,
+    require_ack_response: bool,
+}
+
+impl RequestBuilder<(String, Vec<Event>)> for FluentRequestBuilder {
+    type Metadata = (Option<String>, EventFinalizers, RequestMetadataBuilder);
+    type Events = FluentChunk;
+    type Encoder = FluentEncoder;
+    type Payload = Bytes;
+    type Request = FluentRequest;
+    type Error = io::Error;
+
+    // The entries are compressed by the encoder, as the rest of the message must be left as is.
+    fn compression(&self) -> Compression {
+        Compression::None
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        &self.encoder
+    }
+
+    fn split_input(&self, input: (String, Vec<Event>)) -> (Self::Metadata, Self::Events) {
+        let (tag, mut events) = input;
+        let metadata_builder = RequestMetadata::builder(&events);
+        let finalizers = events.take_finalizers();
+        // The chunks are given a unique ID, which the server acknowledges them with.
+        let chunk = self
+            .require_ack_response
+            .then(|| base64::encode(uuid::Uuid::new_v4().as_bytes()));
+
+        (
+            (chunk.clone(), finalizers, metadata_builder),
+            FluentChunk { tag, chunk, events },
+        )
+    }
+
+    fn build_request(
+        &self,
+        metadata: Self::Metadata,
+        payload: EncodeResult<Self::Payload>,
+    ) -> Self::Request {
+        let (chunk, finalizers, metadata_builder) = metadata;
+        let metadata = metadata_builder.build(&payload);
+
+        FluentRequest {
+            chunk,
+            payload: payload.into_payload(),
+            finalizers,
+            metadata,
+        }
+    }
+}
+
+pub struct FluentSink {
+    partitioner: TagPartitioner,
+    request_builder: FluentRequestBuilder,
+    batch_settings: BatcherSettings,
+    service: Svc<FluentService, FluentRetryLogic>,
+}
+
+impl FluentSink {
+    pub(super) fn new(
+        config: &FluentSinkConfig,
+        batch_settings: BatcherSettings,
+        service: Svc<FluentService, FluentRetryLogic>,
+    ) -> Self {
+        Self {
+            partitioner: TagPartitioner(config.tag.clone()),
+            request_builder: FluentRequestBuilder {
+                encoder: FluentEncoder {
+                    transformer: config.encoding.clone(),
+                    compression: config.compression,
+                },
+                require_ack_response: config.require_ack_response,
+            },
+            batch_settings,
+            service,
+        }
+    }
+
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let sink = input
+            .batched_partitioned(self.partitioner, self.batch_settings)
+            .filter_map(|(tag, batch)| async move {
+                // A `TemplateRenderingError` is already emitted for the events whose tag couldn't
+                // be rendered.
+                tag.map(move |tag| (tag, batch))
+            })
+            .request_builder(NonZeroUsize::new(64), self.request_builder)
+            .filter_map(|request| async move {
+                match request {
+                    Err(error) => {
+                        emit!(SinkRequestBuildError { error });
+                        None
+                    }
+                    Ok(req) => Some(req),
+                }
+            })
+            .into_driver(self.service);
+
+        sink.run().await
+    }
+}
+
+#[async_trait::async_trait]
+impl StreamSink<Event> for FluentSink {
+    async fn run(mut self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use chrono::TimeZone;
+    use flate2::read::MultiGzDecoder;
+    use vector_core::event::LogEvent;
+
+    use super::*;
+
+    fn encode(compression: FluentCompression, events: Vec<Event>) -> Vec<rmpv::Value> {
+        let encoder = FluentEncoder {
+            transformer: Transformer::default(),
+            compression,
+        };
+        let chunk = FluentChunk {
+            tag: "app.logs".to_owned(),
+            chunk: Some("Y2h1bms=".to_owned()),
+            events,
+        };
+
+        let mut bytes = Vec::new();
+        encoder.encode_input(chunk, &mut bytes).unwrap();
+        let message = rmpv::decode::read_value(&mut &bytes[..]).unwrap();
+        message.as_array().unwrap().clone()
+    }
+
+    fn event() -> Event {
+        let mut log = LogEvent::default();
+        log.insert("message", "hello");
+        log.insert("count", 3);
+        log.insert(
+            log_schema().timestamp_key(),
+            Utc.ymd(2022, 11, 2).and_hms_milli(10, 30, 0, 123),
+        );
+        log.into()
+    }
+
+    fn option<'a>(message: &'a [rmpv::Value], name: &str) -> Option<&'a rmpv::Value> {
+        message[2]
+            .as_map()
+            .unwrap()
+            .iter()
+            .find(|(key, _)| key.as_str() == Some(name))
+            .map(|(_, value)| value)
+    }
+
+    #[test]
+    fn encodes_packed_forward() {
+        let message = encode(FluentCompression::None, vec![event(), event()]);
+
+        assert_eq!(message[0].as_str(), Some("app.logs"));
+        assert_eq!(option(&message, "size").unwrap().as_u64(), Some(2));
+        assert_eq!(
+            option(&message, "chunk").unwrap().as_str(),
+            Some("Y2h1bms=")
+        );
+        assert!(option(&message, "compressed").is_none());
+
+        let mut entries = message[1].as_slice().unwrap();
+        for _ in 0..2 {
+            let entry = rmpv::decode::read_value(&mut entries).unwrap();
+            let entry = entry.as_array().unwrap();
+
+            let mut time = 1_667_385_000u32.to_be_bytes().to_vec();
+            time.extend_from_slice(&123_000_000u32.to_be_bytes());
+            assert_eq!(entry[0], rmpv::Value::Ext(0, time));
+
+            let record = entry[1].as_map().unwrap();
+            assert_eq!(record.len(), 2);
+            assert!(record.contains(&("message".into(), "hello".into())));
+            assert!(record.contains(&("count".into(), 3.into())));
+        }
+        assert!(entries.is_empty());
+    }
+
+    #[test]
+    fn compresses_entries() {
+        let message = encode(FluentCompression::Gzip, vec![event()]);
+
+        assert_eq!(
+            option(&message, "compressed").unwrap().as_str(),
+            Some("gzip")
+        );
+
+        let mut entries = Vec::new();
+        MultiGzDecoder::new(message[1].as_slice().unwrap())
+            .read_to_end(&mut entries)
+            .unwrap();
+        let entry = rmpv::decode::read_value(&mut &entries[..]).unwrap();
+        assert_eq!(entry.as_array().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,354 @@
+//! The `gcp_bigquery` sink.
+//!
+//! Writes the events as rows of a BigQuery table, through the [Storage Write API][write_api]: the
+//! rows are encoded as protobuf messages whose descriptor is derived from the schema of the events,
+//! and appended to the streams of the table over gRPC.
+//!
+//! [write_api]: https://cloud.google.com/bigquery/docs/write-api
+
+use std::sync::Arc;
+
+use http::uri::{Scheme, Uri};
+use indoc::indoc;
+use snafu::{ResultExt, Snafu};
+use tonic::transport::{Certificate, ClientTlsConfig, Endpoint, Identity};
+use tower::ServiceBuilder;
+use vector_config::configurable_component;
+
+use crate::{
+    config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
+    gcp::{GcpAuthConfig, Scope},
+    sinks::{
+        util::{uri, BatchConfig, ServiceBuilderExt, SinkBatchSettings, TowerRequestConfig},
+        Healthcheck, UriParseSnafu, VectorSink,
+    },
+    tls::{TlsConfig, TlsSettings},
+};
+
+mod schema;
+mod service;
+mod sink;
+
+use self::{
+    schema::RowSchema,
+    service::{
+        default_stream, AuthInterceptor, BigqueryClient, BigqueryRetryLogic, BigqueryService,
+    },
+    sink::BigquerySink,
+};
+
+// prost emits some generated code that includes clones on `Arc`
+// objects, which causes a clippy ding on this block. We don't
+// directly control the generated code, so allow this lint here.
+#[allow(clippy::clone_on_ref_ptr)]
+#[allow(warnings)]
+mod proto {
+    // The generated code refers to `google.rpc.Status` by its path relative to its own package, so
+    // the packages are nested as they are in the protobuf definitions.
+    pub mod google {
+        pub mod rpc {
+            include!(concat!(env!("OUT_DIR"), "/google.rpc.rs"));
+        }
+
+        pub mod cloud {
+            pub mod bigquery {
+                pub mod storage {
+                    pub mod v1 {
+                        include!(concat!(
+                            env!("OUT_DIR"),
+                            "/google.cloud.bigquery.storage.v1.rs"
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    pub use google::cloud::bigquery::storage::v1::*;
+}
+
+const DEFAULT_ENDPOINT: &str = "https://bigquerystorage.googleapis.com";
+
+// 10MB maximum request size: https://cloud.google.com/bigquery/quotas#write-api-limits
+const MAX_BATCH_PAYLOAD_SIZE: usize = 10_000_000;
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub))]
+pub enum BigquerySinkError {
+    #[snafu(display("Could not create endpoint: {}", source))]
+    Endpoint { source: tonic::transport::Error },
+
+    #[snafu(display("Could not set up endpoint TLS settings: {}", source))]
+    EndpointTls { source: tonic::transport::Error },
+
+    #[snafu(display("Request failed: {}", source))]
+    Request { source: tonic::Status },
+
+    #[snafu(display("Append failed with code {}: {}", code, message))]
+    Append { code: i32, message: String },
+
+    #[snafu(display("{} rows were rejected, the first one with: {}", count, message))]
+    RowErrors { count: usize, message: String },
+
+    #[snafu(display("Commit failed: {}", message))]
+    Commit { message: String },
+
+    #[snafu(display("No response to the append request"))]
+    NoResponse,
+
+    #[snafu(display("The table has no schema"))]
+    NoTableSchema,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BigqueryDefaultBatchSettings;
+
+impl SinkBatchSettings for BigqueryDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = Some(10_000);
+    const MAX_BYTES: Option<usize> = Some(MAX_BATCH_PAYLOAD_SIZE);
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+/// Configuration for the `gcp_bigquery` sink.
+#[configurable_component(sink("gcp_bigquery"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct BigqueryConfig {
+    /// The project of the table.
+    pub project: String,
+
+    /// The dataset of the table.
+    pub dataset: String,
+
+    /// The table the events are written to.
+    ///
+    /// The table must already exist. The columns of the rows are derived from the schema of the
+    /// events received by the sink, and only the fields it knows are written. When it knows none
+    /// of their fields, the columns are those of the table instead.
+    pub table: String,
+
+    /// The endpoint of the BigQuery Storage API.
+    #[serde(default)]
+    pub endpoint: Option<String>,
+
+    #[serde(default, flatten)]
+    pub auth: GcpAuthConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub write_mode: BigqueryWriteMode,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<BigqueryDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+/// How the rows are written to the table.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum BigqueryWriteMode {
+    /// Append the rows to the default stream of the table, where they are committed as soon as
+    /// they are written.
+    ///
+    /// The rows of a request retried after its response was lost are written twice.
+    #[derivative(Default)]
+    Default,
+
+    /// Write the rows of each request to a pending stream of its own, committed once all of them
+    /// are written.
+    ///
+    /// The retries of a request write to the same stream, at the same offset, so that its rows are
+    /// written exactly once, and its events are only acknowledged once the stream is committed.
+    Pending,
+}
+
+impl GenerateConfig for BigqueryConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(indoc! {r#"
+            project = "my-project"
+            dataset = "my-dataset"
+            table = "my-table"
+        "#})
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for BigqueryConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let batch_settings = self
+            .batch
+            .validate()?
+            .limit_max_bytes(MAX_BATCH_PAYLOAD_SIZE)?
+            .into_batcher_settings()?;
+        let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
+
+        let auth = self.auth.build(Scope::CloudPlatform).await?;
+        auth.spawn_regenerate_token();
+        let uri: Uri = self
+            .endpoint
+            .as_deref()
+            .unwrap_or(DEFAULT_ENDPOINT)
+            .parse()
+            .context(UriParseSnafu)?;
+        let client = self.build_client(&uri, AuthInterceptor { auth })?;
+
+        let schema = match RowSchema::from_definition(&cx.merged_schema_definition) {
+            Some(schema) => schema,
+            None => {
+                RowSchema::from_table_schema(&table_schema(client.clone(), self.table()).await?)
+            }
+        };
+
+        let healthcheck = healthcheck(client.clone(), self.table());
+
+        let (protocol, endpoint) = uri::protocol_endpoint(uri);
+        let service = BigqueryService {
+            client,
+            table: self.table(),
+            write_mode: self.write_mode,
+            writer_schema: proto::ProtoSchema {
+                proto_descriptor: Some(schema.descriptor()),
+            },
+            protocol,
+            endpoint,
+        };
+        let service = ServiceBuilder::new()
+            .settings(request_settings, BigqueryRetryLogic)
+            .service(service);
+
+        let sink = BigquerySink {
+            batch_settings,
+            schema: Arc::new(schema),
+            service,
+        };
+
+        Ok((
+            VectorSink::from_event_streamsink(sink),
+            Box::pin(healthcheck),
+        ))
+    }
+
+    fn input(&self) -> Input {
+        Input::new(DataType::Log)
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+impl BigqueryConfig {
+    /// Gets the table, as `projects/{project}/datasets/{dataset}/tables/{table}`.
+    fn table(&self) -> String {
+        format!(
+            "projects/{}/datasets/{}/tables/{}",
+            self.project, self.dataset, self.table
+        )
+    }
+
+    fn build_client(
+        &self,
+        uri: &Uri,
+        interceptor: AuthInterceptor,
+    ) -> crate::Result<BigqueryClient> {
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let host = uri.host().unwrap_or("bigquerystorage.googleapis.com");
+        let mut tls_config = ClientTlsConfig::new().domain_name(host);
+        if let Some((cert, key)) = tls.identity_pem() {
+            tls_config = tls_config.identity(Identity::from_pem(cert, key));
+        }
+        for authority in tls.authorities_pem() {
+            tls_config = tls_config.ca_certificate(Certificate::from_pem(authority));
+        }
+
+        let mut endpoint: Endpoint = uri.to_string().parse().context(EndpointSnafu)?;
+        if uri.scheme() != Some(&Scheme::HTTP) {
+            endpoint = endpoint.tls_config(tls_config).context(EndpointTlsSnafu)?;
+        }
+
+        Ok(
+            proto::big_query_write_client::BigQueryWriteClient::with_interceptor(
+                endpoint.connect_lazy(),
+                interceptor,
+            ),
+        )
+    }
+}
+
+/// Gets the schema of the table, from its default stream.
+async fn table_schema(
+    mut client: BigqueryClient,
+    table: String,
+) -> crate::Result<proto::TableSchema> {
+    let stream = client
+        .get_write_stream(proto::GetWriteStreamRequest {
+            name: default_stream(&table),
+            view: proto::WriteStreamView::Full as i32,
+        })
+        .await
+        .context(RequestSnafu)?
+        .into_inner();
+    stream
+        .table_schema
+        .ok_or_else(|| BigquerySinkError::NoTableSchema.into())
+}
+
+/// Checks that the table exists, and that it can be written to.
+async fn healthcheck(mut client: BigqueryClient, table: String) -> crate::Result<()> {
+    client
+        .get_write_stream(proto::GetWriteStreamRequest {
+            name: default_stream(&table),
+            view: proto::WriteStreamView::Basic as i32,
+        })
+        .await
+        .context(RequestSnafu)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<BigqueryConfig>();
+    }
+
+    #[test]
+    fn parses_config() {
+        let config: BigqueryConfig = toml::from_str(indoc! {r#"
+            project = "my-project"
+            dataset = "my-dataset"
+            table = "my-table"
+            write_mode = "pending"
+        "#})
+        .unwrap();
+
+        assert_eq!(config.write_mode, BigqueryWriteMode::Pending);
+        assert_eq!(
+            config.table(),
+            "projects/my-project/datasets/my-dataset/tables/my-table"
+        );
+    }
+}
@@ -0,0 +1,482 @@
+//! The schema of the rows written to BigQuery, and their encoding as protobuf messages.
+//!
+//! The Storage Write API takes rows serialized as protobuf messages, along with the descriptor of
+//! their message. Rather than generating code for a fixed message, the descriptor is derived at
+//! runtime from the schema definition of the events received by the sink, or from the schema of the
+//! table when the fields of the events aren't known, and the rows are encoded by hand.
+
+use std::collections::BTreeMap;
+
+use lookup::LookupBuf;
+use prost::encoding;
+use prost_types::{
+    field_descriptor_proto::{Label, Type},
+    DescriptorProto, FieldDescriptorProto,
+};
+use value::Kind;
+use vector_core::schema::Definition;
+
+use super::proto::{table_field_schema, TableFieldSchema, TableSchema};
+use crate::{
+    event::{LogEvent, Value},
+    internal_events::GcpBigqueryFieldConversionError,
+};
+
+/// The name of the message of the rows, in the descriptors sent to BigQuery.
+const ROW_MESSAGE: &str = "Row";
+
+/// The fields of the rows, or of a record nested in them.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RowSchema {
+    fields: Vec<RowField>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct RowField {
+    name: String,
+    number: u32,
+    kind: FieldKind,
+    repeated: bool,
+    required: bool,
+}
+
+/// The type of a field, as written in the rows.
+#[derive(Clone, Debug, PartialEq)]
+enum FieldKind {
+    String,
+    Bytes,
+    Int64,
+    Double,
+    Bool,
+    /// Microseconds since the Unix epoch.
+    Timestamp,
+    /// Any value, written as a JSON string.
+    Json,
+    Record(RowSchema),
+}
+
+impl RowSchema {
+    /// Derives the schema of the rows from the schema definition of the events.
+    ///
+    /// Returns `None` if the definition knows none of the fields of the events. The fields that
+    /// aren't known, or whose name isn't a valid column name, are left out of the rows.
+    pub fn from_definition(definition: &Definition) -> Option<Self> {
+        Self::from_object_kind(definition, &LookupBuf::root(), definition.event_kind())
+            .filter(|schema| !schema.fields.is_empty())
+    }
+
+    /// Derives the schema of the rows from the schema of the table.
+    pub fn from_table_schema(schema: &TableSchema) -> Self {
+        Self::from_table_fields(&schema.fields)
+    }
+
+    /// Builds the descriptor of the message of the rows.
+    pub fn descriptor(&self) -> DescriptorProto {
+        self.message_descriptor(ROW_MESSAGE)
+    }
+
+    /// Encodes the fields of the event as a row.
+    ///
+    /// The fields that are missing, or whose value can't be converted to the type of their column,
+    /// are left out of the row.
+    pub fn encode(&self, log: &LogEvent) -> Vec<u8> {
+        let mut row = Vec::new();
+        if let Value::Object(object) = log.value() {
+            self.encode_object(object, &mut row);
+        }
+        row
+    }
+
+    fn from_object_kind(definition: &Definition, path: &LookupBuf, kind: &Kind) -> Option<Self> {
+        let fields = kind
+            .as_object()?
+            .known()
+            .iter()
+            .filter(|(name, _)| is_valid_name(name.as_str()))
+            .filter_map(|(name, kind)| {
+                let mut path = path.clone();
+                path.push_back(name.as_str());
+                let (kind, repeated) = FieldKind::from_kind(definition, &path, kind)?;
+                let required = !repeated
+                    && definition.is_required(path.clone())
+                    && !definition.is_nullable(path);
+                Some((name.to_string(), kind, repeated, required))
+            })
+            .zip(1..)
+            .map(|((name, kind, repeated, required), number)| RowField {
+                name,
+                number,
+                kind,
+                repeated,
+                required,
+            })
+            .collect();
+        Some(Self { fields })
+    }
+
+    fn from_table_fields(fields: &[TableFieldSchema]) -> Self {
+        use table_field_schema::{Mode, Type};
+
+        let fields = fields
+            .iter()
+            .filter(|field| is_valid_name(&field.name))
+            .zip(1..)
+            .map(|(field, number)| {
+                let kind = match Type::from_i32(field.r#type) {
+                    Some(Type::Int64) => FieldKind::Int64,
+                    Some(Type::Double) => FieldKind::Double,
+                    Some(Type::Bool) => FieldKind::Bool,
+                    Some(Type::Timestamp) => FieldKind::Timestamp,
+                    Some(Type::Bytes) => FieldKind::Bytes,
+                    Some(Type::Json) => FieldKind::Json,
+                    Some(Type::Struct) => FieldKind::Record(Self::from_table_fields(&field.fields)),
+                    // The other types, such as dates and numerics, are written as strings.
+                    _ => FieldKind::String,
+                };
+                let mode = Mode::from_i32(field.mode);
+                RowField {
+                    name: field.name.clone(),
+                    number,
+                    kind,
+                    repeated: mode == Some(Mode::Repeated),
+                    required: mode == Some(Mode::Required),
+                }
+            })
+            .collect();
+        Self { fields }
+    }
+
+    fn message_descriptor(&self, name: &str) -> DescriptorProto {
+        let mut nested_type = Vec::new();
+        let field = self
+            .fields
+            .iter()
+            .map(|field| {
+                let (r#type, type_name) = match &field.kind {
+                    FieldKind::String | FieldKind::Json => (Type::String, None),
+                    FieldKind::Bytes => (Type::Bytes, None),
+                    FieldKind::Int64 | FieldKind::Timestamp => (Type::Int64, None),
+                    FieldKind::Double => (Type::Double, None),
+                    FieldKind::Bool => (Type::Bool, None),
+                    FieldKind::Record(schema) => {
+                        // The descriptor must be self-contained, so records are nested messages.
+                        let type_name = format!("Field{}", field.number);
+                        nested_type.push(schema.message_descriptor(&type_name));
+                        (Type::Message, Some(type_name))
+                    }
+                };
+                let label = if field.repeated {
+                    Label::Repeated
+                } else if field.required {
+                    Label::Required
+                } else {
+                    Label::Optional
+                };
+                FieldDescriptorProto {
+                    name: Some(field.name.clone()),
+                    number: Some(field.number as i32),
+                    label: Some(label as i32),
+                    r#type: Some(r#type as i32),
+                    type_name,
+                    ..Default::default()
+                }
+            })
+            .collect();
+        DescriptorProto {
+            name: Some(name.to_owned()),
+            field,
+            nested_type,
+            ..Default::default()
+        }
+    }
+
+    fn encode_object(&self, object: &BTreeMap<String, Value>, buf: &mut Vec<u8>) {
+        for field in &self.fields {
+            let converted = match object.get(&field.name) {
+                None | Some(Value::Null) => true,
+                Some(Value::Array(values)) if field.repeated => values
+                    .iter()
+                    .filter(|value| !matches!(value, Value::Null))
+                    .all(|value| field.encode_value(value, buf)),
+                Some(value) => field.encode_value(value, buf),
+            };
+            if !converted {
+                emit!(GcpBigqueryFieldConversionError {
+                    field: &field.name,
+                    column_type: field.kind.as_str(),
+                });
+            }
+        }
+    }
+}
+
+impl RowField {
+    /// Encodes a value of the field, returning whether it could be converted to its type.
+    fn encode_value(&self, value: &Value, buf: &mut Vec<u8>) -> bool {
+        let tag = self.number;
+        match (&self.kind, value) {
+            (FieldKind::String, Value::Bytes(bytes)) => match std::str::from_utf8(bytes) {
+                Ok(_) => encoding::bytes::encode(tag, bytes, buf),
+                Err(_) => encoding::string::encode(tag, &value.to_string_lossy(), buf),
+            },
+            (FieldKind::String, Value::Object(_) | Value::Array(_)) => return false,
+            (FieldKind::String, value) => {
+                encoding::string::encode(tag, &value.to_string_lossy(), buf);
+            }
+            (FieldKind::Bytes, Value::Bytes(bytes)) => encoding::bytes::encode(tag, bytes, buf),
+            (FieldKind::Int64, Value::Integer(integer)) => {
+                encoding::int64::encode(tag, integer, buf);
+            }
+            (FieldKind::Double, Value::Float(float)) => {
+                encoding::double::encode(tag, &float.into_inner(), buf);
+            }
+            (FieldKind::Double, Value::Integer(integer)) => {
+                encoding::double::encode(tag, &(*integer as f64), buf);
+            }
+            (FieldKind::Bool, Value::Boolean(boolean)) => {
+                encoding::bool::encode(tag, boolean, buf);
+            }
+            (FieldKind::Timestamp, Value::Timestamp(timestamp)) => {
+                encoding::int64::encode(tag, &timestamp.timestamp_micros(), buf);
+            }
+            (FieldKind::Json, value) => match serde_json::to_string(value) {
+                Ok(json) => encoding::string::encode(tag, &json, buf),
+                Err(_) => return false,
+            },
+            (FieldKind::Record(schema), Value::Object(object)) => {
+                let mut record = Vec::new();
+                schema.encode_object(object, &mut record);
+                encoding::bytes::encode(tag, &record, buf);
+            }
+            _ => return false,
+        }
+        true
+    }
+}
+
+impl FieldKind {
+    /// Maps the kind of the values of a field to the type of its column, and whether it is repeated.
+    ///
+    /// Returns `None` if the field is never set to a value other than `null`.
+    fn from_kind(definition: &Definition, path: &LookupBuf, kind: &Kind) -> Option<(Self, bool)> {
+        let mut kind = kind.clone();
+        kind.remove_null();
+        kind.remove_undefined();
+        if kind.is_never() {
+            return None;
+        }
+
+        if kind.is_array() {
+            let mut path = path.clone();
+            path.push_back(0_isize);
+            let element = kind
+                .as_array()
+                .map_or_else(Kind::never, |array| array.reduced_kind());
+            return Some(match Self::from_kind(definition, &path, &element) {
+                Some((kind, false)) => (kind, true),
+                // Arrays of arrays have no column type, and are written as JSON instead.
+                _ => (Self::Json, false),
+            });
+        }
+
+        let kind = if kind.is_object() {
+            match Self::record(definition, path, &kind) {
+                Some(schema) => Self::Record(schema),
+                None => Self::Json,
+            }
+        } else if kind.is_bytes() {
+            Self::String
+        } else if kind.is_integer() {
+            Self::Int64
+        } else if kind.is_float() || is_number(&kind) {
+            Self::Double
+        } else if kind.is_boolean() {
+            Self::Bool
+        } else if kind.is_timestamp() {
+            Self::Timestamp
+        } else {
+            Self::Json
+        };
+        Some((kind, false))
+    }
+
+    /// Derives the schema of a record, if all of its fields are known.
+    fn record(definition: &Definition, path: &LookupBuf, kind: &Kind) -> Option<RowSchema> {
+        let object = kind.as_object()?;
+        if !object.unknown_kind().is_undefined() {
+            return None;
+        }
+        RowSchema::from_object_kind(definition, path, kind)
+            .filter(|schema| !schema.fields.is_empty())
+    }
+
+    const fn as_str(&self) -> &'static str {
+        match self {
+            Self::String => "string",
+            Self::Bytes => "bytes",
+            Self::Int64 => "int64",
+            Self::Double => "double",
+            Self::Bool => "bool",
+            Self::Timestamp => "timestamp",
+            Self::Json => "json",
+            Self::Record(_) => "record",
+        }
+    }
+}
+
+/// Whether the kind is both integers and floats, and nothing else.
+fn is_number(kind: &Kind) -> bool {
+    let mut float = kind.clone();
+    float.remove_integer();
+    kind.contains_integer() && float.is_float()
+}
+
+/// Whether the name is valid for both a column and a protobuf field.
+fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use prost::Message;
+    use value::kind::Collection;
+
+    use super::*;
+
+    #[derive(Clone, PartialEq, Message)]
+    struct Row {
+        #[prost(int64, optional, tag = "1")]
+        count: Option<i64>,
+        #[prost(message, optional, tag = "2")]
+        http: Option<Http>,
+        #[prost(string, optional, tag = "3")]
+        message: Option<String>,
+        #[prost(string, repeated, tag = "4")]
+        tags: Vec<String>,
+        #[prost(int64, optional, tag = "5")]
+        timestamp: Option<i64>,
+    }
+
+    #[derive(Clone, PartialEq, Message)]
+    struct Http {
+        #[prost(int64, optional, tag = "1")]
+        status: Option<i64>,
+    }
+
+    fn definition() -> Definition {
+        Definition::empty_legacy_namespace()
+            .optional_field("count", Kind::integer(), None)
+            .with_field(
+                "http",
+                Kind::object(BTreeMap::from([("status".into(), Kind::integer())])),
+                None,
+            )
+            .with_field("message", Kind::bytes(), None)
+            .with_field(
+                "tags",
+                Kind::array(Collection::from_unknown(Kind::bytes())),
+                None,
+            )
+            .with_field("timestamp", Kind::timestamp().or_null(), None)
+            .with_field("invalid-name", Kind::bytes(), None)
+    }
+
+    #[test]
+    fn derives_schema_from_definition() {
+        let descriptor = RowSchema::from_definition(&definition())
+            .unwrap()
+            .descriptor();
+
+        let fields = descriptor
+            .field
+            .iter()
+            .map(|field| {
+                (
+                    field.name(),
+                    field.number(),
+                    field.label(),
+                    field.r#type(),
+                    field.type_name(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            fields,
+            [
+                ("count", 1, Label::Optional, Type::Int64, ""),
+                ("http", 2, Label::Required, Type::Message, "Field2"),
+                ("message", 3, Label::Required, Type::String, ""),
+                ("tags", 4, Label::Repeated, Type::String, ""),
+                ("timestamp", 5, Label::Optional, Type::Int64, ""),
+            ]
+        );
+        assert_eq!(descriptor.nested_type.len(), 1);
+        assert_eq!(descriptor.nested_type[0].field[0].name(), "status");
+    }
+
+    #[test]
+    fn needs_known_fields() {
+        assert_eq!(RowSchema::from_definition(&Definition::any()), None);
+    }
+
+    #[test]
+    fn derives_schema_from_table() {
+        use table_field_schema::{Mode, Type as ColumnType};
+
+        let column = |name: &str, r#type: ColumnType, mode: Mode| TableFieldSchema {
+            name: name.to_owned(),
+            r#type: r#type as i32,
+            mode: mode as i32,
+            ..Default::default()
+        };
+        let schema = RowSchema::from_table_schema(&TableSchema {
+            fields: vec![
+                column("message", ColumnType::String, Mode::Required),
+                column("day", ColumnType::Date, Mode::Nullable),
+                column("ids", ColumnType::Int64, Mode::Repeated),
+            ],
+        });
+
+        let fields = schema
+            .descriptor()
+            .field
+            .iter()
+            .map(|field| (field.name().to_owned(), field.label(), field.r#type()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            fields,
+            [
+                ("message".to_owned(), Label::Required, Type::String),
+                ("day".to_owned(), Label::Optional, Type::String),
+                ("ids".to_owned(), Label::Repeated, Type::Int64),
+            ]
+        );
+    }
+
+    #[test]
+    fn encodes_rows() {
+        let schema = RowSchema::from_definition(&definition()).unwrap();
+        let timestamp = Utc.ymd(2022, 1, 1).and_hms(0, 0, 0);
+        let mut log = LogEvent::from("hello");
+        log.insert("count", "not an integer");
+        log.insert("http.status", 200);
+        log.insert("tags", vec!["a", "b"]);
+        log.insert("timestamp", timestamp);
+        log.insert("unknown", "left out");
+
+        let row = Row::decode(schema.encode(&log).as_slice()).unwrap();
+        assert_eq!(
+            row,
+            Row {
+                count: None,
+                http: Some(Http { status: Some(200) }),
+                message: Some("hello".to_owned()),
+                tags: vec!["a".to_owned(), "b".to_owned()],
+                timestamp: Some(timestamp.timestamp_micros()),
+            }
+        );
+    }
+}
@@ -0,0 +1,308 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
+
+use futures::{future::BoxFuture, stream};
+use prost::Message;
+use snafu::ResultExt;
+use tokio::sync::OnceCell;
+use tonic::{
+    metadata::MetadataValue,
+    service::{interceptor::InterceptedService, Interceptor},
+    transport::Channel,
+    Code, Request, Status,
+};
+use vector_core::{internal_event::CountByteSize, stream::DriverResponse};
+
+use super::{
+    proto::{self, big_query_write_client::BigQueryWriteClient},
+    BigquerySinkError, BigqueryWriteMode, RequestSnafu,
+};
+use crate::{
+    event::{EventFinalizers, EventStatus, Finalizable},
+    gcp::GcpAuthenticator,
+    internal_events::EndpointBytesSent,
+    sinks::util::retries::RetryLogic,
+};
+
+pub type BigqueryClient = BigQueryWriteClient<InterceptedService<Channel, AuthInterceptor>>;
+
+/// Sets the authorization of the requests to the current token of the authenticator.
+#[derive(Clone)]
+pub struct AuthInterceptor {
+    pub auth: GcpAuthenticator,
+}
+
+impl Interceptor for AuthInterceptor {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        if let Some(token) = self.auth.make_token() {
+            let authorization = MetadataValue::try_from(&token).map_err(|_| {
+                Status::new(
+                    Code::FailedPrecondition,
+                    "Invalid token text returned by GCP",
+                )
+            })?;
+            request
+                .metadata_mut()
+                .insert("authorization", authorization);
+        }
+        Ok(request)
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct BigqueryRequest {
+    pub rows: Vec<Vec<u8>>,
+    pub finalizers: EventFinalizers,
+    pub events_byte_size: usize,
+    pub pending: Arc<PendingWrite>,
+}
+
+impl Finalizable for BigqueryRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        self.finalizers.take_finalizers()
+    }
+}
+
+/// The progress of writing a request to its pending stream.
+///
+/// It is shared by the retries of the request, so that they resume where the previous attempt
+/// stopped, on the same stream, rather than writing the rows to another one.
+#[derive(Debug, Default)]
+pub struct PendingWrite {
+    stream: OnceCell<String>,
+    appended: AtomicBool,
+    finalized: AtomicBool,
+}
+
+pub struct BigqueryResponse {
+    events_count: usize,
+    events_byte_size: usize,
+}
+
+impl DriverResponse for BigqueryResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> CountByteSize {
+        CountByteSize(self.events_count, self.events_byte_size)
+    }
+}
+
+#[derive(Clone)]
+pub struct BigqueryService {
+    pub client: BigqueryClient,
+    /// The table, as `projects/{project}/datasets/{dataset}/tables/{table}`.
+    pub table: String,
+    pub write_mode: BigqueryWriteMode,
+    pub writer_schema: proto::ProtoSchema,
+    pub protocol: String,
+    pub endpoint: String,
+}
+
+impl BigqueryService {
+    async fn write(self, request: BigqueryRequest) -> Result<BigqueryResponse, BigquerySinkError> {
+        let events_count = request.rows.len();
+        let events_byte_size = request.events_byte_size;
+        let rows = proto::ProtoRows {
+            serialized_rows: request.rows,
+        };
+        let byte_size = rows.encoded_len();
+
+        match self.write_mode {
+            BigqueryWriteMode::Default => {
+                self.append(default_stream(&self.table), None, rows).await?;
+            }
+            BigqueryWriteMode::Pending => {
+                let pending = request.pending;
+                let stream = pending
+                    .stream
+                    .get_or_try_init(|| self.create_pending_stream())
+                    .await?;
+                if !pending.appended.load(Ordering::Acquire) {
+                    // The rows are written at the start of the stream, so that a retry of a lost
+                    // append is rejected rather than written twice.
+                    self.append(stream.clone(), Some(0), rows).await?;
+                    pending.appended.store(true, Ordering::Release);
+                }
+                if !pending.finalized.load(Ordering::Acquire) {
+                    self.client
+                        .clone()
+                        .finalize_write_stream(proto::FinalizeWriteStreamRequest {
+                            name: stream.clone(),
+                        })
+                        .await
+                        .context(RequestSnafu)?;
+                    pending.finalized.store(true, Ordering::Release);
+                }
+                self.commit(stream).await?;
+            }
+        }
+
+        emit!(EndpointBytesSent {
+            byte_size,
+            protocol: &self.protocol,
+            endpoint: &self.endpoint,
+        });
+        Ok(BigqueryResponse {
+            events_count,
+            events_byte_size,
+        })
+    }
+
+    async fn create_pending_stream(&self) -> Result<String, BigquerySinkError> {
+        let stream = self
+            .client
+            .clone()
+            .create_write_stream(proto::CreateWriteStreamRequest {
+                parent: self.table.clone(),
+                write_stream: Some(proto::WriteStream {
+                    r#type: proto::write_stream::Type::Pending as i32,
+                    ..Default::default()
+                }),
+            })
+            .await
+            .context(RequestSnafu)?;
+        Ok(stream.into_inner().name)
+    }
+
+    async fn append(
+        &self,
+        write_stream: String,
+        offset: Option<i64>,
+        rows: proto::ProtoRows,
+    ) -> Result<(), BigquerySinkError> {
+        use proto::{append_rows_request, append_rows_response};
+
+        let request = proto::AppendRowsRequest {
+            write_stream,
+            offset,
+            rows: Some(append_rows_request::Rows::ProtoRows(
+                append_rows_request::ProtoData {
+                    writer_schema: Some(self.writer_schema.clone()),
+                    rows: Some(rows),
+                },
+            )),
+            trace_id: "vector".to_owned(),
+        };
+        let mut responses = self
+            .client
+            .clone()
+            .append_rows(stream::iter([request]))
+            .await
+            .context(RequestSnafu)?
+            .into_inner();
+        let response = responses
+            .message()
+            .await
+            .context(RequestSnafu)?
+            .ok_or(BigquerySinkError::NoResponse)?;
+
+        if let Some(error) = response.row_errors.first() {
+            return Err(BigquerySinkError::RowErrors {
+                count: response.row_errors.len(),
+                message: error.message.clone(),
+            });
+        }
+        match response.response {
+            // The rows are already at the offset, written by a previous attempt whose response was
+            // lost.
+            Some(append_rows_response::Response::Error(status))
+                if offset.is_some() && status.code == Code::AlreadyExists as i32 =>
+            {
+                Ok(())
+            }
+            Some(append_rows_response::Response::Error(status)) => Err(BigquerySinkError::Append {
+                code: status.code,
+                message: status.message,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    async fn commit(&self, stream: &str) -> Result<(), BigquerySinkError> {
+        let response = self
+            .client
+            .clone()
+            .batch_commit_write_streams(proto::BatchCommitWriteStreamsRequest {
+                parent: self.table.clone(),
+                write_streams: vec![stream.to_owned()],
+            })
+            .await
+            .context(RequestSnafu)?
+            .into_inner();
+
+        // A stream already committed was committed by a previous attempt whose response was lost.
+        let already_committed = proto::storage_error::StorageErrorCode::StreamAlreadyCommitted;
+        match response
+            .stream_errors
+            .into_iter()
+            .find(|error| error.code != already_committed as i32)
+        {
+            Some(error) => Err(BigquerySinkError::Commit {
+                message: error.error_message,
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+impl tower::Service<BigqueryRequest> for BigqueryService {
+    type Response = BigqueryResponse;
+    type Error = crate::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: BigqueryRequest) -> Self::Future {
+        let service = self.clone();
+        Box::pin(async move { service.write(request).await.map_err(Into::into) })
+    }
+}
+
+/// Gets the default stream of the table, to which rows are committed as soon as they are written.
+pub fn default_stream(table: &str) -> String {
+    format!("{}/streams/_default", table)
+}
+
+#[derive(Debug, Clone)]
+pub struct BigqueryRetryLogic;
+
+impl RetryLogic for BigqueryRetryLogic {
+    type Error = BigquerySinkError;
+    type Response = BigqueryResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            BigquerySinkError::Request { source } => is_retriable_code(source.code()),
+            BigquerySinkError::Append { code, .. } => is_retriable_code(Code::from_i32(*code)),
+            BigquerySinkError::NoResponse => true,
+            _ => false,
+        }
+    }
+}
+
+fn is_retriable_code(code: Code) -> bool {
+    use tonic::Code::*;
+
+    !matches!(
+        code,
+        // List taken from
+        //
+        // <https://github.com/grpc/grpc/blob/ed1b20777c69bd47e730a63271eafc1b299f6ca0/doc/statuscodes.md>
+        NotFound
+            | InvalidArgument
+            | AlreadyExists
+            | PermissionDenied
+            | OutOfRange
+            | Unimplemented
+            | Unauthenticated
+    )
+}
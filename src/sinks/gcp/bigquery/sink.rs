@@ -0,0 +1,72 @@
+use std::{fmt, sync::Arc};
+
+use async_trait::async_trait;
+use futures::{stream::BoxStream, StreamExt};
+use tower::Service;
+use vector_core::{
+    stream::{BatcherSettings, DriverResponse},
+    ByteSizeOf,
+};
+
+use super::{schema::RowSchema, service::BigqueryRequest};
+use crate::{
+    event::{Event, EventFinalizers, Finalizable},
+    sinks::util::{SinkBuilderExt, StreamSink},
+};
+
+struct RowData {
+    byte_size: usize,
+    finalizers: EventFinalizers,
+    row: Vec<u8>,
+}
+
+pub struct BigquerySink<S> {
+    pub batch_settings: BatcherSettings,
+    pub schema: Arc<RowSchema>,
+    pub service: S,
+}
+
+impl<S> BigquerySink<S>
+where
+    S: Service<BigqueryRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let schema = self.schema;
+        input
+            .map(|event| {
+                let mut log = event.into_log();
+                RowData {
+                    byte_size: log.size_of(),
+                    finalizers: log.take_finalizers(),
+                    row: schema.encode(&log),
+                }
+            })
+            .batched(self.batch_settings.into_reducer_config(
+                |data: &RowData| data.row.len(),
+                |request: &mut BigqueryRequest, data: RowData| {
+                    request.events_byte_size += data.byte_size;
+                    request.finalizers.merge(data.finalizers);
+                    request.rows.push(data.row);
+                },
+            ))
+            .into_driver(self.service)
+            .run()
+            .await
+    }
+}
+
+#[async_trait]
+impl<S> StreamSink<Event> for BigquerySink<S>
+where
+    S: Service<BigqueryRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
@@ -0,0 +1,246 @@
+//! This sink sends data to the Google Chronicle ingestion API, either as unstructured log entries
+//! parsed by Chronicle, or as events already in the Unified Data Model (UDM).
+//! See https://cloud.google.com/chronicle/docs/reference/ingestion-api for more information.
+use codecs::encoding::SerializerConfig;
+use goauth::scopes::Scope;
+use indoc::indoc;
+use vector_config::configurable_component;
+use vector_core::{
+    config::{AcknowledgementsConfig, Input},
+    sink::VectorSink,
+};
+
+use super::chronicle_unstructured::{
+    build_healthcheck, build_sink, create_endpoint, ChronicleUnstructuredDefaultBatchSettings,
+    Region, RequestSettings,
+};
+use crate::{
+    codecs::EncodingConfig,
+    config::{GenerateConfig, SinkConfig, SinkContext},
+    gcp::GcpAuthConfig,
+    http::HttpClient,
+    sinks::{
+        util::{partitioner::KeyPartitioner, BatchConfig, TowerRequestConfig},
+        Healthcheck,
+    },
+    template::Template,
+    tls::{TlsConfig, TlsSettings},
+};
+
+/// The format of the events sent to Chronicle.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ChronicleFormat {
+    /// The events are sent as unstructured log entries, which are parsed by Chronicle according to their `log_type`.
+    ///
+    /// The events are encoded with `encoding.codec` to build the text of the entries.
+    #[derivative(Default)]
+    Unstructured,
+
+    /// The events are sent as [UDM events][udm], which must already be in the Unified Data Model.
+    ///
+    /// The events are encoded with `encoding.codec`, which must be `json`.
+    ///
+    /// [udm]: https://cloud.google.com/chronicle/docs/reference/udm-field-list
+    Udm,
+}
+
+impl ChronicleFormat {
+    /// The path of the endpoint of the ingestion API the events are sent to.
+    pub(super) const fn path(self) -> &'static str {
+        match self {
+            Self::Unstructured => "v2/unstructuredlogentries:batchCreate",
+            Self::Udm => "v2/udmevents:batchCreate",
+        }
+    }
+}
+
+/// Configuration for the `gcp_chronicle` sink.
+#[configurable_component(sink("gcp_chronicle"))]
+#[derive(Clone, Debug)]
+pub struct ChronicleConfig {
+    /// The endpoint to send data to.
+    pub endpoint: Option<String>,
+
+    #[configurable(derived)]
+    pub region: Option<Region>,
+
+    /// The Unique identifier (UUID) corresponding to the Chronicle instance.
+    #[configurable(validation(format = "uuid"))]
+    pub customer_id: String,
+
+    #[serde(flatten)]
+    pub auth: GcpAuthConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub format: ChronicleFormat,
+
+    /// The type of log entries in a request.
+    ///
+    /// Required when `format` is `unstructured`. This must be one of the [supported log
+    /// types][unstructured_log_types_doc], otherwise Chronicle will reject the entry with an error.
+    ///
+    /// [unstructured_log_types_doc]: https://cloud.google.com/chronicle/docs/ingestion/parser-list/supported-default-parsers
+    #[configurable(metadata(templateable))]
+    pub log_type: Option<Template>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<ChronicleUnstructuredDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    pub encoding: EncodingConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+impl GenerateConfig for ChronicleConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(indoc! {r#"
+            credentials_path = "/path/to/credentials.json"
+            customer_id = "customer_id"
+            region = "us"
+            log_type = "log_type"
+            encoding.codec = "text"
+        "#})
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for ChronicleConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let partitioner = self.key_partitioner()?;
+        let request_settings =
+            RequestSettings::new(&self.customer_id, self.format, &self.encoding)?;
+
+        let creds = self.auth.build(Scope::MalachiteIngestion).await?;
+
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls, cx.proxy())?;
+
+        let endpoint = create_endpoint(self.endpoint.as_deref(), self.region, self.format.path())?;
+
+        // For the healthcheck we see if we can fetch the list of available log types.
+        let healthcheck_endpoint =
+            create_endpoint(self.endpoint.as_deref(), self.region, "v2/logtypes")?;
+
+        let healthcheck = build_healthcheck(client.clone(), &healthcheck_endpoint, creds.clone())?;
+        let sink = build_sink(
+            client,
+            endpoint,
+            creds,
+            &self.request,
+            &self.batch,
+            partitioner,
+            request_settings,
+        )?;
+
+        Ok((sink, healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+impl ChronicleConfig {
+    /// Unstructured log entries are batched by log type, as each request holds entries of a single
+    /// type, while UDM events all go in the same batches.
+    fn key_partitioner(&self) -> crate::Result<KeyPartitioner> {
+        match (self.format, &self.log_type) {
+            (ChronicleFormat::Unstructured, Some(log_type)) => {
+                Ok(KeyPartitioner::new(log_type.clone()))
+            }
+            (ChronicleFormat::Unstructured, None) => {
+                Err("`log_type` must be set when `format` is `unstructured`".into())
+            }
+            (ChronicleFormat::Udm, _) => {
+                if !matches!(self.encoding.config(), SerializerConfig::Json) {
+                    return Err("`encoding.codec` must be `json` when `format` is `udm`".into());
+                }
+                Ok(KeyPartitioner::new(Template::try_from("udm")?))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ChronicleConfig>();
+    }
+
+    fn config(toml: &str) -> ChronicleConfig {
+        toml::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn unstructured_requires_log_type() {
+        let config = config(indoc! {r#"
+            customer_id = "customer_id"
+            region = "eu"
+            encoding.codec = "text"
+        "#});
+
+        assert!(config.key_partitioner().is_err());
+    }
+
+    #[test]
+    fn udm_requires_json() {
+        let config = config(indoc! {r#"
+            customer_id = "customer_id"
+            region = "eu"
+            format = "udm"
+            encoding.codec = "text"
+        "#});
+
+        assert!(config.key_partitioner().is_err());
+    }
+
+    #[test]
+    fn endpoints() {
+        assert_eq!(
+            create_endpoint(None, Some(Region::Frankfurt), ChronicleFormat::Udm.path()).unwrap(),
+            "https://europe-west3-malachiteingestion-pa.googleapis.com/v2/udmevents:batchCreate"
+        );
+        assert_eq!(
+            create_endpoint(
+                Some("http://localhost:8080/"),
+                None,
+                ChronicleFormat::Unstructured.path()
+            )
+            .unwrap(),
+            "http://localhost:8080/v2/unstructuredlogentries:batchCreate"
+        );
+        assert!(create_endpoint(
+            Some("http://localhost:8080"),
+            Some(Region::Us),
+            ChronicleFormat::Udm.path()
+        )
+        .is_err());
+    }
+}
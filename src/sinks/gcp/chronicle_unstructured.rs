@@ -19,6 +19,7 @@ use vector_core::{
     sink::VectorSink,
 };
 
+use super::chronicle::ChronicleFormat;
 use crate::{
     codecs::{self, EncodingConfig},
     config::{log_schema, GenerateConfig, SinkConfig, SinkContext},
@@ -66,6 +67,45 @@ pub enum Region {
 
     /// APAC region.
     Asia,
+
+    /// London region (`europe-west2`).
+    London,
+
+    /// Frankfurt region (`europe-west3`).
+    Frankfurt,
+
+    /// Zurich region (`europe-west6`).
+    Zurich,
+
+    /// Paris region (`europe-west9`).
+    Paris,
+
+    /// Turin region (`europe-west12`).
+    Turin,
+
+    /// Tel Aviv region (`me-west1`).
+    TelAviv,
+
+    /// Doha region (`me-central1`).
+    Doha,
+
+    /// Dammam region (`me-central2`).
+    Dammam,
+
+    /// Sydney region (`australia-southeast1`).
+    Sydney,
+
+    /// Tokyo region (`asia-northeast1`).
+    Tokyo,
+
+    /// Mumbai region (`asia-south1`).
+    Mumbai,
+
+    /// Toronto region (`northamerica-northeast2`).
+    Toronto,
+
+    /// São Paulo region (`southamerica-east1`).
+    SaoPaulo,
 }
 
 impl Region {
@@ -75,6 +115,21 @@ impl Region {
             Region::Eu => "https://europe-malachiteingestion-pa.googleapis.com",
             Region::Us => "https://malachiteingestion-pa.googleapis.com",
             Region::Asia => "https://asia-southeast1-malachiteingestion-pa.googleapis.com",
+            Region::London => "https://europe-west2-malachiteingestion-pa.googleapis.com",
+            Region::Frankfurt => "https://europe-west3-malachiteingestion-pa.googleapis.com",
+            Region::Zurich => "https://europe-west6-malachiteingestion-pa.googleapis.com",
+            Region::Paris => "https://europe-west9-malachiteingestion-pa.googleapis.com",
+            Region::Turin => "https://europe-west12-malachiteingestion-pa.googleapis.com",
+            Region::TelAviv => "https://me-west1-malachiteingestion-pa.googleapis.com",
+            Region::Doha => "https://me-central1-malachiteingestion-pa.googleapis.com",
+            Region::Dammam => "https://me-central2-malachiteingestion-pa.googleapis.com",
+            Region::Sydney => "https://australia-southeast1-malachiteingestion-pa.googleapis.com",
+            Region::Tokyo => "https://asia-northeast1-malachiteingestion-pa.googleapis.com",
+            Region::Mumbai => "https://asia-south1-malachiteingestion-pa.googleapis.com",
+            Region::Toronto => {
+                "https://northamerica-northeast2-malachiteingestion-pa.googleapis.com"
+            }
+            Region::SaoPaulo => "https://southamerica-east1-malachiteingestion-pa.googleapis.com",
         }
     }
 }
@@ -188,13 +243,31 @@ impl SinkConfig for ChronicleUnstructuredConfig {
         let tls = TlsSettings::from_options(&self.tls)?;
         let client = HttpClient::new(tls, cx.proxy())?;
 
-        let endpoint = self.create_endpoint("v2/unstructuredlogentries:batchCreate")?;
+        let endpoint = create_endpoint(
+            self.endpoint.as_deref(),
+            self.region,
+            ChronicleFormat::Unstructured.path(),
+        )?;
 
         // For the healthcheck we see if we can fetch the list of available log types.
-        let healthcheck_endpoint = self.create_endpoint("v2/logtypes")?;
+        let healthcheck_endpoint =
+            create_endpoint(self.endpoint.as_deref(), self.region, "v2/logtypes")?;
 
         let healthcheck = build_healthcheck(client.clone(), &healthcheck_endpoint, creds.clone())?;
-        let sink = self.build_sink(client, endpoint, creds)?;
+        let request_settings = RequestSettings::new(
+            &self.customer_id,
+            ChronicleFormat::Unstructured,
+            &self.encoding,
+        )?;
+        let sink = build_sink(
+            client,
+            endpoint,
+            creds,
+            &self.request,
+            &self.batch,
+            KeyPartitioner::new(self.log_type.clone()),
+            request_settings,
+        )?;
 
         Ok((sink, healthcheck))
     }
@@ -208,51 +281,49 @@ impl SinkConfig for ChronicleUnstructuredConfig {
     }
 }
 
-impl ChronicleUnstructuredConfig {
-    fn build_sink(
-        &self,
-        client: HttpClient,
-        base_url: String,
-        creds: GcpAuthenticator,
-    ) -> crate::Result<VectorSink> {
-        use crate::sinks::util::service::ServiceBuilderExt;
-
-        let request = self.request.unwrap_with(&TowerRequestConfig {
-            rate_limit_num: Some(1000),
-            ..Default::default()
-        });
-
-        let batch_settings = self.batch.into_batcher_settings()?;
-
-        let partitioner = self.key_partitioner()?;
+/// Builds the sink sending the batches of each partition to a Chronicle ingestion endpoint.
+pub(super) fn build_sink(
+    client: HttpClient,
+    base_url: String,
+    creds: GcpAuthenticator,
+    request: &TowerRequestConfig,
+    batch: &BatchConfig<ChronicleUnstructuredDefaultBatchSettings>,
+    partitioner: KeyPartitioner,
+    request_settings: RequestSettings,
+) -> crate::Result<VectorSink> {
+    use crate::sinks::util::service::ServiceBuilderExt;
 
-        let svc = ServiceBuilder::new()
-            .settings(request, GcsRetryLogic)
-            .service(ChronicleService::new(client, base_url, creds));
+    let request = request.unwrap_with(&TowerRequestConfig {
+        rate_limit_num: Some(1000),
+        ..Default::default()
+    });
 
-        let request_settings = RequestSettings::new(self)?;
+    let batch_settings = batch.into_batcher_settings()?;
 
-        let sink = GcsSink::new(svc, request_settings, partitioner, batch_settings);
+    let svc = ServiceBuilder::new()
+        .settings(request, GcsRetryLogic)
+        .service(ChronicleService::new(client, base_url, creds));
 
-        Ok(VectorSink::from_event_streamsink(sink))
-    }
+    let sink = GcsSink::new(svc, request_settings, partitioner, batch_settings);
 
-    fn key_partitioner(&self) -> crate::Result<KeyPartitioner> {
-        Ok(KeyPartitioner::new(self.log_type.clone()))
-    }
+    Ok(VectorSink::from_event_streamsink(sink))
+}
 
-    fn create_endpoint(&self, path: &str) -> Result<String, ChronicleError> {
-        Ok(format!(
-            "{}/{}",
-            match (&self.endpoint, self.region) {
-                (Some(endpoint), None) => endpoint.trim_end_matches('/'),
-                (None, Some(region)) => region.endpoint(),
-                (Some(_), Some(_)) => return Err(ChronicleError::BothRegionAndEndpoint),
-                (None, None) => return Err(ChronicleError::RegionOrEndpoint),
-            },
-            path
-        ))
-    }
+pub(super) fn create_endpoint(
+    endpoint: Option<&str>,
+    region: Option<Region>,
+    path: &str,
+) -> Result<String, ChronicleError> {
+    Ok(format!(
+        "{}/{}",
+        match (endpoint, region) {
+            (Some(endpoint), None) => endpoint.trim_end_matches('/'),
+            (None, Some(region)) => region.endpoint(),
+            (Some(_), Some(_)) => return Err(ChronicleError::BothRegionAndEndpoint),
+            (None, None) => return Err(ChronicleError::RegionOrEndpoint),
+        },
+        path
+    ))
 }
 
 #[derive(Clone, Debug)]
@@ -269,8 +340,9 @@ impl Finalizable for ChronicleRequest {
 }
 
 #[derive(Clone, Debug)]
-struct ChronicleEncoder {
+pub(super) struct ChronicleEncoder {
     customer_id: String,
+    format: ChronicleFormat,
     encoder: codecs::Encoder<()>,
     transformer: codecs::Transformer,
 }
@@ -295,6 +367,11 @@ impl Encoder<(String, Vec<Event>)> for ChronicleEncoder {
                 self.transformer.transform(&mut event);
                 encoder.encode(event, &mut bytes).ok()?;
 
+                if self.format == ChronicleFormat::Udm {
+                    // The events are already in the UDM schema, and encoded as JSON objects.
+                    return serde_json::from_slice(&bytes).ok();
+                }
+
                 let mut value = json!({
                     "log_text": String::from_utf8_lossy(&bytes),
                 });
@@ -311,11 +388,17 @@ impl Encoder<(String, Vec<Event>)> for ChronicleEncoder {
             })
             .collect::<Vec<_>>();
 
-        let json = json!({
-            "customer_id": self.customer_id,
-            "log_type": partition_key,
-            "entries": events,
-        });
+        let json = match self.format {
+            ChronicleFormat::Unstructured => json!({
+                "customer_id": self.customer_id,
+                "log_type": partition_key,
+                "entries": events,
+            }),
+            ChronicleFormat::Udm => json!({
+                "customer_id": self.customer_id,
+                "events": events,
+            }),
+        };
 
         let size = as_tracked_write::<_, _, io::Error>(writer, &json, |writer, json| {
             serde_json::to_writer(writer, json)?;
@@ -330,11 +413,11 @@ impl Encoder<(String, Vec<Event>)> for ChronicleEncoder {
 // request. All possible values are pre-computed for direct use in
 // producing a request.
 #[derive(Clone, Debug)]
-struct RequestSettings {
+pub(super) struct RequestSettings {
     encoder: ChronicleEncoder,
 }
 
-struct ChronicleRequestPayload {
+pub(super) struct ChronicleRequestPayload {
     bytes: Bytes,
 }
 
@@ -393,12 +476,17 @@ impl RequestBuilder<(String, Vec<Event>)> for RequestSettings {
 }
 
 impl RequestSettings {
-    fn new(config: &ChronicleUnstructuredConfig) -> crate::Result<Self> {
-        let transformer = config.encoding.transformer();
-        let serializer = config.encoding.config().build()?;
+    pub(super) fn new(
+        customer_id: &str,
+        format: ChronicleFormat,
+        encoding: &EncodingConfig,
+    ) -> crate::Result<Self> {
+        let transformer = encoding.transformer();
+        let serializer = encoding.config().build()?;
         let encoder = crate::codecs::Encoder::<()>::new(serializer);
         let encoder = ChronicleEncoder {
-            customer_id: config.customer_id.clone(),
+            customer_id: customer_id.to_string(),
+            format,
             encoder,
             transformer,
         };
@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 use vector_config::configurable_component;
 
+#[cfg(feature = "sinks-gcp_bigquery")]
+pub mod bigquery;
+pub mod chronicle;
 pub mod chronicle_unstructured;
 pub mod cloud_storage;
 pub mod pubsub;
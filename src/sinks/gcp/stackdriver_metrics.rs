@@ -211,6 +211,7 @@ impl HttpSink for HttpEventSink {
             .tags
             .unwrap_or_default()
             .into_iter()
+            .map(|(key, value)| (key.into(), value))
             .collect::<std::collections::HashMap<_, _>>();
 
         let series = gcp::GcpSeries {
@@ -1,19 +1,28 @@
+use std::collections::BTreeMap;
+
 use bytes::Bytes;
-use futures::{FutureExt, SinkExt};
+use futures::{stream, FutureExt, SinkExt, StreamExt};
 use http::{Request, StatusCode, Uri};
 use serde_json::json;
 use vector_common::sensitive_string::SensitiveString;
 use vector_config::configurable_component;
+use vector_core::event::{EventMetadata, TraceEvent};
 
 use crate::{
     codecs::Transformer,
-    config::{log_schema, AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
-    event::{Event, Value},
+    conditions::{AnyCondition, Condition},
+    config::{
+        log_schema, AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig,
+        SinkContext,
+    },
+    event::{Event, LogEvent, Value},
     http::HttpClient,
+    internal_events::{HoneycombMarkerError, TemplateRenderingError},
     sinks::util::{
         http::{BatchedHttpSink, HttpEventEncoder, HttpSink},
         BatchConfig, BoxedRawValue, JsonArrayBuffer, SinkBatchSettings, TowerRequestConfig,
     },
+    template::Template,
 };
 
 /// Configuration for the `honeycomb` sink.
@@ -31,6 +40,15 @@ pub struct HoneycombConfig {
     // but this limits us in how we can do our healthcheck.
     dataset: String,
 
+    /// Whether to create the dataset when the sink starts, if it does not exist yet.
+    ///
+    /// This requires the team key to have the permission to create datasets.
+    #[serde(default)]
+    auto_create_dataset: bool,
+
+    #[configurable(derived)]
+    markers: Option<HoneycombMarkersConfig>,
+
     #[configurable(derived)]
     #[serde(default)]
     batch: BatchConfig<HoneycombDefaultBatchSettings>,
@@ -55,8 +73,36 @@ pub struct HoneycombConfig {
     acknowledgements: AcknowledgementsConfig,
 }
 
+/// Configuration of the [markers][markers] sent to the dataset.
+///
+/// Markers annotate the graphs of the dataset with events such as deploys. The events matching
+/// the condition are sent as markers, in addition to being sent as events.
+///
+/// [markers]: https://docs.honeycomb.io/working-with-your-data/markers/
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct HoneycombMarkersConfig {
+    /// The condition that events must match to be sent as markers.
+    condition: AnyCondition,
+
+    /// The message of the markers.
+    #[configurable(metadata(templateable))]
+    message: Option<Template>,
+
+    /// The type of the markers, such as `deploy`.
+    ///
+    /// Markers of the same type are displayed alike.
+    #[configurable(metadata(templateable))]
+    #[serde(rename = "type")]
+    marker_type: Option<Template>,
+
+    /// The URL the markers link to.
+    #[configurable(metadata(templateable))]
+    url: Option<Template>,
+}
+
 fn default_endpoint() -> String {
-    "https://api.honeycomb.io/1/batch".to_string()
+    "https://api.honeycomb.io".to_string()
 }
 
 #[derive(Clone, Copy, Debug, Default)]
@@ -91,6 +137,16 @@ impl SinkConfig for HoneycombConfig {
 
         let client = HttpClient::new(None, cx.proxy())?;
 
+        if self.auto_create_dataset {
+            create_dataset(self, &client).await?;
+        }
+
+        let markers = self
+            .markers
+            .as_ref()
+            .map(|markers| markers.build(self, client.clone()))
+            .transpose()?;
+
         let sink = BatchedHttpSink::new(
             self.clone(),
             buffer,
@@ -98,7 +154,24 @@ impl SinkConfig for HoneycombConfig {
             batch_settings.timeout,
             client.clone(),
         )
-        .sink_map_err(|error| error!(message = "Fatal honeycomb sink error.", %error));
+        .sink_map_err(|error| error!(message = "Fatal honeycomb sink error.", %error))
+        // Traces are sent as one event per span.
+        .with_flat_map(|event| {
+            let events = match event {
+                Event::Trace(trace) => trace_to_span_events(trace),
+                event => vec![event],
+            };
+            stream::iter(events).map(Ok)
+        })
+        .with(move |event: Event| {
+            let markers = markers.clone();
+            async move {
+                Ok::<_, ()>(match markers {
+                    Some(markers) => markers.send_if_matching(event).await,
+                    None => event,
+                })
+            }
+        });
 
         let healthcheck = healthcheck(self.clone(), client).boxed();
 
@@ -106,7 +179,7 @@ impl SinkConfig for HoneycombConfig {
     }
 
     fn input(&self) -> Input {
-        Input::log()
+        Input::new(DataType::Log | DataType::Trace)
     }
 
     fn acknowledgements(&self) -> &AcknowledgementsConfig {
@@ -114,6 +187,176 @@ impl SinkConfig for HoneycombConfig {
     }
 }
 
+/// Converts the spans of a trace to events in the [span format][span_format] of Honeycomb.
+///
+/// The spans share the metadata of the trace, so that it is only acknowledged once all of them
+/// have been delivered.
+///
+/// [span_format]: https://docs.honeycomb.io/getting-data-in/tracing/send-trace-data/#manual-tracing
+fn trace_to_span_events(trace: TraceEvent) -> Vec<Event> {
+    let (mut fields, metadata) = trace.into_parts();
+    match fields.remove("spans") {
+        Some(Value::Array(spans)) => spans
+            .into_iter()
+            .filter_map(|span| match span {
+                Value::Object(span) => Some(span_to_log(span, metadata.clone()).into()),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn span_to_log(mut span: BTreeMap<String, Value>, metadata: EventMetadata) -> LogEvent {
+    let mut log = LogEvent::new_with_metadata(metadata);
+
+    if let Some(start) = span.remove("start") {
+        log.insert(log_schema().timestamp_key(), start);
+    }
+    if let Some(trace_id) = span.remove("trace_id") {
+        log.insert("trace.trace_id", trace_id);
+    }
+    if let Some(span_id) = span.remove("span_id") {
+        log.insert("trace.span_id", span_id);
+    }
+    // Honeycomb identifies root spans by their lack of parent.
+    match span.remove("parent_id") {
+        None | Some(Value::Integer(0)) | Some(Value::Null) => {}
+        Some(parent_id) => {
+            log.insert("trace.parent_id", parent_id);
+        }
+    }
+    if let Some(service) = span.remove("service") {
+        log.insert("service.name", service);
+    }
+    if let Some(Value::Integer(duration)) = span.remove("duration") {
+        log.insert("duration_ms", duration as f64 / 1_000_000.0);
+    }
+    if let Some(Value::Integer(error)) = span.remove("error") {
+        log.insert("error", error != 0);
+    }
+    // The tags and the measurements of the span are fields of the span in Honeycomb.
+    for nested in ["meta", "metrics"] {
+        if let Some(Value::Object(fields)) = span.remove(nested) {
+            for (key, value) in fields {
+                log.insert(key.as_str(), value);
+            }
+        }
+    }
+    for (key, value) in span {
+        log.insert(key.as_str(), value);
+    }
+
+    log
+}
+
+/// The markers sent to the dataset for the events matching their condition.
+#[derive(Clone)]
+struct HoneycombMarkers {
+    condition: Condition,
+    message: Option<Template>,
+    marker_type: Option<Template>,
+    url: Option<Template>,
+    uri: Uri,
+    api_key: SensitiveString,
+    client: HttpClient,
+}
+
+impl HoneycombMarkersConfig {
+    fn build(
+        &self,
+        config: &HoneycombConfig,
+        client: HttpClient,
+    ) -> crate::Result<HoneycombMarkers> {
+        Ok(HoneycombMarkers {
+            condition: self.condition.build(&Default::default())?,
+            message: self.message.clone(),
+            marker_type: self.marker_type.clone(),
+            url: self.url.clone(),
+            uri: config.build_uri("markers")?,
+            api_key: config.api_key.clone(),
+            client,
+        })
+    }
+}
+
+impl HoneycombMarkers {
+    /// Sends a marker for the event if it matches the condition, and gives the event back.
+    async fn send_if_matching(&self, event: Event) -> Event {
+        let (matches, event) = self.condition.check(event);
+        if matches {
+            if let Err(error) = self.send(&event).await {
+                emit!(HoneycombMarkerError { error });
+            }
+        }
+        event
+    }
+
+    async fn send(&self, event: &Event) -> crate::Result<()> {
+        let mut marker = serde_json::Map::new();
+        for (field, template) in [
+            ("message", &self.message),
+            ("type", &self.marker_type),
+            ("url", &self.url),
+        ] {
+            if let Some(template) = template {
+                let value = template.render_string(event).map_err(|error| {
+                    emit!(TemplateRenderingError {
+                        error,
+                        field: Some(field),
+                        drop_event: false,
+                    });
+                    format!("Failed to render the `{}` of the marker.", field)
+                })?;
+                marker.insert(field.to_string(), value.into());
+            }
+        }
+        if let Some(Value::Timestamp(timestamp)) = event
+            .maybe_as_log()
+            .and_then(|log| log.get(log_schema().timestamp_key()))
+        {
+            marker.insert("start_time".to_string(), timestamp.timestamp().into());
+        }
+
+        let request = Request::post(self.uri.clone())
+            .header("X-Honeycomb-Team", self.api_key.inner())
+            .header("Content-Type", "application/json")
+            .body(hyper::Body::from(serde_json::to_vec(&marker)?))?;
+        let response = self.client.send(request).await?;
+        let status = response.status();
+        if status.is_success() {
+            Ok(())
+        } else {
+            Err(format!("Server returned unexpected error status: {}", status).into())
+        }
+    }
+}
+
+/// Creates the dataset, which is fine if it already exists.
+async fn create_dataset(config: &HoneycombConfig, client: &HttpClient) -> crate::Result<()> {
+    let request = Request::post(config.build_uri("datasets")?)
+        .header("X-Honeycomb-Team", config.api_key.inner())
+        .header("Content-Type", "application/json")
+        .body(hyper::Body::from(
+            json!({ "name": config.dataset }).to_string(),
+        ))?;
+    let response = client.send(request).await?;
+
+    let status = response.status();
+    if status.is_success() || status == StatusCode::CONFLICT {
+        Ok(())
+    } else {
+        let body = hyper::body::to_bytes(response.into_body()).await?;
+        Err(format!(
+            "Failed to create dataset {:?}, server returned status: {} body: {}",
+            config.dataset,
+            status,
+            String::from_utf8_lossy(&body[..])
+        )
+        .into())
+    }
+}
+
 pub struct HoneycombEventEncoder {
     transformer: Transformer,
 }
@@ -152,7 +395,7 @@ impl HttpSink for HoneycombConfig {
     }
 
     async fn build_request(&self, events: Self::Output) -> crate::Result<Request<Bytes>> {
-        let uri = self.build_uri();
+        let uri = self.build_uri("batch")?;
         let request = Request::post(uri).header("X-Honeycomb-Team", self.api_key.inner());
         let body = crate::serde::json::to_bytes(&events).unwrap().freeze();
 
@@ -161,10 +404,17 @@ impl HttpSink for HoneycombConfig {
 }
 
 impl HoneycombConfig {
-    fn build_uri(&self) -> Uri {
-        let uri = format!("{}/{}", self.endpoint, self.dataset);
+    /// Builds the URI of the given resource of the dataset, such as `batch` or `markers`.
+    ///
+    /// The `datasets` resource is not specific to a dataset.
+    fn build_uri(&self, resource: &str) -> crate::Result<Uri> {
+        let endpoint = self.endpoint.trim_end_matches('/');
+        let uri = match resource {
+            "datasets" => format!("{}/1/datasets", endpoint),
+            resource => format!("{}/1/{}/{}", endpoint, resource, self.dataset),
+        };
 
-        uri.parse::<Uri>().expect("This should be a valid uri")
+        Ok(uri.parse::<Uri>()?)
     }
 }
 
@@ -173,7 +423,6 @@ async fn healthcheck(config: HoneycombConfig, client: HttpClient) -> crate::Resu
         .build_request(Vec::new())
         .await?
         .map(hyper::Body::from);
-
     let res = client.send(req).await?;
 
     let status = res.status();
@@ -207,8 +456,11 @@ async fn healthcheck(config: HoneycombConfig, client: HttpClient) -> crate::Resu
 }
 #[cfg(test)]
 mod test {
+    use std::collections::BTreeMap;
+
+    use chrono::{TimeZone, Utc};
     use futures::{future::ready, stream};
-    use vector_core::event::{Event, LogEvent};
+    use vector_core::event::{Event, LogEvent, TraceEvent, Value};
 
     use crate::{
         config::{GenerateConfig, SinkConfig, SinkContext},
@@ -218,7 +470,7 @@ mod test {
         },
     };
 
-    use super::HoneycombConfig;
+    use super::{trace_to_span_events, HoneycombConfig};
 
     #[test]
     fn generate_config() {
@@ -240,4 +492,54 @@ mod test {
         let event = Event::Log(LogEvent::from("simple message"));
         run_and_assert_sink_compliance(sink, stream::once(ready(event)), &HTTP_SINK_TAGS).await;
     }
+
+    fn span(span_id: i64, parent_id: i64) -> Value {
+        Value::from(BTreeMap::from([
+            ("service".to_string(), Value::from("checkout")),
+            ("name".to_string(), Value::from("http.request")),
+            ("resource".to_string(), Value::from("GET /cart")),
+            ("trace_id".to_string(), Value::from(123)),
+            ("span_id".to_string(), Value::from(span_id)),
+            ("parent_id".to_string(), Value::from(parent_id)),
+            (
+                "start".to_string(),
+                Value::from(Utc.ymd(2022, 10, 1).and_hms(12, 0, 0)),
+            ),
+            ("duration".to_string(), Value::from(1_500_000)),
+            ("error".to_string(), Value::from(1)),
+            (
+                "meta".to_string(),
+                Value::from(BTreeMap::from([(
+                    "http.method".to_string(),
+                    Value::from("GET"),
+                )])),
+            ),
+        ]))
+    }
+
+    #[test]
+    fn converts_spans() {
+        let mut trace = TraceEvent::default();
+        trace.insert("spans", Value::Array(vec![span(1, 0), span(2, 1)]));
+
+        let events = trace_to_span_events(trace);
+        assert_eq!(events.len(), 2);
+
+        let root = events[0].as_log();
+        assert_eq!(root["trace.trace_id"], 123.into());
+        assert_eq!(root["trace.span_id"], 1.into());
+        assert!(root.get("trace.parent_id").is_none());
+        assert_eq!(root["service.name"], "checkout".into());
+        assert_eq!(root["name"], "http.request".into());
+        assert_eq!(root["duration_ms"], 1.5.into());
+        assert_eq!(root["error"], true.into());
+        assert_eq!(root["http.method"], "GET".into());
+        assert_eq!(
+            root["timestamp"],
+            Utc.ymd(2022, 10, 1).and_hms(12, 0, 0).into()
+        );
+
+        let child = events[1].as_log();
+        assert_eq!(child["trace.parent_id"], 1.into());
+    }
 }
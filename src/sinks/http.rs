@@ -2,7 +2,6 @@ use std::io::Write;
 
 use bytes::{BufMut, Bytes, BytesMut};
 use codecs::encoding::{CharacterDelimitedEncoder, Framer, Serializer};
-use flate2::write::{GzEncoder, ZlibEncoder};
 use futures::{future, FutureExt, SinkExt};
 use http::{
     header::{self, HeaderName, HeaderValue},
@@ -19,12 +18,14 @@ use crate::{
     config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
     event::Event,
     http::{Auth, HttpClient, MaybeAuth},
+    internal_events::{HttpSinkInvalidRequestPart, TemplateRenderingError},
     sinks::util::{
         self,
-        http::{BatchedHttpSink, HttpEventEncoder, RequestConfig},
-        BatchConfig, Buffer, Compression, RealtimeSizeBasedDefaultBatchSettings,
-        TowerRequestConfig, UriSerde,
+        http::{HttpEventEncoder, PartitionHttpSink, RequestConfig},
+        BatchConfig, Buffer, Compression, Compressor, PartitionBuffer, PartitionInnerBuffer,
+        RealtimeSizeBasedDefaultBatchSettings, TowerRequestConfig, UriSerde,
     },
+    template::Template,
     tls::{TlsConfig, TlsSettings},
 };
 
@@ -40,6 +41,13 @@ enum BuildError {
         value: String,
         source: header::InvalidHeaderValue,
     },
+    #[snafu(display("{}: {}", source, uri))]
+    InvalidUri {
+        uri: String,
+        source: http::uri::InvalidUri,
+    },
+    #[snafu(display("unsupported HTTP method: {}", method))]
+    InvalidMethod { method: String },
 }
 
 /// Configuration for the `http` sink.
@@ -50,10 +58,18 @@ pub struct HttpSinkConfig {
     /// The full URI to make HTTP requests to.
     ///
     /// This should include the protocol and host, but can also include the port, path, and any other valid part of a URI.
-    pub uri: UriSerde,
+    ///
+    /// The URI can refer to event fields, such as `{{ id }}`, in which case events are batched by their rendered URI,
+    /// which must not include credentials.
+    #[configurable(metadata(templateable))]
+    pub uri: String,
 
     /// The HTTP method to use when making the request.
-    pub method: Option<HttpMethod>,
+    ///
+    /// One of `get`, `head`, `post`, `put`, `delete`, `options`, `trace`, or `patch`, in any case.
+    /// When the method is a template, events are batched by their rendered method. Defaults to `post`.
+    #[configurable(metadata(templateable))]
+    pub method: Option<Template>,
 
     #[configurable(derived)]
     pub auth: Option<Auth>,
@@ -77,6 +93,13 @@ pub struct HttpSinkConfig {
     #[serde(default)]
     pub request: RequestConfig,
 
+    /// Whether to send each event in its own request.
+    ///
+    /// The body of each request is then the encoded event alone, rather than a JSON array with the
+    /// `json` codec, as expected by webhooks. The batch size options are ignored.
+    #[serde(default)]
+    pub request_per_event: bool,
+
     #[configurable(derived)]
     pub tls: Option<TlsConfig>,
 
@@ -89,44 +112,6 @@ pub struct HttpSinkConfig {
     pub acknowledgements: AcknowledgementsConfig,
 }
 
-/// HTTP method.
-///
-/// A subset of the HTTP methods described in [RFC 9110, section 9.1][rfc9110] are supported.
-///
-/// [rfc9110]: https://datatracker.ietf.org/doc/html/rfc9110#section-9.1
-#[configurable_component]
-#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
-#[serde(rename_all = "snake_case")]
-#[derivative(Default)]
-pub enum HttpMethod {
-    /// GET.
-    ///
-    /// This is the default.
-    #[derivative(Default)]
-    Get,
-
-    /// HEAD.
-    Head,
-
-    /// POST.
-    Post,
-
-    /// PUT.
-    Put,
-
-    /// DELETE.
-    Delete,
-
-    /// OPTIONS.
-    Options,
-
-    /// TRACE.
-    Trace,
-
-    /// PATCH.
-    Patch,
-}
-
 impl GenerateConfig for HttpSinkConfig {
     fn generate_config() -> toml::Value {
         toml::from_str(
@@ -144,15 +129,56 @@ impl HttpSinkConfig {
     }
 }
 
+/// A part of the requests that is either the same for all events, or rendered from each of them.
+#[derive(Clone, Debug)]
+enum RequestPart<T> {
+    Fixed(T),
+    Template(Template),
+}
+
+impl<T: Clone> RequestPart<T> {
+    fn render(
+        &self,
+        event: &Event,
+        part: &'static str,
+        parse: impl FnOnce(&str) -> Option<T>,
+    ) -> Option<T> {
+        match self {
+            Self::Fixed(value) => Some(value.clone()),
+            Self::Template(template) => {
+                let rendered = render(template, event, part)?;
+                let parsed = parse(&rendered);
+                if parsed.is_none() {
+                    emit!(HttpSinkInvalidRequestPart {
+                        part,
+                        value: &rendered,
+                    });
+                }
+                parsed
+            }
+        }
+    }
+}
+
+/// The parts of a request rendered from the events sent in it.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct RequestKey {
+    uri: Uri,
+    method: Method,
+    headers: Vec<(HeaderName, HeaderValue)>,
+}
+
 struct HttpSink {
-    pub uri: UriSerde,
-    pub method: Option<HttpMethod>,
+    pub uri: RequestPart<Uri>,
+    pub method: RequestPart<Method>,
     pub auth: Option<Auth>,
     pub compression: Compression,
     pub transformer: Transformer,
     pub encoder: Encoder<Framer>,
     pub batch: BatchConfig<RealtimeSizeBasedDefaultBatchSettings>,
     pub request: RequestConfig,
+    pub header_templates: Vec<(HeaderName, Template)>,
+    pub request_per_event: bool,
 }
 
 #[cfg(test)]
@@ -161,14 +187,16 @@ fn default_sink(encoding: EncodingConfigWithFraming) -> HttpSink {
     let encoder = Encoder::<Framer>::new(framing, serializer);
 
     HttpSink {
-        uri: Default::default(),
-        method: Default::default(),
+        uri: RequestPart::Fixed(Default::default()),
+        method: RequestPart::Fixed(Method::POST),
         auth: Default::default(),
         compression: Default::default(),
         transformer: Default::default(),
         encoder,
         batch: Default::default(),
         request: Default::default(),
+        header_templates: Default::default(),
+        request_per_event: false,
     }
 }
 
@@ -191,18 +219,56 @@ impl SinkConfig for HttpSinkConfig {
         request.add_old_option(self.headers.clone());
         validate_headers(&request.headers, &self.auth)?;
 
+        let mut header_templates = Vec::new();
+        request
+            .headers
+            .retain(|name, value| match field_template(value) {
+                Some(template) => {
+                    let name = HeaderName::from_bytes(name.as_bytes()).expect("validated header");
+                    header_templates.push((name, template));
+                    false
+                }
+                None => true,
+            });
+
+        let (uri, auth) = match field_template(&self.uri) {
+            Some(template) => (RequestPart::Template(template), self.auth.clone()),
+            None => {
+                let uri = self
+                    .uri
+                    .parse::<UriSerde>()
+                    .context(InvalidUriSnafu { uri: &self.uri })?;
+                let auth = self.auth.choose_one(&uri.auth)?;
+                (RequestPart::Fixed(uri.with_default_parts().uri), auth)
+            }
+        };
+
+        let method = match &self.method {
+            None => RequestPart::Fixed(Method::POST),
+            Some(method) if method.is_dynamic() => RequestPart::Template(method.clone()),
+            Some(method) => {
+                RequestPart::Fixed(parse_method(method.get_ref()).ok_or_else(|| {
+                    BuildError::InvalidMethod {
+                        method: method.to_string(),
+                    }
+                })?)
+            }
+        };
+
         let (framer, serializer) = self.encoding.build(SinkType::MessageBased)?;
         let encoder = Encoder::<Framer>::new(framer, serializer);
 
         let sink = HttpSink {
-            uri: self.uri.with_default_parts(),
-            method: self.method,
-            auth: self.auth.choose_one(&self.uri.auth)?,
+            uri,
+            method,
+            auth,
             compression: self.compression,
             transformer: self.encoding.transformer(),
             encoder,
             batch: self.batch,
             request,
+            header_templates,
+            request_per_event: self.request_per_event,
         };
 
         let request = sink
@@ -210,10 +276,13 @@ impl SinkConfig for HttpSinkConfig {
             .tower
             .unwrap_with(&TowerRequestConfig::default());
 
-        let batch = sink.batch.into_batch_settings()?;
-        let sink = BatchedHttpSink::new(
+        let mut batch = sink.batch.into_batch_settings()?;
+        if sink.request_per_event {
+            batch.size.events = 1;
+        }
+        let sink = PartitionHttpSink::new(
             sink,
-            Buffer::new(batch.size, Compression::None),
+            PartitionBuffer::new(Buffer::new(batch.size, Compression::None)),
             request,
             batch.timeout,
             client,
@@ -237,44 +306,79 @@ impl SinkConfig for HttpSinkConfig {
 pub struct HttpSinkEventEncoder {
     encoder: Encoder<Framer>,
     transformer: Transformer,
+    uri: RequestPart<Uri>,
+    method: RequestPart<Method>,
+    header_templates: Vec<(HeaderName, Template)>,
 }
 
-impl HttpEventEncoder<BytesMut> for HttpSinkEventEncoder {
-    fn encode_event(&mut self, mut event: Event) -> Option<BytesMut> {
+impl HttpSinkEventEncoder {
+    /// Renders the parts of the request of the event, dropping it if any of them is invalid.
+    fn request_key(&self, event: &Event) -> Option<RequestKey> {
+        let uri = self.uri.render(event, "uri", |uri| {
+            uri.parse::<UriSerde>()
+                .ok()
+                .filter(|uri| uri.auth.is_none())
+                .map(|uri| uri.with_default_parts().uri)
+        })?;
+        let method = self.method.render(event, "method", parse_method)?;
+
+        let mut headers = Vec::with_capacity(self.header_templates.len());
+        for (name, template) in &self.header_templates {
+            let rendered = render(template, event, name.as_str())?;
+            match HeaderValue::from_str(&rendered) {
+                Ok(value) => headers.push((name.clone(), value)),
+                Err(_) => {
+                    emit!(HttpSinkInvalidRequestPart {
+                        part: "header",
+                        value: &rendered,
+                    });
+                    return None;
+                }
+            }
+        }
+
+        Some(RequestKey {
+            uri,
+            method,
+            headers,
+        })
+    }
+}
+
+impl HttpEventEncoder<PartitionInnerBuffer<BytesMut, RequestKey>> for HttpSinkEventEncoder {
+    fn encode_event(
+        &mut self,
+        mut event: Event,
+    ) -> Option<PartitionInnerBuffer<BytesMut, RequestKey>> {
+        let key = self.request_key(&event)?;
+
         self.transformer.transform(&mut event);
 
         let mut body = BytesMut::new();
         self.encoder.encode(event, &mut body).ok()?;
 
-        Some(body)
+        Some(PartitionInnerBuffer::new(body, key))
     }
 }
 
 #[async_trait::async_trait]
 impl util::http::HttpSink for HttpSink {
-    type Input = BytesMut;
-    type Output = BytesMut;
+    type Input = PartitionInnerBuffer<BytesMut, RequestKey>;
+    type Output = PartitionInnerBuffer<BytesMut, RequestKey>;
     type Encoder = HttpSinkEventEncoder;
 
     fn build_encoder(&self) -> Self::Encoder {
         HttpSinkEventEncoder {
             encoder: self.encoder.clone(),
             transformer: self.transformer.clone(),
+            uri: self.uri.clone(),
+            method: self.method.clone(),
+            header_templates: self.header_templates.clone(),
         }
     }
 
-    async fn build_request(&self, mut body: Self::Output) -> crate::Result<http::Request<Bytes>> {
-        let method = match &self.method.unwrap_or(HttpMethod::Post) {
-            HttpMethod::Get => Method::GET,
-            HttpMethod::Head => Method::HEAD,
-            HttpMethod::Post => Method::POST,
-            HttpMethod::Put => Method::PUT,
-            HttpMethod::Delete => Method::DELETE,
-            HttpMethod::Options => Method::OPTIONS,
-            HttpMethod::Trace => Method::TRACE,
-            HttpMethod::Patch => Method::PATCH,
-        };
-        let uri: Uri = self.uri.uri.clone();
+    async fn build_request(&self, output: Self::Output) -> crate::Result<http::Request<Bytes>> {
+        let (mut body, key) = output.into_parts();
 
         let content_type = {
             use Framer::*;
@@ -290,17 +394,24 @@ impl util::http::HttpSink for HttpSink {
                     Some("application/x-ndjson")
                 }
                 (Json(_), CharacterDelimited(CharacterDelimitedEncoder { delimiter: b',' })) => {
-                    // TODO(https://github.com/vectordotdev/vector/issues/11253):
-                    // Prepend before building a request body to eliminate the
-                    // additional copy here.
-                    let message = body.split();
-                    body.put_u8(b'[');
-                    if !message.is_empty() {
-                        body.unsplit(message);
-                        // remove trailing comma from last record
-                        body.truncate(body.len() - 1);
+                    if self.request_per_event {
+                        // The event is sent on its own rather than in an array.
+                        if !body.is_empty() {
+                            body.truncate(body.len() - 1);
+                        }
+                    } else {
+                        // TODO(https://github.com/vectordotdev/vector/issues/11253):
+                        // Prepend before building a request body to eliminate the
+                        // additional copy here.
+                        let message = body.split();
+                        body.put_u8(b'[');
+                        if !message.is_empty() {
+                            body.unsplit(message);
+                            // remove trailing comma from last record
+                            body.truncate(body.len() - 1);
+                        }
+                        body.put_u8(b']');
                     }
-                    body.put_u8(b']');
 
                     Some("application/json")
                 }
@@ -308,35 +419,28 @@ impl util::http::HttpSink for HttpSink {
             }
         };
 
-        let mut builder = Request::builder().method(method).uri(uri);
+        let mut builder = Request::builder().method(key.method).uri(key.uri);
 
         if let Some(content_type) = content_type {
             builder = builder.header("Content-Type", content_type);
         }
 
-        match self.compression {
-            Compression::Gzip(level) => {
-                builder = builder.header("Content-Encoding", "gzip");
+        if let Some(content_encoding) = self.compression.content_encoding() {
+            builder = builder.header("Content-Encoding", content_encoding);
 
-                let buffer = BytesMut::new();
-                let mut w = GzEncoder::new(buffer.writer(), level.as_flate2());
-                w.write_all(&body).expect("Writing to Vec can't fail");
-                body = w.finish().expect("Writing to Vec can't fail").into_inner();
-            }
-            Compression::Zlib(level) => {
-                builder = builder.header("Content-Encoding", "deflate");
-
-                let buffer = BytesMut::new();
-                let mut w = ZlibEncoder::new(buffer.writer(), level.as_flate2());
-                w.write_all(&body).expect("Writing to Vec can't fail");
-                body = w.finish().expect("Writing to Vec can't fail").into_inner();
-            }
-            Compression::None => {}
+            let mut compressor = Compressor::from(self.compression);
+            compressor
+                .write_all(&body)
+                .expect("Writing to Vec can't fail");
+            body = compressor.into_inner();
         }
 
         for (header, value) in self.request.headers.iter() {
             builder = builder.header(header.as_str(), value.as_str());
         }
+        for (header, value) in key.headers {
+            builder = builder.header(header, value);
+        }
 
         let mut request = builder.body(body.freeze()).unwrap();
 
@@ -365,6 +469,43 @@ async fn healthcheck(uri: UriSerde, auth: Option<Auth>, client: HttpClient) -> c
     }
 }
 
+fn render(template: &Template, event: &Event, field: &str) -> Option<String> {
+    template
+        .render_string(event)
+        .map_err(|error| {
+            emit!(TemplateRenderingError {
+                error,
+                field: Some(field),
+                drop_event: true,
+            });
+        })
+        .ok()
+}
+
+fn parse_method(method: &str) -> Option<Method> {
+    match method.to_ascii_lowercase().as_str() {
+        "get" => Some(Method::GET),
+        "head" => Some(Method::HEAD),
+        "post" => Some(Method::POST),
+        "put" => Some(Method::PUT),
+        "delete" => Some(Method::DELETE),
+        "options" => Some(Method::OPTIONS),
+        "trace" => Some(Method::TRACE),
+        "patch" => Some(Method::PATCH),
+        _ => None,
+    }
+}
+
+/// Returns the template of a URI or header value that refers to event fields, such as `{{ token }}`.
+///
+/// Other values are used as is, as URIs and header values can contain `%` without it being a time
+/// specifier.
+fn field_template(value: &str) -> Option<Template> {
+    Template::try_from(value)
+        .ok()
+        .filter(|template| template.get_fields().is_some())
+}
+
 fn validate_headers(map: &IndexMap<String, String>, auth: &Option<Auth>) -> crate::Result<()> {
     for (name, value) in map {
         if auth.is_some() && name.eq_ignore_ascii_case("Authorization") {
@@ -373,8 +514,10 @@ fn validate_headers(map: &IndexMap<String, String>, auth: &Option<Auth>) -> crat
 
         HeaderName::from_bytes(name.as_bytes())
             .with_context(|_| InvalidHeaderNameSnafu { name })?;
-        HeaderValue::from_bytes(value.as_bytes())
-            .with_context(|_| InvalidHeaderValueSnafu { value })?;
+        if field_template(value).is_none() {
+            HeaderValue::from_bytes(value.as_bytes())
+                .with_context(|_| InvalidHeaderValueSnafu { value })?;
+        }
     }
 
     Ok(())
@@ -426,7 +569,7 @@ mod tests {
 
         let sink = default_sink((None::<FramingConfig>, TextSerializerConfig::new()).into());
         let mut encoder = sink.build_encoder();
-        let bytes = encoder.encode_event(event).unwrap();
+        let (bytes, _) = encoder.encode_event(event).unwrap().into_parts();
 
         assert_eq!(bytes, Vec::from("hello world\n"));
     }
@@ -443,7 +586,7 @@ mod tests {
                 .into(),
         );
         let mut encoder = sink.build_encoder();
-        let bytes = encoder.encode_event(event).unwrap();
+        let (bytes, _) = encoder.encode_event(event).unwrap().into_parts();
 
         #[derive(Deserialize, Debug)]
         #[serde(deny_unknown_fields)]
@@ -458,6 +601,73 @@ mod tests {
         assert_eq!(output.message, "hello world".to_string());
     }
 
+    #[test]
+    fn http_renders_request_parts() {
+        let mut sink = default_sink((None::<FramingConfig>, TextSerializerConfig::new()).into());
+        sink.uri = RequestPart::Template(field_template("http://example.com/{{ id }}").unwrap());
+        sink.method = RequestPart::Template(Template::try_from("{{ method }}").unwrap());
+        sink.header_templates = vec![(
+            HeaderName::from_static("x-token"),
+            field_template("Token {{ token }}").unwrap(),
+        )];
+        let mut encoder = sink.build_encoder();
+
+        let mut log = LogEvent::from("hello world");
+        log.insert("id", "abc");
+        log.insert("method", "PUT");
+        log.insert("token", "secret");
+        let (_, key) = encoder.encode_event(log.into()).unwrap().into_parts();
+
+        assert_eq!(key.uri, "http://example.com/abc");
+        assert_eq!(key.method, Method::PUT);
+        assert_eq!(
+            key.headers,
+            vec![(
+                HeaderName::from_static("x-token"),
+                HeaderValue::from_static("Token secret")
+            )]
+        );
+
+        // Missing fields and invalid methods drop the event.
+        let mut log = LogEvent::from("hello world");
+        log.insert("id", "abc");
+        log.insert("method", "FETCH");
+        log.insert("token", "secret");
+        assert!(encoder.encode_event(log.into()).is_none());
+        assert!(encoder
+            .encode_event(LogEvent::from("hello world").into())
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn http_sends_json_event_per_request() {
+        let mut sink = default_sink((None::<FramingConfig>, JsonSerializerConfig::new()).into());
+        sink.request_per_event = true;
+        let mut encoder = sink.build_encoder();
+        let output = encoder
+            .encode_event(LogEvent::from("hello world").into())
+            .unwrap();
+
+        let request = sink.build_request(output).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(request.body()).unwrap();
+
+        assert_eq!(body["message"], "hello world");
+    }
+
+    #[test]
+    fn http_keeps_percent_encoded_uris() {
+        let config: HttpSinkConfig = toml::from_str(
+            r#"
+            uri = "http://example.com/a%20b"
+            encoding.codec = "text"
+            "#,
+        )
+        .unwrap();
+
+        assert!(field_template(&config.uri).is_none());
+        assert!(config.uri.parse::<UriSerde>().is_ok());
+    }
+
     #[test]
     fn http_validates_normal_headers() {
         let config = r#"
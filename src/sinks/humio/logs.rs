@@ -177,7 +177,7 @@ impl HumioLogsConfig {
             encoding: self.encoding.clone(),
             compression: self.compression,
             batch: self.batch,
-            request: self.request,
+            request: self.request.clone(),
             tls: self.tls.clone(),
             acknowledgements: HecClientAcknowledgementsConfig {
                 indexer_acknowledgements_enabled: false,
@@ -142,7 +142,7 @@ impl SinkConfig for HumioMetricsConfig {
             indexed_fields: self.indexed_fields.clone(),
             index: self.index.clone(),
             compression: self.compression,
-            request: self.request,
+            request: self.request.clone(),
             batch: self.batch,
             tls: self.tls.clone(),
             timestamp_nanos_key: None,
@@ -266,7 +266,7 @@ mod tests {
                     MetricValue::Counter { value: 42.0 },
                 )
                 .with_tags(Some(
-                    vec![("os.host".to_string(), "somehost".to_string())]
+                    vec![("os.host".into(), "somehost".to_string())]
                         .into_iter()
                         .collect(),
                 ))
@@ -282,7 +282,7 @@ mod tests {
                     },
                 )
                 .with_tags(Some(
-                    vec![("os.host".to_string(), "somehost".to_string())]
+                    vec![("os.host".into(), "somehost".to_string())]
                         .into_iter()
                         .collect(),
                 ))
@@ -1,8 +1,4 @@
-use std::{
-    collections::{BTreeMap, HashMap},
-    future::ready,
-    task::Poll,
-};
+use std::{collections::HashMap, future::ready, task::Poll};
 
 use bytes::{Bytes, BytesMut};
 use futures::{future::BoxFuture, stream, SinkExt};
@@ -17,7 +13,7 @@ use vector_core::{
 use crate::{
     config::{AcknowledgementsConfig, Input, SinkConfig, SinkContext},
     event::{
-        metric::{Metric, MetricValue, Sample, StatisticKind},
+        metric::{Metric, MetricTags, MetricValue, Sample, StatisticKind},
         Event,
     },
     http::HttpClient,
@@ -229,20 +225,17 @@ fn create_build_request(
     }
 }
 
-fn merge_tags(
-    event: &Metric,
-    tags: Option<&HashMap<String, String>>,
-) -> Option<BTreeMap<String, String>> {
+fn merge_tags(event: &Metric, tags: Option<&HashMap<String, String>>) -> Option<MetricTags> {
     match (event.tags().cloned(), tags) {
         (Some(mut event_tags), Some(config_tags)) => {
-            event_tags.extend(config_tags.iter().map(|(k, v)| (k.clone(), v.clone())));
+            event_tags.extend(config_tags.iter().map(|(k, v)| (k.into(), v.clone())));
             Some(event_tags)
         }
         (Some(event_tags), None) => Some(event_tags),
         (None, Some(config_tags)) => Some(
             config_tags
                 .iter()
-                .map(|(k, v)| (k.clone(), v.clone()))
+                .map(|(k, v)| (k.into(), v.clone()))
                 .collect(),
         ),
         (None, None) => None,
@@ -283,7 +276,7 @@ fn encode_events(
         let (metric_type, fields) = get_type_and_fields(event.value(), quantiles);
 
         let mut unwrapped_tags = tags.unwrap_or_default();
-        unwrapped_tags.insert("metric_type".to_owned(), metric_type.to_owned());
+        unwrapped_tags.insert("metric_type".into(), metric_type.to_owned());
 
         if let Err(error_message) = influx_line_protocol(
             protocol_version,
@@ -334,6 +327,14 @@ fn get_type_and_fields(
 
             ("histogram", Some(fields))
         }
+        MetricValue::ExponentialHistogram { .. } => {
+            // InfluxDB has no notion of exponential buckets, so the histogram is sent with the
+            // bucket boundaries at its current scale.
+            let histogram = value
+                .exponential_to_agg_histogram()
+                .expect("value should be exponential histogram already");
+            get_type_and_fields(&histogram, quantiles)
+        }
         MetricValue::AggregatedSummary {
             quantiles,
             count,
@@ -1111,8 +1112,8 @@ mod integration_tests {
                 .with_namespace(Some("ns"))
                 .with_tags(Some(
                     vec![
-                        ("region".to_owned(), "us-west-1".to_owned()),
-                        ("production".to_owned(), "true".to_owned()),
+                        ("region".into(), "us-west-1".to_owned()),
+                        ("production".into(), "true".to_owned()),
                     ]
                     .into_iter()
                     .collect(),
@@ -1196,8 +1197,8 @@ mod integration_tests {
             .with_namespace(Some("ns"))
             .with_tags(Some(
                 vec![
-                    ("region".to_owned(), "us-west-1".to_owned()),
-                    ("production".to_owned(), "true".to_owned()),
+                    ("region".into(), "us-west-1".to_owned()),
+                    ("production".into(), "true".to_owned()),
                 ]
                 .into_iter()
                 .collect(),
@@ -209,10 +209,10 @@ fn healthcheck(
 }
 
 // https://v2.docs.influxdata.com/v2.0/reference/syntax/line-protocol/
-pub(in crate::sinks) fn influx_line_protocol(
+pub(in crate::sinks) fn influx_line_protocol<K: AsRef<str>>(
     protocol_version: ProtocolVersion,
     measurement: &str,
-    tags: Option<BTreeMap<String, String>>,
+    tags: Option<BTreeMap<K, String>>,
     fields: Option<HashMap<String, Field>>,
     timestamp: i64,
     line_protocol: &mut BytesMut,
@@ -242,14 +242,15 @@ pub(in crate::sinks) fn influx_line_protocol(
     Ok(())
 }
 
-fn encode_tags(tags: BTreeMap<String, String>, output: &mut BytesMut) {
+fn encode_tags<K: AsRef<str>>(tags: BTreeMap<K, String>, output: &mut BytesMut) {
     let original_len = output.len();
     // `tags` is already sorted
     for (key, value) in tags {
+        let key = key.as_ref();
         if key.is_empty() || value.is_empty() {
             continue;
         }
-        encode_string(&key, output);
+        encode_string(key, output);
         output.put_u8(b'=');
         encode_string(&value, output);
         output.put_u8(b',');
@@ -361,7 +362,7 @@ pub mod test_util {
     use chrono::{offset::TimeZone, DateTime, SecondsFormat, Utc};
 
     use super::*;
-    use crate::tls;
+    use crate::{event::metric::MetricTags, tls};
 
     pub(crate) const ORG: &str = "my-org";
     pub(crate) const BUCKET: &str = "my-bucket";
@@ -375,11 +376,11 @@ pub mod test_util {
         Utc.ymd(2018, 11, 14).and_hms_nano(8, 9, 10, 11)
     }
 
-    pub(crate) fn tags() -> BTreeMap<String, String> {
+    pub(crate) fn tags() -> MetricTags {
         vec![
-            ("normal_tag".to_owned(), "value".to_owned()),
-            ("true_tag".to_owned(), "true".to_owned()),
-            ("empty_tag".to_owned(), "".to_owned()),
+            ("normal_tag".into(), "value".to_owned()),
+            ("true_tag".into(), "true".to_owned()),
+            ("empty_tag".into(), "".to_owned()),
         ]
         .into_iter()
         .collect()
@@ -11,10 +11,14 @@ use crate::{
     kafka::{KafkaAuthConfig, KafkaCompression},
     serde::json::to_string,
     sinks::{
-        kafka::sink::{healthcheck, KafkaSink},
-        util::{BatchConfig, NoDefaultsBatchSettings},
+        kafka::{
+            schema_registry::SchemaRegistryConfig,
+            sink::{healthcheck, KafkaSink},
+        },
+        util::{record_partitioner::RecordPartitioner, BatchConfig, NoDefaultsBatchSettings},
         Healthcheck, VectorSink,
     },
+    template::Template,
 };
 
 pub(crate) const QUEUED_MIN_MESSAGES: u64 = 100000;
@@ -40,6 +44,22 @@ pub struct KafkaSinkConfig {
     /// Kafka uses a hash of the key to choose the partition or uses round-robin if the record has no key.
     pub key_field: Option<String>,
 
+    /// The template of the key of the records, such as `{{ tenant }}-{{ host }}`.
+    ///
+    /// If the template can't be rendered, the record is sent without a key. Can't be set along
+    /// with `key_field`.
+    #[configurable(metadata(templateable))]
+    pub key: Option<Template>,
+
+    /// How the records are assigned to the partitions of their topic.
+    ///
+    /// The `murmur2` partitioner assigns the records with a key as the default partitioner of the
+    /// Java producer does, so that consumers relying on the co-partitioning of topics produced by
+    /// both see the records of a key in the same partition. By default, the partitioner of
+    /// `librdkafka` is used, which hashes the keys with CRC32.
+    #[configurable(derived)]
+    pub partitioner: Option<RecordPartitioner>,
+
     #[configurable(derived)]
     pub encoding: EncodingConfig,
 
@@ -78,6 +98,28 @@ pub struct KafkaSinkConfig {
     #[serde(alias = "headers_field")] // accidentally released as `headers_field` in 0.18
     pub headers_key: Option<String>,
 
+    /// Whether or not to produce events exactly once.
+    ///
+    /// When enabled, the producer is idempotent, and events are produced within transactions, one
+    /// for each batch. Events are only acknowledged once their transaction is committed, and
+    /// consumers reading with `isolation.level` set to `read_committed` never see the events of
+    /// aborted transactions, such as those left pending when Vector stops. Requires
+    /// `transactional_id` to be set.
+    #[serde(default)]
+    pub exactly_once: bool,
+
+    /// The transactional ID of the producer, when `exactly_once` is enabled.
+    ///
+    /// It must be unique to this sink, and stay the same across restarts, so that the transactions
+    /// left pending by a previous run of the sink are aborted when it starts again.
+    pub transactional_id: Option<String>,
+
+    #[configurable(derived)]
+    pub topic_creation: Option<KafkaTopicCreationConfig>,
+
+    #[configurable(derived)]
+    pub schema_registry: Option<SchemaRegistryConfig>,
+
     #[configurable(derived)]
     #[serde(
         default,
@@ -87,6 +129,34 @@ pub struct KafkaSinkConfig {
     pub acknowledgements: AcknowledgementsConfig,
 }
 
+/// Options for creating the topics the events are written to.
+///
+/// When set, the topics that don't exist are created before the first event is written to them.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct KafkaTopicCreationConfig {
+    /// The number of partitions of the topics.
+    #[serde(default = "default_partitions")]
+    pub partitions: i32,
+
+    /// The replication factor of the topics.
+    #[serde(default = "default_replication_factor")]
+    pub replication_factor: i32,
+
+    /// The configuration of the topics, such as `retention.ms` or `cleanup.policy`.
+    #[serde(default)]
+    pub config: HashMap<String, String>,
+}
+
+const fn default_partitions() -> i32 {
+    1
+}
+
+const fn default_replication_factor() -> i32 {
+    1
+}
+
 const fn default_socket_timeout_ms() -> u64 {
     60000 // default in librdkafka
 }
@@ -101,6 +171,7 @@ const fn default_message_timeout_ms() -> u64 {
 pub enum KafkaRole {
     Consumer,
     Producer,
+    Admin,
 }
 
 impl KafkaSinkConfig {
@@ -120,6 +191,39 @@ impl KafkaSinkConfig {
                     .set("compression.codec", &to_string(self.compression))
                     .set("message.timeout.ms", &self.message_timeout_ms.to_string());
 
+                // The `sticky` partitioner is applied by the sink, which sets the partition of
+                // each record.
+                match self.partitioner {
+                    Some(RecordPartitioner::Murmur2) => {
+                        client_config.set("partitioner", "murmur2_random");
+                    }
+                    Some(RecordPartitioner::Fnv) => {
+                        client_config.set("partitioner", "fnv1a_random");
+                    }
+                    Some(RecordPartitioner::Random) => {
+                        client_config
+                            .set("partitioner", "random")
+                            .set("sticky.partitioning.linger.ms", "0");
+                    }
+                    Some(RecordPartitioner::Sticky) | None => {}
+                }
+
+                if self.exactly_once {
+                    let transactional_id = self
+                        .transactional_id
+                        .as_ref()
+                        .ok_or("`transactional_id` must be set when `exactly_once` is enabled.")?;
+                    // librdkafka requires the message timeout to be no longer than the
+                    // transaction timeout.
+                    client_config
+                        .set("enable.idempotence", "true")
+                        .set("transactional.id", transactional_id)
+                        .set(
+                            "transaction.timeout.ms",
+                            &self.message_timeout_ms.to_string(),
+                        );
+                }
+
                 if let Some(value) = self.batch.timeout_secs {
                     // Delay in milliseconds to wait for messages in the producer queue to accumulate before
                     // constructing message batches (MessageSets) to transmit to brokers. A higher value
@@ -184,6 +288,8 @@ impl KafkaSinkConfig {
             KafkaRole::Consumer => {
                 client_config.set("queued.min.messages", QUEUED_MIN_MESSAGES.to_string());
             }
+
+            KafkaRole::Admin => {}
         }
 
         for (key, value) in self.librdkafka_options.iter() {
@@ -193,6 +299,17 @@ impl KafkaSinkConfig {
 
         Ok(client_config)
     }
+
+    /// Gets the subject the schema of the events is registered under.
+    fn schema_subject(&self, registry: &SchemaRegistryConfig) -> crate::Result<String> {
+        if let Some(subject) = &registry.subject {
+            return Ok(subject.clone());
+        }
+        if Template::try_from(self.topic.as_str())?.is_dynamic() {
+            return Err("`schema_registry.subject` must be set when `topic` is a template.".into());
+        }
+        Ok(format!("{}-value", self.topic))
+    }
 }
 
 impl GenerateConfig for KafkaSinkConfig {
@@ -201,6 +318,8 @@ impl GenerateConfig for KafkaSinkConfig {
             bootstrap_servers: "10.14.22.123:9092,10.14.23.332:9092".to_owned(),
             topic: "topic-1234".to_owned(),
             key_field: Some("user_id".to_owned()),
+            key: None,
+            partitioner: None,
             encoding: JsonSerializerConfig::new().into(),
             batch: Default::default(),
             compression: KafkaCompression::None,
@@ -209,6 +328,10 @@ impl GenerateConfig for KafkaSinkConfig {
             message_timeout_ms: default_message_timeout_ms(),
             librdkafka_options: Default::default(),
             headers_key: None,
+            exactly_once: false,
+            transactional_id: None,
+            topic_creation: None,
+            schema_registry: None,
             acknowledgements: Default::default(),
         })
         .unwrap()
@@ -217,7 +340,19 @@ impl GenerateConfig for KafkaSinkConfig {
 
 #[async_trait::async_trait]
 impl SinkConfig for KafkaSinkConfig {
-    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        if let Some(registry) = &self.schema_registry {
+            let subject = self.schema_subject(registry)?;
+            let id = registry
+                .register(&subject, &cx.merged_schema_definition, &cx.proxy)
+                .await?;
+            info!(message = "Registered the schema of the events.", %subject, id);
+        }
+
+        if self.key_field.is_some() && self.key.is_some() {
+            return Err("`key_field` and `key` can't both be set.".into());
+        }
+
         let sink = KafkaSink::new(self.clone())?;
         let hc = healthcheck(self.clone()).boxed();
         Ok((VectorSink::from_event_streamsink(sink), hc))
@@ -240,4 +375,80 @@ mod tests {
     fn generate_config() {
         KafkaSinkConfig::generate_config();
     }
+
+    #[test]
+    fn exactly_once_producer_config() {
+        let mut config: KafkaSinkConfig = toml::from_str(
+            r#"
+            bootstrap_servers = "localhost:9092"
+            topic = "topic"
+            encoding.codec = "json"
+            exactly_once = true
+            "#,
+        )
+        .unwrap();
+        assert!(config.to_rdkafka(KafkaRole::Producer).is_err());
+
+        config.transactional_id = Some("vector-kafka".to_owned());
+        let client_config = config.to_rdkafka(KafkaRole::Producer).unwrap();
+        assert_eq!(client_config.get("enable.idempotence"), Some("true"));
+        assert_eq!(client_config.get("transactional.id"), Some("vector-kafka"));
+        assert_eq!(client_config.get("transaction.timeout.ms"), Some("300000"));
+
+        let client_config = config.to_rdkafka(KafkaRole::Consumer).unwrap();
+        assert_eq!(client_config.get("transactional.id"), None);
+    }
+
+    #[test]
+    fn partitioner_producer_config() {
+        let mut config: KafkaSinkConfig = toml::from_str(
+            r#"
+            bootstrap_servers = "localhost:9092"
+            topic = "topic"
+            encoding.codec = "json"
+            key = "{{ tenant }}"
+            partitioner = "murmur2"
+            "#,
+        )
+        .unwrap();
+        let client_config = config.to_rdkafka(KafkaRole::Producer).unwrap();
+        assert_eq!(client_config.get("partitioner"), Some("murmur2_random"));
+
+        config.partitioner = Some(RecordPartitioner::Random);
+        let client_config = config.to_rdkafka(KafkaRole::Producer).unwrap();
+        assert_eq!(client_config.get("partitioner"), Some("random"));
+        assert_eq!(
+            client_config.get("sticky.partitioning.linger.ms"),
+            Some("0")
+        );
+
+        config.partitioner = Some(RecordPartitioner::Sticky);
+        let client_config = config.to_rdkafka(KafkaRole::Producer).unwrap();
+        assert_eq!(client_config.get("partitioner"), None);
+    }
+
+    #[test]
+    fn schema_subject() {
+        let mut config: KafkaSinkConfig = toml::from_str(
+            r#"
+            bootstrap_servers = "localhost:9092"
+            topic = "logs"
+            encoding.codec = "json"
+            schema_registry.url = "http://localhost:8081"
+            schema_registry.format = "avro"
+            "#,
+        )
+        .unwrap();
+        let registry = config.schema_registry.clone().unwrap();
+        assert_eq!(config.schema_subject(&registry).unwrap(), "logs-value");
+
+        config.topic = "logs-{{ service }}".to_owned();
+        assert!(config.schema_subject(&registry).is_err());
+
+        let registry = SchemaRegistryConfig {
+            subject: Some("logs".to_owned()),
+            ..registry
+        };
+        assert_eq!(config.schema_subject(&registry).unwrap(), "logs");
+    }
 }
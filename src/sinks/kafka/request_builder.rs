@@ -13,6 +13,7 @@ use crate::{
 
 pub struct KafkaRequestBuilder {
     pub key_field: Option<String>,
+    pub key_template: Option<Template>,
     pub headers_key: Option<String>,
     pub topic_template: Template,
     pub transformer: Transformer,
@@ -36,7 +37,10 @@ impl KafkaRequestBuilder {
 
         let metadata = KafkaRequestMetadata {
             finalizers: event.take_finalizers(),
-            key: get_key(&event, &self.key_field),
+            key: match &self.key_template {
+                Some(key_template) => render_key(&event, key_template),
+                None => get_key(&event, &self.key_field),
+            },
             timestamp_millis: get_timestamp_millis(&event, self.log_schema),
             headers: get_headers(&event, &self.headers_key),
             topic,
@@ -67,6 +71,19 @@ fn get_key(event: &Event, key_field: &Option<String>) -> Option<Bytes> {
     })
 }
 
+fn render_key(event: &Event, key_template: &Template) -> Option<Bytes> {
+    key_template
+        .render(event)
+        .map_err(|error| {
+            emit!(TemplateRenderingError {
+                field: Some("key"),
+                drop_event: false,
+                error,
+            });
+        })
+        .ok()
+}
+
 fn get_timestamp_millis(event: &Event, log_schema: &'static LogSchema) -> Option<i64> {
     match &event {
         Event::Log(log) => log
@@ -0,0 +1,391 @@
+//! The schema of the events written to Kafka, as registered in a schema registry.
+//!
+//! It is derived from the schema definition of the events received by the sink, and written in
+//! one of the formats of the registry: Avro, Protobuf or JSON Schema.
+
+use std::fmt::Write as _;
+
+use lookup::LookupBuf;
+use serde_json::{json, Map, Value as JsonValue};
+use value::Kind;
+use vector_core::schema::Definition;
+
+/// The fields of the events, or of an object nested in them.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EventSchema {
+    fields: Vec<Field>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Field {
+    name: String,
+    kind: FieldKind,
+    repeated: bool,
+    /// Whether the field is always set, to a value other than `null`.
+    required: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum FieldKind {
+    String,
+    Long,
+    Double,
+    Boolean,
+    Timestamp,
+    /// Any value, such as an object whose fields aren't all known.
+    Any,
+    Record(EventSchema),
+}
+
+impl EventSchema {
+    /// Derives the schema from the schema definition of the events.
+    ///
+    /// Returns `None` if the definition knows none of the fields of the events. The fields that
+    /// aren't known, or whose name isn't valid in all the formats, are left out of the schema.
+    pub fn from_definition(definition: &Definition) -> Option<Self> {
+        Self::from_object_kind(definition, &LookupBuf::root(), definition.event_kind())
+            .filter(|schema| !schema.fields.is_empty())
+    }
+
+    fn from_object_kind(definition: &Definition, path: &LookupBuf, kind: &Kind) -> Option<Self> {
+        let fields = kind
+            .as_object()?
+            .known()
+            .iter()
+            .filter(|(name, _)| is_valid_name(name.as_str()))
+            .filter_map(|(name, kind)| {
+                let mut path = path.clone();
+                path.push_back(name.as_str());
+                let (kind, repeated) = FieldKind::from_kind(definition, &path, kind)?;
+                let required =
+                    definition.is_required(path.clone()) && !definition.is_nullable(path);
+                Some(Field {
+                    name: name.to_string(),
+                    kind,
+                    repeated,
+                    required,
+                })
+            })
+            .collect();
+        Some(Self { fields })
+    }
+
+    /// Writes the schema as an Avro record with the given name.
+    ///
+    /// The fields that aren't required are unions with `null`, which they default to.
+    pub fn to_avro(&self, name: &str) -> JsonValue {
+        let fields = self
+            .fields
+            .iter()
+            .map(|field| {
+                let mut r#type = match &field.kind {
+                    FieldKind::String | FieldKind::Any => json!("string"),
+                    FieldKind::Long => json!("long"),
+                    FieldKind::Double => json!("double"),
+                    FieldKind::Boolean => json!("boolean"),
+                    FieldKind::Timestamp => {
+                        json!({"type": "long", "logicalType": "timestamp-micros"})
+                    }
+                    // The names of the records must be unique within the schema.
+                    FieldKind::Record(schema) => {
+                        schema.to_avro(&format!("{}_{}", name, field.name))
+                    }
+                };
+                if field.repeated {
+                    r#type = json!({"type": "array", "items": r#type});
+                }
+                if field.required {
+                    json!({"name": field.name, "type": r#type})
+                } else {
+                    json!({"name": field.name, "type": ["null", r#type], "default": null})
+                }
+            })
+            .collect::<Vec<_>>();
+        json!({"type": "record", "name": name, "fields": fields})
+    }
+
+    /// Writes the schema as a JSON Schema of the objects of the events.
+    pub fn to_json_schema(&self) -> JsonValue {
+        let mut schema = self.json_object();
+        schema["$schema"] = json!("http://json-schema.org/draft-07/schema#");
+        schema
+    }
+
+    fn json_object(&self) -> JsonValue {
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+        for field in &self.fields {
+            let mut schema = match &field.kind {
+                FieldKind::String => json!({"type": "string"}),
+                FieldKind::Long => json!({"type": "integer"}),
+                FieldKind::Double => json!({"type": "number"}),
+                FieldKind::Boolean => json!({"type": "boolean"}),
+                FieldKind::Timestamp => json!({"type": "string", "format": "date-time"}),
+                FieldKind::Any => json!({}),
+                FieldKind::Record(schema) => schema.json_object(),
+            };
+            if field.repeated {
+                schema = json!({"type": "array", "items": schema});
+            }
+            if field.required {
+                required.push(field.name.clone());
+            } else if field.kind != FieldKind::Any {
+                schema = json!({"oneOf": [{"type": "null"}, schema]});
+            }
+            properties.insert(field.name.clone(), schema);
+        }
+        json!({"type": "object", "properties": properties, "required": required})
+    }
+
+    /// Writes the schema as a Protobuf file, in the `proto3` syntax, with a message of the given
+    /// name.
+    ///
+    /// The timestamps are `google.protobuf.Timestamp` messages, and the values of any type
+    /// `google.protobuf.Value` messages.
+    pub fn to_protobuf(&self, name: &str) -> String {
+        let mut message = String::new();
+        self.write_message(name, 0, &mut message);
+
+        let mut proto = String::from("syntax = \"proto3\";\n\n");
+        for (kind, import) in [
+            (FieldKind::Timestamp, "google/protobuf/timestamp.proto"),
+            (FieldKind::Any, "google/protobuf/struct.proto"),
+        ] {
+            if self.uses(&kind) {
+                let _ = writeln!(proto, "import \"{}\";", import);
+            }
+        }
+        if self.uses(&FieldKind::Timestamp) || self.uses(&FieldKind::Any) {
+            proto.push('\n');
+        }
+        proto.push_str(&message);
+        proto
+    }
+
+    fn write_message(&self, name: &str, depth: usize, proto: &mut String) {
+        let indent = "  ".repeat(depth);
+        let _ = writeln!(proto, "{}message {} {{", indent, name);
+        for (field, number) in self.fields.iter().zip(1..) {
+            let r#type = match &field.kind {
+                FieldKind::String => "string".to_owned(),
+                FieldKind::Long => "int64".to_owned(),
+                FieldKind::Double => "double".to_owned(),
+                FieldKind::Boolean => "bool".to_owned(),
+                FieldKind::Timestamp => "google.protobuf.Timestamp".to_owned(),
+                FieldKind::Any => "google.protobuf.Value".to_owned(),
+                // The records are nested messages, named as the descriptors of the `gcp_bigquery`
+                // sink are.
+                FieldKind::Record(schema) => {
+                    let name = format!("Field{}", number);
+                    schema.write_message(&name, depth + 1, proto);
+                    name
+                }
+            };
+            // The fields of messages always tell whether they are set, so only scalars are optional.
+            let is_message = matches!(
+                field.kind,
+                FieldKind::Timestamp | FieldKind::Any | FieldKind::Record(_)
+            );
+            let label = if field.repeated {
+                "repeated "
+            } else if !field.required && !is_message {
+                "optional "
+            } else {
+                ""
+            };
+            let _ = writeln!(
+                proto,
+                "{}  {}{} {} = {};",
+                indent, label, r#type, field.name, number
+            );
+        }
+        let _ = writeln!(proto, "{}}}", indent);
+    }
+
+    /// Whether any field, nested ones included, is of the given kind.
+    fn uses(&self, kind: &FieldKind) -> bool {
+        self.fields.iter().any(|field| match &field.kind {
+            FieldKind::Record(schema) => schema.uses(kind),
+            field_kind => field_kind == kind,
+        })
+    }
+}
+
+impl FieldKind {
+    /// Maps the kind of the values of a field to its type, and whether it is repeated.
+    ///
+    /// Returns `None` if the field is never set to a value other than `null`.
+    fn from_kind(definition: &Definition, path: &LookupBuf, kind: &Kind) -> Option<(Self, bool)> {
+        let mut kind = kind.clone();
+        kind.remove_null();
+        kind.remove_undefined();
+        if kind.is_never() {
+            return None;
+        }
+
+        if kind.is_array() {
+            let mut path = path.clone();
+            path.push_back(0_isize);
+            let element = kind
+                .as_array()
+                .map_or_else(Kind::never, |array| array.reduced_kind());
+            return Some(match Self::from_kind(definition, &path, &element) {
+                Some((kind, false)) => (kind, true),
+                // Arrays of arrays can't be repeated fields, so they are values of any type.
+                _ => (Self::Any, false),
+            });
+        }
+
+        let kind = if kind.is_object() {
+            match Self::record(definition, path, &kind) {
+                Some(schema) => Self::Record(schema),
+                None => Self::Any,
+            }
+        } else if kind.is_bytes() {
+            Self::String
+        } else if kind.is_integer() {
+            Self::Long
+        } else if kind.is_float() || is_number(&kind) {
+            Self::Double
+        } else if kind.is_boolean() {
+            Self::Boolean
+        } else if kind.is_timestamp() {
+            Self::Timestamp
+        } else {
+            Self::Any
+        };
+        Some((kind, false))
+    }
+
+    /// Derives the schema of a record, if all of its fields are known.
+    fn record(definition: &Definition, path: &LookupBuf, kind: &Kind) -> Option<EventSchema> {
+        let object = kind.as_object()?;
+        if !object.unknown_kind().is_undefined() {
+            return None;
+        }
+        EventSchema::from_object_kind(definition, path, kind)
+            .filter(|schema| !schema.fields.is_empty())
+    }
+}
+
+/// Whether the kind is both integers and floats, and nothing else.
+fn is_number(kind: &Kind) -> bool {
+    let mut float = kind.clone();
+    float.remove_integer();
+    kind.contains_integer() && float.is_float()
+}
+
+/// Whether the name is valid for the fields of both Avro records and Protobuf messages.
+fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use indoc::indoc;
+    use value::kind::Collection;
+
+    use super::*;
+
+    fn schema() -> EventSchema {
+        let definition = Definition::empty_legacy_namespace()
+            .optional_field("count", Kind::integer(), None)
+            .with_field(
+                "http",
+                Kind::object(BTreeMap::from([("status".into(), Kind::integer())])),
+                None,
+            )
+            .with_field("message", Kind::bytes(), None)
+            .with_field(
+                "tags",
+                Kind::array(Collection::from_unknown(Kind::bytes())),
+                None,
+            )
+            .with_field("timestamp", Kind::timestamp().or_null(), None)
+            .with_field("invalid-name", Kind::bytes(), None);
+        EventSchema::from_definition(&definition).unwrap()
+    }
+
+    #[test]
+    fn needs_known_fields() {
+        assert_eq!(EventSchema::from_definition(&Definition::any()), None);
+    }
+
+    #[test]
+    fn writes_avro() {
+        assert_eq!(
+            schema().to_avro("Event"),
+            json!({
+                "type": "record",
+                "name": "Event",
+                "fields": [
+                    {"name": "count", "type": ["null", "long"], "default": null},
+                    {"name": "http", "type": {
+                        "type": "record",
+                        "name": "Event_http",
+                        "fields": [{"name": "status", "type": "long"}],
+                    }},
+                    {"name": "message", "type": "string"},
+                    {"name": "tags", "type": {"type": "array", "items": "string"}},
+                    {
+                        "name": "timestamp",
+                        "type": ["null", {"type": "long", "logicalType": "timestamp-micros"}],
+                        "default": null,
+                    },
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn writes_json_schema() {
+        assert_eq!(
+            schema().to_json_schema(),
+            json!({
+                "$schema": "http://json-schema.org/draft-07/schema#",
+                "type": "object",
+                "properties": {
+                    "count": {"oneOf": [{"type": "null"}, {"type": "integer"}]},
+                    "http": {
+                        "type": "object",
+                        "properties": {"status": {"type": "integer"}},
+                        "required": ["status"],
+                    },
+                    "message": {"type": "string"},
+                    "tags": {"type": "array", "items": {"type": "string"}},
+                    "timestamp": {
+                        "oneOf": [{"type": "null"}, {"type": "string", "format": "date-time"}],
+                    },
+                },
+                "required": ["http", "message", "tags"],
+            })
+        );
+    }
+
+    #[test]
+    fn writes_protobuf() {
+        assert_eq!(
+            schema().to_protobuf("Event"),
+            indoc! {r#"
+                syntax = "proto3";
+
+                import "google/protobuf/timestamp.proto";
+
+                message Event {
+                  optional int64 count = 1;
+                  message Field2 {
+                    int64 status = 1;
+                  }
+                  Field2 http = 2;
+                  string message = 3;
+                  repeated string tags = 4;
+                  google.protobuf.Timestamp timestamp = 5;
+                }
+            "#}
+        );
+    }
+}
@@ -0,0 +1,414 @@
+//! Registration of the schema of the events in a schema registry, with the REST API of the
+//! Confluent Schema Registry.
+//!
+//! See <https://docs.confluent.io/platform/current/schema-registry/develop/api.html>.
+
+use bytes::Bytes;
+use http::{Method, Request, StatusCode};
+use hyper::Body;
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use serde_json::{json, Value as JsonValue};
+use vector_config::configurable_component;
+use vector_core::{config::proxy::ProxyConfig, schema::Definition};
+
+use super::schema::EventSchema;
+use crate::{
+    http::{Auth, HttpClient},
+    tls::{TlsConfig, TlsSettings},
+};
+
+const CONTENT_TYPE: &str = "application/vnd.schemaregistry.v1+json";
+
+/// The characters escaped in the subjects, as path segments of the requests.
+const SUBJECT_ESCAPED: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~');
+
+/// The error codes of the registry when the subject, or its version, doesn't exist.
+const SUBJECT_NOT_FOUND: i64 = 40401;
+const VERSION_NOT_FOUND: i64 = 40402;
+
+/// Configuration of the schema registry the schema of the events is registered in.
+///
+/// The schema is derived from the schema definition of the events, and checked compatible with
+/// the latest version of its subject when the sink starts, which fails otherwise.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SchemaRegistryConfig {
+    /// The URL of the schema registry, such as `http://localhost:8081`.
+    pub url: String,
+
+    #[configurable(derived)]
+    pub format: SchemaFormat,
+
+    /// The subject the schema is registered under.
+    ///
+    /// By default, it is the topic followed by `-value`, as the topic name strategy of the
+    /// Confluent serializers names them, and it must be set when the topic is a template.
+    pub subject: Option<String>,
+
+    /// The name of the record, or message, of the events in the schema.
+    #[serde(default = "default_record_name")]
+    pub record_name: String,
+
+    #[configurable(derived)]
+    pub compatibility: Option<CompatibilityLevel>,
+
+    #[configurable(derived)]
+    pub auth: Option<Auth>,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+}
+
+fn default_record_name() -> String {
+    "Event".to_owned()
+}
+
+/// The format the schema is written in.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SchemaFormat {
+    /// An Avro schema.
+    Avro,
+
+    /// A Protobuf schema.
+    Protobuf,
+
+    /// A JSON Schema.
+    Json,
+}
+
+impl SchemaFormat {
+    const fn schema_type(self) -> &'static str {
+        match self {
+            Self::Avro => "AVRO",
+            Self::Protobuf => "PROTOBUF",
+            Self::Json => "JSON",
+        }
+    }
+}
+
+/// The compatibility level of the subject, set before the schema is checked and registered.
+///
+/// By default, the subject keeps its level, or that of the registry.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CompatibilityLevel {
+    /// The schema can read the data written with the previous version.
+    Backward,
+
+    /// The schema can read the data written with all the previous versions.
+    BackwardTransitive,
+
+    /// The data written with the schema can be read with the previous version.
+    Forward,
+
+    /// The data written with the schema can be read with all the previous versions.
+    ForwardTransitive,
+
+    /// The schema is both backward and forward compatible with the previous version.
+    Full,
+
+    /// The schema is both backward and forward compatible with all the previous versions.
+    FullTransitive,
+
+    /// The schema isn't checked.
+    None,
+}
+
+impl CompatibilityLevel {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Backward => "BACKWARD",
+            Self::BackwardTransitive => "BACKWARD_TRANSITIVE",
+            Self::Forward => "FORWARD",
+            Self::ForwardTransitive => "FORWARD_TRANSITIVE",
+            Self::Full => "FULL",
+            Self::FullTransitive => "FULL_TRANSITIVE",
+            Self::None => "NONE",
+        }
+    }
+}
+
+impl SchemaRegistryConfig {
+    /// Writes the schema of the events, as derived from their schema definition.
+    fn schema(&self, definition: &Definition) -> crate::Result<String> {
+        let schema = EventSchema::from_definition(definition).ok_or(
+            "The schema of the events is unknown, so it can't be registered in the schema registry.",
+        )?;
+        Ok(match self.format {
+            SchemaFormat::Avro => schema.to_avro(&self.record_name).to_string(),
+            SchemaFormat::Protobuf => schema.to_protobuf(&self.record_name),
+            SchemaFormat::Json => schema.to_json_schema().to_string(),
+        })
+    }
+
+    /// Registers the schema of the events under the subject, once checked compatible with its
+    /// latest version.
+    ///
+    /// Returns the ID of the schema in the registry.
+    pub async fn register(
+        &self,
+        subject: &str,
+        definition: &Definition,
+        proxy: &ProxyConfig,
+    ) -> crate::Result<i64> {
+        let schema = json!({
+            "schema": self.schema(definition)?,
+            "schemaType": self.format.schema_type(),
+        });
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls, proxy)?;
+        let subject = utf8_percent_encode(subject, SUBJECT_ESCAPED).to_string();
+
+        if let Some(level) = self.compatibility {
+            self.request(
+                &client,
+                Method::PUT,
+                &format!("config/{}", subject),
+                &json!({ "compatibility": level.as_str() }),
+            )
+            .await?;
+        }
+
+        let compatibility = self
+            .request(
+                &client,
+                Method::POST,
+                &format!(
+                    "compatibility/subjects/{}/versions/latest?verbose=true",
+                    subject
+                ),
+                &schema,
+            )
+            .await;
+        match compatibility {
+            Ok(response) if response["is_compatible"] == json!(false) => {
+                return Err(format!(
+                    "The schema of the events is not compatible with the latest version of the subject: {}",
+                    response["messages"]
+                )
+                .into());
+            }
+            Ok(_) => {}
+            // The first version of a subject has nothing to be compatible with.
+            Err(RegistryError::Response { code, .. })
+                if code == SUBJECT_NOT_FOUND || code == VERSION_NOT_FOUND => {}
+            Err(error) => return Err(error.into()),
+        }
+
+        let response = self
+            .request(
+                &client,
+                Method::POST,
+                &format!("subjects/{}/versions", subject),
+                &schema,
+            )
+            .await?;
+        response["id"]
+            .as_i64()
+            .ok_or_else(|| "The schema registry responded without the ID of the schema.".into())
+    }
+
+    async fn request(
+        &self,
+        client: &HttpClient,
+        method: Method,
+        path: &str,
+        body: &JsonValue,
+    ) -> Result<JsonValue, RegistryError> {
+        let uri = format!("{}/{}", self.url.trim_end_matches('/'), path);
+        let mut request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("Content-Type", CONTENT_TYPE)
+            .header("Accept", CONTENT_TYPE)
+            .body(Body::from(body.to_string()))
+            .map_err(|error| RegistryError::Request {
+                error: error.into(),
+            })?;
+        if let Some(auth) = &self.auth {
+            auth.apply(&mut request);
+        }
+
+        let response = client
+            .send(request)
+            .await
+            .map_err(|error| RegistryError::Request {
+                error: error.into(),
+            })?;
+        let status = response.status();
+        let body: Bytes = hyper::body::to_bytes(response.into_body())
+            .await
+            .map_err(|error| RegistryError::Request {
+                error: error.into(),
+            })?;
+        let body = serde_json::from_slice::<JsonValue>(&body).unwrap_or(JsonValue::Null);
+
+        if status.is_success() {
+            Ok(body)
+        } else {
+            Err(RegistryError::Response {
+                status,
+                code: body["error_code"].as_i64().unwrap_or_default(),
+                message: body["message"].as_str().unwrap_or_default().to_owned(),
+            })
+        }
+    }
+}
+
+#[derive(Debug)]
+enum RegistryError {
+    Request {
+        error: crate::Error,
+    },
+    Response {
+        status: StatusCode,
+        code: i64,
+        message: String,
+    },
+}
+
+impl std::fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Request { error } => write!(f, "Schema registry request failed: {}", error),
+            Self::Response {
+                status,
+                code,
+                message,
+            } => write!(
+                f,
+                "Schema registry responded with {} (error code {}): {}",
+                status, code, message
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RegistryError {}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use value::Kind;
+    use wiremock::{
+        matchers::{body_partial_json, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use super::*;
+
+    fn config(url: String, compatibility: Option<CompatibilityLevel>) -> SchemaRegistryConfig {
+        SchemaRegistryConfig {
+            url,
+            format: SchemaFormat::Json,
+            subject: None,
+            record_name: default_record_name(),
+            compatibility,
+            auth: None,
+            tls: None,
+        }
+    }
+
+    fn definition() -> Definition {
+        Definition::empty_legacy_namespace()
+            .with_field("message", Kind::bytes(), None)
+            .with_field(
+                "http",
+                Kind::object(BTreeMap::from([("status".into(), Kind::integer())])),
+                None,
+            )
+    }
+
+    #[tokio::test]
+    async fn registers_compatible_schema() {
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/config/logs-value"))
+            .and(body_partial_json(json!({"compatibility": "BACKWARD"})))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(json!({"compatibility": "BACKWARD"})),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/compatibility/subjects/logs-value/versions/latest"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"is_compatible": true})))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/subjects/logs-value/versions"))
+            .and(body_partial_json(json!({"schemaType": "JSON"})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"id": 7})))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let config = config(server.uri(), Some(CompatibilityLevel::Backward));
+        let id = config
+            .register("logs-value", &definition(), &ProxyConfig::default())
+            .await
+            .unwrap();
+        assert_eq!(id, 7);
+    }
+
+    #[tokio::test]
+    async fn registers_first_version() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/compatibility/subjects/logs-value/versions/latest"))
+            .respond_with(
+                ResponseTemplate::new(404)
+                    .set_body_json(json!({"error_code": 40401, "message": "Subject not found."})),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/subjects/logs-value/versions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"id": 1})))
+            .mount(&server)
+            .await;
+
+        let config = config(server.uri(), None);
+        let id = config
+            .register("logs-value", &definition(), &ProxyConfig::default())
+            .await
+            .unwrap();
+        assert_eq!(id, 1);
+    }
+
+    #[tokio::test]
+    async fn rejects_incompatible_schema() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/compatibility/subjects/logs-value/versions/latest"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(
+                json!({"is_compatible": false, "messages": ["Field http was removed"]}),
+            ))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/subjects/logs-value/versions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"id": 2})))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let config = config(server.uri(), None);
+        let error = config
+            .register("logs-value", &definition(), &ProxyConfig::default())
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("Field http was removed"));
+    }
+}
@@ -1,12 +1,21 @@
-use std::task::{Context, Poll};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
 
 use bytes::Bytes;
-use futures::future::BoxFuture;
+use futures::future::{self, BoxFuture};
+use rand::Rng;
 use rdkafka::{
-    error::KafkaError,
+    admin::{AdminClient, AdminOptions, NewTopic, TopicReplication},
+    client::DefaultClientContext,
+    error::{KafkaError, RDKafkaErrorCode},
     message::OwnedHeaders,
-    producer::{FutureProducer, FutureRecord},
+    producer::{FutureProducer, FutureRecord, Producer},
     util::Timeout,
+    ClientConfig,
 };
 use tower::Service;
 use vector_core::{
@@ -14,13 +23,23 @@ use vector_core::{
         ByteSize, BytesSent, CountByteSize, InternalEventHandle as _, Protocol, Registered,
     },
     stream::DriverResponse,
+    ByteSizeOf,
 };
 
 use crate::{
     event::{EventFinalizers, EventStatus, Finalizable},
+    internal_events::KafkaAbortTransactionError,
     kafka::KafkaStatisticsContext,
+    sinks::{kafka::config::KafkaTopicCreationConfig, util::record_partitioner::Sticky},
 };
 
+/// How long the `sticky` partitioner keeps a partition before picking another.
+const STICKY_LINGER: Duration = Duration::from_secs(1);
+
+/// How long the number of partitions of a topic is kept before it is fetched again, as the
+/// `metadata.max.age.ms` of the Java producer.
+const METADATA_MAX_AGE: Duration = Duration::from_secs(300);
+
 pub struct KafkaRequest {
     pub body: Bytes,
     pub metadata: KafkaRequestMetadata,
@@ -55,16 +74,194 @@ impl Finalizable for KafkaRequest {
     }
 }
 
+impl ByteSizeOf for KafkaRequest {
+    fn allocated_bytes(&self) -> usize {
+        self.body.len() + self.metadata.key.as_ref().map_or(0, Bytes::len)
+    }
+}
+
+/// The requests produced within a single transaction.
+pub struct KafkaTransactionRequest {
+    pub requests: Vec<KafkaRequest>,
+}
+
+impl Finalizable for KafkaTransactionRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        self.requests
+            .iter_mut()
+            .fold(EventFinalizers::default(), |mut finalizers, request| {
+                finalizers.merge(request.take_finalizers());
+                finalizers
+            })
+    }
+}
+
+pub struct KafkaTransactionResponse {
+    count: usize,
+    event_byte_size: usize,
+}
+
+impl DriverResponse for KafkaTransactionResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> CountByteSize {
+        CountByteSize(self.count, self.event_byte_size)
+    }
+}
+
+/// Creates the topics the requests are produced to, when they don't exist yet.
+pub struct KafkaTopicCreator {
+    admin: AdminClient<DefaultClientContext>,
+    config: KafkaTopicCreationConfig,
+    timeout: Duration,
+    /// The topics known to exist.
+    existing: Mutex<HashSet<String>>,
+}
+
+impl KafkaTopicCreator {
+    pub(crate) fn new(
+        client_config: &ClientConfig,
+        config: KafkaTopicCreationConfig,
+        timeout: Duration,
+    ) -> Result<Self, KafkaError> {
+        Ok(Self {
+            admin: client_config.create()?,
+            config,
+            timeout,
+            existing: Mutex::new(HashSet::new()),
+        })
+    }
+
+    /// Creates the topic, unless it is known to exist.
+    async fn ensure(&self, topic: &str) -> Result<(), KafkaError> {
+        if self.existing.lock().expect("poisoned lock").contains(topic) {
+            return Ok(());
+        }
+
+        let new_topic = self.config.config.iter().fold(
+            NewTopic::new(
+                topic,
+                self.config.partitions,
+                TopicReplication::Fixed(self.config.replication_factor),
+            ),
+            |new_topic, (key, value)| new_topic.set(key, value),
+        );
+        let options = AdminOptions::new().operation_timeout(Some(self.timeout));
+        for result in self.admin.create_topics(&[new_topic], &options).await? {
+            match result {
+                Ok(_) => info!(message = "Created topic.", %topic),
+                Err((_, RDKafkaErrorCode::TopicAlreadyExists)) => {}
+                Err((_, code)) => return Err(KafkaError::AdminOp(code)),
+            }
+        }
+
+        self.existing
+            .lock()
+            .expect("poisoned lock")
+            .insert(topic.to_owned());
+        Ok(())
+    }
+}
+
+/// Assigns the requests of each topic to a random partition, which sticks for a while whatever
+/// their key, as `librdkafka` only does for the records without a key.
+pub struct KafkaStickyPartitioner {
+    timeout: Duration,
+    topics: Mutex<HashMap<String, StickyTopic>>,
+}
+
+struct StickyTopic {
+    partition_count: i32,
+    fetched_at: Instant,
+    partition: Sticky<i32>,
+}
+
+impl KafkaStickyPartitioner {
+    pub(crate) fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            topics: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Gets the partition the next request of the topic is produced to.
+    async fn partition(
+        &self,
+        producer: &FutureProducer<KafkaStatisticsContext>,
+        topic: &str,
+    ) -> Result<i32, KafkaError> {
+        let is_stale = self
+            .topics
+            .lock()
+            .expect("poisoned lock")
+            .get(topic)
+            .map_or(true, |topic| topic.fetched_at.elapsed() >= METADATA_MAX_AGE);
+        if is_stale {
+            let partition_count = self.fetch_partition_count(producer, topic).await?;
+            let mut topics = self.topics.lock().expect("poisoned lock");
+            let topic = topics
+                .entry(topic.to_owned())
+                .or_insert_with(|| StickyTopic {
+                    partition_count,
+                    fetched_at: Instant::now(),
+                    partition: Sticky::new(STICKY_LINGER),
+                });
+            topic.partition_count = partition_count;
+            topic.fetched_at = Instant::now();
+        }
+
+        let mut topics = self.topics.lock().expect("poisoned lock");
+        let topic = topics.get_mut(topic).expect("topic was fetched");
+        let partition_count = topic.partition_count;
+        Ok(topic
+            .partition
+            .get(|| rand::thread_rng().gen_range(0..partition_count)))
+    }
+
+    async fn fetch_partition_count(
+        &self,
+        producer: &FutureProducer<KafkaStatisticsContext>,
+        topic: &str,
+    ) -> Result<i32, KafkaError> {
+        let producer = producer.clone();
+        let topic = topic.to_owned();
+        let timeout = self.timeout;
+        tokio::task::spawn_blocking(move || {
+            let metadata = producer.client().fetch_metadata(Some(&topic), timeout)?;
+            match metadata.topics().first() {
+                Some(topic) if topic.error().is_none() && !topic.partitions().is_empty() => {
+                    Ok(topic.partitions().len() as i32)
+                }
+                _ => Err(KafkaError::MetadataFetch(
+                    RDKafkaErrorCode::UnknownTopicOrPartition,
+                )),
+            }
+        })
+        .await
+        .expect("fetching metadata should not panic")
+    }
+}
+
 #[derive(Clone)]
 pub struct KafkaService {
     kafka_producer: FutureProducer<KafkaStatisticsContext>,
+    topic_creator: Option<Arc<KafkaTopicCreator>>,
+    sticky_partitioner: Option<Arc<KafkaStickyPartitioner>>,
     bytes_sent: Registered<BytesSent>,
 }
 
 impl KafkaService {
-    pub(crate) fn new(kafka_producer: FutureProducer<KafkaStatisticsContext>) -> KafkaService {
+    pub(crate) fn new(
+        kafka_producer: FutureProducer<KafkaStatisticsContext>,
+        topic_creator: Option<KafkaTopicCreator>,
+        sticky_partitioner: Option<KafkaStickyPartitioner>,
+    ) -> KafkaService {
         KafkaService {
             kafka_producer,
+            topic_creator: topic_creator.map(Arc::new),
+            sticky_partitioner: sticky_partitioner.map(Arc::new),
             bytes_sent: register!(BytesSent::from(Protocol("kafka".into()))),
         }
     }
@@ -83,11 +280,26 @@ impl Service<KafkaRequest> for KafkaService {
         let this = self.clone();
 
         Box::pin(async move {
+            if let Some(topic_creator) = &this.topic_creator {
+                topic_creator.ensure(&request.metadata.topic).await?;
+            }
+            let partition = match &this.sticky_partitioner {
+                Some(partitioner) => Some(
+                    partitioner
+                        .partition(&this.kafka_producer, &request.metadata.topic)
+                        .await?,
+                ),
+                None => None,
+            };
+
             let mut record =
                 FutureRecord::to(&request.metadata.topic).payload(request.body.as_ref());
             if let Some(key) = &request.metadata.key {
                 record = record.key(&key[..]);
             }
+            if let Some(partition) = partition {
+                record = record.partition(partition);
+            }
             if let Some(timestamp) = request.metadata.timestamp_millis {
                 record = record.timestamp(timestamp);
             }
@@ -110,3 +322,87 @@ impl Service<KafkaRequest> for KafkaService {
         })
     }
 }
+
+/// Produces each batch of requests within a transaction, so that the requests are only visible to
+/// `read_committed` consumers, and their events only acknowledged, once the transaction commits.
+///
+/// Transactions of a producer can not overlap, so this service must not be called concurrently.
+#[derive(Clone)]
+pub struct KafkaTransactionService {
+    service: KafkaService,
+    timeout: Duration,
+}
+
+impl KafkaTransactionService {
+    pub(crate) const fn new(service: KafkaService, timeout: Duration) -> Self {
+        Self { service, timeout }
+    }
+
+    /// Registers the transactional ID of the producer, fencing any previous producer with the
+    /// same ID and aborting its pending transaction.
+    pub(crate) async fn init_transactions(&self) -> Result<(), KafkaError> {
+        let producer = self.service.kafka_producer.clone();
+        let timeout = self.timeout;
+        tokio::task::spawn_blocking(move || producer.init_transactions(timeout))
+            .await
+            .expect("initializing transactions should not panic")
+    }
+}
+
+impl Service<KafkaTransactionRequest> for KafkaTransactionService {
+    type Response = KafkaTransactionResponse;
+    type Error = KafkaError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: KafkaTransactionRequest) -> Self::Future {
+        let mut service = self.service.clone();
+        let timeout = self.timeout;
+
+        Box::pin(async move {
+            let producer = service.kafka_producer.clone();
+            producer.begin_transaction()?;
+
+            let sends = request
+                .requests
+                .into_iter()
+                .map(|request| service.call(request))
+                .collect::<Vec<_>>();
+            let result = match future::try_join_all(sends).await {
+                Ok(responses) => {
+                    let committer = producer.clone();
+                    tokio::task::spawn_blocking(move || committer.commit_transaction(timeout))
+                        .await
+                        .expect("committing a transaction should not panic")
+                        .map(|()| responses)
+                }
+                Err(error) => Err(error),
+            };
+
+            match result {
+                Ok(responses) => Ok(KafkaTransactionResponse {
+                    count: responses.len(),
+                    event_byte_size: responses
+                        .iter()
+                        .map(|response| response.event_byte_size)
+                        .sum(),
+                }),
+                Err(error) => {
+                    // The records produced so far are discarded along with the transaction, and
+                    // the whole batch is produced again if retried.
+                    let aborted =
+                        tokio::task::spawn_blocking(move || producer.abort_transaction(timeout))
+                            .await
+                            .expect("aborting a transaction should not panic");
+                    if let Err(error) = aborted {
+                        emit!(KafkaAbortTransactionError { error });
+                    }
+                    Err(error)
+                }
+            }
+        })
+    }
+}
@@ -11,7 +11,7 @@ use rdkafka::{
 use snafu::{ResultExt, Snafu};
 use tokio::time::Duration;
 use tower::limit::ConcurrencyLimit;
-use vector_core::config::log_schema;
+use vector_core::{config::log_schema, stream::BatcherSettings};
 
 use super::config::{KafkaRole, KafkaSinkConfig};
 use crate::{
@@ -20,10 +20,14 @@ use crate::{
     kafka::KafkaStatisticsContext,
     sinks::{
         kafka::{
-            config::QUEUED_MIN_MESSAGES, request_builder::KafkaRequestBuilder,
-            service::KafkaService,
+            config::QUEUED_MIN_MESSAGES,
+            request_builder::KafkaRequestBuilder,
+            service::{
+                KafkaService, KafkaStickyPartitioner, KafkaTopicCreator, KafkaTransactionRequest,
+                KafkaTransactionService,
+            },
         },
-        util::{builder::SinkBuilderExt, StreamSink},
+        util::{builder::SinkBuilderExt, record_partitioner::RecordPartitioner, StreamSink},
     },
     template::{Template, TemplateParseError},
 };
@@ -42,7 +46,11 @@ pub struct KafkaSink {
     service: KafkaService,
     topic: Template,
     key_field: Option<String>,
+    key: Option<Template>,
     headers_key: Option<String>,
+    // Set when producing exactly once, with the settings of the batch of each transaction.
+    transactions: Option<BatcherSettings>,
+    message_timeout: Duration,
 }
 
 pub(crate) fn create_producer(
@@ -61,22 +69,42 @@ impl KafkaSink {
         let transformer = config.encoding.transformer();
         let serializer = config.encoding.build()?;
         let encoder = Encoder::<()>::new(serializer);
+        let topic_creator = match config.topic_creation.clone() {
+            Some(topic_creation) => Some(
+                KafkaTopicCreator::new(
+                    &config.to_rdkafka(KafkaRole::Admin)?,
+                    topic_creation,
+                    Duration::from_millis(config.socket_timeout_ms),
+                )
+                .context(KafkaCreateFailedSnafu)?,
+            ),
+            None => None,
+        };
+        let sticky_partitioner = (config.partitioner == Some(RecordPartitioner::Sticky))
+            .then(|| KafkaStickyPartitioner::new(Duration::from_millis(config.socket_timeout_ms)));
+        let transactions = if config.exactly_once {
+            Some(config.batch.into_batcher_settings()?)
+        } else {
+            None
+        };
 
         Ok(KafkaSink {
             headers_key: config.headers_key,
             transformer,
             encoder,
-            service: KafkaService::new(producer),
+            service: KafkaService::new(producer, topic_creator, sticky_partitioner),
             topic: Template::try_from(config.topic).context(TopicTemplateSnafu)?,
             key_field: config.key_field,
+            key: config.key,
+            transactions,
+            message_timeout: Duration::from_millis(config.message_timeout_ms),
         })
     }
 
     async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
-        // rdkafka will internally retry forever, so we need some limit to prevent this from overflowing
-        let service = ConcurrencyLimit::new(self.service, QUEUED_MIN_MESSAGES as usize);
         let mut request_builder = KafkaRequestBuilder {
             key_field: self.key_field,
+            key_template: self.key,
             headers_key: self.headers_key,
             topic_template: self.topic,
             transformer: self.transformer,
@@ -84,13 +112,34 @@ impl KafkaSink {
             log_schema: log_schema(),
         };
 
-        let sink = input
-            .filter_map(|event|
-                // request_builder is fallible but the places it can fail are emitting
-                // `Error` and `DroppedEvent` internal events appropriately so no need to here.
-                future::ready(request_builder.build_request(event)))
-            .into_driver(service);
-        sink.run().await
+        let requests = input.filter_map(|event|
+            // request_builder is fallible but the places it can fail are emitting
+            // `Error` and `DroppedEvent` internal events appropriately so no need to here.
+            future::ready(request_builder.build_request(event)));
+
+        match self.transactions {
+            Some(batch_settings) => {
+                let service = KafkaTransactionService::new(self.service, self.message_timeout);
+                if let Err(error) = service.init_transactions().await {
+                    error!(message = "Failed to initialize Kafka transactions.", %error);
+                    return Err(());
+                }
+
+                // Transactions of a producer can not overlap, so they are produced one at a time.
+                let service = ConcurrencyLimit::new(service, 1);
+                requests
+                    .batched(batch_settings.into_byte_size_config())
+                    .map(|requests| KafkaTransactionRequest { requests })
+                    .into_driver(service)
+                    .run()
+                    .await
+            }
+            None => {
+                // rdkafka will internally retry forever, so we need some limit to prevent this from overflowing
+                let service = ConcurrencyLimit::new(self.service, QUEUED_MIN_MESSAGES as usize);
+                requests.into_driver(service).run().await
+            }
+        }
     }
 }
 
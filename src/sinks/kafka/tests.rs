@@ -25,7 +25,7 @@ mod integration_test {
         kafka::{KafkaAuthConfig, KafkaCompression, KafkaSaslConfig},
         sinks::{
             kafka::{
-                config::{KafkaRole, KafkaSinkConfig},
+                config::{KafkaRole, KafkaSinkConfig, KafkaTopicCreationConfig},
                 sink::KafkaSink,
                 *,
             },
@@ -57,6 +57,8 @@ mod integration_test {
             bootstrap_servers: kafka_address(9091),
             topic: topic.clone(),
             key_field: None,
+            key: None,
+            partitioner: None,
             encoding: TextSerializerConfig::new().into(),
             batch: BatchConfig::default(),
             compression: KafkaCompression::None,
@@ -65,6 +67,10 @@ mod integration_test {
             message_timeout_ms: 300000,
             librdkafka_options: HashMap::new(),
             headers_key: None,
+            exactly_once: false,
+            transactional_id: None,
+            topic_creation: None,
+            schema_registry: None,
             acknowledgements: Default::default(),
         };
         self::sink::healthcheck(config).await.unwrap();
@@ -111,6 +117,8 @@ mod integration_test {
             compression: KafkaCompression::None,
             encoding: TextSerializerConfig::new().into(),
             key_field: None,
+            key: None,
+            partitioner: None,
             auth: KafkaAuthConfig {
                 sasl: None,
                 tls: None,
@@ -120,6 +128,10 @@ mod integration_test {
             batch,
             librdkafka_options,
             headers_key: None,
+            exactly_once: false,
+            transactional_id: None,
+            topic_creation: None,
+            schema_registry: None,
             acknowledgements: Default::default(),
         };
         config.clone().to_rdkafka(KafkaRole::Consumer)?;
@@ -230,6 +242,124 @@ mod integration_test {
         .await;
     }
 
+    #[tokio::test]
+    async fn kafka_exactly_once() {
+        crate::test_util::trace_init();
+
+        let server = kafka_address(9091);
+        let topic = format!("test-{}", random_string(10));
+        let config = KafkaSinkConfig {
+            bootstrap_servers: server.clone(),
+            topic: topic.clone(),
+            key_field: None,
+            key: None,
+            partitioner: None,
+            encoding: TextSerializerConfig::new().into(),
+            batch: BatchConfig::default(),
+            compression: KafkaCompression::None,
+            auth: KafkaAuthConfig::default(),
+            socket_timeout_ms: 60000,
+            message_timeout_ms: 60000,
+            librdkafka_options: HashMap::new(),
+            headers_key: None,
+            exactly_once: true,
+            transactional_id: Some(format!("vector-{}", random_string(10))),
+            topic_creation: None,
+            schema_registry: None,
+            acknowledgements: Default::default(),
+        };
+
+        let num_events = 1000;
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let (input, events) = random_lines_with_stream(100, num_events, Some(batch));
+        assert_sink_compliance(&SINK_TAGS, async move {
+            let sink = KafkaSink::new(config).unwrap();
+            let sink = VectorSink::from_event_streamsink(sink);
+            sink.run(events).await
+        })
+        .await
+        .expect("Running sink failed");
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+        // Only committed records are read back.
+        let mut client_config = rdkafka::ClientConfig::new();
+        client_config.set("bootstrap.servers", server.as_str());
+        client_config.set("group.id", &random_string(10));
+        client_config.set("isolation.level", "read_committed");
+
+        let mut tpl = TopicPartitionList::new();
+        tpl.add_partition(&topic, 0)
+            .set_offset(Offset::Beginning)
+            .unwrap();
+
+        let consumer: BaseConsumer = client_config.create().unwrap();
+        consumer.assign(&tpl).unwrap();
+
+        let mut failures = 0;
+        let mut out = Vec::new();
+        while failures < 100 && out.len() < input.len() {
+            match consumer.poll(Duration::from_secs(3)) {
+                Some(Ok(msg)) => {
+                    let s: &str = msg.payload_view().unwrap().unwrap();
+                    out.push(s.to_owned());
+                }
+                _ => {
+                    failures += 1;
+                    thread::sleep(Duration::from_millis(50));
+                }
+            }
+        }
+
+        assert_eq!(out, input);
+    }
+
+    #[tokio::test]
+    async fn kafka_topic_creation() {
+        crate::test_util::trace_init();
+
+        let server = kafka_address(9091);
+        let topic = format!("test-{}", random_string(10));
+        let config = KafkaSinkConfig {
+            bootstrap_servers: server.clone(),
+            topic: topic.clone(),
+            key_field: None,
+            key: None,
+            partitioner: None,
+            encoding: TextSerializerConfig::new().into(),
+            batch: BatchConfig::default(),
+            compression: KafkaCompression::None,
+            auth: KafkaAuthConfig::default(),
+            socket_timeout_ms: 60000,
+            message_timeout_ms: 300000,
+            librdkafka_options: HashMap::new(),
+            headers_key: None,
+            exactly_once: false,
+            transactional_id: None,
+            topic_creation: Some(KafkaTopicCreationConfig {
+                partitions: 3,
+                replication_factor: 1,
+                config: HashMap::from([("retention.ms".to_owned(), "3600000".to_owned())]),
+            }),
+            schema_registry: None,
+            acknowledgements: Default::default(),
+        };
+
+        let (_input, events) = random_lines_with_stream(100, 10, None);
+        let sink = KafkaSink::new(config).unwrap();
+        VectorSink::from_event_streamsink(sink)
+            .run(events)
+            .await
+            .expect("Running sink failed");
+
+        let mut client_config = rdkafka::ClientConfig::new();
+        client_config.set("bootstrap.servers", server.as_str());
+        let consumer: BaseConsumer = client_config.create().unwrap();
+        let metadata = consumer
+            .fetch_metadata(Some(&topic), Duration::from_secs(3))
+            .unwrap();
+        assert_eq!(metadata.topics()[0].partitions().len(), 3);
+    }
+
     async fn kafka_happy_path(
         server: String,
         sasl: Option<KafkaSaslConfig>,
@@ -243,6 +373,8 @@ mod integration_test {
             bootstrap_servers: server.clone(),
             topic: format!("{}-%Y%m%d", topic),
             key_field: None,
+            key: None,
+            partitioner: None,
             encoding: TextSerializerConfig::new().into(),
             batch: BatchConfig::default(),
             compression,
@@ -251,6 +383,10 @@ mod integration_test {
             message_timeout_ms: 300000,
             librdkafka_options: HashMap::new(),
             headers_key: Some(headers_key.clone()),
+            exactly_once: false,
+            transactional_id: None,
+            topic_creation: None,
+            schema_registry: None,
             acknowledgements: Default::default(),
         };
         let topic = format!("{}-{}", topic, chrono::Utc::now().format("%Y%m%d"));
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
 use futures::future::FutureExt;
+use value::Kind;
 use vector_config::configurable_component;
 
 use super::{healthcheck::healthcheck, sink::LokiSink};
@@ -8,6 +9,7 @@ use crate::{
     codecs::EncodingConfig,
     config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
     http::{Auth, HttpClient, MaybeAuth},
+    schema,
     sinks::{
         util::{BatchConfig, Compression, SinkBatchSettings, TowerRequestConfig, UriSerde},
         VectorSink,
@@ -28,14 +30,17 @@ pub enum ExtendedCompression {
 }
 
 /// Compose with basic compression and Loki-specific compression.
+///
+/// The Loki-specific compression comes first, so that `snappy` selects it rather than the framed
+/// Snappy compression of the basic ones, which Loki doesn't accept.
 #[configurable_component]
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 #[serde(untagged)]
 pub enum CompressionConfigAdapter {
-    /// Basic compression.
-    Original(#[configurable(derived)] Compression),
     /// Loki-specific compression.
     Extended(#[configurable(derived)] ExtendedCompression),
+    /// Basic compression.
+    Original(#[configurable(derived)] Compression),
 }
 
 impl CompressionConfigAdapter {
@@ -60,7 +65,7 @@ impl Default for CompressionConfigAdapter {
 pub struct LokiConfig {
     /// The base URL of the Loki instance.
     ///
-    /// Vector will append `/loki/api/v1/push` to this.
+    /// Vector will append the path of the configured `transport` to this.
     pub endpoint: UriSerde,
 
     #[configurable(derived)]
@@ -84,8 +89,26 @@ pub struct LokiConfig {
     /// with Loki. To prevent this from happening, reduce the number of unique label keys and
     /// values.
     #[configurable(metadata(templateable))]
+    #[serde(default)]
     pub labels: HashMap<Template, Template>,
 
+    #[configurable(derived)]
+    pub auto_labels: Option<AutoLabelsConfig>,
+
+    /// A set of structured metadata that is attached to each event.
+    ///
+    /// Structured metadata is a set of key-value pairs stored alongside the log line, outside of
+    /// the label set, so high-cardinality values such as trace IDs can be attached without
+    /// creating new streams.
+    ///
+    /// Both keys and values are templatable, and keys can be suffixed with a “*” to allow the
+    /// expansion of objects into multiple pairs, the same as `labels`.
+    ///
+    /// Requires Loki 3.0.0 or newer.
+    #[configurable(metadata(templateable))]
+    #[serde(default)]
+    pub structured_metadata: HashMap<Template, Template>,
+
     /// Whether or not to delete fields from the event when they are used as labels.
     #[serde(default = "crate::serde::default_false")]
     pub remove_label_fields: bool,
@@ -104,6 +127,14 @@ pub struct LokiConfig {
     #[serde(default)]
     pub out_of_order_action: OutOfOrderAction,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub out_of_order_window: OutOfOrderWindow,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub transport: LokiTransport,
+
     #[configurable(derived)]
     pub auth: Option<Auth>,
 
@@ -166,6 +197,145 @@ pub enum OutOfOrderAction {
     Accept,
 }
 
+/// Automatic label extraction.
+///
+/// Derives labels from the fields the schema assigns a semantic meaning to. Labels set explicitly
+/// in `labels` take precedence over derived ones.
+///
+/// To protect Loki from high-cardinality streams, each derived label only accepts up to
+/// `max_cardinality` distinct values. Once that limit is reached, new values are sent as
+/// structured metadata instead.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct AutoLabelsConfig {
+    /// The semantic meanings to derive labels from.
+    #[serde(default = "default_auto_label_meanings")]
+    pub meanings: Vec<LabelMeaning>,
+
+    /// The maximum number of distinct values a derived label can take.
+    #[serde(default = "default_max_cardinality")]
+    pub max_cardinality: usize,
+}
+
+impl Default for AutoLabelsConfig {
+    fn default() -> Self {
+        Self {
+            meanings: default_auto_label_meanings(),
+            max_cardinality: default_max_cardinality(),
+        }
+    }
+}
+
+fn default_auto_label_meanings() -> Vec<LabelMeaning> {
+    vec![
+        LabelMeaning::Severity,
+        LabelMeaning::Service,
+        LabelMeaning::Host,
+    ]
+}
+
+const fn default_max_cardinality() -> usize {
+    100
+}
+
+/// A semantic meaning labels can be derived from.
+#[configurable_component]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum LabelMeaning {
+    /// The `severity` meaning, set as the `level` label.
+    Severity,
+
+    /// The `service` meaning, set as the `service_name` label.
+    Service,
+
+    /// The `host` meaning, set as the `host` label.
+    Host,
+}
+
+impl LabelMeaning {
+    /// The name of the semantic meaning.
+    pub const fn meaning(self) -> &'static str {
+        match self {
+            LabelMeaning::Severity => "severity",
+            LabelMeaning::Service => "service",
+            LabelMeaning::Host => "host",
+        }
+    }
+
+    /// The name of the label derived from the semantic meaning.
+    pub const fn label(self) -> &'static str {
+        match self {
+            LabelMeaning::Severity => "level",
+            LabelMeaning::Service => "service_name",
+            LabelMeaning::Host => "host",
+        }
+    }
+}
+
+/// Out-of-order window settings.
+///
+/// Loki 2.4.0 and newer accept events that are older than the latest event of a stream, as long as
+/// they fall within the tenant's `out_of_order_time_window`. Events within the window are sent
+/// as-is, while `out_of_order_action` only applies to events that fall outside of it.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct OutOfOrderWindow {
+    /// The out-of-order window, in seconds, applied to every tenant.
+    ///
+    /// If not set, every event older than the latest event of its stream is out-of-order.
+    pub window_secs: Option<u64>,
+
+    /// Per-tenant out-of-order windows, in seconds, keyed by the rendered `tenant_id`.
+    ///
+    /// These take precedence over `window_secs`.
+    #[serde(default)]
+    pub tenant_window_secs: HashMap<String, u64>,
+}
+
+impl OutOfOrderWindow {
+    /// Returns the window, in nanoseconds, for the given tenant.
+    pub fn window_nanos(&self, tenant_id: Option<&str>) -> Option<i64> {
+        tenant_id
+            .and_then(|tenant_id| self.tenant_window_secs.get(tenant_id))
+            .or(self.window_secs.as_ref())
+            .map(|secs| (*secs as i64).saturating_mul(1_000_000_000))
+    }
+}
+
+/// The transport used to send events to Loki.
+#[configurable_component]
+#[derive(Copy, Clone, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LokiTransport {
+    /// The Loki push API.
+    ///
+    /// Vector will append `/loki/api/v1/push` to the endpoint.
+    #[derivative(Default)]
+    Push,
+
+    /// The native OpenTelemetry (OTLP/HTTP) ingestion endpoint, encoded as JSON.
+    ///
+    /// Vector will append `/otlp/v1/logs` to the endpoint. Labels are sent as resource
+    /// attributes, and structured metadata as log record attributes. As OTLP doesn't support
+    /// `snappy` compression, `gzip` is used instead when `snappy` is configured.
+    ///
+    /// Requires Loki 3.0.0 or newer.
+    Otlp,
+}
+
+impl LokiTransport {
+    pub const fn path(self) -> &'static str {
+        match self {
+            LokiTransport::Push => "loki/api/v1/push",
+            LokiTransport::Otlp => "otlp/v1/logs",
+        }
+    }
+}
+
 impl GenerateConfig for LokiConfig {
     fn generate_config() -> toml::Value {
         toml::from_str(
@@ -191,16 +361,26 @@ impl SinkConfig for LokiConfig {
         &self,
         cx: SinkContext,
     ) -> crate::Result<(VectorSink, crate::sinks::Healthcheck)> {
-        if self.labels.is_empty() {
+        if self.labels.is_empty() && self.auto_labels.is_none() {
             return Err("`labels` must include at least one label.".into());
         }
 
+        if matches!(&self.auto_labels, Some(auto_labels) if auto_labels.max_cardinality == 0) {
+            return Err("`auto_labels.max_cardinality` must be greater than zero.".into());
+        }
+
         for label in self.labels.keys() {
             if !valid_label_name(label) {
                 return Err(format!("Invalid label name {:?}", label.get_ref()).into());
             }
         }
 
+        for key in self.structured_metadata.keys() {
+            if !valid_label_name(key) {
+                return Err(format!("Invalid structured metadata name {:?}", key.get_ref()).into());
+            }
+        }
+
         let client = self.build_client(cx)?;
 
         let config = LokiConfig {
@@ -216,7 +396,13 @@ impl SinkConfig for LokiConfig {
     }
 
     fn input(&self) -> Input {
+        let mut requirement = schema::Requirement::empty();
+        for meaning in self.auto_labels.iter().flat_map(|config| &config.meanings) {
+            requirement = requirement.optional_meaning(meaning.meaning(), Kind::any());
+        }
+
         Input::new(self.encoding.config().input_type() & DataType::Log)
+            .with_schema_requirement(requirement)
     }
 
     fn acknowledgements(&self) -> &AcknowledgementsConfig {
@@ -253,7 +439,23 @@ pub fn valid_label_name(label: &Template) -> bool {
 mod tests {
     use std::convert::TryInto;
 
-    use super::valid_label_name;
+    use super::{valid_label_name, OutOfOrderWindow};
+
+    #[test]
+    fn out_of_order_window_per_tenant() {
+        let window = OutOfOrderWindow {
+            window_secs: Some(60),
+            tenant_window_secs: [("team-a".to_owned(), 3600)].into_iter().collect(),
+        };
+
+        assert_eq!(window.window_nanos(None), Some(60_000_000_000));
+        assert_eq!(window.window_nanos(Some("team-b")), Some(60_000_000_000));
+        assert_eq!(window.window_nanos(Some("team-a")), Some(3_600_000_000_000));
+        assert_eq!(
+            OutOfOrderWindow::default().window_nanos(Some("team-a")),
+            None
+        );
+    }
 
     #[test]
     fn valid_label_names() {
@@ -16,6 +16,7 @@ pub type Labels = Vec<(String, String)>;
 pub enum LokiBatchEncoding {
     Json,
     Protobuf,
+    Otlp,
 }
 
 #[derive(Clone)]
@@ -43,12 +44,14 @@ impl Encoder<Vec<LokiRecord>> for LokiBatchEncoder {
                         loki_logproto::util::Entry(
                             event.timestamp,
                             String::from_utf8_lossy(&event.event).into_owned(),
+                            event.structured_metadata.clone(),
                         )
                     })
                     .collect();
                 let batch = loki_logproto::util::Batch(labels, entries);
                 batch.encode()
             }
+            LokiBatchEncoding::Otlp => serde_json::to_vec(&batch.to_otlp())?,
         };
         write_all(writer, count, &body).map(|()| body.len())
     }
@@ -77,15 +80,60 @@ impl From<Vec<LokiRecord>> for LokiBatch {
     }
 }
 
+impl LokiBatch {
+    /// Converts the batch to an OTLP `ExportLogsServiceRequest`, in its JSON representation.
+    ///
+    /// The stream labels become resource attributes, which Loki turns back into labels, and the
+    /// structured metadata of each event becomes attributes of its log record.
+    fn to_otlp(&self) -> serde_json::Value {
+        fn attributes<'a>(
+            pairs: impl Iterator<Item = (&'a String, &'a String)>,
+        ) -> Vec<serde_json::Value> {
+            pairs
+                .map(|(key, value)| {
+                    serde_json::json!({ "key": key, "value": { "stringValue": value } })
+                })
+                .collect()
+        }
+
+        let mut labels = self.stream.iter().collect::<Vec<_>>();
+        labels.sort();
+
+        let log_records = self
+            .values
+            .iter()
+            .map(|event| {
+                serde_json::json!({
+                    "timeUnixNano": event.timestamp.to_string(),
+                    "body": { "stringValue": String::from_utf8_lossy(&event.event) },
+                    "attributes": attributes(event.structured_metadata.iter().map(|(k, v)| (k, v))),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        serde_json::json!({
+            "resourceLogs": [{
+                "resource": { "attributes": attributes(labels.into_iter()) },
+                "scopeLogs": [{ "logRecords": log_records }],
+            }]
+        })
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct LokiEvent {
     pub timestamp: i64,
     pub event: Bytes,
+    pub structured_metadata: Labels,
 }
 
 impl ByteSizeOf for LokiEvent {
     fn allocated_bytes(&self) -> usize {
-        self.timestamp.allocated_bytes() + self.event.allocated_bytes()
+        self.timestamp.allocated_bytes()
+            + self.event.allocated_bytes()
+            + self.structured_metadata.iter().fold(0, |res, item| {
+                res + item.0.allocated_bytes() + item.1.allocated_bytes()
+            })
     }
 }
 
@@ -94,10 +142,23 @@ impl Serialize for LokiEvent {
     where
         S: serde::Serializer,
     {
-        let mut seq = serializer.serialize_seq(Some(2))?;
+        let len = if self.structured_metadata.is_empty() {
+            2
+        } else {
+            3
+        };
+        let mut seq = serializer.serialize_seq(Some(len))?;
         seq.serialize_element(&self.timestamp.to_string())?;
         let event = String::from_utf8_lossy(&self.event);
         seq.serialize_element(&event)?;
+        if !self.structured_metadata.is_empty() {
+            let structured_metadata = self
+                .structured_metadata
+                .iter()
+                .map(|(k, v)| (k, v))
+                .collect::<std::collections::BTreeMap<_, _>>();
+            seq.serialize_element(&structured_metadata)?;
+        }
         seq.end()
     }
 }
@@ -163,3 +224,63 @@ impl PartitionKey {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(line: &str, structured_metadata: Labels) -> LokiRecord {
+        let mut labels = vec![("app".to_owned(), "vector".to_owned())];
+        LokiRecord {
+            partition: PartitionKey::new(None, &mut labels),
+            labels,
+            event: LokiEvent {
+                timestamp: 1,
+                event: Bytes::from(line.to_owned()),
+                structured_metadata,
+            },
+            finalizers: EventFinalizers::default(),
+        }
+    }
+
+    #[test]
+    fn serialize_structured_metadata() {
+        let batch = LokiBatch::from(vec![
+            record("plain", vec![]),
+            record("with", vec![("trace_id".to_owned(), "abc".to_owned())]),
+        ]);
+
+        assert_eq!(
+            serde_json::to_value(&batch.values).unwrap(),
+            serde_json::json!([["1", "plain"], ["1", "with", { "trace_id": "abc" }]])
+        );
+    }
+
+    #[test]
+    fn encode_otlp() {
+        let batch = LokiBatch::from(vec![record(
+            "hello",
+            vec![("trace_id".to_owned(), "abc".to_owned())],
+        )]);
+
+        assert_eq!(
+            batch.to_otlp(),
+            serde_json::json!({
+                "resourceLogs": [{
+                    "resource": {
+                        "attributes": [{ "key": "app", "value": { "stringValue": "vector" } }]
+                    },
+                    "scopeLogs": [{
+                        "logRecords": [{
+                            "timeUnixNano": "1",
+                            "body": { "stringValue": "hello" },
+                            "attributes": [
+                                { "key": "trace_id", "value": { "stringValue": "abc" } }
+                            ],
+                        }]
+                    }],
+                }]
+            })
+        );
+    }
+}
@@ -12,7 +12,7 @@ use vector_core::{
     stream::DriverResponse,
 };
 
-use crate::sinks::loki::config::{CompressionConfigAdapter, ExtendedCompression};
+use crate::sinks::loki::config::{CompressionConfigAdapter, ExtendedCompression, LokiTransport};
 use crate::{
     http::{get_http_scheme_from_uri, Auth, HttpClient},
     sinks::util::{metadata::RequestMetadata, retries::RetryLogic, UriSerde},
@@ -36,6 +36,13 @@ impl RetryLogic for LokiRetryLogic {
             LokiError::HttpError { .. } => true,
         }
     }
+
+    fn error_status(&self, error: &Self::Error) -> Option<StatusCode> {
+        match error {
+            LokiError::ServerError { code } => Some(*code),
+            LokiError::HttpError { .. } => None,
+        }
+    }
 }
 
 #[derive(Debug, Snafu)]
@@ -91,8 +98,13 @@ pub struct LokiService {
 }
 
 impl LokiService {
-    pub fn new(client: HttpClient, endpoint: UriSerde, auth: Option<Auth>) -> crate::Result<Self> {
-        let endpoint = endpoint.append_path("loki/api/v1/push")?.with_auth(auth);
+    pub fn new(
+        client: HttpClient,
+        endpoint: UriSerde,
+        transport: LokiTransport,
+        auth: Option<Auth>,
+    ) -> crate::Result<Self> {
+        let endpoint = endpoint.append_path(transport.path())?.with_auth(auth);
 
         Ok(Self { client, endpoint })
     }
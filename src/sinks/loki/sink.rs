@@ -1,4 +1,7 @@
-use std::{collections::HashMap, num::NonZeroUsize};
+use std::{
+    collections::{HashMap, HashSet},
+    num::NonZeroUsize,
+};
 
 use bytes::{Bytes, BytesMut};
 use futures::{stream::BoxStream, StreamExt};
@@ -15,7 +18,10 @@ use vector_core::{
 };
 
 use super::{
-    config::{LokiConfig, OutOfOrderAction},
+    config::{
+        AutoLabelsConfig, LabelMeaning, LokiConfig, LokiTransport, OutOfOrderAction,
+        OutOfOrderWindow,
+    },
     event::{LokiBatchEncoder, LokiEvent, LokiRecord, PartitionKey},
     service::{LokiRequest, LokiRetryLogic, LokiService},
 };
@@ -26,8 +32,8 @@ use crate::{
     config::log_schema,
     http::HttpClient,
     internal_events::{
-        LokiEventUnlabeled, LokiOutOfOrderEventDropped, LokiOutOfOrderEventRewritten,
-        SinkRequestBuildError, TemplateRenderingError,
+        LokiEventUnlabeled, LokiLabelCardinalityExceeded, LokiOutOfOrderEventDropped,
+        LokiOutOfOrderEventRewritten, SinkRequestBuildError, TemplateRenderingError,
     },
     sinks::util::{
         builder::SinkBuilderExt,
@@ -148,44 +154,74 @@ impl RequestBuilder<(PartitionKey, Vec<LokiRecord>)> for LokiRequestBuilder {
     }
 }
 
+/// Derives labels from schema meanings, bounding the number of distinct values of each label.
+#[derive(Clone, Debug)]
+pub(super) struct AutoLabels {
+    meanings: Vec<LabelMeaning>,
+    max_cardinality: usize,
+    seen: HashMap<&'static str, HashSet<String>>,
+}
+
+impl AutoLabels {
+    pub(super) fn new(config: AutoLabelsConfig) -> Self {
+        Self {
+            meanings: config.meanings,
+            max_cardinality: config.max_cardinality,
+            seen: HashMap::new(),
+        }
+    }
+
+    /// Adds the derived labels of the event, moving values exceeding the cardinality limit to the
+    /// structured metadata.
+    fn apply(
+        &mut self,
+        event: &Event,
+        labels: &mut Vec<(String, String)>,
+        structured_metadata: &mut Vec<(String, String)>,
+    ) {
+        for meaning in &self.meanings {
+            let label = meaning.label();
+            if labels.iter().any(|(key, _)| key == label) {
+                continue;
+            }
+
+            let value = match event.as_log().get_by_meaning(meaning.meaning()) {
+                Some(value) => value.to_string_lossy(),
+                None => continue,
+            };
+
+            // Values already seen are always admitted, new ones only while below the limit.
+            let seen = self.seen.entry(label).or_default();
+            if seen.contains(&value) || seen.len() < self.max_cardinality {
+                seen.insert(value.clone());
+                labels.push((label.to_owned(), value));
+            } else {
+                emit!(LokiLabelCardinalityExceeded { label });
+                structured_metadata.push((label.to_owned(), value));
+            }
+        }
+    }
+}
+
 #[derive(Clone)]
 pub(super) struct EventEncoder {
     key_partitioner: KeyPartitioner,
     transformer: Transformer,
     encoder: Encoder<()>,
     labels: HashMap<Template, Template>,
+    structured_metadata: HashMap<Template, Template>,
+    auto_labels: Option<AutoLabels>,
     remove_label_fields: bool,
     remove_timestamp: bool,
 }
 
 impl EventEncoder {
     fn build_labels(&self, event: &Event) -> Vec<(String, String)> {
-        let mut vec: Vec<(String, String)> = Vec::new();
-
-        for (key_template, value_template) in self.labels.iter() {
-            if let (Ok(key), Ok(value)) = (
-                key_template.render_string(event),
-                value_template.render_string(event),
-            ) {
-                if let Some(opening_prefix) = key.strip_suffix('*') {
-                    let output: Result<serde_json::map::Map<String, serde_json::Value>, _> =
-                        serde_json::from_str(value.as_str());
-
-                    if let Ok(output) = output {
-                        // key_* -> key_one, key_two, key_three
-                        for (k, v) in output {
-                            vec.push((
-                                slugify_text(format!("{}{}", opening_prefix, k)),
-                                Value::from(v).to_string_lossy(),
-                            ))
-                        }
-                    }
-                } else {
-                    vec.push((key, value));
-                }
-            }
-        }
-        vec
+        render_pairs(&self.labels, event)
+    }
+
+    fn build_structured_metadata(&self, event: &Event) -> Vec<(String, String)> {
+        render_pairs(&self.structured_metadata, event)
     }
 
     fn remove_label_fields(&self, event: &mut Event) {
@@ -204,6 +240,10 @@ impl EventEncoder {
         let tenant_id = self.key_partitioner.partition(&event);
         let finalizers = event.take_finalizers();
         let mut labels = self.build_labels(&event);
+        let mut structured_metadata = self.build_structured_metadata(&event);
+        if let Some(auto_labels) = self.auto_labels.as_mut() {
+            auto_labels.apply(&event, &mut labels, &mut structured_metadata);
+        }
         self.remove_label_fields(&mut event);
 
         let schema = log_schema();
@@ -236,6 +276,7 @@ impl EventEncoder {
             event: LokiEvent {
                 timestamp,
                 event: bytes.freeze(),
+                structured_metadata,
             },
             partition,
             finalizers,
@@ -243,6 +284,37 @@ impl EventEncoder {
     }
 }
 
+/// Renders a set of templated key-value pairs, expanding keys suffixed with `*` into one pair per
+/// field of the rendered object.
+fn render_pairs(templates: &HashMap<Template, Template>, event: &Event) -> Vec<(String, String)> {
+    let mut vec: Vec<(String, String)> = Vec::new();
+
+    for (key_template, value_template) in templates.iter() {
+        if let (Ok(key), Ok(value)) = (
+            key_template.render_string(event),
+            value_template.render_string(event),
+        ) {
+            if let Some(opening_prefix) = key.strip_suffix('*') {
+                let output: Result<serde_json::map::Map<String, serde_json::Value>, _> =
+                    serde_json::from_str(value.as_str());
+
+                if let Ok(output) = output {
+                    // key_* -> key_one, key_two, key_three
+                    for (k, v) in output {
+                        vec.push((
+                            slugify_text(format!("{}{}", opening_prefix, k)),
+                            Value::from(v).to_string_lossy(),
+                        ))
+                    }
+                }
+            } else {
+                vec.push((key, value));
+            }
+        }
+    }
+    vec
+}
+
 struct FilteredRecord {
     pub rewritten: bool,
     pub inner: LokiRecord,
@@ -281,13 +353,15 @@ impl ByteSizeOf for FilteredRecord {
 struct RecordFilter {
     timestamps: HashMap<PartitionKey, i64>,
     out_of_order_action: OutOfOrderAction,
+    out_of_order_window: OutOfOrderWindow,
 }
 
 impl RecordFilter {
-    fn new(out_of_order_action: OutOfOrderAction) -> Self {
+    fn new(out_of_order_action: OutOfOrderAction, out_of_order_window: OutOfOrderWindow) -> Self {
         Self {
             timestamps: HashMap::new(),
             out_of_order_action,
+            out_of_order_window,
         }
     }
 }
@@ -296,6 +370,16 @@ impl RecordFilter {
     pub fn filter_record(&mut self, mut record: LokiRecord) -> Option<FilteredRecord> {
         if let Some(latest) = self.timestamps.get_mut(&record.partition) {
             if record.event.timestamp < *latest {
+                let within_window = self
+                    .out_of_order_window
+                    .window_nanos(record.partition.tenant_id.as_deref())
+                    .map_or(false, |window| {
+                        latest.saturating_sub(record.event.timestamp) <= window
+                    });
+                if within_window {
+                    return Some(FilteredRecord::valid(record));
+                }
+
                 match self.out_of_order_action {
                     OutOfOrderAction::Drop => None,
                     OutOfOrderAction::RewriteTimestamp => {
@@ -321,13 +405,27 @@ pub struct LokiSink {
     pub(super) encoder: EventEncoder,
     batch_settings: BatcherSettings,
     out_of_order_action: OutOfOrderAction,
+    out_of_order_window: OutOfOrderWindow,
     service: Svc<LokiService, LokiRetryLogic>,
 }
 
 impl LokiSink {
     #[allow(clippy::missing_const_for_fn)] // const cannot run destructor
     pub fn new(config: LokiConfig, client: HttpClient) -> crate::Result<Self> {
-        let compression = config.compression;
+        // OTLP has no notion of snappy compression, so fall back to gzip.
+        let compression = match (config.transport, config.compression) {
+            (LokiTransport::Otlp, CompressionConfigAdapter::Extended(_)) => {
+                CompressionConfigAdapter::Original(Compression::gzip_default())
+            }
+            // Loki only accepts snappy compression for Protocol Buffers, without the framing format.
+            (_, CompressionConfigAdapter::Original(Compression::Snappy)) => {
+                return Err(
+                    "Loki doesn't accept framed snappy compression, use `compression = \"snappy\"`."
+                        .into(),
+                );
+            }
+            (_, compression) => compression,
+        };
 
         // if Vector is configured to allow events with out of order timestamps, then then we can
         // safely enable concurrency settings.
@@ -348,16 +446,25 @@ impl LokiSink {
 
         let service = tower::ServiceBuilder::new()
             .settings(request_limits, LokiRetryLogic)
-            .service(LokiService::new(client, config.endpoint, config.auth)?);
+            .service(LokiService::new(
+                client,
+                config.endpoint,
+                config.transport,
+                config.auth,
+            )?);
 
         let transformer = config.encoding.transformer();
         let serializer = config.encoding.build()?;
         let encoder = Encoder::<()>::new(serializer);
-        let batch_encoder = match config.compression {
-            CompressionConfigAdapter::Original(_) => LokiBatchEncoder(LokiBatchEncoding::Json),
-            CompressionConfigAdapter::Extended(ExtendedCompression::Snappy) => {
-                LokiBatchEncoder(LokiBatchEncoding::Protobuf)
+        let batch_encoder = match (config.transport, compression) {
+            (LokiTransport::Otlp, _) => LokiBatchEncoder(LokiBatchEncoding::Otlp),
+            (LokiTransport::Push, CompressionConfigAdapter::Original(_)) => {
+                LokiBatchEncoder(LokiBatchEncoding::Json)
             }
+            (
+                LokiTransport::Push,
+                CompressionConfigAdapter::Extended(ExtendedCompression::Snappy),
+            ) => LokiBatchEncoder(LokiBatchEncoding::Protobuf),
         };
 
         Ok(Self {
@@ -370,18 +477,22 @@ impl LokiSink {
                 transformer,
                 encoder,
                 labels: config.labels,
+                structured_metadata: config.structured_metadata,
+                auto_labels: config.auto_labels.map(AutoLabels::new),
                 remove_label_fields: config.remove_label_fields,
                 remove_timestamp: config.remove_timestamp,
             },
             batch_settings: config.batch.into_batcher_settings()?,
             out_of_order_action: config.out_of_order_action,
+            out_of_order_window: config.out_of_order_window,
             service,
         })
     }
 
     async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
         let mut encoder = self.encoder.clone();
-        let mut filter = RecordFilter::new(self.out_of_order_action);
+        let mut filter =
+            RecordFilter::new(self.out_of_order_action, self.out_of_order_window.clone());
 
         // out_of_order_action's that require a complete ordering are limited to building 1 request
         // at a time
@@ -458,16 +569,23 @@ mod tests {
     use std::{
         collections::{BTreeMap, HashMap},
         convert::TryFrom,
+        sync::Arc,
     };
 
     use codecs::JsonSerializer;
     use futures::stream::StreamExt;
     use vector_core::event::{Event, LogEvent, Value};
 
-    use super::{EventEncoder, KeyPartitioner, RecordFilter};
+    use value::Kind;
+    use vector_core::schema::Definition;
+
+    use super::{AutoLabels, EventEncoder, KeyPartitioner, RecordFilter};
     use crate::{
-        codecs::Encoder, config::log_schema, sinks::loki::config::OutOfOrderAction,
-        template::Template, test_util::random_lines,
+        codecs::Encoder,
+        config::log_schema,
+        sinks::loki::config::{AutoLabelsConfig, LabelMeaning, OutOfOrderAction, OutOfOrderWindow},
+        template::Template,
+        test_util::random_lines,
     };
 
     #[test]
@@ -477,6 +595,8 @@ mod tests {
             transformer: Default::default(),
             encoder: Encoder::<()>::new(JsonSerializer::new().into()),
             labels: HashMap::default(),
+            structured_metadata: HashMap::default(),
+            auto_labels: None,
             remove_label_fields: false,
             remove_timestamp: false,
         };
@@ -516,6 +636,8 @@ mod tests {
             transformer: Default::default(),
             encoder: Encoder::<()>::new(JsonSerializer::new().into()),
             labels,
+            structured_metadata: HashMap::default(),
+            auto_labels: None,
             remove_label_fields: false,
             remove_timestamp: false,
         };
@@ -548,6 +670,8 @@ mod tests {
             transformer: Default::default(),
             encoder: Encoder::<()>::new(JsonSerializer::new().into()),
             labels: HashMap::default(),
+            structured_metadata: HashMap::default(),
+            auto_labels: None,
             remove_label_fields: false,
             remove_timestamp: true,
         };
@@ -576,6 +700,8 @@ mod tests {
             transformer: Default::default(),
             encoder: Encoder::<()>::new(JsonSerializer::new().into()),
             labels,
+            structured_metadata: HashMap::default(),
+            auto_labels: None,
             remove_label_fields: true,
             remove_timestamp: false,
         };
@@ -595,6 +721,8 @@ mod tests {
             transformer: Default::default(),
             encoder: Encoder::<()>::new(JsonSerializer::new().into()),
             labels: HashMap::default(),
+            structured_metadata: HashMap::default(),
+            auto_labels: None,
             remove_label_fields: false,
             remove_timestamp: false,
         };
@@ -614,7 +742,7 @@ mod tests {
                 event
             })
             .collect::<Vec<_>>();
-        let mut filter = RecordFilter::new(OutOfOrderAction::Drop);
+        let mut filter = RecordFilter::new(OutOfOrderAction::Drop, Default::default());
         let stream = futures::stream::iter(events)
             .map(|event| encoder.encode_event(event))
             .filter_map(|event| async { event })
@@ -629,4 +757,136 @@ mod tests {
         }
         assert_eq!(result.len(), 17);
     }
+
+    #[test]
+    fn encoder_with_structured_metadata() {
+        let mut structured_metadata = HashMap::default();
+        structured_metadata.insert(
+            Template::try_from("trace_id").unwrap(),
+            Template::try_from("{{ trace_id }}").unwrap(),
+        );
+        let mut encoder = EventEncoder {
+            key_partitioner: KeyPartitioner::new(None),
+            transformer: Default::default(),
+            encoder: Encoder::<()>::new(JsonSerializer::new().into()),
+            labels: HashMap::default(),
+            structured_metadata,
+            auto_labels: None,
+            remove_label_fields: false,
+            remove_timestamp: false,
+        };
+        let mut event = Event::Log(LogEvent::from("hello world"));
+        event.as_mut_log().insert("trace_id", "abc");
+
+        let record = encoder.encode_event(event).unwrap();
+        assert_eq!(
+            record.event.structured_metadata,
+            vec![("trace_id".to_string(), "abc".to_string())]
+        );
+        // Structured metadata doesn't create new streams.
+        assert_eq!(
+            record.labels,
+            vec![("agent".to_string(), "vector".to_string())]
+        );
+    }
+
+    #[test]
+    fn filter_out_of_order_window() {
+        let mut encoder = EventEncoder {
+            key_partitioner: KeyPartitioner::new(None),
+            transformer: Default::default(),
+            encoder: Encoder::<()>::new(JsonSerializer::new().into()),
+            labels: HashMap::default(),
+            structured_metadata: HashMap::default(),
+            auto_labels: None,
+            remove_label_fields: false,
+            remove_timestamp: false,
+        };
+        let mut filter = RecordFilter::new(
+            OutOfOrderAction::Drop,
+            OutOfOrderWindow {
+                window_secs: Some(60),
+                tenant_window_secs: HashMap::default(),
+            },
+        );
+        let base = chrono::Utc::now();
+        let mut record = |offset_secs: i64| {
+            let mut event = Event::Log(LogEvent::from("hello world"));
+            event.as_mut_log().insert(
+                log_schema().timestamp_key(),
+                base + chrono::Duration::seconds(offset_secs),
+            );
+            filter.filter_record(encoder.encode_event(event).unwrap())
+        };
+
+        assert!(record(0).is_some());
+        // Within the window.
+        assert!(record(-30).is_some());
+        // Outside of the window.
+        assert!(record(-120).is_none());
+    }
+
+    #[test]
+    fn encoder_auto_labels_cardinality_guard() {
+        let mut labels = HashMap::default();
+        labels.insert(
+            Template::try_from("host").unwrap(),
+            Template::try_from("static-host").unwrap(),
+        );
+        let mut encoder = EventEncoder {
+            key_partitioner: KeyPartitioner::new(None),
+            transformer: Default::default(),
+            encoder: Encoder::<()>::new(JsonSerializer::new().into()),
+            labels,
+            structured_metadata: HashMap::default(),
+            auto_labels: Some(AutoLabels::new(AutoLabelsConfig {
+                meanings: vec![LabelMeaning::Service, LabelMeaning::Host],
+                max_cardinality: 2,
+            })),
+            remove_label_fields: false,
+            remove_timestamp: false,
+        };
+        let definition = Definition::empty_legacy_namespace()
+            .with_field("svc", Kind::bytes(), Some("service"))
+            .with_field("hostname", Kind::bytes(), Some("host"));
+
+        let mut records = ["a", "b", "c", "a"].into_iter().map(|service| {
+            let mut log = LogEvent::from("hello world");
+            log.insert("svc", service);
+            log.insert("hostname", "derived-host");
+            log.metadata_mut()
+                .set_schema_definition(&Arc::new(definition.clone()));
+            let mut record = encoder.encode_event(Event::Log(log)).unwrap();
+            record.labels.sort();
+            record
+        });
+
+        let record = records.next().unwrap();
+        // Explicit labels take precedence over derived ones.
+        assert_eq!(
+            record.labels,
+            vec![
+                ("host".to_string(), "static-host".to_string()),
+                ("service_name".to_string(), "a".to_string()),
+            ]
+        );
+        assert!(record.event.structured_metadata.is_empty());
+
+        let record = records.nth(1).unwrap();
+        // The third distinct value exceeds the cardinality limit.
+        assert_eq!(
+            record.labels,
+            vec![("host".to_string(), "static-host".to_string())]
+        );
+        assert_eq!(
+            record.event.structured_metadata,
+            vec![("service_name".to_string(), "c".to_string())]
+        );
+
+        // Values already seen are still accepted.
+        let record = records.next().unwrap();
+        assert!(record
+            .labels
+            .contains(&("service_name".to_string(), "a".to_string())));
+    }
 }
@@ -1,11 +1,23 @@
-use futures::StreamExt;
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
-use super::{config::LokiConfig, healthcheck::healthcheck, sink::LokiSink};
+use futures::StreamExt;
+use http::StatusCode;
+use tower::retry::Policy;
+
+use super::{
+    config::LokiConfig,
+    healthcheck::healthcheck,
+    service::{LokiError, LokiResponse, LokiRetryLogic},
+    sink::LokiSink,
+};
 use crate::{
     config::ProxyConfig,
     event::{Event, LogEvent},
     http::HttpClient,
-    sinks::util::test::{build_test_server, load_sink},
+    sinks::util::{
+        retries::{FixedRetryPolicy, RetryMatrix, RetryOn},
+        test::{build_test_server, load_sink},
+    },
     test_util,
     tls::TlsSettings,
 };
@@ -15,6 +27,32 @@ fn generate_config() {
     test_util::test_generate_config::<LokiConfig>();
 }
 
+#[tokio::test]
+async fn retry_on_applies_to_server_errors() {
+    let retry_on = ["429", "5xx"]
+        .into_iter()
+        .map(|condition| RetryOn::try_from(condition.to_string()).unwrap())
+        .collect();
+    let policy = FixedRetryPolicy::new(
+        10,
+        Duration::from_secs(1),
+        Duration::from_secs(10),
+        LokiRetryLogic,
+    )
+    .with_matrix(Arc::new(RetryMatrix::new(Some(retry_on), &HashMap::new())));
+
+    let retries = |code: StatusCode| {
+        let error: crate::Error = Box::new(LokiError::ServerError { code });
+        Policy::<(), LokiResponse, crate::Error>::retry(&policy, &(), Err(&error)).is_some()
+    };
+
+    assert!(retries(StatusCode::TOO_MANY_REQUESTS));
+    assert!(retries(StatusCode::SERVICE_UNAVAILABLE));
+    // `retry_on` takes precedence over the sink not retrying this status.
+    assert!(retries(StatusCode::NOT_IMPLEMENTED));
+    assert!(!retries(StatusCode::BAD_REQUEST));
+}
+
 #[tokio::test]
 async fn interpolate_labels() {
     let (config, cx) = load_sink::<LokiConfig>(
@@ -24,8 +24,15 @@ pub mod aws_sqs;
 pub mod axiom;
 #[cfg(feature = "sinks-azure_blob")]
 pub mod azure_blob;
-#[cfg(any(feature = "sinks-azure_blob", feature = "sinks-datadog_archives"))]
+#[cfg(any(
+    feature = "sinks-azure_blob",
+    feature = "sinks-azure_logs_ingestion",
+    feature = "sinks-datadog_archives",
+    feature = "sources-azure_blob"
+))]
 pub mod azure_common;
+#[cfg(feature = "sinks-azure_logs_ingestion")]
+pub mod azure_logs_ingestion;
 #[cfg(feature = "sinks-azure_monitor_logs")]
 pub mod azure_monitor_logs;
 #[cfg(feature = "sinks-blackhole")]
@@ -43,10 +50,14 @@ pub mod console;
 pub mod datadog;
 #[cfg(feature = "sinks-datadog_archives")]
 pub mod datadog_archives;
+#[cfg(feature = "sinks-doris")]
+pub mod doris;
 #[cfg(feature = "sinks-elasticsearch")]
 pub mod elasticsearch;
 #[cfg(feature = "sinks-file")]
 pub mod file;
+#[cfg(feature = "sinks-fluent")]
+pub mod fluent;
 #[cfg(feature = "sinks-gcp")]
 pub mod gcp;
 #[cfg(any(feature = "sinks-gcp"))]
@@ -65,16 +76,22 @@ pub mod kafka;
 pub mod logdna;
 #[cfg(feature = "sinks-loki")]
 pub mod loki;
+#[cfg(feature = "sinks-msteams")]
+pub mod msteams;
 #[cfg(feature = "sinks-nats")]
 pub mod nats;
 #[cfg(feature = "sinks-new_relic")]
 pub mod new_relic;
+#[cfg(feature = "sinks-pagerduty")]
+pub mod pagerduty;
 #[cfg(feature = "sinks-papertrail")]
 pub mod papertrail;
 #[cfg(feature = "sinks-prometheus")]
 pub mod prometheus;
 #[cfg(feature = "sinks-pulsar")]
 pub mod pulsar;
+#[cfg(feature = "sinks-quickwit")]
+pub mod quickwit;
 #[cfg(feature = "sinks-redis")]
 pub mod redis;
 #[cfg(all(
@@ -84,14 +101,24 @@ pub mod redis;
 pub mod s3_common;
 #[cfg(feature = "sinks-sematext")]
 pub mod sematext;
+#[cfg(feature = "sinks-slack")]
+pub mod slack;
+#[cfg(feature = "sinks-smtp")]
+pub mod smtp;
 #[cfg(feature = "sinks-socket")]
 pub mod socket;
 #[cfg(feature = "sinks-splunk_hec")]
 pub mod splunk_hec;
+#[cfg(feature = "sinks-splunk_signalfx")]
+pub mod splunk_signalfx;
 #[cfg(feature = "sinks-statsd")]
 pub mod statsd;
 #[cfg(feature = "sinks-vector")]
 pub mod vector;
+#[cfg(feature = "sinks-victorialogs")]
+pub mod victorialogs;
+#[cfg(feature = "sinks-victoriametrics")]
+pub mod victoriametrics;
 #[cfg(feature = "sinks-websocket")]
 pub mod websocket;
 
@@ -174,6 +201,10 @@ pub enum Sinks {
     #[cfg(feature = "sinks-azure_blob")]
     AzureBlob(#[configurable(derived)] azure_blob::AzureBlobSinkConfig),
 
+    /// Azure Logs Ingestion.
+    #[cfg(feature = "sinks-azure_logs_ingestion")]
+    AzureLogsIngestion(#[configurable(derived)] azure_logs_ingestion::AzureLogsIngestionConfig),
+
     /// Azure Monitor Logs.
     #[cfg(feature = "sinks-azure_monitor_logs")]
     AzureMonitorLogs(#[configurable(derived)] azure_monitor_logs::AzureMonitorLogsConfig),
@@ -206,6 +237,10 @@ pub enum Sinks {
     #[cfg(feature = "sinks-datadog_traces")]
     DatadogTraces(#[configurable(derived)] datadog::traces::DatadogTracesConfig),
 
+    /// Apache Doris.
+    #[cfg(feature = "sinks-doris")]
+    Doris(#[configurable(derived)] doris::DorisConfig),
+
     /// Elasticsearch.
     #[cfg(feature = "sinks-elasticsearch")]
     Elasticsearch(#[configurable(derived)] elasticsearch::ElasticsearchConfig),
@@ -214,6 +249,18 @@ pub enum Sinks {
     #[cfg(feature = "sinks-file")]
     File(#[configurable(derived)] file::FileSinkConfig),
 
+    /// Fluent.
+    #[cfg(feature = "sinks-fluent")]
+    Fluent(#[configurable(derived)] fluent::FluentSinkConfig),
+
+    /// Google BigQuery.
+    #[cfg(feature = "sinks-gcp_bigquery")]
+    GcpBigquery(#[configurable(derived)] gcp::bigquery::BigqueryConfig),
+
+    /// Google Chronicle.
+    #[cfg(feature = "sinks-gcp")]
+    GcpChronicle(#[configurable(derived)] gcp::chronicle::ChronicleConfig),
+
     /// Google Chronicle (unstructured).
     #[cfg(feature = "sinks-gcp")]
     GcpChronicleUnstructured(
@@ -272,6 +319,10 @@ pub enum Sinks {
     #[cfg(feature = "sinks-loki")]
     Loki(#[configurable(derived)] loki::LokiConfig),
 
+    /// Microsoft Teams.
+    #[cfg(feature = "sinks-msteams")]
+    MsTeams(#[configurable(derived)] msteams::MsTeamsSinkConfig),
+
     /// NATS.
     #[cfg(feature = "sinks-nats")]
     Nats(#[configurable(derived)] self::nats::NatsSinkConfig),
@@ -280,6 +331,10 @@ pub enum Sinks {
     #[cfg(feature = "sinks-new_relic")]
     NewRelic(#[configurable(derived)] new_relic::NewRelicConfig),
 
+    /// PagerDuty.
+    #[cfg(feature = "sinks-pagerduty")]
+    PagerDuty(#[configurable(derived)] pagerduty::PagerDutySinkConfig),
+
     /// Papertrail.
     #[cfg(feature = "sinks-papertrail")]
     Papertrail(#[configurable(derived)] papertrail::PapertrailConfig),
@@ -296,6 +351,10 @@ pub enum Sinks {
     #[cfg(feature = "sinks-pulsar")]
     Pulsar(#[configurable(derived)] pulsar::PulsarSinkConfig),
 
+    /// Quickwit.
+    #[cfg(feature = "sinks-quickwit")]
+    Quickwit(#[configurable(derived)] quickwit::QuickwitConfig),
+
     /// Redis.
     #[cfg(feature = "sinks-redis")]
     Redis(#[configurable(derived)] redis::RedisSinkConfig),
@@ -308,6 +367,14 @@ pub enum Sinks {
     #[cfg(feature = "sinks-sematext")]
     SematextMetrics(#[configurable(derived)] sematext::metrics::SematextMetricsConfig),
 
+    /// Slack.
+    #[cfg(feature = "sinks-slack")]
+    Slack(#[configurable(derived)] slack::SlackSinkConfig),
+
+    /// SMTP.
+    #[cfg(feature = "sinks-smtp")]
+    Smtp(#[configurable(derived)] smtp::SmtpSinkConfig),
+
     /// Socket.
     #[cfg(feature = "sinks-socket")]
     Socket(#[configurable(derived)] socket::SocketSinkConfig),
@@ -320,6 +387,10 @@ pub enum Sinks {
     #[cfg(feature = "sinks-splunk_hec")]
     SplunkHecMetrics(#[configurable(derived)] splunk_hec::metrics::config::HecMetricsSinkConfig),
 
+    /// Splunk Observability (SignalFx).
+    #[cfg(feature = "sinks-splunk_signalfx")]
+    SplunkSignalFx(#[configurable(derived)] splunk_signalfx::SignalFxConfig),
+
     /// StatsD.
     #[cfg(feature = "sinks-statsd")]
     Statsd(#[configurable(derived)] statsd::StatsdSinkConfig),
@@ -360,6 +431,14 @@ pub enum Sinks {
     #[cfg(feature = "sinks-vector")]
     Vector(#[configurable(derived)] vector::VectorConfig),
 
+    /// VictoriaLogs.
+    #[cfg(feature = "sinks-victorialogs")]
+    Victorialogs(#[configurable(derived)] victorialogs::VictoriaLogsConfig),
+
+    /// VictoriaMetrics.
+    #[cfg(feature = "sinks-victoriametrics")]
+    Victoriametrics(#[configurable(derived)] victoriametrics::VictoriaMetricsConfig),
+
     /// Websocket.
     #[cfg(feature = "sinks-websocket")]
     Websocket(#[configurable(derived)] websocket::WebSocketSinkConfig),
@@ -390,6 +469,8 @@ impl NamedComponent for Sinks {
             Self::Axiom(config) => config.get_component_name(),
             #[cfg(feature = "sinks-azure_blob")]
             Self::AzureBlob(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-azure_logs_ingestion")]
+            Self::AzureLogsIngestion(config) => config.get_component_name(),
             #[cfg(feature = "sinks-azure_monitor_logs")]
             Self::AzureMonitorLogs(config) => config.get_component_name(),
             #[cfg(feature = "sinks-blackhole")]
@@ -406,10 +487,18 @@ impl NamedComponent for Sinks {
             Self::DatadogMetrics(config) => config.get_component_name(),
             #[cfg(feature = "sinks-datadog_traces")]
             Self::DatadogTraces(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-doris")]
+            Self::Doris(config) => config.get_component_name(),
             #[cfg(feature = "sinks-elasticsearch")]
             Self::Elasticsearch(config) => config.get_component_name(),
             #[cfg(feature = "sinks-file")]
             Self::File(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-fluent")]
+            Self::Fluent(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-gcp_bigquery")]
+            Self::GcpBigquery(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-gcp")]
+            Self::GcpChronicle(config) => config.get_component_name(),
             #[cfg(feature = "sinks-gcp")]
             Self::GcpChronicleUnstructured(config) => config.get_component_name(),
             #[cfg(feature = "sinks-gcp")]
@@ -438,10 +527,14 @@ impl NamedComponent for Sinks {
             Self::Logdna(config) => config.get_component_name(),
             #[cfg(feature = "sinks-loki")]
             Self::Loki(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-msteams")]
+            Self::MsTeams(config) => config.get_component_name(),
             #[cfg(feature = "sinks-nats")]
             Self::Nats(config) => config.get_component_name(),
             #[cfg(feature = "sinks-new_relic")]
             Self::NewRelic(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-pagerduty")]
+            Self::PagerDuty(config) => config.get_component_name(),
             #[cfg(feature = "sinks-papertrail")]
             Self::Papertrail(config) => config.get_component_name(),
             #[cfg(feature = "sinks-prometheus")]
@@ -450,18 +543,26 @@ impl NamedComponent for Sinks {
             Self::PrometheusRemoteWrite(config) => config.get_component_name(),
             #[cfg(feature = "sinks-pulsar")]
             Self::Pulsar(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-quickwit")]
+            Self::Quickwit(config) => config.get_component_name(),
             #[cfg(feature = "sinks-redis")]
             Self::Redis(config) => config.get_component_name(),
             #[cfg(feature = "sinks-sematext")]
             Self::SematextLogs(config) => config.get_component_name(),
             #[cfg(feature = "sinks-sematext")]
             Self::SematextMetrics(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-slack")]
+            Self::Slack(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-smtp")]
+            Self::Smtp(config) => config.get_component_name(),
             #[cfg(feature = "sinks-socket")]
             Self::Socket(config) => config.get_component_name(),
             #[cfg(feature = "sinks-splunk_hec")]
             Self::SplunkHecLogs(config) => config.get_component_name(),
             #[cfg(feature = "sinks-splunk_hec")]
             Self::SplunkHecMetrics(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-splunk_signalfx")]
+            Self::SplunkSignalFx(config) => config.get_component_name(),
             #[cfg(feature = "sinks-statsd")]
             Self::Statsd(config) => config.get_component_name(),
             #[cfg(all(test, feature = "sources-demo_logs"))]
@@ -480,6 +581,10 @@ impl NamedComponent for Sinks {
             Self::UnitTestStream(config) => config.get_component_name(),
             #[cfg(feature = "sinks-vector")]
             Self::Vector(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-victorialogs")]
+            Self::Victorialogs(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-victoriametrics")]
+            Self::Victoriametrics(config) => config.get_component_name(),
             #[cfg(feature = "sinks-websocket")]
             Self::Websocket(config) => config.get_component_name(),
         }
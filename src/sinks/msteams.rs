@@ -0,0 +1,342 @@
+//! The `msteams` sink, which posts events as Adaptive Cards to Microsoft Teams incoming webhooks.
+
+use bytes::{Bytes, BytesMut};
+use futures::{future, FutureExt, SinkExt};
+use http::{Request, Uri};
+use serde_json::{json, Value};
+use snafu::Snafu;
+use vector_config::configurable_component;
+
+use crate::{
+    config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
+    event::Event,
+    http::HttpClient,
+    internal_events::{SinkRequestBuildError, TemplateRenderingError},
+    sinks::{
+        util::{
+            http::{HttpEventEncoder, HttpSink, PartitionHttpSink},
+            json_template::JsonTemplate,
+            BatchSettings, Buffer, Compression, PartitionBuffer, PartitionInnerBuffer,
+            TowerRequestConfig,
+        },
+        Healthcheck, VectorSink,
+    },
+    template::{Template, TemplateRenderingError as TemplateRenderingErrorKind},
+    tls::{TlsConfig, TlsSettings},
+};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("either `text` or `card_body` must be set"))]
+    MissingContent,
+}
+
+/// Configuration for the `msteams` sink.
+#[configurable_component(sink("msteams"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct MsTeamsSinkConfig {
+    /// The URL of the incoming webhook to post messages to.
+    ///
+    /// As each webhook posts to a single channel, events can be routed to channels with a template
+    /// such as `{{ teams_webhook }}`.
+    #[configurable(metadata(templateable))]
+    webhook_url: Template,
+
+    /// The title of each message.
+    #[configurable(metadata(templateable))]
+    title: Option<Template>,
+
+    /// The text of each message.
+    #[configurable(metadata(templateable))]
+    text: Option<Template>,
+
+    /// The body of the Adaptive Card of each message, which replaces `title` and `text`.
+    ///
+    /// Each string in the body is a template, so that the card can refer to fields of the event.
+    card_body: Option<Vec<toml::Value>>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+impl GenerateConfig for MsTeamsSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"webhook_url = "https://example.webhook.office.com/webhookb2/00000000-0000-0000-0000-000000000000"
+            text = "{{ message }}""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for MsTeamsSinkConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls, cx.proxy())?;
+
+        let card_body = match (&self.card_body, &self.text) {
+            (Some(card_body), _) => card_body
+                .iter()
+                .map(JsonTemplate::try_from)
+                .collect::<Result<Vec<_>, _>>()?,
+            (None, Some(text)) => text_body(self.title.as_ref(), text),
+            (None, None) => return Err(BuildError::MissingContent.into()),
+        };
+
+        let sink = MsTeamsSink {
+            webhook_url: self.webhook_url.clone(),
+            card_body,
+        };
+
+        // Incoming webhooks allow about four messages per second.
+        let request = self.request.unwrap_with(&TowerRequestConfig {
+            rate_limit_num: Some(4),
+            rate_limit_duration_secs: Some(1),
+            ..Default::default()
+        });
+        // Each event is posted as its own message.
+        let mut batch = BatchSettings::default();
+        batch.size.events = 1;
+        let sink = PartitionHttpSink::new(
+            sink,
+            PartitionBuffer::new(Buffer::new(batch.size, Compression::None)),
+            request,
+            batch.timeout,
+            client,
+        )
+        .sink_map_err(|error| error!(message = "Fatal msteams sink error.", %error));
+
+        // Posting anything to a webhook shows up in its channel, so there is nothing to check.
+        Ok((VectorSink::from_event_sink(sink), future::ok(()).boxed()))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+/// Returns the card body showing the title and the text of the message.
+fn text_body(title: Option<&Template>, text: &Template) -> Vec<JsonTemplate> {
+    let text_block = |text: &Template, style: &[(&str, &str)]| {
+        let mut fields = vec![
+            ("type".to_string(), JsonTemplate::Fixed("TextBlock".into())),
+            ("text".to_string(), JsonTemplate::String(text.clone())),
+            ("wrap".to_string(), JsonTemplate::Fixed(true.into())),
+        ];
+        fields.extend(
+            style
+                .iter()
+                .map(|(key, value)| (key.to_string(), JsonTemplate::Fixed((*value).into()))),
+        );
+        JsonTemplate::Object(fields)
+    };
+
+    title
+        .map(|title| text_block(title, &[("size", "Large"), ("weight", "Bolder")]))
+        .into_iter()
+        .chain(std::iter::once(text_block(text, &[])))
+        .collect()
+}
+
+struct MsTeamsSink {
+    webhook_url: Template,
+    card_body: Vec<JsonTemplate>,
+}
+
+struct MsTeamsEventEncoder {
+    webhook_url: Template,
+    card_body: Vec<JsonTemplate>,
+}
+
+impl MsTeamsEventEncoder {
+    /// Renders the webhook URL and the card body of the event, dropping it if either fails to
+    /// render.
+    fn render(&self, event: &Event) -> Option<(String, Vec<Value>)> {
+        let webhook_url = render(self.webhook_url.render_string(event), "webhook_url")?;
+        let card_body = self
+            .card_body
+            .iter()
+            .map(|element| element.render(event))
+            .collect::<Result<Vec<_>, _>>();
+        let card_body = render(card_body, "card_body")?;
+        Some((webhook_url, card_body))
+    }
+}
+
+impl HttpEventEncoder<PartitionInnerBuffer<BytesMut, Uri>> for MsTeamsEventEncoder {
+    fn encode_event(&mut self, event: Event) -> Option<PartitionInnerBuffer<BytesMut, Uri>> {
+        let (webhook_url, card_body) = self.render(&event)?;
+        let webhook_url = webhook_url
+            .parse::<Uri>()
+            .map_err(|error| emit!(SinkRequestBuildError { error }))
+            .ok()?;
+
+        let message = json!({
+            "type": "message",
+            "attachments": [{
+                "contentType": "application/vnd.microsoft.card.adaptive",
+                "content": {
+                    "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+                    "type": "AdaptiveCard",
+                    "version": "1.4",
+                    "body": card_body,
+                },
+            }],
+        });
+        let body = crate::serde::json::to_bytes(&message)
+            .map_err(|error| emit!(SinkRequestBuildError { error }))
+            .ok()?;
+
+        Some(PartitionInnerBuffer::new(body, webhook_url))
+    }
+}
+
+fn render<T>(rendered: Result<T, TemplateRenderingErrorKind>, field: &str) -> Option<T> {
+    rendered
+        .map_err(|error| {
+            emit!(TemplateRenderingError {
+                error,
+                field: Some(field),
+                drop_event: true,
+            });
+        })
+        .ok()
+}
+
+#[async_trait::async_trait]
+impl HttpSink for MsTeamsSink {
+    type Input = PartitionInnerBuffer<BytesMut, Uri>;
+    type Output = PartitionInnerBuffer<BytesMut, Uri>;
+    type Encoder = MsTeamsEventEncoder;
+
+    fn build_encoder(&self) -> Self::Encoder {
+        MsTeamsEventEncoder {
+            webhook_url: self.webhook_url.clone(),
+            card_body: self.card_body.clone(),
+        }
+    }
+
+    async fn build_request(&self, output: Self::Output) -> crate::Result<Request<Bytes>> {
+        let (body, webhook_url) = output.into_parts();
+
+        Request::post(webhook_url)
+            .header("Content-Type", "application/json")
+            .body(body.freeze())
+            .map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{stream, StreamExt};
+    use indoc::indoc;
+
+    use super::*;
+    use crate::{
+        event::LogEvent,
+        sinks::util::test::{build_test_server, load_sink},
+        test_util::{
+            components::{run_and_assert_sink_compliance, HTTP_SINK_TAGS},
+            next_addr,
+        },
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<MsTeamsSinkConfig>();
+    }
+
+    fn event(webhook: &str) -> Event {
+        let mut log = LogEvent::from("disk full");
+        log.insert("service", "db");
+        log.insert("webhook", webhook);
+        log.into()
+    }
+
+    #[test]
+    fn renders_title_and_text() {
+        let mut encoder = MsTeamsEventEncoder {
+            webhook_url: Template::try_from("{{ webhook }}").unwrap(),
+            card_body: text_body(
+                Some(&Template::try_from("{{ service }} alert").unwrap()),
+                &Template::try_from("{{ message }}").unwrap(),
+            ),
+        };
+
+        let (body, webhook_url) = encoder
+            .encode_event(event("https://example.com/hook"))
+            .unwrap()
+            .into_parts();
+        assert_eq!(webhook_url, "https://example.com/hook");
+
+        let message: Value = serde_json::from_slice(&body).unwrap();
+        let card = &message["attachments"][0]["content"];
+        assert_eq!(card["type"], "AdaptiveCard");
+        assert_eq!(
+            card["body"],
+            json!([
+                {"type": "TextBlock", "text": "db alert", "wrap": true, "size": "Large", "weight": "Bolder"},
+                {"type": "TextBlock", "text": "disk full", "wrap": true},
+            ])
+        );
+
+        // Events without a webhook can't be posted.
+        assert!(encoder
+            .encode_event(LogEvent::from("disk full").into())
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn posts_to_rendered_webhook() {
+        let addr = next_addr();
+        let config = indoc! {r#"
+            webhook_url = "{{ webhook }}"
+            [[card_body]]
+            type = "FactSet"
+            facts = [{ title = "Service", value = "{{ service }}" }]
+        "#};
+        let (config, cx) = load_sink::<MsTeamsSinkConfig>(config).unwrap();
+        let (sink, _) = config.build(cx).await.unwrap();
+        let (rx, _trigger, server) = build_test_server(addr);
+        tokio::spawn(server);
+
+        let event = event(&format!("http://{}/webhookb2/db", addr));
+        run_and_assert_sink_compliance(sink, stream::once(async { event }), &HTTP_SINK_TAGS).await;
+
+        let output = rx.take(1).collect::<Vec<_>>().await;
+        let (parts, body) = &output[0];
+        assert_eq!(parts.uri.path(), "/webhookb2/db");
+
+        let message: Value = serde_json::from_slice(body).unwrap();
+        assert_eq!(
+            message["attachments"][0]["content"]["body"],
+            json!([{"type": "FactSet", "facts": [{"title": "Service", "value": "db"}]}])
+        );
+    }
+
+    #[tokio::test]
+    async fn requires_content() {
+        let config: MsTeamsSinkConfig =
+            toml::from_str(r#"webhook_url = "https://example.com/hook""#).unwrap();
+
+        assert!(config.build(SinkContext::new_test()).await.is_err());
+    }
+}
@@ -140,6 +140,9 @@ impl SinkConfig for NewRelicConfig {
         &self,
         cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        self.compression
+            .check_supported(Compression::FLATE_ALGORITHMS)?;
+
         let batcher_settings = self
             .batch
             .validate()?
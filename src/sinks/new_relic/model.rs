@@ -1,11 +1,28 @@
-use std::{collections::HashMap, convert::TryFrom, fmt::Debug, time::SystemTime};
+use std::{
+    collections::{BTreeMap, HashMap},
+    convert::TryFrom,
+    fmt::Debug,
+    time::SystemTime,
+};
 
 use chrono::{DateTime, Utc};
 use ordered_float::NotNan;
 use serde::{Deserialize, Serialize};
 
 use super::NewRelicSinkError;
-use crate::event::{Event, MetricValue, Value};
+use crate::{
+    config::log_schema,
+    event::{
+        metric::{MetricTags, Sample},
+        Event, LogEvent, MetricKind, MetricValue, Value,
+    },
+};
+
+/// The attribute New Relic uses to synthesize host entities.
+const ENTITY_HOST_ATTRIBUTE: &str = "host.name";
+
+/// The attribute New Relic uses to synthesize service entities.
+const ENTITY_SERVICE_ATTRIBUTE: &str = "service.name";
 
 #[derive(Debug)]
 pub enum NewRelicApiModel {
@@ -21,30 +38,9 @@ type DataStore = HashMap<String, Vec<KeyValData>>;
 pub struct MetricsApiModel(pub Vec<DataStore>);
 
 impl MetricsApiModel {
-    pub fn new(metric_array: Vec<(Value, Value, Value)>) -> Self {
-        let mut metric_data_array = vec![];
-        for (m_name, m_value, m_timestamp) in metric_array {
-            let mut metric_data = KeyValData::new();
-            metric_data.insert("name".to_owned(), m_name);
-            metric_data.insert("value".to_owned(), m_value);
-            match m_timestamp {
-                Value::Timestamp(ts) => {
-                    metric_data.insert("timestamp".to_owned(), Value::from(ts.timestamp()));
-                }
-                Value::Integer(i) => {
-                    metric_data.insert("timestamp".to_owned(), Value::from(i));
-                }
-                _ => {
-                    metric_data.insert(
-                        "timestamp".to_owned(),
-                        Value::from(DateTime::<Utc>::from(SystemTime::now()).timestamp()),
-                    );
-                }
-            }
-            metric_data_array.push(metric_data);
-        }
+    pub fn new(metric_array: Vec<KeyValData>) -> Self {
         let mut metric_store = DataStore::new();
-        metric_store.insert("metrics".to_owned(), metric_data_array);
+        metric_store.insert("metrics".to_owned(), metric_array);
         Self(vec![metric_store])
     }
 }
@@ -57,34 +53,53 @@ impl TryFrom<Vec<Event>> for MetricsApiModel {
 
         for buf_event in buf_events {
             if let Event::Metric(metric) = buf_event {
-                // Future improvement: put metric type. If type = count, NR metric model requires an interval.ms field, that is not provided by the Vector Metric model.
-                match metric.value() {
-                    MetricValue::Gauge { value } => {
-                        metric_array.push((
-                            Value::from(metric.name().to_owned()),
-                            Value::from(
-                                NotNan::new(*value).map_err(|_| {
-                                    NewRelicSinkError::new("NaN value not supported")
-                                })?,
-                            ),
-                            Value::from(metric.timestamp()),
-                        ));
-                    }
-                    MetricValue::Counter { value } => {
-                        metric_array.push((
-                            Value::from(metric.name().to_owned()),
-                            Value::from(
-                                NotNan::new(*value).map_err(|_| {
-                                    NewRelicSinkError::new("NaN value not supported")
-                                })?,
-                            ),
-                            Value::from(metric.timestamp()),
-                        ));
-                    }
-                    _ => {
-                        // Unrecognized metric type
-                    }
+                let interval_ms = metric.interval_ms().map(|interval_ms| interval_ms.get());
+                // Counts and summaries hold the changes over an interval, which the Metric API
+                // requires. Incremental counters without one are sent as gauges instead.
+                let (metric_type, value, interval_ms) =
+                    match (metric.value(), metric.kind(), interval_ms) {
+                        (MetricValue::Gauge { value }, _, _) => ("gauge", to_float(*value)?, None),
+                        (
+                            MetricValue::Counter { value },
+                            MetricKind::Incremental,
+                            Some(interval_ms),
+                        ) => ("count", to_float(*value)?, Some(interval_ms)),
+                        (MetricValue::Counter { value }, _, _) => {
+                            ("gauge", to_float(*value)?, None)
+                        }
+                        (
+                            MetricValue::Distribution { samples, .. },
+                            MetricKind::Incremental,
+                            Some(interval_ms),
+                        ) if !samples.is_empty() => {
+                            ("summary", summarize(samples)?, Some(interval_ms))
+                        }
+                        _ => {
+                            // Unrecognized metric type
+                            continue;
+                        }
+                    };
+
+                let mut metric_data = KeyValData::new();
+                metric_data.insert("name".to_owned(), Value::from(metric.name().to_owned()));
+                metric_data.insert("type".to_owned(), Value::from(metric_type));
+                metric_data.insert("value".to_owned(), value);
+                metric_data.insert(
+                    "timestamp".to_owned(),
+                    Value::from(
+                        metric
+                            .timestamp()
+                            .unwrap_or_else(|| DateTime::<Utc>::from(SystemTime::now()))
+                            .timestamp(),
+                    ),
+                );
+                if let Some(interval_ms) = interval_ms {
+                    metric_data.insert("interval.ms".to_owned(), Value::from(interval_ms));
+                }
+                if let Some(attributes) = metric_attributes(metric.tags()) {
+                    metric_data.insert("attributes".to_owned(), attributes);
                 }
+                metric_array.push(metric_data);
             }
         }
 
@@ -96,6 +111,71 @@ impl TryFrom<Vec<Event>> for MetricsApiModel {
     }
 }
 
+fn to_float(value: f64) -> Result<Value, NewRelicSinkError> {
+    NotNan::new(value)
+        .map(Value::from)
+        .map_err(|_| NewRelicSinkError::new("NaN value not supported"))
+}
+
+/// Summarizes the samples of a distribution into the value of a summary metric.
+fn summarize(samples: &[Sample]) -> Result<Value, NewRelicSinkError> {
+    let mut count = 0;
+    let mut sum = 0.0;
+    let mut min = f64::INFINITY;
+    let mut max = f64::NEG_INFINITY;
+    for sample in samples {
+        count += u64::from(sample.rate);
+        sum += sample.value * f64::from(sample.rate);
+        min = min.min(sample.value);
+        max = max.max(sample.value);
+    }
+
+    Ok(Value::Object(BTreeMap::from([
+        ("count".to_owned(), Value::from(count)),
+        ("sum".to_owned(), to_float(sum)?),
+        ("min".to_owned(), to_float(min)?),
+        ("max".to_owned(), to_float(max)?),
+    ])))
+}
+
+/// Converts the tags of a metric to its attributes, along with the attributes New Relic uses to
+/// synthesize the entities it belongs to.
+fn metric_attributes(tags: Option<&MetricTags>) -> Option<Value> {
+    let tags = tags.filter(|tags| !tags.is_empty())?;
+    let mut attributes = tags
+        .iter()
+        .map(|(key, value)| (key.as_str().to_owned(), Value::from(value.clone())))
+        .collect::<BTreeMap<_, _>>();
+
+    for (tag, attribute) in [
+        (log_schema().host_key(), ENTITY_HOST_ATTRIBUTE),
+        ("service", ENTITY_SERVICE_ATTRIBUTE),
+    ] {
+        if let Some(value) = tags.get(tag) {
+            attributes
+                .entry(attribute.to_owned())
+                .or_insert_with(|| Value::from(value.clone()));
+        }
+    }
+
+    Some(Value::Object(attributes))
+}
+
+/// Adds the attributes New Relic uses to synthesize the entities an event belongs to, from the
+/// fields with the `host` and `service` meanings, unless they are already set.
+fn insert_entity_attributes(log: &LogEvent, model: &mut KeyValData) {
+    for (meaning, attribute) in [
+        ("host", ENTITY_HOST_ATTRIBUTE),
+        ("service", ENTITY_SERVICE_ATTRIBUTE),
+    ] {
+        if !model.contains_key(attribute) {
+            if let Some(value) = log.get_by_meaning(meaning) {
+                model.insert(attribute.to_owned(), value.clone());
+            }
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct EventsApiModel(pub Vec<KeyValData>);
 
@@ -150,6 +230,8 @@ impl TryFrom<Vec<Event>> for EventsApiModel {
                     }
                 }
 
+                insert_entity_attributes(&log, &mut event_model);
+
                 if event_model.get("eventType").is_none() {
                     event_model
                         .insert("eventType".to_owned(), Value::from("VectorSink".to_owned()));
@@ -189,6 +271,8 @@ impl TryFrom<Vec<Event>> for LogsApiModel {
                 for (k, v) in log.convert_to_fields() {
                     log_model.insert(k, v.clone());
                 }
+                insert_entity_attributes(&log, &mut log_model);
+
                 if log.get("message").is_none() {
                     log_model.insert(
                         "message".to_owned(),
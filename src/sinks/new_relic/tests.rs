@@ -1,4 +1,4 @@
-use std::{collections::HashMap, convert::TryFrom, time::SystemTime};
+use std::{collections::HashMap, convert::TryFrom, num::NonZeroU32, time::SystemTime};
 
 use chrono::{DateTime, Utc};
 use futures::{future::ready, stream};
@@ -6,7 +6,7 @@ use futures::{future::ready, stream};
 use super::*;
 use crate::{
     config::{GenerateConfig, SinkConfig, SinkContext},
-    event::{Event, LogEvent, Metric, MetricKind, MetricValue, Value},
+    event::{Event, LogEvent, Metric, MetricKind, MetricValue, StatisticKind, Value},
     test_util::{
         components::{run_and_assert_sink_compliance, SINK_TAGS},
         http::{always_200_response, spawn_blackhole_http_server},
@@ -212,3 +212,64 @@ fn generate_metric_api_model() {
     assert_eq!(metrics[0].get("value").unwrap(), &Value::from(100.0));
     assert!(metrics[0].get("timestamp").is_some());
 }
+
+#[test]
+fn generate_dimensional_metric_api_model() {
+    let tags = vec![
+        ("host".into(), "my_host".to_owned()),
+        ("service".into(), "my_service".to_owned()),
+    ]
+    .into_iter()
+    .collect();
+    let counter = Metric::new(
+        "my_counter",
+        MetricKind::Incremental,
+        MetricValue::Counter { value: 10.0 },
+    )
+    .with_interval_ms(NonZeroU32::new(10000))
+    .with_tags(Some(tags));
+    let summary = Metric::new(
+        "my_summary",
+        MetricKind::Incremental,
+        MetricValue::Distribution {
+            samples: vector_core::samples![1.0 => 2, 4.0 => 1],
+            statistic: StatisticKind::Histogram,
+        },
+    )
+    .with_interval_ms(NonZeroU32::new(10000));
+    // Without an interval, summaries can't be sent.
+    let distribution = Metric::new(
+        "my_distribution",
+        MetricKind::Incremental,
+        MetricValue::Distribution {
+            samples: vector_core::samples![1.0 => 1],
+            statistic: StatisticKind::Histogram,
+        },
+    );
+
+    let model = MetricsApiModel::try_from(vec![
+        Event::Metric(counter),
+        Event::Metric(summary),
+        Event::Metric(distribution),
+    ])
+    .expect("Failed mapping metrics into API model");
+    let metrics = model.0[0]
+        .get("metrics")
+        .expect("Metrics data store not present");
+
+    assert_eq!(metrics.len(), 2);
+    assert_eq!(metrics[0]["type"], Value::from("count"));
+    assert_eq!(metrics[0]["value"], Value::from(10.0));
+    assert_eq!(metrics[0]["interval.ms"], Value::from(10000));
+    let attributes = metrics[0]["attributes"].as_object().unwrap();
+    assert_eq!(attributes["host"], Value::from("my_host"));
+    assert_eq!(attributes["host.name"], Value::from("my_host"));
+    assert_eq!(attributes["service.name"], Value::from("my_service"));
+
+    assert_eq!(metrics[1]["type"], Value::from("summary"));
+    let value = metrics[1]["value"].as_object().unwrap();
+    assert_eq!(value["count"], Value::from(3));
+    assert_eq!(value["sum"], Value::from(6.0));
+    assert_eq!(value["min"], Value::from(1.0));
+    assert_eq!(value["max"], Value::from(4.0));
+}
@@ -0,0 +1,568 @@
+//! The `pagerduty` sink, which sends events to the PagerDuty Events API v2.
+//!
+//! Each event triggers, acknowledges or resolves an alert of the service of its routing key.
+
+use std::time::Duration;
+
+use bytes::{Bytes, BytesMut};
+use futures::{future, FutureExt, SinkExt};
+use http::{Request, Uri};
+use serde_json::{Map, Value};
+use tokio::time::Instant;
+use vector_config::configurable_component;
+
+use crate::{
+    codecs::Transformer,
+    config::{log_schema, AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
+    event::{Event, Value as EventValue},
+    http::HttpClient,
+    internal_events::{
+        PagerDutyInvalidEvent, PagerDutyRateLimited, SinkRequestBuildError, TemplateRenderingError,
+    },
+    sinks::{
+        util::{
+            http::{BatchedHttpSink, HttpEventEncoder, HttpSink},
+            rate_limit::RateLimit,
+            BatchSettings, Buffer, Compression, TowerRequestConfig, UriSerde,
+        },
+        Healthcheck, VectorSink,
+    },
+    template::{Template, TemplateRenderingError as TemplateRenderingErrorKind},
+    tls::{TlsConfig, TlsSettings},
+};
+
+const DEFAULT_ENDPOINT: &str = "https://events.pagerduty.com";
+
+/// Configuration for the `pagerduty` sink.
+#[configurable_component(sink("pagerduty"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct PagerDutySinkConfig {
+    /// The integration key of the PagerDuty service to send events to.
+    #[configurable(metadata(templateable))]
+    routing_key: Template,
+
+    /// The base URL of the PagerDuty Events API.
+    ///
+    /// Defaults to `https://events.pagerduty.com`.
+    endpoint: Option<UriSerde>,
+
+    /// The action of each event, such as `{{ status }}`.
+    ///
+    /// Events whose action is `trigger`, `triggered` or `firing` trigger an alert, events whose
+    /// action is `acknowledge`, `acknowledged` or `ack` acknowledge it, and events whose action is
+    /// `resolve`, `resolved` or `ok` resolve it. Events trigger alerts by default.
+    #[configurable(metadata(templateable))]
+    action: Option<Template>,
+
+    /// The key identifying the alert of each event, such as `{{ service }}-{{ check }}`.
+    ///
+    /// Events with the same key are grouped in the same alert, which they can acknowledge or
+    /// resolve. Each triggering event creates a new alert by default.
+    #[configurable(metadata(templateable))]
+    dedup_key: Option<Template>,
+
+    /// The summary of the alert.
+    #[configurable(metadata(templateable))]
+    #[serde(default = "default_summary")]
+    summary: Template,
+
+    /// The system the alert is about.
+    #[configurable(metadata(templateable))]
+    #[serde(default = "default_source")]
+    source: Template,
+
+    /// The severity of each event, such as `{{ level }}`.
+    ///
+    /// It is mapped to the PagerDuty severity with the closest meaning, so syslog severities and
+    /// level names such as `fatal`, `err`, `warn` or `debug` can be used. Events whose severity
+    /// can't be mapped get `default_severity`.
+    #[configurable(metadata(templateable))]
+    severity: Option<Template>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    default_severity: PagerDutySeverity,
+
+    /// The component of the source system that is responsible for the alert.
+    #[configurable(metadata(templateable))]
+    component: Option<Template>,
+
+    /// The logical grouping of the components of the source system, such as `{{ cluster }}`.
+    #[configurable(metadata(templateable))]
+    group: Option<Template>,
+
+    /// The class or type of the alert, such as `disk full`.
+    #[configurable(metadata(templateable))]
+    class: Option<Template>,
+
+    /// The most events sent for each routing key within `routing_key_rate_limit_duration_secs`.
+    ///
+    /// PagerDuty rejects the events over its own limit, so the events over this one are dropped
+    /// rather than retried.
+    #[serde(default = "default_routing_key_rate_limit_num")]
+    routing_key_rate_limit_num: usize,
+
+    /// The time window, in seconds, used for `routing_key_rate_limit_num`.
+    #[serde(default = "default_routing_key_rate_limit_duration_secs")]
+    routing_key_rate_limit_duration_secs: u64,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    encoding: Transformer,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+/// The severity of a PagerDuty alert.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PagerDutySeverity {
+    /// Critical.
+    Critical,
+
+    /// Error.
+    Error,
+
+    /// Warning.
+    Warning,
+
+    /// Info.
+    Info,
+}
+
+impl Default for PagerDutySeverity {
+    fn default() -> Self {
+        Self::Error
+    }
+}
+
+impl PagerDutySeverity {
+    /// Maps a severity, such as a syslog severity or a level name, to the one with the closest
+    /// meaning.
+    fn from_meaning(severity: &str) -> Option<Self> {
+        match severity.trim().to_ascii_lowercase().as_str() {
+            "0" | "1" | "2" | "emerg" | "emergency" | "panic" | "alert" | "crit" | "critical"
+            | "fatal" => Some(Self::Critical),
+            "3" | "err" | "error" => Some(Self::Error),
+            "4" | "warn" | "warning" => Some(Self::Warning),
+            "5" | "6" | "7" | "notice" | "info" | "informational" | "debug" | "trace" => {
+                Some(Self::Info)
+            }
+            _ => None,
+        }
+    }
+
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Critical => "critical",
+            Self::Error => "error",
+            Self::Warning => "warning",
+            Self::Info => "info",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum PagerDutyAction {
+    Trigger,
+    Acknowledge,
+    Resolve,
+}
+
+impl PagerDutyAction {
+    fn parse(action: &str) -> Option<Self> {
+        match action.trim().to_ascii_lowercase().as_str() {
+            "trigger" | "triggered" | "firing" => Some(Self::Trigger),
+            "acknowledge" | "acknowledged" | "ack" => Some(Self::Acknowledge),
+            "resolve" | "resolved" | "ok" => Some(Self::Resolve),
+            _ => None,
+        }
+    }
+
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Trigger => "trigger",
+            Self::Acknowledge => "acknowledge",
+            Self::Resolve => "resolve",
+        }
+    }
+}
+
+fn default_summary() -> Template {
+    Template::try_from("{{ message }}").expect("valid template")
+}
+
+fn default_source() -> Template {
+    Template::try_from("{{ host }}").expect("valid template")
+}
+
+const fn default_routing_key_rate_limit_num() -> usize {
+    120
+}
+
+const fn default_routing_key_rate_limit_duration_secs() -> u64 {
+    60
+}
+
+impl GenerateConfig for PagerDutySinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"routing_key = "${PAGERDUTY_ROUTING_KEY}"
+            dedup_key = "{{ host }}-{{ service }}""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for PagerDutySinkConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls, cx.proxy())?;
+
+        let endpoint = match &self.endpoint {
+            Some(endpoint) => endpoint.clone(),
+            None => DEFAULT_ENDPOINT.parse()?,
+        };
+        let sink = PagerDutySink {
+            uri: endpoint.append_path("v2/enqueue")?.uri,
+            config: self.clone(),
+        };
+
+        let request = self.request.unwrap_with(&TowerRequestConfig::default());
+        // The Events API takes a single event per request.
+        let mut batch = BatchSettings::default();
+        batch.size.events = 1;
+        let sink = BatchedHttpSink::new(
+            sink,
+            Buffer::new(batch.size, Compression::None),
+            request,
+            batch.timeout,
+            client,
+        )
+        .sink_map_err(|error| error!(message = "Fatal pagerduty sink error.", %error));
+
+        // The Events API has no way to check a routing key without sending an event.
+        Ok((VectorSink::from_event_sink(sink), future::ok(()).boxed()))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+struct PagerDutySink {
+    uri: Uri,
+    config: PagerDutySinkConfig,
+}
+
+struct PagerDutyEventEncoder {
+    config: PagerDutySinkConfig,
+    rate_limit: RateLimit,
+}
+
+impl PagerDutyEventEncoder {
+    /// Renders the PagerDuty event of the event, dropping it if it is invalid or over the rate
+    /// limit of its routing key.
+    fn message(&mut self, mut event: Event) -> Option<Map<String, Value>> {
+        let config = &self.config;
+        let routing_key = render(config.routing_key.render_string(&event), "routing_key")?;
+
+        let action = match &config.action {
+            Some(action) => {
+                let action = render(action.render_string(&event), "action")?;
+                match PagerDutyAction::parse(&action) {
+                    Some(action) => action,
+                    None => {
+                        emit!(PagerDutyInvalidEvent {
+                            reason: "Unknown event action.",
+                        });
+                        return None;
+                    }
+                }
+            }
+            None => PagerDutyAction::Trigger,
+        };
+        let dedup_key = match &config.dedup_key {
+            Some(dedup_key) => Some(render(dedup_key.render_string(&event), "dedup_key")?),
+            None => None,
+        };
+        if action != PagerDutyAction::Trigger && dedup_key.is_none() {
+            emit!(PagerDutyInvalidEvent {
+                reason: "A dedup key is required to acknowledge or resolve an alert.",
+            });
+            return None;
+        }
+
+        let mut message = Map::new();
+        message.insert("event_action".into(), action.as_str().into());
+        if let Some(dedup_key) = dedup_key {
+            message.insert("dedup_key".into(), dedup_key.into());
+        }
+
+        if action == PagerDutyAction::Trigger {
+            let severity = config
+                .severity
+                .as_ref()
+                .and_then(|severity| severity.render_string(&event).ok())
+                .and_then(|severity| PagerDutySeverity::from_meaning(&severity))
+                .unwrap_or(config.default_severity);
+
+            let mut payload = Map::new();
+            payload.insert(
+                "summary".into(),
+                render(config.summary.render_string(&event), "summary")?.into(),
+            );
+            payload.insert(
+                "source".into(),
+                render(config.source.render_string(&event), "source")?.into(),
+            );
+            payload.insert("severity".into(), severity.as_str().into());
+            for (field, template) in [
+                ("component", &config.component),
+                ("group", &config.group),
+                ("class", &config.class),
+            ] {
+                if let Some(template) = template {
+                    payload.insert(
+                        field.into(),
+                        render(template.render_string(&event), field)?.into(),
+                    );
+                }
+            }
+
+            config.encoding.transform(&mut event);
+            let log = event.into_log();
+            if let Some(EventValue::Timestamp(timestamp)) = log.get(log_schema().timestamp_key()) {
+                payload.insert("timestamp".into(), timestamp.to_rfc3339().into());
+            }
+            let details = serde_json::to_value(&log)
+                .map_err(|error| emit!(SinkRequestBuildError { error }))
+                .ok()?;
+            payload.insert("custom_details".into(), details);
+
+            message.insert("payload".into(), payload.into());
+        }
+
+        if !self.rate_limit.check(&routing_key, Instant::now()) {
+            emit!(PagerDutyRateLimited { count: 1 });
+            return None;
+        }
+        message.insert("routing_key".into(), routing_key.into());
+
+        Some(message)
+    }
+}
+
+fn render<T>(rendered: Result<T, TemplateRenderingErrorKind>, field: &str) -> Option<T> {
+    rendered
+        .map_err(|error| {
+            emit!(TemplateRenderingError {
+                error,
+                field: Some(field),
+                drop_event: true,
+            });
+        })
+        .ok()
+}
+
+impl HttpEventEncoder<BytesMut> for PagerDutyEventEncoder {
+    fn encode_event(&mut self, event: Event) -> Option<BytesMut> {
+        let message = self.message(event)?;
+
+        crate::serde::json::to_bytes(&message)
+            .map_err(|error| emit!(SinkRequestBuildError { error }))
+            .ok()
+    }
+}
+
+#[async_trait::async_trait]
+impl HttpSink for PagerDutySink {
+    type Input = BytesMut;
+    type Output = BytesMut;
+    type Encoder = PagerDutyEventEncoder;
+
+    fn build_encoder(&self) -> Self::Encoder {
+        PagerDutyEventEncoder {
+            config: self.config.clone(),
+            rate_limit: RateLimit::new(
+                self.config.routing_key_rate_limit_num,
+                Duration::from_secs(self.config.routing_key_rate_limit_duration_secs),
+            ),
+        }
+    }
+
+    async fn build_request(&self, body: Self::Output) -> crate::Result<Request<Bytes>> {
+        Request::post(self.uri.clone())
+            .header("Content-Type", "application/json")
+            .body(body.freeze())
+            .map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{stream, StreamExt};
+    use indoc::indoc;
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        event::LogEvent,
+        sinks::util::test::{build_test_server, load_sink},
+        test_util::{
+            components::{run_and_assert_sink_compliance, HTTP_SINK_TAGS},
+            next_addr,
+        },
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<PagerDutySinkConfig>();
+    }
+
+    fn encoder(config: &str) -> PagerDutyEventEncoder {
+        let config: PagerDutySinkConfig = toml::from_str(config).unwrap();
+        PagerDutyEventEncoder {
+            rate_limit: RateLimit::new(
+                config.routing_key_rate_limit_num,
+                Duration::from_secs(config.routing_key_rate_limit_duration_secs),
+            ),
+            config,
+        }
+    }
+
+    fn event(status: &str, level: &str) -> Event {
+        let mut log = LogEvent::from("disk full");
+        log.insert("host", "db-1");
+        log.insert("service", "db");
+        log.insert("status", status);
+        log.insert("level", level);
+        log.into()
+    }
+
+    #[test]
+    fn maps_severity_meanings() {
+        for (severity, expected) in [
+            ("emerg", PagerDutySeverity::Critical),
+            ("FATAL", PagerDutySeverity::Critical),
+            ("2", PagerDutySeverity::Critical),
+            ("err", PagerDutySeverity::Error),
+            ("warn", PagerDutySeverity::Warning),
+            ("4", PagerDutySeverity::Warning),
+            ("notice", PagerDutySeverity::Info),
+            ("debug", PagerDutySeverity::Info),
+        ] {
+            assert_eq!(PagerDutySeverity::from_meaning(severity), Some(expected));
+        }
+        assert_eq!(PagerDutySeverity::from_meaning("bad"), None);
+    }
+
+    #[test]
+    fn triggers_alerts() {
+        let mut encoder = encoder(indoc! {r#"
+            routing_key = "key"
+            action = "{{ status }}"
+            dedup_key = "{{ host }}-{{ service }}"
+            severity = "{{ level }}"
+            group = "{{ service }}"
+        "#});
+
+        let message = encoder.message(event("firing", "crit")).unwrap();
+        assert_eq!(message["routing_key"], "key");
+        assert_eq!(message["event_action"], "trigger");
+        assert_eq!(message["dedup_key"], "db-1-db");
+        let payload = &message["payload"];
+        assert_eq!(payload["summary"], "disk full");
+        assert_eq!(payload["source"], "db-1");
+        assert_eq!(payload["severity"], "critical");
+        assert_eq!(payload["group"], "db");
+        assert_eq!(payload["custom_details"]["service"], "db");
+
+        // Unknown severities get the default one.
+        let message = encoder.message(event("firing", "bad")).unwrap();
+        assert_eq!(message["payload"]["severity"], "error");
+    }
+
+    #[test]
+    fn resolves_alerts_with_dedup_key() {
+        let mut encoder = encoder(indoc! {r#"
+            routing_key = "key"
+            action = "{{ status }}"
+            dedup_key = "{{ host }}-{{ service }}"
+        "#});
+        let message = encoder.message(event("resolved", "info")).unwrap();
+        assert_eq!(
+            Value::from(message),
+            json!({"routing_key": "key", "event_action": "resolve", "dedup_key": "db-1-db"})
+        );
+        assert!(encoder.message(event("unknown", "info")).is_none());
+
+        // Alerts can't be resolved without a dedup key.
+        let mut encoder = encoder(indoc! {r#"
+            routing_key = "key"
+            action = "{{ status }}"
+        "#});
+        assert!(encoder.message(event("resolved", "info")).is_none());
+    }
+
+    #[test]
+    fn limits_events_per_routing_key() {
+        let mut encoder = encoder(indoc! {r#"
+            routing_key = "{{ service }}"
+            routing_key_rate_limit_num = 1
+        "#});
+
+        assert!(encoder.message(event("firing", "crit")).is_some());
+        assert!(encoder.message(event("firing", "crit")).is_none());
+    }
+
+    #[tokio::test]
+    async fn sends_events() {
+        let addr = next_addr();
+        let config = format!(
+            indoc! {r#"
+                routing_key = "key"
+                endpoint = "http://{}"
+            "#},
+            addr
+        );
+        let (config, cx) = load_sink::<PagerDutySinkConfig>(&config).unwrap();
+        let (sink, _) = config.build(cx).await.unwrap();
+        let (rx, _trigger, server) = build_test_server(addr);
+        tokio::spawn(server);
+
+        let event = event("firing", "crit");
+        run_and_assert_sink_compliance(sink, stream::once(async { event }), &HTTP_SINK_TAGS).await;
+
+        let output = rx.take(1).collect::<Vec<_>>().await;
+        let (parts, body) = &output[0];
+        assert_eq!(parts.uri.path(), "/v2/enqueue");
+
+        let message: Value = serde_json::from_slice(body).unwrap();
+        assert_eq!(message["event_action"], "trigger");
+        assert_eq!(message["payload"]["summary"], "disk full");
+    }
+}
@@ -3,14 +3,16 @@ use std::{collections::BTreeMap, fmt::Write as _};
 use chrono::Utc;
 use indexmap::map::IndexMap;
 use prometheus_parser::{proto, METRIC_NAME_LABEL};
-use vector_core::event::metric::{samples_to_buckets, MetricSketch, Quantile};
+use vector_core::event::metric::{
+    samples_to_buckets, Bucket, ExponentialBuckets, ExponentialHistogram, MetricSketch, Quantile,
+};
 
 use crate::{
-    event::metric::{Metric, MetricKind, MetricValue, StatisticKind},
+    event::metric::{Metric, MetricKind, MetricTags, MetricValue, StatisticKind},
     sinks::util::{encode_namespace, statistic::DistributionStatistic},
 };
 
-pub(super) trait MetricCollector {
+pub(crate) trait MetricCollector {
     type Output;
 
     fn new() -> Self;
@@ -23,12 +25,83 @@ pub(super) trait MetricCollector {
         name: &str,
         suffix: &str,
         value: f64,
-        tags: Option<&BTreeMap<String, String>>,
+        tags: Option<&MetricTags>,
         extra: Option<(&str, String)>,
     );
 
     fn finish(self) -> Self::Output;
 
+    /// Emits an exponential histogram.
+    ///
+    /// By default, it is downgraded to an aggregated histogram, since the text exposition format
+    /// has no way of representing its buckets.
+    fn emit_exponential_histogram(
+        &mut self,
+        timestamp_millis: Option<i64>,
+        name: &str,
+        histogram: &ExponentialHistogram,
+        tags: Option<&MetricTags>,
+    ) {
+        self.emit_histogram_buckets(
+            timestamp_millis,
+            name,
+            &histogram.to_buckets(),
+            histogram.count,
+            histogram.sum,
+            tags,
+        );
+    }
+
+    fn emit_histogram_buckets(
+        &mut self,
+        timestamp: Option<i64>,
+        name: &str,
+        buckets: &[Bucket],
+        count: u64,
+        sum: f64,
+        tags: Option<&MetricTags>,
+    ) {
+        let mut bucket_count = 0.0;
+        for bucket in buckets {
+            // Aggregated histograms are cumulative in Prometheus.  This means that the
+            // count of values in a bucket should only go up at the upper limit goes up,
+            // because if you count a value in a specific bucket, by definition, it is
+            // less than the upper limit of the next bucket.
+            //
+            // While most sources should give us buckets that have an "infinity" bucket
+            // -- everything else that didn't fit in the non-infinity-upper-limit buckets
+            // -- we can't be sure, so we calculate that bucket ourselves.  This is why
+            // we make sure to avoid encoding a bucket if its upper limit is already
+            // infinity, so that we don't double report.
+            //
+            // This check will also avoid printing out a bucket whose upper limit is
+            // negative infinity, because that would make no sense.
+            if bucket.upper_limit.is_infinite() {
+                continue;
+            }
+
+            bucket_count += bucket.count as f64;
+            self.emit_value(
+                timestamp,
+                name,
+                "_bucket",
+                bucket_count,
+                tags,
+                Some(("le", bucket.upper_limit.to_string())),
+            );
+        }
+        self.emit_value(
+            timestamp,
+            name,
+            "_bucket",
+            count as f64,
+            tags,
+            Some(("le", "+Inf".to_string())),
+        );
+        self.emit_value(timestamp, name, "_sum", sum, tags, None);
+        self.emit_value(timestamp, name, "_count", count as f64, tags, None);
+    }
+
     fn encode_metric(
         &mut self,
         default_namespace: Option<&str>,
@@ -121,45 +194,10 @@ pub(super) trait MetricCollector {
                     count,
                     sum,
                 } => {
-                    let mut bucket_count = 0.0;
-                    for bucket in buckets {
-                        // Aggregated histograms are cumulative in Prometheus.  This means that the
-                        // count of values in a bucket should only go up at the upper limit goes up,
-                        // because if you count a value in a specific bucket, by definition, it is
-                        // less than the upper limit of the next bucket.
-                        //
-                        // While most sources should give us buckets that have an "infinity" bucket
-                        // -- everything else that didn't fit in the non-infinity-upper-limit buckets
-                        // -- we can't be sure, so we calculate that bucket ourselves.  This is why
-                        // we make sure to avoid encoding a bucket if its upper limit is already
-                        // infinity, so that we don't double report.
-                        //
-                        // This check will also avoid printing out a bucket whose upper limit is
-                        // negative infinity, because that would make no sense.
-                        if bucket.upper_limit.is_infinite() {
-                            continue;
-                        }
-
-                        bucket_count += bucket.count as f64;
-                        self.emit_value(
-                            timestamp,
-                            name,
-                            "_bucket",
-                            bucket_count,
-                            tags,
-                            Some(("le", bucket.upper_limit.to_string())),
-                        );
-                    }
-                    self.emit_value(
-                        timestamp,
-                        name,
-                        "_bucket",
-                        *count as f64,
-                        tags,
-                        Some(("le", "+Inf".to_string())),
-                    );
-                    self.emit_value(timestamp, name, "_sum", *sum, tags, None);
-                    self.emit_value(timestamp, name, "_count", *count as f64, tags, None);
+                    self.emit_histogram_buckets(timestamp, name, buckets, *count, *sum, tags);
+                }
+                MetricValue::ExponentialHistogram { histogram } => {
+                    self.emit_exponential_histogram(timestamp, name, histogram, tags);
                 }
                 MetricValue::AggregatedSummary {
                     quantiles,
@@ -218,7 +256,7 @@ pub(super) trait MetricCollector {
     }
 }
 
-pub(super) struct StringCollector {
+pub(crate) struct StringCollector {
     // BTreeMap ensures we get sorted output, which whilst not required is preferable
     processed: BTreeMap<String, String>,
 }
@@ -244,7 +282,7 @@ impl MetricCollector for StringCollector {
         name: &str,
         suffix: &str,
         value: f64,
-        tags: Option<&BTreeMap<String, String>>,
+        tags: Option<&MetricTags>,
         extra: Option<(&str, String)>,
     ) {
         let result = self
@@ -267,11 +305,7 @@ impl MetricCollector for StringCollector {
 }
 
 impl StringCollector {
-    fn encode_tags(
-        result: &mut String,
-        tags: Option<&BTreeMap<String, String>>,
-        extra: Option<(&str, String)>,
-    ) {
+    fn encode_tags(result: &mut String, tags: Option<&MetricTags>, extra: Option<(&str, String)>) {
         match (tags, extra) {
             (None, None) => Ok(()),
             (None, Some(tag)) => write!(result, "{{{}}}", Self::format_tag(tag.0, &tag.1)),
@@ -320,15 +354,20 @@ impl StringCollector {
 
 type Labels = Vec<proto::Label>;
 
+/// The finest and coarsest schemas supported by Prometheus native histograms.
+const MAX_NATIVE_HISTOGRAM_SCHEMA: i32 = 8;
+const MIN_NATIVE_HISTOGRAM_SCHEMA: i32 = -4;
+
 pub(super) struct TimeSeries {
     buffer: IndexMap<Labels, Vec<proto::Sample>>,
+    histograms: IndexMap<Labels, Vec<proto::Histogram>>,
     metadata: IndexMap<String, proto::MetricMetadata>,
     timestamp: Option<i64>,
 }
 
 impl TimeSeries {
     fn make_labels(
-        tags: Option<&BTreeMap<String, String>>,
+        tags: Option<&MetricTags>,
         name: &str,
         suffix: &str,
         extra: Option<(&str, String)>,
@@ -347,7 +386,10 @@ impl TimeSeries {
         // consistent key for the buffer.
         let mut labels = labels
             .into_iter()
-            .map(|(name, value)| proto::Label { name, value })
+            .map(|(name, value)| proto::Label {
+                name: name.into(),
+                value,
+            })
             .collect::<Labels>();
         labels.sort();
         labels
@@ -366,6 +408,7 @@ impl MetricCollector for TimeSeries {
     fn new() -> Self {
         Self {
             buffer: Default::default(),
+            histograms: Default::default(),
             metadata: Default::default(),
             timestamp: None,
         }
@@ -390,7 +433,7 @@ impl MetricCollector for TimeSeries {
         name: &str,
         suffix: &str,
         value: f64,
-        tags: Option<&BTreeMap<String, String>>,
+        tags: Option<&MetricTags>,
         extra: Option<(&str, String)>,
     ) {
         let timestamp = timestamp_millis.unwrap_or_else(|| self.default_timestamp());
@@ -400,12 +443,82 @@ impl MetricCollector for TimeSeries {
             .push(proto::Sample { value, timestamp });
     }
 
+    fn emit_exponential_histogram(
+        &mut self,
+        timestamp_millis: Option<i64>,
+        name: &str,
+        histogram: &ExponentialHistogram,
+        tags: Option<&MetricTags>,
+    ) {
+        if histogram.scale < MIN_NATIVE_HISTOGRAM_SCHEMA {
+            self.emit_histogram_buckets(
+                timestamp_millis,
+                name,
+                &histogram.to_buckets(),
+                histogram.count,
+                histogram.sum,
+                tags,
+            );
+            return;
+        }
+
+        let mut histogram = histogram.clone();
+        histogram.downscale(MAX_NATIVE_HISTOGRAM_SCHEMA);
+
+        // Native histogram bucket `index` holds `(base^(index - 1), base^index]`, one off from ours.
+        let native_spans = |buckets: &ExponentialBuckets| {
+            let (mut spans, deltas) = buckets.to_spans();
+            if let Some((offset, _)) = spans.first_mut() {
+                *offset += 1;
+            }
+            let spans = spans
+                .into_iter()
+                .map(|(offset, length)| proto::BucketSpan { offset, length })
+                .collect::<Vec<_>>();
+            (spans, deltas)
+        };
+        let (positive_spans, positive_deltas) = native_spans(&histogram.positive);
+        let (negative_spans, negative_deltas) = native_spans(&histogram.negative);
+
+        let timestamp = timestamp_millis.unwrap_or_else(|| self.default_timestamp());
+        self.histograms
+            .entry(Self::make_labels(tags, name, "", None))
+            .or_default()
+            .push(proto::Histogram {
+                count: Some(proto::histogram::Count::CountInt(histogram.count)),
+                sum: histogram.sum,
+                schema: histogram.scale,
+                zero_threshold: histogram.zero_threshold,
+                zero_count: Some(proto::histogram::ZeroCount::ZeroCountInt(
+                    histogram.zero_count,
+                )),
+                negative_spans,
+                negative_deltas,
+                positive_spans,
+                positive_deltas,
+                timestamp,
+                ..Default::default()
+            });
+    }
+
     fn finish(self) -> proto::WriteRequest {
-        let timeseries = self
+        let samples = self
             .buffer
             .into_iter()
-            .map(|(labels, samples)| proto::TimeSeries { labels, samples })
-            .collect::<Vec<_>>();
+            .map(|(labels, samples)| proto::TimeSeries {
+                labels,
+                samples,
+                histograms: vec![],
+            });
+        let histograms =
+            self.histograms
+                .into_iter()
+                .map(|(labels, histograms)| proto::TimeSeries {
+                    labels,
+                    samples: vec![],
+                    histograms,
+                });
+        let timeseries = samples.chain(histograms).collect::<Vec<_>>();
         let metadata = self
             .metadata
             .into_iter()
@@ -431,7 +544,9 @@ const fn prometheus_metric_type(metric_value: &MetricValue) -> proto::MetricType
             statistic: StatisticKind::Summary,
             ..
         } => MetricType::Summary,
-        MetricValue::AggregatedHistogram { .. } => MetricType::Histogram,
+        MetricValue::AggregatedHistogram { .. } | MetricValue::ExponentialHistogram { .. } => {
+            MetricType::Histogram
+        }
         MetricValue::AggregatedSummary { .. } => MetricType::Summary,
         MetricValue::Sketch { .. } => MetricType::Summary,
     }
@@ -462,8 +577,8 @@ mod tests {
         s.finish()
     }
 
-    fn tags() -> BTreeMap<String, String> {
-        vec![("code".to_owned(), "200".to_owned())]
+    fn tags() -> MetricTags {
+        vec![("code".into(), "200".to_owned())]
             .into_iter()
             .collect()
     }
@@ -495,6 +610,7 @@ mod tests {
                                 value: $svalue,
                                 timestamp: $timestamp,
                             }],
+                            histograms: vec![],
                         },
                     )*
                 ],
@@ -772,6 +888,73 @@ mod tests {
         encode_one::<T>(Some("vector"), &[], &[], &metric)
     }
 
+    #[test]
+    fn encodes_exponential_histogram_text() {
+        assert_eq!(
+            encode_exponential_histogram::<StringCollector>(),
+            indoc! {r#"
+                # HELP vector_latency latency
+                # TYPE vector_latency histogram
+                vector_latency_bucket{le="0"} 0 1612325106789
+                vector_latency_bucket{le="2"} 2 1612325106789
+                vector_latency_bucket{le="4"} 3 1612325106789
+                vector_latency_bucket{le="+Inf"} 3 1612325106789
+                vector_latency_sum 6 1612325106789
+                vector_latency_count 3 1612325106789
+            "#}
+        );
+    }
+
+    #[test]
+    fn encodes_exponential_histogram_request() {
+        assert_eq!(
+            encode_exponential_histogram::<TimeSeries>(),
+            proto::WriteRequest {
+                timeseries: vec![proto::TimeSeries {
+                    labels: vec![proto::Label {
+                        name: "__name__".into(),
+                        value: "vector_latency".into(),
+                    }],
+                    samples: vec![],
+                    histograms: vec![proto::Histogram {
+                        count: Some(proto::histogram::Count::CountInt(3)),
+                        sum: 6.0,
+                        schema: 0,
+                        zero_threshold: 0.0,
+                        zero_count: Some(proto::histogram::ZeroCount::ZeroCountInt(0)),
+                        positive_spans: vec![proto::BucketSpan {
+                            offset: 1,
+                            length: 2,
+                        }],
+                        positive_deltas: vec![2, -1],
+                        timestamp: 1612325106789,
+                        ..Default::default()
+                    }],
+                }],
+                metadata: vec![proto::MetricMetadata {
+                    r#type: proto::metric_metadata::MetricType::Histogram as i32,
+                    metric_family_name: "vector_latency".into(),
+                    help: "latency".into(),
+                    unit: "".into(),
+                }],
+            }
+        );
+    }
+
+    fn encode_exponential_histogram<T: MetricCollector>() -> T::Output {
+        let mut histogram = ExponentialHistogram::new(0);
+        histogram.record(1.5, 2);
+        histogram.record(3.0, 1);
+
+        let metric = Metric::new(
+            "latency".to_owned(),
+            MetricKind::Absolute,
+            MetricValue::ExponentialHistogram { histogram },
+        )
+        .with_timestamp(Some(timestamp()));
+        encode_one::<T>(Some("vector"), &[], &[], &metric)
+    }
+
     #[test]
     fn encodes_summary_text() {
         assert_eq!(
@@ -922,13 +1105,13 @@ mod tests {
 
     #[test]
     fn escapes_tags_text() {
-        let tags: BTreeMap<String, String> = [
+        let tags: MetricTags = [
             ("code", "200"),
             ("quoted", r#"host"1""#),
             ("path", r#"c:\Windows"#),
         ]
         .iter()
-        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .map(|(k, v)| ((*k).into(), v.to_string()))
         .collect();
         let metric = Metric::new(
             "something".to_owned(),
@@ -1000,7 +1000,7 @@ mod tests {
         let name = name.unwrap_or_else(|| format!("vector_set_{}", random_string(16)));
         let event = Metric::new(name.clone(), MetricKind::Incremental, value)
             .with_tags(Some(
-                vec![("some_tag".to_owned(), "some_value".to_owned())]
+                vec![("some_tag".into(), "some_value".to_owned())]
                     .into_iter()
                     .collect(),
             ))
@@ -1024,13 +1024,13 @@ mod tests {
             MetricValue::Counter { value: 32. },
         )
         .with_tags(Some(
-            vec![("tag1".to_owned(), "value1".to_owned())]
+            vec![("tag1".into(), "value1".to_owned())]
                 .into_iter()
                 .collect(),
         ));
 
         let m2 = m1.clone().with_tags(Some(
-            vec![("tag1".to_owned(), "value2".to_owned())]
+            vec![("tag1".into(), "value2".to_owned())]
                 .into_iter()
                 .collect(),
         ));
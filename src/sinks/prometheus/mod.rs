@@ -1,7 +1,7 @@
 #[cfg(test)]
 use vector_core::event::Metric;
 
-mod collector;
+pub(crate) mod collector;
 pub(crate) mod exporter;
 pub(crate) mod remote_write;
 
@@ -27,13 +27,13 @@ pub enum PrometheusRemoteWriteAuth {
     Aws(#[configurable(derived)] AwsAuthentication),
 }
 
-fn default_histogram_buckets() -> Vec<f64> {
+pub(crate) fn default_histogram_buckets() -> Vec<f64> {
     vec![
         0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
     ]
 }
 
-fn default_summary_quantiles() -> Vec<f64> {
+pub(crate) fn default_summary_quantiles() -> Vec<f64> {
     vec![0.5, 0.75, 0.9, 0.95, 0.99]
 }
 
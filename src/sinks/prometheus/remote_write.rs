@@ -583,8 +583,8 @@ mod tests {
         Metric::new(name, MetricKind::Absolute, MetricValue::Gauge { value })
             .with_tags(Some(
                 vec![
-                    ("region".to_owned(), "us-west-1".to_owned()),
-                    ("production".to_owned(), "true".to_owned()),
+                    ("region".into(), "us-west-1".to_owned()),
+                    ("production".into(), "true".to_owned()),
                 ]
                 .into_iter()
                 .collect(),
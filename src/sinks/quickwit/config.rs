@@ -0,0 +1,226 @@
+use futures::FutureExt;
+use http::{Request, StatusCode};
+use hyper::Body;
+use indoc::indoc;
+use tower::ServiceBuilder;
+use vector_config::configurable_component;
+
+use super::{
+    service::{QuickwitRetryLogic, QuickwitService, PATH_SEGMENT},
+    sink::QuickwitSink,
+};
+use crate::{
+    codecs::Transformer,
+    config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
+    http::{Auth, HttpClient, MaybeAuth},
+    sinks::{
+        util::{
+            BatchConfig, Compression, ServiceBuilderExt, SinkBatchSettings, TowerRequestConfig,
+            UriSerde,
+        },
+        Healthcheck, HealthcheckError, VectorSink,
+    },
+    template::Template,
+    tls::{TlsConfig, TlsSettings},
+};
+
+// The ingest API rejects the requests larger than 10MB:
+// https://quickwit.io/docs/configuration/node-config#rest-configuration
+const MAX_PAYLOAD_SIZE: usize = 10_000_000;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct QuickwitDefaultBatchSettings;
+
+impl SinkBatchSettings for QuickwitDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = None;
+    const MAX_BYTES: Option<usize> = Some(MAX_PAYLOAD_SIZE);
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+/// Configuration for the `quickwit` sink.
+#[configurable_component(sink("quickwit"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct QuickwitConfig {
+    /// The endpoint of the REST API of the Quickwit cluster.
+    pub endpoint: UriSerde,
+
+    /// The index the events are ingested into.
+    ///
+    /// The index must already exist. The events are batched by index, so that each request
+    /// ingests into a single one.
+    pub index: Template,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub commit: QuickwitCommit,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub compression: Compression,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub encoding: Transformer,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<QuickwitDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    pub auth: Option<Auth>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+/// When the documents ingested are committed, and become searchable.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum QuickwitCommit {
+    /// The documents are committed once the commit timeout of the index expires, and the request
+    /// returns as soon as they are queued for indexing.
+    #[derivative(Default)]
+    Auto,
+
+    /// The request waits until the documents are committed, once the commit timeout of the index
+    /// expires.
+    WaitFor,
+
+    /// The documents are committed immediately, and the request waits until they are.
+    ///
+    /// Forcing commits creates many small splits, so this should only be used with large batches
+    /// or low volumes of events.
+    Force,
+}
+
+impl QuickwitCommit {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::WaitFor => "wait_for",
+            Self::Force => "force",
+        }
+    }
+}
+
+impl GenerateConfig for QuickwitConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(indoc! {r#"
+            endpoint = "http://localhost:7280"
+            index = "my-index"
+        "#})
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for QuickwitConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let batch_settings = self
+            .batch
+            .validate()?
+            .limit_max_bytes(MAX_PAYLOAD_SIZE)?
+            .into_batcher_settings()?;
+        let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
+
+        let endpoint = self.endpoint.with_default_parts();
+        let auth = self.auth.choose_one(&endpoint.auth)?;
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls, cx.proxy())?;
+
+        // The index can only be checked when it is the same for all of the events.
+        let index = (!self.index.is_dynamic()).then(|| self.index.get_ref().to_owned());
+        let healthcheck =
+            healthcheck(client.clone(), endpoint.clone(), auth.clone(), index).boxed();
+
+        let service = QuickwitService::new(client, endpoint, auth, self.commit, self.compression);
+        let service = ServiceBuilder::new()
+            .settings(request_settings, QuickwitRetryLogic)
+            .service(service);
+
+        let sink = QuickwitSink::new(self, batch_settings, service);
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+async fn healthcheck(
+    client: HttpClient,
+    endpoint: UriSerde,
+    auth: Option<Auth>,
+    index: Option<String>,
+) -> crate::Result<()> {
+    let mut paths = vec!["health/readyz".to_owned()];
+    if let Some(index) = index {
+        paths.push(format!(
+            "api/v1/indexes/{}",
+            percent_encoding::utf8_percent_encode(&index, PATH_SEGMENT)
+        ));
+    }
+
+    for path in paths {
+        let mut request = Request::get(endpoint.append_path(&path)?.uri)
+            .body(Body::empty())
+            .unwrap();
+        if let Some(auth) = &auth {
+            auth.apply(&mut request);
+        }
+
+        match client.send(request).await?.status() {
+            StatusCode::OK => {}
+            status => return Err(HealthcheckError::UnexpectedStatus { status }.into()),
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<QuickwitConfig>();
+    }
+
+    #[test]
+    fn parses_commit() {
+        let config: QuickwitConfig = toml::from_str(indoc! {r#"
+            endpoint = "http://localhost:7280"
+            index = "{{ service }}-logs"
+            commit = "wait_for"
+        "#})
+        .unwrap();
+
+        assert_eq!(config.commit, QuickwitCommit::WaitFor);
+        assert_eq!(config.commit.as_str(), "wait_for");
+        assert!(config.index.is_dynamic());
+    }
+}
@@ -0,0 +1,13 @@
+//! The `quickwit` sink.
+//!
+//! Sends the events to the indexes of a [Quickwit][quickwit] cluster, as batches of newline
+//! delimited JSON documents, through its [ingest API][ingest_api].
+//!
+//! [quickwit]: https://quickwit.io/
+//! [ingest_api]: https://quickwit.io/docs/reference/rest-api#ingest-data-into-an-index
+
+mod config;
+mod service;
+mod sink;
+
+pub use self::config::QuickwitConfig;
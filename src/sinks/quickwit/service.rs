@@ -0,0 +1,243 @@
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use http::{Request, StatusCode, Uri};
+use hyper::Body;
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use serde::Deserialize;
+use snafu::{ResultExt, Snafu};
+use tower::Service;
+use tracing::Instrument;
+use vector_core::{
+    event::{EventFinalizers, EventStatus, Finalizable},
+    internal_event::CountByteSize,
+    stream::DriverResponse,
+};
+
+use super::config::QuickwitCommit;
+use crate::{
+    http::{get_http_scheme_from_uri, Auth, HttpClient, HttpError},
+    internal_events::QuickwitDocsRejected,
+    sinks::util::{metadata::RequestMetadata, retries::RetryLogic, Compression, UriSerde},
+};
+
+/// The characters escaped in the path segments, leaving the separators of index IDs as they are.
+pub(super) const PATH_SEGMENT: &AsciiSet = &NON_ALPHANUMERIC.remove(b'-').remove(b'_').remove(b'.');
+
+#[derive(Debug, Snafu)]
+pub enum QuickwitError {
+    #[snafu(display("Failed to make HTTP(S) request: {}", source))]
+    Http { source: HttpError },
+
+    #[snafu(display("Failed to read the response: {}", source))]
+    ReadResponse { source: hyper::Error },
+
+    #[snafu(display("Server responded with an error: {}: {}", status, message))]
+    ServerError { status: StatusCode, message: String },
+}
+
+#[derive(Clone, Debug)]
+pub struct QuickwitRetryLogic;
+
+impl RetryLogic for QuickwitRetryLogic {
+    type Error = QuickwitError;
+    type Response = QuickwitResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            // The ingest queues of the cluster are full.
+            QuickwitError::ServerError { status, .. } => {
+                *status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+            }
+            QuickwitError::Http { .. } | QuickwitError::ReadResponse { .. } => true,
+        }
+    }
+
+    fn error_status(&self, error: &Self::Error) -> Option<StatusCode> {
+        match error {
+            QuickwitError::ServerError { status, .. } => Some(*status),
+            QuickwitError::Http { .. } | QuickwitError::ReadResponse { .. } => None,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct QuickwitRequest {
+    pub index: String,
+    pub payload: Bytes,
+    pub finalizers: EventFinalizers,
+    pub metadata: RequestMetadata,
+}
+
+impl Finalizable for QuickwitRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        self.finalizers.take_finalizers()
+    }
+}
+
+pub struct QuickwitResponse {
+    protocol: &'static str,
+    metadata: RequestMetadata,
+}
+
+impl DriverResponse for QuickwitResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> CountByteSize {
+        CountByteSize(
+            self.metadata.event_count(),
+            self.metadata.events_byte_size(),
+        )
+    }
+
+    fn bytes_sent(&self) -> Option<(usize, &str)> {
+        Some((self.metadata.request_wire_size(), self.protocol))
+    }
+}
+
+/// The response of the ingest API.
+///
+/// Only the versions of Quickwit that validate the documents as they are ingested report the
+/// rejected ones.
+#[derive(Debug, Deserialize)]
+struct IngestResponse {
+    #[serde(default)]
+    num_rejected_docs: u64,
+}
+
+#[derive(Clone, Debug)]
+pub struct QuickwitService {
+    client: HttpClient,
+    endpoint: UriSerde,
+    auth: Option<Auth>,
+    commit: QuickwitCommit,
+    compression: Compression,
+}
+
+impl QuickwitService {
+    pub const fn new(
+        client: HttpClient,
+        endpoint: UriSerde,
+        auth: Option<Auth>,
+        commit: QuickwitCommit,
+        compression: Compression,
+    ) -> Self {
+        Self {
+            client,
+            endpoint,
+            auth,
+            commit,
+            compression,
+        }
+    }
+
+    async fn ingest(self, request: QuickwitRequest) -> Result<QuickwitResponse, QuickwitError> {
+        let uri = ingest_uri(&self.endpoint.uri, &request.index, self.commit);
+        let protocol = get_http_scheme_from_uri(&uri);
+
+        let mut builder = Request::post(uri).header("Content-Type", "application/x-ndjson");
+        if let Some(ce) = self.compression.content_encoding() {
+            builder = builder.header("Content-Encoding", ce);
+        }
+        let mut http_request = builder
+            .body(Body::from(request.payload))
+            .expect("Building request never fails.");
+        if let Some(auth) = &self.auth {
+            auth.apply(&mut http_request);
+        }
+
+        let response = self
+            .client
+            .send(http_request)
+            .in_current_span()
+            .await
+            .context(HttpSnafu)?;
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body())
+            .await
+            .context(ReadResponseSnafu)?;
+
+        if !status.is_success() {
+            return Err(QuickwitError::ServerError {
+                status,
+                message: String::from_utf8_lossy(&body).into_owned(),
+            });
+        }
+
+        // The documents were accepted even when the response can't be read.
+        if let Ok(response) = serde_json::from_slice::<IngestResponse>(&body) {
+            if response.num_rejected_docs > 0 {
+                emit!(QuickwitDocsRejected {
+                    count: response.num_rejected_docs,
+                    index: &request.index,
+                });
+            }
+        }
+
+        Ok(QuickwitResponse {
+            protocol,
+            metadata: request.metadata,
+        })
+    }
+}
+
+impl Service<QuickwitRequest> for QuickwitService {
+    type Response = QuickwitResponse;
+    type Error = QuickwitError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: QuickwitRequest) -> Self::Future {
+        let service = self.clone();
+        Box::pin(service.ingest(request))
+    }
+}
+
+/// Gets the URI the documents of the index are ingested at.
+fn ingest_uri(endpoint: &Uri, index: &str, commit: QuickwitCommit) -> Uri {
+    format!(
+        "{}/api/v1/{}/ingest?commit={}",
+        endpoint.to_string().trim_end_matches('/'),
+        utf8_percent_encode(index, PATH_SEGMENT),
+        commit.as_str(),
+    )
+    .parse()
+    .expect("percent-encoded path is a valid URI")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_ingest_uri() {
+        let endpoint: Uri = "http://localhost:7280/".parse().unwrap();
+        assert_eq!(
+            ingest_uri(&endpoint, "app_logs", QuickwitCommit::Auto).to_string(),
+            "http://localhost:7280/api/v1/app_logs/ingest?commit=auto"
+        );
+        assert_eq!(
+            ingest_uri(&endpoint, "logs/other", QuickwitCommit::Force).to_string(),
+            "http://localhost:7280/api/v1/logs%2Fother/ingest?commit=force"
+        );
+    }
+
+    #[test]
+    fn retries_throttled_requests() {
+        let error = |status| QuickwitError::ServerError {
+            status,
+            message: String::new(),
+        };
+
+        assert!(QuickwitRetryLogic.is_retriable_error(&error(StatusCode::TOO_MANY_REQUESTS)));
+        assert!(QuickwitRetryLogic.is_retriable_error(&error(StatusCode::SERVICE_UNAVAILABLE)));
+        assert!(!QuickwitRetryLogic.is_retriable_error(&error(StatusCode::NOT_FOUND)));
+        assert!(!QuickwitRetryLogic.is_retriable_error(&error(StatusCode::PAYLOAD_TOO_LARGE)));
+    }
+}
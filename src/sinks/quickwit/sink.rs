@@ -0,0 +1,155 @@
+use std::num::NonZeroUsize;
+
+use bytes::Bytes;
+use codecs::{encoding::Framer, JsonSerializer, NewlineDelimitedEncoder};
+use futures::{stream::BoxStream, StreamExt};
+use vector_core::{
+    event::{Event, EventFinalizers, Finalizable},
+    partition::Partitioner,
+    sink::StreamSink,
+    stream::BatcherSettings,
+};
+
+use super::{
+    config::QuickwitConfig,
+    service::{QuickwitRequest, QuickwitRetryLogic, QuickwitService},
+};
+use crate::{
+    codecs::{Encoder, Transformer},
+    internal_events::{SinkRequestBuildError, TemplateRenderingError},
+    sinks::util::{
+        metadata::{RequestMetadata, RequestMetadataBuilder},
+        request_builder::EncodeResult,
+        service::Svc,
+        Compression, RequestBuilder, SinkBuilderExt,
+    },
+    template::Template,
+};
+
+/// Partitions the events by the index they are ingested into.
+struct IndexPartitioner(Template);
+
+impl Partitioner for IndexPartitioner {
+    type Item = Event;
+    type Key = Option<String>;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        self.0
+            .render_string(item)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("index"),
+                    drop_event: true,
+                });
+            })
+            .ok()
+    }
+}
+
+struct QuickwitRequestBuilder {
+    compression: Compression,
+    encoder: (Transformer, Encoder<Framer>),
+}
+
+impl RequestBuilder<(String, Vec<Event>)> for QuickwitRequestBuilder {
+    type Metadata = (String, EventFinalizers, RequestMetadataBuilder);
+    type Events = Vec<Event>;
+    type Encoder = (Transformer, Encoder<Framer>);
+    type Payload = Bytes;
+    type Request = QuickwitRequest;
+    type Error = std::io::Error;
+
+    fn compression(&self) -> Compression {
+        self.compression
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        &self.encoder
+    }
+
+    fn split_input(&self, input: (String, Vec<Event>)) -> (Self::Metadata, Self::Events) {
+        let (index, mut events) = input;
+        let metadata_builder = RequestMetadata::builder(&events);
+        let finalizers = events.take_finalizers();
+
+        ((index, finalizers, metadata_builder), events)
+    }
+
+    fn build_request(
+        &self,
+        metadata: Self::Metadata,
+        payload: EncodeResult<Self::Payload>,
+    ) -> Self::Request {
+        let (index, finalizers, metadata_builder) = metadata;
+        let metadata = metadata_builder.build(&payload);
+
+        QuickwitRequest {
+            index,
+            payload: payload.into_payload(),
+            finalizers,
+            metadata,
+        }
+    }
+}
+
+pub struct QuickwitSink {
+    partitioner: IndexPartitioner,
+    request_builder: QuickwitRequestBuilder,
+    batch_settings: BatcherSettings,
+    service: Svc<QuickwitService, QuickwitRetryLogic>,
+}
+
+impl QuickwitSink {
+    pub(super) fn new(
+        config: &QuickwitConfig,
+        batch_settings: BatcherSettings,
+        service: Svc<QuickwitService, QuickwitRetryLogic>,
+    ) -> Self {
+        // The ingest API takes documents as newline delimited JSON objects.
+        let encoder = Encoder::<Framer>::new(
+            NewlineDelimitedEncoder::new().into(),
+            JsonSerializer::new().into(),
+        );
+
+        Self {
+            partitioner: IndexPartitioner(config.index.clone()),
+            request_builder: QuickwitRequestBuilder {
+                compression: config.compression,
+                encoder: (config.encoding.clone(), encoder),
+            },
+            batch_settings,
+            service,
+        }
+    }
+
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let sink = input
+            .batched_partitioned(self.partitioner, self.batch_settings)
+            .filter_map(|(index, batch)| async move {
+                // A `TemplateRenderingError` is already emitted for the events whose index couldn't
+                // be rendered.
+                index.map(move |index| (index, batch))
+            })
+            .request_builder(NonZeroUsize::new(64), self.request_builder)
+            .filter_map(|request| async move {
+                match request {
+                    Err(error) => {
+                        emit!(SinkRequestBuildError { error });
+                        None
+                    }
+                    Ok(req) => Some(req),
+                }
+            })
+            .into_driver(self.service);
+
+        sink.run().await
+    }
+}
+
+#[async_trait::async_trait]
+impl StreamSink<Event> for QuickwitSink {
+    async fn run(mut self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
@@ -0,0 +1,254 @@
+//! Slot routing for Redis Cluster deployments.
+//!
+//! A Redis cluster shards its keys across the nodes by hash slot, and each node only accepts the
+//! commands for the keys of the slots it serves. The slot map is fetched with `CLUSTER SLOTS`, the
+//! commands of a batch are grouped by the node serving the slot of their key, and the map is
+//! fetched again whenever a node redirects a command elsewhere.
+
+use std::{collections::HashMap, sync::Arc};
+
+use redis::{
+    aio::ConnectionManager, ConnectionAddr, ConnectionInfo, ErrorKind, IntoConnectionInfo,
+    RedisError, RedisResult, Value,
+};
+use tokio::sync::RwLock;
+
+/// The number of hash slots of a Redis cluster.
+const SLOT_COUNT: u16 = 16384;
+
+/// A connection to each of the primary nodes of a Redis cluster.
+#[derive(Clone)]
+pub struct ClusterConnection {
+    seed: ConnectionInfo,
+    slots: Arc<RwLock<SlotMap>>,
+}
+
+#[derive(Default)]
+struct SlotMap {
+    // The first slot, last slot, and address of the primary node of each range of slots.
+    ranges: Vec<(u16, u16, String)>,
+    connections: HashMap<String, ConnectionManager>,
+}
+
+impl SlotMap {
+    fn node(&self, slot: u16) -> Option<&str> {
+        self.ranges
+            .iter()
+            .find(|(start, end, _)| (*start..=*end).contains(&slot))
+            .map(|(_, _, address)| address.as_str())
+    }
+}
+
+impl ClusterConnection {
+    /// Connects to the cluster the node at the given URL belongs to.
+    pub async fn open(url: &str) -> RedisResult<Self> {
+        let seed = url.into_connection_info()?;
+        let connection = Self {
+            seed,
+            slots: Arc::new(RwLock::new(SlotMap::default())),
+        };
+        connection.refresh_slots().await?;
+        Ok(connection)
+    }
+
+    /// Gets a connection to any of the nodes of the cluster.
+    pub async fn any_node(&self) -> RedisResult<ConnectionManager> {
+        let slots = self.slots.read().await;
+        match slots.connections.values().next() {
+            Some(connection) => Ok(connection.clone()),
+            None => connect(self.seed.clone()).await,
+        }
+    }
+
+    /// Fetches the slot map of the cluster again, connecting to the nodes not yet connected to.
+    pub async fn refresh_slots(&self) -> RedisResult<()> {
+        let mut connection = self.any_node().await?;
+        let reply: Vec<Value> = redis::cmd("CLUSTER")
+            .arg("SLOTS")
+            .query_async(&mut connection)
+            .await?;
+        let ranges = parse_slots(&reply)?;
+
+        let mut slots = self.slots.write().await;
+        let mut connections = HashMap::new();
+        for (_, _, address) in &ranges {
+            if connections.contains_key(address) {
+                continue;
+            }
+            let connection = match slots.connections.remove(address) {
+                Some(connection) => connection,
+                None => connect(self.node_info(address)?).await?,
+            };
+            connections.insert(address.clone(), connection);
+        }
+        *slots = SlotMap {
+            ranges,
+            connections,
+        };
+        Ok(())
+    }
+
+    /// Gets the connection to the node serving the slot of the given key.
+    pub async fn node_for_key(&self, key: &str) -> RedisResult<(String, ConnectionManager)> {
+        let slot = key_slot(key.as_bytes());
+        let slots = self.slots.read().await;
+        let connection = slots
+            .node(slot)
+            .and_then(|address| Some((address, slots.connections.get(address)?)));
+        match connection {
+            Some((address, connection)) => Ok((address.to_owned(), connection.clone())),
+            None => Err(RedisError::from((
+                ErrorKind::ClusterDown,
+                "No node serves the slot of the key",
+                slot.to_string(),
+            ))),
+        }
+    }
+
+    /// Connection settings for a node, the same as those of the seed node but for the address.
+    fn node_info(&self, address: &str) -> RedisResult<ConnectionInfo> {
+        let (host, port) = address
+            .rsplit_once(':')
+            .and_then(|(host, port)| Some((host.to_owned(), port.parse().ok()?)))
+            .ok_or_else(|| invalid_reply("invalid node address"))?;
+        let addr = match &self.seed.addr {
+            ConnectionAddr::TcpTls { insecure, .. } => ConnectionAddr::TcpTls {
+                host,
+                port,
+                insecure: *insecure,
+            },
+            _ => ConnectionAddr::Tcp(host, port),
+        };
+        Ok(ConnectionInfo {
+            addr,
+            redis: self.seed.redis.clone(),
+        })
+    }
+}
+
+/// Whether the error is a redirection to another node, meaning that the slot map changed.
+pub fn is_redirection(error: &RedisError) -> bool {
+    matches!(error.kind(), ErrorKind::Moved | ErrorKind::Ask)
+}
+
+async fn connect(info: ConnectionInfo) -> RedisResult<ConnectionManager> {
+    redis::Client::open(info)?
+        .get_tokio_connection_manager()
+        .await
+}
+
+/// Parses the reply of `CLUSTER SLOTS`, keeping the primary node of each range of slots.
+fn parse_slots(reply: &[Value]) -> RedisResult<Vec<(u16, u16, String)>> {
+    reply
+        .iter()
+        .map(|range| {
+            let range = match range {
+                Value::Bulk(range) if range.len() >= 3 => range,
+                _ => return Err(invalid_reply("invalid slot range")),
+            };
+            let start: u16 = redis::from_redis_value(&range[0])?;
+            let end: u16 = redis::from_redis_value(&range[1])?;
+            let (host, port): (String, u16) = match &range[2] {
+                Value::Bulk(node) if node.len() >= 2 => (
+                    redis::from_redis_value(&node[0])?,
+                    redis::from_redis_value(&node[1])?,
+                ),
+                _ => return Err(invalid_reply("invalid slot node")),
+            };
+            Ok((start, end, format!("{}:{}", host, port)))
+        })
+        .collect()
+}
+
+fn invalid_reply(description: &'static str) -> RedisError {
+    RedisError::from((ErrorKind::TypeError, description))
+}
+
+/// The hash slot of a key.
+///
+/// Only the part of the key between the first `{` and the following `}` is hashed when it is not
+/// empty, so that related keys can be put in the same slot.
+pub fn key_slot(key: &[u8]) -> u16 {
+    let key = key
+        .iter()
+        .position(|byte| *byte == b'{')
+        .and_then(|open| {
+            let tag = &key[open + 1..];
+            tag.iter()
+                .position(|byte| *byte == b'}')
+                .filter(|close| *close > 0)
+                .map(|close| &tag[..close])
+        })
+        .unwrap_or(key);
+    crc16(key) % SLOT_COUNT
+}
+
+/// The CRC16-CCITT (XMODEM) checksum used by Redis Cluster.
+fn crc16(data: &[u8]) -> u16 {
+    data.iter().fold(0, |crc, byte| {
+        let mut crc = crc ^ (u16::from(*byte) << 8);
+        for _ in 0..8 {
+            crc = if crc & 0x8000 == 0 {
+                crc << 1
+            } else {
+                (crc << 1) ^ 0x1021
+            };
+        }
+        crc
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn computes_key_slots() {
+        // Examples from the Redis Cluster specification.
+        assert_eq!(crc16(b"123456789"), 0x31c3);
+        assert_eq!(key_slot(b"foo"), 12182);
+        assert_eq!(key_slot(b"{user1000}.following"), key_slot(b"user1000"));
+        assert_eq!(key_slot(b"foo{}{bar}"), crc16(b"foo{}{bar}") % SLOT_COUNT);
+        assert_eq!(key_slot(b"foo{{bar}}zap"), key_slot(b"{bar"));
+        assert_eq!(key_slot(b"foo{bar}{zap}"), key_slot(b"bar"));
+    }
+
+    #[test]
+    fn parses_cluster_slots() {
+        let node = |host: &str, port: i64| {
+            Value::Bulk(vec![
+                Value::Data(host.as_bytes().to_vec()),
+                Value::Int(port),
+                Value::Data(b"id".to_vec()),
+            ])
+        };
+        let reply = vec![
+            Value::Bulk(vec![
+                Value::Int(0),
+                Value::Int(8191),
+                node("10.0.0.1", 6379),
+                node("10.0.0.2", 6379),
+            ]),
+            Value::Bulk(vec![
+                Value::Int(8192),
+                Value::Int(16383),
+                node("10.0.0.3", 6380),
+            ]),
+        ];
+
+        let ranges = parse_slots(&reply).unwrap();
+        assert_eq!(
+            ranges,
+            vec![
+                (0, 8191, "10.0.0.1:6379".to_owned()),
+                (8192, 16383, "10.0.0.3:6380".to_owned()),
+            ]
+        );
+
+        let slots = SlotMap {
+            ranges,
+            connections: HashMap::new(),
+        };
+        assert_eq!(slots.node(key_slot(b"foo")), Some("10.0.0.3:6380"));
+    }
+}
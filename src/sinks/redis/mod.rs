@@ -1,8 +1,11 @@
-use std::task::{Context, Poll};
+use std::{
+    collections::HashMap,
+    task::{Context, Poll},
+};
 
 use bytes::{Bytes, BytesMut};
 use futures::{future::BoxFuture, stream, FutureExt, SinkExt, StreamExt};
-use redis::{aio::ConnectionManager, RedisError, RedisResult};
+use redis::{aio::ConnectionManager, streams::StreamMaxlen, RedisError, RedisResult};
 use snafu::{ResultExt, Snafu};
 use tokio_util::codec::Encoder as _;
 use tower::{Service, ServiceBuilder};
@@ -27,6 +30,10 @@ use crate::{
     template::{Template, TemplateParseError},
 };
 
+mod cluster;
+
+use self::cluster::ClusterConnection;
+
 #[derive(Debug, Snafu)]
 enum RedisSinkError {
     #[snafu(display("Creating Redis producer failed: {}", source))]
@@ -53,6 +60,11 @@ pub enum DataTypeConfig {
     ///
     /// Redis channels function in a pub/sub fashion, allowing many-to-many broadcasting and receiving.
     Channel,
+
+    /// The Redis `stream` type.
+    ///
+    /// Messages are appended to the stream with `XADD`, and can be read by consumer groups.
+    Stream,
 }
 
 /// List-specific options.
@@ -64,7 +76,37 @@ pub struct ListOption {
     method: Method,
 }
 
-#[derive(Clone, Copy, Debug, Derivative)]
+/// Stream-specific options.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct StreamOption {
+    /// The field of the stream entries to store messages in.
+    #[serde(default = "default_stream_field")]
+    #[derivative(Default(value = "default_stream_field()"))]
+    field: String,
+
+    /// The maximum length of the stream.
+    ///
+    /// The oldest entries are trimmed off the stream as new ones are added. If not set, the stream
+    /// is not trimmed.
+    maxlen: Option<usize>,
+
+    /// Whether or not to trim the stream approximately.
+    ///
+    /// Approximate trimming only removes whole nodes of the stream, which is much more efficient,
+    /// and leaves the stream at least `maxlen` entries long.
+    #[serde(default = "crate::serde::default_true")]
+    #[derivative(Default(value = "true"))]
+    approximate_maxlen: bool,
+}
+
+fn default_stream_field() -> String {
+    "message".to_owned()
+}
+
+#[derive(Clone, Debug, Derivative)]
 #[derivative(Default)]
 pub enum DataType {
     /// The Redis `list` type.
@@ -77,6 +119,9 @@ pub enum DataType {
     ///
     /// Redis channels function in a pub/sub fashion, allowing many-to-many broadcasting and receiving.
     Channel,
+
+    /// The Redis `stream` type.
+    Stream(StreamOption),
 }
 
 /// Method for pushing messages into a `list`.
@@ -124,12 +169,22 @@ pub struct RedisSinkConfig {
     #[serde(alias = "list")]
     list_option: Option<ListOption>,
 
+    #[configurable(derived)]
+    stream: Option<StreamOption>,
+
     /// The Redis URL to connect to.
     ///
     /// The URL _must_ take the form of `protocol://server:port/db` where the protocol can either be
     /// `redis` or `rediss` for connections secured via TLS.
     url: String,
 
+    /// Whether or not the Redis deployment is a Redis Cluster.
+    ///
+    /// When enabled, `url` is the address of any node of the cluster, and each message is sent to
+    /// the node serving the hash slot of its key.
+    #[serde(default)]
+    cluster: bool,
+
     /// The Redis key to publish messages to.
     #[configurable(metadata(templateable))]
     #[configurable(validation(length(min = 1)))]
@@ -177,7 +232,10 @@ impl SinkConfig for RedisSinkConfig {
         if self.key.is_empty() {
             return Err("`key` cannot be empty.".into());
         }
-        let conn = self.build_client().await.context(RedisCreateFailedSnafu)?;
+        let conn = self
+            .build_connection()
+            .await
+            .context(RedisCreateFailedSnafu)?;
         let healthcheck = RedisSinkConfig::healthcheck(conn.clone()).boxed();
         let sink = self.new(conn)?;
         Ok((sink, healthcheck))
@@ -193,7 +251,7 @@ impl SinkConfig for RedisSinkConfig {
 }
 
 impl RedisSinkConfig {
-    pub fn new(&self, conn: ConnectionManager) -> crate::Result<super::VectorSink> {
+    pub fn new(&self, conn: RedisConnection) -> crate::Result<super::VectorSink> {
         let request = self.request.unwrap_with(&TowerRequestConfig {
             concurrency: Concurrency::Fixed(1),
             ..Default::default()
@@ -210,6 +268,7 @@ impl RedisSinkConfig {
         let data_type = match self.data_type {
             DataTypeConfig::Channel => DataType::Channel,
             DataTypeConfig::List => DataType::List(method.unwrap_or_default()),
+            DataTypeConfig::Stream => DataType::Stream(self.stream.clone().unwrap_or_default()),
         };
 
         let batch = self.batch.into_batch_settings()?;
@@ -246,7 +305,22 @@ impl RedisSinkConfig {
         conn
     }
 
-    async fn healthcheck(mut conn: ConnectionManager) -> crate::Result<()> {
+    async fn build_connection(&self) -> RedisResult<RedisConnection> {
+        if self.cluster {
+            trace!("Open Redis cluster connection.");
+            ClusterConnection::open(&self.url)
+                .await
+                .map(RedisConnection::Cluster)
+        } else {
+            self.build_client().await.map(RedisConnection::Single)
+        }
+    }
+
+    async fn healthcheck(conn: RedisConnection) -> crate::Result<()> {
+        let mut conn = match conn {
+            RedisConnection::Single(conn) => conn,
+            RedisConnection::Cluster(cluster) => cluster.any_node().await?,
+        };
         redis::cmd("PING")
             .query_async(&mut conn)
             .await
@@ -254,6 +328,19 @@ impl RedisSinkConfig {
     }
 }
 
+/// A connection to a single Redis server, or to a Redis cluster.
+#[derive(Clone)]
+pub enum RedisConnection {
+    Single(ConnectionManager),
+    Cluster(ClusterConnection),
+}
+
+impl From<ConnectionManager> for RedisConnection {
+    fn from(conn: ConnectionManager) -> Self {
+        Self::Single(conn)
+    }
+}
+
 #[derive(Debug, Clone)]
 struct RedisKvEntry {
     key: String,
@@ -332,7 +419,7 @@ impl RetryLogic for RedisRetryLogic {
 
 #[derive(Clone)]
 pub struct RedisSink {
-    conn: ConnectionManager,
+    conn: RedisConnection,
     data_type: DataType,
     bytes_sent: Registered<BytesSent>,
 }
@@ -349,44 +436,23 @@ impl Service<Vec<RedisKvEntry>> for RedisSink {
 
     // Emission of Error internal event is handled upstream by the caller
     fn call(&mut self, kvs: Vec<RedisKvEntry>) -> Self::Future {
-        let count = kvs.len();
-        let mut byte_size = 0;
-
-        let mut conn = self.conn.clone();
-        let mut pipe = redis::pipe();
-
-        for kv in kvs {
-            byte_size += kv.encoded_length();
-            match self.data_type {
-                DataType::List(method) => match method {
-                    Method::LPush => {
-                        if count > 1 {
-                            pipe.atomic().lpush(kv.key, kv.value.as_ref());
-                        } else {
-                            pipe.lpush(kv.key, kv.value.as_ref());
-                        }
-                    }
-                    Method::RPush => {
-                        if count > 1 {
-                            pipe.atomic().rpush(kv.key, kv.value.as_ref());
-                        } else {
-                            pipe.rpush(kv.key, kv.value.as_ref());
-                        }
-                    }
-                },
-                DataType::Channel => {
-                    if count > 1 {
-                        pipe.atomic().publish(kv.key, kv.value.as_ref());
-                    } else {
-                        pipe.publish(kv.key, kv.value.as_ref());
-                    }
-                }
-            }
-        }
+        let byte_size: usize = kvs.iter().map(EncodedLength::encoded_length).sum();
 
+        let conn = self.conn.clone();
+        let data_type = self.data_type.clone();
         let bytes_sent = self.bytes_sent.clone();
         Box::pin(async move {
-            let result: RedisPipeResult = pipe.query_async(&mut conn).await;
+            let result: RedisPipeResult = match conn {
+                RedisConnection::Single(mut conn) => {
+                    let atomic = kvs.len() > 1;
+                    build_pipe(&data_type, &kvs, atomic)
+                        .query_async(&mut conn)
+                        .await
+                }
+                RedisConnection::Cluster(cluster) => {
+                    send_to_cluster(&cluster, &data_type, kvs).await
+                }
+            };
             if let Ok(res) = &result {
                 if res.is_successful() {
                     bytes_sent.emit(ByteSize(byte_size));
@@ -399,6 +465,86 @@ impl Service<Vec<RedisKvEntry>> for RedisSink {
     }
 }
 
+fn build_pipe(data_type: &DataType, kvs: &[RedisKvEntry], atomic: bool) -> redis::Pipeline {
+    let mut pipe = redis::pipe();
+    if atomic {
+        pipe.atomic();
+    }
+
+    for kv in kvs {
+        match data_type {
+            DataType::List(Method::LPush) => {
+                pipe.lpush(&kv.key, kv.value.as_ref());
+            }
+            DataType::List(Method::RPush) => {
+                pipe.rpush(&kv.key, kv.value.as_ref());
+            }
+            DataType::Channel => {
+                pipe.publish(&kv.key, kv.value.as_ref());
+            }
+            DataType::Stream(option) => {
+                let items = [(option.field.as_str(), kv.value.as_ref())];
+                match option.maxlen {
+                    Some(maxlen) => {
+                        let maxlen = if option.approximate_maxlen {
+                            StreamMaxlen::Approx(maxlen)
+                        } else {
+                            StreamMaxlen::Equals(maxlen)
+                        };
+                        pipe.xadd_maxlen(&kv.key, maxlen, "*", &items)
+                    }
+                    None => pipe.xadd(&kv.key, "*", &items),
+                }
+                // The reply is the ID of the new entry, which is not a success flag, and failures
+                // are reported as errors anyway.
+                .ignore();
+            }
+        }
+    }
+    pipe
+}
+
+/// Sends each message to the node of the cluster serving the slot of its key.
+///
+/// Cluster nodes only accept transactions whose keys are all in the same slot, so the messages
+/// sent to a node are pipelined without a transaction.
+async fn send_to_cluster(
+    cluster: &ClusterConnection,
+    data_type: &DataType,
+    kvs: Vec<RedisKvEntry>,
+) -> RedisPipeResult {
+    let mut nodes: HashMap<String, (ConnectionManager, Vec<RedisKvEntry>)> = HashMap::new();
+    for kv in kvs {
+        let (address, conn) = cluster.node_for_key(&kv.key).await?;
+        nodes
+            .entry(address)
+            .or_insert_with(|| (conn, Vec::new()))
+            .1
+            .push(kv);
+    }
+
+    let result =
+        futures::future::try_join_all(nodes.into_values().map(|(mut conn, kvs)| async move {
+            build_pipe(data_type, &kvs, false)
+                .query_async::<_, Vec<bool>>(&mut conn)
+                .await
+        }))
+        .await;
+
+    match result {
+        Ok(responses) => Ok(responses.into_iter().flatten().collect()),
+        Err(error) => {
+            // The slots moved to other nodes, so the batch is sent to the right ones when retried.
+            if cluster::is_redirection(&error) {
+                if let Err(error) = cluster.refresh_slots().await {
+                    warn!(message = "Failed to refresh the slots of the Redis cluster.", %error);
+                }
+            }
+            Err(error)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::{collections::HashMap, convert::TryFrom};
@@ -467,6 +613,36 @@ mod tests {
         let map: HashMap<String, String> = serde_json::from_slice(&result[..]).unwrap();
         assert!(!map.contains_key("key"));
     }
+
+    #[test]
+    fn redis_stream_pipe() {
+        let kvs = vec![RedisKvEntry {
+            key: "key".to_owned(),
+            value: Bytes::from("hello_world"),
+        }];
+        let data_type = DataType::Stream(StreamOption {
+            maxlen: Some(1000),
+            ..Default::default()
+        });
+
+        let packed = build_pipe(&data_type, &kvs, false).get_packed_pipeline();
+        let packed = String::from_utf8(packed).unwrap();
+        // Each argument is preceded by its length, after the number of arguments.
+        let args = packed.split("\r\n").skip(2).step_by(2).collect::<Vec<_>>();
+        assert_eq!(
+            args,
+            [
+                "XADD",
+                "key",
+                "MAXLEN",
+                "~",
+                "1000",
+                "*",
+                "message",
+                "hello_world"
+            ]
+        );
+    }
 }
 
 #[cfg(feature = "redis-integration-tests")]
@@ -507,6 +683,8 @@ mod integration_tests {
             list_option: Some(ListOption {
                 method: Method::LPush,
             }),
+            stream: None,
+            cluster: false,
             batch: BatchConfig::default(),
             request: TowerRequestConfig {
                 rate_limit_num: Option::from(u64::MAX),
@@ -527,7 +705,7 @@ mod integration_tests {
         let cnf2 = cnf.clone();
         assert_sink_compliance(&SINK_TAGS, async move {
             let conn = cnf2.build_client().await.unwrap();
-            cnf2.new(conn).unwrap().run(input).await
+            cnf2.new(conn.into()).unwrap().run(input).await
         })
         .await
         .expect("Running sink failed");
@@ -570,6 +748,8 @@ mod integration_tests {
             list_option: Some(ListOption {
                 method: Method::RPush,
             }),
+            stream: None,
+            cluster: false,
             batch: BatchConfig::default(),
             request: TowerRequestConfig {
                 rate_limit_num: Option::from(u64::MAX),
@@ -590,7 +770,7 @@ mod integration_tests {
         let cnf2 = cnf.clone();
         assert_sink_compliance(&SINK_TAGS, async move {
             let conn = cnf2.build_client().await.unwrap();
-            cnf2.new(conn).unwrap().run(input).await
+            cnf2.new(conn.into()).unwrap().run(input).await
         })
         .await
         .expect("Running sink failed");
@@ -614,6 +794,63 @@ mod integration_tests {
         }
     }
 
+    #[tokio::test]
+    async fn redis_sink_stream() {
+        trace_init();
+
+        let key = Template::try_from(format!("test-{}", random_string(10)))
+            .expect("should not fail to create key template");
+        debug!("Test key name: {}.", key);
+        let num_events = 1000;
+
+        let cnf = RedisSinkConfig {
+            url: redis_server(),
+            key: key.clone(),
+            encoding: JsonSerializerConfig::new().into(),
+            data_type: DataTypeConfig::Stream,
+            list_option: None,
+            stream: Some(StreamOption {
+                maxlen: Some(100),
+                approximate_maxlen: false,
+                ..Default::default()
+            }),
+            cluster: false,
+            batch: BatchConfig::default(),
+            request: TowerRequestConfig {
+                rate_limit_num: Option::from(u64::MAX),
+                ..Default::default()
+            },
+            acknowledgements: Default::default(),
+        };
+
+        let events = (0..num_events)
+            .map(|i| Event::from(LogEvent::from(i.to_string())))
+            .collect::<Vec<_>>();
+        let input = stream::iter(events.clone().into_iter().map(Into::into));
+
+        // Publish events.
+        let cnf2 = cnf.clone();
+        assert_sink_compliance(&SINK_TAGS, async move {
+            let conn = cnf2.build_client().await.unwrap();
+            cnf2.new(conn.into()).unwrap().run(input).await
+        })
+        .await
+        .expect("Running sink failed");
+
+        let mut conn = cnf.build_client().await.unwrap();
+
+        // The stream is trimmed down to the last `maxlen` events.
+        let len: usize = conn.xlen(key.to_string()).await.unwrap();
+        assert_eq!(len, 100);
+
+        let reply: redis::streams::StreamRangeReply =
+            conn.xrange_all(key.to_string()).await.unwrap();
+        for (entry, event) in reply.ids.iter().zip(&events[num_events - 100..]) {
+            let expected = serde_json::to_string(event.as_log()).unwrap();
+            assert_eq!(entry.get::<String>("message"), Some(expected));
+        }
+    }
+
     #[tokio::test]
     async fn redis_sink_channel() {
         trace_init();
@@ -647,6 +884,8 @@ mod integration_tests {
             encoding: JsonSerializerConfig::new().into(),
             data_type: DataTypeConfig::Channel,
             list_option: None,
+            stream: None,
+            cluster: false,
             batch: BatchConfig::default(),
             request: TowerRequestConfig {
                 rate_limit_num: Option::from(u64::MAX),
@@ -658,7 +897,7 @@ mod integration_tests {
         // Publish events.
         assert_sink_compliance(&SINK_TAGS, async move {
             let conn = cnf.build_client().await.unwrap();
-            let sink = cnf.new(conn).unwrap();
+            let sink = cnf.new(conn.into()).unwrap();
             let (_input, events) = random_lines_with_stream(100, num_events, None);
             sink.run(events).await
         })
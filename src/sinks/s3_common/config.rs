@@ -1,8 +1,7 @@
 use std::collections::BTreeMap;
 
 use aws_sdk_s3::{
-    error::PutObjectError,
-    model::{ObjectCannedAcl, ServerSideEncryption, StorageClass},
+    model::{ChecksumAlgorithm, ObjectCannedAcl, ServerSideEncryption, StorageClass},
     Client as S3Client,
 };
 use aws_smithy_client::SdkError;
@@ -11,7 +10,7 @@ use http::StatusCode;
 use snafu::Snafu;
 use vector_config::configurable_component;
 
-use super::service::{S3Response, S3Service};
+use super::service::{S3Error, S3Response, S3Service};
 use crate::{
     aws::{create_client, is_retriable_error, AwsAuthentication, RegionOrEndpoint},
     common::s3::S3ClientBuilder,
@@ -94,6 +93,112 @@ pub struct S3Options {
     ///
     /// By default, `text/x-log` is used.
     pub content_type: Option<String>,
+
+    /// The algorithm used to compute the checksum of the created objects.
+    ///
+    /// When set, the checksum of each object, or of each part of a multipart upload, is sent along with it, and S3
+    /// rejects the object if the checksum of the data it received doesn't match.
+    pub checksum_algorithm: Option<S3ChecksumAlgorithm>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub multipart_upload: S3MultipartUploadConfig,
+}
+
+/// The smallest part size allowed by S3 for all but the last part of a multipart upload.
+pub const MIN_PART_SIZE: usize = 5 * 1024 * 1024;
+
+/// The largest part size allowed by S3.
+pub const MAX_PART_SIZE: usize = 5 * 1024 * 1024 * 1024;
+
+/// The largest number of parts allowed by S3 in a multipart upload.
+pub const MAX_PARTS: usize = 10_000;
+
+const fn default_part_size() -> usize {
+    8 * 1024 * 1024
+}
+
+const fn default_part_concurrency() -> usize {
+    4
+}
+
+/// Multipart upload configuration.
+///
+/// Objects larger than `part_size` are uploaded in parts with a [multipart upload][multipart_upload], instead of with a
+/// single `PutObject` request, so that objects larger than the 5 GiB limit of `PutObject` can be created and so that
+/// the parts of an object are uploaded concurrently.
+///
+/// [multipart_upload]: https://docs.aws.amazon.com/AmazonS3/latest/userguide/mpuoverview.html
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct S3MultipartUploadConfig {
+    /// Whether or not to upload large objects with multipart uploads.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// The size, in bytes, of the parts of a multipart upload.
+    ///
+    /// Objects no larger than this are uploaded with a single `PutObject` request. Must be between 5 MiB and 5 GiB. The
+    /// part size is raised if an object would otherwise be split into more than 10,000 parts.
+    #[serde(default = "default_part_size")]
+    #[derivative(Default(value = "default_part_size()"))]
+    pub part_size: usize,
+
+    /// The maximum number of parts of an object uploaded concurrently.
+    #[serde(default = "default_part_concurrency")]
+    #[derivative(Default(value = "default_part_concurrency()"))]
+    pub concurrency: usize,
+}
+
+impl S3MultipartUploadConfig {
+    pub fn validate(&self) -> crate::Result<()> {
+        if !(MIN_PART_SIZE..=MAX_PART_SIZE).contains(&self.part_size) {
+            return Err(format!(
+                "The `part_size` of multipart uploads must be between {} and {} bytes.",
+                MIN_PART_SIZE, MAX_PART_SIZE
+            )
+            .into());
+        }
+        if self.concurrency == 0 {
+            return Err("The `concurrency` of multipart uploads must be at least 1.".into());
+        }
+        Ok(())
+    }
+}
+
+/// S3 checksum algorithms.
+///
+/// More information on each algorithm can be found in the [AWS documentation][aws_docs].
+///
+/// [aws_docs]: https://docs.aws.amazon.com/AmazonS3/latest/userguide/checking-object-integrity.html
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum S3ChecksumAlgorithm {
+    /// CRC32.
+    Crc32,
+
+    /// CRC32C.
+    Crc32c,
+
+    /// SHA-1.
+    Sha1,
+
+    /// SHA-256.
+    Sha256,
+}
+
+impl From<S3ChecksumAlgorithm> for ChecksumAlgorithm {
+    fn from(x: S3ChecksumAlgorithm) -> Self {
+        match x {
+            S3ChecksumAlgorithm::Crc32 => Self::Crc32,
+            S3ChecksumAlgorithm::Crc32c => Self::Crc32C,
+            S3ChecksumAlgorithm::Sha1 => Self::Sha1,
+            S3ChecksumAlgorithm::Sha256 => Self::Sha256,
+        }
+    }
 }
 
 /// S3 storage classes.
@@ -269,11 +374,16 @@ impl From<S3CannedAcl> for ObjectCannedAcl {
 pub struct S3RetryLogic;
 
 impl RetryLogic for S3RetryLogic {
-    type Error = SdkError<PutObjectError>;
+    type Error = S3Error;
     type Response = S3Response;
 
     fn is_retriable_error(&self, error: &Self::Error) -> bool {
-        is_retriable_error(error)
+        match error {
+            S3Error::PutObject { source } => is_retriable_error(source),
+            S3Error::CreateMultipartUpload { source } => is_retriable_error(source),
+            S3Error::UploadPart { source } => is_retriable_error(source),
+            S3Error::CompleteMultipartUpload { source } => is_retriable_error(source),
+        }
     }
 }
 
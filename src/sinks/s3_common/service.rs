@@ -1,13 +1,18 @@
 use std::task::{Context, Poll};
 
 use aws_sdk_s3::{
-    error::PutObjectError,
+    client::fluent_builders::CreateMultipartUpload,
+    error::{
+        CompleteMultipartUploadError, CreateMultipartUploadError, PutObjectError, UploadPartError,
+    },
+    model::{ChecksumAlgorithm, CompletedMultipartUpload, CompletedPart},
     types::{ByteStream, SdkError},
     Client as S3Client,
 };
 use bytes::Bytes;
-use futures::future::BoxFuture;
+use futures::{future::BoxFuture, stream, FutureExt, StreamExt, TryStreamExt};
 use md5::Digest;
+use snafu::{ResultExt, Snafu};
 use tower::Service;
 use tracing::Instrument;
 use vector_core::{
@@ -16,7 +21,7 @@ use vector_core::{
     stream::DriverResponse,
 };
 
-use super::config::S3Options;
+use super::config::{S3MultipartUploadConfig, S3Options, MAX_PARTS};
 
 #[derive(Debug, Clone)]
 pub struct S3Request {
@@ -57,6 +62,22 @@ impl DriverResponse for S3Response {
     }
 }
 
+#[derive(Debug, Snafu)]
+pub enum S3Error {
+    #[snafu(display("Failed to put object: {}", source))]
+    PutObject { source: SdkError<PutObjectError> },
+    #[snafu(display("Failed to create multipart upload: {}", source))]
+    CreateMultipartUpload {
+        source: SdkError<CreateMultipartUploadError>,
+    },
+    #[snafu(display("Failed to upload part: {}", source))]
+    UploadPart { source: SdkError<UploadPartError> },
+    #[snafu(display("Failed to complete multipart upload: {}", source))]
+    CompleteMultipartUpload {
+        source: SdkError<CompleteMultipartUploadError>,
+    },
+}
+
 /// Wrapper for the AWS SDK S3 client.
 ///
 /// Provides a `tower::Service`-compatible wrapper around the native
@@ -80,7 +101,7 @@ impl S3Service {
 
 impl Service<S3Request> for S3Service {
     type Response = S3Response;
-    type Error = SdkError<PutObjectError>;
+    type Error = S3Error;
     type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
 
     fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
@@ -102,8 +123,6 @@ impl Service<S3Request> for S3Service {
             .content_type
             .or_else(|| Some("text/x-log".to_owned()));
 
-        let content_md5 = base64::encode(md5::Md5::digest(&request.body));
-
         let tagging = options.tags.map(|tags| {
             let mut tagging = url::form_urlencoded::Serializer::new(String::new());
             for (p, v) in tags {
@@ -111,31 +130,74 @@ impl Service<S3Request> for S3Service {
             }
             tagging.finish()
         });
+        let checksum_algorithm = options.checksum_algorithm.map(ChecksumAlgorithm::from);
+        let multipart_upload = options.multipart_upload;
         let count = request.metadata.count;
         let events_byte_size = request.metadata.byte_size;
 
         let client = self.client.clone();
+        let body = request.body;
+        let bucket = request.bucket;
+        let key = request.metadata.partition_key;
 
         Box::pin(async move {
-            let request = client
-                .put_object()
-                .body(bytes_to_bytestream(request.body))
-                .bucket(request.bucket)
-                .key(request.metadata.partition_key)
-                .set_content_encoding(content_encoding)
-                .set_content_type(content_type)
-                .set_acl(options.acl.map(Into::into))
-                .set_grant_full_control(options.grant_full_control)
-                .set_grant_read(options.grant_read)
-                .set_grant_read_acp(options.grant_read_acp)
-                .set_grant_write_acp(options.grant_write_acp)
-                .set_server_side_encryption(options.server_side_encryption.map(Into::into))
-                .set_ssekms_key_id(options.ssekms_key_id)
-                .set_storage_class(options.storage_class.map(Into::into))
-                .set_tagging(tagging)
-                .content_md5(content_md5);
-
-            let result = request.send().in_current_span().await;
+            let result = if multipart_upload.enabled && body.len() > multipart_upload.part_size {
+                let create = client
+                    .create_multipart_upload()
+                    .bucket(&bucket)
+                    .key(&key)
+                    .set_content_encoding(content_encoding)
+                    .set_content_type(content_type)
+                    .set_acl(options.acl.map(Into::into))
+                    .set_grant_full_control(options.grant_full_control)
+                    .set_grant_read(options.grant_read)
+                    .set_grant_read_acp(options.grant_read_acp)
+                    .set_grant_write_acp(options.grant_write_acp)
+                    .set_server_side_encryption(options.server_side_encryption.map(Into::into))
+                    .set_ssekms_key_id(options.ssekms_key_id)
+                    .set_storage_class(options.storage_class.map(Into::into))
+                    .set_tagging(tagging)
+                    .set_checksum_algorithm(checksum_algorithm.clone());
+
+                upload_multipart(
+                    &client,
+                    create,
+                    &bucket,
+                    &key,
+                    body,
+                    multipart_upload,
+                    checksum_algorithm,
+                )
+                .in_current_span()
+                .await
+            } else {
+                let content_md5 = base64::encode(md5::Md5::digest(&body));
+                let request = client
+                    .put_object()
+                    .body(bytes_to_bytestream(body))
+                    .bucket(bucket)
+                    .key(key)
+                    .set_content_encoding(content_encoding)
+                    .set_content_type(content_type)
+                    .set_acl(options.acl.map(Into::into))
+                    .set_grant_full_control(options.grant_full_control)
+                    .set_grant_read(options.grant_read)
+                    .set_grant_read_acp(options.grant_read_acp)
+                    .set_grant_write_acp(options.grant_write_acp)
+                    .set_server_side_encryption(options.server_side_encryption.map(Into::into))
+                    .set_ssekms_key_id(options.ssekms_key_id)
+                    .set_storage_class(options.storage_class.map(Into::into))
+                    .set_tagging(tagging)
+                    .set_checksum_algorithm(checksum_algorithm)
+                    .content_md5(content_md5);
+
+                request
+                    .send()
+                    .in_current_span()
+                    .await
+                    .map(|_| ())
+                    .context(PutObjectSnafu)
+            };
 
             result.map(|_| S3Response {
                 count,
@@ -145,6 +207,149 @@ impl Service<S3Request> for S3Service {
     }
 }
 
+/// Uploads the body as an object in parts, aborting the multipart upload if any part fails.
+async fn upload_multipart(
+    client: &S3Client,
+    create: CreateMultipartUpload,
+    bucket: &str,
+    key: &str,
+    body: Bytes,
+    config: S3MultipartUploadConfig,
+    checksum_algorithm: Option<ChecksumAlgorithm>,
+) -> Result<(), S3Error> {
+    let upload_id = create
+        .send()
+        .await
+        .context(CreateMultipartUploadSnafu)?
+        .upload_id
+        .unwrap_or_default();
+
+    let result = upload_parts(
+        client,
+        bucket,
+        key,
+        &upload_id,
+        body,
+        config,
+        checksum_algorithm,
+    )
+    .await;
+
+    if result.is_err() {
+        // The parts already uploaded are billed until the upload is aborted, and a retry of the
+        // request creates a new upload, so the failed upload is aborted right away rather than
+        // left to the lifecycle rules of the bucket.
+        if let Err(error) = client
+            .abort_multipart_upload()
+            .bucket(bucket)
+            .key(key)
+            .upload_id(&upload_id)
+            .send()
+            .await
+        {
+            warn!(message = "Failed to abort multipart upload.", %error, %upload_id);
+        }
+    }
+    result
+}
+
+async fn upload_parts(
+    client: &S3Client,
+    bucket: &str,
+    key: &str,
+    upload_id: &str,
+    body: Bytes,
+    config: S3MultipartUploadConfig,
+    checksum_algorithm: Option<ChecksumAlgorithm>,
+) -> Result<(), S3Error> {
+    let mut parts = stream::iter(split_parts(&body, config.part_size).into_iter().enumerate())
+        .map(|(index, part)| {
+            let part_number = index as i32 + 1;
+            let content_md5 = base64::encode(md5::Md5::digest(&part));
+            client
+                .upload_part()
+                .bucket(bucket)
+                .key(key)
+                .upload_id(upload_id)
+                .part_number(part_number)
+                .set_checksum_algorithm(checksum_algorithm.clone())
+                .content_md5(content_md5)
+                .body(bytes_to_bytestream(part))
+                .send()
+                .map(move |result| {
+                    // The checksum of each part is repeated when completing the upload, so that S3
+                    // can compute the checksum of the whole object.
+                    result.map(|output| {
+                        CompletedPart::builder()
+                            .part_number(part_number)
+                            .set_e_tag(output.e_tag)
+                            .set_checksum_crc32(output.checksum_crc32)
+                            .set_checksum_crc32_c(output.checksum_crc32_c)
+                            .set_checksum_sha1(output.checksum_sha1)
+                            .set_checksum_sha256(output.checksum_sha256)
+                            .build()
+                    })
+                })
+        })
+        .buffer_unordered(config.concurrency)
+        .try_collect::<Vec<_>>()
+        .in_current_span()
+        .await
+        .context(UploadPartSnafu)?;
+    parts.sort_by_key(|part| part.part_number);
+
+    client
+        .complete_multipart_upload()
+        .bucket(bucket)
+        .key(key)
+        .upload_id(upload_id)
+        .multipart_upload(
+            CompletedMultipartUpload::builder()
+                .set_parts(Some(parts))
+                .build(),
+        )
+        .send()
+        .in_current_span()
+        .await
+        .context(CompleteMultipartUploadSnafu)?;
+    Ok(())
+}
+
+/// Splits the body into parts of the given size, raising it if there would be too many parts.
+fn split_parts(body: &Bytes, part_size: usize) -> Vec<Bytes> {
+    let part_size = part_size.max((body.len() + MAX_PARTS - 1) / MAX_PARTS);
+    (0..body.len())
+        .step_by(part_size)
+        .map(|start| body.slice(start..body.len().min(start + part_size)))
+        .collect()
+}
+
 fn bytes_to_bytestream(buf: Bytes) -> ByteStream {
     ByteStream::from(buf)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_body_into_parts() {
+        let body = Bytes::from(vec![0u8; 25]);
+
+        let parts = split_parts(&body, 10);
+        assert_eq!(
+            parts.iter().map(Bytes::len).collect::<Vec<_>>(),
+            vec![10, 10, 5]
+        );
+        assert_eq!(parts.concat(), body);
+    }
+
+    #[test]
+    fn raises_part_size_above_max_parts() {
+        let body = Bytes::from(vec![0u8; MAX_PARTS * 4]);
+
+        let parts = split_parts(&body, 1);
+        assert_eq!(parts.len(), MAX_PARTS);
+        assert!(parts.iter().all(|part| part.len() == 4));
+    }
+}
@@ -6,6 +6,7 @@ use futures_util::StreamExt;
 use tower::Service;
 use vector_core::{
     event::Finalizable,
+    partition::Partitioner,
     sink::StreamSink,
     stream::{BatcherSettings, DriverResponse},
 };
@@ -13,21 +14,21 @@ use vector_core::{
 use crate::internal_events::SinkRequestBuildError;
 use crate::{
     event::Event,
-    sinks::util::{partitioner::KeyPartitioner, RequestBuilder, SinkBuilderExt},
+    sinks::util::{RequestBuilder, SinkBuilderExt},
 };
 
-pub struct S3Sink<Svc, RB> {
+pub struct S3Sink<Svc, RB, P> {
     service: Svc,
     request_builder: RB,
-    partitioner: KeyPartitioner,
+    partitioner: P,
     batcher_settings: BatcherSettings,
 }
 
-impl<Svc, RB> S3Sink<Svc, RB> {
+impl<Svc, RB, P> S3Sink<Svc, RB, P> {
     pub const fn new(
         service: Svc,
         request_builder: RB,
-        partitioner: KeyPartitioner,
+        partitioner: P,
         batcher_settings: BatcherSettings,
     ) -> Self {
         Self {
@@ -39,7 +40,7 @@ impl<Svc, RB> S3Sink<Svc, RB> {
     }
 }
 
-impl<Svc, RB> S3Sink<Svc, RB>
+impl<Svc, RB, P> S3Sink<Svc, RB, P>
 where
     Svc: Service<RB::Request> + Send + 'static,
     Svc::Future: Send + 'static,
@@ -48,6 +49,7 @@ where
     RB: RequestBuilder<(String, Vec<Event>)> + Send + Sync + 'static,
     RB::Error: fmt::Display + Send,
     RB::Request: Finalizable + Send,
+    P: Partitioner<Item = Event, Key = Option<String>> + Unpin + Send,
 {
     async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
         let partitioner = self.partitioner;
@@ -76,7 +78,7 @@ where
 }
 
 #[async_trait]
-impl<Svc, RB> StreamSink<Event> for S3Sink<Svc, RB>
+impl<Svc, RB, P> StreamSink<Event> for S3Sink<Svc, RB, P>
 where
     Svc: Service<RB::Request> + Send + 'static,
     Svc::Future: Send + 'static,
@@ -85,6 +87,7 @@ where
     RB: RequestBuilder<(String, Vec<Event>)> + Send + Sync + 'static,
     RB::Error: fmt::Display + Send,
     RB::Request: Finalizable + Send,
+    P: Partitioner<Item = Event, Key = Option<String>> + Unpin + Send,
 {
     async fn run(mut self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
         self.run_inner(input).await
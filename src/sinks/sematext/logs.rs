@@ -94,7 +94,7 @@ impl SinkConfig for SematextLogsConfig {
             }),
             batch: self.batch,
             request: RequestConfig {
-                tower: self.request,
+                tower: self.request.clone(),
                 ..Default::default()
             },
             encoding: self.encoding.clone(),
@@ -424,7 +424,7 @@ mod tests {
                 )
                 .with_namespace(Some(*namespace))
                 .with_tags(Some(
-                    vec![("os.host".to_owned(), "somehost".to_owned())]
+                    vec![("os.host".into(), "somehost".to_owned())]
                         .into_iter()
                         .collect(),
                 ))
@@ -0,0 +1,302 @@
+//! The `smtp` sink, which sends events as emails.
+//!
+//! Events are grouped by key into digests, so that a burst of alerts ends up in a single email,
+//! and the number of emails sent for each key is limited.
+
+use std::time::Duration;
+
+use futures::FutureExt;
+use lettre::{
+    message::Mailbox,
+    transport::smtp::{authentication::Credentials, Error as SmtpError},
+    AsyncSmtpTransport, Tokio1Executor,
+};
+use snafu::{ResultExt, Snafu};
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+
+use crate::{
+    config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
+    sinks::{util::rate_limit::RateLimit, Healthcheck, VectorSink},
+    template::Template,
+};
+
+mod sink;
+
+use sink::SmtpSink;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("invalid {} address {:?}: {}", field, address, source))]
+    InvalidAddress {
+        field: &'static str,
+        address: String,
+        source: lettre::address::AddressError,
+    },
+    #[snafu(display("invalid SMTP relay: {}", source))]
+    InvalidRelay { source: SmtpError },
+    #[snafu(display("at least one recipient is required"))]
+    NoRecipients,
+}
+
+/// Configuration for the `smtp` sink.
+#[configurable_component(sink("smtp"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SmtpSinkConfig {
+    /// The host name of the SMTP server.
+    host: String,
+
+    /// The port of the SMTP server.
+    ///
+    /// Defaults to 587 with `starttls`, 465 with `tls`, and 25 with `none`.
+    port: Option<u16>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    tls_mode: SmtpTlsMode,
+
+    #[configurable(derived)]
+    auth: Option<SmtpAuth>,
+
+    /// The sender of the emails, such as `Vector <vector@example.com>`.
+    from: String,
+
+    /// The recipients of the emails.
+    to: Vec<String>,
+
+    /// The subject of the emails.
+    ///
+    /// When an email is a digest of several events, the subject is rendered from the first one.
+    #[configurable(metadata(templateable))]
+    subject: Template,
+
+    /// The body of each event in the emails.
+    #[configurable(metadata(templateable))]
+    body: Template,
+
+    /// The key that events are grouped by.
+    ///
+    /// Each key gets its own digests, and its own rate limit. Events are not grouped by default.
+    #[configurable(metadata(templateable))]
+    key_field: Option<Template>,
+
+    /// How long to wait, in seconds, for more events of a key before sending their digest.
+    ///
+    /// With `0`, an email is sent for each event.
+    #[serde(default = "default_digest_secs")]
+    digest_secs: u64,
+
+    /// The most events whose body is included in a digest.
+    ///
+    /// The other events of the digest are only counted.
+    #[serde(default = "default_max_events_per_email")]
+    max_events_per_email: usize,
+
+    /// The most emails sent for each key within `rate_limit_duration_secs`.
+    ///
+    /// The events of digests over the limit are dropped.
+    #[serde(default = "default_rate_limit_num")]
+    rate_limit_num: usize,
+
+    /// The time window, in seconds, used for `rate_limit_num`.
+    #[serde(default = "default_rate_limit_duration_secs")]
+    rate_limit_duration_secs: u64,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+/// How the connection to the SMTP server is secured.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SmtpTlsMode {
+    /// The connection is upgraded to TLS with `STARTTLS`, which the server must support.
+    Starttls,
+
+    /// The connection uses TLS from the start.
+    Tls,
+
+    /// The connection is not secured.
+    None,
+}
+
+impl Default for SmtpTlsMode {
+    fn default() -> Self {
+        Self::Starttls
+    }
+}
+
+/// The credentials used to log in to the SMTP server.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SmtpAuth {
+    /// The username.
+    username: String,
+
+    /// The password.
+    password: SensitiveString,
+}
+
+const fn default_digest_secs() -> u64 {
+    30
+}
+
+const fn default_max_events_per_email() -> usize {
+    25
+}
+
+const fn default_rate_limit_num() -> usize {
+    10
+}
+
+const fn default_rate_limit_duration_secs() -> u64 {
+    3600
+}
+
+impl GenerateConfig for SmtpSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"host = "smtp.example.com"
+            from = "Vector <vector@example.com>"
+            to = ["oncall@example.com"]
+            subject = "Alert from {{ host }}"
+            body = "{{ message }}""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for SmtpSinkConfig {
+    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let transport = self.transport()?;
+
+        let from = parse_mailbox("from", &self.from)?;
+        if self.to.is_empty() {
+            return Err(BuildError::NoRecipients.into());
+        }
+        let to = self
+            .to
+            .iter()
+            .map(|address| parse_mailbox("to", address))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let sink = SmtpSink {
+            transport: transport.clone(),
+            from,
+            to,
+            subject: self.subject.clone(),
+            body: self.body.clone(),
+            key_field: self.key_field.clone(),
+            digest: Duration::from_secs(self.digest_secs),
+            max_events_per_email: self.max_events_per_email,
+            rate_limit: RateLimit::new(
+                self.rate_limit_num,
+                Duration::from_secs(self.rate_limit_duration_secs),
+            ),
+        };
+        let healthcheck = healthcheck(transport).boxed();
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::new(DataType::Log)
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+impl SmtpSinkConfig {
+    fn transport(&self) -> Result<AsyncSmtpTransport<Tokio1Executor>, BuildError> {
+        let mut builder = match self.tls_mode {
+            SmtpTlsMode::Starttls => {
+                AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&self.host)
+                    .context(InvalidRelaySnafu)?
+            }
+            SmtpTlsMode::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(&self.host)
+                .context(InvalidRelaySnafu)?,
+            SmtpTlsMode::None => {
+                AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&self.host)
+            }
+        };
+        if let Some(port) = self.port {
+            builder = builder.port(port);
+        }
+        if let Some(auth) = &self.auth {
+            builder = builder.credentials(Credentials::new(
+                auth.username.clone(),
+                auth.password.inner().to_owned(),
+            ));
+        }
+        Ok(builder.build())
+    }
+}
+
+fn parse_mailbox(field: &'static str, address: &str) -> Result<Mailbox, BuildError> {
+    address
+        .parse()
+        .context(InvalidAddressSnafu { field, address })
+}
+
+async fn healthcheck(transport: AsyncSmtpTransport<Tokio1Executor>) -> crate::Result<()> {
+    if transport.test_connection().await? {
+        Ok(())
+    } else {
+        Err("SMTP server refused the connection".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SmtpSinkConfig>();
+    }
+
+    #[test]
+    fn parses_config() {
+        let config: SmtpSinkConfig = toml::from_str(
+            r#"
+            host = "smtp.example.com"
+            port = 2525
+            tls_mode = "none"
+            auth.username = "vector"
+            auth.password = "secret"
+            from = "vector@example.com"
+            to = ["a@example.com", "B <b@example.com>"]
+            subject = "{{ service }} is down"
+            body = "{{ message }}"
+            key_field = "{{ service }}"
+            digest_secs = 0
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.tls_mode, SmtpTlsMode::None);
+        assert_eq!(config.digest_secs, 0);
+        assert_eq!(config.rate_limit_num, default_rate_limit_num());
+        assert!(config.transport().is_ok());
+    }
+
+    #[test]
+    fn rejects_invalid_addresses() {
+        assert!(parse_mailbox("from", "Vector <vector@example.com>").is_ok());
+        assert!(matches!(
+            parse_mailbox("to", "not an address"),
+            Err(BuildError::InvalidAddress { field: "to", .. })
+        ));
+    }
+}
@@ -0,0 +1,253 @@
+use std::{collections::HashMap, time::Duration};
+
+use async_trait::async_trait;
+use futures::{stream::BoxStream, StreamExt};
+use lettre::{
+    message::{header::ContentType, Mailbox},
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+};
+use tokio::time::{self, Instant};
+use vector_common::internal_event::{BytesSent, EventsSent};
+use vector_core::ByteSizeOf;
+
+use crate::{
+    event::{Event, EventFinalizers, EventStatus, Finalizable},
+    internal_events::{SmtpRateLimited, SmtpSendError, TemplateRenderingError},
+    sinks::util::{rate_limit::RateLimit, StreamSink},
+    template::Template,
+};
+
+/// How often digests are checked for being due.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+pub(super) struct SmtpSink {
+    pub(super) transport: AsyncSmtpTransport<Tokio1Executor>,
+    pub(super) from: Mailbox,
+    pub(super) to: Vec<Mailbox>,
+    pub(super) subject: Template,
+    pub(super) body: Template,
+    pub(super) key_field: Option<Template>,
+    pub(super) digest: Duration,
+    pub(super) max_events_per_email: usize,
+    pub(super) rate_limit: RateLimit,
+}
+
+/// The events of a key waiting to be sent in a single email.
+#[derive(Debug)]
+struct Digest {
+    subject: String,
+    bodies: Vec<String>,
+    count: usize,
+    byte_size: usize,
+    finalizers: EventFinalizers,
+    deadline: Instant,
+}
+
+impl Digest {
+    /// Renders the body of the email, with the events whose body didn't fit only counted.
+    fn body(&self) -> String {
+        let mut body = self.bodies.join("\n\n");
+        let omitted = self.count - self.bodies.len();
+        if omitted > 0 {
+            body.push_str(&format!("\n\n... and {} more events.", omitted));
+        }
+        body
+    }
+}
+
+impl SmtpSink {
+    /// Adds an event to the digest of its key.
+    fn add(&self, digests: &mut HashMap<String, Digest>, mut event: Event) {
+        let finalizers = event.take_finalizers();
+
+        let rendered = self
+            .key_field
+            .as_ref()
+            .map_or(Ok(String::new()), |key| {
+                key.render_string(&event)
+                    .map_err(|error| (error, "key_field"))
+            })
+            .and_then(|key| {
+                let subject = self
+                    .subject
+                    .render_string(&event)
+                    .map_err(|error| (error, "subject"))?;
+                let body = self
+                    .body
+                    .render_string(&event)
+                    .map_err(|error| (error, "body"))?;
+                Ok((key, subject, body))
+            });
+        let (key, subject, body) = match rendered {
+            Ok(rendered) => rendered,
+            Err((error, field)) => {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some(field),
+                    drop_event: true,
+                });
+                finalizers.update_status(EventStatus::Rejected);
+                return;
+            }
+        };
+
+        let digest = digests.entry(key).or_insert_with(|| Digest {
+            subject,
+            bodies: Vec::new(),
+            count: 0,
+            byte_size: 0,
+            finalizers: EventFinalizers::default(),
+            deadline: Instant::now() + self.digest,
+        });
+        digest.count += 1;
+        digest.byte_size += event.size_of();
+        if digest.bodies.len() < self.max_events_per_email {
+            digest.bodies.push(body);
+        }
+        digest.finalizers.merge(finalizers);
+    }
+
+    async fn send(&mut self, key: String, digest: Digest) {
+        if !self.rate_limit.check(&key, Instant::now()) {
+            emit!(SmtpRateLimited {
+                key: &key,
+                count: digest.count,
+            });
+            return;
+        }
+
+        let mut builder = Message::builder()
+            .from(self.from.clone())
+            .subject(&digest.subject)
+            .header(ContentType::TEXT_PLAIN);
+        for to in &self.to {
+            builder = builder.to(to.clone());
+        }
+        let message = match builder.body(digest.body()) {
+            Ok(message) => message,
+            Err(error) => {
+                emit!(SmtpSendError {
+                    error,
+                    count: digest.count,
+                });
+                digest.finalizers.update_status(EventStatus::Rejected);
+                return;
+            }
+        };
+        let message_size = message.formatted().len();
+
+        match self.transport.send(message).await {
+            Ok(_) => {
+                digest.finalizers.update_status(EventStatus::Delivered);
+                emit!(EventsSent {
+                    count: digest.count,
+                    byte_size: digest.byte_size,
+                    output: None,
+                });
+                emit!(BytesSent {
+                    byte_size: message_size,
+                    protocol: "smtp".into(),
+                });
+            }
+            Err(error) => {
+                digest.finalizers.update_status(EventStatus::Errored);
+                emit!(SmtpSendError {
+                    error,
+                    count: digest.count,
+                });
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl StreamSink<Event> for SmtpSink {
+    async fn run(mut self: Box<Self>, mut input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let mut digests = HashMap::new();
+        let mut flush = time::interval(FLUSH_INTERVAL);
+
+        loop {
+            tokio::select! {
+                event = input.next() => match event {
+                    Some(event) => self.add(&mut digests, event),
+                    None => break,
+                },
+                _ = flush.tick() => {},
+            }
+
+            let now = Instant::now();
+            let due = digests
+                .iter()
+                .filter(|(_, digest)| digest.deadline <= now)
+                .map(|(key, _)| key.clone())
+                .collect::<Vec<_>>();
+            for key in due {
+                let digest = digests.remove(&key).expect("digest is due");
+                self.send(key, digest).await;
+            }
+        }
+
+        // The remaining digests are sent without waiting for their deadline.
+        for (key, digest) in digests {
+            self.send(key, digest).await;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::LogEvent;
+
+    fn sink(key_field: Option<&str>, max_events_per_email: usize) -> SmtpSink {
+        SmtpSink {
+            transport: AsyncSmtpTransport::<Tokio1Executor>::unencrypted_localhost(),
+            from: "vector@example.com".parse().unwrap(),
+            to: vec!["oncall@example.com".parse().unwrap()],
+            subject: Template::try_from("{{ service }} alert").unwrap(),
+            body: Template::try_from("{{ message }}").unwrap(),
+            key_field: key_field.map(|key| Template::try_from(key).unwrap()),
+            digest: Duration::from_secs(30),
+            max_events_per_email,
+            rate_limit: RateLimit::new(1, Duration::from_secs(60)),
+        }
+    }
+
+    fn event(service: &str, message: &str) -> Event {
+        let mut log = LogEvent::from(message);
+        log.insert("service", service);
+        log.into()
+    }
+
+    #[test]
+    fn groups_events_by_key() {
+        let sink = sink(Some("{{ service }}"), 2);
+        let mut digests = HashMap::new();
+        for (service, message) in [
+            ("api", "one"),
+            ("db", "two"),
+            ("api", "three"),
+            ("api", "four"),
+        ] {
+            sink.add(&mut digests, event(service, message));
+        }
+
+        assert_eq!(digests.len(), 2);
+        let api = &digests["api"];
+        assert_eq!(api.subject, "api alert");
+        assert_eq!(api.count, 3);
+        assert_eq!(api.body(), "one\n\nthree\n\n... and 1 more events.");
+        assert_eq!(digests["db"].body(), "two");
+    }
+
+    #[test]
+    fn drops_events_that_fail_to_render() {
+        let sink = sink(Some("{{ service }}"), 2);
+        let mut digests = HashMap::new();
+        sink.add(&mut digests, LogEvent::from("no service").into());
+
+        assert!(digests.is_empty());
+    }
+}
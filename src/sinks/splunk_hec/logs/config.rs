@@ -174,6 +174,9 @@ impl HecLogsSinkConfig {
         client: HttpClient,
         cx: SinkContext,
     ) -> crate::Result<VectorSink> {
+        self.compression
+            .check_supported(Compression::FLATE_ALGORITHMS)?;
+
         let ack_client = if self.acknowledgements.indexer_acknowledgements_enabled {
             Some(client.clone())
         } else {
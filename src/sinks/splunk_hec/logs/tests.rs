@@ -237,6 +237,25 @@ async fn splunk_passthrough_token() {
     )
 }
 
+#[tokio::test]
+async fn splunk_rejects_unsupported_compression() {
+    let config = toml::from_str::<HecLogsSinkConfig>(
+        r#"
+            endpoint = "http://localhost:8088"
+            default_token = "token"
+            encoding.codec = "json"
+            compression = "zstd"
+        "#,
+    )
+    .unwrap();
+
+    let error = config.build(SinkContext::new_test()).await.err().unwrap();
+    assert_eq!(
+        error.to_string(),
+        "`zstd` compression isn't supported by this sink, expected one of: none, gzip, zlib."
+    );
+}
+
 #[test]
 fn splunk_encode_log_event_json_timestamps() {
     crate::test_util::trace_init();
@@ -143,6 +143,9 @@ impl HecMetricsSinkConfig {
         client: HttpClient,
         cx: SinkContext,
     ) -> crate::Result<VectorSink> {
+        self.compression
+            .check_supported(Compression::FLATE_ALGORITHMS)?;
+
         let ack_client = if self.acknowledgements.indexer_acknowledgements_enabled {
             Some(client.clone())
         } else {
@@ -55,7 +55,7 @@ impl HecMetricsEncoder {
             .into_iter()
             .flatten()
             // skip the metric tags used for templating
-            .filter(|(k, _)| !metadata.templated_field_keys.contains(k))
+            .filter(|(k, _)| !metadata.templated_field_keys.iter().any(|key| key == *k))
             .map(|(k, v)| (k.as_str(), HecFieldValue::Str(v.as_str())))
             .chain(iter::once((
                 "metric_name",
@@ -0,0 +1,599 @@
+//! The `splunk_signalfx` sink, which sends metrics as datapoints and logs as custom events to the
+//! ingest API of Splunk Observability Cloud, formerly SignalFx.
+
+use std::collections::HashMap;
+
+use bytes::Bytes;
+use futures::{FutureExt, SinkExt};
+use http::{Request, StatusCode, Uri};
+use hyper::Body;
+use serde_json::{json, Map, Value};
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+
+use crate::{
+    config::{
+        log_schema, AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig,
+        SinkContext,
+    },
+    event::{
+        metric::{Metric, MetricKind, MetricTags, MetricValue},
+        Event, LogEvent,
+    },
+    http::HttpClient,
+    internal_events::{SignalFxInvalidMetricError, TemplateRenderingError},
+    sinks::{
+        util::{
+            buffer::metrics::{MetricNormalize, MetricNormalizer, MetricSet},
+            http::{HttpEventEncoder, HttpSink, PartitionHttpSink},
+            BatchConfig, BoxedRawValue, JsonArrayBuffer, PartitionBuffer, PartitionInnerBuffer,
+            SinkBatchSettings, TowerRequestConfig,
+        },
+        Healthcheck, HealthcheckError, VectorSink,
+    },
+    template::Template,
+    tls::{TlsConfig, TlsSettings},
+};
+
+/// The header holding the access token of the organization.
+const TOKEN_HEADER: &str = "X-SF-Token";
+
+/// The longest dimension name accepted by SignalFx.
+const MAX_DIMENSION_NAME_LENGTH: usize = 128;
+
+/// The longest dimension value accepted by SignalFx.
+const MAX_DIMENSION_VALUE_LENGTH: usize = 256;
+
+/// The category of the events that don't have one.
+const DEFAULT_CATEGORY: &str = "USER_DEFINED";
+
+/// Configuration for the `splunk_signalfx` sink.
+#[configurable_component(sink("splunk_signalfx"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SignalFxConfig {
+    /// The realm of the organization, such as `us0` or `eu0`.
+    ///
+    /// The events are sent to the ingest endpoint of the realm, `https://ingest.<realm>.signalfx.com`.
+    #[serde(default = "default_realm")]
+    realm: String,
+
+    /// The ingest endpoint to send events to, instead of the one of the realm.
+    ///
+    /// This is useful to send events through a proxy, such as the OpenTelemetry Collector.
+    endpoint: Option<String>,
+
+    /// An access token of the organization, with the `INGEST` scope.
+    access_token: SensitiveString,
+
+    /// The default namespace for any metrics sent.
+    ///
+    /// This namespace is only used if a metric has no existing namespace. When a namespace is
+    /// present, it is used as a prefix to the metric name, and separated with a period (`.`).
+    default_namespace: Option<String>,
+
+    /// The names of the dimensions the tags of the metrics are sent as, by tag.
+    ///
+    /// Tags that aren't listed are sent as dimensions of the same name. The names of the dimensions
+    /// can only contain letters, digits, underscores and hyphens, so other characters are replaced
+    /// with underscores.
+    #[serde(default)]
+    dimension_mapping: HashMap<String, String>,
+
+    /// The type of the custom events the logs are sent as.
+    ///
+    /// The `category`, `dimensions` and `properties` fields of the logs, if any, are sent as the
+    /// category, dimensions and properties of the events.
+    #[configurable(metadata(templateable))]
+    #[serde(default = "default_event_type")]
+    event_type: Template,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    batch: BatchConfig<SignalFxDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+fn default_realm() -> String {
+    "us0".to_string()
+}
+
+fn default_event_type() -> Template {
+    Template::try_from("{{ event_type }}").expect("valid template")
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SignalFxDefaultBatchSettings;
+
+impl SinkBatchSettings for SignalFxDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = Some(1000);
+    const MAX_BYTES: Option<usize> = Some(1_000_000);
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+impl GenerateConfig for SignalFxConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"realm = "us0"
+            access_token = "${SIGNALFX_ACCESS_TOKEN}""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for SignalFxConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls, cx.proxy())?;
+
+        let endpoint = match &self.endpoint {
+            Some(endpoint) => endpoint.trim_end_matches('/').to_string(),
+            None => format!("https://ingest.{}.signalfx.com", self.realm),
+        };
+        let sink = SignalFxSink {
+            datapoint_uri: format!("{}/v2/datapoint", endpoint).parse()?,
+            event_uri: format!("{}/v2/event", endpoint).parse()?,
+            access_token: self.access_token.clone(),
+            default_namespace: self.default_namespace.clone(),
+            dimension_mapping: self.dimension_mapping.clone(),
+            event_type: self.event_type.clone(),
+        };
+
+        let healthcheck = healthcheck(sink.clone(), client.clone()).boxed();
+
+        let request = self.request.unwrap_with(&TowerRequestConfig::default());
+        let batch = self.batch.into_batch_settings()?;
+        let sink = PartitionHttpSink::new(
+            sink,
+            PartitionBuffer::new(JsonArrayBuffer::new(batch.size)),
+            request,
+            batch.timeout,
+            client,
+        )
+        .sink_map_err(|error| error!(message = "Fatal splunk_signalfx sink error.", %error));
+
+        Ok((VectorSink::from_event_sink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::new(DataType::Metric | DataType::Log)
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+/// The kind of the items of a request, which are sent in separate requests.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+enum SignalFxKind {
+    Gauge,
+    Counter,
+    CumulativeCounter,
+    Event,
+}
+
+impl SignalFxKind {
+    /// The key of the datapoints of this kind in the body of the datapoint requests.
+    const fn datapoint_key(self) -> Option<&'static str> {
+        match self {
+            Self::Gauge => Some("gauge"),
+            Self::Counter => Some("counter"),
+            Self::CumulativeCounter => Some("cumulative_counter"),
+            Self::Event => None,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct SignalFxSink {
+    datapoint_uri: Uri,
+    event_uri: Uri,
+    access_token: SensitiveString,
+    default_namespace: Option<String>,
+    dimension_mapping: HashMap<String, String>,
+    event_type: Template,
+}
+
+/// SignalFx expects gauges to be absolute, while counters can be either deltas or cumulative.
+#[derive(Default)]
+struct SignalFxMetricNormalize;
+
+impl MetricNormalize for SignalFxMetricNormalize {
+    fn normalize(&mut self, state: &mut MetricSet, metric: Metric) -> Option<Metric> {
+        match (metric.kind(), metric.value()) {
+            (MetricKind::Incremental, MetricValue::Gauge { .. } | MetricValue::Set { .. }) => {
+                state.make_absolute(metric)
+            }
+            _ => Some(metric),
+        }
+    }
+}
+
+struct SignalFxEventEncoder {
+    default_namespace: Option<String>,
+    dimension_mapping: HashMap<String, String>,
+    event_type: Template,
+    normalizer: MetricNormalizer<SignalFxMetricNormalize>,
+}
+
+impl HttpEventEncoder<PartitionInnerBuffer<Value, SignalFxKind>> for SignalFxEventEncoder {
+    fn encode_event(&mut self, event: Event) -> Option<PartitionInnerBuffer<Value, SignalFxKind>> {
+        let (value, kind) = match event {
+            Event::Metric(metric) => {
+                let metric = self.normalizer.normalize(metric)?;
+                self.encode_metric(&metric)?
+            }
+            Event::Log(log) => (self.encode_log(&log)?, SignalFxKind::Event),
+            Event::Trace(_) => return None,
+        };
+        Some(PartitionInnerBuffer::new(value, kind))
+    }
+}
+
+impl SignalFxEventEncoder {
+    fn encode_metric(&self, metric: &Metric) -> Option<(Value, SignalFxKind)> {
+        let (value, kind) = match (metric.kind(), metric.value()) {
+            (MetricKind::Incremental, MetricValue::Counter { value }) => {
+                (*value, SignalFxKind::Counter)
+            }
+            (MetricKind::Absolute, MetricValue::Counter { value }) => {
+                (*value, SignalFxKind::CumulativeCounter)
+            }
+            (_, MetricValue::Gauge { value }) => (*value, SignalFxKind::Gauge),
+            (_, MetricValue::Set { values }) => (values.len() as f64, SignalFxKind::Gauge),
+            _ => {
+                emit!(SignalFxInvalidMetricError { metric });
+                return None;
+            }
+        };
+
+        let name = match metric.namespace().or(self.default_namespace.as_deref()) {
+            Some(namespace) => format!("{}.{}", namespace, metric.name()),
+            None => metric.name().to_string(),
+        };
+        let mut datapoint = json!({
+            "metric": name,
+            "value": value,
+            "dimensions": self.dimensions(metric.tags()),
+        });
+        if let Some(timestamp) = metric.timestamp() {
+            datapoint["timestamp"] = timestamp.timestamp_millis().into();
+        }
+
+        Some((datapoint, kind))
+    }
+
+    fn encode_log(&self, log: &LogEvent) -> Option<Value> {
+        let event_type = self
+            .event_type
+            .render_string(log)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("event_type"),
+                    drop_event: true,
+                });
+            })
+            .ok()?;
+
+        let category = log
+            .get("category")
+            .map(|category| category.to_string_lossy())
+            .unwrap_or_else(|| DEFAULT_CATEGORY.to_string());
+        let dimensions = match log.get("dimensions") {
+            Some(crate::event::Value::Object(dimensions)) => dimensions
+                .iter()
+                .map(|(name, value)| {
+                    (
+                        sanitize_dimension_name(name),
+                        truncate(value.to_string_lossy(), MAX_DIMENSION_VALUE_LENGTH).into(),
+                    )
+                })
+                .collect(),
+            _ => Map::new(),
+        };
+        let properties = match log.get("properties") {
+            Some(properties @ crate::event::Value::Object(_)) => {
+                serde_json::to_value(properties).ok()?
+            }
+            _ => json!({}),
+        };
+        let timestamp = log
+            .get(log_schema().timestamp_key())
+            .and_then(|timestamp| timestamp.as_timestamp())
+            .map(|timestamp| timestamp.timestamp_millis())
+            .unwrap_or_else(|| chrono::Utc::now().timestamp_millis());
+
+        Some(json!({
+            "category": category,
+            "eventType": event_type,
+            "dimensions": dimensions,
+            "properties": properties,
+            "timestamp": timestamp,
+        }))
+    }
+
+    /// Maps the tags of a metric to dimensions.
+    fn dimensions(&self, tags: Option<&MetricTags>) -> Map<String, Value> {
+        tags.into_iter()
+            .flatten()
+            .map(|(key, value)| {
+                let name = match self.dimension_mapping.get(key.as_str()) {
+                    Some(name) => name.clone(),
+                    None => sanitize_dimension_name(key.as_str()),
+                };
+                let value = truncate(value.clone(), MAX_DIMENSION_VALUE_LENGTH);
+                (name, value.into())
+            })
+            .collect()
+    }
+}
+
+/// Dimension names must start with a letter, and can only contain letters, digits, underscores and
+/// hyphens.
+fn sanitize_dimension_name(name: &str) -> String {
+    let mut sanitized = name
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '_' | '-' => c,
+            _ => '_',
+        })
+        .collect::<String>();
+    if !sanitized.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        sanitized.insert(0, 'd');
+    }
+    truncate(sanitized, MAX_DIMENSION_NAME_LENGTH)
+}
+
+fn truncate(mut value: String, length: usize) -> String {
+    if let Some((index, _)) = value.char_indices().nth(length) {
+        value.truncate(index);
+    }
+    value
+}
+
+#[async_trait::async_trait]
+impl HttpSink for SignalFxSink {
+    type Input = PartitionInnerBuffer<Value, SignalFxKind>;
+    type Output = PartitionInnerBuffer<Vec<BoxedRawValue>, SignalFxKind>;
+    type Encoder = SignalFxEventEncoder;
+
+    fn build_encoder(&self) -> Self::Encoder {
+        SignalFxEventEncoder {
+            default_namespace: self.default_namespace.clone(),
+            dimension_mapping: self.dimension_mapping.clone(),
+            event_type: self.event_type.clone(),
+            normalizer: MetricNormalizer::default(),
+        }
+    }
+
+    async fn build_request(&self, output: Self::Output) -> crate::Result<Request<Bytes>> {
+        let (items, kind) = output.into_parts();
+        let (uri, body) = match kind.datapoint_key() {
+            // Datapoints are grouped by type in an object.
+            Some(key) => (
+                &self.datapoint_uri,
+                serde_json::to_vec(&HashMap::from([(key, items)]))?,
+            ),
+            None => (&self.event_uri, serde_json::to_vec(&items)?),
+        };
+
+        self.request(uri, Bytes::from(body))
+    }
+}
+
+impl SignalFxSink {
+    fn request(&self, uri: &Uri, body: Bytes) -> crate::Result<Request<Bytes>> {
+        Request::post(uri)
+            .header("Content-Type", "application/json")
+            .header(TOKEN_HEADER, self.access_token.inner())
+            .body(body)
+            .map_err(Into::into)
+    }
+}
+
+/// Sends an empty set of datapoints, which checks the access token without recording anything.
+async fn healthcheck(sink: SignalFxSink, client: HttpClient) -> crate::Result<()> {
+    let request = sink
+        .request(&sink.datapoint_uri, Bytes::from_static(b"{}"))?
+        .map(Body::from);
+
+    let response = client.send(request).await?;
+
+    match response.status() {
+        StatusCode::OK => Ok(()),
+        StatusCode::UNAUTHORIZED => Err("Invalid access token".into()),
+        status => Err(HealthcheckError::UnexpectedStatus { status }.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use futures::{stream, StreamExt};
+    use indoc::indoc;
+
+    use super::*;
+    use crate::{
+        sinks::util::test::{build_test_server, load_sink},
+        test_util::{
+            components::{run_and_assert_sink_compliance, HTTP_SINK_TAGS},
+            next_addr,
+        },
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SignalFxConfig>();
+    }
+
+    fn encoder() -> SignalFxEventEncoder {
+        SignalFxEventEncoder {
+            default_namespace: Some("vector".into()),
+            dimension_mapping: HashMap::from([("host".to_string(), "host_name".to_string())]),
+            event_type: default_event_type(),
+            normalizer: MetricNormalizer::default(),
+        }
+    }
+
+    fn encode(encoder: &mut SignalFxEventEncoder, event: Event) -> (Value, SignalFxKind) {
+        let (value, kind) = encoder.encode_event(event).unwrap().into_parts();
+        (value, kind)
+    }
+
+    #[test]
+    fn encodes_metrics_with_dimensions() {
+        let metric = Metric::new(
+            "requests",
+            MetricKind::Absolute,
+            MetricValue::Counter { value: 42.0 },
+        )
+        .with_tags(Some(
+            vec![
+                ("host".into(), "localhost".to_owned()),
+                ("http.status".into(), "200".to_owned()),
+            ]
+            .into_iter()
+            .collect(),
+        ))
+        .with_timestamp(Some(Utc.timestamp(1_600_000_000, 0)));
+
+        let (value, kind) = encode(&mut encoder(), metric.into());
+
+        assert_eq!(kind, SignalFxKind::CumulativeCounter);
+        assert_eq!(
+            value,
+            json!({
+                "metric": "vector.requests",
+                "value": 42.0,
+                "dimensions": {"host_name": "localhost", "http_status": "200"},
+                "timestamp": 1_600_000_000_000_i64,
+            })
+        );
+    }
+
+    #[test]
+    fn accumulates_incremental_gauges() {
+        let mut encoder = encoder();
+        let gauge = |value| {
+            Event::from(Metric::new(
+                "load",
+                MetricKind::Incremental,
+                MetricValue::Gauge { value },
+            ))
+        };
+
+        let _ = encode(&mut encoder, gauge(1.0));
+        let (value, kind) = encode(&mut encoder, gauge(2.0));
+
+        assert_eq!(kind, SignalFxKind::Gauge);
+        assert_eq!(value["value"], json!(3.0));
+    }
+
+    #[test]
+    fn drops_unsupported_metrics() {
+        let metric = Metric::new(
+            "latency",
+            MetricKind::Incremental,
+            MetricValue::Distribution {
+                samples: vector_core::samples![1.0 => 1],
+                statistic: vector_core::event::StatisticKind::Histogram,
+            },
+        );
+
+        assert!(encoder().encode_event(metric.into()).is_none());
+    }
+
+    #[test]
+    fn encodes_logs_as_events() {
+        let mut log = LogEvent::from("deployed");
+        log.insert("event_type", "deployment");
+        log.insert("dimensions.service", "checkout");
+        log.insert("properties.version", "1.2.3");
+        log.insert(
+            log_schema().timestamp_key(),
+            Utc.timestamp(1_600_000_000, 0),
+        );
+
+        let (value, kind) = encode(&mut encoder(), log.into());
+
+        assert_eq!(kind, SignalFxKind::Event);
+        assert_eq!(
+            value,
+            json!({
+                "category": "USER_DEFINED",
+                "eventType": "deployment",
+                "dimensions": {"service": "checkout"},
+                "properties": {"version": "1.2.3"},
+                "timestamp": 1_600_000_000_000_i64,
+            })
+        );
+    }
+
+    #[test]
+    fn sanitizes_dimension_names() {
+        assert_eq!(sanitize_dimension_name("k8s.pod name"), "k8s_pod_name");
+        assert_eq!(sanitize_dimension_name("_private"), "d_private");
+        assert_eq!(sanitize_dimension_name(&"a".repeat(200)).len(), 128);
+    }
+
+    #[tokio::test]
+    async fn sends_datapoints_and_events_separately() {
+        let addr = next_addr();
+        let config = format!(
+            indoc! {r#"
+                endpoint = "http://{}"
+                access_token = "token"
+            "#},
+            addr
+        );
+        let (config, cx) = load_sink::<SignalFxConfig>(&config).unwrap();
+        let (sink, _) = config.build(cx).await.unwrap();
+        let (rx, _trigger, server) = build_test_server(addr);
+        tokio::spawn(server);
+
+        let gauge = Event::from(Metric::new(
+            "load",
+            MetricKind::Absolute,
+            MetricValue::Gauge { value: 1.5 },
+        ));
+        let mut log = LogEvent::from("deployed");
+        log.insert("event_type", "deployment");
+        run_and_assert_sink_compliance(
+            sink,
+            stream::iter(vec![gauge, Event::from(log)]),
+            &HTTP_SINK_TAGS,
+        )
+        .await;
+
+        let mut output = rx.take(2).collect::<Vec<_>>().await;
+        output.sort_by_key(|(parts, _)| parts.uri.path().to_string());
+
+        let (parts, body) = &output[0];
+        assert_eq!(parts.uri.path(), "/v2/datapoint");
+        assert_eq!(parts.headers[TOKEN_HEADER], "token");
+        let body: Value = serde_json::from_slice(body).unwrap();
+        assert_eq!(body["gauge"][0]["metric"], json!("load"));
+
+        let (parts, body) = &output[1];
+        assert_eq!(parts.uri.path(), "/v2/event");
+        let body: Value = serde_json::from_slice(body).unwrap();
+        assert_eq!(body[0]["eventType"], json!("deployment"));
+    }
+}
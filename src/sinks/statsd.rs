@@ -318,9 +318,9 @@ mod test {
 
     fn tags() -> MetricTags {
         vec![
-            ("normal_tag".to_owned(), "value".to_owned()),
-            ("true_tag".to_owned(), "true".to_owned()),
-            ("empty_tag".to_owned(), "".to_owned()),
+            ("normal_tag".into(), "value".to_owned()),
+            ("true_tag".into(), "true".to_owned()),
+            ("empty_tag".into(), "".to_owned()),
         ]
         .into_iter()
         .collect()
@@ -346,7 +346,7 @@ mod test {
                     ("e", "value"),
                 ]
                 .into_iter()
-                .map(|(k, v)| (k.to_owned(), v.to_owned()))
+                .map(|(k, v)| (k.into(), v.to_owned()))
                 .collect()
             ),
             "a:value,b:value,c:value,d:value,e:value"
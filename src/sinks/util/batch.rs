@@ -19,6 +19,8 @@ pub enum BatchError {
     InvalidMaxBytes,
     #[snafu(display("`max_events` must be greater than zero"))]
     InvalidMaxEvents,
+    #[snafu(display("`max_total_bytes` must be greater than zero"))]
+    InvalidMaxTotalBytes,
     #[snafu(display("`timeout_secs` must be greater than zero"))]
     InvalidTimeout,
     #[snafu(display("provided `max_bytes` exceeds the maximum limit of {}", limit))]
@@ -117,6 +119,16 @@ where
     /// The maximum age of a batch, in seconds, before it is flushed.
     pub timeout_secs: Option<f64>,
 
+    /// The maximum size of all the batches being built by a sink together.
+    ///
+    /// The limits above apply to each batch, so to each partition of the sinks that partition
+    /// their batches, such as by stream or key prefix. When the batches of all the
+    /// partitions together exceed this size, the largest of them are flushed early.
+    ///
+    /// This is based on the uncompressed size of the batched events, before they are
+    /// serialized / compressed.
+    pub max_total_bytes: Option<usize>,
+
     #[serde(skip)]
     _d: PhantomData<D>,
     #[serde(skip)]
@@ -129,6 +141,7 @@ impl<D: SinkBatchSettings + Clone> BatchConfig<D, Unmerged> {
             max_bytes: self.max_bytes.or(D::MAX_BYTES),
             max_events: self.max_events.or(D::MAX_EVENTS),
             timeout_secs: self.timeout_secs.or(Some(D::TIMEOUT_SECS)),
+            max_total_bytes: self.max_total_bytes,
             _d: PhantomData,
             _s: PhantomData,
         };
@@ -142,6 +155,7 @@ impl<D: SinkBatchSettings + Clone> BatchConfig<D, Unmerged> {
             (Some(0), _, _) => Err(BatchError::InvalidMaxBytes),
             (_, Some(0), _) => Err(BatchError::InvalidMaxEvents),
             (_, _, Some(timeout)) if timeout <= 0.0 => Err(BatchError::InvalidTimeout),
+            _ if config.max_total_bytes == Some(0) => Err(BatchError::InvalidMaxTotalBytes),
 
             _ => Ok(config),
         }
@@ -232,11 +246,13 @@ impl<D: SinkBatchSettings + Clone> BatchConfig<D, Merged> {
         // `validate`, but alas.
         let timeout_secs = self.timeout_secs.ok_or(BatchError::InvalidTimeout)?;
 
-        Ok(BatcherSettings::new(
-            Duration::from_secs_f64(timeout_secs),
-            max_bytes,
-            max_events,
-        ))
+        let settings =
+            BatcherSettings::new(Duration::from_secs_f64(timeout_secs), max_bytes, max_events);
+
+        Ok(match self.max_total_bytes.and_then(NonZeroUsize::new) {
+            Some(max_total_bytes) => settings.with_total_size_limit(max_total_bytes),
+            None => settings,
+        })
     }
 }
 
@@ -252,6 +268,7 @@ where
             max_bytes: config.max_bytes,
             max_events: config.max_events,
             timeout_secs: config.timeout_secs,
+            max_total_bytes: config.max_total_bytes,
             _d: PhantomData,
             _s: PhantomData,
         }
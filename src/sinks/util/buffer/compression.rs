@@ -1,12 +1,18 @@
-use std::{collections::BTreeSet, fmt};
+use std::{
+    collections::BTreeSet,
+    fmt, io,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
 
 use indexmap::IndexMap;
+use once_cell::sync::Lazy;
 use serde::{de, ser};
 use vector_config::{
     schema::{
         apply_metadata, generate_const_string_schema, generate_enum_schema,
-        generate_internal_tagged_variant_schema, generate_one_of_schema, generate_struct_schema,
-        get_or_generate_schema,
+        generate_internal_tagged_variant_schema, generate_one_of_schema, generate_string_schema,
+        generate_struct_schema, get_or_generate_schema,
     },
     schemars::{gen::SchemaGenerator, schema::SchemaObject},
     Configurable, GenerateError, Metadata,
@@ -30,6 +36,19 @@ pub enum Compression {
     ///
     /// [zlib]: https://en.wikipedia.org/wiki/Zlib
     Zlib(CompressionLevel),
+
+    /// [Zstandard][zstd] compression, optionally primed with a dictionary.
+    ///
+    /// [zstd]: https://facebook.github.io/zstd/
+    Zstd {
+        level: ZstdCompressionLevel,
+        dictionary: Option<ZstdDictionary>,
+    },
+
+    /// [Snappy][snappy] compression, with its framing format.
+    ///
+    /// [snappy]: https://github.com/google/snappy/blob/main/docs/README.md
+    Snappy,
 }
 
 impl Compression {
@@ -53,11 +72,51 @@ impl Compression {
         Compression::Zlib(CompressionLevel::const_default())
     }
 
+    pub const fn zstd_default() -> Compression {
+        Compression::Zstd {
+            level: ZstdCompressionLevel::const_default(),
+            dictionary: None,
+        }
+    }
+
+    /// The algorithms accepted by endpoints that only decode the `Content-Encoding`s predating
+    /// zstd and snappy support.
+    pub const FLATE_ALGORITHMS: &'static [&'static str] = &["none", "gzip", "zlib"];
+
+    pub const fn algorithm(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Gzip(_) => "gzip",
+            Self::Zlib(_) => "zlib",
+            Self::Zstd { .. } => "zstd",
+            Self::Snappy => "snappy",
+        }
+    }
+
+    /// Checks that the algorithm is one of those supported by the sink.
+    ///
+    /// Every sink shares this configuration, but not every endpoint decodes every algorithm, so
+    /// sinks with narrower support check it when they're built rather than failing every request.
+    pub fn check_supported(self, supported: &[&str]) -> crate::Result<()> {
+        if supported.contains(&self.algorithm()) {
+            Ok(())
+        } else {
+            Err(format!(
+                "`{}` compression isn't supported by this sink, expected one of: {}.",
+                self.algorithm(),
+                supported.join(", ")
+            )
+            .into())
+        }
+    }
+
     pub const fn content_encoding(self) -> Option<&'static str> {
         match self {
             Self::None => None,
             Self::Gzip(_) => Some("gzip"),
             Self::Zlib(_) => Some("deflate"),
+            Self::Zstd { .. } => Some("zstd"),
+            Self::Snappy => Some("snappy"),
         }
     }
 
@@ -66,13 +125,8 @@ impl Compression {
             Self::None => "log",
             Self::Gzip(_) => "log.gz",
             Self::Zlib(_) => "log.zz",
-        }
-    }
-
-    pub const fn level(self) -> flate2::Compression {
-        match self {
-            Self::None => flate2::Compression::none(),
-            Self::Gzip(level) | Self::Zlib(level) => level.as_flate2(),
+            Self::Zstd { .. } => "log.zst",
+            Self::Snappy => "log.sz",
         }
     }
 }
@@ -83,6 +137,8 @@ impl fmt::Display for Compression {
             Compression::None => write!(f, "none"),
             Compression::Gzip(ref level) => write!(f, "gzip({})", level.as_flate2().level()),
             Compression::Zlib(ref level) => write!(f, "zlib({})", level.as_flate2().level()),
+            Compression::Zstd { ref level, .. } => write!(f, "zstd({})", level.as_i32()),
+            Compression::Snappy => write!(f, "snappy"),
         }
     }
 }
@@ -109,9 +165,11 @@ impl<'de> de::Deserialize<'de> for Compression {
                     "none" => Ok(Compression::None),
                     "gzip" => Ok(Compression::gzip_default()),
                     "zlib" => Ok(Compression::zlib_default()),
+                    "zstd" => Ok(Compression::zstd_default()),
+                    "snappy" => Ok(Compression::Snappy),
                     _ => Err(de::Error::invalid_value(
                         de::Unexpected::Str(s),
-                        &r#""none" or "gzip" or "zlib""#,
+                        &r#""none", "gzip", "zlib", "zstd" or "snappy""#,
                     )),
                 }
             }
@@ -122,6 +180,7 @@ impl<'de> de::Deserialize<'de> for Compression {
             {
                 let mut algorithm = None;
                 let mut level = None;
+                let mut dictionary = None;
 
                 while let Some(key) = map.next_key::<String>()? {
                     match key.as_str() {
@@ -135,25 +194,52 @@ impl<'de> de::Deserialize<'de> for Compression {
                             if level.is_some() {
                                 return Err(de::Error::duplicate_field("level"));
                             }
-                            level = Some(map.next_value::<CompressionLevel>()?);
+                            level = Some(map.next_value::<ConfiguredLevel>()?);
+                        }
+                        "dictionary" => {
+                            if dictionary.is_some() {
+                                return Err(de::Error::duplicate_field("dictionary"));
+                            }
+                            dictionary = Some(map.next_value::<PathBuf>()?);
+                        }
+                        _ => {
+                            return Err(de::Error::unknown_field(
+                                &key,
+                                &["algorithm", "level", "dictionary"],
+                            ))
                         }
-                        _ => return Err(de::Error::unknown_field(&key, &["algorithm", "level"])),
                     };
                 }
 
-                match algorithm
-                    .ok_or_else(|| de::Error::missing_field("algorithm"))?
-                    .as_str()
-                {
-                    "none" => match level {
-                        Some(_) => Err(de::Error::unknown_field("level", &[])),
-                        None => Ok(Compression::None),
-                    },
-                    "gzip" => Ok(Compression::Gzip(level.unwrap_or_default())),
-                    "zlib" => Ok(Compression::Zlib(level.unwrap_or_default())),
+                let algorithm = algorithm.ok_or_else(|| de::Error::missing_field("algorithm"))?;
+                // Only zstd can be primed with a dictionary, and only the algorithms that take a
+                // level can be given one.
+                let fields: &'static [&'static str] = match algorithm.as_str() {
+                    "none" | "snappy" => &[],
+                    "gzip" | "zlib" => &["algorithm", "level"],
+                    _ => &["algorithm", "level", "dictionary"],
+                };
+                if level.is_some() && !fields.contains(&"level") {
+                    return Err(de::Error::unknown_field("level", fields));
+                }
+                if dictionary.is_some() && !fields.contains(&"dictionary") {
+                    return Err(de::Error::unknown_field("dictionary", fields));
+                }
+
+                match algorithm.as_str() {
+                    "none" => Ok(Compression::None),
+                    "gzip" => Ok(Compression::Gzip(CompressionLevel::from_configured(level)?)),
+                    "zlib" => Ok(Compression::Zlib(CompressionLevel::from_configured(level)?)),
+                    "zstd" => Ok(Compression::Zstd {
+                        level: ZstdCompressionLevel::from_configured(level)?,
+                        dictionary: dictionary
+                            .map(|path| ZstdDictionary::load(path).map_err(de::Error::custom))
+                            .transpose()?,
+                    }),
+                    "snappy" => Ok(Compression::Snappy),
                     algorithm => Err(de::Error::unknown_variant(
                         algorithm,
-                        &["none", "gzip", "zlib"],
+                        &["none", "gzip", "zlib", "zstd", "snappy"],
                     )),
                 }
             }
@@ -182,6 +268,20 @@ impl ser::Serialize for Compression {
                 map.serialize_entry("algorithm", "zlib")?;
                 level = Some(*zlib_level);
             }
+            Compression::Zstd {
+                level: zstd_level,
+                dictionary,
+            } => {
+                map.serialize_entry("algorithm", "zstd")?;
+                // Same as below, the default level is left out.
+                if *zstd_level != ZstdCompressionLevel::const_default() {
+                    map.serialize_entry("level", zstd_level)?;
+                }
+                if let Some(dictionary) = dictionary {
+                    map.serialize_entry("dictionary", dictionary.path())?;
+                }
+            }
+            Compression::Snappy => map.serialize_entry("algorithm", "snappy")?,
         }
 
         // If there's a level present, and it's _not_ the default compression level, then serialize it. We already
@@ -211,12 +311,15 @@ impl Configurable for Compression {
     fn generate_schema(gen: &mut SchemaGenerator) -> Result<SchemaObject, GenerateError> {
         const ALGORITHM_NAME: &str = "algorithm";
         const LEVEL_NAME: &str = "level";
+        const DICTIONARY_NAME: &str = "dictionary";
         const NONE_NAME: &str = "none";
         const GZIP_NAME: &str = "gzip";
         const ZLIB_NAME: &str = "zlib";
+        const ZSTD_NAME: &str = "zstd";
+        const SNAPPY_NAME: &str = "snappy";
 
         // First, we need to be able to handle all of the string-only variants.
-        let const_values = [NONE_NAME, GZIP_NAME, ZLIB_NAME]
+        let const_values = [NONE_NAME, GZIP_NAME, ZLIB_NAME, ZSTD_NAME, SNAPPY_NAME]
             .iter()
             .map(|s| serde_json::Value::from(*s))
             .collect();
@@ -228,6 +331,11 @@ impl Configurable for Compression {
         let compression_level_schema =
             get_or_generate_schema::<CompressionLevel>(gen, compression_level_metadata)?;
 
+        let mut zstd_compression_level_metadata = Metadata::default();
+        zstd_compression_level_metadata.set_transparent();
+        let zstd_compression_level_schema =
+            get_or_generate_schema::<ZstdCompressionLevel>(gen, zstd_compression_level_metadata)?;
+
         let mut required = BTreeSet::new();
         required.insert(ALGORITHM_NAME.to_string());
 
@@ -268,7 +376,7 @@ impl Configurable for Compression {
         );
         zlib_properties.insert(LEVEL_NAME.to_string(), compression_level_schema);
 
-        let mut zlib_schema = generate_struct_schema(zlib_properties, required, None);
+        let mut zlib_schema = generate_struct_schema(zlib_properties, required.clone(), None);
         let mut zlib_metadata = Metadata::<()>::with_title("[Zlib]][zlib] compression.");
         zlib_metadata.set_description("[zlib]: https://en.wikipedia.org/wiki/Zlib");
         zlib_metadata.add_custom_attribute(CustomAttribute::KeyValue {
@@ -277,6 +385,44 @@ impl Configurable for Compression {
         });
         apply_metadata(&mut zlib_schema, zlib_metadata);
 
+        // Build the Zstd schema.
+        let mut zstd_properties = IndexMap::new();
+        zstd_properties.insert(
+            ALGORITHM_NAME.to_string(),
+            generate_const_string_schema(ZSTD_NAME.to_string()),
+        );
+        zstd_properties.insert(LEVEL_NAME.to_string(), zstd_compression_level_schema);
+
+        let mut dictionary_schema = generate_string_schema();
+        let dictionary_metadata = Metadata::<()>::with_description(
+            "The path of a dictionary, trained with `zstd --train`, to prime the compression with.",
+        );
+        apply_metadata(&mut dictionary_schema, dictionary_metadata);
+        zstd_properties.insert(DICTIONARY_NAME.to_string(), dictionary_schema);
+
+        let mut zstd_schema = generate_struct_schema(zstd_properties, required, None);
+        let mut zstd_metadata = Metadata::<()>::with_title("[Zstandard][zstd] compression.");
+        zstd_metadata.set_description("[zstd]: https://facebook.github.io/zstd/");
+        zstd_metadata.add_custom_attribute(CustomAttribute::KeyValue {
+            key: "logical_name".to_string(),
+            value: "Zstd".to_string(),
+        });
+        apply_metadata(&mut zstd_schema, zstd_metadata);
+
+        // Build the Snappy schema.
+        let mut snappy_schema = generate_internal_tagged_variant_schema(
+            ALGORITHM_NAME.to_string(),
+            SNAPPY_NAME.to_string(),
+        );
+        let mut snappy_metadata = Metadata::<()>::with_description(
+            "[Snappy][snappy] compression, with its framing format.\n\n[snappy]: https://github.com/google/snappy/blob/main/docs/README.md",
+        );
+        snappy_metadata.add_custom_attribute(CustomAttribute::KeyValue {
+            key: "logical_name".to_string(),
+            value: "Snappy".to_string(),
+        });
+        apply_metadata(&mut snappy_schema, snappy_metadata);
+
         Ok(generate_one_of_schema(&[
             // Handle the condensed string form.
             generate_enum_schema(const_values),
@@ -284,42 +430,23 @@ impl Configurable for Compression {
             none_schema,
             gzip_schema,
             zlib_schema,
+            zstd_schema,
+            snappy_schema,
         ]))
     }
 }
 
-/// Compression level.
-#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
-pub struct CompressionLevel(flate2::Compression);
-
-impl CompressionLevel {
-    #[cfg(test)]
-    const fn new(level: u32) -> Self {
-        Self(flate2::Compression::new(level))
-    }
-
-    const fn const_default() -> Self {
-        Self(flate2::Compression::new(6))
-    }
-
-    const fn none() -> Self {
-        Self(flate2::Compression::none())
-    }
-
-    const fn best() -> Self {
-        Self(flate2::Compression::best())
-    }
-
-    const fn fast() -> Self {
-        Self(flate2::Compression::fast())
-    }
-
-    pub const fn as_flate2(self) -> flate2::Compression {
-        self.0
-    }
+/// A compression level as configured, before the algorithm it applies to is known.
+#[derive(Clone, Copy, Debug)]
+enum ConfiguredLevel {
+    None,
+    Fast,
+    Default,
+    Best,
+    Number(u64),
 }
 
-impl<'de> de::Deserialize<'de> for CompressionLevel {
+impl<'de> de::Deserialize<'de> for ConfiguredLevel {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: de::Deserializer<'de>,
@@ -327,7 +454,7 @@ impl<'de> de::Deserialize<'de> for CompressionLevel {
         struct NumberOrString;
 
         impl<'de> de::Visitor<'de> for NumberOrString {
-            type Value = CompressionLevel;
+            type Value = ConfiguredLevel;
 
             fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
                 f.write_str("number or string")
@@ -338,10 +465,10 @@ impl<'de> de::Deserialize<'de> for CompressionLevel {
                 E: de::Error,
             {
                 match s {
-                    "none" => Ok(CompressionLevel::none()),
-                    "fast" => Ok(CompressionLevel::fast()),
-                    "default" => Ok(CompressionLevel::const_default()),
-                    "best" => Ok(CompressionLevel::best()),
+                    "none" => Ok(ConfiguredLevel::None),
+                    "fast" => Ok(ConfiguredLevel::Fast),
+                    "default" => Ok(ConfiguredLevel::Default),
+                    "best" => Ok(ConfiguredLevel::Best),
                     level => {
                         return Err(de::Error::invalid_value(
                             de::Unexpected::Str(level),
@@ -355,24 +482,21 @@ impl<'de> de::Deserialize<'de> for CompressionLevel {
             where
                 E: de::Error,
             {
-                Err(de::Error::invalid_value(
-                    de::Unexpected::Other(&v.to_string()),
-                    &"0, 1, 2, 3, 4, 5, 6, 7, 8 or 9",
-                ))
+                // Some formats, such as TOML, only have signed integers.
+                match u64::try_from(v) {
+                    Ok(v) => self.visit_u64(v),
+                    Err(_) => Err(de::Error::invalid_value(
+                        de::Unexpected::Other(&v.to_string()),
+                        &"0, 1, 2, 3, 4, 5, 6, 7, 8 or 9",
+                    )),
+                }
             }
 
             fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
             where
                 E: de::Error,
             {
-                if v <= 9 {
-                    Ok(CompressionLevel(flate2::Compression::new(v as u32)))
-                } else {
-                    return Err(de::Error::invalid_value(
-                        de::Unexpected::Unsigned(v),
-                        &"0, 1, 2, 3, 4, 5, 6, 7, 8 or 9",
-                    ));
-                }
+                Ok(ConfiguredLevel::Number(v))
             }
         }
 
@@ -380,6 +504,64 @@ impl<'de> de::Deserialize<'de> for CompressionLevel {
     }
 }
 
+/// Compression level.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct CompressionLevel(flate2::Compression);
+
+impl CompressionLevel {
+    #[cfg(test)]
+    const fn new(level: u32) -> Self {
+        Self(flate2::Compression::new(level))
+    }
+
+    const fn const_default() -> Self {
+        Self(flate2::Compression::new(6))
+    }
+
+    const fn none() -> Self {
+        Self(flate2::Compression::none())
+    }
+
+    const fn best() -> Self {
+        Self(flate2::Compression::best())
+    }
+
+    const fn fast() -> Self {
+        Self(flate2::Compression::fast())
+    }
+
+    pub const fn as_flate2(self) -> flate2::Compression {
+        self.0
+    }
+
+    fn from_configured<E: de::Error>(level: Option<ConfiguredLevel>) -> Result<Self, E> {
+        match level {
+            None => Ok(Self::default()),
+            Some(ConfiguredLevel::None) => Ok(Self::none()),
+            Some(ConfiguredLevel::Fast) => Ok(Self::fast()),
+            Some(ConfiguredLevel::Default) => Ok(Self::const_default()),
+            Some(ConfiguredLevel::Best) => Ok(Self::best()),
+            Some(ConfiguredLevel::Number(v)) if v <= 9 => {
+                Ok(Self(flate2::Compression::new(v as u32)))
+            }
+            Some(ConfiguredLevel::Number(v)) => Err(de::Error::invalid_value(
+                de::Unexpected::Unsigned(v),
+                &"0, 1, 2, 3, 4, 5, 6, 7, 8 or 9",
+            )),
+        }
+    }
+}
+
+impl<'de> de::Deserialize<'de> for CompressionLevel {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let level = ConfiguredLevel::deserialize(deserializer)?;
+        Self::from_configured(Some(level))
+    }
+}
+
 impl ser::Serialize for CompressionLevel {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -420,9 +602,183 @@ impl Configurable for CompressionLevel {
     }
 }
 
+/// Zstandard compression level.
+///
+/// `fast` is level 1, `default` is level 3, and `best` is level 19, the highest one that the
+/// `zstd` command line tool allows without `--ultra`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ZstdCompressionLevel(i32);
+
+impl ZstdCompressionLevel {
+    const MAX: u64 = 22;
+
+    #[cfg(test)]
+    const fn new(level: i32) -> Self {
+        Self(level)
+    }
+
+    const fn const_default() -> Self {
+        Self(3)
+    }
+
+    const fn fast() -> Self {
+        Self(1)
+    }
+
+    const fn best() -> Self {
+        Self(19)
+    }
+
+    pub const fn as_i32(self) -> i32 {
+        self.0
+    }
+
+    fn from_configured<E: de::Error>(level: Option<ConfiguredLevel>) -> Result<Self, E> {
+        match level {
+            None | Some(ConfiguredLevel::Default) => Ok(Self::const_default()),
+            Some(ConfiguredLevel::Fast) => Ok(Self::fast()),
+            Some(ConfiguredLevel::Best) => Ok(Self::best()),
+            Some(ConfiguredLevel::None) => Err(de::Error::invalid_value(
+                de::Unexpected::Str("none"),
+                &r#""fast", "best" or "default""#,
+            )),
+            Some(ConfiguredLevel::Number(v)) if (1..=Self::MAX).contains(&v) => Ok(Self(v as i32)),
+            Some(ConfiguredLevel::Number(v)) => Err(de::Error::invalid_value(
+                de::Unexpected::Unsigned(v),
+                &"a level between 1 and 22",
+            )),
+        }
+    }
+}
+
+impl Default for ZstdCompressionLevel {
+    fn default() -> Self {
+        Self::const_default()
+    }
+}
+
+impl<'de> de::Deserialize<'de> for ZstdCompressionLevel {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let level = ConfiguredLevel::deserialize(deserializer)?;
+        Self::from_configured(Some(level))
+    }
+}
+
+impl ser::Serialize for ZstdCompressionLevel {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        const FAST: ZstdCompressionLevel = ZstdCompressionLevel::fast();
+        const BEST: ZstdCompressionLevel = ZstdCompressionLevel::best();
+
+        match *self {
+            FAST => serializer.serialize_str("fast"),
+            BEST => serializer.serialize_str("best"),
+            level => serializer.serialize_i32(level.0),
+        }
+    }
+}
+
+// TODO: Consider an approach for generating schema of "string or number" structure used by this type.
+impl Configurable for ZstdCompressionLevel {
+    fn referenceable_name() -> Option<&'static str> {
+        Some(std::any::type_name::<Self>())
+    }
+
+    fn description() -> Option<&'static str> {
+        Some("Zstandard compression level.")
+    }
+
+    fn generate_schema(_: &mut SchemaGenerator) -> Result<SchemaObject, GenerateError> {
+        let string_consts = ["fast", "best", "default"]
+            .iter()
+            .map(|s| serde_json::Value::from(*s));
+
+        let level_consts = (1u64..=Self::MAX).map(serde_json::Value::from);
+
+        let valid_values = string_consts.chain(level_consts).collect();
+        Ok(generate_enum_schema(valid_values))
+    }
+}
+
+/// The dictionaries loaded so far, which are kept for the lifetime of the process.
+static DICTIONARIES: Lazy<Mutex<Vec<&'static LoadedDictionary>>> = Lazy::new(Mutex::default);
+
+#[derive(Eq, PartialEq)]
+struct LoadedDictionary {
+    path: PathBuf,
+    bytes: Vec<u8>,
+}
+
+/// A [dictionary][dictionary] that zstd compression is primed with.
+///
+/// Dictionaries trained on samples of the data improve the compression of small payloads, such as
+/// small batches of events, whose content is mostly repeated from one payload to the next.
+///
+/// The dictionaries are loaded along with the configuration, and kept for the lifetime of the
+/// process, so that the compression configuration can still be copied. Reloading the configuration
+/// only loads the dictionaries again if their files changed.
+///
+/// [dictionary]: https://facebook.github.io/zstd/#small-data
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct ZstdDictionary(&'static LoadedDictionary);
+
+impl ZstdDictionary {
+    /// Loads the dictionary from the file at the given path.
+    ///
+    /// # Errors
+    ///
+    /// If the file can't be read, or doesn't hold a dictionary that zstd accepts, an error is
+    /// returned.
+    pub fn load(path: PathBuf) -> Result<Self, String> {
+        let bytes = std::fs::read(&path).map_err(|error| {
+            format!(
+                "Failed to read zstd dictionary {}: {}",
+                path.display(),
+                error
+            )
+        })?;
+        zstd::stream::write::Encoder::with_dictionary(io::sink(), 0, &bytes)
+            .map_err(|error| format!("Invalid zstd dictionary {}: {}", path.display(), error))?;
+
+        let dictionary = LoadedDictionary { path, bytes };
+        let mut dictionaries = DICTIONARIES.lock().expect("poisoned lock");
+        let loaded = match dictionaries.iter().find(|loaded| ***loaded == dictionary) {
+            Some(loaded) => *loaded,
+            None => {
+                let loaded: &'static LoadedDictionary = Box::leak(Box::new(dictionary));
+                dictionaries.push(loaded);
+                loaded
+            }
+        };
+
+        Ok(Self(loaded))
+    }
+
+    pub fn path(&self) -> &'static Path {
+        &self.0.path
+    }
+
+    pub fn as_bytes(&self) -> &'static [u8] {
+        &self.0.bytes
+    }
+}
+
+impl fmt::Debug for ZstdDictionary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("ZstdDictionary").field(&self.0.path).finish()
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use super::{Compression, CompressionLevel};
+    use std::io::Write;
+
+    use super::{Compression, CompressionLevel, ZstdCompressionLevel, ZstdDictionary};
 
     #[test]
     fn deserialization() {
@@ -461,6 +817,23 @@ mod test {
                 r#"{"algorithm": "zlib", "level": 8}"#,
                 Compression::Zlib(CompressionLevel::new(8)),
             ),
+            (r#""zstd""#, Compression::zstd_default()),
+            (
+                r#"{"algorithm": "zstd", "level": "best"}"#,
+                Compression::Zstd {
+                    level: ZstdCompressionLevel::best(),
+                    dictionary: None,
+                },
+            ),
+            (
+                r#"{"algorithm": "zstd", "level": 15}"#,
+                Compression::Zstd {
+                    level: ZstdCompressionLevel::new(15),
+                    dictionary: None,
+                },
+            ),
+            (r#""snappy""#, Compression::Snappy),
+            (r#"{"algorithm": "snappy"}"#, Compression::Snappy),
         ];
         for (sources, result) in fixtures_valid.iter() {
             let deserialized: Result<Compression, _> = serde_json::from_str(sources);
@@ -474,11 +847,11 @@ mod test {
             ),
             (
                 r#""b42""#,
-                r#"invalid value: string "b42", expected "none" or "gzip" or "zlib" at line 1 column 5"#,
+                r#"invalid value: string "b42", expected "none", "gzip", "zlib", "zstd" or "snappy" at line 1 column 5"#,
             ),
             (
                 r#"{"algorithm": "b42"}"#,
-                r#"unknown variant `b42`, expected one of `none`, `gzip`, `zlib` at line 1 column 20"#,
+                r#"unknown variant `b42`, expected one of `none`, `gzip`, `zlib`, `zstd`, `snappy` at line 1 column 20"#,
             ),
             (
                 r#"{"algorithm": "none", "level": "default"}"#,
@@ -498,7 +871,23 @@ mod test {
             ),
             (
                 r#"{"algorithm": "gzip", "level": "default", "key": 42}"#,
-                r#"unknown field `key`, expected `algorithm` or `level` at line 1 column 47"#,
+                r#"unknown field `key`, expected one of `algorithm`, `level`, `dictionary` at line 1 column 47"#,
+            ),
+            (
+                r#"{"algorithm": "gzip", "level": 10}"#,
+                r#"invalid value: integer `10`, expected 0, 1, 2, 3, 4, 5, 6, 7, 8 or 9 at line 1 column 34"#,
+            ),
+            (
+                r#"{"algorithm": "gzip", "dictionary": "/etc/vector/logs.dict"}"#,
+                r#"unknown field `dictionary`, expected `algorithm` or `level` at line 1 column 60"#,
+            ),
+            (
+                r#"{"algorithm": "zstd", "level": 23}"#,
+                r#"invalid value: integer `23`, expected a level between 1 and 22 at line 1 column 34"#,
+            ),
+            (
+                r#"{"algorithm": "snappy", "level": "best"}"#,
+                r#"unknown field `level`, there are no fields at line 1 column 40"#,
             ),
         ];
         for (source, result) in fixtures_invalid.iter() {
@@ -516,6 +905,12 @@ mod test {
             Compression::Gzip(CompressionLevel::new(7)),
             Compression::Zlib(CompressionLevel::best()),
             Compression::Zlib(CompressionLevel::new(7)),
+            Compression::zstd_default(),
+            Compression::Zstd {
+                level: ZstdCompressionLevel::fast(),
+                dictionary: None,
+            },
+            Compression::Snappy,
         ];
 
         for v in fixtures_valid {
@@ -524,4 +919,39 @@ mod test {
             serde_json::from_value::<Compression>(value).unwrap();
         }
     }
+
+    #[test]
+    fn zstd_dictionary() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"some samples of the events to compress")
+            .unwrap();
+
+        let config = format!(
+            r#"{{"algorithm": "zstd", "dictionary": {:?}}}"#,
+            file.path().to_str().unwrap()
+        );
+        let compression: Compression = serde_json::from_str(&config).unwrap();
+        let dictionary = match compression {
+            Compression::Zstd {
+                dictionary: Some(dictionary),
+                ..
+            } => dictionary,
+            _ => panic!("expected zstd compression with a dictionary"),
+        };
+        assert_eq!(dictionary.path(), file.path());
+        assert_eq!(
+            dictionary.as_bytes(),
+            b"some samples of the events to compress"
+        );
+
+        // The dictionary is only loaded once.
+        let reloaded = ZstdDictionary::load(file.path().to_owned()).unwrap();
+        assert!(std::ptr::eq(dictionary.as_bytes(), reloaded.as_bytes()));
+
+        let value = serde_json::to_value(compression).unwrap();
+        assert_eq!(
+            serde_json::from_value::<Compression>(value).unwrap(),
+            compression
+        );
+    }
 }
@@ -34,31 +34,27 @@ mod test {
                 MetricKind::Absolute,
                 MetricValue::Gauge { value: 0.0 },
             )
-            .with_tags(Some([("z".to_string(), "z".to_string())].into())),
+            .with_tags(Some([("z".into(), "z".to_string())].into())),
             Metric::new(
                 "metric_4",
                 MetricKind::Absolute,
                 MetricValue::Gauge { value: 0.0 },
             )
-            .with_tags(Some([("a".to_string(), "a".to_string())].into())),
+            .with_tags(Some([("a".into(), "a".to_string())].into())),
             Metric::new(
                 "metric_4",
                 MetricKind::Absolute,
                 MetricValue::Gauge { value: 0.0 },
             )
             .with_tags(Some(
-                [
-                    ("a".to_string(), "a".to_string()),
-                    ("b".to_string(), "b".to_string()),
-                ]
-                .into(),
+                [("a".into(), "a".to_string()), ("b".into(), "b".to_string())].into(),
             )),
             Metric::new(
                 "metric_4",
                 MetricKind::Absolute,
                 MetricValue::Gauge { value: 0.0 },
             )
-            .with_tags(Some([("b".to_string(), "b".to_string())].into())),
+            .with_tags(Some([("b".into(), "b".to_string())].into())),
         ];
 
         let mut rand_metrics = sorted_metrics.clone();
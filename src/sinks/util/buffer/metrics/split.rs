@@ -119,6 +119,7 @@ impl MetricSplit for AggregatedSummarySplitter {
             | MetricValue::Set { .. }
             | MetricValue::Distribution { .. }
             | MetricValue::AggregatedHistogram { .. }
+            | MetricValue::ExponentialHistogram { .. }
             | MetricValue::Sketch { .. } => {
                 SplitIterator::single(Metric::from_parts(series, data, metadata))
             }
@@ -272,7 +273,7 @@ mod tests {
             };
 
             Some(
-                vec![("quantile".to_owned(), quantile.to_quantile_string())]
+                vec![("quantile".into(), quantile.to_quantile_string())]
                     .into_iter()
                     .collect(),
             )
@@ -14,7 +14,9 @@ use vector_core::{
 };
 
 use super::{
-    buffer::metrics::MetricNormalize, IncrementalRequestBuilder, Normalizer, RequestBuilder,
+    buffer::metrics::MetricNormalize,
+    request_builder::{build_split_requests, SplittableInput},
+    IncrementalRequestBuilder, Normalizer, RequestBuilder,
 };
 
 impl<T: ?Sized> SinkBuilderExt for T where T: Stream {}
@@ -111,6 +113,35 @@ pub trait SinkBuilderExt: Stream {
         })
     }
 
+    /// Constructs a [`Stream`] which transforms the input into a number of requests suitable for
+    /// sending to downstream services, splitting inputs that are too large to send in one request.
+    ///
+    /// This behaves like `request_builder`, except that when the encoded payload of an input is
+    /// larger than [`RequestBuilder::max_payload_bytes`], the input is split in half and each half
+    /// is encoded again, recursively, until every payload fits.
+    ///
+    /// Each input is transformed concurrently, up to the given limit.
+    fn splitting_request_builder<B>(
+        self,
+        limit: Option<NonZeroUsize>,
+        builder: B,
+    ) -> ConcurrentMap<Self, Vec<Result<B::Request, B::Error>>>
+    where
+        Self: Sized,
+        Self::Item: SplittableInput + Clone + Send + 'static,
+        B: RequestBuilder<<Self as Stream>::Item> + Send + Sync + 'static,
+        B::Error: Send,
+        B::Request: Send,
+    {
+        let builder = Arc::new(builder);
+
+        self.concurrent_map(limit, move |input| {
+            let builder = Arc::clone(&builder);
+
+            Box::pin(async move { build_split_requests(builder.as_ref(), input) })
+        })
+    }
+
     /// Constructs a [`Stream`] which transforms the input into a number of requests suitable for
     /// sending to downstream services.
     ///
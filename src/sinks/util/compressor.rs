@@ -1,34 +1,69 @@
-use std::io;
+use std::{fmt, io};
 
 use bytes::{BufMut, BytesMut};
 use flate2::write::{GzEncoder, ZlibEncoder};
+use snap::write::FrameEncoder;
 
 use super::Compression;
 
+type ZstdEncoder = zstd::stream::write::Encoder<'static, bytes::buf::Writer<BytesMut>>;
+
 enum Writer {
     Plain(bytes::buf::Writer<BytesMut>),
     Gzip(GzEncoder<bytes::buf::Writer<BytesMut>>),
     Zlib(ZlibEncoder<bytes::buf::Writer<BytesMut>>),
+    Zstd(ZstdEncoder),
+    Snappy(FrameEncoder<bytes::buf::Writer<BytesMut>>),
 }
 
 impl Writer {
+    fn new(compression: Compression, capacity: usize) -> Self {
+        let writer = BytesMut::with_capacity(capacity).writer();
+        match compression {
+            Compression::None => Writer::Plain(writer),
+            Compression::Gzip(level) => Writer::Gzip(GzEncoder::new(writer, level.as_flate2())),
+            Compression::Zlib(level) => Writer::Zlib(ZlibEncoder::new(writer, level.as_flate2())),
+            Compression::Zstd { level, dictionary } => {
+                // The level is validated along with the configuration, and the dictionary when
+                // it's loaded, so that the encoder can't fail to be created here.
+                let encoder = match dictionary {
+                    Some(dictionary) => {
+                        ZstdEncoder::with_dictionary(writer, level.as_i32(), dictionary.as_bytes())
+                    }
+                    None => ZstdEncoder::new(writer, level.as_i32()),
+                };
+                Writer::Zstd(encoder.expect("zstd encoder should not fail to be created"))
+            }
+            Compression::Snappy => Writer::Snappy(FrameEncoder::new(writer)),
+        }
+    }
+
     pub fn get_ref(&self) -> &BytesMut {
         match self {
             Writer::Plain(inner) => inner.get_ref(),
             Writer::Gzip(inner) => inner.get_ref().get_ref(),
             Writer::Zlib(inner) => inner.get_ref().get_ref(),
+            Writer::Zstd(inner) => inner.get_ref().get_ref(),
+            Writer::Snappy(inner) => inner.get_ref().get_ref(),
+        }
+    }
+
+    fn finish(self) -> io::Result<bytes::buf::Writer<BytesMut>> {
+        match self {
+            Writer::Plain(writer) => Ok(writer),
+            Writer::Gzip(writer) => writer.finish(),
+            Writer::Zlib(writer) => writer.finish(),
+            Writer::Zstd(writer) => writer.finish(),
+            Writer::Snappy(writer) => writer
+                .into_inner()
+                .map_err(|error| io::Error::new(error.error().kind(), error.error().to_string())),
         }
     }
 }
 
 impl From<Compression> for Writer {
     fn from(compression: Compression) -> Self {
-        let writer = BytesMut::with_capacity(1_024).writer();
-        match compression {
-            Compression::None => Writer::Plain(writer),
-            Compression::Gzip(level) => Writer::Gzip(GzEncoder::new(writer, level.as_flate2())),
-            Compression::Zlib(level) => Writer::Zlib(ZlibEncoder::new(writer, level.as_flate2())),
-        }
+        Writer::new(compression, 1_024)
     }
 }
 
@@ -39,6 +74,8 @@ impl io::Write for Writer {
             Writer::Plain(inner_buf) => inner_buf.write(buf),
             Writer::Gzip(writer) => writer.write(buf),
             Writer::Zlib(writer) => writer.write(buf),
+            Writer::Zstd(writer) => writer.write(buf),
+            Writer::Snappy(writer) => writer.write(buf),
         }
     }
 
@@ -47,6 +84,8 @@ impl io::Write for Writer {
             Writer::Plain(writer) => writer.flush(),
             Writer::Gzip(writer) => writer.flush(),
             Writer::Zlib(writer) => writer.flush(),
+            Writer::Zstd(writer) => writer.flush(),
+            Writer::Snappy(writer) => writer.flush(),
         }
     }
 }
@@ -60,6 +99,14 @@ pub struct Compressor {
 }
 
 impl Compressor {
+    /// Creates a compressor whose buffer starts with the given capacity.
+    pub fn with_capacity(compression: Compression, capacity: usize) -> Self {
+        Compressor {
+            compression,
+            inner: Writer::new(compression, capacity),
+        }
+    }
+
     /// Gets a mutable reference to the underlying buffer.
     pub fn get_ref(&self) -> &BytesMut {
         self.inner.get_ref()
@@ -84,12 +131,7 @@ impl Compressor {
     /// If the compressor encounters an I/O error while finalizing the payload, an error
     /// variant will be returned.
     pub fn finish(self) -> io::Result<BytesMut> {
-        let buf = match self.inner {
-            Writer::Plain(writer) => writer,
-            Writer::Gzip(writer) => writer.finish()?,
-            Writer::Zlib(writer) => writer.finish()?,
-        }
-        .into_inner();
+        let buf = self.inner.finish()?.into_inner();
 
         Ok(buf)
     }
@@ -104,16 +146,16 @@ impl Compressor {
     ///
     /// Consider using `finish` if catching these scenarios is important.
     pub fn into_inner(self) -> BytesMut {
-        match self.inner {
-            Writer::Plain(writer) => writer,
-            Writer::Gzip(writer) => writer
-                .finish()
-                .expect("gzip writer should not fail to finish"),
-            Writer::Zlib(writer) => writer
-                .finish()
-                .expect("zlib writer should not fail to finish"),
-        }
-        .into_inner()
+        let compression = self.compression;
+        self.inner
+            .finish()
+            .unwrap_or_else(|error| {
+                panic!(
+                    "{} writer should not fail to finish: {}",
+                    compression, error
+                )
+            })
+            .into_inner()
     }
 }
 
@@ -128,6 +170,14 @@ impl io::Write for Compressor {
     }
 }
 
+impl fmt::Debug for Compressor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Compressor")
+            .field("compression", &self.compression)
+            .finish_non_exhaustive()
+    }
+}
+
 impl From<Compression> for Compressor {
     fn from(compression: Compression) -> Self {
         Compressor {
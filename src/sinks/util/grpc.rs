@@ -0,0 +1,343 @@
+//! Shared client settings for gRPC-based sinks.
+//!
+//! gRPC multiplexes every request over a single HTTP/2 connection, which caps the throughput to a
+//! downstream service, and pins all of the traffic to one instance when the service is made of
+//! several instances behind a single host name (such as a Kubernetes headless service). The
+//! [`GrpcChannel`] built here opens several connections instead, each pinned to one of the
+//! addresses the host resolves to, and balances requests across them round-robin.
+
+use std::{
+    io, mem,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use bytes::{BufMut, BytesMut};
+use futures::future::BoxFuture;
+use http::{HeaderValue, Uri};
+use http_body::Body as _;
+use hyper_openssl::HttpsConnector;
+use hyper_proxy::ProxyConnector;
+use tokio::sync::Semaphore;
+use tonic::body::BoxBody;
+use vector_config::configurable_component;
+
+use crate::{
+    config::ProxyConfig,
+    http::{build_pinned_proxy_connector, build_proxy_connector, AddressPin, ProxyTcpConnector},
+    tls::MaybeTlsSettings,
+};
+
+// Every gRPC message has a five byte header: a compressed flag, and the length of the message.
+const GRPC_MESSAGE_HEADER_LEN: usize = mem::size_of::<u8>() + mem::size_of::<u32>();
+
+type GrpcHyperClient = hyper::Client<ProxyConnector<HttpsConnector<ProxyTcpConnector>>, BoxBody>;
+
+const fn default_connections() -> usize {
+    1
+}
+
+/// gRPC client connection settings.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct GrpcClientConfig {
+    /// The interval, in seconds, between HTTP/2 keepalive pings.
+    ///
+    /// Pings are also sent on idle connections, so that connections broken by intermediaries such
+    /// as load balancers are detected before a request is sent over them. If not set, no keepalive
+    /// pings are sent.
+    #[serde(default)]
+    pub keepalive_interval_secs: Option<u64>,
+
+    /// The time, in seconds, to wait for a keepalive ping to be acknowledged before the connection is closed.
+    ///
+    /// Only used if `keepalive_interval_secs` is set.
+    #[serde(default)]
+    pub keepalive_timeout_secs: Option<u64>,
+
+    /// The maximum number of in-flight requests on each connection.
+    ///
+    /// Further requests wait until one of the in-flight requests on that connection completes. If
+    /// not set, the number of in-flight requests is only limited by the downstream service.
+    #[serde(default)]
+    pub max_concurrent_streams: Option<usize>,
+
+    /// The number of connections to open to the endpoint.
+    ///
+    /// The host of the endpoint is resolved, and each connection is pinned to one of the resolved
+    /// addresses in turn. Requests are balanced across the connections round-robin.
+    #[serde(default = "default_connections")]
+    #[configurable(validation(range(min = 1)))]
+    pub connections: usize,
+}
+
+impl Default for GrpcClientConfig {
+    fn default() -> Self {
+        Self {
+            keepalive_interval_secs: None,
+            keepalive_timeout_secs: None,
+            max_concurrent_streams: None,
+            connections: default_connections(),
+        }
+    }
+}
+
+impl GrpcClientConfig {
+    /// Builds a channel sending requests to the given endpoint.
+    pub fn build_channel(
+        &self,
+        uri: Uri,
+        tls_settings: &MaybeTlsSettings,
+        proxy_config: &ProxyConfig,
+    ) -> crate::Result<GrpcChannel> {
+        let connections = if self.connections > 1 {
+            (0..self.connections)
+                .map(|index| {
+                    let connector = build_pinned_proxy_connector(
+                        tls_settings.clone(),
+                        proxy_config,
+                        AddressPin::Index(index),
+                    )?;
+                    Ok(self.build_connection(connector))
+                })
+                .collect::<crate::Result<Vec<_>>>()?
+        } else {
+            let connector = build_proxy_connector(tls_settings.clone(), proxy_config)?;
+            vec![self.build_connection(connector)]
+        };
+
+        Ok(GrpcChannel::new(uri, connections))
+    }
+
+    /// Builds a channel sending requests to the given endpoint, with all of its connections made
+    /// to the given address instead of the addresses the host of the endpoint resolves to.
+    pub fn build_pinned_channel(
+        &self,
+        uri: Uri,
+        address: SocketAddr,
+        tls_settings: &MaybeTlsSettings,
+        proxy_config: &ProxyConfig,
+    ) -> crate::Result<GrpcChannel> {
+        let connections = (0..self.connections)
+            .map(|_| {
+                let connector = build_pinned_proxy_connector(
+                    tls_settings.clone(),
+                    proxy_config,
+                    AddressPin::Fixed(address),
+                )?;
+                Ok(self.build_connection(connector))
+            })
+            .collect::<crate::Result<Vec<_>>>()?;
+
+        Ok(GrpcChannel::new(uri, connections))
+    }
+
+    fn build_connection(
+        &self,
+        connector: ProxyConnector<HttpsConnector<ProxyTcpConnector>>,
+    ) -> GrpcConnection {
+        let mut builder = hyper::Client::builder();
+        builder.http2_only(true);
+        if let Some(interval) = self.keepalive_interval_secs {
+            builder
+                .http2_keep_alive_interval(Duration::from_secs(interval))
+                .http2_keep_alive_while_idle(true);
+            if let Some(timeout) = self.keepalive_timeout_secs {
+                builder.http2_keep_alive_timeout(Duration::from_secs(timeout));
+            }
+        }
+
+        GrpcConnection {
+            client: builder.build(connector),
+            streams: self
+                .max_concurrent_streams
+                .map(|streams| Arc::new(Semaphore::new(streams))),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct GrpcConnection {
+    client: GrpcHyperClient,
+    streams: Option<Arc<Semaphore>>,
+}
+
+/// A gRPC transport balancing requests across one or more HTTP/2 connections.
+///
+/// Every request is sent to the endpoint of the channel, whatever the URI it was built with.
+#[derive(Clone, Debug)]
+pub struct GrpcChannel {
+    uri: Uri,
+    connections: Arc<[GrpcConnection]>,
+    next: Arc<AtomicUsize>,
+    zstd_compression: bool,
+}
+
+impl GrpcChannel {
+    fn new(uri: Uri, connections: Vec<GrpcConnection>) -> Self {
+        Self {
+            uri,
+            connections: connections.into(),
+            next: Arc::new(AtomicUsize::new(0)),
+            zstd_compression: false,
+        }
+    }
+
+    /// Gets the endpoint requests are sent to.
+    pub const fn uri(&self) -> &Uri {
+        &self.uri
+    }
+
+    /// Gets a copy of this channel, sending requests to another endpoint.
+    pub fn with_uri(&self, uri: Uri) -> Self {
+        Self {
+            uri,
+            ..self.clone()
+        }
+    }
+
+    /// Compresses the messages of requests with zstd.
+    ///
+    /// `tonic` only supports gzip compression, so the messages it encodes are compressed here
+    /// instead, before being sent.
+    pub const fn with_zstd_compression(mut self) -> Self {
+        self.zstd_compression = true;
+        self
+    }
+}
+
+impl tower::Service<hyper::Request<BoxBody>> for GrpcChannel {
+    type Response = hyper::Response<hyper::Body>;
+    type Error = crate::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, mut req: hyper::Request<BoxBody>) -> Self::Future {
+        let uri = Uri::builder()
+            .scheme(self.uri.scheme().unwrap().clone())
+            .authority(self.uri.authority().unwrap().clone())
+            .path_and_query(req.uri().path_and_query().unwrap().clone())
+            .build()
+            .unwrap();
+
+        *req.uri_mut() = uri;
+
+        let index = self.next.fetch_add(1, Ordering::Relaxed) % self.connections.len();
+        let connection = self.connections[index].clone();
+        let zstd_compression = self.zstd_compression;
+
+        Box::pin(async move {
+            let req = if zstd_compression {
+                compress_zstd(req).await?
+            } else {
+                req
+            };
+
+            let _permit = match connection.streams {
+                Some(streams) => Some(streams.acquire_owned().await?),
+                None => None,
+            };
+            connection.client.request(req).await.map_err(Into::into)
+        })
+    }
+}
+
+/// Compresses each of the messages of the request body with zstd.
+async fn compress_zstd(req: hyper::Request<BoxBody>) -> crate::Result<hyper::Request<BoxBody>> {
+    let (mut parts, body) = req.into_parts();
+    let body = hyper::body::to_bytes(body).await?;
+
+    let mut compressed = BytesMut::with_capacity(body.len());
+    let mut remaining = &body[..];
+    while !remaining.is_empty() {
+        if remaining.len() < GRPC_MESSAGE_HEADER_LEN {
+            return Err(invalid_message().into());
+        }
+        let message_len = u32::from_be_bytes(
+            remaining[1..GRPC_MESSAGE_HEADER_LEN]
+                .try_into()
+                .expect("there must be four bytes remaining in the header slice"),
+        ) as usize;
+        let message = remaining
+            .get(GRPC_MESSAGE_HEADER_LEN..GRPC_MESSAGE_HEADER_LEN + message_len)
+            .ok_or_else(invalid_message)?;
+
+        let message = zstd::encode_all(message, 0)?;
+        let compressed_len = u32::try_from(message.len()).map_err(|_| invalid_message())?;
+        compressed.put_u8(1);
+        compressed.put_u32(compressed_len);
+        compressed.put_slice(&message);
+
+        remaining = &remaining[GRPC_MESSAGE_HEADER_LEN + message_len..];
+    }
+
+    parts
+        .headers
+        .insert("grpc-encoding", HeaderValue::from_static("zstd"));
+    let body = http_body::Full::new(compressed.freeze())
+        .map_err(|never| match never {})
+        .boxed_unsync();
+    Ok(hyper::Request::from_parts(parts, body))
+}
+
+fn invalid_message() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, "invalid gRPC message framing")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_grpc_client_config() {
+        let config: GrpcClientConfig = toml::from_str(
+            r#"
+            keepalive_interval_secs = 30
+            max_concurrent_streams = 100
+            connections = 4
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config,
+            GrpcClientConfig {
+                keepalive_interval_secs: Some(30),
+                keepalive_timeout_secs: None,
+                max_concurrent_streams: Some(100),
+                connections: 4,
+            }
+        );
+
+        let config: GrpcClientConfig = toml::from_str("").unwrap();
+        assert_eq!(config, GrpcClientConfig::default());
+    }
+
+    #[test]
+    fn builds_one_connection_per_sub_channel() {
+        let config = GrpcClientConfig {
+            connections: 3,
+            ..Default::default()
+        };
+        let channel = config
+            .build_channel(
+                "http://localhost:6000".parse().unwrap(),
+                &MaybeTlsSettings::Raw(()),
+                &ProxyConfig::default(),
+            )
+            .unwrap();
+        assert_eq!(channel.connections.len(), 3);
+
+        let healthcheck = channel.with_uri("http://localhost:6001".parse().unwrap());
+        assert_eq!(healthcheck.uri().port_u16(), Some(6001));
+        assert!(Arc::ptr_eq(&healthcheck.connections, &channel.connections));
+    }
+}
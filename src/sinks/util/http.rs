@@ -457,6 +457,34 @@ impl RetryLogic for HttpRetryLogic {
             _ => RetryAction::DontRetry(format!("response status: {}", status).into()),
         }
     }
+
+    fn response_status(&self, response: &Self::Response) -> Option<StatusCode> {
+        Some(response.status())
+    }
+
+    fn retry_after(&self, response: &Self::Response) -> Option<Duration> {
+        retry_after(response)
+    }
+}
+
+/// Returns the delay asked for by the `Retry-After` header of a response, given either in seconds
+/// or as a date.
+pub fn retry_after<B>(response: &http::Response<B>) -> Option<Duration> {
+    let value = response
+        .headers()
+        .get(http::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim();
+    match value.parse::<u64>() {
+        Ok(seconds) => Some(Duration::from_secs(seconds)),
+        Err(_) => {
+            let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+            (date.with_timezone(&chrono::Utc) - chrono::Utc::now())
+                .to_std()
+                .ok()
+        }
+    }
 }
 
 /// A more generic version of `HttpRetryLogic` that accepts anything that can be converted
@@ -507,6 +535,10 @@ where
             _ => RetryAction::DontRetry(format!("Http status: {}", status).into()),
         }
     }
+
+    fn response_status(&self, response: &T) -> Option<StatusCode> {
+        Some((self.func)(response))
+    }
 }
 
 impl<F, T> Clone for HttpStatusRetryLogic<F, T>
@@ -574,6 +606,31 @@ mod test {
             .is_not_retryable());
     }
 
+    #[test]
+    fn util_http_retry_after() {
+        let response = |value: &str| {
+            Response::builder()
+                .status(429)
+                .header("Retry-After", value)
+                .body(Bytes::new())
+                .unwrap()
+        };
+
+        assert_eq!(
+            retry_after(&response("120")),
+            Some(Duration::from_secs(120))
+        );
+        let date = (chrono::Utc::now() + chrono::Duration::seconds(60)).to_rfc2822();
+        let delay = retry_after(&response(&date)).unwrap();
+        assert!(delay > Duration::from_secs(50) && delay <= Duration::from_secs(60));
+        // A date in the past means no delay.
+        assert_eq!(
+            retry_after(&response("Wed, 21 Oct 2015 07:28:00 GMT")),
+            None
+        );
+        assert_eq!(retry_after(&response("soon")), None);
+    }
+
     #[tokio::test]
     async fn util_http_it_makes_http_requests() {
         let addr = next_addr();
@@ -0,0 +1,111 @@
+//! JSON payloads whose strings are templates, such as the message layouts of chat services.
+
+use serde_json::{Map, Value};
+
+use crate::{
+    event::Event,
+    template::{Template, TemplateParseError, TemplateRenderingError},
+};
+
+/// A JSON value whose strings are rendered from each event.
+#[derive(Clone, Debug)]
+pub enum JsonTemplate {
+    String(Template),
+    Array(Vec<JsonTemplate>),
+    Object(Vec<(String, JsonTemplate)>),
+    Fixed(Value),
+}
+
+impl JsonTemplate {
+    pub fn render(&self, event: &Event) -> Result<Value, TemplateRenderingError> {
+        Ok(match self {
+            Self::String(template) => Value::String(template.render_string(event)?),
+            Self::Array(items) => Value::Array(
+                items
+                    .iter()
+                    .map(|item| item.render(event))
+                    .collect::<Result<_, _>>()?,
+            ),
+            Self::Object(fields) => Value::Object(
+                fields
+                    .iter()
+                    .map(|(key, value)| Ok((key.clone(), value.render(event)?)))
+                    .collect::<Result<Map<_, _>, TemplateRenderingError>>()?,
+            ),
+            Self::Fixed(value) => value.clone(),
+        })
+    }
+}
+
+impl TryFrom<&toml::Value> for JsonTemplate {
+    type Error = TemplateParseError;
+
+    fn try_from(value: &toml::Value) -> Result<Self, Self::Error> {
+        Ok(match value {
+            toml::Value::String(string) => Self::String(Template::try_from(string.as_str())?),
+            toml::Value::Array(items) => Self::Array(
+                items
+                    .iter()
+                    .map(JsonTemplate::try_from)
+                    .collect::<Result<_, _>>()?,
+            ),
+            toml::Value::Table(fields) => Self::Object(
+                fields
+                    .iter()
+                    .map(|(key, value)| Ok((key.clone(), JsonTemplate::try_from(value)?)))
+                    .collect::<Result<_, TemplateParseError>>()?,
+            ),
+            toml::Value::Integer(integer) => Self::Fixed((*integer).into()),
+            toml::Value::Float(float) => Self::Fixed((*float).into()),
+            toml::Value::Boolean(boolean) => Self::Fixed((*boolean).into()),
+            toml::Value::Datetime(datetime) => Self::Fixed(datetime.to_string().into()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::event::LogEvent;
+
+    #[test]
+    fn renders_strings_of_nested_values() {
+        let value: toml::Value = toml::from_str(
+            r#"
+            type = "section"
+            text = { type = "mrkdwn", text = "*{{ service }}*: {{ message }}" }
+            fields = [{ type = "plain_text", text = "{{ host }}" }]
+            expand = true
+            level = 3
+            "#,
+        )
+        .unwrap();
+        let template = JsonTemplate::try_from(&value).unwrap();
+
+        let mut log = LogEvent::from("disk full");
+        log.insert("service", "db");
+        log.insert("host", "db-1");
+
+        assert_eq!(
+            template.render(&log.into()).unwrap(),
+            json!({
+                "type": "section",
+                "text": { "type": "mrkdwn", "text": "*db*: disk full" },
+                "fields": [{ "type": "plain_text", "text": "db-1" }],
+                "expand": true,
+                "level": 3,
+            })
+        );
+    }
+
+    #[test]
+    fn fails_on_missing_fields() {
+        let template = JsonTemplate::try_from(&toml::Value::from("{{ service }}")).unwrap();
+
+        assert!(template
+            .render(&LogEvent::from("disk full").into())
+            .is_err());
+    }
+}
@@ -4,11 +4,17 @@ pub mod buffer;
 pub mod builder;
 pub mod compressor;
 pub mod encoding;
+pub mod grpc;
 pub mod http;
+#[cfg(any(feature = "sinks-msteams", feature = "sinks-slack"))]
+pub mod json_template;
 pub mod metadata;
 pub mod normalizer;
 pub mod partitioner;
 pub mod processed_event;
+pub mod record_partitioner;
+#[cfg(any(feature = "sinks-pagerduty", feature = "sinks-smtp"))]
+pub mod rate_limit;
 pub mod request_builder;
 pub mod retries;
 pub mod service;
@@ -39,7 +45,7 @@ pub use buffer::{
 pub use builder::SinkBuilderExt;
 pub use compressor::Compressor;
 pub use normalizer::Normalizer;
-pub use request_builder::{IncrementalRequestBuilder, RequestBuilder};
+pub use request_builder::{IncrementalRequestBuilder, RequestBuilder, SplittableInput};
 pub use service::{
     Concurrency, ServiceBuilderExt, TowerBatchedSink, TowerPartitionSink, TowerRequestConfig,
     TowerRequestLayer, TowerRequestSettings,
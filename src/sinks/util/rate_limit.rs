@@ -0,0 +1,58 @@
+//! Rate limiting of the events or messages of each key, such as a recipient or a routing key.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    time::Duration,
+};
+
+use tokio::time::Instant;
+
+/// Limits the number of messages sent for each key within a sliding window.
+#[derive(Debug)]
+pub struct RateLimit {
+    num: usize,
+    duration: Duration,
+    sent: HashMap<String, VecDeque<Instant>>,
+}
+
+impl RateLimit {
+    pub fn new(num: usize, duration: Duration) -> Self {
+        Self {
+            num,
+            duration,
+            sent: HashMap::new(),
+        }
+    }
+
+    /// Records a message for the key if it is within the limit, returning whether it is.
+    pub fn check(&mut self, key: &str, now: Instant) -> bool {
+        let sent = self.sent.entry(key.to_string()).or_default();
+        while matches!(sent.front(), Some(at) if now.duration_since(*at) >= self.duration) {
+            sent.pop_front();
+        }
+        if sent.len() >= self.num {
+            return false;
+        }
+        sent.push_back(now);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limits_messages_per_key() {
+        let mut limit = RateLimit::new(2, Duration::from_secs(60));
+        let start = Instant::now();
+
+        assert!(limit.check("api", start));
+        assert!(limit.check("api", start + Duration::from_secs(1)));
+        assert!(!limit.check("api", start + Duration::from_secs(2)));
+        assert!(limit.check("db", start + Duration::from_secs(2)));
+        // The first message is out of the window.
+        assert!(limit.check("api", start + Duration::from_secs(60)));
+        assert!(!limit.check("api", start + Duration::from_secs(60)));
+    }
+}
@@ -0,0 +1,161 @@
+//! The assignment of records to the partitions of a topic, or the shards of a stream, from their
+//! keys.
+
+use std::time::{Duration, Instant};
+
+use vector_config::configurable_component;
+
+/// The seed of the murmur2 hash of the Java producer of Kafka.
+const MURMUR2_SEED: u32 = 0x9747_b28c;
+
+const FNV1A_OFFSET_BASIS: u32 = 0x811c_9dc5;
+const FNV1A_PRIME: u32 = 0x0100_0193;
+
+/// How records are assigned to the partitions, or shards, they are written to.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordPartitioner {
+    /// The murmur2 hash of the key, as the default partitioner of the Java producer of Kafka.
+    ///
+    /// Records without a key stick to a random partition for a short while.
+    Murmur2,
+
+    /// The FNV-1a hash of the key, as the default partitioner of the Sarama producer.
+    ///
+    /// Records without a key stick to a random partition for a short while.
+    Fnv,
+
+    /// Each record is written to a random partition, whatever its key.
+    Random,
+
+    /// The records stick to a random partition for a short while, whatever their key, before
+    /// another is picked.
+    Sticky,
+}
+
+impl RecordPartitioner {
+    /// Hashes the key of a record, when the partitioner assigns records by their key.
+    ///
+    /// The hash is positive, as the partition of the record is the rest of its division by the
+    /// number of partitions.
+    pub fn hash(self, key: &[u8]) -> Option<u32> {
+        match self {
+            Self::Murmur2 => Some(murmur2(key) & 0x7fff_ffff),
+            Self::Fnv => Some(fnv1a(key) & 0x7fff_ffff),
+            Self::Random | Self::Sticky => None,
+        }
+    }
+}
+
+/// A random choice that sticks for a while before another is made.
+pub struct Sticky<T> {
+    linger: Duration,
+    current: Option<(T, Instant)>,
+}
+
+impl<T: Clone> Sticky<T> {
+    pub const fn new(linger: Duration) -> Self {
+        Self {
+            linger,
+            current: None,
+        }
+    }
+
+    /// Gets the current choice, once picked again if it is older than the linger.
+    pub fn get(&mut self, pick: impl FnOnce() -> T) -> T {
+        let now = Instant::now();
+        match &self.current {
+            Some((value, picked)) if now.duration_since(*picked) < self.linger => value.clone(),
+            _ => {
+                let value = pick();
+                self.current = Some((value.clone(), now));
+                value
+            }
+        }
+    }
+}
+
+/// The murmur2 hash, as computed by the `Utils.murmur2` function of the Java client of Kafka.
+pub fn murmur2(data: &[u8]) -> u32 {
+    const M: u32 = 0x5bd1_e995;
+    const R: u32 = 24;
+
+    let mut h = MURMUR2_SEED ^ data.len() as u32;
+    let chunks = data.chunks_exact(4);
+    let tail = chunks.remainder();
+    for chunk in chunks {
+        let mut k = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        k = k.wrapping_mul(M);
+        k ^= k >> R;
+        k = k.wrapping_mul(M);
+        h = h.wrapping_mul(M);
+        h ^= k;
+    }
+
+    if tail.len() >= 3 {
+        h ^= u32::from(tail[2]) << 16;
+    }
+    if tail.len() >= 2 {
+        h ^= u32::from(tail[1]) << 8;
+    }
+    if !tail.is_empty() {
+        h ^= u32::from(tail[0]);
+        h = h.wrapping_mul(M);
+    }
+
+    h ^= h >> 13;
+    h = h.wrapping_mul(M);
+    h ^= h >> 15;
+    h
+}
+
+/// The 32-bit FNV-1a hash.
+pub fn fnv1a(data: &[u8]) -> u32 {
+    data.iter().fold(FNV1A_OFFSET_BASIS, |h, byte| {
+        (h ^ u32::from(*byte)).wrapping_mul(FNV1A_PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn murmur2_matches_java_client() {
+        // The expected hashes are those of the tests of the Java client.
+        assert_eq!(murmur2(b"21") as i32, -973_932_308);
+        assert_eq!(murmur2(b"foobar") as i32, -790_332_482);
+        assert_eq!(murmur2(b"a-little-bit-long-string") as i32, -985_981_536);
+        assert_eq!(
+            murmur2(b"a-little-bit-longer-string") as i32,
+            -1_486_304_829
+        );
+        assert_eq!(murmur2(b"abc") as i32, 479_470_107);
+    }
+
+    #[test]
+    fn hashes_fnv1a() {
+        assert_eq!(fnv1a(b""), 0x811c_9dc5);
+        assert_eq!(fnv1a(b"a"), 0xe40c_292c);
+        assert_eq!(fnv1a(b"foobar"), 0xbf9c_f968);
+    }
+
+    #[test]
+    fn hashes_keys() {
+        assert_eq!(RecordPartitioner::Murmur2.hash(b"21"), Some(1_173_551_340));
+        assert_eq!(RecordPartitioner::Random.hash(b"21"), None);
+        assert_eq!(RecordPartitioner::Sticky.hash(b"21"), None);
+    }
+
+    #[test]
+    fn sticks_for_linger() {
+        let mut sticky = Sticky::new(Duration::from_secs(3600));
+        assert_eq!(sticky.get(|| 1), 1);
+        assert_eq!(sticky.get(|| 2), 1);
+
+        let mut sticky = Sticky::new(Duration::ZERO);
+        assert_eq!(sticky.get(|| 1), 1);
+        assert_eq!(sticky.get(|| 2), 2);
+    }
+}
@@ -1,8 +1,10 @@
 use std::io;
 
 use bytes::Bytes;
+use vector_core::ByteSizeOf;
 
 use super::{encoding::Encoder, Compression, Compressor};
+use crate::internal_events::SinkRequestSplit;
 
 pub struct EncodeResult<P> {
     pub payload: P,
@@ -92,6 +94,126 @@ pub trait RequestBuilder<Input> {
         metadata: Self::Metadata,
         payload: EncodeResult<Self::Payload>,
     ) -> Self::Request;
+
+    /// Gets the maximum size of an uncompressed payload accepted by the downstream service.
+    ///
+    /// This is only enforced by `splitting_request_builder`, which splits inputs whose payload is
+    /// larger than this until every payload fits.
+    fn max_payload_bytes(&self) -> Option<usize> {
+        None
+    }
+}
+
+/// An input to a request builder that can be split into smaller inputs.
+pub trait SplittableInput: Sized {
+    /// Splits the input into two halves.
+    ///
+    /// If the input can't be split any further, such as when it holds a single event, it is handed
+    /// back as-is.
+    fn split_in_half(self) -> Result<(Self, Self), Self>;
+
+    /// Estimates the size of the input, before it's encoded.
+    fn estimated_byte_size(&self) -> usize;
+}
+
+impl<T: ByteSizeOf> SplittableInput for Vec<T> {
+    fn split_in_half(mut self) -> Result<(Self, Self), Self> {
+        if self.len() < 2 {
+            return Err(self);
+        }
+
+        let second = self.split_off(self.len() / 2);
+        Ok((self, second))
+    }
+
+    fn estimated_byte_size(&self) -> usize {
+        self.size_of()
+    }
+}
+
+impl<K: Clone, T: ByteSizeOf> SplittableInput for (K, Vec<T>) {
+    fn split_in_half(self) -> Result<(Self, Self), Self> {
+        let (key, events) = self;
+        match events.split_in_half() {
+            Ok((first, second)) => Ok(((key.clone(), first), (key, second))),
+            Err(events) => Err((key, events)),
+        }
+    }
+
+    fn estimated_byte_size(&self) -> usize {
+        self.1.estimated_byte_size()
+    }
+}
+
+/// Builds the requests for the given input, splitting it in half whenever its encoded payload is
+/// larger than the maximum payload size of the builder.
+///
+/// Inputs are split recursively, so a single input can result in any number of requests, which are
+/// returned in the same order as the events of the input. If a payload is still too large once it
+/// can't be split any further, an error is returned in place of its request.
+///
+/// Encoding consumes the input, so it's copied beforehand in case it has to be split, but only when
+/// its estimated size is at least half of the maximum payload size. Events are generally larger in
+/// memory than encoded, so the encoding of a smaller input would have to more than double in size
+/// to exceed the maximum, in which case it's rejected as if it couldn't be split.
+pub fn build_split_requests<B, Input>(
+    builder: &B,
+    input: Input,
+) -> Vec<Result<B::Request, B::Error>>
+where
+    B: RequestBuilder<Input>,
+    Input: SplittableInput + Clone,
+{
+    let max_payload_bytes = match builder.max_payload_bytes() {
+        Some(max_payload_bytes) => max_payload_bytes,
+        None => {
+            let (metadata, events) = builder.split_input(input);
+            return vec![builder
+                .encode_events(events)
+                .map(|payload| builder.build_request(metadata, payload))];
+        }
+    };
+
+    let mut requests = Vec::new();
+    let mut pending = vec![input];
+    while let Some(input) = pending.pop() {
+        let original =
+            (input.estimated_byte_size() >= max_payload_bytes / 2).then(|| input.clone());
+        let (metadata, events) = builder.split_input(input);
+        let payload = match builder.encode_events(events) {
+            Ok(payload) => payload,
+            Err(error) => {
+                requests.push(Err(error));
+                continue;
+            }
+        };
+
+        if payload.uncompressed_byte_size <= max_payload_bytes {
+            requests.push(Ok(builder.build_request(metadata, payload)));
+            continue;
+        }
+
+        match original.map(SplittableInput::split_in_half) {
+            Some(Ok((first, second))) => {
+                emit!(SinkRequestSplit {
+                    byte_size: payload.uncompressed_byte_size,
+                    max_byte_size: max_payload_bytes,
+                });
+                pending.push(second);
+                pending.push(first);
+            }
+            _ => requests.push(Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "Encoded payload of {} bytes is larger than the limit of {} bytes and can't be split any further.",
+                    payload.uncompressed_byte_size, max_payload_bytes
+                ),
+            )
+            .into())),
+        }
+    }
+
+    requests
 }
 
 /// Generalized interface for defining how a batch of events will incrementally be turned into requests.
@@ -119,3 +241,143 @@ pub trait IncrementalRequestBuilder<Input> {
     /// Builds a request for the given metadata and payload.
     fn build_request(&mut self, metadata: Self::Metadata, payload: Self::Payload) -> Self::Request;
 }
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    thread_local! {
+        static LINE_CLONES: Cell<usize> = Cell::new(0);
+    }
+
+    /// A line that counts how many times lines have been cloned on the current thread.
+    #[derive(Debug)]
+    struct Line(String);
+
+    impl Clone for Line {
+        fn clone(&self) -> Self {
+            LINE_CLONES.with(|clones| clones.set(clones.get() + 1));
+            Self(self.0.clone())
+        }
+    }
+
+    impl ByteSizeOf for Line {
+        fn allocated_bytes(&self) -> usize {
+            self.0.allocated_bytes()
+        }
+    }
+
+    struct LinesEncoder;
+
+    impl Encoder<Vec<Line>> for LinesEncoder {
+        fn encode_input(&self, input: Vec<Line>, writer: &mut dyn io::Write) -> io::Result<usize> {
+            let mut written = 0;
+            for Line(line) in input {
+                writer.write_all(line.as_bytes())?;
+                writer.write_all(b"\n")?;
+                written += line.len() + 1;
+            }
+            Ok(written)
+        }
+    }
+
+    struct LinesRequestBuilder {
+        max_payload_bytes: Option<usize>,
+    }
+
+    impl RequestBuilder<Vec<Line>> for LinesRequestBuilder {
+        type Metadata = usize;
+        type Events = Vec<Line>;
+        type Encoder = LinesEncoder;
+        type Payload = Bytes;
+        type Request = (usize, Bytes);
+        type Error = io::Error;
+
+        fn compression(&self) -> Compression {
+            Compression::None
+        }
+
+        fn encoder(&self) -> &Self::Encoder {
+            &LinesEncoder
+        }
+
+        fn split_input(&self, input: Vec<Line>) -> (Self::Metadata, Self::Events) {
+            (input.len(), input)
+        }
+
+        fn build_request(
+            &self,
+            metadata: Self::Metadata,
+            payload: EncodeResult<Self::Payload>,
+        ) -> Self::Request {
+            (metadata, payload.into_payload())
+        }
+
+        fn max_payload_bytes(&self) -> Option<usize> {
+            self.max_payload_bytes
+        }
+    }
+
+    fn lines(count: usize) -> Vec<Line> {
+        (0..count).map(|i| Line(format!("line {}", i))).collect()
+    }
+
+    #[test]
+    fn splits_oversized_payloads() {
+        // Each line is encoded to 7 bytes.
+        let builder = LinesRequestBuilder {
+            max_payload_bytes: Some(20),
+        };
+
+        let requests = build_split_requests(&builder, lines(6))
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        let counts = requests.iter().map(|(count, _)| *count).collect::<Vec<_>>();
+        assert_eq!(counts, vec![1, 2, 1, 2]);
+        assert!(requests.iter().all(|(_, payload)| payload.len() <= 20));
+
+        let payload = requests
+            .into_iter()
+            .flat_map(|(_, payload)| payload.to_vec())
+            .collect::<Vec<_>>();
+        assert_eq!(payload, b"line 0\nline 1\nline 2\nline 3\nline 4\nline 5\n");
+    }
+
+    #[test]
+    fn fails_unsplittable_payloads() {
+        let builder = LinesRequestBuilder {
+            max_payload_bytes: Some(5),
+        };
+
+        let requests = build_split_requests(&builder, lines(2));
+        assert_eq!(requests.len(), 2);
+        assert!(requests.iter().all(Result::is_err));
+    }
+
+    #[test]
+    fn does_not_split_without_limit() {
+        let builder = LinesRequestBuilder {
+            max_payload_bytes: None,
+        };
+
+        let requests = build_split_requests(&builder, lines(100));
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].as_ref().unwrap().0, 100);
+    }
+
+    #[test]
+    fn does_not_copy_payloads_within_limit() {
+        let builder = LinesRequestBuilder {
+            max_payload_bytes: Some(10_000),
+        };
+
+        let requests = build_split_requests(&builder, lines(100));
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].as_ref().unwrap().0, 100);
+        assert_eq!(LINE_CLONES.with(Cell::get), 0);
+    }
+}
@@ -1,17 +1,27 @@
 use std::{
     borrow::Cow,
     cmp,
+    collections::HashMap,
+    fmt,
     future::Future,
     pin::Pin,
+    sync::Arc,
     task::{Context, Poll},
     time::Duration,
 };
 
 use futures::FutureExt;
+use http::StatusCode;
+use rand::{thread_rng, Rng};
 use tokio::time::{sleep, Sleep};
-use tower::{retry::Policy, timeout::error::Elapsed};
+use tower::{
+    retry::{budget::Budget, Policy},
+    timeout::error::Elapsed,
+};
+use tracing::Span;
+use vector_config::{configurable_component, ConfigurableString};
 
-use crate::{internal_events::SinkSendError, Error};
+use crate::{internal_events::SinkSendError, sinks::util::service::InjectedFault, Error};
 
 pub enum RetryAction {
     /// Indicate that this request should be retried with a reason
@@ -32,24 +42,207 @@ pub trait RetryLogic: Clone + Send + Sync + 'static {
         // Treat the default as the request is successful
         RetryAction::Successful
     }
+
+    /// Returns the HTTP status code of the response, if the response has one.
+    ///
+    /// When a status is returned, the `retry_on` setting of the sink takes precedence over
+    /// `should_retry_response` for unsuccessful responses.
+    fn response_status(&self, _response: &Self::Response) -> Option<StatusCode> {
+        None
+    }
+
+    /// Returns the HTTP status code the error was raised for, if the sink reports unsuccessful
+    /// responses as errors.
+    ///
+    /// When a status is returned, the `retry_on` setting of the sink takes precedence over
+    /// `is_retriable_error`.
+    fn error_status(&self, _error: &Self::Error) -> Option<StatusCode> {
+        None
+    }
+
+    /// Returns how long the service asked to wait before retrying, such as with a `Retry-After`
+    /// header.
+    ///
+    /// This is waited for instead of the backoff when it is longer.
+    fn retry_after(&self, _response: &Self::Response) -> Option<Duration> {
+        None
+    }
+}
+
+/// A condition under which a failed request is retried.
+///
+/// This can be a specific HTTP status code, such as `"429"`, a class of HTTP status codes (`"4xx"`
+/// or `"5xx"`), `"timeout"` for requests that did not complete within `timeout_secs`, or
+/// `"connection"` for requests that failed before a response was received.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[serde(try_from = "String", into = "String")]
+pub struct RetryOn(RetryCondition);
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+enum RetryCondition {
+    Status(u16),
+    ClientError,
+    ServerError,
+    Timeout,
+    Connection,
+}
+
+/// The kind of failure a request ended with.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RetryFailure {
+    Status(StatusCode),
+    Timeout,
+    Connection,
+}
+
+impl RetryOn {
+    pub fn matches(&self, failure: RetryFailure) -> bool {
+        match (self.0, failure) {
+            (RetryCondition::Status(code), RetryFailure::Status(status)) => status.as_u16() == code,
+            (RetryCondition::ClientError, RetryFailure::Status(status)) => status.is_client_error(),
+            (RetryCondition::ServerError, RetryFailure::Status(status)) => status.is_server_error(),
+            (RetryCondition::Timeout, RetryFailure::Timeout) => true,
+            (RetryCondition::Connection, RetryFailure::Connection) => true,
+            _ => false,
+        }
+    }
+
+    /// Specific status codes take precedence over classes of status codes.
+    const fn is_specific(&self) -> bool {
+        !matches!(
+            self.0,
+            RetryCondition::ClientError | RetryCondition::ServerError
+        )
+    }
+}
+
+impl TryFrom<String> for RetryOn {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let condition = match value.to_ascii_lowercase().as_str() {
+            "4xx" => RetryCondition::ClientError,
+            "5xx" => RetryCondition::ServerError,
+            "timeout" => RetryCondition::Timeout,
+            "connection" => RetryCondition::Connection,
+            code => match code.parse::<u16>() {
+                Ok(code) if (100..600).contains(&code) => RetryCondition::Status(code),
+                _ => {
+                    return Err(format!(
+                        "invalid retry condition `{}`, expected a HTTP status code, `4xx`, `5xx`, `timeout` or `connection`",
+                        value
+                    ))
+                }
+            },
+        };
+        Ok(Self(condition))
+    }
+}
+
+impl From<RetryOn> for String {
+    fn from(retry_on: RetryOn) -> Self {
+        retry_on.to_string()
+    }
+}
+
+impl fmt::Display for RetryOn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            RetryCondition::Status(code) => write!(f, "{}", code),
+            RetryCondition::ClientError => write!(f, "4xx"),
+            RetryCondition::ServerError => write!(f, "5xx"),
+            RetryCondition::Timeout => write!(f, "timeout"),
+            RetryCondition::Connection => write!(f, "connection"),
+        }
+    }
+}
+
+impl ConfigurableString for RetryOn {}
+
+/// The retry matrix of a sink, built from its `retry_on` and `retry_attempts_on` settings.
+#[derive(Clone, Debug, Default)]
+pub struct RetryMatrix {
+    retry_on: Option<Vec<RetryOn>>,
+    attempts_on: Vec<(RetryOn, usize)>,
+}
+
+impl RetryMatrix {
+    pub fn new(retry_on: Option<Vec<RetryOn>>, attempts_on: &HashMap<RetryOn, usize>) -> Self {
+        let mut attempts_on = attempts_on
+            .iter()
+            .map(|(retry_on, attempts)| (*retry_on, *attempts))
+            .collect::<Vec<_>>();
+        // Check specific status codes before the class they belong to.
+        attempts_on.sort_by_key(|(retry_on, _)| !retry_on.is_specific());
+
+        Self {
+            retry_on,
+            attempts_on,
+        }
+    }
+
+    /// Whether failures of this kind are retried at all.
+    fn retries(&self, failure: RetryFailure) -> bool {
+        self.overrides(failure).unwrap_or(true)
+    }
+
+    /// Applies the `retry_on` setting to the outcome decided by the sink for an unsuccessful response.
+    fn apply(&self, failure: RetryFailure, action: RetryAction) -> RetryAction {
+        let retry_on = match &self.retry_on {
+            Some(retry_on) => retry_on,
+            None => return action,
+        };
+        let reason = match action {
+            RetryAction::Retry(reason) | RetryAction::DontRetry(reason) => reason,
+            RetryAction::Successful => return RetryAction::Successful,
+        };
+
+        if retry_on.iter().any(|c| c.matches(failure)) {
+            RetryAction::Retry(reason)
+        } else {
+            RetryAction::DontRetry(reason)
+        }
+    }
+
+    /// Whether failures of this kind are retried, if `retry_on` decides it over the sink.
+    fn overrides(&self, failure: RetryFailure) -> Option<bool> {
+        self.retry_on
+            .as_ref()
+            .map(|retry_on| retry_on.iter().any(|c| c.matches(failure)))
+    }
+
+    /// The maximum number of retries for failures of this kind, if it was limited.
+    fn max_attempts(&self, failure: RetryFailure) -> Option<usize> {
+        self.attempts_on
+            .iter()
+            .find(|(retry_on, _)| retry_on.matches(failure))
+            .map(|(_, attempts)| *attempts)
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct FixedRetryPolicy<L> {
     remaining_attempts: usize,
+    attempt: usize,
     previous_duration: Duration,
     current_duration: Duration,
     max_duration: Duration,
+    jitter_factor: f64,
+    matrix: Arc<RetryMatrix>,
+    budget: Option<Arc<Budget>>,
     logic: L,
 }
 
 pub struct RetryPolicyFuture<L: RetryLogic> {
     delay: Pin<Box<Sleep>>,
     policy: FixedRetryPolicy<L>,
+    /// The span of the backoff, within that of the request, between its attempts.
+    span: Span,
 }
 
 impl<L: RetryLogic> FixedRetryPolicy<L> {
-    pub const fn new(
+    pub fn new(
         remaining_attempts: usize,
         initial_backoff: Duration,
         max_duration: Duration,
@@ -57,21 +250,46 @@ impl<L: RetryLogic> FixedRetryPolicy<L> {
     ) -> Self {
         FixedRetryPolicy {
             remaining_attempts,
+            attempt: 0,
             previous_duration: Duration::from_secs(0),
             current_duration: initial_backoff,
             max_duration,
+            jitter_factor: 0.0,
+            matrix: Arc::default(),
+            budget: None,
             logic,
         }
     }
 
+    /// Randomly shortens each backoff by up to the given fraction of it.
+    pub fn with_jitter_factor(mut self, jitter_factor: f64) -> Self {
+        self.jitter_factor = jitter_factor.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn with_matrix(mut self, matrix: Arc<RetryMatrix>) -> Self {
+        self.matrix = matrix;
+        self
+    }
+
+    /// Limits retries to the budget, which is shared by every request sent through this policy.
+    pub fn with_budget(mut self, budget: Option<Arc<Budget>>) -> Self {
+        self.budget = budget;
+        self
+    }
+
     fn advance(&self) -> FixedRetryPolicy<L> {
         let next_duration: Duration = self.previous_duration + self.current_duration;
 
         FixedRetryPolicy {
             remaining_attempts: self.remaining_attempts - 1,
+            attempt: self.attempt + 1,
             previous_duration: self.current_duration,
             current_duration: cmp::min(next_duration, self.max_duration),
             max_duration: self.max_duration,
+            jitter_factor: self.jitter_factor,
+            matrix: Arc::clone(&self.matrix),
+            budget: self.budget.clone(),
             logic: self.logic.clone(),
         }
     }
@@ -80,12 +298,52 @@ impl<L: RetryLogic> FixedRetryPolicy<L> {
         self.current_duration
     }
 
-    fn build_retry(&self) -> RetryPolicyFuture<L> {
+    fn jittered_backoff(&self) -> Duration {
+        if self.jitter_factor > 0.0 {
+            let jitter = self.jitter_factor * thread_rng().gen::<f64>();
+            self.backoff().mul_f64(1.0 - jitter)
+        } else {
+            self.backoff()
+        }
+    }
+
+    fn exhausted(&self, failure: Option<RetryFailure>) -> bool {
+        self.remaining_attempts == 0
+            || failure
+                .and_then(|failure| self.matrix.max_attempts(failure))
+                .map_or(false, |max_attempts| self.attempt >= max_attempts)
+    }
+
+    fn build_retry(&self, retry_after: Option<Duration>) -> Option<RetryPolicyFuture<L>> {
+        if let Some(budget) = &self.budget {
+            if budget.withdraw().is_err() {
+                emit!(SinkSendError {
+                    message: "Retry budget exhausted; dropping the request.",
+                    error: "retry budget exhausted",
+                });
+                return None;
+            }
+        }
+
         let policy = self.advance();
-        let delay = Box::pin(sleep(self.backoff()));
+        // The server may ask for a longer backoff, but never for one beyond the configured maximum.
+        let backoff = cmp::min(
+            cmp::max(self.jittered_backoff(), retry_after.unwrap_or_default()),
+            self.max_duration,
+        );
+        let delay = Box::pin(sleep(backoff));
 
-        debug!(message = "Retrying request.", delay_ms = %self.backoff().as_millis());
-        RetryPolicyFuture { delay, policy }
+        debug!(message = "Retrying request.", delay_ms = %backoff.as_millis());
+        let span = info_span!(
+            "retry_backoff",
+            attempt = policy.attempt,
+            delay_ms = backoff.as_millis() as u64,
+        );
+        Some(RetryPolicyFuture {
+            delay,
+            policy,
+            span,
+        })
     }
 }
 
@@ -97,34 +355,66 @@ where
     type Future = RetryPolicyFuture<L>;
 
     fn retry(&self, _: &Req, result: Result<&Res, &Error>) -> Option<Self::Future> {
+        if self.attempt == 0 {
+            if let Some(budget) = &self.budget {
+                budget.deposit();
+            }
+        }
+
         match result {
-            Ok(response) => match self.logic.should_retry_response(response) {
-                RetryAction::Retry(reason) => {
-                    if self.remaining_attempts == 0 {
+            Ok(response) => {
+                let failure = self
+                    .logic
+                    .response_status(response)
+                    .map(RetryFailure::Status);
+                let action = self.logic.should_retry_response(response);
+                let action = match failure {
+                    Some(failure) => self.matrix.apply(failure, action),
+                    None => action,
+                };
+
+                match action {
+                    RetryAction::Retry(reason) => {
+                        if self.exhausted(failure) {
+                            emit!(SinkSendError {
+                                message:
+                                    "OK/retry response but retries exhausted; dropping the request.",
+                                error: reason,
+                            });
+                            return None;
+                        }
+
+                        warn!(message = "Retrying after response.", reason = %reason);
+                        self.build_retry(self.logic.retry_after(response))
+                    }
+
+                    RetryAction::DontRetry(reason) => {
                         emit!(SinkSendError {
-                            message:
-                                "OK/retry response but retries exhausted; dropping the request.",
+                            message: "Not retriable; dropping the request.",
                             error: reason,
                         });
-                        return None;
+                        None
                     }
 
-                    warn!(message = "Retrying after response.", reason = %reason);
-                    Some(self.build_retry())
-                }
-
-                RetryAction::DontRetry(reason) => {
-                    emit!(SinkSendError {
-                        message: "Not retriable; dropping the request.",
-                        error: reason,
-                    });
-                    None
+                    RetryAction::Successful => None,
                 }
-
-                RetryAction::Successful => None,
-            },
+            }
             Err(error) => {
-                if self.remaining_attempts == 0 {
+                let failure = if error.downcast_ref::<Elapsed>().is_some() {
+                    RetryFailure::Timeout
+                } else if let Some(InjectedFault::ServerError { status }) =
+                    error.downcast_ref::<InjectedFault>()
+                {
+                    RetryFailure::Status(*status)
+                } else if let Some(status) = error
+                    .downcast_ref::<L::Error>()
+                    .and_then(|error| self.logic.error_status(error))
+                {
+                    RetryFailure::Status(status)
+                } else {
+                    RetryFailure::Connection
+                };
+                if self.exhausted(Some(failure)) {
                     emit!(SinkSendError {
                         message: "Retries exhausted; dropping the request.",
                         error,
@@ -133,9 +423,16 @@ where
                 }
 
                 if let Some(expected) = error.downcast_ref::<L::Error>() {
-                    if self.logic.is_retriable_error(expected) {
+                    let retriable = match failure {
+                        RetryFailure::Status(_) => self.matrix.overrides(failure),
+                        _ => None,
+                    };
+                    let retriable = retriable.unwrap_or_else(|| {
+                        self.logic.is_retriable_error(expected) && self.matrix.retries(failure)
+                    });
+                    if retriable {
                         warn!(message = "Retrying after error.", error = %expected);
-                        Some(self.build_retry())
+                        self.build_retry(None)
                     } else {
                         emit!(SinkSendError {
                             message: "Non-retriable error; dropping the request.",
@@ -143,9 +440,29 @@ where
                         });
                         None
                     }
-                } else if error.downcast_ref::<Elapsed>().is_some() {
+                } else if let Some(fault) = error.downcast_ref::<InjectedFault>() {
+                    // Injected faults are retried as the server errors and connection failures of
+                    // the sinks are.
+                    if self.matrix.retries(failure) {
+                        warn!(message = "Retrying after injected fault.", error = %fault);
+                        self.build_retry(None)
+                    } else {
+                        emit!(SinkSendError {
+                            message: "Injected fault is not retried; dropping the request.",
+                            error,
+                        });
+                        None
+                    }
+                } else if failure == RetryFailure::Timeout {
+                    if !self.matrix.retries(failure) {
+                        emit!(SinkSendError {
+                            message: "Request timed out and timeouts are not retried; dropping the request.",
+                            error,
+                        });
+                        return None;
+                    }
                     warn!("Request timed out. If this happens often while the events are actually reaching their destination, try decreasing `batch.max_bytes` and/or using `compression` if applicable. Alternatively `request.timeout_secs` can be increased.");
-                    Some(self.build_retry())
+                    self.build_retry(None)
                 } else {
                     emit!(SinkSendError {
                         message: "Unexpected error type; dropping the request.",
@@ -169,9 +486,11 @@ impl<L: RetryLogic> Unpin for RetryPolicyFuture<L> {}
 impl<L: RetryLogic> Future for RetryPolicyFuture<L> {
     type Output = FixedRetryPolicy<L>;
 
-    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        std::task::ready!(self.delay.poll_unpin(cx));
-        Poll::Ready(self.policy.clone())
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let _enter = this.span.enter();
+        std::task::ready!(this.delay.poll_unpin(cx));
+        Poll::Ready(this.policy.clone())
     }
 }
 
@@ -279,7 +598,7 @@ mod tests {
     use tower_test::{assert_request_eq, mock};
 
     use super::*;
-    use crate::test_util::trace_init;
+    use crate::{sinks::util::TowerRequestConfig, test_util::trace_init};
 
     #[tokio::test]
     async fn service_error_retry() {
@@ -392,6 +711,159 @@ mod tests {
         assert_eq!(Duration::from_secs(10), policy.backoff());
     }
 
+    #[test]
+    fn parse_retry_on() {
+        for condition in ["429", "5xx", "4XX", "timeout", "connection"] {
+            RetryOn::try_from(condition.to_string()).unwrap();
+        }
+        for condition in ["600", "99", "3xx", "refused"] {
+            RetryOn::try_from(condition.to_string()).unwrap_err();
+        }
+
+        let config =
+            toml::from_str::<TowerRequestConfig>(r#"retry_on = ["429", "5xx", "timeout"]"#)
+                .unwrap();
+        assert_eq!(
+            config.retry_on,
+            Some(vec![retry_on("429"), retry_on("5xx"), retry_on("timeout")])
+        );
+    }
+
+    #[tokio::test]
+    async fn retry_on_overrides_response_status() {
+        let matrix = RetryMatrix::new(Some(vec![retry_on("404")]), &HashMap::new());
+        let policy = status_policy().with_matrix(Arc::new(matrix));
+
+        assert!(retries(&policy, 404));
+        assert!(!retries(&policy, 503));
+        assert!(!retries(&policy, 200));
+    }
+
+    #[tokio::test]
+    async fn retry_attempts_on_status() {
+        let attempts_on = HashMap::from([(retry_on("503"), 1), (retry_on("5xx"), 2)]);
+        let mut policy =
+            status_policy().with_matrix(Arc::new(RetryMatrix::new(None, &attempts_on)));
+
+        assert!(retries(&policy, 503));
+        assert!(retries(&policy, 500));
+
+        policy = policy.advance();
+        assert!(!retries(&policy, 503));
+        assert!(retries(&policy, 500));
+
+        policy = policy.advance();
+        assert!(!retries(&policy, 500));
+    }
+
+    #[tokio::test]
+    async fn retry_budget_exhausted() {
+        // Every request allows for one retry.
+        let budget = Budget::new(Duration::from_secs(10), 0, 1.0);
+        let policy = status_policy().with_budget(Some(Arc::new(budget)));
+
+        assert!(retries(&policy, 500));
+        assert!(!retries(&policy.advance(), 500));
+    }
+
+    #[test]
+    fn jitter_shortens_backoff() {
+        let policy = status_policy().with_jitter_factor(0.5);
+        for _ in 0..100 {
+            let backoff = policy.jittered_backoff();
+            assert!(backoff <= Duration::from_secs(1));
+            assert!(backoff >= Duration::from_millis(500));
+        }
+    }
+
+    #[tokio::test]
+    async fn retry_after_extends_backoff() {
+        let retry_with_max_duration = |max_duration| {
+            let policy =
+                FixedRetryPolicy::new(10, Duration::from_secs(1), max_duration, RetryAfterLogic);
+            Policy::<(), StatusCode, crate::Error>::retry(
+                &policy,
+                &(),
+                Ok(&StatusCode::TOO_MANY_REQUESTS),
+            )
+            .unwrap()
+        };
+
+        let start = tokio::time::Instant::now();
+        let retry = retry_with_max_duration(Duration::from_secs(60));
+        assert!(retry.delay.deadline() >= start + Duration::from_secs(30));
+
+        // The `Retry-After` delay is capped to the maximum backoff.
+        let start = tokio::time::Instant::now();
+        let retry = retry_with_max_duration(Duration::from_secs(10));
+        assert!(retry.delay.deadline() >= start + Duration::from_secs(10));
+        assert!(retry.delay.deadline() < start + Duration::from_secs(30));
+    }
+
+    fn retry_on(condition: &str) -> RetryOn {
+        RetryOn::try_from(condition.to_string()).unwrap()
+    }
+
+    fn status_policy() -> FixedRetryPolicy<StatusRetryLogic> {
+        FixedRetryPolicy::new(
+            10,
+            Duration::from_secs(1),
+            Duration::from_secs(10),
+            StatusRetryLogic,
+        )
+    }
+
+    fn retries(policy: &FixedRetryPolicy<StatusRetryLogic>, status: u16) -> bool {
+        let status = StatusCode::from_u16(status).unwrap();
+        Policy::<(), StatusCode, crate::Error>::retry(policy, &(), Ok(&status)).is_some()
+    }
+
+    #[derive(Debug, Clone)]
+    struct StatusRetryLogic;
+
+    impl RetryLogic for StatusRetryLogic {
+        type Error = Error;
+        type Response = StatusCode;
+
+        fn is_retriable_error(&self, error: &Self::Error) -> bool {
+            error.0
+        }
+
+        fn should_retry_response(&self, status: &StatusCode) -> RetryAction {
+            if status.is_success() {
+                RetryAction::Successful
+            } else if status.is_server_error() {
+                RetryAction::Retry(status.to_string().into())
+            } else {
+                RetryAction::DontRetry(status.to_string().into())
+            }
+        }
+
+        fn response_status(&self, status: &StatusCode) -> Option<StatusCode> {
+            Some(*status)
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    struct RetryAfterLogic;
+
+    impl RetryLogic for RetryAfterLogic {
+        type Error = Error;
+        type Response = StatusCode;
+
+        fn is_retriable_error(&self, error: &Self::Error) -> bool {
+            error.0
+        }
+
+        fn should_retry_response(&self, _status: &StatusCode) -> RetryAction {
+            RetryAction::Retry("too many requests".into())
+        }
+
+        fn retry_after(&self, _status: &StatusCode) -> Option<Duration> {
+            Some(Duration::from_secs(30))
+        }
+    }
+
     #[derive(Debug, Clone)]
     struct SvcRetryLogic;
 
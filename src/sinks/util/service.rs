@@ -1,4 +1,6 @@
-use std::{hash::Hash, marker::PhantomData, pin::Pin, sync::Arc, time::Duration};
+use std::{
+    collections::HashMap, hash::Hash, marker::PhantomData, pin::Pin, sync::Arc, time::Duration,
+};
 
 use futures_util::stream::{self, BoxStream};
 use tower::{
@@ -7,7 +9,7 @@ use tower::{
     discover::Change,
     layer::{util::Stack, Layer},
     limit::RateLimit,
-    retry::Retry,
+    retry::{budget::Budget, Retry},
     timeout::Timeout,
     Service, ServiceBuilder,
 };
@@ -15,6 +17,7 @@ use vector_config::configurable_component;
 
 pub use crate::sinks::util::service::{
     concurrency::{concurrency_is_none, Concurrency},
+    fault::{FaultConfig, FaultService, InjectedFault},
     health::{HealthConfig, HealthLogic, HealthService},
     map::Map,
 };
@@ -24,18 +27,20 @@ use crate::{
         adaptive_concurrency::{
             AdaptiveConcurrencyLimit, AdaptiveConcurrencyLimitLayer, AdaptiveConcurrencySettings,
         },
-        retries::{FixedRetryPolicy, RetryLogic},
-        service::map::MapLayer,
+        retries::{FixedRetryPolicy, RetryLogic, RetryMatrix, RetryOn},
+        service::{fault::FaultLayer, map::MapLayer},
         sink::Response,
         Batch, BatchSink, Partition, PartitionBatchSink,
     },
 };
 
 mod concurrency;
+mod fault;
 mod health;
 mod map;
 
-pub type Svc<S, L> = RateLimit<AdaptiveConcurrencyLimit<Retry<FixedRetryPolicy<L>, Timeout<S>>, L>>;
+pub type Svc<S, L> =
+    RateLimit<AdaptiveConcurrencyLimit<Retry<FixedRetryPolicy<L>, Timeout<FaultService<S>>>, L>>;
 pub type TowerBatchedSink<S, B, RL> = BatchSink<Svc<S, RL>, B>;
 pub type TowerPartitionSink<S, B, RL, K> = PartitionBatchSink<Svc<S, RL>, B, K>;
 
@@ -46,7 +51,7 @@ pub type DistributedService<S, RL, HL, K, Req> = RateLimit<
 pub type DiscoveryService<S, RL, HL, K> =
     BoxStream<'static, Result<Change<K, SingleDistributedService<S, RL, HL>>, crate::Error>>;
 pub type SingleDistributedService<S, RL, HL> =
-    AdaptiveConcurrencyLimit<HealthService<Timeout<S>, HL>, RL>;
+    AdaptiveConcurrencyLimit<HealthService<Timeout<FaultService<S>>, HL>, RL>;
 
 pub trait ServiceBuilderExt<L> {
     fn map<R1, R2, F>(self, f: F) -> ServiceBuilder<Stack<MapLayer<R1, R2>, L>>
@@ -85,7 +90,7 @@ impl<L> ServiceBuilderExt<L> for ServiceBuilder<L> {
 ///
 /// Various settings can be configured, such as concurrency and rate limits, timeouts, etc.
 #[configurable_component]
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub struct TowerRequestConfig {
     #[configurable(derived)]
     #[serde(default)]
@@ -117,9 +122,42 @@ pub struct TowerRequestConfig {
     /// After the first retry has failed, the fibonacci sequence will be used to select future backoffs.
     pub retry_initial_backoff_secs: Option<u64>,
 
+    /// The failures that are retried.
+    ///
+    /// Each entry is either a HTTP status code, such as `"429"`, a class of HTTP status codes (`"4xx"` or
+    /// `"5xx"`), `"timeout"`, or `"connection"`. HTTP statuses listed here are retried even if the sink
+    /// would not retry them by default, while errors are only retried if the sink considers them retriable.
+    ///
+    /// By default, the sink decides which failures are retried.
+    pub retry_on: Option<Vec<RetryOn>>,
+
+    /// The maximum number of retries for failed requests, by failure.
+    ///
+    /// The keys have the same format as the entries of `retry_on`. Specific HTTP status codes take
+    /// precedence over classes of HTTP status codes. Failures not listed here are retried up to
+    /// `retry_attempts` times.
+    pub retry_attempts_on: Option<HashMap<RetryOn, usize>>,
+
+    /// The fraction of each backoff that is randomized.
+    ///
+    /// Each backoff is shortened by a random amount of up to this fraction, which spreads out the retries
+    /// of requests that failed at the same time.
+    #[configurable(validation(range(min = 0.0, max = 1.0)))]
+    pub retry_jitter_factor: Option<f64>,
+
+    /// The percentage of requests that can be retried, in addition to a minimum of ten retries per second.
+    ///
+    /// Once the budget is spent, failed requests are dropped instead of retried, which prevents retries from
+    /// overwhelming a service that is already failing. By default, there is no retry budget.
+    #[configurable(validation(range(min = 0.0)))]
+    pub retry_budget_percent: Option<f64>,
+
     #[configurable(derived)]
     #[serde(default)]
     pub adaptive_concurrency: AdaptiveConcurrencySettings,
+
+    #[configurable(derived)]
+    pub fault: Option<FaultConfig>,
 }
 
 pub const CONCURRENCY_DEFAULT: Concurrency = Concurrency::None;
@@ -128,6 +166,9 @@ pub const RATE_LIMIT_NUM_DEFAULT: u64 = i64::max_value() as u64; // i64 avoids T
 pub const RETRY_ATTEMPTS_DEFAULT: usize = isize::max_value() as usize; // isize avoids TOML deserialize issue
 pub const RETRY_MAX_DURATION_SECONDS_DEFAULT: u64 = 3_600;
 pub const RETRY_INITIAL_BACKOFF_SECONDS_DEFAULT: u64 = 1;
+pub const RETRY_JITTER_FACTOR_DEFAULT: f64 = 0.0;
+pub const RETRY_BUDGET_TTL: Duration = Duration::from_secs(10);
+pub const RETRY_BUDGET_MIN_PER_SECOND: u32 = 10;
 pub const TIMEOUT_SECONDS_DEFAULT: u64 = 60;
 
 impl Default for TowerRequestConfig {
@@ -146,7 +187,12 @@ impl TowerRequestConfig {
             retry_attempts: Some(RETRY_ATTEMPTS_DEFAULT),
             retry_max_duration_secs: Some(RETRY_MAX_DURATION_SECONDS_DEFAULT),
             retry_initial_backoff_secs: Some(RETRY_INITIAL_BACKOFF_SECONDS_DEFAULT),
+            retry_on: None,
+            retry_attempts_on: None,
+            retry_jitter_factor: None,
+            retry_budget_percent: None,
             adaptive_concurrency: AdaptiveConcurrencySettings::const_default(),
+            fault: None,
         }
     }
 
@@ -215,7 +261,29 @@ impl TowerRequestConfig {
                     .or(defaults.retry_initial_backoff_secs)
                     .unwrap_or(RETRY_INITIAL_BACKOFF_SECONDS_DEFAULT),
             ),
+            retry_jitter_factor: self
+                .retry_jitter_factor
+                .or(defaults.retry_jitter_factor)
+                .unwrap_or(RETRY_JITTER_FACTOR_DEFAULT),
+            retry_matrix: Arc::new(RetryMatrix::new(
+                self.retry_on.clone().or_else(|| defaults.retry_on.clone()),
+                self.retry_attempts_on
+                    .as_ref()
+                    .or(defaults.retry_attempts_on.as_ref())
+                    .unwrap_or(&HashMap::new()),
+            )),
+            retry_budget: self
+                .retry_budget_percent
+                .or(defaults.retry_budget_percent)
+                .map(|percent| {
+                    Arc::new(Budget::new(
+                        RETRY_BUDGET_TTL,
+                        RETRY_BUDGET_MIN_PER_SECOND,
+                        (percent / 100.0).clamp(0.0, 1000.0) as f32,
+                    ))
+                }),
             adaptive_concurrency: self.adaptive_concurrency,
+            fault: self.fault.or(defaults.fault),
         }
     }
 }
@@ -229,17 +297,24 @@ pub struct TowerRequestSettings {
     pub retry_attempts: usize,
     pub retry_max_duration_secs: Duration,
     pub retry_initial_backoff_secs: Duration,
+    pub retry_jitter_factor: f64,
+    pub retry_matrix: Arc<RetryMatrix>,
+    pub retry_budget: Option<Arc<Budget>>,
     pub adaptive_concurrency: AdaptiveConcurrencySettings,
+    pub fault: Option<FaultConfig>,
 }
 
 impl TowerRequestSettings {
-    pub const fn retry_policy<L: RetryLogic>(&self, logic: L) -> FixedRetryPolicy<L> {
+    pub fn retry_policy<L: RetryLogic>(&self, logic: L) -> FixedRetryPolicy<L> {
         FixedRetryPolicy::new(
             self.retry_attempts,
             self.retry_initial_backoff_secs,
             self.retry_max_duration_secs,
             logic,
         )
+        .with_jitter_factor(self.retry_jitter_factor)
+        .with_matrix(Arc::clone(&self.retry_matrix))
+        .with_budget(self.retry_budget.clone())
     }
 
     /// Note: This has been deprecated, please do not use when creating new Sinks.
@@ -328,6 +403,7 @@ impl TowerRequestSettings {
                             health_logic.clone(),
                             ServiceBuilder::new()
                                 .timeout(settings.timeout)
+                                .layer(FaultLayer::new(settings.fault))
                                 .service(inner),
                             open.clone(),
                             endpoint,
@@ -381,6 +457,7 @@ where
             ))
             .retry(policy)
             .timeout(self.settings.timeout)
+            .layer(FaultLayer::new(self.settings.fault))
             .service(inner)
     }
 }
@@ -0,0 +1,288 @@
+//! Faults injected beneath the service of a sink, to test how its failed requests are retried
+//! and acknowledged without an endpoint that actually misbehaves.
+
+use std::{
+    fmt,
+    task::{Context, Poll},
+};
+
+use futures::future::{self, BoxFuture};
+use http::StatusCode;
+use rand::{thread_rng, Rng};
+use tower::{Layer, Service};
+use vector_config::configurable_component;
+
+/// Faults injected into the requests of the sink, for testing.
+///
+/// Each request meets at most one fault, drawn with the ratios below, each between `0.0` and `1.0`. The failed
+/// requests are retried, or not, as their real counterparts, so the faults are a way to test the retries, the
+/// acknowledgements and the buffers of a topology in staging. They must not be set in production.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct FaultConfig {
+    /// The ratio of the requests that time out.
+    ///
+    /// These requests aren't sent, and hang until the `timeout_secs` of the request elapses.
+    #[serde(default)]
+    #[configurable(validation(range(min = 0.0, max = 1.0)))]
+    pub timeout_rate: f64,
+
+    /// The ratio of the requests that fail with a server error response.
+    ///
+    /// These requests aren't sent.
+    #[serde(default)]
+    #[configurable(validation(range(min = 0.0, max = 1.0)))]
+    pub server_error_rate: f64,
+
+    /// The HTTP status code of the server error responses.
+    #[serde(default = "default_server_error_status")]
+    #[configurable(validation(range(min = 500, max = 599)))]
+    pub server_error_status: u16,
+
+    /// The ratio of the requests whose connection is reset before they are sent.
+    #[serde(default)]
+    #[configurable(validation(range(min = 0.0, max = 1.0)))]
+    pub connection_reset_rate: f64,
+
+    /// The ratio of the requests that fail with a server error response once they are sent, as when only part of a
+    /// batch was written.
+    ///
+    /// The events of these requests reach the endpoint, so they are duplicated when the requests are retried.
+    #[serde(default)]
+    #[configurable(validation(range(min = 0.0, max = 1.0)))]
+    pub partial_failure_rate: f64,
+}
+
+const fn default_server_error_status() -> u16 {
+    500
+}
+
+impl FaultConfig {
+    fn server_error_status(&self) -> StatusCode {
+        StatusCode::from_u16(self.server_error_status)
+            .ok()
+            .filter(StatusCode::is_server_error)
+            .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+
+    /// Draws the fault a request meets, if any.
+    fn draw<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<Fault> {
+        let mut draw = rng.gen::<f64>();
+        [
+            (self.timeout_rate, Fault::Timeout),
+            (self.server_error_rate, Fault::ServerError),
+            (self.connection_reset_rate, Fault::ConnectionReset),
+            (self.partial_failure_rate, Fault::PartialFailure),
+        ]
+        .into_iter()
+        .find_map(|(rate, fault)| {
+            if draw < rate {
+                Some(fault)
+            } else {
+                draw -= rate;
+                None
+            }
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Fault {
+    Timeout,
+    ServerError,
+    ConnectionReset,
+    PartialFailure,
+}
+
+/// The error of the requests that met an injected fault.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InjectedFault {
+    ServerError { status: StatusCode },
+    ConnectionReset,
+}
+
+impl fmt::Display for InjectedFault {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ServerError { status } => write!(f, "injected server error: {}", status),
+            Self::ConnectionReset => write!(f, "injected connection reset"),
+        }
+    }
+}
+
+impl std::error::Error for InjectedFault {}
+
+#[derive(Clone, Debug)]
+pub struct FaultLayer {
+    config: Option<FaultConfig>,
+}
+
+impl FaultLayer {
+    pub const fn new(config: Option<FaultConfig>) -> Self {
+        Self { config }
+    }
+}
+
+impl<S> Layer<S> for FaultLayer {
+    type Service = FaultService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        FaultService {
+            inner,
+            config: self.config,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FaultService<S> {
+    inner: S,
+    config: Option<FaultConfig>,
+}
+
+impl<S, Request> Service<Request> for FaultService<S>
+where
+    S: Service<Request>,
+    S::Error: Into<crate::Error>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = crate::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        let fault = self
+            .config
+            .and_then(|config| Some((config, config.draw(&mut thread_rng())?)));
+        let (config, fault) = match fault {
+            Some(fault) => fault,
+            None => {
+                let response = self.inner.call(request);
+                return Box::pin(async move { response.await.map_err(Into::into) });
+            }
+        };
+
+        debug!(message = "Injecting fault into the request.", ?fault);
+        match fault {
+            Fault::Timeout => Box::pin(future::pending()),
+            Fault::ServerError => Box::pin(future::err(
+                InjectedFault::ServerError {
+                    status: config.server_error_status(),
+                }
+                .into(),
+            )),
+            Fault::ConnectionReset => Box::pin(future::err(InjectedFault::ConnectionReset.into())),
+            Fault::PartialFailure => {
+                let response = self.inner.call(request);
+                let status = config.server_error_status();
+                Box::pin(async move {
+                    response.await.map_err(Into::into)?;
+                    Err(InjectedFault::ServerError { status }.into())
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use rand::{rngs::SmallRng, SeedableRng};
+    use tower::{service_fn, ServiceExt};
+
+    use super::*;
+
+    fn config(server_error_rate: f64, partial_failure_rate: f64) -> FaultConfig {
+        FaultConfig {
+            timeout_rate: 0.0,
+            server_error_rate,
+            server_error_status: 503,
+            connection_reset_rate: 0.0,
+            partial_failure_rate,
+        }
+    }
+
+    #[test]
+    fn draws_faults_with_their_rates() {
+        let config = FaultConfig {
+            timeout_rate: 0.1,
+            server_error_rate: 0.2,
+            server_error_status: 500,
+            connection_reset_rate: 0.3,
+            partial_failure_rate: 0.0,
+        };
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut counts = [0_usize; 4];
+        for _ in 0..10_000 {
+            match config.draw(&mut rng) {
+                Some(Fault::Timeout) => counts[0] += 1,
+                Some(Fault::ServerError) => counts[1] += 1,
+                Some(Fault::ConnectionReset) => counts[2] += 1,
+                Some(Fault::PartialFailure) => counts[3] += 1,
+                None => {}
+            }
+        }
+
+        assert!((800..1200).contains(&counts[0]));
+        assert!((1800..2200).contains(&counts[1]));
+        assert!((2800..3200).contains(&counts[2]));
+        assert_eq!(counts[3], 0);
+    }
+
+    #[test]
+    fn falls_back_to_internal_server_error() {
+        let mut config = config(1.0, 0.0);
+        assert_eq!(
+            config.server_error_status(),
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+        config.server_error_status = 404;
+        assert_eq!(
+            config.server_error_status(),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+
+    #[tokio::test]
+    async fn injects_faults() {
+        let (sent_tx, mut sent_rx) = tokio::sync::mpsc::unbounded_channel();
+        let inner = service_fn(move |request: usize| {
+            let _ = sent_tx.send(request);
+            future::ok::<_, crate::Error>(request)
+        });
+
+        let service = FaultLayer::new(None).layer(inner.clone());
+        assert_eq!(service.oneshot(1).await.unwrap(), 1);
+        assert_eq!(sent_rx.recv().await, Some(1));
+
+        let service = FaultLayer::new(Some(config(1.0, 0.0))).layer(inner.clone());
+        let error = service.oneshot(2).await.unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<InjectedFault>(),
+            Some(&InjectedFault::ServerError {
+                status: StatusCode::SERVICE_UNAVAILABLE
+            })
+        );
+        assert!(sent_rx.try_recv().is_err());
+
+        // The requests that fail partially are sent nonetheless.
+        let service = FaultLayer::new(Some(config(0.0, 1.0))).layer(inner.clone());
+        assert!(service.oneshot(3).await.is_err());
+        assert_eq!(sent_rx.recv().await, Some(3));
+
+        let mut timeout = config(0.0, 0.0);
+        timeout.timeout_rate = 1.0;
+        let service = FaultLayer::new(Some(timeout)).layer(inner);
+        assert!(
+            tokio::time::timeout(Duration::from_millis(50), service.oneshot(4))
+                .await
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,520 @@
+//! Client-side load balancing of the `vector` sink across several downstream Vector instances.
+//!
+//! Requests are spread across the endpoints with a smooth weighted round-robin, where the weight
+//! of an endpoint is its recent success rate, so that an unhealthy endpoint gets less traffic
+//! before it is ejected. Endpoints that fail too many requests in a row are ejected for a while,
+//! and the addresses can be resolved again periodically to pick up the instances of a headless
+//! service as they come and go.
+
+use std::{
+    net::SocketAddr,
+    sync::{Arc, Mutex, RwLock, Weak},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use futures::future::BoxFuture;
+use http::Uri;
+use tower::Service;
+use vector_config::configurable_component;
+
+use super::{service::VectorRequest, VectorSinkError};
+use crate::internal_events::{VectorEndpointEjected, VectorEndpointResolveError};
+
+// How much the outcome of a single request moves the success rate of an endpoint.
+const SUCCESS_RATE_ALPHA: f64 = 0.2;
+
+// The floor of the weight of an endpoint, so that failing endpoints still get some traffic to
+// recover their success rate with.
+const MIN_WEIGHT: f64 = 0.05;
+
+// The cap on how many times longer than `ejection_duration_secs` a repeatedly ejected endpoint is
+// ejected for.
+const MAX_EJECTION_MULTIPLIER: u32 = 10;
+
+const fn default_ejection_consecutive_errors() -> usize {
+    5
+}
+
+const fn default_ejection_duration_secs() -> u64 {
+    30
+}
+
+const fn default_max_ejection_percent() -> u8 {
+    50
+}
+
+/// Load balancing settings.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct LoadBalancingConfig {
+    /// The interval, in seconds, at which the hosts of the addresses are resolved again.
+    ///
+    /// When set, requests are balanced across all of the addresses the hosts resolve to, rather
+    /// than across the configured addresses. This is useful with DNS names resolving to several
+    /// instances, such as Kubernetes headless services.
+    #[serde(default)]
+    pub resolve_interval_secs: Option<u64>,
+
+    /// The number of consecutive failed requests after which an endpoint is ejected.
+    ///
+    /// Ejected endpoints are not sent requests until the end of their ejection.
+    #[serde(default = "default_ejection_consecutive_errors")]
+    pub ejection_consecutive_errors: usize,
+
+    /// The time, in seconds, an endpoint is ejected for.
+    ///
+    /// An endpoint which is ejected again right after its ejection ends is ejected for longer each
+    /// time, up to ten times this duration.
+    #[serde(default = "default_ejection_duration_secs")]
+    pub ejection_duration_secs: u64,
+
+    /// The maximum percentage of the endpoints which can be ejected at the same time.
+    #[serde(default = "default_max_ejection_percent")]
+    #[configurable(validation(range(max = 100)))]
+    pub max_ejection_percent: u8,
+}
+
+impl Default for LoadBalancingConfig {
+    fn default() -> Self {
+        Self {
+            resolve_interval_secs: None,
+            ejection_consecutive_errors: default_ejection_consecutive_errors(),
+            ejection_duration_secs: default_ejection_duration_secs(),
+            max_ejection_percent: default_max_ejection_percent(),
+        }
+    }
+}
+
+/// Builds the service sending requests to an endpoint, given its URI and, if the host of the URI
+/// was resolved, the address to connect to.
+pub type EndpointFactory<S> =
+    Arc<dyn Fn(&Uri, Option<SocketAddr>) -> crate::Result<S> + Send + Sync>;
+
+#[derive(Debug)]
+struct EndpointHealth {
+    success_rate: f64,
+    // The running weight of the smooth weighted round-robin.
+    current_weight: f64,
+    consecutive_errors: usize,
+    ejections: u32,
+    ejected_until: Option<Instant>,
+}
+
+impl EndpointHealth {
+    const fn new() -> Self {
+        Self {
+            success_rate: 1.0,
+            current_weight: 0.0,
+            consecutive_errors: 0,
+            ejections: 0,
+            ejected_until: None,
+        }
+    }
+
+    fn is_ejected(&self, now: Instant) -> bool {
+        self.ejected_until.map_or(false, |until| until > now)
+    }
+}
+
+struct Endpoint<S> {
+    uri: Uri,
+    address: Option<SocketAddr>,
+    service: S,
+    health: Mutex<EndpointHealth>,
+}
+
+impl<S> Endpoint<S> {
+    fn new(uri: Uri, address: Option<SocketAddr>, service: S) -> Self {
+        Self {
+            uri,
+            address,
+            service,
+            health: Mutex::new(EndpointHealth::new()),
+        }
+    }
+
+    fn name(&self) -> String {
+        match self.address {
+            Some(address) => format!("{} ({})", self.uri, address),
+            None => self.uri.to_string(),
+        }
+    }
+}
+
+/// A service balancing requests across the endpoints of the downstream Vector instances.
+pub struct BalancedService<S> {
+    endpoints: Arc<RwLock<Vec<Arc<Endpoint<S>>>>>,
+    config: LoadBalancingConfig,
+}
+
+impl<S> Clone for BalancedService<S> {
+    fn clone(&self) -> Self {
+        Self {
+            endpoints: Arc::clone(&self.endpoints),
+            config: self.config,
+        }
+    }
+}
+
+impl<S> BalancedService<S>
+where
+    S: Send + Sync + 'static,
+{
+    /// Builds the service, resolving the hosts of the URIs if `resolve_interval_secs` is set.
+    ///
+    /// The hosts are then resolved again in the background for as long as the service is alive.
+    pub async fn new(
+        uris: Vec<Uri>,
+        config: LoadBalancingConfig,
+        factory: EndpointFactory<S>,
+    ) -> crate::Result<Self> {
+        let endpoints = match config.resolve_interval_secs {
+            Some(_) => resolve_endpoints(&uris, &[], &factory).await?,
+            None => uris
+                .iter()
+                .map(|uri| {
+                    Ok(Arc::new(Endpoint::new(
+                        uri.clone(),
+                        None,
+                        factory(uri, None)?,
+                    )))
+                })
+                .collect::<crate::Result<Vec<_>>>()?,
+        };
+
+        let service = Self {
+            endpoints: Arc::new(RwLock::new(endpoints)),
+            config,
+        };
+
+        if let Some(interval) = config.resolve_interval_secs {
+            tokio::spawn(refresh_endpoints(
+                Arc::downgrade(&service.endpoints),
+                uris,
+                Duration::from_secs(interval),
+                factory,
+            ));
+        }
+
+        Ok(service)
+    }
+
+    /// Picks the next endpoint of the smooth weighted round-robin, skipping the ejected endpoints.
+    fn pick(&self) -> Arc<Endpoint<S>> {
+        let endpoints = self.endpoints.read().expect("poisoned lock");
+        let now = Instant::now();
+
+        let mut available = endpoints
+            .iter()
+            .filter(|endpoint| {
+                !endpoint
+                    .health
+                    .lock()
+                    .expect("poisoned lock")
+                    .is_ejected(now)
+            })
+            .collect::<Vec<_>>();
+        // If every endpoint is ejected, falling back to all of them beats not sending at all.
+        if available.is_empty() {
+            available = endpoints.iter().collect();
+        }
+
+        let mut total_weight = 0.0;
+        let mut picked: Option<(&Arc<Endpoint<S>>, f64)> = None;
+        for endpoint in available {
+            let mut health = endpoint.health.lock().expect("poisoned lock");
+            let weight = health.success_rate.max(MIN_WEIGHT);
+            health.current_weight += weight;
+            total_weight += weight;
+            if picked.map_or(true, |(_, current)| health.current_weight > current) {
+                picked = Some((endpoint, health.current_weight));
+            }
+        }
+
+        let (endpoint, _) = picked.expect("there is always at least one endpoint");
+        endpoint
+            .health
+            .lock()
+            .expect("poisoned lock")
+            .current_weight -= total_weight;
+        Arc::clone(endpoint)
+    }
+
+    /// Records the outcome of a request sent to the endpoint, ejecting it if needed.
+    fn record(&self, endpoint: &Endpoint<S>, failed: bool) {
+        let now = Instant::now();
+        let mut health = endpoint.health.lock().expect("poisoned lock");
+        let outcome = if failed { 0.0 } else { 1.0 };
+        health.success_rate += SUCCESS_RATE_ALPHA * (outcome - health.success_rate);
+
+        if !failed {
+            health.consecutive_errors = 0;
+            if !health.is_ejected(now) {
+                health.ejections = 0;
+            }
+            return;
+        }
+
+        health.consecutive_errors += 1;
+        if health.consecutive_errors < self.config.ejection_consecutive_errors
+            || health.is_ejected(now)
+        {
+            return;
+        }
+        // The lock on this endpoint's health is released while counting the ejected endpoints, as
+        // counting locks each endpoint in turn.
+        drop(health);
+
+        let (ejected, total) = {
+            let endpoints = self.endpoints.read().expect("poisoned lock");
+            let ejected = endpoints
+                .iter()
+                .filter(|other| other.health.lock().expect("poisoned lock").is_ejected(now))
+                .count();
+            (ejected, endpoints.len())
+        };
+        if (ejected + 1) * 100 > total * self.config.max_ejection_percent as usize {
+            return;
+        }
+
+        let mut health = endpoint.health.lock().expect("poisoned lock");
+        health.ejections = (health.ejections + 1).min(MAX_EJECTION_MULTIPLIER);
+        let duration = Duration::from_secs(self.config.ejection_duration_secs) * health.ejections;
+        health.ejected_until = Some(now + duration);
+        emit!(VectorEndpointEjected {
+            endpoint: &endpoint.name(),
+            consecutive_errors: health.consecutive_errors,
+            duration,
+        });
+        health.consecutive_errors = 0;
+    }
+}
+
+impl<S> Service<VectorRequest> for BalancedService<S>
+where
+    S: Service<VectorRequest, Error = crate::Error> + Clone + Send + Sync + 'static,
+    S::Future: Send + 'static,
+    S::Response: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = crate::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // The endpoint services are always ready, see `VectorService::poll_ready`.
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: VectorRequest) -> Self::Future {
+        let endpoint = self.pick();
+        let mut service = endpoint.service.clone();
+        let balancer = self.clone();
+
+        Box::pin(async move {
+            let result = service.call(request).await;
+            let failed = matches!(&result, Err(error) if is_endpoint_failure(error));
+            balancer.record(&endpoint, failed);
+            result
+        })
+    }
+}
+
+/// Whether the error is the endpoint's fault, rather than the request's.
+fn is_endpoint_failure(error: &crate::Error) -> bool {
+    use tonic::Code::*;
+
+    match error.downcast_ref::<VectorSinkError>() {
+        Some(VectorSinkError::Request { source }) => matches!(
+            source.code(),
+            Unknown | DeadlineExceeded | ResourceExhausted | Internal | Unavailable
+        ),
+        _ => true,
+    }
+}
+
+/// Resolves the hosts of the URIs, reusing the existing endpoints of the addresses still resolved.
+async fn resolve_endpoints<S>(
+    uris: &[Uri],
+    existing: &[Arc<Endpoint<S>>],
+    factory: &EndpointFactory<S>,
+) -> crate::Result<Vec<Arc<Endpoint<S>>>> {
+    let mut endpoints = Vec::new();
+    for uri in uris {
+        let host = uri
+            .host()
+            .map(|host| host.trim_start_matches('[').trim_end_matches(']'))
+            .ok_or(VectorSinkError::NoHost)?;
+        let port = uri.port_u16().unwrap_or(match uri.scheme_str() {
+            Some("https") => 443,
+            _ => 80,
+        });
+
+        let mut addresses = tokio::net::lookup_host((host, port))
+            .await?
+            .collect::<Vec<_>>();
+        addresses.sort();
+        addresses.dedup();
+
+        for address in addresses {
+            let endpoint = match existing
+                .iter()
+                .find(|endpoint| &endpoint.uri == uri && endpoint.address == Some(address))
+            {
+                Some(endpoint) => Arc::clone(endpoint),
+                None => Arc::new(Endpoint::new(
+                    uri.clone(),
+                    Some(address),
+                    factory(uri, Some(address))?,
+                )),
+            };
+            endpoints.push(endpoint);
+        }
+    }
+
+    if endpoints.is_empty() {
+        return Err("None of the addresses resolved.".into());
+    }
+    Ok(endpoints)
+}
+
+async fn refresh_endpoints<S>(
+    endpoints: Weak<RwLock<Vec<Arc<Endpoint<S>>>>>,
+    uris: Vec<Uri>,
+    interval: Duration,
+    factory: EndpointFactory<S>,
+) {
+    loop {
+        tokio::time::sleep(interval).await;
+
+        let existing = match endpoints.upgrade() {
+            Some(endpoints) => endpoints.read().expect("poisoned lock").clone(),
+            // The service was dropped, along with the sink.
+            None => break,
+        };
+
+        // Keep the previous endpoints if resolution fails, rather than stopping sending altogether.
+        match resolve_endpoints(&uris, &existing, &factory).await {
+            Ok(resolved) => match endpoints.upgrade() {
+                Some(endpoints) => *endpoints.write().expect("poisoned lock") = resolved,
+                None => break,
+            },
+            Err(error) => emit!(VectorEndpointResolveError { error }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use futures::future;
+
+    use super::*;
+
+    #[derive(Clone)]
+    struct MockService {
+        fail: bool,
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl MockService {
+        fn new(fail: bool) -> Self {
+            Self {
+                fail,
+                calls: Arc::new(AtomicUsize::new(0)),
+            }
+        }
+    }
+
+    impl Service<VectorRequest> for MockService {
+        type Response = ();
+        type Error = crate::Error;
+        type Future = future::Ready<Result<(), crate::Error>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _request: VectorRequest) -> Self::Future {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            future::ready(if self.fail {
+                Err(VectorSinkError::Request {
+                    source: tonic::Status::unavailable("down"),
+                }
+                .into())
+            } else {
+                Ok(())
+            })
+        }
+    }
+
+    async fn balanced(
+        services: Vec<MockService>,
+        config: LoadBalancingConfig,
+    ) -> BalancedService<MockService> {
+        let uris = (0..services.len())
+            .map(|i| format!("http://vector-{}:6000", i).parse().unwrap())
+            .collect();
+        let factory: EndpointFactory<MockService> = Arc::new(move |uri: &Uri, _| {
+            let index = uri.host().unwrap()["vector-".len()..].parse::<usize>()?;
+            Ok(services[index].clone())
+        });
+        BalancedService::new(uris, config, factory).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn balances_across_endpoints() {
+        let services = vec![MockService::new(false), MockService::new(false)];
+        let mut service = balanced(services.clone(), LoadBalancingConfig::default()).await;
+
+        for _ in 0..10 {
+            service.call(VectorRequest::default()).await.unwrap();
+        }
+
+        assert_eq!(services[0].calls.load(Ordering::Relaxed), 5);
+        assert_eq!(services[1].calls.load(Ordering::Relaxed), 5);
+    }
+
+    #[tokio::test]
+    async fn ejects_failing_endpoint() {
+        let services = vec![MockService::new(false), MockService::new(true)];
+        let config = LoadBalancingConfig {
+            ejection_consecutive_errors: 2,
+            ..Default::default()
+        };
+        let mut service = balanced(services.clone(), config).await;
+
+        for _ in 0..20 {
+            let _ = service.call(VectorRequest::default()).await;
+        }
+
+        // The failing endpoint is weighted down after its first failure, and ejected after its
+        // second one, which only leaves the healthy endpoint.
+        assert_eq!(services[1].calls.load(Ordering::Relaxed), 2);
+        assert_eq!(services[0].calls.load(Ordering::Relaxed), 18);
+    }
+
+    #[tokio::test]
+    async fn never_ejects_every_endpoint() {
+        let services = vec![MockService::new(true)];
+        let config = LoadBalancingConfig {
+            ejection_consecutive_errors: 1,
+            ..Default::default()
+        };
+        let mut service = balanced(services.clone(), config).await;
+
+        for _ in 0..3 {
+            let _ = service.call(VectorRequest::default()).await;
+        }
+
+        assert_eq!(services[0].calls.load(Ordering::Relaxed), 3);
+        let endpoints = service.endpoints.read().unwrap();
+        assert!(!endpoints[0]
+            .health
+            .lock()
+            .unwrap()
+            .is_ejected(Instant::now()));
+    }
+}
@@ -1,30 +1,29 @@
+use std::{net::SocketAddr, sync::Arc};
+
 use http::Uri;
-use hyper::client::HttpConnector;
-use hyper_openssl::HttpsConnector;
-use hyper_proxy::ProxyConnector;
-use tonic::body::BoxBody;
 use tower::ServiceBuilder;
 use vector_config::configurable_component;
 
 use super::{
+    balance::{BalancedService, EndpointFactory, LoadBalancingConfig},
     service::{VectorResponse, VectorService},
     sink::VectorSink,
     VectorSinkError,
 };
 use crate::{
     config::{
-        AcknowledgementsConfig, GenerateConfig, Input, ProxyConfig, SinkConfig, SinkContext,
+        AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext,
         SinkHealthcheckOptions,
     },
     proto::vector as proto,
     sinks::{
         util::{
-            retries::RetryLogic, BatchConfig, RealtimeEventBasedDefaultBatchSettings,
-            ServiceBuilderExt, TowerRequestConfig,
+            grpc::GrpcClientConfig, retries::RetryLogic, BatchConfig,
+            RealtimeEventBasedDefaultBatchSettings, ServiceBuilderExt, TowerRequestConfig,
         },
         Healthcheck, VectorSink as VectorSinkType,
     },
-    tls::{tls_connector_builder, MaybeTlsSettings, TlsEnableableConfig},
+    tls::{MaybeTlsSettings, TlsEnableableConfig},
 };
 
 /// Configuration for the `vector` sink.
@@ -40,13 +39,16 @@ pub struct VectorConfig {
     /// The address _must_ include a port.
     address: String,
 
-    /// Whether or not to compress requests.
-    ///
-    /// If set to `true`, requests will be compressed with [`gzip`][gzip_docs].
+    /// Additional downstream Vector addresses to connect to.
     ///
-    /// [gzip_docs]: https://en.wikipedia.org/wiki/Gzip
+    /// Requests are balanced across `address` and these addresses. Each address _must_ include a
+    /// port.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    addresses: Vec<String>,
+
+    #[configurable(derived)]
     #[serde(default)]
-    compression: bool,
+    compression: VectorCompression,
 
     #[configurable(derived)]
     #[serde(default)]
@@ -56,6 +58,14 @@ pub struct VectorConfig {
     #[serde(default)]
     pub request: TowerRequestConfig,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub grpc: GrpcClientConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub load_balancing: LoadBalancingConfig,
+
     #[configurable(derived)]
     #[serde(default)]
     tls: Option<TlsEnableableConfig>,
@@ -69,6 +79,57 @@ pub struct VectorConfig {
     pub(in crate::sinks::vector) acknowledgements: AcknowledgementsConfig,
 }
 
+/// Compression of requests.
+///
+/// For backwards compatibility, this can also be a boolean, where `true` is the same as `gzip`.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(untagged)]
+pub enum VectorCompression {
+    /// Whether or not to compress requests with gzip.
+    Enabled(#[configurable(transparent)] bool),
+
+    /// The compression algorithm to use.
+    Algorithm(#[configurable(transparent)] VectorCompressionAlgorithm),
+}
+
+impl Default for VectorCompression {
+    fn default() -> Self {
+        Self::Enabled(false)
+    }
+}
+
+impl VectorCompression {
+    pub const fn algorithm(self) -> VectorCompressionAlgorithm {
+        match self {
+            Self::Enabled(true) => VectorCompressionAlgorithm::Gzip,
+            Self::Enabled(false) => VectorCompressionAlgorithm::None,
+            Self::Algorithm(algorithm) => algorithm,
+        }
+    }
+}
+
+/// Compression algorithm of requests.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum VectorCompressionAlgorithm {
+    /// No compression.
+    None,
+
+    /// [Gzip][gzip] compression.
+    ///
+    /// [gzip]: https://en.wikipedia.org/wiki/Gzip
+    Gzip,
+
+    /// [Zstandard][zstd] compression.
+    ///
+    /// The downstream Vector must be recent enough for its `vector` source to support zstd.
+    ///
+    /// [zstd]: https://facebook.github.io/zstd/
+    Zstd,
+}
+
 impl GenerateConfig for VectorConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(default_config("127.0.0.1:6000")).unwrap()
@@ -79,9 +140,12 @@ fn default_config(address: &str) -> VectorConfig {
     VectorConfig {
         version: None,
         address: address.to_owned(),
-        compression: false,
+        addresses: Vec::new(),
+        compression: VectorCompression::default(),
         batch: BatchConfig::default(),
         request: TowerRequestConfig::default(),
+        grpc: GrpcClientConfig::default(),
+        load_balancing: LoadBalancingConfig::default(),
         tls: None,
         acknowledgements: Default::default(),
     }
@@ -91,19 +155,35 @@ fn default_config(address: &str) -> VectorConfig {
 impl SinkConfig for VectorConfig {
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSinkType, Healthcheck)> {
         let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
-        let uri = with_default_scheme(&self.address, tls.is_tls())?;
-
-        let client = new_client(&tls, cx.proxy())?;
-
-        let healthcheck_uri = cx
-            .healthcheck
-            .uri
-            .clone()
-            .map(|uri| uri.uri)
-            .unwrap_or_else(|| uri.clone());
-        let healthcheck_client = VectorService::new(client.clone(), healthcheck_uri, false);
+        let uris = std::iter::once(&self.address)
+            .chain(&self.addresses)
+            .map(|address| with_default_scheme(address, tls.is_tls()))
+            .collect::<crate::Result<Vec<_>>>()?;
+
+        // The healthcheck only checks the first address.
+        let channel = self.grpc.build_channel(uris[0].clone(), &tls, cx.proxy())?;
+        let healthcheck_channel = match cx.healthcheck.uri.clone() {
+            Some(uri) => channel.with_uri(uri.uri),
+            None => channel,
+        };
+        let healthcheck_client =
+            VectorService::new(healthcheck_channel, VectorCompressionAlgorithm::None);
         let healthcheck = healthcheck(healthcheck_client, cx.healthcheck);
-        let service = VectorService::new(client, uri, self.compression);
+
+        let grpc = self.grpc;
+        let proxy = cx.proxy().clone();
+        let compression = self.compression.algorithm();
+        let factory: EndpointFactory<VectorService> =
+            Arc::new(move |uri: &Uri, address: Option<SocketAddr>| {
+                let channel = match address {
+                    Some(address) => {
+                        grpc.build_pinned_channel(uri.clone(), address, &tls, &proxy)?
+                    }
+                    None => grpc.build_channel(uri.clone(), &tls, &proxy)?,
+                };
+                Ok(VectorService::new(channel, compression))
+            });
+        let service = BalancedService::new(uris, self.load_balancing, factory).await?;
         let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
         let batch_settings = self.batch.into_batcher_settings()?;
 
@@ -187,31 +267,6 @@ pub fn with_default_scheme(address: &str, tls: bool) -> crate::Result<Uri> {
     }
 }
 
-fn new_client(
-    tls_settings: &MaybeTlsSettings,
-    proxy_config: &ProxyConfig,
-) -> crate::Result<hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>> {
-    let mut http = HttpConnector::new();
-    http.enforce_http(false);
-
-    let tls = tls_connector_builder(tls_settings)?;
-    let mut https = HttpsConnector::with_connector(http, tls)?;
-
-    let settings = tls_settings.tls().cloned();
-    https.set_callback(move |c, _uri| {
-        if let Some(settings) = &settings {
-            settings.apply_connect_configuration(c);
-        }
-
-        Ok(())
-    });
-
-    let mut proxy = ProxyConnector::new(https).unwrap();
-    proxy_config.configure(&mut proxy)?;
-
-    Ok(hyper::Client::builder().http2_only(true).build(proxy))
-}
-
 #[derive(Debug, Clone)]
 struct VectorGrpcRetryLogic;
 
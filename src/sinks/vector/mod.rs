@@ -2,6 +2,7 @@ use snafu::Snafu;
 
 use vector_config::configurable_component;
 
+mod balance;
 mod config;
 mod service;
 mod sink;
@@ -1,29 +1,29 @@
-use std::task::{Context, Poll};
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
 
 use futures::{future::BoxFuture, TryFutureExt};
-use http::Uri;
-use hyper::client::HttpConnector;
-use hyper_openssl::HttpsConnector;
-use hyper_proxy::ProxyConnector;
 use prost::Message;
 use proto_event::EventWrapper;
-use tonic::{body::BoxBody, IntoRequest};
+use tonic::IntoRequest;
 use vector_core::{
-    event::proto as proto_event, internal_event::CountByteSize, stream::DriverResponse,
+    event::proto as proto_event, internal_event::CountByteSize, schema::Definition,
+    stream::DriverResponse,
 };
 
-use super::VectorSinkError;
+use super::{config::VectorCompressionAlgorithm, VectorSinkError};
 use crate::{
     event::{EventFinalizers, EventStatus, Finalizable},
     internal_events::EndpointBytesSent,
-    proto::vector as proto_vector,
-    sinks::util::uri,
+    proto::{schema::insert_schema_definition, vector as proto_vector},
+    sinks::util::{grpc::GrpcChannel, uri},
     Error,
 };
 
 #[derive(Clone, Debug)]
 pub struct VectorService {
-    pub client: proto_vector::Client<HyperSvc>,
+    pub client: proto_vector::Client<GrpcChannel>,
     pub protocol: String,
     pub endpoint: String,
 }
@@ -48,6 +48,29 @@ pub struct VectorRequest {
     pub events: Vec<EventWrapper>,
     pub finalizers: EventFinalizers,
     pub events_byte_size: usize,
+    pub schema_definitions: Vec<Arc<Definition>>,
+}
+
+impl VectorRequest {
+    /// Adds the schema definition of an event of the request.
+    pub fn add_schema_definition(&mut self, definition: Arc<Definition>) {
+        // Events of a batch usually come from a handful of components, so there are few distinct definitions.
+        if !self
+            .schema_definitions
+            .iter()
+            .any(|existing| Arc::ptr_eq(existing, &definition))
+        {
+            self.schema_definitions.push(definition);
+        }
+    }
+
+    /// Gets the schema definition covering all the events of the request.
+    fn schema_definition(&self) -> Option<Definition> {
+        self.schema_definitions
+            .iter()
+            .map(|definition| definition.as_ref().clone())
+            .reduce(Definition::merge)
+    }
 }
 
 impl Finalizable for VectorRequest {
@@ -57,20 +80,16 @@ impl Finalizable for VectorRequest {
 }
 
 impl VectorService {
-    pub fn new(
-        hyper_client: hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>,
-        uri: Uri,
-        compression: bool,
-    ) -> Self {
-        let (protocol, endpoint) = uri::protocol_endpoint(uri.clone());
-        let mut proto_client = proto_vector::Client::new(HyperSvc {
-            uri,
-            client: hyper_client,
-        });
-
-        if compression {
-            proto_client = proto_client.send_compressed(tonic::codec::CompressionEncoding::Gzip);
-        }
+    pub fn new(channel: GrpcChannel, compression: VectorCompressionAlgorithm) -> Self {
+        let (protocol, endpoint) = uri::protocol_endpoint(channel.uri().clone());
+        let proto_client = match compression {
+            VectorCompressionAlgorithm::None => proto_vector::Client::new(channel),
+            VectorCompressionAlgorithm::Gzip => proto_vector::Client::new(channel)
+                .send_compressed(tonic::codec::CompressionEncoding::Gzip),
+            VectorCompressionAlgorithm::Zstd => {
+                proto_vector::Client::new(channel.with_zstd_compression())
+            }
+        };
         Self {
             client: proto_client,
             protocol,
@@ -97,15 +116,20 @@ impl tower::Service<VectorRequest> for VectorService {
         let mut service = self.clone();
         let events_count = list.events.len();
         let events_byte_size = list.events_byte_size;
+        let schema_definition = list.schema_definition();
 
         let request = proto_vector::PushEventsRequest {
             events: list.events,
         };
         let byte_size = request.encoded_len();
+        let mut request = request.into_request();
+        if let Some(definition) = schema_definition {
+            insert_schema_definition(request.metadata_mut(), &definition);
+        }
         let future = async move {
             service
                 .client
-                .push_events(request.into_request())
+                .push_events(request)
                 .map_ok(|_response| {
                     emit!(EndpointBytesSent {
                         byte_size,
@@ -124,32 +148,3 @@ impl tower::Service<VectorRequest> for VectorService {
         Box::pin(future)
     }
 }
-
-#[derive(Clone, Debug)]
-pub struct HyperSvc {
-    uri: Uri,
-    client: hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>,
-}
-
-impl tower::Service<hyper::Request<BoxBody>> for HyperSvc {
-    type Response = hyper::Response<hyper::Body>;
-    type Error = hyper::Error;
-    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
-
-    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
-        Poll::Ready(Ok(()))
-    }
-
-    fn call(&mut self, mut req: hyper::Request<BoxBody>) -> Self::Future {
-        let uri = Uri::builder()
-            .scheme(self.uri.scheme().unwrap().clone())
-            .authority(self.uri.authority().unwrap().clone())
-            .path_and_query(req.uri().path_and_query().unwrap().clone())
-            .build()
-            .unwrap();
-
-        *req.uri_mut() = uri;
-
-        Box::pin(self.client.request(req))
-    }
-}
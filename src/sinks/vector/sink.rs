@@ -1,10 +1,11 @@
-use std::fmt;
+use std::{fmt, sync::Arc};
 
 use async_trait::async_trait;
 use futures::{stream::BoxStream, StreamExt};
 use prost::Message;
 use tower::Service;
 use vector_core::{
+    schema::Definition,
     stream::{BatcherSettings, DriverResponse},
     ByteSizeOf,
 };
@@ -18,6 +19,7 @@ use crate::{
 struct EventData {
     byte_size: usize,
     finalizers: EventFinalizers,
+    schema_definition: Arc<Definition>,
     wrapper: EventWrapper,
 }
 
@@ -38,6 +40,7 @@ where
             .map(|mut event| EventData {
                 byte_size: event.size_of(),
                 finalizers: event.take_finalizers(),
+                schema_definition: Arc::clone(event.metadata().shared_schema_definition()),
                 wrapper: EventWrapper::from(event),
             })
             .batched(self.batch_settings.into_reducer_config(
@@ -45,6 +48,7 @@ where
                 |req: &mut VectorRequest, item: EventData| {
                     req.events_byte_size += item.byte_size;
                     req.finalizers.merge(item.finalizers);
+                    req.add_schema_definition(item.schema_definition);
                     req.events.push(item.wrapper);
                 },
             ))
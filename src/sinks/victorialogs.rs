@@ -0,0 +1,390 @@
+use bytes::{Bytes, BytesMut};
+use chrono::{DateTime, TimeZone, Utc};
+use futures::{FutureExt, SinkExt};
+use http::{Request, StatusCode, Uri};
+use hyper::Body;
+use vector_config::configurable_component;
+
+use crate::{
+    codecs::Transformer,
+    config::{log_schema, AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
+    event::{Event, Value},
+    http::{Auth, HttpClient, MaybeAuth},
+    internal_events::SinkRequestBuildError,
+    sinks::{
+        util::{
+            http::{BatchedHttpSink, HttpEventEncoder, HttpSink},
+            BatchConfig, Buffer, Compression, SinkBatchSettings, TowerRequestConfig, UriSerde,
+        },
+        Healthcheck, HealthcheckError, VectorSink,
+    },
+    tls::{TlsConfig, TlsSettings},
+};
+
+/// Configuration for the `victorialogs` sink.
+#[configurable_component(sink("victorialogs"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct VictoriaLogsConfig {
+    /// The base URL of the VictoriaLogs instance.
+    ///
+    /// Vector will append `/insert/jsonline` to this.
+    endpoint: UriSerde,
+
+    /// The field holding the log message.
+    ///
+    /// It is sent as the `_msg` field. If not set, the global `log_schema.message_key` is used.
+    message_field: Option<String>,
+
+    /// The field holding the log timestamp.
+    ///
+    /// It is sent as the `_time` field. If not set, the global `log_schema.timestamp_key` is used.
+    ///
+    /// RFC 3339 strings and integer Unix timestamps, in seconds, are converted to timestamps, while
+    /// other values are sent as is. Events without a timestamp are sent with the current time.
+    timestamp_field: Option<String>,
+
+    /// The fields identifying the log stream.
+    ///
+    /// These should be low-cardinality fields, such as the host or the application name.
+    #[serde(default)]
+    stream_fields: Vec<String>,
+
+    /// The `AccountID` of the tenant to send logs to.
+    account_id: Option<u32>,
+
+    /// The `ProjectID` of the tenant to send logs to.
+    project_id: Option<u32>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    encoding: Transformer,
+
+    #[configurable(derived)]
+    #[serde(default = "Compression::gzip_default")]
+    compression: Compression,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    batch: BatchConfig<VictoriaLogsDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    auth: Option<Auth>,
+
+    #[configurable(derived)]
+    tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct VictoriaLogsDefaultBatchSettings;
+
+impl SinkBatchSettings for VictoriaLogsDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = None;
+    const MAX_BYTES: Option<usize> = Some(10_000_000);
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+impl GenerateConfig for VictoriaLogsConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"endpoint = "http://localhost:9428""#).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for VictoriaLogsConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls, cx.proxy())?;
+        let auth = self.auth.choose_one(&self.endpoint.auth)?;
+
+        let healthcheck = healthcheck(self.endpoint.clone(), auth.clone(), client.clone()).boxed();
+
+        let sink = VictoriaLogsSink {
+            uri: self.build_uri()?,
+            account_id: self.account_id,
+            project_id: self.project_id,
+            message_field: self
+                .message_field
+                .clone()
+                .unwrap_or_else(|| log_schema().message_key().to_owned()),
+            timestamp_field: self
+                .timestamp_field
+                .clone()
+                .unwrap_or_else(|| log_schema().timestamp_key().to_owned()),
+            transformer: self.encoding.clone(),
+            compression: self.compression,
+            auth,
+        };
+
+        let request = self.request.unwrap_with(&TowerRequestConfig::default());
+        let batch = self.batch.into_batch_settings()?;
+        let sink = BatchedHttpSink::new(
+            sink,
+            Buffer::new(batch.size, self.compression),
+            request,
+            batch.timeout,
+            client,
+        )
+        .sink_map_err(|error| error!(message = "Fatal victorialogs sink error.", %error));
+
+        Ok((VectorSink::from_event_sink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+impl VictoriaLogsConfig {
+    fn build_uri(&self) -> crate::Result<Uri> {
+        let uri = self.endpoint.append_path("insert/jsonline")?;
+        if self.stream_fields.is_empty() {
+            return Ok(uri.uri);
+        }
+
+        let query = url::form_urlencoded::Serializer::new(String::new())
+            .append_pair("_stream_fields", &self.stream_fields.join(","))
+            .finish();
+        format!("{}?{}", uri.uri, query)
+            .parse::<Uri>()
+            .map_err(Into::into)
+    }
+}
+
+struct VictoriaLogsSink {
+    uri: Uri,
+    account_id: Option<u32>,
+    project_id: Option<u32>,
+    message_field: String,
+    timestamp_field: String,
+    transformer: Transformer,
+    compression: Compression,
+    auth: Option<Auth>,
+}
+
+struct VictoriaLogsEventEncoder {
+    message_field: String,
+    timestamp_field: String,
+    transformer: Transformer,
+}
+
+impl HttpEventEncoder<BytesMut> for VictoriaLogsEventEncoder {
+    fn encode_event(&mut self, mut event: Event) -> Option<BytesMut> {
+        self.transformer.transform(&mut event);
+        let mut log = event.into_log();
+
+        // VictoriaLogs expects the message and the timestamp in the `_msg` and `_time` fields.
+        if let Some(message) = log.remove(self.message_field.as_str()) {
+            log.insert("_msg", message);
+        }
+        let time = match log.remove(self.timestamp_field.as_str()) {
+            Some(Value::Timestamp(timestamp)) => format_time(timestamp),
+            Some(Value::Bytes(bytes)) => {
+                match DateTime::parse_from_rfc3339(&String::from_utf8_lossy(&bytes)) {
+                    Ok(timestamp) => format_time(timestamp.with_timezone(&Utc)),
+                    Err(_) => Value::Bytes(bytes),
+                }
+            }
+            Some(Value::Integer(seconds)) => match Utc.timestamp_opt(seconds, 0).single() {
+                Some(timestamp) => format_time(timestamp),
+                None => Value::Integer(seconds),
+            },
+            // VictoriaLogs may still be able to make sense of other values.
+            Some(value) => value,
+            None => format_time(Utc::now()),
+        };
+        log.insert("_time", time);
+
+        let mut body = crate::serde::json::to_bytes(&log)
+            .map_err(|error| emit!(SinkRequestBuildError { error }))
+            .ok()?;
+        body.extend_from_slice(b"\n");
+
+        Some(body)
+    }
+}
+
+fn format_time(timestamp: DateTime<Utc>) -> Value {
+    timestamp
+        .to_rfc3339_opts(chrono::SecondsFormat::Nanos, true)
+        .into()
+}
+
+#[async_trait::async_trait]
+impl HttpSink for VictoriaLogsSink {
+    type Input = BytesMut;
+    type Output = BytesMut;
+    type Encoder = VictoriaLogsEventEncoder;
+
+    fn build_encoder(&self) -> Self::Encoder {
+        VictoriaLogsEventEncoder {
+            message_field: self.message_field.clone(),
+            timestamp_field: self.timestamp_field.clone(),
+            transformer: self.transformer.clone(),
+        }
+    }
+
+    async fn build_request(&self, body: Self::Output) -> crate::Result<Request<Bytes>> {
+        let mut builder =
+            Request::post(self.uri.clone()).header("Content-Type", "application/stream+json");
+
+        if let Some(ce) = self.compression.content_encoding() {
+            builder = builder.header("Content-Encoding", ce);
+        }
+        if let Some(account_id) = self.account_id {
+            builder = builder.header("AccountID", account_id);
+        }
+        if let Some(project_id) = self.project_id {
+            builder = builder.header("ProjectID", project_id);
+        }
+
+        let mut request = builder.body(body.freeze())?;
+        if let Some(auth) = &self.auth {
+            auth.apply(&mut request);
+        }
+
+        Ok(request)
+    }
+}
+
+async fn healthcheck(
+    endpoint: UriSerde,
+    auth: Option<Auth>,
+    client: HttpClient,
+) -> crate::Result<()> {
+    let uri = endpoint.append_path("health")?;
+    let mut request = Request::get(&uri.uri).body(Body::empty())?;
+    if let Some(auth) = auth {
+        auth.apply(&mut request);
+    }
+
+    let response = client.send(request).await?;
+
+    match response.status() {
+        StatusCode::OK => Ok(()),
+        status => Err(HealthcheckError::UnexpectedStatus { status }.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{stream, StreamExt};
+    use indoc::indoc;
+
+    use super::*;
+    use crate::{
+        event::LogEvent,
+        sinks::util::test::{build_test_server, load_sink},
+        test_util::{
+            components::{run_and_assert_sink_compliance, HTTP_SINK_TAGS},
+            next_addr,
+        },
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<VictoriaLogsConfig>();
+    }
+
+    #[test]
+    fn maps_message_and_timestamp() {
+        let mut encoder = VictoriaLogsEventEncoder {
+            message_field: log_schema().message_key().to_owned(),
+            timestamp_field: log_schema().timestamp_key().to_owned(),
+            transformer: Default::default(),
+        };
+        let mut log = LogEvent::from("hello world");
+        log.insert(log_schema().timestamp_key(), Utc.timestamp(1, 0));
+        log.insert("host", "localhost");
+
+        let encoded = encoder.encode_event(log.into()).unwrap();
+        assert_eq!(encoded.last(), Some(&b'\n'));
+
+        let line: serde_json::Value = serde_json::from_slice(&encoded).unwrap();
+        assert_eq!(
+            line,
+            serde_json::json!({
+                "_msg": "hello world",
+                "_time": "1970-01-01T00:00:01.000000000Z",
+                "host": "localhost",
+            })
+        );
+    }
+
+    #[test]
+    fn converts_timestamp_values() {
+        let mut encoder = VictoriaLogsEventEncoder {
+            message_field: log_schema().message_key().to_owned(),
+            timestamp_field: log_schema().timestamp_key().to_owned(),
+            transformer: Default::default(),
+        };
+        let mut encode_time = |timestamp: Option<Value>| {
+            let mut log = LogEvent::from("hello world");
+            if let Some(timestamp) = timestamp {
+                log.insert(log_schema().timestamp_key(), timestamp);
+            }
+            let encoded = encoder.encode_event(log.into()).unwrap();
+            serde_json::from_slice::<serde_json::Value>(&encoded).unwrap()["_time"].clone()
+        };
+
+        assert_eq!(
+            encode_time(Some("1970-01-01T02:00:01+02:00".into())),
+            "1970-01-01T00:00:01.000000000Z"
+        );
+        assert_eq!(
+            encode_time(Some(Value::Integer(1))),
+            "1970-01-01T00:00:01.000000000Z"
+        );
+        assert_eq!(encode_time(Some("yesterday".into())), "yesterday");
+        assert!(encode_time(None).is_string());
+    }
+
+    #[tokio::test]
+    async fn sends_stream_fields_and_tenant() {
+        let addr = next_addr();
+        let config = format!(
+            indoc! {r#"
+                endpoint = "http://{}"
+                stream_fields = ["host", "app"]
+                account_id = 12
+                compression = "none"
+            "#},
+            addr
+        );
+        let (config, cx) = load_sink::<VictoriaLogsConfig>(&config).unwrap();
+        let (sink, _) = config.build(cx).await.unwrap();
+        let (rx, _trigger, server) = build_test_server(addr);
+        tokio::spawn(server);
+
+        let event = Event::from(LogEvent::from("hello world"));
+        run_and_assert_sink_compliance(sink, stream::once(async { event }), &HTTP_SINK_TAGS).await;
+
+        let output = rx.take(1).collect::<Vec<_>>().await;
+        let (parts, body) = &output[0];
+        assert_eq!(parts.uri.path(), "/insert/jsonline");
+        assert_eq!(parts.uri.query(), Some("_stream_fields=host%2Capp"));
+        assert_eq!(parts.headers["AccountID"], "12");
+
+        let line: serde_json::Value = serde_json::from_slice(body).unwrap();
+        assert_eq!(line["_msg"], "hello world");
+    }
+}
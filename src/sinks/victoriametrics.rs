@@ -0,0 +1,678 @@
+use std::io::Write;
+
+use bytes::{Bytes, BytesMut};
+use futures::{FutureExt, SinkExt};
+use http::{Request, StatusCode};
+use hyper::Body;
+use serde_json::json;
+use vector_config::configurable_component;
+
+use crate::{
+    config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
+    event::{metric::MetricTags, Event},
+    http::{Auth, HttpClient, MaybeAuth},
+    sinks::{
+        prometheus::{
+            collector::{MetricCollector, StringCollector},
+            default_histogram_buckets, default_summary_quantiles,
+            remote_write::PrometheusMetricNormalize,
+        },
+        util::{
+            buffer::metrics::MetricNormalizer,
+            http::{BatchedHttpSink, HttpEventEncoder, HttpSink},
+            BatchConfig, Buffer, Compression, Compressor, SinkBatchSettings, TowerRequestConfig,
+            UriSerde,
+        },
+        Healthcheck, HealthcheckError, VectorSink,
+    },
+    tls::{TlsConfig, TlsSettings},
+};
+
+/// The import format used to send metrics to VictoriaMetrics.
+#[configurable_component]
+#[derive(Copy, Clone, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum VictoriaMetricsFormat {
+    /// JSON lines, sent to `/api/v1/import`.
+    ///
+    /// This is the format VictoriaMetrics exports with `/api/v1/export`.
+    #[derivative(Default)]
+    Jsonl,
+
+    /// The Prometheus text exposition format, with timestamps, sent to `/api/v1/import/prometheus`.
+    Prometheus,
+
+    /// The binary format, sent to `/api/v1/import/native`.
+    ///
+    /// This is the format VictoriaMetrics exports with `/api/v1/export/native`, and the most
+    /// efficient one for it to ingest.
+    Native,
+}
+
+impl VictoriaMetricsFormat {
+    const fn path(self) -> &'static str {
+        match self {
+            VictoriaMetricsFormat::Jsonl => "api/v1/import",
+            VictoriaMetricsFormat::Prometheus => "api/v1/import/prometheus",
+            VictoriaMetricsFormat::Native => "api/v1/import/native",
+        }
+    }
+
+    const fn content_type(self) -> &'static str {
+        match self {
+            VictoriaMetricsFormat::Jsonl => "application/stream+json",
+            VictoriaMetricsFormat::Prometheus => "text/plain",
+            VictoriaMetricsFormat::Native => "application/octet-stream",
+        }
+    }
+
+    /// The bytes heading each request, before the encoded events.
+    ///
+    /// Native imports start with the time range outside of which VictoriaMetrics drops samples.
+    /// The events are encoded before the batch they end up in is known, so it spans every
+    /// timestamp.
+    fn header(self) -> Vec<u8> {
+        match self {
+            VictoriaMetricsFormat::Native => [0, i64::MAX]
+                .into_iter()
+                .flat_map(|timestamp| zigzag(timestamp).to_be_bytes())
+                .collect(),
+            VictoriaMetricsFormat::Jsonl | VictoriaMetricsFormat::Prometheus => Vec::new(),
+        }
+    }
+}
+
+/// Configuration for the `victoriametrics` sink.
+#[configurable_component(sink("victoriametrics"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct VictoriaMetricsConfig {
+    /// The base URL of the VictoriaMetrics instance.
+    ///
+    /// Vector will append the import path of the configured `format` to this. For the cluster
+    /// version, this should point to `vminsert`, including the tenant, such as
+    /// `http://vminsert:8480/insert/0/prometheus`.
+    endpoint: UriSerde,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    format: VictoriaMetricsFormat,
+
+    /// The default namespace for any metrics sent.
+    ///
+    /// This namespace is only used if a metric has no existing namespace. When a namespace is
+    /// present, it is used as a prefix to the metric name, and separated with an underscore (`_`).
+    default_namespace: Option<String>,
+
+    /// Default buckets to use for aggregating [distribution][dist_metric_docs] metrics into histograms.
+    ///
+    /// [dist_metric_docs]: https://vector.dev/docs/about/under-the-hood/architecture/data-model/metric/#distribution
+    #[serde(default = "default_histogram_buckets")]
+    buckets: Vec<f64>,
+
+    /// Quantiles to use for aggregating [distribution][dist_metric_docs] metrics into a summary.
+    ///
+    /// [dist_metric_docs]: https://vector.dev/docs/about/under-the-hood/architecture/data-model/metric/#distribution
+    #[serde(default = "default_summary_quantiles")]
+    quantiles: Vec<f64>,
+
+    #[configurable(derived)]
+    #[serde(default = "Compression::gzip_default")]
+    compression: Compression,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    batch: BatchConfig<VictoriaMetricsDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    auth: Option<Auth>,
+
+    #[configurable(derived)]
+    tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct VictoriaMetricsDefaultBatchSettings;
+
+impl SinkBatchSettings for VictoriaMetricsDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = Some(10_000);
+    const MAX_BYTES: Option<usize> = Some(10_000_000);
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+impl GenerateConfig for VictoriaMetricsConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"endpoint = "http://localhost:8428""#).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for VictoriaMetricsConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls, cx.proxy())?;
+        let auth = self.auth.choose_one(&self.endpoint.auth)?;
+
+        let healthcheck = healthcheck(self.endpoint.clone(), auth.clone(), client.clone()).boxed();
+
+        let sink = VictoriaMetricsSink {
+            endpoint: self.endpoint.append_path(self.format.path())?,
+            format: self.format,
+            default_namespace: self.default_namespace.clone(),
+            buckets: self.buckets.clone(),
+            quantiles: self.quantiles.clone(),
+            compression: self.compression,
+            auth,
+        };
+
+        let request = self.request.unwrap_with(&TowerRequestConfig::default());
+        let batch = self.batch.into_batch_settings()?;
+        // Batches are compressed along with their header when the request is built.
+        let sink = BatchedHttpSink::new(
+            sink,
+            Buffer::new(batch.size, Compression::None),
+            request,
+            batch.timeout,
+            client,
+        )
+        .sink_map_err(|error| error!(message = "Fatal victoriametrics sink error.", %error));
+
+        Ok((VectorSink::from_event_sink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::metric()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+struct VictoriaMetricsSink {
+    endpoint: UriSerde,
+    format: VictoriaMetricsFormat,
+    default_namespace: Option<String>,
+    buckets: Vec<f64>,
+    quantiles: Vec<f64>,
+    compression: Compression,
+    auth: Option<Auth>,
+}
+
+/// Collects metrics as VictoriaMetrics JSON lines, one line per sample.
+struct JsonLinesCollector {
+    lines: String,
+}
+
+impl MetricCollector for JsonLinesCollector {
+    type Output = String;
+
+    fn new() -> Self {
+        Self {
+            lines: String::new(),
+        }
+    }
+
+    fn emit_metadata(&mut self, _name: &str, _fullname: &str, _value: &crate::event::MetricValue) {}
+
+    fn emit_value(
+        &mut self,
+        timestamp_millis: Option<i64>,
+        name: &str,
+        suffix: &str,
+        value: f64,
+        tags: Option<&MetricTags>,
+        extra: Option<(&str, String)>,
+    ) {
+        let mut labels = serde_json::Map::new();
+        labels.insert("__name__".into(), format!("{}{}", name, suffix).into());
+        for (key, value) in tags.into_iter().flatten() {
+            labels.insert(key.to_string(), value.clone().into());
+        }
+        if let Some((key, value)) = extra {
+            labels.insert(key.into(), value.into());
+        }
+
+        let timestamp = timestamp_millis.unwrap_or_else(|| chrono::Utc::now().timestamp_millis());
+        let line = json!({
+            "metric": labels,
+            "values": [value],
+            "timestamps": [timestamp],
+        });
+
+        self.lines.push_str(&line.to_string());
+        self.lines.push('\n');
+    }
+
+    fn finish(self) -> String {
+        self.lines
+    }
+}
+
+/// The marshal type of a block column whose rows all have the same value, which is then stored in
+/// the block header rather than in its data.
+const MARSHAL_TYPE_CONST: u8 = 3;
+
+/// Collects metrics as VictoriaMetrics native blocks, one block per sample.
+///
+/// Each block is made of the metric name, then the block of samples in its portable form, each
+/// prefixed by its length. A block holding a single sample has constant timestamps and values, so
+/// they're entirely carried by its header.
+struct NativeCollector {
+    blocks: Vec<u8>,
+}
+
+impl MetricCollector for NativeCollector {
+    type Output = Vec<u8>;
+
+    fn new() -> Self {
+        Self { blocks: Vec::new() }
+    }
+
+    fn emit_metadata(&mut self, _name: &str, _fullname: &str, _value: &crate::event::MetricValue) {}
+
+    fn emit_value(
+        &mut self,
+        timestamp_millis: Option<i64>,
+        name: &str,
+        suffix: &str,
+        value: f64,
+        tags: Option<&MetricTags>,
+        extra: Option<(&str, String)>,
+    ) {
+        // The only NaN VictoriaMetrics stores is its staleness marker, which isn't a sample.
+        if value.is_nan() {
+            return;
+        }
+
+        let mut labels = tags
+            .into_iter()
+            .flatten()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect::<Vec<_>>();
+        if let Some((key, value)) = extra {
+            labels.push((key.into(), value));
+        }
+        labels.sort();
+
+        let mut metric_name = Vec::new();
+        put_tag_value(&mut metric_name, &format!("{}{}", name, suffix));
+        for (key, value) in &labels {
+            put_tag_value(&mut metric_name, key);
+            put_tag_value(&mut metric_name, value);
+        }
+
+        let timestamp = timestamp_millis.unwrap_or_else(|| chrono::Utc::now().timestamp_millis());
+        let (mantissa, scale) = to_decimal(value);
+        let mut block = Vec::new();
+        put_varint(&mut block, timestamp);
+        put_varint(&mut block, mantissa);
+        put_varuint(&mut block, 1);
+        put_varint(&mut block, scale.into());
+        block.push(MARSHAL_TYPE_CONST);
+        block.push(MARSHAL_TYPE_CONST);
+        // Neither the timestamps nor the values have any data.
+        put_varuint(&mut block, 0);
+        put_varuint(&mut block, 0);
+
+        for part in [metric_name, block] {
+            self.blocks
+                .extend_from_slice(&(part.len() as u32).to_be_bytes());
+            self.blocks.extend_from_slice(&part);
+        }
+    }
+
+    fn finish(self) -> Vec<u8> {
+        self.blocks
+    }
+}
+
+/// Appends a metric name or label, escaping the bytes VictoriaMetrics uses as separators.
+fn put_tag_value(buf: &mut Vec<u8>, value: &str) {
+    for byte in value.bytes() {
+        match byte {
+            0 => buf.extend_from_slice(b"\x000"),
+            1 => buf.extend_from_slice(b"\x001"),
+            2 => buf.extend_from_slice(b"\x002"),
+            byte => buf.push(byte),
+        }
+    }
+    buf.push(1);
+}
+
+const fn zigzag(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+fn put_varint(buf: &mut Vec<u8>, value: i64) {
+    put_varuint(buf, zigzag(value));
+}
+
+fn put_varuint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+/// Splits a value into the decimal mantissa and exponent VictoriaMetrics stores it as.
+fn to_decimal(value: f64) -> (i64, i16) {
+    if value.is_infinite() {
+        let mantissa = if value.is_sign_positive() {
+            i64::MAX
+        } else {
+            i64::MIN
+        };
+        return (mantissa, 0);
+    }
+
+    // The shortest representation that round trips has at most 17 significant digits, which
+    // always fit in the mantissa.
+    let formatted = format!("{:e}", value);
+    let (digits, exponent) = formatted
+        .split_once('e')
+        .expect("scientific notation should have an exponent");
+    let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    let mantissa = format!("{}{}", integer, fraction)
+        .parse()
+        .expect("digits should fit in the mantissa");
+    let exponent = exponent
+        .parse::<i16>()
+        .expect("exponent should fit in the scale");
+
+    (mantissa, exponent - fraction.len() as i16)
+}
+
+struct VictoriaMetricsEventEncoder {
+    format: VictoriaMetricsFormat,
+    default_namespace: Option<String>,
+    buckets: Vec<f64>,
+    quantiles: Vec<f64>,
+    normalizer: MetricNormalizer<PrometheusMetricNormalize>,
+}
+
+impl VictoriaMetricsEventEncoder {
+    fn encode_with<C>(&self, metric: &crate::event::Metric) -> Vec<u8>
+    where
+        C: MetricCollector,
+        C::Output: Into<Vec<u8>>,
+    {
+        let mut collector = C::new();
+        collector.encode_metric(
+            self.default_namespace.as_deref(),
+            &self.buckets,
+            &self.quantiles,
+            metric,
+        );
+        collector.finish().into()
+    }
+}
+
+impl HttpEventEncoder<BytesMut> for VictoriaMetricsEventEncoder {
+    fn encode_event(&mut self, event: Event) -> Option<BytesMut> {
+        // VictoriaMetrics only stores absolute values, so incremental metrics are accumulated
+        // first.
+        let metric = self.normalizer.normalize(event.into_metric())?;
+
+        let encoded = match self.format {
+            VictoriaMetricsFormat::Jsonl => self.encode_with::<JsonLinesCollector>(&metric),
+            VictoriaMetricsFormat::Prometheus => self.encode_with::<StringCollector>(&metric),
+            VictoriaMetricsFormat::Native => self.encode_with::<NativeCollector>(&metric),
+        };
+
+        Some(encoded.as_slice().into())
+    }
+}
+
+#[async_trait::async_trait]
+impl HttpSink for VictoriaMetricsSink {
+    type Input = BytesMut;
+    type Output = BytesMut;
+    type Encoder = VictoriaMetricsEventEncoder;
+
+    fn build_encoder(&self) -> Self::Encoder {
+        VictoriaMetricsEventEncoder {
+            format: self.format,
+            default_namespace: self.default_namespace.clone(),
+            buckets: self.buckets.clone(),
+            quantiles: self.quantiles.clone(),
+            normalizer: MetricNormalizer::default(),
+        }
+    }
+
+    async fn build_request(&self, body: Self::Output) -> crate::Result<Request<Bytes>> {
+        let mut builder =
+            Request::post(&self.endpoint.uri).header("Content-Type", self.format.content_type());
+
+        if let Some(ce) = self.compression.content_encoding() {
+            builder = builder.header("Content-Encoding", ce);
+        }
+
+        let mut compressor = Compressor::from(self.compression);
+        compressor.write_all(&self.format.header())?;
+        compressor.write_all(&body)?;
+
+        let mut request = builder.body(compressor.finish()?.freeze())?;
+        if let Some(auth) = &self.auth {
+            auth.apply(&mut request);
+        }
+
+        Ok(request)
+    }
+}
+
+async fn healthcheck(
+    endpoint: UriSerde,
+    auth: Option<Auth>,
+    client: HttpClient,
+) -> crate::Result<()> {
+    let uri = endpoint.append_path("health")?;
+    let mut request = Request::get(&uri.uri).body(Body::empty())?;
+    if let Some(auth) = auth {
+        auth.apply(&mut request);
+    }
+
+    let response = client.send(request).await?;
+
+    match response.status() {
+        StatusCode::OK => Ok(()),
+        status => Err(HealthcheckError::UnexpectedStatus { status }.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use futures::{stream, StreamExt};
+    use indoc::indoc;
+    use vector_core::event::{Metric, MetricKind, MetricValue};
+
+    use super::*;
+    use crate::{
+        sinks::util::test::{build_test_server, load_sink},
+        test_util::{
+            components::{run_and_assert_sink_compliance, HTTP_SINK_TAGS},
+            next_addr,
+        },
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<VictoriaMetricsConfig>();
+    }
+
+    fn encoder(format: VictoriaMetricsFormat) -> VictoriaMetricsEventEncoder {
+        VictoriaMetricsEventEncoder {
+            format,
+            default_namespace: Some("vector".into()),
+            buckets: default_histogram_buckets(),
+            quantiles: default_summary_quantiles(),
+            normalizer: MetricNormalizer::default(),
+        }
+    }
+
+    fn gauge() -> Event {
+        Metric::new(
+            "requests",
+            MetricKind::Absolute,
+            MetricValue::Gauge { value: 42.0 },
+        )
+        .with_tags(Some(
+            vec![("host".into(), "localhost".to_owned())]
+                .into_iter()
+                .collect(),
+        ))
+        .with_timestamp(Some(chrono::Utc.timestamp(1_600_000_000, 0)))
+        .into()
+    }
+
+    #[test]
+    fn encodes_jsonl() {
+        let encoded = encoder(VictoriaMetricsFormat::Jsonl)
+            .encode_event(gauge())
+            .unwrap();
+        let line: serde_json::Value = serde_json::from_slice(&encoded).unwrap();
+
+        assert_eq!(
+            line,
+            json!({
+                "metric": { "__name__": "vector_requests", "host": "localhost" },
+                "values": [42.0],
+                "timestamps": [1_600_000_000_000_i64],
+            })
+        );
+    }
+
+    #[test]
+    fn encodes_prometheus() {
+        let encoded = encoder(VictoriaMetricsFormat::Prometheus)
+            .encode_event(gauge())
+            .unwrap();
+
+        assert_eq!(
+            String::from_utf8_lossy(&encoded),
+            indoc! {r#"
+                # HELP vector_requests requests
+                # TYPE vector_requests gauge
+                vector_requests{host="localhost"} 42 1600000000000
+            "#}
+        );
+    }
+
+    #[test]
+    fn encodes_native() {
+        let encoded = encoder(VictoriaMetricsFormat::Native)
+            .encode_event(gauge())
+            .unwrap();
+
+        let metric_name = b"vector_requests\x01host\x01localhost\x01";
+        // The zigzag encoded timestamp, the value 42, a single row, a scale of 0, then constant
+        // timestamps and values without any data.
+        let block = [
+            0x80, 0x80, 0xf4, 0xf6, 0x90, 0x5d, 0x54, 0x01, 0x00, 0x03, 0x03, 0x00, 0x00,
+        ];
+        let mut expected = Vec::new();
+        expected.extend_from_slice(&(metric_name.len() as u32).to_be_bytes());
+        expected.extend_from_slice(metric_name);
+        expected.extend_from_slice(&(block.len() as u32).to_be_bytes());
+        expected.extend_from_slice(&block);
+
+        assert_eq!(&encoded[..], &expected[..]);
+    }
+
+    #[test]
+    fn converts_values_to_decimal() {
+        assert_eq!(to_decimal(0.0), (0, 0));
+        assert_eq!(to_decimal(42.0), (42, 0));
+        assert_eq!(to_decimal(0.1), (1, -1));
+        assert_eq!(to_decimal(-2.5), (-25, -1));
+        assert_eq!(to_decimal(1.5e300), (15, 299));
+        assert_eq!(to_decimal(f64::INFINITY), (i64::MAX, 0));
+    }
+
+    #[test]
+    fn accumulates_incremental_counters() {
+        let mut encoder = encoder(VictoriaMetricsFormat::Jsonl);
+        let counter = |value| {
+            Event::from(Metric::new(
+                "hits",
+                MetricKind::Incremental,
+                MetricValue::Counter { value },
+            ))
+        };
+
+        let _ = encoder.encode_event(counter(1.0));
+        let encoded = encoder.encode_event(counter(2.0)).unwrap();
+        let line: serde_json::Value = serde_json::from_slice(&encoded).unwrap();
+
+        assert_eq!(line["values"], json!([3.0]));
+    }
+
+    #[tokio::test]
+    async fn sends_to_import_endpoint() {
+        let addr = next_addr();
+        let config = format!(
+            indoc! {r#"
+                endpoint = "http://{}"
+                compression = "none"
+            "#},
+            addr
+        );
+        let (config, cx) = load_sink::<VictoriaMetricsConfig>(&config).unwrap();
+        let (sink, _) = config.build(cx).await.unwrap();
+        let (rx, _trigger, server) = build_test_server(addr);
+        tokio::spawn(server);
+
+        run_and_assert_sink_compliance(sink, stream::once(async { gauge() }), &HTTP_SINK_TAGS)
+            .await;
+
+        let output = rx.take(1).collect::<Vec<_>>().await;
+        let (parts, body) = &output[0];
+        assert_eq!(parts.uri.path(), "/api/v1/import");
+        assert!(String::from_utf8_lossy(body).contains("\"__name__\":\"requests\""));
+    }
+
+    #[tokio::test]
+    async fn sends_native_with_time_range() {
+        let addr = next_addr();
+        let config = format!(
+            indoc! {r#"
+                endpoint = "http://{}"
+                format = "native"
+                compression = "none"
+            "#},
+            addr
+        );
+        let (config, cx) = load_sink::<VictoriaMetricsConfig>(&config).unwrap();
+        let (sink, _) = config.build(cx).await.unwrap();
+        let (rx, _trigger, server) = build_test_server(addr);
+        tokio::spawn(server);
+
+        run_and_assert_sink_compliance(sink, stream::once(async { gauge() }), &HTTP_SINK_TAGS)
+            .await;
+
+        let output = rx.take(1).collect::<Vec<_>>().await;
+        let (parts, body) = &output[0];
+        assert_eq!(parts.uri.path(), "/api/v1/import/native");
+        assert_eq!(body[..8], [0; 8]);
+        assert_eq!(body[8..16], zigzag(i64::MAX).to_be_bytes());
+        let encoded = encoder(VictoriaMetricsFormat::Native)
+            .encode_event(gauge())
+            .unwrap();
+        assert_eq!(&body[16..], &encoded[..]);
+    }
+}
@@ -2,7 +2,7 @@ use std::{collections::HashMap, fmt};
 
 use chrono::Utc;
 use futures::{Stream, StreamExt};
-use metrics::{register_histogram, Histogram};
+use metrics::{register_gauge, register_histogram, Gauge, Histogram};
 use value::Value;
 use vector_buffers::topology::channel::{self, LimitedReceiver, LimitedSender};
 #[cfg(test)]
@@ -11,6 +11,7 @@ use vector_core::{
     config::{log_schema, Output},
     event::{array, Event, EventArray, EventContainer, EventRef},
     internal_event::{EventsSent, DEFAULT_OUTPUT},
+    memory::MemoryBudget,
     ByteSizeOf,
 };
 
@@ -24,12 +25,14 @@ pub(crate) const CHUNK_SIZE: usize = 1000;
 const TEST_BUFFER_SIZE: usize = 100;
 
 const LAG_TIME_NAME: &str = "source_lag_time_seconds";
+const IN_FLIGHT_BYTES_NAME: &str = "component_in_flight_event_bytes";
 
 pub struct Builder {
     buf_size: usize,
     inner: Option<Inner>,
     named_inners: HashMap<String, Inner>,
     lag_time: Option<Histogram>,
+    memory: Option<SourceMemory>,
 }
 
 impl Builder {
@@ -41,9 +44,20 @@ impl Builder {
             inner: self.inner,
             named_inners: self.named_inners,
             lag_time: self.lag_time,
+            memory: self.memory,
         }
     }
 
+    /// Reserves the size of the events sent from the given memory budget, if any, so that sending
+    /// waits while the events in flight exceed it.
+    pub fn with_memory_budget(mut self, budget: Option<MemoryBudget>) -> Self {
+        self.memory = budget.map(|budget| SourceMemory {
+            budget,
+            in_flight_bytes: register_gauge!(IN_FLIGHT_BYTES_NAME),
+        });
+        self
+    }
+
     pub fn add_output(&mut self, output: Output) -> LimitedReceiver<EventArray> {
         match output.port {
             None => {
@@ -51,13 +65,18 @@ impl Builder {
                     self.buf_size,
                     DEFAULT_OUTPUT.to_owned(),
                     self.lag_time.clone(),
+                    self.memory.clone(),
                 );
                 self.inner = Some(inner);
                 rx
             }
             Some(name) => {
-                let (inner, rx) =
-                    Inner::new_with_buffer(self.buf_size, name.clone(), self.lag_time.clone());
+                let (inner, rx) = Inner::new_with_buffer(
+                    self.buf_size,
+                    name.clone(),
+                    self.lag_time.clone(),
+                    self.memory.clone(),
+                );
                 self.named_inners.insert(name, inner);
                 rx
             }
@@ -87,12 +106,13 @@ impl SourceSender {
             inner: None,
             named_inners: Default::default(),
             lag_time: Some(register_histogram!(LAG_TIME_NAME)),
+            memory: None,
         }
     }
 
     pub fn new_with_buffer(n: usize) -> (Self, LimitedReceiver<EventArray>) {
         let lag_time = Some(register_histogram!(LAG_TIME_NAME));
-        let (inner, rx) = Inner::new_with_buffer(n, DEFAULT_OUTPUT.to_owned(), lag_time);
+        let (inner, rx) = Inner::new_with_buffer(n, DEFAULT_OUTPUT.to_owned(), lag_time, None);
         (
             Self {
                 inner: Some(inner),
@@ -160,7 +180,7 @@ impl SourceSender {
     ) -> impl Stream<Item = EventArray> + Unpin {
         // The lag_time parameter here will need to be filled in if this function is ever used for
         // non-test situations.
-        let (inner, recv) = Inner::new_with_buffer(100, name.clone(), None);
+        let (inner, recv) = Inner::new_with_buffer(100, name.clone(), None, None);
         let recv = recv.into_stream().map(move |mut events| {
             events.iter_events_mut().for_each(|mut event| {
                 let metadata = event.metadata_mut();
@@ -223,6 +243,14 @@ struct Inner {
     inner: LimitedSender<EventArray>,
     output: String,
     lag_time: Option<Histogram>,
+    memory: Option<SourceMemory>,
+}
+
+/// The memory budget of a source, along with the gauge of the bytes it holds in flight.
+#[derive(Clone)]
+struct SourceMemory {
+    budget: MemoryBudget,
+    in_flight_bytes: Gauge,
 }
 
 impl fmt::Debug for Inner {
@@ -240,6 +268,7 @@ impl Inner {
         n: usize,
         output: String,
         lag_time: Option<Histogram>,
+        memory: Option<SourceMemory>,
     ) -> (Self, LimitedReceiver<EventArray>) {
         let (tx, rx) = channel::limited(n);
         (
@@ -247,18 +276,20 @@ impl Inner {
                 inner: tx,
                 output,
                 lag_time,
+                memory,
             },
             rx,
         )
     }
 
-    async fn send(&mut self, events: EventArray) -> Result<(), ClosedError> {
+    async fn send(&mut self, mut events: EventArray) -> Result<(), ClosedError> {
         let reference = Utc::now().timestamp_millis();
         events
             .iter_events()
             .for_each(|event| self.emit_lag_time(event, reference));
         let byte_size = events.size_of();
         let count = events.len();
+        self.reserve_memory(&mut events, byte_size).await;
         self.inner.send(events).await.map_err(|_| ClosedError)?;
         emit!(EventsSent {
             count,
@@ -294,12 +325,13 @@ impl Inner {
 
         let reference = Utc::now().timestamp_millis();
         let events = events.into_iter().map(Into::into);
-        for events in array::events_into_arrays(events, Some(CHUNK_SIZE)) {
+        for mut events in array::events_into_arrays(events, Some(CHUNK_SIZE)) {
             events
                 .iter_events()
                 .for_each(|event| self.emit_lag_time(event, reference));
             let this_count = events.len();
             let this_size = events.size_of();
+            self.reserve_memory(&mut events, this_size).await;
             match self.inner.send(events).await {
                 Ok(()) => {
                     count += this_count;
@@ -325,6 +357,23 @@ impl Inner {
         Ok(())
     }
 
+    /// Reserve the size of the events from the memory budget, if any, and attach the reservation
+    /// to them, so that it is released once all of them have been dropped.
+    async fn reserve_memory(&self, events: &mut EventArray, byte_size: usize) {
+        if let Some(memory) = &self.memory {
+            let reservation = memory
+                .budget
+                .reserve(byte_size, Some(memory.in_flight_bytes.clone()))
+                .await
+                .share();
+            for mut event in events.iter_events_mut() {
+                event
+                    .metadata_mut()
+                    .set_memory_reservation(reservation.clone());
+            }
+        }
+    }
+
     /// Calculate the difference between the reference time and the
     /// timestamp stored in the given event reference, and emit the
     /// different, as expressed in milliseconds, as a histogram.
@@ -360,9 +409,14 @@ fn get_timestamp_millis(value: &Value) -> Option<i64> {
 
 #[cfg(test)]
 mod tests {
+    use std::num::NonZeroUsize;
+
     use chrono::{DateTime, Duration};
     use rand::{thread_rng, Rng};
-    use vector_core::event::{LogEvent, Metric, MetricKind, MetricValue, TraceEvent};
+    use vector_core::{
+        config::DataType,
+        event::{LogEvent, Metric, MetricKind, MetricValue, TraceEvent},
+    };
 
     use super::*;
     use crate::metrics::{self, Controller};
@@ -450,4 +504,25 @@ mod tests {
             _ => panic!("source_lag_time_seconds has invalid type"),
         }
     }
+
+    #[tokio::test]
+    async fn waits_for_memory_budget() {
+        let budget = MemoryBudget::new(NonZeroUsize::new(1).unwrap());
+        let mut builder = SourceSender::builder()
+            .with_buffer(10)
+            .with_memory_budget(Some(budget.clone()));
+        let mut rx = builder.add_output(Output::default(DataType::all()));
+        let mut sender = builder.build();
+
+        sender.send_event(LogEvent::from("first")).await.unwrap();
+        assert_eq!(budget.available_bytes(), 0);
+
+        let second = sender.send_event(LogEvent::from("second"));
+        let waited = tokio::time::timeout(std::time::Duration::from_millis(10), second).await;
+        assert!(waited.is_err());
+
+        drop(rx.next().await);
+        sender.send_event(LogEvent::from("third")).await.unwrap();
+        assert_eq!(budget.available_bytes(), 0);
+    }
 }
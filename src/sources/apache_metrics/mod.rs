@@ -1,5 +1,4 @@
 use std::{
-    collections::BTreeMap,
     future::ready,
     time::{Duration, Instant},
 };
@@ -15,7 +14,7 @@ use vector_core::ByteSizeOf;
 
 use crate::{
     config::{self, GenerateConfig, Output, ProxyConfig, SourceConfig, SourceContext},
-    event::metric::{Metric, MetricKind, MetricValue},
+    event::metric::{Metric, MetricKind, MetricTags, MetricValue},
     http::HttpClient,
     internal_events::{
         ApacheMetricsEventsReceived, ApacheMetricsHttpError, ApacheMetricsParseError,
@@ -161,7 +160,7 @@ fn apache_metrics(
                     .body(Body::empty())
                     .expect("error creating request");
 
-                let mut tags: BTreeMap<String, String> = BTreeMap::new();
+                let mut tags = MetricTags::new();
                 tags.insert("endpoint".into(), sanitized_url.to_string());
                 tags.insert("host".into(), url.sanitized_authority());
 
@@ -1,12 +1,9 @@
-use std::{
-    collections::{BTreeMap, HashMap},
-    error, fmt, iter, num,
-};
+use std::{collections::HashMap, error, fmt, iter, num};
 
 use chrono::{DateTime, Utc};
 use once_cell::sync::Lazy;
 
-use crate::event::metric::{Metric, MetricKind, MetricValue};
+use crate::event::metric::{Metric, MetricKind, MetricTags, MetricValue};
 
 static SCOREBOARD: Lazy<HashMap<char, &'static str>> = Lazy::new(|| {
     vec![
@@ -115,7 +112,7 @@ pub fn parse(
     payload: &str,
     namespace: Option<&str>,
     now: DateTime<Utc>,
-    tags: Option<&BTreeMap<String, String>>,
+    tags: Option<&MetricTags>,
 ) -> impl Iterator<Item = Result<Metric, ParseError>> {
     // We use a HashMap rather than a Vector as mod_status has
     // BusyWorkers/IdleWorkers repeated
@@ -151,7 +148,7 @@ fn line_to_metrics<'a>(
     value: &str,
     namespace: Option<&'a str>,
     now: DateTime<Utc>,
-    tags: Option<&'a BTreeMap<String, String>>,
+    tags: Option<&'a MetricTags>,
 ) -> Option<Result<Box<dyn Iterator<Item = Metric> + 'a>, ParseError>> {
     StatusFieldStatistic::from_key_value(key, value).map(move |result| {
         result.map(move |statistic| match statistic {
@@ -212,7 +209,7 @@ fn line_to_metrics<'a>(
                 .with_namespace(namespace.map(str::to_string))
                 .with_tags({
                     let mut tags = tags.cloned().unwrap_or_default();
-                    tags.insert("type".to_string(), "user".to_string());
+                    tags.insert("type".into(), "user".to_string());
                     Some(tags)
                 })
                 .with_timestamp(Some(now)),
@@ -227,7 +224,7 @@ fn line_to_metrics<'a>(
                 .with_namespace(namespace.map(str::to_string))
                 .with_tags({
                     let mut tags = tags.cloned().unwrap_or_default();
-                    tags.insert("type".to_string(), "system".to_string());
+                    tags.insert("type".into(), "system".to_string());
                     Some(tags)
                 })
                 .with_timestamp(Some(now)),
@@ -242,7 +239,7 @@ fn line_to_metrics<'a>(
                 .with_namespace(namespace.map(str::to_string))
                 .with_tags({
                     let mut tags = tags.cloned().unwrap_or_default();
-                    tags.insert("type".to_string(), "children_user".to_string());
+                    tags.insert("type".into(), "children_user".to_string());
                     Some(tags)
                 })
                 .with_timestamp(Some(now)),
@@ -257,7 +254,7 @@ fn line_to_metrics<'a>(
                 .with_namespace(namespace.map(str::to_string))
                 .with_tags({
                     let mut tags = tags.cloned().unwrap_or_default();
-                    tags.insert("type".to_string(), "children_system".to_string());
+                    tags.insert("type".into(), "children_system".to_string());
                     Some(tags)
                 })
                 .with_timestamp(Some(now)),
@@ -285,7 +282,7 @@ fn line_to_metrics<'a>(
                 .with_namespace(namespace.map(str::to_string))
                 .with_tags({
                     let mut tags = tags.cloned().unwrap_or_default();
-                    tags.insert("state".to_string(), "idle".to_string());
+                    tags.insert("state".into(), "idle".to_string());
                     Some(tags)
                 })
                 .with_timestamp(Some(now)),
@@ -302,7 +299,7 @@ fn line_to_metrics<'a>(
                 .with_namespace(namespace.map(str::to_string))
                 .with_tags({
                     let mut tags = tags.cloned().unwrap_or_default();
-                    tags.insert("state".to_string(), "busy".to_string());
+                    tags.insert("state".into(), "busy".to_string());
                     Some(tags)
                 })
                 .with_timestamp(Some(now)),
@@ -318,7 +315,7 @@ fn line_to_metrics<'a>(
                 .with_namespace(namespace.map(str::to_string))
                 .with_tags({
                     let mut tags = tags.cloned().unwrap_or_default();
-                    tags.insert("state".to_string(), "total".to_string());
+                    tags.insert("state".into(), "total".to_string());
                     Some(tags)
                 })
                 .with_timestamp(Some(now)),
@@ -334,7 +331,7 @@ fn line_to_metrics<'a>(
                 .with_namespace(namespace.map(str::to_string))
                 .with_tags({
                     let mut tags = tags.cloned().unwrap_or_default();
-                    tags.insert("state".to_string(), "writing".to_string());
+                    tags.insert("state".into(), "writing".to_string());
                     Some(tags)
                 })
                 .with_timestamp(Some(now)),
@@ -350,7 +347,7 @@ fn line_to_metrics<'a>(
                 .with_namespace(namespace.map(str::to_string))
                 .with_tags({
                     let mut tags = tags.cloned().unwrap_or_default();
-                    tags.insert("state".to_string(), "closing".to_string());
+                    tags.insert("state".into(), "closing".to_string());
                     Some(tags)
                 })
                 .with_timestamp(Some(now)),
@@ -366,7 +363,7 @@ fn line_to_metrics<'a>(
                 .with_namespace(namespace.map(str::to_string))
                 .with_tags({
                     let mut tags = tags.cloned().unwrap_or_default();
-                    tags.insert("state".to_string(), "keepalive".to_string());
+                    tags.insert("state".into(), "keepalive".to_string());
                     Some(tags)
                 })
                 .with_timestamp(Some(now)),
@@ -404,7 +401,7 @@ where
 fn score_to_metric(
     namespace: Option<&str>,
     now: DateTime<Utc>,
-    tags: Option<&BTreeMap<String, String>>,
+    tags: Option<&MetricTags>,
     state: &str,
     count: u32,
 ) -> Metric {
@@ -418,7 +415,7 @@ fn score_to_metric(
     .with_namespace(namespace.map(str::to_string))
     .with_tags({
         let mut tags = tags.cloned().unwrap_or_default();
-        tags.insert("state".to_string(), state.to_string());
+        tags.insert("state".into(), state.to_string());
         Some(tags)
     })
     .with_timestamp(Some(now))
@@ -480,6 +477,8 @@ impl error::Error for ParseError {
 
 #[cfg(test)]
 mod test {
+    use std::collections::BTreeMap;
+
     use chrono::{DateTime, Utc};
     use pretty_assertions::assert_eq;
     use vector_common::assert_event_data_eq;
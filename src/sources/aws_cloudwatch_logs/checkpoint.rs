@@ -0,0 +1,139 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+use crate::{internal_events::AwsCloudwatchLogsCheckpointError, state_store::BoxedStateStore};
+
+const CHECKPOINTS_KEY: &str = "checkpoints.json";
+
+/// The position reached in a log group.
+///
+/// `FilterLogEvents` only filters log events by timestamp, with millisecond precision, so the IDs
+/// of the log events read at the timestamp of the checkpoint are kept to skip them when the log
+/// group is read again from that timestamp.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct GroupCheckpoint {
+    /// The timestamp, in milliseconds since the Unix epoch, of the latest log event read.
+    pub timestamp: i64,
+    /// The IDs of the log events read with that timestamp.
+    pub event_ids: Vec<String>,
+}
+
+impl GroupCheckpoint {
+    pub const fn new(timestamp: i64) -> Self {
+        Self {
+            timestamp,
+            event_ids: Vec::new(),
+        }
+    }
+
+    /// Returns whether the log event was written after the checkpoint.
+    pub fn is_new(&self, timestamp: i64, event_id: &str) -> bool {
+        timestamp > self.timestamp
+            || (timestamp == self.timestamp && !self.event_ids.iter().any(|id| id == event_id))
+    }
+
+    /// Moves the checkpoint past the log event, if it is the latest one read.
+    pub fn advance(&mut self, timestamp: i64, event_id: &str) {
+        if timestamp > self.timestamp {
+            self.timestamp = timestamp;
+            self.event_ids.clear();
+        }
+        if timestamp == self.timestamp && !self.event_ids.iter().any(|id| id == event_id) {
+            self.event_ids.push(event_id.to_owned());
+        }
+    }
+}
+
+/// Stores the checkpoint of each log group in the state store of the source.
+#[derive(Clone)]
+pub struct Checkpointer {
+    store: BoxedStateStore,
+    checkpoints: Arc<Mutex<BTreeMap<String, GroupCheckpoint>>>,
+}
+
+impl Checkpointer {
+    pub async fn new(store: BoxedStateStore) -> crate::Result<Self> {
+        let checkpoints = match store.get(CHECKPOINTS_KEY).await? {
+            Some(contents) => serde_json::from_slice(&contents)?,
+            None => BTreeMap::new(),
+        };
+        Ok(Self {
+            store,
+            checkpoints: Arc::new(Mutex::new(checkpoints)),
+        })
+    }
+
+    /// Gets the checkpoint of the given log group, if it has one.
+    pub async fn get(&self, log_group: &str) -> Option<GroupCheckpoint> {
+        self.checkpoints.lock().await.get(log_group).cloned()
+    }
+
+    /// Sets the checkpoint of the given log group.
+    pub async fn set(&self, log_group: &str, checkpoint: GroupCheckpoint) {
+        if let Err(error) = self.write(log_group, checkpoint).await {
+            emit!(AwsCloudwatchLogsCheckpointError { error, log_group });
+        }
+    }
+
+    async fn write(&self, log_group: &str, checkpoint: GroupCheckpoint) -> crate::Result<()> {
+        let mut checkpoints = self.checkpoints.lock().await;
+        checkpoints.insert(log_group.to_owned(), checkpoint);
+
+        // The lock is held until the checkpoints are stored, so that they are stored in order.
+        let contents = serde_json::to_vec(&*checkpoints)?;
+        self.store.set(CHECKPOINTS_KEY, contents.into()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::state_store::FileStateStore;
+
+    #[test]
+    fn skips_events_read_at_checkpoint_timestamp() {
+        let mut checkpoint = GroupCheckpoint::new(1000);
+        assert!(!checkpoint.is_new(999, "a"));
+        assert!(checkpoint.is_new(1000, "a"));
+
+        checkpoint.advance(1000, "a");
+        checkpoint.advance(1000, "b");
+        assert!(!checkpoint.is_new(1000, "a"));
+        assert!(!checkpoint.is_new(1000, "b"));
+        assert!(checkpoint.is_new(1000, "c"));
+
+        checkpoint.advance(1001, "c");
+        assert_eq!(
+            checkpoint,
+            GroupCheckpoint {
+                timestamp: 1001,
+                event_ids: vec!["c".to_owned()],
+            }
+        );
+
+        // Log events older than the checkpoint don't move it back.
+        checkpoint.advance(999, "d");
+        assert_eq!(checkpoint.timestamp, 1001);
+        assert_eq!(checkpoint.event_ids, vec!["c".to_owned()]);
+    }
+
+    #[tokio::test]
+    async fn checkpointer_persists_checkpoints() {
+        let directory = tempdir().unwrap();
+        let store: BoxedStateStore = Arc::new(FileStateStore::new(directory.path().to_path_buf()));
+
+        let checkpointer = Checkpointer::new(Arc::clone(&store)).await.unwrap();
+        assert_eq!(checkpointer.get("/my/group").await, None);
+
+        let mut checkpoint = GroupCheckpoint::new(1000);
+        checkpoint.advance(1000, "a");
+        checkpointer.set("/my/group", checkpoint.clone()).await;
+
+        let checkpointer = Checkpointer::new(store).await.unwrap();
+        assert_eq!(checkpointer.get("/my/group").await, Some(checkpoint));
+    }
+}
@@ -0,0 +1,189 @@
+use std::path::PathBuf;
+
+use codecs::decoding::{DeserializerConfig, FramingConfig};
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use super::{checkpoint::Checkpointer, source::CloudwatchLogsSource};
+use crate::{
+    aws::{auth::AwsAuthentication, create_client, region::RegionOrEndpoint},
+    codecs::DecodingConfig,
+    common::cloudwatch_logs::CloudwatchLogsClientBuilder,
+    config::{AcknowledgementsConfig, Output, SourceConfig, SourceContext},
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
+    state_store::StateStoreConfig,
+    tls::TlsConfig,
+};
+
+/// Configuration for the `aws_cloudwatch_logs` source.
+#[configurable_component(source("aws_cloudwatch_logs"))]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct AwsCloudwatchLogsConfig {
+    #[serde(flatten)]
+    pub region: RegionOrEndpoint,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub auth: AwsAuthentication,
+
+    /// The names of the log groups to read from.
+    #[serde(default)]
+    pub log_group_names: Vec<String>,
+
+    /// The prefix of the names of the log groups to read from.
+    ///
+    /// The log groups whose name starts with this prefix are listed with `DescribeLogGroups` every
+    /// `group_discovery_interval_secs`, and read from along with the log groups of `log_group_names`.
+    pub log_group_prefix: Option<String>,
+
+    /// The prefix of the names of the log streams to read from, in each log group.
+    ///
+    /// By default, all of the log streams of the log groups are read from.
+    pub log_stream_prefix: Option<String>,
+
+    /// The [filter pattern](\(urls.aws_cloudwatch_logs_filter_pattern)) the log events must match.
+    ///
+    /// By default, all of the log events are read.
+    pub filter_pattern: Option<String>,
+
+    /// How far back, in seconds, to start reading a log group that has no checkpoint.
+    ///
+    /// By default, only the log events written after the log group is first read from are read.
+    #[serde(default)]
+    pub start_lookback_secs: u64,
+
+    /// The interval, in seconds, between reads of a log group that has no new log events.
+    #[serde(default = "default_poll_interval_secs")]
+    #[derivative(Default(value = "default_poll_interval_secs()"))]
+    pub poll_interval_secs: u64,
+
+    /// The interval, in seconds, between listings of the log groups matching `log_group_prefix`.
+    #[serde(default = "default_group_discovery_interval_secs")]
+    #[derivative(Default(value = "default_group_discovery_interval_secs()"))]
+    pub group_discovery_interval_secs: u64,
+
+    /// The directory used to persist the checkpoint of each log group, when `state_store` is `file`.
+    ///
+    /// By default, the global `data_dir` option is used. Please make sure the user Vector is running as has write permissions to this directory.
+    pub data_dir: Option<PathBuf>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub state_store: StateStoreConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_framing_message_based")]
+    #[derivative(Default(value = "default_framing_message_based()"))]
+    pub framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    #[derivative(Default(value = "default_decoding()"))]
+    pub decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    pub acknowledgements: AcknowledgementsConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+}
+
+const fn default_poll_interval_secs() -> u64 {
+    10
+}
+
+const fn default_group_discovery_interval_secs() -> u64 {
+    300
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for AwsCloudwatchLogsConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<crate::sources::Source> {
+        if self.log_group_names.is_empty() && self.log_group_prefix.is_none() {
+            return Err(
+                "At least one of `log_group_names` or `log_group_prefix` must be set.".into(),
+            );
+        }
+
+        let client = create_client::<CloudwatchLogsClientBuilder>(
+            &self.auth,
+            self.region.region(),
+            self.region.endpoint()?,
+            &cx.proxy,
+            &self.tls,
+            false,
+        )
+        .await?;
+        let store = self.state_store.build(&cx, self.data_dir.as_ref()).await?;
+        let checkpointer = Checkpointer::new(store).await?;
+        let decoder = DecodingConfig::new(
+            self.framing.clone(),
+            self.decoding.clone(),
+            LogNamespace::Legacy,
+        )
+        .build();
+        let acknowledgements = cx.do_acknowledgements(&self.acknowledgements);
+
+        Ok(Box::pin(
+            CloudwatchLogsSource {
+                client,
+                log_group_names: self.log_group_names.clone(),
+                log_group_prefix: self.log_group_prefix.clone(),
+                log_stream_prefix: self.log_stream_prefix.clone(),
+                filter_pattern: self.filter_pattern.clone(),
+                start_lookback_secs: self.start_lookback_secs,
+                poll_interval_secs: self.poll_interval_secs,
+                group_discovery_interval_secs: self.group_discovery_interval_secs,
+                checkpointer,
+                decoder,
+                acknowledgements,
+            }
+            .run(cx.out, cx.shutdown),
+        ))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(self.decoding.output_type())]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+impl_generate_config_from_default!(AwsCloudwatchLogsConfig);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AwsCloudwatchLogsConfig>();
+    }
+
+    #[test]
+    fn parse_config() {
+        let config: AwsCloudwatchLogsConfig = toml::from_str(
+            r#"
+            region = "us-east-1"
+            log_group_names = ["/aws/lambda/my-function"]
+            log_group_prefix = "/ecs/"
+            log_stream_prefix = "web/"
+            filter_pattern = "ERROR"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.log_group_names, vec!["/aws/lambda/my-function"]);
+        assert_eq!(config.log_group_prefix.as_deref(), Some("/ecs/"));
+        assert_eq!(config.log_stream_prefix.as_deref(), Some("web/"));
+        assert_eq!(config.filter_pattern.as_deref(), Some("ERROR"));
+        assert_eq!(config.start_lookback_secs, 0);
+        assert_eq!(config.poll_interval_secs, 10);
+        assert_eq!(config.group_discovery_interval_secs, 300);
+    }
+}
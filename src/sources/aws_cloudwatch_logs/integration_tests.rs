@@ -0,0 +1,124 @@
+#![cfg(feature = "aws-cloudwatch-logs-integration-tests")]
+#![cfg(test)]
+
+use std::{collections::HashSet, time::Duration};
+
+use aws_sdk_cloudwatchlogs::model::InputLogEvent;
+use chrono::Utc;
+use futures::StreamExt;
+use tempfile::tempdir;
+use tokio::time::timeout;
+
+use super::config::AwsCloudwatchLogsConfig;
+use crate::{
+    aws::{auth::AwsAuthentication, create_client, region::RegionOrEndpoint},
+    common::cloudwatch_logs::CloudwatchLogsClientBuilder,
+    config::{log_schema, ProxyConfig, SourceConfig, SourceContext},
+    test_util::{
+        components::{assert_source_compliance, HTTP_PULL_SOURCE_TAGS},
+        random_string,
+    },
+    SourceSender,
+};
+
+fn watchlogs_address() -> String {
+    std::env::var("WATCHLOGS_ADDRESS").unwrap_or_else(|_| "http://localhost:6000".into())
+}
+
+async fn client() -> aws_sdk_cloudwatchlogs::Client {
+    let region = RegionOrEndpoint::with_both("localstack", watchlogs_address());
+    create_client::<CloudwatchLogsClientBuilder>(
+        &AwsAuthentication::test_auth(),
+        region.region(),
+        region.endpoint().unwrap(),
+        &ProxyConfig::default(),
+        &None,
+        true,
+    )
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn cloudwatch_logs_reads_log_group() {
+    let client = client().await;
+    let group_prefix = format!("/vector/{}", random_string(10).to_lowercase());
+    let log_group = format!("{}/app", group_prefix);
+    client
+        .create_log_group()
+        .log_group_name(&log_group)
+        .send()
+        .await
+        .unwrap();
+    client
+        .create_log_stream()
+        .log_group_name(&log_group)
+        .log_stream_name("stream")
+        .send()
+        .await
+        .unwrap();
+
+    let now = Utc::now().timestamp_millis();
+    let lines = (0..10)
+        .map(|index| format!("line {}", index))
+        .collect::<Vec<_>>();
+    client
+        .put_log_events()
+        .log_group_name(&log_group)
+        .log_stream_name("stream")
+        .set_log_events(Some(
+            lines
+                .iter()
+                .map(|line| {
+                    InputLogEvent::builder()
+                        .message(line.as_str())
+                        .timestamp(now)
+                        .build()
+                })
+                .collect(),
+        ))
+        .send()
+        .await
+        .unwrap();
+
+    let data_dir = tempdir().unwrap();
+    let config = AwsCloudwatchLogsConfig {
+        region: RegionOrEndpoint::with_both("localstack", watchlogs_address()),
+        auth: AwsAuthentication::test_auth(),
+        log_group_prefix: Some(group_prefix),
+        start_lookback_secs: 60,
+        poll_interval_secs: 1,
+        data_dir: Some(data_dir.path().to_path_buf()),
+        ..Default::default()
+    };
+
+    let events = assert_source_compliance(&HTTP_PULL_SOURCE_TAGS, async move {
+        let (tx, rx) = SourceSender::new_test();
+        let source = config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+        timeout(
+            Duration::from_secs(30),
+            rx.take(lines.len()).collect::<Vec<_>>(),
+        )
+        .await
+        .unwrap()
+    })
+    .await;
+
+    let messages = events
+        .iter()
+        .map(|event| {
+            let log = event.as_log();
+            assert_eq!(log["log_group"], log_group.clone().into());
+            assert_eq!(log["log_stream"], "stream".into());
+            log[log_schema().message_key()].to_string_lossy()
+        })
+        .collect::<HashSet<_>>();
+    assert_eq!(
+        messages,
+        (0..10).map(|index| format!("line {}", index)).collect()
+    );
+}
@@ -0,0 +1,365 @@
+use std::{collections::HashSet, panic, sync::Arc, time::Duration};
+
+use aws_sdk_cloudwatchlogs::{
+    error::FilterLogEventsErrorKind, model::FilteredLogEvent, types::SdkError,
+    Client as CloudwatchLogsClient,
+};
+use chrono::{TimeZone, Utc};
+use futures::{stream::FuturesUnordered, StreamExt};
+use tokio::{pin, select, time::sleep};
+use tracing_futures::Instrument;
+use vector_common::finalizer::OrderedFinalizer;
+
+use super::checkpoint::{Checkpointer, GroupCheckpoint};
+use crate::{
+    codecs::Decoder,
+    event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event},
+    internal_events::{AwsCloudwatchLogsRequestError, EndpointBytesReceived, StreamClosedError},
+    shutdown::ShutdownSignal,
+    sources::util,
+    SourceSender,
+};
+
+/// The time to wait before retrying a failed request.
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+pub struct CloudwatchLogsSource {
+    pub client: CloudwatchLogsClient,
+    pub log_group_names: Vec<String>,
+    pub log_group_prefix: Option<String>,
+    pub log_stream_prefix: Option<String>,
+    pub filter_pattern: Option<String>,
+    pub start_lookback_secs: u64,
+    pub poll_interval_secs: u64,
+    pub group_discovery_interval_secs: u64,
+    pub checkpointer: Checkpointer,
+    pub decoder: Decoder,
+    pub acknowledgements: bool,
+}
+
+/// The reason a log group stopped being read.
+enum GroupOutcome {
+    /// The log group doesn't exist anymore.
+    Deleted,
+    /// The source is shutting down.
+    Stopped,
+}
+
+impl CloudwatchLogsSource {
+    pub async fn run(self, out: SourceSender, shutdown: ShutdownSignal) -> Result<(), ()> {
+        let finalizer = Arc::new(Finalizer::new(
+            self.acknowledgements,
+            self.checkpointer.clone(),
+            shutdown.clone(),
+        ));
+        let source = Arc::new(self);
+
+        let mut running = HashSet::new();
+        let mut tasks = FuturesUnordered::new();
+        let mut discovery =
+            tokio::time::interval(Duration::from_secs(source.group_discovery_interval_secs));
+
+        let task_shutdown = shutdown.clone();
+        pin!(shutdown);
+        loop {
+            select! {
+                _ = &mut shutdown => break,
+                _ = discovery.tick() => {
+                    let log_groups = match source.list_log_groups().await {
+                        Ok(log_groups) => log_groups,
+                        Err(error) => {
+                            emit!(AwsCloudwatchLogsRequestError {
+                                operation: "DescribeLogGroups",
+                                error,
+                                log_group: None,
+                            });
+                            continue;
+                        }
+                    };
+
+                    for log_group in log_groups {
+                        if !running.insert(log_group.clone()) {
+                            continue;
+                        }
+                        let source = Arc::clone(&source);
+                        let out = out.clone();
+                        let finalizer = Arc::clone(&finalizer);
+                        let shutdown = task_shutdown.clone();
+                        tasks.push(tokio::spawn(
+                            async move {
+                                let outcome = select! {
+                                    _ = shutdown => GroupOutcome::Stopped,
+                                    outcome = source.read_log_group(&log_group, out, &finalizer) => outcome,
+                                };
+                                (log_group, outcome)
+                            }
+                            .in_current_span(),
+                        ));
+                    }
+                },
+                Some(result) = tasks.next() => {
+                    match result {
+                        Ok((log_group, outcome)) => {
+                            if let GroupOutcome::Deleted = outcome {
+                                // The log group is read again if it is recreated.
+                                running.remove(&log_group);
+                            }
+                        }
+                        Err(error) => {
+                            if error.is_panic() {
+                                panic::resume_unwind(error.into_panic());
+                            }
+                        }
+                    }
+                },
+            }
+        }
+
+        // Wait for all of the log groups to stop being read. If any one of them panics, we resume
+        // that panic here to properly shutdown Vector.
+        while let Some(result) = tasks.next().await {
+            if let Err(error) = result {
+                if error.is_panic() {
+                    panic::resume_unwind(error.into_panic());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Lists the configured log groups, along with the log groups matching the prefix.
+    async fn list_log_groups(&self) -> crate::Result<Vec<String>> {
+        let mut log_groups = self.log_group_names.clone();
+        let prefix = match &self.log_group_prefix {
+            Some(prefix) => prefix,
+            None => return Ok(log_groups),
+        };
+
+        let mut next_token = None;
+        loop {
+            let output = self
+                .client
+                .describe_log_groups()
+                .log_group_name_prefix(prefix)
+                .set_next_token(next_token.take())
+                .send()
+                .await?;
+            log_groups.extend(
+                output
+                    .log_groups
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter_map(|log_group| log_group.log_group_name),
+            );
+            match output.next_token {
+                Some(token) => next_token = Some(token),
+                None => return Ok(log_groups),
+            }
+        }
+    }
+
+    async fn read_log_group(
+        &self,
+        log_group: &str,
+        mut out: SourceSender,
+        finalizer: &Finalizer,
+    ) -> GroupOutcome {
+        let mut checkpoint = match self.checkpointer.get(log_group).await {
+            Some(checkpoint) => checkpoint,
+            None => GroupCheckpoint::new(
+                (Utc::now() - chrono::Duration::seconds(self.start_lookback_secs as i64))
+                    .timestamp_millis(),
+            ),
+        };
+        debug!(message = "Reading log group.", %log_group, timestamp = %checkpoint.timestamp);
+
+        let poll_interval = Duration::from_secs(self.poll_interval_secs);
+        loop {
+            // The log events are filtered from the timestamp of the checkpoint, for all of the
+            // pages of the results. Log events ingested with a timestamp older than the
+            // checkpoint are not read.
+            let start = checkpoint.clone();
+            let mut next_token = None;
+            loop {
+                let output = match self
+                    .client
+                    .filter_log_events()
+                    .log_group_name(log_group)
+                    .start_time(start.timestamp)
+                    .set_log_stream_name_prefix(self.log_stream_prefix.clone())
+                    .set_filter_pattern(self.filter_pattern.clone())
+                    .set_next_token(next_token.clone())
+                    .send()
+                    .await
+                {
+                    Ok(output) => output,
+                    Err(SdkError::ServiceError { err, raw: _ })
+                        if matches!(
+                            err.kind,
+                            FilterLogEventsErrorKind::ResourceNotFoundException(_)
+                        ) =>
+                    {
+                        debug!(message = "Log group was deleted.", %log_group);
+                        return GroupOutcome::Deleted;
+                    }
+                    Err(error) => {
+                        emit!(AwsCloudwatchLogsRequestError {
+                            operation: "FilterLogEvents",
+                            error,
+                            log_group: Some(log_group),
+                        });
+                        sleep(RETRY_DELAY).await;
+                        continue;
+                    }
+                };
+
+                let log_events = output
+                    .events
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|log_event| {
+                        start.is_new(
+                            log_event.timestamp.unwrap_or_default(),
+                            log_event.event_id.as_deref().unwrap_or_default(),
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                if !log_events.is_empty() {
+                    for log_event in &log_events {
+                        checkpoint.advance(
+                            log_event.timestamp.unwrap_or_default(),
+                            log_event.event_id.as_deref().unwrap_or_default(),
+                        );
+                    }
+                    self.handle_log_events(
+                        log_group,
+                        log_events,
+                        checkpoint.clone(),
+                        &mut out,
+                        finalizer,
+                    )
+                    .await;
+                }
+
+                next_token = output.next_token;
+                if next_token.is_none() {
+                    break;
+                }
+            }
+
+            sleep(poll_interval).await;
+        }
+    }
+
+    /// Sends the events of the log events, and checkpoints the log group once they are finalized.
+    async fn handle_log_events(
+        &self,
+        log_group: &str,
+        log_events: Vec<FilteredLogEvent>,
+        checkpoint: GroupCheckpoint,
+        out: &mut SourceSender,
+        finalizer: &Finalizer,
+    ) {
+        let byte_size = log_events
+            .iter()
+            .map(|log_event| log_event.message.as_ref().map_or(0, String::len))
+            .sum();
+        emit!(EndpointBytesReceived {
+            byte_size,
+            protocol: "http",
+            endpoint: log_group,
+        });
+
+        let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(self.acknowledgements);
+        let mut events = Vec::new();
+        for log_event in log_events {
+            let timestamp = log_event
+                .timestamp
+                .and_then(|timestamp| Utc.timestamp_millis_opt(timestamp).single());
+            let message = log_event.message.unwrap_or_default();
+            let log_stream = log_event.log_stream_name.unwrap_or_default();
+            let event_id = log_event.event_id.unwrap_or_default();
+
+            events.extend(
+                util::decode_message(
+                    self.decoder.clone(),
+                    "aws_cloudwatch_logs",
+                    message.as_bytes(),
+                    timestamp,
+                    &batch,
+                )
+                .map(|mut event| {
+                    if let Event::Log(ref mut log) = event {
+                        log.try_insert("id", event_id.clone());
+                        log.try_insert("log_group", log_group.to_owned());
+                        log.try_insert("log_stream", log_stream.clone());
+                    }
+                    event
+                }),
+            );
+        }
+        drop(batch); // Drop last reference to batch acknowledgement finalizer
+
+        let count = events.len();
+        match out.send_batch(events).await {
+            Ok(()) => {
+                finalizer
+                    .finalize(log_group.to_owned(), checkpoint, receiver)
+                    .await
+            }
+            Err(error) => emit!(StreamClosedError { error, count }),
+        }
+    }
+}
+
+struct GroupEntry {
+    log_group: String,
+    checkpoint: GroupCheckpoint,
+}
+
+enum Finalizer {
+    Sync(Checkpointer),
+    Async(OrderedFinalizer<GroupEntry>),
+}
+
+impl Finalizer {
+    fn new(acknowledgements: bool, checkpointer: Checkpointer, shutdown: ShutdownSignal) -> Self {
+        if acknowledgements {
+            let (finalizer, mut ack_stream) = OrderedFinalizer::new(shutdown);
+            tokio::spawn(
+                async move {
+                    while let Some((status, entry)) = ack_stream.next().await {
+                        if status == BatchStatus::Delivered {
+                            checkpointer.set(&entry.log_group, entry.checkpoint).await;
+                        }
+                    }
+                }
+                .in_current_span(),
+            );
+            Self::Async(finalizer)
+        } else {
+            Self::Sync(checkpointer)
+        }
+    }
+
+    async fn finalize(
+        &self,
+        log_group: String,
+        checkpoint: GroupCheckpoint,
+        receiver: Option<BatchStatusReceiver>,
+    ) {
+        match (self, receiver) {
+            (Self::Sync(checkpointer), None) => checkpointer.set(&log_group, checkpoint).await,
+            (Self::Async(finalizer), Some(receiver)) => finalizer.add(
+                GroupEntry {
+                    log_group,
+                    checkpoint,
+                },
+                receiver,
+            ),
+            _ => unreachable!(
+                "Cannot have async finalization without a receiver in aws_cloudwatch_logs source"
+            ),
+        }
+    }
+}
@@ -23,6 +23,7 @@ use crate::{
 pub fn firehose(
     access_key: Option<String>,
     record_compression: Compression,
+    unwrap_cloudwatch_logs: bool,
     decoder: codecs::Decoder,
     acknowledgements: bool,
     out: SourceSender,
@@ -30,6 +31,7 @@ pub fn firehose(
     let bytes_received = register!(BytesReceived::from(Protocol::HTTP));
     let context = handlers::Context {
         compression: record_compression,
+        unwrap_cloudwatch_logs,
         decoder,
         acknowledgements,
         bytes_received,
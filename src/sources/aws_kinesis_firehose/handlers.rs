@@ -27,12 +27,16 @@ use crate::{
     internal_events::{
         AwsKinesisFirehoseAutomaticRecordDecodeError, EventsReceived, StreamClosedError,
     },
+    sources::util::cloudwatch_logs_subscription::{
+        insert_subscription_fields, parse_subscription_message, MessageType, SubscriptionLogEvent,
+    },
     SourceSender,
 };
 
 #[derive(Clone)]
 pub(super) struct Context {
     pub(super) compression: Compression,
+    pub(super) unwrap_cloudwatch_logs: bool,
     pub(super) decoder: Decoder,
     pub(super) acknowledgements: bool,
     pub(super) bytes_received: Registered<BytesReceived>,
@@ -54,76 +58,102 @@ pub(super) async fn firehose(
             .map_err(reject::custom)?;
         context.bytes_received.emit(ByteSize(bytes.len()));
 
-        let mut stream = FramedRead::new(bytes.as_ref(), context.decoder.clone());
-        loop {
-            match stream.next().await {
-                Some(Ok((mut events, _byte_size))) => {
-                    emit!(EventsReceived {
-                        count: events.len(),
-                        byte_size: events.size_of(),
-                    });
-
-                    let (batch, receiver) = context
-                        .acknowledgements
-                        .then(|| {
-                            let (batch, receiver) = BatchNotifier::new_with_receiver();
-                            (Some(batch), Some(receiver))
-                        })
-                        .unwrap_or((None, None));
-
-                    for event in &mut events {
-                        if let Some(batch) = &batch {
-                            event.add_batch_notifier(batch.clone());
-                        }
-                        if let Event::Log(ref mut log) = event {
-                            log.try_insert(
-                                log_schema().source_type_key(),
-                                Bytes::from("aws_kinesis_firehose"),
-                            );
-                            log.try_insert(log_schema().timestamp_key(), request.timestamp);
-                            log.try_insert(event_path!("request_id"), request_id.to_string());
-                            log.try_insert(event_path!("source_arn"), source_arn.to_string());
-                        }
-                    }
-
-                    let count = events.len();
-                    if let Err(error) = context.out.send_batch(events).await {
-                        emit!(StreamClosedError {
-                            error: error.clone(),
-                            count,
+        let subscription = if context.unwrap_cloudwatch_logs {
+            parse_subscription_message(&bytes)
+        } else {
+            None
+        };
+        let payloads: Vec<(Bytes, Option<&SubscriptionLogEvent>)> = match &subscription {
+            // Control messages are only sent by CloudWatch Logs to check that the delivery
+            // stream is reachable, so they are dropped.
+            Some(message) if message.message_type != MessageType::DataMessage => Vec::new(),
+            Some(message) => message
+                .log_events
+                .iter()
+                .map(|log_event| (Bytes::from(log_event.message.clone()), Some(log_event)))
+                .collect(),
+            None => vec![(bytes, None)],
+        };
+
+        for (bytes, log_event) in payloads {
+            let mut stream = FramedRead::new(bytes.as_ref(), context.decoder.clone());
+            loop {
+                match stream.next().await {
+                    Some(Ok((mut events, _byte_size))) => {
+                        emit!(EventsReceived {
+                            count: events.len(),
+                            byte_size: events.size_of(),
                         });
-                        let error = RequestError::ShuttingDown {
-                            request_id: request_id.clone(),
-                            source: error,
-                        };
-                        warp::reject::custom(error);
-                    }
 
-                    drop(batch);
-                    if let Some(receiver) = receiver {
-                        match receiver.await {
-                            BatchStatus::Delivered => Ok(()),
-                            BatchStatus::Rejected => {
-                                Err(warp::reject::custom(RequestError::DeliveryFailed {
-                                    request_id: request_id.clone(),
-                                }))
+                        let (batch, receiver) = context
+                            .acknowledgements
+                            .then(|| {
+                                let (batch, receiver) = BatchNotifier::new_with_receiver();
+                                (Some(batch), Some(receiver))
+                            })
+                            .unwrap_or((None, None));
+
+                        for event in &mut events {
+                            if let Some(batch) = &batch {
+                                event.add_batch_notifier(batch.clone());
                             }
-                            BatchStatus::Errored => {
-                                Err(warp::reject::custom(RequestError::DeliveryErrored {
-                                    request_id: request_id.clone(),
-                                }))
+                            if let Event::Log(ref mut log) = event {
+                                if let (Some(message), Some(log_event)) = (&subscription, log_event)
+                                {
+                                    insert_subscription_fields(log, message, log_event);
+                                    if let Some(timestamp) = log_event.timestamp() {
+                                        log.try_insert(log_schema().timestamp_key(), timestamp);
+                                    }
+                                }
+                                log.try_insert(
+                                    log_schema().source_type_key(),
+                                    Bytes::from("aws_kinesis_firehose"),
+                                );
+                                log.try_insert(log_schema().timestamp_key(), request.timestamp);
+                                log.try_insert(event_path!("request_id"), request_id.to_string());
+                                log.try_insert(event_path!("source_arn"), source_arn.to_string());
                             }
-                        }?;
+                        }
+
+                        let count = events.len();
+                        if let Err(error) = context.out.send_batch(events).await {
+                            emit!(StreamClosedError {
+                                error: error.clone(),
+                                count,
+                            });
+                            let error = RequestError::ShuttingDown {
+                                request_id: request_id.clone(),
+                                source: error,
+                            };
+                            warp::reject::custom(error);
+                        }
+
+                        drop(batch);
+                        if let Some(receiver) = receiver {
+                            match receiver.await {
+                                BatchStatus::Delivered => Ok(()),
+                                BatchStatus::Rejected => {
+                                    Err(warp::reject::custom(RequestError::DeliveryFailed {
+                                        request_id: request_id.clone(),
+                                    }))
+                                }
+                                BatchStatus::Errored => {
+                                    Err(warp::reject::custom(RequestError::DeliveryErrored {
+                                        request_id: request_id.clone(),
+                                    }))
+                                }
+                            }?;
+                        }
                     }
-                }
-                Some(Err(error)) => {
-                    // Error is logged by `crate::codecs::Decoder`, no further
-                    // handling is needed here.
-                    if !error.can_continue() {
-                        break;
+                    Some(Err(error)) => {
+                        // Error is logged by `crate::codecs::Decoder`, no further
+                        // handling is needed here.
+                        if !error.can_continue() {
+                            break;
+                        }
                     }
+                    None => break,
                 }
-                None => break,
             }
         }
     }
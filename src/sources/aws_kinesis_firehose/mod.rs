@@ -45,6 +45,14 @@ pub struct AwsKinesisFirehoseConfig {
     /// Firehose HTTP endpoint destination. That option controls the content encoding of the entire HTTP request.
     record_compression: Option<Compression>,
 
+    /// Whether or not to unwrap the messages of AWS CloudWatch Logs subscription filters.
+    ///
+    /// When enabled, each record holding a [subscription filter message](\(urls.aws_cloudwatch_logs_subscriptions)) is
+    /// unwrapped into one event per log event, decoded from the log event's message, and control messages are dropped.
+    /// Records holding anything else are decoded as they are.
+    #[serde(default)]
+    unwrap_cloudwatch_logs: bool,
+
     #[configurable(derived)]
     tls: Option<TlsEnableableConfig>,
 
@@ -108,6 +116,7 @@ impl SourceConfig for AwsKinesisFirehoseConfig {
         let svc = filters::firehose(
             self.access_key.as_ref().map(|k| k.inner().to_owned()),
             self.record_compression.unwrap_or_default(),
+            self.unwrap_cloudwatch_logs,
             decoder,
             acknowledgements,
             cx.out,
@@ -149,6 +158,7 @@ impl GenerateConfig for AwsKinesisFirehoseConfig {
             access_key: None,
             tls: None,
             record_compression: None,
+            unwrap_cloudwatch_logs: false,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             acknowledgements: Default::default(),
@@ -167,7 +177,7 @@ mod tests {
     };
 
     use bytes::Bytes;
-    use chrono::{DateTime, SubsecRound, Utc};
+    use chrono::{DateTime, SubsecRound, TimeZone, Utc};
     use flate2::read::GzEncoder;
     use futures::Stream;
     use pretty_assertions::assert_eq;
@@ -219,6 +229,7 @@ mod tests {
     async fn source(
         access_key: Option<SensitiveString>,
         record_compression: Option<Compression>,
+        unwrap_cloudwatch_logs: bool,
         delivered: bool,
     ) -> (impl Stream<Item = Event> + Unpin, SocketAddr) {
         use EventStatus::*;
@@ -232,6 +243,7 @@ mod tests {
                 tls: None,
                 access_key,
                 record_compression,
+                unwrap_cloudwatch_logs,
                 framing: default_framing_message_based(),
                 decoding: default_decoding(),
                 acknowledgements: true.into(),
@@ -394,7 +406,7 @@ mod tests {
                 Vec::new(),
             ),
         ] {
-            let (rx, addr) = source(None, Some(source_record_compression), true).await;
+            let (rx, addr) = source(None, Some(source_record_compression), false, true).await;
 
             let timestamp: DateTime<Utc> = Utc::now();
 
@@ -437,7 +449,7 @@ mod tests {
     #[tokio::test]
     async fn aws_kinesis_firehose_forwards_events_gzip_request() {
         assert_source_compliance(&SOURCE_TAGS, async move {
-            let (rx, addr) = source(None, None, true).await;
+            let (rx, addr) = source(None, None, false, true).await;
 
             let timestamp: DateTime<Utc> = Utc::now();
 
@@ -472,9 +484,44 @@ mod tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn aws_kinesis_firehose_unwraps_cloudwatch_logs() {
+        let (rx, addr) = source(None, Some(Compression::Gzip), true, true).await;
+
+        let timestamp: DateTime<Utc> = Utc::now();
+
+        let res = spawn_send(
+            addr,
+            timestamp,
+            vec![RECORD.as_bytes()],
+            None,
+            false,
+            Compression::Gzip,
+        )
+        .await;
+
+        let events = collect_ready(rx).await;
+        let res = res.await.unwrap().unwrap();
+        assert_eq!(200, res.status().as_u16());
+
+        assert_eq!(events.len(), 2);
+        let log = events[0].as_log();
+        assert_eq!(
+            log["id"],
+            "35683658089614582423604394983260738922885519999578275840".into()
+        );
+        assert_eq!(log["log_group"], "/jesse/test".into());
+        assert_eq!(log["log_stream"], "test".into());
+        assert_eq!(log["timestamp"], Utc.timestamp_millis(1600110569039).into());
+        assert!(log["message"]
+            .to_string_lossy()
+            .starts_with(r#"{"bytes":26780,"#));
+        assert_eq!(log["request_id"], REQUEST_ID.into());
+    }
+
     #[tokio::test]
     async fn aws_kinesis_firehose_rejects_bad_access_key() {
-        let (_rx, addr) = source(Some("an access key".to_string().into()), None, true).await;
+        let (_rx, addr) = source(Some("an access key".to_string().into()), None, false, true).await;
 
         let res = send(
             addr,
@@ -496,7 +543,7 @@ mod tests {
     async fn handles_acknowledgement_failure() {
         let expected = RECORD.as_bytes().to_owned();
 
-        let (rx, addr) = source(None, Some(Compression::None), false).await;
+        let (rx, addr) = source(None, Some(Compression::None), false, false).await;
 
         let timestamp: DateTime<Utc> = Utc::now();
 
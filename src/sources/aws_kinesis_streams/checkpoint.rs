@@ -0,0 +1,166 @@
+use std::{collections::BTreeMap, io, path::PathBuf, sync::Arc};
+
+use aws_sdk_dynamodb::{model::AttributeValue, Client as DynamoDbClient};
+use tokio::sync::Mutex;
+
+use crate::internal_events::AwsKinesisStreamsCheckpointError;
+
+/// The checkpoint of a shard whose records have all been read.
+pub const SHARD_END: &str = "SHARD_END";
+
+const LEASE_KEY: &str = "lease_key";
+const CHECKPOINT_ATTRIBUTE: &str = "checkpoint";
+
+/// Stores the sequence number of the last record delivered from each shard.
+#[derive(Clone)]
+pub enum Checkpointer {
+    File(Arc<FileCheckpointer>),
+    DynamoDb(Arc<DynamoDbCheckpointer>),
+}
+
+impl Checkpointer {
+    /// Creates a checkpointer storing the checkpoints of the stream in a file of the given directory.
+    pub async fn file(directory: PathBuf, stream_name: &str) -> io::Result<Self> {
+        let path = directory.join(format!("{}.json", stream_name));
+        let checkpoints = match tokio::fs::read(&path).await {
+            Ok(contents) => serde_json::from_slice(&contents)?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(error) => return Err(error),
+        };
+        Ok(Self::File(Arc::new(FileCheckpointer {
+            path,
+            checkpoints: Mutex::new(checkpoints),
+        })))
+    }
+
+    /// Creates a checkpointer storing the checkpoints in a DynamoDB table.
+    pub fn dynamodb(client: DynamoDbClient, table_name: String, key_prefix: String) -> Self {
+        Self::DynamoDb(Arc::new(DynamoDbCheckpointer {
+            client,
+            table_name,
+            key_prefix,
+        }))
+    }
+
+    /// Gets the checkpoint of the given shard, if it has one.
+    pub async fn get(&self, shard_id: &str) -> crate::Result<Option<String>> {
+        match self {
+            Self::File(checkpointer) => {
+                Ok(checkpointer.checkpoints.lock().await.get(shard_id).cloned())
+            }
+            Self::DynamoDb(checkpointer) => checkpointer.get(shard_id).await,
+        }
+    }
+
+    /// Sets the checkpoint of the given shard.
+    pub async fn set(&self, shard_id: &str, checkpoint: &str) {
+        let result = match self {
+            Self::File(checkpointer) => checkpointer.set(shard_id, checkpoint).await,
+            Self::DynamoDb(checkpointer) => checkpointer.set(shard_id, checkpoint).await,
+        };
+        if let Err(error) = result {
+            emit!(AwsKinesisStreamsCheckpointError { error, shard_id });
+        }
+    }
+}
+
+pub struct FileCheckpointer {
+    path: PathBuf,
+    checkpoints: Mutex<BTreeMap<String, String>>,
+}
+
+impl FileCheckpointer {
+    async fn set(&self, shard_id: &str, checkpoint: &str) -> crate::Result<()> {
+        let mut checkpoints = self.checkpoints.lock().await;
+        checkpoints.insert(shard_id.to_owned(), checkpoint.to_owned());
+
+        // The checkpoints are written to a temporary file first, so that they are never left
+        // half-written if Vector stops in the middle of the write.
+        let contents = serde_json::to_vec(&*checkpoints)?;
+        let tmp_path = self.path.with_extension("json.tmp");
+        tokio::fs::write(&tmp_path, contents).await?;
+        tokio::fs::rename(&tmp_path, &self.path).await?;
+        Ok(())
+    }
+}
+
+pub struct DynamoDbCheckpointer {
+    client: DynamoDbClient,
+    table_name: String,
+    key_prefix: String,
+}
+
+impl DynamoDbCheckpointer {
+    fn lease_key(&self, shard_id: &str) -> AttributeValue {
+        AttributeValue::S(format!("{}/{}", self.key_prefix, shard_id))
+    }
+
+    async fn get(&self, shard_id: &str) -> crate::Result<Option<String>> {
+        let output = self
+            .client
+            .get_item()
+            .table_name(&self.table_name)
+            .key(LEASE_KEY, self.lease_key(shard_id))
+            .consistent_read(true)
+            .send()
+            .await?;
+
+        Ok(output
+            .item
+            .and_then(|mut item| item.remove(CHECKPOINT_ATTRIBUTE))
+            .and_then(|checkpoint| match checkpoint {
+                AttributeValue::S(checkpoint) => Some(checkpoint),
+                _ => None,
+            }))
+    }
+
+    async fn set(&self, shard_id: &str, checkpoint: &str) -> crate::Result<()> {
+        self.client
+            .put_item()
+            .table_name(&self.table_name)
+            .item(LEASE_KEY, self.lease_key(shard_id))
+            .item(
+                CHECKPOINT_ATTRIBUTE,
+                AttributeValue::S(checkpoint.to_owned()),
+            )
+            .send()
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn file_checkpointer_persists_checkpoints() {
+        let directory = tempdir().unwrap();
+
+        let checkpointer = Checkpointer::file(directory.path().to_path_buf(), "my-stream")
+            .await
+            .unwrap();
+        assert_eq!(
+            checkpointer.get("shardId-000000000000").await.unwrap(),
+            None
+        );
+
+        checkpointer.set("shardId-000000000000", "1234").await;
+        checkpointer.set("shardId-000000000001", SHARD_END).await;
+        checkpointer.set("shardId-000000000000", "5678").await;
+
+        let checkpointer = Checkpointer::file(directory.path().to_path_buf(), "my-stream")
+            .await
+            .unwrap();
+        assert_eq!(
+            checkpointer.get("shardId-000000000000").await.unwrap(),
+            Some("5678".to_owned())
+        );
+        assert_eq!(
+            checkpointer.get("shardId-000000000001").await.unwrap(),
+            Some(SHARD_END.to_owned())
+        );
+    }
+}
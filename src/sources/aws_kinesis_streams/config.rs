@@ -0,0 +1,325 @@
+use std::path::PathBuf;
+
+use codecs::decoding::{DeserializerConfig, FramingConfig};
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use super::{checkpoint::Checkpointer, source::KinesisSource};
+use crate::{
+    aws::{auth::AwsAuthentication, create_client, region::RegionOrEndpoint},
+    codecs::DecodingConfig,
+    common::{dynamodb::DynamoDbClientBuilder, kinesis::KinesisClientBuilder},
+    config::{AcknowledgementsConfig, Output, SourceConfig, SourceContext},
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
+    tls::TlsConfig,
+};
+
+/// Configuration for the `aws_kinesis_streams` source.
+#[configurable_component(source("aws_kinesis_streams"))]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct AwsKinesisStreamsConfig {
+    #[serde(flatten)]
+    pub region: RegionOrEndpoint,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub auth: AwsAuthentication,
+
+    /// The name of the stream to consume.
+    pub stream_name: String,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub consumer: ConsumerConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub starting_position: StartingPosition,
+
+    /// The maximum number of records returned by each `GetRecords` request, when polling.
+    #[serde(default = "default_max_records")]
+    #[derivative(Default(value = "default_max_records()"))]
+    #[configurable(validation(range(min = 1, max = 10000)))]
+    pub max_records: u32,
+
+    /// The interval, in seconds, between `GetRecords` requests to a shard that has no new records, when polling.
+    ///
+    /// Kinesis allows five `GetRecords` requests per second to each shard, shared by all of the consumers
+    /// polling the stream.
+    #[serde(default = "default_poll_interval_secs")]
+    #[derivative(Default(value = "default_poll_interval_secs()"))]
+    pub poll_interval_secs: u64,
+
+    /// The interval, in seconds, between listings of the shards of the stream.
+    ///
+    /// New shards, such as the child shards created when the stream is resharded, are only consumed once
+    /// they have been listed. A child shard is only consumed once all of the records of its parent shards
+    /// have been read, so that the records sharing a partition key are read in order.
+    #[serde(default = "default_shard_discovery_interval_secs")]
+    #[derivative(Default(value = "default_shard_discovery_interval_secs()"))]
+    pub shard_discovery_interval_secs: u64,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub checkpoint: CheckpointConfig,
+
+    /// The directory used to persist file checkpoints.
+    ///
+    /// By default, the global `data_dir` option is used. Please make sure the user Vector is running as has write permissions to this directory.
+    pub data_dir: Option<PathBuf>,
+
+    /// Whether or not to unwrap the messages of AWS CloudWatch Logs subscription filters.
+    ///
+    /// When enabled, each record holding a [subscription filter message](\(urls.aws_cloudwatch_logs_subscriptions)) is
+    /// unwrapped into one event per log event, decoded from the log event's message, and control messages are dropped.
+    /// Records holding anything else are decoded as they are.
+    #[serde(default)]
+    pub unwrap_cloudwatch_logs: bool,
+
+    #[configurable(derived)]
+    #[serde(default = "default_framing_message_based")]
+    #[derivative(Default(value = "default_framing_message_based()"))]
+    pub framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    #[derivative(Default(value = "default_decoding()"))]
+    pub decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    pub acknowledgements: AcknowledgementsConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+}
+
+/// How records are read from the shards of the stream.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum ConsumerConfig {
+    /// Poll each shard with `GetRecords` requests.
+    ///
+    /// The read throughput of each shard, 2 MB per second, is shared by all of the consumers polling the
+    /// stream.
+    #[derivative(Default)]
+    Polling,
+
+    /// Subscribe to each shard with enhanced fan-out.
+    ///
+    /// Records are pushed to the consumer over HTTP/2 as soon as they are written, and each enhanced
+    /// fan-out consumer gets a dedicated read throughput of 2 MB per second for each shard. The consumer
+    /// is registered on the stream if it doesn't already exist.
+    EnhancedFanOut {
+        /// The name of the enhanced fan-out consumer.
+        ///
+        /// Each Vector instance consuming the same stream with enhanced fan-out should use a different
+        /// consumer name.
+        consumer_name: String,
+    },
+}
+
+/// The position in a shard to start reading from, when it has no checkpoint.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum StartingPosition {
+    /// Start reading the records written after the shard is first read.
+    #[derivative(Default)]
+    Latest,
+
+    /// Start reading from the oldest record of the shard.
+    TrimHorizon,
+}
+
+/// Where the position of each shard is checkpointed.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CheckpointConfig {
+    /// Checkpoint to a file in the data directory.
+    #[derivative(Default)]
+    File,
+
+    /// Checkpoint to a DynamoDB table.
+    ///
+    /// The table must have a string partition key named `lease_key`. This allows several Vector instances
+    /// to share the checkpoints of the stream, such as when a Vector instance is replaced.
+    #[serde(rename = "dynamodb")]
+    DynamoDb {
+        /// The name of the DynamoDB table.
+        table_name: String,
+
+        /// The prefix of the keys the checkpoints are stored under.
+        ///
+        /// Each checkpoint is stored under the key `<key_prefix>/<shard_id>`. By default, the name of the
+        /// stream is used.
+        key_prefix: Option<String>,
+
+        /// Custom endpoint for use with DynamoDB-compatible services.
+        ///
+        /// By default, the regional endpoint of DynamoDB is used.
+        endpoint: Option<String>,
+    },
+}
+
+const fn default_max_records() -> u32 {
+    10_000
+}
+
+const fn default_poll_interval_secs() -> u64 {
+    1
+}
+
+const fn default_shard_discovery_interval_secs() -> u64 {
+    60
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for AwsKinesisStreamsConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<crate::sources::Source> {
+        let client = self.build_client(&cx).await?;
+        let checkpointer = self.build_checkpointer(&cx).await?;
+        let decoder = DecodingConfig::new(
+            self.framing.clone(),
+            self.decoding.clone(),
+            LogNamespace::Legacy,
+        )
+        .build();
+        let acknowledgements = cx.do_acknowledgements(&self.acknowledgements);
+
+        Ok(Box::pin(
+            KinesisSource {
+                client,
+                stream_name: self.stream_name.clone(),
+                consumer: self.consumer.clone(),
+                starting_position: self.starting_position,
+                max_records: self.max_records,
+                poll_interval_secs: self.poll_interval_secs,
+                shard_discovery_interval_secs: self.shard_discovery_interval_secs,
+                checkpointer,
+                unwrap_cloudwatch_logs: self.unwrap_cloudwatch_logs,
+                decoder,
+                acknowledgements,
+            }
+            .run(cx.out, cx.shutdown),
+        ))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(self.decoding.output_type())]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+impl AwsKinesisStreamsConfig {
+    async fn build_client(&self, cx: &SourceContext) -> crate::Result<aws_sdk_kinesis::Client> {
+        // Enhanced fan-out subscriptions are only served over HTTP/2, which is negotiated with ALPN.
+        let tls = match self.consumer {
+            ConsumerConfig::EnhancedFanOut { .. } => {
+                let mut tls = self.tls.clone().unwrap_or_default();
+                tls.alpn_protocols
+                    .get_or_insert_with(|| vec!["h2".to_owned(), "http/1.1".to_owned()]);
+                Some(tls)
+            }
+            ConsumerConfig::Polling => self.tls.clone(),
+        };
+
+        create_client::<KinesisClientBuilder>(
+            &self.auth,
+            self.region.region(),
+            self.region.endpoint()?,
+            &cx.proxy,
+            &tls,
+            false,
+        )
+        .await
+    }
+
+    async fn build_checkpointer(&self, cx: &SourceContext) -> crate::Result<Checkpointer> {
+        match &self.checkpoint {
+            CheckpointConfig::File => {
+                let data_dir = cx
+                    .globals
+                    .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
+                Ok(Checkpointer::file(data_dir, &self.stream_name).await?)
+            }
+            CheckpointConfig::DynamoDb {
+                table_name,
+                key_prefix,
+                endpoint,
+            } => {
+                let endpoint = RegionOrEndpoint {
+                    region: None,
+                    endpoint: endpoint.clone(),
+                }
+                .endpoint()?;
+                let client = create_client::<DynamoDbClientBuilder>(
+                    &self.auth,
+                    self.region.region(),
+                    endpoint,
+                    &cx.proxy,
+                    &self.tls,
+                    false,
+                )
+                .await?;
+                Ok(Checkpointer::dynamodb(
+                    client,
+                    table_name.clone(),
+                    key_prefix
+                        .clone()
+                        .unwrap_or_else(|| self.stream_name.clone()),
+                ))
+            }
+        }
+    }
+}
+
+impl_generate_config_from_default!(AwsKinesisStreamsConfig);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AwsKinesisStreamsConfig>();
+    }
+
+    #[test]
+    fn parse_config() {
+        let config: AwsKinesisStreamsConfig = toml::from_str(
+            r#"
+            region = "us-east-1"
+            stream_name = "my-stream"
+            starting_position = "trim_horizon"
+            consumer.mode = "enhanced_fan_out"
+            consumer.consumer_name = "vector"
+            checkpoint.type = "dynamodb"
+            checkpoint.table_name = "vector-checkpoints"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.starting_position, StartingPosition::TrimHorizon);
+        assert!(matches!(
+            config.consumer,
+            ConsumerConfig::EnhancedFanOut { ref consumer_name } if consumer_name == "vector"
+        ));
+        assert!(matches!(
+            config.checkpoint,
+            CheckpointConfig::DynamoDb { ref table_name, key_prefix: None, endpoint: None }
+                if table_name == "vector-checkpoints"
+        ));
+        assert_eq!(config.max_records, 10_000);
+    }
+}
@@ -0,0 +1,116 @@
+#![cfg(feature = "aws-kinesis-streams-integration-tests")]
+#![cfg(test)]
+
+use std::{collections::HashSet, time::Duration};
+
+use aws_sdk_kinesis::types::Blob;
+use futures::StreamExt;
+use tempfile::tempdir;
+use tokio::time::{sleep, timeout};
+
+use super::config::{AwsKinesisStreamsConfig, StartingPosition};
+use crate::{
+    aws::{auth::AwsAuthentication, create_client, region::RegionOrEndpoint},
+    common::kinesis::KinesisClientBuilder,
+    config::{log_schema, ProxyConfig, SourceConfig, SourceContext},
+    event::Event,
+    test_util::{
+        components::{assert_source_compliance, HTTP_PULL_SOURCE_TAGS},
+        random_string,
+    },
+    SourceSender,
+};
+
+fn kinesis_address() -> String {
+    std::env::var("KINESIS_ADDRESS").unwrap_or_else(|_| "http://localhost:4566".into())
+}
+
+async fn client() -> aws_sdk_kinesis::Client {
+    let region = RegionOrEndpoint::with_both("us-east-1", kinesis_address());
+    create_client::<KinesisClientBuilder>(
+        &AwsAuthentication::test_auth(),
+        region.region(),
+        region.endpoint().unwrap(),
+        &ProxyConfig::default(),
+        &None,
+        false,
+    )
+    .await
+    .unwrap()
+}
+
+async fn ensure_stream(client: &aws_sdk_kinesis::Client, stream_name: &str) {
+    client
+        .create_stream()
+        .stream_name(stream_name)
+        .shard_count(2)
+        .send()
+        .await
+        .unwrap();
+
+    // Wait for localstack to create the stream, otherwise it returns ResourceNotFound errors.
+    sleep(Duration::from_secs(1)).await;
+}
+
+#[tokio::test]
+async fn kinesis_polling_consumer() {
+    assert_source_compliance(&HTTP_PULL_SOURCE_TAGS, async {
+        let client = client().await;
+        let stream_name = format!("test-{}", random_string(10).to_lowercase());
+        ensure_stream(&client, &stream_name).await;
+
+        let num_events = 10;
+        let mut expected_messages = HashSet::new();
+        for i in 0..num_events {
+            let message = format!("Test message: {}", i);
+            client
+                .put_record()
+                .stream_name(&stream_name)
+                .partition_key(i.to_string())
+                .data(Blob::new(message.as_bytes()))
+                .send()
+                .await
+                .unwrap();
+            expected_messages.insert(message);
+        }
+
+        let data_dir = tempdir().unwrap();
+        let config = AwsKinesisStreamsConfig {
+            region: RegionOrEndpoint::with_both("us-east-1", kinesis_address()),
+            auth: AwsAuthentication::test_auth(),
+            stream_name: stream_name.clone(),
+            starting_position: StartingPosition::TrimHorizon,
+            data_dir: Some(data_dir.path().to_path_buf()),
+            ..Default::default()
+        };
+
+        let (tx, rx) = SourceSender::new_test();
+        tokio::spawn(async move {
+            config
+                .build(SourceContext::new_test(tx, None))
+                .await
+                .unwrap()
+                .await
+                .unwrap()
+        });
+
+        let events: Vec<Event> = timeout(Duration::from_secs(10), rx.take(num_events).collect())
+            .await
+            .unwrap();
+
+        for event in events {
+            let log = event.as_log();
+            assert_eq!(log["stream"].to_string_lossy(), stream_name,);
+            assert!(log.contains("partition_key"));
+            assert!(log.contains("sequence_number"));
+            assert!(log.contains("shard_id"));
+
+            let message = log[log_schema().message_key()].to_string_lossy();
+            if !expected_messages.remove(&message) {
+                panic!("Received unexpected message: {:?}", message);
+            }
+        }
+        assert!(expected_messages.is_empty());
+    })
+    .await;
+}
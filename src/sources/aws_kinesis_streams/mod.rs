@@ -0,0 +1,6 @@
+mod checkpoint;
+mod config;
+mod integration_tests;
+mod source;
+
+pub use config::AwsKinesisStreamsConfig;
@@ -0,0 +1,682 @@
+use std::{collections::HashSet, panic, sync::Arc, time::Duration};
+
+use aws_sdk_kinesis::{
+    error::RegisterStreamConsumerErrorKind,
+    model::{
+        ConsumerStatus, Record, Shard, ShardIteratorType,
+        StartingPosition as KinesisStartingPosition, SubscribeToShardEventStream,
+    },
+    types::SdkError,
+    Client as KinesisClient,
+};
+use chrono::{TimeZone, Utc};
+use futures::{stream::FuturesUnordered, StreamExt};
+use tokio::{pin, select, time::sleep};
+use tracing_futures::Instrument;
+use vector_common::finalizer::OrderedFinalizer;
+
+use super::{
+    checkpoint::{Checkpointer, SHARD_END},
+    config::{ConsumerConfig, StartingPosition},
+};
+use crate::{
+    codecs::Decoder,
+    event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event},
+    internal_events::{AwsKinesisStreamsRequestError, EndpointBytesReceived, StreamClosedError},
+    shutdown::ShutdownSignal,
+    sources::util::{
+        self,
+        cloudwatch_logs_subscription::{
+            insert_subscription_fields, parse_subscription_message, MessageType,
+        },
+    },
+    SourceSender,
+};
+
+/// The time to wait before retrying a failed request.
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+pub struct KinesisSource {
+    pub client: KinesisClient,
+    pub stream_name: String,
+    pub consumer: ConsumerConfig,
+    pub starting_position: StartingPosition,
+    pub max_records: u32,
+    pub poll_interval_secs: u64,
+    pub shard_discovery_interval_secs: u64,
+    pub checkpointer: Checkpointer,
+    pub unwrap_cloudwatch_logs: bool,
+    pub decoder: Decoder,
+    pub acknowledgements: bool,
+}
+
+/// The reason a shard stopped being consumed.
+enum ShardOutcome {
+    /// All of the records of the shard have been read, as it was closed by a resharding.
+    Ended,
+    /// The source is shutting down.
+    Stopped,
+}
+
+impl KinesisSource {
+    pub async fn run(self, out: SourceSender, shutdown: ShutdownSignal) -> Result<(), ()> {
+        let consumer_arn = match &self.consumer {
+            ConsumerConfig::Polling => None,
+            ConsumerConfig::EnhancedFanOut { consumer_name } => {
+                match self.register_consumer(consumer_name).await {
+                    Ok(consumer_arn) => Some(consumer_arn),
+                    Err(error) => {
+                        emit!(AwsKinesisStreamsRequestError {
+                            operation: "RegisterStreamConsumer",
+                            error,
+                            shard_id: None,
+                        });
+                        return Err(());
+                    }
+                }
+            }
+        };
+
+        let finalizer = Arc::new(Finalizer::new(
+            self.acknowledgements,
+            self.checkpointer.clone(),
+            shutdown.clone(),
+        ));
+        let source = Arc::new(self);
+        let discovery_interval = Duration::from_secs(source.shard_discovery_interval_secs);
+
+        let mut running = HashSet::new();
+        let mut finished = HashSet::new();
+        let mut tasks = FuturesUnordered::new();
+        let mut discovery = tokio::time::interval(discovery_interval);
+
+        let task_shutdown = shutdown.clone();
+        pin!(shutdown);
+        loop {
+            select! {
+                _ = &mut shutdown => break,
+                _ = discovery.tick() => {
+                    let shards = match source.list_shards().await {
+                        Ok(shards) => shards,
+                        Err(error) => {
+                            emit!(AwsKinesisStreamsRequestError {
+                                operation: "ListShards",
+                                error,
+                                shard_id: None,
+                            });
+                            continue;
+                        }
+                    };
+
+                    for shard_id in ready_shards(&shards, &running, &finished) {
+                        running.insert(shard_id.clone());
+                        let source = Arc::clone(&source);
+                        let consumer_arn = consumer_arn.clone();
+                        let out = out.clone();
+                        let finalizer = Arc::clone(&finalizer);
+                        let shutdown = task_shutdown.clone();
+                        tasks.push(tokio::spawn(
+                            async move {
+                                let outcome = select! {
+                                    _ = shutdown => ShardOutcome::Stopped,
+                                    outcome = source.consume_shard(
+                                        &shard_id,
+                                        consumer_arn.as_deref(),
+                                        out,
+                                        &finalizer,
+                                    ) => outcome,
+                                };
+                                (shard_id, outcome)
+                            }
+                            .in_current_span(),
+                        ));
+                    }
+                },
+                Some(result) = tasks.next() => {
+                    match result {
+                        Ok((shard_id, outcome)) => {
+                            running.remove(&shard_id);
+                            if let ShardOutcome::Ended = outcome {
+                                finished.insert(shard_id);
+                                // The child shards of the shard can now be consumed, so the shards
+                                // are listed again right away.
+                                discovery = tokio::time::interval(discovery_interval);
+                            }
+                        }
+                        Err(error) => {
+                            if error.is_panic() {
+                                panic::resume_unwind(error.into_panic());
+                            }
+                        }
+                    }
+                },
+            }
+        }
+
+        // Wait for all of the shards to stop being consumed. If any one of them panics, we resume
+        // that panic here to properly shutdown Vector.
+        while let Some(result) = tasks.next().await {
+            if let Err(error) = result {
+                if error.is_panic() {
+                    panic::resume_unwind(error.into_panic());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Registers the enhanced fan-out consumer on the stream if it doesn't exist yet, and waits
+    /// for it to be active.
+    async fn register_consumer(&self, consumer_name: &str) -> crate::Result<String> {
+        let stream_arn = self
+            .client
+            .describe_stream_summary()
+            .stream_name(&self.stream_name)
+            .send()
+            .await?
+            .stream_description_summary
+            .and_then(|summary| summary.stream_arn)
+            .ok_or("The stream description has no ARN.")?;
+
+        match self
+            .client
+            .register_stream_consumer()
+            .stream_arn(&stream_arn)
+            .consumer_name(consumer_name)
+            .send()
+            .await
+        {
+            Ok(_) => {}
+            Err(SdkError::ServiceError { err, raw: _ })
+                if matches!(
+                    err.kind,
+                    RegisterStreamConsumerErrorKind::ResourceInUseException(_)
+                ) =>
+            {
+                debug!(message = "Enhanced fan-out consumer already exists.", %consumer_name);
+            }
+            Err(error) => return Err(error.into()),
+        }
+
+        loop {
+            let description = self
+                .client
+                .describe_stream_consumer()
+                .stream_arn(&stream_arn)
+                .consumer_name(consumer_name)
+                .send()
+                .await?
+                .consumer_description
+                .ok_or("The stream consumer has no description.")?;
+
+            if description.consumer_status == Some(ConsumerStatus::Active) {
+                return description
+                    .consumer_arn
+                    .ok_or_else(|| "The stream consumer description has no ARN.".into());
+            }
+            sleep(RETRY_DELAY).await;
+        }
+    }
+
+    async fn list_shards(&self) -> crate::Result<Vec<Shard>> {
+        let mut shards = Vec::new();
+        let mut next_token = None;
+        loop {
+            // The stream name can't be set along with the token of the next page.
+            let request = match next_token.take() {
+                Some(next_token) => self.client.list_shards().next_token(next_token),
+                None => self.client.list_shards().stream_name(&self.stream_name),
+            };
+            let output = request.send().await?;
+            shards.extend(output.shards.unwrap_or_default());
+            match output.next_token {
+                Some(token) => next_token = Some(token),
+                None => return Ok(shards),
+            }
+        }
+    }
+
+    async fn consume_shard(
+        &self,
+        shard_id: &str,
+        consumer_arn: Option<&str>,
+        mut out: SourceSender,
+        finalizer: &Finalizer,
+    ) -> ShardOutcome {
+        let checkpoint = loop {
+            match self.checkpointer.get(shard_id).await {
+                Ok(checkpoint) => break checkpoint,
+                Err(error) => {
+                    emit!(AwsKinesisStreamsRequestError {
+                        operation: "GetCheckpoint",
+                        error,
+                        shard_id: Some(shard_id),
+                    });
+                    sleep(RETRY_DELAY).await;
+                }
+            }
+        };
+        if checkpoint.as_deref() == Some(SHARD_END) {
+            return ShardOutcome::Ended;
+        }
+
+        debug!(message = "Consuming shard.", %shard_id, ?checkpoint);
+        match consumer_arn {
+            Some(consumer_arn) => {
+                self.subscribe_shard(shard_id, consumer_arn, checkpoint, &mut out, finalizer)
+                    .await
+            }
+            None => {
+                self.poll_shard(shard_id, checkpoint, &mut out, finalizer)
+                    .await
+            }
+        }
+    }
+
+    async fn poll_shard(
+        &self,
+        shard_id: &str,
+        mut sequence_number: Option<String>,
+        out: &mut SourceSender,
+        finalizer: &Finalizer,
+    ) -> ShardOutcome {
+        let poll_interval = Duration::from_secs(self.poll_interval_secs);
+        let mut shard_iterator = None;
+        loop {
+            let iterator = match shard_iterator.take() {
+                Some(iterator) => iterator,
+                None => match self
+                    .get_shard_iterator(shard_id, sequence_number.as_deref())
+                    .await
+                {
+                    Ok(iterator) => iterator,
+                    Err(error) => {
+                        emit!(AwsKinesisStreamsRequestError {
+                            operation: "GetShardIterator",
+                            error,
+                            shard_id: Some(shard_id),
+                        });
+                        sleep(RETRY_DELAY).await;
+                        continue;
+                    }
+                },
+            };
+
+            let output = match self
+                .client
+                .get_records()
+                .shard_iterator(iterator)
+                .limit(self.max_records as i32)
+                .send()
+                .await
+            {
+                Ok(output) => output,
+                Err(error) => {
+                    // The shard iterator may have expired, so a new one is fetched from the last
+                    // record read.
+                    emit!(AwsKinesisStreamsRequestError {
+                        operation: "GetRecords",
+                        error,
+                        shard_id: Some(shard_id),
+                    });
+                    sleep(RETRY_DELAY).await;
+                    continue;
+                }
+            };
+
+            let records = output.records.unwrap_or_default();
+            let caught_up = records.is_empty();
+            if let Some(last) = self.handle_records(shard_id, records, out, finalizer).await {
+                sequence_number = Some(last);
+            }
+
+            match output.next_shard_iterator {
+                Some(next) => shard_iterator = Some(next),
+                None => {
+                    finalizer.finalize_shard_end(shard_id).await;
+                    return ShardOutcome::Ended;
+                }
+            }
+
+            if caught_up {
+                sleep(poll_interval).await;
+            }
+        }
+    }
+
+    async fn get_shard_iterator(
+        &self,
+        shard_id: &str,
+        sequence_number: Option<&str>,
+    ) -> crate::Result<String> {
+        self.client
+            .get_shard_iterator()
+            .stream_name(&self.stream_name)
+            .shard_id(shard_id)
+            .shard_iterator_type(self.shard_iterator_type(sequence_number))
+            .set_starting_sequence_number(sequence_number.map(ToOwned::to_owned))
+            .send()
+            .await?
+            .shard_iterator
+            .ok_or_else(|| "No shard iterator was returned.".into())
+    }
+
+    async fn subscribe_shard(
+        &self,
+        shard_id: &str,
+        consumer_arn: &str,
+        mut sequence_number: Option<String>,
+        out: &mut SourceSender,
+        finalizer: &Finalizer,
+    ) -> ShardOutcome {
+        loop {
+            let starting_position = KinesisStartingPosition::builder()
+                .r#type(self.shard_iterator_type(sequence_number.as_deref()))
+                .set_sequence_number(sequence_number.clone())
+                .build();
+            let mut events = match self
+                .client
+                .subscribe_to_shard()
+                .consumer_arn(consumer_arn)
+                .shard_id(shard_id)
+                .starting_position(starting_position)
+                .send()
+                .await
+            {
+                Ok(output) => output.event_stream,
+                Err(error) => {
+                    emit!(AwsKinesisStreamsRequestError {
+                        operation: "SubscribeToShard",
+                        error,
+                        shard_id: Some(shard_id),
+                    });
+                    sleep(RETRY_DELAY).await;
+                    continue;
+                }
+            };
+
+            // Subscriptions expire after five minutes, at which point the shard is subscribed to
+            // again from where the last subscription left off.
+            loop {
+                match events.recv().await {
+                    Ok(Some(SubscribeToShardEventStream::SubscribeToShardEvent(event))) => {
+                        let records = event.records.unwrap_or_default();
+                        if let Some(last) =
+                            self.handle_records(shard_id, records, out, finalizer).await
+                        {
+                            sequence_number = Some(last);
+                        }
+
+                        match event.continuation_sequence_number {
+                            Some(continuation) => sequence_number = Some(continuation),
+                            None => {
+                                finalizer.finalize_shard_end(shard_id).await;
+                                return ShardOutcome::Ended;
+                            }
+                        }
+                    }
+                    Ok(Some(_)) => {}
+                    Ok(None) => break,
+                    Err(error) => {
+                        emit!(AwsKinesisStreamsRequestError {
+                            operation: "SubscribeToShard",
+                            error,
+                            shard_id: Some(shard_id),
+                        });
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    fn shard_iterator_type(&self, sequence_number: Option<&str>) -> ShardIteratorType {
+        match (sequence_number, self.starting_position) {
+            (Some(_), _) => ShardIteratorType::AfterSequenceNumber,
+            (None, StartingPosition::Latest) => ShardIteratorType::Latest,
+            (None, StartingPosition::TrimHorizon) => ShardIteratorType::TrimHorizon,
+        }
+    }
+
+    /// Sends the events of the records, and returns the sequence number of the last record.
+    async fn handle_records(
+        &self,
+        shard_id: &str,
+        records: Vec<Record>,
+        out: &mut SourceSender,
+        finalizer: &Finalizer,
+    ) -> Option<String> {
+        let last = records.last()?.sequence_number.clone()?;
+
+        let byte_size = records
+            .iter()
+            .map(|record| record.data.as_ref().map_or(0, |data| data.as_ref().len()))
+            .sum();
+        emit!(EndpointBytesReceived {
+            byte_size,
+            protocol: "http",
+            endpoint: &self.stream_name,
+        });
+
+        let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(self.acknowledgements);
+        let mut events = Vec::new();
+        for record in records {
+            let timestamp = record.approximate_arrival_timestamp.and_then(|timestamp| {
+                Utc.timestamp_opt(timestamp.secs(), timestamp.subsec_nanos())
+                    .single()
+            });
+            let data = record
+                .data
+                .map(|data| data.into_inner())
+                .unwrap_or_default();
+            let partition_key = record.partition_key.unwrap_or_default();
+            let sequence_number = record.sequence_number.unwrap_or_default();
+            let insert_fields = |mut event: Event| {
+                if let Event::Log(ref mut log) = event {
+                    log.try_insert("partition_key", partition_key.clone());
+                    log.try_insert("sequence_number", sequence_number.clone());
+                    log.try_insert("shard_id", shard_id.to_owned());
+                    log.try_insert("stream", self.stream_name.clone());
+                }
+                event
+            };
+
+            let subscription = if self.unwrap_cloudwatch_logs {
+                parse_subscription_message(&data)
+            } else {
+                None
+            };
+            match subscription {
+                // Control messages are only sent by CloudWatch Logs to check that the stream is
+                // reachable, so they are dropped.
+                Some(message) if message.message_type != MessageType::DataMessage => {}
+                Some(message) => {
+                    for log_event in &message.log_events {
+                        events.extend(
+                            util::decode_message(
+                                self.decoder.clone(),
+                                "aws_kinesis_streams",
+                                log_event.message.as_bytes(),
+                                log_event.timestamp(),
+                                &batch,
+                            )
+                            .map(|mut event| {
+                                if let Event::Log(ref mut log) = event {
+                                    insert_subscription_fields(log, &message, log_event);
+                                }
+                                insert_fields(event)
+                            }),
+                        );
+                    }
+                }
+                None => events.extend(
+                    util::decode_message(
+                        self.decoder.clone(),
+                        "aws_kinesis_streams",
+                        &data,
+                        timestamp,
+                        &batch,
+                    )
+                    .map(insert_fields),
+                ),
+            }
+        }
+        drop(batch); // Drop last reference to batch acknowledgement finalizer
+
+        let count = events.len();
+        match out.send_batch(events).await {
+            Ok(()) => {
+                finalizer
+                    .finalize(shard_id.to_owned(), last.clone(), receiver)
+                    .await
+            }
+            Err(error) => emit!(StreamClosedError { error, count }),
+        }
+
+        Some(last)
+    }
+}
+
+/// Gets the shards ready to be consumed, which are the shards not consumed yet, and whose parent
+/// shards have all been read.
+///
+/// Parent shards which are not listed anymore have expired, so their records can't be read.
+fn ready_shards(
+    shards: &[Shard],
+    running: &HashSet<String>,
+    finished: &HashSet<String>,
+) -> Vec<String> {
+    let listed = shards
+        .iter()
+        .filter_map(|shard| shard.shard_id())
+        .collect::<HashSet<_>>();
+    let is_pending = |parent: Option<&str>| {
+        parent.map_or(false, |parent| {
+            listed.contains(parent) && !finished.contains(parent)
+        })
+    };
+
+    shards
+        .iter()
+        .filter(|shard| {
+            !is_pending(shard.parent_shard_id()) && !is_pending(shard.adjacent_parent_shard_id())
+        })
+        .filter_map(|shard| shard.shard_id())
+        .filter(|shard_id| !running.contains(*shard_id) && !finished.contains(*shard_id))
+        .map(ToOwned::to_owned)
+        .collect()
+}
+
+struct ShardCheckpoint {
+    shard_id: String,
+    checkpoint: String,
+}
+
+enum Finalizer {
+    Sync(Checkpointer),
+    Async(OrderedFinalizer<ShardCheckpoint>),
+}
+
+impl Finalizer {
+    fn new(acknowledgements: bool, checkpointer: Checkpointer, shutdown: ShutdownSignal) -> Self {
+        if acknowledgements {
+            let (finalizer, mut ack_stream) = OrderedFinalizer::new(shutdown);
+            tokio::spawn(
+                async move {
+                    while let Some((status, entry)) = ack_stream.next().await {
+                        if status == BatchStatus::Delivered {
+                            checkpointer.set(&entry.shard_id, &entry.checkpoint).await;
+                        }
+                    }
+                }
+                .in_current_span(),
+            );
+            Self::Async(finalizer)
+        } else {
+            Self::Sync(checkpointer)
+        }
+    }
+
+    async fn finalize(
+        &self,
+        shard_id: String,
+        checkpoint: String,
+        receiver: Option<BatchStatusReceiver>,
+    ) {
+        match (self, receiver) {
+            (Self::Sync(checkpointer), None) => checkpointer.set(&shard_id, &checkpoint).await,
+            (Self::Async(finalizer), Some(receiver)) => finalizer.add(
+                ShardCheckpoint {
+                    shard_id,
+                    checkpoint,
+                },
+                receiver,
+            ),
+            _ => unreachable!(
+                "Cannot have async finalization without a receiver in aws_kinesis_streams source"
+            ),
+        }
+    }
+
+    /// Checkpoints the end of the shard, once all of the records read from it are finalized.
+    async fn finalize_shard_end(&self, shard_id: &str) {
+        let receiver = match self {
+            Self::Sync(_) => None,
+            Self::Async(_) => Some(BatchNotifier::new_with_receiver().1),
+        };
+        self.finalize(shard_id.to_owned(), SHARD_END.to_owned(), receiver)
+            .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shard(shard_id: &str, parents: &[&str]) -> Shard {
+        Shard::builder()
+            .shard_id(shard_id)
+            .set_parent_shard_id(parents.first().map(|parent| parent.to_string()))
+            .set_adjacent_parent_shard_id(parents.get(1).map(|parent| parent.to_string()))
+            .build()
+    }
+
+    fn set(shard_ids: &[&str]) -> HashSet<String> {
+        shard_ids
+            .iter()
+            .map(|shard_id| shard_id.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn children_wait_for_parents() {
+        // Shard 0 was split into shards 1 and 2, which were then merged into shard 3.
+        let shards = vec![
+            shard("shard-0", &[]),
+            shard("shard-1", &["shard-0"]),
+            shard("shard-2", &["shard-0"]),
+            shard("shard-3", &["shard-1", "shard-2"]),
+        ];
+
+        assert_eq!(ready_shards(&shards, &set(&[]), &set(&[])), vec!["shard-0"]);
+        assert!(ready_shards(&shards, &set(&["shard-0"]), &set(&[])).is_empty());
+        assert_eq!(
+            ready_shards(&shards, &set(&[]), &set(&["shard-0"])),
+            vec!["shard-1", "shard-2"]
+        );
+        assert_eq!(
+            ready_shards(&shards, &set(&["shard-2"]), &set(&["shard-0", "shard-1"])),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            ready_shards(&shards, &set(&[]), &set(&["shard-0", "shard-1", "shard-2"])),
+            vec!["shard-3"]
+        );
+    }
+
+    #[test]
+    fn expired_parents_are_skipped() {
+        let shards = vec![shard("shard-1", &["shard-0"])];
+        assert_eq!(ready_shards(&shards, &set(&[]), &set(&[])), vec!["shard-1"]);
+    }
+}
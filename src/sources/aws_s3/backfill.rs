@@ -0,0 +1,363 @@
+//! Backfills: the objects already in a bucket are listed by prefix and time of last modification,
+//! and read once, to re-ingest them after an incident.
+//!
+//! The objects are listed in the order of their keys, and read at a bounded concurrency. The key
+//! of the last object read, in that order, is persisted in the state store of the source, so that
+//! an interrupted backfill resumes after it.
+
+use std::path::PathBuf;
+
+use aws_sdk_s3::error::ListObjectsV2Error;
+use aws_sdk_s3::Client as S3Client;
+use aws_smithy_client::SdkError;
+use chrono::{DateTime, TimeZone, Utc};
+use futures::{Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use vector_common::internal_event::{BytesReceived, Protocol};
+use vector_config::configurable_component;
+
+use super::sqs::read_object;
+use crate::{
+    config::{AcknowledgementsConfig, SourceContext},
+    internal_events::{
+        S3BackfillCheckpointError, S3BackfillListError, S3BackfillObjectError, S3BackfillObjectRead,
+    },
+    line_agg,
+    state_store::{BoxedStateStore, StateStoreConfig},
+};
+
+const PROGRESS_KEY: &str = "backfill.json";
+
+/// Backfill configuration options.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub(super) struct Config {
+    /// The name of the bucket to read the objects of.
+    pub(super) bucket: String,
+
+    /// The prefix of the keys of the objects to read.
+    ///
+    /// By default, all the objects of the bucket are read.
+    pub(super) prefix: Option<String>,
+
+    /// Only the objects last modified at, or after, this time are read.
+    pub(super) start_time: Option<DateTime<Utc>>,
+
+    /// Only the objects last modified before this time are read.
+    pub(super) end_time: Option<DateTime<Utc>>,
+
+    /// The number of objects read at once.
+    ///
+    /// Defaults to the number of available CPUs on the system.
+    #[serde(default = "default_concurrency")]
+    #[derivative(Default(value = "default_concurrency()"))]
+    pub(super) concurrency: u32,
+
+    /// The directory used to persist the progress of the backfill, when `state_store` is `file`.
+    ///
+    /// By default, the global `data_dir` option is used. Please make sure the user Vector is running as has write permissions to this directory.
+    pub(super) data_dir: Option<PathBuf>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub(super) state_store: StateStoreConfig,
+}
+
+fn default_concurrency() -> u32 {
+    crate::num_threads() as u32
+}
+
+impl Config {
+    /// Whether an object is read, from the time it was last modified.
+    ///
+    /// The objects without a time of last modification are only read when the time range is
+    /// unbounded.
+    fn includes(&self, last_modified: Option<DateTime<Utc>>) -> bool {
+        match last_modified {
+            Some(last_modified) => {
+                self.start_time.map_or(true, |start| last_modified >= start)
+                    && self.end_time.map_or(true, |end| last_modified < end)
+            }
+            None => self.start_time.is_none() && self.end_time.is_none(),
+        }
+    }
+}
+
+/// The progress of a backfill, as persisted in the state store.
+#[derive(Debug, Default, Deserialize, PartialEq, Serialize)]
+struct Checkpoint {
+    bucket: String,
+    prefix: Option<String>,
+    start_time: Option<DateTime<Utc>>,
+    end_time: Option<DateTime<Utc>>,
+    /// The key of the last object read, all the objects before it having been read too.
+    last_key: Option<String>,
+    objects_read: u64,
+}
+
+impl Checkpoint {
+    fn new(config: &Config) -> Self {
+        Self {
+            bucket: config.bucket.clone(),
+            prefix: config.prefix.clone(),
+            start_time: config.start_time,
+            end_time: config.end_time,
+            ..Default::default()
+        }
+    }
+
+    /// Whether the progress is that of the backfill configured, rather than of a previous one.
+    fn is_of(&self, config: &Config) -> bool {
+        self.bucket == config.bucket
+            && self.prefix == config.prefix
+            && self.start_time == config.start_time
+            && self.end_time == config.end_time
+    }
+}
+
+/// The progress of a backfill, persisted in its state store as it advances.
+pub(super) struct Progress {
+    store: BoxedStateStore,
+    checkpoint: Checkpoint,
+}
+
+impl Progress {
+    /// Loads the persisted progress of the backfill, if any.
+    ///
+    /// The progress of a backfill of another bucket, prefix or time range is discarded.
+    pub(super) async fn load(store: BoxedStateStore, config: &Config) -> crate::Result<Self> {
+        let checkpoint = match store.get(PROGRESS_KEY).await? {
+            Some(checkpoint) => serde_json::from_slice(&checkpoint)?,
+            None => Checkpoint::new(config),
+        };
+        let checkpoint = if checkpoint.is_of(config) {
+            if let Some(last_key) = &checkpoint.last_key {
+                info!(
+                    message = "Resuming backfill.",
+                    %last_key,
+                    objects_read = %checkpoint.objects_read,
+                );
+            }
+            checkpoint
+        } else {
+            warn!(message = "Discarding the progress of a previous backfill of other objects.");
+            Checkpoint::new(config)
+        };
+        Ok(Self { store, checkpoint })
+    }
+
+    /// Records that an object was read, with all the objects before it.
+    async fn advance(&mut self, key: String) {
+        self.checkpoint.objects_read += 1;
+        emit!(S3BackfillObjectRead {
+            key: &key,
+            objects_read: self.checkpoint.objects_read,
+        });
+        self.checkpoint.last_key = Some(key);
+
+        let checkpoint = serde_json::to_vec(&self.checkpoint).expect("checkpoint is serializable");
+        if let Err(error) = self.store.set(PROGRESS_KEY, checkpoint.into()).await {
+            emit!(S3BackfillCheckpointError { error });
+        }
+    }
+}
+
+pub(super) struct Backfill {
+    config: Config,
+    region: String,
+    s3_client: S3Client,
+    compression: super::Compression,
+    multiline: Option<line_agg::Config>,
+    progress: Progress,
+}
+
+impl Backfill {
+    pub(super) const fn new(
+        config: Config,
+        region: String,
+        s3_client: S3Client,
+        compression: super::Compression,
+        multiline: Option<line_agg::Config>,
+        progress: Progress,
+    ) -> Self {
+        Self {
+            config,
+            region,
+            s3_client,
+            compression,
+            multiline,
+            progress,
+        }
+    }
+
+    /// Reads the objects of the backfill, until they have all been read or the source is shut
+    /// down.
+    ///
+    /// The backfill stops at the first object that can't be read, so that it is read again when
+    /// the backfill resumes.
+    pub(super) async fn run(
+        self,
+        cx: SourceContext,
+        acknowledgements: AcknowledgementsConfig,
+    ) -> Result<(), ()> {
+        let acknowledgements = cx.do_acknowledgements(&acknowledgements);
+        let bytes_received = register!(BytesReceived::from(Protocol::HTTP));
+        let Self {
+            config,
+            region,
+            s3_client,
+            compression,
+            multiline,
+            mut progress,
+        } = self;
+
+        // The objects are read concurrently, but their results come in the order of their keys,
+        // so that the progress only advances past the objects that have all been read.
+        let mut reads = list_objects(&s3_client, &config, progress.checkpoint.last_key.clone())
+            .map(|object| {
+                let mut out = cx.out.clone();
+                let (s3_client, config, region) = (&s3_client, &config, &region);
+                let (multiline, bytes_received) = (multiline.as_ref(), &bytes_received);
+                async move {
+                    let key = object.map_err(|error| {
+                        emit!(S3BackfillListError {
+                            bucket: &config.bucket,
+                            error: &error,
+                        });
+                    })?;
+                    read_object(
+                        s3_client,
+                        compression,
+                        multiline,
+                        &config.bucket,
+                        &key,
+                        region,
+                        &mut out,
+                        acknowledgements,
+                        bytes_received,
+                    )
+                    .await
+                    .map_err(|error| emit!(S3BackfillObjectError { error: &error }))?;
+                    Ok::<_, ()>(key)
+                }
+            })
+            .buffered(config.concurrency.max(1) as usize)
+            .take_until(cx.shutdown.clone());
+
+        while let Some(result) = reads.next().await {
+            progress.advance(result?).await;
+        }
+
+        if !reads.is_stopped() {
+            info!(
+                message = "Backfill complete.",
+                objects_read = %progress.checkpoint.objects_read,
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Lists the keys of the objects of the backfill after the given one, in their order.
+fn list_objects<'a>(
+    s3_client: &'a S3Client,
+    config: &'a Config,
+    start_after: Option<String>,
+) -> impl Stream<Item = Result<String, SdkError<ListObjectsV2Error>>> + Send + 'a {
+    async_stream::stream! {
+        let mut continuation_token = None;
+        loop {
+            let result = s3_client
+                .list_objects_v2()
+                .bucket(&config.bucket)
+                .set_prefix(config.prefix.clone())
+                .set_start_after(start_after.clone())
+                .set_continuation_token(continuation_token.take())
+                .send()
+                .await;
+            let output = match result {
+                Ok(output) => output,
+                Err(error) => {
+                    yield Err(error);
+                    break;
+                }
+            };
+
+            for object in output.contents.unwrap_or_default() {
+                let last_modified = object
+                    .last_modified
+                    .map(|ts| Utc.timestamp(ts.secs(), ts.subsec_nanos()));
+                match object.key {
+                    Some(key) if config.includes(last_modified) => yield Ok(key),
+                    _ => (),
+                }
+            }
+
+            continuation_token = output.next_continuation_token;
+            if continuation_token.is_none() {
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::state_store::FileStateStore;
+
+    fn config() -> Config {
+        Config {
+            bucket: "logs".to_string(),
+            prefix: Some("2022/10/".to_string()),
+            start_time: Some(Utc.ymd(2022, 10, 1).and_hms(0, 0, 0)),
+            end_time: Some(Utc.ymd(2022, 10, 2).and_hms(0, 0, 0)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn includes_objects_in_time_range() {
+        let config = config();
+        assert!(config.includes(Some(Utc.ymd(2022, 10, 1).and_hms(0, 0, 0))));
+        assert!(config.includes(Some(Utc.ymd(2022, 10, 1).and_hms(23, 59, 59))));
+        assert!(!config.includes(Some(Utc.ymd(2022, 10, 2).and_hms(0, 0, 0))));
+        assert!(!config.includes(Some(Utc.ymd(2022, 9, 30).and_hms(23, 59, 59))));
+        assert!(!config.includes(None));
+
+        let unbounded = Config {
+            bucket: "logs".to_string(),
+            ..Default::default()
+        };
+        assert!(unbounded.includes(Some(Utc.ymd(2022, 9, 30).and_hms(0, 0, 0))));
+        assert!(unbounded.includes(None));
+    }
+
+    #[tokio::test]
+    async fn resumes_progress_of_same_backfill() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let store: BoxedStateStore = Arc::new(FileStateStore::new(data_dir.path().to_path_buf()));
+
+        let mut progress = Progress::load(Arc::clone(&store), &config()).await.unwrap();
+        assert_eq!(progress.checkpoint.last_key, None);
+        progress.advance("2022/10/a.log".to_string()).await;
+        progress.advance("2022/10/b.log".to_string()).await;
+
+        let progress = Progress::load(Arc::clone(&store), &config()).await.unwrap();
+        assert_eq!(
+            progress.checkpoint.last_key.as_deref(),
+            Some("2022/10/b.log")
+        );
+        assert_eq!(progress.checkpoint.objects_read, 2);
+
+        // The progress of another backfill is discarded.
+        let mut other = config();
+        other.end_time = None;
+        let progress = Progress::load(store, &other).await.unwrap();
+        assert_eq!(progress.checkpoint, Checkpoint::new(&other));
+    }
+}
@@ -23,6 +23,7 @@ use crate::{
     serde::bool_or_struct,
 };
 
+mod backfill;
 pub mod sqs;
 
 /// Compression scheme for objects retrieved from S3.
@@ -58,6 +59,15 @@ enum Strategy {
     /// [aws_sqs]: https://aws.amazon.com/sqs/
     #[derivative(Default)]
     Sqs,
+
+    /// Reads the objects already in a bucket once, by prefix and time of last modification.
+    ///
+    /// This re-ingests the objects of a time range after an incident, and can be run to completion with `vector
+    /// --once`. An [S3-compatible][gcs_interop] Google Cloud Storage bucket can be backfilled by setting `endpoint` to
+    /// `https://storage.googleapis.com`.
+    ///
+    /// [gcs_interop]: https://cloud.google.com/storage/docs/interoperability
+    Backfill,
 }
 
 /// Configuration for the `aws_s3` source.
@@ -84,6 +94,11 @@ pub struct AwsS3Config {
     /// Only relevant when `strategy = "sqs"`.
     sqs: Option<sqs::Config>,
 
+    /// Configuration options for backfills.
+    ///
+    /// Only relevant when `strategy = "backfill"`.
+    backfill: Option<backfill::Config>,
+
     /// The ARN of an [IAM role][iam_role] to assume at startup.
     ///
     /// [iam_role]: https://docs.aws.amazon.com/IAM/latest/UserGuide/id_roles.html
@@ -124,6 +139,11 @@ impl SourceConfig for AwsS3Config {
                     .await?
                     .run(cx, self.acknowledgements),
             )),
+            Strategy::Backfill => Ok(Box::pin(
+                self.create_backfill(multiline_config, &cx)
+                    .await?
+                    .run(cx, self.acknowledgements),
+            )),
         }
     }
 
@@ -134,6 +154,10 @@ impl SourceConfig for AwsS3Config {
     fn can_acknowledge(&self) -> bool {
         true
     }
+
+    fn can_run_once(&self) -> bool {
+        matches!(self.strategy, Strategy::Backfill)
+    }
 }
 
 impl AwsS3Config {
@@ -189,6 +213,52 @@ impl AwsS3Config {
             None => Err(CreateSqsIngestorError::ConfigMissing {}.into()),
         }
     }
+
+    async fn create_backfill(
+        &self,
+        multiline: Option<line_agg::Config>,
+        cx: &SourceContext,
+    ) -> crate::Result<backfill::Backfill> {
+        let config = self
+            .backfill
+            .clone()
+            .ok_or(CreateBackfillError::ConfigMissing)?;
+
+        let region = self
+            .region
+            .region()
+            .ok_or(CreateBackfillError::RegionMissing)?;
+
+        let endpoint = self
+            .region
+            .endpoint()
+            .map_err(|_| CreateBackfillError::InvalidEndpoint)?;
+
+        let s3_client = create_client::<S3ClientBuilder>(
+            &self.auth,
+            Some(region.clone()),
+            endpoint,
+            &cx.proxy,
+            &self.tls_options,
+            false,
+        )
+        .await?;
+
+        let store = config
+            .state_store
+            .build(cx, config.data_dir.as_ref())
+            .await?;
+        let progress = backfill::Progress::load(store, &config).await?;
+
+        Ok(backfill::Backfill::new(
+            config,
+            region.to_string(),
+            s3_client,
+            self.compression,
+            multiline,
+            progress,
+        ))
+    }
 }
 
 #[derive(Debug, Snafu)]
@@ -207,6 +277,16 @@ enum CreateSqsIngestorError {
     InvalidEndpoint,
 }
 
+#[derive(Debug, Snafu)]
+enum CreateBackfillError {
+    #[snafu(display("Configuration for `backfill` required when strategy=backfill"))]
+    ConfigMissing,
+    #[snafu(display("Region is required"))]
+    RegionMissing,
+    #[snafu(display("Endpoint is invalid"))]
+    InvalidEndpoint,
+}
+
 /// None if body is empty
 async fn s3_object_decoder(
     compression: Compression,
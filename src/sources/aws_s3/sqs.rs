@@ -434,144 +434,18 @@ impl IngestorProcess {
             });
         }
 
-        let object_result = self
-            .state
-            .s3_client
-            .get_object()
-            .bucket(s3_event.s3.bucket.name.clone())
-            .key(s3_event.s3.object.key.clone())
-            .send()
-            .await
-            .context(GetObjectSnafu {
-                bucket: s3_event.s3.bucket.name.clone(),
-                key: s3_event.s3.object.key.clone(),
-            });
-
-        let object = object_result?;
-
-        let metadata = object.metadata;
-        let timestamp = object
-            .last_modified
-            .map(|ts| Utc.timestamp(ts.secs(), ts.subsec_nanos()))
-            .unwrap_or_else(Utc::now);
-
-        let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(self.acknowledgements);
-        let object_reader = super::s3_object_decoder(
+        read_object(
+            &self.state.s3_client,
             self.state.compression,
+            self.state.multiline.as_ref(),
+            &s3_event.s3.bucket.name,
             &s3_event.s3.object.key,
-            object.content_encoding.as_deref(),
-            object.content_type.as_deref(),
-            object.body,
+            &s3_event.aws_region,
+            &mut self.out,
+            self.acknowledgements,
+            &self.bytes_received,
         )
-        .await;
-
-        // Record the read error seen to propagate up later so we avoid ack'ing the SQS
-        // message
-        //
-        // String is used as we cannot clone std::io::Error to take ownership in closure
-        //
-        // FramedRead likely stops when it gets an i/o error but I found it more clear to
-        // show that we `take_while` there hasn't been an error
-        //
-        // This can result in objects being partially processed before an error, but we
-        // prefer duplicate lines over message loss. Future work could include recording
-        // the offset of the object that has been read, but this would only be relevant in
-        // the case that the same vector instance processes the same message.
-        let mut read_error = None;
-        let bytes_received = self.bytes_received.clone();
-        let lines: Box<dyn Stream<Item = Bytes> + Send + Unpin> = Box::new(
-            FramedRead::new(object_reader, CharacterDelimitedDecoder::new(b'\n'))
-                .map(|res| {
-                    res.map(|bytes| {
-                        bytes_received.emit(ByteSize(bytes.len()));
-                        bytes
-                    })
-                    .map_err(|err| {
-                        read_error = Some(err);
-                    })
-                    .ok()
-                })
-                .take_while(|res| ready(res.is_some()))
-                .map(|r| r.expect("validated by take_while")),
-        );
-
-        let lines = match &self.state.multiline {
-            Some(config) => Box::new(
-                LineAgg::new(
-                    lines.map(|line| ((), line, ())),
-                    line_agg::Logic::new(config.clone()),
-                )
-                .map(|(_src, line, _context)| line),
-            ),
-            None => lines,
-        };
-
-        let bucket_name = Bytes::from(s3_event.s3.bucket.name.as_str().as_bytes().to_vec());
-        let object_key = Bytes::from(s3_event.s3.object.key.as_str().as_bytes().to_vec());
-        let aws_region = Bytes::from(s3_event.aws_region.as_str().as_bytes().to_vec());
-
-        let mut stream = lines.filter_map(move |line| {
-            let mut log = LogEvent::from_bytes_legacy(&line).with_batch_notifier_option(&batch);
-
-            log.insert(event_path!("bucket"), bucket_name.clone());
-            log.insert(event_path!("object"), object_key.clone());
-            log.insert(event_path!("region"), aws_region.clone());
-            log.insert(log_schema().source_type_key(), Bytes::from("aws_s3"));
-            log.insert(log_schema().timestamp_key(), timestamp);
-
-            if let Some(metadata) = &metadata {
-                for (key, value) in metadata {
-                    log.insert(key.as_str(), value.clone());
-                }
-            }
-
-            emit!(EventsReceived {
-                count: 1,
-                byte_size: log.size_of()
-            });
-
-            ready(Some(log))
-        });
-
-        let send_error = match self.out.send_event_stream(&mut stream).await {
-            Ok(_) => None,
-            Err(error) => {
-                let (count, _) = stream.size_hint();
-                emit!(StreamClosedError { error, count });
-                Some(crate::source_sender::ClosedError)
-            }
-        };
-
-        // Up above, `lines` captures `read_error`, and eventually is captured by `stream`,
-        // so we explicitly drop it so that we can again utilize `read_error` below.
-        drop(stream);
-
-        if let Some(error) = read_error {
-            Err(ProcessingError::ReadObject {
-                source: error,
-                bucket: s3_event.s3.bucket.name.clone(),
-                key: s3_event.s3.object.key.clone(),
-            })
-        } else if let Some(error) = send_error {
-            Err(ProcessingError::PipelineSend {
-                source: error,
-                bucket: s3_event.s3.bucket.name.clone(),
-                key: s3_event.s3.object.key.clone(),
-            })
-        } else {
-            match receiver {
-                None => Ok(()),
-                Some(receiver) => match receiver.await {
-                    BatchStatus::Delivered => Ok(()),
-                    BatchStatus::Errored => Err(ProcessingError::ErrorAcknowledgement),
-                    BatchStatus::Rejected => {
-                        // Sinks are responsible for emitting ComponentEventsDropped.
-                        // Failed events cannot be retried, so continue to delete the SQS source message.
-                        Ok(())
-                    }
-                },
-            }
-        }
+        .await
     }
 
     async fn receive_messages(&mut self) -> Result<Vec<Message>, SdkError<ReceiveMessageError>> {
@@ -601,6 +475,156 @@ impl IngestorProcess {
     }
 }
 
+/// Reads an object, sending an event for each of its lines, and waits for them to be delivered
+/// when acknowledgements are enabled.
+#[allow(clippy::too_many_arguments)]
+pub(super) async fn read_object(
+    s3_client: &S3Client,
+    compression: super::Compression,
+    multiline: Option<&line_agg::Config>,
+    bucket: &str,
+    key: &str,
+    region: &str,
+    out: &mut SourceSender,
+    acknowledgements: bool,
+    bytes_received: &Registered<BytesReceived>,
+) -> Result<(), ProcessingError> {
+    let object_result = s3_client
+        .get_object()
+        .bucket(bucket)
+        .key(key)
+        .send()
+        .await
+        .context(GetObjectSnafu { bucket, key });
+
+    let object = object_result?;
+
+    let metadata = object.metadata;
+    let timestamp = object
+        .last_modified
+        .map(|ts| Utc.timestamp(ts.secs(), ts.subsec_nanos()))
+        .unwrap_or_else(Utc::now);
+
+    let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(acknowledgements);
+    let object_reader = super::s3_object_decoder(
+        compression,
+        key,
+        object.content_encoding.as_deref(),
+        object.content_type.as_deref(),
+        object.body,
+    )
+    .await;
+
+    // Record the read error seen to propagate up later so we avoid ack'ing the SQS
+    // message, or checkpointing the object
+    //
+    // String is used as we cannot clone std::io::Error to take ownership in closure
+    //
+    // FramedRead likely stops when it gets an i/o error but I found it more clear to
+    // show that we `take_while` there hasn't been an error
+    //
+    // This can result in objects being partially processed before an error, but we
+    // prefer duplicate lines over message loss. Future work could include recording
+    // the offset of the object that has been read, but this would only be relevant in
+    // the case that the same vector instance processes the same message.
+    let mut read_error = None;
+    let bytes_received = bytes_received.clone();
+    let lines: Box<dyn Stream<Item = Bytes> + Send + Unpin> = Box::new(
+        FramedRead::new(object_reader, CharacterDelimitedDecoder::new(b'\n'))
+            .map(|res| {
+                res.map(|bytes| {
+                    bytes_received.emit(ByteSize(bytes.len()));
+                    bytes
+                })
+                .map_err(|err| {
+                    read_error = Some(err);
+                })
+                .ok()
+            })
+            .take_while(|res| ready(res.is_some()))
+            .map(|r| r.expect("validated by take_while")),
+    );
+
+    let lines = match multiline {
+        Some(config) => Box::new(
+            LineAgg::new(
+                lines.map(|line| ((), line, ())),
+                line_agg::Logic::new(config.clone()),
+            )
+            .map(|(_src, line, _context)| line),
+        ),
+        None => lines,
+    };
+
+    let bucket_name = Bytes::from(bucket.as_bytes().to_vec());
+    let object_key = Bytes::from(key.as_bytes().to_vec());
+    let aws_region = Bytes::from(region.as_bytes().to_vec());
+
+    let mut stream = lines.filter_map(move |line| {
+        let mut log = LogEvent::from_bytes_legacy(&line).with_batch_notifier_option(&batch);
+
+        log.insert(event_path!("bucket"), bucket_name.clone());
+        log.insert(event_path!("object"), object_key.clone());
+        log.insert(event_path!("region"), aws_region.clone());
+        log.insert(log_schema().source_type_key(), Bytes::from("aws_s3"));
+        log.insert(log_schema().timestamp_key(), timestamp);
+
+        if let Some(metadata) = &metadata {
+            for (key, value) in metadata {
+                log.insert(key.as_str(), value.clone());
+            }
+        }
+
+        emit!(EventsReceived {
+            count: 1,
+            byte_size: log.size_of()
+        });
+
+        ready(Some(log))
+    });
+
+    let send_error = match out.send_event_stream(&mut stream).await {
+        Ok(_) => None,
+        Err(error) => {
+            let (count, _) = stream.size_hint();
+            emit!(StreamClosedError { error, count });
+            Some(crate::source_sender::ClosedError)
+        }
+    };
+
+    // Up above, `lines` captures `read_error`, and eventually is captured by `stream`,
+    // so we explicitly drop it so that we can again utilize `read_error` below.
+    drop(stream);
+
+    if let Some(error) = read_error {
+        Err(ProcessingError::ReadObject {
+            source: error,
+            bucket: bucket.to_owned(),
+            key: key.to_owned(),
+        })
+    } else if let Some(error) = send_error {
+        Err(ProcessingError::PipelineSend {
+            source: error,
+            bucket: bucket.to_owned(),
+            key: key.to_owned(),
+        })
+    } else {
+        match receiver {
+            None => Ok(()),
+            Some(receiver) => match receiver.await {
+                BatchStatus::Delivered => Ok(()),
+                BatchStatus::Errored => Err(ProcessingError::ErrorAcknowledgement),
+                BatchStatus::Rejected => {
+                    // Sinks are responsible for emitting ComponentEventsDropped.
+                    // Failed events cannot be retried, so continue to delete the SQS source message,
+                    // or to checkpoint the object.
+                    Ok(())
+                }
+            },
+        }
+    }
+}
+
 // https://docs.aws.amazon.com/AmazonS3/latest/userguide/how-to-enable-disable-notification-intro.html
 #[derive(Clone, Debug, Deserialize)]
 #[serde(untagged)]
@@ -98,6 +98,7 @@ impl SourceConfig for AwsSqsConfig {
         )
         .build();
         let acknowledgements = cx.do_acknowledgements(&self.acknowledgements);
+        let acknowledgements_timeout = cx.acknowledgements_timeout(&self.acknowledgements);
 
         Ok(Box::pin(
             SqsSource {
@@ -109,6 +110,7 @@ impl SourceConfig for AwsSqsConfig {
                 visibility_timeout_secs: self.visibility_timeout_secs,
                 delete_message: self.delete_message,
                 acknowledgements,
+                acknowledgements_timeout,
             }
             .run(cx.out, cx.shutdown),
         ))
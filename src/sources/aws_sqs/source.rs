@@ -1,7 +1,10 @@
-use std::{collections::HashMap, panic, str::FromStr, sync::Arc};
+use std::{collections::HashMap, panic, str::FromStr, sync::Arc, time::Duration};
 
 use aws_sdk_sqs::{
-    model::{DeleteMessageBatchRequestEntry, MessageSystemAttributeName, QueueAttributeName},
+    model::{
+        ChangeMessageVisibilityBatchRequestEntry, DeleteMessageBatchRequestEntry,
+        MessageSystemAttributeName, QueueAttributeName,
+    },
     Client as SqsClient,
 };
 use chrono::{DateTime, TimeZone, Utc};
@@ -14,7 +17,8 @@ use crate::{
     codecs::Decoder,
     event::{BatchNotifier, BatchStatus},
     internal_events::{
-        EndpointBytesReceived, SqsMessageDeleteError, SqsMessageReceiveError, StreamClosedError,
+        EndpointBytesReceived, SqsMessageDeleteError, SqsMessageReceiveError,
+        SqsMessageVisibilityError, StreamClosedError,
     },
     shutdown::ShutdownSignal,
     sources::util,
@@ -36,20 +40,30 @@ pub struct SqsSource {
     pub delete_message: bool,
     pub concurrency: u32,
     pub(super) acknowledgements: bool,
+    pub(super) acknowledgements_timeout: Option<Duration>,
 }
 
 impl SqsSource {
     pub async fn run(self, out: SourceSender, shutdown: ShutdownSignal) -> Result<(), ()> {
         let mut task_handles = vec![];
         let finalizer = self.acknowledgements.then(|| {
-            let (finalizer, mut ack_stream) = Finalizer::new(shutdown.clone());
+            let (finalizer, mut ack_stream) =
+                Finalizer::with_timeout(shutdown.clone(), self.acknowledgements_timeout);
             let client = self.client.clone();
             let queue_url = self.queue_url.clone();
             tokio::spawn(
                 async move {
                     while let Some((status, receipts)) = ack_stream.next().await {
-                        if status == BatchStatus::Delivered {
-                            delete_messages(client.clone(), receipts, queue_url.clone()).await;
+                        match status {
+                            BatchStatus::Delivered => {
+                                delete_messages(client.clone(), receipts, queue_url.clone()).await;
+                            }
+                            // The messages are received again right away, instead of once their
+                            // visibility timeout expires, as the failure is transient.
+                            BatchStatus::Errored => {
+                                release_messages(client.clone(), receipts, queue_url.clone()).await;
+                            }
+                            BatchStatus::Rejected => {}
                         }
                     }
                 }
@@ -201,6 +215,28 @@ async fn delete_messages(client: SqsClient, receipts: Vec<String>, queue_url: St
     }
 }
 
+/// Makes the messages visible again, so that they are received again.
+async fn release_messages(client: SqsClient, receipts: Vec<String>, queue_url: String) {
+    if !receipts.is_empty() {
+        let mut batch = client
+            .change_message_visibility_batch()
+            .queue_url(queue_url);
+
+        for (id, receipt) in receipts.into_iter().enumerate() {
+            batch = batch.entries(
+                ChangeMessageVisibilityBatchRequestEntry::builder()
+                    .id(id.to_string())
+                    .receipt_handle(receipt)
+                    .visibility_timeout(0)
+                    .build(),
+            );
+        }
+        if let Err(err) = batch.send().await {
+            emit!(SqsMessageVisibilityError { error: &err });
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use chrono::SecondsFormat;
@@ -0,0 +1,100 @@
+use std::{collections::HashSet, time::Duration};
+
+use azure_storage_blobs::prelude::*;
+use futures::StreamExt;
+use tokio::time::timeout;
+
+use super::{queue, AzureBlobConfig, Strategy};
+use crate::{
+    config::{log_schema, SourceConfig, SourceContext},
+    sinks::azure_common::config::{build_blob_service_client, resolve_storage_account},
+    test_util::{
+        components::{assert_source_compliance, SOURCE_TAGS},
+        random_lines, random_string,
+    },
+    SourceSender,
+};
+
+fn connection_string() -> String {
+    let address = std::env::var("AZURE_ADDRESS").unwrap_or_else(|_| "localhost".into());
+    format!("UseDevelopmentStorage=true;DefaultEndpointsProtocol=http;AccountName=devstoreaccount1;AccountKey=Eby8vdM02xNOcqFlqUwJPLlmEtlCDXJ1OUzFT50uSRZ6IFsuFq2UVErCz4I6tq/K1SZFPTOtr/KBHBeksoGMGw==;BlobEndpoint=http://{}:10000/devstoreaccount1;QueueEndpoint=http://{}:10001/devstoreaccount1;TableEndpoint=http://{}:10002/devstoreaccount1;", address, address, address)
+}
+
+fn config(queue_name: &str) -> AzureBlobConfig {
+    AzureBlobConfig {
+        connection_string: Some(connection_string().into()),
+        strategy: Strategy::StorageQueue,
+        queue: Some(queue::Config {
+            queue_name: queue_name.to_owned(),
+            poll_secs: 1,
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn azure_blob_reads_blob_created_events() {
+    let container = format!("logs-{}", random_string(10).to_lowercase());
+    let queue_name = format!("events-{}", random_string(10).to_lowercase());
+    let config = config(&queue_name);
+
+    let account = resolve_storage_account(Some(connection_string()), None, &Default::default())
+        .expect("Failed to resolve storage account");
+    let queue_client = queue::build_queue_client(&account, &queue_name);
+    let container_client = build_blob_service_client(account).container_client(&container);
+
+    container_client.create().into_future().await.unwrap();
+    queue_client.create().into_future().await.unwrap();
+
+    let lines = random_lines(100).take(10).collect::<Vec<_>>();
+    container_client
+        .blob_client("app/out.log")
+        .put_block_blob(lines.join("\n"))
+        .content_type("text/plain")
+        .into_future()
+        .await
+        .unwrap();
+
+    let event = serde_json::json!([{
+        "id": random_string(10),
+        "eventType": "Microsoft.Storage.BlobCreated",
+        "subject": format!("/blobServices/default/containers/{}/blobs/app/out.log", container),
+        "eventTime": "2022-07-18T20:34:44.0000000Z",
+        "data": {
+            "contentType": "text/plain",
+            "blobType": "BlockBlob",
+        },
+    }]);
+    queue_client
+        .put_message(base64::encode(event.to_string()))
+        .into_future()
+        .await
+        .unwrap();
+
+    let count = lines.len();
+    let events = assert_source_compliance(&SOURCE_TAGS, async move {
+        let (tx, rx) = SourceSender::new_test();
+        let source = config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+        timeout(Duration::from_secs(30), rx.take(count).collect::<Vec<_>>())
+            .await
+            .unwrap()
+    })
+    .await;
+
+    let messages = events
+        .iter()
+        .map(|event| {
+            let log = event.as_log();
+            assert_eq!(log["container"], container.clone().into());
+            assert_eq!(log["blob"], "app/out.log".into());
+            assert_eq!(log["storage_account"], "devstoreaccount1".into());
+            log[log_schema().message_key()].to_string_lossy()
+        })
+        .collect::<HashSet<_>>();
+    assert_eq!(messages, lines.into_iter().collect());
+}
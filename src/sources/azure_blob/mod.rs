@@ -0,0 +1,302 @@
+use std::{convert::TryInto, io};
+
+use async_compression::tokio::bufread;
+use bytes::Bytes;
+use futures::{Stream, TryStreamExt};
+use snafu::Snafu;
+use tokio_util::io::StreamReader;
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use super::util::MultilineConfig;
+use crate::{
+    config::{AcknowledgementsConfig, DataType, Output, SourceConfig, SourceContext},
+    line_agg,
+    serde::bool_or_struct,
+    sinks::azure_common::config::{
+        build_blob_service_client, resolve_storage_account, AzureAuthentication,
+    },
+};
+
+#[cfg(all(test, feature = "azure-blob-integration-tests"))]
+mod integration_tests;
+pub mod queue;
+
+/// Compression scheme for blobs retrieved from Azure Blob Storage.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+#[derivative(Default)]
+pub enum Compression {
+    /// Automatically attempt to determine the compression scheme.
+    ///
+    /// Vector will try to determine the compression scheme of the blob from its: `Content-Encoding` and
+    /// `Content-Type` properties, as well as the blob name suffix (e.g. `.gz`).
+    ///
+    /// It will fallback to 'none' if the compression scheme cannot be determined.
+    #[derivative(Default)]
+    Auto,
+    /// Uncompressed.
+    None,
+    /// GZIP.
+    Gzip,
+    /// ZSTD.
+    Zstd,
+}
+
+/// Strategies for consuming blobs from Azure Blob Storage.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative)]
+#[serde(rename_all = "snake_case")]
+#[derivative(Default)]
+enum Strategy {
+    /// Consumes blobs by processing the `BlobCreated` events sent by [Azure Event Grid][event_grid] to an
+    /// [Azure Storage queue][storage_queue].
+    ///
+    /// [event_grid]: https://docs.microsoft.com/en-us/azure/storage/blobs/storage-blob-event-overview
+    /// [storage_queue]: https://docs.microsoft.com/en-us/azure/storage/queues/storage-queues-introduction
+    #[derivative(Default)]
+    StorageQueue,
+}
+
+/// Configuration for the `azure_blob` source.
+#[configurable_component(source("azure_blob"))]
+#[derive(Clone, Debug, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct AzureBlobConfig {
+    /// The Azure Blob Storage Account connection string.
+    ///
+    /// Authentication with access key is the only supported authentication method.
+    ///
+    /// Either `storage_account`, or this field, must be specified.
+    connection_string: Option<SensitiveString>,
+
+    /// The Azure Blob Storage Account name.
+    ///
+    /// Credentials are loaded according to `auth`.
+    ///
+    /// Either `connection_string`, or this field, must be specified.
+    storage_account: Option<String>,
+
+    /// The strategy used to authenticate with the storage account, when `storage_account` is set.
+    auth: AzureAuthentication,
+
+    /// The compression scheme used for decompressing blobs retrieved from Azure Blob Storage.
+    compression: Compression,
+
+    /// The strategy to use to consume blobs from Azure Blob Storage.
+    strategy: Strategy,
+
+    /// Configuration options for the Azure Storage queue.
+    ///
+    /// Only relevant when `strategy = "storage_queue"`.
+    queue: Option<queue::Config>,
+
+    /// Multiline aggregation configuration.
+    ///
+    /// If not specified, multiline aggregation is disabled.
+    multiline: Option<MultilineConfig>,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+impl_generate_config_from_default!(AzureBlobConfig);
+
+#[async_trait::async_trait]
+impl SourceConfig for AzureBlobConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let multiline_config: Option<line_agg::Config> = self
+            .multiline
+            .as_ref()
+            .map(|config| config.try_into())
+            .transpose()?;
+
+        match self.strategy {
+            Strategy::StorageQueue => Ok(Box::pin(
+                self.create_queue_ingestor(multiline_config)?
+                    .run(cx, self.acknowledgements),
+            )),
+        }
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+impl AzureBlobConfig {
+    fn create_queue_ingestor(
+        &self,
+        multiline: Option<line_agg::Config>,
+    ) -> crate::Result<queue::Ingestor> {
+        let config = self
+            .queue
+            .as_ref()
+            .ok_or(CreateQueueIngestorError::ConfigMissing)?;
+
+        let account = resolve_storage_account(
+            self.connection_string.as_ref().map(|v| v.to_string()),
+            self.storage_account.clone(),
+            &self.auth,
+        )?;
+        let queue_client = queue::build_queue_client(&account, &config.queue_name);
+        let account_name = account.name.clone();
+        let blob_client = build_blob_service_client(account);
+
+        Ok(queue::Ingestor::new(
+            account_name,
+            queue_client,
+            blob_client,
+            config.clone(),
+            self.compression,
+            multiline,
+        ))
+    }
+}
+
+#[derive(Debug, Snafu)]
+enum CreateQueueIngestorError {
+    #[snafu(display("Configuration for `queue` required when strategy=storage_queue"))]
+    ConfigMissing,
+}
+
+/// Decompresses the body of a blob, as chunks of bytes.
+fn blob_decoder(
+    compression: Compression,
+    blob_name: &str,
+    content_encoding: Option<&str>,
+    content_type: Option<&str>,
+    body: impl Stream<Item = io::Result<Bytes>> + Send + Unpin + 'static,
+) -> Box<dyn tokio::io::AsyncRead + Send + Unpin> {
+    let r = tokio::io::BufReader::new(StreamReader::new(
+        body.map_err(|error| io::Error::new(io::ErrorKind::Other, error)),
+    ));
+
+    let compression = match compression {
+        Compression::Auto => determine_compression(content_encoding, content_type, blob_name)
+            .unwrap_or(Compression::None),
+        _ => compression,
+    };
+
+    match compression {
+        Compression::Auto => unreachable!(), // is mapped above
+        Compression::None => Box::new(r),
+        Compression::Gzip => Box::new({
+            let mut decoder = bufread::GzipDecoder::new(r);
+            decoder.multiple_members(true);
+            decoder
+        }),
+        Compression::Zstd => Box::new({
+            let mut decoder = bufread::ZstdDecoder::new(r);
+            decoder.multiple_members(true);
+            decoder
+        }),
+    }
+}
+
+// try to determine the compression given the:
+// * content-encoding
+// * content-type
+// * blob name (for file extension)
+//
+// It will use this information in this order
+fn determine_compression(
+    content_encoding: Option<&str>,
+    content_type: Option<&str>,
+    blob_name: &str,
+) -> Option<Compression> {
+    content_encoding
+        .and_then(|content_encoding| match content_encoding {
+            "gzip" => Some(Compression::Gzip),
+            "zstd" => Some(Compression::Zstd),
+            _ => None,
+        })
+        .or_else(|| {
+            content_type.and_then(|content_type| match content_type {
+                "application/gzip" | "application/x-gzip" => Some(Compression::Gzip),
+                "application/zstd" => Some(Compression::Zstd),
+                _ => None,
+            })
+        })
+        .or_else(|| {
+            match std::path::Path::new(blob_name)
+                .extension()
+                .and_then(std::ffi::OsStr::to_str)
+            {
+                Some("gz") => Some(Compression::Gzip),
+                Some("zst") => Some(Compression::Zstd),
+                _ => None,
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::{write::GzEncoder, Compression as GzCompression};
+    use futures::stream;
+    use tokio::io::AsyncReadExt;
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AzureBlobConfig>();
+    }
+
+    #[test]
+    fn determines_compression() {
+        let cases = vec![
+            ("out.log", Some("gzip"), None, Some(Compression::Gzip)),
+            (
+                "out.log",
+                None,
+                Some("application/gzip"),
+                Some(Compression::Gzip),
+            ),
+            ("out.log.gz", None, None, Some(Compression::Gzip)),
+            ("out.log.zst", None, None, Some(Compression::Zstd)),
+            ("out.txt", None, None, None),
+        ];
+        for (blob_name, content_encoding, content_type, expected) in cases {
+            assert_eq!(
+                determine_compression(content_encoding, content_type, blob_name),
+                expected,
+                "blob_name={:?} content_encoding={:?} content_type={:?}",
+                blob_name,
+                content_encoding,
+                content_type,
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn decodes_gzip_blob_chunks() {
+        let mut encoder = GzEncoder::new(Vec::new(), GzCompression::default());
+        encoder.write_all(b"hello\nworld\n").unwrap();
+        let compressed = Bytes::from(encoder.finish().unwrap());
+        let chunks = vec![Ok(compressed.slice(..5)), Ok(compressed.slice(5..))];
+
+        let mut data = Vec::new();
+        blob_decoder(
+            Compression::Auto,
+            "logs/out.log.gz",
+            None,
+            None,
+            stream::iter(chunks),
+        )
+        .read_to_end(&mut data)
+        .await
+        .unwrap();
+
+        assert_eq!(data, b"hello\nworld\n");
+    }
+}
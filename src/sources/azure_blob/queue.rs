@@ -0,0 +1,587 @@
+use std::{future::ready, io, panic, sync::Arc, time::Duration};
+
+use azure_core::RetryOptions;
+use azure_storage::CloudLocation;
+use azure_storage_blobs::prelude::BlobServiceClient;
+use azure_storage_queues::{
+    operations::Message, PopReceipt, QueueClient, QueueServiceClientBuilder,
+};
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use codecs::{decoding::FramingError, CharacterDelimitedDecoder};
+use futures::{stream, Stream, StreamExt};
+use serde::Deserialize;
+use snafu::{ResultExt, Snafu};
+use tokio::{pin, select, time::sleep};
+use tokio_util::codec::FramedRead;
+use tracing::Instrument;
+use vector_common::internal_event::{
+    ByteSize, BytesReceived, InternalEventHandle as _, Protocol, Registered,
+};
+use vector_config::configurable_component;
+use vector_core::ByteSizeOf;
+
+use crate::{
+    config::{log_schema, AcknowledgementsConfig, SourceContext},
+    event::{BatchNotifier, BatchStatus, LogEvent},
+    internal_events::{
+        AzureBlobEventIgnored, AzureQueueMessageDeleteError, AzureQueueMessageProcessingError,
+        AzureQueueMessageReceiveError, AzureQueueMessageReceiveSucceeded, EventsReceived,
+        StreamClosedError,
+    },
+    line_agg::{self, LineAgg},
+    shutdown::ShutdownSignal,
+    sinks::azure_common::config::StorageAccount,
+    SourceSender,
+};
+use lookup::event_path;
+
+/// The largest number of messages that can be received from a queue at once.
+const MAX_MESSAGES: u8 = 32;
+
+const BLOB_CREATED_EVENT_TYPE: &str = "Microsoft.Storage.BlobCreated";
+
+/// Azure Storage queue configuration options.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub(super) struct Config {
+    /// The name of the Azure Storage queue to poll for Event Grid notifications.
+    ///
+    /// The queue must be in the storage account of the blobs.
+    pub(super) queue_name: String,
+
+    /// How long to wait before polling the queue again when it is empty, in seconds.
+    #[serde(default = "default_poll_secs")]
+    #[derivative(Default(value = "default_poll_secs()"))]
+    pub(super) poll_secs: u32,
+
+    /// The visibility timeout to use for messages, in seconds.
+    ///
+    /// This controls how long a message is left unavailable after Vector receives it. If Vector receives a message, and
+    /// takes longer than `visibility_timeout_secs` to process and delete the message from the queue, it will be made
+    /// reavailable for another consumer.
+    ///
+    /// This can happen if, for example, if Vector crashes between consuming a message and deleting it.
+    #[serde(default = "default_visibility_timeout_secs")]
+    #[derivative(Default(value = "default_visibility_timeout_secs()"))]
+    pub(super) visibility_timeout_secs: u32,
+
+    /// Whether to delete the message once Vector processes it.
+    ///
+    /// It can be useful to set this to `false` to debug or during initial Vector setup.
+    #[serde(default = "default_true")]
+    #[derivative(Default(value = "default_true()"))]
+    pub(super) delete_message: bool,
+
+    /// Number of concurrent tasks to create for polling the queue for messages.
+    ///
+    /// Defaults to the number of available CPUs on the system.
+    #[serde(default = "default_client_concurrency")]
+    #[derivative(Default(value = "default_client_concurrency()"))]
+    pub(super) client_concurrency: u32,
+}
+
+const fn default_poll_secs() -> u32 {
+    15
+}
+
+const fn default_visibility_timeout_secs() -> u32 {
+    300
+}
+
+const fn default_true() -> bool {
+    true
+}
+
+fn default_client_concurrency() -> u32 {
+    crate::num_threads() as u32
+}
+
+pub(super) fn build_queue_client(account: &StorageAccount, queue_name: &str) -> QueueClient {
+    let mut builder = QueueServiceClientBuilder::new(&account.name, account.credentials.clone())
+        .retry(RetryOptions::none());
+    if let Some(uri) = &account.queue_endpoint {
+        builder = builder.cloud_location(CloudLocation::Custom {
+            uri: uri.clone(),
+            credentials: account.credentials.clone(),
+        });
+    }
+    builder.build().queue_client(queue_name)
+}
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Snafu)]
+pub enum ProcessingError {
+    #[snafu(display(
+        "Could not parse Azure Queue message with id {} as Event Grid events: {}",
+        message_id,
+        source
+    ))]
+    InvalidQueueMessage {
+        source: serde_json::Error,
+        message_id: String,
+    },
+    #[snafu(display(
+        "Could not decode Azure Queue message with id {}: {}",
+        message_id,
+        source
+    ))]
+    InvalidQueueMessageEncoding {
+        source: base64::DecodeError,
+        message_id: String,
+    },
+    #[snafu(display("Event Grid event subject {:?} is not a blob", subject))]
+    InvalidSubject { subject: String },
+    #[snafu(display("Failed to fetch {}/{}: {}", container, blob, source))]
+    GetBlob {
+        source: azure_core::Error,
+        container: String,
+        blob: String,
+    },
+    #[snafu(display("Failed to read all of {}/{}: {}", container, blob, source))]
+    ReadBlob {
+        source: Box<dyn FramingError>,
+        container: String,
+        blob: String,
+    },
+    #[snafu(display("Failed to flush all of {}/{}: {}", container, blob, source))]
+    PipelineSend {
+        source: crate::source_sender::ClosedError,
+        container: String,
+        blob: String,
+    },
+    #[snafu(display("Sink reported an error sending events"))]
+    ErrorAcknowledgement,
+}
+
+pub struct State {
+    account_name: String,
+
+    queue_client: QueueClient,
+    blob_client: BlobServiceClient,
+
+    multiline: Option<line_agg::Config>,
+    compression: super::Compression,
+
+    poll_secs: u64,
+    client_concurrency: u32,
+    visibility_timeout_secs: u64,
+    delete_message: bool,
+}
+
+pub(super) struct Ingestor {
+    state: Arc<State>,
+}
+
+impl Ingestor {
+    pub(super) fn new(
+        account_name: String,
+        queue_client: QueueClient,
+        blob_client: BlobServiceClient,
+        config: Config,
+        compression: super::Compression,
+        multiline: Option<line_agg::Config>,
+    ) -> Ingestor {
+        let state = Arc::new(State {
+            account_name,
+
+            queue_client,
+            blob_client,
+
+            compression,
+            multiline,
+
+            poll_secs: config.poll_secs as u64,
+            client_concurrency: config.client_concurrency,
+            visibility_timeout_secs: config.visibility_timeout_secs as u64,
+            delete_message: config.delete_message,
+        });
+
+        Ingestor { state }
+    }
+
+    pub(super) async fn run(
+        self,
+        cx: SourceContext,
+        acknowledgements: AcknowledgementsConfig,
+    ) -> Result<(), ()> {
+        let acknowledgements = cx.do_acknowledgements(&acknowledgements);
+        let mut handles = Vec::new();
+        for _ in 0..self.state.client_concurrency {
+            let process = IngestorProcess::new(
+                Arc::clone(&self.state),
+                cx.out.clone(),
+                cx.shutdown.clone(),
+                acknowledgements,
+            );
+            let fut = process.run();
+            let handle = tokio::spawn(fut.in_current_span());
+            handles.push(handle);
+        }
+
+        // Wait for all of the processes to finish.  If any one of them panics, we resume
+        // that panic here to properly shutdown Vector.
+        for handle in handles.drain(..) {
+            if let Err(e) = handle.await {
+                if e.is_panic() {
+                    panic::resume_unwind(e.into_panic());
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub struct IngestorProcess {
+    state: Arc<State>,
+    out: SourceSender,
+    shutdown: ShutdownSignal,
+    acknowledgements: bool,
+    bytes_received: Registered<BytesReceived>,
+}
+
+impl IngestorProcess {
+    pub fn new(
+        state: Arc<State>,
+        out: SourceSender,
+        shutdown: ShutdownSignal,
+        acknowledgements: bool,
+    ) -> Self {
+        Self {
+            state,
+            out,
+            shutdown,
+            acknowledgements,
+            bytes_received: register!(BytesReceived::from(Protocol::HTTP)),
+        }
+    }
+
+    async fn run(mut self) {
+        let shutdown = self.shutdown.clone();
+        pin!(shutdown);
+
+        let poll_interval = Duration::from_secs(self.state.poll_secs);
+        loop {
+            select! {
+                _ = &mut shutdown => break,
+                received = self.run_once() => {
+                    // Storage queues don't support long polling, so we wait before polling an
+                    // empty queue again.
+                    if received == 0 {
+                        select! {
+                            _ = &mut shutdown => break,
+                            _ = sleep(poll_interval) => {},
+                        }
+                    }
+                },
+            }
+        }
+    }
+
+    /// Processes a batch of messages, returning the number of messages received.
+    async fn run_once(&mut self) -> usize {
+        let messages = match self
+            .state
+            .queue_client
+            .get_messages()
+            .number_of_messages(MAX_MESSAGES)
+            .visibility_timeout(Duration::from_secs(self.state.visibility_timeout_secs))
+            .into_future()
+            .await
+        {
+            Ok(response) => {
+                emit!(AzureQueueMessageReceiveSucceeded {
+                    count: response.messages.len(),
+                });
+                response.messages
+            }
+            Err(error) => {
+                emit!(AzureQueueMessageReceiveError { error: &error });
+                Vec::new()
+            }
+        };
+
+        let count = messages.len();
+        for message in messages {
+            let message_id = message.message_id.clone();
+            match self.handle_queue_message(&message).await {
+                Ok(()) => {
+                    // The message is only deleted once the events of its blobs are delivered, so
+                    // that it is received again, after its visibility timeout, otherwise.
+                    if self.state.delete_message {
+                        self.delete_message(message).await;
+                    }
+                }
+                Err(error) => {
+                    emit!(AzureQueueMessageProcessingError {
+                        message_id: &message_id,
+                        error: &error,
+                    });
+                }
+            }
+        }
+        count
+    }
+
+    async fn delete_message(&self, message: Message) {
+        let message_id = message.message_id.clone();
+        let pop_receipt = PopReceipt::new(message.message_id, message.pop_receipt);
+        if let Err(error) = self
+            .state
+            .queue_client
+            .pop_receipt_client(pop_receipt)
+            .delete()
+            .into_future()
+            .await
+        {
+            emit!(AzureQueueMessageDeleteError {
+                message_id: &message_id,
+                error: &error,
+            });
+        }
+    }
+
+    async fn handle_queue_message(&mut self, message: &Message) -> Result<(), ProcessingError> {
+        for event in parse_message(&message.message_id, &message.message_text)? {
+            if event.event_type != BLOB_CREATED_EVENT_TYPE {
+                emit!(AzureBlobEventIgnored {
+                    event_type: &event.event_type,
+                    subject: &event.subject,
+                });
+                continue;
+            }
+            self.handle_blob_created(event).await?;
+        }
+        Ok(())
+    }
+
+    async fn handle_blob_created(&mut self, event: BlobEvent) -> Result<(), ProcessingError> {
+        let (container, blob) =
+            parse_subject(&event.subject).ok_or_else(|| ProcessingError::InvalidSubject {
+                subject: event.subject.clone(),
+            })?;
+
+        let mut responses = self
+            .state
+            .blob_client
+            .container_client(container.clone())
+            .blob_client(blob.clone())
+            .get()
+            .into_stream();
+        let first = match responses.next().await {
+            Some(response) => response.context(GetBlobSnafu {
+                container: container.clone(),
+                blob: blob.clone(),
+            })?,
+            None => return Ok(()),
+        };
+
+        let properties = first.blob.properties.clone();
+        let timestamp = event.event_time.unwrap_or(properties.last_modified);
+
+        // The blob is downloaded in ranges, each of which is read as it is decompressed.
+        let body = stream::once(first.data.collect())
+            .chain(responses.then(|response| async move {
+                match response {
+                    Ok(response) => response.data.collect().await,
+                    Err(error) => Err(error),
+                }
+            }))
+            .map(|chunk| chunk.map_err(|error| io::Error::new(io::ErrorKind::Other, error)));
+
+        let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(self.acknowledgements);
+        let blob_reader = super::blob_decoder(
+            self.state.compression,
+            &blob,
+            properties.content_encoding.as_deref(),
+            Some(properties.content_type.as_str()),
+            Box::pin(body),
+        );
+
+        // Record the read error seen to propagate up later so we avoid deleting the queue
+        // message, as in the `aws_s3` source.
+        let mut read_error = None;
+        let bytes_received = self.bytes_received.clone();
+        let lines: Box<dyn Stream<Item = Bytes> + Send + Unpin> = Box::new(
+            FramedRead::new(blob_reader, CharacterDelimitedDecoder::new(b'\n'))
+                .map(|res| {
+                    res.map(|bytes| {
+                        bytes_received.emit(ByteSize(bytes.len()));
+                        bytes
+                    })
+                    .map_err(|err| {
+                        read_error = Some(err);
+                    })
+                    .ok()
+                })
+                .take_while(|res| ready(res.is_some()))
+                .map(|r| r.expect("validated by take_while")),
+        );
+
+        let lines = match &self.state.multiline {
+            Some(config) => Box::new(
+                LineAgg::new(
+                    lines.map(|line| ((), line, ())),
+                    line_agg::Logic::new(config.clone()),
+                )
+                .map(|(_src, line, _context)| line),
+            ),
+            None => lines,
+        };
+
+        let container_name = Bytes::from(container.clone());
+        let blob_name = Bytes::from(blob.clone());
+        let storage_account = Bytes::from(self.state.account_name.clone());
+
+        let mut stream = lines.filter_map(move |line| {
+            let mut log = LogEvent::from_bytes_legacy(&line).with_batch_notifier_option(&batch);
+
+            log.insert(event_path!("container"), container_name.clone());
+            log.insert(event_path!("blob"), blob_name.clone());
+            log.insert(event_path!("storage_account"), storage_account.clone());
+            log.insert(log_schema().source_type_key(), Bytes::from("azure_blob"));
+            log.insert(log_schema().timestamp_key(), timestamp);
+
+            emit!(EventsReceived {
+                count: 1,
+                byte_size: log.size_of()
+            });
+
+            ready(Some(log))
+        });
+
+        let send_error = match self.out.send_event_stream(&mut stream).await {
+            Ok(_) => None,
+            Err(error) => {
+                let (count, _) = stream.size_hint();
+                emit!(StreamClosedError { error, count });
+                Some(crate::source_sender::ClosedError)
+            }
+        };
+
+        // Up above, `lines` captures `read_error`, and eventually is captured by `stream`,
+        // so we explicitly drop it so that we can again utilize `read_error` below.
+        drop(stream);
+
+        if let Some(error) = read_error {
+            Err(ProcessingError::ReadBlob {
+                source: error,
+                container,
+                blob,
+            })
+        } else if let Some(error) = send_error {
+            Err(ProcessingError::PipelineSend {
+                source: error,
+                container,
+                blob,
+            })
+        } else {
+            match receiver {
+                None => Ok(()),
+                Some(receiver) => match receiver.await {
+                    BatchStatus::Delivered => Ok(()),
+                    BatchStatus::Errored => Err(ProcessingError::ErrorAcknowledgement),
+                    BatchStatus::Rejected => {
+                        // Sinks are responsible for emitting ComponentEventsDropped.
+                        // Failed events cannot be retried, so continue to delete the queue message.
+                        Ok(())
+                    }
+                },
+            }
+        }
+    }
+}
+
+/// An [Event Grid event][event_schema] of Azure Blob Storage.
+///
+/// [event_schema]: https://docs.microsoft.com/en-us/azure/event-grid/event-schema-blob-storage
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlobEvent {
+    pub id: String,
+    pub event_type: String,
+    pub subject: String,
+    pub event_time: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum BlobEvents {
+    One(BlobEvent),
+    Many(Vec<BlobEvent>),
+}
+
+/// Parses the Event Grid events of a queue message.
+///
+/// Event Grid base64-encodes the events it sends to storage queues, but messages holding the raw
+/// JSON of the events are accepted too.
+fn parse_message(message_id: &str, message_text: &str) -> Result<Vec<BlobEvent>, ProcessingError> {
+    let text = message_text.trim();
+    let body = if text.starts_with('{') || text.starts_with('[') {
+        text.as_bytes().to_vec()
+    } else {
+        base64::decode(text).context(InvalidQueueMessageEncodingSnafu { message_id })?
+    };
+
+    let events = serde_json::from_slice(&body).context(InvalidQueueMessageSnafu { message_id })?;
+    Ok(match events {
+        BlobEvents::One(event) => vec![event],
+        BlobEvents::Many(events) => events,
+    })
+}
+
+/// Gets the container and blob names from the subject of a blob event, such as
+/// `/blobServices/default/containers/logs/blobs/path/to/blob.log`.
+fn parse_subject(subject: &str) -> Option<(String, String)> {
+    let path = subject.strip_prefix("/blobServices/default/containers/")?;
+    let (container, blob) = path.split_once("/blobs/")?;
+    (!container.is_empty() && !blob.is_empty()).then(|| (container.to_owned(), blob.to_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_blob_subject() {
+        assert_eq!(
+            parse_subject("/blobServices/default/containers/logs/blobs/path/to/blob.log"),
+            Some(("logs".to_owned(), "path/to/blob.log".to_owned()))
+        );
+        assert_eq!(parse_subject("/blobServices/default/containers/logs"), None);
+        assert_eq!(parse_subject("/subscriptions/id"), None);
+    }
+
+    #[test]
+    fn parses_event_grid_events() {
+        let events = r#"{
+            "topic": "/subscriptions/id/resourceGroups/group/providers/Microsoft.Storage/storageAccounts/account",
+            "subject": "/blobServices/default/containers/logs/blobs/blob.log",
+            "eventType": "Microsoft.Storage.BlobCreated",
+            "id": "831e1650-001e-001b-66ab-eeb76e069631",
+            "data": {
+                "api": "PutBlockList",
+                "contentType": "text/plain",
+                "contentLength": 524288,
+                "blobType": "BlockBlob",
+                "url": "https://account.blob.core.windows.net/logs/blob.log"
+            },
+            "dataVersion": "",
+            "metadataVersion": "1",
+            "eventTime": "2022-07-18T20:34:44.0000000Z"
+        }"#;
+
+        for message_text in [events.to_owned(), base64::encode(events)] {
+            let events = parse_message("id", &message_text).unwrap();
+            assert_eq!(events.len(), 1);
+            assert_eq!(events[0].event_type, BLOB_CREATED_EVENT_TYPE);
+            assert_eq!(
+                events[0].subject,
+                "/blobServices/default/containers/logs/blobs/blob.log"
+            );
+        }
+    }
+}
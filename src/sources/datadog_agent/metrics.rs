@@ -1,4 +1,4 @@
-use std::{collections::BTreeMap, num::NonZeroU32, sync::Arc};
+use std::{num::NonZeroU32, sync::Arc};
 
 use bytes::Bytes;
 use chrono::{TimeZone, Utc};
@@ -9,10 +9,12 @@ use vector_core::{metrics::AgentDDSketch, ByteSizeOf};
 use warp::{filters::BoxedFilter, path, path::FullPath, reply::Response, Filter};
 
 use crate::{
-    common::datadog::{DatadogMetricType, DatadogSeriesMetric},
+    common::datadog::{
+        DatadogMetricMetadata, DatadogMetricOrigin, DatadogMetricType, DatadogSeriesMetric,
+    },
     config::log_schema,
     event::{
-        metric::{Metric, MetricValue},
+        metric::{Metric, MetricTags, MetricValue},
         Event, MetricKind,
     },
     internal_events::EventsReceived,
@@ -254,7 +256,7 @@ pub(crate) fn decode_ddseries_v2(
         .into_iter()
         .flat_map(|serie| {
             let (namespace, name) = namespace_name_from_dd_metric(&serie.metric);
-            let mut tags: BTreeMap<String, String> = serie
+            let mut tags: MetricTags = serie
                 .tags
                 .iter()
                 .map(|tag| {
@@ -267,17 +269,28 @@ pub(crate) fn decode_ddseries_v2(
                 // As per https://github.com/DataDog/datadog-agent/blob/a62ac9fb13e1e5060b89e731b8355b2b20a07c5b/pkg/serializer/internal/metrics/iterable_series.go#L180-L189
                 // the hostname can be found in MetricSeries::resources and that is the only value stored there.
                 if r.r#type.eq("host") {
-                    tags.insert(log_schema().host_key().to_string(), r.name);
+                    tags.insert(log_schema().interned_host_key().clone(), r.name);
                 } else {
                     // But to avoid losing information if this situation changes, any other resource type/name will be saved in the tags map
-                    tags.insert(format!("resource.{}", r.r#type), r.name);
+                    tags.insert(format!("resource.{}", r.r#type).into(), r.name);
                 }
             });
             (!serie.source_type_name.is_empty())
                 .then(|| tags.insert("source_type_name".into(), serie.source_type_name));
-            // As per https://github.com/DataDog/datadog-agent/blob/a62ac9fb13e1e5060b89e731b8355b2b20a07c5b/pkg/serializer/internal/metrics/iterable_series.go#L224
-            // serie.unit is omitted
-            match metric_payload::MetricType::from_i32(serie.r#type) {
+            // The unit and the origin of the serie have no counterpart in the Vector metric model, so
+            // they are kept in the metadata of the metrics for the `datadog_metrics` sink.
+            let dd_metadata = DatadogMetricMetadata {
+                unit: Some(serie.unit).filter(|unit| !unit.is_empty()),
+                origin: serie
+                    .metadata
+                    .and_then(|metadata| metadata.origin)
+                    .map(|origin| DatadogMetricOrigin {
+                        product: origin.origin_product,
+                        category: origin.origin_category,
+                        service: origin.origin_service,
+                    }),
+            };
+            let mut metrics = match metric_payload::MetricType::from_i32(serie.r#type) {
                 Some(metric_payload::MetricType::Count) => serie
                     .points
                     .iter()
@@ -306,6 +319,13 @@ pub(crate) fn decode_ddseries_v2(
                             },
                         )
                         .with_timestamp(Some(Utc.timestamp(dd_point.timestamp, 0)))
+                        // serie.interval is in seconds, convert to ms
+                        .with_interval_ms(
+                            u32::try_from(serie.interval)
+                                .ok()
+                                .and_then(|i| i.checked_mul(1000))
+                                .and_then(NonZeroU32::new),
+                        )
                         .with_tags(Some(tags.clone()))
                         .with_namespace(namespace)
                     })
@@ -336,7 +356,13 @@ pub(crate) fn decode_ddseries_v2(
                     warn!("Unspecified metric type ({}).", serie.r#type);
                     Vec::new()
                 }
+            };
+            if dd_metadata != DatadogMetricMetadata::default() {
+                for metric in &mut metrics {
+                    dd_metadata.clone().insert_into(metric.metadata_mut());
+                }
             }
+            metrics
         })
         .map(|mut metric| {
             if let Some(k) = &api_key {
@@ -398,7 +424,7 @@ fn into_vector_metric(
     api_key: Option<Arc<str>>,
     schema_definition: &Arc<schema::Definition>,
 ) -> Vec<Event> {
-    let mut tags: BTreeMap<String, String> = dd_metric
+    let mut tags: MetricTags = dd_metric
         .tags
         .unwrap_or_default()
         .iter()
@@ -410,7 +436,7 @@ fn into_vector_metric(
 
     dd_metric
         .host
-        .and_then(|host| tags.insert(log_schema().host_key().to_owned(), host));
+        .and_then(|host| tags.insert(log_schema().interned_host_key().clone(), host));
     dd_metric
         .source_type_name
         .and_then(|source| tags.insert("source_type_name".into(), source));
@@ -508,7 +534,7 @@ pub(crate) fn decode_ddsketch(
         .into_iter()
         .flat_map(|sketch_series| {
             // sketch_series.distributions is also always empty from payload coming from dd agents
-            let mut tags: BTreeMap<String, String> = sketch_series
+            let mut tags: MetricTags = sketch_series
                 .tags
                 .iter()
                 .map(|tag| {
@@ -518,7 +544,7 @@ pub(crate) fn decode_ddsketch(
                 .collect();
 
             tags.insert(
-                log_schema().host_key().to_string(),
+                log_schema().interned_host_key().clone(),
                 sketch_series.host.clone(),
             );
             sketch_series.dogsketches.into_iter().map(move |sketch| {
@@ -25,7 +25,10 @@ use vrl::prelude::Collection;
 
 use crate::schema::Definition;
 use crate::{
-    common::datadog::{DatadogMetricType, DatadogPoint, DatadogSeriesMetric},
+    common::datadog::{
+        DatadogMetricMetadata, DatadogMetricOrigin, DatadogMetricType, DatadogPoint,
+        DatadogSeriesMetric,
+    },
     config::{log_schema, SourceConfig, SourceContext},
     event::{
         into_event_stream,
@@ -1388,7 +1391,9 @@ fn test_config_outputs() {
         (
             "json / single output",
             TestCase {
-                decoding: DeserializerConfig::Json,
+                decoding: DeserializerConfig::Json {
+                    json: Default::default(),
+                },
                 multiple_outputs: false,
                 want: HashMap::from([(
                     None,
@@ -1410,7 +1415,9 @@ fn test_config_outputs() {
         (
             "json / multiple output",
             TestCase {
-                decoding: DeserializerConfig::Json,
+                decoding: DeserializerConfig::Json {
+                    json: Default::default(),
+                },
                 multiple_outputs: true,
                 want: HashMap::from([
                     (
@@ -1605,9 +1612,16 @@ async fn decode_series_endpoint_v2() {
                     },
                 ],
                 r#type: ddmetric_proto::metric_payload::MetricType::Gauge as i32,
-                unit: "".to_string(),
+                unit: "byte".to_string(),
                 source_type_name: "a_random_source_type_name".to_string(),
                 interval: 0,
+                metadata: Some(ddmetric_proto::Metadata {
+                    origin: Some(ddmetric_proto::Origin {
+                        origin_product: 10,
+                        origin_category: 11,
+                        origin_service: 12,
+                    }),
+                }),
             },
             ddmetric_proto::metric_payload::MetricSeries {
                 resources: vec![ddmetric_proto::metric_payload::Resource {
@@ -1624,6 +1638,7 @@ async fn decode_series_endpoint_v2() {
                 unit: "".to_string(),
                 source_type_name: "another_random_source_type_name".to_string(),
                 interval: 10,
+                metadata: None,
             },
             ddmetric_proto::metric_payload::MetricSeries {
                 resources: vec![ddmetric_proto::metric_payload::Resource {
@@ -1640,6 +1655,7 @@ async fn decode_series_endpoint_v2() {
                 unit: "".to_string(),
                 source_type_name: "a_very_random_source_type_name".to_string(),
                 interval: 0,
+                metadata: None,
             },
         ];
 
@@ -1687,6 +1703,17 @@ async fn decode_series_endpoint_v2() {
                 &events[0].metadata().datadog_api_key().as_ref().unwrap()[..],
                 "12345678abcdefgh12345678abcdefgh"
             );
+            assert_eq!(
+                DatadogMetricMetadata::from_event_metadata(events[0].metadata()),
+                DatadogMetricMetadata {
+                    unit: Some("byte".to_string()),
+                    origin: Some(DatadogMetricOrigin {
+                        product: 10,
+                        category: 11,
+                        service: 12,
+                    }),
+                }
+            );
 
             metric = events[1].as_metric();
             assert_eq!(metric.name(), "dd_gauge");
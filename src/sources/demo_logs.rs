@@ -5,13 +5,13 @@ use codecs::{
 };
 use fakedata::logs::*;
 use futures::StreamExt;
-use rand::seq::SliceRandom;
+use rand::{rngs::SmallRng, seq::SliceRandom, SeedableRng};
 use snafu::Snafu;
-use std::task::Poll;
-use tokio::time::{self, Duration};
+use std::{fmt, task::Poll};
+use tokio::time::{self, Duration, Instant};
 use tokio_util::codec::FramedRead;
 use vector_common::internal_event::{ByteSize, BytesReceived, InternalEventHandle as _, Protocol};
-use vector_config::configurable_component;
+use vector_config::{configurable_component, ConfigurableString};
 use vector_core::config::LogNamespace;
 use vector_core::ByteSizeOf;
 
@@ -38,6 +38,9 @@ pub struct DemoLogsConfig {
     #[derivative(Default(value = "default_interval()"))]
     pub interval: f64,
 
+    #[configurable(derived)]
+    pub rate: Option<Rate>,
+
     /// The total number of lines to output.
     ///
     /// By default, the source continuously prints logs (infinitely).
@@ -47,6 +50,12 @@ pub struct DemoLogsConfig {
     #[serde(flatten)]
     pub format: OutputFormat,
 
+    /// The seed of the random generation of the output lines.
+    ///
+    /// With a seed, the source outputs the same lines each time it runs, but for their timestamps, which are those of
+    /// the current time. By default, the lines differ from one run to the next.
+    pub seed: Option<u64>,
+
     #[configurable(derived)]
     #[derivative(Default(value = "default_framing_message_based()"))]
     pub framing: FramingConfig,
@@ -74,6 +83,119 @@ pub enum DemoLogsConfigError {
     ShuffleDemoLogsItemsEmpty,
 }
 
+/// The rate of the output lines, in lines per second, which takes precedence over `interval`.
+///
+/// This is either a constant rate, such as `"1000"`, or a rate ramping up, or down, linearly over a duration before it
+/// stays at its final value, such as `"linear:100..10000/60s"`. The duration is in milliseconds (`ms`), seconds (`s`),
+/// minutes (`m`) or hours (`h`).
+#[configurable_component]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[serde(try_from = "String", into = "String")]
+pub struct Rate(RateKind);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum RateKind {
+    Constant(f64),
+    Linear {
+        start: f64,
+        end: f64,
+        duration: Duration,
+    },
+}
+
+impl Rate {
+    /// The rate, in lines per second, once the given time has elapsed since the source started.
+    fn at(&self, elapsed: Duration) -> f64 {
+        match self.0 {
+            RateKind::Constant(rate) => rate,
+            RateKind::Linear {
+                start,
+                end,
+                duration,
+            } => {
+                if elapsed >= duration {
+                    end
+                } else {
+                    start + (end - start) * elapsed.as_secs_f64() / duration.as_secs_f64()
+                }
+            }
+        }
+    }
+}
+
+fn parse_rate_value(value: &str) -> Option<f64> {
+    value
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|rate| rate.is_finite() && *rate > 0.0)
+}
+
+fn parse_rate_duration(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    let (number, unit_secs) = if let Some(number) = value.strip_suffix("ms") {
+        (number, 0.001)
+    } else if let Some(number) = value.strip_suffix('s') {
+        (number, 1.0)
+    } else if let Some(number) = value.strip_suffix('m') {
+        (number, 60.0)
+    } else if let Some(number) = value.strip_suffix('h') {
+        (number, 3600.0)
+    } else {
+        return None;
+    };
+    number
+        .parse::<f64>()
+        .ok()
+        .filter(|number| number.is_finite() && *number > 0.0)
+        .map(|number| Duration::from_secs_f64(number * unit_secs))
+}
+
+impl TryFrom<String> for Rate {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let kind = match value.strip_prefix("linear:") {
+            None => parse_rate_value(&value).map(RateKind::Constant),
+            Some(ramp) => ramp.split_once('/').and_then(|(rates, duration)| {
+                let (start, end) = rates.split_once("..")?;
+                Some(RateKind::Linear {
+                    start: parse_rate_value(start)?,
+                    end: parse_rate_value(end)?,
+                    duration: parse_rate_duration(duration)?,
+                })
+            }),
+        };
+        kind.map(Self).ok_or_else(|| {
+            format!(
+                "invalid rate `{}`, expected a positive number of lines per second, or a ramp such as `linear:100..10000/60s`",
+                value
+            )
+        })
+    }
+}
+
+impl From<Rate> for String {
+    fn from(rate: Rate) -> Self {
+        rate.to_string()
+    }
+}
+
+impl fmt::Display for Rate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            RateKind::Constant(rate) => write!(f, "{}", rate),
+            RateKind::Linear {
+                start,
+                end,
+                duration,
+            } => write!(f, "linear:{}..{}/{}s", start, end, duration.as_secs_f64()),
+        }
+    }
+}
+
+impl ConfigurableString for Rate {}
+
 /// Output format configuration.
 #[configurable_component]
 #[derive(Clone, Debug, Derivative)]
@@ -106,28 +228,45 @@ pub enum OutputFormat {
     /// Randomly generated HTTP server logs in [JSON](\(urls.json)) format.
     #[derivative(Default)]
     Json,
+
+    /// Randomly generated [Kubernetes audit](\(urls.kubernetes_auditing)) events, in JSON format.
+    KubernetesAudit,
+
+    /// Randomly generated access logs of the [NGINX Ingress Controller](\(urls.nginx_ingress_log_format)), in its
+    /// default format.
+    NginxIngress,
+
+    /// Randomly generated [AWS Application Load Balancer](\(urls.aws_elb_access_format)) access logs.
+    AwsAlb,
+
+    /// Randomly generated Windows event log events, in JSON format.
+    WindowsEvent,
 }
 
 impl OutputFormat {
-    fn generate_line(&self, n: usize) -> String {
+    fn generate_line(&self, n: usize, rng: &mut SmallRng) -> String {
         emit!(DemoLogsEventProcessed);
 
         match self {
             Self::Shuffle {
                 sequence,
                 ref lines,
-            } => Self::shuffle_generate(*sequence, lines, n),
-            Self::ApacheCommon => apache_common_log_line(),
-            Self::ApacheError => apache_error_log_line(),
-            Self::Syslog => syslog_5424_log_line(),
-            Self::BsdSyslog => syslog_3164_log_line(),
-            Self::Json => json_log_line(),
+            } => Self::shuffle_generate(*sequence, lines, n, rng),
+            Self::ApacheCommon => apache_common_log_line(rng),
+            Self::ApacheError => apache_error_log_line(rng),
+            Self::Syslog => syslog_5424_log_line(rng),
+            Self::BsdSyslog => syslog_3164_log_line(rng),
+            Self::Json => json_log_line(rng),
+            Self::KubernetesAudit => kubernetes_audit_log_line(rng),
+            Self::NginxIngress => nginx_ingress_log_line(rng),
+            Self::AwsAlb => aws_alb_log_line(rng),
+            Self::WindowsEvent => windows_event_log_line(rng),
         }
     }
 
-    fn shuffle_generate(sequence: bool, lines: &[String], n: usize) -> String {
+    fn shuffle_generate(sequence: bool, lines: &[String], n: usize, rng: &mut SmallRng) -> String {
         // unwrap can be called here because `lines` can't be empty
-        let line = lines.choose(&mut rand::thread_rng()).unwrap();
+        let line = lines.choose(rng).unwrap();
 
         if sequence {
             format!("{} {}", n, line)
@@ -162,10 +301,12 @@ impl DemoLogsConfig {
         Self {
             count,
             interval,
+            rate: None,
             format: OutputFormat::Shuffle {
                 lines,
                 sequence: false,
             },
+            seed: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             log_namespace,
@@ -173,19 +314,33 @@ impl DemoLogsConfig {
     }
 }
 
+fn rng(seed: Option<u64>) -> SmallRng {
+    match seed {
+        Some(seed) => SmallRng::seed_from_u64(seed),
+        None => SmallRng::from_entropy(),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 async fn demo_logs_source(
     interval: f64,
+    rate: Option<Rate>,
     count: usize,
     format: OutputFormat,
+    mut rng: SmallRng,
     decoder: Decoder,
     mut shutdown: ShutdownSignal,
     mut out: SourceSender,
     log_namespace: LogNamespace,
 ) -> Result<(), ()> {
-    let maybe_interval: Option<f64> = (interval != 0.0).then_some(interval);
+    let maybe_interval: Option<f64> = (interval != 0.0 && rate.is_none()).then_some(interval);
 
     let mut interval = maybe_interval.map(|i| time::interval(Duration::from_secs_f64(i)));
 
+    // The lines are paced from the start, so that the rate holds even when some are late.
+    let start = Instant::now();
+    let mut next_line = start;
+
     let bytes_received = register!(BytesReceived::from(Protocol::NONE));
 
     for n in 0..count {
@@ -196,9 +351,13 @@ async fn demo_logs_source(
         if let Some(interval) = &mut interval {
             interval.tick().await;
         }
+        if let Some(rate) = &rate {
+            time::sleep_until(next_line).await;
+            next_line += Duration::from_secs_f64(1.0 / rate.at(next_line - start));
+        }
         bytes_received.emit(ByteSize(0));
 
-        let line = format.generate_line(n);
+        let line = format.generate_line(n, &mut rng);
 
         let mut stream = FramedRead::new(line.as_bytes(), decoder.clone());
         while let Some(next) = stream.next().await {
@@ -258,8 +417,10 @@ impl SourceConfig for DemoLogsConfig {
             DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace).build();
         Ok(Box::pin(demo_logs_source(
             self.interval,
+            self.rate,
             self.count,
             self.format.clone(),
+            rng(self.seed),
             decoder,
             cx.shutdown,
             cx.out,
@@ -283,6 +444,10 @@ impl SourceConfig for DemoLogsConfig {
     fn can_acknowledge(&self) -> bool {
         false
     }
+
+    fn can_run_once(&self) -> bool {
+        self.count < default_count()
+    }
 }
 
 #[cfg(test)]
@@ -318,8 +483,10 @@ mod tests {
         assert_source_compliance(&SOURCE_TAGS, async {
             demo_logs_source(
                 config.interval,
+                config.rate,
                 config.count,
                 config.format,
+                rng(config.seed),
                 decoder,
                 ShutdownSignal::noop(),
                 tx,
@@ -434,6 +601,66 @@ mod tests {
         assert!(duration >= Duration::from_secs(2));
     }
 
+    #[tokio::test]
+    async fn shuffle_demo_logs_obeys_rate() {
+        let start = Instant::now();
+        let mut rx = runit(
+            r#"format = "shuffle"
+               lines = ["one", "two"]
+               count = 3
+               rate = "2""#,
+        )
+        .await;
+
+        for _ in 0..3 {
+            assert!(poll!(rx.next()).is_ready());
+        }
+        assert_eq!(poll!(rx.next()), Poll::Ready(None));
+
+        let duration = start.elapsed();
+        assert!(duration >= Duration::from_secs(1));
+    }
+
+    #[test]
+    fn seeded_demo_logs_replay_lines() {
+        let format = OutputFormat::Shuffle {
+            sequence: false,
+            lines: vec!["one".into(), "two".into(), "three".into(), "four".into()],
+        };
+        let lines = |seed| {
+            let mut rng = rng(Some(seed));
+            (0..20)
+                .map(|n| format.generate_line(n, &mut rng))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(lines(42), lines(42));
+        assert_ne!(lines(42), lines(7));
+    }
+
+    #[test]
+    fn parses_rates() {
+        let rate = |value: &str| Rate::try_from(value.to_owned());
+
+        let constant = rate("1000").unwrap();
+        assert_eq!(constant.at(Duration::ZERO), 1000.0);
+        assert_eq!(constant.at(Duration::from_secs(3600)), 1000.0);
+
+        let linear = rate("linear:100..10000/60s").unwrap();
+        assert_eq!(linear.at(Duration::ZERO), 100.0);
+        assert_eq!(linear.at(Duration::from_secs(30)), 5050.0);
+        assert_eq!(linear.at(Duration::from_secs(60)), 10000.0);
+        assert_eq!(linear.at(Duration::from_secs(120)), 10000.0);
+        assert_eq!(rate(&linear.to_string()).unwrap(), linear);
+
+        let down = rate("linear:1000..10/2m").unwrap();
+        assert_eq!(down.at(Duration::from_secs(60)), 505.0);
+
+        for invalid in ["", "0", "-5", "fast", "linear:100..10000", "linear:100/60s"] {
+            assert!(rate(invalid).is_err(), "{} should be invalid", invalid);
+        }
+    }
+
     #[tokio::test]
     async fn apache_common_format_generates_output() {
         let mut rx = runit(
@@ -510,4 +737,76 @@ mod tests {
         }
         assert_eq!(poll!(rx.next()), Poll::Ready(None));
     }
+
+    #[tokio::test]
+    async fn kubernetes_audit_format_generates_output() {
+        let message_key = log_schema().message_key();
+        let mut rx = runit(
+            r#"format = "kubernetes_audit"
+            count = 5"#,
+        )
+        .await;
+
+        for _ in 0..5 {
+            let event = match poll!(rx.next()) {
+                Poll::Ready(event) => event.unwrap(),
+                _ => unreachable!(),
+            };
+            let log = event.as_log();
+            let message = log[&message_key].to_string_lossy();
+            let audit = serde_json::from_str::<serde_json::Value>(&message).unwrap();
+            assert_eq!(audit["apiVersion"], "audit.k8s.io/v1");
+        }
+        assert_eq!(poll!(rx.next()), Poll::Ready(None));
+    }
+
+    #[tokio::test]
+    async fn nginx_ingress_format_generates_output() {
+        let mut rx = runit(
+            r#"format = "nginx_ingress"
+            count = 5"#,
+        )
+        .await;
+
+        for _ in 0..5 {
+            assert!(poll!(rx.next()).is_ready());
+        }
+        assert_eq!(poll!(rx.next()), Poll::Ready(None));
+    }
+
+    #[tokio::test]
+    async fn aws_alb_format_generates_output() {
+        let mut rx = runit(
+            r#"format = "aws_alb"
+            count = 5"#,
+        )
+        .await;
+
+        for _ in 0..5 {
+            assert!(poll!(rx.next()).is_ready());
+        }
+        assert_eq!(poll!(rx.next()), Poll::Ready(None));
+    }
+
+    #[tokio::test]
+    async fn windows_event_format_generates_output() {
+        let message_key = log_schema().message_key();
+        let mut rx = runit(
+            r#"format = "windows_event"
+            count = 5"#,
+        )
+        .await;
+
+        for _ in 0..5 {
+            let event = match poll!(rx.next()) {
+                Poll::Ready(event) => event.unwrap(),
+                _ => unreachable!(),
+            };
+            let log = event.as_log();
+            let message = log[&message_key].to_string_lossy();
+            let event = serde_json::from_str::<serde_json::Value>(&message).unwrap();
+            assert!(event["EventID"].is_u64());
+        }
+        assert_eq!(poll!(rx.next()), Poll::Ready(None));
+    }
 }
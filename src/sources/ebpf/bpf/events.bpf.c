@@ -0,0 +1,255 @@
+// SPDX-License-Identifier: GPL-2.0
+//
+// The BPF programs of the `ebpf` source, which send process and TCP connection events to user
+// space through the `EVENTS` perf event array.
+//
+// The tracepoint arguments follow the stable formats of their tracepoints, while the fields of
+// `task_struct` are relocated with CO-RE, so the object runs on any kernel with BTF.
+
+#include <linux/bpf.h>
+#include <linux/types.h>
+#include <bpf/bpf_core_read.h>
+#include <bpf/bpf_helpers.h>
+
+#define TASK_COMM_LEN 16
+#define FILENAME_LEN 256
+
+#define AF_INET 2
+#define AF_INET6 10
+#define IPPROTO_TCP 6
+
+#define TCP_ESTABLISHED 1
+#define TCP_SYN_SENT 2
+#define TCP_SYN_RECV 3
+#define TCP_CLOSE 7
+
+// Kept in sync with `EventKind` of `mod.rs`.
+#define EVENT_PROCESS_EXEC 1
+#define EVENT_PROCESS_EXIT 2
+#define EVENT_TCP_CONNECT 3
+#define EVENT_TCP_ACCEPT 4
+
+// Kept in sync with `RawEvent::parse` of `mod.rs`.
+struct event {
+	__u32 kind;
+	__u32 pid;
+	__u32 ppid;
+	__u32 uid;
+	__u32 gid;
+	__s32 exit_code;
+	char comm[TASK_COMM_LEN];
+	char filename[FILENAME_LEN];
+	__u16 family;
+	__u16 sport;
+	__u16 dport;
+	__u16 padding;
+	__u8 saddr[16];
+	__u8 daddr[16];
+};
+
+struct task_struct {
+	int tgid;
+	int exit_code;
+	struct task_struct *real_parent;
+} __attribute__((preserve_access_index));
+
+struct trace_entry {
+	unsigned short type;
+	unsigned char flags;
+	unsigned char preempt_count;
+	int pid;
+};
+
+struct sched_process_exec_args {
+	struct trace_entry entry;
+	int filename_loc;
+	int pid;
+	int old_pid;
+};
+
+struct sched_process_exit_args {
+	struct trace_entry entry;
+	char comm[TASK_COMM_LEN];
+	int pid;
+	int prio;
+};
+
+struct inet_sock_set_state_args {
+	struct trace_entry entry;
+	const void *skaddr;
+	int oldstate;
+	int newstate;
+	__u16 sport;
+	__u16 dport;
+	__u16 family;
+	__u16 protocol;
+	__u8 saddr[4];
+	__u8 daddr[4];
+	__u8 saddr_v6[16];
+	__u8 daddr_v6[16];
+};
+
+// The process that initiated each connection, as the connection is established outside of it.
+struct connector {
+	__u32 pid;
+	__u32 ppid;
+	__u32 uid;
+	__u32 gid;
+	char comm[TASK_COMM_LEN];
+};
+
+struct {
+	__uint(type, BPF_MAP_TYPE_PERF_EVENT_ARRAY);
+	__uint(key_size, sizeof(__u32));
+	__uint(value_size, sizeof(__u32));
+} EVENTS SEC(".maps");
+
+struct {
+	__uint(type, BPF_MAP_TYPE_HASH);
+	__uint(max_entries, 65536);
+	__type(key, const void *);
+	__type(value, struct connector);
+} CONNECTORS SEC(".maps");
+
+// Events are too large for the stack of some kernels, so they are built in a per-CPU slot.
+struct {
+	__uint(type, BPF_MAP_TYPE_PERCPU_ARRAY);
+	__uint(max_entries, 1);
+	__type(key, __u32);
+	__type(value, struct event);
+} SCRATCH SEC(".maps");
+
+static __always_inline struct event *new_event(__u32 kind)
+{
+	__u32 zero = 0;
+	struct event *event = bpf_map_lookup_elem(&SCRATCH, &zero);
+	if (!event)
+		return 0;
+
+	__builtin_memset(event, 0, sizeof(*event));
+	event->kind = kind;
+	return event;
+}
+
+static __always_inline void fill_process(struct event *event)
+{
+	struct task_struct *task = (struct task_struct *)bpf_get_current_task();
+	__u64 uid_gid = bpf_get_current_uid_gid();
+
+	event->pid = bpf_get_current_pid_tgid() >> 32;
+	event->ppid = BPF_CORE_READ(task, real_parent, tgid);
+	event->uid = uid_gid;
+	event->gid = uid_gid >> 32;
+	bpf_get_current_comm(&event->comm, sizeof(event->comm));
+}
+
+SEC("tracepoint/sched/sched_process_exec")
+int process_exec(struct sched_process_exec_args *args)
+{
+	struct event *event = new_event(EVENT_PROCESS_EXEC);
+	if (!event)
+		return 0;
+
+	fill_process(event);
+	bpf_probe_read_kernel_str(&event->filename, sizeof(event->filename),
+				  (void *)args + (args->filename_loc & 0xffff));
+
+	bpf_perf_event_output(args, &EVENTS, BPF_F_CURRENT_CPU, event, sizeof(*event));
+	return 0;
+}
+
+SEC("tracepoint/sched/sched_process_exit")
+int process_exit(struct sched_process_exit_args *args)
+{
+	__u64 pid_tgid = bpf_get_current_pid_tgid();
+
+	// Only the exit of the last thread is the exit of the process.
+	if ((__u32)pid_tgid != pid_tgid >> 32)
+		return 0;
+
+	struct event *event = new_event(EVENT_PROCESS_EXIT);
+	if (!event)
+		return 0;
+
+	struct task_struct *task = (struct task_struct *)bpf_get_current_task();
+	fill_process(event);
+	event->exit_code = BPF_CORE_READ(task, exit_code);
+
+	bpf_perf_event_output(args, &EVENTS, BPF_F_CURRENT_CPU, event, sizeof(*event));
+	return 0;
+}
+
+SEC("tracepoint/sock/inet_sock_set_state")
+int inet_sock_set_state(struct inet_sock_set_state_args *args)
+{
+	if (args->protocol != IPPROTO_TCP)
+		return 0;
+
+	const void *sk = args->skaddr;
+
+	if (args->newstate == TCP_SYN_SENT) {
+		struct event *event = new_event(0);
+		if (!event)
+			return 0;
+
+		fill_process(event);
+		struct connector connector = {
+			.pid = event->pid,
+			.ppid = event->ppid,
+			.uid = event->uid,
+			.gid = event->gid,
+		};
+		__builtin_memcpy(connector.comm, event->comm, sizeof(connector.comm));
+		bpf_map_update_elem(&CONNECTORS, &sk, &connector, BPF_ANY);
+		return 0;
+	}
+
+	if (args->newstate == TCP_CLOSE) {
+		bpf_map_delete_elem(&CONNECTORS, &sk);
+		return 0;
+	}
+
+	if (args->newstate != TCP_ESTABLISHED)
+		return 0;
+
+	struct event *event;
+	if (args->oldstate == TCP_SYN_SENT) {
+		struct connector *connector = bpf_map_lookup_elem(&CONNECTORS, &sk);
+		event = new_event(EVENT_TCP_CONNECT);
+		if (!event)
+			return 0;
+
+		if (connector) {
+			event->pid = connector->pid;
+			event->ppid = connector->ppid;
+			event->uid = connector->uid;
+			event->gid = connector->gid;
+			__builtin_memcpy(event->comm, connector->comm, sizeof(event->comm));
+		}
+		bpf_map_delete_elem(&CONNECTORS, &sk);
+	} else if (args->oldstate == TCP_SYN_RECV) {
+		// Connections are accepted by the kernel before any process accepts them, so they
+		// have no process.
+		event = new_event(EVENT_TCP_ACCEPT);
+		if (!event)
+			return 0;
+	} else {
+		return 0;
+	}
+
+	event->family = args->family;
+	event->sport = args->sport;
+	event->dport = args->dport;
+	if (args->family == AF_INET) {
+		__builtin_memcpy(event->saddr, args->saddr, 4);
+		__builtin_memcpy(event->daddr, args->daddr, 4);
+	} else if (args->family == AF_INET6) {
+		__builtin_memcpy(event->saddr, args->saddr_v6, 16);
+		__builtin_memcpy(event->daddr, args->daddr_v6, 16);
+	}
+
+	bpf_perf_event_output(args, &EVENTS, BPF_F_CURRENT_CPU, event, sizeof(*event));
+	return 0;
+}
+
+char LICENSE[] SEC("license") = "GPL";
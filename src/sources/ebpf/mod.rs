@@ -0,0 +1,480 @@
+use std::{
+    io,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+};
+
+use aya::{
+    maps::{
+        perf::{AsyncPerfEventArray, Events, PerfBufferError},
+        MapError,
+    },
+    programs::{ProgramError, TracePoint},
+    util::online_cpus,
+    Bpf, BpfError,
+};
+use bytes::{Bytes, BytesMut};
+use chrono::Utc;
+use futures::{stream, StreamExt};
+use snafu::{ResultExt, Snafu};
+use vector_config::configurable_component;
+use vector_core::{config::LogNamespace, ByteSizeOf};
+
+use crate::{
+    config::{log_schema, DataType, GenerateConfig, Output, SourceConfig, SourceContext},
+    event::{Event, LogEvent},
+    internal_events::{EbpfEventsLost, EbpfReadError, EventsReceived, StreamClosedError},
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+/// The BPF object, compiled from `bpf/events.bpf.c` by the build script.
+static OBJECT: &[u8] = aya::include_bytes_aligned!(concat!(env!("OUT_DIR"), "/events.bpf.o"));
+
+/// The length of the events sent by the BPF programs.
+const EVENT_LEN: usize = 336;
+
+/// The number of events read from a per-CPU buffer at once.
+const READ_BATCH: usize = 32;
+
+const AF_INET: u16 = 2;
+const AF_INET6: u16 = 10;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("At least one kind of event must be captured"))]
+    NoEvents,
+
+    #[snafu(display("Could not load BPF object: {}", source))]
+    Load { source: BpfError },
+
+    #[snafu(display("Could not attach BPF program {}: {}", name, source))]
+    Attach {
+        name: &'static str,
+        source: ProgramError,
+    },
+
+    #[snafu(display("Could not open events map: {}", source))]
+    Map { source: MapError },
+
+    #[snafu(display("Could not list online CPUs: {}", source))]
+    Cpus { source: io::Error },
+
+    #[snafu(display("Could not open events buffer of CPU {}: {}", cpu, source))]
+    Buffer { cpu: u32, source: PerfBufferError },
+}
+
+/// Configuration for the `ebpf` source.
+#[configurable_component(source("ebpf"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct EbpfConfig {
+    /// The kinds of events to capture.
+    #[serde(default = "default_events")]
+    events: Vec<EventKind>,
+
+    /// The number of memory pages of the buffer that each CPU sends events through.
+    ///
+    /// Events are lost when a buffer fills up faster than it is read, so this may need to be
+    /// increased on busy hosts. Must be a power of two.
+    #[serde(default = "default_buffer_pages")]
+    buffer_pages: usize,
+}
+
+/// A kind of event.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    /// A process executed a program.
+    ProcessExec,
+
+    /// A process exited.
+    ProcessExit,
+
+    /// A TCP connection initiated by this host was established.
+    TcpConnect,
+
+    /// A TCP connection initiated by a peer was established.
+    TcpAccept,
+}
+
+impl EventKind {
+    /// The kind of an event sent by the BPF programs.
+    const fn from_raw(kind: u32) -> Option<Self> {
+        match kind {
+            1 => Some(Self::ProcessExec),
+            2 => Some(Self::ProcessExit),
+            3 => Some(Self::TcpConnect),
+            4 => Some(Self::TcpAccept),
+            _ => None,
+        }
+    }
+
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::ProcessExec => "process_exec",
+            Self::ProcessExit => "process_exit",
+            Self::TcpConnect => "tcp_connect",
+            Self::TcpAccept => "tcp_accept",
+        }
+    }
+}
+
+fn default_events() -> Vec<EventKind> {
+    vec![
+        EventKind::ProcessExec,
+        EventKind::ProcessExit,
+        EventKind::TcpConnect,
+        EventKind::TcpAccept,
+    ]
+}
+
+const fn default_buffer_pages() -> usize {
+    64
+}
+
+impl Default for EbpfConfig {
+    fn default() -> Self {
+        Self {
+            events: default_events(),
+            buffer_pages: default_buffer_pages(),
+        }
+    }
+}
+
+impl GenerateConfig for EbpfConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self::default()).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for EbpfConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        if self.events.is_empty() {
+            return Err(BuildError::NoEvents.into());
+        }
+
+        raise_memlock_limit();
+
+        let mut bpf = Bpf::load(OBJECT).context(LoadSnafu)?;
+        let capture = |kind| self.events.contains(&kind);
+        if capture(EventKind::ProcessExec) {
+            attach(&mut bpf, "process_exec", "sched", "sched_process_exec")?;
+        }
+        if capture(EventKind::ProcessExit) {
+            attach(&mut bpf, "process_exit", "sched", "sched_process_exit")?;
+        }
+        if capture(EventKind::TcpConnect) || capture(EventKind::TcpAccept) {
+            attach(
+                &mut bpf,
+                "inet_sock_set_state",
+                "sock",
+                "inet_sock_set_state",
+            )?;
+        }
+
+        let mut events = AsyncPerfEventArray::try_from(bpf.map_mut("EVENTS").context(MapSnafu)?)
+            .context(MapSnafu)?;
+        let mut buffers = Vec::new();
+        for cpu in online_cpus().context(CpusSnafu)? {
+            buffers.push(
+                events
+                    .open(cpu, Some(self.buffer_pages))
+                    .context(BufferSnafu { cpu })?,
+            );
+        }
+        let reads = stream::select_all(buffers.into_iter().map(|buffer| {
+            stream::unfold(buffer, |mut buffer| async move {
+                let mut chunks = (0..READ_BATCH)
+                    .map(|_| BytesMut::with_capacity(EVENT_LEN))
+                    .collect::<Vec<_>>();
+                let result = buffer.read_events(&mut chunks).await;
+                Some((result.map(|events| (events, chunks)), buffer))
+            })
+            .boxed()
+        }));
+
+        let hostname = crate::get_hostname().ok();
+        let kinds = self.events.clone();
+        Ok(Box::pin(async move {
+            // The programs stay attached for as long as they are loaded.
+            let _bpf = bpf;
+            run(reads, kinds, hostname, cx.shutdown, cx.out).await
+        }))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+fn attach(
+    bpf: &mut Bpf,
+    name: &'static str,
+    category: &str,
+    tracepoint: &str,
+) -> Result<(), BuildError> {
+    let program: &mut TracePoint = bpf
+        .program_mut(name)
+        .expect("program is defined in the BPF object")
+        .try_into()
+        .context(AttachSnafu { name })?;
+    program.load().context(AttachSnafu { name })?;
+    program
+        .attach(category, tracepoint)
+        .context(AttachSnafu { name })?;
+    Ok(())
+}
+
+/// Kernels before 5.11 account the memory of BPF maps against the locked memory limit, which is
+/// too low for them by default.
+fn raise_memlock_limit() {
+    let limit = libc::rlimit {
+        rlim_cur: libc::RLIM_INFINITY,
+        rlim_max: libc::RLIM_INFINITY,
+    };
+    // SAFETY: `setrlimit` only reads the limit that it is given.
+    if unsafe { libc::setrlimit(libc::RLIMIT_MEMLOCK, &limit) } != 0 {
+        debug!(
+            message = "Failed to raise the locked memory limit.",
+            error = %io::Error::last_os_error(),
+        );
+    }
+}
+
+async fn run(
+    reads: impl futures::Stream<Item = Result<(Events, Vec<BytesMut>), PerfBufferError>> + Unpin,
+    kinds: Vec<EventKind>,
+    hostname: Option<String>,
+    shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let mut reads = reads.take_until(shutdown);
+    while let Some(result) = reads.next().await {
+        let (read, chunks) = match result {
+            Ok(read) => read,
+            Err(error) => {
+                emit!(EbpfReadError { error });
+                continue;
+            }
+        };
+        if read.lost > 0 {
+            emit!(EbpfEventsLost { count: read.lost });
+        }
+
+        let events = chunks[..read.read]
+            .iter()
+            .filter_map(|chunk| RawEvent::parse(chunk))
+            .filter(|event| kinds.contains(&event.kind))
+            .map(|event| event.into_event(hostname.as_deref()))
+            .collect::<Vec<_>>();
+        if events.is_empty() {
+            continue;
+        }
+
+        let count = events.len();
+        emit!(EventsReceived {
+            count,
+            byte_size: events.size_of(),
+        });
+        if let Err(error) = out.send_batch(events).await {
+            emit!(StreamClosedError { error, count });
+            return Err(());
+        }
+    }
+
+    Ok(())
+}
+
+/// An event sent by the BPF programs, laid out as `struct event` of `bpf/events.bpf.c`.
+#[derive(Debug, PartialEq)]
+struct RawEvent {
+    kind: EventKind,
+    pid: u32,
+    ppid: u32,
+    uid: u32,
+    gid: u32,
+    exit_code: i32,
+    comm: String,
+    filename: String,
+    family: u16,
+    src_port: u16,
+    dst_port: u16,
+    src_addr: [u8; 16],
+    dst_addr: [u8; 16],
+}
+
+impl RawEvent {
+    fn parse(data: &[u8]) -> Option<Self> {
+        if data.len() < EVENT_LEN {
+            return None;
+        }
+        let u32_at =
+            |offset: usize| u32::from_ne_bytes(data[offset..offset + 4].try_into().unwrap());
+        let u16_at =
+            |offset: usize| u16::from_ne_bytes(data[offset..offset + 2].try_into().unwrap());
+
+        Some(Self {
+            kind: EventKind::from_raw(u32_at(0))?,
+            pid: u32_at(4),
+            ppid: u32_at(8),
+            uid: u32_at(12),
+            gid: u32_at(16),
+            exit_code: u32_at(20) as i32,
+            comm: c_string(&data[24..40]),
+            filename: c_string(&data[40..296]),
+            family: u16_at(296),
+            src_port: u16_at(298),
+            dst_port: u16_at(300),
+            src_addr: data[304..320].try_into().unwrap(),
+            dst_addr: data[320..336].try_into().unwrap(),
+        })
+    }
+
+    fn address(&self, address: [u8; 16]) -> Option<IpAddr> {
+        match self.family {
+            AF_INET => Some(Ipv4Addr::new(address[0], address[1], address[2], address[3]).into()),
+            AF_INET6 => Some(Ipv6Addr::from(address).into()),
+            _ => None,
+        }
+    }
+
+    fn into_event(self, hostname: Option<&str>) -> Event {
+        let mut log = LogEvent::default();
+        log.insert(log_schema().source_type_key(), Bytes::from("ebpf"));
+        log.insert(log_schema().timestamp_key(), Utc::now());
+        if let Some(hostname) = hostname {
+            log.insert(log_schema().host_key(), hostname);
+        }
+        log.insert("event_type", self.kind.as_str());
+
+        // Connections accepted by the kernel have no process.
+        if self.pid != 0 {
+            log.insert("process.pid", self.pid);
+            log.insert("process.ppid", self.ppid);
+            log.insert("process.uid", self.uid);
+            log.insert("process.gid", self.gid);
+            log.insert("process.comm", self.comm.as_str());
+        }
+
+        match self.kind {
+            EventKind::ProcessExec => {
+                log.insert("process.filename", self.filename.as_str());
+            }
+            EventKind::ProcessExit => {
+                // The exit code is a wait status, of either an exit status or a signal.
+                let signal = self.exit_code & 0x7f;
+                if signal == 0 {
+                    log.insert("process.exit_code", i64::from((self.exit_code >> 8) & 0xff));
+                } else {
+                    log.insert("process.signal", i64::from(signal));
+                }
+            }
+            EventKind::TcpConnect | EventKind::TcpAccept => {
+                if let Some(address) = self.address(self.src_addr) {
+                    log.insert("connection.src_addr", address.to_string());
+                }
+                if let Some(address) = self.address(self.dst_addr) {
+                    log.insert("connection.dst_addr", address.to_string());
+                }
+                log.insert("connection.src_port", self.src_port);
+                log.insert("connection.dst_port", self.dst_port);
+            }
+        }
+
+        log.into()
+    }
+}
+
+/// Decodes a NUL-terminated string, of which the kernel only guarantees the termination.
+fn c_string(data: &[u8]) -> String {
+    let end = data
+        .iter()
+        .position(|byte| *byte == 0)
+        .unwrap_or(data.len());
+    String::from_utf8_lossy(&data[..end]).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<EbpfConfig>();
+    }
+
+    fn raw(kind: u32, pid: u32, exit_code: i32, family: u16) -> Vec<u8> {
+        let mut data = vec![0; EVENT_LEN];
+        data[0..4].copy_from_slice(&kind.to_ne_bytes());
+        data[4..8].copy_from_slice(&pid.to_ne_bytes());
+        data[8..12].copy_from_slice(&1u32.to_ne_bytes());
+        data[12..16].copy_from_slice(&1000u32.to_ne_bytes());
+        data[20..24].copy_from_slice(&exit_code.to_ne_bytes());
+        data[24..28].copy_from_slice(b"curl");
+        data[40..53].copy_from_slice(b"/usr/bin/curl");
+        data[296..298].copy_from_slice(&family.to_ne_bytes());
+        data[298..300].copy_from_slice(&51234u16.to_ne_bytes());
+        data[300..302].copy_from_slice(&443u16.to_ne_bytes());
+        data[304..308].copy_from_slice(&[10, 0, 0, 1]);
+        data[320..324].copy_from_slice(&[93, 184, 216, 34]);
+        data
+    }
+
+    #[test]
+    fn parses_process_events() {
+        let event = RawEvent::parse(&raw(1, 4242, 0, 0))
+            .unwrap()
+            .into_event(Some("host1"));
+        let log = event.as_log();
+
+        assert_eq!(log["event_type"], "process_exec".into());
+        assert_eq!(log["process.pid"], 4242.into());
+        assert_eq!(log["process.ppid"], 1.into());
+        assert_eq!(log["process.uid"], 1000.into());
+        assert_eq!(log["process.comm"], "curl".into());
+        assert_eq!(log["process.filename"], "/usr/bin/curl".into());
+        assert_eq!(log[log_schema().host_key()], "host1".into());
+
+        let event = RawEvent::parse(&raw(2, 4242, 3 << 8, 0))
+            .unwrap()
+            .into_event(None);
+        assert_eq!(event.as_log()["process.exit_code"], 3.into());
+
+        let event = RawEvent::parse(&raw(2, 4242, 9, 0))
+            .unwrap()
+            .into_event(None);
+        assert_eq!(event.as_log()["process.signal"], 9.into());
+        assert!(event.as_log().get("process.exit_code").is_none());
+    }
+
+    #[test]
+    fn parses_connection_events() {
+        let event = RawEvent::parse(&raw(3, 4242, 0, AF_INET))
+            .unwrap()
+            .into_event(None);
+        let log = event.as_log();
+
+        assert_eq!(log["event_type"], "tcp_connect".into());
+        assert_eq!(log["connection.src_addr"], "10.0.0.1".into());
+        assert_eq!(log["connection.dst_addr"], "93.184.216.34".into());
+        assert_eq!(log["connection.src_port"], 51234.into());
+        assert_eq!(log["connection.dst_port"], 443.into());
+
+        let event = RawEvent::parse(&raw(4, 0, 0, AF_INET))
+            .unwrap()
+            .into_event(None);
+        assert!(event.as_log().get("process.pid").is_none());
+    }
+
+    #[test]
+    fn rejects_invalid_events() {
+        assert_eq!(RawEvent::parse(&raw(9, 1, 0, 0)), None);
+        assert_eq!(RawEvent::parse(&[0; 8]), None);
+    }
+}
@@ -1,11 +1,9 @@
-use std::collections::BTreeMap;
-
 use serde::{
     de::{MapAccess, Visitor},
     Deserialize, Deserializer,
 };
 
-use crate::event::{Metric, MetricKind, MetricValue};
+use crate::event::{metric::MetricTags, Metric, MetricKind, MetricValue};
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -17,11 +15,11 @@ pub struct Stats {
 impl Stats {
     pub fn metrics(&self, namespace: Option<String>) -> Vec<Metric> {
         let mut result = Vec::new();
-        let mut tags = BTreeMap::new();
+        let mut tags = MetricTags::new();
         let now = chrono::Utc::now();
         let namespace = namespace.unwrap_or_else(|| "eventstoredb".to_string());
 
-        tags.insert("id".to_string(), self.proc.id.to_string());
+        tags.insert("id".into(), self.proc.id.to_string());
 
         result.push(
             Metric::new(
@@ -102,7 +100,7 @@ impl Stats {
         );
 
         if let Some(drive) = self.sys.drive.as_ref() {
-            tags.insert("path".to_string(), drive.path.clone());
+            tags.insert("path".into(), drive.path.clone());
 
             result.push(
                 Metric::new(
@@ -233,13 +233,15 @@ impl SourceConfig for ExecConfig {
                     self.clone(),
                     hostname,
                     exec_interval_secs,
+                    cx.once,
                     decoder,
                     cx.shutdown,
                     cx.out,
                 )))
             }
             Mode::Streaming => {
-                let respawn_on_exit = self.respawn_on_exit_or_default();
+                // Running once, the command isn't respawned once it exits.
+                let respawn_on_exit = self.respawn_on_exit_or_default() && !cx.once;
                 let respawn_interval_secs = self.respawn_interval_secs_or_default();
 
                 Ok(Box::pin(run_streaming(
@@ -262,12 +264,17 @@ impl SourceConfig for ExecConfig {
     fn can_acknowledge(&self) -> bool {
         false
     }
+
+    fn can_run_once(&self) -> bool {
+        true
+    }
 }
 
 async fn run_scheduled(
     config: ExecConfig,
     hostname: Option<String>,
     exec_interval_secs: u64,
+    once: bool,
     decoder: Decoder,
     shutdown: ShutdownSignal,
     out: SourceSender,
@@ -275,7 +282,10 @@ async fn run_scheduled(
     debug!("Starting scheduled exec runs.");
     let schedule = Duration::from_secs(exec_interval_secs);
 
-    let mut interval = IntervalStream::new(time::interval(schedule)).take_until(shutdown.clone());
+    // Running once, the command runs at the first tick only.
+    let mut interval = IntervalStream::new(time::interval(schedule))
+        .take_until(shutdown.clone())
+        .take(if once { 1 } else { usize::MAX });
 
     while interval.next().await.is_some() {
         // Wait for our task to finish, wrapping it in a timeout
@@ -1,10 +1,17 @@
-use std::{convert::TryInto, path::PathBuf, time::Duration};
+use std::{
+    collections::HashMap,
+    convert::TryInto,
+    io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
 use bytes::Bytes;
 use chrono::Utc;
 use file_source::{
     paths_provider::glob::{Glob, MatchOptions},
     Checkpointer, FileFingerprint, FileServer, FingerprintStrategy, Fingerprinter, Line, ReadFrom,
+    Rewind, CHECKPOINT_FILE_NAME,
 };
 use futures::{FutureExt, Stream, StreamExt, TryFutureExt};
 use regex::bytes::Regex;
@@ -21,15 +28,19 @@ use crate::{
     encoding_transcode::{Decoder, Encoder},
     event::{BatchNotifier, BatchStatus, LogEvent},
     internal_events::{
-        FileBytesReceived, FileEventsReceived, FileOpen, FileSourceInternalEventsEmitter,
-        StreamClosedError,
+        FileBytesReceived, FileCheckpointStoreError, FileEventsReceived, FileOpen,
+        FileSourceInternalEventsEmitter, StreamClosedError,
     },
     line_agg::{self, LineAgg},
     serde::bool_or_struct,
     shutdown::ShutdownSignal,
+    state_store::{BoxedStateStore, StateStoreConfig},
     SourceSender,
 };
 
+/// How often the checkpoints are copied to the state store, when it is not `file`.
+const CHECKPOINT_STORE_INTERVAL: Duration = Duration::from_secs(5);
+
 #[derive(Debug, Snafu)]
 enum BuildError {
     #[snafu(display("data_dir option required, but not given here or globally"))]
@@ -114,9 +125,15 @@ pub struct FileConfig {
 
     /// The directory used to persist file checkpoint positions.
     ///
+    /// When `state_store` is not `file`, the checkpoints are also copied to the state store, and read back from it when
+    /// the source starts.
+    ///
     /// By default, the global `data_dir` option is used. Please make sure the user Vector is running as has write permissions to this directory.
     pub data_dir: Option<PathBuf>,
 
+    #[configurable(derived)]
+    pub state_store: StateStoreConfig,
+
     /// Enables adding the file offset to each event and sets the name of the log field used.
     ///
     /// The value will be the byte offset of the start of the line within the file.
@@ -272,7 +289,10 @@ const fn default_lines() -> usize {
 #[derive(Debug)]
 pub(crate) struct FinalizerEntry {
     pub(crate) file_id: FileFingerprint,
+    /// The position the file is read again from if the line fails to be delivered.
+    pub(crate) start_offset: u64,
     pub(crate) offset: u64,
+    pub(crate) generation: u64,
 }
 
 impl Default for FileConfig {
@@ -295,6 +315,7 @@ impl Default for FileConfig {
             host_key: None,
             offset_key: None,
             data_dir: None,
+            state_store: StateStoreConfig::default(),
             glob_minimum_cooldown_ms: 1000, // millis
             message_start_indicator: None,
             multi_line_timeout: 1000, // millis
@@ -336,14 +357,28 @@ impl SourceConfig for FileConfig {
             }
         }
 
+        // The checkpoints of a remote store take precedence over those of the data directory, so
+        // that the source resumes where another instance of it left off.
+        let state_store = if self.state_store.is_file() {
+            None
+        } else {
+            let store = self.state_store.build(&cx, Some(&data_dir)).await?;
+            restore_checkpoints(&store, &data_dir).await?;
+            Some(store)
+        };
+
         let acknowledgements = cx.do_acknowledgements(&self.acknowledgements);
+        let acknowledgements_timeout = cx.acknowledgements_timeout(&self.acknowledgements);
 
         Ok(file_source(
             self,
             data_dir,
+            state_store,
             cx.shutdown,
             cx.out,
             acknowledgements,
+            acknowledgements_timeout,
+            cx.once,
         ))
     }
 
@@ -354,14 +389,21 @@ impl SourceConfig for FileConfig {
     fn can_acknowledge(&self) -> bool {
         true
     }
+
+    fn can_run_once(&self) -> bool {
+        true
+    }
 }
 
 pub fn file_source(
     config: &FileConfig,
     data_dir: PathBuf,
+    state_store: Option<BoxedStateStore>,
     shutdown: ShutdownSignal,
     mut out: SourceSender,
     acknowledgements: bool,
+    acknowledgements_timeout: Option<Duration>,
+    once: bool,
 ) -> super::Source {
     let ignore_before = config
         .ignore_older_secs
@@ -390,6 +432,8 @@ pub fn file_source(
         None => Bytes::from(config.line_delimiter.clone()),
     };
 
+    let (rewind_tx, rewind_rx) = futures::channel::mpsc::unbounded::<Rewind>();
+    let checkpoints_path = data_dir.join(CHECKPOINT_FILE_NAME);
     let checkpointer = Checkpointer::new(&data_dir);
     let file_server = FileServer {
         paths_provider,
@@ -408,6 +452,8 @@ pub fn file_source(
         },
         oldest_first: config.oldest_first,
         remove_after: config.remove_after_secs.map(Duration::from_secs),
+        once,
+        rewinds: acknowledgements.then(|| rewind_rx),
         emitter: FileSourceInternalEventsEmitter,
         handle: tokio::runtime::Handle::current(),
     };
@@ -428,11 +474,17 @@ pub fn file_source(
     let message_start_indicator = config.message_start_indicator.clone();
     let multi_line_timeout = config.multi_line_timeout;
 
+    // Running once without acknowledgements, the checkpoint writer stops once all the lines read
+    // have been sent, as the file server stops before the global shutdown.
+    let (sent_tx, sent_rx) = oneshot::channel::<()>();
     let (finalizer, shutdown_checkpointer) = if acknowledgements {
         // The shutdown sent in to the finalizer is the global
         // shutdown handle used to tell it to stop accepting new batch
         // statuses and just wait for the remaining acks to come in.
-        let (finalizer, mut ack_stream) = OrderedFinalizer::<FinalizerEntry>::new(shutdown.clone());
+        let (finalizer, mut ack_stream) = OrderedFinalizer::<FinalizerEntry>::with_timeout(
+            shutdown.clone(),
+            acknowledgements_timeout,
+        );
         // We set up a separate shutdown signal to tie together the
         // finalizer and the checkpoint writer task in the file
         // server, to make it continue to write out updated
@@ -440,14 +492,45 @@ pub fn file_source(
         let (send_shutdown, shutdown2) = oneshot::channel::<()>();
         let checkpoints = checkpointer.view();
         tokio::spawn(async move {
+            // A file is read again from the first line that failed transiently, and its
+            // checkpoint stops before that line until it is delivered. The lines read before the
+            // file was rewound are ignored, as they are read again as well.
+            let mut rewound = HashMap::new();
             while let Some((status, entry)) = ack_stream.next().await {
-                if status == BatchStatus::Delivered {
-                    checkpoints.update(entry.file_id, entry.offset);
+                if rewound.get(&entry.file_id) == Some(&entry.generation) {
+                    continue;
+                }
+                rewound.remove(&entry.file_id);
+
+                match status {
+                    BatchStatus::Delivered => checkpoints.update(entry.file_id, entry.offset),
+                    BatchStatus::Errored => {
+                        let rewind = Rewind {
+                            file_id: entry.file_id,
+                            position: entry.start_offset,
+                        };
+                        // The file server has stopped when the channel is closed.
+                        let _ = rewind_tx.unbounded_send(rewind);
+                        rewound.insert(entry.file_id, entry.generation);
+                    }
+                    BatchStatus::Rejected => {}
                 }
             }
             send_shutdown.send(())
         });
         (Some(finalizer), shutdown2.map(|_| ()).boxed())
+    } else if once {
+        let shutdown = shutdown.clone();
+        (
+            None,
+            async move {
+                tokio::select! {
+                    _ = shutdown => {},
+                    _ = sent_rx => {},
+                }
+            }
+            .boxed(),
+        )
     } else {
         // When not dealing with end-to-end acknowledgements, just
         // clone the global shutdown to stop the checkpoint writer.
@@ -514,7 +597,9 @@ pub fn file_source(
                 event = event.with_batch_notifier(&batch);
                 let entry = FinalizerEntry {
                     file_id: line.file_id,
+                    start_offset: line.start_offset,
                     offset: line.end_offset,
+                    generation: line.generation,
                 };
                 finalizer.add(entry, receiver);
             } else {
@@ -536,10 +621,19 @@ pub fn file_source(
                     emit!(StreamClosedError { error, count });
                 }
             }
+            let _ = sent_tx.send(());
+        });
+
+        // The checkpoints are copied to the state store as they are written, and once more after
+        // the file server has written them for the last time.
+        let storing = state_store.map(|store| {
+            let (stop, stopped) = oneshot::channel::<()>();
+            let task = tokio::spawn(store_checkpoints(store, checkpoints_path, stopped));
+            (stop, task)
         });
 
         let span = info_span!("file_server");
-        spawn_blocking(move || {
+        let result = spawn_blocking(move || {
             let _enter = span.enter();
             let result = file_server.run(tx, shutdown, shutdown_checkpointer, checkpointer);
             emit!(FileOpen { count: 0 });
@@ -549,10 +643,63 @@ pub fn file_source(
             result.unwrap();
         })
         .map_err(|error| error!(message="File server unexpectedly stopped.", %error))
-        .await
+        .await;
+
+        if let Some((stop, task)) = storing {
+            let _ = stop.send(());
+            let _ = task.await;
+        }
+        result
     })
 }
 
+/// Writes the checkpoints of the state store to the data directory, where the file server reads
+/// them from.
+async fn restore_checkpoints(store: &BoxedStateStore, data_dir: &Path) -> crate::Result<()> {
+    if let Some(checkpoints) = store.get(CHECKPOINT_FILE_NAME).await? {
+        tokio::fs::write(data_dir.join(CHECKPOINT_FILE_NAME), checkpoints).await?;
+    }
+    Ok(())
+}
+
+/// Copies the checkpoints written by the file server to the state store whenever they change,
+/// until stopped.
+async fn store_checkpoints(
+    store: BoxedStateStore,
+    path: PathBuf,
+    mut stopped: oneshot::Receiver<()>,
+) {
+    let mut last = None;
+    loop {
+        let stopping = tokio::select! {
+            _ = &mut stopped => true,
+            _ = tokio::time::sleep(CHECKPOINT_STORE_INTERVAL) => false,
+        };
+
+        match tokio::fs::read(&path).await {
+            Ok(checkpoints) if last.as_ref() != Some(&checkpoints) => {
+                match store
+                    .set(CHECKPOINT_FILE_NAME, checkpoints.clone().into())
+                    .await
+                {
+                    Ok(()) => last = Some(checkpoints),
+                    Err(error) => emit!(FileCheckpointStoreError { error }),
+                }
+            }
+            // The checkpoints are unchanged, or haven't been written yet.
+            Ok(_) => {}
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {}
+            Err(error) => emit!(FileCheckpointStoreError {
+                error: error.into()
+            }),
+        }
+
+        if stopping {
+            break;
+        }
+    }
+}
+
 /// Emit deprecation warning if the old option is used, and take it into account when determining
 /// defaults. Any of the newer options will override it when set directly.
 fn reconcile_position_options(
@@ -587,18 +734,24 @@ fn wrap_with_line_agg(
                 (
                     line.filename,
                     line.text,
-                    (line.file_id, line.start_offset, line.end_offset),
+                    (
+                        line.file_id,
+                        line.start_offset,
+                        line.end_offset,
+                        line.generation,
+                    ),
                 )
             }),
             logic,
         )
         .map(
-            |(filename, text, (file_id, start_offset, end_offset))| Line {
+            |(filename, text, (file_id, start_offset, end_offset, generation))| Line {
                 text,
                 filename,
                 file_id,
                 start_offset,
                 end_offset,
+                generation,
             },
         ),
     )
@@ -645,6 +798,7 @@ mod tests {
         fs::{self, File},
         future::Future,
         io::{Seek, Write},
+        sync::Arc,
     };
 
     use encoding_rs::UTF_16LE;
@@ -658,6 +812,7 @@ mod tests {
         event::{Event, EventStatus, Value},
         shutdown::ShutdownSignal,
         sources::file,
+        state_store::FileStateStore,
         test_util::components::{assert_source_compliance, FILE_SOURCE_TAGS},
     };
 
@@ -799,6 +954,48 @@ mod tests {
         assert_eq!(log[log_schema().source_type_key()], "file".into());
     }
 
+    #[tokio::test]
+    async fn file_read_once() {
+        let dir = tempdir().unwrap();
+        let config = file::FileConfig {
+            include: vec![dir.path().join("*")],
+            read_from: Some(ReadFromConfig::Beginning),
+            ..test_default_file_config(&dir)
+        };
+
+        let path = dir.path().join("file");
+        let mut file = File::create(&path).unwrap();
+        for i in 0..3 {
+            writeln!(&mut file, "hello {}", i).unwrap();
+        }
+
+        let (tx, rx) = SourceSender::new_test();
+        let data_dir = config.data_dir.clone().unwrap();
+        // The source ends by itself, without being shut down.
+        let source = file::file_source(
+            &config,
+            data_dir,
+            None,
+            ShutdownSignal::noop(),
+            tx,
+            false,
+            None,
+            true,
+        );
+        timeout(Duration::from_secs(5), source)
+            .await
+            .expect("The source didn't end once the file was read.")
+            .unwrap();
+
+        let received = timeout(Duration::from_secs(5), rx.collect::<Vec<_>>())
+            .await
+            .unwrap();
+        assert_eq!(
+            extract_messages_string(received),
+            vec!["hello 0", "hello 1", "hello 2"]
+        );
+    }
+
     #[tokio::test]
     async fn file_happy_path() {
         let n = 5;
@@ -1250,6 +1447,99 @@ mod tests {
         assert_eq!(lines, vec!["the line"]);
     }
 
+    #[tokio::test]
+    async fn file_redelivers_errored_lines() {
+        let dir = tempdir().unwrap();
+        let config = file::FileConfig {
+            include: vec![dir.path().join("*")],
+            ..test_default_file_config(&dir)
+        };
+
+        let path = dir.path().join("file");
+        let mut file = File::create(&path).unwrap();
+        writeln!(&mut file, "the line").unwrap();
+        sleep_500_millis().await;
+
+        // The line failed to be delivered the first time, so it is read again while running.
+        let received =
+            run_file_source(&config, true, FirstErrored, sleep(Duration::from_secs(3))).await;
+        let lines = extract_messages_string(received);
+        assert_eq!(lines, vec!["the line", "the line"]);
+
+        // Restart server, the file is checkpointed past the line once it was delivered.
+        let received = run_file_source(&config, true, Acks, async {
+            sleep_500_millis().await;
+            writeln!(&mut file, "another line").unwrap();
+            sleep_500_millis().await;
+        })
+        .await;
+        let lines = extract_messages_string(received);
+        assert_eq!(lines, vec!["another line"]);
+    }
+
+    #[tokio::test]
+    async fn file_checkpoints_in_state_store() {
+        let dir = tempdir().unwrap();
+        let store_dir = tempdir().unwrap();
+        let store: BoxedStateStore = Arc::new(FileStateStore::new(store_dir.path().to_path_buf()));
+
+        let path = dir.path().join("file");
+        let mut file = File::create(&path).unwrap();
+        writeln!(&mut file, "first line").unwrap();
+        sleep_500_millis().await;
+
+        let data_dir = tempdir().unwrap();
+        let config = file::FileConfig {
+            include: vec![dir.path().join("*")],
+            ..test_default_file_config(&data_dir)
+        };
+        let received = run_file_source_with_state_store(
+            &config,
+            Some(Arc::clone(&store)),
+            true,
+            NoAcks,
+            sleep_500_millis(),
+        )
+        .await;
+        let lines = extract_messages_string(received);
+        assert_eq!(lines, vec!["first line"]);
+
+        // The checkpoints are copied to the store once the file server has stopped.
+        let checkpoints_path = data_dir.path().join(CHECKPOINT_FILE_NAME);
+        timeout(Duration::from_secs(5), async {
+            while store.get(CHECKPOINT_FILE_NAME).await.unwrap()
+                != fs::read(&checkpoints_path).ok().map(Into::into)
+            {
+                sleep(Duration::from_millis(100)).await;
+            }
+        })
+        .await
+        .expect("The checkpoints weren't copied to the state store.");
+
+        // A new instance of the source, without the data directory of the previous one, resumes
+        // from the checkpoints of the store.
+        let data_dir = tempdir().unwrap();
+        restore_checkpoints(&store, data_dir.path()).await.unwrap();
+        let config = file::FileConfig {
+            include: vec![dir.path().join("*")],
+            ..test_default_file_config(&data_dir)
+        };
+        let received = run_file_source_with_state_store(
+            &config,
+            Some(Arc::clone(&store)),
+            true,
+            NoAcks,
+            async {
+                sleep_500_millis().await;
+                writeln!(&mut file, "second line").unwrap();
+                sleep_500_millis().await;
+            },
+        )
+        .await;
+        let lines = extract_messages_string(received);
+        assert_eq!(lines, vec!["second line"]);
+    }
+
     #[tokio::test]
     async fn file_start_position_server_restart_with_file_rotation_acknowledged() {
         file_start_position_server_restart_with_file_rotation(Acks).await
@@ -1800,9 +2090,10 @@ mod tests {
 
     #[derive(Clone, Copy, Eq, PartialEq)]
     enum AckingMode {
-        NoAcks,      // No acknowledgement handling and no finalization
-        Unfinalized, // Acknowledgement handling but no finalization
-        Acks,        // Full acknowledgements and proper finalization
+        NoAcks,       // No acknowledgement handling and no finalization
+        Unfinalized,  // Acknowledgement handling but no finalization
+        Acks,         // Full acknowledgements and proper finalization
+        FirstErrored, // Full acknowledgements, with the first batch failing to be delivered
     }
     use AckingMode::*;
 
@@ -1811,11 +2102,24 @@ mod tests {
         wait_shutdown: bool,
         acking_mode: AckingMode,
         inner: impl Future<Output = ()>,
+    ) -> Vec<Event> {
+        run_file_source_with_state_store(config, None, wait_shutdown, acking_mode, inner).await
+    }
+
+    async fn run_file_source_with_state_store(
+        config: &FileConfig,
+        state_store: Option<BoxedStateStore>,
+        wait_shutdown: bool,
+        acking_mode: AckingMode,
+        inner: impl Future<Output = ()>,
     ) -> Vec<Event> {
         assert_source_compliance(&FILE_SOURCE_TAGS, async move {
             let (tx, rx) = if acking_mode == Acks {
                 let (tx, rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
                 (tx, rx.boxed())
+            } else if acking_mode == FirstErrored {
+                let (tx, rx) = SourceSender::new_test_errors(|count| count == 0);
+                (tx, rx.boxed())
             } else {
                 let (tx, rx) = SourceSender::new_test();
                 (tx, rx.boxed())
@@ -1825,7 +2129,16 @@ mod tests {
             let data_dir = config.data_dir.clone().unwrap();
             let acks = !matches!(acking_mode, NoAcks);
 
-            tokio::spawn(file::file_source(config, data_dir, shutdown, tx, acks));
+            tokio::spawn(file::file_source(
+                config,
+                data_dir,
+                state_store,
+                shutdown,
+                tx,
+                acks,
+                None,
+                false,
+            ));
 
             inner.await;
 
@@ -87,6 +87,10 @@ impl SourceConfig for StdinConfig {
     fn can_acknowledge(&self) -> bool {
         false
     }
+
+    fn can_run_once(&self) -> bool {
+        true
+    }
 }
 
 #[cfg(test)]
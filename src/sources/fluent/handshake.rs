@@ -0,0 +1,285 @@
+//! The handshake of the forward protocol, with which the clients authenticate with a shared key,
+//! and optionally a username and password, before sending any messages.
+//!
+//! See <https://github.com/fluent/fluentd/wiki/Forward-Protocol-Specification-v1#handshake-messages>.
+
+use bytes::BytesMut;
+use rmpv::Value;
+use snafu::{ResultExt, Snafu};
+use tokio::io::{AsyncRead, AsyncWrite};
+use vector_common::sensitive_string::SensitiveString;
+
+use crate::fluent::{digest, read_message, value_bytes, write_message, ProtocolError};
+
+/// The largest `PING` message accepted, which only holds a few short strings.
+const MAX_PING_SIZE: usize = 16 * 1024;
+
+#[derive(Debug, Snafu)]
+pub enum HandshakeError {
+    #[snafu(display("Failed to exchange the handshake messages: {}", source))]
+    Protocol { source: ProtocolError },
+
+    #[snafu(display("Expected a PING message from the client."))]
+    UnexpectedMessage,
+
+    #[snafu(display("Client {} failed to authenticate: {}", hostname, reason))]
+    Unauthorized {
+        hostname: String,
+        reason: &'static str,
+    },
+}
+
+/// The server side of the handshake, carried out on each connection.
+#[derive(Clone, Debug)]
+pub(super) struct Handshake {
+    self_hostname: String,
+    shared_key: SensitiveString,
+    users: Vec<(String, SensitiveString)>,
+}
+
+impl Handshake {
+    pub(super) const fn new(
+        self_hostname: String,
+        shared_key: SensitiveString,
+        users: Vec<(String, SensitiveString)>,
+    ) -> Self {
+        Self {
+            self_hostname,
+            shared_key,
+            users,
+        }
+    }
+
+    /// Sends the `HELO` message, then checks the `PING` message the client replies with, and
+    /// answers it with a `PONG` message.
+    ///
+    /// Returns the bytes read past the end of the `PING` message.
+    pub(super) async fn run<S>(&self, socket: &mut S) -> Result<BytesMut, HandshakeError>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let nonce = rand::random::<[u8; 16]>().to_vec();
+        // Usernames and passwords are only asked for when there are users to authenticate as.
+        let auth_salt = if self.users.is_empty() {
+            Vec::new()
+        } else {
+            rand::random::<[u8; 16]>().to_vec()
+        };
+
+        let helo = Value::Array(vec![
+            "HELO".into(),
+            Value::Map(vec![
+                ("nonce".into(), Value::Binary(nonce.clone())),
+                ("auth".into(), Value::Binary(auth_salt.clone())),
+                ("keepalive".into(), true.into()),
+            ]),
+        ]);
+        write_message(socket, &helo).await.context(ProtocolSnafu)?;
+
+        let mut buf = BytesMut::new();
+        let ping = read_message(socket, &mut buf, MAX_PING_SIZE)
+            .await
+            .context(ProtocolSnafu)?;
+        let ping = Ping::parse(&ping).ok_or(HandshakeError::UnexpectedMessage)?;
+
+        match self.authenticate(&ping, &nonce, &auth_salt) {
+            Ok(()) => {
+                let pong = Value::Array(vec![
+                    "PONG".into(),
+                    true.into(),
+                    "".into(),
+                    self.self_hostname.as_str().into(),
+                    digest(&[
+                        ping.shared_key_salt,
+                        self.self_hostname.as_bytes(),
+                        &nonce,
+                        self.shared_key.inner().as_bytes(),
+                    ])
+                    .into(),
+                ]);
+                write_message(socket, &pong).await.context(ProtocolSnafu)?;
+                Ok(buf)
+            }
+            Err(reason) => {
+                let pong = Value::Array(vec![
+                    "PONG".into(),
+                    false.into(),
+                    reason.into(),
+                    "".into(),
+                    "".into(),
+                ]);
+                write_message(socket, &pong).await.context(ProtocolSnafu)?;
+                Err(HandshakeError::Unauthorized {
+                    hostname: String::from_utf8_lossy(ping.hostname).into_owned(),
+                    reason,
+                })
+            }
+        }
+    }
+
+    fn authenticate(
+        &self,
+        ping: &Ping,
+        nonce: &[u8],
+        auth_salt: &[u8],
+    ) -> Result<(), &'static str> {
+        let shared_key_digest = digest(&[
+            ping.shared_key_salt,
+            ping.hostname,
+            nonce,
+            self.shared_key.inner().as_bytes(),
+        ]);
+        if ping.shared_key_digest != shared_key_digest.as_bytes() {
+            return Err("shared_key mismatch");
+        }
+
+        if self.users.is_empty() {
+            return Ok(());
+        }
+        let authenticated = self.users.iter().any(|(username, password)| {
+            username.as_bytes() == ping.username
+                && digest(&[auth_salt, ping.username, password.inner().as_bytes()]).as_bytes()
+                    == ping.password_digest
+        });
+        if authenticated {
+            Ok(())
+        } else {
+            Err("username/password mismatch")
+        }
+    }
+}
+
+/// The `PING` message, with which the client authenticates.
+struct Ping<'a> {
+    hostname: &'a [u8],
+    shared_key_salt: &'a [u8],
+    shared_key_digest: &'a [u8],
+    username: &'a [u8],
+    password_digest: &'a [u8],
+}
+
+impl<'a> Ping<'a> {
+    fn parse(value: &'a Value) -> Option<Self> {
+        match value.as_array()?.as_slice() {
+            [kind, hostname, shared_key_salt, shared_key_digest, username, password_digest]
+                if kind.as_str() == Some("PING") =>
+            {
+                Some(Self {
+                    hostname: value_bytes(hostname)?,
+                    shared_key_salt: value_bytes(shared_key_salt)?,
+                    shared_key_digest: value_bytes(shared_key_digest)?,
+                    username: value_bytes(username)?,
+                    password_digest: value_bytes(password_digest)?,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::{duplex, AsyncWriteExt};
+
+    use super::*;
+
+    /// Connects a client, which authenticates with the given credentials, to the handshake.
+    ///
+    /// Returns the result of the handshake, the `PONG` message received by the client, and the nonce
+    /// of the `HELO` message.
+    async fn connect(
+        handshake: Handshake,
+        shared_key: &str,
+        user: Option<(&str, &str)>,
+    ) -> (Result<BytesMut, HandshakeError>, Vec<Value>, Vec<u8>) {
+        let (mut client, mut server) = duplex(4096);
+        let server = tokio::spawn(async move { handshake.run(&mut server).await });
+
+        let mut buf = BytesMut::new();
+        let helo = read_message(&mut client, &mut buf, MAX_PING_SIZE)
+            .await
+            .unwrap();
+        let options = helo.as_array().unwrap()[1].as_map().unwrap().clone();
+        let option = |name: &str| {
+            options
+                .iter()
+                .find(|(key, _)| key.as_str() == Some(name))
+                .map(|(_, value)| value_bytes(value).unwrap().to_vec())
+                .unwrap()
+        };
+        let (nonce, auth_salt) = (option("nonce"), option("auth"));
+
+        let salt = b"client-salt";
+        let (username, password) = user.unwrap_or(("", ""));
+        let ping = Value::Array(vec![
+            "PING".into(),
+            "client.local".into(),
+            Value::Binary(salt.to_vec()),
+            digest(&[salt, b"client.local", &nonce, shared_key.as_bytes()]).into(),
+            username.into(),
+            digest(&[&auth_salt, username.as_bytes(), password.as_bytes()]).into(),
+        ]);
+        // The first message is sent along with the `PING` message.
+        let mut bytes = Vec::new();
+        rmpv::encode::write_value(&mut bytes, &ping).unwrap();
+        bytes.push(0xc0);
+        client.write_all(&bytes).await.unwrap();
+
+        let pong = read_message(&mut client, &mut buf, MAX_PING_SIZE)
+            .await
+            .unwrap();
+        let result = server.await.unwrap();
+        (result, pong.as_array().unwrap().clone(), nonce)
+    }
+
+    fn handshake(users: Vec<(String, SensitiveString)>) -> Handshake {
+        Handshake::new("vector.local".into(), "secret".to_owned().into(), users)
+    }
+
+    #[tokio::test]
+    async fn accepts_shared_key() {
+        let (result, pong, nonce) = connect(handshake(Vec::new()), "secret", None).await;
+
+        assert_eq!(&result.unwrap()[..], &[0xc0]);
+        assert_eq!(pong[0].as_str(), Some("PONG"));
+        assert_eq!(pong[1].as_bool(), Some(true));
+        assert_eq!(pong[3].as_str(), Some("vector.local"));
+        // The client checks that the server knows the shared key as well.
+        assert_eq!(
+            pong[4].as_str().unwrap(),
+            digest(&[b"client-salt", b"vector.local", &nonce, b"secret"])
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_wrong_shared_key() {
+        let (result, pong, _) = connect(handshake(Vec::new()), "wrong", None).await;
+
+        assert!(matches!(
+            result,
+            Err(HandshakeError::Unauthorized {
+                reason: "shared_key mismatch",
+                ..
+            })
+        ));
+        assert_eq!(pong[1].as_bool(), Some(false));
+        assert_eq!(pong[2].as_str(), Some("shared_key mismatch"));
+    }
+
+    #[tokio::test]
+    async fn authenticates_users() {
+        let users = vec![("alice".to_owned(), "hunter2".to_owned().into())];
+
+        let (result, _, _) = connect(
+            handshake(users.clone()),
+            "secret",
+            Some(("alice", "hunter2")),
+        )
+        .await;
+        assert!(result.is_ok());
+
+        let (result, pong, _) = connect(handshake(users), "secret", Some(("alice", "wrong"))).await;
+        assert!(result.is_err());
+        assert_eq!(pong[2].as_str(), Some("username/password mismatch"));
+    }
+}
@@ -11,7 +11,8 @@ use vector_core::event::Value;
 /// The spec refers to 4 ways, but really CompressedPackedForward is encoded the
 /// same as PackedForward, it just has an additional decompression step.
 ///
-/// Not yet handled are the handshake messages.
+/// The handshake messages, which are exchanged before any of these, are handled
+/// separately, in the `handshake` module.
 ///
 /// https://github.com/fluent/fluentd/wiki/Forward-Protocol-Specification-v1#event-modes
 #[derive(Debug, Deserialize, Serialize)]
@@ -4,11 +4,14 @@ use std::net::SocketAddr;
 use bytes::{Buf, Bytes, BytesMut};
 use codecs::StreamDecodingError;
 use flate2::read::MultiGzDecoder;
+use futures::{future::BoxFuture, FutureExt};
 use lookup::event_path;
 use rmp_serde::{decode, Deserializer};
 use serde::Deserialize;
 use smallvec::{smallvec, SmallVec};
+use tokio::net::TcpStream;
 use tokio_util::codec::Decoder;
+use vector_common::sensitive_string::SensitiveString;
 use vector_config::configurable_component;
 use vector_core::config::LogNamespace;
 
@@ -19,14 +22,19 @@ use crate::{
         SourceConfig, SourceContext,
     },
     event::{Event, LogEvent},
-    internal_events::{FluentMessageDecodeError, FluentMessageReceived},
+    internal_events::{FluentHandshakeError, FluentMessageDecodeError, FluentMessageReceived},
     serde::bool_or_struct,
     tcp::TcpKeepaliveConfig,
-    tls::{MaybeTlsSettings, TlsSourceConfig},
+    tls::{MaybeTlsIncomingStream, MaybeTlsSettings, TlsSourceConfig},
 };
 
+mod handshake;
 mod message;
-use self::message::{FluentEntry, FluentMessage, FluentRecord, FluentTag, FluentTimestamp};
+pub use self::handshake::HandshakeError;
+use self::{
+    handshake::Handshake,
+    message::{FluentEntry, FluentMessage, FluentRecord, FluentTag, FluentTimestamp},
+};
 
 /// Configuration for the `fluent` source.
 #[configurable_component(source("fluent"))]
@@ -49,11 +57,68 @@ pub struct FluentConfig {
     #[configurable(derived)]
     tls: Option<TlsSourceConfig>,
 
+    #[configurable(derived)]
+    security: Option<FluentSecurityConfig>,
+
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: AcknowledgementsConfig,
 }
 
+/// Authentication of the clients.
+///
+/// When set, the clients must authenticate with the shared key, and optionally a username and
+/// password, with the handshake of the forward protocol before sending any messages.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct FluentSecurityConfig {
+    /// The hostname the source identifies itself with to the clients.
+    ///
+    /// Defaults to the hostname of the machine.
+    self_hostname: Option<String>,
+
+    /// The key shared with the clients.
+    shared_key: SensitiveString,
+
+    /// The users the clients authenticate as.
+    ///
+    /// When set, the clients must also authenticate with the username and password of one of them.
+    #[serde(default)]
+    users: Vec<FluentUserConfig>,
+}
+
+/// A user the clients authenticate as.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct FluentUserConfig {
+    /// The username of the user.
+    username: String,
+
+    /// The password of the user.
+    password: SensitiveString,
+}
+
+impl FluentSecurityConfig {
+    fn build(&self) -> crate::Result<Handshake> {
+        let self_hostname = match &self.self_hostname {
+            Some(hostname) => hostname.clone(),
+            None => crate::get_hostname()?,
+        };
+        let users = self
+            .users
+            .iter()
+            .map(|user| (user.username.clone(), user.password.clone()))
+            .collect();
+        Ok(Handshake::new(
+            self_hostname,
+            self.shared_key.clone(),
+            users,
+        ))
+    }
+}
+
 impl GenerateConfig for FluentConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
@@ -61,6 +126,7 @@ impl GenerateConfig for FluentConfig {
             keepalive: None,
             tls: None,
             receive_buffer_bytes: None,
+            security: None,
             acknowledgements: Default::default(),
             connection_limit: Some(2),
         })
@@ -71,7 +137,12 @@ impl GenerateConfig for FluentConfig {
 #[async_trait::async_trait]
 impl SourceConfig for FluentConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
-        let source = FluentSource {};
+        let handshake = self
+            .security
+            .as_ref()
+            .map(FluentSecurityConfig::build)
+            .transpose()?;
+        let source = FluentSource { handshake };
         let shutdown_secs = 30;
         let tls_config = self.tls.as_ref().map(|tls| tls.tls_config.clone());
         let tls_client_metadata_key = self
@@ -106,7 +177,9 @@ impl SourceConfig for FluentConfig {
 }
 
 #[derive(Debug, Clone)]
-struct FluentSource;
+struct FluentSource {
+    handshake: Option<Handshake>,
+}
 
 impl TcpSource for FluentSource {
     type Error = DecodeError;
@@ -131,6 +204,22 @@ impl TcpSource for FluentSource {
     fn build_acker(&self, frame: &[Self::Item]) -> Self::Acker {
         FluentAcker::new(frame)
     }
+
+    fn handshake<'a>(
+        &'a self,
+        socket: &'a mut MaybeTlsIncomingStream<TcpStream>,
+    ) -> BoxFuture<'a, Result<BytesMut, ()>> {
+        async move {
+            match &self.handshake {
+                None => Ok(BytesMut::new()),
+                Some(handshake) => handshake
+                    .run(socket)
+                    .await
+                    .map_err(|error| emit!(FluentHandshakeError { error })),
+            }
+        }
+        .boxed()
+    }
 }
 
 #[derive(Debug)]
@@ -403,18 +492,17 @@ impl FluentAcker {
 }
 
 impl TcpSourceAcker for FluentAcker {
+    // The clients resend the chunks that aren't acknowledged, so nothing is sent back for the
+    // chunks that failed.
     fn build_ack(self, ack: TcpSourceAck) -> Option<Bytes> {
-        if self.chunks.is_empty() {
+        if self.chunks.is_empty() || ack != TcpSourceAck::Ack {
             return None;
         }
 
-        let mut acks = String::new();
+        let mut acks = Vec::new();
         for chunk in self.chunks {
-            let ack = match ack {
-                TcpSourceAck::Ack => format!(r#"{{"ack": "{}"}}"#, chunk),
-                _ => String::from("{}"),
-            };
-            acks.push_str(&ack);
+            let ack = rmpv::Value::Map(vec![("ack".into(), chunk.into())]);
+            rmpv::encode::write_value(&mut acks, &ack).expect("writing to a Vec never fails");
         }
         Some(acks.into())
     }
@@ -840,7 +928,12 @@ mod tests {
     async fn ack_delivered_with_chunk() {
         let (result, output) = check_acknowledgements(EventStatus::Delivered, true).await;
         assert_eq!(result.unwrap().unwrap(), output.len());
-        assert!(output.starts_with(b"{\"ack\":"));
+
+        let ack = rmpv::decode::read_value(&mut &output[..]).unwrap();
+        let ack = ack.as_map().unwrap();
+        assert_eq!(ack.len(), 1);
+        assert_eq!(ack[0].0.as_str(), Some("ack"));
+        assert!(ack[0].1.is_str());
     }
 
     #[tokio::test]
@@ -854,7 +947,7 @@ mod tests {
     async fn ack_failed_with_chunk() {
         let (result, output) = check_acknowledgements(EventStatus::Rejected, true).await;
         assert_eq!(result.unwrap().unwrap(), output.len());
-        assert_eq!(output, &b"{}"[..]);
+        assert!(output.is_empty());
     }
 
     async fn check_acknowledgements(
@@ -870,6 +963,7 @@ mod tests {
             tls: None,
             keepalive: None,
             receive_buffer_bytes: None,
+            security: None,
             acknowledgements: true.into(),
             connection_limit: None,
         }
@@ -1119,6 +1213,7 @@ mod integration_tests {
                 tls: None,
                 keepalive: None,
                 receive_buffer_bytes: None,
+                security: None,
                 acknowledgements: false.into(),
                 connection_limit: None,
             }
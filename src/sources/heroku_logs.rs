@@ -114,7 +114,7 @@ impl SourceConfig for LogplexConfig {
             decoder,
         };
         source.run(
-            self.address,
+            self.address.into(),
             "events",
             HttpMethod::Post,
             true,
@@ -26,8 +26,8 @@ impl HostMetrics {
                 for (index, times) in times.into_iter().enumerate() {
                     let tags = |name: &str| {
                         BTreeMap::from([
-                            (String::from(MODE), String::from(name)),
-                            (String::from("cpu"), index.to_string()),
+                            (MODE.into(), String::from(name)),
+                            ("cpu".into(), index.to_string()),
                         ])
                     };
                     output.counter(CPU_SECS_TOTAL, times.idle().get::<second>(), tags("idle"));
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, net::SocketAddr};
+use std::collections::HashMap;
 
 use bytes::{Bytes, BytesMut};
 use chrono::Utc;
@@ -24,7 +24,7 @@ use crate::{
     serde::{bool_or_struct, default_decoding},
     sources::util::{
         add_query_parameters, http::HttpMethod, Encoding, ErrorMessage, HttpSource,
-        HttpSourceAuthConfig,
+        HttpSourceAuthConfig, SocketListenAddr,
     },
     tls::TlsEnableableConfig,
 };
@@ -34,7 +34,10 @@ use crate::{
 #[derive(Clone, Debug)]
 pub struct SimpleHttpConfig {
     /// The address to listen for connections on.
-    address: SocketAddr,
+    ///
+    /// Can also be `systemd` or `systemd#N` for a socket passed by `systemd` socket activation, or
+    /// `fd#N` for a socket inherited from the process that started Vector.
+    address: SocketListenAddr,
 
     /// The expected encoding of received data.
     ///
@@ -96,7 +99,7 @@ pub struct SimpleHttpConfig {
 impl GenerateConfig for SimpleHttpConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
-            address: "0.0.0.0:8080".parse().unwrap(),
+            address: SocketListenAddr::SocketAddr("0.0.0.0:8080".parse().unwrap()),
             encoding: None,
             headers: Vec::new(),
             query_parameters: Vec::new(),
@@ -244,7 +247,7 @@ impl SourceConfig for SimpleHttpConfig {
     }
 
     fn resources(&self) -> Vec<Resource> {
-        vec![Resource::tcp(self.address)]
+        vec![self.address.into()]
     }
 
     fn can_acknowledge(&self) -> bool {
@@ -335,7 +338,7 @@ mod tests {
 
         tokio::spawn(async move {
             SimpleHttpConfig {
-                address,
+                address: address.into(),
                 headers,
                 encoding: None,
                 query_parameters,
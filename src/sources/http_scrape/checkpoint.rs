@@ -0,0 +1,132 @@
+//! The checkpoint that the scrapes of the `http_scrape` source resume from.
+
+use tokio::sync::watch;
+use vector_config::configurable_component;
+use vector_core::event::Value;
+
+use crate::{internal_events::HttpScrapeCheckpointError, state_store::BoxedStateStore};
+
+const CHECKPOINT_KEY: &str = "checkpoint.txt";
+
+/// Resuming each scrape from the position of the last event received.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SinceConfig {
+    /// The field of the events holding their position, such as `timestamp` or `id`.
+    ///
+    /// After each complete scrape, the greatest value of the field becomes the checkpoint of the
+    /// next scrape. The checkpoint is persisted across restarts.
+    pub field: String,
+
+    /// The checkpoint of the first scrape.
+    pub initial: String,
+
+    /// The query parameter to send the checkpoint in.
+    ///
+    /// The checkpoint is also available to `body` as `{{ since }}`.
+    pub param: Option<String>,
+}
+
+/// The checkpoint shared by the scrapes, persisted in the state store of the source.
+#[derive(Debug)]
+pub(super) struct Checkpoint {
+    config: SinceConfig,
+    since: watch::Sender<String>,
+}
+
+impl Checkpoint {
+    /// Loads the persisted checkpoint, if any, or starts from the initial one.
+    ///
+    /// The checkpoints are persisted in the background, by a task that ends with the checkpoint.
+    pub(super) async fn load(store: BoxedStateStore, config: SinceConfig) -> crate::Result<Self> {
+        let since = match store.get(CHECKPOINT_KEY).await? {
+            Some(since) => String::from_utf8_lossy(&since)
+                .trim_end_matches('\n')
+                .to_string(),
+            None => config.initial.clone(),
+        };
+
+        let (since, mut receiver) = watch::channel(since);
+        tokio::spawn(async move {
+            // Only the latest checkpoint is persisted, when they are set faster than they are
+            // written.
+            while receiver.changed().await.is_ok() {
+                let since = format!("{}\n", *receiver.borrow_and_update());
+                if let Err(error) = store.set(CHECKPOINT_KEY, since.into()).await {
+                    emit!(HttpScrapeCheckpointError { error });
+                }
+            }
+        });
+
+        Ok(Self { config, since })
+    }
+
+    pub(super) const fn config(&self) -> &SinceConfig {
+        &self.config
+    }
+
+    pub(super) fn get(&self) -> String {
+        self.since.borrow().clone()
+    }
+
+    /// Sets the checkpoint, which is persisted in the background.
+    pub(super) fn set(&self, since: String) {
+        self.since.send_replace(since);
+    }
+}
+
+/// Returns whether the position of an event is after another one.
+///
+/// Positions of different types can't be compared, so the latest one is used.
+pub(super) fn is_after(position: &Value, other: &Value) -> bool {
+    match (position, other) {
+        (Value::Integer(position), Value::Integer(other)) => position > other,
+        (Value::Float(position), Value::Float(other)) => position > other,
+        (Value::Timestamp(position), Value::Timestamp(other)) => position > other,
+        (Value::Bytes(position), Value::Bytes(other)) => position > other,
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+    use crate::state_store::FileStateStore;
+
+    #[tokio::test]
+    async fn persists_checkpoint() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let store: BoxedStateStore = Arc::new(FileStateStore::new(data_dir.path().to_path_buf()));
+        let config = SinceConfig {
+            field: "timestamp".to_string(),
+            initial: "2022-01-01".to_string(),
+            param: None,
+        };
+
+        let checkpoint = Checkpoint::load(Arc::clone(&store), config.clone())
+            .await
+            .unwrap();
+        assert_eq!(checkpoint.get(), "2022-01-01");
+        checkpoint.set("2022-06-01".to_string());
+        drop(checkpoint);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let checkpoint = Checkpoint::load(store, config).await.unwrap();
+        assert_eq!(checkpoint.get(), "2022-06-01");
+    }
+
+    #[test]
+    fn compares_positions() {
+        assert!(is_after(&Value::Integer(10), &Value::Integer(9)));
+        assert!(!is_after(&Value::from("a"), &Value::from("b")));
+        assert!(is_after(
+            &Value::from(Utc.ymd(2022, 6, 1).and_hms(0, 0, 0)),
+            &Value::from(Utc.ymd(2022, 1, 1).and_hms(0, 0, 0)),
+        ));
+    }
+}
@@ -0,0 +1,117 @@
+//! Headers computed for each request of the `http_scrape` source by a VRL program.
+
+use bytes::Bytes;
+use http::{header::HeaderName, HeaderValue, Request};
+use vector_common::TimeZone;
+use vector_core::compile_vrl;
+use vrl::{diagnostic::Formatter, CompilationResult, CompileConfig, Program, Runtime};
+
+use crate::event::{Event, LogEvent, TargetEvents, Value, VrlTarget};
+
+/// Compiles the program computing the headers.
+pub(super) fn compile(source: &str) -> crate::Result<Program> {
+    let functions = vrl_stdlib::all()
+        .into_iter()
+        .chain(vector_vrl_functions::vrl_functions())
+        .collect::<Vec<_>>();
+
+    let state = vrl::state::TypeState::default();
+
+    let mut config = CompileConfig::default();
+    config.set_read_only();
+
+    let CompilationResult {
+        program,
+        warnings,
+        config: _,
+    } = compile_vrl(source, &functions, &state, config)
+        .map_err(|diagnostics| Formatter::new(source, diagnostics).colored().to_string())?;
+
+    if !warnings.is_empty() {
+        let warnings = Formatter::new(source, warnings).colored().to_string();
+        warn!(message = "VRL compilation warning.", %warnings);
+    }
+
+    Ok(program)
+}
+
+/// Runs the program against the request, returning the headers of the object it resolves to.
+///
+/// The program can read the `url`, `method` and `body` of the request, along with the fields
+/// that the body template can refer to.
+pub(super) fn compute(
+    program: &Program,
+    mut fields: LogEvent,
+    request: &Request<Bytes>,
+) -> Result<Vec<(HeaderName, HeaderValue)>, String> {
+    fields.insert("url", request.uri().to_string());
+    fields.insert("method", request.method().as_str());
+    fields.insert("body", request.body().clone());
+
+    let mut target = VrlTarget::new(Event::Log(fields), program.info());
+    let timezone = TimeZone::default();
+    let result = Runtime::default().resolve(&mut target, program, &timezone);
+    if !matches!(target.into_events(), TargetEvents::One(_)) {
+        panic!("Event was modified by a read-only program. This is an internal compiler error.");
+    }
+
+    match result.map_err(|error| error.to_string())? {
+        Value::Null => Ok(Vec::new()),
+        Value::Object(headers) => headers
+            .into_iter()
+            .map(|(name, value)| {
+                let value = match value {
+                    Value::Bytes(value) => value,
+                    value => {
+                        return Err(format!(
+                            "header {:?} must be a string, not {}",
+                            name,
+                            value.kind_str()
+                        ))
+                    }
+                };
+                let name =
+                    HeaderName::try_from(name.as_str()).map_err(|error| error.to_string())?;
+                let value =
+                    HeaderValue::from_maybe_shared(value).map_err(|error| error.to_string())?;
+                Ok((name, value))
+            })
+            .collect(),
+        value => Err(format!(
+            "program must resolve to an object of headers, not {}",
+            value.kind_str()
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn computes_headers() {
+        let program = compile(
+            r#"
+            {
+                "X-Signature": sha2(string!(.method) + " " + string!(.url) + " " + string!(.body)),
+                "X-Since": .since,
+            }
+            "#,
+        )
+        .unwrap();
+        let request = Request::post("http://example.com/logs")
+            .body(Bytes::from("{}"))
+            .unwrap();
+        let mut fields = LogEvent::default();
+        fields.insert("since", "2022-01-01");
+
+        let headers = compute(&program, fields, &request).unwrap();
+        assert_eq!(headers.len(), 2);
+        assert_eq!(headers[1].0, "x-since");
+        assert_eq!(headers[1].1, "2022-01-01");
+
+        // Headers must be strings.
+        let program = compile(r#"{ "X-Count": 1 }"#).unwrap();
+        assert!(compute(&program, LogEvent::default(), &request).is_err());
+    }
+}
@@ -8,9 +8,9 @@ use tokio::time::{Duration, Instant};
 use crate::sources::util::http::HttpMethod;
 use crate::{
     config::{ComponentKey, SourceConfig, SourceContext},
-    http::Auth,
     serde::default_decoding,
     serde::default_framing_message_based,
+    sources::util::http_scrape_auth::HttpScrapeAuth,
     tls,
     tls::TlsConfig,
     SourceSender,
@@ -58,7 +58,14 @@ async fn invalid_endpoint() {
         decoding: default_decoding(),
         framing: default_framing_message_based(),
         headers: HashMap::new(),
+        headers_vrl: None,
         method: HttpMethod::Get,
+        body: None,
+        split_pointer: None,
+        pagination: None,
+        since: None,
+        data_dir: None,
+        state_store: Default::default(),
         auth: None,
         tls: None,
         log_namespace: None,
@@ -76,7 +83,14 @@ async fn scraped_logs_bytes() {
         decoding: DeserializerConfig::Bytes,
         framing: default_framing_message_based(),
         headers: HashMap::new(),
+        headers_vrl: None,
         method: HttpMethod::Get,
+        body: None,
+        split_pointer: None,
+        pagination: None,
+        since: None,
+        data_dir: None,
+        state_store: Default::default(),
         auth: None,
         tls: None,
         log_namespace: None,
@@ -97,10 +111,19 @@ async fn scraped_logs_json() {
         endpoint: format!("{}/logs/json.json", dufs_address()),
         scrape_interval_secs: INTERVAL_SECS,
         query: HashMap::new(),
-        decoding: DeserializerConfig::Json,
+        decoding: DeserializerConfig::Json {
+            json: Default::default(),
+        },
         framing: default_framing_message_based(),
         headers: HashMap::new(),
+        headers_vrl: None,
         method: HttpMethod::Get,
+        body: None,
+        split_pointer: None,
+        pagination: None,
+        since: None,
+        data_dir: None,
+        state_store: Default::default(),
         auth: None,
         tls: None,
         log_namespace: None,
@@ -124,7 +147,14 @@ async fn scraped_metrics_native_json() {
         decoding: DeserializerConfig::NativeJson,
         framing: default_framing_message_based(),
         headers: HashMap::new(),
+        headers_vrl: None,
         method: HttpMethod::Get,
+        body: None,
+        split_pointer: None,
+        pagination: None,
+        since: None,
+        data_dir: None,
+        state_store: Default::default(),
         auth: None,
         tls: None,
         log_namespace: None,
@@ -149,7 +179,14 @@ async fn scraped_trace_native_json() {
         decoding: DeserializerConfig::NativeJson,
         framing: default_framing_message_based(),
         headers: HashMap::new(),
+        headers_vrl: None,
         method: HttpMethod::Get,
+        body: None,
+        split_pointer: None,
+        pagination: None,
+        since: None,
+        data_dir: None,
+        state_store: Default::default(),
         auth: None,
         tls: None,
         log_namespace: None,
@@ -170,10 +207,19 @@ async fn unauthorized_no_auth() {
         endpoint: format!("{}/logs/json.json", dufs_auth_address()),
         scrape_interval_secs: INTERVAL_SECS,
         query: HashMap::new(),
-        decoding: DeserializerConfig::Json,
+        decoding: DeserializerConfig::Json {
+            json: Default::default(),
+        },
         framing: default_framing_message_based(),
         headers: HashMap::new(),
+        headers_vrl: None,
         method: HttpMethod::Get,
+        body: None,
+        split_pointer: None,
+        pagination: None,
+        since: None,
+        data_dir: None,
+        state_store: Default::default(),
         auth: None,
         tls: None,
         log_namespace: None,
@@ -188,12 +234,21 @@ async fn unauthorized_wrong_auth() {
         endpoint: format!("{}/logs/json.json", dufs_auth_address()),
         scrape_interval_secs: INTERVAL_SECS,
         query: HashMap::new(),
-        decoding: DeserializerConfig::Json,
+        decoding: DeserializerConfig::Json {
+            json: Default::default(),
+        },
         framing: default_framing_message_based(),
         headers: HashMap::new(),
+        headers_vrl: None,
         method: HttpMethod::Get,
+        body: None,
+        split_pointer: None,
+        pagination: None,
+        since: None,
+        data_dir: None,
+        state_store: Default::default(),
         tls: None,
-        auth: Some(Auth::Basic {
+        auth: Some(HttpScrapeAuth::Basic {
             user: "white_rabbit".to_string(),
             password: "morpheus".to_string().into(),
         }),
@@ -209,12 +264,21 @@ async fn authorized() {
         endpoint: format!("{}/logs/json.json", dufs_auth_address()),
         scrape_interval_secs: INTERVAL_SECS,
         query: HashMap::new(),
-        decoding: DeserializerConfig::Json,
+        decoding: DeserializerConfig::Json {
+            json: Default::default(),
+        },
         framing: default_framing_message_based(),
         headers: HashMap::new(),
+        headers_vrl: None,
         method: HttpMethod::Get,
+        body: None,
+        split_pointer: None,
+        pagination: None,
+        since: None,
+        data_dir: None,
+        state_store: Default::default(),
         tls: None,
-        auth: Some(Auth::Basic {
+        auth: Some(HttpScrapeAuth::Basic {
             user: "user".to_string(),
             password: "pass".to_string().into(),
         }),
@@ -230,10 +294,19 @@ async fn tls_invalid_ca() {
         endpoint: format!("{}/logs/json.json", dufs_https_address()),
         scrape_interval_secs: INTERVAL_SECS,
         query: HashMap::new(),
-        decoding: DeserializerConfig::Json,
+        decoding: DeserializerConfig::Json {
+            json: Default::default(),
+        },
         framing: default_framing_message_based(),
         headers: HashMap::new(),
+        headers_vrl: None,
         method: HttpMethod::Get,
+        body: None,
+        split_pointer: None,
+        pagination: None,
+        since: None,
+        data_dir: None,
+        state_store: Default::default(),
         tls: Some(TlsConfig {
             ca_file: Some("tests/data/http-scrape/certs/invalid-ca-cert.pem".into()),
             ..Default::default()
@@ -251,10 +324,19 @@ async fn tls_valid() {
         endpoint: format!("{}/logs/json.json", dufs_https_address()),
         scrape_interval_secs: INTERVAL_SECS,
         query: HashMap::new(),
-        decoding: DeserializerConfig::Json,
+        decoding: DeserializerConfig::Json {
+            json: Default::default(),
+        },
         framing: default_framing_message_based(),
         headers: HashMap::new(),
+        headers_vrl: None,
         method: HttpMethod::Get,
+        body: None,
+        split_pointer: None,
+        pagination: None,
+        since: None,
+        data_dir: None,
+        state_store: Default::default(),
         tls: Some(TlsConfig {
             ca_file: Some(tls::TEST_PEM_CA_PATH.into()),
             ..Default::default()
@@ -273,10 +355,19 @@ async fn shutdown() {
         endpoint: format!("{}/logs/json.json", dufs_address()),
         scrape_interval_secs: INTERVAL_SECS,
         query: HashMap::new(),
-        decoding: DeserializerConfig::Json,
+        decoding: DeserializerConfig::Json {
+            json: Default::default(),
+        },
         framing: default_framing_message_based(),
         headers: HashMap::new(),
+        headers_vrl: None,
         method: HttpMethod::Get,
+        body: None,
+        split_pointer: None,
+        pagination: None,
+        since: None,
+        data_dir: None,
+        state_store: Default::default(),
         tls: None,
         auth: None,
         log_namespace: None,
@@ -1,4 +1,10 @@
 #[cfg(feature = "sources-http_scrape")]
+mod checkpoint;
+#[cfg(feature = "sources-http_scrape")]
+mod headers;
+#[cfg(feature = "sources-http_scrape")]
+mod pagination;
+#[cfg(feature = "sources-http_scrape")]
 pub mod scrape;
 
 #[cfg(test)]
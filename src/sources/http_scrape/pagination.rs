@@ -0,0 +1,287 @@
+//! Pagination of the responses of the `http_scrape` source.
+
+use bytes::Bytes;
+use http::{response::Parts, Uri};
+use vector_config::configurable_component;
+use vector_core::event::LogEvent;
+
+use crate::{
+    internal_events::{HttpScrapeMaxPagesReached, HttpScrapeResponseParseError},
+    sources::util::http_scrape::set_query_param,
+};
+
+/// Pagination of the responses.
+///
+/// The pages of a scrape are requested one after the other, until the last one.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct PaginationConfig {
+    #[configurable(derived)]
+    #[serde(flatten)]
+    pub strategy: PaginationStrategy,
+
+    /// The most pages requested in a single scrape.
+    ///
+    /// Once reached, the scrape ends without requesting the next pages.
+    #[serde(default = "default_max_pages")]
+    pub max_pages: usize,
+}
+
+/// How the next page of a response is found.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "strategy", rename_all = "snake_case")]
+pub enum PaginationStrategy {
+    /// The cursor of the next page is read from each JSON response.
+    Cursor {
+        /// A JSON pointer to the cursor in the response, such as `/meta/next_cursor`.
+        ///
+        /// The scrape ends with the first response without a cursor.
+        cursor_pointer: String,
+
+        /// The query parameter to send the cursor in.
+        ///
+        /// The cursor is also available to `body` as `{{ cursor }}`.
+        param: Option<String>,
+    },
+
+    /// The URL of the next page is read from the `next` link of the `Link` header of each response.
+    LinkHeader,
+
+    /// The pages are requested by number, until a page has no events.
+    Page {
+        /// The query parameter to send the page number in.
+        ///
+        /// The page number is also available to `body` as `{{ page }}`.
+        param: Option<String>,
+
+        /// The number of the first page.
+        #[serde(default = "default_first_page")]
+        first_page: u64,
+    },
+}
+
+const fn default_max_pages() -> usize {
+    100
+}
+
+const fn default_first_page() -> u64 {
+    1
+}
+
+/// The pagination state of a scrape.
+#[derive(Clone, Debug)]
+pub(super) struct Paginator {
+    config: PaginationConfig,
+    pages: usize,
+    cursor: Option<String>,
+    page: u64,
+}
+
+impl Paginator {
+    pub(super) fn new(config: PaginationConfig) -> Self {
+        let page = match config.strategy {
+            PaginationStrategy::Page { first_page, .. } => first_page,
+            _ => 0,
+        };
+        Self {
+            config,
+            pages: 0,
+            cursor: None,
+            page,
+        }
+    }
+
+    /// Sets the query parameter of the current page on the url.
+    pub(super) fn apply(&self, url: &Uri) -> Uri {
+        match &self.config.strategy {
+            PaginationStrategy::Cursor {
+                param: Some(param), ..
+            } => match &self.cursor {
+                Some(cursor) => set_query_param(url, param, cursor),
+                None => url.clone(),
+            },
+            PaginationStrategy::Page {
+                param: Some(param), ..
+            } => set_query_param(url, param, &self.page.to_string()),
+            _ => url.clone(),
+        }
+    }
+
+    /// Adds the fields of the current page that request bodies can refer to.
+    pub(super) fn insert_fields(&self, fields: &mut LogEvent) {
+        match &self.config.strategy {
+            PaginationStrategy::Cursor { .. } => {
+                if let Some(cursor) = &self.cursor {
+                    fields.insert("cursor", cursor.clone());
+                }
+            }
+            PaginationStrategy::Page { .. } => {
+                fields.insert("page", self.page as i64);
+            }
+            PaginationStrategy::LinkHeader => {}
+        }
+    }
+
+    /// Moves to the next page of the response, returning its url if there is one.
+    pub(super) fn next_page(
+        &mut self,
+        url: &Uri,
+        header: &Parts,
+        body: &Bytes,
+        events: usize,
+    ) -> Option<Uri> {
+        let next_page = match &self.config.strategy {
+            PaginationStrategy::Cursor { cursor_pointer, .. } => {
+                self.cursor = cursor(url, body, cursor_pointer);
+                self.cursor.as_ref().map(|_| url.clone())
+            }
+            PaginationStrategy::LinkHeader => next_link(url, header),
+            PaginationStrategy::Page { .. } => {
+                self.page += 1;
+                (events > 0).then(|| url.clone())
+            }
+        }?;
+
+        self.pages += 1;
+        if self.pages >= self.config.max_pages {
+            emit!(HttpScrapeMaxPagesReached {
+                max_pages: self.config.max_pages,
+                url: url.to_string(),
+            });
+            return None;
+        }
+        Some(next_page)
+    }
+}
+
+/// Reads the cursor of the next page from a JSON response.
+fn cursor(url: &Uri, body: &Bytes, pointer: &str) -> Option<String> {
+    let response = serde_json::from_slice::<serde_json::Value>(body)
+        .map_err(|error| {
+            emit!(HttpScrapeResponseParseError {
+                error,
+                url: url.to_string(),
+            })
+        })
+        .ok()?;
+
+    match response.pointer(pointer)? {
+        serde_json::Value::String(cursor) if !cursor.is_empty() => Some(cursor.clone()),
+        serde_json::Value::Number(cursor) => Some(cursor.to_string()),
+        _ => None,
+    }
+}
+
+/// Finds the `next` link of the `Link` headers of a response, resolved against its url.
+fn next_link(url: &Uri, header: &Parts) -> Option<Uri> {
+    let target = header
+        .headers
+        .get_all(http::header::LINK)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        // Each link starts with its target between angle brackets, which may contain commas.
+        .flat_map(|value| value.split('<').skip(1))
+        .find_map(|link| {
+            let (target, params) = link.split_once('>')?;
+            params
+                .split(';')
+                .filter_map(|param| param.trim().strip_prefix("rel="))
+                .any(|rel| {
+                    rel.trim_matches('"')
+                        .split_whitespace()
+                        .any(|rel| rel.eq_ignore_ascii_case("next"))
+                })
+                .then(|| target.trim())
+        })?;
+
+    url::Url::parse(&url.to_string())
+        .and_then(|url| url.join(target))
+        .ok()?
+        .as_str()
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use http::Response;
+
+    use super::*;
+
+    fn header(link: &str) -> Parts {
+        let (header, _) = Response::builder()
+            .header("Link", link)
+            .body(())
+            .unwrap()
+            .into_parts();
+        header
+    }
+
+    #[test]
+    fn finds_next_link() {
+        let url = "http://example.com/api/items?page=1".parse().unwrap();
+        let header = header(
+            r#"<http://example.com/api/items?page=1>; rel="prev", </api/items?page=3&fields=a,b>; rel="next last""#,
+        );
+
+        assert_eq!(
+            next_link(&url, &header).unwrap(),
+            "http://example.com/api/items?page=3&fields=a,b"
+        );
+        assert!(next_link(&url, &self::header(r#"<http://example.com/>; rel="prev""#)).is_none());
+    }
+
+    #[test]
+    fn follows_cursors() {
+        let config: PaginationConfig = toml::from_str(
+            r#"
+            strategy = "cursor"
+            cursor_pointer = "/meta/next"
+            param = "after"
+            max_pages = 2
+            "#,
+        )
+        .unwrap();
+        let mut paginator = Paginator::new(config);
+        let url = "http://example.com/items?limit=10".parse().unwrap();
+        let header = header("");
+
+        let body = Bytes::from(r#"{"meta": {"next": "abc"}}"#);
+        let next = paginator.next_page(&url, &header, &body, 10).unwrap();
+        assert_eq!(
+            paginator.apply(&next),
+            "http://example.com/items?limit=10&after=abc"
+        );
+
+        // The scrape ends once `max_pages` are requested.
+        let body = Bytes::from(r#"{"meta": {"next": "def"}}"#);
+        assert!(paginator.next_page(&url, &header, &body, 10).is_none());
+    }
+
+    #[test]
+    fn counts_pages() {
+        let config: PaginationConfig = toml::from_str(
+            r#"
+            strategy = "page"
+            param = "page"
+            first_page = 0
+            "#,
+        )
+        .unwrap();
+        let mut paginator = Paginator::new(config);
+        let url = "http://example.com/items?page=7".parse().unwrap();
+        let header = header("");
+
+        assert_eq!(paginator.apply(&url), "http://example.com/items?page=0");
+        let next = paginator
+            .next_page(&url, &header, &Bytes::new(), 10)
+            .unwrap();
+        assert_eq!(paginator.apply(&next), "http://example.com/items?page=1");
+
+        // A page without events is the last one.
+        assert!(paginator
+            .next_page(&url, &header, &Bytes::new(), 0)
+            .is_none());
+    }
+}
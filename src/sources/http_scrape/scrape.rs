@@ -4,25 +4,35 @@
 use bytes::{Bytes, BytesMut};
 use chrono::Utc;
 use futures_util::FutureExt;
-use http::{response::Parts, Uri};
+use http::{response::Parts, Request, Uri};
 use snafu::ResultExt;
-use std::collections::HashMap;
+use std::{collections::HashMap, path::PathBuf, sync::Arc};
 use tokio_util::codec::Decoder as _;
 
+use super::{
+    checkpoint::{is_after, Checkpoint, SinceConfig},
+    headers,
+    pagination::{PaginationConfig, Paginator},
+};
 use crate::{
     codecs::{Decoder, DecodingConfig},
     config::{SourceConfig, SourceContext},
-    http::Auth,
+    internal_events::{
+        HttpScrapeHeadersError, HttpScrapeResponseParseError, TemplateRenderingError,
+    },
     serde::default_decoding,
     serde::default_framing_message_based,
     sources,
     sources::util::{
         http::HttpMethod,
         http_scrape::{
-            build_url, default_scrape_interval_secs, http_scrape, GenericHttpScrapeInputs,
-            HttpScraperBuilder, HttpScraperContext,
+            build_url, default_scrape_interval_secs, http_scrape, set_query_param,
+            GenericHttpScrapeInputs, HttpScraperBuilder, HttpScraperContext,
         },
+        http_scrape_auth::{HttpScrapeAuth, RequestAuth},
     },
+    state_store::StateStoreConfig,
+    template::Template,
     tls::{TlsConfig, TlsSettings},
     Result,
 };
@@ -33,7 +43,7 @@ use codecs::{
 use vector_config::{configurable_component, NamedComponent};
 use vector_core::{
     config::{log_schema, LogNamespace, Output},
-    event::Event,
+    event::{Event, LogEvent, Value},
 };
 
 /// Configuration for the `http_scrape` source.
@@ -70,17 +80,53 @@ pub struct HttpScrapeConfig {
     #[serde(default)]
     pub headers: HashMap<String, Vec<String>>,
 
+    /// A VRL program computing headers for each HTTP request, such as signatures or timestamps.
+    ///
+    /// The program can read the `url`, `method` and `body` of the request, and the `since`,
+    /// `cursor` and `page` fields that `body` can refer to. It must resolve to an object whose
+    /// fields are set as headers, replacing the headers of `headers`.
+    pub headers_vrl: Option<String>,
+
     /// Specifies the action of the HTTP request.
     #[serde(default = "default_http_method")]
     pub method: HttpMethod,
 
+    /// The body of the HTTP requests.
+    ///
+    /// It is rendered before each request, and can refer to the checkpoint of `since` as
+    /// `{{ since }}`, and to the cursor or number of the current page of `pagination` as
+    /// `{{ cursor }}` or `{{ page }}`.
+    #[configurable(metadata(templateable))]
+    pub body: Option<Template>,
+
+    /// A JSON pointer, such as `/data/items`, to the array of each response whose elements are
+    /// decoded as separate events.
+    ///
+    /// The elements are decoded with `decoding`. By default, the whole response is decoded.
+    pub split_pointer: Option<String>,
+
+    #[configurable(derived)]
+    pub pagination: Option<PaginationConfig>,
+
+    #[configurable(derived)]
+    pub since: Option<SinceConfig>,
+
+    /// The directory used to persist the checkpoint of `since`, when `state_store` is `file`.
+    ///
+    /// By default, the global `data_dir` option is used. Please make sure the user Vector is running as has write permissions to this directory.
+    pub data_dir: Option<PathBuf>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub state_store: StateStoreConfig,
+
     /// TLS configuration.
     #[configurable(derived)]
     pub tls: Option<TlsConfig>,
 
     /// HTTP Authentication.
     #[configurable(derived)]
-    pub auth: Option<Auth>,
+    pub auth: Option<HttpScrapeAuth>,
 
     /// The namespace to use for logs. This overrides the global setting
     #[serde(default)]
@@ -100,7 +146,14 @@ impl Default for HttpScrapeConfig {
             decoding: default_decoding(),
             framing: default_framing_message_based(),
             headers: HashMap::new(),
+            headers_vrl: None,
             method: default_http_method(),
+            body: None,
+            split_pointer: None,
+            pagination: None,
+            since: None,
+            data_dir: None,
+            state_store: StateStoreConfig::default(),
             tls: None,
             auth: None,
             log_namespace: None,
@@ -131,10 +184,36 @@ impl SourceConfig for HttpScrapeConfig {
 
         let content_type = self.decoding.content_type(&self.framing).to_string();
 
-        // the only specific context needed is the codec decoding
+        let since = match &self.since {
+            Some(since) => {
+                let store = self.state_store.build(&cx, self.data_dir.as_ref()).await?;
+                Some(Arc::new(Checkpoint::load(store, since.clone()).await?))
+            }
+            None => None,
+        };
+
+        let headers_vrl = self
+            .headers_vrl
+            .as_deref()
+            .map(headers::compile)
+            .transpose()?;
+
+        let auth = match &self.auth {
+            Some(auth) => Some(RequestAuth::build(auth).await?),
+            None => None,
+        };
+
         let context = HttpScrapeContext {
             decoder,
             log_namespace,
+            body: self.body.clone(),
+            headers_vrl,
+            split_pointer: self.split_pointer.clone(),
+            pagination: self.pagination.clone(),
+            since,
+            paginator: None,
+            page_events: 0,
+            latest: None,
         };
 
         let inputs = GenericHttpScrapeInputs {
@@ -142,10 +221,11 @@ impl SourceConfig for HttpScrapeConfig {
             interval_secs: self.scrape_interval_secs,
             headers: self.headers.clone(),
             content_type,
-            auth: self.auth.clone(),
+            auth,
             tls,
             proxy: cx.proxy.clone(),
             shutdown: cx.shutdown,
+            once: cx.once,
         };
 
         Ok(http_scrape(inputs, context, cx.out, self.method).boxed())
@@ -167,13 +247,29 @@ impl SourceConfig for HttpScrapeConfig {
     fn can_acknowledge(&self) -> bool {
         false
     }
+
+    fn can_run_once(&self) -> bool {
+        true
+    }
 }
 
-/// Captures the configuration options required to decode the incoming requests into events.
+/// Captures the configuration options required to request and decode the pages of each scrape
+/// into events, and the state of the current scrape.
 #[derive(Clone)]
 struct HttpScrapeContext {
     decoder: Decoder,
     log_namespace: LogNamespace,
+    body: Option<Template>,
+    headers_vrl: Option<vrl::Program>,
+    split_pointer: Option<String>,
+    pagination: Option<PaginationConfig>,
+    since: Option<Arc<Checkpoint>>,
+    /// The pagination state of the current scrape.
+    paginator: Option<Paginator>,
+    /// The number of events of the last page of the current scrape.
+    page_events: usize,
+    /// The greatest position of the events of the current scrape, used as the next checkpoint.
+    latest: Option<Value>,
 }
 
 impl HttpScrapeContext {
@@ -199,6 +295,58 @@ impl HttpScrapeContext {
         events
     }
 
+    /// Decodes each element of the array of the response that the JSON pointer refers to.
+    fn split_events(&mut self, url: &Uri, pointer: &str, body: &Bytes) -> Option<Vec<Event>> {
+        let response = serde_json::from_slice::<serde_json::Value>(body)
+            .map_err(|error| {
+                emit!(HttpScrapeResponseParseError {
+                    error,
+                    url: url.to_string(),
+                })
+            })
+            .ok()?;
+
+        let elements = match response.pointer(pointer) {
+            Some(serde_json::Value::Array(elements)) => elements.as_slice(),
+            Some(serde_json::Value::Null) => &[],
+            Some(element) => std::slice::from_ref(element),
+            None => {
+                emit!(HttpScrapeResponseParseError {
+                    error: format!("No value found at JSON pointer {:?}.", pointer),
+                    url: url.to_string(),
+                });
+                return None;
+            }
+        };
+
+        let mut events = Vec::new();
+        for element in elements {
+            let element = serde_json::to_vec(element).expect("JSON values are serializable");
+            let mut buf = BytesMut::from(element.as_slice());
+            events.extend(self.decode_events(&mut buf));
+        }
+        Some(events)
+    }
+
+    /// Keeps the greatest position of the events, to become the next checkpoint.
+    fn track_position(&mut self, events: &[Event]) {
+        let field = match &self.since {
+            Some(since) => since.config().field.as_str(),
+            None => return,
+        };
+        for event in events {
+            if let Some(position) = event.maybe_as_log().and_then(|log| log.get(field)) {
+                if self
+                    .latest
+                    .as_ref()
+                    .map_or(true, |latest| is_after(position, latest))
+                {
+                    self.latest = Some(position.clone());
+                }
+            }
+        }
+    }
+
     /// Enriches events with source_type, timestamp
     fn enrich_events(&self, events: &mut Vec<Event>) {
         for event in events {
@@ -237,9 +385,14 @@ impl HttpScrapeContext {
 impl HttpScraperBuilder for HttpScrapeContext {
     type Context = HttpScrapeContext;
 
-    /// No additional context from request data is needed from this particular scraper.
+    /// Starts a scrape from its first page.
     fn build(&self, _uri: &Uri) -> Self::Context {
-        self.clone()
+        HttpScrapeContext {
+            paginator: self.pagination.clone().map(Paginator::new),
+            page_events: 0,
+            latest: None,
+            ..self.clone()
+        }
     }
 }
 
@@ -247,19 +400,92 @@ impl HttpScraperContext for HttpScrapeContext {
     /// Decodes the HTTP response body into events per the decoder configured.
     fn on_response(
         &mut self,
-        _url: &http::Uri,
+        url: &http::Uri,
         _header: &Parts,
         body: &Bytes,
     ) -> Option<Vec<Event>> {
-        // get the body into a byte array
-        let mut buf = BytesMut::new();
-        let body = String::from_utf8_lossy(body);
-        buf.extend_from_slice(body.as_bytes());
+        self.page_events = 0;
+        let mut events = match self.split_pointer.clone() {
+            Some(pointer) => self.split_events(url, &pointer, body)?,
+            None => {
+                // get the body into a byte array
+                let mut buf = BytesMut::new();
+                let body = String::from_utf8_lossy(body);
+                buf.extend_from_slice(body.as_bytes());
+
+                self.decode_events(&mut buf)
+            }
+        };
 
-        // decode and enrich
-        let mut events = self.decode_events(&mut buf);
+        // enrich
+        self.page_events = events.len();
+        self.track_position(&events);
         self.enrich_events(&mut events);
 
         Some(events)
     }
+
+    /// Sets the query parameters of the checkpoint and the current page, renders the body and
+    /// computes the headers.
+    fn build_request(&mut self, mut request: Request<Bytes>) -> Option<Request<Bytes>> {
+        let mut url = request.uri().clone();
+        let mut fields = LogEvent::default();
+        if let Some(since) = &self.since {
+            let checkpoint = since.get();
+            if let Some(param) = &since.config().param {
+                url = set_query_param(&url, param, &checkpoint);
+            }
+            fields.insert("since", checkpoint);
+        }
+        if let Some(paginator) = &self.paginator {
+            url = paginator.apply(&url);
+            paginator.insert_fields(&mut fields);
+        }
+
+        *request.uri_mut() = url;
+
+        if let Some(body) = &self.body {
+            match body.render(&fields) {
+                Ok(body) => *request.body_mut() = body,
+                Err(error) => {
+                    emit!(TemplateRenderingError {
+                        error,
+                        field: Some("body"),
+                        drop_event: false,
+                    });
+                    return None;
+                }
+            }
+        }
+
+        if let Some(program) = &self.headers_vrl {
+            match headers::compute(program, fields, &request) {
+                Ok(headers) => {
+                    for (name, value) in headers {
+                        request.headers_mut().insert(name, value);
+                    }
+                }
+                Err(error) => {
+                    emit!(HttpScrapeHeadersError { error });
+                    return None;
+                }
+            }
+        }
+
+        Some(request)
+    }
+
+    fn next_page(&mut self, url: &Uri, header: &Parts, body: &Bytes) -> Option<Uri> {
+        let events = self.page_events;
+        self.paginator
+            .as_mut()?
+            .next_page(url, header, body, events)
+    }
+
+    /// Persists the greatest position of the events of the scrape as the next checkpoint.
+    fn on_scrape_complete(&mut self) {
+        if let (Some(since), Some(latest)) = (&self.since, self.latest.take()) {
+            since.set(latest.to_string_lossy());
+        }
+    }
 }
@@ -53,7 +53,14 @@ async fn bytes_decoding() {
         decoding: default_decoding(),
         framing: default_framing_message_based(),
         headers: HashMap::new(),
+        headers_vrl: None,
         method: HttpMethod::Get,
+        body: None,
+        split_pointer: None,
+        pagination: None,
+        since: None,
+        data_dir: None,
+        state_store: Default::default(),
         tls: None,
         auth: None,
         log_namespace: None,
@@ -78,12 +85,21 @@ async fn json_decoding_newline_delimited() {
         endpoint: format!("http://{}/endpoint", in_addr),
         scrape_interval_secs: INTERVAL_SECS,
         query: HashMap::new(),
-        decoding: DeserializerConfig::Json,
+        decoding: DeserializerConfig::Json {
+            json: Default::default(),
+        },
         framing: FramingConfig::NewlineDelimited {
             newline_delimited: NewlineDelimitedDecoderOptions::default(),
         },
         headers: HashMap::new(),
+        headers_vrl: None,
         method: HttpMethod::Get,
+        body: None,
+        split_pointer: None,
+        pagination: None,
+        since: None,
+        data_dir: None,
+        state_store: Default::default(),
         tls: None,
         auth: None,
         log_namespace: None,
@@ -108,7 +124,9 @@ async fn json_decoding_character_delimited() {
         endpoint: format!("http://{}/endpoint", in_addr),
         scrape_interval_secs: INTERVAL_SECS,
         query: HashMap::new(),
-        decoding: DeserializerConfig::Json,
+        decoding: DeserializerConfig::Json {
+            json: Default::default(),
+        },
         framing: FramingConfig::CharacterDelimited {
             character_delimited: CharacterDelimitedDecoderOptions {
                 delimiter: b',',
@@ -116,7 +134,14 @@ async fn json_decoding_character_delimited() {
             },
         },
         headers: HashMap::new(),
+        headers_vrl: None,
         method: HttpMethod::Get,
+        body: None,
+        split_pointer: None,
+        pagination: None,
+        since: None,
+        data_dir: None,
+        state_store: Default::default(),
         tls: None,
         auth: None,
         log_namespace: None,
@@ -146,10 +171,19 @@ async fn request_query_applied() {
                 vec!["val1".to_string(), "val2".to_string()],
             ),
         ]),
-        decoding: DeserializerConfig::Json,
+        decoding: DeserializerConfig::Json {
+            json: Default::default(),
+        },
         framing: default_framing_message_based(),
         headers: HashMap::new(),
+        headers_vrl: None,
         method: HttpMethod::Get,
+        body: None,
+        split_pointer: None,
+        pagination: None,
+        since: None,
+        data_dir: None,
+        state_store: Default::default(),
         tls: None,
         auth: None,
         log_namespace: None,
@@ -214,7 +248,14 @@ async fn headers_applied() {
             "f00".to_string(),
             vec!["bazz".to_string(), "bizz".to_string()],
         )]),
+        headers_vrl: None,
         method: HttpMethod::Get,
+        body: None,
+        split_pointer: None,
+        pagination: None,
+        since: None,
+        data_dir: None,
+        state_store: Default::default(),
         auth: None,
         tls: None,
         log_namespace: None,
@@ -242,10 +283,74 @@ async fn accept_header_override() {
         decoding: DeserializerConfig::Bytes,
         framing: default_framing_message_based(),
         headers: HashMap::from([("ACCEPT".to_string(), vec!["application/json".to_string()])]),
+        headers_vrl: None,
         method: HttpMethod::Get,
+        body: None,
+        split_pointer: None,
+        pagination: None,
+        since: None,
+        data_dir: None,
+        state_store: Default::default(),
         auth: None,
         tls: None,
         log_namespace: None,
     })
     .await;
 }
+
+/// The pages of each scrape should be followed, with their events split out of the responses,
+/// and the next scrapes should resume from the checkpoint.
+#[tokio::test]
+async fn paginated_scrape_resumes_from_checkpoint() {
+    let in_addr = next_addr();
+
+    // Returns the items after the checkpoint in the body, two at a time.
+    let dummy_endpoint = warp::path!("items")
+        .and(warp::query::<HashMap<String, i64>>())
+        .and(warp::body::json())
+        .map(
+            |query: HashMap<String, i64>, body: HashMap<String, String>| {
+                let since = body["since"].parse::<i64>().unwrap();
+                let after = query.get("after").copied().unwrap_or(since).max(since);
+                let items = (after + 1..=3).take(2).collect::<Vec<_>>();
+                let next = items.last().filter(|&&id| id < 3);
+                warp::reply::json(&serde_json::json!({
+                    "data": items.iter().map(|id| serde_json::json!({ "id": id })).collect::<Vec<_>>(),
+                    "next": next,
+                }))
+            },
+        );
+
+    tokio::spawn(warp::serve(dummy_endpoint).run(in_addr));
+    wait_for_tcp(in_addr).await;
+
+    let data_dir = tempfile::tempdir().unwrap();
+    let config = toml::from_str::<HttpScrapeConfig>(&format!(
+        r#"
+        endpoint = "http://{}/items"
+        scrape_interval_secs = {}
+        method = "POST"
+        body = '{{"since": "{{{{ since }}}}"}}'
+        split_pointer = "/data"
+        decoding.codec = "json"
+        data_dir = "{}"
+        pagination.strategy = "cursor"
+        pagination.cursor_pointer = "/next"
+        pagination.param = "after"
+        since.field = "id"
+        since.initial = "0"
+        "#,
+        in_addr,
+        INTERVAL_SECS,
+        data_dir.path().display(),
+    ))
+    .unwrap();
+
+    let events = run_compliance(config).await;
+
+    let ids = events
+        .into_iter()
+        .map(|event| event.as_log()["id"].clone())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, vec![1.into(), 2.into(), 3.into()]);
+}
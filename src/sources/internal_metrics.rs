@@ -253,7 +253,7 @@ mod tests {
         }
 
         let mut labels = BTreeMap::new();
-        labels.insert(String::from("host"), String::from("foo"));
+        labels.insert("host".into(), String::from("foo"));
         assert_eq!(Some(&labels), output["quux"].tags());
     }
 
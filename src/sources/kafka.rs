@@ -1,7 +1,9 @@
 use std::{
     collections::{BTreeMap, HashMap},
+    future::Future,
     io::Cursor,
     sync::Arc,
+    time::Duration,
 };
 
 use async_stream::stream;
@@ -16,6 +18,7 @@ use rdkafka::{
     config::ClientConfig,
     consumer::{Consumer, StreamConsumer},
     message::{BorrowedMessage, Headers, Message},
+    Offset, TopicPartitionList,
 };
 use snafu::{ResultExt, Snafu};
 use tokio_util::codec::FramedRead;
@@ -30,8 +33,8 @@ use crate::{
     config::{log_schema, AcknowledgementsConfig, LogSchema, Output, SourceConfig, SourceContext},
     event::{BatchNotifier, BatchStatus, Event, Value},
     internal_events::{
-        KafkaBytesReceived, KafkaEventsReceived, KafkaOffsetUpdateError, KafkaReadError,
-        StreamClosedError,
+        KafkaBytesReceived, KafkaEventsReceived, KafkaOffsetUpdateError, KafkaPauseResumeError,
+        KafkaReadError, KafkaSeekError, StreamClosedError,
     },
     kafka::{KafkaAuthConfig, KafkaStatisticsContext},
     serde::{bool_or_struct, default_decoding, default_framing_message_based},
@@ -69,6 +72,21 @@ pub struct KafkaSourceConfig {
     /// The consumer group name to be used to consume events from Kafka.
     group_id: String,
 
+    /// The static member ID of the consumer within the consumer group.
+    ///
+    /// Static members which leave the group, such as when Vector restarts, get their partitions
+    /// back without a rebalance of the group if they come back within `session_timeout_ms`. The ID
+    /// must be unique within the consumer group, and stay the same across restarts.
+    group_instance_id: Option<String>,
+
+    /// Whether or not to rebalance the partitions of the consumer group incrementally.
+    ///
+    /// With incremental cooperative rebalancing, only the partitions moving to another consumer are
+    /// revoked on a rebalance, instead of all the partitions of every consumer. All the consumers of
+    /// the group must use it.
+    #[serde(default)]
+    cooperative_rebalancing: bool,
+
     /// If offsets for consumer group do not exist, set them using this strategy.
     ///
     /// See the [librdkafka documentation](https://github.com/edenhill/librdkafka/blob/master/CONFIGURATION.md) for the `auto.offset.reset` option for further clarification.
@@ -91,6 +109,16 @@ pub struct KafkaSourceConfig {
     #[serde(default = "default_commit_interval_ms")]
     commit_interval_ms: u64,
 
+    /// The time, in milliseconds, after which the assigned partitions are paused while the
+    /// downstream components apply backpressure.
+    ///
+    /// Paused partitions are not fetched from the brokers, which keeps the messages from piling up
+    /// in the queue of the consumer, and are resumed as soon as the events are accepted again. Set
+    /// to `0` to never pause the partitions.
+    #[serde(default = "default_backpressure_pause_ms")]
+    #[derivative(Default(value = "default_backpressure_pause_ms()"))]
+    backpressure_pause_ms: u64,
+
     /// Overrides the name of the log field used to add the message key to each event.
     ///
     /// The value will be the message key of the Kafka message itself.
@@ -170,6 +198,10 @@ const fn default_commit_interval_ms() -> u64 {
     5000 // default in librdkafka
 }
 
+const fn default_backpressure_pause_ms() -> u64 {
+    1000
+}
+
 fn default_auto_offset_reset() -> String {
     "largest".into() // default in librdkafka
 }
@@ -207,6 +239,7 @@ impl SourceConfig for KafkaSourceConfig {
         )
         .build();
         let acknowledgements = cx.do_acknowledgements(&self.acknowledgements);
+        let acknowledgements_timeout = cx.acknowledgements_timeout(&self.acknowledgements);
 
         Ok(Box::pin(kafka_source(
             self.clone(),
@@ -215,6 +248,7 @@ impl SourceConfig for KafkaSourceConfig {
             cx.shutdown,
             cx.out,
             acknowledgements,
+            acknowledgements_timeout,
         )))
     }
 
@@ -234,23 +268,35 @@ async fn kafka_source(
     mut shutdown: ShutdownSignal,
     mut out: SourceSender,
     acknowledgements: bool,
+    acknowledgements_timeout: Option<Duration>,
 ) -> Result<(), ()> {
     let consumer = Arc::new(consumer);
-    let (finalizer, mut ack_stream) =
-        OrderedFinalizer::<FinalizerEntry>::maybe_new(acknowledgements, shutdown.clone());
+    let (finalizer, mut ack_stream) = OrderedFinalizer::<FinalizerEntry>::maybe_with_timeout(
+        acknowledgements,
+        shutdown.clone(),
+        acknowledgements_timeout,
+    );
+    let mut rewinder = Rewinder::default();
     let mut stream = consumer.stream();
     let keys = Keys::from(log_schema(), &config);
+    let pause_after = (config.backpressure_pause_ms > 0)
+        .then(|| Duration::from_millis(config.backpressure_pause_ms));
 
     loop {
         tokio::select! {
             _ = &mut shutdown => break,
             entry = ack_stream.next() => if let Some((status, entry)) = entry {
-                if status == BatchStatus::Delivered {
-                    if let Err(error) =
-                        consumer.store_offset(&entry.topic, entry.partition, entry.offset)
-                    {
-                        emit!(KafkaOffsetUpdateError { error });
+                match status {
+                    BatchStatus::Delivered => {
+                        if let Err(error) =
+                            consumer.store_offset(&entry.topic, entry.partition, entry.offset)
+                        {
+                            emit!(KafkaOffsetUpdateError { error });
+                        }
                     }
+                    // The message is consumed again, as the failure is transient.
+                    BatchStatus::Errored => rewinder.rewind(&consumer, &entry),
+                    BatchStatus::Rejected => {}
                 }
             },
             message = stream.next() => match message {
@@ -264,7 +310,10 @@ async fn kafka_source(
                         partition: msg.partition(),
                     });
 
-                    parse_message(msg, decoder.clone(), keys, &finalizer, &mut out, &consumer).await;
+                    if finalizer.is_some() {
+                        rewinder.consumed(&msg);
+                    }
+                    parse_message(msg, decoder.clone(), keys, &finalizer, &mut out, &consumer, pause_after).await;
                 }
             },
         }
@@ -280,13 +329,15 @@ async fn parse_message(
     finalizer: &Option<OrderedFinalizer<FinalizerEntry>>,
     out: &mut SourceSender,
     consumer: &Arc<StreamConsumer<KafkaStatisticsContext>>,
+    pause_after: Option<Duration>,
 ) {
     if let Some((count, mut stream)) = parse_stream(&msg, decoder, keys) {
         match finalizer {
             Some(finalizer) => {
                 let (batch, receiver) = BatchNotifier::new_with_receiver();
                 let mut stream = stream.map(|event| event.with_batch_notifier(&batch));
-                match out.send_event_stream(&mut stream).await {
+                let send = out.send_event_stream(&mut stream);
+                match send_with_backpressure(send, consumer, pause_after).await {
                     Err(error) => {
                         emit!(StreamClosedError { error, count });
                     }
@@ -298,7 +349,13 @@ async fn parse_message(
                     }
                 }
             }
-            None => match out.send_event_stream(&mut stream).await {
+            None => match send_with_backpressure(
+                out.send_event_stream(&mut stream),
+                consumer,
+                pause_after,
+            )
+            .await
+            {
                 Err(error) => {
                     emit!(StreamClosedError { error, count });
                 }
@@ -314,6 +371,61 @@ async fn parse_message(
     }
 }
 
+/// Sends events downstream, pausing the assigned partitions while the downstream components apply
+/// backpressure for longer than `pause_after`.
+async fn send_with_backpressure<T>(
+    send: impl Future<Output = T>,
+    consumer: &StreamConsumer<KafkaStatisticsContext>,
+    pause_after: Option<Duration>,
+) -> T {
+    let pause_after = match pause_after {
+        Some(pause_after) => pause_after,
+        None => return send.await,
+    };
+
+    tokio::pin!(send);
+    tokio::select! {
+        biased;
+        result = &mut send => result,
+        _ = tokio::time::sleep(pause_after) => {
+            let paused = pause_partitions(consumer);
+            let result = send.await;
+            if let Some(partitions) = paused {
+                debug!(message = "Resuming partitions.", count = partitions.count());
+                if let Err(error) = consumer.resume(&partitions) {
+                    emit!(KafkaPauseResumeError { error, action: "resume" });
+                }
+            }
+            result
+        }
+    }
+}
+
+/// Pauses the partitions assigned to the consumer, returning the ones which were paused.
+fn pause_partitions(
+    consumer: &StreamConsumer<KafkaStatisticsContext>,
+) -> Option<TopicPartitionList> {
+    let result = consumer
+        .assignment()
+        .and_then(|partitions| consumer.pause(&partitions).map(|()| partitions));
+    match result {
+        Ok(partitions) => {
+            debug!(
+                message = "Pausing partitions while downstream components apply backpressure.",
+                count = partitions.count(),
+            );
+            Some(partitions)
+        }
+        Err(error) => {
+            emit!(KafkaPauseResumeError {
+                error,
+                action: "pause"
+            });
+            None
+        }
+    }
+}
+
 // Turn the received message into a stream of parsed events.
 fn parse_stream<'a>(
     msg: &BorrowedMessage<'a>,
@@ -459,6 +571,67 @@ impl<'a> From<BorrowedMessage<'a>> for FinalizerEntry {
     }
 }
 
+/// Seeks the partitions back to the messages that failed to be delivered, so that they are consumed
+/// again.
+#[derive(Default)]
+struct Rewinder {
+    /// The offset of the last message consumed from each partition, by topic.
+    positions: HashMap<String, HashMap<i32, i64>>,
+}
+
+impl Rewinder {
+    fn consumed(&mut self, msg: &BorrowedMessage<'_>) {
+        match self.positions.get_mut(msg.topic()) {
+            Some(partitions) => {
+                partitions.insert(msg.partition(), msg.offset());
+            }
+            None => {
+                self.positions.insert(
+                    msg.topic().to_owned(),
+                    HashMap::from([(msg.partition(), msg.offset())]),
+                );
+            }
+        }
+    }
+
+    fn rewind(
+        &mut self,
+        consumer: &StreamConsumer<KafkaStatisticsContext>,
+        entry: &FinalizerEntry,
+    ) {
+        let position = match self
+            .positions
+            .get_mut(&entry.topic)
+            .and_then(|partitions| partitions.get_mut(&entry.partition))
+        {
+            Some(position) => position,
+            None => return,
+        };
+        // When the partition was already rewound to an earlier message, this one is consumed
+        // again as well, and seeking to it would skip the messages in between.
+        if *position < entry.offset {
+            return;
+        }
+
+        debug!(
+            message = "Consuming the failed message again.",
+            topic = %entry.topic,
+            partition = %entry.partition,
+            offset = %entry.offset,
+        );
+        // A zero timeout makes the seek asynchronous, instead of blocking the source.
+        match consumer.seek(
+            &entry.topic,
+            entry.partition,
+            Offset::Offset(entry.offset),
+            Duration::ZERO,
+        ) {
+            Ok(()) => *position = entry.offset - 1,
+            Err(error) => emit!(KafkaSeekError { error }),
+        }
+    }
+}
+
 fn create_consumer(
     config: &KafkaSourceConfig,
 ) -> crate::Result<StreamConsumer<KafkaStatisticsContext>> {
@@ -480,6 +653,13 @@ fn create_consumer(
         .set("statistics.interval.ms", "1000")
         .set("client.id", "vector");
 
+    if let Some(group_instance_id) = &config.group_instance_id {
+        client_config.set("group.instance.id", group_instance_id);
+    }
+    if config.cooperative_rebalancing {
+        client_config.set("partition.assignment.strategy", "cooperative-sticky");
+    }
+
     config.auth.apply(&mut client_config)?;
 
     if let Some(librdkafka_options) = &config.librdkafka_options {
@@ -539,6 +719,16 @@ mod test {
         assert!(create_consumer(&config).is_ok());
     }
 
+    #[tokio::test]
+    async fn consumer_create_static_membership() {
+        let config = KafkaSourceConfig {
+            group_instance_id: Some("vector-0".to_owned()),
+            cooperative_rebalancing: true,
+            ..make_config("topic", "group")
+        };
+        assert!(create_consumer(&config).is_ok());
+    }
+
     #[tokio::test]
     async fn consumer_create_incorrect_auto_offset_reset() {
         let config = KafkaSourceConfig {
@@ -663,6 +853,7 @@ mod integration_test {
                 shutdown,
                 tx,
                 acknowledgements,
+                None,
             ));
             let events = collect_n(rx, SEND_COUNT).await;
             // Yield to the finalization task to let it collect the
@@ -449,6 +449,11 @@ impl Source {
             oldest_first: true,
             // We do not remove the log files, `kubelet` is responsible for it.
             remove_after: None,
+            // The pods write to their log files for as long as they run.
+            once: false,
+            // The checkpoints are updated without waiting for acknowledgements, so there is
+            // nothing to read again.
+            rewinds: None,
             // The standard emitter.
             emitter: FileSourceInternalEventsEmitter,
             // A handle to the current tokio runtime
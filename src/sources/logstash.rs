@@ -149,17 +149,23 @@ impl TcpSource for LogstashSource {
 }
 
 struct LogstashAcker {
-    sequence_number: u32,
+    /// The sequence number of the last frame of each window, in the order they were received.
+    sequence_numbers: Vec<u32>,
     protocol_version: Option<LogstashProtocolVersion>,
 }
 
 impl LogstashAcker {
     fn new(frames: &[LogstashEventFrame]) -> Self {
-        let mut sequence_number = 0;
+        let mut sequence_numbers: Vec<u32> = Vec::new();
         let mut protocol_version = None;
 
         for frame in frames {
-            sequence_number = std::cmp::max(sequence_number, frame.sequence_number);
+            // The sequence numbers restart with each window, so a frame that doesn't follow the
+            // previous one starts a new window, which must be acknowledged on its own.
+            match sequence_numbers.last_mut() {
+                Some(last) if frame.sequence_number > *last => *last = frame.sequence_number,
+                _ => sequence_numbers.push(frame.sequence_number),
+            }
             // We assume that it's valid to ack via any of the protocol versions that we've seen in
             // a set of frames from a single stream, so here we just take the last. In reality, we
             // do not expect stream with multiple protocol versions to occur.
@@ -167,7 +173,7 @@ impl LogstashAcker {
         }
 
         Self {
-            sequence_number,
+            sequence_numbers,
             protocol_version,
         }
     }
@@ -178,10 +184,28 @@ impl TcpSourceAcker for LogstashAcker {
     fn build_ack(self, ack: TcpSourceAck) -> Option<Bytes> {
         match (ack, self.protocol_version) {
             (TcpSourceAck::Ack, Some(protocol_version)) => {
+                let mut bytes: Vec<u8> = Vec::with_capacity(6 * self.sequence_numbers.len());
+                for sequence_number in self.sequence_numbers {
+                    bytes.push(protocol_version.into());
+                    bytes.push(LogstashFrameType::Ack.into());
+                    bytes.extend(sequence_number.to_be_bytes().iter());
+                }
+                Some(Bytes::from(bytes))
+            }
+            _ => None,
+        }
+    }
+
+    // Version 2 of the protocol acknowledges the sequence number 0 to tell the writer that the
+    // events are still being processed, which keeps it from timing out and sending them again, as
+    // Logstash does.
+    fn build_keepalive(&self) -> Option<Bytes> {
+        match self.protocol_version {
+            Some(LogstashProtocolVersion::V2) => {
                 let mut bytes: Vec<u8> = Vec::with_capacity(6);
-                bytes.push(protocol_version.into());
+                bytes.push(LogstashProtocolVersion::V2.into());
                 bytes.push(LogstashFrameType::Ack.into());
-                bytes.extend(self.sequence_number.to_be_bytes().iter());
+                bytes.extend(0u32.to_be_bytes().iter());
                 Some(Bytes::from(bytes))
             }
             _ => None,
@@ -378,7 +402,8 @@ impl Decoder for LogstashDecoder {
                 }
                 // The window size indicates how many events the writer will send before waiting
                 // for acks. As we forward events as we get them, and ack as they are received, we
-                // do not need to keep track of this.
+                // do not need to keep track of this. The sequence numbers restart with each
+                // window, which is what the acker relies on to ack each window in turn.
                 //
                 // https://github.com/logstash-plugins/logstash-input-beats/blob/master/PROTOCOL.md#window-size-frame-type
                 LogstashDecoderReadState::ReadFrame(_protocol, LogstashFrameType::WindowSize) => {
@@ -390,7 +415,8 @@ impl Decoder for LogstashDecoder {
 
                     LogstashDecoderReadState::ReadProtocol
                 }
-                // we shouldn't receive acks from the writer, just skip
+                // we shouldn't receive acks from the writer, just skip them, including the
+                // keepalives of version 2 that acknowledge the sequence number 0
                 //
                 // https://github.com/logstash-plugins/logstash-input-beats/blob/master/PROTOCOL.md#ack-frame-type
                 LogstashDecoderReadState::ReadFrame(_protocol, LogstashFrameType::Ack) => {
@@ -638,6 +664,35 @@ mod test {
         assert!(log.get("timestamp").is_some());
     }
 
+    fn frame(sequence_number: u32) -> LogstashEventFrame {
+        LogstashEventFrame {
+            protocol: LogstashProtocolVersion::V2,
+            sequence_number,
+            fields: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn acks_each_window() {
+        // The frames of a window, followed by the start of the next one.
+        let frames = [frame(1), frame(2), frame(3), frame(1), frame(2)];
+        let ack = LogstashAcker::new(&frames)
+            .build_ack(TcpSourceAck::Ack)
+            .unwrap();
+
+        assert_eq!(&ack[..], b"2A\0\0\0\x032A\0\0\0\x02");
+    }
+
+    #[test]
+    fn sends_keepalives_with_v2() {
+        let acker = LogstashAcker::new(&[frame(1)]);
+        assert_eq!(&acker.build_keepalive().unwrap()[..], b"2A\0\0\0\0");
+
+        let mut v1 = frame(1);
+        v1.protocol = LogstashProtocolVersion::V1;
+        assert!(LogstashAcker::new(&[v1]).build_keepalive().is_none());
+    }
+
     fn encode_req(seq: u32, pairs: &[(&str, &str)]) -> Bytes {
         let mut req = BytesMut::new();
         req.put_u8(b'2');
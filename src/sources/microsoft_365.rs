@@ -0,0 +1,381 @@
+use std::{collections::HashSet, path::PathBuf, time::Duration};
+
+use bytes::Bytes;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use futures::FutureExt;
+use http::{header, HeaderName, Request, Uri};
+use serde::Deserialize;
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use crate::{
+    config::{DataType, Output, SourceConfig, SourceContext},
+    event::Event,
+    http::HttpClient,
+    sources::util::{
+        audit_log::{
+            run_poller, send_events, string_at, AuditLogClient, AuditLogPoller, AuditRecord,
+            Cursors, PollError,
+        },
+        http_scrape_auth::{HttpScrapeAuth, RequestAuth},
+    },
+    state_store::StateStoreConfig,
+    tls::{TlsConfig, TlsSettings},
+    SourceSender,
+};
+
+/// The time format of the Management Activity API.
+const TIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+/// The longest time range that the content of a content type can be listed for, in hours.
+const MAX_WINDOW_HOURS: i64 = 24;
+
+/// How long the content is available, in hours. An hour of margin avoids requesting expired
+/// content.
+const RETENTION_HOURS: i64 = 7 * 24 - 1;
+
+static NEXT_PAGE_URI: HeaderName = HeaderName::from_static("nextpageuri");
+
+/// Configuration for the `microsoft_365` source.
+#[configurable_component(source("microsoft_365"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Microsoft365Config {
+    /// The ID of the Azure Active Directory tenant of the organization.
+    tenant_id: String,
+
+    /// The client ID of the Azure Active Directory application used to pull the audit logs.
+    ///
+    /// The application must be granted the `ActivityFeed.Read` permission of the Office 365
+    /// Management APIs, and the `ActivityFeed.ReadDlp` one to pull the `DLP.All` content type.
+    client_id: String,
+
+    /// The client secret of the Azure Active Directory application.
+    client_secret: SensitiveString,
+
+    /// The content types pulled.
+    ///
+    /// The source subscribes to each of them when it starts, if needed.
+    #[serde(default = "default_content_types")]
+    content_types: Vec<String>,
+
+    /// The publisher identifier sent with the requests, which Microsoft uses to share the
+    /// request quota.
+    ///
+    /// By default, the tenant ID is used.
+    publisher_id: Option<String>,
+
+    /// The endpoint of the Management Activity API.
+    ///
+    /// The API of government clouds have different endpoints, such as
+    /// `https://manage-gcc.office.com`.
+    #[serde(default = "default_endpoint")]
+    endpoint: String,
+
+    /// The endpoint of the Microsoft identity platform, where access tokens are requested.
+    #[serde(default = "default_login_endpoint")]
+    login_endpoint: String,
+
+    /// The interval between polls, in seconds.
+    #[serde(default = "default_poll_interval_secs")]
+    poll_interval_secs: u64,
+
+    /// The directory used to persist the cursors of the source, when `state_store` is `file`.
+    ///
+    /// By default, the global `data_dir` option is used. Please make sure the user Vector is running as has write permissions to this directory.
+    data_dir: Option<PathBuf>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    state_store: StateStoreConfig,
+
+    #[configurable(derived)]
+    tls: Option<TlsConfig>,
+}
+
+fn default_content_types() -> Vec<String> {
+    [
+        "Audit.AzureActiveDirectory",
+        "Audit.Exchange",
+        "Audit.SharePoint",
+        "Audit.General",
+        "DLP.All",
+    ]
+    .iter()
+    .map(ToString::to_string)
+    .collect()
+}
+
+fn default_endpoint() -> String {
+    "https://manage.office.com".to_string()
+}
+
+fn default_login_endpoint() -> String {
+    "https://login.microsoftonline.com".to_string()
+}
+
+const fn default_poll_interval_secs() -> u64 {
+    60
+}
+
+impl Default for Microsoft365Config {
+    fn default() -> Self {
+        Self {
+            tenant_id: "${AZURE_TENANT_ID}".to_string(),
+            client_id: "${AZURE_CLIENT_ID}".to_string(),
+            client_secret: "${AZURE_CLIENT_SECRET}".to_string().into(),
+            content_types: default_content_types(),
+            publisher_id: None,
+            endpoint: default_endpoint(),
+            login_endpoint: default_login_endpoint(),
+            poll_interval_secs: default_poll_interval_secs(),
+            data_dir: None,
+            state_store: StateStoreConfig::default(),
+            tls: None,
+        }
+    }
+}
+
+impl_generate_config_from_default!(Microsoft365Config);
+
+#[async_trait::async_trait]
+impl SourceConfig for Microsoft365Config {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let store = self.state_store.build(&cx, self.data_dir.as_ref()).await?;
+        let cursors = Cursors::load(store).await?;
+
+        let endpoint = self.endpoint.trim_end_matches('/');
+        let auth = RequestAuth::build(&HttpScrapeAuth::Oauth2 {
+            token_endpoint: format!(
+                "{}/{}/oauth2/v2.0/token",
+                self.login_endpoint.trim_end_matches('/'),
+                self.tenant_id
+            ),
+            client_id: self.client_id.clone(),
+            client_secret: Some(self.client_secret.clone()),
+            refresh_token: None,
+            scopes: vec![format!("{}/.default", endpoint)],
+        })
+        .await?;
+
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = AuditLogClient::new(
+            HttpClient::new(tls, &cx.proxy)?,
+            Some(auth),
+            "microsoft_365",
+        );
+
+        let poller = Microsoft365Poller {
+            feed_url: format!("{}/api/v1.0/{}/activity/feed", endpoint, self.tenant_id),
+            publisher_id: self
+                .publisher_id
+                .clone()
+                .unwrap_or_else(|| self.tenant_id.clone()),
+            content_types: self.content_types.clone(),
+            subscribed: HashSet::new(),
+        };
+
+        Ok(run_poller(
+            poller,
+            client,
+            cursors,
+            Duration::from_secs(self.poll_interval_secs),
+            cx.shutdown,
+            cx.out,
+        )
+        .boxed())
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+/// Pulls the content types of the Office 365 Management Activity API.
+///
+/// The API makes the records available as blobs of content, which are listed by the time they
+/// were created. The cursor of each content type is the end of the last time range whose blobs
+/// have all been pulled.
+struct Microsoft365Poller {
+    feed_url: String,
+    publisher_id: String,
+    content_types: Vec<String>,
+    /// The content types that the source is known to be subscribed to.
+    subscribed: HashSet<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Content {
+    content_uri: String,
+}
+
+#[async_trait::async_trait]
+impl AuditLogPoller for Microsoft365Poller {
+    async fn poll(
+        &mut self,
+        client: &AuditLogClient,
+        cursors: &mut Cursors,
+        out: &mut SourceSender,
+    ) -> Result<(), PollError> {
+        for content_type in self.content_types.clone() {
+            if !self.subscribed.contains(&content_type) {
+                self.subscribe(client, &content_type).await?;
+                self.subscribed.insert(content_type.clone());
+            }
+
+            let now = Utc::now();
+            let max_window = chrono::Duration::hours(MAX_WINDOW_HOURS);
+            let mut start = cursors
+                .get(&content_type)
+                .and_then(|cursor| NaiveDateTime::parse_from_str(cursor, TIME_FORMAT).ok())
+                .map(|cursor| DateTime::<Utc>::from_utc(cursor, Utc))
+                .unwrap_or(now - max_window)
+                .max(now - chrono::Duration::hours(RETENTION_HOURS));
+
+            while start < now {
+                let end = (start + max_window).min(now);
+                let mut url: Uri = self.url(
+                    "subscriptions/content",
+                    &[
+                        ("contentType", &content_type),
+                        ("startTime", &start.format(TIME_FORMAT).to_string()),
+                        ("endTime", &end.format(TIME_FORMAT).to_string()),
+                    ],
+                )?;
+
+                loop {
+                    let (parts, contents) = client
+                        .get_json::<Vec<Content>>(Request::get(&url).body(Bytes::new())?)
+                        .await?;
+                    for content in contents {
+                        let content_url = format!(
+                            "{}{}PublisherIdentifier={}",
+                            content.content_uri,
+                            if content.content_uri.contains('?') {
+                                '&'
+                            } else {
+                                '?'
+                            },
+                            self.publisher_id
+                        );
+                        let (_, records) = client
+                            .get_json::<Vec<serde_json::Value>>(
+                                Request::get(content_url).body(Bytes::new())?,
+                            )
+                            .await?;
+                        if !records.is_empty() {
+                            send_events(out, records.into_iter().map(record_to_event).collect())
+                                .await?;
+                        }
+                    }
+
+                    match parts
+                        .headers
+                        .get(&NEXT_PAGE_URI)
+                        .and_then(|next| next.to_str().ok())
+                    {
+                        Some(next) => url = next.parse()?,
+                        None => break,
+                    }
+                }
+
+                // The times are truncated to seconds, so that the cursor is the next start.
+                let end = end.format(TIME_FORMAT).to_string();
+                start = DateTime::from_utc(NaiveDateTime::parse_from_str(&end, TIME_FORMAT)?, Utc);
+                cursors.set(&content_type, end).await;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Microsoft365Poller {
+    fn url(&self, path: &str, params: &[(&str, &str)]) -> Result<Uri, PollError> {
+        let mut query = url::form_urlencoded::Serializer::new(String::new());
+        query.extend_pairs(params);
+        query.append_pair("PublisherIdentifier", &self.publisher_id);
+        Ok(format!("{}/{}?{}", self.feed_url, path, query.finish()).parse()?)
+    }
+
+    /// Starts the subscription to a content type, unless it is already started.
+    async fn subscribe(
+        &self,
+        client: &AuditLogClient,
+        content_type: &str,
+    ) -> Result<(), PollError> {
+        let url = self.url("subscriptions/start", &[("contentType", content_type)])?;
+        let request = Request::post(&url)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Bytes::from_static(b"{}"))?;
+        match client.send(request).await {
+            Ok(_) => Ok(()),
+            // The API rejects subscriptions that are already started with this error code.
+            Err(error) if error.to_string().contains("AF20024") => Ok(()),
+            Err(error) => Err(PollError::Request(error)),
+        }
+    }
+}
+
+fn record_to_event(record: serde_json::Value) -> Event {
+    // The creation times are in UTC, without time zone.
+    let timestamp = string_at(&record, "/CreationTime")
+        .and_then(|time| NaiveDateTime::parse_from_str(&time, "%Y-%m-%dT%H:%M:%S%.f").ok())
+        .map(|time| DateTime::<Utc>::from_utc(time, Utc));
+    let fields = AuditRecord {
+        timestamp,
+        id: string_at(&record, "/Id"),
+        action: string_at(&record, "/Operation"),
+        outcome: string_at(&record, "/ResultStatus").map(|status| status.to_lowercase()),
+        actor_id: string_at(&record, "/UserKey"),
+        actor_name: string_at(&record, "/UserId"),
+        client_ip: string_at(&record, "/ClientIP"),
+    };
+    fields
+        .into_log("microsoft_365", "microsoft_365", record)
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use vector_core::event::Value;
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<Microsoft365Config>();
+    }
+
+    #[test]
+    fn normalizes_records() {
+        let event = record_to_event(serde_json::json!({
+            "CreationTime": "2022-06-01T12:30:00",
+            "Id": "80c76bd2-9d81-4c57-a97a-accfc3443dca",
+            "Operation": "UserLoggedIn",
+            "ResultStatus": "Succeeded",
+            "UserKey": "10030000A1A2B3C4",
+            "UserId": "jane@example.com",
+            "ClientIP": "10.0.0.1",
+            "Workload": "AzureActiveDirectory",
+        }));
+        let log = event.as_log();
+
+        assert_eq!(
+            log["timestamp"],
+            Value::from(Utc.ymd(2022, 6, 1).and_hms(12, 30, 0))
+        );
+        assert_eq!(log["provider"], "microsoft_365".into());
+        assert_eq!(log["event.action"], "UserLoggedIn".into());
+        assert_eq!(log["event.outcome"], "succeeded".into());
+        assert_eq!(log["actor.id"], "10030000A1A2B3C4".into());
+        assert_eq!(log["actor.name"], "jane@example.com".into());
+        assert_eq!(log["original.Workload"], "AzureActiveDirectory".into());
+    }
+}
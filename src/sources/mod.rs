@@ -5,14 +5,20 @@ use snafu::Snafu;
 pub mod amqp;
 #[cfg(feature = "sources-apache_metrics")]
 pub mod apache_metrics;
+#[cfg(feature = "sources-aws_cloudwatch_logs")]
+pub mod aws_cloudwatch_logs;
 #[cfg(feature = "sources-aws_ecs_metrics")]
 pub mod aws_ecs_metrics;
 #[cfg(feature = "sources-aws_kinesis_firehose")]
 pub mod aws_kinesis_firehose;
+#[cfg(feature = "sources-aws_kinesis_streams")]
+pub mod aws_kinesis_streams;
 #[cfg(feature = "sources-aws_s3")]
 pub mod aws_s3;
 #[cfg(feature = "sources-aws_sqs")]
 pub mod aws_sqs;
+#[cfg(feature = "sources-azure_blob")]
+pub mod azure_blob;
 #[cfg(any(feature = "sources-datadog_agent"))]
 pub mod datadog_agent;
 #[cfg(feature = "sources-demo_logs")]
@@ -21,6 +27,8 @@ pub mod demo_logs;
 pub mod dnstap;
 #[cfg(feature = "sources-docker_logs")]
 pub mod docker_logs;
+#[cfg(all(target_os = "linux", feature = "sources-ebpf"))]
+pub mod ebpf;
 #[cfg(feature = "sources-eventstoredb_metrics")]
 pub mod eventstoredb_metrics;
 #[cfg(feature = "sources-exec")]
@@ -36,6 +44,8 @@ pub mod file_descriptors;
 pub mod fluent;
 #[cfg(feature = "sources-gcp_pubsub")]
 pub mod gcp_pubsub;
+#[cfg(feature = "sources-google_workspace")]
+pub mod google_workspace;
 #[cfg(feature = "sources-heroku_logs")]
 pub mod heroku_logs;
 #[cfg(feature = "sources-host_metrics")]
@@ -56,20 +66,32 @@ pub mod kafka;
 pub mod kubernetes_logs;
 #[cfg(all(feature = "sources-logstash"))]
 pub mod logstash;
+#[cfg(feature = "sources-microsoft_365")]
+pub mod microsoft_365;
 #[cfg(feature = "sources-mongodb_metrics")]
 pub mod mongodb_metrics;
+#[cfg(feature = "sources-mysql_cdc")]
+pub mod mysql_cdc;
 #[cfg(all(feature = "sources-nats"))]
 pub mod nats;
+#[cfg(feature = "sources-netflow")]
+pub mod netflow;
 #[cfg(feature = "sources-nginx_metrics")]
 pub mod nginx_metrics;
+#[cfg(feature = "sources-okta")]
+pub mod okta;
 #[cfg(feature = "sources-opentelemetry")]
 pub mod opentelemetry;
+#[cfg(feature = "sources-postgres_cdc")]
+pub mod postgres_cdc;
 #[cfg(feature = "sources-postgresql_metrics")]
 pub mod postgresql_metrics;
 #[cfg(feature = "sources-prometheus")]
 pub mod prometheus;
 #[cfg(feature = "sources-redis")]
 pub mod redis;
+#[cfg(feature = "sources-snmp")]
+pub mod snmp;
 #[cfg(feature = "sources-socket")]
 pub mod socket;
 #[cfg(feature = "sources-splunk_hec")]
@@ -114,6 +136,10 @@ pub enum Sources {
     #[cfg(feature = "sources-apache_metrics")]
     ApacheMetrics(#[configurable(derived)] apache_metrics::ApacheMetricsConfig),
 
+    /// AWS CloudWatch Logs.
+    #[cfg(feature = "sources-aws_cloudwatch_logs")]
+    AwsCloudwatchLogs(#[configurable(derived)] aws_cloudwatch_logs::AwsCloudwatchLogsConfig),
+
     /// AWS ECS Metrics.
     #[cfg(feature = "sources-aws_ecs_metrics")]
     AwsEcsMetrics(#[configurable(derived)] aws_ecs_metrics::AwsEcsMetricsSourceConfig),
@@ -122,6 +148,10 @@ pub enum Sources {
     #[cfg(feature = "sources-aws_kinesis_firehose")]
     AwsKinesisFirehose(#[configurable(derived)] aws_kinesis_firehose::AwsKinesisFirehoseConfig),
 
+    /// AWS Kinesis Streams.
+    #[cfg(feature = "sources-aws_kinesis_streams")]
+    AwsKinesisStreams(#[configurable(derived)] aws_kinesis_streams::AwsKinesisStreamsConfig),
+
     /// AWS S3.
     #[cfg(feature = "sources-aws_s3")]
     AwsS3(#[configurable(derived)] aws_s3::AwsS3Config),
@@ -130,6 +160,10 @@ pub enum Sources {
     #[cfg(feature = "sources-aws_sqs")]
     AwsSqs(#[configurable(derived)] aws_sqs::AwsSqsConfig),
 
+    /// Azure Blob Storage.
+    #[cfg(feature = "sources-azure_blob")]
+    AzureBlob(#[configurable(derived)] azure_blob::AzureBlobConfig),
+
     /// Datadog Agent.
     #[cfg(feature = "sources-datadog_agent")]
     DatadogAgent(#[configurable(derived)] datadog_agent::DatadogAgentConfig),
@@ -146,6 +180,10 @@ pub enum Sources {
     #[cfg(feature = "sources-docker_logs")]
     DockerLogs(#[configurable(derived)] docker_logs::DockerLogsConfig),
 
+    /// eBPF.
+    #[cfg(all(target_os = "linux", feature = "sources-ebpf"))]
+    Ebpf(#[configurable(derived)] ebpf::EbpfConfig),
+
     /// EventStoreDB Metrics.
     #[cfg(feature = "sources-eventstoredb_metrics")]
     EventstoredbMetrics(#[configurable(derived)] eventstoredb_metrics::EventStoreDbConfig),
@@ -172,6 +210,10 @@ pub enum Sources {
     #[cfg(feature = "sources-gcp_pubsub")]
     GcpPubsub(#[configurable(derived)] gcp_pubsub::PubsubConfig),
 
+    /// Google Workspace.
+    #[cfg(feature = "sources-google_workspace")]
+    GoogleWorkspace(#[configurable(derived)] google_workspace::GoogleWorkspaceConfig),
+
     /// Heroku Logs.
     #[cfg(feature = "sources-heroku_logs")]
     HerokuLogs(#[configurable(derived)] heroku_logs::LogplexConfig),
@@ -212,22 +254,42 @@ pub enum Sources {
     #[cfg(all(feature = "sources-logstash"))]
     Logstash(#[configurable(derived)] logstash::LogstashConfig),
 
+    /// Microsoft 365.
+    #[cfg(feature = "sources-microsoft_365")]
+    Microsoft365(#[configurable(derived)] microsoft_365::Microsoft365Config),
+
     /// MongoDB Metrics.
     #[cfg(feature = "sources-mongodb_metrics")]
     MongodbMetrics(#[configurable(derived)] mongodb_metrics::MongoDbMetricsConfig),
 
+    /// MySQL CDC.
+    #[cfg(feature = "sources-mysql_cdc")]
+    MysqlCdc(#[configurable(derived)] mysql_cdc::MysqlCdcConfig),
+
     /// NATS.
     #[cfg(all(feature = "sources-nats"))]
     Nats(#[configurable(derived)] nats::NatsSourceConfig),
 
+    /// NetFlow.
+    #[cfg(feature = "sources-netflow")]
+    Netflow(#[configurable(derived)] netflow::NetflowConfig),
+
     /// NGINX Metrics.
     #[cfg(feature = "sources-nginx_metrics")]
     NginxMetrics(#[configurable(derived)] nginx_metrics::NginxMetricsConfig),
 
+    /// Okta.
+    #[cfg(feature = "sources-okta")]
+    Okta(#[configurable(derived)] okta::OktaConfig),
+
     /// OpenTelemetry.
     #[cfg(feature = "sources-opentelemetry")]
     Opentelemetry(#[configurable(derived)] opentelemetry::OpentelemetryConfig),
 
+    /// PostgreSQL CDC.
+    #[cfg(feature = "sources-postgres_cdc")]
+    PostgresCdc(#[configurable(derived)] postgres_cdc::PostgresCdcConfig),
+
     /// PostgreSQL Metrics.
     #[cfg(feature = "sources-postgresql_metrics")]
     PostgresqlMetrics(#[configurable(derived)] postgresql_metrics::PostgresqlMetricsConfig),
@@ -244,6 +306,10 @@ pub enum Sources {
     #[cfg(feature = "sources-redis")]
     Redis(#[configurable(derived)] redis::RedisSourceConfig),
 
+    /// SNMP.
+    #[cfg(feature = "sources-snmp")]
+    Snmp(#[configurable(derived)] snmp::SnmpConfig),
+
     /// Test (backpressure).
     #[cfg(test)]
     TestBackpressure(
@@ -307,14 +373,20 @@ impl NamedComponent for Sources {
             Self::Amqp(config) => config.get_component_name(),
             #[cfg(feature = "sources-apache_metrics")]
             Self::ApacheMetrics(config) => config.get_component_name(),
+            #[cfg(feature = "sources-aws_cloudwatch_logs")]
+            Self::AwsCloudwatchLogs(config) => config.get_component_name(),
             #[cfg(feature = "sources-aws_ecs_metrics")]
             Self::AwsEcsMetrics(config) => config.get_component_name(),
             #[cfg(feature = "sources-aws_kinesis_firehose")]
             Self::AwsKinesisFirehose(config) => config.get_component_name(),
+            #[cfg(feature = "sources-aws_kinesis_streams")]
+            Self::AwsKinesisStreams(config) => config.get_component_name(),
             #[cfg(feature = "sources-aws_s3")]
             Self::AwsS3(config) => config.get_component_name(),
             #[cfg(feature = "sources-aws_sqs")]
             Self::AwsSqs(config) => config.get_component_name(),
+            #[cfg(feature = "sources-azure_blob")]
+            Self::AzureBlob(config) => config.get_component_name(),
             #[cfg(feature = "sources-datadog_agent")]
             Self::DatadogAgent(config) => config.get_component_name(),
             #[cfg(feature = "sources-demo_logs")]
@@ -323,6 +395,8 @@ impl NamedComponent for Sources {
             Self::Dnstap(config) => config.get_component_name(),
             #[cfg(feature = "sources-docker_logs")]
             Self::DockerLogs(config) => config.get_component_name(),
+            #[cfg(all(target_os = "linux", feature = "sources-ebpf"))]
+            Self::Ebpf(config) => config.get_component_name(),
             #[cfg(feature = "sources-eventstoredb_metrics")]
             Self::EventstoredbMetrics(config) => config.get_component_name(),
             #[cfg(feature = "sources-exec")]
@@ -335,6 +409,8 @@ impl NamedComponent for Sources {
             Self::Fluent(config) => config.get_component_name(),
             #[cfg(feature = "sources-gcp_pubsub")]
             Self::GcpPubsub(config) => config.get_component_name(),
+            #[cfg(feature = "sources-google_workspace")]
+            Self::GoogleWorkspace(config) => config.get_component_name(),
             #[cfg(feature = "sources-heroku_logs")]
             Self::HerokuLogs(config) => config.get_component_name(),
             #[cfg(feature = "sources-host_metrics")]
@@ -355,14 +431,24 @@ impl NamedComponent for Sources {
             Self::KubernetesLogs(config) => config.get_component_name(),
             #[cfg(all(feature = "sources-logstash"))]
             Self::Logstash(config) => config.get_component_name(),
+            #[cfg(feature = "sources-microsoft_365")]
+            Self::Microsoft365(config) => config.get_component_name(),
             #[cfg(feature = "sources-mongodb_metrics")]
             Self::MongodbMetrics(config) => config.get_component_name(),
+            #[cfg(feature = "sources-mysql_cdc")]
+            Self::MysqlCdc(config) => config.get_component_name(),
             #[cfg(all(feature = "sources-nats"))]
             Self::Nats(config) => config.get_component_name(),
+            #[cfg(feature = "sources-netflow")]
+            Self::Netflow(config) => config.get_component_name(),
             #[cfg(feature = "sources-nginx_metrics")]
             Self::NginxMetrics(config) => config.get_component_name(),
+            #[cfg(feature = "sources-okta")]
+            Self::Okta(config) => config.get_component_name(),
             #[cfg(feature = "sources-opentelemetry")]
             Self::Opentelemetry(config) => config.get_component_name(),
+            #[cfg(feature = "sources-postgres_cdc")]
+            Self::PostgresCdc(config) => config.get_component_name(),
             #[cfg(feature = "sources-postgresql_metrics")]
             Self::PostgresqlMetrics(config) => config.get_component_name(),
             #[cfg(feature = "sources-prometheus")]
@@ -371,6 +457,8 @@ impl NamedComponent for Sources {
             Self::PrometheusRemoteWrite(config) => config.get_component_name(),
             #[cfg(feature = "sources-redis")]
             Self::Redis(config) => config.get_component_name(),
+            #[cfg(feature = "sources-snmp")]
+            Self::Snmp(config) => config.get_component_name(),
             #[cfg(test)]
             Self::TestBackpressure(config) => config.get_component_name(),
             #[cfg(test)]
@@ -0,0 +1,166 @@
+//! The sets of global transaction identifiers (GTIDs) of MySQL, which the source checkpoints as
+//! the transactions it delivered.
+//!
+//! See <https://dev.mysql.com/doc/refman/8.0/en/replication-gtids-concepts.html>.
+
+use std::{collections::BTreeMap, fmt, str::FromStr};
+
+use mysql_async::{GnoInterval, Sid};
+use snafu::Snafu;
+use uuid::Uuid;
+
+#[derive(Debug, Snafu, PartialEq)]
+pub enum ParseError {
+    #[snafu(display("invalid server UUID {:?}", uuid))]
+    InvalidUuid { uuid: String },
+
+    #[snafu(display("invalid transaction interval {:?}", interval))]
+    InvalidInterval { interval: String },
+}
+
+/// A set of GTIDs, as the intervals of the transaction numbers of each server.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GtidSet {
+    /// The sorted and disjoint intervals of each server, with both ends included.
+    intervals: BTreeMap<Uuid, Vec<(u64, u64)>>,
+}
+
+impl GtidSet {
+    /// Adds the transaction with the given number, of the given server, to the set.
+    pub fn add(&mut self, sid: Uuid, gno: u64) {
+        self.insert(sid, gno, gno);
+    }
+
+    /// Adds the transactions from `start` to `end`, both included, of the given server.
+    fn insert(&mut self, sid: Uuid, start: u64, end: u64) {
+        let intervals = self.intervals.entry(sid).or_default();
+        // The intervals overlapping or adjacent to the new one are merged with it.
+        let first = intervals.partition_point(|(_, e)| e.saturating_add(1) < start);
+        let last = intervals.partition_point(|(s, _)| *s <= end.saturating_add(1));
+        let merged = if first < last {
+            (
+                intervals[first].0.min(start),
+                intervals[last - 1].1.max(end),
+            )
+        } else {
+            (start, end)
+        };
+        intervals.splice(first..last, [merged]);
+    }
+
+    /// The set as the SIDs of a binlog request, asking for the transactions that aren't in it.
+    pub fn sids(&self) -> Vec<Sid<'static>> {
+        self.intervals
+            .iter()
+            .map(|(uuid, intervals)| {
+                intervals
+                    .iter()
+                    .fold(Sid::new(*uuid.as_bytes()), |sid, (start, end)| {
+                        // The ends of the intervals of the protocol are excluded.
+                        sid.with_interval(GnoInterval::new(*start, end + 1))
+                    })
+            })
+            .collect()
+    }
+}
+
+/// Parses the set as MySQL formats it, such as
+/// `3e11fa47-71ca-11e1-9e33-c80aa9429562:1-5:7,a1b2c3d4-71ca-11e1-9e33-c80aa9429562:1-3`.
+impl FromStr for GtidSet {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut set = GtidSet::default();
+        for server in s
+            .split(',')
+            .map(str::trim)
+            .filter(|server| !server.is_empty())
+        {
+            let mut parts = server.split(':');
+            let uuid = parts.next().unwrap_or_default();
+            let sid = Uuid::parse_str(uuid).map_err(|_| ParseError::InvalidUuid {
+                uuid: uuid.to_owned(),
+            })?;
+
+            for interval in parts {
+                let invalid = || ParseError::InvalidInterval {
+                    interval: interval.to_owned(),
+                };
+                let (start, end) = match interval.split_once('-') {
+                    Some((start, end)) => (start, end),
+                    None => (interval, interval),
+                };
+                let start = start.parse::<u64>().map_err(|_| invalid())?;
+                let end = end.parse::<u64>().map_err(|_| invalid())?;
+                if start == 0 || start > end {
+                    return Err(invalid());
+                }
+                set.insert(sid, start, end);
+            }
+        }
+        Ok(set)
+    }
+}
+
+impl fmt::Display for GtidSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, (uuid, intervals)) in self.intervals.iter().enumerate() {
+            if index > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{}", uuid)?;
+            for (start, end) in intervals {
+                if start == end {
+                    write!(f, ":{}", start)?;
+                } else {
+                    write!(f, ":{}-{}", start, end)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SERVER: &str = "3e11fa47-71ca-11e1-9e33-c80aa9429562";
+
+    #[test]
+    fn parses_and_formats() {
+        let text = format!("{}:1-5:7,a1b2c3d4-71ca-11e1-9e33-c80aa9429562:1-3", SERVER);
+        let set = text.parse::<GtidSet>().unwrap();
+        assert_eq!(set.to_string(), text);
+
+        assert_eq!("".parse::<GtidSet>().unwrap(), GtidSet::default());
+        assert_eq!(
+            format!("{}:1-5:3-9", SERVER)
+                .parse::<GtidSet>()
+                .unwrap()
+                .to_string(),
+            format!("{}:1-9", SERVER)
+        );
+        assert!(format!("{}:5-1", SERVER).parse::<GtidSet>().is_err());
+        assert!("vector:1-5".parse::<GtidSet>().is_err());
+    }
+
+    #[test]
+    fn adds_transactions() {
+        let sid = Uuid::parse_str(SERVER).unwrap();
+        let mut set = format!("{}:1-3:7", SERVER).parse::<GtidSet>().unwrap();
+
+        set.add(sid, 4);
+        assert_eq!(set.to_string(), format!("{}:1-4:7", SERVER));
+        set.add(sid, 2);
+        assert_eq!(set.to_string(), format!("{}:1-4:7", SERVER));
+        set.add(sid, 9);
+        assert_eq!(set.to_string(), format!("{}:1-4:7:9", SERVER));
+        set.add(sid, 6);
+        assert_eq!(set.to_string(), format!("{}:1-4:6-7:9", SERVER));
+        set.add(sid, 5);
+        assert_eq!(set.to_string(), format!("{}:1-7:9", SERVER));
+        set.add(sid, 8);
+        assert_eq!(set.to_string(), format!("{}:1-9", SERVER));
+    }
+}
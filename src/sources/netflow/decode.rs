@@ -0,0 +1,80 @@
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use snafu::Snafu;
+
+#[derive(Debug, Snafu, PartialEq, Eq)]
+pub(super) enum DecodeError {
+    #[snafu(display("Unexpected end of datagram"))]
+    UnexpectedEnd,
+
+    #[snafu(display("Unsupported version {}", version))]
+    UnsupportedVersion { version: u32 },
+
+    #[snafu(display("Invalid length {}", length))]
+    InvalidLength { length: usize },
+
+    #[snafu(display("No template {} for observation domain {}", template_id, domain))]
+    MissingTemplate { domain: u32, template_id: u16 },
+}
+
+/// Reads big-endian values from a datagram, failing instead of panicking on truncated data.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub(super) const fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    pub(super) const fn remaining(&self) -> usize {
+        self.data.len()
+    }
+
+    pub(super) const fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub(super) fn bytes(&mut self, count: usize) -> Result<&'a [u8], DecodeError> {
+        if self.data.len() < count {
+            return Err(DecodeError::UnexpectedEnd);
+        }
+        let (bytes, rest) = self.data.split_at(count);
+        self.data = rest;
+        Ok(bytes)
+    }
+
+    /// Reads the next `count` bytes as a reader of their own.
+    pub(super) fn sub(&mut self, count: usize) -> Result<Reader<'a>, DecodeError> {
+        self.bytes(count).map(Reader::new)
+    }
+
+    pub(super) fn skip(&mut self, count: usize) -> Result<(), DecodeError> {
+        self.bytes(count).map(|_| ())
+    }
+
+    pub(super) fn u8(&mut self) -> Result<u8, DecodeError> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    pub(super) fn u16(&mut self) -> Result<u16, DecodeError> {
+        let bytes = self.bytes(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    pub(super) fn u32(&mut self) -> Result<u32, DecodeError> {
+        let bytes = self.bytes(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    pub(super) fn ipv4(&mut self) -> Result<Ipv4Addr, DecodeError> {
+        self.u32().map(Ipv4Addr::from)
+    }
+
+    pub(super) fn ipv6(&mut self) -> Result<Ipv6Addr, DecodeError> {
+        let mut octets = [0; 16];
+        octets.copy_from_slice(self.bytes(16)?);
+        Ok(Ipv6Addr::from(octets))
+    }
+}
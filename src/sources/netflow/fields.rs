@@ -0,0 +1,225 @@
+//! The standardized fields of flows, and their mapping from NetFlow v9 and IPFIX information
+//! elements.
+
+use std::{
+    collections::BTreeMap,
+    net::{Ipv4Addr, Ipv6Addr},
+};
+
+use chrono::{TimeZone, Utc};
+
+use crate::event::Value;
+
+/// A decoded flow, by standardized field name.
+pub(super) type Flow = BTreeMap<String, Value>;
+
+/// How the value of an information element is decoded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+    Unsigned,
+    Ipv4,
+    Ipv6,
+    Mac,
+    /// Milliseconds since the exporter booted.
+    Uptime,
+    /// Seconds since the Unix epoch.
+    Seconds,
+    /// Milliseconds since the Unix epoch.
+    Milliseconds,
+}
+
+/// The standardized fields of the information elements of the IANA registry, which NetFlow v9
+/// shares for its field types.
+const ELEMENTS: &[(u16, &str, Kind)] = &[
+    (1, "bytes", Kind::Unsigned),
+    (2, "packets", Kind::Unsigned),
+    (4, "protocol", Kind::Unsigned),
+    (5, "tos", Kind::Unsigned),
+    (6, "tcp_flags", Kind::Unsigned),
+    (7, "src_port", Kind::Unsigned),
+    (8, "src_addr", Kind::Ipv4),
+    (9, "src_mask", Kind::Unsigned),
+    (10, "input_interface", Kind::Unsigned),
+    (11, "dst_port", Kind::Unsigned),
+    (12, "dst_addr", Kind::Ipv4),
+    (13, "dst_mask", Kind::Unsigned),
+    (14, "output_interface", Kind::Unsigned),
+    (15, "next_hop", Kind::Ipv4),
+    (16, "src_as", Kind::Unsigned),
+    (17, "dst_as", Kind::Unsigned),
+    (18, "bgp_next_hop", Kind::Ipv4),
+    (21, "flow_end", Kind::Uptime),
+    (22, "flow_start", Kind::Uptime),
+    (27, "src_addr", Kind::Ipv6),
+    (28, "dst_addr", Kind::Ipv6),
+    (29, "src_mask", Kind::Unsigned),
+    (30, "dst_mask", Kind::Unsigned),
+    (31, "flow_label", Kind::Unsigned),
+    (32, "icmp_type_code", Kind::Unsigned),
+    (34, "sampling_interval", Kind::Unsigned),
+    (35, "sampling_algorithm", Kind::Unsigned),
+    (56, "src_mac", Kind::Mac),
+    (57, "post_dst_mac", Kind::Mac),
+    (58, "vlan_id", Kind::Unsigned),
+    (59, "post_vlan_id", Kind::Unsigned),
+    (60, "ip_version", Kind::Unsigned),
+    (61, "direction", Kind::Unsigned),
+    (62, "next_hop", Kind::Ipv6),
+    (63, "bgp_next_hop", Kind::Ipv6),
+    (80, "dst_mac", Kind::Mac),
+    (81, "post_src_mac", Kind::Mac),
+    (85, "bytes_total", Kind::Unsigned),
+    (86, "packets_total", Kind::Unsigned),
+    (136, "flow_end_reason", Kind::Unsigned),
+    (139, "icmp_type_code", Kind::Unsigned),
+    (150, "flow_start", Kind::Seconds),
+    (151, "flow_end", Kind::Seconds),
+    (152, "flow_start", Kind::Milliseconds),
+    (153, "flow_end", Kind::Milliseconds),
+    (176, "icmp_type", Kind::Unsigned),
+    (177, "icmp_code", Kind::Unsigned),
+    (225, "post_nat_src_addr", Kind::Ipv4),
+    (226, "post_nat_dst_addr", Kind::Ipv4),
+    (227, "post_napt_src_port", Kind::Unsigned),
+    (228, "post_napt_dst_port", Kind::Unsigned),
+];
+
+/// The clock of an exporter, to convert its uptime to timestamps.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct Clock {
+    /// The uptime of the exporter when the datagram was exported, in milliseconds.
+    pub(super) uptime_ms: u32,
+    /// The time when the datagram was exported, in milliseconds since the Unix epoch.
+    pub(super) export_ms: i64,
+}
+
+impl Clock {
+    /// Returns the timestamp of an uptime of the exporter.
+    pub(super) fn timestamp(&self, uptime_ms: u32) -> Value {
+        // Uptimes wrap around after about 49 days.
+        let elapsed = i64::from(self.uptime_ms.wrapping_sub(uptime_ms));
+        timestamp_ms(self.export_ms - elapsed)
+    }
+}
+
+pub(super) fn timestamp_ms(ms: i64) -> Value {
+    Utc.timestamp_millis_opt(ms)
+        .single()
+        .map_or(Value::Null, Value::from)
+}
+
+/// Inserts the value of an information element in a flow.
+///
+/// Elements without a standardized field are inserted as `field_<id>`, or
+/// `field_<enterprise>_<id>` for enterprise-specific elements.
+pub(super) fn insert_element(
+    flow: &mut Flow,
+    enterprise: u32,
+    id: u16,
+    value: &[u8],
+    clock: Option<Clock>,
+) {
+    let element = (enterprise == 0)
+        .then(|| ELEMENTS.iter().find(|(element_id, _, _)| *element_id == id))
+        .flatten();
+
+    let (name, value) = match element.and_then(|(_, name, kind)| {
+        decode(*kind, value, clock).map(|value| (name.to_string(), value))
+    }) {
+        Some(field) => field,
+        None => {
+            let name = if enterprise == 0 {
+                format!("field_{}", id)
+            } else {
+                format!("field_{}_{}", enterprise, id)
+            };
+            (name, decode_unknown(value))
+        }
+    };
+    flow.insert(name, value);
+}
+
+fn decode(kind: Kind, value: &[u8], clock: Option<Clock>) -> Option<Value> {
+    Some(match kind {
+        Kind::Unsigned => unsigned(value)?.into(),
+        Kind::Ipv4 => {
+            let octets: [u8; 4] = value.try_into().ok()?;
+            Ipv4Addr::from(octets).to_string().into()
+        }
+        Kind::Ipv6 => {
+            let octets: [u8; 16] = value.try_into().ok()?;
+            Ipv6Addr::from(octets).to_string().into()
+        }
+        Kind::Mac => {
+            if value.len() != 6 {
+                return None;
+            }
+            format_mac(value).into()
+        }
+        Kind::Uptime => clock?.timestamp(u32::try_from(unsigned(value)?).ok()?),
+        Kind::Seconds => timestamp_ms(i64::try_from(unsigned(value)?).ok()?.checked_mul(1000)?),
+        Kind::Milliseconds => timestamp_ms(i64::try_from(unsigned(value)?).ok()?),
+    })
+}
+
+/// Decodes an element of unknown type, as an unsigned integer when it fits, or hexadecimal.
+pub(super) fn decode_unknown(value: &[u8]) -> Value {
+    match unsigned(value) {
+        Some(value) => value.into(),
+        None => hex::encode(value).into(),
+    }
+}
+
+/// Decodes an unsigned integer of up to 8 bytes, as elements can use reduced-size encoding.
+fn unsigned(value: &[u8]) -> Option<u64> {
+    if value.is_empty() || value.len() > 8 {
+        return None;
+    }
+    Some(
+        value
+            .iter()
+            .fold(0, |value, byte| (value << 8) | u64::from(*byte)),
+    )
+}
+
+pub(super) fn format_mac(mac: &[u8]) -> String {
+    mac.iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inserts_elements() {
+        let clock = Clock {
+            uptime_ms: 10_000,
+            export_ms: 1_600_000_010_000,
+        };
+        let mut flow = Flow::new();
+        insert_element(&mut flow, 0, 8, &[10, 0, 0, 1], Some(clock));
+        insert_element(&mut flow, 0, 1, &[0, 0, 5, 220], Some(clock));
+        insert_element(&mut flow, 0, 22, &[0, 0, 3, 232], Some(clock));
+        insert_element(&mut flow, 0, 56, &[0, 0x1b, 0x21, 0x3c, 0x9d, 0xf8], None);
+        insert_element(&mut flow, 0, 999, &[1, 2], None);
+        insert_element(&mut flow, 9, 1, &[0; 10], None);
+
+        assert_eq!(flow["src_addr"], "10.0.0.1".into());
+        assert_eq!(flow["bytes"], 1500.into());
+        assert_eq!(flow["flow_start"], timestamp_ms(1_600_000_001_000));
+        assert_eq!(flow["src_mac"], "00:1b:21:3c:9d:f8".into());
+        assert_eq!(flow["field_999"], 258.into());
+        assert_eq!(flow["field_9_1"], "00000000000000000000".into());
+    }
+
+    #[test]
+    fn uptime_without_clock() {
+        let mut flow = Flow::new();
+        insert_element(&mut flow, 0, 22, &[0, 0, 3, 232], None);
+
+        assert_eq!(flow["field_22"], 1000.into());
+    }
+}
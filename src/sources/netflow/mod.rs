@@ -0,0 +1,282 @@
+use std::net::{Ipv4Addr, SocketAddr};
+
+use bytes::Bytes;
+use chrono::Utc;
+use tokio::net::UdpSocket;
+use vector_config::configurable_component;
+use vector_core::{config::LogNamespace, ByteSizeOf};
+
+use crate::{
+    config::{log_schema, DataType, GenerateConfig, Output, Resource, SourceConfig, SourceContext},
+    event::{Event, LogEvent},
+    internal_events::{
+        EventsReceived, NetflowDecodeError, NetflowSocketError, SocketBytesReceived, SocketMode,
+        StreamClosedError,
+    },
+    shutdown::ShutdownSignal,
+    udp, SourceSender,
+};
+
+mod decode;
+mod fields;
+mod netflow_v5;
+mod sflow;
+mod template;
+
+use decode::{DecodeError, Reader};
+use template::{Decoded, TemplateCache};
+
+/// The largest datagram received.
+const MAX_DATAGRAM_SIZE: usize = 65_507;
+
+/// Configuration for the `netflow` source.
+#[configurable_component(source("netflow"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct NetflowConfig {
+    /// The address to listen for flows on.
+    ///
+    /// NetFlow v5, NetFlow v9, IPFIX and sFlow v5 datagrams are all accepted on this address, and
+    /// told apart by their version.
+    #[serde(default = "default_address")]
+    address: SocketAddr,
+
+    /// The size, in bytes, of the receive buffer used for the listening socket.
+    ///
+    /// This should not typically needed to be changed.
+    receive_buffer_bytes: Option<usize>,
+}
+
+fn default_address() -> SocketAddr {
+    SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), 2055)
+}
+
+impl Default for NetflowConfig {
+    fn default() -> Self {
+        Self {
+            address: default_address(),
+            receive_buffer_bytes: None,
+        }
+    }
+}
+
+impl GenerateConfig for NetflowConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self::default()).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for NetflowConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        Ok(Box::pin(run(
+            self.address,
+            self.receive_buffer_bytes,
+            cx.shutdown,
+            cx.out,
+        )))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![Resource::udp(self.address)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+async fn run(
+    address: SocketAddr,
+    receive_buffer_bytes: Option<usize>,
+    mut shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let socket = UdpSocket::bind(address)
+        .await
+        .map_err(|error| emit!(NetflowSocketError { error }))?;
+
+    if let Some(receive_buffer_bytes) = receive_buffer_bytes {
+        if let Err(error) = udp::set_receive_buffer_size(&socket, receive_buffer_bytes) {
+            warn!(message = "Failed configuring receive buffer size on UDP socket.", %error);
+        }
+    }
+
+    info!(message = "Listening.", addr = %address, r#type = "udp");
+
+    let mut templates = TemplateCache::default();
+    let mut buffer = vec![0; MAX_DATAGRAM_SIZE];
+    loop {
+        let (size, peer) = tokio::select! {
+            result = socket.recv_from(&mut buffer) => match result {
+                Ok(received) => received,
+                Err(error) => {
+                    emit!(NetflowSocketError { error });
+                    continue;
+                }
+            },
+            _ = &mut shutdown => return Ok(()),
+        };
+        emit!(SocketBytesReceived {
+            mode: SocketMode::Udp,
+            byte_size: size,
+        });
+
+        let events = match decode_datagram(&mut templates, &buffer[..size], peer) {
+            Ok(events) => events,
+            Err(error) => {
+                emit!(NetflowDecodeError { error, peer });
+                continue;
+            }
+        };
+        if events.is_empty() {
+            continue;
+        }
+
+        let count = events.len();
+        emit!(EventsReceived {
+            count,
+            byte_size: events.size_of(),
+        });
+        if let Err(error) = out.send_batch(events).await {
+            emit!(StreamClosedError { error, count });
+            return Err(());
+        }
+    }
+}
+
+/// Decodes the flows of a datagram into events, telling the protocols apart by their version.
+///
+/// Errors of sets that couldn't be decoded are emitted, while the flows of the other sets are
+/// still returned.
+fn decode_datagram(
+    templates: &mut TemplateCache,
+    data: &[u8],
+    peer: SocketAddr,
+) -> Result<Vec<Event>, DecodeError> {
+    let mut reader = Reader::new(data);
+    let Decoded { flows, errors } = match reader.u16()? {
+        5 => Decoded {
+            flows: netflow_v5::decode(data)?,
+            errors: Vec::new(),
+        },
+        9 => templates.decode_v9(peer, data)?,
+        10 => templates.decode_ipfix(peer, data)?,
+        // sFlow has a 32 bit version.
+        0 => Decoded {
+            flows: sflow::decode(data)?,
+            errors: Vec::new(),
+        },
+        version => {
+            return Err(DecodeError::UnsupportedVersion {
+                version: version.into(),
+            })
+        }
+    };
+
+    for error in errors {
+        emit!(NetflowDecodeError { error, peer });
+    }
+
+    let now = Utc::now();
+    let host = peer.ip().to_string();
+    Ok(flows
+        .into_iter()
+        .map(|flow| {
+            let mut log = LogEvent::from(flow);
+            log.insert(log_schema().source_type_key(), Bytes::from("netflow"));
+            log.insert(log_schema().timestamp_key(), now);
+            log.insert(log_schema().host_key(), host.clone());
+            Event::from(log)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{
+        event::EventStatus,
+        test_util::{
+            collect_ready,
+            components::{assert_source_compliance, SOCKET_HIGH_CARDINALITY_PUSH_SOURCE_TAGS},
+            next_addr,
+        },
+        SourceSender,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<NetflowConfig>();
+    }
+
+    #[test]
+    fn decodes_each_version() {
+        let mut templates = TemplateCache::default();
+        let peer = template::tests::exporter();
+
+        for (data, flow_type, count) in [
+            (netflow_v5::tests::datagram(), "netflow_v5", 1),
+            (template::tests::v9_datagram(true), "netflow_v9", 2),
+            (sflow::tests::datagram(), "sflow", 1),
+        ] {
+            let events = decode_datagram(&mut templates, &data, peer).unwrap();
+
+            assert_eq!(events.len(), count);
+            let log = events[0].as_log();
+            assert_eq!(log["flow_type"], flow_type.into());
+            assert_eq!(log[log_schema().source_type_key()], "netflow".into());
+            assert_eq!(log[log_schema().host_key()], peer.ip().to_string().into());
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_versions() {
+        let mut templates = TemplateCache::default();
+
+        assert_eq!(
+            decode_datagram(&mut templates, &[0, 1, 0, 0], template::tests::exporter()),
+            Err(DecodeError::UnsupportedVersion { version: 1 })
+        );
+    }
+
+    #[tokio::test]
+    async fn receives_flows() {
+        assert_source_compliance(&SOCKET_HIGH_CARDINALITY_PUSH_SOURCE_TAGS, async {
+            let address = next_addr();
+            let config: NetflowConfig =
+                toml::from_str(&format!(r#"address = "{}""#, address)).unwrap();
+
+            let (tx, mut rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+            let source = config
+                .build(SourceContext::new_test(tx, None))
+                .await
+                .unwrap();
+            tokio::spawn(source);
+            tokio::time::sleep(Duration::from_millis(100)).await;
+
+            let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+            // The records of the second datagram are decoded with the template of the first.
+            for data in [
+                template::tests::v9_datagram(true),
+                template::tests::v9_datagram(false),
+            ] {
+                socket.send_to(&data, address).await.unwrap();
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+
+            let events = collect_ready(&mut rx).await;
+            assert_eq!(events.len(), 4);
+            for event in events {
+                assert_eq!(event.as_log()["flow_type"], "netflow_v9".into());
+            }
+        })
+        .await;
+    }
+}
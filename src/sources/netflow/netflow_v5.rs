@@ -0,0 +1,138 @@
+//! NetFlow v5, whose records have a fixed format.
+
+use super::{
+    decode::{DecodeError, Reader},
+    fields::{timestamp_ms, Clock, Flow},
+};
+
+const HEADER_LEN: usize = 24;
+const RECORD_LEN: usize = 48;
+
+pub(super) fn decode(data: &[u8]) -> Result<Vec<Flow>, DecodeError> {
+    let mut reader = Reader::new(data);
+    let _version = reader.u16()?;
+    let count = usize::from(reader.u16()?);
+    let uptime_ms = reader.u32()?;
+    let secs = reader.u32()?;
+    let nsecs = reader.u32()?;
+    let _sequence = reader.u32()?;
+    let engine_type = reader.u8()?;
+    let engine_id = reader.u8()?;
+    let sampling = reader.u16()?;
+
+    if data.len() < HEADER_LEN + count * RECORD_LEN {
+        return Err(DecodeError::InvalidLength { length: data.len() });
+    }
+
+    let clock = Clock {
+        uptime_ms,
+        export_ms: i64::from(secs) * 1000 + i64::from(nsecs / 1_000_000),
+    };
+
+    let mut flows = Vec::with_capacity(count);
+    for _ in 0..count {
+        let mut flow = Flow::new();
+        flow.insert("flow_type".to_string(), "netflow_v5".into());
+        flow.insert("export_time".to_string(), timestamp_ms(clock.export_ms));
+        flow.insert("engine_type".to_string(), engine_type.into());
+        flow.insert("engine_id".to_string(), engine_id.into());
+        // The two high bits are the sampling mode, the others the interval.
+        let interval = sampling & 0x3fff;
+        if interval != 0 {
+            flow.insert("sampling_interval".to_string(), interval.into());
+        }
+
+        flow.insert("src_addr".to_string(), reader.ipv4()?.to_string().into());
+        flow.insert("dst_addr".to_string(), reader.ipv4()?.to_string().into());
+        flow.insert("next_hop".to_string(), reader.ipv4()?.to_string().into());
+        flow.insert("input_interface".to_string(), reader.u16()?.into());
+        flow.insert("output_interface".to_string(), reader.u16()?.into());
+        flow.insert("packets".to_string(), reader.u32()?.into());
+        flow.insert("bytes".to_string(), reader.u32()?.into());
+        flow.insert("flow_start".to_string(), clock.timestamp(reader.u32()?));
+        flow.insert("flow_end".to_string(), clock.timestamp(reader.u32()?));
+        flow.insert("src_port".to_string(), reader.u16()?.into());
+        flow.insert("dst_port".to_string(), reader.u16()?.into());
+        reader.skip(1)?;
+        flow.insert("tcp_flags".to_string(), reader.u8()?.into());
+        flow.insert("protocol".to_string(), reader.u8()?.into());
+        flow.insert("tos".to_string(), reader.u8()?.into());
+        flow.insert("src_as".to_string(), reader.u16()?.into());
+        flow.insert("dst_as".to_string(), reader.u16()?.into());
+        flow.insert("src_mask".to_string(), reader.u8()?.into());
+        flow.insert("dst_mask".to_string(), reader.u8()?.into());
+        reader.skip(2)?;
+
+        flows.push(flow);
+    }
+
+    Ok(flows)
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+
+    /// Encodes a NetFlow v5 datagram with a single TCP flow.
+    pub(in crate::sources::netflow) fn datagram() -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&5u16.to_be_bytes());
+        data.extend_from_slice(&1u16.to_be_bytes());
+        data.extend_from_slice(&60_000u32.to_be_bytes());
+        data.extend_from_slice(&1_600_000_000u32.to_be_bytes());
+        data.extend_from_slice(&0u32.to_be_bytes());
+        data.extend_from_slice(&7u32.to_be_bytes());
+        data.extend_from_slice(&[0, 1]);
+        data.extend_from_slice(&0u16.to_be_bytes());
+
+        data.extend_from_slice(&[10, 0, 0, 1]);
+        data.extend_from_slice(&[192, 168, 1, 10]);
+        data.extend_from_slice(&[10, 0, 0, 254]);
+        data.extend_from_slice(&3u16.to_be_bytes());
+        data.extend_from_slice(&4u16.to_be_bytes());
+        data.extend_from_slice(&10u32.to_be_bytes());
+        data.extend_from_slice(&1500u32.to_be_bytes());
+        data.extend_from_slice(&50_000u32.to_be_bytes());
+        data.extend_from_slice(&59_000u32.to_be_bytes());
+        data.extend_from_slice(&51_234u16.to_be_bytes());
+        data.extend_from_slice(&443u16.to_be_bytes());
+        data.extend_from_slice(&[0, 0x1b, 6, 0]);
+        data.extend_from_slice(&64_512u16.to_be_bytes());
+        data.extend_from_slice(&64_513u16.to_be_bytes());
+        data.extend_from_slice(&[24, 16, 0, 0]);
+        data
+    }
+
+    #[test]
+    fn decodes_records() {
+        let flows = decode(&datagram()).unwrap();
+
+        assert_eq!(flows.len(), 1);
+        let flow = &flows[0];
+        assert_eq!(flow["flow_type"], "netflow_v5".into());
+        assert_eq!(flow["src_addr"], "10.0.0.1".into());
+        assert_eq!(flow["dst_addr"], "192.168.1.10".into());
+        assert_eq!(flow["src_port"], 51234.into());
+        assert_eq!(flow["dst_port"], 443.into());
+        assert_eq!(flow["protocol"], 6.into());
+        assert_eq!(flow["tcp_flags"], 0x1b.into());
+        assert_eq!(flow["bytes"], 1500.into());
+        assert_eq!(flow["packets"], 10.into());
+        assert_eq!(flow["src_as"], 64512.into());
+        assert_eq!(flow["dst_mask"], 16.into());
+        assert_eq!(flow["flow_start"], timestamp_ms(1_599_999_990_000));
+        assert_eq!(flow["flow_end"], timestamp_ms(1_599_999_999_000));
+    }
+
+    #[test]
+    fn rejects_truncated_datagrams() {
+        let data = datagram();
+
+        assert_eq!(
+            decode(&data[..data.len() - 1]),
+            Err(DecodeError::InvalidLength {
+                length: data.len() - 1
+            })
+        );
+    }
+}
@@ -0,0 +1,263 @@
+//! sFlow v5, whose flow samples hold the headers of sampled packets.
+
+use super::{
+    decode::{DecodeError, Reader},
+    fields::{format_mac, Flow},
+};
+
+const VERSION: u32 = 5;
+
+const FLOW_SAMPLE: u32 = 1;
+const EXPANDED_FLOW_SAMPLE: u32 = 3;
+
+const RAW_PACKET_HEADER: u32 = 1;
+const ETHERNET_FRAME: u32 = 2;
+const IPV4_DATA: u32 = 3;
+const EXTENDED_SWITCH: u32 = 1001;
+
+const HEADER_PROTOCOL_ETHERNET: u32 = 1;
+
+const ETHERTYPE_VLAN: u16 = 0x8100;
+const ETHERTYPE_IPV4: u16 = 0x0800;
+const ETHERTYPE_IPV6: u16 = 0x86dd;
+
+const PROTOCOL_TCP: u8 = 6;
+const PROTOCOL_UDP: u8 = 17;
+
+pub(super) fn decode(data: &[u8]) -> Result<Vec<Flow>, DecodeError> {
+    let mut reader = Reader::new(data);
+    let version = reader.u32()?;
+    if version != VERSION {
+        return Err(DecodeError::UnsupportedVersion { version });
+    }
+    let agent_address = match reader.u32()? {
+        1 => reader.ipv4()?.to_string(),
+        2 => reader.ipv6()?.to_string(),
+        _ => String::new(),
+    };
+    let sub_agent_id = reader.u32()?;
+    let _sequence = reader.u32()?;
+    let _uptime = reader.u32()?;
+    let count = reader.u32()?;
+
+    let mut flows = Vec::new();
+    for _ in 0..count {
+        // The format is an enterprise number followed by the format for that enterprise, only the
+        // standard formats, of enterprise 0, are decoded.
+        let format = reader.u32()?;
+        let length = reader.u32()? as usize;
+        let mut sample = reader.sub(length)?;
+
+        let mut flow = Flow::new();
+        flow.insert("flow_type".to_string(), "sflow".into());
+        flow.insert("agent_address".to_string(), agent_address.clone().into());
+        flow.insert("sub_agent_id".to_string(), sub_agent_id.into());
+
+        let record_count = match format {
+            FLOW_SAMPLE => {
+                let _sequence = sample.u32()?;
+                let _source_id = sample.u32()?;
+                flow.insert("sampling_interval".to_string(), sample.u32()?.into());
+                let _sample_pool = sample.u32()?;
+                let _drops = sample.u32()?;
+                // The two high bits are the format of the interface, the others its index.
+                flow.insert(
+                    "input_interface".to_string(),
+                    (sample.u32()? & 0x3fff_ffff).into(),
+                );
+                flow.insert(
+                    "output_interface".to_string(),
+                    (sample.u32()? & 0x3fff_ffff).into(),
+                );
+                sample.u32()?
+            }
+            EXPANDED_FLOW_SAMPLE => {
+                let _sequence = sample.u32()?;
+                let _source_id_type = sample.u32()?;
+                let _source_id_index = sample.u32()?;
+                flow.insert("sampling_interval".to_string(), sample.u32()?.into());
+                let _sample_pool = sample.u32()?;
+                let _drops = sample.u32()?;
+                let _input_format = sample.u32()?;
+                flow.insert("input_interface".to_string(), sample.u32()?.into());
+                let _output_format = sample.u32()?;
+                flow.insert("output_interface".to_string(), sample.u32()?.into());
+                sample.u32()?
+            }
+            // Counter samples, and samples of other enterprises, are skipped.
+            _ => continue,
+        };
+
+        for _ in 0..record_count {
+            let format = sample.u32()?;
+            let length = sample.u32()? as usize;
+            let mut record = sample.sub(length)?;
+
+            match format {
+                RAW_PACKET_HEADER => {
+                    let protocol = record.u32()?;
+                    flow.insert("bytes".to_string(), record.u32()?.into());
+                    let _stripped = record.u32()?;
+                    let header_length = record.u32()? as usize;
+                    let header = record.sub(header_length)?;
+                    if protocol == HEADER_PROTOCOL_ETHERNET {
+                        // Headers are truncated to the sampled length, so whatever could be
+                        // decoded is kept.
+                        let _ = decode_ethernet(header, &mut flow);
+                    }
+                }
+                ETHERNET_FRAME => {
+                    flow.insert("bytes".to_string(), record.u32()?.into());
+                    // The addresses are padded to 8 bytes.
+                    let src_mac = record.bytes(8)?;
+                    let dst_mac = record.bytes(8)?;
+                    flow.insert("src_mac".to_string(), format_mac(&src_mac[..6]).into());
+                    flow.insert("dst_mac".to_string(), format_mac(&dst_mac[..6]).into());
+                }
+                IPV4_DATA => {
+                    flow.insert("bytes".to_string(), record.u32()?.into());
+                    flow.insert("protocol".to_string(), record.u32()?.into());
+                    flow.insert("src_addr".to_string(), record.ipv4()?.to_string().into());
+                    flow.insert("dst_addr".to_string(), record.ipv4()?.to_string().into());
+                    flow.insert("src_port".to_string(), record.u32()?.into());
+                    flow.insert("dst_port".to_string(), record.u32()?.into());
+                    flow.insert("tcp_flags".to_string(), record.u32()?.into());
+                    flow.insert("tos".to_string(), record.u32()?.into());
+                }
+                EXTENDED_SWITCH => {
+                    flow.insert("vlan_id".to_string(), record.u32()?.into());
+                    let _src_priority = record.u32()?;
+                    flow.insert("post_vlan_id".to_string(), record.u32()?.into());
+                }
+                _ => {}
+            }
+        }
+
+        // Each flow sample is a single sampled packet.
+        flow.insert("packets".to_string(), 1.into());
+        flows.push(flow);
+    }
+
+    Ok(flows)
+}
+
+/// Decodes the header of an Ethernet frame, and the IP and transport headers it holds.
+fn decode_ethernet(mut header: Reader<'_>, flow: &mut Flow) -> Result<(), DecodeError> {
+    flow.insert("dst_mac".to_string(), format_mac(header.bytes(6)?).into());
+    flow.insert("src_mac".to_string(), format_mac(header.bytes(6)?).into());
+
+    let mut ethertype = header.u16()?;
+    if ethertype == ETHERTYPE_VLAN {
+        flow.insert("vlan_id".to_string(), (header.u16()? & 0x0fff).into());
+        ethertype = header.u16()?;
+    }
+
+    let protocol = match ethertype {
+        ETHERTYPE_IPV4 => {
+            let version_ihl = header.u8()?;
+            flow.insert("ip_version".to_string(), 4.into());
+            flow.insert("tos".to_string(), header.u8()?.into());
+            header.skip(7)?;
+            let protocol = header.u8()?;
+            header.skip(2)?;
+            flow.insert("src_addr".to_string(), header.ipv4()?.to_string().into());
+            flow.insert("dst_addr".to_string(), header.ipv4()?.to_string().into());
+            // Skips the options, whose length is what the header length leaves after 20 bytes.
+            header.skip((usize::from(version_ihl & 0x0f) * 4).saturating_sub(20))?;
+            protocol
+        }
+        ETHERTYPE_IPV6 => {
+            let first = header.u32()?;
+            flow.insert("ip_version".to_string(), 6.into());
+            flow.insert("tos".to_string(), ((first >> 20) & 0xff).into());
+            header.skip(2)?;
+            let protocol = header.u8()?;
+            header.skip(1)?;
+            flow.insert("src_addr".to_string(), header.ipv6()?.to_string().into());
+            flow.insert("dst_addr".to_string(), header.ipv6()?.to_string().into());
+            protocol
+        }
+        _ => return Ok(()),
+    };
+    flow.insert("protocol".to_string(), protocol.into());
+
+    if protocol == PROTOCOL_TCP || protocol == PROTOCOL_UDP {
+        flow.insert("src_port".to_string(), header.u16()?.into());
+        flow.insert("dst_port".to_string(), header.u16()?.into());
+    }
+    if protocol == PROTOCOL_TCP {
+        header.skip(9)?;
+        flow.insert("tcp_flags".to_string(), header.u8()?.into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+
+    fn words(values: &[u32]) -> Vec<u8> {
+        values
+            .iter()
+            .flat_map(|value| value.to_be_bytes())
+            .collect()
+    }
+
+    /// Encodes an sFlow datagram with a flow sample of a TCP packet in a VLAN.
+    pub(in crate::sources::netflow) fn datagram() -> Vec<u8> {
+        let mut packet = Vec::new();
+        packet.extend_from_slice(&[0, 0x1b, 0x21, 0x3c, 0x9d, 0xf8]);
+        packet.extend_from_slice(&[0, 0x1b, 0x21, 0x3c, 0x9d, 0xf9]);
+        packet.extend_from_slice(&[0x81, 0x00, 0x00, 0x64, 0x08, 0x00]);
+        packet.extend_from_slice(&[0x45, 0x10, 0, 60, 0, 0, 0x40, 0, 64, 6, 0, 0]);
+        packet.extend_from_slice(&[10, 0, 0, 1, 10, 0, 0, 2]);
+        packet.extend_from_slice(&[0xc3, 0x50, 0x00, 0x16, 0, 0, 0, 0, 0, 0, 0, 0, 0x50, 0x02]);
+        // Padded to 4 bytes.
+        let header_length = packet.len() as u32;
+        packet.resize((packet.len() + 3) / 4 * 4, 0);
+
+        let record = [words(&[1, 1514, 4, header_length]), packet].concat();
+        let records = [words(&[RAW_PACKET_HEADER, record.len() as u32]), record].concat();
+        let sample = [words(&[1, 7, 512, 4096, 0, 3, 4, 1]), records].concat();
+
+        let mut data = words(&[5, 1, 0xc000_0201, 0, 1, 60_000, 2]);
+        data.extend(words(&[FLOW_SAMPLE, sample.len() as u32]));
+        data.extend(sample);
+        // A counter sample, which is skipped.
+        data.extend(words(&[2, 4, 0]));
+        data
+    }
+
+    #[test]
+    fn decodes_flow_samples() {
+        let flows = decode(&datagram()).unwrap();
+
+        assert_eq!(flows.len(), 1);
+        let flow = &flows[0];
+        assert_eq!(flow["flow_type"], "sflow".into());
+        assert_eq!(flow["agent_address"], "192.0.2.1".into());
+        assert_eq!(flow["sampling_interval"], 512.into());
+        assert_eq!(flow["input_interface"], 3.into());
+        assert_eq!(flow["output_interface"], 4.into());
+        assert_eq!(flow["bytes"], 1514.into());
+        assert_eq!(flow["packets"], 1.into());
+        assert_eq!(flow["dst_mac"], "00:1b:21:3c:9d:f8".into());
+        assert_eq!(flow["vlan_id"], 100.into());
+        assert_eq!(flow["ip_version"], 4.into());
+        assert_eq!(flow["src_addr"], "10.0.0.1".into());
+        assert_eq!(flow["dst_addr"], "10.0.0.2".into());
+        assert_eq!(flow["protocol"], 6.into());
+        assert_eq!(flow["src_port"], 50000.into());
+        assert_eq!(flow["dst_port"], 22.into());
+        assert_eq!(flow["tcp_flags"], 2.into());
+    }
+
+    #[test]
+    fn rejects_other_versions() {
+        assert_eq!(
+            decode(&words(&[4, 1])),
+            Err(DecodeError::UnsupportedVersion { version: 4 })
+        );
+    }
+}
@@ -0,0 +1,481 @@
+//! NetFlow v9 and IPFIX, whose records are described by templates sent by each exporter.
+
+use std::{collections::HashMap, net::SocketAddr};
+
+use super::{
+    decode::{DecodeError, Reader},
+    fields::{decode_unknown, insert_element, timestamp_ms, Clock, Flow},
+};
+
+const NETFLOW_V9: u16 = 9;
+const IPFIX: u16 = 10;
+
+const V9_TEMPLATE_SET: u16 = 0;
+const V9_OPTIONS_TEMPLATE_SET: u16 = 1;
+const IPFIX_TEMPLATE_SET: u16 = 2;
+const IPFIX_OPTIONS_TEMPLATE_SET: u16 = 3;
+const MIN_DATA_SET_ID: u16 = 256;
+
+/// The length of IPFIX fields whose length is given in each record.
+const VARIABLE_LENGTH: u16 = 65535;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct TemplateField {
+    enterprise: u32,
+    id: u16,
+    length: u16,
+    /// Whether this is a scope field of a NetFlow v9 options template, whose types are not
+    /// information elements.
+    scope: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Template {
+    fields: Vec<TemplateField>,
+    options: bool,
+}
+
+impl Template {
+    /// The length of the shortest record, with one byte for each variable length field.
+    fn min_record_len(&self) -> usize {
+        self.fields
+            .iter()
+            .map(|field| match field.length {
+                VARIABLE_LENGTH => 1,
+                length => usize::from(length),
+            })
+            .sum()
+    }
+}
+
+/// The key of a template, which is only valid for its exporter and observation domain.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+struct TemplateKey {
+    exporter: SocketAddr,
+    version: u16,
+    domain: u32,
+    template_id: u16,
+}
+
+/// The flows of a datagram, along with the errors of the sets that couldn't be decoded.
+#[derive(Debug, Default)]
+pub(super) struct Decoded {
+    pub(super) flows: Vec<Flow>,
+    pub(super) errors: Vec<DecodeError>,
+}
+
+/// The templates received from each exporter.
+#[derive(Debug, Default)]
+pub(super) struct TemplateCache {
+    templates: HashMap<TemplateKey, Template>,
+}
+
+impl TemplateCache {
+    pub(super) fn decode_v9(
+        &mut self,
+        exporter: SocketAddr,
+        data: &[u8],
+    ) -> Result<Decoded, DecodeError> {
+        let mut reader = Reader::new(data);
+        let _version = reader.u16()?;
+        let _count = reader.u16()?;
+        let uptime_ms = reader.u32()?;
+        let secs = reader.u32()?;
+        let _sequence = reader.u32()?;
+        let domain = reader.u32()?;
+
+        let clock = Clock {
+            uptime_ms,
+            export_ms: i64::from(secs) * 1000,
+        };
+        let context = Context {
+            exporter,
+            version: NETFLOW_V9,
+            domain,
+            clock: Some(clock),
+            flow_type: "netflow_v9",
+        };
+
+        self.decode_sets(reader, &context, |cache, set_id, set| match set_id {
+            V9_TEMPLATE_SET => cache.read_v9_templates(set, &context, false),
+            V9_OPTIONS_TEMPLATE_SET => cache.read_v9_templates(set, &context, true),
+            _ => Ok(()),
+        })
+    }
+
+    pub(super) fn decode_ipfix(
+        &mut self,
+        exporter: SocketAddr,
+        data: &[u8],
+    ) -> Result<Decoded, DecodeError> {
+        let mut reader = Reader::new(data);
+        let _version = reader.u16()?;
+        let length = usize::from(reader.u16()?);
+        let export_secs = reader.u32()?;
+        let _sequence = reader.u32()?;
+        let domain = reader.u32()?;
+
+        if length < 16 || length > data.len() {
+            return Err(DecodeError::InvalidLength { length });
+        }
+        let reader = Reader::new(&data[16..length]);
+
+        let context = Context {
+            exporter,
+            version: IPFIX,
+            domain,
+            clock: None,
+            flow_type: "ipfix",
+        };
+        // Exported records are timestamped with the export time, as IPFIX has no uptime.
+        let export_time = timestamp_ms(i64::from(export_secs) * 1000);
+
+        let mut decoded =
+            self.decode_sets(reader, &context, |cache, set_id, set| match set_id {
+                IPFIX_TEMPLATE_SET => cache.read_ipfix_templates(set, &context, false),
+                IPFIX_OPTIONS_TEMPLATE_SET => cache.read_ipfix_templates(set, &context, true),
+                _ => Ok(()),
+            })?;
+        for flow in &mut decoded.flows {
+            flow.insert("export_time".to_string(), export_time.clone());
+        }
+        Ok(decoded)
+    }
+
+    /// Decodes the sets of a datagram, with template sets read by `read_templates`.
+    fn decode_sets(
+        &mut self,
+        mut reader: Reader<'_>,
+        context: &Context,
+        read_templates: impl Fn(&mut Self, u16, Reader<'_>) -> Result<(), DecodeError>,
+    ) -> Result<Decoded, DecodeError> {
+        let mut decoded = Decoded::default();
+
+        // Sets are padded, so there can be fewer bytes left than a set header.
+        while reader.remaining() >= 4 {
+            let set_id = reader.u16()?;
+            let length = usize::from(reader.u16()?);
+            if length < 4 {
+                return Err(DecodeError::InvalidLength { length });
+            }
+            let set = reader.sub(length - 4)?;
+
+            if set_id < MIN_DATA_SET_ID {
+                read_templates(self, set_id, set)?;
+                continue;
+            }
+
+            let key = context.key(set_id);
+            match self.templates.get(&key) {
+                Some(template) => match read_records(set, template, context) {
+                    Ok(flows) => decoded.flows.extend(flows),
+                    Err(error) => decoded.errors.push(error),
+                },
+                None => decoded.errors.push(DecodeError::MissingTemplate {
+                    domain: context.domain,
+                    template_id: set_id,
+                }),
+            }
+        }
+
+        Ok(decoded)
+    }
+
+    fn read_v9_templates(
+        &mut self,
+        mut set: Reader<'_>,
+        context: &Context,
+        options: bool,
+    ) -> Result<(), DecodeError> {
+        while set.remaining() >= 4 {
+            let template_id = set.u16()?;
+            let (scope_count, field_count) = if options {
+                // Options templates give the length of their scope and option fields, in bytes.
+                let scope_length = usize::from(set.u16()?);
+                let option_length = usize::from(set.u16()?);
+                (scope_length / 4, (scope_length + option_length) / 4)
+            } else {
+                (0, usize::from(set.u16()?))
+            };
+
+            let fields = (0..field_count)
+                .map(|index| {
+                    Ok::<_, DecodeError>(TemplateField {
+                        enterprise: 0,
+                        id: set.u16()?,
+                        length: set.u16()?,
+                        scope: index < scope_count,
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            self.insert(context.key(template_id), Template { fields, options });
+        }
+        Ok(())
+    }
+
+    fn read_ipfix_templates(
+        &mut self,
+        mut set: Reader<'_>,
+        context: &Context,
+        options: bool,
+    ) -> Result<(), DecodeError> {
+        while set.remaining() >= 4 {
+            let template_id = set.u16()?;
+            let field_count = usize::from(set.u16()?);
+            if field_count == 0 {
+                // Templates are withdrawn by sending them without fields.
+                self.templates.remove(&context.key(template_id));
+                continue;
+            }
+            if options {
+                let _scope_count = set.u16()?;
+            }
+
+            let fields = (0..field_count)
+                .map(|_| {
+                    let id = set.u16()?;
+                    let length = set.u16()?;
+                    // The high bit of the element ID marks enterprise-specific elements.
+                    let enterprise = if id & 0x8000 != 0 { set.u32()? } else { 0 };
+                    Ok::<_, DecodeError>(TemplateField {
+                        enterprise,
+                        id: id & 0x7fff,
+                        length,
+                        scope: false,
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            self.insert(context.key(template_id), Template { fields, options });
+        }
+        Ok(())
+    }
+
+    fn insert(&mut self, key: TemplateKey, template: Template) {
+        if self.templates.get(&key) != Some(&template) {
+            debug!(
+                message = "Received template.",
+                exporter = %key.exporter,
+                domain = key.domain,
+                template_id = key.template_id,
+            );
+            self.templates.insert(key, template);
+        }
+    }
+}
+
+struct Context {
+    exporter: SocketAddr,
+    version: u16,
+    domain: u32,
+    clock: Option<Clock>,
+    flow_type: &'static str,
+}
+
+impl Context {
+    const fn key(&self, template_id: u16) -> TemplateKey {
+        TemplateKey {
+            exporter: self.exporter,
+            version: self.version,
+            domain: self.domain,
+            template_id,
+        }
+    }
+}
+
+fn read_records(
+    mut set: Reader<'_>,
+    template: &Template,
+    context: &Context,
+) -> Result<Vec<Flow>, DecodeError> {
+    let min_record_len = template.min_record_len();
+    if min_record_len == 0 {
+        return Ok(Vec::new());
+    }
+
+    let mut flows = Vec::new();
+    // Sets are padded, so there can be fewer bytes left than a record.
+    while set.remaining() >= min_record_len {
+        let mut flow = Flow::new();
+        flow.insert("flow_type".to_string(), context.flow_type.into());
+        flow.insert("observation_domain_id".to_string(), context.domain.into());
+        if template.options {
+            flow.insert("record_type".to_string(), "options".into());
+        }
+
+        for field in &template.fields {
+            let length = match field.length {
+                VARIABLE_LENGTH => match set.u8()? {
+                    255 => usize::from(set.u16()?),
+                    length => usize::from(length),
+                },
+                length => usize::from(length),
+            };
+            let value = set.bytes(length)?;
+
+            if field.scope {
+                flow.insert(format!("scope_{}", field.id), decode_unknown(value));
+            } else {
+                insert_element(&mut flow, field.enterprise, field.id, value, context.clock);
+            }
+        }
+
+        if let Some(clock) = context.clock {
+            flow.insert("export_time".to_string(), timestamp_ms(clock.export_ms));
+        }
+        flows.push(flow);
+    }
+
+    Ok(flows)
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+
+    pub(in crate::sources::netflow) fn exporter() -> SocketAddr {
+        "10.0.0.254:40000".parse().unwrap()
+    }
+
+    fn set(id: u16, contents: &[u8]) -> Vec<u8> {
+        let mut set = Vec::new();
+        set.extend_from_slice(&id.to_be_bytes());
+        set.extend_from_slice(&(contents.len() as u16 + 4).to_be_bytes());
+        set.extend_from_slice(contents);
+        set
+    }
+
+    fn fields(fields: &[(u16, u16)]) -> Vec<u8> {
+        fields
+            .iter()
+            .flat_map(|(id, length)| [id.to_be_bytes(), length.to_be_bytes()].concat())
+            .collect()
+    }
+
+    /// A record of the template with the source and destination addresses, ports and bytes.
+    fn record(src: [u8; 4], dst: [u8; 4], bytes: u32) -> Vec<u8> {
+        [
+            &src[..],
+            &dst,
+            &1234u16.to_be_bytes(),
+            &53u16.to_be_bytes(),
+            &bytes.to_be_bytes(),
+        ]
+        .concat()
+    }
+
+    const TEMPLATE: &[(u16, u16)] = &[(8, 4), (12, 4), (7, 2), (11, 2), (1, 4)];
+
+    pub(in crate::sources::netflow) fn v9_datagram(with_template: bool) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&9u16.to_be_bytes());
+        data.extend_from_slice(&2u16.to_be_bytes());
+        data.extend_from_slice(&60_000u32.to_be_bytes());
+        data.extend_from_slice(&1_600_000_000u32.to_be_bytes());
+        data.extend_from_slice(&1u32.to_be_bytes());
+        data.extend_from_slice(&42u32.to_be_bytes());
+
+        if with_template {
+            let template = [
+                &256u16.to_be_bytes()[..],
+                &5u16.to_be_bytes(),
+                &fields(TEMPLATE),
+            ]
+            .concat();
+            data.extend(set(V9_TEMPLATE_SET, &template));
+        }
+        // Two records, padded to four bytes.
+        let records = [
+            record([10, 0, 0, 1], [8, 8, 8, 8], 100),
+            record([10, 0, 0, 2], [1, 1, 1, 1], 200),
+            vec![0, 0, 0],
+        ]
+        .concat();
+        data.extend(set(256, &records));
+        data
+    }
+
+    #[test]
+    fn decodes_v9() {
+        let mut cache = TemplateCache::default();
+
+        // Records received before their template are dropped.
+        let decoded = cache.decode_v9(exporter(), &v9_datagram(false)).unwrap();
+        assert!(decoded.flows.is_empty());
+        assert_eq!(
+            decoded.errors,
+            vec![DecodeError::MissingTemplate {
+                domain: 42,
+                template_id: 256
+            }]
+        );
+
+        let decoded = cache.decode_v9(exporter(), &v9_datagram(true)).unwrap();
+        assert!(decoded.errors.is_empty());
+        assert_eq!(decoded.flows.len(), 2);
+        let flow = &decoded.flows[1];
+        assert_eq!(flow["flow_type"], "netflow_v9".into());
+        assert_eq!(flow["observation_domain_id"], 42.into());
+        assert_eq!(flow["src_addr"], "10.0.0.2".into());
+        assert_eq!(flow["dst_addr"], "1.1.1.1".into());
+        assert_eq!(flow["src_port"], 1234.into());
+        assert_eq!(flow["dst_port"], 53.into());
+        assert_eq!(flow["bytes"], 200.into());
+
+        // The template is cached for the exporter only.
+        assert_eq!(cache.templates.len(), 1);
+        let other = "10.0.0.253:40000".parse().unwrap();
+        let decoded = cache.decode_v9(other, &v9_datagram(false)).unwrap();
+        assert_eq!(decoded.errors.len(), 1);
+    }
+
+    fn ipfix_datagram(sets: &[Vec<u8>]) -> Vec<u8> {
+        let sets = sets.concat();
+        let mut data = Vec::new();
+        data.extend_from_slice(&10u16.to_be_bytes());
+        data.extend_from_slice(&(sets.len() as u16 + 16).to_be_bytes());
+        data.extend_from_slice(&1_600_000_000u32.to_be_bytes());
+        data.extend_from_slice(&1u32.to_be_bytes());
+        data.extend_from_slice(&7u32.to_be_bytes());
+        data.extend(sets);
+        data
+    }
+
+    #[test]
+    fn decodes_ipfix() {
+        let mut cache = TemplateCache::default();
+
+        // A template with an enterprise-specific and a variable length field.
+        let template = [
+            &300u16.to_be_bytes()[..],
+            &3u16.to_be_bytes(),
+            &fields(&[(8, 4)]),
+            &(0x8000u16 | 12).to_be_bytes(),
+            &2u16.to_be_bytes(),
+            &9u32.to_be_bytes(),
+            &fields(&[(96, VARIABLE_LENGTH)]),
+        ]
+        .concat();
+        let record = [&[192, 0, 2, 1][..], &[0, 80], &[9], b"https-alt"].concat();
+
+        let data = ipfix_datagram(&[set(IPFIX_TEMPLATE_SET, &template), set(300, &record)]);
+        let decoded = cache.decode_ipfix(exporter(), &data).unwrap();
+
+        assert!(decoded.errors.is_empty());
+        assert_eq!(decoded.flows.len(), 1);
+        let flow = &decoded.flows[0];
+        assert_eq!(flow["flow_type"], "ipfix".into());
+        assert_eq!(flow["observation_domain_id"], 7.into());
+        assert_eq!(flow["src_addr"], "192.0.2.1".into());
+        assert_eq!(flow["field_9_12"], 80.into());
+        assert_eq!(flow["field_96"], "68747470732d616c74".into());
+        assert_eq!(flow["export_time"], timestamp_ms(1_600_000_000_000));
+
+        // Withdrawn templates are removed.
+        let withdrawal = [&300u16.to_be_bytes()[..], &0u16.to_be_bytes()].concat();
+        let data = ipfix_datagram(&[set(IPFIX_TEMPLATE_SET, &withdrawal)]);
+        cache.decode_ipfix(exporter(), &data).unwrap();
+        assert_eq!(cache.templates.len(), 0);
+    }
+}
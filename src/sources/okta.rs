@@ -0,0 +1,321 @@
+use std::{path::PathBuf, time::Duration};
+
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use futures::FutureExt;
+use http::{header, Request, Uri};
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use crate::{
+    config::{DataType, Output, SourceConfig, SourceContext},
+    event::Event,
+    http::HttpClient,
+    sources::util::audit_log::{
+        next_link, run_poller, send_events, string_at, AuditLogClient, AuditLogPoller, AuditRecord,
+        Cursors, PollError,
+    },
+    state_store::StateStoreConfig,
+    tls::{TlsConfig, TlsSettings},
+    SourceSender,
+};
+
+/// The cursor holding the URL of the next page of the System Log.
+const NEXT_CURSOR: &str = "next";
+
+/// Configuration for the `okta` source.
+#[configurable_component(source("okta"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct OktaConfig {
+    /// The Okta domain of the organization, such as `example.okta.com`.
+    domain: String,
+
+    /// An API token of the organization.
+    token: SensitiveString,
+
+    /// The time of the first records pulled, as an RFC 3339 timestamp.
+    ///
+    /// By default, the records of the last seven days are pulled. Once records have been pulled,
+    /// the source resumes from its cursor instead.
+    since: Option<String>,
+
+    /// A filter expression on the records pulled, such as `eventType eq "user.session.start"`.
+    filter: Option<String>,
+
+    /// The maximum number of records of each page.
+    #[serde(default = "default_limit")]
+    limit: u32,
+
+    /// The interval between polls, in seconds.
+    #[serde(default = "default_poll_interval_secs")]
+    poll_interval_secs: u64,
+
+    /// The directory used to persist the cursor of the source, when `state_store` is `file`.
+    ///
+    /// By default, the global `data_dir` option is used. Please make sure the user Vector is running as has write permissions to this directory.
+    data_dir: Option<PathBuf>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    state_store: StateStoreConfig,
+
+    #[configurable(derived)]
+    tls: Option<TlsConfig>,
+}
+
+const fn default_limit() -> u32 {
+    1000
+}
+
+const fn default_poll_interval_secs() -> u64 {
+    60
+}
+
+impl Default for OktaConfig {
+    fn default() -> Self {
+        Self {
+            domain: "example.okta.com".to_string(),
+            token: "${OKTA_API_TOKEN}".to_string().into(),
+            since: None,
+            filter: None,
+            limit: default_limit(),
+            poll_interval_secs: default_poll_interval_secs(),
+            data_dir: None,
+            state_store: StateStoreConfig::default(),
+            tls: None,
+        }
+    }
+}
+
+impl_generate_config_from_default!(OktaConfig);
+
+#[async_trait::async_trait]
+impl SourceConfig for OktaConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        if let Some(since) = &self.since {
+            DateTime::parse_from_rfc3339(since)
+                .map_err(|error| format!("Invalid `since` timestamp {:?}: {}", since, error))?;
+        }
+
+        let store = self.state_store.build(&cx, self.data_dir.as_ref()).await?;
+        let cursors = Cursors::load(store).await?;
+
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = AuditLogClient::new(HttpClient::new(tls, &cx.proxy)?, None, "okta");
+
+        let poller = OktaPoller {
+            first_url: self.first_url()?,
+            token: self.token.clone(),
+        };
+
+        Ok(run_poller(
+            poller,
+            client,
+            cursors,
+            Duration::from_secs(self.poll_interval_secs),
+            cx.shutdown,
+            cx.out,
+        )
+        .boxed())
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+impl OktaConfig {
+    /// Builds the URL of the first page of the System Log, used until a cursor is persisted.
+    fn first_url(&self) -> crate::Result<Uri> {
+        let base = if self.domain.starts_with("http://") || self.domain.starts_with("https://") {
+            self.domain.trim_end_matches('/').to_string()
+        } else {
+            format!("https://{}", self.domain.trim_end_matches('/'))
+        };
+
+        let mut query = url::form_urlencoded::Serializer::new(String::new());
+        query
+            .append_pair("sortOrder", "ASCENDING")
+            .append_pair("limit", &self.limit.to_string());
+        if let Some(since) = &self.since {
+            query.append_pair("since", since);
+        }
+        if let Some(filter) = &self.filter {
+            query.append_pair("filter", filter);
+        }
+
+        Ok(format!("{}/api/v1/logs?{}", base, query.finish()).parse()?)
+    }
+}
+
+/// Pulls the System Log of an organization.
+///
+/// Okta always returns the link of the next page, even once the last record has been pulled, so
+/// that the next poll starts from it. This link is the cursor of the source.
+struct OktaPoller {
+    first_url: Uri,
+    token: SensitiveString,
+}
+
+#[async_trait::async_trait]
+impl AuditLogPoller for OktaPoller {
+    async fn poll(
+        &mut self,
+        client: &AuditLogClient,
+        cursors: &mut Cursors,
+        out: &mut SourceSender,
+    ) -> Result<(), PollError> {
+        let mut url = match cursors.get(NEXT_CURSOR) {
+            Some(next) => next.parse()?,
+            None => self.first_url.clone(),
+        };
+
+        loop {
+            let request = Request::get(&url)
+                .header(
+                    header::AUTHORIZATION,
+                    format!("SSWS {}", self.token.inner()),
+                )
+                .header(header::ACCEPT, "application/json")
+                .body(Bytes::new())?;
+            let (parts, records) = client.get_json::<Vec<serde_json::Value>>(request).await?;
+
+            let done = records.is_empty();
+            if !done {
+                send_events(out, records.into_iter().map(record_to_event).collect()).await?;
+            }
+
+            match next_link(&url, &parts) {
+                Some(next) => {
+                    cursors.set(NEXT_CURSOR, next.to_string()).await;
+                    url = next;
+                }
+                None => return Ok(()),
+            }
+            if done {
+                return Ok(());
+            }
+        }
+    }
+}
+
+fn record_to_event(record: serde_json::Value) -> Event {
+    let timestamp = string_at(&record, "/published")
+        .and_then(|published| DateTime::parse_from_rfc3339(&published).ok())
+        .map(|published| published.with_timezone(&Utc));
+    let fields = AuditRecord {
+        timestamp,
+        id: string_at(&record, "/uuid"),
+        action: string_at(&record, "/eventType"),
+        outcome: string_at(&record, "/outcome/result").map(|result| result.to_lowercase()),
+        actor_id: string_at(&record, "/actor/id"),
+        actor_name: string_at(&record, "/actor/alternateId"),
+        client_ip: string_at(&record, "/client/ipAddress"),
+    };
+    fields.into_log("okta", "okta", record).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, sync::Arc};
+
+    use warp::Filter;
+
+    use super::*;
+    use crate::{
+        state_store::{BoxedStateStore, FileStateStore},
+        test_util::{collect_ready, next_addr, wait_for_tcp},
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<OktaConfig>();
+    }
+
+    #[tokio::test]
+    async fn pulls_pages_and_resumes_from_next_link() {
+        let addr = next_addr();
+        let logs = warp::path!("api" / "v1" / "logs")
+            .and(warp::header::<String>("authorization"))
+            .and(warp::query::<HashMap<String, String>>())
+            .map(
+                move |authorization: String, query: HashMap<String, String>| {
+                    assert_eq!(authorization, "SSWS secret");
+                    let (records, after) = match query.get("after").map(String::as_str) {
+                        None => (
+                            serde_json::json!([
+                                {
+                                    "uuid": "1",
+                                    "published": "2022-01-01T00:00:00.000Z",
+                                    "eventType": "user.session.start",
+                                    "outcome": {"result": "SUCCESS"},
+                                    "actor": {"id": "00u1", "alternateId": "jane@example.com"},
+                                    "client": {"ipAddress": "10.0.0.1"},
+                                },
+                                {"uuid": "2", "published": "2022-01-01T00:00:01.000Z"},
+                            ]),
+                            "2",
+                        ),
+                        Some("2") => (serde_json::json!([{"uuid": "3"}]), "3"),
+                        Some(_) => (serde_json::json!([]), "3"),
+                    };
+                    warp::http::Response::builder()
+                        .header(
+                            "Link",
+                            format!(
+                                "<http://{}/api/v1/logs?after={}>; rel=\"next\"",
+                                addr, after
+                            ),
+                        )
+                        .body(records.to_string())
+                },
+            );
+        tokio::spawn(warp::serve(logs).run(addr));
+        wait_for_tcp(addr).await;
+
+        let config = OktaConfig {
+            domain: format!("http://{}", addr),
+            token: "secret".to_string().into(),
+            ..Default::default()
+        };
+        let mut poller = OktaPoller {
+            first_url: config.first_url().unwrap(),
+            token: config.token.clone(),
+        };
+        let client = AuditLogClient::new(
+            HttpClient::new(None, &Default::default()).unwrap(),
+            None,
+            "okta",
+        );
+        let data_dir = tempfile::tempdir().unwrap();
+        let store: BoxedStateStore = Arc::new(FileStateStore::new(data_dir.path().to_path_buf()));
+        let mut cursors = Cursors::load(Arc::clone(&store)).await.unwrap();
+        let (mut out, rx) = SourceSender::new_test();
+
+        poller.poll(&client, &mut cursors, &mut out).await.unwrap();
+        drop(out);
+        let events = collect_ready(rx).await;
+        assert_eq!(events.len(), 3);
+
+        let log = events[0].as_log();
+        assert_eq!(log["event.id"], "1".into());
+        assert_eq!(log["event.action"], "user.session.start".into());
+        assert_eq!(log["event.outcome"], "success".into());
+        assert_eq!(log["actor.name"], "jane@example.com".into());
+        assert_eq!(log["client.ip"], "10.0.0.1".into());
+        assert_eq!(log["original.actor.id"], "00u1".into());
+
+        // The next poll resumes from the last link, even after a restart.
+        let cursors = Cursors::load(store).await.unwrap();
+        assert_eq!(
+            cursors.get(NEXT_CURSOR).unwrap(),
+            format!("http://{}/api/v1/logs?after=3", addr)
+        );
+    }
+}
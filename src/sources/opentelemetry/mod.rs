@@ -112,6 +112,7 @@ impl SourceConfig for OpentelemetryConfig {
             self.grpc.address,
             grpc_tls_settings,
             grpc_service,
+            None,
             cx.shutdown.clone(),
         )
         .map_err(|error| {
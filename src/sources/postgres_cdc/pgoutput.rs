@@ -0,0 +1,381 @@
+//! Decoding of the messages of the `pgoutput` logical decoding plugin, in version 1 of its
+//! protocol.
+//!
+//! See <https://www.postgresql.org/docs/current/protocol-logicalrep-message-formats.html>.
+
+use bytes::{Buf, Bytes};
+use chrono::{DateTime, TimeZone, Utc};
+use snafu::Snafu;
+
+/// The seconds between the Unix epoch and the PostgreSQL one, 2000-01-01.
+const POSTGRES_EPOCH_SECS: i64 = 946_684_800;
+
+#[derive(Debug, Snafu, PartialEq)]
+pub enum DecodeError {
+    #[snafu(display("Message truncated"))]
+    Truncated,
+
+    #[snafu(display("Unterminated string"))]
+    UnterminatedString,
+
+    #[snafu(display("Unknown message type {:?}", char::from(*tag)))]
+    UnknownMessage { tag: u8 },
+
+    #[snafu(display("Unknown tuple type {:?}", char::from(*tag)))]
+    UnknownTuple { tag: u8 },
+
+    #[snafu(display("Unknown column kind {:?}", char::from(*kind)))]
+    UnknownColumn { kind: u8 },
+}
+
+/// A column of a relation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Column {
+    pub name: String,
+    pub type_oid: u32,
+}
+
+/// The description of a relation, sent before the first change of each relation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Relation {
+    pub id: u32,
+    pub namespace: String,
+    pub name: String,
+    pub columns: Vec<Column>,
+}
+
+/// The value of a column of a tuple.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TupleValue {
+    Null,
+    /// A TOASTed value that didn't change, and so isn't sent.
+    UnchangedToast,
+    Text(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Message {
+    Begin {
+        final_lsn: u64,
+        timestamp: DateTime<Utc>,
+        xid: u32,
+    },
+    Commit {
+        end_lsn: u64,
+        timestamp: DateTime<Utc>,
+    },
+    Relation(Relation),
+    Insert {
+        relation_id: u32,
+        new: Vec<TupleValue>,
+    },
+    Update {
+        relation_id: u32,
+        /// The old tuple, or only its key, when the replica identity of the relation includes it.
+        old: Option<Vec<TupleValue>>,
+        new: Vec<TupleValue>,
+    },
+    Delete {
+        relation_id: u32,
+        old: Vec<TupleValue>,
+    },
+    Truncate {
+        relation_ids: Vec<u32>,
+    },
+    /// The messages that don't describe changes, such as origins and types.
+    Other,
+}
+
+impl Message {
+    pub fn decode(mut data: Bytes) -> Result<Self, DecodeError> {
+        let tag = get_u8(&mut data)?;
+        Ok(match tag {
+            b'B' => Message::Begin {
+                final_lsn: get_u64(&mut data)?,
+                timestamp: get_timestamp(&mut data)?,
+                xid: get_u32(&mut data)?,
+            },
+            b'C' => {
+                let _flags = get_u8(&mut data)?;
+                let _commit_lsn = get_u64(&mut data)?;
+                Message::Commit {
+                    end_lsn: get_u64(&mut data)?,
+                    timestamp: get_timestamp(&mut data)?,
+                }
+            }
+            b'R' => {
+                let id = get_u32(&mut data)?;
+                let namespace = get_string(&mut data)?;
+                let name = get_string(&mut data)?;
+                let _replica_identity = get_u8(&mut data)?;
+                let count = get_u16(&mut data)?;
+                let columns = (0..count)
+                    .map(|_| {
+                        let _flags = get_u8(&mut data)?;
+                        let name = get_string(&mut data)?;
+                        let type_oid = get_u32(&mut data)?;
+                        let _type_modifier = get_u32(&mut data)?;
+                        Ok(Column { name, type_oid })
+                    })
+                    .collect::<Result<_, _>>()?;
+                Message::Relation(Relation {
+                    id,
+                    namespace,
+                    name,
+                    columns,
+                })
+            }
+            b'I' => {
+                let relation_id = get_u32(&mut data)?;
+                expect_tuple(&mut data, b'N')?;
+                Message::Insert {
+                    relation_id,
+                    new: get_tuple(&mut data)?,
+                }
+            }
+            b'U' => {
+                let relation_id = get_u32(&mut data)?;
+                let old = match get_u8(&mut data)? {
+                    b'K' | b'O' => {
+                        let old = get_tuple(&mut data)?;
+                        expect_tuple(&mut data, b'N')?;
+                        Some(old)
+                    }
+                    b'N' => None,
+                    tag => return Err(DecodeError::UnknownTuple { tag }),
+                };
+                Message::Update {
+                    relation_id,
+                    old,
+                    new: get_tuple(&mut data)?,
+                }
+            }
+            b'D' => {
+                let relation_id = get_u32(&mut data)?;
+                match get_u8(&mut data)? {
+                    b'K' | b'O' => Message::Delete {
+                        relation_id,
+                        old: get_tuple(&mut data)?,
+                    },
+                    tag => return Err(DecodeError::UnknownTuple { tag }),
+                }
+            }
+            b'T' => {
+                let count = get_u32(&mut data)?;
+                let _options = get_u8(&mut data)?;
+                let relation_ids = (0..count)
+                    .map(|_| get_u32(&mut data))
+                    .collect::<Result<_, _>>()?;
+                Message::Truncate { relation_ids }
+            }
+            b'O' | b'Y' | b'M' => Message::Other,
+            tag => return Err(DecodeError::UnknownMessage { tag }),
+        })
+    }
+}
+
+/// Formats the LSN as PostgreSQL does, such as `16/B374D848`.
+pub fn format_lsn(lsn: u64) -> String {
+    format!("{:X}/{:X}", lsn >> 32, lsn as u32)
+}
+
+fn expect_tuple(data: &mut Bytes, expected: u8) -> Result<(), DecodeError> {
+    match get_u8(data)? {
+        tag if tag == expected => Ok(()),
+        tag => Err(DecodeError::UnknownTuple { tag }),
+    }
+}
+
+fn get_tuple(data: &mut Bytes) -> Result<Vec<TupleValue>, DecodeError> {
+    let count = get_u16(data)?;
+    (0..count)
+        .map(|_| match get_u8(data)? {
+            b'n' => Ok(TupleValue::Null),
+            b'u' => Ok(TupleValue::UnchangedToast),
+            b't' => {
+                let len = get_u32(data)? as usize;
+                if data.remaining() < len {
+                    return Err(DecodeError::Truncated);
+                }
+                let text = data.split_to(len);
+                Ok(TupleValue::Text(
+                    String::from_utf8_lossy(&text).into_owned(),
+                ))
+            }
+            kind => Err(DecodeError::UnknownColumn { kind }),
+        })
+        .collect()
+}
+
+fn get_string(data: &mut Bytes) -> Result<String, DecodeError> {
+    let end = data
+        .iter()
+        .position(|byte| *byte == 0)
+        .ok_or(DecodeError::UnterminatedString)?;
+    let string = String::from_utf8_lossy(&data[..end]).into_owned();
+    data.advance(end + 1);
+    Ok(string)
+}
+
+fn get_timestamp(data: &mut Bytes) -> Result<DateTime<Utc>, DecodeError> {
+    let micros = get_u64(data)? as i64;
+    Ok(Utc.timestamp(
+        POSTGRES_EPOCH_SECS + micros.div_euclid(1_000_000),
+        (micros.rem_euclid(1_000_000) * 1000) as u32,
+    ))
+}
+
+macro_rules! get_int {
+    ($name:ident, $ty:ty, $get:ident) => {
+        fn $name(data: &mut Bytes) -> Result<$ty, DecodeError> {
+            if data.remaining() < std::mem::size_of::<$ty>() {
+                return Err(DecodeError::Truncated);
+            }
+            Ok(data.$get())
+        }
+    };
+}
+
+get_int!(get_u8, u8, get_u8);
+get_int!(get_u16, u16, get_u16);
+get_int!(get_u32, u32, get_u32);
+get_int!(get_u64, u64, get_u64);
+
+#[cfg(test)]
+mod tests {
+    use bytes::{BufMut, BytesMut};
+
+    use super::*;
+
+    fn put_string(buf: &mut BytesMut, string: &str) {
+        buf.put_slice(string.as_bytes());
+        buf.put_u8(0);
+    }
+
+    fn put_text(buf: &mut BytesMut, text: &str) {
+        buf.put_u8(b't');
+        buf.put_u32(text.len() as u32);
+        buf.put_slice(text.as_bytes());
+    }
+
+    #[test]
+    fn decodes_transaction() {
+        let mut begin = BytesMut::new();
+        begin.put_u8(b'B');
+        begin.put_u64(0x16_B374_D848);
+        begin.put_u64(1_000_500);
+        begin.put_u32(42);
+        assert_eq!(
+            Message::decode(begin.freeze()).unwrap(),
+            Message::Begin {
+                final_lsn: 0x16_B374_D848,
+                timestamp: Utc.timestamp(POSTGRES_EPOCH_SECS + 1, 500_000),
+                xid: 42,
+            }
+        );
+
+        let mut commit = BytesMut::new();
+        commit.put_u8(b'C');
+        commit.put_u8(0);
+        commit.put_u64(0x16_B374_D848);
+        commit.put_u64(0x16_B374_D878);
+        commit.put_u64(1_000_500);
+        assert_eq!(
+            Message::decode(commit.freeze()).unwrap(),
+            Message::Commit {
+                end_lsn: 0x16_B374_D878,
+                timestamp: Utc.timestamp(POSTGRES_EPOCH_SECS + 1, 500_000),
+            }
+        );
+        assert_eq!(format_lsn(0x16_B374_D878), "16/B374D878");
+    }
+
+    #[test]
+    fn decodes_relation_and_changes() {
+        let mut relation = BytesMut::new();
+        relation.put_u8(b'R');
+        relation.put_u32(16385);
+        put_string(&mut relation, "public");
+        put_string(&mut relation, "users");
+        relation.put_u8(b'f');
+        relation.put_u16(2);
+        for (name, type_oid) in [("id", 23), ("name", 25)] {
+            relation.put_u8(1);
+            put_string(&mut relation, name);
+            relation.put_u32(type_oid);
+            relation.put_u32(u32::MAX);
+        }
+        assert_eq!(
+            Message::decode(relation.freeze()).unwrap(),
+            Message::Relation(Relation {
+                id: 16385,
+                namespace: "public".into(),
+                name: "users".into(),
+                columns: vec![
+                    Column {
+                        name: "id".into(),
+                        type_oid: 23
+                    },
+                    Column {
+                        name: "name".into(),
+                        type_oid: 25
+                    },
+                ],
+            })
+        );
+
+        let mut update = BytesMut::new();
+        update.put_u8(b'U');
+        update.put_u32(16385);
+        update.put_u8(b'O');
+        update.put_u16(2);
+        put_text(&mut update, "1");
+        update.put_u8(b'n');
+        update.put_u8(b'N');
+        update.put_u16(2);
+        put_text(&mut update, "1");
+        put_text(&mut update, "alice");
+        assert_eq!(
+            Message::decode(update.freeze()).unwrap(),
+            Message::Update {
+                relation_id: 16385,
+                old: Some(vec![TupleValue::Text("1".into()), TupleValue::Null]),
+                new: vec![
+                    TupleValue::Text("1".into()),
+                    TupleValue::Text("alice".into())
+                ],
+            }
+        );
+
+        let mut delete = BytesMut::new();
+        delete.put_u8(b'D');
+        delete.put_u32(16385);
+        delete.put_u8(b'K');
+        delete.put_u16(2);
+        put_text(&mut delete, "1");
+        delete.put_u8(b'u');
+        assert_eq!(
+            Message::decode(delete.freeze()).unwrap(),
+            Message::Delete {
+                relation_id: 16385,
+                old: vec![TupleValue::Text("1".into()), TupleValue::UnchangedToast],
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_truncated_message() {
+        let mut insert = BytesMut::new();
+        insert.put_u8(b'I');
+        insert.put_u32(16385);
+        insert.put_u8(b'N');
+        insert.put_u16(1);
+        insert.put_u8(b't');
+        insert.put_u32(10);
+        insert.put_slice(b"abc");
+        assert_eq!(
+            Message::decode(insert.freeze()),
+            Err(DecodeError::Truncated)
+        );
+    }
+}
@@ -1,10 +1,4 @@
-use std::{
-    collections::{BTreeMap, HashSet},
-    fmt::Write as _,
-    iter,
-    path::PathBuf,
-    time::Instant,
-};
+use std::{collections::HashSet, fmt::Write as _, iter, path::PathBuf, time::Instant};
 
 use chrono::{DateTime, Utc};
 use futures::{
@@ -30,7 +24,7 @@ use vector_core::ByteSizeOf;
 
 use crate::{
     config::{DataType, Output, SourceConfig, SourceContext},
-    event::metric::{Metric, MetricKind, MetricValue},
+    event::metric::{Metric, MetricKind, MetricTags, MetricValue},
     internal_events::{
         CollectionCompleted, EndpointBytesReceived, EventsReceived, PostgresqlMetricsCollectError,
         StreamClosedError,
@@ -459,7 +453,7 @@ struct PostgresqlMetrics {
     client: PostgresqlClient,
     endpoint: String,
     namespace: Option<String>,
-    tags: BTreeMap<String, String>,
+    tags: MetricTags,
     datname_filter: DatnameFilter,
 }
 
@@ -490,7 +484,7 @@ impl PostgresqlMetrics {
             }
         };
 
-        let mut tags = BTreeMap::new();
+        let mut tags = MetricTags::new();
         tags.insert("endpoint".into(), endpoint.clone());
         tags.insert("host".into(), host);
 
@@ -812,12 +806,7 @@ impl PostgresqlMetrics {
         ))
     }
 
-    fn create_metric(
-        &self,
-        name: &str,
-        value: MetricValue,
-        tags: BTreeMap<String, String>,
-    ) -> Metric {
+    fn create_metric(&self, name: &str, value: MetricValue, tags: MetricTags) -> Metric {
         Metric::new(name, MetricKind::Absolute, value)
             .with_namespace(self.namespace.clone())
             .with_tags(Some(tags))
@@ -4,15 +4,22 @@ use chrono::{DateTime, TimeZone, Utc};
 use prometheus_parser::{proto, GroupKind, MetricGroup, ParserError};
 
 use crate::event::{
-    metric::{Bucket, Metric, MetricKind, MetricValue, Quantile},
+    metric::{
+        Bucket, ExponentialBuckets, ExponentialHistogram, Metric, MetricKind, MetricTags,
+        MetricValue, Quantile,
+    },
     Event,
 };
 
-fn has_values_or_none(tags: BTreeMap<String, String>) -> Option<BTreeMap<String, String>> {
+fn has_values_or_none(tags: BTreeMap<String, String>) -> Option<MetricTags> {
     if tags.is_empty() {
         None
     } else {
-        Some(tags)
+        Some(
+            tags.into_iter()
+                .map(|(key, value)| (key.into(), value))
+                .collect(),
+        )
     }
 }
 
@@ -25,6 +32,71 @@ fn utc_timestamp(timestamp: Option<i64>, default: DateTime<Utc>) -> DateTime<Utc
         .unwrap_or(default)
 }
 
+/// Decodes the buckets of a native histogram, from either the deltas of an integer histogram or the
+/// absolute counts of a float histogram.
+fn native_buckets(
+    spans: &[proto::BucketSpan],
+    deltas: &[i64],
+    counts: &[f64],
+) -> Option<ExponentialBuckets> {
+    let spans = spans
+        .iter()
+        .map(|span| (span.offset, span.length))
+        .collect::<Vec<_>>();
+    let mut buckets = if deltas.is_empty() && !counts.is_empty() {
+        let mut previous = 0;
+        let deltas = counts
+            .iter()
+            .map(|count| {
+                let count = count.round() as i64;
+                let delta = count - previous;
+                previous = count;
+                delta
+            })
+            .collect::<Vec<_>>();
+        ExponentialBuckets::from_spans(&spans, &deltas)?
+    } else {
+        ExponentialBuckets::from_spans(&spans, deltas)?
+    };
+    // Native histogram bucket `index` holds `(base^(index - 1), base^index]`, one off from ours.
+    if !buckets.is_empty() {
+        buckets.offset -= 1;
+    }
+    Some(buckets)
+}
+
+fn native_histogram(histogram: proto::Histogram) -> Option<ExponentialHistogram> {
+    use proto::histogram::{Count, ZeroCount};
+
+    let count = match histogram.count? {
+        Count::CountInt(count) => count,
+        Count::CountFloat(count) => count.round() as u64,
+    };
+    let zero_count = match histogram.zero_count {
+        Some(ZeroCount::ZeroCountInt(count)) => count,
+        Some(ZeroCount::ZeroCountFloat(count)) => count.round() as u64,
+        None => 0,
+    };
+
+    Some(ExponentialHistogram {
+        scale: histogram.schema,
+        zero_count,
+        zero_threshold: histogram.zero_threshold,
+        positive: native_buckets(
+            &histogram.positive_spans,
+            &histogram.positive_deltas,
+            &histogram.positive_counts,
+        )?,
+        negative: native_buckets(
+            &histogram.negative_spans,
+            &histogram.negative_deltas,
+            &histogram.negative_counts,
+        )?,
+        count,
+        sum: histogram.sum,
+    })
+}
+
 pub(super) fn parse_text(packet: &str) -> Result<Vec<Event>, ParserError> {
     prometheus_parser::parse_text(packet).map(reparse_groups)
 }
@@ -105,6 +177,23 @@ fn reparse_groups(groups: Vec<MetricGroup>) -> Vec<Event> {
                     );
                 }
             }
+            GroupKind::NativeHistogram(metrics) => {
+                for (key, metric) in metrics {
+                    // Invalid bucket layouts can't be recovered from, so the series is skipped.
+                    if let Some(histogram) = native_histogram(metric) {
+                        result.push(
+                            Metric::new(
+                                group.name.clone(),
+                                MetricKind::Absolute,
+                                MetricValue::ExponentialHistogram { histogram },
+                            )
+                            .with_timestamp(Some(utc_timestamp(key.timestamp, start)))
+                            .with_tags(has_values_or_none(key.labels))
+                            .into(),
+                        );
+                    }
+                }
+            }
             GroupKind::Summary(metrics) => {
                 for (key, metric) in metrics {
                     result.push(
@@ -1056,4 +1145,59 @@ mod test {
             ]
         );
     }
+
+    #[test]
+    fn test_native_histogram_request() {
+        let request = proto::WriteRequest {
+            metadata: vec![],
+            timeseries: vec![proto::TimeSeries {
+                labels: vec![proto::Label {
+                    name: "__name__".into(),
+                    value: "latency".into(),
+                }],
+                samples: vec![],
+                histograms: vec![proto::Histogram {
+                    count: Some(proto::histogram::Count::CountInt(4)),
+                    sum: 7.5,
+                    schema: 0,
+                    zero_count: Some(proto::histogram::ZeroCount::ZeroCountInt(1)),
+                    // Buckets 1 and 3 of a native histogram, that is `(1, 2]` and `(4, 8]`.
+                    positive_spans: vec![
+                        proto::BucketSpan {
+                            offset: 1,
+                            length: 1,
+                        },
+                        proto::BucketSpan {
+                            offset: 1,
+                            length: 1,
+                        },
+                    ],
+                    positive_deltas: vec![2, -1],
+                    timestamp: TIMESTAMP.timestamp_millis(),
+                    ..Default::default()
+                }],
+            }],
+        };
+
+        let metrics = parse_request(request)
+            .unwrap()
+            .into_iter()
+            .map(Event::into_metric)
+            .collect::<Vec<_>>();
+
+        let mut histogram = ExponentialHistogram::new(0);
+        histogram.record(0.0, 1);
+        histogram.record(1.5, 2);
+        histogram.record(4.5, 1);
+        assert_eq!(histogram.sum, 7.5);
+        assert_event_data_eq!(
+            metrics,
+            vec![Metric::new(
+                "latency",
+                MetricKind::Absolute,
+                MetricValue::ExponentialHistogram { histogram },
+            )
+            .with_timestamp(Some(*TIMESTAMP))]
+        );
+    }
 }
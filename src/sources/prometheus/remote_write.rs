@@ -69,7 +69,7 @@ impl SourceConfig for PrometheusRemoteWriteConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<sources::Source> {
         let source = RemoteWriteSource;
         source.run(
-            self.address,
+            self.address.into(),
             "",
             HttpMethod::Post,
             true,
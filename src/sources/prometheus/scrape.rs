@@ -127,10 +127,11 @@ impl SourceConfig for PrometheusScrapeConfig {
             interval_secs: self.scrape_interval_secs,
             headers: HashMap::new(),
             content_type: "text/plain".to_string(),
-            auth: self.auth.clone(),
+            auth: self.auth.clone().map(Into::into),
             tls,
             proxy: cx.proxy.clone(),
             shutdown: cx.shutdown,
+            once: cx.once,
         };
 
         Ok(http_scrape(inputs, builder, cx.out, HttpMethod::Get).boxed())
@@ -143,6 +144,10 @@ impl SourceConfig for PrometheusScrapeConfig {
     fn can_acknowledge(&self) -> bool {
         false
     }
+
+    fn can_run_once(&self) -> bool {
+        true
+    }
 }
 
 // InstanceInfo stores the scraped instance info and the tag to insert into the log event with. It
@@ -55,6 +55,7 @@ pub async fn subscribe(
                         &key,
                         redis_key.as_deref(),
                         decoder.clone(),
+                        None,
                         &bytes_received,
                         &mut tx,
                     )
@@ -50,6 +50,7 @@ pub async fn watch(
                         &key,
                         redis_key.as_deref(),
                         decoder.clone(),
+                        None,
                         &bytes_received,
                         &mut tx,
                     )
@@ -16,15 +16,18 @@ use vector_core::ByteSizeOf;
 
 use crate::{
     codecs::{Decoder, DecodingConfig},
-    config::{log_schema, GenerateConfig, Output, SourceConfig, SourceContext},
-    event::Event,
+    config::{
+        log_schema, AcknowledgementsConfig, GenerateConfig, Output, SourceConfig, SourceContext,
+    },
+    event::{BatchNotifier, Event},
     internal_events::{EventsReceived, StreamClosedError},
-    serde::{default_decoding, default_framing_message_based},
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
     SourceSender,
 };
 
 mod channel;
 mod list;
+mod stream;
 
 #[derive(Debug, Snafu)]
 enum BuildError {
@@ -46,6 +49,12 @@ pub enum DataTypeConfig {
     ///
     /// This is based on Redis' Pub/Sub capabilities.
     Channel,
+
+    /// The `stream` data type.
+    ///
+    /// Entries are read as a member of a consumer group, and acknowledged once their events are
+    /// processed.
+    Stream,
 }
 
 /// Options for the Redis `list` data type.
@@ -57,6 +66,85 @@ pub struct ListOption {
     method: Method,
 }
 
+/// Options for the Redis `stream` data type.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct StreamOption {
+    /// The name of the consumer group to read entries as.
+    #[serde(default = "default_group")]
+    #[derivative(Default(value = "default_group()"))]
+    group: String,
+
+    /// The name of the consumer within the consumer group.
+    ///
+    /// Each Vector instance reading from the same consumer group must use a distinct name, which
+    /// must also stay the same across restarts for the entries left pending to be read again.
+    #[serde(default = "default_consumer")]
+    #[derivative(Default(value = "default_consumer()"))]
+    consumer: String,
+
+    /// Whether or not to create the consumer group, and the stream, if they do not exist.
+    #[serde(default = "crate::serde::default_true")]
+    #[derivative(Default(value = "true"))]
+    create_group: bool,
+
+    /// The ID of the entry after which a consumer group created by Vector starts reading.
+    ///
+    /// The default, `$`, only reads the entries added after the group is created, and `0` reads
+    /// the whole stream.
+    #[serde(default = "default_start_id")]
+    #[derivative(Default(value = "default_start_id()"))]
+    start_id: String,
+
+    /// The field of the entries holding the message to decode.
+    #[serde(default = "default_stream_field")]
+    #[derivative(Default(value = "default_stream_field()"))]
+    field: String,
+
+    /// The maximum number of entries to read at once.
+    #[serde(default = "default_stream_count")]
+    #[derivative(Default(value = "default_stream_count()"))]
+    count: usize,
+
+    /// The maximum time, in milliseconds, to wait for new entries.
+    #[serde(default = "default_stream_block_ms")]
+    #[derivative(Default(value = "default_stream_block_ms()"))]
+    block_ms: u64,
+
+    /// The time, in seconds, after which the pending entries of other consumers are claimed.
+    ///
+    /// When set, the entries read but not acknowledged by any consumer of the group for longer
+    /// than this are claimed when Vector starts, and read again. This picks up the entries of
+    /// consumers that are gone for good.
+    claim_min_idle_secs: Option<u64>,
+}
+
+fn default_group() -> String {
+    "vector".to_owned()
+}
+
+fn default_consumer() -> String {
+    crate::get_hostname().unwrap_or_else(|_| "vector".to_owned())
+}
+
+fn default_start_id() -> String {
+    "$".to_owned()
+}
+
+fn default_stream_field() -> String {
+    "message".to_owned()
+}
+
+const fn default_stream_count() -> usize {
+    100
+}
+
+const fn default_stream_block_ms() -> u64 {
+    1000
+}
+
 /// Method for getting events from the `list` data type.
 #[configurable_component]
 #[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
@@ -102,6 +190,9 @@ pub struct RedisSourceConfig {
     #[configurable(derived)]
     list: Option<ListOption>,
 
+    #[configurable(derived)]
+    stream: Option<StreamOption>,
+
     /// The Redis URL to connect to.
     ///
     /// The URL must take the form of `protocol://server:port/db` where the `protocol` can either be `redis` or `rediss` for connections secured via TLS.
@@ -126,6 +217,15 @@ pub struct RedisSourceConfig {
     #[serde(default = "default_decoding")]
     #[derivative(Default(value = "default_decoding()"))]
     decoding: DeserializerConfig,
+
+    /// Controls how acknowledgements are handled by this source.
+    ///
+    /// Only the `stream` data type supports acknowledgements. Stream entries are acknowledged with
+    /// `XACK` once their events are delivered, and the others are left pending, to be read again
+    /// when Vector restarts.
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: AcknowledgementsConfig,
 }
 
 impl GenerateConfig for RedisSourceConfig {
@@ -190,6 +290,20 @@ impl SourceConfig for RedisSourceConfig {
                 )
                 .await
             }
+            DataTypeConfig::Stream => {
+                let acknowledgements = cx.do_acknowledgements(&self.acknowledgements);
+                stream::consume(
+                    client,
+                    bytes_received.clone(),
+                    self.key.clone(),
+                    self.redis_key.clone(),
+                    self.stream.clone().unwrap_or_default(),
+                    decoder,
+                    acknowledgements,
+                    cx,
+                )
+                .await
+            }
         }
     }
 
@@ -198,7 +312,7 @@ impl SourceConfig for RedisSourceConfig {
     }
 
     fn can_acknowledge(&self) -> bool {
-        false
+        matches!(self.data_type, DataTypeConfig::Stream)
     }
 }
 
@@ -207,6 +321,7 @@ async fn handle_line(
     key: &str,
     redis_key: Option<&str>,
     decoder: Decoder,
+    batch: Option<&BatchNotifier>,
     bytes_received: &Registered<BytesReceived>,
     out: &mut SourceSender,
 ) -> Result<(), ()> {
@@ -232,7 +347,10 @@ async fn handle_line(
                             event.as_mut_log().insert(redis_key, key);
                         }
                     }
-                    event
+                    match batch {
+                        Some(batch) => event.with_batch_notifier(batch),
+                        None => event,
+                    }
                 });
 
                 if let Err(error) = out.send_batch(events).await {
@@ -295,11 +413,13 @@ mod integration_test {
             list: Some(ListOption {
                 method: Method::Rpop,
             }),
+            stream: None,
             url: REDIS_SERVER.to_owned(),
             key: key.clone(),
             redis_key: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            acknowledgements: Default::default(),
         };
 
         let events = run_and_assert_source_compliance_n(config, 3, &SOURCE_TAGS).await;
@@ -328,11 +448,47 @@ mod integration_test {
             list: Some(ListOption {
                 method: Method::Lpop,
             }),
+            stream: None,
+            url: REDIS_SERVER.to_owned(),
+            key: key.clone(),
+            redis_key: None,
+            framing: default_framing_message_based(),
+            decoding: default_decoding(),
+            acknowledgements: Default::default(),
+        };
+
+        let events = run_and_assert_source_compliance_n(config, 3, &SOURCE_TAGS).await;
+
+        assert_eq!(events[0].as_log()[log_schema().message_key()], "1".into());
+        assert_eq!(events[1].as_log()[log_schema().message_key()], "2".into());
+        assert_eq!(events[2].as_log()[log_schema().message_key()], "3".into());
+    }
+
+    #[tokio::test]
+    async fn redis_source_stream_consumer_group() {
+        let client = redis::Client::open(REDIS_SERVER).unwrap();
+        let mut conn = client.get_tokio_connection_manager().await.unwrap();
+
+        let key = format!("test-stream-{}", random_string(10));
+        debug!("Test key name: {}.", key);
+
+        for message in ["1", "2", "3"] {
+            let _: String = conn.xadd(&key, "*", &[("message", message)]).await.unwrap();
+        }
+
+        let config = RedisSourceConfig {
+            data_type: DataTypeConfig::Stream,
+            list: None,
+            stream: Some(StreamOption {
+                start_id: "0".to_owned(),
+                ..Default::default()
+            }),
             url: REDIS_SERVER.to_owned(),
             key: key.clone(),
             redis_key: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            acknowledgements: true.into(),
         };
 
         let events = run_and_assert_source_compliance_n(config, 3, &SOURCE_TAGS).await;
@@ -351,11 +507,13 @@ mod integration_test {
         let config = RedisSourceConfig {
             data_type: DataTypeConfig::Channel,
             list: None,
+            stream: None,
             url: REDIS_SERVER.to_owned(),
             key: key.clone(),
             redis_key: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            acknowledgements: Default::default(),
         };
 
         let (tx, rx) = SourceSender::new_test();
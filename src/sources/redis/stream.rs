@@ -0,0 +1,244 @@
+use futures::StreamExt;
+use redis::{
+    aio::ConnectionManager,
+    streams::{StreamId, StreamReadOptions, StreamReadReply},
+    AsyncCommands, RedisResult,
+};
+use snafu::{ResultExt, Snafu};
+use vector_common::{
+    finalizer::UnorderedFinalizer,
+    internal_event::{BytesReceived, Registered},
+};
+
+use super::{handle_line, StreamOption};
+use crate::{
+    codecs,
+    config::SourceContext,
+    event::{BatchNotifier, BatchStatus},
+    internal_events::{RedisAckError, RedisReceiveEventError},
+    sources::Source,
+};
+
+// The ID to read new entries from, rather than the pending entries of the consumer.
+const NEW_ENTRIES_ID: &str = ">";
+
+// The ID of the first pending entry of the consumer, and the cursor of a complete `XAUTOCLAIM` scan.
+const FIRST_ENTRY_ID: &str = "0-0";
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Failed to create connection: {}", source))]
+    Connection { source: redis::RedisError },
+    #[snafu(display("Failed to create consumer group: {}", source))]
+    CreateGroup { source: redis::RedisError },
+    #[snafu(display("Failed to claim pending entries: {}", source))]
+    Claim { source: redis::RedisError },
+}
+
+/// The IDs of the stream entries whose events are awaiting acknowledgement.
+#[derive(Debug)]
+struct FinalizerEntry {
+    id: String,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn consume(
+    client: redis::Client,
+    bytes_received: Registered<BytesReceived>,
+    key: String,
+    redis_key: Option<String>,
+    options: StreamOption,
+    decoder: codecs::Decoder,
+    acknowledgements: bool,
+    cx: SourceContext,
+) -> crate::Result<Source> {
+    let mut conn = client
+        .get_tokio_connection_manager()
+        .await
+        .context(ConnectionSnafu {})?;
+    // Reads block the connection they are sent on for up to `block_ms`, so entries are
+    // acknowledged over a connection of their own.
+    let mut ack_conn = client
+        .get_tokio_connection_manager()
+        .await
+        .context(ConnectionSnafu {})?;
+
+    if options.create_group {
+        create_group(&mut conn, &key, &options).await?;
+    }
+    if let Some(min_idle_secs) = options.claim_min_idle_secs {
+        claim_pending(&mut conn, &key, &options, min_idle_secs * 1000).await?;
+    }
+
+    Ok(Box::pin(async move {
+        let mut shutdown = cx.shutdown.clone();
+        let mut tx = cx.out;
+        let (finalizer, mut ack_stream) =
+            UnorderedFinalizer::<FinalizerEntry>::maybe_new(acknowledgements, cx.shutdown);
+
+        // Acknowledging entries while a read is in flight would cancel the read, and the entries
+        // it delivered to the consumer would then stay pending until the next restart, so
+        // acknowledgements are handled by a task of their own.
+        if finalizer.is_some() {
+            let mut ack_conn = ack_conn.clone();
+            let key = key.clone();
+            let group = options.group.clone();
+            tokio::spawn(async move {
+                while let Some((status, entry)) = ack_stream.next().await {
+                    if status == BatchStatus::Delivered {
+                        ack(&mut ack_conn, &key, &group, &[entry.id]).await;
+                    }
+                }
+            });
+        }
+
+        // The pending entries of the consumer, left unacknowledged when it last stopped, are read
+        // first, and then the entries never delivered to the group.
+        let mut next_id = Some(FIRST_ENTRY_ID.to_owned());
+        loop {
+            let id = next_id.as_deref().unwrap_or(NEW_ENTRIES_ID);
+            let res = tokio::select! {
+                res = read_group(&mut conn, &key, &options, id) => res,
+                _ = &mut shutdown => break,
+            };
+
+            let entries = match res {
+                Err(error) => {
+                    emit!(RedisReceiveEventError::from(error));
+                    continue;
+                }
+                Ok(entries) => entries,
+            };
+
+            if next_id.is_some() {
+                next_id = entries.last().map(|entry| entry.id.clone());
+            }
+
+            for entry in entries {
+                let line = match entry.get::<String>(&options.field) {
+                    Some(line) => line,
+                    None => {
+                        warn!(
+                            message = "Stream entry has no message field, skipping.",
+                            id = %entry.id,
+                            field = %options.field,
+                            internal_log_rate_limit = true,
+                        );
+                        ack(&mut ack_conn, &key, &options.group, &[entry.id]).await;
+                        continue;
+                    }
+                };
+
+                let (batch, receiver) = match finalizer {
+                    Some(_) => {
+                        let (batch, receiver) = BatchNotifier::new_with_receiver();
+                        (Some(batch), Some(receiver))
+                    }
+                    None => (None, None),
+                };
+
+                if let Err(()) = handle_line(
+                    line,
+                    &key,
+                    redis_key.as_deref(),
+                    decoder.clone(),
+                    batch.as_ref(),
+                    &bytes_received,
+                    &mut tx,
+                )
+                .await
+                {
+                    return Ok(());
+                }
+
+                match (&finalizer, receiver) {
+                    (Some(finalizer), Some(receiver)) => {
+                        finalizer.add(FinalizerEntry { id: entry.id }, receiver);
+                    }
+                    _ => ack(&mut ack_conn, &key, &options.group, &[entry.id]).await,
+                }
+            }
+        }
+        Ok(())
+    }))
+}
+
+async fn create_group(
+    conn: &mut ConnectionManager,
+    key: &str,
+    options: &StreamOption,
+) -> crate::Result<()> {
+    let res: RedisResult<()> = conn
+        .xgroup_create_mkstream(key, &options.group, &options.start_id)
+        .await;
+    match res {
+        // The group already exists, which is expected whenever the source restarts.
+        Err(error) if error.code() == Some("BUSYGROUP") => Ok(()),
+        res => res.context(CreateGroupSnafu {}).map_err(Into::into),
+    }
+}
+
+/// Claims the entries pending in the group for longer than `min_idle_ms`, so that the entries
+/// read by consumers which are gone are read again by this one.
+async fn claim_pending(
+    conn: &mut ConnectionManager,
+    key: &str,
+    options: &StreamOption,
+    min_idle_ms: u64,
+) -> crate::Result<()> {
+    let mut cursor = FIRST_ENTRY_ID.to_owned();
+    loop {
+        // Only the IDs are returned, as the claimed entries are read from the pending entries of
+        // the consumer afterwards. Redis 7 returns a third element, the IDs of deleted entries.
+        let reply: Vec<redis::Value> = redis::cmd("XAUTOCLAIM")
+            .arg(key)
+            .arg(&options.group)
+            .arg(&options.consumer)
+            .arg(min_idle_ms)
+            .arg(&cursor)
+            .arg("COUNT")
+            .arg(options.count)
+            .arg("JUSTID")
+            .query_async(conn)
+            .await
+            .context(ClaimSnafu {})?;
+
+        cursor = match reply.first() {
+            Some(value) => redis::from_redis_value(value).context(ClaimSnafu {})?,
+            None => break,
+        };
+        if cursor == FIRST_ENTRY_ID {
+            break;
+        }
+    }
+    Ok(())
+}
+
+async fn read_group(
+    conn: &mut ConnectionManager,
+    key: &str,
+    options: &StreamOption,
+    id: &str,
+) -> RedisResult<Vec<StreamId>> {
+    let mut read_options = StreamReadOptions::default()
+        .group(&options.group, &options.consumer)
+        .count(options.count);
+    // Pending entries are returned right away, so only reads of new entries block.
+    if id == NEW_ENTRIES_ID {
+        read_options = read_options.block(options.block_ms as usize);
+    }
+
+    let reply: Option<StreamReadReply> = conn.xread_options(&[key], &[id], &read_options).await?;
+    Ok(reply
+        .into_iter()
+        .flat_map(|reply| reply.keys)
+        .flat_map(|stream| stream.ids)
+        .collect())
+}
+
+async fn ack(conn: &mut ConnectionManager, key: &str, group: &str, ids: &[String]) {
+    let res: RedisResult<usize> = conn.xack(key, group, ids).await;
+    if let Err(error) = res {
+        emit!(RedisAckError { error });
+    }
+}
@@ -0,0 +1,321 @@
+//! A minimal implementation of the Basic Encoding Rules of ASN.1, as used by SNMP.
+
+use snafu::Snafu;
+
+pub(super) const INTEGER: u8 = 0x02;
+pub(super) const OCTET_STRING: u8 = 0x04;
+pub(super) const NULL: u8 = 0x05;
+pub(super) const OBJECT_IDENTIFIER: u8 = 0x06;
+pub(super) const SEQUENCE: u8 = 0x30;
+
+#[derive(Debug, Snafu, PartialEq, Eq)]
+pub(super) enum BerError {
+    #[snafu(display("Unexpected end of data"))]
+    UnexpectedEnd,
+
+    #[snafu(display("Unexpected tag {:#04x}, expected {:#04x}", found, expected))]
+    UnexpectedTag { found: u8, expected: u8 },
+
+    #[snafu(display("Invalid length"))]
+    InvalidLength,
+
+    #[snafu(display("Integer out of range"))]
+    IntegerOutOfRange,
+
+    #[snafu(display("Invalid object identifier"))]
+    InvalidObjectIdentifier,
+}
+
+/// Reads values from BER encoded data.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub(super) const fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    pub(super) const fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns the data that is left to read.
+    pub(super) const fn remaining(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Returns the tag of the next value, without reading it.
+    pub(super) fn peek_tag(&self) -> Result<u8, BerError> {
+        self.data.first().copied().ok_or(BerError::UnexpectedEnd)
+    }
+
+    /// Reads the tag and the contents of the next value.
+    pub(super) fn read_any(&mut self) -> Result<(u8, &'a [u8]), BerError> {
+        let (&tag, rest) = self.data.split_first().ok_or(BerError::UnexpectedEnd)?;
+        let (&first, mut rest) = rest.split_first().ok_or(BerError::UnexpectedEnd)?;
+
+        let length = if first & 0x80 == 0 {
+            usize::from(first)
+        } else {
+            let count = usize::from(first & 0x7f);
+            if count == 0 || count > std::mem::size_of::<usize>() || rest.len() < count {
+                return Err(BerError::InvalidLength);
+            }
+            let (bytes, remaining) = rest.split_at(count);
+            rest = remaining;
+            bytes
+                .iter()
+                .fold(0, |length, byte| (length << 8) | usize::from(*byte))
+        };
+
+        if rest.len() < length {
+            return Err(BerError::UnexpectedEnd);
+        }
+        let (contents, rest) = rest.split_at(length);
+        self.data = rest;
+
+        Ok((tag, contents))
+    }
+
+    /// Reads the contents of the next value, which must have the given tag.
+    pub(super) fn read(&mut self, expected: u8) -> Result<&'a [u8], BerError> {
+        let (found, contents) = self.read_any()?;
+        if found == expected {
+            Ok(contents)
+        } else {
+            Err(BerError::UnexpectedTag { found, expected })
+        }
+    }
+
+    /// Reads a constructed value, returning a reader of its contents.
+    pub(super) fn read_constructed(&mut self, expected: u8) -> Result<Reader<'a>, BerError> {
+        self.read(expected).map(Reader::new)
+    }
+
+    pub(super) fn read_integer(&mut self) -> Result<i64, BerError> {
+        decode_integer(self.read(INTEGER)?)
+    }
+
+    pub(super) fn read_octet_string(&mut self) -> Result<&'a [u8], BerError> {
+        self.read(OCTET_STRING)
+    }
+
+    pub(super) fn read_oid(&mut self) -> Result<Vec<u32>, BerError> {
+        decode_oid(self.read(OBJECT_IDENTIFIER)?)
+    }
+}
+
+/// Decodes a signed integer, in two's complement.
+pub(super) fn decode_integer(contents: &[u8]) -> Result<i64, BerError> {
+    if contents.is_empty() || contents.len() > 8 {
+        return Err(BerError::IntegerOutOfRange);
+    }
+    let initial = if contents[0] & 0x80 == 0 { 0 } else { -1 };
+    Ok(contents
+        .iter()
+        .fold(initial, |value, byte| (value << 8) | i64::from(*byte)))
+}
+
+/// Decodes an unsigned integer, such as counters, which can have a leading zero byte.
+pub(super) fn decode_unsigned(contents: &[u8]) -> Result<u64, BerError> {
+    let contents = match contents {
+        [0, rest @ ..] => rest,
+        _ => contents,
+    };
+    if contents.len() > 8 {
+        return Err(BerError::IntegerOutOfRange);
+    }
+    Ok(contents
+        .iter()
+        .fold(0, |value, byte| (value << 8) | u64::from(*byte)))
+}
+
+pub(super) fn decode_oid(contents: &[u8]) -> Result<Vec<u32>, BerError> {
+    let mut oid = Vec::with_capacity(contents.len() + 1);
+    let mut value: u32 = 0;
+    for (index, byte) in contents.iter().enumerate() {
+        value = value
+            .checked_mul(128)
+            .map(|value| value | u32::from(byte & 0x7f))
+            .ok_or(BerError::InvalidObjectIdentifier)?;
+        if byte & 0x80 == 0 {
+            if oid.is_empty() {
+                // The first two arcs are encoded together.
+                let first = (value / 40).min(2);
+                oid.push(first);
+                oid.push(value - first * 40);
+            } else {
+                oid.push(value);
+            }
+            value = 0;
+        } else if index == contents.len() - 1 {
+            return Err(BerError::InvalidObjectIdentifier);
+        }
+    }
+
+    if oid.is_empty() {
+        Err(BerError::InvalidObjectIdentifier)
+    } else {
+        Ok(oid)
+    }
+}
+
+/// Writes BER encoded values.
+#[derive(Debug, Default)]
+pub(super) struct Writer {
+    buffer: Vec<u8>,
+}
+
+impl Writer {
+    pub(super) fn into_inner(self) -> Vec<u8> {
+        self.buffer
+    }
+
+    pub(super) fn write(&mut self, tag: u8, contents: &[u8]) {
+        self.buffer.push(tag);
+        let length = contents.len();
+        if length < 0x80 {
+            self.buffer.push(length as u8);
+        } else {
+            let bytes = length.to_be_bytes();
+            let skip = bytes.iter().take_while(|byte| **byte == 0).count();
+            self.buffer.push(0x80 | (bytes.len() - skip) as u8);
+            self.buffer.extend_from_slice(&bytes[skip..]);
+        }
+        self.buffer.extend_from_slice(contents);
+    }
+
+    /// Writes a constructed value, whose contents are written by `f`.
+    pub(super) fn write_constructed(&mut self, tag: u8, f: impl FnOnce(&mut Writer)) {
+        let mut inner = Writer::default();
+        f(&mut inner);
+        self.write(tag, &inner.buffer);
+    }
+
+    pub(super) fn write_integer(&mut self, value: i64) {
+        let bytes = value.to_be_bytes();
+        // The shortest two's complement encoding, where the sign bit of the first byte is kept.
+        let mut skip = 0;
+        while skip < 7
+            && ((bytes[skip] == 0 && bytes[skip + 1] & 0x80 == 0)
+                || (bytes[skip] == 0xff && bytes[skip + 1] & 0x80 != 0))
+        {
+            skip += 1;
+        }
+        self.write(INTEGER, &bytes[skip..]);
+    }
+
+    pub(super) fn write_octet_string(&mut self, value: &[u8]) {
+        self.write(OCTET_STRING, value);
+    }
+
+    pub(super) fn write_null(&mut self) {
+        self.write(NULL, &[]);
+    }
+
+    pub(super) fn write_oid(&mut self, oid: &[u32]) {
+        let mut contents = Vec::with_capacity(oid.len() + 1);
+        let (first, rest) = match oid {
+            [first, second, rest @ ..] => (first * 40 + second, rest),
+            [first] => (first * 40, &[][..]),
+            [] => (0, &[][..]),
+        };
+        for arc in std::iter::once(&first).chain(rest) {
+            let mut bytes = Vec::with_capacity(5);
+            let mut arc = *arc;
+            bytes.push((arc & 0x7f) as u8);
+            arc >>= 7;
+            while arc > 0 {
+                bytes.push(0x80 | (arc & 0x7f) as u8);
+                arc >>= 7;
+            }
+            contents.extend(bytes.iter().rev());
+        }
+        self.write(OBJECT_IDENTIFIER, &contents);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integers_round_trip() {
+        for value in [
+            0,
+            1,
+            127,
+            128,
+            255,
+            256,
+            -1,
+            -128,
+            -129,
+            i64::from(i32::MAX),
+            i64::MIN,
+        ] {
+            let mut writer = Writer::default();
+            writer.write_integer(value);
+            let encoded = writer.into_inner();
+            assert_eq!(Reader::new(&encoded).read_integer(), Ok(value), "{}", value);
+        }
+
+        let mut writer = Writer::default();
+        writer.write_integer(128);
+        assert_eq!(writer.into_inner(), vec![INTEGER, 2, 0, 128]);
+    }
+
+    #[test]
+    fn oids_round_trip() {
+        let oid = vec![1, 3, 6, 1, 4, 1, 2021, 10, 1, 3, 1];
+        let mut writer = Writer::default();
+        writer.write_oid(&oid);
+        let encoded = writer.into_inner();
+
+        assert_eq!(
+            encoded,
+            vec![0x06, 0x0b, 0x2b, 0x06, 0x01, 0x04, 0x01, 0x8f, 0x65, 0x0a, 0x01, 0x03, 0x01]
+        );
+        assert_eq!(Reader::new(&encoded).read_oid(), Ok(oid));
+    }
+
+    #[test]
+    fn long_lengths() {
+        let contents = vec![7; 300];
+        let mut writer = Writer::default();
+        writer.write_octet_string(&contents);
+        let encoded = writer.into_inner();
+
+        assert_eq!(&encoded[..4], &[OCTET_STRING, 0x82, 0x01, 0x2c]);
+        assert_eq!(Reader::new(&encoded).read_octet_string(), Ok(&contents[..]));
+    }
+
+    #[test]
+    fn truncated_data() {
+        assert_eq!(
+            Reader::new(&[OCTET_STRING, 5, 1, 2]).read_octet_string(),
+            Err(BerError::UnexpectedEnd)
+        );
+        assert_eq!(
+            Reader::new(&[NULL, 0]).read_integer(),
+            Err(BerError::UnexpectedTag {
+                found: NULL,
+                expected: INTEGER
+            })
+        );
+    }
+
+    #[test]
+    fn unsigned_integers() {
+        assert_eq!(
+            decode_unsigned(&[0, 0xff, 0xff, 0xff, 0xff]),
+            Ok(u64::from(u32::MAX))
+        );
+        assert_eq!(
+            decode_unsigned(&[0, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]),
+            Ok(u64::MAX)
+        );
+    }
+}
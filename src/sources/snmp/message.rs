@@ -0,0 +1,416 @@
+//! SNMP messages, as defined by RFC 3416 for PDUs, RFC 1901 for community-based messages, and
+//! RFC 3412 for version 3 messages.
+
+use std::net::Ipv4Addr;
+
+use snafu::{ResultExt, Snafu};
+
+use super::ber::{
+    self, BerError, Reader, Writer, INTEGER, OBJECT_IDENTIFIER, OCTET_STRING, SEQUENCE,
+};
+
+pub(super) const VERSION_2C: i64 = 1;
+pub(super) const VERSION_3: i64 = 3;
+
+pub(super) const GET_REQUEST: u8 = 0xa0;
+pub(super) const GET_NEXT_REQUEST: u8 = 0xa1;
+pub(super) const RESPONSE: u8 = 0xa2;
+pub(super) const INFORM_REQUEST: u8 = 0xa6;
+pub(super) const SNMPV2_TRAP: u8 = 0xa7;
+pub(super) const REPORT: u8 = 0xa8;
+
+const IP_ADDRESS: u8 = 0x40;
+const COUNTER32: u8 = 0x41;
+const GAUGE32: u8 = 0x42;
+const TIME_TICKS: u8 = 0x43;
+const OPAQUE: u8 = 0x44;
+const COUNTER64: u8 = 0x46;
+const NO_SUCH_OBJECT: u8 = 0x80;
+const NO_SUCH_INSTANCE: u8 = 0x81;
+const END_OF_MIB_VIEW: u8 = 0x82;
+
+/// The `sysUpTime.0` variable, first in the variable bindings of notifications.
+pub(super) const SYS_UP_TIME: &[u32] = &[1, 3, 6, 1, 2, 1, 1, 3, 0];
+
+/// The `snmpTrapOID.0` variable, second in the variable bindings of notifications.
+pub(super) const SNMP_TRAP_OID: &[u32] = &[1, 3, 6, 1, 6, 3, 1, 1, 4, 1, 0];
+
+#[derive(Debug, Snafu)]
+pub(super) enum MessageError {
+    #[snafu(display("Invalid encoding: {}", source))]
+    Encoding { source: BerError },
+
+    #[snafu(display("Unsupported SNMP version {}", version))]
+    UnsupportedVersion { version: i64 },
+
+    #[snafu(display("Unsupported PDU type {:#04x}", tag))]
+    UnsupportedPdu { tag: u8 },
+
+    #[snafu(display("Unsupported security model {}", model))]
+    UnsupportedSecurityModel { model: i64 },
+}
+
+/// The value of a variable.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) enum VarValue {
+    Integer(i64),
+    OctetString(Vec<u8>),
+    ObjectIdentifier(Vec<u32>),
+    IpAddress(Ipv4Addr),
+    Counter32(u64),
+    Gauge32(u64),
+    TimeTicks(u64),
+    Opaque(Vec<u8>),
+    Counter64(u64),
+    Null,
+    NoSuchObject,
+    NoSuchInstance,
+    EndOfMibView,
+}
+
+impl VarValue {
+    fn decode(tag: u8, contents: &[u8]) -> Result<Self, BerError> {
+        Ok(match tag {
+            INTEGER => Self::Integer(ber::decode_integer(contents)?),
+            OCTET_STRING => Self::OctetString(contents.to_vec()),
+            OBJECT_IDENTIFIER => Self::ObjectIdentifier(ber::decode_oid(contents)?),
+            IP_ADDRESS => {
+                let octets: [u8; 4] = contents.try_into().map_err(|_| BerError::InvalidLength)?;
+                Self::IpAddress(Ipv4Addr::from(octets))
+            }
+            COUNTER32 => Self::Counter32(ber::decode_unsigned(contents)?),
+            GAUGE32 => Self::Gauge32(ber::decode_unsigned(contents)?),
+            TIME_TICKS => Self::TimeTicks(ber::decode_unsigned(contents)?),
+            OPAQUE => Self::Opaque(contents.to_vec()),
+            COUNTER64 => Self::Counter64(ber::decode_unsigned(contents)?),
+            NO_SUCH_OBJECT => Self::NoSuchObject,
+            NO_SUCH_INSTANCE => Self::NoSuchInstance,
+            END_OF_MIB_VIEW => Self::EndOfMibView,
+            // Unknown types are kept as their raw contents.
+            _ => Self::OctetString(contents.to_vec()),
+        })
+    }
+
+    fn encode(&self, writer: &mut Writer) {
+        match self {
+            Self::Integer(value) => writer.write_integer(*value),
+            Self::OctetString(value) => writer.write_octet_string(value),
+            Self::ObjectIdentifier(value) => writer.write_oid(value),
+            Self::IpAddress(value) => writer.write(IP_ADDRESS, &value.octets()),
+            Self::Counter32(value) => write_unsigned(writer, COUNTER32, *value),
+            Self::Gauge32(value) => write_unsigned(writer, GAUGE32, *value),
+            Self::TimeTicks(value) => write_unsigned(writer, TIME_TICKS, *value),
+            Self::Opaque(value) => writer.write(OPAQUE, value),
+            Self::Counter64(value) => write_unsigned(writer, COUNTER64, *value),
+            Self::Null => writer.write_null(),
+            Self::NoSuchObject => writer.write(NO_SUCH_OBJECT, &[]),
+            Self::NoSuchInstance => writer.write(NO_SUCH_INSTANCE, &[]),
+            Self::EndOfMibView => writer.write(END_OF_MIB_VIEW, &[]),
+        }
+    }
+}
+
+fn write_unsigned(writer: &mut Writer, tag: u8, value: u64) {
+    let bytes = value.to_be_bytes();
+    let skip = bytes.iter().take_while(|byte| **byte == 0).count().min(7);
+    // A leading zero byte is needed when the high bit is set, as the value is unsigned.
+    if bytes[skip] & 0x80 != 0 {
+        let mut contents = vec![0];
+        contents.extend_from_slice(&bytes[skip..]);
+        writer.write(tag, &contents);
+    } else {
+        writer.write(tag, &bytes[skip..]);
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct VarBind {
+    pub(super) oid: Vec<u32>,
+    pub(super) value: VarValue,
+}
+
+/// A protocol data unit.
+///
+/// For `GetBulkRequest` PDUs, `error_status` and `error_index` hold the non-repeaters and
+/// max-repetitions fields, which share their encoding.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct Pdu {
+    pub(super) tag: u8,
+    pub(super) request_id: i64,
+    pub(super) error_status: i64,
+    pub(super) error_index: i64,
+    pub(super) varbinds: Vec<VarBind>,
+}
+
+impl Pdu {
+    fn read(reader: &mut Reader<'_>) -> Result<Self, MessageError> {
+        let (tag, contents) = reader.read_any().context(EncodingSnafu)?;
+        // SNMPv1 traps, tagged 0xa4, have a different structure.
+        if !(GET_REQUEST..=REPORT).contains(&tag) || tag == 0xa4 {
+            return Err(MessageError::UnsupportedPdu { tag });
+        }
+
+        let mut reader = Reader::new(contents);
+        let request_id = reader.read_integer().context(EncodingSnafu)?;
+        let error_status = reader.read_integer().context(EncodingSnafu)?;
+        let error_index = reader.read_integer().context(EncodingSnafu)?;
+
+        let mut list = reader.read_constructed(SEQUENCE).context(EncodingSnafu)?;
+        let mut varbinds = Vec::new();
+        while !list.is_empty() {
+            let mut varbind = list.read_constructed(SEQUENCE).context(EncodingSnafu)?;
+            let oid = varbind.read_oid().context(EncodingSnafu)?;
+            let (tag, contents) = varbind.read_any().context(EncodingSnafu)?;
+            let value = VarValue::decode(tag, contents).context(EncodingSnafu)?;
+            varbinds.push(VarBind { oid, value });
+        }
+
+        Ok(Self {
+            tag,
+            request_id,
+            error_status,
+            error_index,
+            varbinds,
+        })
+    }
+
+    pub(super) fn write(&self, writer: &mut Writer) {
+        writer.write_constructed(self.tag, |writer| {
+            writer.write_integer(self.request_id);
+            writer.write_integer(self.error_status);
+            writer.write_integer(self.error_index);
+            writer.write_constructed(SEQUENCE, |writer| {
+                for varbind in &self.varbinds {
+                    writer.write_constructed(SEQUENCE, |writer| {
+                        writer.write_oid(&varbind.oid);
+                        varbind.value.encode(writer);
+                    });
+                }
+            });
+        });
+    }
+
+    /// Decodes a scoped PDU, returning the context engine ID, the context name and the PDU.
+    pub(super) fn decode_scoped(data: &[u8]) -> Result<(Vec<u8>, Vec<u8>, Self), MessageError> {
+        let mut reader = Reader::new(data)
+            .read_constructed(SEQUENCE)
+            .context(EncodingSnafu)?;
+        let context_engine_id = reader.read_octet_string().context(EncodingSnafu)?;
+        let context_name = reader.read_octet_string().context(EncodingSnafu)?;
+        let pdu = Self::read(&mut reader)?;
+
+        Ok((context_engine_id.to_vec(), context_name.to_vec(), pdu))
+    }
+}
+
+/// A community-based message, of SNMPv2c.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct CommunityMessage {
+    pub(super) community: Vec<u8>,
+    pub(super) pdu: Pdu,
+}
+
+impl CommunityMessage {
+    pub(super) fn encode(&self) -> Vec<u8> {
+        let mut writer = Writer::default();
+        writer.write_constructed(SEQUENCE, |writer| {
+            writer.write_integer(VERSION_2C);
+            writer.write_octet_string(&self.community);
+            self.pdu.write(writer);
+        });
+        writer.into_inner()
+    }
+}
+
+/// The security parameters of the user-based security model, defined by RFC 3414.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct UsmParameters<'a> {
+    pub(super) engine_id: &'a [u8],
+    pub(super) engine_boots: i64,
+    pub(super) engine_time: i64,
+    pub(super) user_name: &'a [u8],
+    pub(super) auth_params: &'a [u8],
+    pub(super) priv_params: &'a [u8],
+}
+
+/// The data of an SNMPv3 message, which is encrypted when privacy is used.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum ScopedPduData<'a> {
+    /// The encoded scoped PDU.
+    Plaintext(&'a [u8]),
+    Encrypted(&'a [u8]),
+}
+
+/// An SNMPv3 message, with the user-based security model.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct V3Message<'a> {
+    pub(super) flags: u8,
+    pub(super) usm: UsmParameters<'a>,
+    pub(super) data: ScopedPduData<'a>,
+    /// The whole message, as received, for its authentication.
+    pub(super) raw: &'a [u8],
+}
+
+impl<'a> V3Message<'a> {
+    pub(super) const fn authenticated(&self) -> bool {
+        self.flags & 0x01 != 0
+    }
+
+    pub(super) const fn encrypted(&self) -> bool {
+        self.flags & 0x02 != 0
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) enum Message<'a> {
+    Community(CommunityMessage),
+    V3(V3Message<'a>),
+}
+
+impl<'a> Message<'a> {
+    pub(super) fn decode(data: &'a [u8]) -> Result<Self, MessageError> {
+        let mut reader = Reader::new(data)
+            .read_constructed(SEQUENCE)
+            .context(EncodingSnafu)?;
+
+        match reader.read_integer().context(EncodingSnafu)? {
+            VERSION_2C => {
+                let community = reader.read_octet_string().context(EncodingSnafu)?.to_vec();
+                let pdu = Pdu::read(&mut reader)?;
+                Ok(Self::Community(CommunityMessage { community, pdu }))
+            }
+            VERSION_3 => Self::decode_v3(reader, data).map(Self::V3),
+            version => Err(MessageError::UnsupportedVersion { version }),
+        }
+    }
+
+    fn decode_v3(mut reader: Reader<'a>, raw: &'a [u8]) -> Result<V3Message<'a>, MessageError> {
+        let mut global = reader.read_constructed(SEQUENCE).context(EncodingSnafu)?;
+        let _message_id = global.read_integer().context(EncodingSnafu)?;
+        let _max_size = global.read_integer().context(EncodingSnafu)?;
+        let flags = global
+            .read_octet_string()
+            .context(EncodingSnafu)?
+            .first()
+            .copied()
+            .unwrap_or_default();
+        let model = global.read_integer().context(EncodingSnafu)?;
+        if model != 3 {
+            return Err(MessageError::UnsupportedSecurityModel { model });
+        }
+
+        let mut security = Reader::new(reader.read_octet_string().context(EncodingSnafu)?)
+            .read_constructed(SEQUENCE)
+            .context(EncodingSnafu)?;
+        let usm = UsmParameters {
+            engine_id: security.read_octet_string().context(EncodingSnafu)?,
+            engine_boots: security.read_integer().context(EncodingSnafu)?,
+            engine_time: security.read_integer().context(EncodingSnafu)?,
+            user_name: security.read_octet_string().context(EncodingSnafu)?,
+            auth_params: security.read_octet_string().context(EncodingSnafu)?,
+            priv_params: security.read_octet_string().context(EncodingSnafu)?,
+        };
+
+        let data = if reader.peek_tag().context(EncodingSnafu)? == OCTET_STRING {
+            ScopedPduData::Encrypted(reader.read_octet_string().context(EncodingSnafu)?)
+        } else {
+            ScopedPduData::Plaintext(reader.remaining())
+        };
+
+        Ok(V3Message {
+            flags,
+            usm,
+            data,
+            raw,
+        })
+    }
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+
+    /// Encodes an SNMPv2c notification, with `sysUpTime.0` and `snmpTrapOID.0` followed by the
+    /// given variable bindings.
+    pub(in crate::sources::snmp) fn notification(
+        tag: u8,
+        community: &str,
+        trap_oid: &[u32],
+        varbinds: Vec<VarBind>,
+    ) -> Vec<u8> {
+        let mut all = vec![
+            VarBind {
+                oid: SYS_UP_TIME.to_vec(),
+                value: VarValue::TimeTicks(12345),
+            },
+            VarBind {
+                oid: SNMP_TRAP_OID.to_vec(),
+                value: VarValue::ObjectIdentifier(trap_oid.to_vec()),
+            },
+        ];
+        all.extend(varbinds);
+
+        CommunityMessage {
+            community: community.as_bytes().to_vec(),
+            pdu: Pdu {
+                tag,
+                request_id: 42,
+                error_status: 0,
+                error_index: 0,
+                varbinds: all,
+            },
+        }
+        .encode()
+    }
+
+    #[test]
+    fn community_message_round_trip() {
+        let encoded = notification(
+            SNMPV2_TRAP,
+            "public",
+            &[1, 3, 6, 1, 6, 3, 1, 1, 5, 3],
+            vec![
+                VarBind {
+                    oid: vec![1, 3, 6, 1, 2, 1, 2, 2, 1, 1, 3],
+                    value: VarValue::Integer(3),
+                },
+                VarBind {
+                    oid: vec![1, 3, 6, 1, 2, 1, 2, 2, 1, 10, 3],
+                    value: VarValue::Counter32(u64::from(u32::MAX)),
+                },
+                VarBind {
+                    oid: vec![1, 3, 6, 1, 2, 1, 4, 20, 1, 1],
+                    value: VarValue::IpAddress(Ipv4Addr::new(10, 0, 0, 1)),
+                },
+            ],
+        );
+
+        let message = match Message::decode(&encoded).unwrap() {
+            Message::Community(message) => message,
+            message => panic!("unexpected message {:?}", message),
+        };
+        assert_eq!(message.community, b"public");
+        assert_eq!(message.pdu.tag, SNMPV2_TRAP);
+        assert_eq!(message.pdu.request_id, 42);
+        assert_eq!(message.pdu.varbinds.len(), 5);
+        assert_eq!(
+            message.pdu.varbinds[3].value,
+            VarValue::Counter32(u64::from(u32::MAX))
+        );
+        assert_eq!(message.encode(), encoded);
+    }
+
+    #[test]
+    fn rejects_snmpv1() {
+        let mut writer = Writer::default();
+        writer.write_constructed(SEQUENCE, |writer| {
+            writer.write_integer(0);
+            writer.write_octet_string(b"public");
+        });
+
+        assert!(matches!(
+            Message::decode(&writer.into_inner()),
+            Err(MessageError::UnsupportedVersion { version: 0 })
+        ));
+    }
+}
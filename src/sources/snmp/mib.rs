@@ -0,0 +1,394 @@
+//! Resolution of object identifiers to names, from well-known objects and from MIB modules.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use snafu::{ResultExt, Snafu};
+
+/// The objects known without loading any MIB module, as their module, name and OID.
+const WELL_KNOWN: &[(&str, &str, &str)] = &[
+    ("SNMPv2-SMI", "iso", "1"),
+    ("SNMPv2-SMI", "org", "1.3"),
+    ("SNMPv2-SMI", "dod", "1.3.6"),
+    ("SNMPv2-SMI", "internet", "1.3.6.1"),
+    ("SNMPv2-SMI", "directory", "1.3.6.1.1"),
+    ("SNMPv2-SMI", "mgmt", "1.3.6.1.2"),
+    ("SNMPv2-SMI", "mib-2", "1.3.6.1.2.1"),
+    ("SNMPv2-SMI", "transmission", "1.3.6.1.2.1.10"),
+    ("SNMPv2-SMI", "experimental", "1.3.6.1.3"),
+    ("SNMPv2-SMI", "private", "1.3.6.1.4"),
+    ("SNMPv2-SMI", "enterprises", "1.3.6.1.4.1"),
+    ("SNMPv2-SMI", "security", "1.3.6.1.5"),
+    ("SNMPv2-SMI", "snmpV2", "1.3.6.1.6"),
+    ("SNMPv2-SMI", "snmpModules", "1.3.6.1.6.3"),
+    ("SNMPv2-MIB", "system", "1.3.6.1.2.1.1"),
+    ("SNMPv2-MIB", "sysDescr", "1.3.6.1.2.1.1.1"),
+    ("SNMPv2-MIB", "sysObjectID", "1.3.6.1.2.1.1.2"),
+    ("SNMPv2-MIB", "sysUpTime", "1.3.6.1.2.1.1.3"),
+    ("SNMPv2-MIB", "sysContact", "1.3.6.1.2.1.1.4"),
+    ("SNMPv2-MIB", "sysName", "1.3.6.1.2.1.1.5"),
+    ("SNMPv2-MIB", "sysLocation", "1.3.6.1.2.1.1.6"),
+    ("SNMPv2-MIB", "sysServices", "1.3.6.1.2.1.1.7"),
+    ("SNMPv2-MIB", "snmpTrapOID", "1.3.6.1.6.3.1.1.4.1"),
+    ("SNMPv2-MIB", "snmpTrapEnterprise", "1.3.6.1.6.3.1.1.4.3"),
+    ("SNMPv2-MIB", "coldStart", "1.3.6.1.6.3.1.1.5.1"),
+    ("SNMPv2-MIB", "warmStart", "1.3.6.1.6.3.1.1.5.2"),
+    ("SNMPv2-MIB", "authenticationFailure", "1.3.6.1.6.3.1.1.5.5"),
+    ("IF-MIB", "interfaces", "1.3.6.1.2.1.2"),
+    ("IF-MIB", "ifNumber", "1.3.6.1.2.1.2.1"),
+    ("IF-MIB", "ifTable", "1.3.6.1.2.1.2.2"),
+    ("IF-MIB", "ifEntry", "1.3.6.1.2.1.2.2.1"),
+    ("IF-MIB", "ifIndex", "1.3.6.1.2.1.2.2.1.1"),
+    ("IF-MIB", "ifDescr", "1.3.6.1.2.1.2.2.1.2"),
+    ("IF-MIB", "ifType", "1.3.6.1.2.1.2.2.1.3"),
+    ("IF-MIB", "ifMtu", "1.3.6.1.2.1.2.2.1.4"),
+    ("IF-MIB", "ifSpeed", "1.3.6.1.2.1.2.2.1.5"),
+    ("IF-MIB", "ifPhysAddress", "1.3.6.1.2.1.2.2.1.6"),
+    ("IF-MIB", "ifAdminStatus", "1.3.6.1.2.1.2.2.1.7"),
+    ("IF-MIB", "ifOperStatus", "1.3.6.1.2.1.2.2.1.8"),
+    ("IF-MIB", "ifLastChange", "1.3.6.1.2.1.2.2.1.9"),
+    ("IF-MIB", "ifInOctets", "1.3.6.1.2.1.2.2.1.10"),
+    ("IF-MIB", "ifInUcastPkts", "1.3.6.1.2.1.2.2.1.11"),
+    ("IF-MIB", "ifInDiscards", "1.3.6.1.2.1.2.2.1.13"),
+    ("IF-MIB", "ifInErrors", "1.3.6.1.2.1.2.2.1.14"),
+    ("IF-MIB", "ifOutOctets", "1.3.6.1.2.1.2.2.1.16"),
+    ("IF-MIB", "ifOutUcastPkts", "1.3.6.1.2.1.2.2.1.17"),
+    ("IF-MIB", "ifOutDiscards", "1.3.6.1.2.1.2.2.1.19"),
+    ("IF-MIB", "ifOutErrors", "1.3.6.1.2.1.2.2.1.20"),
+    ("IF-MIB", "ifXTable", "1.3.6.1.2.1.31.1.1"),
+    ("IF-MIB", "ifXEntry", "1.3.6.1.2.1.31.1.1.1"),
+    ("IF-MIB", "ifName", "1.3.6.1.2.1.31.1.1.1.1"),
+    ("IF-MIB", "ifHCInOctets", "1.3.6.1.2.1.31.1.1.1.6"),
+    ("IF-MIB", "ifHCOutOctets", "1.3.6.1.2.1.31.1.1.1.10"),
+    ("IF-MIB", "ifAlias", "1.3.6.1.2.1.31.1.1.1.18"),
+    ("IF-MIB", "linkDown", "1.3.6.1.6.3.1.1.5.3"),
+    ("IF-MIB", "linkUp", "1.3.6.1.6.3.1.1.5.4"),
+];
+
+static MODULE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"([A-Z][A-Za-z0-9-]*)\s+DEFINITIONS\s*::=\s*BEGIN").unwrap());
+
+static DEFINITION: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?s)\b([a-z][A-Za-z0-9-]*)\s+(?:OBJECT\s+IDENTIFIER|OBJECT-TYPE|OBJECT-IDENTITY|MODULE-IDENTITY|NOTIFICATION-TYPE|OBJECT-GROUP|NOTIFICATION-GROUP|MODULE-COMPLIANCE|AGENT-CAPABILITIES)\b.*?::=\s*\{([^}]*)\}",
+    )
+    .unwrap()
+});
+
+static STRING: Lazy<Regex> = Lazy::new(|| Regex::new(r#""[^"]*""#).unwrap());
+
+static COMMENT: Lazy<Regex> = Lazy::new(|| Regex::new(r"--[^\n]*").unwrap());
+
+static NAMED_ARC: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^([a-z][A-Za-z0-9-]*)\((\d+)\)$").unwrap());
+
+#[derive(Debug, Snafu)]
+pub(super) enum MibError {
+    #[snafu(display("Could not read MIB files from {:?}: {}", path, source))]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+}
+
+/// An object defined by a MIB module, relative to its parent.
+struct Definition {
+    module: String,
+    name: String,
+    parent: String,
+    arcs: Vec<u32>,
+}
+
+/// The names of objects, from well-known objects and the loaded MIB modules.
+#[derive(Debug, Default)]
+pub(super) struct Mib {
+    names: HashMap<Vec<u32>, String>,
+    oids: HashMap<String, Vec<u32>>,
+}
+
+impl Mib {
+    /// Loads the MIB modules of the given files, or of the files in the given directories.
+    pub(super) fn load(paths: &[PathBuf]) -> Result<Self, MibError> {
+        let mut mib = Self::default();
+        for (module, name, oid) in WELL_KNOWN {
+            mib.insert(
+                module,
+                name,
+                parse_oid(oid).expect("well-known OID is valid"),
+            );
+        }
+
+        let mut definitions = Vec::new();
+        for path in paths {
+            for file in files(path).context(ReadSnafu { path })? {
+                let text = fs::read_to_string(&file).context(ReadSnafu { path: &file })?;
+                definitions.extend(parse(&text));
+            }
+        }
+
+        // Objects can be defined before their parents, so they are resolved until no more can be.
+        loop {
+            let count = definitions.len();
+            definitions.retain(|definition| match mib.oids.get(&definition.parent) {
+                Some(parent) => {
+                    let mut oid = parent.clone();
+                    oid.extend_from_slice(&definition.arcs);
+                    mib.insert(&definition.module, &definition.name, oid);
+                    false
+                }
+                None => true,
+            });
+            if definitions.len() == count {
+                break;
+            }
+        }
+
+        for definition in definitions {
+            warn!(
+                message = "Unresolved MIB object, its parent is not defined.",
+                object = %format!("{}::{}", definition.module, definition.name),
+                parent = %definition.parent,
+            );
+        }
+
+        Ok(mib)
+    }
+
+    fn insert(&mut self, module: &str, name: &str, oid: Vec<u32>) {
+        let qualified = format!("{}::{}", module, name);
+        self.oids.insert(name.to_string(), oid.clone());
+        self.oids.insert(qualified.clone(), oid.clone());
+        self.names.entry(oid).or_insert(qualified);
+    }
+
+    /// Returns the name of an OID, from its longest known prefix, such as `IF-MIB::ifDescr.3`.
+    ///
+    /// OIDs without any known prefix are returned in their numeric form.
+    pub(super) fn name(&self, oid: &[u32]) -> String {
+        for length in (1..=oid.len()).rev() {
+            if let Some(name) = self.names.get(&oid[..length]) {
+                let mut name = name.clone();
+                for arc in &oid[length..] {
+                    name.push('.');
+                    name.push_str(&arc.to_string());
+                }
+                return name;
+            }
+        }
+        format_oid(oid)
+    }
+
+    /// Returns the OID of a name, either numeric, such as `1.3.6.1.2.1.1.5.0`, or symbolic with an
+    /// optional module and numeric suffix, such as `sysName.0` or `IF-MIB::ifDescr`.
+    pub(super) fn resolve(&self, name: &str) -> Option<Vec<u32>> {
+        if let Some(oid) = parse_oid(name) {
+            return Some(oid);
+        }
+
+        let symbol_start = name.find("::").map_or(0, |index| index + 2);
+        let (symbol, suffix) = match name[symbol_start..].find('.') {
+            Some(index) => name.split_at(symbol_start + index),
+            None => (name, ""),
+        };
+
+        let mut oid = self.oids.get(symbol)?.clone();
+        if !suffix.is_empty() {
+            oid.extend(parse_oid(&suffix[1..])?);
+        }
+        Some(oid)
+    }
+}
+
+/// Returns the files of a path, which is either a file or a directory.
+fn files(path: &Path) -> std::io::Result<Vec<PathBuf>> {
+    if path.is_dir() {
+        let mut files = fs::read_dir(path)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<Vec<_>, _>>()?;
+        files.retain(|file| file.is_file());
+        files.sort();
+        Ok(files)
+    } else {
+        Ok(vec![path.to_path_buf()])
+    }
+}
+
+/// Parses the object definitions of MIB modules.
+fn parse(text: &str) -> Vec<Definition> {
+    let text = STRING.replace_all(text, "\"\"");
+    let text = COMMENT.replace_all(&text, "");
+
+    let starts = MODULE
+        .captures_iter(&text)
+        .map(|captures| {
+            let module = captures.get(1).expect("module has a name");
+            (module.start(), module.as_str())
+        })
+        .collect::<Vec<_>>();
+
+    let mut definitions = Vec::new();
+    for captures in DEFINITION.captures_iter(&text) {
+        let start = captures.get(0).expect("definition matched").start();
+        let module = starts
+            .iter()
+            .rev()
+            .find(|(module_start, _)| *module_start <= start)
+            .map_or("", |(_, module)| module);
+
+        let mut components = captures[2].split_whitespace();
+        let mut parent = match components.next() {
+            Some(parent) => parent.to_string(),
+            None => continue,
+        };
+        let mut arcs = Vec::new();
+        for component in components {
+            if let Ok(arc) = component.parse() {
+                arcs.push(arc);
+            } else if let Some(named) = NAMED_ARC.captures(component) {
+                // Named arcs, as in `{ iso org(3) dod(6) }`, define objects of their own.
+                let arc = match named[2].parse() {
+                    Ok(arc) => arc,
+                    Err(_) => continue,
+                };
+                arcs.push(arc);
+                definitions.push(Definition {
+                    module: module.to_string(),
+                    name: named[1].to_string(),
+                    parent: parent.clone(),
+                    arcs: arcs.clone(),
+                });
+                parent = named[1].to_string();
+                arcs.clear();
+            }
+        }
+
+        definitions.push(Definition {
+            module: module.to_string(),
+            name: captures[1].to_string(),
+            parent,
+            arcs,
+        });
+    }
+    definitions
+}
+
+fn parse_oid(oid: &str) -> Option<Vec<u32>> {
+    oid.trim_start_matches('.')
+        .split('.')
+        .map(|arc| arc.parse().ok())
+        .collect()
+}
+
+fn format_oid(oid: &[u32]) -> String {
+    oid.iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    const MODULE_TEXT: &str = r#"
+ACME-MIB DEFINITIONS ::= BEGIN
+
+IMPORTS
+    MODULE-IDENTITY, OBJECT-TYPE, NOTIFICATION-TYPE, enterprises
+        FROM SNMPv2-SMI;
+
+-- Defined before its parent.
+acmeTemperature OBJECT-TYPE
+    SYNTAX      Integer32
+    MAX-ACCESS  read-only
+    STATUS      current
+    DESCRIPTION "The temperature, which is ::= { not an object }."
+    ::= { acmeSensors 1 }
+
+acme MODULE-IDENTITY
+    LAST-UPDATED "202201010000Z"
+    ORGANIZATION "Acme"
+    DESCRIPTION  "The Acme MIB."
+    ::= { enterprises 99999 }
+
+acmeSensors OBJECT IDENTIFIER ::= { acme 1 }
+
+acmeOverheat NOTIFICATION-TYPE
+    OBJECTS { acmeTemperature }
+    STATUS  current
+    DESCRIPTION "Sent when too hot."
+    ::= { acme 0 1 }
+
+acmeOrphan OBJECT IDENTIFIER ::= { unknownParent 1 }
+
+END
+"#;
+
+    fn mib() -> Mib {
+        let dir = tempfile::tempdir().unwrap();
+        let mut file = fs::File::create(dir.path().join("ACME-MIB.txt")).unwrap();
+        file.write_all(MODULE_TEXT.as_bytes()).unwrap();
+
+        Mib::load(&[dir.path().to_path_buf()]).unwrap()
+    }
+
+    #[test]
+    fn names_well_known_objects() {
+        let mib = Mib::load(&[]).unwrap();
+
+        assert_eq!(
+            mib.name(&[1, 3, 6, 1, 2, 1, 1, 5, 0]),
+            "SNMPv2-MIB::sysName.0"
+        );
+        assert_eq!(
+            mib.name(&[1, 3, 6, 1, 2, 1, 2, 2, 1, 2, 12]),
+            "IF-MIB::ifDescr.12"
+        );
+        assert_eq!(mib.name(&[2, 5, 4]), "2.5.4");
+    }
+
+    #[test]
+    fn names_loaded_objects() {
+        let mib = mib();
+
+        assert_eq!(
+            mib.name(&[1, 3, 6, 1, 4, 1, 99999, 1, 1, 0]),
+            "ACME-MIB::acmeTemperature.0"
+        );
+        assert_eq!(
+            mib.name(&[1, 3, 6, 1, 4, 1, 99999, 0, 1]),
+            "ACME-MIB::acmeOverheat"
+        );
+        assert_eq!(mib.resolve("acmeOrphan"), None);
+    }
+
+    #[test]
+    fn resolves_names() {
+        let mib = mib();
+
+        assert_eq!(
+            mib.resolve("1.3.6.1.2.1.1.5.0"),
+            Some(vec![1, 3, 6, 1, 2, 1, 1, 5, 0])
+        );
+        assert_eq!(
+            mib.resolve("sysName.0"),
+            Some(vec![1, 3, 6, 1, 2, 1, 1, 5, 0])
+        );
+        assert_eq!(
+            mib.resolve("IF-MIB::ifDescr"),
+            Some(vec![1, 3, 6, 1, 2, 1, 2, 2, 1, 2])
+        );
+        assert_eq!(
+            mib.resolve("ACME-MIB::acmeTemperature.0"),
+            Some(vec![1, 3, 6, 1, 4, 1, 99999, 1, 1, 0])
+        );
+        assert_eq!(mib.resolve("unknown"), None);
+    }
+}
@@ -0,0 +1,869 @@
+use std::{
+    collections::BTreeMap,
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr},
+    path::PathBuf,
+    sync::Arc,
+    time::Duration,
+};
+
+use bytes::Bytes;
+use chrono::Utc;
+use futures::{future::join_all, FutureExt, StreamExt};
+use snafu::{ResultExt, Snafu};
+use tokio::{net::UdpSocket, time};
+use tokio_stream::wrappers::IntervalStream;
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+use vector_core::{config::LogNamespace, ByteSizeOf};
+
+use crate::{
+    config::{log_schema, DataType, GenerateConfig, Output, Resource, SourceConfig, SourceContext},
+    event::{Event, LogEvent, Value},
+    internal_events::{
+        EventsReceived, SnmpMessageError, SnmpPollError, SnmpSocketError, SocketBytesReceived,
+        SocketMode, StreamClosedError,
+    },
+    shutdown::ShutdownSignal,
+    udp, SourceSender,
+};
+
+mod ber;
+mod message;
+mod mib;
+mod usm;
+
+use message::{
+    CommunityMessage, Message, MessageError, Pdu, VarBind, VarValue, GET_NEXT_REQUEST, GET_REQUEST,
+    INFORM_REQUEST, RESPONSE, SNMPV2_TRAP, SNMP_TRAP_OID, SYS_UP_TIME,
+};
+use mib::Mib;
+pub use usm::{AuthProtocol, PrivProtocol, UsmUserConfig};
+use usm::{Usm, UsmError};
+
+/// The largest SNMP message received, which is the largest UDP datagram.
+const MAX_MESSAGE_SIZE: usize = 65_507;
+
+/// The maximum number of requests made to walk a subtree, in case an agent never ends it.
+const MAX_WALK_REQUESTS: usize = 10_000;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("At least one of `traps` or `poll` must be configured"))]
+    NothingConfigured,
+
+    #[snafu(display("Unknown object name {:?}", name))]
+    UnknownObject { name: String },
+}
+
+#[derive(Debug, Snafu)]
+enum TrapError {
+    #[snafu(display("{}", source))]
+    Decode { source: MessageError },
+
+    #[snafu(display("{}", source))]
+    Security { source: UsmError },
+
+    #[snafu(display("Unknown community"))]
+    UnknownCommunity,
+
+    #[snafu(display("Unexpected PDU type {:#04x}", tag))]
+    NotNotification { tag: u8 },
+}
+
+#[derive(Debug, Snafu)]
+enum PollError {
+    #[snafu(display("Could not resolve address"))]
+    Resolve,
+
+    #[snafu(display("I/O error: {}", source))]
+    Io { source: std::io::Error },
+
+    #[snafu(display("Timed out waiting for a response"))]
+    Timeout,
+
+    #[snafu(display("Invalid response: {}", source))]
+    Response { source: MessageError },
+
+    #[snafu(display("Error status {} for variable {}", status, index))]
+    ErrorStatus { status: i64, index: i64 },
+}
+
+/// Configuration for the `snmp` source.
+#[configurable_component(source("snmp"))]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct SnmpConfig {
+    #[configurable(derived)]
+    traps: Option<TrapsConfig>,
+
+    #[configurable(derived)]
+    poll: Option<PollConfig>,
+
+    /// Files or directories of MIB modules, used to resolve object identifiers to names.
+    ///
+    /// The objects of `SNMPv2-SMI`, `SNMPv2-MIB` and `IF-MIB` are always known.
+    #[serde(default)]
+    mib_paths: Vec<PathBuf>,
+}
+
+/// Reception of SNMP notifications, which are traps and informs.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct TrapsConfig {
+    /// The address to listen for notifications on.
+    #[serde(default = "default_trap_address")]
+    address: SocketAddr,
+
+    /// The communities of the SNMPv2c notifications that are accepted.
+    ///
+    /// When empty, SNMPv2c notifications of any community are accepted.
+    #[serde(default)]
+    communities: Vec<SensitiveString>,
+
+    /// The users of the SNMPv3 notifications that are accepted.
+    ///
+    /// When empty, SNMPv3 notifications are rejected.
+    #[serde(default)]
+    users: Vec<UsmUserConfig>,
+
+    /// The size, in bytes, of the receive buffer used for the listening socket.
+    ///
+    /// This should not typically needed to be changed.
+    receive_buffer_bytes: Option<usize>,
+}
+
+fn default_trap_address() -> SocketAddr {
+    SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), 162)
+}
+
+/// Periodic polling of SNMP agents, with SNMPv2c.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct PollConfig {
+    /// The interval between polls, in seconds.
+    #[serde(default = "default_interval_secs")]
+    interval_secs: u64,
+
+    /// How long to wait for each response, in seconds.
+    #[serde(default = "default_timeout_secs")]
+    timeout_secs: u64,
+
+    /// The agents to poll.
+    targets: Vec<PollTargetConfig>,
+}
+
+const fn default_interval_secs() -> u64 {
+    60
+}
+
+const fn default_timeout_secs() -> u64 {
+    5
+}
+
+/// An SNMP agent to poll.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct PollTargetConfig {
+    /// The address of the agent, as `host:port`.
+    address: String,
+
+    /// The community of the agent.
+    #[serde(default = "default_community")]
+    community: SensitiveString,
+
+    /// The objects to get, either by name, such as `sysName.0`, or numeric OID.
+    #[serde(default)]
+    get: Vec<String>,
+
+    /// The subtrees to walk, either by name, such as `IF-MIB::ifDescr`, or numeric OID.
+    #[serde(default)]
+    walk: Vec<String>,
+}
+
+fn default_community() -> SensitiveString {
+    "public".to_string().into()
+}
+
+impl GenerateConfig for SnmpConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"traps.address = "0.0.0.0:162"
+            traps.communities = ["public"]"#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for SnmpConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        if self.traps.is_none() && self.poll.is_none() {
+            return Err(BuildError::NothingConfigured.into());
+        }
+
+        let mib = Arc::new(Mib::load(&self.mib_paths)?);
+
+        let traps = match &self.traps {
+            Some(config) => {
+                let receiver = TrapReceiver {
+                    communities: config
+                        .communities
+                        .iter()
+                        .map(|community| community.inner().as_bytes().to_vec())
+                        .collect(),
+                    usm: Usm::new(&config.users)?,
+                    mib: Arc::clone(&mib),
+                };
+                receiver
+                    .run(
+                        config.address,
+                        config.receive_buffer_bytes,
+                        cx.shutdown.clone(),
+                        cx.out.clone(),
+                    )
+                    .boxed()
+            }
+            None => futures::future::ok(()).boxed(),
+        };
+
+        let poll = match &self.poll {
+            Some(config) => {
+                let targets = config
+                    .targets
+                    .iter()
+                    .map(|target| Target::new(target, &mib))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let poller = Poller {
+                    targets,
+                    timeout: Duration::from_secs(config.timeout_secs),
+                    mib,
+                };
+                poller
+                    .run(
+                        Duration::from_secs(config.interval_secs),
+                        cx.shutdown,
+                        cx.out,
+                    )
+                    .boxed()
+            }
+            None => futures::future::ok(()).boxed(),
+        };
+
+        Ok(Box::pin(async move {
+            let (traps, poll) = futures::future::join(traps, poll).await;
+            traps.and(poll)
+        }))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        self.traps
+            .iter()
+            .map(|traps| Resource::udp(traps.address))
+            .collect()
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+struct TrapReceiver {
+    communities: Vec<Vec<u8>>,
+    usm: Usm,
+    mib: Arc<Mib>,
+}
+
+impl TrapReceiver {
+    async fn run(
+        mut self,
+        address: SocketAddr,
+        receive_buffer_bytes: Option<usize>,
+        mut shutdown: ShutdownSignal,
+        mut out: SourceSender,
+    ) -> Result<(), ()> {
+        let socket = UdpSocket::bind(address)
+            .await
+            .map_err(|error| emit!(SnmpSocketError { error }))?;
+
+        if let Some(receive_buffer_bytes) = receive_buffer_bytes {
+            if let Err(error) = udp::set_receive_buffer_size(&socket, receive_buffer_bytes) {
+                warn!(message = "Failed configuring receive buffer size on UDP socket.", %error);
+            }
+        }
+
+        info!(message = "Listening.", addr = %address, r#type = "udp");
+
+        let mut buffer = vec![0; MAX_MESSAGE_SIZE];
+        loop {
+            let (size, peer) = tokio::select! {
+                result = socket.recv_from(&mut buffer) => match result {
+                    Ok(received) => received,
+                    Err(error) => {
+                        emit!(SnmpSocketError { error });
+                        continue;
+                    }
+                },
+                _ = &mut shutdown => return Ok(()),
+            };
+            emit!(SocketBytesReceived {
+                mode: SocketMode::Udp,
+                byte_size: size,
+            });
+
+            let (event, reply) = match self.handle(&buffer[..size], peer) {
+                Ok(handled) => handled,
+                Err(error) => {
+                    emit!(SnmpMessageError { error, peer });
+                    continue;
+                }
+            };
+
+            if let Some(reply) = reply {
+                if let Err(error) = socket.send_to(&reply, peer).await {
+                    emit!(SnmpSocketError { error });
+                }
+            }
+
+            emit!(EventsReceived {
+                count: 1,
+                byte_size: event.size_of(),
+            });
+            if let Err(error) = out.send_event(event).await {
+                emit!(StreamClosedError { error, count: 1 });
+                return Err(());
+            }
+        }
+    }
+
+    /// Turns a notification into an event, along with the reply to send for informs.
+    fn handle(
+        &mut self,
+        data: &[u8],
+        peer: SocketAddr,
+    ) -> Result<(Event, Option<Vec<u8>>), TrapError> {
+        let mut log = LogEvent::default();
+
+        let (pdu, reply) = match Message::decode(data).context(DecodeSnafu)? {
+            Message::Community(message) => {
+                if !self.communities.is_empty() && !self.communities.contains(&message.community) {
+                    return Err(TrapError::UnknownCommunity);
+                }
+                log.insert("version", "2c");
+
+                // Informs are acknowledged with a response holding the same variable bindings.
+                let reply = (message.pdu.tag == INFORM_REQUEST).then(|| {
+                    CommunityMessage {
+                        community: message.community.clone(),
+                        pdu: Pdu {
+                            tag: RESPONSE,
+                            error_status: 0,
+                            error_index: 0,
+                            ..message.pdu.clone()
+                        },
+                    }
+                    .encode()
+                });
+                (message.pdu, reply)
+            }
+            Message::V3(message) => {
+                let notification = self.usm.process(&message).context(SecuritySnafu)?;
+                log.insert("version", "3");
+                log.insert("user", notification.user);
+                if !notification.context_name.is_empty() {
+                    log.insert(
+                        "context_name",
+                        String::from_utf8_lossy(&notification.context_name).into_owned(),
+                    );
+                }
+                // SNMPv3 informs aren't acknowledged, as that requires this receiver to be an
+                // authoritative engine.
+                (notification.pdu, None)
+            }
+        };
+
+        let pdu_type = match pdu.tag {
+            SNMPV2_TRAP => "trap",
+            INFORM_REQUEST => "inform",
+            tag => return Err(TrapError::NotNotification { tag }),
+        };
+
+        log.insert(log_schema().source_type_key(), Bytes::from("snmp"));
+        log.insert(log_schema().timestamp_key(), Utc::now());
+        log.insert(log_schema().host_key(), peer.ip().to_string());
+        log.insert("pdu_type", pdu_type);
+
+        let mut varbinds = BTreeMap::new();
+        for VarBind { oid, value } in pdu.varbinds {
+            if oid == SYS_UP_TIME {
+                log.insert("uptime", to_value(value, &self.mib));
+            } else if oid == SNMP_TRAP_OID {
+                log.insert("trap_oid", to_value(value, &self.mib));
+            } else {
+                varbinds.insert(self.mib.name(&oid), to_value(value, &self.mib));
+            }
+        }
+        log.insert("varbinds", varbinds);
+
+        Ok((Event::from(log), reply))
+    }
+}
+
+/// An agent to poll, with its objects resolved to OIDs.
+struct Target {
+    address: String,
+    community: Vec<u8>,
+    get: Vec<Vec<u32>>,
+    walk: Vec<Vec<u32>>,
+}
+
+impl Target {
+    fn new(config: &PollTargetConfig, mib: &Mib) -> Result<Self, BuildError> {
+        let resolve = |names: &[String]| {
+            names
+                .iter()
+                .map(|name| {
+                    mib.resolve(name)
+                        .ok_or_else(|| BuildError::UnknownObject { name: name.clone() })
+                })
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        Ok(Self {
+            address: config.address.clone(),
+            community: config.community.inner().as_bytes().to_vec(),
+            get: resolve(&config.get)?,
+            walk: resolve(&config.walk)?,
+        })
+    }
+}
+
+struct Poller {
+    targets: Vec<Target>,
+    timeout: Duration,
+    mib: Arc<Mib>,
+}
+
+impl Poller {
+    async fn run(
+        self,
+        interval: Duration,
+        shutdown: ShutdownSignal,
+        mut out: SourceSender,
+    ) -> Result<(), ()> {
+        let mut interval = IntervalStream::new(time::interval(interval)).take_until(shutdown);
+        while interval.next().await.is_some() {
+            let events = join_all(self.targets.iter().map(|target| self.poll(target)))
+                .await
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>();
+
+            let count = events.len();
+            emit!(EventsReceived {
+                count,
+                byte_size: events.size_of(),
+            });
+            if let Err(error) = out.send_batch(events).await {
+                emit!(StreamClosedError { error, count });
+                return Err(());
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn poll(&self, target: &Target) -> Option<Event> {
+        match self.poll_values(target).await {
+            Ok(values) => {
+                let mut log = LogEvent::default();
+                log.insert(log_schema().source_type_key(), Bytes::from("snmp"));
+                log.insert(log_schema().timestamp_key(), Utc::now());
+                log.insert(log_schema().host_key(), target.address.clone());
+                log.insert("values", values);
+                Some(Event::from(log))
+            }
+            Err(error) => {
+                emit!(SnmpPollError {
+                    error,
+                    target: &target.address,
+                });
+                None
+            }
+        }
+    }
+
+    async fn poll_values(&self, target: &Target) -> Result<BTreeMap<String, Value>, PollError> {
+        let address = tokio::net::lookup_host(&target.address)
+            .await
+            .context(IoSnafu)?
+            .next()
+            .ok_or(PollError::Resolve)?;
+        let local: SocketAddr = if address.is_ipv4() {
+            (Ipv4Addr::UNSPECIFIED, 0).into()
+        } else {
+            (Ipv6Addr::UNSPECIFIED, 0).into()
+        };
+        let socket = UdpSocket::bind(local).await.context(IoSnafu)?;
+        socket.connect(address).await.context(IoSnafu)?;
+
+        let mut client = Client {
+            socket,
+            community: &target.community,
+            timeout: self.timeout,
+            request_id: 0,
+        };
+        let mut varbinds = Vec::new();
+
+        if !target.get.is_empty() {
+            varbinds.extend(client.request(GET_REQUEST, &target.get).await?);
+        }
+
+        for root in &target.walk {
+            let mut oid = root.clone();
+            for _ in 0..MAX_WALK_REQUESTS {
+                let varbind = match client.request(GET_NEXT_REQUEST, &[oid]).await?.pop() {
+                    Some(varbind) => varbind,
+                    None => break,
+                };
+                if !varbind.oid.starts_with(root) || varbind.value == VarValue::EndOfMibView {
+                    break;
+                }
+                oid = varbind.oid.clone();
+                varbinds.push(varbind);
+            }
+        }
+
+        Ok(varbinds
+            .into_iter()
+            .map(|VarBind { oid, value }| (self.mib.name(&oid), to_value(value, &self.mib)))
+            .collect())
+    }
+}
+
+/// A client of an SNMPv2c agent.
+struct Client<'a> {
+    socket: UdpSocket,
+    community: &'a [u8],
+    timeout: Duration,
+    request_id: i64,
+}
+
+impl<'a> Client<'a> {
+    async fn request(&mut self, tag: u8, oids: &[Vec<u32>]) -> Result<Vec<VarBind>, PollError> {
+        self.request_id += 1;
+        let request = CommunityMessage {
+            community: self.community.to_vec(),
+            pdu: Pdu {
+                tag,
+                request_id: self.request_id,
+                error_status: 0,
+                error_index: 0,
+                varbinds: oids
+                    .iter()
+                    .map(|oid| VarBind {
+                        oid: oid.clone(),
+                        value: VarValue::Null,
+                    })
+                    .collect(),
+            },
+        };
+        self.socket.send(&request.encode()).await.context(IoSnafu)?;
+
+        let mut buffer = vec![0; MAX_MESSAGE_SIZE];
+        let deadline = time::Instant::now() + self.timeout;
+        loop {
+            let size = time::timeout_at(deadline, self.socket.recv(&mut buffer))
+                .await
+                .map_err(|_| PollError::Timeout)?
+                .context(IoSnafu)?;
+
+            // Responses to earlier requests, which timed out, are skipped.
+            let pdu = match Message::decode(&buffer[..size]).context(ResponseSnafu)? {
+                Message::Community(response) if response.pdu.request_id == self.request_id => {
+                    response.pdu
+                }
+                _ => continue,
+            };
+            if pdu.error_status != 0 {
+                return Err(PollError::ErrorStatus {
+                    status: pdu.error_status,
+                    index: pdu.error_index,
+                });
+            }
+            return Ok(pdu.varbinds);
+        }
+    }
+}
+
+/// Converts the value of a variable to the value of a field.
+fn to_value(value: VarValue, mib: &Mib) -> Value {
+    match value {
+        VarValue::Integer(value) => value.into(),
+        VarValue::OctetString(value) => match String::from_utf8(value) {
+            Ok(value) if !value.chars().any(|c| c.is_control() && !c.is_whitespace()) => {
+                value.into()
+            }
+            // Binary strings, such as physical addresses, are formatted as hexadecimal.
+            Ok(value) => format_hex(value.as_bytes()).into(),
+            Err(error) => format_hex(error.as_bytes()).into(),
+        },
+        VarValue::ObjectIdentifier(oid) => mib.name(&oid).into(),
+        VarValue::IpAddress(address) => address.to_string().into(),
+        VarValue::Counter32(value)
+        | VarValue::Gauge32(value)
+        | VarValue::TimeTicks(value)
+        | VarValue::Counter64(value) => match i64::try_from(value) {
+            Ok(value) => value.into(),
+            Err(_) => value.to_string().into(),
+        },
+        VarValue::Opaque(value) => format_hex(&value).into(),
+        VarValue::Null
+        | VarValue::NoSuchObject
+        | VarValue::NoSuchInstance
+        | VarValue::EndOfMibView => Value::Null,
+    }
+}
+
+fn format_hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        event::EventStatus,
+        test_util::{
+            collect_ready,
+            components::{assert_source_compliance, SOCKET_HIGH_CARDINALITY_PUSH_SOURCE_TAGS},
+            next_addr,
+        },
+        SourceSender,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SnmpConfig>();
+    }
+
+    fn receiver(communities: &[&str]) -> TrapReceiver {
+        TrapReceiver {
+            communities: communities
+                .iter()
+                .map(|community| community.as_bytes().to_vec())
+                .collect(),
+            usm: Usm::new(&[]).unwrap(),
+            mib: Arc::new(Mib::load(&[]).unwrap()),
+        }
+    }
+
+    fn link_down(tag: u8, community: &str) -> Vec<u8> {
+        message::tests::notification(
+            tag,
+            community,
+            &[1, 3, 6, 1, 6, 3, 1, 1, 5, 3],
+            vec![
+                VarBind {
+                    oid: vec![1, 3, 6, 1, 2, 1, 2, 2, 1, 1, 3],
+                    value: VarValue::Integer(3),
+                },
+                VarBind {
+                    oid: vec![1, 3, 6, 1, 2, 1, 2, 2, 1, 2, 3],
+                    value: VarValue::OctetString(b"eth0".to_vec()),
+                },
+                VarBind {
+                    oid: vec![1, 3, 6, 1, 2, 1, 2, 2, 1, 6, 3],
+                    value: VarValue::OctetString(vec![0, 0x1b, 0x21, 0x3c, 0x9d, 0xf8]),
+                },
+            ],
+        )
+    }
+
+    #[test]
+    fn handles_traps() {
+        let peer = "10.1.2.3:50000".parse().unwrap();
+        let (event, reply) = receiver(&["public"])
+            .handle(&link_down(SNMPV2_TRAP, "public"), peer)
+            .unwrap();
+        let log = event.into_log();
+
+        assert!(reply.is_none());
+        assert_eq!(log["host"], "10.1.2.3".into());
+        assert_eq!(log["version"], "2c".into());
+        assert_eq!(log["pdu_type"], "trap".into());
+        assert_eq!(log["uptime"], 12345.into());
+        assert_eq!(log["trap_oid"], "IF-MIB::linkDown".into());
+
+        let varbinds = log["varbinds"].as_object().unwrap();
+        assert_eq!(varbinds["IF-MIB::ifIndex.3"], 3.into());
+        assert_eq!(varbinds["IF-MIB::ifDescr.3"], "eth0".into());
+        assert_eq!(
+            varbinds["IF-MIB::ifPhysAddress.3"],
+            "00:1b:21:3c:9d:f8".into()
+        );
+    }
+
+    #[test]
+    fn acknowledges_informs() {
+        let peer = "10.1.2.3:50000".parse().unwrap();
+        let (event, reply) = receiver(&[])
+            .handle(&link_down(INFORM_REQUEST, "private"), peer)
+            .unwrap();
+
+        assert_eq!(event.as_log()["pdu_type"], "inform".into());
+        let reply = match Message::decode(&reply.unwrap()).unwrap() {
+            Message::Community(reply) => reply,
+            reply => panic!("unexpected reply {:?}", reply),
+        };
+        assert_eq!(reply.community, b"private");
+        assert_eq!(reply.pdu.tag, RESPONSE);
+        assert_eq!(reply.pdu.request_id, 42);
+        assert_eq!(reply.pdu.varbinds.len(), 5);
+    }
+
+    #[test]
+    fn rejects_unknown_communities() {
+        let peer = "10.1.2.3:50000".parse().unwrap();
+
+        assert!(matches!(
+            receiver(&["public"]).handle(&link_down(SNMPV2_TRAP, "private"), peer),
+            Err(TrapError::UnknownCommunity)
+        ));
+    }
+
+    #[tokio::test]
+    async fn receives_traps() {
+        assert_source_compliance(&SOCKET_HIGH_CARDINALITY_PUSH_SOURCE_TAGS, async {
+            let address = next_addr();
+            let config: SnmpConfig =
+                toml::from_str(&format!(r#"traps.address = "{}""#, address)).unwrap();
+
+            let (tx, mut rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+            let source = config
+                .build(SourceContext::new_test(tx, None))
+                .await
+                .unwrap();
+            tokio::spawn(source);
+            tokio::time::sleep(Duration::from_millis(100)).await;
+
+            let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+            socket
+                .send_to(&link_down(SNMPV2_TRAP, "public"), address)
+                .await
+                .unwrap();
+            tokio::time::sleep(Duration::from_millis(100)).await;
+
+            let events = collect_ready(&mut rx).await;
+            assert_eq!(events.len(), 1);
+            assert_eq!(events[0].as_log()["trap_oid"], "IF-MIB::linkDown".into());
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn polls_agents() {
+        // A minimal agent, answering gets with the name of the system and walks of the interface
+        // descriptions.
+        let agent = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let agent_address = agent.local_addr().unwrap();
+        tokio::spawn(async move {
+            let objects = [
+                (
+                    vec![1, 3, 6, 1, 2, 1, 1, 5, 0],
+                    VarValue::OctetString(b"router1".to_vec()),
+                ),
+                (
+                    vec![1, 3, 6, 1, 2, 1, 2, 2, 1, 2, 1],
+                    VarValue::OctetString(b"lo".to_vec()),
+                ),
+                (
+                    vec![1, 3, 6, 1, 2, 1, 2, 2, 1, 2, 2],
+                    VarValue::OctetString(b"eth0".to_vec()),
+                ),
+                (vec![1, 3, 6, 1, 2, 1, 2, 2, 1, 3, 1], VarValue::Integer(24)),
+            ];
+            let mut buffer = vec![0; MAX_MESSAGE_SIZE];
+            loop {
+                let (size, peer) = agent.recv_from(&mut buffer).await.unwrap();
+                let mut message = match Message::decode(&buffer[..size]).unwrap() {
+                    Message::Community(message) => message,
+                    Message::V3(_) => unreachable!(),
+                };
+                for varbind in &mut message.pdu.varbinds {
+                    let found = if message.pdu.tag == GET_REQUEST {
+                        objects.iter().find(|(oid, _)| *oid == varbind.oid)
+                    } else {
+                        objects.iter().find(|(oid, _)| *oid > varbind.oid)
+                    };
+                    *varbind = match found {
+                        Some((oid, value)) => VarBind {
+                            oid: oid.clone(),
+                            value: value.clone(),
+                        },
+                        None => VarBind {
+                            oid: varbind.oid.clone(),
+                            value: VarValue::EndOfMibView,
+                        },
+                    };
+                }
+                message.pdu.tag = RESPONSE;
+                agent.send_to(&message.encode(), peer).await.unwrap();
+            }
+        });
+
+        let config: SnmpConfig = toml::from_str(&format!(
+            r#"
+            poll.interval_secs = 1
+            [[poll.targets]]
+            address = "{}"
+            get = ["sysName.0"]
+            walk = ["IF-MIB::ifDescr"]
+            "#,
+            agent_address
+        ))
+        .unwrap();
+
+        let (tx, mut rx) = SourceSender::new_test();
+        let source = config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+
+        let event = tokio::time::timeout(Duration::from_secs(5), rx.next())
+            .await
+            .unwrap()
+            .unwrap();
+        let log = event.into_log();
+        assert_eq!(log["host"], agent_address.to_string().into());
+
+        let values = log["values"].as_object().unwrap();
+        assert_eq!(values.len(), 3);
+        assert_eq!(values["SNMPv2-MIB::sysName.0"], "router1".into());
+        assert_eq!(values["IF-MIB::ifDescr.1"], "lo".into());
+        assert_eq!(values["IF-MIB::ifDescr.2"], "eth0".into());
+    }
+
+    #[test]
+    fn rejects_unknown_objects() {
+        let config = PollTargetConfig {
+            address: "127.0.0.1:161".to_string(),
+            community: default_community(),
+            get: vec!["sysNmae.0".to_string()],
+            walk: vec![],
+        };
+
+        assert!(Target::new(&config, &Mib::load(&[]).unwrap()).is_err());
+    }
+}
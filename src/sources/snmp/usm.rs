@@ -0,0 +1,606 @@
+//! The user-based security model of SNMPv3, defined by RFC 3414, with the SHA-2 authentication
+//! protocols of RFC 7860 and the AES privacy protocol of RFC 3826.
+
+use std::collections::HashMap;
+
+use openssl::{
+    hash::{hash, MessageDigest},
+    pkey::PKey,
+    sign::Signer,
+    symm::{Cipher, Crypter, Mode},
+};
+use snafu::{ResultExt, Snafu};
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+
+use super::message::{MessageError, Pdu, ScopedPduData, V3Message};
+
+/// The length of the password, repeated, from which keys are derived.
+const PASSWORD_EXPANSION_LEN: usize = 1024 * 1024;
+
+/// The minimum length of passwords, as required by RFC 3414.
+const MIN_PASSWORD_LEN: usize = 8;
+
+/// The authentication protocol of an SNMPv3 user.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AuthProtocol {
+    /// HMAC-MD5-96.
+    Md5,
+
+    /// HMAC-SHA-96.
+    Sha,
+
+    /// HMAC-SHA-224-128.
+    Sha224,
+
+    /// HMAC-SHA-256-192.
+    Sha256,
+
+    /// HMAC-SHA-384-256.
+    Sha384,
+
+    /// HMAC-SHA-512-384.
+    Sha512,
+}
+
+impl AuthProtocol {
+    fn digest(self) -> MessageDigest {
+        match self {
+            Self::Md5 => MessageDigest::md5(),
+            Self::Sha => MessageDigest::sha1(),
+            Self::Sha224 => MessageDigest::sha224(),
+            Self::Sha256 => MessageDigest::sha256(),
+            Self::Sha384 => MessageDigest::sha384(),
+            Self::Sha512 => MessageDigest::sha512(),
+        }
+    }
+
+    /// The length of the truncated HMAC sent in messages.
+    const fn mac_len(self) -> usize {
+        match self {
+            Self::Md5 | Self::Sha => 12,
+            Self::Sha224 => 16,
+            Self::Sha256 => 24,
+            Self::Sha384 => 32,
+            Self::Sha512 => 48,
+        }
+    }
+}
+
+/// The privacy protocol of an SNMPv3 user.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PrivProtocol {
+    /// CBC-DES.
+    Des,
+
+    /// CFB128-AES-128.
+    Aes,
+}
+
+/// An SNMPv3 user, whose notifications are accepted.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct UsmUserConfig {
+    /// The name of the user.
+    pub name: String,
+
+    #[configurable(derived)]
+    pub auth_protocol: Option<AuthProtocol>,
+
+    /// The authentication password of the user.
+    ///
+    /// Required when `auth_protocol` is set.
+    pub auth_password: Option<SensitiveString>,
+
+    #[configurable(derived)]
+    pub priv_protocol: Option<PrivProtocol>,
+
+    /// The privacy password of the user.
+    ///
+    /// Required when `priv_protocol` is set.
+    pub priv_password: Option<SensitiveString>,
+}
+
+#[derive(Debug, Snafu)]
+pub(super) enum UsmError {
+    #[snafu(display("Unknown user {:?}", name))]
+    UnknownUser { name: String },
+
+    #[snafu(display("Security level doesn't match the one of user {:?}", name))]
+    SecurityLevel { name: String },
+
+    #[snafu(display("Authentication failed for user {:?}", name))]
+    Authentication { name: String },
+
+    #[snafu(display("Decryption failed: {}", source))]
+    Decryption { source: openssl::error::ErrorStack },
+
+    #[snafu(display("Invalid privacy parameters"))]
+    PrivacyParameters,
+
+    #[snafu(display("Invalid scoped PDU: {}", source))]
+    ScopedPdu { source: MessageError },
+}
+
+#[derive(Debug, Snafu)]
+pub(super) enum UsmConfigError {
+    #[snafu(display(
+        "User {:?} has a protocol without its password, or a password without its protocol",
+        name
+    ))]
+    MissingSetting { name: String },
+
+    #[snafu(display("User {:?} has privacy without authentication", name))]
+    PrivacyWithoutAuthentication { name: String },
+
+    #[snafu(display(
+        "The passwords of user {:?} must be at least {} characters long",
+        name,
+        MIN_PASSWORD_LEN
+    ))]
+    ShortPassword { name: String },
+}
+
+struct User {
+    auth: Option<(AuthProtocol, Vec<u8>)>,
+    privacy: Option<(PrivProtocol, Vec<u8>)>,
+    /// The keys of the user, localized to each engine ID it was seen with.
+    localized: HashMap<Vec<u8>, LocalizedKeys>,
+}
+
+struct LocalizedKeys {
+    auth: Vec<u8>,
+    privacy: Option<Vec<u8>>,
+}
+
+/// An SNMPv3 notification, authenticated and decrypted.
+#[derive(Debug)]
+pub(super) struct ScopedNotification {
+    pub(super) user: String,
+    pub(super) context_name: Vec<u8>,
+    pub(super) pdu: Pdu,
+}
+
+/// Authenticates and decrypts SNMPv3 messages of the configured users.
+pub(super) struct Usm {
+    users: HashMap<Vec<u8>, User>,
+}
+
+impl Usm {
+    pub(super) fn new(users: &[UsmUserConfig]) -> Result<Self, UsmConfigError> {
+        let users = users
+            .iter()
+            .map(|user| {
+                let name = user.name.clone();
+                let auth = match (user.auth_protocol, &user.auth_password) {
+                    (Some(protocol), Some(password)) => Some((protocol, password)),
+                    (None, None) => None,
+                    _ => return Err(UsmConfigError::MissingSetting { name }),
+                };
+                let privacy = match (user.priv_protocol, &user.priv_password) {
+                    (Some(protocol), Some(password)) => Some((protocol, password)),
+                    (None, None) => None,
+                    _ => return Err(UsmConfigError::MissingSetting { name }),
+                };
+                if privacy.is_some() && auth.is_none() {
+                    return Err(UsmConfigError::PrivacyWithoutAuthentication { name });
+                }
+                let passwords = auth.iter().map(|(_, password)| password);
+                let passwords = passwords.chain(privacy.iter().map(|(_, password)| password));
+                if passwords.any(|password| password.inner().len() < MIN_PASSWORD_LEN) {
+                    return Err(UsmConfigError::ShortPassword { name });
+                }
+
+                Ok((
+                    name.into_bytes(),
+                    User {
+                        auth: auth.map(|(protocol, password)| {
+                            (protocol, password.inner().as_bytes().to_vec())
+                        }),
+                        privacy: privacy.map(|(protocol, password)| {
+                            (protocol, password.inner().as_bytes().to_vec())
+                        }),
+                        localized: HashMap::new(),
+                    },
+                ))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { users })
+    }
+
+    /// Authenticates a message and decrypts its scoped PDU.
+    pub(super) fn process(
+        &mut self,
+        message: &V3Message<'_>,
+    ) -> Result<ScopedNotification, UsmError> {
+        let name = String::from_utf8_lossy(message.usm.user_name).into_owned();
+        let user = match self.users.get_mut(message.usm.user_name) {
+            Some(user) => user,
+            None => return Err(UsmError::UnknownUser { name }),
+        };
+
+        if message.authenticated() != user.auth.is_some()
+            || message.encrypted() != user.privacy.is_some()
+        {
+            return Err(UsmError::SecurityLevel { name });
+        }
+
+        let scoped_pdu = match user.auth.as_ref().map(|(protocol, _)| *protocol) {
+            None => match message.data {
+                ScopedPduData::Plaintext(data) => data.to_vec(),
+                ScopedPduData::Encrypted(_) => return Err(UsmError::SecurityLevel { name }),
+            },
+            Some(auth_protocol) => {
+                let priv_protocol = user.privacy.as_ref().map(|(protocol, _)| *protocol);
+                let keys = user.localized_keys(message.usm.engine_id);
+                if !authenticate(auth_protocol, &keys.auth, message) {
+                    return Err(UsmError::Authentication { name });
+                }
+
+                match (message.data, priv_protocol, &keys.privacy) {
+                    (ScopedPduData::Plaintext(data), None, _) => data.to_vec(),
+                    (ScopedPduData::Encrypted(data), Some(priv_protocol), Some(key)) => {
+                        decrypt(priv_protocol, key, message, data)?
+                    }
+                    _ => return Err(UsmError::SecurityLevel { name }),
+                }
+            }
+        };
+
+        let (_, context_name, pdu) = Pdu::decode_scoped(&scoped_pdu).context(ScopedPduSnafu)?;
+        Ok(ScopedNotification {
+            user: name,
+            context_name,
+            pdu,
+        })
+    }
+}
+
+impl User {
+    /// Returns the keys of the user, localized to an engine ID.
+    ///
+    /// Localizing keys hashes a megabyte for each password, so they are computed once for each
+    /// engine ID.
+    fn localized_keys(&mut self, engine_id: &[u8]) -> &LocalizedKeys {
+        let auth = &self.auth;
+        let privacy = &self.privacy;
+        self.localized.entry(engine_id.to_vec()).or_insert_with(|| {
+            let (auth_protocol, auth_password) = auth.as_ref().expect("user has authentication");
+            LocalizedKeys {
+                auth: localize_key(*auth_protocol, auth_password, engine_id),
+                // The privacy key is derived with the hash function of the authentication
+                // protocol.
+                privacy: privacy.as_ref().map(|(_, priv_password)| {
+                    localize_key(*auth_protocol, priv_password, engine_id)
+                }),
+            }
+        })
+    }
+}
+
+/// Derives a key from a password, and localizes it to an engine ID, as described in section
+/// A.2 of RFC 3414.
+fn localize_key(protocol: AuthProtocol, password: &[u8], engine_id: &[u8]) -> Vec<u8> {
+    let digest = protocol.digest();
+    let expanded = password
+        .iter()
+        .cycle()
+        .take(PASSWORD_EXPANSION_LEN)
+        .copied()
+        .collect::<Vec<_>>();
+    let key = hash(digest, &expanded).expect("digest is supported");
+
+    let mut localized = key.to_vec();
+    localized.extend_from_slice(engine_id);
+    localized.extend_from_slice(&key);
+    hash(digest, &localized)
+        .expect("digest is supported")
+        .to_vec()
+}
+
+/// Checks the HMAC of a message, computed over the whole message with its authentication
+/// parameters zeroed.
+fn authenticate(protocol: AuthProtocol, key: &[u8], message: &V3Message<'_>) -> bool {
+    let received = message.usm.auth_params;
+    if received.len() != protocol.mac_len() {
+        return false;
+    }
+
+    // The authentication parameters are a slice of the message, so their offset is found from
+    // their address.
+    let offset = received.as_ptr() as usize - message.raw.as_ptr() as usize;
+    let mut zeroed = message.raw.to_vec();
+    zeroed[offset..offset + received.len()].fill(0);
+
+    let expected = PKey::hmac(key)
+        .and_then(|key| {
+            let mut signer = Signer::new(protocol.digest(), &key)?;
+            signer.update(&zeroed)?;
+            signer.sign_to_vec()
+        })
+        .expect("digest is supported");
+
+    openssl::memcmp::eq(&expected[..received.len()], received)
+}
+
+/// Decrypts the scoped PDU of a message.
+fn decrypt(
+    protocol: PrivProtocol,
+    key: &[u8],
+    message: &V3Message<'_>,
+    data: &[u8],
+) -> Result<Vec<u8>, UsmError> {
+    let salt = message.usm.priv_params;
+    if salt.len() != 8 {
+        return Err(UsmError::PrivacyParameters);
+    }
+
+    let (cipher, key, iv) = match protocol {
+        PrivProtocol::Des => {
+            if data.len() % 8 != 0 {
+                return Err(UsmError::PrivacyParameters);
+            }
+            let iv = key[8..16]
+                .iter()
+                .zip(salt)
+                .map(|(pre_iv, salt)| pre_iv ^ salt)
+                .collect::<Vec<_>>();
+            (Cipher::des_cbc(), &key[..8], iv)
+        }
+        PrivProtocol::Aes => {
+            let mut iv = Vec::with_capacity(16);
+            iv.extend_from_slice(&(message.usm.engine_boots as u32).to_be_bytes());
+            iv.extend_from_slice(&(message.usm.engine_time as u32).to_be_bytes());
+            iv.extend_from_slice(salt);
+            (Cipher::aes_128_cfb128(), &key[..16], iv)
+        }
+    };
+
+    crypt(cipher, Mode::Decrypt, key, &iv, data).context(DecryptionSnafu)
+}
+
+fn crypt(
+    cipher: Cipher,
+    mode: Mode,
+    key: &[u8],
+    iv: &[u8],
+    data: &[u8],
+) -> Result<Vec<u8>, openssl::error::ErrorStack> {
+    let mut crypter = Crypter::new(cipher, mode, key, Some(iv))?;
+    // Encrypted scoped PDUs aren't padded as PKCS#7 expects, trailing bytes are ignored instead.
+    crypter.pad(false);
+    let mut output = vec![0; data.len() + cipher.block_size()];
+    let mut count = crypter.update(data, &mut output)?;
+    count += crypter.finalize(&mut output[count..])?;
+    output.truncate(count);
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sources::snmp::{
+        ber::{Writer, OCTET_STRING, SEQUENCE},
+        message::{Message, VarBind, VarValue, SNMPV2_TRAP},
+    };
+
+    const ENGINE_ID: &[u8] = &[0x80, 0, 0x1f, 0x88, 0x80, 1, 2, 3, 4, 5, 6, 7, 8];
+
+    #[test]
+    fn localizes_keys() {
+        // The test vectors of section A.3 of RFC 3414.
+        let engine_id = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2];
+        assert_eq!(
+            hex::encode(localize_key(AuthProtocol::Md5, b"maplesyrup", &engine_id)),
+            "526f5eed9fcce26f8964c2930787d82b"
+        );
+        assert_eq!(
+            hex::encode(localize_key(AuthProtocol::Sha, b"maplesyrup", &engine_id)),
+            "6695febc9288e36282235fc7151f128497b38f3f"
+        );
+    }
+
+    fn user(auth: Option<AuthProtocol>, privacy: Option<PrivProtocol>) -> UsmUserConfig {
+        UsmUserConfig {
+            name: "vector".to_string(),
+            auth_protocol: auth,
+            auth_password: auth.map(|_| "authpassword".to_string().into()),
+            priv_protocol: privacy,
+            priv_password: privacy.map(|_| "privpassword".to_string().into()),
+        }
+    }
+
+    fn write_scoped_pdu(writer: &mut Writer) {
+        let pdu = Pdu {
+            tag: SNMPV2_TRAP,
+            request_id: 7,
+            error_status: 0,
+            error_index: 0,
+            varbinds: vec![VarBind {
+                oid: vec![1, 3, 6, 1, 2, 1, 1, 5, 0],
+                value: VarValue::OctetString(b"router1".to_vec()),
+            }],
+        };
+        writer.write_constructed(SEQUENCE, |writer| {
+            writer.write_octet_string(ENGINE_ID);
+            writer.write_octet_string(b"");
+            pdu.write(writer);
+        });
+    }
+
+    /// Encodes an SNMPv3 message for the user, authenticated and encrypted as configured.
+    fn encode(config: &UsmUserConfig) -> Vec<u8> {
+        let keys = {
+            let mut usm = Usm::new(&[config.clone()]).unwrap();
+            let user = usm.users.get_mut(config.name.as_bytes()).unwrap();
+            user.auth.is_some().then(|| {
+                let keys = user.localized_keys(ENGINE_ID);
+                (keys.auth.clone(), keys.privacy.clone())
+            })
+        };
+        let (boots, time) = (3, 1200);
+        let salt = [1, 2, 3, 4, 5, 6, 7, 8];
+
+        let mut flags = 0;
+        let mut encrypted = Vec::new();
+        let mut priv_params = Vec::new();
+        if let Some((_, Some(priv_key))) = &keys {
+            flags |= 0x02;
+            priv_params = salt.to_vec();
+            let mut writer = Writer::default();
+            write_scoped_pdu(&mut writer);
+            let mut data = writer.into_inner();
+            encrypted = match config.priv_protocol.unwrap() {
+                PrivProtocol::Des => {
+                    data.resize((data.len() + 7) / 8 * 8, 0);
+                    let iv = priv_key[8..16]
+                        .iter()
+                        .zip(salt)
+                        .map(|(pre_iv, salt)| pre_iv ^ salt)
+                        .collect::<Vec<_>>();
+                    crypt(Cipher::des_cbc(), Mode::Encrypt, &priv_key[..8], &iv, &data).unwrap()
+                }
+                PrivProtocol::Aes => {
+                    let mut iv = Vec::new();
+                    iv.extend_from_slice(&(boots as u32).to_be_bytes());
+                    iv.extend_from_slice(&(time as u32).to_be_bytes());
+                    iv.extend_from_slice(&salt);
+                    crypt(
+                        Cipher::aes_128_cfb128(),
+                        Mode::Encrypt,
+                        &priv_key[..16],
+                        &iv,
+                        &data,
+                    )
+                    .unwrap()
+                }
+            };
+        }
+        let mac_len = config.auth_protocol.map_or(0, AuthProtocol::mac_len);
+        if keys.is_some() {
+            flags |= 0x01;
+        }
+
+        let message = |auth_params: &[u8]| {
+            let mut security = Writer::default();
+            security.write_constructed(SEQUENCE, |writer| {
+                writer.write_octet_string(ENGINE_ID);
+                writer.write_integer(boots);
+                writer.write_integer(time);
+                writer.write_octet_string(config.name.as_bytes());
+                writer.write_octet_string(auth_params);
+                writer.write_octet_string(&priv_params);
+            });
+
+            let mut writer = Writer::default();
+            writer.write_constructed(SEQUENCE, |writer| {
+                writer.write_integer(3);
+                writer.write_constructed(SEQUENCE, |writer| {
+                    writer.write_integer(1);
+                    writer.write_integer(65507);
+                    writer.write_octet_string(&[flags]);
+                    writer.write_integer(3);
+                });
+                writer.write_octet_string(&security.into_inner());
+                if flags & 0x02 == 0 {
+                    write_scoped_pdu(writer);
+                } else {
+                    writer.write(OCTET_STRING, &encrypted);
+                }
+            });
+            writer.into_inner()
+        };
+
+        let unauthenticated = message(&vec![0; mac_len]);
+        match keys {
+            None => unauthenticated,
+            Some((auth_key, _)) => {
+                let protocol = config.auth_protocol.unwrap();
+                let key = PKey::hmac(&auth_key).unwrap();
+                let mut signer = Signer::new(protocol.digest(), &key).unwrap();
+                signer.update(&unauthenticated).unwrap();
+                let mac = signer.sign_to_vec().unwrap();
+                message(&mac[..mac_len])
+            }
+        }
+    }
+
+    fn process(usm: &mut Usm, encoded: &[u8]) -> Result<ScopedNotification, UsmError> {
+        match Message::decode(encoded).unwrap() {
+            Message::V3(message) => usm.process(&message),
+            Message::Community(_) => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn processes_all_security_levels() {
+        let configs = [
+            user(None, None),
+            user(Some(AuthProtocol::Md5), None),
+            user(Some(AuthProtocol::Sha), None),
+            user(Some(AuthProtocol::Sha256), None),
+            user(Some(AuthProtocol::Sha512), None),
+            user(Some(AuthProtocol::Sha), Some(PrivProtocol::Des)),
+            user(Some(AuthProtocol::Sha224), Some(PrivProtocol::Aes)),
+        ];
+
+        for config in configs {
+            let mut usm = Usm::new(&[config.clone()]).unwrap();
+            let notification = process(&mut usm, &encode(&config)).unwrap();
+
+            assert_eq!(notification.user, "vector");
+            assert_eq!(notification.pdu.request_id, 7, "{:?}", config);
+            assert_eq!(
+                notification.pdu.varbinds[0].value,
+                VarValue::OctetString(b"router1".to_vec())
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_messages() {
+        let config = user(Some(AuthProtocol::Sha), Some(PrivProtocol::Aes));
+        let encoded = encode(&config);
+
+        // A different password.
+        let mut other = config.clone();
+        other.auth_password = Some("otherpassword".to_string().into());
+        let mut usm = Usm::new(&[other]).unwrap();
+        assert!(matches!(
+            process(&mut usm, &encoded),
+            Err(UsmError::Authentication { .. })
+        ));
+
+        // A lower security level than the one of the user.
+        let mut usm = Usm::new(&[config]).unwrap();
+        assert!(matches!(
+            process(&mut usm, &encode(&user(None, None))),
+            Err(UsmError::SecurityLevel { .. })
+        ));
+
+        // An unknown user.
+        let mut usm = Usm::new(&[]).unwrap();
+        assert!(matches!(
+            process(&mut usm, &encoded),
+            Err(UsmError::UnknownUser { .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_invalid_users() {
+        let mut config = user(Some(AuthProtocol::Sha), None);
+        config.auth_password = Some("short".to_string().into());
+        assert!(Usm::new(&[config]).is_err());
+
+        let mut config = user(None, None);
+        config.priv_protocol = Some(PrivProtocol::Aes);
+        config.priv_password = Some("privpassword".to_string().into());
+        assert!(Usm::new(&[config]).is_err());
+    }
+}
@@ -224,7 +224,7 @@ impl SourceConfig for SocketConfig {
     fn resources(&self) -> Vec<Resource> {
         match self.mode.clone() {
             Mode::Tcp(tcp) => vec![tcp.address().into()],
-            Mode::Udp(udp) => vec![Resource::udp(udp.address())],
+            Mode::Udp(udp) => vec![udp.address().udp_resource()],
             #[cfg(unix)]
             Mode::UnixDatagram(_) => vec![],
             #[cfg(unix)]
@@ -282,6 +282,7 @@ mod test {
         event::{Event, LogEvent},
         shutdown::{ShutdownSignal, SourceShutdownCoordinator},
         sinks::util::tcp::TcpSinkConfig,
+        sources::util::SocketListenAddr,
         test_util::{
             collect_n, collect_n_limited,
             components::{assert_source_compliance, SOCKET_HIGH_CARDINALITY_PUSH_SOURCE_TAGS},
@@ -460,7 +461,9 @@ mod test {
             );
 
             let tls_meta: BTreeMap<String, value::Value> = btreemap!(
-                "subject" => "CN=localhost,OU=Vector,O=Datadog,L=New York,ST=New York,C=US"
+                "subject" => "CN=localhost,OU=Vector,O=Datadog,L=New York,ST=New York,C=US",
+                "common_name" => "localhost",
+                "subject_alt_names" => Vec::<value::Value>::new(),
             );
 
             assert_eq!(event.as_log()["tls_peer"], tls_meta.clone().into(),);
@@ -661,10 +664,13 @@ mod test {
         config: Option<UdpConfig>,
     ) -> (SocketAddr, JoinHandle<Result<(), ()>>) {
         let (address, config) = match config {
-            Some(config) => (config.address(), config),
+            Some(config) => match config.address() {
+                SocketListenAddr::SocketAddr(address) => (address, config),
+                address => panic!("Unexpected test address {}", address),
+            },
             None => {
                 let address = next_addr();
-                (address, UdpConfig::from_address(address))
+                (address, UdpConfig::from_address(address.into()))
             }
         };
 
@@ -678,6 +684,7 @@ mod test {
                 acknowledgements: false,
                 schema: Default::default(),
                 schema_definitions: HashMap::default(),
+                once: false,
             })
             .await
             .unwrap();
@@ -749,7 +756,7 @@ mod test {
         assert_source_compliance(&SOCKET_HIGH_CARDINALITY_PUSH_SOURCE_TAGS, async {
             let (tx, rx) = SourceSender::new_test();
             let address = next_addr();
-            let mut config = UdpConfig::from_address(address);
+            let mut config = UdpConfig::from_address(address.into());
             config.max_length = 11;
             let address = init_udp_with_config(tx, config).await;
 
@@ -785,7 +792,7 @@ mod test {
         assert_source_compliance(&SOCKET_HIGH_CARDINALITY_PUSH_SOURCE_TAGS, async {
             let (tx, rx) = SourceSender::new_test();
             let address = next_addr();
-            let mut config = UdpConfig::from_address(address);
+            let mut config = UdpConfig::from_address(address.into());
             config.max_length = 10;
             config.framing = CharacterDelimitedDecoderConfig {
                 character_delimited: CharacterDelimitedDecoderOptions::new(b',', None),
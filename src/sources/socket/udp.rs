@@ -1,5 +1,3 @@
-use std::net::SocketAddr;
-
 use bytes::{Bytes, BytesMut};
 use chrono::Utc;
 use codecs::{
@@ -7,7 +5,6 @@ use codecs::{
     StreamDecodingError,
 };
 use futures::StreamExt;
-use tokio::net::UdpSocket;
 use tokio_util::codec::FramedRead;
 use vector_common::internal_event::{ByteSize, BytesReceived, InternalEventHandle as _, Protocol};
 use vector_config::configurable_component;
@@ -20,7 +17,7 @@ use crate::{
     internal_events::{SocketEventsReceived, SocketMode, SocketReceiveError, StreamClosedError},
     serde::{default_decoding, default_framing_message_based},
     shutdown::ShutdownSignal,
-    sources::Source,
+    sources::{util::SocketListenAddr, Source},
     udp, SourceSender,
 };
 
@@ -30,7 +27,7 @@ use crate::{
 #[serde(deny_unknown_fields)]
 pub struct UdpConfig {
     /// The address to listen for messages on.
-    address: SocketAddr,
+    address: SocketListenAddr,
 
     /// The maximum buffer size, in bytes, of incoming messages.
     ///
@@ -81,11 +78,11 @@ impl UdpConfig {
         &self.decoding
     }
 
-    pub(super) const fn address(&self) -> SocketAddr {
+    pub(super) const fn address(&self) -> SocketListenAddr {
         self.address
     }
 
-    pub fn from_address(address: SocketAddr) -> Self {
+    pub fn from_address(address: SocketListenAddr) -> Self {
         Self {
             address,
             max_length: crate::serde::default_max_length(),
@@ -106,9 +103,9 @@ pub(super) fn udp(
     mut out: SourceSender,
 ) -> Source {
     Box::pin(async move {
-        let socket = UdpSocket::bind(&config.address)
-            .await
-            .expect("Failed to bind to udp listener socket");
+        let socket = config.address.udp_socket().await.map_err(|error| {
+            error!(message = "Failed to bind to UDP listener socket.", %error);
+        })?;
 
         if let Some(receive_buffer_bytes) = config.receive_buffer_bytes {
             if let Err(error) = udp::set_receive_buffer_size(&socket, receive_buffer_bytes) {
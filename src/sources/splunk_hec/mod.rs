@@ -36,6 +36,7 @@ use crate::{
     },
     serde::bool_or_struct,
     source_sender::ClosedError,
+    sources::util::SocketListenAddr,
     tls::{MaybeTlsSettings, TlsEnableableConfig},
     SourceSender,
 };
@@ -146,7 +147,9 @@ impl SourceConfig for SplunkConfig {
             )
             .or_else(finish_err);
 
-        let listener = tls.bind(&self.address).await?;
+        let listener = SocketListenAddr::from(self.address)
+            .tcp_listener(&tls)
+            .await?;
 
         Ok(Box::pin(async move {
             let span = Span::current();
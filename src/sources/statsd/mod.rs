@@ -16,7 +16,7 @@ use self::parser::ParseError;
 use super::util::{SocketListenAddr, TcpNullAcker, TcpSource};
 use crate::{
     codecs::Decoder,
-    config::{self, GenerateConfig, Output, Resource, SourceConfig, SourceContext},
+    config::{self, log_schema, GenerateConfig, Output, Resource, SourceConfig, SourceContext},
     event::Event,
     internal_events::{
         EventsReceived, SocketBytesReceived, SocketMode, StatsdSocketError, StreamClosedError,
@@ -31,9 +31,9 @@ pub mod parser;
 #[cfg(unix)]
 mod unix;
 
-use parser::parse;
+use parser::parse_message;
 #[cfg(unix)]
-use unix::{statsd_unix, UnixConfig};
+use unix::{statsd_unix, statsd_unix_datagram, UnixConfig, UnixDatagramConfig};
 use vector_core::config::LogNamespace;
 
 /// Configuration for the `statsd` source.
@@ -47,9 +47,15 @@ pub enum StatsdConfig {
     /// Listen on UDP.
     Udp(#[configurable(derived)] UdpConfig),
 
-    /// Listen on UDS. (Unix domain socket)
+    /// Listen on UDS, in stream mode. (Unix domain socket)
     #[cfg(unix)]
     Unix(#[configurable(derived)] UnixConfig),
+
+    /// Listen on UDS, in datagram mode. (Unix domain socket)
+    ///
+    /// This is how DogStatsD clients usually send metrics over a Unix domain socket.
+    #[cfg(unix)]
+    UnixDatagram(#[configurable(derived)] UnixDatagramConfig),
 }
 
 /// UDP configuration for the `statsd` source.
@@ -157,11 +163,18 @@ impl SourceConfig for StatsdConfig {
             }
             #[cfg(unix)]
             StatsdConfig::Unix(config) => statsd_unix(config.clone(), cx.shutdown, cx.out),
+            #[cfg(unix)]
+            StatsdConfig::UnixDatagram(config) => {
+                statsd_unix_datagram(config.clone(), cx.shutdown, cx.out)
+            }
         }
     }
 
     fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
-        vec![Output::default(config::DataType::Metric)]
+        // DogStatsD events and service checks are sent as logs.
+        vec![Output::default(
+            config::DataType::Metric | config::DataType::Log,
+        )]
     }
 
     fn resources(&self) -> Vec<Resource> {
@@ -169,7 +182,7 @@ impl SourceConfig for StatsdConfig {
             Self::Tcp(tcp) => vec![tcp.address.into()],
             Self::Udp(udp) => vec![Resource::udp(udp.address)],
             #[cfg(unix)]
-            Self::Unix(_) => vec![],
+            Self::Unix(_) | Self::UnixDatagram(_) => vec![],
         }
     }
 
@@ -216,10 +229,12 @@ impl decoding::format::Deserializer for StatsdDeserializer {
 
         match std::str::from_utf8(&bytes)
             .map_err(ParseError::InvalidUtf8)
-            .and_then(parse)
+            .and_then(parse_message)
         {
-            Ok(metric) => {
-                let event = Event::Metric(metric);
+            Ok(mut event) => {
+                if let Event::Log(log) = &mut event {
+                    log.insert(log_schema().source_type_key(), Bytes::from("statsd"));
+                }
                 // The other modes already emit EventsReceived
                 if matches!(self.socket_mode, Some(SocketMode::Udp)) {
                     emit!(EventsReceived {
@@ -412,6 +427,31 @@ mod test {
         .await;
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_statsd_unix_datagram() {
+        assert_source_compliance(&SOCKET_HIGH_CARDINALITY_PUSH_SOURCE_TAGS, async move {
+            let in_path = tempfile::tempdir()
+                .unwrap()
+                .into_path()
+                .join("unix_datagram_test");
+            let config = StatsdConfig::UnixDatagram(UnixDatagramConfig {
+                path: in_path.clone(),
+                socket_file_mode: None,
+                max_length: crate::serde::default_max_length(),
+            });
+            let (sender, mut receiver) = mpsc::channel(200);
+            tokio::spawn(async move {
+                let socket = tokio::net::UnixDatagram::unbound().unwrap();
+                while let Some(bytes) = receiver.next().await {
+                    socket.send_to(bytes, &in_path).await.unwrap();
+                }
+            });
+            test_statsd(config, sender).await;
+        })
+        .await;
+    }
+
     async fn test_statsd(statsd_config: StatsdConfig, mut sender: mpsc::Sender<&'static [u8]>) {
         // Build our statsd source and then spawn it.  We use a big pipeline buffer because each
         // packet we send has a lot of metrics per packet.  We could technically count them all up
@@ -1,27 +1,43 @@
 use std::{
-    collections::BTreeMap,
     error, fmt,
     num::{ParseFloatError, ParseIntError},
     str::Utf8Error,
 };
 
+use chrono::{DateTime, TimeZone, Utc};
 use once_cell::sync::Lazy;
 use regex::Regex;
 
-use crate::event::metric::{Metric, MetricKind, MetricValue, StatisticKind};
+use crate::{
+    config::log_schema,
+    event::{
+        metric::{Metric, MetricKind, MetricTags, MetricValue, Sample, StatisticKind},
+        Event, LogEvent, Value,
+    },
+};
 
 static WHITESPACE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s+").unwrap());
 static NONALPHANUM: Lazy<Regex> = Lazy::new(|| Regex::new(r"[^a-zA-Z_\-0-9\.]").unwrap());
 
+/// Parses a single StatsD line, including the DogStatsD events and service checks.
+///
+/// Metrics are returned as metric events, while DogStatsD events and service checks are returned
+/// as log events.
+pub fn parse_message(packet: &str) -> Result<Event, ParseError> {
+    if packet.starts_with("_e{") {
+        parse_event(packet).map(Event::Log)
+    } else if packet.starts_with("_sc|") {
+        parse_service_check(packet).map(Event::Log)
+    } else {
+        parse(packet).map(Event::Metric)
+    }
+}
+
 pub fn parse(packet: &str) -> Result<Metric, ParseError> {
     // https://docs.datadoghq.com/developers/dogstatsd/datagram_shell/#datagram-format
-    let key_and_body = packet.splitn(2, ':').collect::<Vec<_>>();
-    if key_and_body.len() != 2 {
-        return Err(ParseError::Malformed(
-            "should be key and body with ':' separator",
-        ));
-    }
-    let (key, body) = (key_and_body[0], key_and_body[1]);
+    let (key, body) = packet.split_once(':').ok_or(ParseError::Malformed(
+        "should be key and body with ':' separator",
+    ))?;
 
     let parts = body.split('|').collect::<Vec<_>>();
     if parts.len() < 2 {
@@ -33,84 +49,227 @@ pub fn parse(packet: &str) -> Result<Metric, ParseError> {
     let name = sanitize_key(key);
     let metric_type = parts[1];
 
-    // sampling part is optional and comes after metric type part
-    let sampling = parts.get(2).filter(|s| s.starts_with('@'));
-    let sample_rate = if let Some(s) = sampling {
-        1.0 / sanitize_sampling(parse_sampling(s)?)
-    } else {
-        1.0
-    };
+    // Sampling, tags, container ID and timestamp are all optional, and come after the metric type
+    // part in any order.
+    let mut sample_rate = 1.0;
+    let mut tags = None;
+    let mut timestamp = None;
+    for part in &parts[2..] {
+        if part.starts_with('@') {
+            sample_rate = 1.0 / sanitize_sampling(parse_sampling(part)?);
+        } else if part.starts_with('#') {
+            tags.get_or_insert_with(MetricTags::new)
+                .extend(parse_tags(part)?);
+        } else if let Some(container_id) = part.strip_prefix("c:") {
+            tags.get_or_insert_with(MetricTags::new)
+                .insert("container_id".into(), container_id.to_owned());
+        } else if let Some(seconds) = part.strip_prefix('T') {
+            timestamp = Some(parse_timestamp(seconds)?);
+        }
+    }
 
-    // tags are optional and could be found either after sampling of after metric type part
-    let tags = if sampling.is_none() {
-        parts.get(2)
-    } else {
-        parts.get(3)
-    };
-    let tags = tags.filter(|s| s.starts_with('#'));
-    let tags = if let Some(t) = tags {
-        Some(parse_tags(t)?)
-    } else {
-        None
-    };
+    // DogStatsD allows packing multiple values of the same metric in one line, separated by ':'.
+    let values = parts[0].split(':').collect::<Vec<_>>();
 
     let metric = match metric_type {
         "c" => {
-            let val: f64 = parts[0].parse()?;
+            let value = values
+                .iter()
+                .map(|value| value.parse::<f64>())
+                .sum::<Result<f64, _>>()?;
             Metric::new(
                 name,
                 MetricKind::Incremental,
                 MetricValue::Counter {
-                    value: val * sample_rate,
+                    value: value * sample_rate,
                 },
             )
-            .with_tags(tags)
         }
         unit @ "h" | unit @ "ms" | unit @ "d" => {
-            let val: f64 = parts[0].parse()?;
+            // Each value stands for `1 / sampling` observations, which can't be fractional.
+            let rate = (sample_rate.round() as u32).max(1);
+            let samples = values
+                .iter()
+                .map(|value| {
+                    Ok(Sample {
+                        value: convert_to_base_units(unit, value.parse()?),
+                        rate,
+                    })
+                })
+                .collect::<Result<Vec<_>, ParseError>>()?;
             Metric::new(
-                name, MetricKind::Incremental, MetricValue::Distribution {
-                    samples: vector_core::samples![convert_to_base_units(unit, val) => sample_rate as u32],
+                name,
+                MetricKind::Incremental,
+                MetricValue::Distribution {
+                    samples,
                     statistic: convert_to_statistic(unit),
                 },
-            ).with_tags(tags)
+            )
         }
         "g" => {
-            let value = if parts[0]
-                .chars()
-                .next()
-                .map(|c| c.is_ascii_digit())
-                .ok_or(ParseError::Malformed("empty first body component"))?
-            {
-                parts[0].parse()?
-            } else {
-                parts[0][1..].parse()?
-            };
-
-            match parse_direction(parts[0])? {
-                None => Metric::new(name, MetricKind::Absolute, MetricValue::Gauge { value })
-                    .with_tags(tags),
-                Some(sign) => Metric::new(
-                    name,
-                    MetricKind::Incremental,
-                    MetricValue::Gauge {
-                        value: value * sign,
-                    },
-                )
-                .with_tags(tags),
+            // Absolute values reset the gauge, while signed values adjust it, so a sequence of
+            // values is only incremental if none of them is absolute.
+            let mut kind = MetricKind::Incremental;
+            let mut gauge = 0.0;
+            for value in &values {
+                let (sign, value) = parse_gauge(value)?;
+                match sign {
+                    None => {
+                        kind = MetricKind::Absolute;
+                        gauge = value;
+                    }
+                    Some(sign) => gauge += value * sign,
+                }
             }
+            Metric::new(name, kind, MetricValue::Gauge { value: gauge })
         }
         "s" => Metric::new(
             name,
             MetricKind::Incremental,
             MetricValue::Set {
-                values: vec![parts[0].into()].into_iter().collect(),
+                values: values.iter().map(|value| (*value).into()).collect(),
             },
-        )
-        .with_tags(tags),
+        ),
         other => return Err(ParseError::UnknownMetricType(other.into())),
     };
-    Ok(metric)
+    Ok(metric.with_tags(tags).with_timestamp(timestamp))
+}
+
+/// Parses a DogStatsD event.
+///
+/// `_e{<TITLE_LENGTH>,<TEXT_LENGTH>}:<TITLE>|<TEXT>|d:<TIMESTAMP>|h:<HOSTNAME>|...|#<TAGS>`
+fn parse_event(packet: &str) -> Result<LogEvent, ParseError> {
+    let (lengths, rest) = packet["_e{".len()..]
+        .split_once("}:")
+        .ok_or(ParseError::Malformed(
+            "event should start with a '{<title length>,<text length>}:' header",
+        ))?;
+    let (title_length, text_length) = lengths.split_once(',').ok_or(ParseError::Malformed(
+        "event header should have the title and text lengths separated by ','",
+    ))?;
+    let title_length: usize = title_length.parse()?;
+    let text_length: usize = text_length.parse()?;
+
+    // The lengths are in bytes, so slicing fails if they don't match the actual title and text.
+    let title = rest.get(..title_length).ok_or(ParseError::Malformed(
+        "event title doesn't match its declared length",
+    ))?;
+    if rest.as_bytes().get(title_length) != Some(&b'|') {
+        return Err(ParseError::Malformed(
+            "event title and text should be separated by '|'",
+        ));
+    }
+    let text_end = title_length + 1 + text_length;
+    let text = rest
+        .get(title_length + 1..text_end)
+        .ok_or(ParseError::Malformed(
+            "event text doesn't match its declared length",
+        ))?;
+
+    let mut log = LogEvent::default();
+    log.insert("dogstatsd_type", "event");
+    log.insert("title", title);
+    log.insert(log_schema().message_key(), text.replace("\\n", "\n"));
+
+    let mut timestamp = None;
+    for part in rest[text_end..].split('|').filter(|part| !part.is_empty()) {
+        if let Some(seconds) = part.strip_prefix("d:") {
+            timestamp = Some(parse_timestamp(seconds)?);
+        } else if let Some(host) = part.strip_prefix("h:") {
+            log.insert(log_schema().host_key(), host);
+        } else if let Some(key) = part.strip_prefix("k:") {
+            log.insert("aggregation_key", key);
+        } else if let Some(priority) = part.strip_prefix("p:") {
+            log.insert("priority", priority);
+        } else if let Some(source) = part.strip_prefix("s:") {
+            log.insert("source_type_name", source);
+        } else if let Some(alert_type) = part.strip_prefix("t:") {
+            log.insert("alert_type", alert_type);
+        } else if let Some(container_id) = part.strip_prefix("c:") {
+            log.insert("container_id", container_id);
+        } else if part.starts_with('#') {
+            log.insert("tags", tags_value(parse_tags(part)?));
+        }
+    }
+    log.insert(
+        log_schema().timestamp_key(),
+        timestamp.unwrap_or_else(Utc::now),
+    );
+
+    Ok(log)
+}
+
+/// Parses a DogStatsD service check.
+///
+/// `_sc|<NAME>|<STATUS>|d:<TIMESTAMP>|h:<HOSTNAME>|#<TAGS>|m:<MESSAGE>`
+fn parse_service_check(packet: &str) -> Result<LogEvent, ParseError> {
+    let mut parts = packet["_sc|".len()..].split('|');
+    let name = parts
+        .next()
+        .filter(|name| !name.is_empty())
+        .ok_or(ParseError::Malformed("service check should have a name"))?;
+    let status: u8 = parts
+        .next()
+        .ok_or(ParseError::Malformed("service check should have a status"))?
+        .parse()?;
+    if status > 3 {
+        return Err(ParseError::Malformed(
+            "service check status should be 0, 1, 2 or 3",
+        ));
+    }
+
+    let mut log = LogEvent::default();
+    log.insert("dogstatsd_type", "service_check");
+    log.insert("check", name);
+    log.insert("status", status);
+
+    let mut timestamp = None;
+    while let Some(part) = parts.next() {
+        if let Some(seconds) = part.strip_prefix("d:") {
+            timestamp = Some(parse_timestamp(seconds)?);
+        } else if let Some(host) = part.strip_prefix("h:") {
+            log.insert(log_schema().host_key(), host);
+        } else if let Some(container_id) = part.strip_prefix("c:") {
+            log.insert("container_id", container_id);
+        } else if part.starts_with('#') {
+            log.insert("tags", tags_value(parse_tags(part)?));
+        } else if let Some(message) = part.strip_prefix("m:") {
+            // The message is always the last field, and may itself contain '|'.
+            let message = std::iter::once(message).chain(parts).collect::<Vec<_>>();
+            log.insert(log_schema().message_key(), message.join("|"));
+            break;
+        }
+    }
+    log.insert(
+        log_schema().timestamp_key(),
+        timestamp.unwrap_or_else(Utc::now),
+    );
+
+    Ok(log)
+}
+
+fn parse_timestamp(input: &str) -> Result<DateTime<Utc>, ParseError> {
+    Utc.timestamp_opt(input.parse()?, 0)
+        .single()
+        .ok_or(ParseError::Malformed("timestamp is out of range"))
+}
+
+fn parse_gauge(input: &str) -> Result<(Option<f64>, f64), ParseError> {
+    let sign = parse_direction(input)?;
+    let value = if sign.is_none() {
+        input.parse()?
+    } else {
+        input[1..].parse()?
+    };
+    Ok((sign, value))
+}
+
+fn tags_value(tags: MetricTags) -> Value {
+    Value::Object(
+        tags.into_iter()
+            .map(|(key, value)| (key.into(), Value::from(value)))
+            .collect(),
+    )
 }
 
 fn parse_sampling(input: &str) -> Result<f64, ParseError> {
@@ -128,14 +287,14 @@ fn parse_sampling(input: &str) -> Result<f64, ParseError> {
     }
 }
 
-fn parse_tags(input: &str) -> Result<BTreeMap<String, String>, ParseError> {
+fn parse_tags(input: &str) -> Result<MetricTags, ParseError> {
     if !input.starts_with('#') || input.len() < 2 {
         return Err(ParseError::Malformed(
             "expected non empty '#'-prefixed tags component",
         ));
     }
 
-    let mut result = BTreeMap::new();
+    let mut result = MetricTags::new();
 
     let chunks = input[1..].split(',').collect::<Vec<_>>();
     for chunk in chunks {
@@ -145,7 +304,7 @@ fn parse_tags(input: &str) -> Result<BTreeMap<String, String>, ParseError> {
         // if tag value is not provided, use "true"
         // https://github.com/influxdata/telegraf/blob/master/plugins/inputs/statsd/datadog.go#L152
         let value = pair.get(1).unwrap_or(&"true");
-        result.insert((*key).to_owned(), (*value).to_owned());
+        result.insert((*key).into(), (*value).to_owned());
     }
 
     Ok(result)
@@ -228,8 +387,13 @@ impl From<ParseFloatError> for ParseError {
 mod test {
     use vector_common::assert_event_data_eq;
 
-    use super::{parse, sanitize_key, sanitize_sampling};
-    use crate::event::metric::{Metric, MetricKind, MetricValue, StatisticKind};
+    use chrono::{TimeZone, Utc};
+
+    use super::{parse, parse_message, sanitize_key, sanitize_sampling};
+    use crate::{
+        config::log_schema,
+        event::metric::{Metric, MetricKind, MetricValue, StatisticKind},
+    };
 
     #[test]
     fn basic_counter() {
@@ -254,8 +418,8 @@ mod test {
             )
             .with_tags(Some(
                 vec![
-                    ("tag1".to_owned(), "true".to_owned()),
-                    ("tag2".to_owned(), "value".to_owned()),
+                    ("tag1".into(), "true".to_owned()),
+                    ("tag2".into(), "value".to_owned()),
                 ]
                 .into_iter()
                 .collect(),
@@ -316,9 +480,9 @@ mod test {
             )
             .with_tags(Some(
                 vec![
-                    ("region".to_owned(), "us-west1".to_owned()),
-                    ("production".to_owned(), "true".to_owned()),
-                    ("e".to_owned(), "".to_owned()),
+                    ("region".into(), "us-west1".to_owned()),
+                    ("production".into(), "true".to_owned()),
+                    ("e".into(), "".to_owned()),
                 ]
                 .into_iter()
                 .collect(),
@@ -340,9 +504,9 @@ mod test {
             )
             .with_tags(Some(
                 vec![
-                    ("region".to_owned(), "us-west1".to_owned()),
-                    ("production".to_owned(), "true".to_owned()),
-                    ("e".to_owned(), "".to_owned()),
+                    ("region".into(), "us-west1".to_owned()),
+                    ("production".into(), "true".to_owned()),
+                    ("e".into(), "".to_owned()),
                 ]
                 .into_iter()
                 .collect(),
@@ -396,6 +560,130 @@ mod test {
         );
     }
 
+    #[test]
+    fn multi_value_counter() {
+        assert_event_data_eq!(
+            parse("foo:1:2:3|c|@0.5"),
+            Ok(Metric::new(
+                "foo",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 12.0 },
+            )),
+        );
+    }
+
+    #[test]
+    fn multi_value_timer() {
+        assert_event_data_eq!(
+            parse("glork:320:640|ms|@0.3"),
+            Ok(Metric::new(
+                "glork",
+                MetricKind::Incremental,
+                MetricValue::Distribution {
+                    samples: vector_core::samples![0.320 => 3, 0.640 => 3],
+                    statistic: StatisticKind::Histogram
+                },
+            )),
+        );
+    }
+
+    #[test]
+    fn multi_value_gauge() {
+        assert_event_data_eq!(
+            parse("gaugor:+1:-3|g"),
+            Ok(Metric::new(
+                "gaugor",
+                MetricKind::Incremental,
+                MetricValue::Gauge { value: -2.0 },
+            )),
+        );
+        assert_event_data_eq!(
+            parse("gaugor:+1:10:-3|g"),
+            Ok(Metric::new(
+                "gaugor",
+                MetricKind::Absolute,
+                MetricValue::Gauge { value: 7.0 },
+            )),
+        );
+    }
+
+    #[test]
+    fn multi_value_set() {
+        assert_event_data_eq!(
+            parse("uniques:765:766|s"),
+            Ok(Metric::new(
+                "uniques",
+                MetricKind::Incremental,
+                MetricValue::Set {
+                    values: vec!["765".into(), "766".into()].into_iter().collect()
+                },
+            )),
+        );
+    }
+
+    #[test]
+    fn container_id_and_timestamp() {
+        assert_event_data_eq!(
+            parse("foo:1|c|#env:prod|c:abc123|T1656581400"),
+            Ok(Metric::new(
+                "foo",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 1.0 },
+            )
+            .with_tags(Some(
+                vec![
+                    ("container_id".into(), "abc123".to_owned()),
+                    ("env".into(), "prod".to_owned()),
+                ]
+                .into_iter()
+                .collect(),
+            ))
+            .with_timestamp(Some(Utc.timestamp(1656581400, 0)))),
+        );
+    }
+
+    #[test]
+    fn dogstatsd_event() {
+        let event = parse_message(
+            "_e{5,12}:title|line1\\nline2|d:1656581400|h:web-1|p:low|t:warning|#env:prod",
+        )
+        .unwrap();
+        let log = event.as_log();
+        assert_eq!(log["dogstatsd_type"], "event".into());
+        assert_eq!(log["title"], "title".into());
+        assert_eq!(log[log_schema().message_key()], "line1\nline2".into());
+        assert_eq!(log[log_schema().host_key()], "web-1".into());
+        assert_eq!(log["priority"], "low".into());
+        assert_eq!(log["alert_type"], "warning".into());
+        assert_eq!(log["tags.env"], "prod".into());
+        assert_eq!(
+            log[log_schema().timestamp_key()],
+            Utc.timestamp(1656581400, 0).into()
+        );
+    }
+
+    #[test]
+    fn dogstatsd_event_length_mismatch() {
+        assert!(parse_message("_e{6,4}:title|text").is_err());
+        assert!(parse_message("_e{5,10}:title|text").is_err());
+    }
+
+    #[test]
+    fn dogstatsd_service_check() {
+        let event =
+            parse_message("_sc|app.ok|2|h:web-1|c:abc123|#env:prod|m:down | retrying").unwrap();
+        let log = event.as_log();
+        assert_eq!(log["dogstatsd_type"], "service_check".into());
+        assert_eq!(log["check"], "app.ok".into());
+        assert_eq!(log["status"], 2.into());
+        assert_eq!(log[log_schema().host_key()], "web-1".into());
+        assert_eq!(log["container_id"], "abc123".into());
+        assert_eq!(log["tags.env"], "prod".into());
+        assert_eq!(log[log_schema().message_key()], "down | retrying".into());
+
+        assert!(parse_message("_sc|app.ok|4").is_err());
+    }
+
     #[test]
     fn sanitizing_keys() {
         assert_eq!("foo-bar-baz", sanitize_key("foo/bar/baz"));
@@ -10,7 +10,10 @@ use super::StatsdDeserializer;
 use crate::{
     codecs::Decoder,
     shutdown::ShutdownSignal,
-    sources::{util::build_unix_stream_source, Source},
+    sources::{
+        util::{build_unix_datagram_source, build_unix_stream_source},
+        Source,
+    },
     SourceSender,
 };
 
@@ -24,6 +27,28 @@ pub struct UnixConfig {
     pub path: PathBuf,
 }
 
+/// Unix domain socket configuration for the `statsd` source, in datagram mode.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct UnixDatagramConfig {
+    /// The Unix socket path.
+    ///
+    /// This should be an absolute path.
+    pub path: PathBuf,
+
+    /// Unix file mode bits to be applied to the unix socket file as its designated file permissions.
+    ///
+    /// Note that the file mode value can be specified in any numeric format supported by your configuration
+    /// language, but it is most intuitive to use an octal number.
+    pub socket_file_mode: Option<u32>,
+
+    /// The maximum size, in bytes, of a datagram.
+    ///
+    /// Each datagram may hold several newline-delimited metrics.
+    #[serde(default = "crate::serde::default_max_length")]
+    pub max_length: usize,
+}
+
 pub fn statsd_unix(
     config: UnixConfig,
     shutdown: ShutdownSignal,
@@ -43,3 +68,24 @@ pub fn statsd_unix(
         out,
     )
 }
+
+pub fn statsd_unix_datagram(
+    config: UnixDatagramConfig,
+    shutdown: ShutdownSignal,
+    out: SourceSender,
+) -> crate::Result<Source> {
+    let decoder = Decoder::new(
+        Framer::NewlineDelimited(NewlineDelimitedDecoder::new()),
+        Deserializer::Boxed(Box::new(StatsdDeserializer::unix())),
+    );
+
+    build_unix_datagram_source(
+        config.path,
+        config.socket_file_mode,
+        config.max_length,
+        decoder,
+        |_events, _host| {},
+        shutdown,
+        out,
+    )
+}
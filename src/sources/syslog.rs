@@ -10,7 +10,6 @@ use codecs::{
 };
 use futures::StreamExt;
 use smallvec::SmallVec;
-use tokio::net::UdpSocket;
 use tokio_util::udp::UdpFramed;
 use vector_config::configurable_component;
 use vector_core::config::LogNamespace;
@@ -82,7 +81,7 @@ pub enum Mode {
     /// Listen on UDP.
     Udp {
         /// The address to listen for messages on.
-        address: SocketAddr,
+        address: SocketListenAddr,
 
         /// The size, in bytes, of the receive buffer used for the listening socket.
         ///
@@ -212,7 +211,7 @@ impl SourceConfig for SyslogConfig {
     fn resources(&self) -> Vec<Resource> {
         match self.mode.clone() {
             Mode::Tcp { address, .. } => vec![address.into()],
-            Mode::Udp { address, .. } => vec![Resource::udp(address)],
+            Mode::Udp { address, .. } => vec![address.udp_resource()],
             #[cfg(unix)]
             Mode::Unix { .. } => vec![],
         }
@@ -252,7 +251,7 @@ impl TcpSource for SyslogTcpSource {
 }
 
 pub fn udp(
-    addr: SocketAddr,
+    addr: SocketListenAddr,
     _max_length: usize,
     host_key: String,
     receive_buffer_bytes: Option<usize>,
@@ -260,9 +259,9 @@ pub fn udp(
     mut out: SourceSender,
 ) -> super::Source {
     Box::pin(async move {
-        let socket = UdpSocket::bind(&addr)
-            .await
-            .expect("Failed to bind to UDP listener socket");
+        let socket = addr.udp_socket().await.map_err(|error| {
+            error!(message = "Failed to bind to UDP listener socket.", %error);
+        })?;
 
         if let Some(receive_buffer_bytes) = receive_buffer_bytes {
             if let Err(error) = udp::set_receive_buffer_size(&socket, receive_buffer_bytes) {
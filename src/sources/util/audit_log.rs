@@ -0,0 +1,497 @@
+//! Common logic for sources that pull audit logs from SaaS APIs.
+//!
+//! Specific audit log sources will:
+//!   - Load their `Cursors` from their state store.
+//!   - Implement the `AuditLogPoller` trait, paging through the records after their cursors
+//!     with an `AuditLogClient`, which retries the requests that are rate limited.
+//!   - Build the events of the records with `AuditRecord`, so that all the sources output the
+//!     same normalized fields.
+//!   - Advance their cursors once the events of each page have been sent with `send_events()`,
+//!     so that a restart resumes from the last page sent.
+//!   - Call run_poller() to poll at an interval until shutdown.
+
+use std::{collections::HashMap, time::Duration};
+
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use http::{header::HeaderName, response::Parts, Request, StatusCode, Uri};
+use hyper::Body;
+use serde::de::DeserializeOwned;
+use tokio_stream::wrappers::IntervalStream;
+use vector_common::shutdown::ShutdownSignal;
+use vector_core::{
+    config::log_schema,
+    event::{Event, LogEvent, Value},
+    ByteSizeOf,
+};
+
+use super::http_scrape_auth::RequestAuth;
+use crate::{
+    http::HttpClient,
+    internal_events::{
+        AuditLogCheckpointError, AuditLogRateLimited, AuditLogRequestError,
+        AuditLogResponseParseError, EndpointBytesReceived, EventsReceived, StreamClosedError,
+    },
+    state_store::BoxedStateStore,
+    SourceSender,
+};
+
+const CURSORS_KEY: &str = "cursors.json";
+
+/// How many times a rate limited request is retried before giving up.
+const MAX_RETRIES: u32 = 5;
+
+/// How long to wait before retrying a rate limited request, when the API doesn't say.
+const DEFAULT_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// The longest wait between two requests, whatever the API asks for.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(300);
+
+static RATE_LIMIT_REMAINING: HeaderName = HeaderName::from_static("x-rate-limit-remaining");
+static RATE_LIMIT_RESET: HeaderName = HeaderName::from_static("x-rate-limit-reset");
+
+/// The cursors of a source, such as the position of the last record received of each log,
+/// persisted in its state store.
+pub(crate) struct Cursors {
+    store: BoxedStateStore,
+    cursors: HashMap<String, String>,
+}
+
+impl Cursors {
+    /// Loads the persisted cursors, if any.
+    pub(crate) async fn load(store: BoxedStateStore) -> crate::Result<Self> {
+        let cursors = match store.get(CURSORS_KEY).await? {
+            Some(cursors) => serde_json::from_slice(&cursors)?,
+            None => HashMap::new(),
+        };
+        Ok(Self { store, cursors })
+    }
+
+    pub(crate) fn get(&self, key: &str) -> Option<&str> {
+        self.cursors.get(key).map(String::as_str)
+    }
+
+    /// Sets and persists a cursor.
+    pub(crate) async fn set(&mut self, key: &str, cursor: String) {
+        self.cursors.insert(key.to_string(), cursor);
+
+        let cursors = serde_json::to_vec(&self.cursors).expect("cursors are serializable");
+        if let Err(error) = self.store.set(CURSORS_KEY, cursors.into()).await {
+            emit!(AuditLogCheckpointError { error });
+        }
+    }
+}
+
+/// Pulls the records of an audit log API.
+#[async_trait::async_trait]
+pub(crate) trait AuditLogPoller: Send {
+    /// Pulls the records after the cursors, sending their events and advancing the cursors
+    /// page by page.
+    async fn poll(
+        &mut self,
+        client: &AuditLogClient,
+        cursors: &mut Cursors,
+        out: &mut SourceSender,
+    ) -> Result<(), PollError>;
+}
+
+/// Why a poll ended early.
+#[derive(Debug)]
+pub(crate) enum PollError {
+    /// A request failed. The next poll resumes from the cursors.
+    Request(crate::Error),
+    /// A response couldn't be parsed, which has already been reported.
+    Parse,
+    /// The events couldn't be sent downstream, which ends the source.
+    Closed,
+}
+
+impl<E: Into<crate::Error>> From<E> for PollError {
+    fn from(error: E) -> Self {
+        Self::Request(error.into())
+    }
+}
+
+/// Polls an audit log API at an interval, until shutdown.
+pub(crate) async fn run_poller<P: AuditLogPoller>(
+    mut poller: P,
+    client: AuditLogClient,
+    mut cursors: Cursors,
+    interval: Duration,
+    shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let mut ticks =
+        IntervalStream::new(tokio::time::interval(interval)).take_until(shutdown.clone());
+    while ticks.next().await.is_some() {
+        // A poll can take a while when it is rate limited, so it is interrupted by shutdown.
+        // The cursors are only advanced once the events of a page have been sent, so no
+        // events are lost.
+        let result = tokio::select! {
+            result = poller.poll(&client, &mut cursors, &mut out) => result,
+            _ = shutdown.clone() => break,
+        };
+        match result {
+            Ok(()) | Err(PollError::Parse) => (),
+            Err(PollError::Request(error)) => emit!(AuditLogRequestError {
+                provider: client.provider,
+                error,
+            }),
+            Err(PollError::Closed) => return Err(()),
+        }
+    }
+    Ok(())
+}
+
+/// Sends the events of a page of records.
+pub(crate) async fn send_events(
+    out: &mut SourceSender,
+    events: Vec<Event>,
+) -> Result<(), PollError> {
+    let count = events.len();
+    emit!(EventsReceived {
+        count,
+        byte_size: events.size_of(),
+    });
+    out.send_batch(events).await.map_err(|error| {
+        emit!(StreamClosedError { error, count });
+        PollError::Closed
+    })
+}
+
+/// An HTTP client for audit log APIs, authenticating the requests and retrying those that are
+/// rate limited.
+pub(crate) struct AuditLogClient {
+    client: HttpClient,
+    auth: Option<RequestAuth>,
+    provider: &'static str,
+}
+
+impl AuditLogClient {
+    pub(crate) const fn new(
+        client: HttpClient,
+        auth: Option<RequestAuth>,
+        provider: &'static str,
+    ) -> Self {
+        Self {
+            client,
+            auth,
+            provider,
+        }
+    }
+
+    /// Sends a request, returning the JSON body of its successful response.
+    pub(crate) async fn get_json<T: DeserializeOwned>(
+        &self,
+        request: Request<Bytes>,
+    ) -> Result<(Parts, T), PollError> {
+        let (parts, body) = self.send(request).await?;
+        match serde_json::from_slice(&body) {
+            Ok(body) => Ok((parts, body)),
+            Err(error) => {
+                emit!(AuditLogResponseParseError {
+                    provider: self.provider,
+                    error,
+                });
+                Err(PollError::Parse)
+            }
+        }
+    }
+
+    /// Sends a request, returning its successful response.
+    ///
+    /// Requests rejected with a `429` or `503` status, or with a `403` status and a rate limit
+    /// error, are retried after the delay asked for by the API. Successful responses saying that the rate limit is exhausted are returned once
+    /// it is reset, so that the next request isn't rejected.
+    pub(crate) async fn send(&self, request: Request<Bytes>) -> crate::Result<(Parts, Bytes)> {
+        let mut attempt = 0;
+        loop {
+            let mut attempt_request = clone_request(&request);
+            if let Some(auth) = &self.auth {
+                auth.apply(&self.client, &mut attempt_request).await?;
+            }
+
+            let response = self.client.send(attempt_request.map(Body::from)).await?;
+            let (parts, body) = response.into_parts();
+            let body = hyper::body::to_bytes(body).await?;
+            emit!(EndpointBytesReceived {
+                byte_size: body.len(),
+                protocol: "http",
+                endpoint: request.uri().host().unwrap_or_default(),
+            });
+
+            match parts.status {
+                status if status.is_success() => {
+                    if let Some(delay) = rate_limit_reset(&parts) {
+                        emit!(AuditLogRateLimited {
+                            provider: self.provider,
+                            delay,
+                        });
+                        tokio::time::sleep(delay).await;
+                    }
+                    return Ok((parts, body));
+                }
+                status if is_rate_limited(status, &body) && attempt < MAX_RETRIES => {
+                    let delay = retry_delay(&parts)
+                        .unwrap_or(DEFAULT_RETRY_DELAY * 2u32.pow(attempt))
+                        .min(MAX_RETRY_DELAY);
+                    emit!(AuditLogRateLimited {
+                        provider: self.provider,
+                        delay,
+                    });
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                status => {
+                    if status == StatusCode::UNAUTHORIZED {
+                        if let Some(auth) = &self.auth {
+                            auth.on_unauthorized().await;
+                        }
+                    }
+                    return Err(format!(
+                        "{} responded with {}: {}",
+                        request.uri(),
+                        status,
+                        String::from_utf8_lossy(&body)
+                    )
+                    .into());
+                }
+            }
+        }
+    }
+}
+
+fn is_rate_limited(status: StatusCode, body: &Bytes) -> bool {
+    match status {
+        StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE => true,
+        // Some APIs, such as the Google ones, report exhausted quotas as forbidden requests.
+        StatusCode::FORBIDDEN => String::from_utf8_lossy(body)
+            .to_ascii_lowercase()
+            .contains("ratelimitexceeded"),
+        _ => false,
+    }
+}
+
+fn clone_request(request: &Request<Bytes>) -> Request<Bytes> {
+    let mut clone = Request::new(request.body().clone());
+    *clone.method_mut() = request.method().clone();
+    *clone.uri_mut() = request.uri().clone();
+    *clone.headers_mut() = request.headers().clone();
+    clone
+}
+
+/// Returns the delay asked for by a rate limited response, from either its `Retry-After` header
+/// or its `X-Rate-Limit-Reset` header.
+fn retry_delay(parts: &Parts) -> Option<Duration> {
+    let retry_after = parts
+        .headers
+        .get(http::header::RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse().ok())
+        .map(Duration::from_secs);
+    retry_after.or_else(|| reset_delay(parts))
+}
+
+/// Returns how long until the rate limit is reset, if a response says that it is exhausted.
+fn rate_limit_reset(parts: &Parts) -> Option<Duration> {
+    let remaining = parts
+        .headers
+        .get(&RATE_LIMIT_REMAINING)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    if remaining == 0 {
+        reset_delay(parts).map(|delay| delay.min(MAX_RETRY_DELAY))
+    } else {
+        None
+    }
+}
+
+/// Returns how long until the time of the `X-Rate-Limit-Reset` header, in seconds since the epoch.
+fn reset_delay(parts: &Parts) -> Option<Duration> {
+    let reset = parts
+        .headers
+        .get(&RATE_LIMIT_RESET)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<i64>()
+        .ok()?;
+    let seconds = u64::try_from(reset - Utc::now().timestamp()).ok()?;
+    // The clocks of the API and of Vector may differ slightly, so wait an extra second.
+    Some(Duration::from_secs(seconds + 1))
+}
+
+/// Returns the URL of the `next` link of the `Link` header of a response, if any.
+pub(crate) fn next_link(url: &Uri, parts: &Parts) -> Option<Uri> {
+    parts
+        .headers
+        .get_all(http::header::LINK)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .find_map(|link| {
+            let (target, params) = link.trim().split_once(';')?;
+            let is_next = params.split(';').any(|param| {
+                matches!(
+                    param.trim().split_once('='),
+                    Some((name, value)) if name.trim() == "rel" && value.trim().trim_matches('"') == "next"
+                )
+            });
+            if !is_next {
+                return None;
+            }
+            let target = target.trim().strip_prefix('<')?.strip_suffix('>')?;
+            let base = url::Url::parse(&url.to_string()).ok()?;
+            base.join(target).ok()?.as_str().parse().ok()
+        })
+}
+
+/// The normalized fields of an audit log record.
+///
+/// All the audit log sources output these fields, so that the records of the different APIs can
+/// be processed the same way, along with the original record in the `original` field.
+#[derive(Debug, Default)]
+pub(crate) struct AuditRecord {
+    pub timestamp: Option<DateTime<Utc>>,
+    pub id: Option<String>,
+    pub action: Option<String>,
+    pub outcome: Option<String>,
+    pub actor_id: Option<String>,
+    pub actor_name: Option<String>,
+    pub client_ip: Option<String>,
+}
+
+impl AuditRecord {
+    /// Builds the event of a record.
+    pub(crate) fn into_log(
+        self,
+        source_type: &'static str,
+        provider: &'static str,
+        original: serde_json::Value,
+    ) -> LogEvent {
+        let mut log = LogEvent::default();
+        log.insert(
+            log_schema().timestamp_key(),
+            self.timestamp.unwrap_or_else(Utc::now),
+        );
+        log.insert(log_schema().source_type_key(), source_type);
+        log.insert("provider", provider);
+        let fields = [
+            ("event.id", self.id),
+            ("event.action", self.action),
+            ("event.outcome", self.outcome),
+            ("actor.id", self.actor_id),
+            ("actor.name", self.actor_name),
+            ("client.ip", self.client_ip),
+        ];
+        for (field, value) in fields {
+            if let Some(value) = value {
+                log.insert(field, value);
+            }
+        }
+        log.insert("original", Value::from(original));
+        log
+    }
+}
+
+/// Returns the string at a JSON pointer of a record, if any.
+pub(crate) fn string_at(record: &serde_json::Value, pointer: &str) -> Option<String> {
+    match record.pointer(pointer)? {
+        serde_json::Value::String(value) if !value.is_empty() => Some(value.clone()),
+        serde_json::Value::Number(value) => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::state_store::FileStateStore;
+
+    fn parts(headers: &[(&str, &str)]) -> Parts {
+        let mut response = http::Response::builder().status(StatusCode::TOO_MANY_REQUESTS);
+        for (name, value) in headers {
+            response = response.header(*name, *value);
+        }
+        response.body(()).unwrap().into_parts().0
+    }
+
+    #[tokio::test]
+    async fn persists_cursors() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let store: BoxedStateStore = Arc::new(FileStateStore::new(data_dir.path().to_path_buf()));
+
+        let mut cursors = Cursors::load(Arc::clone(&store)).await.unwrap();
+        assert_eq!(cursors.get("logs"), None);
+        cursors.set("logs", "2022-06-01".to_string()).await;
+
+        let cursors = Cursors::load(store).await.unwrap();
+        assert_eq!(cursors.get("logs"), Some("2022-06-01"));
+    }
+
+    #[test]
+    fn parses_rate_limit_headers() {
+        assert_eq!(
+            retry_delay(&parts(&[("Retry-After", "30")])),
+            Some(Duration::from_secs(30))
+        );
+
+        let reset = (Utc::now().timestamp() + 60).to_string();
+        let delay = retry_delay(&parts(&[("X-Rate-Limit-Reset", &reset)])).unwrap();
+        assert!(delay > Duration::from_secs(55) && delay <= Duration::from_secs(61));
+
+        assert_eq!(
+            rate_limit_reset(&parts(&[
+                ("X-Rate-Limit-Remaining", "10"),
+                ("X-Rate-Limit-Reset", &reset)
+            ])),
+            None
+        );
+        assert!(rate_limit_reset(&parts(&[
+            ("X-Rate-Limit-Remaining", "0"),
+            ("X-Rate-Limit-Reset", &reset)
+        ]))
+        .is_some());
+        assert_eq!(retry_delay(&parts(&[])), None);
+    }
+
+    #[test]
+    fn finds_next_link() {
+        let url = "https://example.okta.com/api/v1/logs".parse().unwrap();
+        let parts = parts(&[(
+            "Link",
+            r#"<https://example.okta.com/api/v1/logs?limit=2>; rel="self", </api/v1/logs?after=abc>; rel="next""#,
+        )]);
+        assert_eq!(
+            next_link(&url, &parts).unwrap(),
+            "https://example.okta.com/api/v1/logs?after=abc"
+        );
+    }
+
+    #[test]
+    fn builds_normalized_events() {
+        let record = serde_json::json!({"actor": {"id": "00u1"}, "ip": ""});
+        let log = AuditRecord {
+            timestamp: Some(Utc.ymd(2022, 1, 1).and_hms(0, 0, 0)),
+            action: Some("user.session.start".to_string()),
+            actor_id: string_at(&record, "/actor/id"),
+            client_ip: string_at(&record, "/ip"),
+            ..Default::default()
+        }
+        .into_log("okta", "okta", record);
+
+        assert_eq!(log["provider"], "okta".into());
+        assert_eq!(log["event.action"], "user.session.start".into());
+        assert_eq!(log["actor.id"], "00u1".into());
+        assert!(!log.contains("client.ip"));
+        assert_eq!(log["original.actor.id"], "00u1".into());
+    }
+}
@@ -0,0 +1,165 @@
+//! Unwrapping of the messages delivered by CloudWatch Logs subscription filters.
+//!
+//! Subscription filters deliver the log events of a log group to Kinesis Data Streams or Kinesis
+//! Data Firehose as gzipped JSON messages, each holding a batch of log events along with the log
+//! group and log stream they were written to.
+
+use std::io::Read;
+
+use chrono::{DateTime, TimeZone, Utc};
+use flate2::read::MultiGzDecoder;
+use lookup::event_path;
+use serde::Deserialize;
+
+use crate::event::LogEvent;
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// A message delivered by a CloudWatch Logs subscription filter.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubscriptionMessage {
+    pub message_type: MessageType,
+    pub owner: String,
+    pub log_group: String,
+    pub log_stream: String,
+    pub subscription_filters: Vec<String>,
+    pub log_events: Vec<SubscriptionLogEvent>,
+}
+
+/// The type of a subscription filter message.
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum MessageType {
+    /// The message holds log events.
+    DataMessage,
+    /// The message was sent by CloudWatch Logs to check that the destination is reachable.
+    ControlMessage,
+}
+
+/// A log event of a subscription filter message.
+#[derive(Debug, Deserialize)]
+pub struct SubscriptionLogEvent {
+    pub id: String,
+    pub timestamp: i64,
+    pub message: String,
+}
+
+impl SubscriptionLogEvent {
+    pub fn timestamp(&self) -> Option<DateTime<Utc>> {
+        Utc.timestamp_millis_opt(self.timestamp).single()
+    }
+}
+
+/// Parses a subscription filter message, decompressing it first if it is gzipped.
+///
+/// Returns `None` if the data is not a subscription filter message.
+pub fn parse_subscription_message(data: &[u8]) -> Option<SubscriptionMessage> {
+    if data.starts_with(&GZIP_MAGIC) {
+        let mut decoded = Vec::new();
+        MultiGzDecoder::new(data).read_to_end(&mut decoded).ok()?;
+        serde_json::from_slice(&decoded).ok()
+    } else {
+        serde_json::from_slice(data).ok()
+    }
+}
+
+/// Adds the fields of the subscription filter message, and of the log event it was unwrapped from,
+/// to the log.
+pub fn insert_subscription_fields(
+    log: &mut LogEvent,
+    message: &SubscriptionMessage,
+    event: &SubscriptionLogEvent,
+) {
+    log.try_insert(event_path!("id"), event.id.clone());
+    log.try_insert(event_path!("log_group"), message.log_group.clone());
+    log.try_insert(event_path!("log_stream"), message.log_stream.clone());
+    log.try_insert(event_path!("owner"), message.owner.clone());
+    log.try_insert(
+        event_path!("subscription_filters"),
+        message.subscription_filters.clone(),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::{write::GzEncoder, Compression};
+
+    use super::*;
+
+    const DATA_MESSAGE: &str = r#"{
+        "messageType": "DATA_MESSAGE",
+        "owner": "071959437513",
+        "logGroup": "/jesse/test",
+        "logStream": "test",
+        "subscriptionFilters": ["Destination"],
+        "logEvents": [
+            {
+                "id": "35683658089614582423604394983260738922885519999578275840",
+                "timestamp": 1600110569039,
+                "message": "{\"bytes\":26780,\"datetime\":\"14/Sep/2020:11:45:41 -0400\"}"
+            },
+            {
+                "id": "35683658089659183914001456229543810359430816722590236673",
+                "timestamp": 1600110569041,
+                "message": "{\"bytes\":17707,\"datetime\":\"14/Sep/2020:11:45:41 -0400\"}"
+            }
+        ]
+    }"#;
+
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn parses_gzipped_data_message() {
+        let message = parse_subscription_message(&gzip(DATA_MESSAGE.as_bytes())).unwrap();
+        assert_eq!(message.message_type, MessageType::DataMessage);
+        assert_eq!(message.log_group, "/jesse/test");
+        assert_eq!(message.log_events.len(), 2);
+        assert_eq!(
+            message.log_events[0].timestamp(),
+            Some(Utc.timestamp_millis(1600110569039))
+        );
+
+        let mut log = LogEvent::default();
+        insert_subscription_fields(&mut log, &message, &message.log_events[1]);
+        assert_eq!(log["log_stream"], "test".into());
+        assert_eq!(
+            log["id"],
+            "35683658089659183914001456229543810359430816722590236673".into()
+        );
+    }
+
+    #[test]
+    fn parses_control_message() {
+        let message = parse_subscription_message(
+            br#"{
+                "messageType": "CONTROL_MESSAGE",
+                "owner": "CloudwatchLogs",
+                "logGroup": "",
+                "logStream": "",
+                "subscriptionFilters": [],
+                "logEvents": [
+                    {
+                        "id": "",
+                        "timestamp": 1600110003794,
+                        "message": "CWL CONTROL MESSAGE: Checking health of destination Firehose."
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(message.message_type, MessageType::ControlMessage);
+    }
+
+    #[test]
+    fn ignores_other_data() {
+        assert!(parse_subscription_message(b"plain text").is_none());
+        assert!(parse_subscription_message(&gzip(br#"{"message": "json"}"#)).is_none());
+    }
+}
@@ -1,6 +1,6 @@
 use std::{
     cmp,
-    io::Write,
+    io::{self, Write},
     mem,
     pin::Pin,
     task::{Context, Poll},
@@ -31,8 +31,10 @@ const GRPC_MESSAGE_HEADER_LEN: usize = mem::size_of::<u8>() + mem::size_of::<u32
 const GRPC_ENCODING_HEADER: &str = "grpc-encoding";
 const GRPC_ACCEPT_ENCODING_HEADER: &str = "grpc-accept-encoding";
 
+#[derive(Clone, Copy)]
 enum CompressionScheme {
     Gzip,
+    Zstd,
 }
 
 impl CompressionScheme {
@@ -52,6 +54,7 @@ impl CompressionScheme {
                 None => Ok(None),
                 Some(scheme) => match scheme.as_str() {
                     "gzip" => Ok(Some(CompressionScheme::Gzip)),
+                    "zstd" => Ok(Some(CompressionScheme::Zstd)),
                     other => Err(Status::unimplemented(format!(
                         "compression scheme `{}` is not supported",
                         other
@@ -61,7 +64,7 @@ impl CompressionScheme {
             .map_err(|mut status| {
                 status.metadata_mut().insert(
                     GRPC_ACCEPT_ENCODING_HEADER,
-                    AsciiMetadataValue::from_static("gzip,identity"),
+                    AsciiMetadataValue::from_static("gzip,zstd,identity"),
                 );
                 status
             })
@@ -80,18 +83,46 @@ impl Default for State {
     }
 }
 
-fn new_decompressor() -> GzDecoder<Vec<u8>> {
-    // Create the backing buffer for the decompressor and set the compression flag to false (0) and pre-allocate
-    // the space for the length prefix, which we'll fill out once we've finalized the decompressor.
-    let mut buf = Vec::new();
-    buf.resize(GRPC_MESSAGE_HEADER_LEN, 0x00);
+enum Decompressor {
+    Gzip(GzDecoder<Vec<u8>>),
+    Zstd(zstd::stream::write::Decoder<'static, Vec<u8>>),
+}
+
+impl Decompressor {
+    fn new(scheme: CompressionScheme) -> io::Result<Self> {
+        // Create the backing buffer for the decompressor and set the compression flag to false (0) and pre-allocate
+        // the space for the length prefix, which we'll fill out once we've finalized the decompressor.
+        let mut buf = Vec::new();
+        buf.resize(GRPC_MESSAGE_HEADER_LEN, 0x00);
+
+        Ok(match scheme {
+            CompressionScheme::Gzip => Self::Gzip(GzDecoder::new(buf)),
+            CompressionScheme::Zstd => Self::Zstd(zstd::stream::write::Decoder::new(buf)?),
+        })
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        match self {
+            Self::Gzip(decoder) => decoder.write_all(buf),
+            Self::Zstd(decoder) => decoder.write_all(buf),
+        }
+    }
 
-    GzDecoder::new(buf)
+    fn finish(self) -> io::Result<Vec<u8>> {
+        match self {
+            Self::Gzip(decoder) => decoder.finish(),
+            Self::Zstd(mut decoder) => {
+                decoder.flush()?;
+                Ok(decoder.into_inner())
+            }
+        }
+    }
 }
 
 async fn drive_body_decompression(
     mut source: Body,
     mut destination: Sender,
+    scheme: CompressionScheme,
 ) -> Result<usize, Status> {
     let mut state = State::default();
     let mut buf = BytesMut::new();
@@ -173,7 +204,14 @@ async fn drive_body_decompression(
                             // the decompressor. This is _technically_ synchronous but there's really no way to do it
                             // asynchronously since we already have the data, and that's the only asynchronous part.
                             let to_take = cmp::min(available, *remaining);
-                            let decompressor = decompressor.get_or_insert_with(new_decompressor);
+                            if decompressor.is_none() {
+                                decompressor = Some(Decompressor::new(scheme).map_err(|_| {
+                                    Status::internal("failed to create decompressor")
+                                })?);
+                            }
+                            let decompressor = decompressor
+                                .as_mut()
+                                .expect("decompressor must have been created");
                             if decompressor.write_all(&buf[..to_take]).is_err() {
                                 return Err(Status::internal("failed to write to decompressor"));
                             }
@@ -244,6 +282,7 @@ async fn drive_body_decompression(
 async fn drive_request<F, E>(
     source: Body,
     destination: Sender,
+    scheme: CompressionScheme,
     inner: F,
     bytes_received: Registered<BytesReceived>,
 ) -> Result<Response<BoxBody>, E>
@@ -251,7 +290,7 @@ where
     F: Future<Output = Result<Response<BoxBody>, E>>,
     E: std::fmt::Display,
 {
-    let body_decompression = drive_body_decompression(source, destination);
+    let body_decompression = drive_body_decompression(source, destination, scheme);
 
     pin!(inner);
     pin!(body_decompression);
@@ -328,16 +367,24 @@ where
             // The request either isn't using compression, or it has indicated compression may be used and we know we
             // can support decompression based on the indicated compression scheme... so wrap the body to decompress, if
             // need be, and then track the bytes that flowed through.
-            //
-            // TODO: Actually use the scheme given back to us to support other compression schemes.
-            Ok(_) => {
+            Ok(scheme) => {
                 let (destination, decompressed_body) = Body::channel();
-                let (req_parts, req_body) = req.into_parts();
+                let (mut req_parts, req_body) = req.into_parts();
+                // Every message is decompressed before reaching `tonic`, which rejects the compression schemes it
+                // doesn't support itself, such as zstd.
+                req_parts.headers.remove(GRPC_ENCODING_HEADER);
                 let mapped_req = Request::from_parts(req_parts, decompressed_body);
 
                 let inner = self.inner.call(mapped_req);
 
-                drive_request(req_body, destination, inner, self.bytes_received.clone()).boxed()
+                drive_request(
+                    req_body,
+                    destination,
+                    scheme.unwrap_or(CompressionScheme::Gzip),
+                    inner,
+                    self.bytes_received.clone(),
+                )
+                .boxed()
             }
         }
     }
@@ -362,7 +409,8 @@ where
 /// request was valid, and was processed -- we can now report the number of bytes (after decompression) that were
 /// received _and_ processed correctly.
 ///
-/// The only supported compression scheme is gzip, which is also the only supported compression scheme in `tonic` itself.
+/// The supported compression schemes are gzip, which is also the only supported compression scheme in `tonic` itself,
+/// and zstd.
 #[derive(Clone, Default)]
 pub struct DecompressionAndMetricsLayer;
 
@@ -0,0 +1,74 @@
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use futures::{future::BoxFuture, FutureExt};
+use http::{header::CONTENT_TYPE, Request, Response};
+use hyper::Body;
+use tonic::body::BoxBody;
+use tower::{Layer, Service};
+
+/// Handles the plain HTTP requests received on the port of a gRPC server.
+pub type HttpFallback =
+    Arc<dyn Fn(Request<Body>) -> BoxFuture<'static, Response<BoxBody>> + Send + Sync>;
+
+/// A layer routing the requests that aren't gRPC requests, as told by their content type, to an HTTP
+/// handler instead of the gRPC services.
+#[derive(Clone, Default)]
+pub struct HttpFallbackLayer {
+    fallback: Option<HttpFallback>,
+}
+
+impl HttpFallbackLayer {
+    pub fn new(fallback: Option<HttpFallback>) -> Self {
+        Self { fallback }
+    }
+}
+
+impl<S> Layer<S> for HttpFallbackLayer {
+    type Service = HttpFallbackService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        HttpFallbackService {
+            inner,
+            fallback: self.fallback.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct HttpFallbackService<S> {
+    inner: S,
+    fallback: Option<HttpFallback>,
+}
+
+impl<S> Service<Request<Body>> for HttpFallbackService<S>
+where
+    S: Service<Request<Body>, Response = Response<BoxBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = Response<BoxBody>;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        match &self.fallback {
+            Some(fallback) if !is_grpc(&req) => fallback(req).map(Ok).boxed(),
+            _ => self.inner.call(req).boxed(),
+        }
+    }
+}
+
+fn is_grpc(req: &Request<Body>) -> bool {
+    req.headers()
+        .get(CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .map_or(false, |content_type| {
+            content_type.starts_with("application/grpc")
+        })
+}
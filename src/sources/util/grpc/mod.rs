@@ -14,12 +14,19 @@ use tower::Service;
 use tracing::{Instrument, Span};
 
 mod decompression;
+mod http_fallback;
 pub use self::decompression::{DecompressionAndMetrics, DecompressionAndMetricsLayer};
+pub use self::http_fallback::{HttpFallback, HttpFallbackLayer, HttpFallbackService};
 
+/// Runs a gRPC server for the service.
+///
+/// With an HTTP fallback, the server also accepts HTTP/1.1 connections, and the requests that aren't gRPC requests
+/// are handled by the fallback instead of the service.
 pub async fn run_grpc_server<S>(
     address: SocketAddr,
     tls_settings: MaybeTlsSettings,
     service: S,
+    http_fallback: Option<HttpFallback>,
     shutdown: ShutdownSignal,
 ) -> crate::Result<()>
 where
@@ -38,7 +45,11 @@ where
     info!(message = "Building gRPC server.", address = %address);
 
     Server::builder()
+        .accept_http1(http_fallback.is_some())
         .trace_fn(move |_| span.clone())
+        // The plain HTTP requests are routed before the gRPC payloads are decompressed and counted, as they aren't
+        // framed as gRPC messages.
+        .layer(HttpFallbackLayer::new(http_fallback))
         // This layer explicitly decompresses payloads, if compressed, and reports the number of message bytes we've
         // received if the message is processed successfully, aka `BytesReceived`. We do this because otherwise the only
         // access we have is either the event-specific bytes (the in-memory representation) or the raw bytes over the
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, convert::TryFrom, fmt, net::SocketAddr};
+use std::{collections::HashMap, convert::TryFrom, fmt};
 
 use async_trait::async_trait;
 use bytes::Bytes;
@@ -23,7 +23,7 @@ use crate::{
     internal_events::{
         HttpBadRequest, HttpBytesReceived, HttpEventsReceived, HttpInternalError, StreamClosedError,
     },
-    sources::util::http::HttpMethod,
+    sources::util::{http::HttpMethod, SocketListenAddr},
     tls::{MaybeTlsSettings, TlsEnableableConfig},
     SourceSender,
 };
@@ -47,7 +47,7 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
     #[allow(clippy::too_many_arguments)]
     fn run(
         self,
-        address: SocketAddr,
+        address: SocketListenAddr,
         path: &str,
         method: HttpMethod,
         strict_path: bool,
@@ -151,7 +151,9 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
 
             info!(message = "Building HTTP server.", address = %address);
 
-            let listener = tls.bind(&address).await.unwrap();
+            let listener = address.tcp_listener(&tls).await.map_err(|error| {
+                error!(message = "Failed to bind to listener socket.", %error);
+            })?;
             warp::serve(routes)
                 .serve_incoming_with_graceful_shutdown(
                     listener.accept_stream(),
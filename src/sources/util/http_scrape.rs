@@ -5,19 +5,22 @@
 //!   - Implmement a specific context struct which:
 //!       - Contains the data that source needs in order to process the HTTP responses into internal_events
 //!       - Implements the HttpScraper trait
+//!       - Optionally builds the URL and body of each request, and finds the next page of each
+//!         response, for sources that scrape paginated APIs
 //!   - Call http_scrape() supplying the generic inputs for scraping and the source-specific
 //!     context.
 
 use bytes::Bytes;
-use futures_util::{stream, FutureExt, StreamExt, TryFutureExt};
+use futures_util::{stream, StreamExt, TryFutureExt};
 use http::{response::Parts, Uri};
 use hyper::{Body, Request};
 use std::time::{Duration, Instant};
-use std::{collections::HashMap, future::ready};
+use std::{collections::HashMap, sync::Arc};
 use tokio_stream::wrappers::IntervalStream;
 
+use super::http_scrape_auth::RequestAuth;
 use crate::{
-    http::{Auth, HttpClient},
+    http::HttpClient,
     internal_events::{
         EndpointBytesReceived, HttpScrapeEventsReceived, HttpScrapeHttpError,
         HttpScrapeHttpResponseError, RequestCompleted, StreamClosedError,
@@ -39,10 +42,12 @@ pub(crate) struct GenericHttpScrapeInputs {
     pub headers: HashMap<String, Vec<String>>,
     /// Content type of the HTTP request, determined by the source
     pub content_type: String,
-    pub auth: Option<Auth>,
+    pub auth: Option<RequestAuth>,
     pub tls: TlsSettings,
     pub proxy: ProxyConfig,
     pub shutdown: ShutdownSignal,
+    /// Whether to scrape the urls once, rather than at every interval
+    pub once: bool,
 }
 
 /// The default interval to scrape the http endpoint if none is configured.
@@ -66,6 +71,22 @@ pub(crate) trait HttpScraperContext {
 
     /// (Optional) Called if the HTTP response is not 200 ('OK').
     fn on_http_response_error(&self, _uri: &Uri, _header: &Parts) {}
+
+    /// (Optional) Called before each HTTP request of a scrape to finish building it, such as by
+    /// setting its query parameters, headers or body.
+    ///
+    /// Returning `None` skips the request, and the rest of the scrape.
+    fn build_request(&mut self, request: Request<Bytes>) -> Option<Request<Bytes>> {
+        Some(request)
+    }
+
+    /// (Optional) Called after `on_response` to get the URL of the next page of the scrape.
+    fn next_page(&mut self, _url: &Uri, _header: &Parts, _body: &Bytes) -> Option<Uri> {
+        None
+    }
+
+    /// (Optional) Called once all the pages of a scrape have been received.
+    fn on_scrape_complete(&mut self) {}
 }
 
 /// Builds a url for the HTTP requests.
@@ -95,115 +116,93 @@ pub(crate) fn build_url(uri: &Uri, query: &HashMap<String, Vec<String>>) -> Uri
         .expect("Failed to build URI from parsed arguments")
 }
 
+/// Sets a query parameter of a url, replacing its previous values.
+pub(crate) fn set_query_param(uri: &Uri, param: &str, value: &str) -> Uri {
+    let mut serializer = url::form_urlencoded::Serializer::new(String::new());
+    if let Some(query) = uri.query() {
+        serializer.extend_pairs(
+            url::form_urlencoded::parse(query.as_bytes()).filter(|(key, _)| key != param),
+        );
+    }
+    serializer.append_pair(param, value);
+
+    let mut parts = uri.clone().into_parts();
+    parts.path_and_query = Some(
+        format!("{}?{}", uri.path(), serializer.finish())
+            .parse()
+            .expect("Failed to build URI from parsed arguments"),
+    );
+    Uri::from_parts(parts).expect("Failed to build URI from parsed arguments")
+}
+
 /// Scrapes one or more urls at an interval.
 ///   - The HTTP request is built per the options in provided generic inputs.
 ///   - The HTTP response is decoded/parsed into events by the specific context.
 ///   - The events are then sent to the output stream.
+///   - The next pages of the scrape, if any, are requested the same way.
 pub(crate) async fn http_scrape<
     B: HttpScraperBuilder<Context = C> + std::marker::Send + Clone,
-    C: HttpScraperContext + std::marker::Send,
+    C: HttpScraperContext + std::marker::Send + 'static,
 >(
     inputs: GenericHttpScrapeInputs,
     context_builder: B,
     mut out: SourceSender,
     http_method: HttpMethod,
 ) -> Result<(), ()> {
-    let mut stream = IntervalStream::new(tokio::time::interval(Duration::from_secs(
-        inputs.interval_secs,
-    )))
-    .take_until(inputs.shutdown)
-    .map(move |_| stream::iter(inputs.urls.clone()))
-    .flatten()
-    .map(move |url| {
-        // Building the HttpClient should not fail as it is just setting up the client with the
-        // proxy and tls settings.
-        let client = HttpClient::new(inputs.tls.clone(), &inputs.proxy)
-            .expect("Building HTTP client failed");
-        let endpoint = url.to_string();
-
-        let context_builder = context_builder.clone();
-        let mut context = context_builder.build(&url);
-
-        let mut builder = match http_method {
-            HttpMethod::Head => Request::head(&url),
-            HttpMethod::Get => Request::get(&url),
-            HttpMethod::Post => Request::post(&url),
-            HttpMethod::Put => Request::put(&url),
-            HttpMethod::Patch => Request::patch(&url),
-            HttpMethod::Delete => Request::delete(&url),
-        };
-
-        // add user specified headers
-        for (header, values) in &inputs.headers {
-            for value in values {
-                builder = builder.header(header, value);
-            }
-        }
-
-        // set ACCEPT header if not user specified
-        if !inputs.headers.contains_key(http::header::ACCEPT.as_str()) {
-            builder = builder.header(http::header::ACCEPT, &inputs.content_type);
-        }
+    // Building the HttpClient should not fail as it is just setting up the client with the
+    // proxy and tls settings.
+    let client =
+        HttpClient::new(inputs.tls.clone(), &inputs.proxy).expect("Building HTTP client failed");
+    let request = Arc::new(RequestInputs {
+        headers: inputs.headers,
+        content_type: inputs.content_type,
+        auth: inputs.auth,
+        method: http_method,
+    });
+    let urls = inputs.urls;
 
-        // building an empty request should be infallible
-        let mut request = builder.body(Body::empty()).expect("error creating request");
+    let mut stream =
+        IntervalStream::new(tokio::time::interval(Duration::from_secs(
+            inputs.interval_secs,
+        )))
+        .take_until(inputs.shutdown)
+        .take(if inputs.once { 1 } else { usize::MAX })
+        .map(move |_| stream::iter(urls.clone()))
+        .flatten()
+        .map(move |url| {
+            let context = context_builder.build(&url);
+            let client = client.clone();
+            let request = Arc::clone(&request);
 
-        if let Some(auth) = &inputs.auth {
-            auth.apply(&mut request);
-        }
-
-        let start = Instant::now();
-        client
-            .send(request)
-            .map_err(Error::from)
-            .and_then(|response| async move {
-                let (header, body) = response.into_parts();
-                let body = hyper::body::to_bytes(body).await?;
-                emit!(EndpointBytesReceived {
-                    byte_size: body.len(),
-                    protocol: "http",
-                    endpoint: endpoint.as_str(),
-                });
-                Ok((header, body))
-            })
-            .into_stream()
-            .filter_map(move |response| {
-                ready(match response {
-                    Ok((header, body)) if header.status == hyper::StatusCode::OK => {
-                        emit!(RequestCompleted {
-                            start,
-                            end: Instant::now()
-                        });
-                        context.on_response(&url, &header, &body).map(|events| {
-                            emit!(HttpScrapeEventsReceived {
-                                byte_size: events.size_of(),
-                                count: events.len(),
-                                url: url.to_string()
-                            });
-                            stream::iter(events)
-                        })
-                    }
-                    Ok((header, _)) => {
-                        context.on_http_response_error(&url, &header);
-                        emit!(HttpScrapeHttpResponseError {
-                            code: header.status,
-                            url: url.to_string(),
-                        });
-                        None
-                    }
-                    Err(error) => {
-                        emit!(HttpScrapeHttpError {
-                            error,
-                            url: url.to_string()
-                        });
-                        None
-                    }
-                })
+            // Each page is requested once the previous one has been received, until the context
+            // finds no next page.
+            stream::unfold(Some((url.clone(), context)), move |state| {
+                let client = client.clone();
+                let request = Arc::clone(&request);
+                let endpoint = url.clone();
+                async move {
+                    let (page_url, mut context) = state?;
+                    let (events, next_page) =
+                        match scrape_page(&client, &request, &endpoint, &page_url, &mut context)
+                            .await
+                        {
+                            Some(page) => page,
+                            None => return Some((stream::iter(Vec::new()), None)),
+                        };
+                    let next = match next_page {
+                        Some(next_page) => Some((next_page, context)),
+                        None => {
+                            context.on_scrape_complete();
+                            None
+                        }
+                    };
+                    Some((stream::iter(events), next))
+                }
             })
             .flatten()
-    })
-    .flatten()
-    .boxed();
+        })
+        .flatten()
+        .boxed();
 
     match out.send_event_stream(&mut stream).await {
         Ok(()) => {
@@ -217,3 +216,117 @@ pub(crate) async fn http_scrape<
         }
     }
 }
+
+/// The inputs of the HTTP requests, shared by all the pages of all the scrapes.
+struct RequestInputs {
+    headers: HashMap<String, Vec<String>>,
+    content_type: String,
+    auth: Option<RequestAuth>,
+    method: HttpMethod,
+}
+
+/// Requests a page of a scrape, returning its events and the URL of the next page, if any.
+///
+/// Returns `None` if the page couldn't be received, which ends the scrape.
+async fn scrape_page<C: HttpScraperContext>(
+    client: &HttpClient,
+    inputs: &RequestInputs,
+    endpoint: &Uri,
+    url: &Uri,
+    context: &mut C,
+) -> Option<(Vec<Event>, Option<Uri>)> {
+    let mut builder = match inputs.method {
+        HttpMethod::Head => Request::head(&url),
+        HttpMethod::Get => Request::get(&url),
+        HttpMethod::Post => Request::post(&url),
+        HttpMethod::Put => Request::put(&url),
+        HttpMethod::Patch => Request::patch(&url),
+        HttpMethod::Delete => Request::delete(&url),
+    };
+
+    // add user specified headers
+    for (header, values) in &inputs.headers {
+        for value in values {
+            builder = builder.header(header, value);
+        }
+    }
+
+    // set ACCEPT header if not user specified
+    if !inputs.headers.contains_key(http::header::ACCEPT.as_str()) {
+        builder = builder.header(http::header::ACCEPT, &inputs.content_type);
+    }
+
+    // building an empty request should be infallible
+    let request = builder.body(Bytes::new()).expect("error creating request");
+    let mut request = context.build_request(request)?;
+    let url = request.uri().clone();
+
+    // the request is authenticated last, as signatures cover the rest of the request
+    if let Some(auth) = &inputs.auth {
+        if let Err(error) = auth.apply(client, &mut request).await {
+            emit!(HttpScrapeHttpError {
+                error,
+                url: endpoint.to_string()
+            });
+            return None;
+        }
+    }
+
+    let start = Instant::now();
+    let response = client
+        .send(request.map(Body::from))
+        .map_err(Error::from)
+        .and_then(|response| async move {
+            let (header, body) = response.into_parts();
+            let body = hyper::body::to_bytes(body).await?;
+            emit!(EndpointBytesReceived {
+                byte_size: body.len(),
+                protocol: "http",
+                endpoint: endpoint.to_string().as_str(),
+            });
+            Ok((header, body))
+        })
+        .await;
+
+    match response {
+        Ok((header, body)) if header.status == hyper::StatusCode::OK => {
+            emit!(RequestCompleted {
+                start,
+                end: Instant::now()
+            });
+            let events = context
+                .on_response(&url, &header, &body)
+                .map(|events| {
+                    emit!(HttpScrapeEventsReceived {
+                        byte_size: events.size_of(),
+                        count: events.len(),
+                        url: endpoint.to_string()
+                    });
+                    events
+                })
+                .unwrap_or_default();
+            let next_page = context.next_page(&url, &header, &body);
+            Some((events, next_page))
+        }
+        Ok((header, _)) => {
+            if header.status == hyper::StatusCode::UNAUTHORIZED {
+                if let Some(auth) = &inputs.auth {
+                    auth.on_unauthorized().await;
+                }
+            }
+            context.on_http_response_error(&url, &header);
+            emit!(HttpScrapeHttpResponseError {
+                code: header.status,
+                url: endpoint.to_string(),
+            });
+            None
+        }
+        Err(error) => {
+            emit!(HttpScrapeHttpError {
+                error,
+                url: endpoint.to_string()
+            });
+            None
+        }
+    }
+}
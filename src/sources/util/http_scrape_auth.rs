@@ -0,0 +1,336 @@
+//! Authentication of the requests of HTTP scrapers.
+//!
+//! Besides the static strategies of [`Auth`], the requests can be authenticated with OAuth2
+//! access tokens, which are fetched and refreshed as needed, or signed with AWS SigV4.
+
+use std::{sync::Arc, time::Duration};
+
+use bytes::Bytes;
+use http::{header, Request, StatusCode, Uri};
+use hyper::Body;
+use serde::Deserialize;
+use tokio::{sync::Mutex, time::Instant};
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+
+#[cfg(feature = "aws-core")]
+use crate::aws::AwsAuthentication;
+use crate::http::{Auth, HttpClient};
+
+/// How long before their expiration access tokens are refreshed.
+const TOKEN_EXPIRATION_MARGIN: Duration = Duration::from_secs(60);
+
+/// How long access tokens are used when the token endpoint doesn't say when they expire.
+const DEFAULT_TOKEN_LIFETIME: Duration = Duration::from_secs(3600);
+
+/// Authentication strategies.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields, rename_all = "snake_case", tag = "strategy")]
+pub enum HttpScrapeAuth {
+    /// Basic authentication.
+    ///
+    /// The username and password are concatenated and encoded via base64.
+    Basic {
+        /// The username to send.
+        user: String,
+
+        /// The password to send.
+        password: SensitiveString,
+    },
+
+    /// Bearer authentication.
+    ///
+    /// A bearer token (OAuth2, JWT, etc) is passed as-is.
+    Bearer {
+        /// The bearer token to send.
+        token: SensitiveString,
+    },
+
+    /// OAuth2 authentication.
+    ///
+    /// Access tokens are fetched from the token endpoint with the client credentials grant, or
+    /// with the refresh token grant if a refresh token is set, and are refreshed before they
+    /// expire.
+    Oauth2 {
+        /// The URL of the token endpoint.
+        token_endpoint: String,
+
+        /// The client ID.
+        client_id: String,
+
+        /// The client secret.
+        client_secret: Option<SensitiveString>,
+
+        /// The refresh token.
+        ///
+        /// If the token endpoint returns a new refresh token, it is used for the next refreshes.
+        refresh_token: Option<SensitiveString>,
+
+        /// The scopes to request.
+        #[serde(default)]
+        scopes: Vec<String>,
+    },
+
+    /// AWS authentication.
+    ///
+    /// The requests are signed with AWS Signature Version 4.
+    #[cfg(feature = "aws-core")]
+    Aws {
+        /// The AWS region of the API.
+        region: String,
+
+        /// The name of the AWS service of the API, such as `execute-api`.
+        service: String,
+
+        #[configurable(derived)]
+        #[serde(default)]
+        auth: AwsAuthentication,
+    },
+}
+
+/// The authentication of the requests of a scraper, built from its configuration.
+#[derive(Clone)]
+pub(crate) enum RequestAuth {
+    Static(Auth),
+    OAuth2(Arc<OAuth2>),
+    #[cfg(feature = "aws-core")]
+    Aws {
+        credentials_provider: aws_types::credentials::SharedCredentialsProvider,
+        region: aws_types::region::Region,
+        service: String,
+    },
+}
+
+impl From<Auth> for RequestAuth {
+    fn from(auth: Auth) -> Self {
+        Self::Static(auth)
+    }
+}
+
+impl RequestAuth {
+    pub(crate) async fn build(config: &HttpScrapeAuth) -> crate::Result<Self> {
+        Ok(match config {
+            HttpScrapeAuth::Basic { user, password } => Self::Static(Auth::Basic {
+                user: user.clone(),
+                password: password.clone(),
+            }),
+            HttpScrapeAuth::Bearer { token } => Self::Static(Auth::Bearer {
+                token: token.clone(),
+            }),
+            HttpScrapeAuth::Oauth2 {
+                token_endpoint,
+                client_id,
+                client_secret,
+                refresh_token,
+                scopes,
+            } => Self::OAuth2(Arc::new(OAuth2 {
+                token_endpoint: token_endpoint.parse()?,
+                client_id: client_id.clone(),
+                client_secret: client_secret.clone(),
+                scopes: scopes.join(" "),
+                state: Mutex::new(OAuth2State {
+                    refresh_token: refresh_token.clone(),
+                    token: None,
+                }),
+            })),
+            #[cfg(feature = "aws-core")]
+            HttpScrapeAuth::Aws {
+                region,
+                service,
+                auth,
+            } => {
+                let region = aws_types::region::Region::new(region.clone());
+                Self::Aws {
+                    credentials_provider: auth.credentials_provider(region.clone()).await?,
+                    region,
+                    service: service.clone(),
+                }
+            }
+        })
+    }
+
+    /// Authenticates a request, fetching an access token or credentials first if needed.
+    pub(crate) async fn apply(
+        &self,
+        client: &HttpClient,
+        request: &mut Request<Bytes>,
+    ) -> crate::Result<()> {
+        match self {
+            Self::Static(auth) => auth.apply(request),
+            Self::OAuth2(oauth2) => {
+                let token = oauth2.access_token(client).await?;
+                request
+                    .headers_mut()
+                    .insert(header::AUTHORIZATION, format!("Bearer {}", token).parse()?);
+            }
+            #[cfg(feature = "aws-core")]
+            Self::Aws {
+                credentials_provider,
+                region,
+                service,
+            } => {
+                crate::aws::sign_request(
+                    service,
+                    request,
+                    credentials_provider,
+                    &Some(region.clone()),
+                )
+                .await?
+            }
+        }
+        Ok(())
+    }
+
+    /// Called when a request is rejected as unauthorized, so that the next one gets a new
+    /// access token.
+    pub(crate) async fn on_unauthorized(&self) {
+        if let Self::OAuth2(oauth2) = self {
+            oauth2.state.lock().await.token = None;
+        }
+    }
+}
+
+/// An OAuth2 client, caching its access token until it expires.
+pub(crate) struct OAuth2 {
+    token_endpoint: Uri,
+    client_id: String,
+    client_secret: Option<SensitiveString>,
+    scopes: String,
+    state: Mutex<OAuth2State>,
+}
+
+struct OAuth2State {
+    refresh_token: Option<SensitiveString>,
+    token: Option<(String, Instant)>,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: Option<u64>,
+    refresh_token: Option<String>,
+}
+
+impl OAuth2 {
+    /// Returns the cached access token, or fetches a new one if it is about to expire.
+    async fn access_token(&self, client: &HttpClient) -> crate::Result<String> {
+        // The lock is held while fetching, so that concurrent requests share a single new token.
+        let mut state = self.state.lock().await;
+        if let Some((token, expires_at)) = &state.token {
+            if Instant::now() < *expires_at {
+                return Ok(token.clone());
+            }
+        }
+
+        let mut form = url::form_urlencoded::Serializer::new(String::new());
+        match &state.refresh_token {
+            Some(refresh_token) => form
+                .append_pair("grant_type", "refresh_token")
+                .append_pair("refresh_token", refresh_token.inner()),
+            None => form.append_pair("grant_type", "client_credentials"),
+        };
+        form.append_pair("client_id", &self.client_id);
+        if let Some(client_secret) = &self.client_secret {
+            form.append_pair("client_secret", client_secret.inner());
+        }
+        if !self.scopes.is_empty() {
+            form.append_pair("scope", &self.scopes);
+        }
+
+        let request = Request::post(&self.token_endpoint)
+            .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .header(header::ACCEPT, "application/json")
+            .body(Body::from(form.finish()))?;
+        let response = client.send(request).await?;
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body()).await?;
+        if status != StatusCode::OK {
+            return Err(format!(
+                "OAuth2 token endpoint responded with {}: {}",
+                status,
+                String::from_utf8_lossy(&body)
+            )
+            .into());
+        }
+
+        let response: TokenResponse = serde_json::from_slice(&body)?;
+        let lifetime = response
+            .expires_in
+            .map_or(DEFAULT_TOKEN_LIFETIME, Duration::from_secs);
+        let expires_at = Instant::now() + lifetime.saturating_sub(TOKEN_EXPIRATION_MARGIN);
+        if let Some(refresh_token) = response.refresh_token {
+            state.refresh_token = Some(refresh_token.into());
+        }
+        state.token = Some((response.access_token.clone(), expires_at));
+
+        Ok(response.access_token)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use warp::Filter;
+
+    use super::*;
+    use crate::test_util::{next_addr, wait_for_tcp};
+
+    #[tokio::test]
+    async fn caches_oauth2_access_tokens() {
+        let addr = next_addr();
+        let fetches = Arc::new(AtomicUsize::new(0));
+        let token_endpoint = {
+            let fetches = Arc::clone(&fetches);
+            warp::path!("token").and(warp::body::form()).map(
+                move |form: std::collections::HashMap<String, String>| {
+                    assert_eq!(form["grant_type"], "refresh_token");
+                    assert_eq!(form["client_id"], "vector");
+                    let fetch = fetches.fetch_add(1, Ordering::SeqCst);
+                    // Each token rotates the refresh token.
+                    assert_eq!(form["refresh_token"], format!("refresh-{}", fetch));
+                    warp::reply::json(&serde_json::json!({
+                        "access_token": format!("token-{}", fetch),
+                        "expires_in": 3600,
+                        "refresh_token": format!("refresh-{}", fetch + 1),
+                    }))
+                },
+            )
+        };
+        tokio::spawn(warp::serve(token_endpoint).run(addr));
+        wait_for_tcp(addr).await;
+
+        let config: HttpScrapeAuth = toml::from_str(&format!(
+            r#"
+            strategy = "oauth2"
+            token_endpoint = "http://{}/token"
+            client_id = "vector"
+            refresh_token = "refresh-0"
+            "#,
+            addr
+        ))
+        .unwrap();
+        let auth = RequestAuth::build(&config).await.unwrap();
+        let client = HttpClient::new(None, &Default::default()).unwrap();
+
+        let authorization = |request: &Request<Bytes>| {
+            request.headers()[header::AUTHORIZATION]
+                .to_str()
+                .unwrap()
+                .to_string()
+        };
+        let mut request = Request::get("http://example.com")
+            .body(Bytes::new())
+            .unwrap();
+        auth.apply(&client, &mut request).await.unwrap();
+        assert_eq!(authorization(&request), "Bearer token-0");
+
+        // The token is cached until the request is rejected.
+        auth.apply(&client, &mut request).await.unwrap();
+        assert_eq!(authorization(&request), "Bearer token-0");
+        auth.on_unauthorized().await;
+        auth.apply(&client, &mut request).await.unwrap();
+        assert_eq!(authorization(&request), "Bearer token-1");
+    }
+}
@@ -1,5 +1,13 @@
+#[cfg(feature = "sources-utils-audit-log")]
+pub mod audit_log;
 #[cfg(any(feature = "sources-http"))]
 mod body_decoding;
+#[cfg(any(
+    feature = "sources-aws_cloudwatch_logs",
+    feature = "sources-aws_kinesis_firehose",
+    feature = "sources-aws_kinesis_streams"
+))]
+pub mod cloudwatch_logs_subscription;
 mod encoding_config;
 #[cfg(all(unix, feature = "sources-dnstap"))]
 pub mod framestream;
@@ -15,14 +23,27 @@ pub mod grpc;
 pub mod http;
 #[cfg(any(feature = "sources-http_scrape", feature = "sources-prometheus"))]
 pub mod http_scrape;
-#[cfg(any(feature = "sources-aws_sqs", feature = "sources-gcp_pubsub"))]
+#[cfg(any(
+    feature = "sources-http_scrape",
+    feature = "sources-prometheus",
+    feature = "sources-utils-audit-log"
+))]
+pub mod http_scrape_auth;
+#[cfg(any(
+    feature = "sources-aws_cloudwatch_logs",
+    feature = "sources-aws_kinesis_streams",
+    feature = "sources-aws_sqs",
+    feature = "sources-gcp_pubsub"
+))]
 mod message_decoding;
 pub mod multiline_config;
 #[cfg(feature = "listenfd")]
+mod net;
+#[cfg(feature = "listenfd")]
 mod tcp;
 #[cfg(all(unix, any(feature = "sources-socket", feature = "sources-utils-unix",)))]
 mod unix;
-#[cfg(all(unix, feature = "sources-socket"))]
+#[cfg(all(unix, any(feature = "sources-socket", feature = "sources-statsd")))]
 mod unix_datagram;
 #[cfg(all(unix, feature = "sources-utils-unix"))]
 mod unix_stream;
@@ -32,10 +53,12 @@ mod wrappers;
 pub use encoding_config::EncodingConfig;
 pub use multiline_config::MultilineConfig;
 #[cfg(feature = "listenfd")]
-pub use tcp::{SocketListenAddr, TcpNullAcker, TcpSource, TcpSourceAck, TcpSourceAcker};
+pub use net::SocketListenAddr;
+#[cfg(feature = "listenfd")]
+pub use tcp::{TcpNullAcker, TcpSource, TcpSourceAck, TcpSourceAcker};
 #[cfg(all(unix, any(feature = "sources-socket", feature = "sources-utils-unix",)))]
 pub use unix::change_socket_permissions;
-#[cfg(all(unix, feature = "sources-socket",))]
+#[cfg(all(unix, any(feature = "sources-socket", feature = "sources-statsd")))]
 pub use unix_datagram::build_unix_datagram_source;
 #[cfg(all(unix, feature = "sources-utils-unix",))]
 pub use unix_stream::build_unix_stream_source;
@@ -56,5 +79,10 @@ pub use self::http::ErrorMessage;
 pub use self::http::HttpSource;
 #[cfg(feature = "sources-utils-http-auth")]
 pub use self::http::HttpSourceAuthConfig;
-#[cfg(any(feature = "sources-aws_sqs", feature = "sources-gcp_pubsub"))]
+#[cfg(any(
+    feature = "sources-aws_cloudwatch_logs",
+    feature = "sources-aws_kinesis_streams",
+    feature = "sources-aws_sqs",
+    feature = "sources-gcp_pubsub"
+))]
 pub use self::message_decoding::decode_message;
@@ -0,0 +1,241 @@
+use std::{fmt, net::SocketAddr};
+
+use listenfd::ListenFd;
+use serde::{de, Deserialize, Deserializer};
+use tokio::net::{TcpListener, UdpSocket};
+use vector_config::configurable_component;
+
+#[cfg(unix)]
+use crate::upgrade;
+use crate::{
+    config::Resource,
+    tls::{MaybeTlsListener, MaybeTlsSettings},
+};
+
+/// A listening address that can be given directly, be managed via `systemd` socket activation, or
+/// be a socket inherited from the process that started Vector.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum SocketListenAddr {
+    /// An IPv4/IPv6 address and port.
+    SocketAddr(#[configurable(derived)] SocketAddr),
+
+    /// A file descriptor identifier that is given from, and managed by, the socket activation feature of `systemd`.
+    #[serde(deserialize_with = "parse_systemd_fd")]
+    SystemdFd(#[configurable(transparent)] usize),
+
+    /// The file descriptor of a socket that is already bound, inherited from the process that started Vector.
+    ///
+    /// The socket is left open when the source stops, so that it can be reused on reload, or by another process.
+    #[serde(deserialize_with = "parse_inherited_fd")]
+    InheritedFd(#[configurable(transparent)] u32),
+}
+
+impl SocketListenAddr {
+    /// Returns a listener for TCP connections on this address.
+    pub async fn tcp_listener(self, tls: &MaybeTlsSettings) -> crate::Result<MaybeTlsListener> {
+        let listener = match self {
+            Self::SocketAddr(addr) => return Ok(tls.listen(bind_tcp(addr).await?)?),
+            Self::SystemdFd(offset) => ListenFd::from_env()
+                .take_tcp_listener(offset)?
+                .ok_or("Listen FD not open or already taken")?,
+            Self::InheritedFd(fd) => inherited_socket(fd, socket2::Type::STREAM)?.into(),
+        };
+        listener.set_nonblocking(true)?;
+        Ok(tls.listen(TcpListener::from_std(listener)?)?)
+    }
+
+    /// Returns a socket for UDP datagrams on this address.
+    pub async fn udp_socket(self) -> crate::Result<UdpSocket> {
+        let socket = match self {
+            Self::SocketAddr(addr) => return bind_udp(addr).await,
+            Self::SystemdFd(offset) => ListenFd::from_env()
+                .take_udp_socket(offset)?
+                .ok_or("Listen FD not open or already taken")?,
+            Self::InheritedFd(fd) => inherited_socket(fd, socket2::Type::DGRAM)?.into(),
+        };
+        socket.set_nonblocking(true)?;
+        Ok(UdpSocket::from_std(socket)?)
+    }
+
+    /// Returns the resource of this address when listening for UDP datagrams.
+    pub const fn udp_resource(self) -> Resource {
+        match self {
+            Self::SocketAddr(addr) => Resource::udp(addr),
+            Self::SystemdFd(offset) => Resource::SystemFdOffset(offset),
+            Self::InheritedFd(fd) => Resource::Fd(fd),
+        }
+    }
+}
+
+/// Binds a TCP listener, or takes the one handed over by the Vector this one upgraded.
+async fn bind_tcp(addr: SocketAddr) -> crate::Result<TcpListener> {
+    #[cfg(unix)]
+    {
+        let resource = Resource::tcp(addr);
+        let listener = match upgrade::take_inherited(&resource) {
+            Some(fd) => {
+                let listener = std::net::TcpListener::from(fd);
+                listener.set_nonblocking(true)?;
+                TcpListener::from_std(listener)?
+            }
+            None => TcpListener::bind(addr).await?,
+        };
+        upgrade::register(resource, &listener);
+        Ok(listener)
+    }
+    #[cfg(not(unix))]
+    Ok(TcpListener::bind(addr).await?)
+}
+
+/// Binds a UDP socket, or takes the one handed over by the Vector this one upgraded.
+async fn bind_udp(addr: SocketAddr) -> crate::Result<UdpSocket> {
+    #[cfg(unix)]
+    {
+        let resource = Resource::udp(addr);
+        let socket = match upgrade::take_inherited(&resource) {
+            Some(fd) => {
+                let socket = std::net::UdpSocket::from(fd);
+                socket.set_nonblocking(true)?;
+                UdpSocket::from_std(socket)?
+            }
+            None => UdpSocket::bind(addr).await?,
+        };
+        upgrade::register(resource, &socket);
+        Ok(socket)
+    }
+    #[cfg(not(unix))]
+    Ok(UdpSocket::bind(addr).await?)
+}
+
+/// Duplicates an inherited socket, after checking that it is of the expected type.
+#[cfg(unix)]
+fn inherited_socket(fd: u32, expected: socket2::Type) -> crate::Result<socket2::Socket> {
+    use std::os::unix::io::{BorrowedFd, RawFd};
+
+    let fd = RawFd::try_from(fd)?;
+    // SAFETY: The file descriptor is never closed by Vector, and only used to duplicate it, which
+    // fails when it isn't open.
+    let borrowed = unsafe { BorrowedFd::borrow_raw(fd) };
+    let socket = socket2::SockRef::from(&borrowed);
+    if socket.r#type()? != expected {
+        return Err(format!(
+            "File descriptor {} is not a socket of the expected type",
+            fd
+        )
+        .into());
+    }
+    Ok(socket.try_clone()?)
+}
+
+#[cfg(not(unix))]
+fn inherited_socket(_fd: u32, _expected: socket2::Type) -> crate::Result<socket2::Socket> {
+    Err("Inherited sockets are only supported on Unix".into())
+}
+
+impl fmt::Display for SocketListenAddr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::SocketAddr(ref addr) => addr.fmt(f),
+            Self::SystemdFd(offset) => write!(f, "systemd socket #{}", offset),
+            Self::InheritedFd(fd) => write!(f, "inherited socket fd {}", fd),
+        }
+    }
+}
+
+impl From<SocketAddr> for SocketListenAddr {
+    fn from(addr: SocketAddr) -> Self {
+        Self::SocketAddr(addr)
+    }
+}
+
+impl From<SocketListenAddr> for Resource {
+    fn from(addr: SocketListenAddr) -> Resource {
+        match addr {
+            SocketListenAddr::SocketAddr(addr) => Resource::tcp(addr),
+            SocketListenAddr::SystemdFd(offset) => Self::SystemFdOffset(offset),
+            SocketListenAddr::InheritedFd(fd) => Self::Fd(fd),
+        }
+    }
+}
+
+fn parse_systemd_fd<'de, D>(des: D) -> Result<usize, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s: &'de str = Deserialize::deserialize(des)?;
+    match s {
+        "systemd" => Ok(0),
+        s if s.starts_with("systemd#") => s[8..]
+            .parse::<usize>()
+            .map_err(de::Error::custom)?
+            .checked_sub(1)
+            .ok_or_else(|| de::Error::custom("systemd indices start from 1, found 0")),
+        _ => Err(de::Error::custom("must start with \"systemd\"")),
+    }
+}
+
+fn parse_inherited_fd<'de, D>(des: D) -> Result<u32, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s: &'de str = Deserialize::deserialize(des)?;
+    match s.strip_prefix("fd#") {
+        Some(fd) => fd.parse::<u32>().map_err(de::Error::custom),
+        None => Err(de::Error::custom("must start with \"fd#\"")),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    struct Config {
+        addr: SocketListenAddr,
+    }
+
+    #[test]
+    fn parse_socket_listen_addr() {
+        let test: Config = toml::from_str(r#"addr="127.1.2.3:1234""#).unwrap();
+        assert_eq!(
+            test.addr,
+            SocketListenAddr::SocketAddr(SocketAddr::V4(SocketAddrV4::new(
+                Ipv4Addr::new(127, 1, 2, 3),
+                1234,
+            )))
+        );
+        let test: Config = toml::from_str(r#"addr="systemd""#).unwrap();
+        assert_eq!(test.addr, SocketListenAddr::SystemdFd(0));
+        let test: Config = toml::from_str(r#"addr="systemd#3""#).unwrap();
+        assert_eq!(test.addr, SocketListenAddr::SystemdFd(2));
+        let test: Config = toml::from_str(r#"addr="fd#3""#).unwrap();
+        assert_eq!(test.addr, SocketListenAddr::InheritedFd(3));
+        assert!(toml::from_str::<Config>(r#"addr="fd#x""#).is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn listens_on_inherited_sockets() {
+        use std::os::unix::io::AsRawFd;
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let addr = SocketListenAddr::InheritedFd(listener.as_raw_fd() as u32);
+
+        let inherited = addr.tcp_listener(&MaybeTlsSettings::Raw(())).await.unwrap();
+        assert_eq!(inherited.local_addr().unwrap(), address);
+
+        // The socket is of the wrong type for UDP.
+        assert!(addr.udp_socket().await.is_err());
+
+        // The inherited socket is left open.
+        drop(inherited);
+        assert_eq!(listener.local_addr().unwrap(), address);
+    }
+}
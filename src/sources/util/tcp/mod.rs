@@ -2,19 +2,17 @@ mod request_limiter;
 
 use std::collections::BTreeMap;
 use std::net::SocketAddr;
-use std::{fmt, io, mem::drop, time::Duration};
+use std::{io, mem::drop, time::Duration};
 
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 use codecs::StreamDecodingError;
 use futures::{future::BoxFuture, FutureExt, StreamExt};
-use listenfd::ListenFd;
-use serde::{de, Deserialize, Deserializer};
 use smallvec::SmallVec;
 use socket2::SockRef;
 use tokio::{
-    io::AsyncWriteExt,
-    net::{TcpListener, TcpStream},
-    time::sleep,
+    io::{AsyncWrite, AsyncWriteExt},
+    net::TcpStream,
+    time::{sleep, Instant},
 };
 use tokio_util::codec::{Decoder, FramedRead};
 use tracing::Instrument;
@@ -22,12 +20,12 @@ use vector_common::finalization::AddBatchNotifier;
 use vector_config::configurable_component;
 use vector_core::ByteSizeOf;
 
-use super::AfterReadExt as _;
+use super::{AfterReadExt as _, SocketListenAddr};
 use crate::sources::util::tcp::request_limiter::RequestLimiter;
 use crate::{
     codecs::ReadyFrames,
-    config::{AcknowledgementsConfig, Resource, SourceContext},
-    event::{BatchNotifier, BatchStatus, Event},
+    config::{AcknowledgementsConfig, SourceContext},
+    event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event},
     internal_events::{
         ConnectionOpen, DecoderFramingError, OpenGauge, SocketEventsReceived, SocketMode,
         StreamClosedError, TcpBytesReceived, TcpSendAckError, TcpSocketReceiveError,
@@ -35,44 +33,14 @@ use crate::{
     },
     shutdown::ShutdownSignal,
     tcp::TcpKeepaliveConfig,
-    tls::{CertificateMetadata, MaybeTlsIncomingStream, MaybeTlsListener, MaybeTlsSettings},
+    tls::{CertificateMetadata, MaybeTlsIncomingStream, MaybeTlsSettings},
     SourceSender,
 };
 
 const MAX_IN_FLIGHT_EVENTS_TARGET: usize = 100_000;
 
-async fn make_listener(
-    addr: SocketListenAddr,
-    mut listenfd: ListenFd,
-    tls: &MaybeTlsSettings,
-) -> Option<MaybeTlsListener> {
-    match addr {
-        SocketListenAddr::SocketAddr(addr) => match tls.bind(&addr).await {
-            Ok(listener) => Some(listener),
-            Err(error) => {
-                error!(message = "Failed to bind to listener socket.", %error);
-                None
-            }
-        },
-        SocketListenAddr::SystemdFd(offset) => match listenfd.take_tcp_listener(offset) {
-            Ok(Some(listener)) => match TcpListener::from_std(listener) {
-                Ok(listener) => Some(listener.into()),
-                Err(error) => {
-                    error!(message = "Failed to bind to listener socket.", %error);
-                    None
-                }
-            },
-            Ok(None) => {
-                error!("Failed to take listen FD, not open or already taken.");
-                None
-            }
-            Err(error) => {
-                error!(message = "Failed to take listen FD.", %error);
-                None
-            }
-        },
-    }
-}
+/// How often the keepalives of the acker are sent while waiting for the events to be acknowledged.
+const ACK_KEEPALIVE_INTERVAL: Duration = Duration::from_secs(5);
 
 #[derive(Clone, Copy, Eq, PartialEq)]
 pub enum TcpSourceAck {
@@ -83,6 +51,12 @@ pub enum TcpSourceAck {
 
 pub trait TcpSourceAcker {
     fn build_ack(self, ack: TcpSourceAck) -> Option<Bytes>;
+
+    /// Builds the message sent periodically to the client while the events are waiting to be
+    /// acknowledged, for the protocols whose clients would otherwise time out and send them again.
+    fn build_keepalive(&self) -> Option<Bytes> {
+        None
+    }
 }
 
 pub struct TcpNullAcker;
@@ -117,6 +91,18 @@ where
 
     fn build_acker(&self, item: &[Self::Item]) -> Self::Acker;
 
+    /// Performs the handshake of the protocol of the source on a new connection, before any frames
+    /// are read from it.
+    ///
+    /// Returns the bytes read past the end of the handshake, which are decoded as the start of the
+    /// frames, or an error, which closes the connection. The source emits its own errors.
+    fn handshake<'a>(
+        &'a self,
+        _socket: &'a mut MaybeTlsIncomingStream<TcpStream>,
+    ) -> BoxFuture<'a, Result<BytesMut, ()>> {
+        futures::future::ready(Ok(BytesMut::new())).boxed()
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn run(
         self,
@@ -132,12 +118,13 @@ where
     ) -> crate::Result<crate::sources::Source> {
         let acknowledgements = cx.do_acknowledgements(&acknowledgements);
 
-        let listenfd = ListenFd::from_env();
-
         Ok(Box::pin(async move {
-            let listener = match make_listener(addr, listenfd, &tls).await {
-                None => return Err(()),
-                Some(listener) => listener,
+            let listener = match addr.tcp_listener(&tls).await {
+                Ok(listener) => listener,
+                Err(error) => {
+                    error!(message = "Failed to bind to listener socket.", %error);
+                    return Err(());
+                }
             };
 
             info!(
@@ -271,6 +258,16 @@ async fn handle_stream<T>(
         }
     }
 
+    let leftover = tokio::select! {
+        result = source.handshake(&mut socket) => match result {
+            Ok(leftover) => leftover,
+            Err(()) => return,
+        },
+        _ = &mut shutdown_signal => {
+            return;
+        }
+    };
+
     let socket = socket.after_read(move |byte_size| {
         emit!(TcpBytesReceived {
             byte_size,
@@ -284,7 +281,8 @@ async fn handle_stream<T>(
         .and_then(|stream| stream.ssl().peer_certificate())
         .map(CertificateMetadata::from);
 
-    let reader = FramedRead::new(socket, source.decoder());
+    let mut reader = FramedRead::new(socket, source.decoder());
+    reader.read_buffer_mut().extend_from_slice(&leftover);
     let mut reader = ReadyFrames::new(reader);
 
     loop {
@@ -351,6 +349,11 @@ async fn handle_stream<T>(
                             if let Some(certificate_metadata) = &certificate_metadata {
                                 let mut metadata: BTreeMap<String, value::Value> = BTreeMap::new();
                                 metadata.insert("subject".to_string(), certificate_metadata.subject().into());
+                                if let Some(common_name) = &certificate_metadata.common_name {
+                                    metadata.insert("common_name".to_string(), common_name.as_str().into());
+                                }
+                                let subject_alt_names = certificate_metadata.subject_alt_names.iter().map(|name| name.as_str().into()).collect::<Vec<value::Value>>();
+                                metadata.insert("subject_alt_names".to_string(), subject_alt_names.into());
                                 for event in &mut events {
                                     let log = event.as_mut_log();
                                     log.insert(&tls_client_metadata_key[..], value::Value::from(metadata.clone()));
@@ -363,15 +366,21 @@ async fn handle_stream<T>(
                             Ok(_) => {
                                 let ack = match receiver {
                                     None => TcpSourceAck::Ack,
-                                    Some(receiver) =>
-                                        match receiver.await {
-                                            BatchStatus::Delivered => TcpSourceAck::Ack,
-                                            BatchStatus::Errored => {TcpSourceAck::Error},
-                                            BatchStatus::Rejected => {
+                                    Some(receiver) => {
+                                        let stream = reader.get_mut().get_mut();
+                                        match wait_for_ack(receiver, acker.build_keepalive(), stream).await {
+                                            Ok(BatchStatus::Delivered) => TcpSourceAck::Ack,
+                                            Ok(BatchStatus::Errored) => {TcpSourceAck::Error},
+                                            Ok(BatchStatus::Rejected) => {
                                                 // Sinks are responsible for emitting ComponentEventsDropped.
                                                 TcpSourceAck::Reject
                                             }
+                                            Err(error) => {
+                                                emit!(TcpSendAckError{ error });
+                                                break;
+                                            }
                                         }
+                                    }
                                 };
                                 if let Some(ack_bytes) = acker.build_ack(ack){
                                     let stream = reader.get_mut().get_mut();
@@ -409,6 +418,31 @@ async fn handle_stream<T>(
     }
 }
 
+/// Waits for the events to be acknowledged, sending the keepalive to the client in the meantime,
+/// if any.
+async fn wait_for_ack<S>(
+    mut receiver: BatchStatusReceiver,
+    keepalive: Option<Bytes>,
+    stream: &mut S,
+) -> io::Result<BatchStatus>
+where
+    S: AsyncWrite + Unpin,
+{
+    let keepalive = match keepalive {
+        Some(keepalive) => keepalive,
+        None => return Ok(receiver.await),
+    };
+
+    let start = Instant::now() + ACK_KEEPALIVE_INTERVAL;
+    let mut interval = tokio::time::interval_at(start, ACK_KEEPALIVE_INTERVAL);
+    loop {
+        tokio::select! {
+            status = &mut receiver => return Ok(status),
+            _ = interval.tick() => stream.write_all(&keepalive).await?,
+        }
+    }
+}
+
 fn close_socket(socket: &MaybeTlsIncomingStream<TcpStream>) -> bool {
     debug!("Start graceful shutdown.");
     // Close our write part of TCP socket to signal the other side
@@ -425,86 +459,3 @@ fn close_socket(socket: &MaybeTlsIncomingStream<TcpStream>) -> bool {
         true
     }
 }
-
-/// A listening address that can be given directly or be managed via `systemd` socket activation.
-#[configurable_component]
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-#[serde(untagged)]
-pub enum SocketListenAddr {
-    /// An IPv4/IPv6 address and port.
-    SocketAddr(#[configurable(derived)] SocketAddr),
-
-    /// A file descriptor identifier that is given from, and managed by, the socket activation feature of `systemd`.
-    #[serde(deserialize_with = "parse_systemd_fd")]
-    SystemdFd(#[configurable(transparent)] usize),
-}
-
-impl fmt::Display for SocketListenAddr {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            Self::SocketAddr(ref addr) => addr.fmt(f),
-            Self::SystemdFd(offset) => write!(f, "systemd socket #{}", offset),
-        }
-    }
-}
-
-impl From<SocketAddr> for SocketListenAddr {
-    fn from(addr: SocketAddr) -> Self {
-        Self::SocketAddr(addr)
-    }
-}
-
-impl From<SocketListenAddr> for Resource {
-    fn from(addr: SocketListenAddr) -> Resource {
-        match addr {
-            SocketListenAddr::SocketAddr(addr) => Resource::tcp(addr),
-            SocketListenAddr::SystemdFd(offset) => Self::SystemFdOffset(offset),
-        }
-    }
-}
-
-fn parse_systemd_fd<'de, D>(des: D) -> Result<usize, D::Error>
-where
-    D: Deserializer<'de>,
-{
-    let s: &'de str = Deserialize::deserialize(des)?;
-    match s {
-        "systemd" => Ok(0),
-        s if s.starts_with("systemd#") => s[8..]
-            .parse::<usize>()
-            .map_err(de::Error::custom)?
-            .checked_sub(1)
-            .ok_or_else(|| de::Error::custom("systemd indices start from 1, found 0")),
-        _ => Err(de::Error::custom("must start with \"systemd\"")),
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
-
-    use serde::Deserialize;
-
-    use super::*;
-
-    #[derive(Debug, Deserialize)]
-    struct Config {
-        addr: SocketListenAddr,
-    }
-
-    #[test]
-    fn parse_socket_listen_addr() {
-        let test: Config = toml::from_str(r#"addr="127.1.2.3:1234""#).unwrap();
-        assert_eq!(
-            test.addr,
-            SocketListenAddr::SocketAddr(SocketAddr::V4(SocketAddrV4::new(
-                Ipv4Addr::new(127, 1, 2, 3),
-                1234,
-            )))
-        );
-        let test: Config = toml::from_str(r#"addr="systemd""#).unwrap();
-        assert_eq!(test.addr, SocketListenAddr::SystemdFd(0));
-        let test: Config = toml::from_str(r#"addr="systemd#3""#).unwrap();
-        assert_eq!(test.addr, SocketListenAddr::SystemdFd(2));
-    }
-}
@@ -0,0 +1,255 @@
+//! The plain HTTP endpoint of the `vector` source, which accepts the events in their native JSON,
+//! or MessagePack, encoding on the port of the gRPC server.
+
+use std::{io::Read, sync::Arc};
+
+use bytes::Bytes;
+use flate2::read::MultiGzDecoder;
+use futures::FutureExt;
+use http::{
+    header::{CONTENT_ENCODING, CONTENT_TYPE},
+    Request, Response, StatusCode,
+};
+use hyper::Body;
+use serde_json::json;
+use tonic::{body::BoxBody, Code};
+use vector_common::internal_event::{
+    ByteSize, BytesReceived, InternalEventHandle as _, Protocol, Registered,
+};
+use vector_core::event::Event;
+
+use super::Service;
+use crate::{internal_events::GrpcHttpFallbackError, sources::util::grpc::HttpFallback};
+
+/// The encoding of the events in the body of a request.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Encoding {
+    Json,
+    MessagePack,
+}
+
+impl Encoding {
+    /// Negotiates the encoding from the content type of the request, JSON when there is none.
+    fn from_content_type(content_type: Option<&str>) -> Option<Self> {
+        let content_type = match content_type {
+            Some(content_type) => content_type,
+            None => return Some(Self::Json),
+        };
+        let mime = content_type.split(';').next().unwrap_or_default().trim();
+        if mime.eq_ignore_ascii_case("application/json") {
+            Some(Self::Json)
+        } else if [
+            "application/msgpack",
+            "application/x-msgpack",
+            "application/vnd.msgpack",
+        ]
+        .iter()
+        .any(|msgpack| mime.eq_ignore_ascii_case(msgpack))
+        {
+            Some(Self::MessagePack)
+        } else {
+            None
+        }
+    }
+
+    /// Decodes a single event, or an array of events.
+    fn decode(self, body: &[u8]) -> Result<Vec<Event>, String> {
+        match self {
+            Self::Json => {
+                let is_array = body
+                    .iter()
+                    .find(|byte| !byte.is_ascii_whitespace())
+                    .map_or(false, |byte| *byte == b'[');
+                if is_array {
+                    serde_json::from_slice(body)
+                } else {
+                    serde_json::from_slice(body).map(|event| vec![event])
+                }
+                .map_err(|error| format!("Error parsing JSON: {}", error))
+            }
+            Self::MessagePack => {
+                // The array markers: fixarray, array 16 and array 32.
+                let is_array = matches!(body.first(), Some(0x90..=0x9f | 0xdc | 0xdd));
+                if is_array {
+                    rmp_serde::from_slice(body)
+                } else {
+                    rmp_serde::from_slice(body).map(|event| vec![event])
+                }
+                .map_err(|error| format!("Error parsing MessagePack: {}", error))
+            }
+        }
+    }
+}
+
+/// An error response, with the status code and message it is sent with.
+#[derive(Debug)]
+struct ErrorResponse {
+    status: StatusCode,
+    message: String,
+}
+
+impl ErrorResponse {
+    fn new(status: StatusCode, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+/// Builds the HTTP fallback of the gRPC server, sending the events of the requests through the
+/// service.
+pub(super) fn http_fallback(service: Service) -> HttpFallback {
+    let bytes_received = register!(BytesReceived::from(Protocol::HTTP));
+    Arc::new(move |request| {
+        let service = service.clone();
+        let bytes_received = bytes_received.clone();
+        async move {
+            let result = match decode_request(request, &bytes_received).await {
+                Ok(events) => service.receive(events).await.map_err(|status| {
+                    let code = match status.code() {
+                        Code::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+                        Code::DataLoss => StatusCode::BAD_REQUEST,
+                        _ => StatusCode::INTERNAL_SERVER_ERROR,
+                    };
+                    ErrorResponse::new(code, status.message())
+                }),
+                Err(error) => {
+                    emit!(GrpcHttpFallbackError {
+                        error: &error.message,
+                        http_code: error.status.as_u16(),
+                    });
+                    Err(error)
+                }
+            };
+
+            match result {
+                Ok(()) => response(StatusCode::OK, Body::empty()),
+                Err(error) => {
+                    let body = json!({
+                        "code": error.status.as_u16(),
+                        "message": error.message,
+                    });
+                    response(error.status, Body::from(body.to_string()))
+                }
+            }
+        }
+        .boxed()
+    })
+}
+
+/// Decodes the events of a request, from its content type and encoding.
+async fn decode_request(
+    request: Request<Body>,
+    bytes_received: &Registered<BytesReceived>,
+) -> Result<Vec<Event>, ErrorResponse> {
+    let content_type = request
+        .headers()
+        .get(CONTENT_TYPE)
+        .map(|content_type| {
+            content_type
+                .to_str()
+                .map_err(|_| ErrorResponse::new(StatusCode::BAD_REQUEST, "Invalid content type."))
+        })
+        .transpose()?;
+    let encoding = Encoding::from_content_type(content_type).ok_or_else(|| {
+        ErrorResponse::new(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            format!(
+                "Unsupported content type: {:?}.",
+                content_type.unwrap_or_default()
+            ),
+        )
+    })?;
+    let gzip = match request.headers().get(CONTENT_ENCODING) {
+        None => false,
+        Some(encoding) if encoding.as_bytes().eq_ignore_ascii_case(b"identity") => false,
+        Some(encoding) if encoding.as_bytes().eq_ignore_ascii_case(b"gzip") => true,
+        Some(encoding) => {
+            return Err(ErrorResponse::new(
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                format!("Unsupported content encoding: {:?}.", encoding),
+            ))
+        }
+    };
+
+    let body = hyper::body::to_bytes(request.into_body())
+        .await
+        .map_err(|error| {
+            ErrorResponse::new(
+                StatusCode::BAD_REQUEST,
+                format!("Error reading the body: {}", error),
+            )
+        })?;
+    bytes_received.emit(ByteSize(body.len()));
+    let body = if gzip { gunzip(&body)? } else { body };
+
+    encoding
+        .decode(&body)
+        .map_err(|message| ErrorResponse::new(StatusCode::BAD_REQUEST, message))
+}
+
+fn gunzip(body: &[u8]) -> Result<Bytes, ErrorResponse> {
+    let mut decoded = Vec::new();
+    MultiGzDecoder::new(body)
+        .read_to_end(&mut decoded)
+        .map_err(|error| {
+            ErrorResponse::new(
+                StatusCode::BAD_REQUEST,
+                format!("Failed decompressing payload with gzip encoding: {}", error),
+            )
+        })?;
+    Ok(decoded.into())
+}
+
+fn response(status: StatusCode, body: Body) -> Response<BoxBody> {
+    let mut response = Response::new(tonic::body::boxed(body));
+    *response.status_mut() = status;
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_common::assert_event_data_eq;
+    use vector_core::event::LogEvent;
+
+    use super::*;
+
+    #[test]
+    fn negotiates_encoding() {
+        assert_eq!(Encoding::from_content_type(None), Some(Encoding::Json));
+        assert_eq!(
+            Encoding::from_content_type(Some("application/json; charset=utf-8")),
+            Some(Encoding::Json)
+        );
+        assert_eq!(
+            Encoding::from_content_type(Some("application/x-msgpack")),
+            Some(Encoding::MessagePack)
+        );
+        assert_eq!(Encoding::from_content_type(Some("text/plain")), None);
+    }
+
+    #[test]
+    fn decodes_events_and_arrays() {
+        // Without a timestamp, as it would be decoded as a string.
+        let mut log = LogEvent::default();
+        log.insert("message", "hello");
+        let event = Event::from(log);
+        let events = vec![event.clone(), event.clone()];
+
+        let json = serde_json::to_vec(&event).unwrap();
+        assert_event_data_eq!(Encoding::Json.decode(&json).unwrap(), vec![event.clone()]);
+        let json = serde_json::to_vec(&events).unwrap();
+        assert_event_data_eq!(Encoding::Json.decode(&json).unwrap(), events);
+
+        let msgpack = rmp_serde::to_vec_named(&event).unwrap();
+        assert_event_data_eq!(
+            Encoding::MessagePack.decode(&msgpack).unwrap(),
+            vec![event.clone()]
+        );
+        let msgpack = rmp_serde::to_vec_named(&events).unwrap();
+        assert_event_data_eq!(Encoding::MessagePack.decode(&msgpack).unwrap(), events);
+
+        assert!(Encoding::Json.decode(b"{\"metric\":1}").is_err());
+    }
+}
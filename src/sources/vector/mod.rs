@@ -1,4 +1,4 @@
-use std::net::SocketAddr;
+use std::{net::SocketAddr, sync::Arc};
 
 use futures::TryFutureExt;
 use tonic::{Request, Response, Status};
@@ -9,13 +9,15 @@ use vector_core::{
     ByteSizeOf,
 };
 
+mod http;
+
 use crate::{
     config::{
         AcknowledgementsConfig, DataType, GenerateConfig, Output, Resource, SourceConfig,
         SourceContext,
     },
     internal_events::{EventsReceived, StreamClosedError},
-    proto::vector as proto,
+    proto::{schema::get_schema_definition, vector as proto},
     serde::bool_or_struct,
     sources::{util::grpc::run_grpc_server, Source},
     tls::{MaybeTlsSettings, TlsEnableableConfig},
@@ -37,19 +39,10 @@ pub struct Service {
     acknowledgements: bool,
 }
 
-#[tonic::async_trait]
-impl proto::Service for Service {
-    async fn push_events(
-        &self,
-        request: Request<proto::PushEventsRequest>,
-    ) -> Result<Response<proto::PushEventsResponse>, Status> {
-        let mut events: Vec<Event> = request
-            .into_inner()
-            .events
-            .into_iter()
-            .map(Event::from)
-            .collect();
-
+impl Service {
+    /// Sends the received events through the pipeline, waiting for their delivery when
+    /// acknowledgements are enabled.
+    async fn receive(&self, mut events: Vec<Event>) -> Result<(), Status> {
         let count = events.len();
         let byte_size = events.size_of();
 
@@ -66,7 +59,33 @@ impl proto::Service for Service {
                 Status::unavailable(message)
             })
             .and_then(|_| handle_batch_status(receiver))
-            .await?;
+            .await
+    }
+}
+
+#[tonic::async_trait]
+impl proto::Service for Service {
+    async fn push_events(
+        &self,
+        request: Request<proto::PushEventsRequest>,
+    ) -> Result<Response<proto::PushEventsResponse>, Status> {
+        let schema_definition = get_schema_definition(request.metadata()).map(Arc::new);
+        let mut events: Vec<Event> = request
+            .into_inner()
+            .events
+            .into_iter()
+            .map(Event::from)
+            .collect();
+
+        // The upstream Vector sends the schema definition of the events along with them, so that
+        // they keep their type information across the hop.
+        if let Some(definition) = &schema_definition {
+            for event in &mut events {
+                event.metadata_mut().set_schema_definition(definition);
+            }
+        }
+
+        self.receive(events).await?;
 
         Ok(Response::new(proto::PushEventsResponse {}))
     }
@@ -117,6 +136,15 @@ pub struct VectorConfig {
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: AcknowledgementsConfig,
+
+    /// Whether or not to also accept events over plain HTTP, on the same port.
+    ///
+    /// The events are `POST`ed in the body of the requests, as a single event or an array of events, in their
+    /// native JSON encoding (`application/json`, the default) or MessagePack encoding (`application/msgpack`),
+    /// optionally compressed with `gzip`. The requests whose content type is `application/grpc` are still handled
+    /// as gRPC requests.
+    #[serde(default)]
+    http_fallback: bool,
 }
 
 impl GenerateConfig for VectorConfig {
@@ -126,6 +154,7 @@ impl GenerateConfig for VectorConfig {
             address: "0.0.0.0:6000".parse().unwrap(),
             tls: None,
             acknowledgements: Default::default(),
+            http_fallback: false,
         })
         .unwrap()
     }
@@ -136,16 +165,26 @@ impl SourceConfig for VectorConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<Source> {
         let tls_settings = MaybeTlsSettings::from_config(&self.tls, true)?;
         let acknowledgements = cx.do_acknowledgements(&self.acknowledgements);
-        let service = proto::Server::new(Service {
+        let service = Service {
             pipeline: cx.out,
             acknowledgements,
-        })
-        .accept_compressed(tonic::codec::CompressionEncoding::Gzip);
-
-        let source =
-            run_grpc_server(self.address, tls_settings, service, cx.shutdown).map_err(|error| {
-                error!(message = "Source future failed.", %error);
-            });
+        };
+        let http_fallback = self
+            .http_fallback
+            .then(|| http::http_fallback(service.clone()));
+        let service =
+            proto::Server::new(service).accept_compressed(tonic::codec::CompressionEncoding::Gzip);
+
+        let source = run_grpc_server(
+            self.address,
+            tls_settings,
+            service,
+            http_fallback,
+            cx.shutdown,
+        )
+        .map_err(|error| {
+            error!(message = "Source future failed.", %error);
+        });
 
         Ok(Box::pin(source))
     }
@@ -174,7 +213,9 @@ mod test {
 #[cfg(feature = "sinks-vector")]
 #[cfg(test)]
 mod tests {
+    use value::{kind::Collection, Kind};
     use vector_common::assert_event_data_eq;
+    use vector_core::{event::LogEvent, schema::Definition};
 
     use super::*;
     use crate::{
@@ -254,4 +295,120 @@ mod tests {
         })
         .await;
     }
+
+    #[tokio::test]
+    async fn receive_zstd_compressed_message() {
+        assert_source_compliance(&SOURCE_TAGS, async {
+            let addr = test_util::next_addr();
+            let config = format!(r#"address = "{}""#, addr);
+            let source: VectorConfig = toml::from_str(&config).unwrap();
+
+            let (tx, rx) = SourceSender::new_test();
+            let server = source
+                .build(SourceContext::new_test(tx, None))
+                .await
+                .unwrap();
+            tokio::spawn(server);
+            test_util::wait_for_tcp(addr).await;
+
+            let config = format!(
+                r#"address = "{}"
+            compression = "zstd""#,
+                addr
+            );
+            let sink: SinkConfig = toml::from_str(&config).unwrap();
+            let cx = SinkContext::new_test();
+            let (sink, _) = sink.build(cx).await.unwrap();
+
+            let (events, stream) = test_util::random_events_with_stream(100, 100, None);
+            sink.run(stream).await.unwrap();
+
+            let output = test_util::collect_ready(rx).await;
+            assert_event_data_eq!(events, output);
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn receive_http_fallback() {
+        assert_source_compliance(&SOURCE_TAGS, async {
+            let addr = test_util::next_addr();
+            let config = format!(
+                r#"address = "{}"
+            http_fallback = true"#,
+                addr
+            );
+            let source: VectorConfig = toml::from_str(&config).unwrap();
+
+            let (tx, rx) = SourceSender::new_test();
+            let server = source
+                .build(SourceContext::new_test(tx, None))
+                .await
+                .unwrap();
+            tokio::spawn(server);
+            test_util::wait_for_tcp(addr).await;
+
+            let client = reqwest::Client::new();
+            let response = client
+                .post(format!("http://{}/", addr))
+                .header("Content-Type", "application/json")
+                .body(r#"[{"log":{"message":"hello"}},{"log":{"message":"world"}}]"#)
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status(), 200);
+
+            let response = client
+                .post(format!("http://{}/", addr))
+                .header("Content-Type", "text/plain")
+                .body("hello")
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status(), 415);
+
+            let output = test_util::collect_ready(rx).await;
+            assert_eq!(output.len(), 2);
+            assert_eq!(output[0].as_log()["message"], "hello".into());
+            assert_eq!(output[1].as_log()["message"], "world".into());
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn receive_schema_definition() {
+        let addr = test_util::next_addr();
+        let config = format!(r#"address = "{}""#, addr);
+        let source: VectorConfig = toml::from_str(&config).unwrap();
+
+        let (tx, rx) = SourceSender::new_test();
+        let server = source
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(server);
+        test_util::wait_for_tcp(addr).await;
+
+        let sink: SinkConfig = toml::from_str(&config).unwrap();
+        let (sink, _) = sink.build(SinkContext::new_test()).await.unwrap();
+
+        let definition = Arc::new(
+            Definition::new_with_default_metadata(
+                Kind::object(Collection::empty()),
+                [LogNamespace::Legacy],
+            )
+            .with_field("message", Kind::bytes(), Some("message")),
+        );
+        let mut event = Event::from(LogEvent::from("hello"));
+        event.metadata_mut().set_schema_definition(&definition);
+        sink.run_events(vec![event]).await.unwrap();
+
+        let output = test_util::collect_ready(rx).await;
+        let received = output[0].metadata().schema_definition();
+        assert_eq!(received.event_kind(), definition.event_kind());
+        assert_eq!(
+            received.meaning_path("message").map(ToString::to_string),
+            Some("message".to_owned())
+        );
+    }
 }
@@ -0,0 +1,184 @@
+use std::sync::Arc;
+
+use aws_sdk_dynamodb::{model::AttributeValue, types::Blob, Client as DynamoDbClient};
+use bytes::Bytes;
+use vector_config::configurable_component;
+
+use super::{default_key_prefix, namespaced_key, BoxedStateStore, StateStore};
+use crate::{
+    aws::{auth::AwsAuthentication, create_client, region::RegionOrEndpoint},
+    common::dynamodb::DynamoDbClientBuilder,
+    config::ProxyConfig,
+    tls::TlsConfig,
+};
+
+const KEY_ATTRIBUTE: &str = "key";
+const VALUE_ATTRIBUTE: &str = "value";
+
+/// Storing the state in items of a DynamoDB table.
+#[configurable_component]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct DynamoDbStateStoreConfig {
+    /// The name of the DynamoDB table.
+    ///
+    /// The table must have a string partition key named `key`.
+    table_name: String,
+
+    /// The prefix of the keys the state is stored under.
+    ///
+    /// Each value is stored under the key `<key_prefix>/<component_id>/<key>`.
+    #[serde(default = "default_key_prefix")]
+    key_prefix: String,
+
+    #[serde(flatten)]
+    region: RegionOrEndpoint,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    auth: AwsAuthentication,
+
+    #[configurable(derived)]
+    tls: Option<TlsConfig>,
+}
+
+impl DynamoDbStateStoreConfig {
+    pub(super) async fn build(
+        &self,
+        component_id: &str,
+        proxy: &ProxyConfig,
+    ) -> crate::Result<BoxedStateStore> {
+        let client = create_client::<DynamoDbClientBuilder>(
+            &self.auth,
+            self.region.region(),
+            self.region.endpoint()?,
+            proxy,
+            &self.tls,
+            false,
+        )
+        .await?;
+        Ok(Arc::new(DynamoDbStateStore {
+            client,
+            table_name: self.table_name.clone(),
+            key_prefix: self.key_prefix.clone(),
+            component_id: component_id.to_owned(),
+        }))
+    }
+}
+
+struct DynamoDbStateStore {
+    client: DynamoDbClient,
+    table_name: String,
+    key_prefix: String,
+    component_id: String,
+}
+
+impl DynamoDbStateStore {
+    fn key(&self, key: &str) -> AttributeValue {
+        AttributeValue::S(namespaced_key(&self.key_prefix, &self.component_id, key))
+    }
+}
+
+#[async_trait::async_trait]
+impl StateStore for DynamoDbStateStore {
+    async fn get(&self, key: &str) -> crate::Result<Option<Bytes>> {
+        let output = self
+            .client
+            .get_item()
+            .table_name(&self.table_name)
+            .key(KEY_ATTRIBUTE, self.key(key))
+            .consistent_read(true)
+            .send()
+            .await?;
+
+        Ok(output
+            .item
+            .and_then(|mut item| item.remove(VALUE_ATTRIBUTE))
+            .and_then(|value| match value {
+                AttributeValue::B(value) => Some(value.into_inner().into()),
+                _ => None,
+            }))
+    }
+
+    async fn set(&self, key: &str, value: Bytes) -> crate::Result<()> {
+        self.client
+            .put_item()
+            .table_name(&self.table_name)
+            .item(KEY_ATTRIBUTE, self.key(key))
+            .item(
+                VALUE_ATTRIBUTE,
+                AttributeValue::B(Blob::new(value.to_vec())),
+            )
+            .send()
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "aws-dynamodb-integration-tests")]
+#[cfg(test)]
+mod integration_tests {
+    use aws_sdk_dynamodb::model::{
+        AttributeDefinition, KeySchemaElement, KeyType, ProvisionedThroughput, ScalarAttributeType,
+    };
+
+    use super::*;
+    use crate::{state_store::assert_persists_values, test_util::random_string};
+
+    fn dynamodb_address() -> String {
+        std::env::var("DYNAMODB_ADDRESS").unwrap_or_else(|_| "http://localhost:4566".into())
+    }
+
+    #[tokio::test]
+    async fn aws_dynamodb_state_store() {
+        let config = DynamoDbStateStoreConfig {
+            table_name: format!("test-{}", random_string(10)),
+            key_prefix: default_key_prefix(),
+            region: RegionOrEndpoint::with_both("us-east-1", dynamodb_address()),
+            auth: AwsAuthentication::test_auth(),
+            tls: None,
+        };
+        let proxy = ProxyConfig::default();
+
+        let client = create_client::<DynamoDbClientBuilder>(
+            &config.auth,
+            config.region.region(),
+            config.region.endpoint().unwrap(),
+            &proxy,
+            &None,
+            false,
+        )
+        .await
+        .unwrap();
+        client
+            .create_table()
+            .table_name(&config.table_name)
+            .attribute_definitions(
+                AttributeDefinition::builder()
+                    .attribute_name(KEY_ATTRIBUTE)
+                    .attribute_type(ScalarAttributeType::S)
+                    .build(),
+            )
+            .key_schema(
+                KeySchemaElement::builder()
+                    .attribute_name(KEY_ATTRIBUTE)
+                    .key_type(KeyType::Hash)
+                    .build(),
+            )
+            .provisioned_throughput(
+                ProvisionedThroughput::builder()
+                    .read_capacity_units(1)
+                    .write_capacity_units(1)
+                    .build(),
+            )
+            .send()
+            .await
+            .expect("Could not create table");
+
+        assert_persists_values(
+            config.build("in", &proxy).await.unwrap(),
+            config.build("other", &proxy).await.unwrap(),
+        )
+        .await;
+    }
+}
@@ -0,0 +1,145 @@
+use std::sync::Arc;
+
+use aws_sdk_s3::{types::ByteStream, Client as S3Client};
+use aws_smithy_client::SdkError;
+use bytes::Bytes;
+use vector_config::configurable_component;
+
+use super::{default_key_prefix, namespaced_key, BoxedStateStore, StateStore};
+use crate::{
+    aws::{auth::AwsAuthentication, create_client, region::RegionOrEndpoint},
+    common::s3::S3ClientBuilder,
+    config::ProxyConfig,
+    tls::TlsConfig,
+};
+
+/// Storing the state in objects of an S3 bucket.
+#[configurable_component]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct S3StateStoreConfig {
+    /// The name of the bucket.
+    bucket: String,
+
+    /// The prefix of the keys of the objects the state is stored in.
+    ///
+    /// Each value is stored in the object `<key_prefix>/<component_id>/<key>`.
+    #[serde(default = "default_key_prefix")]
+    key_prefix: String,
+
+    #[serde(flatten)]
+    region: RegionOrEndpoint,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    auth: AwsAuthentication,
+
+    #[configurable(derived)]
+    tls: Option<TlsConfig>,
+}
+
+impl S3StateStoreConfig {
+    pub(super) async fn build(
+        &self,
+        component_id: &str,
+        proxy: &ProxyConfig,
+    ) -> crate::Result<BoxedStateStore> {
+        let client = create_client::<S3ClientBuilder>(
+            &self.auth,
+            self.region.region(),
+            self.region.endpoint()?,
+            proxy,
+            &self.tls,
+            false,
+        )
+        .await?;
+        Ok(Arc::new(S3StateStore {
+            client,
+            bucket: self.bucket.clone(),
+            key_prefix: self.key_prefix.clone(),
+            component_id: component_id.to_owned(),
+        }))
+    }
+}
+
+struct S3StateStore {
+    client: S3Client,
+    bucket: String,
+    key_prefix: String,
+    component_id: String,
+}
+
+#[async_trait::async_trait]
+impl StateStore for S3StateStore {
+    async fn get(&self, key: &str) -> crate::Result<Option<Bytes>> {
+        let result = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(namespaced_key(&self.key_prefix, &self.component_id, key))
+            .send()
+            .await;
+        match result {
+            Ok(object) => Ok(Some(object.body.collect().await?.into_bytes())),
+            Err(SdkError::ServiceError { err, .. }) if err.is_no_such_key() => Ok(None),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    async fn set(&self, key: &str, value: Bytes) -> crate::Result<()> {
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(namespaced_key(&self.key_prefix, &self.component_id, key))
+            .body(ByteStream::from(value))
+            .send()
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "aws-s3-integration-tests")]
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+    use crate::state_store::assert_persists_values;
+
+    fn s3_address() -> String {
+        std::env::var("S3_ADDRESS").unwrap_or_else(|_| "http://localhost:4566".into())
+    }
+
+    #[tokio::test]
+    async fn aws_s3_state_store() {
+        let config = S3StateStoreConfig {
+            bucket: uuid::Uuid::new_v4().to_string(),
+            key_prefix: default_key_prefix(),
+            region: RegionOrEndpoint::with_both("us-east-1", s3_address()),
+            auth: AwsAuthentication::test_auth(),
+            tls: None,
+        };
+        let proxy = ProxyConfig::default();
+
+        let client = create_client::<S3ClientBuilder>(
+            &config.auth,
+            config.region.region(),
+            config.region.endpoint().unwrap(),
+            &proxy,
+            &None,
+            false,
+        )
+        .await
+        .unwrap();
+        client
+            .create_bucket()
+            .bucket(&config.bucket)
+            .send()
+            .await
+            .expect("Could not create bucket");
+
+        assert_persists_values(
+            config.build("in", &proxy).await.unwrap(),
+            config.build("other", &proxy).await.unwrap(),
+        )
+        .await;
+    }
+}
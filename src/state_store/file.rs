@@ -0,0 +1,67 @@
+use std::{io, path::PathBuf};
+
+use bytes::Bytes;
+use tokio::sync::Mutex;
+
+use super::StateStore;
+
+/// Stores each value in a file of the data directory of the source, named after its key.
+pub struct FileStateStore {
+    data_dir: PathBuf,
+    /// Serializes the writes, which share the temporary file of their key.
+    write_lock: Mutex<()>,
+}
+
+impl FileStateStore {
+    pub fn new(data_dir: PathBuf) -> Self {
+        Self {
+            data_dir,
+            write_lock: Mutex::new(()),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl StateStore for FileStateStore {
+    async fn get(&self, key: &str) -> crate::Result<Option<Bytes>> {
+        match tokio::fs::read(self.data_dir.join(key)).await {
+            Ok(value) => Ok(Some(value.into())),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// The value is written to a temporary file first, so that a crash can't leave a partial
+    /// value behind.
+    async fn set(&self, key: &str, value: Bytes) -> crate::Result<()> {
+        let _guard = self.write_lock.lock().await;
+        let path = self.data_dir.join(key);
+        let temp_path = self.data_dir.join(format!("{}.tmp", key));
+        tokio::fs::write(&temp_path, value).await?;
+        tokio::fs::rename(&temp_path, &path).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn persists_values() {
+        let data_dir = tempfile::tempdir().unwrap();
+
+        let store = FileStateStore::new(data_dir.path().to_path_buf());
+        assert_eq!(store.get("cursors.json").await.unwrap(), None);
+        store
+            .set("cursors.json", Bytes::from_static(b"{}"))
+            .await
+            .unwrap();
+
+        let store = FileStateStore::new(data_dir.path().to_path_buf());
+        assert_eq!(
+            store.get("cursors.json").await.unwrap(),
+            Some(Bytes::from_static(b"{}"))
+        );
+    }
+}
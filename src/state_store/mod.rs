@@ -0,0 +1,151 @@
+//! Stores for the state of sources, such as their checkpoints and cursors.
+//!
+//! By default, sources persist their state in files of their data directory, which ties the state
+//! to the disk of the host they run on. Stateless containers and autoscaled aggregators can instead
+//! store it remotely, so that a replacement instance resumes where a dead peer left off.
+
+use std::{path::PathBuf, sync::Arc};
+
+use bytes::Bytes;
+use vector_config::configurable_component;
+
+use crate::config::SourceContext;
+
+#[cfg(feature = "state-stores-aws_dynamodb")]
+mod aws_dynamodb;
+#[cfg(feature = "state-stores-aws_s3")]
+mod aws_s3;
+mod file;
+#[cfg(feature = "state-stores-redis")]
+mod redis;
+
+#[cfg(feature = "state-stores-aws_dynamodb")]
+pub use self::aws_dynamodb::DynamoDbStateStoreConfig;
+#[cfg(feature = "state-stores-aws_s3")]
+pub use self::aws_s3::S3StateStoreConfig;
+pub use self::file::FileStateStore;
+#[cfg(feature = "state-stores-redis")]
+pub use self::redis::RedisStateStoreConfig;
+
+/// A store of the state of a source, as values by key.
+///
+/// The keys are chosen by the source, and are only unique within it: the store namespaces them
+/// by the ID of the source.
+#[async_trait::async_trait]
+pub trait StateStore: Send + Sync {
+    /// Gets the value of the given key, if it has one.
+    async fn get(&self, key: &str) -> crate::Result<Option<Bytes>>;
+
+    /// Sets the value of the given key.
+    async fn set(&self, key: &str, value: Bytes) -> crate::Result<()>;
+}
+
+pub type BoxedStateStore = Arc<dyn StateStore>;
+
+/// Where the state of the source, such as its checkpoints, is stored.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StateStoreConfig {
+    /// Store the state in files of the data directory of the source.
+    #[derivative(Default)]
+    File,
+
+    /// Store the state in Redis.
+    #[cfg(feature = "state-stores-redis")]
+    Redis(#[configurable(derived)] RedisStateStoreConfig),
+
+    /// Store the state in objects of an S3 bucket.
+    #[cfg(feature = "state-stores-aws_s3")]
+    AwsS3(#[configurable(derived)] S3StateStoreConfig),
+
+    /// Store the state in items of a DynamoDB table.
+    #[cfg(feature = "state-stores-aws_dynamodb")]
+    #[serde(rename = "aws_dynamodb")]
+    AwsDynamoDb(#[configurable(derived)] DynamoDbStateStoreConfig),
+}
+
+impl StateStoreConfig {
+    /// Whether the state is stored in files of the data directory of the source.
+    pub const fn is_file(&self) -> bool {
+        matches!(self, Self::File)
+    }
+
+    /// Builds the state store of the source.
+    ///
+    /// The data directory of the source is only created when the state is stored in files.
+    pub async fn build(
+        &self,
+        cx: &SourceContext,
+        data_dir: Option<&PathBuf>,
+    ) -> crate::Result<BoxedStateStore> {
+        match self {
+            Self::File => {
+                let data_dir = cx
+                    .globals
+                    .resolve_and_make_data_subdir(data_dir, cx.key.id())?;
+                Ok(Arc::new(FileStateStore::new(data_dir)))
+            }
+            #[cfg(feature = "state-stores-redis")]
+            Self::Redis(config) => config.build(cx.key.id()).await,
+            #[cfg(feature = "state-stores-aws_s3")]
+            Self::AwsS3(config) => config.build(cx.key.id(), &cx.proxy).await,
+            #[cfg(feature = "state-stores-aws_dynamodb")]
+            Self::AwsDynamoDb(config) => config.build(cx.key.id(), &cx.proxy).await,
+        }
+    }
+}
+
+#[cfg(any(
+    feature = "state-stores-redis",
+    feature = "state-stores-aws_s3",
+    feature = "state-stores-aws_dynamodb"
+))]
+fn default_key_prefix() -> String {
+    "vector".to_string()
+}
+
+/// Builds the key a value of the source is stored under, in the stores shared by several
+/// components.
+#[cfg(any(
+    feature = "state-stores-redis",
+    feature = "state-stores-aws_s3",
+    feature = "state-stores-aws_dynamodb"
+))]
+fn namespaced_key(key_prefix: &str, component_id: &str, key: &str) -> String {
+    format!(
+        "{}/{}/{}",
+        key_prefix.trim_end_matches('/'),
+        component_id,
+        key
+    )
+}
+
+/// Asserts that the store persists values, apart from those of the other component.
+#[cfg(all(
+    test,
+    any(
+        feature = "redis-integration-tests",
+        feature = "aws-s3-integration-tests",
+        feature = "aws-dynamodb-integration-tests"
+    )
+))]
+async fn assert_persists_values(store: BoxedStateStore, other: BoxedStateStore) {
+    let key = "checkpoints.json";
+    assert_eq!(store.get(key).await.unwrap(), None);
+
+    store.set(key, Bytes::from_static(b"1")).await.unwrap();
+    assert_eq!(
+        store.get(key).await.unwrap(),
+        Some(Bytes::from_static(b"1"))
+    );
+
+    store.set(key, Bytes::from_static(b"2")).await.unwrap();
+    assert_eq!(
+        store.get(key).await.unwrap(),
+        Some(Bytes::from_static(b"2"))
+    );
+
+    assert_eq!(other.get(key).await.unwrap(), None);
+}
@@ -0,0 +1,84 @@
+use std::sync::Arc;
+
+use bytes::Bytes;
+use redis::{aio::ConnectionManager, AsyncCommands};
+use vector_config::configurable_component;
+
+use super::{default_key_prefix, namespaced_key, BoxedStateStore, StateStore};
+
+/// Storing the state in Redis.
+#[configurable_component]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RedisStateStoreConfig {
+    /// The URL of the Redis endpoint to connect to.
+    ///
+    /// The URL _must_ take the form of `protocol://server:port/db` where the protocol can either be
+    /// `redis` or `rediss` for connections secured via TLS.
+    url: String,
+
+    /// The prefix of the keys the state is stored under.
+    ///
+    /// Each value is stored under the key `<key_prefix>/<component_id>/<key>`.
+    #[serde(default = "default_key_prefix")]
+    key_prefix: String,
+}
+
+impl RedisStateStoreConfig {
+    pub(super) async fn build(&self, component_id: &str) -> crate::Result<BoxedStateStore> {
+        let connection = redis::Client::open(self.url.as_str())?
+            .get_tokio_connection_manager()
+            .await?;
+        Ok(Arc::new(RedisStateStore {
+            connection,
+            key_prefix: self.key_prefix.clone(),
+            component_id: component_id.to_owned(),
+        }))
+    }
+}
+
+struct RedisStateStore {
+    connection: ConnectionManager,
+    key_prefix: String,
+    component_id: String,
+}
+
+#[async_trait::async_trait]
+impl StateStore for RedisStateStore {
+    async fn get(&self, key: &str) -> crate::Result<Option<Bytes>> {
+        let key = namespaced_key(&self.key_prefix, &self.component_id, key);
+        let value: Option<Vec<u8>> = self.connection.clone().get(key).await?;
+        Ok(value.map(Into::into))
+    }
+
+    async fn set(&self, key: &str, value: Bytes) -> crate::Result<()> {
+        let key = namespaced_key(&self.key_prefix, &self.component_id, key);
+        let _: () = self.connection.clone().set(key, value.as_ref()).await?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "redis-integration-tests")]
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+    use crate::{state_store::assert_persists_values, test_util::random_string};
+
+    fn redis_server() -> String {
+        std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379/0".to_owned())
+    }
+
+    #[tokio::test]
+    async fn redis_state_store() {
+        let config = RedisStateStoreConfig {
+            url: redis_server(),
+            key_prefix: format!("test-{}", random_string(10)),
+        };
+
+        assert_persists_values(
+            config.build("in").await.unwrap(),
+            config.build("other").await.unwrap(),
+        )
+        .await;
+    }
+}
@@ -34,7 +34,10 @@ pub(crate) async fn cmd(opts: &super::Opts, mut signal_rx: SignalRx) -> exitcode
 
     // Return early with instructions for enabling the API if the endpoint isn't reachable
     // via a healthcheck.
-    if Client::new_with_healthcheck(url.clone()).await.is_none() {
+    if Client::new_with_healthcheck(url.clone(), &opts.connect)
+        .await
+        .is_none()
+    {
         return exitcode::UNAVAILABLE;
     }
 
@@ -85,7 +88,7 @@ async fn run(
     outputs_patterns: Vec<String>,
     formatter: EventFormatter,
 ) -> exitcode::ExitCode {
-    let subscription_client = match connect_subscription_client(url).await {
+    let subscription_client = match connect_subscription_client(url, &opts.connect).await {
         Ok(c) => c,
         Err(e) => {
             #[allow(clippy::print_stderr)]
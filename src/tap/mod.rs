@@ -3,7 +3,7 @@ mod cmd;
 use clap::Parser;
 pub(crate) use cmd::cmd;
 use url::Url;
-use vector_api_client::gql::TapEncodingFormat;
+use vector_api_client::{gql::TapEncodingFormat, ConnectOptions};
 
 #[derive(Parser, Debug, Clone)]
 #[command(rename_all = "kebab-case")]
@@ -47,4 +47,7 @@ pub struct Opts {
     /// Whether to reconnect if the underlying Vector API connection drops. By default, tap will attempt to reconnect if the connection drops.
     #[arg(short, long)]
     no_reconnect: bool,
+
+    #[command(flatten)]
+    connect: ConnectOptions,
 }
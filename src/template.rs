@@ -3,7 +3,7 @@ use std::{borrow::Cow, convert::TryFrom, fmt, hash::Hash, path::PathBuf};
 use bytes::Bytes;
 use chrono::{
     format::{strftime::StrftimeItems, Item},
-    Utc,
+    DateTime, Utc,
 };
 use once_cell::sync::Lazy;
 use regex::{Captures, Regex};
@@ -157,6 +157,25 @@ impl Template {
         }
     }
 
+    /// Renders the template, formatting its time specifiers with the given timestamp rather than
+    /// the timestamp of the event.
+    pub fn render_string_at<'a>(
+        &self,
+        event: impl Into<EventRef<'a>>,
+        timestamp: DateTime<Utc>,
+    ) -> Result<String, TemplateRenderingError> {
+        let event = event.into();
+        match (self.has_fields, self.has_ts) {
+            (false, false) => Ok(self.src.clone()),
+            (true, false) => render_fields(&self.src, event),
+            (false, true) => Ok(timestamp.format(&self.src).to_string()),
+            (true, true) => {
+                let tmp = render_fields(&self.src, event)?;
+                Ok(timestamp.format(&tmp).to_string())
+            }
+        }
+    }
+
     pub fn get_fields(&self) -> Option<Vec<String>> {
         if self.has_fields {
             RE.captures_iter(&self.src)
@@ -214,6 +233,11 @@ fn render_metric_field(key: &str, metric: &Metric) -> Option<String> {
         _ if key.starts_with("tags.") => {
             metric.tags().and_then(|tags| tags.get(&key[5..]).cloned())
         }
+        _ if key.starts_with('%') => metric
+            .metadata()
+            .value()
+            .get(&key[1..])
+            .map(Value::to_string_lossy),
         _ => None,
     }
 }
@@ -390,6 +414,25 @@ mod tests {
         )
     }
 
+    #[test]
+    fn render_log_at_timestamp() {
+        let ts = Utc.ymd(2001, 2, 3).and_hms(4, 5, 6);
+
+        let mut event = Event::Log(LogEvent::from("hello world"));
+        event.as_mut_log().insert("foo", "butts");
+        event.as_mut_log().insert(
+            log_schema().timestamp_key(),
+            Utc.ymd(2010, 1, 1).and_hms(0, 0, 0),
+        );
+
+        let template = Template::try_from("{{ foo }}-%F_%T").unwrap();
+
+        assert_eq!(
+            Ok("butts-2001-02-03_04:05:06".to_owned()),
+            template.render_string_at(&event, ts)
+        )
+    }
+
     #[test]
     fn render_log_dynamic_with_strftime() {
         let ts = Utc.ymd(2001, 2, 3).and_hms(4, 5, 6);
@@ -452,8 +495,8 @@ mod tests {
     fn render_metric_with_tags() {
         let template = Template::try_from("name={{name}} component={{tags.component}}").unwrap();
         let metric = sample_metric().with_tags(Some(BTreeMap::from([
-            (String::from("test"), String::from("true")),
-            (String::from("component"), String::from("template")),
+            ("test".into(), String::from("true")),
+            ("component".into(), String::from("template")),
         ])));
         assert_eq!(
             Ok(Bytes::from("name=a-counter component=template")),
@@ -461,6 +504,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn render_metric_metadata() {
+        let template = Template::try_from("tenant={{ %vector.tenant }}").unwrap();
+        let mut metric = sample_metric();
+        metric
+            .metadata_mut()
+            .value_mut()
+            .insert("vector.tenant", "acme");
+        assert_eq!(Ok(Bytes::from("tenant=acme")), template.render(&metric));
+    }
+
     #[test]
     fn render_metric_without_tags() {
         let template = Template::try_from("name={{name}} component={{tags.component}}").unwrap();
@@ -0,0 +1,311 @@
+//! Limits of the events each tenant can send through the topology.
+//!
+//! The events received by the sources are attributed to their tenant, and counted against its
+//! limits before being sent to the rest of the topology. The counts are shared by all of the
+//! sources, so that a tenant sending to several sources of a shared aggregator is limited as a
+//! whole. The events exceeding the limits of their tenant are discarded rather than applying
+//! backpressure, since backpressure would also slow down the other tenants of the same sources.
+
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use lookup::path;
+use once_cell::sync::OnceCell;
+use vector_core::{
+    config::{TenancyOptions, TenantLimits},
+    event::{EventArray, EventMetadata, LogEvent, Metric, TraceEvent, Value},
+    ByteSizeOf,
+};
+
+use crate::{
+    config::ComponentKey,
+    internal_events::{TenantEventsDiscarded, TenantEventsReceived},
+};
+
+static TENANT_LIMITER: OnceCell<TenantLimiter> = OnceCell::new();
+
+/// Counts the events of each tenant against its limits.
+#[derive(Debug)]
+pub(crate) struct TenantLimiter {
+    options: TenancyOptions,
+    quota_window: Duration,
+    usage: Mutex<HashMap<String, TenantUsage>>,
+}
+
+impl TenantLimiter {
+    pub(crate) fn new(options: TenancyOptions) -> Self {
+        Self {
+            quota_window: Duration::from_secs(options.quota_window_secs()),
+            options,
+            usage: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Gets the global limiter, if the given options limit any tenant.
+    ///
+    /// The global limiter is created the first time it is requested, and then shared by all of the
+    /// topologies built afterwards, since the global options can't change when reloading.
+    pub(crate) fn global(options: &TenancyOptions) -> Option<&'static Self> {
+        options
+            .is_enabled()
+            .then(|| TENANT_LIMITER.get_or_init(|| Self::new(options.clone())))
+    }
+
+    /// Removes the events of the array that exceed the limits of their tenant, which are those of
+    /// the given source when the events don't name one.
+    ///
+    /// The tenant of the events kept is set in their `%vector.tenant` metadata field, so that the
+    /// following components can read it.
+    pub(crate) fn limit(&self, source: &ComponentKey, array: &mut EventArray) {
+        let now = Instant::now();
+        let mut counts = HashMap::<String, TenantCounts>::new();
+        {
+            let mut usage = self.usage.lock().expect("tenant usage lock poisoned");
+            usage.retain(|_, usage| !usage.is_expired(self.quota_window, now));
+
+            let mut admit = |tenant: String, byte_size: usize, metadata: &mut EventMetadata| {
+                let limits = self.options.limits(&tenant);
+                let result = if limits.is_limited() {
+                    usage
+                        .entry(tenant.clone())
+                        .or_insert_with(|| TenantUsage::new(now))
+                        .admit(limits, self.quota_window, byte_size, now)
+                } else {
+                    Ok(())
+                };
+                let counts = counts.entry(tenant.clone()).or_default();
+                match result {
+                    Ok(()) => {
+                        counts.received += 1;
+                        counts.received_bytes += byte_size;
+                        metadata
+                            .value_mut()
+                            .insert(path!("vector", "tenant"), tenant);
+                        true
+                    }
+                    Err(reason) => {
+                        *counts.discarded.entry(reason).or_default() += 1;
+                        false
+                    }
+                }
+            };
+
+            match array {
+                EventArray::Logs(logs) => logs.retain_mut(|log| {
+                    let tenant = self.log_tenant(log, source);
+                    let byte_size = log.size_of();
+                    admit(tenant, byte_size, log.metadata_mut())
+                }),
+                EventArray::Metrics(metrics) => metrics.retain_mut(|metric| {
+                    let tenant = self.metric_tenant(metric, source);
+                    let byte_size = metric.size_of();
+                    admit(tenant, byte_size, metric.metadata_mut())
+                }),
+                EventArray::Traces(traces) => traces.retain_mut(|trace| {
+                    let tenant = self.trace_tenant(trace, source);
+                    let byte_size = trace.size_of();
+                    admit(tenant, byte_size, trace.metadata_mut())
+                }),
+            }
+        }
+
+        for (tenant, counts) in counts {
+            if counts.received > 0 {
+                emit!(TenantEventsReceived {
+                    tenant: &tenant,
+                    count: counts.received,
+                    byte_size: counts.received_bytes,
+                });
+            }
+            for (reason, count) in counts.discarded {
+                emit!(TenantEventsDiscarded {
+                    tenant: &tenant,
+                    count,
+                    reason,
+                });
+            }
+        }
+    }
+
+    fn log_tenant(&self, log: &LogEvent, source: &ComponentKey) -> String {
+        self.field_tenant(|field| log.get(field), source)
+    }
+
+    fn trace_tenant(&self, trace: &TraceEvent, source: &ComponentKey) -> String {
+        self.field_tenant(|field| trace.get(field), source)
+    }
+
+    fn metric_tenant(&self, metric: &Metric, source: &ComponentKey) -> String {
+        self.options
+            .tenant_field
+            .as_ref()
+            .and_then(|field| metric.tag_value(field))
+            .unwrap_or_else(|| source.id().to_owned())
+    }
+
+    fn field_tenant<'a>(
+        &self,
+        get: impl FnOnce(&str) -> Option<&'a Value>,
+        source: &ComponentKey,
+    ) -> String {
+        self.options
+            .tenant_field
+            .as_deref()
+            .and_then(get)
+            .map_or_else(|| source.id().to_owned(), Value::to_string_lossy)
+    }
+}
+
+/// The events of a tenant counted in the current windows of its limits.
+#[derive(Debug)]
+struct TenantUsage {
+    second_start: Instant,
+    events: u64,
+    window_start: Instant,
+    bytes: u64,
+}
+
+impl TenantUsage {
+    const fn new(now: Instant) -> Self {
+        Self {
+            second_start: now,
+            events: 0,
+            window_start: now,
+            bytes: 0,
+        }
+    }
+
+    /// Whether both windows are over, so that the usage can be forgotten.
+    fn is_expired(&self, quota_window: Duration, now: Instant) -> bool {
+        now.duration_since(self.window_start) >= quota_window
+            && now.duration_since(self.second_start) >= Duration::from_secs(1)
+    }
+
+    /// Counts an event against the limits, unless it would exceed them, in which case the reason
+    /// it is discarded is returned instead.
+    fn admit(
+        &mut self,
+        limits: &TenantLimits,
+        quota_window: Duration,
+        byte_size: usize,
+        now: Instant,
+    ) -> Result<(), &'static str> {
+        if now.duration_since(self.second_start) >= Duration::from_secs(1) {
+            self.second_start = now;
+            self.events = 0;
+        }
+        if now.duration_since(self.window_start) >= quota_window {
+            self.window_start = now;
+            self.bytes = 0;
+        }
+
+        let byte_size = byte_size as u64;
+        if matches!(limits.events_per_sec, Some(limit) if self.events >= limit.get()) {
+            return Err("Rate limit of tenant exceeded.");
+        }
+        if matches!(limits.quota_bytes, Some(quota) if self.bytes + byte_size > quota.get()) {
+            return Err("Byte quota of tenant exceeded.");
+        }
+        self.events += 1;
+        self.bytes += byte_size;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default)]
+struct TenantCounts {
+    received: usize,
+    received_bytes: usize,
+    discarded: HashMap<&'static str, usize>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU64;
+
+    use super::*;
+    use vector_buffers::EventCount;
+
+    fn limits(events_per_sec: Option<u64>, quota_bytes: Option<u64>) -> TenantLimits {
+        TenantLimits {
+            events_per_sec: events_per_sec.and_then(NonZeroU64::new),
+            quota_bytes: quota_bytes.and_then(NonZeroU64::new),
+        }
+    }
+
+    #[test]
+    fn limits_rate_per_second() {
+        let now = Instant::now();
+        let window = Duration::from_secs(3600);
+        let limits = limits(Some(2), None);
+        let mut usage = TenantUsage::new(now);
+
+        assert!(usage.admit(&limits, window, 10, now).is_ok());
+        assert!(usage.admit(&limits, window, 10, now).is_ok());
+        assert!(usage.admit(&limits, window, 10, now).is_err());
+
+        let later = now + Duration::from_secs(1);
+        assert!(usage.admit(&limits, window, 10, later).is_ok());
+    }
+
+    #[test]
+    fn limits_bytes_per_window() {
+        let now = Instant::now();
+        let window = Duration::from_secs(60);
+        let limits = limits(None, Some(100));
+        let mut usage = TenantUsage::new(now);
+
+        assert!(usage.admit(&limits, window, 60, now).is_ok());
+        assert!(usage.admit(&limits, window, 60, now).is_err());
+        assert!(usage.admit(&limits, window, 40, now).is_ok());
+
+        let later = now + window;
+        assert!(usage.admit(&limits, window, 60, later).is_ok());
+    }
+
+    #[test]
+    fn limits_tenants_separately() {
+        let options = TenancyOptions {
+            tenant_field: Some("tenant".to_owned()),
+            tenants: [("noisy".to_owned(), limits(Some(1), None))].into(),
+            ..Default::default()
+        };
+        let limiter = TenantLimiter::new(options);
+        let source = ComponentKey::from("in");
+
+        let mut array = EventArray::Logs(
+            ["noisy", "noisy", "quiet", "quiet"]
+                .into_iter()
+                .map(|tenant| {
+                    let mut log = LogEvent::from("message");
+                    log.insert("tenant", tenant);
+                    log
+                })
+                .chain([LogEvent::from("no tenant"), LogEvent::from("no tenant")])
+                .collect(),
+        );
+        limiter.limit(&source, &mut array);
+
+        assert_eq!(array.event_count(), 5);
+        let tenants = array
+            .iter_events()
+            .map(|event| {
+                event
+                    .metadata()
+                    .value()
+                    .get(path!("vector", "tenant"))
+                    .cloned()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            tenants,
+            ["noisy", "quiet", "quiet", "in", "in"]
+                .into_iter()
+                .map(|tenant| Some(Value::from(tenant)))
+                .collect::<Vec<_>>()
+        );
+    }
+}
@@ -87,6 +87,7 @@ impl FunctionTransform for BasicTransform {
                         })
                     }
                     MetricValue::AggregatedHistogram { .. } => None,
+                    MetricValue::ExponentialHistogram { .. } => None,
                     MetricValue::AggregatedSummary { .. } => None,
                     MetricValue::Sketch { .. } => None,
                     MetricValue::Set { .. } => {
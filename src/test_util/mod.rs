@@ -127,7 +127,7 @@ pub fn trace_init() {
 
     let levels = std::env::var("TEST_LOG").unwrap_or_else(|_| "error".to_string());
 
-    trace::init(color, false, &levels, 10);
+    trace::init(color, false, &levels, 10, None);
 
     // Initialize metrics as well
     if let Err(error) = vector_core::metrics::init_test() {
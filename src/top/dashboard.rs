@@ -1,4 +1,4 @@
-use std::io::stdout;
+use std::{cmp::Reverse, collections::BTreeMap, io::stdout};
 
 use crossterm::{
     cursor::Show,
@@ -8,6 +8,7 @@ use crossterm::{
     tty::IsTty,
     ExecutableCommand,
 };
+use glob::Pattern;
 use num_format::{Locale, ToFormattedString};
 use number_prefix::NumberPrefix;
 use tokio::sync::oneshot;
@@ -16,14 +17,15 @@ use tui::{
     layout::{Alignment, Constraint, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Span, Spans},
-    widgets::{Block, Borders, Cell, Paragraph, Row, Table, Wrap},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState, Wrap},
     Frame, Terminal,
 };
 
 use super::{
     events::capture_key_press,
-    state::{self, ConnectionStatus},
+    state::{self, ComponentRow, ConnectionStatus, SortBy},
 };
+use crate::config::ComponentKey;
 
 /// Format metrics, with thousands separation
 trait ThousandsFormatter {
@@ -104,7 +106,15 @@ fn format_metric(total: i64, throughput: i64, human_metrics: bool) -> String {
     }
 }
 
-const NUM_COLUMNS: usize = 8;
+/// Format a utilization, from 0 to 1, as a percentage
+fn format_utilization(utilization: Option<f64>) -> String {
+    match utilization {
+        Some(u) if u.is_finite() => format!("{:.0}%", u * 100.0),
+        _ => "--".to_string(),
+    }
+}
+
+const NUM_COLUMNS: usize = 9;
 static HEADER: [&str; NUM_COLUMNS] = [
     "ID",
     "Output",
@@ -114,8 +124,140 @@ static HEADER: [&str; NUM_COLUMNS] = [
     "Events Out",
     "Bytes",
     "Errors",
+    "Busy %",
 ];
 
+static ERRORS_HEADER: [&str; 4] = ["Error type", "Stage", "Errors", "Rate"];
+
+/// The state of the interactive controls of the dashboard.
+struct Controls {
+    sort: SortBy,
+    filter: Option<Pattern>,
+    /// The filter being typed, after pressing '/'
+    filter_input: Option<String>,
+    /// Tracked by key, so that the selection follows the component as rows are re-sorted
+    selected: Option<ComponentKey>,
+    show_errors: bool,
+}
+
+impl Controls {
+    fn new(opts: &super::Opts) -> Self {
+        Self {
+            sort: opts.sort,
+            filter: opts.components.clone(),
+            filter_input: None,
+            selected: None,
+            show_errors: false,
+        }
+    }
+
+    /// Returns the components matching the filter, in the selected order. Components with
+    /// equal metrics are listed by ID.
+    fn visible_components<'a>(&self, state: &'a state::State) -> Vec<&'a ComponentRow> {
+        let mut rows = state
+            .components
+            .values()
+            .filter(|r| {
+                self.filter
+                    .as_ref()
+                    .map_or(true, |pattern| pattern.matches(r.key.id()))
+            })
+            .collect::<Vec<_>>();
+
+        match self.sort {
+            SortBy::Id => {}
+            SortBy::Throughput => rows.sort_by_key(|r| {
+                Reverse(
+                    r.received_events_throughput_sec
+                        .max(r.sent_events_throughput_sec),
+                )
+            }),
+            SortBy::Errors => rows.sort_by_key(|r| Reverse((r.errors_throughput_sec, r.errors))),
+        }
+
+        rows
+    }
+
+    /// Returns the selected component, if it's visible.
+    fn selected_component<'a>(&self, state: &'a state::State) -> Option<&'a ComponentRow> {
+        let key = self.selected.as_ref()?;
+        self.visible_components(state)
+            .into_iter()
+            .find(|r| &r.key == key)
+    }
+
+    /// Moves the selection by `offset` rows, staying within the visible components.
+    fn move_selection(&mut self, state: &state::State, offset: isize) {
+        let keys = self
+            .visible_components(state)
+            .into_iter()
+            .map(|r| r.key.clone())
+            .collect::<Vec<_>>();
+        if keys.is_empty() {
+            self.selected = None;
+            return;
+        }
+
+        let index = match self
+            .selected
+            .as_ref()
+            .and_then(|selected| keys.iter().position(|key| key == selected))
+        {
+            Some(index) => (index as isize + offset).clamp(0, keys.len() as isize - 1) as usize,
+            None => 0,
+        };
+        self.selected = Some(keys[index].clone());
+    }
+
+    /// Updates the controls from a key press. Returns whether the dashboard should be exited.
+    fn handle_key(&mut self, key: KeyCode, state: &state::State) -> bool {
+        if let Some(input) = &mut self.filter_input {
+            match key {
+                KeyCode::Char(c) => input.push(c),
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Esc => self.filter_input = None,
+                // Invalid patterns are left in the input, to be corrected
+                KeyCode::Enter => {
+                    if input.is_empty() {
+                        self.filter = None;
+                        self.filter_input = None;
+                    } else if let Ok(pattern) = Pattern::new(input) {
+                        self.filter = Some(pattern);
+                        self.filter_input = None;
+                    }
+                }
+                _ => {}
+            }
+            return false;
+        }
+
+        match key {
+            KeyCode::Esc | KeyCode::Char('q') => return true,
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(state, -1),
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(state, 1),
+            KeyCode::Enter => {
+                if self.selected_component(state).is_none() {
+                    self.move_selection(state, 0);
+                }
+                self.show_errors = !self.show_errors;
+            }
+            KeyCode::Char('s') => self.sort = self.sort.next(),
+            KeyCode::Char('/') => {
+                self.filter_input = Some(
+                    self.filter
+                        .as_ref()
+                        .map(|pattern| pattern.as_str().to_owned())
+                        .unwrap_or_default(),
+                );
+            }
+            _ => {}
+        }
+        false
+    }
+}
+
 struct Widgets<'a> {
     constraints: Vec<Constraint>,
     url_string: &'a str,
@@ -167,8 +309,14 @@ impl<'a> Widgets<'a> {
     }
 
     /// Renders a components table, showing sources, transforms and sinks in tabular form, with
-    /// statistics pulled from `ComponentsState`,
-    fn components_table<B: Backend>(&self, f: &mut Frame<B>, state: &state::State, area: Rect) {
+    /// statistics pulled from `ComponentsState`, filtered and sorted as per `Controls`.
+    fn components_table<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        state: &state::State,
+        controls: &Controls,
+        area: Rect,
+    ) {
         // Header columns
         let header = HEADER
             .iter()
@@ -177,7 +325,12 @@ impl<'a> Widgets<'a> {
 
         // Data columns
         let mut items = Vec::new();
-        for (_, r) in state.components.iter() {
+        let mut selected = None;
+        for r in controls.visible_components(state) {
+            if controls.selected.as_ref() == Some(&r.key) {
+                selected = Some(items.len());
+            }
+
             let mut data = vec![
                 r.key.id().to_string(),
                 (!r.has_displayable_outputs())
@@ -209,6 +362,7 @@ impl<'a> Widgets<'a> {
                 } else {
                     r.errors.thousands_format()
                 },
+                format_utilization(r.utilization),
             ];
 
             data.extend_from_slice(&formatted_metrics);
@@ -233,19 +387,78 @@ impl<'a> Widgets<'a> {
             }
         }
 
+        let title = match &controls.filter {
+            Some(pattern) => format!(
+                "Components (sorted by {}, matching '{}')",
+                controls.sort, pattern
+            ),
+            None => format!("Components (sorted by {})", controls.sort),
+        };
+
         let w = Table::new(items)
             .header(Row::new(header).bottom_margin(1))
-            .block(Block::default().borders(Borders::ALL).title("Components"))
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
             .column_spacing(2)
             .widths(&[
                 Constraint::Percentage(15), // ID
-                Constraint::Percentage(15), // Output
+                Constraint::Percentage(10), // Output
                 Constraint::Percentage(10), // Kind
                 Constraint::Percentage(10), // Type
                 Constraint::Percentage(10), // Events In
                 Constraint::Percentage(10), // Events Out
                 Constraint::Percentage(10), // Bytes
                 Constraint::Percentage(10), // Errors
+                Constraint::Percentage(10), // Busy %
+            ]);
+
+        // Selecting the row also scrolls the table to it
+        let mut table_state = TableState::default();
+        table_state.select(selected);
+
+        f.render_stateful_widget(w, area, &mut table_state);
+    }
+
+    /// Renders the errors of a component, by error type and stage, with the most frequent
+    /// errors first.
+    fn errors_table<B: Backend>(&self, f: &mut Frame<B>, row: &ComponentRow, area: Rect) {
+        let header = ERRORS_HEADER
+            .iter()
+            .map(|s| Cell::from(*s).style(Style::default().add_modifier(Modifier::BOLD)))
+            .collect::<Vec<_>>();
+
+        let mut error_types = row.error_types.iter().collect::<Vec<_>>();
+        error_types.sort_by_key(|e| Reverse((e.errors_throughput_sec, e.errors_total)));
+
+        let items = error_types
+            .into_iter()
+            .map(|e| {
+                Row::new(vec![
+                    e.error_type.clone(),
+                    e.stage.clone().unwrap_or_else(|| "--".to_string()),
+                    if self.opts.human_metrics {
+                        e.errors_total.human_format()
+                    } else {
+                        e.errors_total.thousands_format()
+                    },
+                    format!("{}/s", e.errors_throughput_sec.human_format()),
+                ])
+            })
+            .collect::<Vec<_>>();
+
+        let w = Table::new(items)
+            .header(Row::new(header).bottom_margin(1))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!("Errors of {}", row.key.id())),
+            )
+            .column_spacing(2)
+            .widths(&[
+                Constraint::Percentage(40), // Error type
+                Constraint::Percentage(20), // Stage
+                Constraint::Percentage(20), // Errors
+                Constraint::Percentage(20), // Rate
             ]);
 
         f.render_widget(w, area);
@@ -261,9 +474,27 @@ impl<'a> Widgets<'a> {
         f.render_widget(w, area);
     }
 
-    /// Renders a box showing instructions on how to exit from `vector top`.
-    fn quit_box<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
-        let text = vec![Spans::from("To quit, press ESC or 'q'")];
+    /// Renders a box showing instructions on how to use and exit from `vector top`, or the
+    /// filter being typed.
+    fn quit_box<B: Backend>(&self, f: &mut Frame<B>, controls: &Controls, area: Rect) {
+        let text = match &controls.filter_input {
+            Some(input) => {
+                let mut spans = vec![Span::from(format!(
+                    "Filter components by ID: {}_ (ENTER to apply, ESC to cancel)",
+                    input
+                ))];
+                if !input.is_empty() && Pattern::new(input).is_err() {
+                    spans.push(Span::styled(
+                        " | Invalid pattern",
+                        Style::default().fg(Color::Red),
+                    ));
+                }
+                vec![Spans::from(spans)]
+            }
+            None => vec![Spans::from(
+                "To quit, press ESC or 'q' | Select: up/down | Errors: ENTER | Sort: 's' | Filter: '/'",
+            )],
+        };
 
         let block = Block::default()
             .borders(Borders::ALL)
@@ -277,7 +508,7 @@ impl<'a> Widgets<'a> {
     }
 
     /// Draw a single frame. Creates a layout and renders widgets into it.
-    fn draw<B: Backend>(&self, f: &mut Frame<B>, state: state::State) {
+    fn draw<B: Backend>(&self, f: &mut Frame<B>, state: &state::State, controls: &Controls) {
         let size = f.size();
         let rects = Layout::default()
             .constraints(self.constraints.clone())
@@ -287,12 +518,24 @@ impl<'a> Widgets<'a> {
 
         // Require a minimum of 80 chars of line width to display the table
         if size.width >= 80 {
-            self.components_table(f, &state, rects[1]);
+            match controls
+                .selected_component(state)
+                .filter(|_| controls.show_errors)
+            {
+                Some(row) => {
+                    let panes = Layout::default()
+                        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+                        .split(rects[1]);
+                    self.components_table(f, state, controls, panes[0]);
+                    self.errors_table(f, row, panes[1]);
+                }
+                None => self.components_table(f, state, controls, rects[1]),
+            }
         } else {
             self.components_resize_window(f, rects[1]);
         }
 
-        self.quit_box(f, rects[2]);
+        self.quit_box(f, controls, rects[2]);
     }
 }
 
@@ -311,7 +554,7 @@ pub async fn init_dashboard<'a>(
     mut state_rx: state::StateRx,
     mut shutdown_rx: oneshot::Receiver<()>,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    // Capture key presses, to determine when to quit
+    // Capture key presses, to control the dashboard and determine when to quit
     let (mut key_press_rx, key_press_kill_tx) = capture_key_press();
 
     // Write to stdout, and enter an alternate screen, to avoid overwriting existing
@@ -331,17 +574,21 @@ pub async fn init_dashboard<'a>(
     terminal.clear()?;
 
     let widgets = Widgets::new(url, opts);
+    let mut controls = Controls::new(opts);
+    let mut state = state::State::new(BTreeMap::new());
 
     loop {
         tokio::select! {
-            Some(state) = state_rx.recv() => {
-                terminal.draw(|f| widgets.draw(f, state))?;
+            Some(new_state) = state_rx.recv() => {
+                state = new_state;
+                terminal.draw(|f| widgets.draw(f, &state, &controls))?;
             },
             k = key_press_rx.recv() => {
-                if let KeyCode::Esc | KeyCode::Char('q') = k.unwrap() {
+                if controls.handle_key(k.unwrap(), &state) {
                     let _ = key_press_kill_tx.send(());
                     break
                 }
+                terminal.draw(|f| widgets.draw(f, &state, &controls))?;
             }
             _ = &mut shutdown_rx => {
                 let _ = key_press_kill_tx.send(());
@@ -362,8 +609,100 @@ pub async fn init_dashboard<'a>(
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
     use super::*;
 
+    fn row(id: &str, throughput: i64, errors_throughput: i64) -> ComponentRow {
+        ComponentRow {
+            key: ComponentKey::from(id),
+            kind: "source".to_string(),
+            component_type: "demo_logs".to_string(),
+            outputs: HashMap::new(),
+            processed_bytes_total: 0,
+            processed_bytes_throughput_sec: 0,
+            received_events_total: 0,
+            received_events_throughput_sec: 0,
+            sent_events_total: 0,
+            sent_events_throughput_sec: throughput,
+            errors: errors_throughput,
+            errors_throughput_sec: errors_throughput,
+            error_types: Vec::new(),
+            utilization: None,
+        }
+    }
+
+    fn controls(sort: SortBy, filter: Option<&str>) -> Controls {
+        Controls {
+            sort,
+            filter: filter.map(|filter| Pattern::new(filter).unwrap()),
+            filter_input: None,
+            selected: None,
+            show_errors: false,
+        }
+    }
+
+    fn visible_ids(state: &state::State, controls: &Controls) -> Vec<String> {
+        controls
+            .visible_components(state)
+            .into_iter()
+            .map(|r| r.key.id().to_string())
+            .collect()
+    }
+
+    #[test]
+    /// Components should be filtered by glob, and sorted with ties listed by ID
+    fn filter_and_sort_components() {
+        let state = state::State::new(
+            [
+                row("http_a", 10, 0),
+                row("http_b", 30, 5),
+                row("kafka", 20, 1),
+                row("http_c", 10, 5),
+            ]
+            .into_iter()
+            .map(|r| (r.key.clone(), r))
+            .collect(),
+        );
+
+        assert_eq!(
+            visible_ids(&state, &controls(SortBy::Id, None)),
+            vec!["http_a", "http_b", "http_c", "kafka"]
+        );
+        assert_eq!(
+            visible_ids(&state, &controls(SortBy::Throughput, None)),
+            vec!["http_b", "kafka", "http_a", "http_c"]
+        );
+        assert_eq!(
+            visible_ids(&state, &controls(SortBy::Errors, Some("http_*"))),
+            vec!["http_b", "http_c", "http_a"]
+        );
+    }
+
+    #[test]
+    /// The selection should follow the component, and stay within the visible components
+    fn move_selection() {
+        let state = state::State::new(
+            [row("a", 1, 0), row("b", 2, 0), row("c", 3, 0)]
+                .into_iter()
+                .map(|r| (r.key.clone(), r))
+                .collect(),
+        );
+        let mut controls = controls(SortBy::Id, None);
+
+        controls.move_selection(&state, 1);
+        assert_eq!(controls.selected, Some(ComponentKey::from("a")));
+        controls.move_selection(&state, 1);
+        controls.move_selection(&state, 1);
+        controls.move_selection(&state, 1);
+        assert_eq!(controls.selected, Some(ComponentKey::from("c")));
+
+        controls.handle_key(KeyCode::Char('s'), &state);
+        assert_eq!(controls.sort, SortBy::Throughput);
+        controls.move_selection(&state, -1);
+        assert_eq!(controls.selected, Some(ComponentKey::from("c")));
+    }
+
     #[test]
     /// Zero should be formatted as "--" in all cases
     fn format_zero() {
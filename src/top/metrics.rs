@@ -11,7 +11,10 @@ use vector_api_client::{
 };
 
 use super::state::{self, OutputMetrics};
-use crate::{config::ComponentKey, top::state::SentEventsMetric};
+use crate::{
+    config::ComponentKey,
+    top::state::{ErrorTypeMetric, SentEventsMetric},
+};
 
 /// Components that have been added
 async fn component_added(client: Arc<SubscriptionClient>, tx: state::EventTx) {
@@ -36,6 +39,9 @@ async fn component_added(client: Arc<SubscriptionClient>, tx: state::EventTx) {
                     processed_bytes_total: 0,
                     processed_bytes_throughput_sec: 0,
                     errors: 0,
+                    errors_throughput_sec: 0,
+                    error_types: Vec::new(),
+                    utilization: None,
                 }))
                 .await;
         }
@@ -236,6 +242,52 @@ async fn errors_totals(client: Arc<SubscriptionClient>, tx: state::EventTx, inte
     }
 }
 
+async fn error_types(client: Arc<SubscriptionClient>, tx: state::EventTx, interval: i64) {
+    tokio::pin! {
+        let stream = client.component_error_types_subscription(interval);
+    };
+
+    while let Some(Some(res)) = stream.next().await {
+        if let Some(d) = res.data {
+            let c = d.component_error_types;
+            let _ = tx
+                .send(state::EventType::ErrorTypes(
+                    interval,
+                    c.into_iter()
+                        .map(|c| ErrorTypeMetric {
+                            key: ComponentKey::from(c.component_id.as_str()),
+                            // Errors reported without a type are labelled by their metric
+                            error_type: c.error_type.unwrap_or(c.name),
+                            stage: c.stage,
+                            total: c.errors_total as i64,
+                            throughput: c.throughput as i64,
+                        })
+                        .collect(),
+                ))
+                .await;
+        }
+    }
+}
+
+async fn utilizations(client: Arc<SubscriptionClient>, tx: state::EventTx, interval: i64) {
+    tokio::pin! {
+        let stream = client.component_utilizations_subscription(interval);
+    };
+
+    while let Some(Some(res)) = stream.next().await {
+        if let Some(d) = res.data {
+            let c = d.component_utilizations;
+            let _ = tx
+                .send(state::EventType::Utilizations(
+                    c.into_iter()
+                        .map(|c| (ComponentKey::from(c.component_id.as_str()), c.utilization))
+                        .collect(),
+                ))
+                .await;
+        }
+    }
+}
+
 /// Subscribe to each metrics channel through a separate client. This is a temporary workaround
 /// until client multiplexing is fixed. In future, we should be able to use a single client
 pub fn subscribe(
@@ -278,7 +330,9 @@ pub fn subscribe(
             tx.clone(),
             interval,
         )),
-        tokio::spawn(errors_totals(Arc::clone(&client), tx, interval)),
+        tokio::spawn(errors_totals(Arc::clone(&client), tx.clone(), interval)),
+        tokio::spawn(error_types(Arc::clone(&client), tx.clone(), interval)),
+        tokio::spawn(utilizations(Arc::clone(&client), tx, interval)),
     ]
 }
 
@@ -322,6 +376,9 @@ pub async fn init_components(client: &Client) -> Result<state::State, ()> {
                         processed_bytes_total: d.on.processed_bytes_total(),
                         processed_bytes_throughput_sec: 0,
                         errors: 0,
+                        errors_throughput_sec: 0,
+                        error_types: Vec::new(),
+                        utilization: None,
                     },
                 ))
             })
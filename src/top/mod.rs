@@ -7,6 +7,7 @@ mod state;
 use clap::Parser;
 pub use cmd::cmd;
 use url::Url;
+use vector_api_client::ConnectOptions;
 
 #[derive(Parser, Debug, Clone)]
 #[command(rename_all = "kebab-case")]
@@ -26,4 +27,15 @@ pub struct Opts {
     /// Whether to reconnect if the underlying Vector API connection drops. By default, top will attempt to reconnect if the connection drops.
     #[arg(short, long)]
     no_reconnect: bool,
+
+    /// Only show the components with an ID matching this glob pattern, e.g. `http_*`. The filter can also be changed from the dashboard by pressing '/'
+    #[arg(long)]
+    components: Option<glob::Pattern>,
+
+    /// Order in which to list the components. The order can also be changed from the dashboard by pressing 's'
+    #[arg(long, value_enum, default_value = "id")]
+    sort: state::SortBy,
+
+    #[command(flatten)]
+    connect: ConnectOptions,
 }
@@ -18,6 +18,15 @@ pub struct SentEventsMetric {
     pub outputs: HashMap<String, i64>,
 }
 
+#[derive(Debug)]
+pub struct ErrorTypeMetric {
+    pub key: ComponentKey,
+    pub error_type: String,
+    pub stage: Option<String>,
+    pub total: i64,
+    pub throughput: i64,
+}
+
 #[derive(Debug)]
 pub enum EventType {
     InitializeState(State),
@@ -32,6 +41,10 @@ pub enum EventType {
     /// Interval + identified metric
     ProcessedBytesThroughputs(i64, Vec<IdentifiedMetric>),
     ErrorsTotals(Vec<IdentifiedMetric>),
+    /// Interval in ms + errors by type and stage
+    ErrorTypes(i64, Vec<ErrorTypeMetric>),
+    /// Fraction of time spent processing events, from 0 to 1
+    Utilizations(Vec<(ComponentKey, f64)>),
     ComponentAdded(ComponentRow),
     ComponentRemoved(ComponentKey),
     ConnectionUpdated(ConnectionStatus),
@@ -73,6 +86,38 @@ impl Display for ConnectionStatus {
     }
 }
 
+/// The order in which components are listed.
+#[derive(clap::ValueEnum, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SortBy {
+    /// By component ID
+    Id,
+    /// By events throughput, highest first
+    Throughput,
+    /// By error rate, highest first
+    Errors,
+}
+
+impl SortBy {
+    /// The order to switch to when cycling through them from the dashboard
+    pub const fn next(self) -> Self {
+        match self {
+            SortBy::Id => SortBy::Throughput,
+            SortBy::Throughput => SortBy::Errors,
+            SortBy::Errors => SortBy::Id,
+        }
+    }
+}
+
+impl Display for SortBy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SortBy::Id => write!(f, "ID"),
+            SortBy::Throughput => write!(f, "throughput"),
+            SortBy::Errors => write!(f, "error rate"),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct State {
     pub connection_status: ConnectionStatus,
@@ -106,6 +151,14 @@ impl From<i64> for OutputMetrics {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct ErrorTypeMetrics {
+    pub error_type: String,
+    pub stage: Option<String>,
+    pub errors_total: i64,
+    pub errors_throughput_sec: i64,
+}
+
 #[derive(Debug, Clone)]
 pub struct ComponentRow {
     pub key: ComponentKey,
@@ -119,6 +172,10 @@ pub struct ComponentRow {
     pub sent_events_total: i64,
     pub sent_events_throughput_sec: i64,
     pub errors: i64,
+    pub errors_throughput_sec: i64,
+    pub error_types: Vec<ErrorTypeMetrics>,
+    /// Only reported by transforms and sinks
+    pub utilization: Option<f64>,
 }
 
 impl ComponentRow {
@@ -208,6 +265,36 @@ pub async fn updater(mut event_rx: EventRx) -> StateRx {
                         }
                     }
                 }
+                EventType::ErrorTypes(interval, rows) => {
+                    let mut by_component = BTreeMap::<_, Vec<_>>::new();
+                    for m in rows {
+                        by_component
+                            .entry(m.key)
+                            .or_default()
+                            .push(ErrorTypeMetrics {
+                                error_type: m.error_type,
+                                stage: m.stage,
+                                errors_total: m.total,
+                                errors_throughput_sec: (m.throughput as f64
+                                    * (1000.0 / interval as f64))
+                                    as i64,
+                            });
+                    }
+                    for (key, error_types) in by_component {
+                        if let Some(r) = state.components.get_mut(&key) {
+                            r.errors_throughput_sec =
+                                error_types.iter().map(|e| e.errors_throughput_sec).sum();
+                            r.error_types = error_types;
+                        }
+                    }
+                }
+                EventType::Utilizations(rows) => {
+                    for (key, v) in rows {
+                        if let Some(r) = state.components.get_mut(&key) {
+                            r.utilization = Some(v);
+                        }
+                    }
+                }
                 EventType::ComponentAdded(c) => {
                     let _ = state.components.insert(c.key.clone(), c);
                 }
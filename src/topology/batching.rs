@@ -0,0 +1,191 @@
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures::{Future, Stream, StreamExt};
+use tokio::time::{sleep, Sleep};
+use vector_core::config::InterComponentBatchConfig;
+
+use crate::event::{EventArray, EventContainer};
+
+/// A stream combinator merging the `EventArray`s sent between components into larger batches.
+///
+/// Unlike `ReadyArrays`, this merges the arrays themselves, so that the next component receives,
+/// and sends along, fewer arrays. Consecutive arrays are merged as long as they are of the same
+/// type and their total number of events is within `max_events`. A batch is flushed once it is
+/// full, and otherwise once the inner stream is pending, after waiting up to `timeout` for more
+/// arrays if set.
+pub struct BatchedArrays<T> {
+    inner: T,
+    max_events: usize,
+    timeout: Option<Duration>,
+    batch: Option<EventArray>,
+    deadline: Option<Pin<Box<Sleep>>>,
+    done: bool,
+}
+
+impl<T> BatchedArrays<T>
+where
+    T: Stream<Item = EventArray> + Unpin,
+{
+    pub fn new(inner: T, config: &InterComponentBatchConfig) -> Self {
+        Self {
+            inner,
+            max_events: config.max_events.get(),
+            timeout: config.timeout(),
+            batch: None,
+            deadline: None,
+            done: false,
+        }
+    }
+
+    fn flush(&mut self) -> Option<EventArray> {
+        self.deadline = None;
+        self.batch.take()
+    }
+
+    /// Adds an array to the current batch, returning the batch if the array could not be merged
+    /// into it.
+    fn push(&mut self, array: EventArray) -> Option<EventArray> {
+        match self.batch.take() {
+            None => {
+                self.batch = Some(array);
+                None
+            }
+            Some(batch) => match merge(batch, array, self.max_events) {
+                Ok(merged) => {
+                    self.batch = Some(merged);
+                    None
+                }
+                Err((batch, array)) => {
+                    self.deadline = None;
+                    self.batch = Some(array);
+                    Some(batch)
+                }
+            },
+        }
+    }
+}
+
+impl<T> Stream for BatchedArrays<T>
+where
+    T: Stream<Item = EventArray> + Unpin,
+{
+    type Item = EventArray;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.done {
+            return Poll::Ready(self.flush());
+        }
+
+        loop {
+            match self.inner.poll_next_unpin(cx) {
+                Poll::Ready(Some(array)) => {
+                    if let Some(batch) = self.push(array) {
+                        return Poll::Ready(Some(batch));
+                    }
+                    if self.batch.as_ref().map_or(0, EventContainer::len) >= self.max_events {
+                        return Poll::Ready(self.flush());
+                    }
+                }
+                Poll::Ready(None) => {
+                    // Flush the last batch, if any, and signal that we're complete next time
+                    // we're polled.
+                    self.done = true;
+                    return Poll::Ready(self.flush());
+                }
+                Poll::Pending => {
+                    if self.batch.is_none() {
+                        return Poll::Pending;
+                    }
+                    let timeout = match self.timeout {
+                        Some(timeout) => timeout,
+                        None => return Poll::Ready(self.flush()),
+                    };
+                    let deadline = self
+                        .deadline
+                        .get_or_insert_with(|| Box::pin(sleep(timeout)));
+                    return match deadline.as_mut().poll(cx) {
+                        Poll::Ready(()) => Poll::Ready(self.flush()),
+                        Poll::Pending => Poll::Pending,
+                    };
+                }
+            }
+        }
+    }
+}
+
+fn merge(
+    batch: EventArray,
+    array: EventArray,
+    max_events: usize,
+) -> Result<EventArray, (EventArray, EventArray)> {
+    if batch.len() + array.len() > max_events {
+        return Err((batch, array));
+    }
+    match (batch, array) {
+        (EventArray::Logs(mut batch), EventArray::Logs(array)) => {
+            batch.extend(array);
+            Ok(EventArray::Logs(batch))
+        }
+        (EventArray::Metrics(mut batch), EventArray::Metrics(array)) => {
+            batch.extend(array);
+            Ok(EventArray::Metrics(batch))
+        }
+        (EventArray::Traces(mut batch), EventArray::Traces(array)) => {
+            batch.extend(array);
+            Ok(EventArray::Traces(batch))
+        }
+        (batch, array) => Err((batch, array)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use futures::{stream, StreamExt};
+    use vector_core::event::{LogEvent, Metric, MetricKind, MetricValue};
+
+    use super::*;
+
+    fn logs(count: usize) -> EventArray {
+        EventArray::Logs((0..count).map(|_| LogEvent::from("message")).collect())
+    }
+
+    fn config(max_events: usize) -> InterComponentBatchConfig {
+        InterComponentBatchConfig {
+            max_events: NonZeroUsize::new(max_events).unwrap(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn merges_ready_arrays() {
+        let arrays = vec![logs(1), logs(1), logs(3), logs(2), logs(10)];
+        let batches = BatchedArrays::new(stream::iter(arrays), &config(5))
+            .map(|batch| batch.len())
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(batches, vec![5, 2, 10]);
+    }
+
+    #[tokio::test]
+    async fn keeps_event_types_apart() {
+        let metric = Metric::new(
+            "name",
+            MetricKind::Absolute,
+            MetricValue::Counter { value: 1.0 },
+        );
+        let arrays = vec![logs(1), metric.into(), logs(1)];
+        let batches = BatchedArrays::new(stream::iter(arrays), &config(5))
+            .map(|batch| batch.len())
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(batches, vec![1, 1, 1]);
+    }
+}
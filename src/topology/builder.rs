@@ -6,7 +6,11 @@ use std::{
     time::Instant,
 };
 
-use futures::{stream::FuturesOrdered, FutureExt, StreamExt, TryStreamExt};
+use futures::{
+    future::{self, BoxFuture},
+    stream::{self, FuturesOrdered},
+    FutureExt, StreamExt, TryStreamExt,
+};
 use futures_util::stream::FuturesUnordered;
 use once_cell::sync::Lazy;
 use stream_cancel::{StreamExt as StreamCancelExt, Trigger, Tripwire};
@@ -15,6 +19,7 @@ use tokio::{
     sync::oneshot,
     time::{timeout, Duration},
 };
+use tokio_stream::wrappers::ReceiverStream;
 use tracing::Instrument;
 use vector_config::NamedComponent;
 use vector_core::{
@@ -23,15 +28,21 @@ use vector_core::{
             builder::TopologyBuilder,
             channel::{BufferReceiver, BufferSender},
         },
-        BufferType, WhenFull,
+        BufferType, EventCount, WhenFull,
     },
+    config::{GlobalOptions, InterComponentBatchConfig, UtilizationOptions},
     internal_event::EventsSent,
+    memory::MemoryBudget,
     schema::Definition,
     ByteSizeOf,
 };
 
 use super::{
+    batching::BatchedArrays,
     fanout::{self, Fanout},
+    parallel::{EventPartitioner, InFlight},
+    pause::{self, Pausable, PausableKind},
+    scheduling::DedicatedRuntime,
     schema,
     task::{Task, TaskOutput, TaskResult},
     BuiltBuffer, ConfigDiff,
@@ -40,16 +51,20 @@ use crate::{
     config::{
         ComponentKey, DataType, EnrichmentTableConfig, Input, Output, OutputId, ProxyConfig,
         SinkConfig, SinkContext, SourceConfig, SourceContext, TransformConfig, TransformContext,
-        TransformOuter,
+        TransformOuter, TransformPartitioningOptions, TransformSchedulingOptions,
     },
     event::{EventArray, EventContainer},
     internal_events::EventsReceived,
     shutdown::SourceShutdownCoordinator,
     source_sender::CHUNK_SIZE,
     spawn_named,
+    tenancy::TenantLimiter,
     topology::task::TaskError,
-    transforms::{SyncTransform, TaskTransform, Transform, TransformOutputs, TransformOutputsBuf},
-    utilization::wrap,
+    transforms::{
+        OutputsTaskTransform, SyncTransform, TaskTransform, Transform, TransformOutputs,
+        TransformOutputsBuf,
+    },
+    utilization::{wrap, OutputsTimer, Timer},
     SourceSender,
 };
 
@@ -154,6 +169,9 @@ pub async fn build_pieces(
     let (enrichment_tables, enrichment_errors) = load_enrichment_tables(config, diff).await;
     errors.extend(enrichment_errors);
 
+    let memory_budget = MemoryBudget::global(&config.global.memory);
+    let tenant_limiter = TenantLimiter::global(&config.global.tenancy);
+
     // Build sources
     for (key, source) in config
         .sources()
@@ -180,20 +198,36 @@ pub async fn build_pieces(
 
         let mut builder = {
             let _span = span.enter();
-            SourceSender::builder().with_buffer(*SOURCE_SENDER_BUFFER_SIZE)
+            SourceSender::builder()
+                .with_buffer(*SOURCE_SENDER_BUFFER_SIZE)
+                .with_memory_budget(memory_budget.clone())
         };
         let mut pumps = Vec::new();
         let mut controls = HashMap::new();
         let mut schema_definitions = HashMap::with_capacity(source_outputs.len());
+        let paused = pause::register(key, PausableKind::Source);
 
         for output in source_outputs {
-            let mut rx = builder.add_output(output.clone());
+            let mut rx = BatchedArrays::new(
+                Pausable::new(
+                    builder.add_output(output.clone()).into_stream(),
+                    paused.clone(),
+                ),
+                &config.global.batch.inter_component,
+            );
 
             let (mut fanout, control) = Fanout::new();
+            let source = key.clone();
             let pump = async move {
                 debug!("Source pump starting.");
 
-                while let Some(array) = rx.next().await {
+                while let Some(mut array) = rx.next().await {
+                    if let Some(limiter) = tenant_limiter {
+                        limiter.limit(&source, &mut array);
+                        if array.event_count() == 0 {
+                            continue;
+                        }
+                    }
                     fanout.send(array).await.map_err(|e| {
                         debug!("Source pump finished with an error.");
                         TaskError::wrapped(e)
@@ -266,8 +300,15 @@ pub async fn build_pieces(
             acknowledgements: source.sink_acknowledgements,
             schema_definitions,
             schema: config.schema,
+            once: config.once,
         };
-        let server = match source.inner.build(context).await {
+        let build_span = info_span!(
+            "build",
+            component_kind = "source",
+            component_id = %key.id(),
+            component_type = %typetag,
+        );
+        let server = match source.inner.build(context).instrument(build_span).await {
             Err(error) => {
                 errors.push(format!("Source \"{}\": {}", key, error));
                 continue;
@@ -362,22 +403,44 @@ pub async fn build_pieces(
             merged_schema_definition: merged_definition.clone(),
         };
 
-        let node = TransformNode::from_parts(key.clone(), transform, &merged_definition);
+        let node =
+            TransformNode::from_parts(key.clone(), transform, &merged_definition, &config.global);
 
-        let transform = match transform.inner.build(&context).await {
+        let runtime = match DedicatedRuntime::build(key, &transform.scheduling) {
             Err(error) => {
                 errors.push(format!("Transform \"{}\": {}", key, error));
                 continue;
             }
-            Ok(transform) => transform,
+            Ok(runtime) => runtime,
         };
 
+        // Transforms with `parallelism` are built once per copy, so that each copy has its own state.
+        let copies = transform.parallelism.map_or(1, NonZeroUsize::get);
+        let build_span = info_span!(
+            "build",
+            component_kind = "transform",
+            component_id = %key.id(),
+            component_type = %transform.inner.get_component_name(),
+        );
+        let transforms =
+            match future::try_join_all((0..copies).map(|_| transform.inner.build(&context)))
+                .instrument(build_span)
+                .await
+            {
+                Err(error) => {
+                    errors.push(format!("Transform \"{}\": {}", key, error));
+                    continue;
+                }
+                Ok(transforms) => transforms,
+            };
+
         let (input_tx, input_rx) =
             TopologyBuilder::standalone_memory(TOPOLOGY_BUFFER_SIZE, WhenFull::Block).await;
 
         inputs.insert(key.clone(), (input_tx, node.inputs.clone()));
 
-        let (transform_task, transform_outputs) = build_transform(transform, node, input_rx);
+        let (transform_task, transform_outputs) =
+            build_transform(transforms, node, input_rx, runtime);
 
         outputs.extend(transform_outputs);
         tasks.insert(key.clone(), transform_task);
@@ -439,9 +502,20 @@ pub async fn build_pieces(
             globals: config.global.clone(),
             proxy: ProxyConfig::merge_with_env(&config.global.proxy, sink.proxy()),
             schema: config.schema,
+            merged_schema_definition: schema::merged_definition(
+                sink_inputs,
+                config,
+                &mut definition_cache,
+            ),
         };
 
-        let (sink, healthcheck) = match sink.inner.build(cx).await {
+        let build_span = info_span!(
+            "build",
+            component_kind = "sink",
+            component_id = %key.id(),
+            component_type = typetag,
+        );
+        let (sink, healthcheck) = match sink.inner.build(cx).instrument(build_span).await {
             Err(error) => {
                 errors.push(format!("Sink \"{}\": {}", key, error));
                 continue;
@@ -450,6 +524,8 @@ pub async fn build_pieces(
         };
 
         let (trigger, tripwire) = Tripwire::new();
+        let paused = pause::register(key, PausableKind::Sink);
+        let utilization = config.global.utilization.clone();
 
         let sink = async move {
             debug!("Sink starting.");
@@ -466,10 +542,10 @@ pub async fn build_pieces(
                 .take()
                 .expect("Task started but input has been taken.");
 
-            let mut rx = wrap(rx);
+            let mut rx = wrap(rx, &utilization);
 
             sink.run(
-                rx.by_ref()
+                Pausable::new(rx.by_ref(), paused)
                     .filter(|events: &EventArray| ready(filter_events_type(events, input_type)))
                     .inspect(|events| {
                         emit!(EventsReceived {
@@ -586,6 +662,10 @@ struct TransformNode {
     input_details: Input,
     outputs: Vec<Output>,
     enable_concurrency: bool,
+    scheduling: TransformSchedulingOptions,
+    partitioning: TransformPartitioningOptions,
+    batch: InterComponentBatchConfig,
+    utilization: UtilizationOptions,
 }
 
 impl TransformNode {
@@ -593,6 +673,7 @@ impl TransformNode {
         key: ComponentKey,
         transform: &TransformOuter<OutputId>,
         schema_definition: &Definition,
+        globals: &GlobalOptions,
     ) -> Self {
         Self {
             key,
@@ -601,38 +682,97 @@ impl TransformNode {
             input_details: transform.inner.input(),
             outputs: transform.inner.outputs(schema_definition),
             enable_concurrency: transform.inner.enable_concurrency(),
+            scheduling: transform.scheduling.clone(),
+            partitioning: transform.partitioning.clone(),
+            batch: globals.batch.inter_component.clone(),
+            utilization: globals.utilization.clone(),
         }
     }
 }
 
 fn build_transform(
-    transform: Transform,
+    transforms: Vec<Transform>,
     node: TransformNode,
     input_rx: BufferReceiver<EventArray>,
+    runtime: Option<DedicatedRuntime>,
 ) -> (Task, HashMap<OutputId, fanout::ControlChannel>) {
-    match transform {
-        // TODO: avoid the double boxing for function transforms here
-        Transform::Function(t) => build_sync_transform(Box::new(t), node, input_rx),
-        Transform::Synchronous(t) => build_sync_transform(t, node, input_rx),
-        Transform::Task(t) => build_task_transform(
-            t,
+    // All of the copies of a transform with `parallelism` are of the same kind.
+    let mut sync_transforms = Vec::new();
+    let mut task_transforms = Vec::new();
+    let mut outputs_task_transforms = Vec::new();
+    for transform in transforms {
+        match transform {
+            // TODO: avoid the double boxing for function transforms here
+            Transform::Function(t) => sync_transforms.push(Box::new(t) as Box<dyn SyncTransform>),
+            Transform::Synchronous(t) => sync_transforms.push(t),
+            Transform::Task(t) => task_transforms.push(t),
+            Transform::OutputsTask(t) => outputs_task_transforms.push(t),
+        }
+    }
+
+    if !sync_transforms.is_empty() {
+        build_sync_transform(sync_transforms, node, input_rx, runtime)
+    } else if !outputs_task_transforms.is_empty() {
+        build_outputs_task_transform(outputs_task_transforms, node, input_rx, runtime)
+    } else if task_transforms.len() == 1 {
+        build_task_transform(
+            task_transforms.pop().expect("checked above"),
+            node,
             input_rx,
-            node.input_details.data_type(),
-            node.typetag,
-            &node.key,
-        ),
+            runtime,
+        )
+    } else {
+        build_parallel_task_transform(task_transforms, node, input_rx, runtime)
+    }
+}
+
+/// Runs the task of a transform on its dedicated worker threads, if it has any.
+fn schedule(
+    transform: BoxFuture<'static, TaskResult>,
+    runtime: Option<DedicatedRuntime>,
+) -> BoxFuture<'static, TaskResult> {
+    match runtime {
+        Some(runtime) => runtime.run(transform).boxed(),
+        None => transform,
     }
 }
 
 fn build_sync_transform(
-    t: Box<dyn SyncTransform>,
+    transforms: Vec<Box<dyn SyncTransform>>,
     node: TransformNode,
     input_rx: BufferReceiver<EventArray>,
+    runtime: Option<DedicatedRuntime>,
 ) -> (Task, HashMap<OutputId, fanout::ControlChannel>) {
     let (outputs, controls) = TransformOutputs::new(node.outputs);
 
-    let runner = Runner::new(t, input_rx, node.input_details.data_type(), outputs);
-    let transform = if node.enable_concurrency {
+    let copies = transforms.len();
+    let mut transforms = transforms.into_iter();
+    let t = transforms
+        .next()
+        .expect("at least one copy of the transform");
+
+    // Transforms with dedicated worker threads process up to one batch of events per thread at
+    // once, like the other transforms do with the shared worker threads.
+    let concurrency_limit = node
+        .scheduling
+        .worker_threads
+        .map_or(*TRANSFORM_CONCURRENCY_LIMIT, NonZeroUsize::get);
+    let runner = Runner::new(
+        t,
+        input_rx,
+        node.input_details.data_type(),
+        outputs,
+        concurrency_limit,
+        node.scheduling.max_in_flight_events,
+        node.batch.clone(),
+        &node.utilization,
+    );
+    let transform = if copies > 1 {
+        let partitioner = EventPartitioner::new(copies, node.partitioning.key_field.clone());
+        runner
+            .run_parallel(transforms.collect(), partitioner, node.partitioning.ordered)
+            .boxed()
+    } else if node.enable_concurrency {
         runner.run_concurrently().boxed()
     } else {
         runner.run_inline().boxed()
@@ -652,6 +792,7 @@ fn build_sync_transform(
             }
         }
     };
+    let transform = schedule(transform.boxed(), runtime);
 
     let mut output_controls = HashMap::new();
     for (name, control) in controls {
@@ -671,31 +812,51 @@ struct Runner {
     input_rx: Option<BufferReceiver<EventArray>>,
     input_type: DataType,
     outputs: TransformOutputs,
-    timer: crate::utilization::Timer,
+    concurrency_limit: usize,
+    max_in_flight_events: Option<NonZeroUsize>,
+    batch: InterComponentBatchConfig,
+    timer: Timer,
+    outputs_timer: Option<OutputsTimer>,
+    report_interval: Duration,
     last_report: Instant,
 }
 
 impl Runner {
+    #[allow(clippy::too_many_arguments)]
     fn new(
         transform: Box<dyn SyncTransform>,
         input_rx: BufferReceiver<EventArray>,
         input_type: DataType,
         outputs: TransformOutputs,
+        concurrency_limit: usize,
+        max_in_flight_events: Option<NonZeroUsize>,
+        batch: InterComponentBatchConfig,
+        utilization: &UtilizationOptions,
     ) -> Self {
+        // Only the transforms with several outputs report the utilization of each.
+        let outputs_timer = (outputs.output_count() > 1).then(|| OutputsTimer::new(utilization));
         Self {
             transform,
             input_rx: Some(input_rx),
             input_type,
             outputs,
-            timer: crate::utilization::Timer::new(),
+            concurrency_limit,
+            max_in_flight_events,
+            batch,
+            timer: Timer::new(utilization),
+            outputs_timer,
+            report_interval: utilization.report_interval(),
             last_report: Instant::now(),
         }
     }
 
     fn on_events_received(&mut self, events: &EventArray) {
         let stopped = self.timer.stop_wait();
-        if stopped.duration_since(self.last_report).as_secs() >= 5 {
+        if stopped.duration_since(self.last_report) >= self.report_interval {
             self.timer.report();
+            if let Some(outputs_timer) = self.outputs_timer.as_mut() {
+                outputs_timer.report(&mut self.outputs);
+            }
             self.last_report = stopped;
         }
 
@@ -716,12 +877,13 @@ impl Runner {
 
         let mut outputs_buf = self.outputs.new_buf_with_capacity(INLINE_BATCH_SIZE);
 
-        let mut input_rx = self
+        let input_rx = self
             .input_rx
             .take()
             .expect("can't run runner twice")
             .into_stream()
             .filter(move |events| ready(filter_events_type(events, self.input_type)));
+        let mut input_rx = BatchedArrays::new(input_rx, &self.batch);
 
         self.timer.start_wait();
         while let Some(events) = input_rx.next().await {
@@ -735,6 +897,14 @@ impl Runner {
         Ok(TaskOutput::Transform)
     }
 
+    /// Whether another batch of events can be processed, given the batches and events in flight.
+    fn has_capacity(&self, in_flight: usize, in_flight_events: usize) -> bool {
+        in_flight < self.concurrency_limit
+            && self
+                .max_in_flight_events
+                .map_or(true, |max| in_flight_events < max.get())
+    }
+
     async fn run_concurrently(mut self) -> TaskResult {
         let input_rx = self
             .input_rx
@@ -743,10 +913,13 @@ impl Runner {
             .into_stream()
             .filter(move |events| ready(filter_events_type(events, self.input_type)));
 
-        let mut input_rx =
-            super::ready_arrays::ReadyArrays::with_capacity(input_rx, READY_ARRAY_CAPACITY);
+        let capacity = self
+            .max_in_flight_events
+            .map_or(READY_ARRAY_CAPACITY, |max| max.min(READY_ARRAY_CAPACITY));
+        let mut input_rx = super::ready_arrays::ReadyArrays::with_capacity(input_rx, capacity);
 
         let mut in_flight = FuturesOrdered::new();
+        let mut in_flight_events = 0;
         let mut shutting_down = false;
 
         self.timer.start_wait();
@@ -756,7 +929,8 @@ impl Runner {
 
                 result = in_flight.next(), if !in_flight.is_empty() => {
                     match result {
-                        Some(Ok(outputs_buf)) => {
+                        Some(Ok((len, outputs_buf))) => {
+                            in_flight_events -= len;
                             let mut outputs_buf: TransformOutputsBuf = outputs_buf;
                             self.send_outputs(&mut outputs_buf).await
                                 .map_err(TaskError::wrapped)?;
@@ -765,7 +939,7 @@ impl Runner {
                     }
                 }
 
-                input_arrays = input_rx.next(), if in_flight.len() < *TRANSFORM_CONCURRENCY_LIMIT && !shutting_down => {
+                input_arrays = input_rx.next(), if self.has_capacity(in_flight.len(), in_flight_events) && !shutting_down => {
                     match input_arrays {
                         Some(input_arrays) => {
                             let mut len = 0;
@@ -774,13 +948,15 @@ impl Runner {
                                 len += events.len();
                             }
 
+                            in_flight_events += len;
+
                             let mut t = self.transform.clone();
                             let mut outputs_buf = self.outputs.new_buf_with_capacity(len);
                             let task = tokio::spawn(async move {
                                 for events in input_arrays {
                                     t.transform_all(events, &mut outputs_buf);
                                 }
-                                outputs_buf
+                                (len, outputs_buf)
                             }.in_current_span());
                             in_flight.push_back(task);
                         }
@@ -801,18 +977,120 @@ impl Runner {
 
         Ok(TaskOutput::Transform)
     }
+
+    /// Runs the copies of a transform with `parallelism`, each on its own task.
+    ///
+    /// The first copy is the transform of the runner, and the others are given.
+    async fn run_parallel(
+        mut self,
+        others: Vec<Box<dyn SyncTransform>>,
+        mut partitioner: EventPartitioner,
+        ordered: bool,
+    ) -> TaskResult {
+        type Job = (
+            EventArray,
+            TransformOutputsBuf,
+            oneshot::Sender<TransformOutputsBuf>,
+        );
+
+        let copies = std::iter::once(self.transform.clone()).chain(others);
+        let workers = copies
+            .map(|mut t| {
+                let (tx, mut rx) = tokio::sync::mpsc::channel::<Job>(1);
+                tokio::spawn(
+                    async move {
+                        while let Some((events, mut outputs_buf, reply)) = rx.recv().await {
+                            t.transform_all(events, &mut outputs_buf);
+                            let _ = reply.send(outputs_buf);
+                        }
+                    }
+                    .in_current_span(),
+                );
+                tx
+            })
+            .collect::<Vec<_>>();
+
+        let input_rx = self
+            .input_rx
+            .take()
+            .expect("can't run runner twice")
+            .into_stream()
+            .filter(move |events| ready(filter_events_type(events, self.input_type)));
+        let mut input_rx = BatchedArrays::new(input_rx, &self.batch);
+
+        // In ordered mode, the outputs of the copies for a batch of events are sent together, in
+        // the order the batches were received.
+        let mut in_flight = InFlight::new(ordered);
+        let mut in_flight_events = 0;
+        let mut shutting_down = false;
+
+        self.timer.start_wait();
+        loop {
+            tokio::select! {
+                biased;
+
+                result = in_flight.next(), if !in_flight.is_empty() => {
+                    let (len, outputs_bufs): (usize, Vec<TransformOutputsBuf>) = result
+                        .expect("in flight is not empty")
+                        .map_err(|_| TaskError::Panicked)?;
+                    in_flight_events -= len;
+                    for mut outputs_buf in outputs_bufs {
+                        self.send_outputs(&mut outputs_buf).await
+                            .map_err(TaskError::wrapped)?;
+                    }
+                }
+
+                events = input_rx.next(), if self.has_capacity(in_flight.len(), in_flight_events) && !shutting_down => {
+                    match events {
+                        Some(events) => {
+                            self.on_events_received(&events);
+                            let len = events.len();
+                            in_flight_events += len;
+
+                            let mut replies = Vec::new();
+                            for (index, events) in partitioner.partition(events) {
+                                let (reply_tx, reply_rx) = oneshot::channel();
+                                let outputs_buf = self.outputs.new_buf_with_capacity(events.len());
+                                workers[index]
+                                    .send((events, outputs_buf, reply_tx))
+                                    .await
+                                    .map_err(|_| TaskError::Panicked)?;
+                                replies.push(reply_rx);
+                            }
+                            in_flight.push(future::try_join_all(replies).map(move |result| result.map(|outputs_bufs| (len, outputs_bufs))));
+                        }
+                        None => {
+                            shutting_down = true;
+                            continue
+                        }
+                    }
+                }
+
+                else => {
+                    if shutting_down {
+                        break
+                    }
+                }
+            }
+        }
+
+        Ok(TaskOutput::Transform)
+    }
 }
 
 fn build_task_transform(
     t: Box<dyn TaskTransform<EventArray>>,
+    node: TransformNode,
     input_rx: BufferReceiver<EventArray>,
-    input_type: DataType,
-    typetag: &str,
-    key: &ComponentKey,
+    runtime: Option<DedicatedRuntime>,
 ) -> (Task, HashMap<OutputId, fanout::ControlChannel>) {
     let (mut fanout, control) = Fanout::new();
 
-    let input_rx = crate::utilization::wrap(input_rx.into_stream());
+    let input_type = node.input_details.data_type();
+    let input_rx = wrap(
+        BatchedArrays::new(input_rx.into_stream(), &node.batch),
+        &node.utilization,
+    );
 
     let filtered = input_rx
         .filter(move |events| ready(filter_events_type(events, input_type)))
@@ -846,11 +1124,187 @@ fn build_task_transform(
         }
     }
     .boxed();
+    let transform = schedule(transform, runtime);
 
     let mut outputs = HashMap::new();
-    outputs.insert(OutputId::from(key), control);
+    outputs.insert(OutputId::from(&node.key), control);
 
-    let task = Task::new(key.clone(), typetag, transform);
+    let task = Task::new(node.key.clone(), node.typetag, transform);
 
     (task, outputs)
 }
+
+/// Builds the copies of a task transform with `parallelism`.
+///
+/// The events of the copies are sent as soon as they are processed, whatever the ordering mode.
+fn build_parallel_task_transform(
+    transforms: Vec<Box<dyn TaskTransform<EventArray>>>,
+    node: TransformNode,
+    input_rx: BufferReceiver<EventArray>,
+    runtime: Option<DedicatedRuntime>,
+) -> (Task, HashMap<OutputId, fanout::ControlChannel>) {
+    let (mut fanout, control) = Fanout::new();
+
+    let input_type = node.input_details.data_type();
+    let mut filtered = wrap(
+        BatchedArrays::new(input_rx.into_stream(), &node.batch),
+        &node.utilization,
+    )
+    .filter(move |events| ready(filter_events_type(events, input_type)))
+    .inspect(|events| {
+        emit!(EventsReceived {
+            count: events.len(),
+            byte_size: events.size_of(),
+        })
+    });
+
+    let mut partitioner =
+        EventPartitioner::new(transforms.len(), node.partitioning.key_field.clone());
+    let (senders, streams): (Vec<_>, Vec<_>) = transforms
+        .into_iter()
+        .map(|t| {
+            let (tx, rx) = tokio::sync::mpsc::channel(1);
+            (tx, t.transform(Box::pin(ReceiverStream::new(rx))))
+        })
+        .unzip();
+
+    // The copies are finished once their inputs are closed, when the input of the transform is.
+    let partition = async move {
+        while let Some(events) = filtered.next().await {
+            for (index, events) in partitioner.partition(events) {
+                if senders[index].send(events).await.is_err() {
+                    return;
+                }
+            }
+        }
+    };
+
+    let stream = stream::select_all(streams).inspect(|events: &EventArray| {
+        emit!(EventsSent {
+            count: events.len(),
+            byte_size: events.size_of(),
+            output: None,
+        });
+    });
+    let transform = async move {
+        debug!("Task transform starting.");
+
+        match future::join(partition, fanout.send_stream(stream)).await {
+            (_, Ok(())) => {
+                debug!("Task transform finished normally.");
+                Ok(TaskOutput::Transform)
+            }
+            (_, Err(e)) => {
+                debug!("Task transform finished with an error.");
+                Err(TaskError::wrapped(e))
+            }
+        }
+    }
+    .boxed();
+    let transform = schedule(transform, runtime);
+
+    let mut outputs = HashMap::new();
+    outputs.insert(OutputId::from(&node.key), control);
+
+    let task = Task::new(node.key.clone(), node.typetag, transform);
+
+    (task, outputs)
+}
+
+/// Builds a task transform writing to multiple outputs, and its copies with `parallelism`.
+///
+/// The events of the copies are sent as soon as they are processed, whatever the ordering mode.
+fn build_outputs_task_transform(
+    mut transforms: Vec<Box<dyn OutputsTaskTransform>>,
+    node: TransformNode,
+    input_rx: BufferReceiver<EventArray>,
+    runtime: Option<DedicatedRuntime>,
+) -> (Task, HashMap<OutputId, fanout::ControlChannel>) {
+    let (mut outputs, controls) = TransformOutputs::new(node.outputs);
+
+    let input_type = node.input_details.data_type();
+    let mut filtered = wrap(
+        BatchedArrays::new(input_rx.into_stream(), &node.batch),
+        &node.utilization,
+    )
+    .filter(move |events| ready(filter_events_type(events, input_type)))
+    .inspect(|events| {
+        emit!(EventsReceived {
+            count: events.len(),
+            byte_size: events.size_of(),
+        })
+    });
+
+    let (partition, mut stream) = if transforms.len() == 1 {
+        let t = transforms.pop().expect("checked above");
+        (future::ready(()).boxed(), t.transform(Box::pin(filtered)))
+    } else {
+        let mut partitioner =
+            EventPartitioner::new(transforms.len(), node.partitioning.key_field.clone());
+        let (senders, streams): (Vec<_>, Vec<_>) = transforms
+            .into_iter()
+            .map(|t| {
+                let (tx, rx) = tokio::sync::mpsc::channel(1);
+                (tx, t.transform(Box::pin(ReceiverStream::new(rx))))
+            })
+            .unzip();
+
+        // The copies are finished once their inputs are closed, when the input of the transform is.
+        let partition = async move {
+            while let Some(events) = filtered.next().await {
+                for (index, events) in partitioner.partition(events) {
+                    if senders[index].send(events).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        };
+        (partition.boxed(), stream::select_all(streams).boxed())
+    };
+
+    // Only the transforms with several outputs report the utilization of each.
+    let mut outputs_timer =
+        (outputs.output_count() > 1).then(|| OutputsTimer::new(&node.utilization));
+    let report_interval = node.utilization.report_interval();
+    let send = async move {
+        let mut last_report = Instant::now();
+        while let Some(mut buf) = stream.next().await {
+            outputs.send(&mut buf).await?;
+            if let Some(outputs_timer) = outputs_timer.as_mut() {
+                if last_report.elapsed() >= report_interval {
+                    outputs_timer.report(&mut outputs);
+                    last_report = Instant::now();
+                }
+            }
+        }
+        Ok::<_, crate::Error>(())
+    };
+    let transform = async move {
+        debug!("Task transform starting.");
+
+        match future::join(partition, send).await {
+            (_, Ok(())) => {
+                debug!("Task transform finished normally.");
+                Ok(TaskOutput::Transform)
+            }
+            (_, Err(e)) => {
+                debug!("Task transform finished with an error.");
+                Err(TaskError::wrapped(e))
+            }
+        }
+    }
+    .boxed();
+    let transform = schedule(transform, runtime);
+
+    let mut output_controls = HashMap::new();
+    for (name, control) in controls {
+        let id = name
+            .map(|name| OutputId::from((&node.key, name)))
+            .unwrap_or_else(|| OutputId::from(&node.key));
+        output_controls.insert(id, control);
+    }
+
+    let task = Task::new(node.key.clone(), node.typetag, transform);
+
+    (task, output_controls)
+}
@@ -9,9 +9,13 @@
 pub(super) use vector_core::fanout;
 pub mod schema;
 
+mod batching;
 pub mod builder;
+mod parallel;
+pub mod pause;
 mod ready_arrays;
 mod running;
+mod scheduling;
 mod task;
 
 #[cfg(test)]
@@ -0,0 +1,181 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures::{
+    stream::{FuturesOrdered, FuturesUnordered},
+    Future, Stream,
+};
+
+use crate::event::{EventArray, EventContainer};
+
+/// Assigns the events received by a transform with `parallelism` to its copies.
+pub(super) struct EventPartitioner {
+    copies: usize,
+    key_field: Option<String>,
+    next: usize,
+}
+
+impl EventPartitioner {
+    pub(super) const fn new(copies: usize, key_field: Option<String>) -> Self {
+        Self {
+            copies,
+            key_field,
+            next: 0,
+        }
+    }
+
+    /// Splits a batch of events into the batches of each copy, along with the index of the copy.
+    ///
+    /// Without a key field, each batch is assigned as a whole to the next copy. Otherwise, the
+    /// events are assigned by the hash of the value of their key field, such that the events with
+    /// the same value are processed, in order, by the same copy.
+    pub(super) fn partition(&mut self, events: EventArray) -> Vec<(usize, EventArray)> {
+        let key_field = match &self.key_field {
+            Some(key_field) => key_field.as_str(),
+            None => {
+                let index = self.next;
+                self.next = (self.next + 1) % self.copies;
+                return vec![(index, events)];
+            }
+        };
+
+        let copies = self.copies;
+        match events {
+            EventArray::Logs(logs) => split(logs, copies, |log| hash(log.get(key_field)))
+                .map(|(index, logs)| (index, EventArray::Logs(logs)))
+                .collect(),
+            EventArray::Metrics(metrics) => split(metrics, copies, |metric| {
+                hash(metric.tag_value(key_field).as_ref())
+            })
+            .map(|(index, metrics)| (index, EventArray::Metrics(metrics)))
+            .collect(),
+            EventArray::Traces(traces) => split(traces, copies, |trace| hash(trace.get(key_field)))
+                .map(|(index, traces)| (index, EventArray::Traces(traces)))
+                .collect(),
+        }
+    }
+}
+
+fn hash<T: Hash>(value: Option<&T>) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn split<T>(
+    items: Vec<T>,
+    copies: usize,
+    hash: impl Fn(&T) -> u64,
+) -> impl Iterator<Item = (usize, Vec<T>)> {
+    let mut parts = (0..copies).map(|_| Vec::new()).collect::<Vec<_>>();
+    for item in items {
+        let index = (hash(&item) % copies as u64) as usize;
+        parts[index].push(item);
+    }
+    parts
+        .into_iter()
+        .enumerate()
+        .filter(|(_, part)| !part.is_empty())
+}
+
+/// The work in flight of a transform with `parallelism`, completed either in the order it was
+/// started, or as soon as it is done.
+pub(super) enum InFlight<F: Future> {
+    Ordered(FuturesOrdered<F>),
+    Unordered(FuturesUnordered<F>),
+}
+
+impl<F: Future> InFlight<F> {
+    pub(super) fn new(ordered: bool) -> Self {
+        if ordered {
+            Self::Ordered(FuturesOrdered::new())
+        } else {
+            Self::Unordered(FuturesUnordered::new())
+        }
+    }
+
+    pub(super) fn push(&mut self, future: F) {
+        match self {
+            Self::Ordered(futures) => futures.push_back(future),
+            Self::Unordered(futures) => futures.push(future),
+        }
+    }
+
+    pub(super) fn len(&self) -> usize {
+        match self {
+            Self::Ordered(futures) => futures.len(),
+            Self::Unordered(futures) => futures.len(),
+        }
+    }
+
+    pub(super) fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<F: Future> Stream for InFlight<F> {
+    type Item = F::Output;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        // Both `FuturesOrdered` and `FuturesUnordered` are `Unpin`.
+        match self.get_mut() {
+            Self::Ordered(futures) => Pin::new(futures).poll_next(cx),
+            Self::Unordered(futures) => Pin::new(futures).poll_next(cx),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::LogEvent;
+
+    fn logs(hosts: &[&str]) -> EventArray {
+        EventArray::Logs(
+            hosts
+                .iter()
+                .map(|host| {
+                    let mut log = LogEvent::from("message");
+                    log.insert("host", *host);
+                    log
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn partitions_round_robin() {
+        let mut partitioner = EventPartitioner::new(3, None);
+        let indexes = (0..4)
+            .flat_map(|_| partitioner.partition(logs(&["a", "b"])))
+            .map(|(index, events)| {
+                assert_eq!(events.len(), 2);
+                index
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(indexes, vec![0, 1, 2, 0]);
+    }
+
+    #[test]
+    fn partitions_by_key_field() {
+        let mut partitioner = EventPartitioner::new(4, Some("host".to_owned()));
+        let first = partitioner.partition(logs(&["a", "b", "a", "c", "b"]));
+        let second = partitioner.partition(logs(&["c", "a", "b"]));
+
+        assert_eq!(
+            first.iter().map(|(_, events)| events.len()).sum::<usize>(),
+            5
+        );
+        for (index, events) in first.iter().chain(second.iter()) {
+            for event in events.iter_events() {
+                let host = event.as_log().get("host").unwrap();
+                let expected = (hash(Some(host)) % 4) as usize;
+                assert_eq!(*index, expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,213 @@
+//! Pausing of components at runtime.
+//!
+//! Pausing a source stops its pump from reading the events it sends, so that the source is held
+//! back once its output channel is full, and stops ingesting. Pausing a sink stops it from reading
+//! its input, so that it stops delivering events while they keep accumulating in its buffer.
+//!
+//! The paused state of each component is kept across reloads, and is only forgotten once the
+//! component is removed from the topology.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    pin::Pin,
+    sync::Mutex,
+    task::{Context, Poll},
+};
+
+use futures::{Stream, StreamExt};
+use once_cell::sync::Lazy;
+use snafu::Snafu;
+use tokio::sync::watch;
+use tokio_stream::wrappers::WatchStream;
+
+use crate::config::ComponentKey;
+
+static COMPONENTS: Lazy<Mutex<HashMap<ComponentKey, PausableComponent>>> =
+    Lazy::new(Default::default);
+
+/// The kind of a component that can be paused.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PausableKind {
+    /// Pausing a source stops it from ingesting events.
+    Source,
+    /// Pausing a sink stops it from delivering events, which are still buffered.
+    Sink,
+}
+
+impl fmt::Display for PausableKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Source => write!(f, "source"),
+            Self::Sink => write!(f, "sink"),
+        }
+    }
+}
+
+struct PausableComponent {
+    kind: PausableKind,
+    paused: watch::Sender<bool>,
+}
+
+#[derive(Debug, Snafu, PartialEq, Eq)]
+pub enum PauseError {
+    #[snafu(display("Component \"{}\" is not a running source or sink", key))]
+    NotPausable { key: ComponentKey },
+}
+
+/// Registers a component built for the topology, returning the receiver of its paused state.
+///
+/// If the component is being rebuilt, it keeps the state of its previous version, which is
+/// released.
+pub(super) fn register(key: &ComponentKey, kind: PausableKind) -> watch::Receiver<bool> {
+    let mut components = COMPONENTS.lock().expect("pause registry poisoned");
+    let paused = components
+        .get(key)
+        .map_or(false, |component| *component.paused.borrow());
+    let (tx, rx) = watch::channel(paused);
+    components.insert(key.clone(), PausableComponent { kind, paused: tx });
+    rx
+}
+
+/// Releases the running version of a component, which is being shut down, from being paused,
+/// without changing the state the component is rebuilt with.
+pub(super) fn release(key: &ComponentKey) {
+    let mut components = COMPONENTS.lock().expect("pause registry poisoned");
+    if let Some(component) = components.get_mut(key) {
+        let paused = *component.paused.borrow();
+        component.paused = watch::channel(paused).0;
+    }
+}
+
+/// Releases all of the running components, as the topology is being stopped.
+pub(super) fn release_all() {
+    let mut components = COMPONENTS.lock().expect("pause registry poisoned");
+    for component in components.values_mut() {
+        let paused = *component.paused.borrow();
+        component.paused = watch::channel(paused).0;
+    }
+}
+
+/// Forgets a component that is removed from the topology, releasing it.
+pub(super) fn unregister(key: &ComponentKey) {
+    COMPONENTS
+        .lock()
+        .expect("pause registry poisoned")
+        .remove(key);
+}
+
+/// Pauses or resumes a source or a sink, returning its kind.
+pub fn set_paused(key: &ComponentKey, paused: bool) -> Result<PausableKind, PauseError> {
+    let components = COMPONENTS.lock().expect("pause registry poisoned");
+    let component = components
+        .get(key)
+        .ok_or_else(|| PauseError::NotPausable { key: key.clone() })?;
+    component.paused.send_replace(paused);
+    Ok(component.kind)
+}
+
+/// Returns whether a source or a sink is paused, or `None` if it is neither.
+pub fn is_paused(key: &ComponentKey) -> Option<bool> {
+    COMPONENTS
+        .lock()
+        .expect("pause registry poisoned")
+        .get(key)
+        .map(|component| *component.paused.borrow())
+}
+
+/// A stream combinator that stops polling the inner stream while the component is paused.
+///
+/// Once the component is released, the inner stream is polled as if the component was resumed.
+pub(super) struct Pausable<S> {
+    inner: S,
+    states: WatchStream<bool>,
+    paused: bool,
+}
+
+impl<S> Pausable<S> {
+    pub(super) fn new(inner: S, paused: watch::Receiver<bool>) -> Self {
+        Self {
+            inner,
+            states: WatchStream::new(paused),
+            paused: false,
+        }
+    }
+}
+
+impl<S> Stream for Pausable<S>
+where
+    S: Stream + Unpin,
+{
+    type Item = S::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        // Catch up on the latest state. This registers for the next change while paused.
+        loop {
+            match self.states.poll_next_unpin(cx) {
+                Poll::Ready(Some(paused)) => self.paused = paused,
+                Poll::Ready(None) => {
+                    self.paused = false;
+                    break;
+                }
+                Poll::Pending => break,
+            }
+        }
+
+        if self.paused {
+            Poll::Pending
+        } else {
+            self.inner.poll_next_unpin(cx)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use futures::{stream, FutureExt};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn pauses_and_resumes() {
+        let key = ComponentKey::from("pauses_and_resumes");
+        let paused = register(&key, PausableKind::Source);
+        let mut stream = Pausable::new(stream::iter(0..3), paused);
+
+        assert_eq!(stream.next().await, Some(0));
+        assert_eq!(set_paused(&key, true), Ok(PausableKind::Source));
+        assert_eq!(is_paused(&key), Some(true));
+        assert!(
+            tokio::time::timeout(Duration::from_millis(10), stream.next())
+                .await
+                .is_err()
+        );
+
+        set_paused(&key, false).unwrap();
+        assert_eq!(stream.next().await, Some(1));
+
+        unregister(&key);
+        assert_eq!(is_paused(&key), None);
+        assert!(set_paused(&key, true).is_err());
+    }
+
+    #[tokio::test]
+    async fn keeps_state_across_rebuilds() {
+        let key = ComponentKey::from("keeps_state_across_rebuilds");
+        let old = register(&key, PausableKind::Sink);
+        set_paused(&key, true).unwrap();
+
+        let mut old = Pausable::new(stream::iter(0..3), old);
+        assert!(old.next().now_or_never().is_none());
+
+        // The shut down version is released, while the new one stays paused.
+        release(&key);
+        assert_eq!(old.next().await, Some(0));
+        let mut new = Pausable::new(stream::iter(0..3), register(&key, PausableKind::Sink));
+        assert!(new.next().now_or_never().is_none());
+        assert_eq!(is_paused(&key), Some(true));
+
+        unregister(&key);
+    }
+}
@@ -20,6 +20,7 @@ use crate::{
     config::{
         ComponentKey, Config, ConfigDiff, HealthcheckOptions, OutputId, Resource, SourceConfig,
     },
+    data_dir::{monitor_usage, UsageTargets},
     event::EventArray,
     shutdown::SourceShutdownCoordinator,
     spawn_named,
@@ -27,7 +28,7 @@ use crate::{
         build_or_log_errors, builder,
         builder::Pieces,
         fanout::{ControlChannel, ControlMessage},
-        handle_errors, retain, take_healthchecks,
+        handle_errors, pause, retain, take_healthchecks,
         task::TaskOutput,
         BuiltBuffer, TaskHandle, WatchRx, WatchTx,
     },
@@ -45,10 +46,15 @@ pub struct RunningTopology {
     abort_tx: mpsc::UnboundedSender<()>,
     watch: (WatchTx, WatchRx),
     pub(crate) running: Arc<AtomicBool>,
+    usage_targets: watch::Sender<UsageTargets>,
 }
 
 impl RunningTopology {
     pub fn new(config: Config, abort_tx: mpsc::UnboundedSender<()>) -> Self {
+        // The disk usage of the components is measured until the topology is dropped.
+        let (usage_targets, usage_targets_rx) = watch::channel(UsageTargets::new(&config));
+        spawn_named(monitor_usage(usage_targets_rx), "data_dir_usage");
+
         Self {
             inputs: HashMap::new(),
             outputs: HashMap::new(),
@@ -60,6 +66,7 @@ impl RunningTopology {
             abort_tx,
             watch: watch::channel(TapResource::default()),
             running: Arc::new(AtomicBool::new(true)),
+            usage_targets,
         }
     }
 
@@ -102,6 +109,8 @@ impl RunningTopology {
     pub fn stop(self) -> impl Future<Output = ()> {
         // Update the API's health endpoint to signal shutdown
         self.running.store(false, Ordering::Relaxed);
+        // Paused components would otherwise hold back the shutdown until the deadline.
+        pause::release_all();
         // Create handy handles collections of all tasks for the subsequent
         // operations.
         let mut wait_handles = Vec::new();
@@ -247,6 +256,8 @@ impl RunningTopology {
                 self.connect_diff(&diff, &mut new_pieces).await;
                 self.spawn_diff(&diff, new_pieces);
                 self.config = new_config;
+                self.usage_targets
+                    .send_replace(UsageTargets::new(&self.config));
 
                 info!("New configuration loaded successfully.");
 
@@ -332,6 +343,7 @@ impl RunningTopology {
                 let previous = self.tasks.remove(key).unwrap();
                 drop(previous); // detach and forget
 
+                pause::unregister(key);
                 self.remove_outputs(key);
                 source_shutdown_handles
                     .push(self.shutdown_coordinator.shutdown_source(key, deadline));
@@ -340,6 +352,7 @@ impl RunningTopology {
             for key in &diff.sources.to_change {
                 debug!(component = %key, "Changing source.");
 
+                pause::release(key);
                 self.remove_outputs(key);
                 source_shutdown_handles
                     .push(self.shutdown_coordinator.shutdown_source(key, deadline));
@@ -437,6 +450,7 @@ impl RunningTopology {
         // First, we remove any inputs to removed sinks so they can naturally shut down.
         for key in &diff.sinks.to_remove {
             debug!(component = %key, "Removing sink.");
+            pause::unregister(key);
             self.remove_inputs(key, diff, new_config).await;
         }
 
@@ -465,6 +479,7 @@ impl RunningTopology {
                 // at other stages.
                 buffer_tx.insert(key.clone(), self.inputs.get(key).unwrap().clone());
             }
+            pause::release(key);
             self.remove_inputs(key, diff, new_config).await;
         }
 
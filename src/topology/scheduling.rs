@@ -0,0 +1,139 @@
+use std::{future::Future, io};
+
+use tokio::runtime::{self, Runtime};
+use tracing::Instrument;
+
+use super::task::{TaskError, TaskResult};
+use crate::config::{ComponentKey, TransformSchedulingOptions};
+
+/// The worker threads dedicated to a transform, as set by its `scheduling.worker_threads` option.
+pub(super) struct DedicatedRuntime {
+    runtime: Option<Runtime>,
+}
+
+impl DedicatedRuntime {
+    /// Builds the dedicated runtime of the transform, if it has dedicated worker threads.
+    pub(super) fn build(
+        key: &ComponentKey,
+        options: &TransformSchedulingOptions,
+    ) -> io::Result<Option<Self>> {
+        let worker_threads = match options.worker_threads {
+            Some(worker_threads) => worker_threads,
+            None => return Ok(None),
+        };
+
+        let mut builder = runtime::Builder::new_multi_thread();
+        builder
+            .enable_all()
+            .worker_threads(worker_threads.get())
+            .thread_name(format!("vector-{}", key));
+        if !options.cpu_affinity.is_empty() {
+            let cpus = options.cpu_affinity.clone();
+            builder.on_thread_start(move || set_cpu_affinity(&cpus));
+        }
+
+        Ok(Some(Self {
+            runtime: Some(builder.build()?),
+        }))
+    }
+
+    /// Runs the task of the transform on the dedicated worker threads.
+    ///
+    /// The worker threads are shut down once the task completes, or once the returned future is dropped.
+    pub(super) fn run<F>(self, task: F) -> impl Future<Output = TaskResult> + Send + 'static
+    where
+        F: Future<Output = TaskResult> + Send + 'static,
+    {
+        let handle = self
+            .runtime
+            .as_ref()
+            .expect("runtime is only taken on drop")
+            .spawn(task.in_current_span());
+
+        async move {
+            let result = handle
+                .await
+                .unwrap_or_else(|error| Err(TaskError::from(error)));
+            drop(self);
+            result
+        }
+    }
+}
+
+impl Drop for DedicatedRuntime {
+    fn drop(&mut self) {
+        // Dropping a runtime blocks until its worker threads have stopped, which isn't allowed
+        // from within the shared runtime.
+        if let Some(runtime) = self.runtime.take() {
+            runtime.shutdown_background();
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn set_cpu_affinity(cpus: &[usize]) {
+    // SAFETY: `cpu_set_t` is a plain bit mask, for which all zeroes is the empty set, and only the
+    // CPUs within its bounds are added to it.
+    let result = unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        for &cpu in cpus.iter().filter(|&&cpu| cpu < libc::CPU_SETSIZE as usize) {
+            libc::CPU_SET(cpu, &mut set);
+        }
+        libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set)
+    };
+    if result != 0 {
+        warn!(
+            message = "Failed to set the CPU affinity of a worker thread.",
+            ?cpus,
+            error = %io::Error::last_os_error(),
+        );
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn set_cpu_affinity(cpus: &[usize]) {
+    debug!(
+        message = "CPU affinity is not supported on this platform.",
+        ?cpus
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use super::*;
+    use crate::topology::task::TaskOutput;
+
+    #[tokio::test]
+    async fn runs_task_on_dedicated_threads() {
+        let runtime = DedicatedRuntime::build(
+            &ComponentKey::from("hot"),
+            &TransformSchedulingOptions {
+                worker_threads: NonZeroUsize::new(2),
+                ..Default::default()
+            },
+        )
+        .unwrap()
+        .unwrap();
+
+        let result = runtime
+            .run(async {
+                let name = std::thread::current().name().map(ToOwned::to_owned);
+                assert_eq!(name.as_deref(), Some("vector-hot"));
+                Ok(TaskOutput::Transform)
+            })
+            .await;
+        assert!(matches!(result, Ok(TaskOutput::Transform)));
+    }
+
+    #[test]
+    fn shares_runtime_by_default() {
+        assert!(DedicatedRuntime::build(
+            &ComponentKey::from("hot"),
+            &TransformSchedulingOptions::default()
+        )
+        .unwrap()
+        .is_none());
+    }
+}
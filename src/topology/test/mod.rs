@@ -1,6 +1,7 @@
 use std::{
     collections::HashMap,
     iter,
+    num::NonZeroUsize,
     sync::{
         atomic::{AtomicBool, AtomicUsize, Ordering},
         Arc,
@@ -8,7 +9,7 @@ use std::{
 };
 
 use crate::{
-    config::{Config, ConfigDiff, SinkOuter},
+    config::{ComponentKey, Config, ConfigDiff, SinkOuter},
     event::{into_event_stream, Event, EventArray, EventContainer, LogEvent},
     test_util::{
         mock::{
@@ -242,6 +243,43 @@ async fn topology_transform_chain() {
     assert_eq!(vec!["this first second"], res);
 }
 
+#[tokio::test]
+async fn topology_transform_parallelism_keeps_order() {
+    trace_init();
+
+    let (mut in1, source1) = basic_source();
+    let transform1 = basic_transform(" transformed", 0.0);
+    let (out1, sink1) = basic_sink(10);
+
+    let mut config = Config::builder();
+    config.add_source("in1", source1);
+    config.add_transform("t1", &["in1"], transform1);
+    config.add_sink("out1", &["t1"], sink1);
+    config
+        .transforms
+        .get_mut(&ComponentKey::from("t1"))
+        .unwrap()
+        .parallelism = NonZeroUsize::new(3);
+
+    let (topology, _crash) = start_topology(config.build().unwrap(), false).await;
+
+    for index in 0..10 {
+        let event = Event::Log(LogEvent::from(format!("event {}", index)));
+        in1.send_event(event).await.unwrap();
+    }
+
+    topology.stop().await;
+
+    let res = out1.flat_map(into_message_stream).collect::<Vec<_>>().await;
+
+    assert_eq!(
+        (0..10)
+            .map(|index| format!("event {} transformed", index))
+            .collect::<Vec<_>>(),
+        res
+    );
+}
+
 #[tokio::test]
 async fn topology_remove_one_source() {
     trace_init();
@@ -9,6 +9,7 @@ use std::{
 };
 
 use futures_util::{future::ready, Stream, StreamExt};
+use http::Uri;
 use lookup::event_path;
 use metrics_tracing_context::MetricsLayer;
 use once_cell::sync::OnceCell;
@@ -30,6 +31,9 @@ use value::Value;
 
 use crate::event::LogEvent;
 
+#[cfg(feature = "internal-tracing-otlp")]
+mod otlp;
+
 /// BUFFER contains all of the internal log events generated by Vector between the initialization of `tracing` and early
 /// buffering being stopped, which occurs once the topology reports as having successfully started.
 ///
@@ -55,7 +59,13 @@ fn metrics_layer_enabled() -> bool {
     !matches!(std::env::var("DISABLE_INTERNAL_METRICS_TRACING_INTEGRATION"), Ok(x) if x == "true")
 }
 
-pub fn init(color: bool, json: bool, levels: &str, internal_log_rate_limit: u64) {
+pub fn init(
+    color: bool,
+    json: bool,
+    levels: &str,
+    internal_log_rate_limit: u64,
+    internal_tracing_otlp_endpoint: Option<Uri>,
+) {
     let fmt_filter = tracing_subscriber::filter::Targets::from_str(levels).expect(
         "logging filter targets were not formatted correctly or did not specify a valid level",
     );
@@ -80,6 +90,35 @@ pub fn init(color: bool, json: bool, levels: &str, internal_log_rate_limit: u64)
         subscriber.with(console_layer)
     };
 
+    #[cfg(feature = "internal-tracing-otlp")]
+    let subscriber = {
+        let otlp_layer = internal_tracing_otlp_endpoint.and_then(|endpoint| {
+            match otlp::OtlpLayer::new(endpoint) {
+                Ok(layer) => Some(layer.with_filter(otlp::targets())),
+                Err(error) => {
+                    #[allow(clippy::print_stderr)]
+                    {
+                        eprintln!(
+                            "Failed to set up the export of internal tracing spans: {}",
+                            error
+                        );
+                    }
+                    None
+                }
+            }
+        });
+
+        subscriber.with(otlp_layer)
+    };
+
+    #[cfg(not(feature = "internal-tracing-otlp"))]
+    if internal_tracing_otlp_endpoint.is_some() {
+        #[allow(clippy::print_stderr)]
+        {
+            eprintln!("The export of internal tracing spans requires Vector to be built with the `internal-tracing-otlp` feature.");
+        }
+    }
+
     if json {
         let formatter = tracing_subscriber::fmt::layer().json().flatten_event(true);
 
@@ -0,0 +1,433 @@
+//! Export of the internal tracing spans of Vector, such as those of the build of the components, of
+//! the requests of the sinks and of their retries, to an OpenTelemetry collector over OTLP/gRPC.
+//!
+//! The spans of the components, entered at the `ERROR` level as long as the components run, aren't
+//! exported themselves: their fields are added to the spans within them, each of these starting a
+//! trace of its own.
+
+use std::{
+    num::{NonZeroU128, NonZeroU64},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use http::Uri;
+use opentelemetry_proto::proto::{
+    collector::trace::v1::{trace_service_client::TraceServiceClient, ExportTraceServiceRequest},
+    common::v1::{any_value, AnyValue, InstrumentationScope, KeyValue},
+    resource::v1::Resource,
+    trace::v1::{
+        span::{Event as SpanEvent, SpanKind},
+        status::StatusCode,
+        ResourceSpans, ScopeSpans, Span, Status,
+    },
+};
+use rand::{thread_rng, Rng};
+use tokio::sync::mpsc;
+use tonic::transport::{Channel, ClientTlsConfig, Endpoint};
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    Event, Level, Subscriber,
+};
+use tracing_subscriber::{filter::Targets, layer::Context, registry::LookupSpan, Layer};
+
+use crate::get_version;
+
+/// The maximum number of spans exported at once.
+const MAX_BATCH_SIZE: usize = 512;
+
+/// How long the spans wait for a batch to fill up before they are exported.
+const BATCH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The maximum number of spans waiting to be exported. The spans closed beyond it are dropped.
+const MAX_QUEUE_SIZE: usize = 8192;
+
+/// The fields of the internal events that only control how they are logged.
+const IGNORED_FIELDS: [&str; 3] = [
+    "internal_log_rate_limit",
+    "internal_log_rate_secs",
+    "message",
+];
+
+/// The spans and events exported: those of Vector and of its libraries, such as `vector_core`, at
+/// the `INFO` level or above.
+pub(super) fn targets() -> Targets {
+    Targets::new().with_target("vector", Level::INFO)
+}
+
+/// The fields of a component span, added to the spans within it.
+struct ComponentFields(Vec<KeyValue>);
+
+/// An exported span, until it is closed.
+struct SpanData {
+    trace_id: [u8; 16],
+    span_id: [u8; 8],
+    parent_span_id: Option<[u8; 8]>,
+    start_time: SystemTime,
+    attributes: Vec<KeyValue>,
+    events: Vec<SpanEvent>,
+    error: Option<String>,
+}
+
+pub(super) struct OtlpLayer {
+    spans: mpsc::Sender<Span>,
+}
+
+impl OtlpLayer {
+    /// Creates the layer, and spawns the task exporting its spans to the endpoint.
+    pub(super) fn new(endpoint: Uri) -> Result<Self, tonic::transport::Error> {
+        let is_https = endpoint.scheme_str() == Some("https");
+        let mut endpoint = Endpoint::from(endpoint);
+        if is_https {
+            endpoint = endpoint.tls_config(ClientTlsConfig::new())?;
+        }
+        let client = TraceServiceClient::new(endpoint.connect_lazy());
+
+        let (spans, receiver) = mpsc::channel(MAX_QUEUE_SIZE);
+        tokio::spawn(export(client, receiver));
+        Ok(Self { spans })
+    }
+}
+
+impl<S> Layer<S> for OtlpLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let span = match ctx.span(id) {
+            Some(span) => span,
+            None => return,
+        };
+        let parent = span.parent();
+
+        if *attrs.metadata().level() == Level::ERROR {
+            let mut fields = parent
+                .and_then(|parent| {
+                    let extensions = parent.extensions();
+                    extensions
+                        .get::<ComponentFields>()
+                        .map(|fields| fields.0.clone())
+                })
+                .unwrap_or_default();
+            attrs.record(&mut AttributeVisitor(&mut fields));
+            span.extensions_mut().insert(ComponentFields(fields));
+            return;
+        }
+
+        let mut rng = thread_rng();
+        let mut attributes = Vec::new();
+        let mut parent_ids = None;
+        if let Some(parent) = parent {
+            let extensions = parent.extensions();
+            if let Some(parent) = extensions.get::<SpanData>() {
+                parent_ids = Some((parent.trace_id, parent.span_id));
+            } else if let Some(fields) = extensions.get::<ComponentFields>() {
+                attributes = fields.0.clone();
+            }
+        }
+        let (trace_id, parent_span_id) = match parent_ids {
+            Some((trace_id, parent_span_id)) => (trace_id, Some(parent_span_id)),
+            None => (rng.gen::<NonZeroU128>().get().to_be_bytes(), None),
+        };
+        attrs.record(&mut AttributeVisitor(&mut attributes));
+
+        span.extensions_mut().insert(SpanData {
+            trace_id,
+            span_id: rng.gen::<NonZeroU64>().get().to_be_bytes(),
+            parent_span_id,
+            start_time: SystemTime::now(),
+            attributes,
+            events: Vec::new(),
+            error: None,
+        });
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            let mut extensions = span.extensions_mut();
+            if let Some(data) = extensions.get_mut::<SpanData>() {
+                values.record(&mut AttributeVisitor(&mut data.attributes));
+            } else if let Some(fields) = extensions.get_mut::<ComponentFields>() {
+                values.record(&mut AttributeVisitor(&mut fields.0));
+            }
+        }
+    }
+
+    /// Adds the warnings and errors logged within a span as its events, such as the reasons of the
+    /// retries of a request. The errors mark the span as failed.
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let level = *event.metadata().level();
+        if level > Level::WARN {
+            return;
+        }
+        let span = match ctx.event_span(event) {
+            Some(span) => span,
+            None => return,
+        };
+        let mut extensions = span.extensions_mut();
+        let data = match extensions.get_mut::<SpanData>() {
+            Some(data) => data,
+            None => return,
+        };
+
+        let mut visitor = EventVisitor::default();
+        event.record(&mut visitor);
+        let name = visitor
+            .message
+            .unwrap_or_else(|| event.metadata().name().to_owned());
+        if level == Level::ERROR {
+            data.error = Some(name.clone());
+        }
+        visitor
+            .attributes
+            .push(key_value("level", level.as_str().to_owned()));
+        data.events.push(SpanEvent {
+            time_unix_nano: unix_nanos(SystemTime::now()),
+            name,
+            attributes: visitor.attributes,
+            dropped_attributes_count: 0,
+        });
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let span = match ctx.span(&id) {
+            Some(span) => span,
+            None => return,
+        };
+        let data = match span.extensions_mut().remove::<SpanData>() {
+            Some(data) => data,
+            None => return,
+        };
+
+        let span = Span {
+            trace_id: data.trace_id.to_vec(),
+            span_id: data.span_id.to_vec(),
+            parent_span_id: data.parent_span_id.map(Vec::from).unwrap_or_default(),
+            name: span.name().to_owned(),
+            kind: SpanKind::Internal as i32,
+            start_time_unix_nano: unix_nanos(data.start_time),
+            end_time_unix_nano: unix_nanos(SystemTime::now()),
+            attributes: data.attributes,
+            events: data.events,
+            status: data.error.map(|message| Status {
+                message,
+                code: StatusCode::Error as i32,
+            }),
+            ..Default::default()
+        };
+        // The spans are dropped, rather than slowing Vector down, when the export falls behind.
+        let _ = self.spans.try_send(span);
+    }
+}
+
+/// Exports the spans in batches, until the layer is dropped.
+async fn export(mut client: TraceServiceClient<Channel>, mut spans: mpsc::Receiver<Span>) {
+    let resource = Resource {
+        attributes: vec![
+            key_value("service.name", "vector".to_owned()),
+            key_value("service.version", get_version()),
+        ],
+        dropped_attributes_count: 0,
+    };
+    let scope = InstrumentationScope {
+        name: "vector".to_owned(),
+        version: crate::built_info::PKG_VERSION.to_owned(),
+        ..Default::default()
+    };
+
+    let mut batch = Vec::with_capacity(MAX_BATCH_SIZE);
+    while let Some(span) = spans.recv().await {
+        batch.push(span);
+        let timeout = tokio::time::sleep(BATCH_TIMEOUT);
+        tokio::pin!(timeout);
+        while batch.len() < MAX_BATCH_SIZE {
+            tokio::select! {
+                Some(span) = spans.recv() => batch.push(span),
+                _ = &mut timeout => break,
+                else => break,
+            }
+        }
+
+        let request = ExportTraceServiceRequest {
+            resource_spans: vec![ResourceSpans {
+                resource: Some(resource.clone()),
+                scope_spans: vec![ScopeSpans {
+                    scope: Some(scope.clone()),
+                    spans: std::mem::take(&mut batch),
+                    schema_url: String::new(),
+                }],
+                schema_url: String::new(),
+            }],
+        };
+        if let Err(status) = client.export(request).await {
+            error!(
+                message = "Failed to export internal tracing spans.",
+                error = %status,
+                internal_log_rate_limit = true,
+            );
+        }
+    }
+}
+
+fn key_value(key: &str, value: String) -> KeyValue {
+    KeyValue {
+        key: key.to_owned(),
+        value: Some(AnyValue {
+            value: Some(any_value::Value::StringValue(value)),
+        }),
+    }
+}
+
+fn unix_nanos(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_nanos() as u64)
+}
+
+/// Records the fields of a span as attributes.
+struct AttributeVisitor<'a>(&'a mut Vec<KeyValue>);
+
+impl AttributeVisitor<'_> {
+    fn record(&mut self, field: &Field, value: any_value::Value) {
+        if IGNORED_FIELDS.contains(&field.name()) {
+            return;
+        }
+        let attribute = KeyValue {
+            key: field.name().to_owned(),
+            value: Some(AnyValue { value: Some(value) }),
+        };
+        // A field recorded again replaces its previous value.
+        match self
+            .0
+            .iter_mut()
+            .find(|existing| existing.key == attribute.key)
+        {
+            Some(existing) => *existing = attribute,
+            None => self.0.push(attribute),
+        }
+    }
+}
+
+impl Visit for AttributeVisitor<'_> {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.record(field, any_value::Value::DoubleValue(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.record(field, any_value::Value::IntValue(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        match i64::try_from(value) {
+            Ok(value) => self.record(field, any_value::Value::IntValue(value)),
+            Err(_) => self.record(field, any_value::Value::StringValue(value.to_string())),
+        }
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.record(field, any_value::Value::BoolValue(value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record(field, any_value::Value::StringValue(value.to_owned()));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.record(field, any_value::Value::StringValue(format!("{:?}", value)));
+    }
+}
+
+/// Records the message of an event, and its other fields as attributes.
+#[derive(Default)]
+struct EventVisitor {
+    message: Option<String>,
+    attributes: Vec<KeyValue>,
+}
+
+impl Visit for EventVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = Some(value.to_owned());
+        } else {
+            AttributeVisitor(&mut self.attributes).record_str(field, value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            self.message = Some(format!("{:?}", value));
+        } else {
+            AttributeVisitor(&mut self.attributes).record_debug(field, value);
+        }
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        AttributeVisitor(&mut self.attributes).record_f64(field, value);
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        AttributeVisitor(&mut self.attributes).record_i64(field, value);
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        AttributeVisitor(&mut self.attributes).record_u64(field, value);
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        AttributeVisitor(&mut self.attributes).record_bool(field, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use super::*;
+
+    fn string_attribute<'a>(attributes: &'a [KeyValue], key: &str) -> Option<&'a str> {
+        attributes
+            .iter()
+            .find(|attribute| attribute.key == key)
+            .and_then(
+                |attribute| match attribute.value.as_ref()?.value.as_ref()? {
+                    any_value::Value::StringValue(value) => Some(value.as_str()),
+                    _ => None,
+                },
+            )
+    }
+
+    #[test]
+    fn exports_spans_within_components() {
+        let (spans, mut receiver) = mpsc::channel(16);
+        let subscriber = tracing_subscriber::registry().with(OtlpLayer { spans });
+
+        tracing::subscriber::with_default(subscriber, || {
+            let component = error_span!("sink", component_id = "out", component_kind = "sink");
+            let _component = component.enter();
+            let request = info_span!("request", request_id = 1);
+            let _request = request.enter();
+            let retry = info_span!("retry_backoff", attempt = 1);
+            let _retry = retry.enter();
+            error!(message = "Request failed.", status = 503);
+        });
+
+        let retry = receiver.try_recv().unwrap();
+        let request = receiver.try_recv().unwrap();
+        assert!(receiver.try_recv().is_err());
+
+        assert_eq!(request.name, "request");
+        assert!(request.parent_span_id.is_empty());
+        assert_eq!(
+            string_attribute(&request.attributes, "component_id"),
+            Some("out")
+        );
+
+        assert_eq!(retry.name, "retry_backoff");
+        assert_eq!(retry.trace_id, request.trace_id);
+        assert_eq!(retry.parent_span_id, request.span_id);
+        assert_eq!(retry.events[0].name, "Request failed.");
+        assert_eq!(
+            retry.status.map(|status| status.code),
+            Some(StatusCode::Error as i32)
+        );
+    }
+}
@@ -0,0 +1,504 @@
+use std::{collections::BTreeMap, pin::Pin, time::Duration};
+
+use async_stream::stream;
+use chrono::Utc;
+use futures::{Stream, StreamExt};
+use indexmap::IndexMap;
+use vector_config::configurable_component;
+
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext,
+    },
+    event::{discriminant::Discriminant, Event, EventMetadata, LogEvent, Value},
+    schema,
+    transforms::{TaskTransform, Transform},
+};
+
+/// Configuration for the `aggregate_logs` transform.
+#[configurable_component(transform("aggregate_logs"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct AggregateLogsConfig {
+    /// The interval between flushes, in milliseconds.
+    ///
+    /// Over this period, events with the same values for the `group_by` fields are rolled up into
+    /// a single summary event.
+    #[serde(default = "default_interval_ms")]
+    pub interval_ms: u64,
+
+    /// An ordered list of fields by which to group events.
+    ///
+    /// Each group with matching values for the specified fields is summarized independently, and
+    /// the values of these fields are kept in the summary event. When no fields are specified, all
+    /// events are summarized in a single group.
+    #[serde(default)]
+    pub group_by: Vec<String>,
+
+    /// A list of numeric fields to sum over each group.
+    ///
+    /// The sums are held in the `sum` object of the summary event, keyed by field name. Events
+    /// without a numeric value for a field are left out of its sum.
+    #[serde(default)]
+    pub sum: Vec<String>,
+
+    /// A list of numeric fields to track the minimum of over each group.
+    ///
+    /// The minimums are held in the `min` object of the summary event, keyed by field name.
+    #[serde(default)]
+    pub min: Vec<String>,
+
+    /// A list of numeric fields to track the maximum of over each group.
+    ///
+    /// The maximums are held in the `max` object of the summary event, keyed by field name.
+    #[serde(default)]
+    pub max: Vec<String>,
+
+    /// Which events of each group are kept, as a sample, in the summary event.
+    #[serde(default)]
+    pub sample: Sample,
+}
+
+const fn default_interval_ms() -> u64 {
+    10 * 1000
+}
+
+/// Which events of each group are kept in the summary event.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Sample {
+    /// No events are kept.
+    None,
+
+    /// The first event of the group is kept in the `first` field.
+    First,
+
+    /// The last event of the group is kept in the `last` field.
+    Last,
+
+    /// Both the first and the last events of the group are kept, in the `first` and `last`
+    /// fields.
+    Both,
+}
+
+impl Sample {
+    const fn first(self) -> bool {
+        matches!(self, Self::First | Self::Both)
+    }
+
+    const fn last(self) -> bool {
+        matches!(self, Self::Last | Self::Both)
+    }
+}
+
+impl Default for Sample {
+    fn default() -> Self {
+        Self::None
+    }
+}
+
+impl GenerateConfig for AggregateLogsConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            interval_ms: default_interval_ms(),
+            group_by: vec!["host".to_string()],
+            sum: Vec::new(),
+            min: Vec::new(),
+            max: Vec::new(),
+            sample: Sample::None,
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl TransformConfig for AggregateLogsConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        Ok(Transform::event_task(AggregateLogs::new(self)))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+}
+
+/// A numeric value, kept as an integer for as long as possible.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Number {
+    Integer(i64),
+    Float(f64),
+}
+
+impl Number {
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Integer(value) => Some(Self::Integer(*value)),
+            Value::Float(value) => Some(Self::Float(value.into_inner())),
+            _ => None,
+        }
+    }
+
+    fn as_f64(self) -> f64 {
+        match self {
+            Self::Integer(value) => value as f64,
+            Self::Float(value) => value,
+        }
+    }
+
+    fn sum(self, other: Self) -> Self {
+        match (self, other) {
+            (Self::Integer(a), Self::Integer(b)) => a.checked_add(b).map_or_else(
+                || Self::Float(self.as_f64() + other.as_f64()),
+                Self::Integer,
+            ),
+            _ => Self::Float(self.as_f64() + other.as_f64()),
+        }
+    }
+
+    fn min(self, other: Self) -> Self {
+        if other.as_f64() < self.as_f64() {
+            other
+        } else {
+            self
+        }
+    }
+
+    fn max(self, other: Self) -> Self {
+        if other.as_f64() > self.as_f64() {
+            other
+        } else {
+            self
+        }
+    }
+}
+
+impl From<Number> for Value {
+    fn from(number: Number) -> Self {
+        match number {
+            Number::Integer(value) => Value::Integer(value),
+            Number::Float(value) => Value::from_f64_or_zero(value),
+        }
+    }
+}
+
+/// The rollup of the events of a group.
+#[derive(Debug)]
+struct GroupState {
+    group: Vec<(String, Value)>,
+    count: u64,
+    sum: BTreeMap<String, Number>,
+    min: BTreeMap<String, Number>,
+    max: BTreeMap<String, Number>,
+    first: Option<Value>,
+    last: Option<Value>,
+    metadata: EventMetadata,
+}
+
+impl GroupState {
+    fn new(event: &LogEvent, group_by: &[String]) -> Self {
+        let group = group_by
+            .iter()
+            .filter_map(|field| {
+                event
+                    .get(field.as_str())
+                    .map(|value| (field.clone(), value.clone()))
+            })
+            .collect();
+
+        Self {
+            group,
+            count: 0,
+            sum: BTreeMap::new(),
+            min: BTreeMap::new(),
+            max: BTreeMap::new(),
+            first: None,
+            last: None,
+            metadata: EventMetadata::default(),
+        }
+    }
+
+    fn add(&mut self, event: LogEvent, config: &AggregateLogsConfig) {
+        self.count += 1;
+
+        fold(&mut self.sum, &config.sum, &event, Number::sum);
+        fold(&mut self.min, &config.min, &event, Number::min);
+        fold(&mut self.max, &config.max, &event, Number::max);
+
+        let (value, metadata) = event.into_parts();
+        self.metadata.merge(metadata);
+        if config.sample.first() && self.first.is_none() {
+            self.first = Some(value.clone());
+        }
+        if config.sample.last() {
+            self.last = Some(value);
+        }
+    }
+
+    fn flush(self) -> LogEvent {
+        let mut event = LogEvent::new_with_metadata(self.metadata);
+        for (field, value) in self.group {
+            event.insert(field.as_str(), value);
+        }
+
+        event.insert("count", self.count);
+        for (name, numbers) in [("sum", self.sum), ("min", self.min), ("max", self.max)] {
+            if !numbers.is_empty() {
+                let numbers = numbers
+                    .into_iter()
+                    .map(|(field, number)| (field, Value::from(number)))
+                    .collect::<BTreeMap<_, _>>();
+                event.insert(name, numbers);
+            }
+        }
+
+        if let Some(first) = self.first {
+            event.insert("first", first);
+        }
+        if let Some(last) = self.last {
+            event.insert("last", last);
+        }
+
+        event.insert(log_schema().timestamp_key(), Utc::now());
+        event
+    }
+}
+
+/// Folds the numeric values of the given fields of an event into their running values.
+fn fold(
+    numbers: &mut BTreeMap<String, Number>,
+    fields: &[String],
+    event: &LogEvent,
+    op: fn(Number, Number) -> Number,
+) {
+    for field in fields {
+        if let Some(number) = event.get(field.as_str()).and_then(Number::from_value) {
+            numbers
+                .entry(field.clone())
+                .and_modify(|current| *current = op(*current, number))
+                .or_insert(number);
+        }
+    }
+}
+
+pub struct AggregateLogs {
+    interval: Duration,
+    config: AggregateLogsConfig,
+    groups: IndexMap<Discriminant, GroupState>,
+}
+
+impl AggregateLogs {
+    pub fn new(config: &AggregateLogsConfig) -> Self {
+        Self {
+            interval: Duration::from_millis(config.interval_ms),
+            config: config.clone(),
+            groups: IndexMap::new(),
+        }
+    }
+
+    fn record(&mut self, event: Event) {
+        let event = event.into_log();
+        let discriminant = Discriminant::from_log_event(&event, &self.config.group_by);
+
+        self.groups
+            .entry(discriminant)
+            .or_insert_with(|| GroupState::new(&event, &self.config.group_by))
+            .add(event, &self.config);
+    }
+
+    fn flush_into(&mut self, output: &mut Vec<Event>) {
+        output.extend(
+            self.groups
+                .drain(..)
+                .map(|(_, state)| Event::from(state.flush())),
+        );
+    }
+}
+
+impl TaskTransform<Event> for AggregateLogs {
+    fn transform(
+        mut self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut flush_stream = tokio::time::interval(self.interval);
+
+        Box::pin(stream! {
+            let mut output = Vec::new();
+            let mut done = false;
+            while !done {
+                tokio::select! {
+                    _ = flush_stream.tick() => {
+                        self.flush_into(&mut output);
+                    },
+                    maybe_event = input_rx.next() => {
+                        match maybe_event {
+                            None => {
+                                self.flush_into(&mut output);
+                                done = true;
+                            }
+                            Some(event) => self.record(event),
+                        }
+                    }
+                };
+                for event in output.drain(..) {
+                    yield event;
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::task::Poll;
+
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::ReceiverStream;
+
+    use super::*;
+    use crate::{
+        test_util::components::assert_transform_compliance, transforms::test::create_topology,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AggregateLogsConfig>();
+    }
+
+    fn config() -> AggregateLogsConfig {
+        AggregateLogsConfig {
+            interval_ms: 1000,
+            group_by: vec!["user".to_string(), "action".to_string()],
+            sum: vec!["bytes".to_string()],
+            min: vec!["duration".to_string()],
+            max: vec!["duration".to_string()],
+            sample: Sample::None,
+        }
+    }
+
+    fn make_event(user: &str, action: &str, bytes: i64, duration: f64) -> Event {
+        let mut log = LogEvent::from("audit");
+        log.insert("user", user);
+        log.insert("action", action);
+        log.insert("bytes", bytes);
+        log.insert("duration", duration);
+        Event::from(log)
+    }
+
+    #[test]
+    fn rolls_up_groups() {
+        let mut aggregate = AggregateLogs::new(&config());
+
+        aggregate.record(make_event("alice", "read", 10, 0.5));
+        aggregate.record(make_event("bob", "read", 1, 2.0));
+        aggregate.record(make_event("alice", "read", 20, 1.5));
+        aggregate.record(make_event("alice", "write", 5, 0.25));
+
+        let mut output = Vec::new();
+        aggregate.flush_into(&mut output);
+        assert_eq!(output.len(), 3);
+
+        let log = output[0].as_log();
+        assert_eq!(log["user"], "alice".into());
+        assert_eq!(log["action"], "read".into());
+        assert_eq!(log["count"], 2.into());
+        assert_eq!(log["sum.bytes"], 30.into());
+        assert_eq!(log["min.duration"], 0.5.into());
+        assert_eq!(log["max.duration"], 1.5.into());
+        assert!(log.get("message").is_none());
+        assert!(log.get("first").is_none());
+
+        assert_eq!(output[1].as_log()["user"], "bob".into());
+        assert_eq!(output[1].as_log()["count"], 1.into());
+        assert_eq!(output[2].as_log()["action"], "write".into());
+
+        // The groups are cleared once flushed.
+        let mut output = Vec::new();
+        aggregate.flush_into(&mut output);
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn skips_non_numeric_values() {
+        let mut aggregate = AggregateLogs::new(&config());
+
+        let mut event = make_event("alice", "read", 10, 0.5);
+        event.as_mut_log().insert("bytes", "many");
+        aggregate.record(event);
+        aggregate.record(make_event("alice", "read", 20, 1.5));
+
+        let mut output = Vec::new();
+        aggregate.flush_into(&mut output);
+        assert_eq!(output[0].as_log()["count"], 2.into());
+        assert_eq!(output[0].as_log()["sum.bytes"], 20.into());
+    }
+
+    #[test]
+    fn promotes_overflowing_sums_to_floats() {
+        let mut aggregate = AggregateLogs::new(&config());
+
+        aggregate.record(make_event("alice", "read", i64::MAX, 0.5));
+        aggregate.record(make_event("alice", "read", 1, 0.5));
+
+        let mut output = Vec::new();
+        aggregate.flush_into(&mut output);
+        assert!(output[0].as_log()["sum.bytes"].is_float());
+    }
+
+    #[test]
+    fn samples_first_and_last_events() {
+        let mut aggregate = AggregateLogs::new(&AggregateLogsConfig {
+            sample: Sample::Both,
+            ..config()
+        });
+
+        aggregate.record(make_event("alice", "read", 10, 0.5));
+        aggregate.record(make_event("alice", "read", 20, 1.5));
+        aggregate.record(make_event("alice", "read", 30, 2.5));
+
+        let mut output = Vec::new();
+        aggregate.flush_into(&mut output);
+        let log = output[0].as_log();
+        assert_eq!(log["first.bytes"], 10.into());
+        assert_eq!(log["first.message"], "audit".into());
+        assert_eq!(log["last.bytes"], 30.into());
+    }
+
+    #[tokio::test]
+    async fn emits_summaries_on_interval() {
+        assert_transform_compliance(async {
+            let (tx, rx) = mpsc::channel(10);
+            let (topology, out) = create_topology(ReceiverStream::new(rx), config()).await;
+            let mut out = ReceiverStream::new(out);
+
+            tokio::time::pause();
+
+            // tokio interval is always immediately ready, so we poll once to make sure
+            // we trip it/set the interval in the future
+            assert_eq!(Poll::Pending, futures::poll!(out.next()));
+
+            tx.send(make_event("alice", "read", 10, 0.5)).await.unwrap();
+            tx.send(make_event("alice", "read", 20, 1.5)).await.unwrap();
+            assert_eq!(Poll::Pending, futures::poll!(out.next()));
+
+            tokio::time::advance(Duration::from_secs(2)).await;
+
+            let event = out.next().await.unwrap();
+            assert_eq!(event.as_log()["count"], 2.into());
+            assert_eq!(event.as_log()["sum.bytes"], 30.into());
+            assert_eq!(Poll::Pending, futures::poll!(out.next()));
+
+            drop(tx);
+            topology.stop().await;
+            assert_eq!(out.next().await, None);
+        })
+        .await;
+    }
+}
@@ -0,0 +1,257 @@
+use aws_sdk_kms::{types::Blob, Client as KmsClient};
+use goauth::scopes::Scope;
+use http::{header::CONTENT_TYPE, Request};
+use hyper::Body;
+use openssl::symm::{encrypt_aead, Cipher};
+use serde::Deserialize;
+use serde_json::json;
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+
+use super::{AES_256_GCM_NONCE_LEN, AES_256_GCM_TAG_LEN};
+use crate::{
+    aws::{create_client, AwsAuthentication, ClientBuilder, RegionOrEndpoint},
+    config::ProxyConfig,
+    gcp::{GcpAuthConfig, GcpAuthenticator},
+    http::HttpClient,
+    tls::{TlsConfig, TlsSettings},
+};
+
+const GCP_KMS_URL: &str = "https://cloudkms.googleapis.com";
+
+/// The service wrapping the data keys.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum KmsConfig {
+    /// Data keys are wrapped by a key of AWS KMS.
+    AwsKms {
+        /// The ID, ARN, or alias of the KMS key.
+        key_id: String,
+
+        /// The AWS region of the KMS key.
+        region: Option<String>,
+
+        /// Custom endpoint for use with KMS-compatible services.
+        endpoint: Option<String>,
+
+        #[configurable(derived)]
+        #[serde(default)]
+        auth: AwsAuthentication,
+    },
+
+    /// Data keys are wrapped by a key of GCP Cloud KMS.
+    GcpKms {
+        /// The resource name of the Cloud KMS key.
+        ///
+        /// For example, `projects/my-project/locations/global/keyRings/my-ring/cryptoKeys/my-key`.
+        key_name: String,
+
+        /// Custom endpoint for use with Cloud KMS-compatible services.
+        endpoint: Option<String>,
+
+        #[configurable(derived)]
+        #[serde(default)]
+        auth: GcpAuthConfig,
+    },
+
+    /// Data keys are wrapped, with AES-256-GCM, by a key given in the configuration.
+    ///
+    /// This is meant for testing, and for environments without a KMS.
+    Static {
+        /// The key encryption key, as 32 bytes encoded with base64.
+        key: SensitiveString,
+
+        /// An identifier of the key, written in the envelopes of the events.
+        key_id: String,
+    },
+}
+
+pub(super) struct KmsClientBuilder;
+
+impl ClientBuilder for KmsClientBuilder {
+    type Config = aws_sdk_kms::config::Config;
+    type Client = aws_sdk_kms::client::Client;
+    type DefaultMiddleware = aws_sdk_kms::middleware::DefaultMiddleware;
+
+    fn default_middleware() -> Self::DefaultMiddleware {
+        aws_sdk_kms::middleware::DefaultMiddleware::new()
+    }
+
+    fn build(client: aws_smithy_client::Client, config: &aws_types::SdkConfig) -> Self::Client {
+        aws_sdk_kms::client::Client::with_config(client, config.into())
+    }
+}
+
+/// Wraps data keys with a key encryption key.
+#[derive(Clone)]
+pub(super) enum Kms {
+    Aws {
+        client: KmsClient,
+        key_id: String,
+    },
+    Gcp {
+        client: HttpClient,
+        auth: GcpAuthenticator,
+        uri: String,
+        key_name: String,
+    },
+    Static {
+        key: Vec<u8>,
+        key_id: String,
+    },
+}
+
+#[derive(Deserialize)]
+struct GcpEncryptResponse {
+    ciphertext: String,
+}
+
+impl KmsConfig {
+    pub(super) async fn build(
+        &self,
+        proxy: &ProxyConfig,
+        tls: &Option<TlsConfig>,
+    ) -> crate::Result<Kms> {
+        match self {
+            Self::AwsKms {
+                key_id,
+                region,
+                endpoint,
+                auth,
+            } => {
+                let region = RegionOrEndpoint {
+                    region: region.clone(),
+                    endpoint: endpoint.clone(),
+                };
+                let client = create_client::<KmsClientBuilder>(
+                    auth,
+                    region.region(),
+                    region.endpoint()?,
+                    proxy,
+                    tls,
+                    false,
+                )
+                .await?;
+
+                Ok(Kms::Aws {
+                    client,
+                    key_id: key_id.clone(),
+                })
+            }
+            Self::GcpKms {
+                key_name,
+                endpoint,
+                auth,
+            } => {
+                let auth = auth.build(Scope::CloudPlatform).await?;
+                auth.spawn_regenerate_token();
+                let client = HttpClient::new(TlsSettings::from_options(tls)?, proxy)?;
+                let uri = format!(
+                    "{}/v1/{}:encrypt",
+                    endpoint.as_deref().unwrap_or(GCP_KMS_URL),
+                    key_name
+                );
+
+                Ok(Kms::Gcp {
+                    client,
+                    auth,
+                    uri,
+                    key_name: key_name.clone(),
+                })
+            }
+            Self::Static { key, key_id } => {
+                let key = base64::decode(key.inner())
+                    .map_err(|error| format!("Invalid static key: {}", error))?;
+                if key.len() != 32 {
+                    return Err("Invalid static key: the key must be 32 bytes long".into());
+                }
+
+                Ok(Kms::Static {
+                    key,
+                    key_id: key_id.clone(),
+                })
+            }
+        }
+    }
+}
+
+impl Kms {
+    /// The name of the service, written in the envelopes of the events.
+    pub(super) const fn provider(&self) -> &'static str {
+        match self {
+            Self::Aws { .. } => "aws_kms",
+            Self::Gcp { .. } => "gcp_kms",
+            Self::Static { .. } => "static",
+        }
+    }
+
+    /// The identifier of the key encryption key, written in the envelopes of the events.
+    pub(super) fn key_id(&self) -> &str {
+        match self {
+            Self::Aws { key_id, .. } | Self::Static { key_id, .. } => key_id,
+            Self::Gcp { key_name, .. } => key_name,
+        }
+    }
+
+    /// Wraps a data key, returning the wrapped key as it's returned by the service.
+    pub(super) async fn wrap(&self, data_key: &[u8]) -> crate::Result<Vec<u8>> {
+        match self {
+            Self::Aws { client, key_id } => {
+                let output = client
+                    .encrypt()
+                    .key_id(key_id)
+                    .plaintext(Blob::new(data_key))
+                    .send()
+                    .await?;
+                output
+                    .ciphertext_blob()
+                    .map(|blob| blob.as_ref().to_vec())
+                    .ok_or_else(|| "AWS KMS returned no ciphertext".into())
+            }
+            Self::Gcp {
+                client, auth, uri, ..
+            } => {
+                let body = json!({ "plaintext": base64::encode(data_key) });
+                let mut request = Request::post(uri)
+                    .header(CONTENT_TYPE, "application/json")
+                    .body(Body::from(serde_json::to_vec(&body)?))?;
+                auth.apply(&mut request);
+
+                let response = client.send(request).await?;
+                let status = response.status();
+                let body = hyper::body::to_bytes(response.into_body()).await?;
+                if !status.is_success() {
+                    return Err(format!(
+                        "GCP Cloud KMS returned {}: {}",
+                        status,
+                        String::from_utf8_lossy(&body)
+                    )
+                    .into());
+                }
+
+                let response: GcpEncryptResponse = serde_json::from_slice(&body)?;
+                Ok(base64::decode(response.ciphertext)?)
+            }
+            // The wrapped key is laid out like the encrypted fields, as the nonce followed by the
+            // ciphertext and the tag.
+            Self::Static { key, .. } => {
+                let mut wrapped = vec![0; AES_256_GCM_NONCE_LEN];
+                openssl::rand::rand_bytes(&mut wrapped)?;
+                let mut tag = [0; AES_256_GCM_TAG_LEN];
+                let ciphertext = encrypt_aead(
+                    Cipher::aes_256_gcm(),
+                    key,
+                    Some(&wrapped),
+                    &[],
+                    data_key,
+                    &mut tag,
+                )?;
+                wrapped.extend_from_slice(&ciphertext);
+                wrapped.extend_from_slice(&tag);
+
+                Ok(wrapped)
+            }
+        }
+    }
+}
@@ -0,0 +1,351 @@
+use std::{
+    collections::BTreeMap,
+    pin::Pin,
+    time::{Duration, Instant},
+};
+
+use async_stream::stream;
+use futures::{Stream, StreamExt};
+use openssl::symm::{encrypt_aead, Cipher};
+use vector_config::configurable_component;
+
+use crate::{
+    config::{
+        DataType, GenerateConfig, Input, Output, ProxyConfig, TransformConfig, TransformContext,
+    },
+    event::{Event, LogEvent, Value},
+    internal_events::{EncryptFieldsEncryptionError, EncryptFieldsKeyError},
+    schema,
+    tls::TlsConfig,
+    transforms::{TaskTransform, Transform},
+};
+
+mod kms;
+
+use kms::Kms;
+pub use kms::KmsConfig;
+
+const AES_256_GCM_KEY_LEN: usize = 32;
+const AES_256_GCM_NONCE_LEN: usize = 12;
+const AES_256_GCM_TAG_LEN: usize = 16;
+
+/// Configuration for the `encrypt_fields` transform.
+#[configurable_component(transform("encrypt_fields"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct EncryptFieldsConfig {
+    /// The fields to encrypt.
+    ///
+    /// Each field is replaced by its value, encoded as JSON and encrypted with AES-256-GCM. Fields
+    /// that are missing from an event are skipped.
+    pub fields: Vec<String>,
+
+    #[configurable(derived)]
+    pub kms: KmsConfig,
+
+    /// The field holding the envelope of the encrypted fields.
+    ///
+    /// The envelope holds the wrapped data key, the key that wrapped it, and the encrypted fields,
+    /// which is everything needed, along with access to the KMS, to decrypt them.
+    #[serde(default = "default_envelope_field")]
+    pub envelope_field: String,
+
+    /// How long a data key is used, in seconds, before a new one is generated and wrapped.
+    #[serde(default = "default_data_key_ttl_secs")]
+    pub data_key_ttl_secs: u64,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub proxy: ProxyConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+}
+
+fn default_envelope_field() -> String {
+    "encryption".to_string()
+}
+
+const fn default_data_key_ttl_secs() -> u64 {
+    60 * 60
+}
+
+impl GenerateConfig for EncryptFieldsConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"fields = ["message"]
+            kms.type = "aws_kms"
+            kms.key_id = "alias/vector""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl TransformConfig for EncryptFieldsConfig {
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        let proxy = ProxyConfig::merge_with_env(&context.globals.proxy, &self.proxy);
+        let kms = self.kms.build(&proxy, &self.tls).await?;
+
+        Ok(Transform::event_task(EncryptFields::new(self, kms)))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+}
+
+/// A data key, along with its wrapped form.
+struct DataKey {
+    key: Vec<u8>,
+    wrapped: String,
+    generated_at: Instant,
+}
+
+pub struct EncryptFields {
+    fields: Vec<String>,
+    envelope_field: String,
+    data_key_ttl: Duration,
+    kms: Kms,
+    data_key: Option<DataKey>,
+}
+
+impl EncryptFields {
+    fn new(config: &EncryptFieldsConfig, kms: Kms) -> Self {
+        Self {
+            fields: config.fields.clone(),
+            envelope_field: config.envelope_field.clone(),
+            data_key_ttl: Duration::from_secs(config.data_key_ttl_secs),
+            kms,
+            data_key: None,
+        }
+    }
+
+    /// Generates and wraps a new data key, if the current one expired.
+    async fn refresh_data_key(&mut self) -> crate::Result<()> {
+        let expired = self.data_key.as_ref().map_or(true, |data_key| {
+            data_key.generated_at.elapsed() >= self.data_key_ttl
+        });
+        if expired {
+            let mut key = vec![0; AES_256_GCM_KEY_LEN];
+            openssl::rand::rand_bytes(&mut key)?;
+            let wrapped = base64::encode(self.kms.wrap(&key).await?);
+            self.data_key = Some(DataKey {
+                key,
+                wrapped,
+                generated_at: Instant::now(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Encrypts the fields of an event, and adds the envelope of the encrypted fields.
+    fn encrypt(&self, log: &mut LogEvent, data_key: &DataKey) -> Result<(), ()> {
+        let mut encrypted = Vec::new();
+        for field in &self.fields {
+            let value = match log.get(field.as_str()) {
+                Some(value) => value,
+                None => continue,
+            };
+
+            match encrypt_value(&data_key.key, field, value) {
+                Ok(ciphertext) => {
+                    log.insert(field.as_str(), ciphertext);
+                    encrypted.push(Value::from(field.clone()));
+                }
+                Err(error) => {
+                    emit!(EncryptFieldsEncryptionError { field, error });
+                    return Err(());
+                }
+            }
+        }
+
+        if !encrypted.is_empty() {
+            let envelope = BTreeMap::from([
+                ("algorithm".to_string(), Value::from("AES-256-GCM")),
+                ("key_provider".to_string(), self.kms.provider().into()),
+                ("key_id".to_string(), self.kms.key_id().into()),
+                ("wrapped_key".to_string(), data_key.wrapped.clone().into()),
+                ("fields".to_string(), encrypted.into()),
+            ]);
+            log.insert(self.envelope_field.as_str(), envelope);
+        }
+
+        Ok(())
+    }
+}
+
+/// Encrypts a value, encoded as JSON, with the path of its field as additional authenticated
+/// data, so that encrypted values can't be moved between fields.
+///
+/// The result is the nonce, followed by the ciphertext and the tag, encoded with base64.
+fn encrypt_value(key: &[u8], field: &str, value: &Value) -> crate::Result<String> {
+    let plaintext = serde_json::to_vec(value)?;
+
+    let mut encrypted = vec![0; AES_256_GCM_NONCE_LEN];
+    openssl::rand::rand_bytes(&mut encrypted)?;
+    let mut tag = [0; AES_256_GCM_TAG_LEN];
+    let ciphertext = encrypt_aead(
+        Cipher::aes_256_gcm(),
+        key,
+        Some(&encrypted),
+        field.as_bytes(),
+        &plaintext,
+        &mut tag,
+    )?;
+    encrypted.extend_from_slice(&ciphertext);
+    encrypted.extend_from_slice(&tag);
+
+    Ok(base64::encode(encrypted))
+}
+
+impl TaskTransform<Event> for EncryptFields {
+    fn transform(
+        mut self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        Box::pin(stream! {
+            while let Some(mut event) = input_rx.next().await {
+                if let Err(error) = self.refresh_data_key().await {
+                    emit!(EncryptFieldsKeyError { error });
+                    continue;
+                }
+
+                let data_key = self.data_key.as_ref().expect("data key was just refreshed");
+                if self.encrypt(event.as_mut_log(), data_key).is_ok() {
+                    yield event;
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use openssl::symm::decrypt_aead;
+
+    use super::*;
+
+    const KEY: &str = "MDEyMzQ1Njc4OWFiY2RlZjAxMjM0NTY3ODlhYmNkZWY=";
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<EncryptFieldsConfig>();
+    }
+
+    fn config() -> EncryptFieldsConfig {
+        toml::from_str(&format!(
+            r#"fields = ["message", "user.email", "missing"]
+            kms.type = "static"
+            kms.key = "{}"
+            kms.key_id = "test""#,
+            KEY
+        ))
+        .unwrap()
+    }
+
+    async fn encrypt(events: Vec<Event>) -> Vec<LogEvent> {
+        let config = config();
+        let kms = config
+            .kms
+            .build(&ProxyConfig::default(), &None)
+            .await
+            .unwrap();
+        let transform = Box::new(EncryptFields::new(&config, kms));
+
+        transform
+            .transform(Box::pin(futures::stream::iter(events)))
+            .map(Event::into_log)
+            .collect()
+            .await
+    }
+
+    fn decrypt(key: &[u8], aad: &[u8], encoded: &Value) -> Vec<u8> {
+        let encrypted = base64::decode(encoded.as_bytes().unwrap()).unwrap();
+        let (nonce, rest) = encrypted.split_at(AES_256_GCM_NONCE_LEN);
+        let (ciphertext, tag) = rest.split_at(rest.len() - AES_256_GCM_TAG_LEN);
+        decrypt_aead(
+            Cipher::aes_256_gcm(),
+            key,
+            Some(nonce),
+            aad,
+            ciphertext,
+            tag,
+        )
+        .unwrap()
+    }
+
+    fn event() -> Event {
+        let mut log = LogEvent::from("card 4111 1111 1111 1111");
+        log.insert("user.email", "jane@example.com");
+        log.insert("user.id", 42);
+        Event::from(log)
+    }
+
+    #[tokio::test]
+    async fn encrypts_fields() {
+        let log = encrypt(vec![event()]).await.remove(0);
+
+        assert_eq!(log["user.id"], 42.into());
+        assert_eq!(log["encryption.algorithm"], "AES-256-GCM".into());
+        assert_eq!(log["encryption.key_provider"], "static".into());
+        assert_eq!(log["encryption.key_id"], "test".into());
+        assert_eq!(
+            log["encryption.fields"],
+            Value::from(vec!["message", "user.email"])
+        );
+
+        let kek = base64::decode(KEY).unwrap();
+        let data_key = decrypt(&kek, &[], &log["encryption.wrapped_key"]);
+        assert_eq!(
+            decrypt(&data_key, b"message", &log["message"]),
+            br#""card 4111 1111 1111 1111""#
+        );
+        assert_eq!(
+            decrypt(&data_key, b"user.email", &log["user.email"]),
+            br#""jane@example.com""#
+        );
+    }
+
+    #[tokio::test]
+    async fn reuses_data_key() {
+        let logs = encrypt(vec![event(), event()]).await;
+
+        assert_eq!(
+            logs[0]["encryption.wrapped_key"],
+            logs[1]["encryption.wrapped_key"]
+        );
+        // Each value is encrypted with its own nonce.
+        assert_ne!(logs[0]["message"], logs[1]["message"]);
+    }
+
+    #[tokio::test]
+    async fn skips_envelope_without_fields() {
+        let log = encrypt(vec![Event::from(LogEvent::default())])
+            .await
+            .remove(0);
+
+        assert!(log.get("encryption").is_none());
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_static_key() {
+        let kms = KmsConfig::Static {
+            key: "c2hvcnQ=".to_string().into(),
+            key_id: "test".to_string(),
+        };
+
+        assert!(kms.build(&ProxyConfig::default(), &None).await.is_err());
+    }
+}
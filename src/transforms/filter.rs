@@ -1,15 +1,25 @@
+use std::collections::BTreeMap;
+
+use lookup::{lookup_v2::ValuePath, metadata_path, path, PathPrefix};
+use value::Kind;
 use vector_common::internal_event::{Count, InternalEventHandle as _, Registered};
 use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
 
 use crate::{
     conditions::{AnyCondition, Condition},
-    config::{DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext},
+    config::{
+        log_schema, ComponentKey, DataType, GenerateConfig, Input, Output, TransformConfig,
+        TransformContext,
+    },
     event::Event,
     internal_events::FilterEventsDropped,
     schema,
-    transforms::{FunctionTransform, OutputBuffer, Transform},
+    transforms::{SyncTransform, Transform, TransformOutputsBuf},
 };
 
+const DROPPED: &str = "dropped";
+
 /// Configuration for the `filter` transform.
 #[configurable_component(transform("filter"))]
 #[derive(Clone, Debug)]
@@ -17,11 +27,43 @@ use crate::{
 pub struct FilterConfig {
     #[configurable(derived)]
     condition: AnyCondition,
+
+    /// A name for the condition, annotated on the events rerouted to the `dropped` output.
+    ///
+    /// If left unspecified, the condition itself is annotated: the source of VRL conditions, or the
+    /// configuration of other conditions, encoded as JSON.
+    condition_name: Option<String>,
+
+    /// Reroutes the events that don't match the condition to a named output, instead of dropping
+    /// them.
+    ///
+    /// The events are forwarded to a specially-named output, `dropped`, and annotated with
+    /// additional fields describing why they were dropped, including the name of the condition, so
+    /// that drop decisions can be audited downstream.
+    #[serde(default)]
+    reroute_dropped: bool,
 }
 
 impl From<AnyCondition> for FilterConfig {
     fn from(condition: AnyCondition) -> Self {
-        Self { condition }
+        Self {
+            condition,
+            condition_name: None,
+            reroute_dropped: false,
+        }
+    }
+}
+
+impl FilterConfig {
+    fn condition_name(&self) -> String {
+        self.condition_name
+            .clone()
+            .unwrap_or_else(|| match &self.condition {
+                AnyCondition::String(source) => source.clone(),
+                AnyCondition::Map(config) => {
+                    serde_json::to_string(config).expect("conditions are always serializable")
+                }
+            })
     }
 }
 
@@ -38,17 +80,69 @@ impl GenerateConfig for FilterConfig {
 #[async_trait::async_trait]
 impl TransformConfig for FilterConfig {
     async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
-        Ok(Transform::function(Filter::new(
-            self.condition.build(&context.enrichment_tables)?,
-        )))
+        let mut filter = Filter::new(self.condition.build(&context.enrichment_tables)?);
+        if self.reroute_dropped {
+            filter.dropped = Some(Dropped {
+                condition_name: self.condition_name(),
+                component_key: context.key.clone(),
+            });
+        }
+
+        Ok(Transform::synchronous(filter))
     }
 
     fn input(&self) -> Input {
         Input::all()
     }
 
-    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
-        vec![Output::default(DataType::all())]
+    fn outputs(&self, input_definition: &schema::Definition) -> Vec<Output> {
+        let default_output = Output::default(DataType::all());
+        if !self.reroute_dropped {
+            return vec![default_output];
+        }
+
+        // Dropped events are forwarded unchanged, but annotated with additional metadata.
+        let mut dropped_definition = schema::Definition::new_with_default_metadata(
+            Kind::never(),
+            input_definition.log_namespaces().clone(),
+        );
+        if input_definition
+            .log_namespaces()
+            .contains(&LogNamespace::Legacy)
+        {
+            dropped_definition = dropped_definition.merge(input_definition.clone().with_field(
+                log_schema().metadata_key(),
+                Kind::object(BTreeMap::from([
+                    ("reason".into(), Kind::bytes()),
+                    ("condition".into(), Kind::bytes()),
+                    ("component_id".into(), Kind::bytes()),
+                    ("component_type".into(), Kind::bytes()),
+                    ("component_kind".into(), Kind::bytes()),
+                ])),
+                Some("metadata"),
+            ));
+        }
+        if input_definition
+            .log_namespaces()
+            .contains(&LogNamespace::Vector)
+        {
+            dropped_definition = dropped_definition.merge(
+                input_definition
+                    .clone()
+                    .with_metadata_field("reason", Kind::bytes())
+                    .with_metadata_field("condition", Kind::bytes())
+                    .with_metadata_field("component_id", Kind::bytes())
+                    .with_metadata_field("component_type", Kind::bytes())
+                    .with_metadata_field("component_kind", Kind::bytes()),
+            );
+        }
+
+        vec![
+            default_output,
+            Output::default(DataType::all())
+                .with_schema_definition(dropped_definition)
+                .with_port(DROPPED),
+        ]
     }
 
     fn enable_concurrency(&self) -> bool {
@@ -56,9 +150,68 @@ impl TransformConfig for FilterConfig {
     }
 }
 
+/// How events that don't match the condition are annotated, when they are rerouted.
+#[derive(Clone)]
+struct Dropped {
+    condition_name: String,
+    component_key: Option<ComponentKey>,
+}
+
+impl Dropped {
+    fn data(&self) -> serde_json::Value {
+        serde_json::json!({
+            "reason": "condition_failed",
+            "condition": self.condition_name,
+            "component_id": self.component_key,
+            "component_type": "filter",
+            "component_kind": "transform",
+        })
+    }
+
+    fn annotate(&self, event: &mut Event) {
+        match event {
+            Event::Log(ref mut log) => match log.namespace() {
+                LogNamespace::Legacy => {
+                    log.insert(
+                        (
+                            PathPrefix::Event,
+                            log_schema().metadata_key().concat(path!("dropped")),
+                        ),
+                        self.data(),
+                    );
+                }
+                LogNamespace::Vector => {
+                    log.insert(metadata_path!("vector", "dropped"), self.data());
+                }
+            },
+            Event::Metric(ref mut metric) => {
+                let m = log_schema().metadata_key();
+                metric.insert_tag(format!("{}.dropped.reason", m), "condition_failed".into());
+                metric.insert_tag(
+                    format!("{}.dropped.condition", m),
+                    self.condition_name.clone(),
+                );
+                metric.insert_tag(
+                    format!("{}.dropped.component_id", m),
+                    self.component_key
+                        .as_ref()
+                        .map(ToString::to_string)
+                        .unwrap_or_else(String::new),
+                );
+                metric.insert_tag(format!("{}.dropped.component_type", m), "filter".into());
+                metric.insert_tag(format!("{}.dropped.component_kind", m), "transform".into());
+            }
+            Event::Trace(ref mut trace) => {
+                trace.insert(log_schema().metadata_key(), self.data());
+            }
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct Filter {
     condition: Condition,
+    dropped: Option<Dropped>,
     events_dropped: Registered<FilterEventsDropped>,
 }
 
@@ -66,16 +219,20 @@ impl Filter {
     pub fn new(condition: Condition) -> Self {
         Self {
             condition,
+            dropped: None,
             events_dropped: register!(FilterEventsDropped),
         }
     }
 }
 
-impl FunctionTransform for Filter {
-    fn transform(&mut self, output: &mut OutputBuffer, event: Event) {
-        let (result, event) = self.condition.check(event);
+impl SyncTransform for Filter {
+    fn transform(&mut self, event: Event, output: &mut TransformOutputsBuf) {
+        let (result, mut event) = self.condition.check(event);
         if result {
             output.push(event);
+        } else if let Some(dropped) = &self.dropped {
+            dropped.annotate(&mut event);
+            output.push_named(DROPPED, event);
         } else {
             self.events_dropped.emit(Count(1));
         }
@@ -128,4 +285,54 @@ mod test {
         })
         .await;
     }
+
+    #[test]
+    fn filter_reroute_dropped() {
+        let config = toml::from_str::<FilterConfig>(
+            r#"
+            condition = '.level == "error"'
+            condition_name = "errors_only"
+            reroute_dropped = true
+        "#,
+        )
+        .unwrap();
+        let mut filter = Filter::new(config.condition.build(&Default::default()).unwrap());
+        filter.dropped = Some(Dropped {
+            condition_name: config.condition_name(),
+            component_key: Some(ComponentKey::from("filterer")),
+        });
+        let mut outputs =
+            TransformOutputsBuf::new_with_capacity(config.outputs(&schema::Definition::any()), 1);
+
+        let mut error = LogEvent::default();
+        error.insert("level", "error");
+        let mut info = LogEvent::default();
+        info.insert("level", "info");
+        filter.transform(Event::from(error.clone()), &mut outputs);
+        filter.transform(Event::from(info.clone()), &mut outputs);
+
+        let passed: Vec<_> = outputs.take_primary().into_events().collect();
+        assert_eq!(passed, vec![Event::from(error)]);
+
+        let dropped: Vec<_> = outputs.drain_named(DROPPED).collect();
+        assert_eq!(dropped.len(), 1);
+        let dropped = dropped[0].as_log();
+        assert_eq!(dropped["level"], "info".into());
+        assert_eq!(
+            dropped["metadata.dropped.reason"],
+            "condition_failed".into()
+        );
+        assert_eq!(dropped["metadata.dropped.condition"], "errors_only".into());
+        assert_eq!(dropped["metadata.dropped.component_id"], "filterer".into());
+    }
+
+    #[test]
+    fn filter_condition_name_defaults_to_condition() {
+        let config = toml::from_str::<FilterConfig>(r#"condition = '.level == "error"'"#).unwrap();
+        assert_eq!(config.condition_name(), r#".level == "error""#);
+        assert_eq!(config.outputs(&schema::Definition::any()).len(), 1);
+
+        let config = FilterConfig::from(AnyCondition::from(ConditionConfig::IsLog));
+        assert_eq!(config.condition_name(), r#"{"type":"is_log"}"#);
+    }
 }
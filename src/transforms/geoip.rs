@@ -93,6 +93,9 @@ impl GenerateConfig for GeoipConfig {
 #[async_trait::async_trait]
 impl TransformConfig for GeoipConfig {
     async fn build(&self, _context: &TransformContext) -> Result<Transform> {
+        warn!(
+            message = "The `geoip` transform is deprecated, use the `ip_enrichment` transform with a `geoip` or `mmdb` enrichment table instead."
+        );
         Ok(Transform::function(Geoip::new(
             self.database.clone(),
             self.source.clone(),
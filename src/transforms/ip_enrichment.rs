@@ -0,0 +1,451 @@
+use std::{mem, net::IpAddr, num::NonZeroUsize, pin::Pin};
+
+use enrichment::{Case, Condition, TableSearch};
+use futures::{Stream, StreamExt};
+use lru::LruCache;
+use vector_config::configurable_component;
+use vector_core::ByteSizeOf;
+
+use crate::{
+    config::{DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext},
+    event::{Event, Value},
+    internal_events::{
+        IpEnrichmentAddressParseError, IpEnrichmentLookupError, ParserMissingFieldError,
+        TemplateRenderingError, RETAIN_EVENT,
+    },
+    schema,
+    template::Template,
+    transforms::{TaskTransform, Transform},
+};
+
+/// A lookup of the IP address of a field in an enrichment table.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct LookupConfig {
+    /// The name of the enrichment table to look the IP address up in.
+    ///
+    /// The table must be a `geoip` or `mmdb` enrichment table.
+    pub table: String,
+
+    /// The field name that contains the IP address.
+    ///
+    /// This field should contain a valid IPv4 or IPv6 address.
+    pub source: String,
+
+    /// The field to insert the data found for the IP address into.
+    ///
+    /// This can be a template, so that the field depends on the event, such as
+    /// `geo.{{ direction }}`. Nothing is inserted when the IP address isn't found.
+    pub target: Template,
+
+    /// The fields of the data found to insert.
+    ///
+    /// By default, all of the fields are inserted.
+    #[serde(default)]
+    pub fields: Option<Vec<String>>,
+}
+
+/// Caching configuration for the lookups.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct CacheConfig {
+    /// Number of IP addresses to cache the data of, for each lookup.
+    ///
+    /// The least recently used IP addresses are evicted first.
+    #[serde(default = "default_max_entries")]
+    pub max_entries: NonZeroUsize,
+
+    /// Maximum size of the cached data, in bytes.
+    ///
+    /// By default, the size of the cache is only limited by `max_entries`.
+    #[serde(default)]
+    pub max_bytes: Option<NonZeroUsize>,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            max_entries: default_max_entries(),
+            max_bytes: None,
+        }
+    }
+}
+
+fn default_max_entries() -> NonZeroUsize {
+    NonZeroUsize::new(10_000).expect("static non-zero number")
+}
+
+/// Configuration for the `ip_enrichment` transform.
+#[configurable_component(transform("ip_enrichment"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct IpEnrichmentConfig {
+    /// The lookups to make for each event, in order.
+    pub lookups: Vec<LookupConfig>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub cache: CacheConfig,
+}
+
+impl GenerateConfig for IpEnrichmentConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            lookups: vec![LookupConfig {
+                table: "geoip".to_owned(),
+                source: "ip address".to_owned(),
+                target: Template::try_from("geoip").expect("static template"),
+                fields: None,
+            }],
+            cache: CacheConfig::default(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl TransformConfig for IpEnrichmentConfig {
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        let table_ids = context.enrichment_tables.table_ids();
+        for lookup in &self.lookups {
+            if !table_ids.contains(&lookup.table) {
+                return Err(format!(
+                    "Enrichment table {:?} of the lookup of {:?} isn't configured.",
+                    lookup.table, lookup.source
+                )
+                .into());
+            }
+        }
+
+        Ok(Transform::event_task(IpEnrichment::new(
+            self.lookups.clone(),
+            context.enrichment_tables.as_readonly(),
+            &self.cache,
+        )))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+}
+
+/// The cached data of IP addresses, keyed by the index of the lookup and the IP address.
+///
+/// IP addresses not found are cached too, as `None`.
+struct Cache {
+    entries: LruCache<(usize, IpAddr), Option<Value>>,
+    bytes: usize,
+    max_bytes: Option<usize>,
+    /// The generation of the enrichment tables the data was found in.
+    generation: usize,
+}
+
+impl Cache {
+    fn new(config: &CacheConfig) -> Self {
+        Self {
+            entries: LruCache::new(config.max_entries),
+            bytes: 0,
+            max_bytes: config.max_bytes.map(NonZeroUsize::get),
+            generation: 0,
+        }
+    }
+
+    fn entry_size(data: &Option<Value>) -> usize {
+        mem::size_of::<((usize, IpAddr), Option<Value>)>()
+            + data.as_ref().map_or(0, ByteSizeOf::allocated_bytes)
+    }
+
+    /// Discards the cached data when the enrichment tables have been reloaded since it was found.
+    fn invalidate(&mut self, generation: usize) {
+        if generation != self.generation {
+            self.entries.clear();
+            self.bytes = 0;
+            self.generation = generation;
+        }
+    }
+
+    fn get(&mut self, key: &(usize, IpAddr)) -> Option<Option<Value>> {
+        self.entries.get(key).cloned()
+    }
+
+    fn insert(&mut self, key: (usize, IpAddr), data: Option<Value>) {
+        self.bytes += Self::entry_size(&data);
+        if let Some((_, evicted)) = self.entries.push(key, data) {
+            self.bytes -= Self::entry_size(&evicted);
+        }
+
+        while self
+            .max_bytes
+            .map_or(false, |max_bytes| self.bytes > max_bytes)
+        {
+            match self.entries.pop_lru() {
+                Some((_, evicted)) => self.bytes -= Self::entry_size(&evicted),
+                None => break,
+            }
+        }
+    }
+}
+
+pub struct IpEnrichment {
+    lookups: Vec<LookupConfig>,
+    tables: TableSearch,
+    cache: Cache,
+}
+
+impl IpEnrichment {
+    fn new(lookups: Vec<LookupConfig>, tables: TableSearch, cache: &CacheConfig) -> Self {
+        Self {
+            lookups,
+            tables,
+            cache: Cache::new(cache),
+        }
+    }
+
+    fn transform_one(&mut self, mut event: Event) -> Event {
+        self.cache.invalidate(self.tables.generation());
+
+        let log = event.as_mut_log();
+        for (index, lookup) in self.lookups.iter().enumerate() {
+            let address = match log.get(lookup.source.as_str()) {
+                Some(address) => address.to_string_lossy(),
+                None => {
+                    emit!(ParserMissingFieldError::<RETAIN_EVENT> {
+                        field: &lookup.source
+                    });
+                    continue;
+                }
+            };
+            let ip = match address.parse::<IpAddr>() {
+                Ok(ip) => ip,
+                Err(error) => {
+                    emit!(IpEnrichmentAddressParseError {
+                        error,
+                        address: &address
+                    });
+                    continue;
+                }
+            };
+
+            let data = match self.cache.get(&(index, ip)) {
+                Some(data) => data,
+                None => {
+                    let condition = [Condition::Equals {
+                        field: "ip",
+                        value: Value::from(address.as_str()),
+                    }];
+                    let rows = self.tables.find_table_rows(
+                        &lookup.table,
+                        Case::Sensitive,
+                        &condition,
+                        lookup.fields.as_deref(),
+                        None,
+                    );
+                    match rows {
+                        Ok(mut rows) => {
+                            let data = rows.pop().map(Value::from);
+                            self.cache.insert((index, ip), data.clone());
+                            data
+                        }
+                        Err(error) => {
+                            // Not cached, as the table may be readable again once reloaded.
+                            emit!(IpEnrichmentLookupError {
+                                table: &lookup.table,
+                                error
+                            });
+                            continue;
+                        }
+                    }
+                }
+            };
+
+            if let Some(data) = data {
+                match lookup.target.render_string(&*log) {
+                    Ok(target) => {
+                        log.insert(target.as_str(), data);
+                    }
+                    Err(error) => emit!(TemplateRenderingError {
+                        error,
+                        field: Some("target"),
+                        drop_event: false,
+                    }),
+                }
+            }
+        }
+
+        event
+    }
+}
+
+impl TaskTransform<Event> for IpEnrichment {
+    fn transform(
+        self: Box<Self>,
+        task: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut inner = self;
+        Box::pin(task.map(move |event| inner.transform_one(event)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<IpEnrichmentConfig>();
+    }
+
+    #[test]
+    fn cache_evicts_over_max_bytes() {
+        let data = Some(Value::from("Verizon Business"));
+        let entry_size = Cache::entry_size(&data);
+        let mut cache = Cache::new(&CacheConfig {
+            max_entries: NonZeroUsize::new(10).unwrap(),
+            max_bytes: NonZeroUsize::new(entry_size * 2),
+        });
+
+        let ips = ["10.0.0.1", "10.0.0.2", "10.0.0.3"].map(|ip| ip.parse::<IpAddr>().unwrap());
+        for ip in ips {
+            cache.insert((0, ip), data.clone());
+        }
+        assert_eq!(cache.entries.len(), 2);
+        assert_eq!(cache.bytes, entry_size * 2);
+        assert_eq!(cache.get(&(0, ips[0])), None);
+        assert_eq!(cache.get(&(0, ips[2])), Some(data));
+
+        cache.invalidate(1);
+        assert_eq!(cache.entries.len(), 0);
+        assert_eq!(cache.bytes, 0);
+    }
+
+    #[cfg(feature = "enrichment-tables-geoip")]
+    mod geoip {
+        use std::collections::HashMap;
+
+        use vector_common::btreemap;
+        use vector_core::event::LogEvent;
+
+        use super::*;
+        use crate::enrichment_tables::geoip::{Geoip, GeoipConfig};
+
+        fn registry() -> enrichment::TableRegistry {
+            let mut tables: HashMap<String, Box<dyn enrichment::Table + Send + Sync>> =
+                HashMap::new();
+            for (name, path) in [
+                ("city", "tests/data/GeoIP2-City-Test.mmdb"),
+                ("isp", "tests/data/GeoIP2-ISP-Test.mmdb"),
+            ] {
+                let config = GeoipConfig {
+                    path: path.to_owned(),
+                    locale: "en".to_owned(),
+                };
+                tables.insert(name.to_owned(), Box::new(Geoip::new(config).unwrap()));
+            }
+
+            let registry = enrichment::TableRegistry::default();
+            registry.load(tables);
+            registry
+        }
+
+        fn lookup(table: &str, source: &str, target: &str, fields: &[&str]) -> LookupConfig {
+            LookupConfig {
+                table: table.to_owned(),
+                source: source.to_owned(),
+                target: Template::try_from(target).unwrap(),
+                fields: Some(fields.iter().map(|field| field.to_string()).collect()),
+            }
+        }
+
+        #[test]
+        fn multiple_lookups() {
+            let registry = registry();
+            let lookups = vec![
+                lookup("city", "client", "geo.{{ direction }}", &["country_code"]),
+                lookup("isp", "server", "network", &["isp"]),
+            ];
+            let mut transform =
+                IpEnrichment::new(lookups, registry.as_readonly(), &CacheConfig::default());
+            registry.finish_load();
+
+            let event = Event::Log(LogEvent::from(btreemap! {
+                "client" => "2.125.160.216",
+                "server" => "208.192.1.2",
+                "direction" => "inbound",
+            }));
+            let event = transform.transform_one(event);
+            let log = event.as_log();
+
+            assert_eq!(
+                log.get("geo.inbound.country_code"),
+                Some(&Value::from("GB"))
+            );
+            assert_eq!(
+                log.get("network.isp"),
+                Some(&Value::from("Verizon Business"))
+            );
+            assert_eq!(transform.cache.entries.len(), 2);
+
+            // IP addresses not found are cached too, but insert nothing.
+            let event = Event::Log(LogEvent::from(btreemap! {
+                "client" => "10.1.12.1",
+                "direction" => "outbound",
+            }));
+            let event = transform.transform_one(event);
+            assert!(event.as_log().get("geo").is_none());
+            assert_eq!(transform.cache.entries.len(), 3);
+        }
+
+        #[test]
+        fn clears_cache_on_reload() {
+            let registry = registry();
+            let lookups = vec![lookup("city", "client", "geo", &["country_code"])];
+            let mut transform =
+                IpEnrichment::new(lookups, registry.as_readonly(), &CacheConfig::default());
+            registry.finish_load();
+
+            let event = Event::Log(LogEvent::from(btreemap! {
+                "client" => "2.125.160.216",
+            }));
+            transform.transform_one(event.clone());
+            assert_eq!(transform.cache.entries.len(), 1);
+
+            registry.load(HashMap::new());
+            registry.finish_load();
+            transform.transform_one(event);
+            assert_eq!(transform.cache.entries.len(), 1);
+            assert_eq!(
+                transform.cache.generation,
+                registry.as_readonly().generation()
+            );
+        }
+
+        #[tokio::test]
+        async fn build_requires_configured_tables() {
+            let context = TransformContext {
+                enrichment_tables: registry(),
+                ..Default::default()
+            };
+
+            let config = IpEnrichmentConfig {
+                lookups: vec![lookup("city", "client", "geo", &[])],
+                cache: CacheConfig::default(),
+            };
+            assert!(config.build(&context).await.is_ok());
+
+            let config = IpEnrichmentConfig {
+                lookups: vec![lookup("asn", "client", "geo", &[])],
+                cache: CacheConfig::default(),
+            };
+            assert!(config.build(&context).await.is_err());
+        }
+    }
+}
@@ -0,0 +1,327 @@
+use std::{
+    collections::{hash_map::Entry, HashMap, VecDeque},
+    pin::Pin,
+    time::Duration,
+};
+
+use async_stream::stream;
+use futures::{Stream, StreamExt};
+use tokio::time::Instant;
+use vector_config::configurable_component;
+
+use crate::{
+    conditions::{AnyCondition, Condition},
+    config::{DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext},
+    event::{discriminant::Discriminant, Event, EventArray, EventContainer, LogEvent, Value},
+    schema,
+    transforms::{OutputsTaskTransform, Transform, TransformOutputsBuf},
+};
+
+/// The output the events of the left side that weren't joined are sent to.
+const LEFT_UNMATCHED_OUTPUT: &str = "left_unmatched";
+
+/// The output the events of the right side that weren't joined are sent to.
+const RIGHT_UNMATCHED_OUTPUT: &str = "right_unmatched";
+
+/// Configuration for the `join` transform.
+#[configurable_component(transform("join"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct JoinConfig {
+    /// A condition telling the events of the left side of the join apart from those of the right
+    /// side.
+    ///
+    /// The events that match it are on the left side, and those that don't are on the right side,
+    /// so that the two streams to join can both be `inputs` of the transform.
+    pub left_when: AnyCondition,
+
+    /// An ordered list of fields by which to join events.
+    ///
+    /// An event of the left side is joined with the first event of the right side with the same
+    /// values for these fields, and vice versa.
+    pub join_by: Vec<String>,
+
+    /// The maximum period of time to wait for the matching event of the other side, in
+    /// milliseconds.
+    ///
+    /// The events that aren't joined within this window are sent to the `left_unmatched` or
+    /// `right_unmatched` output.
+    #[serde(default = "default_window_ms")]
+    pub window_ms: u64,
+
+    /// The interval to check for and flush any expired events, in milliseconds.
+    #[serde(default = "default_flush_period_ms")]
+    pub flush_period_ms: u64,
+}
+
+const fn default_window_ms() -> u64 {
+    30000
+}
+
+const fn default_flush_period_ms() -> u64 {
+    1000
+}
+
+impl GenerateConfig for JoinConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(indoc::indoc! {r#"
+            left_when = '.type == "request"'
+            join_by = ["request_id"]
+            window_ms = 30000
+        "#})
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl TransformConfig for JoinConfig {
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        Join::new(self, &context.enrichment_tables).map(Transform::outputs_task)
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        vec![
+            Output::default(DataType::Log),
+            Output::default(DataType::Log).with_port(LEFT_UNMATCHED_OUTPUT),
+            Output::default(DataType::Log).with_port(RIGHT_UNMATCHED_OUTPUT),
+        ]
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Side {
+    Left,
+    Right,
+}
+
+impl Side {
+    const fn unmatched_output(self) -> &'static str {
+        match self {
+            Self::Left => LEFT_UNMATCHED_OUTPUT,
+            Self::Right => RIGHT_UNMATCHED_OUTPUT,
+        }
+    }
+}
+
+/// The events of a side waiting for the matching events of the other side, in the order they were
+/// received, along with the time they expire at.
+struct Pending {
+    side: Side,
+    events: VecDeque<(LogEvent, Instant)>,
+}
+
+pub struct Join {
+    left_when: Condition,
+    join_by: Vec<String>,
+    window: Duration,
+    flush_period: Duration,
+    pending: HashMap<Discriminant, Pending>,
+    outputs: Vec<Output>,
+}
+
+impl Join {
+    pub fn new(
+        config: &JoinConfig,
+        enrichment_tables: &enrichment::TableRegistry,
+    ) -> crate::Result<Self> {
+        if config.join_by.is_empty() {
+            return Err("`join_by` must hold at least one field".into());
+        }
+
+        Ok(Join {
+            left_when: config.left_when.build(enrichment_tables)?,
+            join_by: config.join_by.clone(),
+            window: Duration::from_millis(config.window_ms),
+            flush_period: Duration::from_millis(config.flush_period_ms),
+            pending: HashMap::new(),
+            outputs: config.outputs(&schema::Definition::any()),
+        })
+    }
+
+    fn transform_one(&mut self, event: Event, output: &mut TransformOutputsBuf) {
+        let (is_left, event) = self.left_when.check(event);
+        let side = if is_left { Side::Left } else { Side::Right };
+        let event = event.into_log();
+        let discriminant = Discriminant::from_log_event(&event, &self.join_by);
+
+        match self.pending.entry(discriminant) {
+            Entry::Occupied(mut entry) if entry.get().side != side => {
+                let (other, _) = entry
+                    .get_mut()
+                    .events
+                    .pop_front()
+                    .expect("pending events should never be empty");
+                if entry.get().events.is_empty() {
+                    entry.remove();
+                }
+
+                let joined = match side {
+                    Side::Left => join(event, other),
+                    Side::Right => join(other, event),
+                };
+                output.push(joined.into());
+            }
+            Entry::Occupied(mut entry) => {
+                let expires_at = Instant::now() + self.window;
+                entry.get_mut().events.push_back((event, expires_at));
+            }
+            Entry::Vacant(entry) => {
+                let expires_at = Instant::now() + self.window;
+                entry.insert(Pending {
+                    side,
+                    events: VecDeque::from([(event, expires_at)]),
+                });
+            }
+        }
+    }
+
+    fn flush_expired(&mut self, output: &mut TransformOutputsBuf) {
+        let now = Instant::now();
+        self.pending.retain(|_, pending| {
+            while let Some((_, expires_at)) = pending.events.front() {
+                if *expires_at > now {
+                    break;
+                }
+                let (event, _) = pending.events.pop_front().expect("checked above");
+                output.push_named(pending.side.unmatched_output(), event.into());
+            }
+            !pending.events.is_empty()
+        });
+    }
+
+    fn flush_all(&mut self, output: &mut TransformOutputsBuf) {
+        for (_, pending) in self.pending.drain() {
+            for (event, _) in pending.events {
+                output.push_named(pending.side.unmatched_output(), event.into());
+            }
+        }
+    }
+}
+
+/// Joins the events, by adding the top-level fields of the right event to the left event.
+///
+/// The fields both events have keep the values of the left event.
+fn join(mut left: LogEvent, right: LogEvent) -> LogEvent {
+    let (value, metadata) = right.into_parts();
+    if let (Some(fields), Value::Object(right_fields)) = (left.as_map_mut(), value) {
+        for (key, value) in right_fields {
+            fields.entry(key).or_insert(value);
+        }
+    }
+    left.metadata_mut().merge(metadata);
+    left
+}
+
+impl OutputsTaskTransform for Join {
+    fn transform(
+        self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = EventArray> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = TransformOutputsBuf> + Send>> {
+        let mut me = self;
+
+        let mut flush_stream = tokio::time::interval(me.flush_period);
+
+        Box::pin(stream! {
+            loop {
+                let mut output = TransformOutputsBuf::new_with_capacity(me.outputs.clone(), 0);
+                let done = tokio::select! {
+                    _ = flush_stream.tick() => {
+                        me.flush_expired(&mut output);
+                        false
+                    }
+                    maybe_events = input_rx.next() => {
+                        match maybe_events {
+                            None => {
+                                me.flush_all(&mut output);
+                                true
+                            }
+                            Some(events) => {
+                                for event in events.into_events() {
+                                    me.transform_one(event, &mut output);
+                                }
+                                false
+                            }
+                        }
+                    }
+                };
+                if !output.is_empty() {
+                    yield output;
+                }
+                if done { break }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::ReceiverStream;
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<JoinConfig>();
+    }
+
+    fn log(request_id: i64, kind: &str, field: &str, value: &str) -> EventArray {
+        let mut log = LogEvent::default();
+        log.insert("request_id", request_id);
+        log.insert("type", kind);
+        log.insert(field, value);
+        vec![log].into()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn joins_events_by_key() {
+        let config = toml::from_str::<JoinConfig>(
+            r#"
+left_when = '.type == "request"'
+join_by = ["request_id"]
+window_ms = 5000
+"#,
+        )
+        .unwrap();
+        let join = Join::new(&config, &Default::default()).unwrap();
+
+        let (tx, rx) = mpsc::channel(10);
+        let mut out = Box::new(join).transform(Box::pin(ReceiverStream::new(rx)));
+
+        tx.send(log(1, "request", "path", "/")).await.unwrap();
+        tx.send(log(1, "response", "status", "200")).await.unwrap();
+        tx.send(log(2, "request", "path", "/missing"))
+            .await
+            .unwrap();
+
+        let mut buf = out.next().await.unwrap();
+        let joined = buf.drain().collect::<Vec<_>>();
+        assert_eq!(joined.len(), 1);
+        let joined = joined[0].as_log();
+        assert_eq!(joined["request_id"], 1.into());
+        assert_eq!(joined["type"], "request".into());
+        assert_eq!(joined["path"], "/".into());
+        assert_eq!(joined["status"], "200".into());
+
+        // The request without a response is sent once the window expires.
+        let mut buf = out.next().await.unwrap();
+        assert_eq!(buf.drain().count(), 0);
+        let unmatched = buf.drain_named(LEFT_UNMATCHED_OUTPUT).collect::<Vec<_>>();
+        assert_eq!(unmatched.len(), 1);
+        assert_eq!(unmatched[0].as_log()["path"], "/missing".into());
+
+        // The pending events are sent once the input ends.
+        tx.send(log(3, "response", "status", "500")).await.unwrap();
+        drop(tx);
+        let mut buf = out.next().await.unwrap();
+        let unmatched = buf.drain_named(RIGHT_UNMATCHED_OUTPUT).collect::<Vec<_>>();
+        assert_eq!(unmatched.len(), 1);
+        assert_eq!(unmatched[0].as_log()["status"], "500".into());
+
+        assert!(out.next().await.is_none());
+    }
+}
@@ -1,20 +1,23 @@
 use std::{collections::BTreeMap, convert::TryFrom, num::ParseFloatError};
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use indexmap::IndexMap;
+use vector_common::TimeZone;
 use vector_config::configurable_component;
+use vector_core::compile_vrl;
+use vrl::{diagnostic::Formatter, CompilationResult, CompileConfig, Program, Runtime};
 
 use crate::{
     config::{
         log_schema, DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext,
     },
     event::{
-        metric::{Metric, MetricKind, MetricValue, StatisticKind},
-        Event, Value,
+        metric::{Metric, MetricKind, MetricTags, MetricValue, StatisticKind},
+        Event, EventMetadata, TargetEvents, Value, VrlTarget,
     },
     internal_events::{
         LogToMetricFieldNullError, LogToMetricParseFloatError, LogToMetricTemplateParseError,
-        ParserMissingFieldError, DROP_EVENT,
+        LogToMetricVrlError, ParserMissingFieldError, DROP_EVENT,
     },
     schema,
     template::{Template, TemplateParseError, TemplateRenderingError},
@@ -133,6 +136,29 @@ pub struct SummaryConfig {
     tags: Option<IndexMap<String, String>>,
 }
 
+/// Specification of metrics computed by a VRL program.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct VrlMetricsConfig {
+    /// The [Vector Remap Language][vrl] (VRL) program computing the metrics.
+    ///
+    /// The program can read the event but not modify it. It must resolve to an object describing a
+    /// metric, to an array of such objects, or to `null` to not emit anything. Each object has the
+    /// following fields:
+    ///
+    /// - `name`: the name of the metric.
+    /// - `type`: one of `counter`, `gauge`, `set`, `histogram`, or `summary`.
+    /// - `value`: the value of the metric. Strings holding numbers are parsed, and sets accept any
+    ///   value.
+    /// - `namespace`: the namespace of the metric, if any.
+    /// - `kind`: either `incremental` or `absolute`. Gauges are absolute by default, and all other
+    ///   metrics are incremental.
+    /// - `tags`: an object holding the tags of the metric, if any.
+    ///
+    /// [vrl]: https://vector.dev/docs/reference/vrl
+    source: String,
+}
+
 /// Specification of a metric derived from a log event.
 #[configurable_component]
 #[derive(Clone, Debug)]
@@ -152,18 +178,55 @@ pub enum MetricConfig {
 
     /// A summary.
     Summary(#[configurable(derived)] SummaryConfig),
+
+    /// Any number of metrics, computed by a VRL program.
+    Vrl(#[configurable(derived)] VrlMetricsConfig),
 }
 
 impl MetricConfig {
-    fn field(&self) -> &str {
+    fn field(&self) -> Option<&str> {
         match self {
-            MetricConfig::Counter(CounterConfig { field, .. }) => field,
-            MetricConfig::Histogram(HistogramConfig { field, .. }) => field,
-            MetricConfig::Gauge(GaugeConfig { field, .. }) => field,
-            MetricConfig::Set(SetConfig { field, .. }) => field,
-            MetricConfig::Summary(SummaryConfig { field, .. }) => field,
+            MetricConfig::Counter(CounterConfig { field, .. }) => Some(field),
+            MetricConfig::Histogram(HistogramConfig { field, .. }) => Some(field),
+            MetricConfig::Gauge(GaugeConfig { field, .. }) => Some(field),
+            MetricConfig::Set(SetConfig { field, .. }) => Some(field),
+            MetricConfig::Summary(SummaryConfig { field, .. }) => Some(field),
+            MetricConfig::Vrl(_) => None,
         }
     }
+
+    fn compile(&self, context: &TransformContext) -> crate::Result<Option<Program>> {
+        let source = match self {
+            MetricConfig::Vrl(VrlMetricsConfig { source }) => source,
+            _ => return Ok(None),
+        };
+
+        let functions = vrl_stdlib::all()
+            .into_iter()
+            .chain(enrichment::vrl_functions().into_iter())
+            .chain(vector_vrl_functions::vrl_functions())
+            .collect::<Vec<_>>();
+
+        let state = vrl::state::TypeState::default();
+
+        let mut config = CompileConfig::default();
+        config.set_custom(context.enrichment_tables.clone());
+        config.set_read_only();
+
+        let CompilationResult {
+            program,
+            warnings,
+            config: _,
+        } = compile_vrl(source, &functions, &state, config)
+            .map_err(|diagnostics| Formatter::new(source, diagnostics).colored().to_string())?;
+
+        if !warnings.is_empty() {
+            let warnings = Formatter::new(source, warnings).colored().to_string();
+            warn!(message = "VRL compilation warning.", %warnings);
+        }
+
+        Ok(Some(program))
+    }
 }
 
 const fn default_increment_by_value() -> bool {
@@ -177,6 +240,8 @@ const fn default_kind() -> MetricKind {
 #[derive(Debug, Clone)]
 pub struct LogToMetric {
     config: LogToMetricConfig,
+    // The compiled programs of the VRL metrics, in the same order as the configured metrics.
+    programs: Vec<Option<Program>>,
 }
 
 impl GenerateConfig for LogToMetricConfig {
@@ -197,8 +262,11 @@ impl GenerateConfig for LogToMetricConfig {
 
 #[async_trait::async_trait]
 impl TransformConfig for LogToMetricConfig {
-    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
-        Ok(Transform::function(LogToMetric::new(self.clone())))
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        Ok(Transform::function(LogToMetric::new(
+            self.clone(),
+            context,
+        )?))
     }
 
     fn input(&self) -> Input {
@@ -215,8 +283,13 @@ impl TransformConfig for LogToMetricConfig {
 }
 
 impl LogToMetric {
-    pub const fn new(config: LogToMetricConfig) -> Self {
-        LogToMetric { config }
+    pub fn new(config: LogToMetricConfig, context: &TransformContext) -> crate::Result<Self> {
+        let programs = config
+            .metrics
+            .iter()
+            .map(|metric| metric.compile(context))
+            .collect::<crate::Result<_>>()?;
+        Ok(LogToMetric { config, programs })
     }
 }
 
@@ -233,6 +306,9 @@ enum TransformError {
         field: String,
         error: ParseFloatError,
     },
+    Vrl {
+        error: String,
+    },
 }
 
 fn render_template(s: &str, event: &Event) -> Result<String, TransformError> {
@@ -245,15 +321,15 @@ fn render_template(s: &str, event: &Event) -> Result<String, TransformError> {
 fn render_tags(
     tags: &Option<IndexMap<String, String>>,
     event: &Event,
-) -> Result<Option<BTreeMap<String, String>>, TransformError> {
+) -> Result<Option<MetricTags>, TransformError> {
     Ok(match tags {
         None => None,
         Some(tags) => {
-            let mut map = BTreeMap::new();
+            let mut map = MetricTags::new();
             for (name, value) in tags {
                 match render_template(value, event) {
                     Ok(tag) => {
-                        map.insert(name.to_string(), tag);
+                        map.insert(name.into(), tag);
                     }
                     Err(TransformError::TemplateRenderingError(error)) => {
                         emit!(crate::internal_events::TemplateRenderingError {
@@ -274,17 +350,24 @@ fn render_tags(
     })
 }
 
-fn to_metric(config: &MetricConfig, event: &Event) -> Result<Metric, TransformError> {
-    let log = event.as_log();
-
-    let timestamp = log
+fn event_timestamp(event: &Event) -> Option<DateTime<Utc>> {
+    event
+        .as_log()
         .get(log_schema().timestamp_key())
         .and_then(Value::as_timestamp)
         .cloned()
-        .or_else(|| Some(Utc::now()));
+        .or_else(|| Some(Utc::now()))
+}
+
+fn to_metric(config: &MetricConfig, event: &Event) -> Result<Metric, TransformError> {
+    let log = event.as_log();
+
+    let timestamp = event_timestamp(event);
     let metadata = event.metadata().clone();
 
-    let field = config.field();
+    let field = config
+        .field()
+        .expect("VRL metrics are computed by their program");
 
     let value = match log.get(field) {
         None => Err(TransformError::FieldNotFound {
@@ -444,15 +527,152 @@ fn to_metric(config: &MetricConfig, event: &Event) -> Result<Metric, TransformEr
             .with_tags(tags)
             .with_timestamp(timestamp))
         }
+        MetricConfig::Vrl(_) => unreachable!("VRL metrics are computed by their program"),
+    }
+}
+
+fn vrl_error(error: impl Into<String>) -> TransformError {
+    TransformError::Vrl {
+        error: error.into(),
+    }
+}
+
+fn vrl_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Integer(value) => Some(*value as f64),
+        Value::Float(value) => Some(value.into_inner()),
+        Value::Bytes(_) => value.to_string_lossy().parse().ok(),
+        _ => None,
+    }
+}
+
+fn vrl_string(object: &mut BTreeMap<String, Value>, key: &str) -> Option<String> {
+    match object.remove(key) {
+        None | Some(Value::Null) => None,
+        Some(value) => Some(value.to_string_lossy()),
+    }
+}
+
+fn vrl_metric(
+    value: Value,
+    timestamp: Option<DateTime<Utc>>,
+    metadata: &EventMetadata,
+) -> Result<Metric, TransformError> {
+    let mut object = match value {
+        Value::Object(object) => object,
+        value => {
+            return Err(vrl_error(format!(
+                "expected an object describing a metric, got {}",
+                value.kind_str()
+            )))
+        }
+    };
+
+    let name = vrl_string(&mut object, "name").ok_or_else(|| vrl_error("missing metric name"))?;
+    let namespace = vrl_string(&mut object, "namespace");
+    let value = object.remove("value").unwrap_or(Value::Null);
+    let number = || {
+        vrl_number(&value).ok_or_else(|| vrl_error(format!("invalid value for metric {:?}", name)))
+    };
+
+    let (default_kind, value) = match vrl_string(&mut object, "type").as_deref() {
+        Some("counter") => (
+            MetricKind::Incremental,
+            MetricValue::Counter { value: number()? },
+        ),
+        Some("gauge") => (
+            MetricKind::Absolute,
+            MetricValue::Gauge { value: number()? },
+        ),
+        Some("set") => (
+            MetricKind::Incremental,
+            MetricValue::Set {
+                values: std::iter::once(value.to_string_lossy()).collect(),
+            },
+        ),
+        Some("histogram") => (
+            MetricKind::Incremental,
+            MetricValue::Distribution {
+                samples: vector_core::samples![number()? => 1],
+                statistic: StatisticKind::Histogram,
+            },
+        ),
+        Some("summary") => (
+            MetricKind::Incremental,
+            MetricValue::Distribution {
+                samples: vector_core::samples![number()? => 1],
+                statistic: StatisticKind::Summary,
+            },
+        ),
+        Some(other) => return Err(vrl_error(format!("unknown metric type {:?}", other))),
+        None => return Err(vrl_error(format!("missing type for metric {:?}", name))),
+    };
+
+    let kind = match vrl_string(&mut object, "kind").as_deref() {
+        None => default_kind,
+        Some("incremental") => MetricKind::Incremental,
+        Some("absolute") => MetricKind::Absolute,
+        Some(other) => return Err(vrl_error(format!("unknown metric kind {:?}", other))),
+    };
+
+    let tags = match object.remove("tags") {
+        None | Some(Value::Null) => None,
+        Some(Value::Object(tags)) => Some(
+            tags.into_iter()
+                .filter(|(_, value)| !matches!(value, Value::Null))
+                .map(|(key, value)| (key.into(), value.to_string_lossy()))
+                .collect::<MetricTags>(),
+        )
+        .filter(|tags| !tags.is_empty()),
+        Some(_) => return Err(vrl_error(format!("invalid tags for metric {:?}", name))),
+    };
+
+    Ok(
+        Metric::new_with_metadata(name, kind, value, metadata.clone())
+            .with_namespace(namespace)
+            .with_tags(tags)
+            .with_timestamp(timestamp),
+    )
+}
+
+fn to_vrl_metrics(program: &Program, event: &Event) -> Result<Vec<Metric>, TransformError> {
+    let mut target = VrlTarget::new(event.clone(), program.info());
+    let timezone = TimeZone::default();
+    let result = Runtime::default().resolve(&mut target, program, &timezone);
+    let event = match target.into_events() {
+        TargetEvents::One(event) => event,
+        _ => {
+            panic!("Event was modified by a read-only program. This is an internal compiler error.")
+        }
+    };
+    let timestamp = event_timestamp(&event);
+    let metadata = event.metadata();
+
+    match result.map_err(|error| vrl_error(error.to_string()))? {
+        Value::Null => Ok(Vec::new()),
+        Value::Array(values) => values
+            .into_iter()
+            .map(|value| vrl_metric(value, timestamp, metadata))
+            .collect(),
+        value => vrl_metric(value, timestamp, metadata).map(|metric| vec![metric]),
     }
 }
 
 impl FunctionTransform for LogToMetric {
     fn transform(&mut self, output: &mut OutputBuffer, event: Event) {
-        for config in self.config.metrics.iter() {
-            match to_metric(config, &event) {
-                Ok(metric) => {
-                    output.push(Event::Metric(metric));
+        for (config, program) in self.config.metrics.iter().zip(self.programs.iter()) {
+            let metrics = match program {
+                Some(program) => to_vrl_metrics(program, &event),
+                None => to_metric(config, &event).map(|metric| vec![metric]),
+            };
+            match metrics {
+                Ok(metrics) => {
+                    for metric in metrics {
+                        output.push(Event::Metric(metric));
+                    }
+                }
+                Err(TransformError::Vrl { error }) => {
+                    emit!(LogToMetricVrlError { error: &error })
                 }
                 Err(TransformError::FieldNull { field }) => emit!(LogToMetricFieldNullError {
                     field: field.as_ref()
@@ -529,7 +749,7 @@ mod tests {
 
         let event = create_event("status", "42");
         let metadata = event.metadata().clone();
-        let mut transform = LogToMetric::new(config);
+        let mut transform = LogToMetric::new(config, &Default::default()).unwrap();
         let metric = transform_one(&mut transform, event).unwrap();
 
         assert_eq!(
@@ -562,7 +782,7 @@ mod tests {
         event.as_mut_log().insert("code", "200");
         let metadata = event.metadata().clone();
 
-        let mut transform = LogToMetric::new(config);
+        let mut transform = LogToMetric::new(config, &Default::default()).unwrap();
         let metric = transform_one(&mut transform, event).unwrap();
 
         assert_eq!(
@@ -576,9 +796,9 @@ mod tests {
             .with_namespace(Some("app"))
             .with_tags(Some(
                 vec![
-                    ("method".to_owned(), "post".to_owned()),
-                    ("code".to_owned(), "200".to_owned()),
-                    ("host".to_owned(), "localhost".to_owned()),
+                    ("method".into(), "post".to_owned()),
+                    ("code".into(), "200".to_owned()),
+                    ("host".into(), "localhost".to_owned()),
                 ]
                 .into_iter()
                 .collect(),
@@ -600,7 +820,7 @@ mod tests {
 
         let event = create_event("backtrace", "message");
         let metadata = event.metadata().clone();
-        let mut transform = LogToMetric::new(config);
+        let mut transform = LogToMetric::new(config, &Default::default()).unwrap();
         let metric = transform_one(&mut transform, event).unwrap();
 
         assert_eq!(
@@ -627,7 +847,7 @@ mod tests {
         );
 
         let event = create_event("success", "42");
-        let mut transform = LogToMetric::new(config);
+        let mut transform = LogToMetric::new(config, &Default::default()).unwrap();
 
         assert_eq!(transform_one(&mut transform, event), None);
     }
@@ -646,7 +866,7 @@ mod tests {
 
         let event = create_event("amount", "33.99");
         let metadata = event.metadata().clone();
-        let mut transform = LogToMetric::new(config);
+        let mut transform = LogToMetric::new(config, &Default::default()).unwrap();
         let metric = transform_one(&mut transform, event).unwrap();
 
         assert_eq!(
@@ -676,7 +896,7 @@ mod tests {
 
         let event = create_event("amount", "33.99");
         let metadata = event.metadata().clone();
-        let mut transform = LogToMetric::new(config);
+        let mut transform = LogToMetric::new(config, &Default::default()).unwrap();
         let metric = transform_one(&mut transform, event).unwrap();
 
         assert_eq!(
@@ -704,7 +924,7 @@ mod tests {
 
         let event = create_event("memory_rss", "123");
         let metadata = event.metadata().clone();
-        let mut transform = LogToMetric::new(config);
+        let mut transform = LogToMetric::new(config, &Default::default()).unwrap();
         let metric = transform_one(&mut transform, event).unwrap();
 
         assert_eq!(
@@ -732,7 +952,7 @@ mod tests {
         );
 
         let event = create_event("status", "not a number");
-        let mut transform = LogToMetric::new(config);
+        let mut transform = LogToMetric::new(config, &Default::default()).unwrap();
 
         assert_eq!(transform_one(&mut transform, event), None);
     }
@@ -749,7 +969,7 @@ mod tests {
         );
 
         let event = create_event("not foo", "not a number");
-        let mut transform = LogToMetric::new(config);
+        let mut transform = LogToMetric::new(config, &Default::default()).unwrap();
 
         assert_eq!(transform_one(&mut transform, event), None);
     }
@@ -766,7 +986,7 @@ mod tests {
         );
 
         let event = create_event("status", Value::Null);
-        let mut transform = LogToMetric::new(config);
+        let mut transform = LogToMetric::new(config, &Default::default()).unwrap();
 
         assert_eq!(transform_one(&mut transform, event), None);
     }
@@ -794,7 +1014,7 @@ mod tests {
         event.as_mut_log().insert("backtrace", "message");
         let metadata = event.metadata().clone();
 
-        let mut transform = LogToMetric::new(config);
+        let mut transform = LogToMetric::new(config, &Default::default()).unwrap();
 
         let mut output = OutputBuffer::default();
         transform.transform(&mut output, event);
@@ -822,6 +1042,91 @@ mod tests {
         );
     }
 
+    #[test]
+    fn vrl_metrics_from_stats_line() {
+        let config = parse_config(
+            r#"
+            [[metrics]]
+            type = "vrl"
+            source = '''
+            stats = parse_key_value!(.message)
+            [
+              { "name": "requests_total", "type": "counter", "value": stats.requests, "tags": { "host": .host } },
+              { "name": "queue_depth", "type": "gauge", "value": stats.queue, "namespace": "app" },
+            ]
+            '''
+            "#,
+        );
+
+        let mut event = create_event("message", "requests=12 queue=3");
+        event.as_mut_log().insert("host", "web-1");
+        let metadata = event.metadata().clone();
+
+        let mut transform = LogToMetric::new(config, &Default::default()).unwrap();
+
+        let mut output = OutputBuffer::default();
+        transform.transform(&mut output, event);
+        assert_eq!(2, output.len());
+        let mut output = output.into_events();
+        assert_eq!(
+            output.next().unwrap().into_metric(),
+            Metric::new_with_metadata(
+                "requests_total",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 12.0 },
+                metadata.clone(),
+            )
+            .with_tags(Some(
+                vec![("host".into(), "web-1".to_owned())]
+                    .into_iter()
+                    .collect()
+            ))
+            .with_timestamp(Some(ts()))
+        );
+        assert_eq!(
+            output.next().unwrap().into_metric(),
+            Metric::new_with_metadata(
+                "queue_depth",
+                MetricKind::Absolute,
+                MetricValue::Gauge { value: 3.0 },
+                metadata,
+            )
+            .with_namespace(Some("app"))
+            .with_timestamp(Some(ts()))
+        );
+    }
+
+    #[test]
+    fn vrl_metrics_skip_invalid_output() {
+        let config = parse_config(
+            r#"
+            [[metrics]]
+            type = "vrl"
+            source = '''
+            if .status == "ok" { null } else { { "name": "failures", "type": "counter", "value": "many" } }
+            '''
+            "#,
+        );
+        let mut transform = LogToMetric::new(config, &Default::default()).unwrap();
+
+        let mut output = OutputBuffer::default();
+        transform.transform(&mut output, create_event("status", "ok"));
+        transform.transform(&mut output, create_event("status", "error"));
+        assert_eq!(0, output.len());
+    }
+
+    #[test]
+    fn vrl_metrics_reject_invalid_program() {
+        let config = parse_config(
+            r#"
+            [[metrics]]
+            type = "vrl"
+            source = ".foo = 1"
+            "#,
+        );
+        assert!(LogToMetric::new(config, &Default::default()).is_err());
+    }
+
     #[test]
     fn multiple_metrics_with_multiple_templates() {
         let config = parse_config(
@@ -850,7 +1155,7 @@ mod tests {
         event.as_mut_log().insert("service", "xyz");
         let metadata = event.metadata().clone();
 
-        let mut transform = LogToMetric::new(config);
+        let mut transform = LogToMetric::new(config, &Default::default()).unwrap();
 
         let mut output = OutputBuffer::default();
         transform.transform(&mut output, event);
@@ -894,7 +1199,7 @@ mod tests {
 
         let event = create_event("user_ip", "1.2.3.4");
         let metadata = event.metadata().clone();
-        let mut transform = LogToMetric::new(config);
+        let mut transform = LogToMetric::new(config, &Default::default()).unwrap();
         let metric = transform_one(&mut transform, event).unwrap();
 
         assert_eq!(
@@ -923,7 +1228,7 @@ mod tests {
 
         let event = create_event("response_time", "2.5");
         let metadata = event.metadata().clone();
-        let mut transform = LogToMetric::new(config);
+        let mut transform = LogToMetric::new(config, &Default::default()).unwrap();
         let metric = transform_one(&mut transform, event).unwrap();
 
         assert_eq!(
@@ -953,7 +1258,7 @@ mod tests {
 
         let event = create_event("response_time", "2.5");
         let metadata = event.metadata().clone();
-        let mut transform = LogToMetric::new(config);
+        let mut transform = LogToMetric::new(config, &Default::default()).unwrap();
         let metric = transform_one(&mut transform, event).unwrap();
 
         assert_eq!(
@@ -1,6 +1,8 @@
+use std::collections::HashMap;
+
 use chrono::Utc;
 use lookup::event_path;
-use serde_json::Value;
+use serde_json::{Map, Value};
 use vector_common::TimeZone;
 use vector_config::configurable_component;
 
@@ -8,9 +10,10 @@ use crate::{
     config::{
         log_schema, DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext,
     },
-    event::{self, Event, LogEvent, Metric},
+    event::{self, metric::MetricValue, Event, EventMetadata, LogEvent, Metric},
     internal_events::MetricToLogSerializeError,
     schema,
+    sinks::util::buffer::metrics::MetricSet,
     transforms::{FunctionTransform, OutputBuffer, Transform},
     types::Conversion,
 };
@@ -37,13 +40,61 @@ pub struct MetricToLogConfig {
     /// [global_timezone]: https://vector.dev/docs/reference/configuration//global-options#timezone
     /// [tz_database]: https://en.wikipedia.org/wiki/List_of_tz_database_time_zones
     pub timezone: Option<TimeZone>,
+
+    /// The aggregation temporality to convert metrics to before turning them into logs.
+    ///
+    /// The previous value of each series is kept so that absolute metrics can be turned into
+    /// deltas, and incremental metrics into running totals. Gauges are left as they are, and the
+    /// first absolute value of a series is only used as the baseline for its deltas.
+    ///
+    /// If not set, metrics keep their temporality.
+    pub temporality: Option<Temporality>,
+
+    #[configurable(derived)]
+    pub histogram_layout: HistogramLayout,
+
+    /// Overrides of `histogram_layout` for specific metrics, keyed by metric name.
+    pub histogram_layout_overrides: HashMap<String, HistogramLayout>,
+}
+
+/// The aggregation temporality of metrics.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Temporality {
+    /// Each value is the change since the previous one.
+    Delta,
+
+    /// Each value is the running total of its series.
+    Cumulative,
+}
+
+/// How the buckets of aggregated and exponential histograms are laid out in logs.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum HistogramLayout {
+    /// A single log holds all the buckets of the histogram.
+    Nested,
+
+    /// One log is produced for each bucket of the histogram.
+    ///
+    /// The bucket is held in the `bucket` field of the histogram, along with the total `count`
+    /// and `sum` of the histogram.
+    PerBucket,
+}
+
+impl Default for HistogramLayout {
+    fn default() -> Self {
+        Self::Nested
+    }
 }
 
 impl GenerateConfig for MetricToLogConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
             host_tag: Some("host-tag".to_string()),
-            timezone: None,
+            ..Default::default()
         })
         .unwrap()
     }
@@ -52,10 +103,17 @@ impl GenerateConfig for MetricToLogConfig {
 #[async_trait::async_trait]
 impl TransformConfig for MetricToLogConfig {
     async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
-        Ok(Transform::function(MetricToLog::new(
-            self.host_tag.clone(),
-            self.timezone.unwrap_or(context.globals.timezone),
-        )))
+        Ok(Transform::function(
+            MetricToLog::new(
+                self.host_tag.clone(),
+                self.timezone.unwrap_or(context.globals.timezone),
+            )
+            .with_temporality(self.temporality)
+            .with_histogram_layout(
+                self.histogram_layout,
+                self.histogram_layout_overrides.clone(),
+            ),
+        ))
     }
 
     fn input(&self) -> Input {
@@ -67,7 +125,8 @@ impl TransformConfig for MetricToLogConfig {
     }
 
     fn enable_concurrency(&self) -> bool {
-        true
+        // Converting the temporality relies on seeing every metric of a series in order.
+        self.temporality.is_none()
     }
 }
 
@@ -76,6 +135,10 @@ pub struct MetricToLog {
     timestamp_key: String,
     host_tag: String,
     timezone: TimeZone,
+    temporality: Option<Temporality>,
+    state: MetricSet,
+    histogram_layout: HistogramLayout,
+    histogram_layout_overrides: HashMap<String, HistogramLayout>,
 }
 
 impl MetricToLog {
@@ -87,56 +150,141 @@ impl MetricToLog {
                 host_tag.unwrap_or_else(|| log_schema().host_key().to_string())
             ),
             timezone,
+            temporality: None,
+            state: MetricSet::default(),
+            histogram_layout: HistogramLayout::Nested,
+            histogram_layout_overrides: HashMap::new(),
         }
     }
 
+    pub fn with_temporality(mut self, temporality: Option<Temporality>) -> Self {
+        self.temporality = temporality;
+        self
+    }
+
+    pub fn with_histogram_layout(
+        mut self,
+        layout: HistogramLayout,
+        overrides: HashMap<String, HistogramLayout>,
+    ) -> Self {
+        self.histogram_layout = layout;
+        self.histogram_layout_overrides = overrides;
+        self
+    }
+
     pub fn transform_one(&self, metric: Metric) -> Option<LogEvent> {
-        serde_json::to_value(&metric)
+        self.serialize(&metric)
+            .map(|object| self.object_to_log(object, metric.metadata()))
+    }
+
+    fn serialize(&self, metric: &Metric) -> Option<Map<String, Value>> {
+        serde_json::to_value(metric)
             .map_err(|error| emit!(MetricToLogSerializeError { error }))
             .ok()
             .and_then(|value| match value {
-                Value::Object(object) => {
-                    // TODO: Avoid a clone here
-                    let mut log = LogEvent::new_with_metadata(metric.metadata().clone());
-
-                    for (key, value) in object {
-                        log.insert(event_path!(&key), value);
-                    }
-
-                    let timestamp = log
-                        .remove(self.timestamp_key.as_str())
-                        .and_then(|value| {
-                            Conversion::Timestamp(self.timezone)
-                                .convert(value.coerce_to_bytes())
-                                .ok()
-                        })
-                        .unwrap_or_else(|| event::Value::Timestamp(Utc::now()));
-                    log.insert(log_schema().timestamp_key(), timestamp);
-
-                    if let Some(host) = log.remove_prune(self.host_tag.as_str(), true) {
-                        log.insert(log_schema().host_key(), host);
-                    }
-
-                    Some(log)
-                }
+                Value::Object(object) => Some(object),
                 _ => None,
             })
     }
+
+    fn object_to_log(&self, object: Map<String, Value>, metadata: &EventMetadata) -> LogEvent {
+        // TODO: Avoid a clone here
+        let mut log = LogEvent::new_with_metadata(metadata.clone());
+
+        for (key, value) in object {
+            log.insert(event_path!(&key), value);
+        }
+
+        let timestamp = log
+            .remove(self.timestamp_key.as_str())
+            .and_then(|value| {
+                Conversion::Timestamp(self.timezone)
+                    .convert(value.coerce_to_bytes())
+                    .ok()
+            })
+            .unwrap_or_else(|| event::Value::Timestamp(Utc::now()));
+        log.insert(log_schema().timestamp_key(), timestamp);
+
+        if let Some(host) = log.remove_prune(self.host_tag.as_str(), true) {
+            log.insert(log_schema().host_key(), host);
+        }
+
+        log
+    }
+
+    /// Produces one log per bucket of an aggregated or exponential histogram.
+    fn transform_buckets(&self, metric: Metric) -> Vec<LogEvent> {
+        let (key, buckets, count, sum) = match metric.value() {
+            MetricValue::AggregatedHistogram {
+                buckets,
+                count,
+                sum,
+            } => ("aggregated_histogram", buckets.clone(), *count, *sum),
+            MetricValue::ExponentialHistogram { histogram } => (
+                "exponential_histogram",
+                histogram.to_buckets(),
+                histogram.count,
+                histogram.sum,
+            ),
+            _ => return self.transform_one(metric).into_iter().collect(),
+        };
+
+        let mut object = match self.serialize(&metric) {
+            Some(object) => object,
+            None => return Vec::new(),
+        };
+        object.remove(key);
+
+        buckets
+            .into_iter()
+            .map(|bucket| {
+                let mut log = self.object_to_log(object.clone(), metric.metadata());
+                log.insert(
+                    format!("{}.bucket.upper_limit", key).as_str(),
+                    bucket.upper_limit,
+                );
+                log.insert(format!("{}.bucket.count", key).as_str(), bucket.count);
+                log.insert(format!("{}.count", key).as_str(), count);
+                log.insert(format!("{}.sum", key).as_str(), sum);
+                log
+            })
+            .collect()
+    }
+
+    fn convert_temporality(&mut self, metric: Metric) -> Option<Metric> {
+        match (self.temporality, metric.value()) {
+            (None, _) | (_, MetricValue::Gauge { .. }) => Some(metric),
+            (Some(Temporality::Delta), _) => self.state.make_incremental(metric),
+            (Some(Temporality::Cumulative), _) => self.state.make_absolute(metric),
+        }
+    }
+
+    fn histogram_layout(&self, name: &str) -> HistogramLayout {
+        self.histogram_layout_overrides
+            .get(name)
+            .copied()
+            .unwrap_or(self.histogram_layout)
+    }
 }
 
 impl FunctionTransform for MetricToLog {
     fn transform(&mut self, output: &mut OutputBuffer, event: Event) {
-        let retval: Option<Event> = self
-            .transform_one(event.into_metric())
-            .map(|log| log.into());
-        output.extend(retval.into_iter())
+        let metric = match self.convert_temporality(event.into_metric()) {
+            Some(metric) => metric,
+            None => return,
+        };
+
+        match self.histogram_layout(metric.name()) {
+            HistogramLayout::Nested => output.extend(self.transform_one(metric).map(Event::from)),
+            HistogramLayout::PerBucket => {
+                output.extend(self.transform_buckets(metric).into_iter().map(Event::from))
+            }
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::collections::BTreeMap;
-
     use chrono::{offset::TimeZone, DateTime, Utc};
     use pretty_assertions::assert_eq;
     use tokio::sync::mpsc;
@@ -144,7 +292,7 @@ mod tests {
 
     use super::*;
     use crate::event::{
-        metric::{MetricKind, MetricValue, StatisticKind},
+        metric::{MetricKind, MetricTags, MetricValue, StatisticKind},
         Metric, Value,
     };
     use crate::test_util::components::assert_transform_compliance;
@@ -159,7 +307,7 @@ mod tests {
         assert_transform_compliance(async move {
             let config = MetricToLogConfig {
                 host_tag: Some("host".into()),
-                timezone: None,
+                ..Default::default()
             };
             let (tx, rx) = mpsc::channel(1);
             let (topology, mut out) = create_topology(ReceiverStream::new(rx), config).await;
@@ -182,10 +330,10 @@ mod tests {
         Utc.ymd(2018, 11, 14).and_hms_nano(8, 9, 10, 11)
     }
 
-    fn tags() -> BTreeMap<String, String> {
+    fn tags() -> MetricTags {
         vec![
-            ("host".to_owned(), "localhost".to_owned()),
-            ("some_tag".to_owned(), "some_value".to_owned()),
+            ("host".into(), "localhost".to_owned()),
+            ("some_tag".into(), "some_value".to_owned()),
         ]
         .into_iter()
         .collect()
@@ -410,4 +558,89 @@ mod tests {
         );
         assert_eq!(log.metadata(), &metadata);
     }
+
+    fn transform_all(transform: &mut MetricToLog, metrics: Vec<Metric>) -> Vec<LogEvent> {
+        let mut output = OutputBuffer::default();
+        for metric in metrics {
+            transform.transform(&mut output, metric.into());
+        }
+        output.into_events().map(Event::into_log).collect()
+    }
+
+    #[test]
+    fn converts_absolute_counters_to_deltas() {
+        let mut transform = MetricToLog::new(None, vector_common::TimeZone::default())
+            .with_temporality(Some(Temporality::Delta));
+        let counter = |value| {
+            Metric::new(
+                "counter",
+                MetricKind::Absolute,
+                MetricValue::Counter { value },
+            )
+            .with_timestamp(Some(ts()))
+        };
+
+        let logs = transform_all(&mut transform, vec![counter(5.0), counter(8.0)]);
+
+        // The first value is only the baseline of the series.
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0]["counter.value"], Value::from(3.0));
+        assert_eq!(logs[0]["kind"], Value::from("incremental"));
+    }
+
+    #[test]
+    fn splits_histogram_buckets_per_metric_name() {
+        let mut transform = MetricToLog::new(None, vector_common::TimeZone::default())
+            .with_histogram_layout(
+                HistogramLayout::Nested,
+                vec![("split".to_owned(), HistogramLayout::PerBucket)]
+                    .into_iter()
+                    .collect(),
+            );
+        let histogram = |name| {
+            Metric::new(
+                name,
+                MetricKind::Absolute,
+                MetricValue::AggregatedHistogram {
+                    buckets: vector_core::buckets![1.0 => 10, 2.0 => 20],
+                    count: 30,
+                    sum: 50.0,
+                },
+            )
+            .with_timestamp(Some(ts()))
+        };
+
+        let logs = transform_all(
+            &mut transform,
+            vec![histogram("split"), histogram("nested")],
+        );
+
+        assert_eq!(logs.len(), 3);
+        for (log, (upper_limit, count)) in logs.iter().zip([(1.0, 10), (2.0, 20)]) {
+            let collected: Vec<_> = log.all_fields().unwrap().collect();
+            assert_eq!(
+                collected,
+                vec![
+                    (
+                        String::from("aggregated_histogram.bucket.count"),
+                        &Value::from(count)
+                    ),
+                    (
+                        String::from("aggregated_histogram.bucket.upper_limit"),
+                        &Value::from(upper_limit)
+                    ),
+                    (String::from("aggregated_histogram.count"), &Value::from(30)),
+                    (String::from("aggregated_histogram.sum"), &Value::from(50.0)),
+                    (String::from("kind"), &Value::from("absolute")),
+                    (String::from("name"), &Value::from("split")),
+                    (String::from("timestamp"), &Value::from(ts())),
+                ]
+            );
+        }
+        assert_eq!(logs[2]["name"], Value::from("nested"));
+        assert_eq!(
+            logs[2]["aggregated_histogram.buckets[1].count"],
+            Value::from(20)
+        );
+    }
 }
@@ -6,14 +6,22 @@ use snafu::Snafu;
 
 #[cfg(feature = "transforms-aggregate")]
 pub mod aggregate;
+#[cfg(feature = "transforms-aggregate_logs")]
+pub mod aggregate_logs;
 #[cfg(feature = "transforms-aws_ec2_metadata")]
 pub mod aws_ec2_metadata;
 #[cfg(feature = "transforms-dedupe")]
 pub mod dedupe;
+#[cfg(feature = "transforms-encrypt_fields")]
+pub mod encrypt_fields;
 #[cfg(feature = "transforms-filter")]
 pub mod filter;
 #[cfg(feature = "transforms-geoip")]
 pub mod geoip;
+#[cfg(feature = "transforms-ip_enrichment")]
+pub mod ip_enrichment;
+#[cfg(feature = "transforms-join")]
+pub mod join;
 pub mod log_to_metric;
 #[cfg(feature = "transforms-lua")]
 pub mod lua;
@@ -21,6 +29,8 @@ pub mod lua;
 pub mod metric_to_log;
 #[cfg(feature = "transforms-pipelines")]
 pub mod pipelines;
+#[cfg(feature = "transforms-redact_pii")]
+pub mod redact_pii;
 #[cfg(feature = "transforms-reduce")]
 pub mod reduce;
 #[cfg(feature = "transforms-remap")]
@@ -37,7 +47,8 @@ pub mod throttle;
 use vector_common::config::ComponentKey;
 use vector_config::{configurable_component, NamedComponent};
 pub use vector_core::transform::{
-    FunctionTransform, OutputBuffer, SyncTransform, TaskTransform, Transform, TransformOutputs,
+    FunctionTransform, OutputBuffer, OutputsTaskTransform, SyncTransform, TaskTransform, Transform,
+    TransformOutputs,
     TransformOutputsBuf,
 };
 use vector_core::{
@@ -66,6 +77,10 @@ pub enum Transforms {
     #[cfg(feature = "transforms-aggregate")]
     Aggregate(#[configurable(derived)] aggregate::AggregateConfig),
 
+    /// Aggregate logs.
+    #[cfg(feature = "transforms-aggregate_logs")]
+    AggregateLogs(#[configurable(derived)] aggregate_logs::AggregateLogsConfig),
+
     /// AWS EC2 metadata.
     #[cfg(feature = "transforms-aws_ec2_metadata")]
     AwsEc2Metadata(#[configurable(derived)] aws_ec2_metadata::Ec2Metadata),
@@ -74,6 +89,10 @@ pub enum Transforms {
     #[cfg(feature = "transforms-dedupe")]
     Dedupe(#[configurable(derived)] dedupe::DedupeConfig),
 
+    /// Encrypt fields.
+    #[cfg(feature = "transforms-encrypt_fields")]
+    EncryptFields(#[configurable(derived)] encrypt_fields::EncryptFieldsConfig),
+
     /// Filter.
     #[cfg(feature = "transforms-filter")]
     Filter(#[configurable(derived)] filter::FilterConfig),
@@ -82,6 +101,14 @@ pub enum Transforms {
     #[cfg(feature = "transforms-geoip")]
     Geoip(#[configurable(derived)] geoip::GeoipConfig),
 
+    /// IP enrichment.
+    #[cfg(feature = "transforms-ip_enrichment")]
+    IpEnrichment(#[configurable(derived)] ip_enrichment::IpEnrichmentConfig),
+
+    /// Join.
+    #[cfg(feature = "transforms-join")]
+    Join(#[configurable(derived)] join::JoinConfig),
+
     /// Log to metric.
     LogToMetric(#[configurable(derived)] log_to_metric::LogToMetricConfig),
 
@@ -102,6 +129,10 @@ pub enum Transforms {
     #[cfg(feature = "transforms-pipelines")]
     Pipelines(#[configurable(derived)] pipelines::PipelinesConfig),
 
+    /// Redact PII.
+    #[cfg(feature = "transforms-redact_pii")]
+    RedactPii(#[configurable(derived)] redact_pii::RedactPiiConfig),
+
     /// Reduce.
     #[cfg(feature = "transforms-reduce")]
     Reduce(#[configurable(derived)] reduce::ReduceConfig),
@@ -143,14 +174,22 @@ impl NamedComponent for Transforms {
         match self {
             #[cfg(feature = "transforms-aggregate")]
             Transforms::Aggregate(config) => config.get_component_name(),
+            #[cfg(feature = "transforms-aggregate_logs")]
+            Transforms::AggregateLogs(config) => config.get_component_name(),
             #[cfg(feature = "transforms-aws_ec2_metadata")]
             Transforms::AwsEc2Metadata(config) => config.get_component_name(),
             #[cfg(feature = "transforms-dedupe")]
             Transforms::Dedupe(config) => config.get_component_name(),
+            #[cfg(feature = "transforms-encrypt_fields")]
+            Transforms::EncryptFields(config) => config.get_component_name(),
             #[cfg(feature = "transforms-filter")]
             Transforms::Filter(config) => config.get_component_name(),
             #[cfg(feature = "transforms-geoip")]
             Transforms::Geoip(config) => config.get_component_name(),
+            #[cfg(feature = "transforms-ip_enrichment")]
+            Transforms::IpEnrichment(config) => config.get_component_name(),
+            #[cfg(feature = "transforms-join")]
+            Transforms::Join(config) => config.get_component_name(),
             Transforms::LogToMetric(config) => config.get_component_name(),
             #[cfg(feature = "transforms-lua")]
             Transforms::Lua(config) => config.get_component_name(),
@@ -160,6 +199,8 @@ impl NamedComponent for Transforms {
             Transforms::Pipeline(config) => config.get_component_name(),
             #[cfg(feature = "transforms-pipelines")]
             Transforms::Pipelines(config) => config.get_component_name(),
+            #[cfg(feature = "transforms-redact_pii")]
+            Transforms::RedactPii(config) => config.get_component_name(),
             #[cfg(feature = "transforms-reduce")]
             Transforms::Reduce(config) => config.get_component_name(),
             #[cfg(feature = "transforms-remap")]
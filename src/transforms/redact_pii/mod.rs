@@ -0,0 +1,336 @@
+use std::{borrow::Cow, ops::Range};
+
+use openssl::{hash::MessageDigest, pkey::PKey, pkey::Private, sign::Signer};
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+
+use crate::{
+    config::{DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext},
+    event::{Event, Value},
+    internal_events::RedactPiiRedacted,
+    schema,
+    transforms::{FunctionTransform, OutputBuffer, Transform},
+};
+
+mod detector;
+
+pub use detector::Detector;
+
+/// Configuration for the `redact_pii` transform.
+#[configurable_component(transform("redact_pii"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct RedactPiiConfig {
+    /// The detectors used to find personally identifiable information.
+    ///
+    /// All of the string fields of events are scanned, recursively. By default, all detectors are
+    /// used.
+    #[serde(default = "Detector::all")]
+    pub detectors: Vec<Detector>,
+
+    /// The action taken on the information that is found.
+    #[serde(default)]
+    pub action: Action,
+
+    /// The text replacing the information that is found, when `action` is `mask`.
+    #[serde(default = "default_replacement")]
+    pub replacement: String,
+
+    /// The key used to derive tokens, when `action` is `tokenize`.
+    ///
+    /// The same information is always replaced by the same token for a given key, so that redacted
+    /// events can still be correlated.
+    pub key: Option<SensitiveString>,
+}
+
+fn default_replacement() -> String {
+    "[REDACTED]".to_string()
+}
+
+/// The action taken on personally identifiable information.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    /// The information is replaced by the `replacement` text.
+    Mask,
+
+    /// The information is replaced by its SHA-256 hash, encoded as hexadecimal.
+    Hash,
+
+    /// The information is replaced by a token, derived from the information and the `key` with
+    /// HMAC-SHA256.
+    Tokenize,
+
+    /// The field, or array element, holding the information is removed from the event.
+    Remove,
+}
+
+impl Default for Action {
+    fn default() -> Self {
+        Self::Mask
+    }
+}
+
+impl GenerateConfig for RedactPiiConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            detectors: Detector::all(),
+            action: Action::Mask,
+            replacement: default_replacement(),
+            key: None,
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl TransformConfig for RedactPiiConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        RedactPii::new(self).map(Transform::function)
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+}
+
+/// How the information that is found is replaced.
+#[derive(Clone)]
+enum Redaction {
+    Mask(String),
+    Hash,
+    Tokenize(PKey<Private>),
+    Remove,
+}
+
+#[derive(Clone)]
+pub struct RedactPii {
+    detectors: Vec<Detector>,
+    redaction: Redaction,
+}
+
+impl RedactPii {
+    pub fn new(config: &RedactPiiConfig) -> crate::Result<Self> {
+        let redaction = match config.action {
+            Action::Mask => Redaction::Mask(config.replacement.clone()),
+            Action::Hash => Redaction::Hash,
+            Action::Tokenize => {
+                let key = config
+                    .key
+                    .as_ref()
+                    .ok_or("`key` is required when `action` is `tokenize`")?;
+                Redaction::Tokenize(PKey::hmac(key.inner().as_bytes())?)
+            }
+            Action::Remove => Redaction::Remove,
+        };
+
+        Ok(Self {
+            detectors: config.detectors.clone(),
+            redaction,
+        })
+    }
+
+    /// Redacts the information found in a value, recursively, counting the detections of each
+    /// detector.
+    ///
+    /// Returns `true` if the value itself has to be removed.
+    fn redact(&self, value: &mut Value, counts: &mut [usize]) -> bool {
+        match value {
+            Value::Bytes(bytes) => {
+                let string = match std::str::from_utf8(bytes) {
+                    Ok(string) => string,
+                    Err(_) => return false,
+                };
+
+                let mut ranges = Vec::new();
+                for (detector, count) in self.detectors.iter().zip(counts.iter_mut()) {
+                    *count += detector.detect(string, &mut ranges);
+                }
+
+                if ranges.is_empty() {
+                    return false;
+                }
+                if matches!(self.redaction, Redaction::Remove) {
+                    return true;
+                }
+
+                let redacted = self.replace(string, merge(ranges));
+                *value = Value::from(redacted);
+                false
+            }
+            Value::Object(map) => {
+                map.retain(|_, value| !self.redact(value, counts));
+                false
+            }
+            Value::Array(array) => {
+                array.retain_mut(|value| !self.redact(value, counts));
+                false
+            }
+            _ => false,
+        }
+    }
+
+    /// Replaces the given, ordered and disjoint, ranges of a string.
+    fn replace(&self, string: &str, ranges: Vec<Range<usize>>) -> String {
+        let mut redacted = String::with_capacity(string.len());
+        let mut end = 0;
+        for range in ranges {
+            redacted.push_str(&string[end..range.start]);
+            redacted.push_str(&self.replacement(&string[range.clone()]));
+            end = range.end;
+        }
+        redacted.push_str(&string[end..]);
+
+        redacted
+    }
+
+    fn replacement(&self, found: &str) -> Cow<'_, str> {
+        match &self.redaction {
+            Redaction::Mask(replacement) => Cow::Borrowed(replacement),
+            Redaction::Hash => Cow::Owned(hex::encode(openssl::sha::sha256(found.as_bytes()))),
+            Redaction::Tokenize(key) => Signer::new(MessageDigest::sha256(), key)
+                .and_then(|mut signer| signer.sign_oneshot_to_vec(found.as_bytes()))
+                .map(|signature| Cow::Owned(format!("tok_{}", hex::encode(&signature[..16]))))
+                // Signing with an HMAC key can't fail in practice, but the information must not
+                // leak if it does.
+                .unwrap_or(Cow::Borrowed("[REDACTED]")),
+            Redaction::Remove => unreachable!("removed values aren't replaced"),
+        }
+    }
+}
+
+/// Sorts ranges and merges the ones that overlap, as several detectors can find the same
+/// information.
+fn merge(mut ranges: Vec<Range<usize>>) -> Vec<Range<usize>> {
+    ranges.sort_by_key(|range| (range.start, range.end));
+
+    let mut merged: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start < last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+
+    merged
+}
+
+impl FunctionTransform for RedactPii {
+    fn transform(&mut self, output: &mut OutputBuffer, mut event: Event) {
+        let mut counts = vec![0; self.detectors.len()];
+        self.redact(event.as_mut_log().value_mut(), &mut counts);
+
+        for (detector, count) in self.detectors.iter().zip(counts) {
+            if count > 0 {
+                emit!(RedactPiiRedacted {
+                    detector: detector.as_str(),
+                    count,
+                });
+            }
+        }
+
+        output.push(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{event::LogEvent, transforms::test::transform_one};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<RedactPiiConfig>();
+    }
+
+    fn config(action: Action) -> RedactPiiConfig {
+        RedactPiiConfig {
+            detectors: Detector::all(),
+            action,
+            replacement: default_replacement(),
+            key: Some("secret key".to_string().into()),
+        }
+    }
+
+    fn event() -> Event {
+        let mut log = LogEvent::from("user jane@example.com paid with 4111 1111 1111 1111");
+        log.insert("client.ip", "10.1.2.3");
+        log.insert("client.name", "jane");
+        log.insert("tags[0]", "ssn 123-12-1234");
+        log.insert("tags[1]", "safe");
+        log.insert("count", 3);
+        Event::from(log)
+    }
+
+    fn redact(action: Action) -> LogEvent {
+        let mut transform = RedactPii::new(&config(action)).unwrap();
+        transform_one(&mut transform, event()).unwrap().into_log()
+    }
+
+    #[test]
+    fn masks_information() {
+        let log = redact(Action::Mask);
+
+        assert_eq!(
+            log["message"],
+            "user [REDACTED] paid with [REDACTED]".into()
+        );
+        assert_eq!(log["client.ip"], "[REDACTED]".into());
+        assert_eq!(log["client.name"], "jane".into());
+        assert_eq!(log["tags[0]"], "ssn [REDACTED]".into());
+        assert_eq!(log["tags[1]"], "safe".into());
+        assert_eq!(log["count"], 3.into());
+    }
+
+    #[test]
+    fn hashes_information() {
+        let log = redact(Action::Hash);
+
+        assert_eq!(
+            log["client.ip"],
+            "8e099943f7370d7ecd46ad56442e02670e8ed1a8b2ba5c42a69f06f1c050189b".into()
+        );
+    }
+
+    #[test]
+    fn tokenizes_information() {
+        let log = redact(Action::Tokenize);
+        let token = log["client.ip"].to_string_lossy();
+
+        assert!(token.starts_with("tok_"));
+        assert_eq!(token.len(), 36);
+        // Tokens are stable for a given key.
+        assert_eq!(redact(Action::Tokenize)["client.ip"], token.into());
+    }
+
+    #[test]
+    fn removes_information() {
+        let log = redact(Action::Remove);
+
+        assert!(log.get("message").is_none());
+        assert!(log.get("client.ip").is_none());
+        assert_eq!(log["client.name"], "jane".into());
+        assert_eq!(log["tags[0]"], "safe".into());
+        assert!(log.get("tags[1]").is_none());
+    }
+
+    #[test]
+    fn requires_key_to_tokenize() {
+        let config = RedactPiiConfig {
+            key: None,
+            ..config(Action::Tokenize)
+        };
+
+        assert!(RedactPii::new(&config).is_err());
+    }
+
+    #[test]
+    fn merges_overlapping_ranges() {
+        assert_eq!(merge(vec![5..9, 0..3, 2..4, 8..12]), vec![0..4, 5..12]);
+    }
+}
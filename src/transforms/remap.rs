@@ -1,9 +1,10 @@
-use std::sync::Arc;
+use std::sync::{Arc, Weak};
 use std::{
     collections::BTreeMap,
-    fs::File,
+    fs::{self, File},
     io::{self, Read},
     path::PathBuf,
+    time::{Duration, SystemTime},
 };
 
 use lookup::lookup_v2::ValuePath;
@@ -16,7 +17,11 @@ use vector_core::compile_vrl;
 use vector_core::config::LogNamespace;
 use vector_core::schema::Definition;
 
-use vector_vrl_functions::set_semantic_meaning::MeaningList;
+use serde::{Deserialize, Serialize};
+use vector_vrl_functions::{
+    set_semantic_meaning::MeaningList,
+    state::{ComponentState, StateEntry},
+};
 use vrl::prelude::state::TypeState;
 use vrl::{
     diagnostic::{Formatter, Note},
@@ -29,7 +34,7 @@ use crate::{
         log_schema, ComponentKey, DataType, Input, Output, TransformConfig, TransformContext,
     },
     event::{Event, TargetEvents, VrlTarget},
-    internal_events::{RemapMappingAbort, RemapMappingError},
+    internal_events::{RemapMappingAbort, RemapMappingError, RemapStateSnapshotError},
     schema,
     transforms::{SyncTransform, Transform, TransformOutputsBuf},
     Result,
@@ -37,6 +42,13 @@ use crate::{
 
 const DROPPED: &str = "dropped";
 
+/// The file the state of the transform is persisted in, within its data directory.
+const STATE_SNAPSHOT_FILE: &str = "state.json";
+
+const fn default_state_snapshot_interval_secs() -> u64 {
+    60
+}
+
 /// Configuration for the `remap` transform.
 #[configurable_component(transform("remap"))]
 #[derive(Clone, Debug, Derivative)]
@@ -108,6 +120,28 @@ pub struct RemapConfig {
     /// fields describing why the event was dropped.
     pub reroute_dropped: bool,
 
+    /// Persists the state of the transform, set with the `set_state` function, in its data
+    /// directory.
+    ///
+    /// The state is snapshot periodically, and when the transform is stopped, then restored when it
+    /// is started again, so that it survives restarts of Vector. Otherwise, it is only kept in
+    /// memory.
+    pub persist_state: bool,
+
+    /// The interval between the snapshots of the state, in seconds, when `persist_state` is
+    /// enabled.
+    #[serde(default = "default_state_snapshot_interval_secs")]
+    #[derivative(Default(value = "default_state_snapshot_interval_secs()"))]
+    pub state_snapshot_interval_secs: u64,
+
+    /// The directory used to persist the state of the transform, when `persist_state` is enabled.
+    ///
+    /// By default, the [global `data_dir` option][global_data_dir] is used. Make sure the running
+    /// user has write permissions to this directory.
+    ///
+    /// [global_data_dir]: https://vector.dev/docs/reference/configuration/global-options/#data_dir
+    pub data_dir: Option<PathBuf>,
+
     #[configurable(derived)]
     #[serde(default)]
     pub runtime: VrlRuntime,
@@ -117,6 +151,7 @@ impl RemapConfig {
     fn compile_vrl_program(
         &self,
         enrichment_tables: enrichment::TableRegistry,
+        state: ComponentState,
         merged_schema_definition: schema::Definition,
     ) -> Result<(
         vrl::Program,
@@ -154,6 +189,7 @@ impl RemapConfig {
 
         config.set_custom(enrichment_tables);
         config.set_custom(MeaningList::default());
+        config.set_custom(state);
 
         compile_vrl(&source, &functions, &state, config)
             .map_err(|diagnostics| {
@@ -207,6 +243,7 @@ impl TransformConfig for RemapConfig {
         let default_definition = self
             .compile_vrl_program(
                 enrichment::TableRegistry::default(),
+                ComponentState::default(),
                 input_definition.clone(),
             )
             .map(|(program, _, _, external_context)| {
@@ -218,8 +255,9 @@ impl TransformConfig for RemapConfig {
 
                 let state = program.final_type_state();
 
-                let mut new_type_def = Definition::new_with_default_metadata(
+                let mut new_type_def = Definition::new(
                     state.external.target_kind().clone(),
+                    state.external.metadata_kind().clone(),
                     input_definition.log_namespaces().clone(),
                 );
                 for (id, path) in meaning {
@@ -308,6 +346,8 @@ where
     default_schema_definition: Arc<schema::Definition>,
     dropped_schema_definition: Arc<schema::Definition>,
     runner: Runner,
+    /// Snapshots the state once the last copy of the transform is dropped.
+    persisted_state: Option<Arc<PersistedState>>,
 }
 
 pub trait VrlRunner {
@@ -350,15 +390,32 @@ impl Remap<AstRunner> {
         config: RemapConfig,
         context: &TransformContext,
     ) -> crate::Result<(Self, String)> {
+        let persisted_state = config
+            .persist_state
+            .then(|| PersistedState::load(&config, context))
+            .transpose()?;
+        let state = persisted_state
+            .as_ref()
+            .map(|persisted| persisted.state.clone())
+            .unwrap_or_default();
+
         let (program, warnings, _, _) = config.compile_vrl_program(
             context.enrichment_tables.clone(),
+            state,
             context.merged_schema_definition.clone(),
         )?;
 
+        let persisted_state = persisted_state.map(|persisted| {
+            let persisted = Arc::new(persisted);
+            let interval = Duration::from_secs(config.state_snapshot_interval_secs.max(1));
+            tokio::spawn(snapshot_state(Arc::downgrade(&persisted), interval));
+            persisted
+        });
+
         let runtime = Runtime::default();
         let runner = AstRunner { runtime };
 
-        Self::new(config, context, program, runner).map(|remap| (remap, warnings))
+        Self::new(config, context, program, runner, persisted_state).map(|remap| (remap, warnings))
     }
 }
 
@@ -371,6 +428,7 @@ where
         context: &TransformContext,
         program: Program,
         runner: Runner,
+        persisted_state: Option<Arc<PersistedState>>,
     ) -> crate::Result<Self> {
         let default_schema_definition = context
             .schema_definitions
@@ -395,6 +453,7 @@ where
             default_schema_definition: Arc::new(default_schema_definition),
             dropped_schema_definition: Arc::new(dropped_schema_definition),
             runner,
+            persisted_state,
         })
     }
 
@@ -564,6 +623,105 @@ fn push_dropped(
     output.push_named(DROPPED, event)
 }
 
+/// An entry of the snapshots of the state, with its expiration as a timestamp.
+#[derive(Deserialize, Serialize)]
+struct SnapshotEntry {
+    key: String,
+    value: value::Value,
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// The state of the transform, persisted in its data directory.
+#[derive(Debug)]
+struct PersistedState {
+    state: ComponentState,
+    path: PathBuf,
+}
+
+impl PersistedState {
+    /// Restores the state from its last snapshot, if there is one.
+    fn load(config: &RemapConfig, context: &TransformContext) -> Result<Self> {
+        let component_id = context.key.as_ref().map_or("remap", ComponentKey::id);
+        let path = context
+            .globals
+            .resolve_and_make_data_subdir(config.data_dir.as_ref(), component_id)?
+            .join(STATE_SNAPSHOT_FILE);
+
+        let state = match fs::read(&path) {
+            Ok(snapshot) => {
+                let entries = serde_json::from_slice::<Vec<SnapshotEntry>>(&snapshot)
+                    .with_context(|_| StateSnapshotInvalidSnafu { path: path.clone() })?;
+                ComponentState::from_entries(entries.into_iter().map(|entry| {
+                    (
+                        entry.key,
+                        StateEntry {
+                            value: entry.value,
+                            expires_at: entry.expires_at.map(SystemTime::from),
+                        },
+                    )
+                }))
+            }
+            Err(error) if error.kind() == io::ErrorKind::NotFound => ComponentState::default(),
+            Err(source) => {
+                return Err(Box::new(BuildError::StateSnapshotReadFailed {
+                    path,
+                    source,
+                }))
+            }
+        };
+
+        Ok(Self { state, path })
+    }
+
+    /// Writes a snapshot of the state, to a temporary file that then replaces the last snapshot.
+    fn snapshot(&self) {
+        let entries = self
+            .state
+            .entries()
+            .into_iter()
+            .map(|(key, entry)| SnapshotEntry {
+                key,
+                value: entry.value,
+                expires_at: entry.expires_at.map(Into::into),
+            })
+            .collect::<Vec<_>>();
+
+        let tmp_path = self.path.with_extension("json.tmp");
+        let result = serde_json::to_vec(&entries)
+            .map_err(io::Error::from)
+            .and_then(|snapshot| fs::write(&tmp_path, snapshot))
+            .and_then(|()| fs::rename(&tmp_path, &self.path));
+        if let Err(error) = result {
+            emit!(RemapStateSnapshotError {
+                error,
+                path: &self.path,
+            });
+        }
+    }
+}
+
+impl Drop for PersistedState {
+    fn drop(&mut self) {
+        self.snapshot();
+    }
+}
+
+/// Snapshots the state periodically, until the transform is dropped.
+async fn snapshot_state(persisted: Weak<PersistedState>, interval: Duration) {
+    let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + interval, interval);
+    loop {
+        interval.tick().await;
+        match persisted.upgrade() {
+            Some(persisted) => {
+                // The transform may be dropped meanwhile, in which case this snapshot is taken
+                // again once this task is done with it.
+                let _ = tokio::task::spawn_blocking(move || persisted.snapshot()).await;
+            }
+            None => break,
+        }
+    }
+}
+
 #[derive(Debug, Snafu)]
 pub enum BuildError {
     #[snafu(display("must provide exactly one of `source` or `file` configuration"))]
@@ -573,6 +731,14 @@ pub enum BuildError {
     FileOpenFailed { path: PathBuf, source: io::Error },
     #[snafu(display("Could not read vrl program {:?}: {}", path, source))]
     FileReadFailed { path: PathBuf, source: io::Error },
+
+    #[snafu(display("Could not read the state snapshot {:?}: {}", path, source))]
+    StateSnapshotReadFailed { path: PathBuf, source: io::Error },
+    #[snafu(display("Invalid state snapshot {:?}: {}", path, source))]
+    StateSnapshotInvalid {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
 }
 
 #[cfg(test)]
@@ -975,7 +1141,7 @@ mod tests {
                 MetricKind::Absolute,
                 MetricValue::Counter { value: 1.0 },
             );
-            metric.insert_tag("hello".into(), "world".into());
+            metric.insert_tag("hello", "world".into());
             Event::Metric(metric)
         };
 
@@ -985,7 +1151,7 @@ mod tests {
                 MetricKind::Absolute,
                 MetricValue::Counter { value: 1.0 },
             );
-            metric.insert_tag("hello".into(), "goodbye".into());
+            metric.insert_tag("hello", "goodbye".into());
             Event::Metric(metric)
         };
 
@@ -995,7 +1161,7 @@ mod tests {
                 MetricKind::Absolute,
                 MetricValue::Counter { value: 1.0 },
             );
-            metric.insert_tag("not_hello".into(), "oops".into());
+            metric.insert_tag("not_hello", "oops".into());
             Event::Metric(metric)
         };
 
@@ -1312,6 +1478,28 @@ mod tests {
         assert!(out.named[DROPPED].is_empty());
     }
 
+    #[test]
+    fn check_remap_tracks_metadata_kind() {
+        let conf = RemapConfig {
+            source: Some(r#"%tenant = "acme""#.to_owned()),
+            ..Default::default()
+        };
+
+        let outputs = conf.outputs(&schema::Definition::new_with_default_metadata(
+            Kind::any_object(),
+            [LogNamespace::Vector],
+        ));
+
+        assert_eq!(
+            outputs[0]
+                .log_schema_definition
+                .as_ref()
+                .unwrap()
+                .metadata_kind(),
+            &Kind::object(BTreeMap::from([("tenant".into(), Kind::bytes())]))
+        );
+    }
+
     #[tokio::test]
     async fn check_remap_branching_metrics_with_output() {
         init_test();
@@ -1345,6 +1533,56 @@ mod tests {
         COMPONENT_MULTIPLE_OUTPUTS_TESTS.assert(&["output"]);
     }
 
+    #[test]
+    fn check_remap_keeps_state_between_events() {
+        let conf = RemapConfig {
+            source: Some(
+                indoc! {r#"
+                .count = (int(get_state(string!(.user))) ?? 0) + 1
+                set_state(string!(.user), .count)
+            "#}
+                .to_string(),
+            ),
+            ..Default::default()
+        };
+        let mut tform = remap(conf).unwrap();
+
+        for (user, count) in [("alice", 1), ("bob", 1), ("alice", 2)] {
+            let mut event = LogEvent::default();
+            event.insert("user", user);
+            let result = transform_one(&mut tform, event.into()).unwrap();
+            assert_eq!(get_field_string(&result, "count"), count.to_string());
+        }
+    }
+
+    #[tokio::test]
+    async fn check_remap_persists_state() {
+        let data_dir = crate::test_util::temp_dir();
+        std::fs::create_dir_all(&data_dir).unwrap();
+        let conf = RemapConfig {
+            source: Some(
+                indoc! {r#"
+                .seen = get_state("seen")
+                set_state("seen", true)
+            "#}
+                .to_string(),
+            ),
+            persist_state: true,
+            data_dir: Some(data_dir),
+            ..Default::default()
+        };
+
+        let mut tform = remap(conf.clone()).unwrap();
+        let result = transform_one(&mut tform, LogEvent::default().into()).unwrap();
+        assert_eq!(result.as_log()["seen"], Value::Null);
+        // The state is snapshot once the transform is dropped.
+        drop(tform);
+
+        let mut tform = remap(conf).unwrap();
+        let result = transform_one(&mut tform, LogEvent::default().into()).unwrap();
+        assert_eq!(result.as_log()["seen"], true.into());
+    }
+
     struct CollectedOuput {
         primary: OutputBuffer,
         named: HashMap<String, OutputBuffer>,
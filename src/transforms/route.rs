@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use indexmap::IndexMap;
 use vector_config::configurable_component;
 use vector_core::transform::SyncTransform;
@@ -15,6 +17,8 @@ pub(crate) const UNMATCHED_ROUTE: &str = "_unmatched";
 #[derive(Clone)]
 pub struct Route {
     conditions: Vec<(String, Condition)>,
+    split: Option<Split>,
+    unmatched_route: String,
 }
 
 impl Route {
@@ -24,7 +28,88 @@ impl Route {
             let condition = condition.build(&context.enrichment_tables)?;
             conditions.push((output_name.clone(), condition));
         }
-        Ok(Self { conditions })
+        let split = config
+            .split
+            .as_ref()
+            .map(|split| Split::new(split, context))
+            .transpose()?;
+        let unmatched_route = config
+            .reroute_unmatched
+            .clone()
+            .unwrap_or_else(|| UNMATCHED_ROUTE.to_string());
+
+        Ok(Self {
+            conditions,
+            split,
+            unmatched_route,
+        })
+    }
+}
+
+/// Splits events between weighted outputs.
+#[derive(Clone)]
+struct Split {
+    /// The outputs, along with the cumulated weights up to, and including, each of them.
+    outputs: Vec<(String, u64)>,
+    total_weight: u64,
+    key_field: Option<String>,
+    condition: Option<Condition>,
+    count: u64,
+}
+
+impl Split {
+    fn new(config: &SplitConfig, context: &TransformContext) -> crate::Result<Self> {
+        let mut total_weight = 0;
+        let outputs = config
+            .weights
+            .iter()
+            .map(|(output_name, weight)| {
+                total_weight += u64::from(*weight);
+                (output_name.clone(), total_weight)
+            })
+            .collect();
+        let condition = config
+            .condition
+            .as_ref()
+            .map(|condition| condition.build(&context.enrichment_tables))
+            .transpose()?;
+
+        Ok(Self {
+            outputs,
+            total_weight,
+            key_field: config.key_field.clone(),
+            condition,
+            count: 0,
+        })
+    }
+
+    /// Picks the output of an event.
+    ///
+    /// Events are picked by the hash of their `key_field`, so that events with the same value are
+    /// consistently sent to the same output, or in turn, when they don't have the field.
+    fn pick(&mut self, event: &Event) -> &str {
+        let value = self.key_field.as_ref().and_then(|key_field| match event {
+            Event::Log(event) => event
+                .get(key_field.as_str())
+                .map(|value| value.to_string_lossy()),
+            Event::Trace(event) => event
+                .get(key_field.as_str())
+                .map(|value| value.to_string_lossy()),
+            Event::Metric(metric) => metric.tag_value(key_field),
+        });
+
+        let num = match value {
+            Some(value) => seahash::hash(value.as_bytes()),
+            None => {
+                self.count = self.count.wrapping_add(1);
+                self.count
+            }
+        } % self.total_weight;
+
+        let index = self
+            .outputs
+            .partition_point(|(_, cumulated_weight)| *cumulated_weight <= num);
+        &self.outputs[index].0
     }
 }
 
@@ -34,17 +119,33 @@ impl SyncTransform for Route {
         event: Event,
         output: &mut vector_core::transform::TransformOutputsBuf,
     ) {
-        let mut check_failed: usize = 0;
+        let mut matched = false;
         for (output_name, condition) in &self.conditions {
             let (result, event) = condition.check(event.clone());
             if result {
                 output.push_named(output_name, event);
-            } else {
-                check_failed += 1;
+                matched = true;
             }
         }
-        if check_failed == self.conditions.len() {
-            output.push_named(UNMATCHED_ROUTE, event);
+
+        let event = match self.split.as_mut() {
+            Some(split) => {
+                let (result, event) = match split.condition.as_ref() {
+                    Some(condition) => condition.check(event),
+                    None => (true, event),
+                };
+                if result {
+                    let output_name = split.pick(&event);
+                    output.push_named(output_name, event);
+                    return;
+                }
+                event
+            }
+            None => event,
+        };
+
+        if !matched {
+            output.push_named(&self.unmatched_route, event);
         }
     }
 }
@@ -62,14 +163,56 @@ pub struct RouteConfig {
     ///
     /// Both `_unmatched`, as well as `_default`, are reserved output names and cannot be used as a
     /// route name.
-    #[serde(alias = "lanes")]
+    #[serde(alias = "lanes", default)]
     route: IndexMap<String, AnyCondition>,
+
+    #[configurable(derived)]
+    split: Option<SplitConfig>,
+
+    /// The route to which events that don't match any route are sent, instead of the `_unmatched`
+    /// output.
+    ///
+    /// This can be either a route or an output of `split`. When set, the `_unmatched` output
+    /// doesn't exist.
+    reroute_unmatched: Option<String>,
+}
+
+/// Configuration for splitting events between weighted outputs.
+///
+/// Each event is sent to exactly one of the outputs of the split, independently of `route`, which
+/// enables canary deliveries such as sending 20% of the traffic to a new pipeline.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SplitConfig {
+    /// A table of output identifiers to their weight.
+    ///
+    /// Each output receives its share of events, relative to the sum of all weights, so weights can
+    /// be expressed as percentages. Outputs are referenced like routes, with the name
+    /// `<transform_name>.<output_id>`.
+    weights: IndexMap<String, u32>,
+
+    /// The name of the field whose value is hashed to pick the output of an event.
+    ///
+    /// Events with the same value are consistently sent to the same output. For metrics, this is
+    /// the name of a tag. If left unspecified, or if the event doesn't have `key_field`, events are
+    /// sent to outputs in turn.
+    key_field: Option<String>,
+
+    /// A logical condition restricting which events are split.
+    ///
+    /// Events that don't match it are handled as if there was no split.
+    condition: Option<AnyCondition>,
 }
 
 #[cfg(feature = "transforms-pipelines")]
 impl RouteConfig {
     pub(crate) const fn new(route: IndexMap<String, AnyCondition>) -> Self {
-        Self { route }
+        Self {
+            route,
+            split: None,
+            reroute_unmatched: None,
+        }
     }
 }
 
@@ -77,11 +220,22 @@ impl GenerateConfig for RouteConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
             route: IndexMap::new(),
+            split: None,
+            reroute_unmatched: None,
         })
         .unwrap()
     }
 }
 
+impl RouteConfig {
+    /// The names of the routes and split outputs.
+    fn output_names(&self) -> impl Iterator<Item = &String> {
+        self.route
+            .keys()
+            .chain(self.split.iter().flat_map(|split| split.weights.keys()))
+    }
+}
+
 #[async_trait::async_trait]
 impl TransformConfig for RouteConfig {
     async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
@@ -94,22 +248,50 @@ impl TransformConfig for RouteConfig {
     }
 
     fn validate(&self, _: &schema::Definition) -> Result<(), Vec<String>> {
-        if self.route.contains_key(UNMATCHED_ROUTE) {
-            Err(vec![format!(
-                "cannot have a named output with reserved name: `{UNMATCHED_ROUTE}`"
-            )])
-        } else {
+        let mut errors = Vec::new();
+
+        let mut output_names = HashSet::new();
+        for output_name in self.output_names() {
+            if output_name == UNMATCHED_ROUTE {
+                errors.push(format!(
+                    "cannot have a named output with reserved name: `{UNMATCHED_ROUTE}`"
+                ));
+            } else if !output_names.insert(output_name) {
+                errors.push(format!(
+                    "output `{output_name}` is both a route and a split output"
+                ));
+            }
+        }
+
+        if let Some(split) = &self.split {
+            if split.weights.values().all(|weight| *weight == 0) {
+                errors.push("split must have an output with a weight above zero".to_string());
+            }
+        }
+
+        if let Some(reroute) = &self.reroute_unmatched {
+            if !output_names.contains(reroute) {
+                errors.push(format!(
+                    "`reroute_unmatched` must be a route or a split output, found `{reroute}`"
+                ));
+            }
+        }
+
+        if errors.is_empty() {
             Ok(())
+        } else {
+            Err(errors)
         }
     }
 
     fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
         let mut result: Vec<Output> = self
-            .route
-            .keys()
+            .output_names()
             .map(|output_name| Output::default(DataType::all()).with_port(output_name))
             .collect();
-        result.push(Output::default(DataType::all()).with_port(UNMATCHED_ROUTE));
+        if self.reroute_unmatched.is_none() {
+            result.push(Output::default(DataType::all()).with_port(UNMATCHED_ROUTE));
+        }
         result
     }
 
@@ -305,6 +487,153 @@ mod test {
         }
     }
 
+    fn transform_all(
+        config: &str,
+        output_names: &[&str],
+        events: Vec<Event>,
+    ) -> TransformOutputsBuf {
+        let config = toml::from_str::<RouteConfig>(config).unwrap();
+        let mut transform = Route::new(&config, &Default::default()).unwrap();
+        let mut outputs = TransformOutputsBuf::new_with_capacity(
+            output_names
+                .iter()
+                .map(|output_name| {
+                    Output::default(DataType::all()).with_port(output_name.to_owned())
+                })
+                .collect(),
+            events.len(),
+        );
+
+        for event in events {
+            transform.transform(event, &mut outputs);
+        }
+        outputs
+    }
+
+    #[test]
+    fn split_by_weight() {
+        let events = (0..1000)
+            .map(|i| Event::try_from(serde_json::json!({ "user": i })).unwrap())
+            .collect();
+        let mut outputs = transform_all(
+            r#"
+            split.weights.stable = 80
+            split.weights.canary = 20
+            split.key_field = "user"
+        "#,
+            &["stable", "canary", UNMATCHED_ROUTE],
+            events,
+        );
+
+        let stable = outputs.drain_named("stable").count();
+        let canary = outputs.drain_named("canary").count();
+        assert_eq!(stable + canary, 1000);
+        assert!((700..900).contains(&stable), "stable: {}", stable);
+        assert_eq!(outputs.drain_named(UNMATCHED_ROUTE).count(), 0);
+    }
+
+    #[test]
+    fn split_consistently_by_key_field() {
+        let events = (0..100)
+            .map(|_| Event::try_from(serde_json::json!({ "user": "jane" })).unwrap())
+            .collect();
+        let mut outputs = transform_all(
+            r#"
+            split.weights.stable = 50
+            split.weights.canary = 50
+            split.key_field = "user"
+        "#,
+            &["stable", "canary", UNMATCHED_ROUTE],
+            events,
+        );
+
+        let stable = outputs.drain_named("stable").count();
+        let canary = outputs.drain_named("canary").count();
+        assert!(stable == 100 || canary == 100);
+    }
+
+    #[test]
+    fn split_in_turn_without_key_field() {
+        let events = (0..10)
+            .map(|_| Event::try_from(serde_json::json!({ "message": "hello" })).unwrap())
+            .collect();
+        let mut outputs = transform_all(
+            r#"
+            split.weights.stable = 7
+            split.weights.canary = 3
+        "#,
+            &["stable", "canary", UNMATCHED_ROUTE],
+            events,
+        );
+
+        assert_eq!(outputs.drain_named("stable").count(), 7);
+        assert_eq!(outputs.drain_named("canary").count(), 3);
+    }
+
+    #[test]
+    fn split_with_condition() {
+        let events = vec![
+            Event::try_from(serde_json::json!({ "message": "split" })).unwrap(),
+            Event::try_from(serde_json::json!({ "message": "other" })).unwrap(),
+        ];
+        let mut outputs = transform_all(
+            r#"
+            split.weights.canary = 100
+            split.condition = '.message == "split"'
+        "#,
+            &["canary", UNMATCHED_ROUTE],
+            events,
+        );
+
+        assert_eq!(outputs.drain_named("canary").count(), 1);
+        assert_eq!(outputs.drain_named(UNMATCHED_ROUTE).count(), 1);
+    }
+
+    #[test]
+    fn reroute_unmatched() {
+        let events = vec![
+            Event::try_from(serde_json::json!({ "level": "error" })).unwrap(),
+            Event::try_from(serde_json::json!({ "level": "info" })).unwrap(),
+        ];
+        let config = r#"
+            route.errors = '.level == "error"'
+            route.others = '.level == "debug"'
+            reroute_unmatched = "others"
+        "#;
+        let mut outputs = transform_all(config, &["errors", "others"], events);
+
+        assert_eq!(outputs.drain_named("errors").count(), 1);
+        assert_eq!(outputs.drain_named("others").count(), 1);
+
+        let config = toml::from_str::<RouteConfig>(config).unwrap();
+        let outputs = config.outputs(&schema::Definition::any());
+        assert!(outputs
+            .iter()
+            .all(|output| output.port.as_deref() != Some(UNMATCHED_ROUTE)));
+    }
+
+    #[test]
+    fn validate_outputs() {
+        let config = toml::from_str::<RouteConfig>(
+            r#"
+            route.canary = '.level == "error"'
+            split.weights.canary = 0
+            reroute_unmatched = "missing"
+        "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.validate(&schema::Definition::any()).unwrap_err(),
+            vec![
+                "output `canary` is both a route and a split output".to_string(),
+                "split must have an output with a weight above zero".to_string(),
+                "`reroute_unmatched` must be a route or a split output, found `missing`"
+                    .to_string(),
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn route_metrics_with_output_tag() {
         init_test();
@@ -0,0 +1,338 @@
+//! Handover of listening sockets from a running Vector to the one replacing it.
+//!
+//! Each Vector with a data directory listens on a control socket in it. A Vector started with
+//! `--upgrade` connects to that socket before building its topology, and receives the sockets the
+//! running Vector listens on. The running Vector then shuts down gracefully, and the connection is
+//! closed when it exits, after which its disk buffers can be opened. Connections and datagrams that
+//! arrive in between are queued by the kernel on the handed over sockets, and served as soon as the
+//! new topology starts.
+
+use std::{
+    collections::HashMap,
+    io::{self, IoSlice, IoSliceMut, Read, Write},
+    net::SocketAddr,
+    os::unix::{
+        fs::PermissionsExt,
+        io::{AsRawFd, BorrowedFd, FromRawFd, OwnedFd, RawFd},
+        net::UnixStream,
+    },
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use nix::sys::socket::{recvmsg, sendmsg, ControlMessage, ControlMessageOwned, MsgFlags, UnixAddr};
+use once_cell::sync::{Lazy, OnceCell};
+use tokio::net::UnixListener;
+
+use crate::{
+    config::{Protocol, Resource},
+    signal::{SignalTo, SignalTx},
+};
+
+/// The name of the control socket in the data directory.
+const SOCKET_NAME: &str = "upgrade.sock";
+
+/// The most file descriptors that can be passed in a single message.
+const MAX_SOCKETS: usize = 253;
+
+const HANDOVER: &[u8] = b"handover\n";
+const SHUTDOWN: &[u8] = b"shutdown\n";
+
+/// The sockets this process listens on, by the resource they are bound to.
+///
+/// Sources don't unregister their sockets when they stop, so each one is checked to still be bound
+/// to its resource before it is handed over.
+static LISTENING: Lazy<Mutex<HashMap<Resource, RawFd>>> = Lazy::new(Default::default);
+
+/// The sockets handed over by the previous Vector, until a source listens on them.
+static INHERITED: Lazy<Mutex<HashMap<String, OwnedFd>>> = Lazy::new(Default::default);
+
+/// The connection of the Vector this one handed over to, which is kept open until this one exits.
+static SUCCESSOR: OnceCell<UnixStream> = OnceCell::new();
+
+/// Returns the path of the control socket in the given data directory.
+pub fn socket_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(SOCKET_NAME)
+}
+
+/// Records a socket that this process listens on, so that it can be handed over on upgrade.
+pub(crate) fn register(resource: Resource, socket: &impl AsRawFd) {
+    LISTENING
+        .lock()
+        .expect("poisoned lock")
+        .insert(resource, socket.as_raw_fd());
+}
+
+/// Takes the socket bound to the given resource that was handed over by the previous Vector, if any.
+pub(crate) fn take_inherited(resource: &Resource) -> Option<OwnedFd> {
+    INHERITED
+        .lock()
+        .expect("poisoned lock")
+        .remove(&resource.to_string())
+}
+
+/// Closes the handed over sockets that no source listens on, as they are no longer configured.
+pub fn close_unused() {
+    for (resource, _) in INHERITED.lock().expect("poisoned lock").drain() {
+        warn!(message = "Closing inherited socket that is no longer configured.", %resource);
+    }
+}
+
+/// Takes over the listening sockets of the Vector running with the control socket at `path`, and
+/// waits for it to exit.
+///
+/// If no Vector is running, this returns without inheriting anything.
+pub async fn take_over(path: PathBuf) -> crate::Result<()> {
+    tokio::task::spawn_blocking(move || {
+        let mut stream = match UnixStream::connect(&path) {
+            Ok(stream) => stream,
+            Err(error)
+                if matches!(
+                    error.kind(),
+                    io::ErrorKind::NotFound | io::ErrorKind::ConnectionRefused
+                ) =>
+            {
+                warn!(
+                    message = "No running Vector to take over from, starting normally.",
+                    path = ?path,
+                );
+                return Ok(());
+            }
+            Err(error) => return Err(error.into()),
+        };
+
+        stream.write_all(HANDOVER)?;
+        let sockets = receive_sockets(&mut stream)?;
+        info!(
+            message = "Received listening sockets from the running Vector.",
+            count = sockets.len()
+        );
+        INHERITED.lock().expect("poisoned lock").extend(sockets);
+
+        stream.write_all(SHUTDOWN)?;
+        info!("Waiting for the running Vector to shut down.");
+        // The connection is only closed when the running Vector exits, which releases its disk
+        // buffers. Anything else it sends is ignored.
+        io::copy(&mut stream, &mut io::sink())?;
+        Ok::<_, crate::Error>(())
+    })
+    .await?
+}
+
+/// Listens on the control socket at `path`, and hands the listening sockets of this process over
+/// to the first Vector started with `--upgrade` that connects, before shutting down.
+pub fn serve(path: PathBuf, signal_tx: SignalTx) -> io::Result<()> {
+    // Left behind by the previous Vector, which has exited by now.
+    if let Err(error) = std::fs::remove_file(&path) {
+        if error.kind() != io::ErrorKind::NotFound {
+            return Err(error);
+        }
+    }
+    let listener = UnixListener::bind(&path)?;
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))?;
+
+    tokio::spawn(async move {
+        loop {
+            let stream = match listener.accept().await {
+                Ok((stream, _)) => stream,
+                Err(error) => {
+                    error!(message = "Failed accepting upgrade connection.", %error);
+                    continue;
+                }
+            };
+
+            let handed_over = tokio::task::spawn_blocking(move || {
+                let mut stream = stream.into_std()?;
+                stream.set_nonblocking(false)?;
+                hand_over(&mut stream)?;
+                Ok::<_, io::Error>(stream)
+            })
+            .await
+            .expect("handover panicked");
+
+            match handed_over {
+                Ok(stream) => {
+                    info!("Handed over listening sockets to the upgraded Vector, shutting down.");
+                    let _ = SUCCESSOR.set(stream);
+                    let _ = signal_tx.send(SignalTo::Shutdown);
+                    break;
+                }
+                Err(error) => {
+                    warn!(message = "Upgrade aborted, continuing to run.", %error);
+                }
+            }
+        }
+    });
+
+    info!(message = "Listening for upgrades.", path = ?path);
+    Ok(())
+}
+
+/// Sends the listening sockets over the connection of the upgraded Vector, and waits for it to ask
+/// for the shutdown of this one.
+fn hand_over(stream: &mut UnixStream) -> io::Result<()> {
+    expect_message(stream, HANDOVER)?;
+
+    let mut sockets = listening_sockets();
+    if sockets.len() > MAX_SOCKETS {
+        warn!(
+            message = "Too many listening sockets, some of them won't be handed over.",
+            count = sockets.len(),
+            max = MAX_SOCKETS,
+        );
+        sockets.truncate(MAX_SOCKETS);
+    }
+    let (resources, fds): (Vec<String>, Vec<RawFd>) = sockets.into_iter().unzip();
+
+    let mut payload = serde_json::to_vec(&resources)?;
+    payload.push(b'\n');
+    let rights = [ControlMessage::ScmRights(&fds)];
+    let cmsgs: &[ControlMessage] = if fds.is_empty() { &[] } else { &rights };
+    let sent = sendmsg::<UnixAddr>(
+        stream.as_raw_fd(),
+        &[IoSlice::new(&payload)],
+        cmsgs,
+        MsgFlags::empty(),
+        None,
+    )?;
+    stream.write_all(&payload[sent..])?;
+
+    expect_message(stream, SHUTDOWN)
+}
+
+/// Returns the registered sockets that are still bound to their resource.
+fn listening_sockets() -> Vec<(String, RawFd)> {
+    let mut listening = LISTENING.lock().expect("poisoned lock");
+    listening.retain(|resource, fd| is_bound(*fd, resource));
+    listening
+        .iter()
+        .map(|(resource, fd)| (resource.to_string(), *fd))
+        .collect()
+}
+
+fn is_bound(fd: RawFd, resource: &Resource) -> bool {
+    let (address, protocol) = match resource {
+        Resource::Port(address, protocol) => (address, protocol),
+        _ => return false,
+    };
+    let expected = match protocol {
+        Protocol::Tcp => socket2::Type::STREAM,
+        Protocol::Udp => socket2::Type::DGRAM,
+    };
+
+    // SAFETY: The file descriptor is only used to query the socket, which fails if it was closed.
+    let borrowed = unsafe { BorrowedFd::borrow_raw(fd) };
+    let socket = socket2::SockRef::from(&borrowed);
+    let local = socket.local_addr().ok().and_then(|local| local.as_socket());
+    matches!(socket.r#type(), Ok(kind) if kind == expected)
+        && matches!(local, Some(local) if same_address(local, *address))
+}
+
+/// Whether a socket bound to `local` was bound with `configured`, which can have a port of 0.
+fn same_address(local: SocketAddr, configured: SocketAddr) -> bool {
+    local.ip() == configured.ip() && (configured.port() == 0 || local.port() == configured.port())
+}
+
+/// Receives the sockets sent by `hand_over`, by the resource they are bound to.
+fn receive_sockets(stream: &mut UnixStream) -> crate::Result<Vec<(String, OwnedFd)>> {
+    let mut payload = vec![0; 64 * 1024];
+    let mut cmsg_buffer = nix::cmsg_space!([RawFd; MAX_SOCKETS]);
+    let (received, fds) = {
+        let mut iov = [IoSliceMut::new(&mut payload)];
+        let message = recvmsg::<UnixAddr>(
+            stream.as_raw_fd(),
+            &mut iov,
+            Some(&mut cmsg_buffer),
+            MsgFlags::empty(),
+        )?;
+        let mut fds = Vec::new();
+        for cmsg in message.cmsgs() {
+            if let ControlMessageOwned::ScmRights(rights) = cmsg {
+                // SAFETY: The file descriptors were just received, and are owned by nothing else.
+                fds.extend(
+                    rights
+                        .into_iter()
+                        .map(|fd| unsafe { OwnedFd::from_raw_fd(fd) }),
+                );
+            }
+        }
+        (message.bytes, fds)
+    };
+    payload.truncate(received);
+
+    // The rest of the payload, if it didn't fit in the message with the sockets.
+    while !payload.ends_with(b"\n") {
+        let mut byte = [0];
+        if stream.read(&mut byte)? == 0 {
+            return Err("Connection closed during handover".into());
+        }
+        payload.push(byte[0]);
+    }
+
+    let resources: Vec<String> = serde_json::from_slice(&payload)?;
+    if resources.len() != fds.len() {
+        return Err(format!(
+            "Received {} sockets for {} resources",
+            fds.len(),
+            resources.len()
+        )
+        .into());
+    }
+    Ok(resources.into_iter().zip(fds).collect())
+}
+
+fn expect_message(stream: &mut UnixStream, expected: &[u8]) -> io::Result<()> {
+    let mut message = vec![0; expected.len()];
+    stream.read_exact(&mut message)?;
+    if message == expected {
+        Ok(())
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "unexpected upgrade message",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{net::TcpListener, thread};
+
+    use super::*;
+
+    #[test]
+    fn hands_over_listening_sockets() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        register(Resource::tcp(address), &listener);
+        // Closed before the handover, so not handed over.
+        let closed = TcpListener::bind("127.0.0.1:0").unwrap();
+        let closed_resource = Resource::tcp(closed.local_addr().unwrap());
+        register(closed_resource.clone(), &closed);
+        drop(closed);
+
+        let (mut old, mut new) = UnixStream::pair().unwrap();
+        let old = thread::spawn(move || hand_over(&mut old));
+
+        new.write_all(HANDOVER).unwrap();
+        let sockets = receive_sockets(&mut new).unwrap();
+        new.write_all(SHUTDOWN).unwrap();
+        old.join().unwrap().unwrap();
+
+        // Other tests may have registered sockets of their own.
+        let mut sockets: HashMap<_, _> = sockets.into_iter().collect();
+        assert!(!sockets.contains_key(&closed_resource.to_string()));
+        let fd = sockets.remove(&Resource::tcp(address).to_string()).unwrap();
+        let inherited = TcpListener::from(fd);
+        assert_eq!(inherited.local_addr().unwrap(), address);
+    }
+
+    #[test]
+    fn compares_configured_addresses() {
+        let local = "127.0.0.1:9000".parse().unwrap();
+        assert!(same_address(local, "127.0.0.1:9000".parse().unwrap()));
+        assert!(same_address(local, "127.0.0.1:0".parse().unwrap()));
+        assert!(!same_address(local, "0.0.0.0:9000".parse().unwrap()));
+        assert!(!same_address(local, "127.0.0.1:9001".parse().unwrap()));
+    }
+}
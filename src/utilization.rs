@@ -1,4 +1,5 @@
 use std::{
+    collections::HashMap,
     pin::Pin,
     task::{ready, Context, Poll},
     time::{Duration, Instant},
@@ -9,6 +10,9 @@ use metrics::gauge;
 use pin_project::pin_project;
 use tokio::time::interval;
 use tokio_stream::wrappers::IntervalStream;
+use vector_core::{
+    config::UtilizationOptions, internal_event::DEFAULT_OUTPUT, transform::TransformOutputs,
+};
 
 use crate::stats;
 
@@ -72,10 +76,10 @@ where
 /// and the rest of the time it is doing useful work. This is more true for
 /// sinks than transforms, which can be blocked by downstream components, but
 /// with knowledge of the config the data is still useful.
-pub(crate) fn wrap<S>(inner: S) -> Utilization<S> {
+pub(crate) fn wrap<S>(inner: S, options: &UtilizationOptions) -> Utilization<S> {
     Utilization {
-        timer: Timer::new(),
-        intervals: IntervalStream::new(interval(Duration::from_secs(5))),
+        timer: Timer::new(options),
+        intervals: IntervalStream::new(interval(options.report_interval())),
         inner,
     }
 }
@@ -97,13 +101,13 @@ pub(super) struct Timer {
 /// to be of uniform length and used to aggregate span data into time-weighted
 /// averages.
 impl Timer {
-    pub(crate) fn new() -> Self {
+    pub(crate) fn new(options: &UtilizationOptions) -> Self {
         Self {
             overall_start: Instant::now(),
             span_start: Instant::now(),
             waiting: false,
             total_wait: Duration::new(0, 0),
-            ewma: stats::Ewma::new(0.9),
+            ewma: stats::Ewma::new(options.ewma_alpha()),
         }
     }
 
@@ -157,3 +161,52 @@ impl Timer {
         self.span_start
     }
 }
+
+/// Tracks the utilization of each output of a transform with several, as the fraction of time it
+/// spends sending events to the output, and reports it as the `output_utilization` gauge.
+///
+/// Sending only takes time when the components downstream of the output apply backpressure, so this
+/// tells which of the outputs hold the transform back.
+pub(super) struct OutputsTimer {
+    period_start: Instant,
+    alpha: f64,
+    ewmas: HashMap<Option<String>, stats::Ewma>,
+}
+
+impl OutputsTimer {
+    pub(crate) fn new(options: &UtilizationOptions) -> Self {
+        Self {
+            period_start: Instant::now(),
+            alpha: options.ewma_alpha(),
+            ewmas: HashMap::new(),
+        }
+    }
+
+    /// Meant to be called on a regular interval, this method calculates the share of the time
+    /// spent sending to each output since the last time it was called, and reports the resulting
+    /// utilization averages.
+    pub(crate) fn report(&mut self, outputs: &mut TransformOutputs) {
+        let now = Instant::now();
+        let period = now.duration_since(self.period_start).as_secs_f64();
+        self.period_start = now;
+
+        for (output, sending) in outputs.take_send_durations() {
+            let utilization = if period > 0.0 {
+                (sending.as_secs_f64() / period).min(1.0)
+            } else {
+                0.0
+            };
+
+            let alpha = self.alpha;
+            let ewma = self
+                .ewmas
+                .entry(output.map(str::to_owned))
+                .or_insert_with(|| stats::Ewma::new(alpha));
+            ewma.update(utilization);
+            let avg = ewma.average().unwrap_or(f64::NAN);
+            let output = output.unwrap_or(DEFAULT_OUTPUT).to_owned();
+            debug!(output_utilization = %avg, %output);
+            gauge!("output_utilization", avg, "output" => output);
+        }
+    }
+}
@@ -388,7 +388,7 @@ _values: {
 
 	#ConditionExample: {
 		title:    !=""
-		name:     "vrl" | "datadog_search"
+		name:     "vrl" | "datadog_search" | "cel"
 		example:  !=""
 		vrl_only: bool | *false
 	}
@@ -407,6 +407,15 @@ _values: {
 			description: "A [Datadog Search](\(urls.datadog_search_syntax)) query string."
 			example:     #"*stack"#
 		},
+		{
+			name: "cel"
+			description: """
+				A [Common Expression Language](\(urls.cel)) (CEL) Boolean expression. The top-level fields of logs
+				and traces are available as variables, and metrics expose their `name`, `namespace`, `kind`, and
+				`tags`.
+				"""
+			example: #"level == "error" && status >= 500"#
+		},
 	]
 
 	options: {
@@ -448,6 +457,11 @@ _values: {
 			name:    "datadog_search"
 			example: "*stack"
 		},
+		{
+			title:   "CEL"
+			name:    "cel"
+			example: #"level == "error" && status >= 500"#
+		},
 		{
 			title:    "VRL shorthand"
 			name:     "vrl"
@@ -47,6 +47,97 @@ api: {
 				of the address set using the `bind` parameter.
 				"""
 		}
+		control_token: {
+			common:   false
+			required: false
+			type: string: {
+				default: null
+				examples: ["${VECTOR_API_CONTROL_TOKEN}"]
+			}
+			description: """
+				A token that enables the mutations of the API, such as the
+				`pauseComponent` and `resumeComponent` mutations, which pause and
+				resume the ingestion of sources and the delivery of sinks at runtime.
+				Mutations must be sent with this token as a bearer token, in the
+				`Authorization` header. Without a token, mutations are disabled.
+				"""
+		}
+		auth_token: {
+			common:   false
+			required: false
+			type: string: {
+				default: null
+				examples: ["${VECTOR_API_AUTH_TOKEN}"]
+			}
+			description: """
+				A token that is required to access the `/graphql` endpoint. Requests
+				must be sent with this token as a bearer token, in the `Authorization`
+				header. Subscriptions can also send it as the `token` field of the
+				payload of their `connection_init` message. The `/health` and
+				`/playground` endpoints remain accessible without a token.
+				`vector top` and `vector tap` send the token set with their `--token`
+				option, or the `VECTOR_API_TOKEN` environment variable.
+				"""
+		}
+		tls: {
+			common:      false
+			required:    false
+			description: """
+				Configures the TLS options for the API endpoint. Clients are
+				authenticated with mutual TLS by enabling `verify_certificate`,
+				along with a `ca_file` to verify their certificates against.
+				"""
+			type: object: options: {
+				enabled: {
+					common:      false
+					required:    false
+					description: "Require TLS for the API endpoint. If enabled, `crt_file` and `key_file` must also be set."
+					type: bool: default: false
+				}
+				crt_file: {
+					common:      false
+					required:    false
+					description: "Absolute path to a certificate file used to identify the API server, in DER or PEM (X.509) format."
+					type: string: {
+						default: null
+						examples: ["/path/to/host_certificate.crt"]
+					}
+				}
+				key_file: {
+					common:      false
+					required:    false
+					description: "Absolute path to the private key of the certificate, in DER or PEM (PKCS#8) format."
+					type: string: {
+						default: null
+						examples: ["/path/to/host_certificate.key"]
+					}
+				}
+				key_pass: {
+					common:      false
+					required:    false
+					description: "Passphrase used to unlock the encrypted key file. This has no effect unless `key_file` is set."
+					type: string: {
+						default: null
+						examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
+					}
+				}
+				ca_file: {
+					common:      false
+					required:    false
+					description: "Absolute path to an additional CA certificate file, in DER or PEM (X.509) format, to verify client certificates against."
+					type: string: {
+						default: null
+						examples: ["/path/to/certificate_authority.crt"]
+					}
+				}
+				verify_certificate: {
+					common:      false
+					required:    false
+					description: "Require clients to present a valid certificate, issued by a trusted issuer, enabling mutual TLS."
+					type: bool: default: false
+				}
+			}
+		}
 	}
 
 	endpoints: {
@@ -85,6 +85,10 @@ cli: {
 	name: "vector"
 
 	flags: _default_flags & {
+		"once": {
+			description: env_vars.VECTOR_ONCE.description
+			env_var:     "VECTOR_ONCE"
+		}
 		"quiet": {
 			_short: "q"
 			description: """
@@ -96,6 +100,13 @@ cli: {
 			description: env_vars.VECTOR_REQUIRE_HEALTHY.description
 			env_var:     "VECTOR_REQUIRE_HEALTHY"
 		}
+		"upgrade": {
+			description: """
+				Take over from the Vector running with the same data directory, without downtime. The
+				listening sockets of the running Vector are handed over to this one, which starts once
+				the running Vector has shut down and released its disk buffers. Only supported on Unix.
+				"""
+		}
 		"verbose": {
 			_short:      "v"
 			description: "Enable more detailed logging. Repeat to reduce further. Overrides `--verbose`."
@@ -169,11 +180,108 @@ cli: {
 			type:        "integer"
 			env_var:     "VECTOR_INTERNAL_LOG_RATE_LIMIT"
 		}
+		"internal-tracing-otlp-endpoint": {
+			description: env_vars.VECTOR_INTERNAL_TRACING_OTLP_ENDPOINT.description
+			type:        "string"
+			example:     "http://localhost:4317"
+			env_var:     "VECTOR_INTERNAL_TRACING_OTLP_ENDPOINT"
+		}
 	}
 
 	options: _core_options
 
 	commands: {
+		"benchmark": {
+			description: """
+				Run the configuration with synthetic events generated in place of each of its sources,
+				then report the events received and sent per second by each component, its
+				utilization, the latency of the events until they are delivered or dropped, and the CPU
+				and peak memory used by Vector. Sources with named outputs can't be replaced, so the
+				components reading them fail to build.
+				"""
+
+			example: "vector benchmark --shape syslog --duration-secs 30 --config /etc/vector/vector.toml"
+
+			options: _core_config_options & {
+				"shape": {
+					description: "The shape of the events generated in place of each source."
+					type:        "enum"
+					default:     "json"
+					enum: {
+						json:    "Logs whose message is a JSON object, as written by a web server."
+						syslog:  "Logs whose message is a RFC 5424 Syslog line."
+						metrics: "Counters and gauges of a web server."
+					}
+				}
+				"duration-secs": {
+					description: "How long to generate events for, in seconds."
+					type:        "integer"
+					default:     60
+				}
+				"rate": {
+					description: "The number of events generated per second in place of each source. By default, events are generated as fast as the topology accepts them."
+					type:        "integer"
+				}
+			}
+		}
+		"buffer export": {
+			description: """
+				Copy the disk buffer of a sink to a snapshot directory, along with a `snapshot.json`
+				manifest, so that the events stranded in the buffer of a decommissioned sink can be
+				replayed later. The sink doesn't need to be part of the configuration anymore, but Vector
+				must not be running with its buffer, which is locked while it is copied.
+				"""
+
+			example: "vector buffer export old_sink --output /var/backups/old_sink --config /etc/vector/vector.toml"
+
+			options: _core_config_options & {
+				"output": {
+					_short:      "o"
+					description: "The directory the snapshot is written to. It must not exist, or be empty."
+					type:        "string"
+					required:    true
+				}
+				"data-dir": {
+					description: "The data directory of the buffer. By default, the `data_dir` of the configuration is used."
+					type:        "string"
+				}
+			}
+
+			args: {
+				sink: {
+					description: "The ID of the sink whose disk buffer is exported"
+					type:        "string"
+					required:    true
+				}
+			}
+		}
+		"buffer replay": {
+			description: """
+				Send the events of a snapshot in place of a source of the configuration, then exit
+				once they have all been delivered. The other sources are replaced by sources without
+				events. The events of the snapshot were already processed before they reached the
+				buffer, so the source is best connected to the new sink directly. The events are
+				removed from the snapshot as they are delivered, so an interrupted replay resumes
+				where it left off.
+				"""
+
+			example: "vector buffer replay /var/backups/old_sink --source replay --config /etc/vector/replay.toml"
+
+			options: _core_config_options & {
+				"source": {
+					description: "The ID of the source whose events are replaced by those of the snapshot. It may be omitted when the configuration has a single source."
+					type:        "string"
+				}
+			}
+
+			args: {
+				snapshot: {
+					description: "The directory of the snapshot to replay"
+					type:        "string"
+					required:    true
+				}
+			}
+		}
 		"graph": {
 			description: """
 				Generate a visual representation of topologies. The output is in the [DOT format](\(urls.dot_format)),
@@ -186,6 +294,67 @@ cli: {
 
 			options: _core_options
 		}
+		"clean-data": {
+			description: """
+				Remove the data that components which are no longer part of the configuration left
+				in the data directory, such as their disk buffers and checkpoints.
+				"""
+
+			example: "vector clean-data --dry-run --config /etc/vector/vector.toml"
+
+			flags: _default_flags & {
+				"dry-run": {
+					description: "Only print the paths that would be removed, without removing them."
+				}
+			}
+
+			options: _core_options
+		}
+		"convert-config": {
+			description: """
+				Convert a config file from one format to another. If the input path is a directory,
+				every `toml`, `yaml`, and `json` file found in it is converted and written to the
+				output directory using the same layout. When converting TOML to TOML, the original
+				file is kept as-is so comments are preserved.
+				"""
+
+			example: "vector convert-config --output-format yaml /etc/vector/vector.toml /etc/vector/vector.yaml"
+
+			flags: _default_flags & {
+				"merge": {
+					description: """
+						Merge every input file, including component files found in `sources`, `transforms`,
+						`sinks`, and `tests` sub-directories, into a single output file. Comments are not
+						preserved when merging.
+						"""
+				}
+			}
+
+			options: {
+				"output-format": {
+					description: "The target format to which the existing config files will be converted"
+					default:     "yaml"
+					enum: {
+						toml: "Output the config as TOML"
+						yaml: "Output the config as YAML"
+						json: "Output the config as JSON"
+					}
+				}
+			}
+
+			args: {
+				input_path: {
+					description: "The input file or directory"
+					type:        "string"
+					required:    true
+				}
+				output_path: {
+					description: "The output file or directory to be created. Must not already exist"
+					type:        "string"
+					required:    true
+				}
+			}
+		}
 		"generate": {
 			description: "Generate a Vector configuration containing a list of components"
 
@@ -289,9 +458,9 @@ cli: {
 					_short:      "n"
 					description: "Whether to reconnect if the underlying Vector API connection drops. By default, tap will attempt to reconnect if the connection drops."
 				}
-			}
+			} & _api_connect_flags
 
-			options: {
+			options: _api_connect_options & {
 				"interval": {
 					_short:      "i"
 					description: "Interval to sample events at, in milliseconds"
@@ -363,9 +532,9 @@ cli: {
 					_short:      "n"
 					description: "Whether to reconnect if the underlying Vector API connection drops. By default, top will attempt to reconnect if the connection drops."
 				}
-			}
+			} & _api_connect_flags
 
-			options: {
+			options: _api_connect_options & {
 				"refresh-interval": {
 					_short:      "i"
 					description: "How often the screen refreshes (in milliseconds)"
@@ -377,6 +546,27 @@ cli: {
 					description: "The URL for the GraphQL endpoint of the running Vector instance"
 					type:        "string"
 				}
+				"components": {
+					description: """
+						Only show the components with an ID matching this glob pattern, e.g. `http_*`.
+						The filter can also be changed from the dashboard by pressing `/`.
+						"""
+					type: "string"
+				}
+				"sort": {
+					description: """
+						Order in which to list the components. The order can also be changed from the
+						dashboard by pressing `s`. Pressing `Enter` on a selected component shows its
+						errors, by error type and stage.
+						"""
+					type:    "enum"
+					default: "id"
+					enum: {
+						id:         "Sort by component ID"
+						throughput: "Sort by events throughput, highest first"
+						errors:     "Sort by error rate, highest first"
+					}
+				}
 			}
 		}
 
@@ -444,6 +634,18 @@ cli: {
 						The same result can be achieved by using `.` as the final expression.
 						"""
 				}
+				"profile": {
+					description: """
+						Run the program against all of the objects, then report the time spent
+						and the memory allocated by each of its top-level expressions, along with
+						their types and the resulting type of the objects. The report is printed
+						to stderr. Allocations are only counted by the standalone `vrl` binary.
+
+						In the REPL, the `load <path>`, `replay <program>` and `profile <program>`
+						commands respectively load objects from a file, run a program against all
+						of them, and profile a program against them.
+						"""
+				}
 			}
 
 			options: {
@@ -579,6 +781,15 @@ cli: {
 				}
 			}
 		}
+		VECTOR_ONCE: {
+			description: """
+				Run the configuration once, then exit. The sources end once they have read what is available,
+				rather than waiting for more, and Vector exits once their events have been processed by the
+				sinks. All the sources must be able to run once, such as the `file`, `exec`, `http_scrape` and
+				`stdin` sources, and the exit status is non-zero if a component failed.
+				"""
+			type: bool: default: false
+		}
 		VECTOR_REQUIRE_HEALTHY: {
 			description: "Exit on startup if any sinks fail healthchecks."
 			type: bool: default: false
@@ -603,6 +814,22 @@ cli: {
 				unit:    null
 			}
 		}
+		VECTOR_API_TOKEN: {
+			description: "The bearer token that `vector top` and `vector tap` authenticate with, if the API sets `api.auth_token`."
+			type: string: default: null
+		}
+		VECTOR_INTERNAL_TRACING_OTLP_ENDPOINT: {
+			description: """
+				Export the internal tracing spans of Vector to this OpenTelemetry collector, over OTLP/gRPC.
+				The spans cover the build of the components, the requests of the sinks and the backoffs
+				between their retries, so that the latency of the requests of a sink can be broken down
+				by attempt. The spans are attributed to their component with the `component_id`,
+				`component_kind` and `component_type` attributes, and the warnings and errors logged
+				within a span, such as the reasons of the retries, are its events. The spans are
+				dropped, rather than slowing Vector down, if the collector can't keep up.
+				"""
+			type: string: default: null
+		}
 	}
 
 	// Helpers
@@ -610,4 +837,30 @@ cli: {
 		type:    "list"
 		default: "/etc/vector/vector.toml"
 	}
+
+	_api_connect_flags: {
+		"tls-insecure": {
+			description: "Disable the verification of the API server certificate. Do NOT use this unless you understand the risks."
+		}
+	}
+
+	_api_connect_options: {
+		"token": {
+			description: env_vars.VECTOR_API_TOKEN.description
+			type:        "string"
+			env_var:     "VECTOR_API_TOKEN"
+		}
+		"tls-ca-file": {
+			description: "Absolute path to an additional CA certificate file, in PEM format, to verify the API server certificate with"
+			type:        "string"
+		}
+		"tls-crt-file": {
+			description: "Absolute path to a client certificate file, in PEM format, to identify with if the API server verifies certificates. Requires `--tls-key-file`."
+			type:        "string"
+		}
+		"tls-key-file": {
+			description: "Absolute path to the private key of the client certificate, in PEM (PKCS#8) format"
+			type:        "string"
+		}
+	}
 }
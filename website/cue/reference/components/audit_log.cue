@@ -0,0 +1,162 @@
+package metadata
+
+components: _audit_log: {
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	how_it_works: {
+		cursors: {
+			title: "Cursors"
+			body: """
+				The source keeps a cursor of the position of the last records pulled, and advances
+				it once the events of each page of records have been sent. The cursors are
+				persisted in the data directory, so that the source resumes where it left off
+				after a restart. Records of a page that was being sent when Vector stopped are
+				pulled again.
+				"""
+		}
+		rate_limits: {
+			title: "Rate limits"
+			body: """
+				Requests rejected by the API because of its rate limit are retried after the delay
+				asked for in the `Retry-After` or `X-Rate-Limit-Reset` headers of the response.
+				When a response says that the rate limit is exhausted, the next request waits
+				until it is reset.
+				"""
+		}
+		normalized_fields: {
+			title: "Normalized fields"
+			body: """
+				The audit log sources output the same fields, whatever the API they pull from,
+				so that their records can be processed the same way. The original record is kept
+				in the `original` field.
+				"""
+		}
+	}
+
+	output: logs: record: {
+		description: "An audit log record."
+		fields: {
+			provider: {
+				description: "The API that the record was pulled from."
+				required:    true
+				type: string: {
+					examples: ["okta", "microsoft_365", "google_workspace"]
+				}
+			}
+			event: {
+				description: "The event that the record is about."
+				required:    true
+				type: object: options: {
+					id: {
+						description: "The unique ID of the record."
+						required:    false
+						common:      true
+						type: string: {
+							default: null
+							examples: ["8d3a4e2c-7d77-11ed-9e4d-0242ac120002"]
+						}
+					}
+					action: {
+						description: "The action recorded."
+						required:    false
+						common:      true
+						type: string: {
+							default: null
+							examples: ["user.session.start", "UserLoggedIn", "login_success"]
+						}
+					}
+					outcome: {
+						description: "The outcome of the action, in lowercase."
+						required:    false
+						common:      true
+						type: string: {
+							default: null
+							examples: ["success", "failure", "succeeded"]
+						}
+					}
+				}
+			}
+			actor: {
+				description: "The user or application that performed the action."
+				required:    false
+				common:      true
+				type: object: options: {
+					id: {
+						description: "The ID of the actor."
+						required:    false
+						common:      true
+						type: string: {
+							default: null
+							examples: ["00u1a2b3c4d5e6f7g8h9"]
+						}
+					}
+					name: {
+						description: "The name of the actor, usually an email address."
+						required:    false
+						common:      true
+						type: string: {
+							default: null
+							examples: ["jane@example.com"]
+						}
+					}
+				}
+			}
+			client: {
+				description: "The client that the action was performed from."
+				required:    false
+				common:      true
+				type: object: options: ip: {
+					description: "The IP address of the client."
+					required:    false
+					common:      true
+					type: string: {
+						default: null
+						examples: ["10.0.0.1"]
+					}
+				}
+			}
+			original: {
+				description: "The original record, as returned by the API."
+				required:    true
+				type: object: {}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["okta", "microsoft_365", "google_workspace"]
+				}
+			}
+			timestamp: {
+				description: "The time of the action. If the record has no time, the time at which the record was pulled is used."
+				required:    true
+				type: timestamp: {}
+			}
+		}
+	}
+
+	telemetry: metrics: {
+		audit_log_rate_limited_total:         components.sources.internal_metrics.output.metrics.audit_log_rate_limited_total
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+	}
+}
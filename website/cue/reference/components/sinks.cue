@@ -52,6 +52,15 @@ components: sinks: [Name=string]: {
 									unit:    "events"
 								}
 							}
+							max_total_bytes: {
+								common:      false
+								description: "The maximum size of all the batches being built by the sink together. The limits above apply to each batch, so to each partition of the sinks that partition their batches, such as by stream or key prefix. When the batches of all the partitions together exceed this size, the largest of them are flushed early. This is based on the uncompressed size of the batched events, before they are serialized / compressed."
+								required:    false
+								type: uint: {
+									default: null
+									unit:    "bytes"
+								}
+							}
 							timeout_secs: {
 								common:      true
 								description: "The maximum age of a batch before it is flushed."
@@ -174,7 +183,7 @@ components: sinks: [Name=string]: {
 									lz4: "[lz4](\(urls.lz4)) compression."
 								}
 								if algo == "zstd" {
-									zstd: "[zstd](\(urls.zstd)) compression. Compression level is `3` unless otherwise specified. Dictionaries are not supported."
+									zstd: "[zstd](\(urls.zstd)) compression. Compression level is `3` unless otherwise specified. Sinks that accept the `algorithm`, `level` and `dictionary` object form can be primed with a dictionary trained with `zstd --train`."
 								}
 							}
 						}
@@ -459,6 +468,89 @@ components: sinks: [Name=string]: {
 									unit:    "seconds"
 								}
 							}
+							retry_on: {
+								common:      false
+								description: "The failures that are retried. Each entry is either a HTTP status code, such as `\"429\"`, a class of HTTP status codes (`\"4xx\"` or `\"5xx\"`), `\"timeout\"`, or `\"connection\"`. HTTP statuses listed here are retried even if the sink would not retry them by default, while errors are only retried if the sink considers them retriable. By default, the sink decides which failures are retried."
+								required:    false
+								type: array: {
+									default: null
+									items: type: string: {
+										examples: ["429", "5xx", "timeout", "connection"]
+									}
+								}
+							}
+							retry_attempts_on: {
+								common:      false
+								description: "The maximum number of retries for failed requests, by failure. The keys have the same format as the entries of `retry_on`. Specific HTTP status codes take precedence over classes of HTTP status codes. Failures not listed here are retried up to `retry_attempts` times."
+								required:    false
+								type: object: {
+									examples: [
+										{
+											"429": 20
+											"5xx": 5
+										},
+									]
+									options: {}
+								}
+							}
+							retry_jitter_factor: {
+								common:      false
+								description: "The fraction of each backoff that is randomized. Each backoff is shortened by a random amount of up to this fraction, which spreads out the retries of requests that failed at the same time."
+								required:    false
+								type: float: default: 0.0
+							}
+							retry_budget_percent: {
+								common:      false
+								description: "The percentage of requests that can be retried, in addition to a minimum of ten retries per second. Once the budget is spent, failed requests are dropped instead of retried, which prevents retries from overwhelming a service that is already failing."
+								required:    false
+								type: float: {
+									default: null
+									examples: [20.0]
+								}
+							}
+							fault: {
+								common:      false
+								description: "Faults injected into the requests of the sink, for testing. Each request meets at most one fault, drawn with the ratios below, each between `0.0` and `1.0`. The failed requests are retried, or not, as their real counterparts, so the faults are a way to test the retries, the acknowledgements and the buffers of a topology in staging. They must not be set in production."
+								required:    false
+								type: object: {
+									examples: []
+									options: {
+										timeout_rate: {
+											common:      true
+											description: "The ratio of the requests that time out. These requests aren't sent, and hang until the `timeout_secs` of the request elapses."
+											required:    false
+											type: float: default: 0.0
+										}
+										server_error_rate: {
+											common:      true
+											description: "The ratio of the requests that fail with a server error response. These requests aren't sent."
+											required:    false
+											type: float: default: 0.0
+										}
+										server_error_status: {
+											common:      false
+											description: "The HTTP status code of the server error responses."
+											required:    false
+											type: uint: {
+												default: 500
+												unit:    null
+											}
+										}
+										connection_reset_rate: {
+											common:      true
+											description: "The ratio of the requests whose connection is reset before they are sent."
+											required:    false
+											type: float: default: 0.0
+										}
+										partial_failure_rate: {
+											common:      true
+											description: "The ratio of the requests that fail with a server error response once they are sent, as when only part of a batch was written. The events of these requests reach the endpoint, so they are duplicated when the requests are retried."
+											required:    false
+											type: float: default: 0.0
+										}
+									}
+								}
+							}
 							timeout_secs: {
 								common:      true
 								description: "The maximum time a request can take before being aborted. It is highly recommended that you do not lower this value below the service's internal timeout, as this could create orphaned requests, pile on retries, and result in duplicate data downstream."
@@ -569,6 +661,11 @@ components: sinks: [Name=string]: {
 								1. The batch age meets or exceeds the configured `timeout_secs`.
 								2. The batch size meets or exceeds the configured `max_bytes` or `max_events`.
 
+								The sinks that partition their batches, such as by stream or key prefix, apply these limits
+								to the batch of each partition, and additionally flush their largest batches early when all
+								of them together exceed the configured `max_total_bytes`. The reasons batches are flushed
+								for are counted by the `component_batches_flushed_total` metric.
+
 								*Buffers* are controlled via the [`buffer.*`](#buffer) options.
 								"""#
 						}
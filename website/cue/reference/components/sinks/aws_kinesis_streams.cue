@@ -73,6 +73,28 @@ components: sinks: aws_kinesis_streams: components._aws & {
 	}
 
 	configuration: {
+		aggregation: {
+			common:      false
+			description: "Configures the aggregation of events into [KPL aggregated records](\(urls.aws_kinesis_aggregation))."
+			required:    false
+			type: object: options: {
+				enabled: {
+					common:      true
+					description: "Whether or not to aggregate the events of a batch into KPL aggregated records."
+					required:    false
+					type: bool: default: false
+				}
+				max_bytes: {
+					common:      false
+					description: "The maximum size of an aggregated record. Events are added to an aggregated record until it would exceed this size."
+					required:    false
+					type: uint: {
+						default: 51200
+						unit:    "bytes"
+					}
+				}
+			}
+		}
 		partition_key_field: {
 			common:      true
 			description: "The log field used as the Kinesis record's partition key value."
@@ -82,6 +104,30 @@ components: sinks: aws_kinesis_streams: components._aws & {
 				examples: ["user_id"]
 			}
 		}
+		partition_key: {
+			common:      false
+			description: "The template of the Kinesis record's partition key value. Events whose partition key can't be rendered are dropped. Can't be set along with `partition_key_field`."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["{{ tenant }}-{{ host }}"]
+				syntax: "template"
+			}
+		}
+		partitioner: {
+			common:      false
+			description: "How the Kinesis records are assigned to the shards of the stream. By default, Kinesis routes each record by the MD5 hash of its partition key."
+			required:    false
+			type: string: {
+				default: null
+				enum: {
+					murmur2: "The explicit hash key of each record is the murmur2 hash of its partition key, as hashed by the Java producer of Kafka."
+					fnv:     "The explicit hash key of each record is the FNV-1a hash of its partition key."
+					random:  "Each record is sent with a random partition key, whatever the partition key of its event."
+					sticky:  "The records are sent with the same random partition key for a second, whatever the partition key of their event, before another is picked."
+				}
+			}
+		}
 		stream_name: {
 			description: "The [stream name](\(urls.aws_cloudwatch_logs_stream_name)) of the target Kinesis Logs stream."
 			required:    true
@@ -106,7 +152,8 @@ components: sinks: aws_kinesis_streams: components._aws & {
 				distributing records across your Kinesis partitions. Depending on your use case
 				this might not be sufficient since random distribution does not preserve order.
 				To override this, you can supply the `partition_key_field` option. This option
-				presents an alternate field on your event to use as the partition key value instead.
+				presents an alternate field on your event to use as the partition key value instead,
+				while the `partition_key` option renders it from a template.
 				This is useful if you have a field already on your event, and it also pairs
 				nicely with the [`remap` transform](\(urls.vector_remap_transform)), which enables you
 				to add partition-related metadata to events.
@@ -135,8 +182,38 @@ components: sinks: aws_kinesis_streams: components._aws & {
 						Vector will coerce the value into a string.
 						"""
 				},
+				{
+					title: "Partitioners"
+					body: """
+						Kinesis routes each record to the shard whose hash key range contains the
+						MD5 hash of its partition key. With the `murmur2` and `fnv` partitioners,
+						Vector instead sets the explicit hash key of each record from the murmur2,
+						or FNV-1a, hash of its partition key, spread over the whole range of hash
+						keys. The `random` partitioner sends each record with a random partition
+						key, and the `sticky` one keeps the same random partition key for a second,
+						which packs more records together when `aggregation.enabled` is set.
+						"""
+				},
 			]
 		}
+		aggregation: {
+			title: "Record aggregation"
+			body:  """
+				Kinesis limits the number of records each shard accepts per second, and bills
+				each record as at least 25 KB. When `aggregation.enabled` is set, Vector packs the
+				events of each batch into [KPL aggregated records](\(urls.aws_kinesis_aggregation)),
+				up to `aggregation.max_bytes` each, cutting the number of records sent. Consumers
+				built on the Kinesis Client Library deaggregate these records transparently, and
+				other consumers can use one of the Kinesis deaggregation libraries.
+
+				Each event keeps its own partition key inside of the aggregated record, but the
+				aggregated record is routed to a shard by the partition key of its first event. If
+				`partition_key_field` or `partition_key` is set, only events sharing the same
+				partition key are aggregated together, so that ordering by partition key is preserved. The batch
+				`max_events` option still limits the number of events in each batch, and can be
+				raised to aggregate more events into each request.
+				"""
+		}
 	}
 
 	permissions: iam: [
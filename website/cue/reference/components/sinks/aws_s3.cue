@@ -25,7 +25,7 @@ components: sinks: aws_s3: components._aws & {
 			compression: {
 				enabled: true
 				default: "gzip"
-				algorithms: ["none", "gzip"]
+				algorithms: ["none", "gzip", "zstd", "snappy"]
 				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
 			}
 			encoding: {
@@ -99,6 +99,21 @@ components: sinks: aws_s3: components._aws & {
 				examples: ["my-bucket"]
 			}
 		}
+		checksum_algorithm: {
+			category:    "Integrity"
+			common:      false
+			description: "The algorithm used to compute the checksum of the created objects, or of each part of a multipart upload. The checksum is sent along with the data, and S3 rejects the object if the checksum of the data it received doesn't match."
+			required:    false
+			type: string: {
+				default: null
+				enum: {
+					CRC32:  "CRC32 checksum."
+					CRC32C: "CRC32C checksum."
+					SHA1:   "SHA-1 checksum."
+					SHA256: "SHA-256 checksum."
+				}
+			}
+		}
 		content_encoding: {
 			category:    "Content Type"
 			common:      false
@@ -195,6 +210,71 @@ components: sinks: aws_s3: components._aws & {
 				syntax: "template"
 			}
 		}
+		event_time_partitioning: {
+			category:    "File Naming"
+			common:      false
+			description: "Renders the time specifiers of `key_prefix` strictly from the timestamp of each event, routing late events to a separate prefix."
+			required:    false
+			type: object: options: {
+				enabled: {
+					common:      true
+					description: "Whether or not to render the time specifiers of `key_prefix` strictly from the timestamp of each event. The timestamp is read from the field with the `timestamp` semantic meaning, or from the `timestamp` field of the global log schema, and events without a timestamp are handled as late events."
+					required:    false
+					type: bool: default: false
+				}
+				allowed_lateness_secs: {
+					common:      false
+					description: "The maximum lateness of an event. Events whose timestamp is further in the past than this when they are processed are late, and their object key is prefixed with `late_key_prefix`."
+					required:    false
+					type: uint: {
+						default: 3600
+						unit:    "seconds"
+					}
+				}
+				late_key_prefix: {
+					common:      false
+					description: "The prefix of the object keys of late events, prepended to the rendered `key_prefix`."
+					required:    false
+					type: string: {
+						default: "late/"
+						examples: ["late/"]
+						syntax: "literal"
+					}
+				}
+			}
+		}
+		multipart_upload: {
+			category:    "Upload"
+			common:      false
+			description: "Uploads objects larger than `part_size` in parts with a [multipart upload](\(urls.aws_s3_multipart_upload)), instead of with a single `PutObject` request."
+			required:    false
+			type: object: options: {
+				enabled: {
+					common:      true
+					description: "Whether or not to upload large objects with multipart uploads."
+					required:    false
+					type: bool: default: false
+				}
+				part_size: {
+					common:      false
+					description: "The size of the parts of a multipart upload. Objects no larger than this are uploaded with a single `PutObject` request. Must be between 5 MiB and 5 GiB. The part size is raised if an object would otherwise be split into more than 10,000 parts."
+					required:    false
+					type: uint: {
+						default: 8388608
+						unit:    "bytes"
+					}
+				}
+				concurrency: {
+					common:      false
+					description: "The maximum number of parts of an object uploaded concurrently."
+					required:    false
+					type: uint: {
+						default: 4
+						unit:    null
+					}
+				}
+			}
+		}
 		server_side_encryption: {
 			category:    "Encryption"
 			common:      false
@@ -322,6 +402,46 @@ components: sinks: aws_s3: components._aws & {
 				"""
 		}
 
+		event_time_partitioning: {
+			title: "Event-time partitioning"
+			body:  """
+				By default, the time specifiers of [`key_prefix`](#key_prefix) are rendered from the
+				`timestamp` field of each event if it has one, and from the time the event is
+				processed otherwise. With [`event_time_partitioning.enabled`](#event_time_partitioning.enabled)
+				set to `true`, they are rendered strictly from the timestamp of each event, so that
+				Hive-style partitions such as `date=%F/hour=%H/` only hold the events of their hour.
+
+				Events arriving later than
+				[`event_time_partitioning.allowed_lateness_secs`](#event_time_partitioning.allowed_lateness_secs),
+				and events without a timestamp, are written under
+				[`event_time_partitioning.late_key_prefix`](#event_time_partitioning.late_key_prefix)
+				instead, such as `late/date=2022-07-18/hour=18/`, so that the partitions are not
+				updated once they have been processed downstream.
+				"""
+		}
+
+		multipart_upload: {
+			title: "Multipart uploads"
+			body:  """
+				A single `PutObject` request can create objects of up to 5 GiB, and uploads each
+				object over a single connection. With
+				[`multipart_upload.enabled`](#multipart_upload.enabled) set to `true`, objects larger
+				than [`multipart_upload.part_size`](#multipart_upload.part_size) are uploaded with a
+				[multipart upload](\(urls.aws_s3_multipart_upload)) instead, with up to
+				[`multipart_upload.concurrency`](#multipart_upload.concurrency) parts uploaded at the
+				same time. This is useful for large batches, such as batches with a large
+				`batch.max_bytes`.
+
+				If any part fails to upload, the multipart upload is aborted and the whole object is
+				retried. Consider adding a lifecycle rule to the bucket that aborts incomplete
+				multipart uploads, in case Vector stops in the middle of an upload.
+
+				With [`checksum_algorithm`](#checksum_algorithm) set, the checksum of each object, or
+				of each part, is sent along with its data, so that S3 rejects data corrupted in
+				transit.
+				"""
+		}
+
 		object_tags_and_metadata: {
 			title: "Object Tags & metadata"
 			body:  """
@@ -374,6 +494,10 @@ components: sinks: aws_s3: components._aws & {
 				{
 					_action: "PutObject"
 				},
+				{
+					_action:       "AbortMultipartUpload"
+					required_when: "[`multipart_upload.enabled`](#multipart_upload.enabled) is set to `true`"
+				},
 			]
 		},
 	]
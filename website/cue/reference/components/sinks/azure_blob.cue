@@ -25,7 +25,7 @@ components: sinks: azure_blob: {
 			compression: {
 				enabled: true
 				default: "gzip"
-				algorithms: ["none", "gzip"]
+				algorithms: ["none", "gzip", "zstd", "snappy"]
 				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
 			}
 			encoding: {
@@ -85,6 +85,77 @@ components: sinks: azure_blob: {
 				examples: ["mylogstorage"]
 			}
 		}
+		auth: {
+			common:      false
+			description: "The strategy used to authenticate with the storage account, when `storage_account` is set."
+			required:    false
+			type: object: options: {
+				strategy: {
+					common:      true
+					description: "The authentication strategy."
+					required:    false
+					type: string: {
+						default: "default"
+						enum: {
+							default:           "Loads credentials from environment variables, from the managed identity of the host, or from the `az` CLI tool, in that order."
+							managed_identity:  "Authenticates as a user-assigned managed identity."
+							workload_identity: "Authenticates as an application with workload identity federation, such as with Azure AD Workload Identity on Kubernetes."
+						}
+					}
+				}
+				client_id: {
+					common:        false
+					description:   "The client ID of the user-assigned managed identity, or of the application. For workload identity, defaults to the `AZURE_CLIENT_ID` environment variable."
+					required:      false
+					relevant_when: "strategy = \"managed_identity\" or strategy = \"workload_identity\""
+					type: string: {
+						default: null
+						examples: ["00000000-0000-0000-0000-000000000000"]
+					}
+				}
+				tenant_id: {
+					common:        false
+					description:   "The ID of the tenant of the application. Defaults to the `AZURE_TENANT_ID` environment variable."
+					required:      false
+					relevant_when: "strategy = \"workload_identity\""
+					type: string: {
+						default: null
+						examples: ["00000000-0000-0000-0000-000000000000"]
+					}
+				}
+				token_file: {
+					common:        false
+					description:   "The path of the file holding the token exchanged for an access token. Defaults to the `AZURE_FEDERATED_TOKEN_FILE` environment variable."
+					required:      false
+					relevant_when: "strategy = \"workload_identity\""
+					type: string: {
+						default: null
+						examples: ["/var/run/secrets/azure/tokens/azure-identity-token"]
+					}
+				}
+			}
+		}
+		blob_type: {
+			common:      false
+			description: "The type of the created blobs."
+			required:    false
+			type: string: {
+				default: "block"
+				enum: {
+					block:  "Each batch of events is written to a new block blob. Batches larger than `block_size` are staged in blocks, which are then committed at once."
+					append: "Each batch of events is appended to an append blob, which is created if it doesn't exist, such that the blob can be tailed while it is written to."
+				}
+			}
+		}
+		block_size: {
+			common:      false
+			description: "The size of the blocks the blobs are written in. With the `block` blob type, batches larger than this are staged in blocks of this size. With the `append` blob type, batches are appended in blocks of this size, which must be no larger than 4 MiB."
+			required:    false
+			type: uint: {
+				default: 4194304
+				unit:    "bytes"
+			}
+		}
 		container_name: {
 			description: "The Azure Blob Storage Account container name."
 			required:    true
@@ -106,14 +177,14 @@ components: sinks: azure_blob: {
 		blob_append_uuid: {
 			category:    "File Naming"
 			common:      false
-			description: "Whether or not to append a UUID v4 token to the end of the file. This ensures there are no name collisions high volume use cases."
+			description: "Whether or not to append a UUID v4 token to the end of the file. This ensures there are no name collisions high volume use cases. Defaults to `false` with the `append` blob type."
 			required:    false
 			type: bool: default: true
 		}
 		blob_time_format: {
 			category:    "File Naming"
 			common:      false
-			description: "The format of the resulting object file name. [`strftime` specifiers](\(urls.strptime_specifiers)) are supported."
+			description: "The format of the resulting object file name. [`strftime` specifiers](\(urls.strptime_specifiers)) are supported. Defaults to `%H` with the `append` blob type."
 			required:    false
 			type: string: {
 				default: "%s"
@@ -129,6 +200,20 @@ components: sinks: azure_blob: {
 	}
 
 	how_it_works: {
+		append_blobs: {
+			title: "Append blobs"
+			body:  """
+				With [`blob_type`](#blob_type) set to `append`, each batch of events is appended to an
+				[append blob](\(urls.azure_append_blobs)) instead of being written to a new block blob, so
+				that downstream consumers can tail the blob while it is written to. The blob is created
+				if it doesn't exist, and a new blob is started each hour by default, as the blob name
+				only includes the hour by default.
+
+				When compression is enabled, each batch is compressed separately, so the blob is made of
+				concatenated gzip members, which most gzip readers read as a single stream.
+				"""
+		}
+
 		object_naming: {
 			title: "Object naming"
 			body:  """
@@ -0,0 +1,185 @@
+package metadata
+
+components: sinks: azure_logs_ingestion: {
+	title: "Azure Logs Ingestion"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["Azure"]
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    1_000_000
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.azure_logs_ingestion
+
+				interface: {
+					socket: {
+						api: {
+							title: "Azure Monitor Logs Ingestion API"
+							url:   urls.azure_logs_ingestion_api
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: [
+			"""
+				A [Data Collection Rule](\(urls.azure_data_collection_rules)) must route the stream to a table of a Log
+				Analytics workspace, such as a Microsoft Sentinel workspace, and the identity Vector authenticates as
+				must be granted the `Monitoring Metrics Publisher` role on it.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		endpoint: {
+			description: "The logs ingestion endpoint of the [Data Collection Endpoint](\(urls.azure_data_collection_endpoints)), or of the Data Collection Rule."
+			required:    true
+			type: string: {
+				examples: ["https://my-dce-a1b2.eastus-1.ingest.monitor.azure.com"]
+			}
+		}
+		dcr_immutable_id: {
+			description: "The immutable ID of the [Data Collection Rule](\(urls.azure_data_collection_rules))."
+			required:    true
+			type: string: {
+				examples: ["dcr-000a00a000a00000a000000aa000a0aa"]
+			}
+		}
+		stream_name: {
+			description: "The name of the stream of the Data Collection Rule the events are sent to."
+			required:    true
+			type: string: {
+				examples: ["Custom-MyTable_CL", "Microsoft-Syslog"]
+			}
+		}
+		auth: {
+			common:      false
+			description: "The strategy used to authenticate with Microsoft Entra ID."
+			required:    false
+			type: object: options: components.sinks.azure_blob.configuration.auth.type.object.options
+		}
+		columns: {
+			common: true
+			description: """
+				The columns of the stream, and the fields of the events they are taken from. Each value is converted to
+				the type of its column. Fields that are missing, or whose value can't be converted, are left out of the
+				record.
+
+				When no columns are set, all the fields of the events are sent as is. In both cases, the `TimeGenerated`
+				column is set to the timestamp of the event, unless it is explicitly configured.
+				"""
+			required: false
+			type: object: {
+				examples: [
+					{
+						Computer:      {field: "host", type: "string"}
+						SeverityLevel: {field: "severity", type: "int"}
+						Message:       {field: "message", type: "string"}
+						Properties:    {field: "properties", type: "dynamic"}
+					},
+				]
+				options: {
+					"*": {
+						description: "A column of the stream."
+						required:    true
+						type: object: options: {
+							field: {
+								description: "The field of the event the column is taken from. Defaults to the name of the column."
+								required:    false
+								type: string: {
+									default: null
+									examples: ["host", "request.path"]
+								}
+							}
+							type: {
+								description: "The type of the column."
+								required:    true
+								type: string: enum: {
+									string:   "A string. Values of other types are converted to their string representation."
+									int:      "A 32-bit integer."
+									long:     "A 64-bit integer."
+									real:     "A floating point number."
+									boolean:  "A boolean."
+									datetime: "A timestamp. Strings are parsed as RFC 3339 timestamps, and integers as Unix timestamps in seconds."
+									dynamic:  "Any value, sent as JSON."
+									guid:     "A GUID, as a string."
+								}
+							}
+						}
+					}
+				}
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		chunking: {
+			title: "Request size"
+			body: """
+				The Logs Ingestion API rejects requests larger than 1 MB, so `batch.max_bytes` can't be set above
+				1,000,000 bytes. Batches are flushed before the JSON array of records they are sent as would exceed it.
+				"""
+		}
+		schema: {
+			title: "Schema transformation"
+			body: """
+				The records sent must match the columns declared by the stream of the Data Collection Rule. The
+				`columns` option maps the fields of the events to these columns and converts their values, such that
+				events of any shape can be sent to an existing table, without a transformation in the Data Collection
+				Rule. Timestamps are sent with millisecond precision.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		events_out_total:                 components.sources.internal_metrics.output.metrics.events_out_total
+	}
+}
@@ -50,6 +50,102 @@ components: sinks: blackhole: {
 				unit: null
 			}
 		}
+		rate_bytes: {
+			common:      false
+			description: "The number of bytes, per second, that the sink is allowed to consume. The size of the events is their size in memory."
+			required:    false
+			type: uint: {
+				default: null
+				examples: [1048576]
+				unit: "bytes"
+			}
+		}
+		latency: {
+			common:      false
+			description: "The latency of the sink, as the distribution of the delays before each batch of events is consumed. The batches are consumed one at a time, so the latency also slows the sink down."
+			required:    false
+			type: object: {
+				examples: []
+				options: {
+					distribution: {
+						description: "The distribution of the latency."
+						required:    true
+						type: string: {
+							enum: {
+								constant:    "Each batch is delayed by the same latency."
+								uniform:     "The latency is uniformly distributed between a minimum and a maximum."
+								normal:      "The latency is normally distributed, and never less than zero."
+								exponential: "The latency is exponentially distributed, as the delays between events occurring at random."
+							}
+						}
+					}
+					latency_ms: {
+						description:   "The latency."
+						relevant_when: "distribution = \"constant\""
+						required:      true
+						type: uint: {
+							examples: [50]
+							unit: "milliseconds"
+						}
+					}
+					min_ms: {
+						description:   "The minimum latency."
+						relevant_when: "distribution = \"uniform\""
+						required:      true
+						type: uint: {
+							examples: [10]
+							unit: "milliseconds"
+						}
+					}
+					max_ms: {
+						description:   "The maximum latency."
+						relevant_when: "distribution = \"uniform\""
+						required:      true
+						type: uint: {
+							examples: [200]
+							unit: "milliseconds"
+						}
+					}
+					mean_ms: {
+						description:   "The mean of the latency."
+						relevant_when: "distribution = \"normal\" or distribution = \"exponential\""
+						required:      true
+						type: float: {
+							examples: [50.0]
+						}
+					}
+					stddev_ms: {
+						description:   "The standard deviation of the latency."
+						relevant_when: "distribution = \"normal\""
+						required:      true
+						type: float: {
+							examples: [10.0]
+						}
+					}
+				}
+			}
+		}
+		error_rate: {
+			common:      false
+			description: "The ratio of the batches of events that the sink fails to consume, between `0.0` and `1.0`. The events of these batches are acknowledged with the `error_status`, so that the sources that support end-to-end acknowledgements report the failure upstream."
+			required:    false
+			type: float: {
+				default: 0.0
+				examples: [0.01, 0.5]
+			}
+		}
+		error_status: {
+			common:      false
+			description: "The status the events of the batches that the sink fails to consume are acknowledged with."
+			required:    false
+			type: string: {
+				default: "errored"
+				enum: {
+					errored:  "The events encountered a retriable error."
+					rejected: "The events were rejected, permanently."
+				}
+			}
+		}
 	}
 
 	input: {
@@ -72,6 +72,42 @@ components: sinks: datadog_metrics: {
 				examples: ["myservice"]
 			}
 		}
+		distribution_aggregation: {
+			common: false
+			description: """
+				Where distributions are aggregated.
+				"""
+			required: false
+			warnings: []
+			type: string: {
+				default: "client"
+				enum: {
+					client: "Distributions are aggregated by Vector into sketches, which are sent to the Sketches API."
+					server: """
+						The samples of distributions are sent to the Distribution Points API, and aggregated by Datadog.
+						Aggregated histograms are sent as sketches regardless, as their samples are unknown.
+						"""
+				}
+			}
+		}
+		series_api_version: {
+			common: false
+			description: """
+				The version of the Series API that series metrics are sent to.
+				"""
+			required: false
+			warnings: []
+			type: string: {
+				default: "v1"
+				enum: {
+					v1: "Series metrics are sent as JSON to the v1 API."
+					v2: """
+						Series metrics are sent as Protocol Buffers to the v2 API. Unlike the v1 API, the v2 API preserves
+						the unit and the origin of the metrics received from the Datadog Agent.
+						"""
+				}
+			}
+		}
 	}
 
 	input: {
@@ -87,6 +123,17 @@ components: sinks: datadog_metrics: {
 		traces: false
 	}
 
+	how_it_works: {
+		metric_metadata: {
+			title: "Metric metadata"
+			body: """
+				The unit and the origin of the metrics received by the `datadog_agent` source are kept in the
+				event metadata, under `%datadog.unit` and `%datadog.origin`, where they can be set or changed
+				with VRL. They are only forwarded when `series_api_version` is `v2`.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
 		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
 		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
@@ -0,0 +1,174 @@
+package metadata
+
+components: sinks: doris: {
+	title: "Apache Doris"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    10_000_000
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.doris
+
+				interface: {
+					socket: {
+						api: {
+							title: "Stream Load"
+							url:   urls.doris_stream_load
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		auth: configuration._http_auth & {_args: {
+			password_example: "${DORIS_PASSWORD}"
+			username_example: "${DORIS_USERNAME}"
+		}}
+		column_separator: {
+			common:      false
+			description: "The separator between the columns of the rows, with the `csv` format. Neither the separator nor newlines may appear in the values of the fields."
+			required:    false
+			type: string: {
+				default: "\t"
+				examples: [",", "|"]
+			}
+		}
+		columns: {
+			common:      true
+			description: "The columns of the table the fields of the events are loaded into. With the `csv` format, these are also the fields of the events written, in order, to each row, and must be set. With the `json` format, the columns default to those of the table, and the fields of the events are matched to them by name."
+			required:    false
+			type: array: {
+				default: []
+				items: type: string: examples: ["timestamp", "host", "message"]
+			}
+		}
+		database: {
+			description: "The database that contains the tables that data will be loaded into."
+			required:    true
+			type: string: {
+				examples: ["logs"]
+			}
+		}
+		endpoint: {
+			description: "The endpoint of the HTTP server of a frontend (FE) of the cluster. The frontend redirects the loads to the backends, which must be reachable at the addresses they are known by in the cluster."
+			required:    true
+			type: string: {
+				examples: ["http://localhost:8030"]
+			}
+		}
+		format: {
+			common:      true
+			description: "The format of the data loaded."
+			required:    false
+			type: string: {
+				default: "json"
+				enum: {
+					json: "Each event is a JSON object, whose fields are loaded into the columns of the same name."
+					csv:  "Each event is a row of delimited values, of the fields named by `columns`. Missing and null fields are written as `\\N`, which is loaded as `NULL`, and timestamps as `YYYY-MM-DD HH:MM:SS.ffffff`, in UTC."
+				}
+			}
+		}
+		label_prefix: {
+			common:      false
+			description: "The prefix of the labels of the loads. Each load is labeled with the prefix followed by a unique identifier, which the cluster uses to reject the retries of a load that already succeeded."
+			required:    false
+			type: string: {
+				default: "vector"
+				examples: ["vector_app"]
+			}
+		}
+		table: {
+			description: "The table that data will be loaded into. The table must already exist. The events are batched by table, so that each request loads a single one."
+			required:    true
+			type: string: {
+				examples: ["app_logs", "{{ service }}_logs"]
+				syntax: "template"
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		labels: {
+			title: "Load labels"
+			body: """
+				Each request is given a label, made of `label_prefix` and a unique identifier, which is kept by its
+				retries. When the response to a load is lost, its retry is rejected by the cluster with the
+				`Label Already Exists` status, which the sink considers successful once the first load is finished, so
+				that the rows of a request are loaded at most once.
+				"""
+		}
+		redirects: {
+			title: "Frontend redirects"
+			body: """
+				The loads are sent to the frontend, which redirects them to one of the backends. The sink follows the
+				redirects itself, sending the load again to the backend with the same headers and credentials. The
+				backends must therefore be reachable by Vector at the addresses they are known by in the cluster.
+				"""
+		}
+		starrocks: {
+			title: "StarRocks"
+			body: """
+				[StarRocks](\(urls.starrocks)) implements the same Stream Load protocol, so this sink can load events into
+				StarRocks clusters as well, by setting `endpoint` to the HTTP server of one of their frontends.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_discarded_events_total: components.sources.internal_metrics.output.metrics.component_discarded_events_total
+		component_errors_total:           components.sources.internal_metrics.output.metrics.component_errors_total
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+	}
+}
@@ -0,0 +1,175 @@
+package metadata
+
+components: sinks: fluent: {
+	title: "Fluent"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    8_000_000
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			keepalive: enabled: true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      false
+			}
+			to: {
+				service: services.fluent
+
+				interface: {
+					socket: {
+						api: {
+							title: "Fluent"
+							url:   urls.fluent
+						}
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		address: {
+			description: "The address of the Fluentd, or Fluent Bit, server to forward the events to. The address _must_ include a port."
+			required:    true
+			type: string: {
+				examples: ["127.0.0.1:24224", "fluentd.example.com:24224"]
+			}
+		}
+		compression: {
+			common:      true
+			description: "The compression of the chunks forwarded."
+			required:    false
+			type: string: {
+				default: "none"
+				enum: {
+					none: "The chunks are not compressed."
+					gzip: "The entries of the chunks are compressed with gzip, as Fluentd does with `compress gzip`."
+				}
+			}
+		}
+		require_ack_response: {
+			common:      true
+			description: "Whether to wait for the server to acknowledge each chunk, before considering it forwarded. The chunks that aren't acknowledged before the request times out are forwarded again."
+			required:    false
+			type: bool: default: true
+		}
+		security: {
+			common:      false
+			description: "Authentication with the server. When set, the sink authenticates with the shared key, and optionally a username and password, with the handshake of the forward protocol on each connection."
+			required:    false
+			type: object: options: {
+				self_hostname: {
+					description: "The hostname the sink identifies itself with to the server. Defaults to the hostname of the machine."
+					required:    false
+					type: string: {
+						default: null
+						examples: ["vector.example.com"]
+					}
+				}
+				shared_key: {
+					description: "The key shared with the server."
+					required:    true
+					type: string: {
+						examples: ["${FLUENT_SHARED_KEY}"]
+					}
+				}
+				username: {
+					description: "The username to authenticate with, when the server requires it."
+					required:    false
+					type: string: {
+						default: null
+						examples: ["vector"]
+					}
+				}
+				password: {
+					description: "The password to authenticate with, when the server requires it."
+					required:    false
+					type: string: {
+						default: null
+						examples: ["${FLUENT_PASSWORD}"]
+					}
+				}
+			}
+		}
+		tag: {
+			description: "The tag of the events. The events are batched by tag, so that each chunk forwarded holds a single one."
+			required:    true
+			type: string: {
+				examples: ["vector", "app.{{ service }}"]
+				syntax: "template"
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		chunks: {
+			title: "Chunks and acknowledgements"
+			body: """
+				Each batch of events is forwarded as a single `PackedForward` message, holding the entries of the
+				events, whose timestamps are those of the events. With `require_ack_response`, each message is given a
+				unique chunk ID, and is only considered forwarded once the server acknowledges it. The connections are
+				kept open between the requests, and each carries a single message at a time, so more connections are
+				opened as more requests are in flight.
+				"""
+		}
+		migrations: {
+			title: "Migrating from Fluentd"
+			body: """
+				This sink can feed existing Fluentd aggregators, configured with the
+				[`in_forward`](\(urls.fluentd)) input, so that Vector can replace the Fluentd or Fluent Bit agents
+				before the aggregators themselves are migrated, for instance to Vector instances running the
+				[`fluent` source](\(urls.vector_fluent_source)).
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_discarded_events_total: components.sources.internal_metrics.output.metrics.component_discarded_events_total
+		component_errors_total:           components.sources.internal_metrics.output.metrics.component_errors_total
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+	}
+}
@@ -0,0 +1,182 @@
+package metadata
+
+components: sinks: gcp_bigquery: {
+	title: "GCP BigQuery"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["GCP"]
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    10_000_000
+				max_events:   10_000
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: enabled:    false
+			proxy: enabled:       false
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.gcp_bigquery
+
+				interface: {
+					socket: {
+						api: {
+							title: "BigQuery Storage Write API"
+							url:   urls.gcp_bigquery_write_api
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: [
+			"""
+				The table must already exist. Vector doesn't create it, nor update its schema.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		api_key:          configuration._gcp_api_key
+		credentials_path: configuration._gcp_credentials_path
+		endpoint: {
+			common:      false
+			description: "The endpoint of the BigQuery Storage API."
+			required:    false
+			type: string: {
+				default: "https://bigquerystorage.googleapis.com"
+				examples: ["https://bigquerystorage.googleapis.com"]
+			}
+		}
+		project: {
+			description: "The project of the table."
+			required:    true
+			type: string: {
+				examples: ["vector-123456"]
+			}
+		}
+		dataset: {
+			description: "The dataset of the table."
+			required:    true
+			type: string: {
+				examples: ["logs"]
+			}
+		}
+		table: {
+			description: """
+				The table the events are written to. The columns of the rows are derived from the schema of the events
+				received by the sink, and only the fields it knows are written. When it knows none of their fields, the
+				columns are those of the table instead.
+				"""
+			required: true
+			type: string: {
+				examples: ["events"]
+			}
+		}
+		write_mode: {
+			common:      true
+			description: "How the rows are written to the table."
+			required:    false
+			type: string: {
+				default: "default"
+				enum: {
+					default: """
+						Append the rows to the default stream of the table, where they are committed as soon as they
+						are written. The rows of a request retried after its response was lost are written twice.
+						"""
+					pending: """
+						Write the rows of each request to a pending stream of its own, committed once all of them are
+						written. The retries of a request write to the same stream, at the same offset, so that its rows
+						are written exactly once, and its events are only acknowledged once the stream is committed.
+						"""
+				}
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		schema: {
+			title: "Schema derivation"
+			body: """
+				The rows are sent as protobuf messages, whose descriptor is derived from the schema definition of the
+				events received by the sink. Strings, integers, floats, booleans and timestamps are written as the
+				`STRING`, `INT64`, `DOUBLE`, `BOOL` and `TIMESTAMP` columns of the same name, objects whose fields are
+				all known as `STRUCT` columns, arrays as `REPEATED` columns, and values of any other kind as JSON strings.
+				Fields that are always present and never `null` are `REQUIRED`.
+
+				When the schema definition knows none of the fields of the events, such as when `schema.enabled` isn't
+				set, the descriptor is derived from the schema of the table instead, fetched when the sink starts. Fields whose value can't be converted to the type of their column
+				are left out of the row.
+				"""
+		}
+		exactly_once: {
+			title: "Exactly-once delivery"
+			body: """
+				With `write_mode` set to `pending`, each request creates a pending stream, appends its rows at offset
+				zero, then finalizes and commits the stream. A retried request reuses its stream, and the appends and
+				commits that BigQuery reports as already done are considered successful, so that a request is never
+				written twice. Its events are acknowledged once the commit succeeds.
+				"""
+		}
+	}
+
+	permissions: iam: [
+		{
+			platform: "gcp"
+			_service: "bigquery"
+
+			policies: [
+				{
+					_action: "tables.get"
+					required_for: ["healthcheck"]
+				},
+				{
+					_action: "tables.updateData"
+					required_for: ["operation"]
+				},
+			]
+		},
+	]
+
+	telemetry: metrics: {
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		events_out_total:                 components.sources.internal_metrics.output.metrics.events_out_total
+	}
+}
@@ -0,0 +1,130 @@
+package metadata
+
+components: sinks: gcp_chronicle: {
+	title: "GCP Chronicle"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["GCP"]
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    1_000_000
+				timeout_secs: 15.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: {
+					enabled: true
+					framing: true
+					enum: ["json", "text"]
+				}
+			}
+			proxy: enabled: true
+			request: {
+				enabled:        true
+				rate_limit_num: 1000
+				headers:        false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.gcp_chronicle
+
+				interface: {
+					socket: {
+						api: {
+							title: "Chronicle Ingestion API"
+							url:   urls.gcp_chronicle_ingestion_api
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		api_key:          components.sinks.gcp_chronicle_unstructured.configuration.api_key
+		credentials_path: components.sinks.gcp_chronicle_unstructured.configuration.credentials_path
+		endpoint:         components.sinks.gcp_chronicle_unstructured.configuration.endpoint
+		region:           components.sinks.gcp_chronicle_unstructured.configuration.region
+		customer_id:      components.sinks.gcp_chronicle_unstructured.configuration.customer_id
+		format: {
+			common:      true
+			description: "The format of the events sent to Chronicle."
+			required:    false
+			type: string: {
+				default: "unstructured"
+				enum: {
+					unstructured: "The events are sent as unstructured log entries, which are parsed by Chronicle according to their `log_type`. The events are encoded with `encoding.codec` to build the text of the entries."
+					udm:          "The events are sent as [UDM events](\(urls.gcp_chronicle_udm)), which must already be in the Unified Data Model. The events are encoded with `encoding.codec`, which must be `json`."
+				}
+			}
+		}
+		log_type: {
+			common:        true
+			description:   "Identifies the log entry. This must be one of the supported log types, otherwise Chronicle will reject the entry with an error."
+			required:      false
+			relevant_when: "format = \"unstructured\""
+			type: string: {
+				default: null
+				examples: ["WINDOWS_DNS", "{{ log_type }}"]
+				syntax: "template"
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		formats: {
+			title: "Unstructured entries and UDM events"
+			body: """
+				With the `unstructured` format, the events are sent as raw log entries, which Chronicle parses with the
+				parser of their `log_type`. Each request holds entries of a single log type, so the events are batched
+				by log type.
+
+				With the `udm` format, the events are sent as is to the UDM events endpoint. They must already be
+				normalized to the [Unified Data Model](\(urls.gcp_chronicle_udm)), such as with a `remap` transform,
+				and are rejected by Chronicle otherwise.
+				"""
+		}
+		request_size: {
+			title: "Request size"
+			body: """
+				The ingestion API rejects requests larger than 1 MB, which is the default `batch.max_bytes`.
+				"""
+		}
+	}
+
+	telemetry: components.sinks.gcp_chronicle_unstructured.telemetry
+}
@@ -98,9 +98,22 @@ components: sinks: gcp_chronicle_unstructured: {
 			type: string: {
 				default: null
 				enum: {
-					us:   "United States"
-					eu:   "Europe"
-					asia: "Asia"
+					us:        "United States"
+					eu:        "Europe"
+					asia:      "Asia"
+					london:    "London (`europe-west2`)"
+					frankfurt: "Frankfurt (`europe-west3`)"
+					zurich:    "Zurich (`europe-west6`)"
+					paris:     "Paris (`europe-west9`)"
+					turin:     "Turin (`europe-west12`)"
+					tel_aviv:  "Tel Aviv (`me-west1`)"
+					doha:      "Doha (`me-central1`)"
+					dammam:    "Dammam (`me-central2`)"
+					sydney:    "Sydney (`australia-southeast1`)"
+					tokyo:     "Tokyo (`asia-northeast1`)"
+					mumbai:    "Mumbai (`asia-south1`)"
+					toronto:   "Toronto (`northamerica-northeast2`)"
+					sao_paulo: "São Paulo (`southamerica-east1`)"
 				}
 			}
 		}
@@ -65,6 +65,15 @@ components: sinks: honeycomb: {
 				examples: ["${HONEYCOMB_API_KEY}", "some-api-key"]
 			}
 		}
+		auto_create_dataset: {
+			common:      false
+			description: """
+				Whether to create the dataset when the sink starts, if it does not exist yet. This requires the team
+				key to have the permission to create datasets.
+				"""
+			required: false
+			type: bool: default: false
+		}
 		dataset: {
 			description: "The dataset that Vector will send logs to."
 			required:    true
@@ -72,12 +81,54 @@ components: sinks: honeycomb: {
 				examples: ["my-honeycomb-dataset"]
 			}
 		}
+		markers: {
+			common:      false
+			description: """
+				Configuration of the [markers](\(urls.honeycomb_markers)) sent to the dataset. The events matching
+				the condition are sent as markers, in addition to being sent as events.
+				"""
+			required: false
+			type: object: options: {
+				condition: {
+					description: "The condition that events must match to be sent as markers."
+					required:    true
+					type: condition: {}
+				}
+				message: {
+					description: "The message of the markers."
+					required:    false
+					type: string: {
+						default: null
+						examples: ["Deployed {{ version }}"]
+						syntax: "template"
+					}
+				}
+				type: {
+					description: "The type of the markers, such as `deploy`. Markers of the same type are displayed alike."
+					required:    false
+					type: string: {
+						default: null
+						examples: ["deploy"]
+						syntax: "template"
+					}
+				}
+				url: {
+					description: "The URL the markers link to."
+					required:    false
+					type: string: {
+						default: null
+						examples: ["https://github.com/my-org/my-service/releases/tag/{{ version }}"]
+						syntax: "template"
+					}
+				}
+			}
+		}
 	}
 
 	input: {
 		logs:    true
 		metrics: null
-		traces:  false
+		traces:  true
 	}
 
 	how_it_works: {
@@ -90,6 +141,27 @@ components: sinks: honeycomb: {
 				curl option and use the key provided with the curl example.
 				"""
 		}
+
+		traces: {
+			title: "Traces"
+			body:  """
+				Traces are sent in the [span format](\(urls.honeycomb_tracing)) of Honeycomb, as one event per span.
+				The IDs of the trace, of the span and of its parent span are set in the `trace.trace_id`,
+				`trace.span_id` and `trace.parent_id` fields, the latter being left out for root spans. The
+				`service` and `duration` of the spans are set in the `service.name` and `duration_ms` fields, and
+				their `meta` and `metrics` are set as fields of the span.
+				"""
+		}
+
+		markers: {
+			title: "Markers"
+			body:  """
+				Events such as deploys can be sent as markers, which annotate the graphs of the dataset. Each event
+				matching the `markers.condition` is sent as a marker, starting at the timestamp of the event, in
+				addition to being sent as an event. Failing to send a marker does not prevent the event from being
+				sent.
+				"""
+		}
 	}
 
 	telemetry: metrics: {
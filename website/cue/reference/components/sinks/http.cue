@@ -25,7 +25,7 @@ components: sinks: http: {
 			compression: {
 				enabled: true
 				default: "none"
-				algorithms: ["none", "gzip"]
+				algorithms: ["none", "gzip", "zstd", "snappy"]
 				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
 			}
 			encoding: {
@@ -78,14 +78,43 @@ components: sinks: http: {
 			password_example: "${HTTP_PASSWORD}"
 			username_example: "${HTTP_USERNAME}"
 		}}
+		method: {
+			common:      false
+			description: """
+				The HTTP method to use when making the request, one of `get`, `head`, `post`, `put`,
+				`delete`, `options`, `trace`, or `patch`. When the method is a template, events are
+				batched by their rendered method.
+				"""
+			required: false
+			type: string: {
+				default: "post"
+				examples: ["put", "{{ method }}"]
+				syntax: "template"
+			}
+		}
+		request_per_event: {
+			common:      false
+			description: """
+				Whether to send each event in its own request. The body of each request is then the
+				encoded event alone, rather than a JSON array with the `json` codec, as expected by
+				webhooks such as the ones of PagerDuty or Opsgenie. The batch size options are ignored.
+				"""
+			required: false
+			type: bool: default: false
+		}
 		uri: {
 			description: """
 				The full URI to make HTTP requests to. This should include the protocol and host,
 				but can also include the port, path, and any other valid part of a URI.
+
+				The URI can refer to event fields, in which case events are batched by their rendered
+				URI, which must not include credentials. Header values in `request.headers` can refer to
+				event fields in the same way.
 				"""
 			required: true
 			type: string: {
-				examples: ["https://10.22.212.22:9000/endpoint"]
+				examples: ["https://10.22.212.22:9000/endpoint", "https://api.example.com/incidents/{{ incident_id }}"]
+				syntax: "template"
 			}
 		}
 		healthcheck: type: object: options: uri: {
@@ -61,6 +61,30 @@ components: sinks: kafka: {
 				examples: ["user_id"]
 			}
 		}
+		key: {
+			common:      false
+			description: "The template of the key of the records. If the template can't be rendered, the record is sent without a key. Can't be set along with `key_field`."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["{{ tenant }}-{{ host }}"]
+				syntax: "template"
+			}
+		}
+		partitioner: {
+			common:      false
+			description: "How the records are assigned to the partitions of their topic. The `murmur2` partitioner assigns the records with a key as the default partitioner of the Java producer does, so that consumers relying on the co-partitioning of topics produced by both see the records of a key in the same partition. By default, the partitioner of `librdkafka` is used, which hashes the keys with CRC32."
+			required:    false
+			type: string: {
+				default: null
+				enum: {
+					murmur2: "The murmur2 hash of the key, as the default partitioner of the Java producer of Kafka. Records without a key stick to a random partition for a short while."
+					fnv:     "The FNV-1a hash of the key, as the default partitioner of the Sarama producer. Records without a key stick to a random partition for a short while."
+					random:  "Each record is written to a random partition, whatever its key."
+					sticky:  "The records stick to a random partition for a short while, whatever their key, before another is picked."
+				}
+			}
+		}
 		librdkafka_options: components._kafka.configuration.librdkafka_options
 		message_timeout_ms: {
 			common:      false
@@ -133,6 +157,128 @@ components: sinks: kafka: {
 				examples: ["headers"]
 			}
 		}
+		exactly_once: {
+			common:      false
+			description: """
+				Whether or not to produce events exactly once. When enabled, the producer is idempotent, and
+				events are produced within transactions, one for each batch. Events are only acknowledged
+				once their transaction is committed, and consumers reading with `isolation.level` set to
+				`read_committed` never see the events of aborted transactions, such as those left pending
+				when Vector stops. Requires `transactional_id` to be set.
+				"""
+			required: false
+			type: bool: default: false
+		}
+		transactional_id: {
+			common:      false
+			description: "The transactional ID of the producer, when `exactly_once` is enabled. It must be unique to this sink, and stay the same across restarts, so that the transactions left pending by a previous run of the sink are aborted when it starts again."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["vector-kafka-sink"]
+			}
+		}
+		topic_creation: {
+			common:      false
+			description: "Options for creating the topics the events are written to. When set, the topics that don't exist are created before the first event is written to them."
+			required:    false
+			type: object: {
+				examples: []
+				options: {
+					partitions: {
+						common:      true
+						description: "The number of partitions of the topics."
+						required:    false
+						type: int: default: 1
+					}
+					replication_factor: {
+						common:      true
+						description: "The replication factor of the topics."
+						required:    false
+						type: int: default: 1
+					}
+					config: {
+						common:      false
+						description: "The configuration of the topics, such as `retention.ms` or `cleanup.policy`."
+						required:    false
+						type: object: {
+							examples: [{"retention.ms": "604800000"}]
+							options: {}
+						}
+					}
+				}
+			}
+		}
+		schema_registry: {
+			common:      false
+			description: "The schema registry the schema of the events is registered in, when the sink starts."
+			required:    false
+			type: object: {
+				examples: []
+				options: {
+					url: {
+						description: "The URL of the schema registry."
+						required:    true
+						type: string: {
+							examples: ["http://localhost:8081"]
+						}
+					}
+					format: {
+						description: "The format the schema is written in."
+						required:    true
+						type: string: {
+							enum: {
+								avro:     "An Avro schema."
+								protobuf: "A Protobuf schema, in the `proto3` syntax."
+								json:     "A JSON Schema."
+							}
+						}
+					}
+					subject: {
+						common:      true
+						description: "The subject the schema is registered under. By default, it is the topic followed by `-value`, and it must be set when the topic is a template."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["logs-value"]
+						}
+					}
+					record_name: {
+						common:      false
+						description: "The name of the record, or message, of the events in the schema."
+						required:    false
+						type: string: default: "Event"
+					}
+					compatibility: {
+						common:      false
+						description: "The compatibility level of the subject, set before the schema is checked and registered. By default, the subject keeps its level, or that of the registry."
+						required:    false
+						type: string: {
+							default: null
+							enum: {
+								backward:            "The schema can read the data written with the previous version."
+								backward_transitive: "The schema can read the data written with all the previous versions."
+								forward:             "The data written with the schema can be read with the previous version."
+								forward_transitive:  "The data written with the schema can be read with all the previous versions."
+								full:                "The schema is both backward and forward compatible with the previous version."
+								full_transitive:     "The schema is both backward and forward compatible with all the previous versions."
+								none:                "The schema isn't checked."
+							}
+						}
+					}
+					auth: configuration._http_auth & {_args: {
+						password_example: "${SCHEMA_REGISTRY_PASSWORD}"
+						username_example: "${SCHEMA_REGISTRY_USERNAME}"
+					}}
+					tls: configuration._tls_connect & {_args: {
+						can_verify_certificate: true
+						can_verify_hostname:    true
+						enabled_default:        false
+						enabled_by_scheme:      true
+					}}
+				}
+			}
+		}
 	}
 
 	input: {
@@ -148,7 +294,23 @@ components: sinks: kafka: {
 		traces: false
 	}
 
-	how_it_works: components._kafka.how_it_works
+	how_it_works: components._kafka.how_it_works & {
+		schema_registry: {
+			title: "Schema registry"
+			body: """
+				When `schema_registry` is set, the sink derives the schema of the events from their schema
+				definition, as known from the sources and transforms upstream of it, and writes it as an Avro,
+				Protobuf or JSON Schema. When it starts, it sets the compatibility level of the subject, if
+				configured, checks the schema against the latest version of the subject, and registers it. The
+				sink fails to start when the schema is not compatible, or when none of the fields of the events
+				are known.
+
+				The fields whose type isn't known are left out of the schema, along with those whose name isn't
+				valid in Avro and Protobuf. The events are still encoded with the configured `encoding`, which
+				should match the format of the schema.
+				"""
+		}
+	}
 
 	telemetry: metrics: {
 		component_sent_events_total:         components.sources.internal_metrics.output.metrics.component_sent_events_total
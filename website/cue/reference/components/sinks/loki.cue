@@ -70,7 +70,7 @@ components: sinks: loki: {
 
 	configuration: {
 		endpoint: {
-			description: "The base URL of the Loki instance. Vector will append `/loki/api/v1/push` to this."
+			description: "The base URL of the Loki instance. Vector will append the path of the configured `transport` to this."
 			required:    true
 			type: string: {
 				examples: ["http://localhost:3100"]
@@ -80,7 +80,41 @@ components: sinks: loki: {
 			password_example: "${LOKI_PASSWORD}"
 			username_example: "${LOKI_USERNAME}"
 		}}
+		auto_labels: {
+			common:      false
+			description: """
+				Derives labels from the fields the schema assigns a semantic meaning to. Labels set explicitly in
+				`labels` take precedence over derived ones. Each derived label only accepts up to `max_cardinality`
+				distinct values; once that limit is reached, new values are sent as structured metadata instead.
+				"""
+			required: false
+			type: object: options: {
+				meanings: {
+					common:      false
+					description: "The semantic meanings to derive labels from."
+					required:    false
+					type: array: {
+						default: ["severity", "service", "host"]
+						items: type: string: enum: {
+							severity: "The `severity` meaning, set as the `level` label."
+							service:  "The `service` meaning, set as the `service_name` label."
+							host:     "The `host` meaning, set as the `host` label."
+						}
+					}
+				}
+				max_cardinality: {
+					common:      false
+					description: "The maximum number of distinct values a derived label can take."
+					required:    false
+					type: uint: {
+						default: 100
+						unit:    null
+					}
+				}
+			}
+		}
 		labels: {
+			common:      true
 			description: """
 				A set of labels that are attached to each batch of events. Both keys and values are templatable, which
 				enables you to attach dynamic labels to events. Labels can be suffixed with a "*" to allow the expansion
@@ -88,8 +122,10 @@ components: sinks: loki: {
 
 				Note: If the set of labels has high cardinality, this can cause drastic performance issues with Loki.
 				To prevent this from happening, reduce the number of unique label keys and values.
+
+				At least one label is required, unless `auto_labels` is set.
 				"""
-			required: true
+			required: false
 			type: object: {
 				examples: [
 					{
@@ -133,6 +169,35 @@ components: sinks: loki: {
 				}
 			}
 		}
+		out_of_order_window: {
+			common:      false
+			description: """
+				The out-of-order window of the Loki tenants. Events older than the latest event of their stream, but
+				within the window, are sent as-is; `out_of_order_action` only applies to events outside of it. This
+				should match the `out_of_order_time_window` limit configured in Loki.
+				"""
+			required: false
+			type: object: options: {
+				window_secs: {
+					common:      false
+					description: "The out-of-order window, in seconds, applied to every tenant."
+					required:    false
+					type: uint: {
+						default: null
+						unit:    "seconds"
+					}
+				}
+				tenant_window_secs: {
+					common:      false
+					description: "Per-tenant out-of-order windows, in seconds, keyed by the rendered `tenant_id`. These take precedence over `window_secs`."
+					required:    false
+					type: object: {
+						examples: [{"team-a": 3600}]
+						options: {}
+					}
+				}
+			}
+		}
 		remove_label_fields: {
 			common:      false
 			description: "If this is set to `true` then when labels are collected from events those fields will also get removed from the event."
@@ -146,6 +211,47 @@ components: sinks: loki: {
 			required:    false
 			type: bool: default: true
 		}
+		structured_metadata: {
+			common:      false
+			description: """
+				A set of structured metadata that is attached to each event. Structured metadata is stored alongside
+				the log line, outside of the label set, so high-cardinality values can be attached without creating new
+				streams. Both keys and values are templatable, and keys can be suffixed with a "*", the same as `labels`.
+				Requires Loki 3.0.0 and newer.
+				"""
+			required: false
+			type: object: {
+				examples: [
+					{
+						"trace_id": "{{ trace_id }}"
+					},
+				]
+				options: {
+					"*": {
+						common:      false
+						description: "Any structured metadata, templatable"
+						required:    false
+						type: string: {
+							default: null
+							examples: ["{{ trace_id }}"]
+							syntax: "template"
+						}
+					}
+				}
+			}
+		}
+		transport: {
+			common:      false
+			description: "The transport used to send events to Loki."
+			required:    false
+			type: string: {
+				default: "push"
+				enum: {
+					"push": "The Loki push API, at `/loki/api/v1/push`."
+					"otlp": "The native OTLP/HTTP ingestion endpoint, at `/otlp/v1/logs`, encoded as JSON. Labels are sent as resource attributes, and structured metadata as log record attributes. `snappy` compression falls back to `gzip`. Requires Loki 3.0.0 and newer."
+				}
+			}
+		}
 		tenant_id: {
 			common:      false
 			description: """
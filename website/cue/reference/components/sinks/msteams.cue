@@ -0,0 +1,126 @@
+package metadata
+
+components: sinks: msteams: {
+	title: "Microsoft Teams"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: ["Microsoft"]
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: false
+		send: {
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: true
+			request: {
+				enabled:        true
+				rate_limit_num: 4
+				headers:        false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.msteams
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		webhook_url: {
+			description: "The URL of the incoming webhook to post messages to. As each webhook posts to a single channel, events can be routed to channels with a template."
+			required:    true
+			type: string: {
+				examples: ["${TEAMS_WEBHOOK_URL}", "{{ teams_webhook }}"]
+				syntax: "template"
+			}
+		}
+		title: {
+			common:      true
+			description: "The title of each message."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["{{ service }} alert"]
+				syntax: "template"
+			}
+		}
+		text: {
+			common:      true
+			description: "The text of each message. Either this or `card_body` must be set."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["{{ message }}"]
+				syntax: "template"
+			}
+		}
+		card_body: {
+			common:      false
+			description: "The body of the [Adaptive Card](\(urls.adaptive_cards)) of each message, which replaces `title` and `text`. Each string in the body is a template, so that the card can refer to fields of the event."
+			required:    false
+			type: array: {
+				default: null
+				items: type: object: {
+					examples: [{
+						type: "FactSet"
+						facts: [{title: "Service", value: "{{ service }}"}]
+					}]
+					options: {}
+				}
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		rate_limiting: {
+			title: "Rate limiting"
+			body: """
+				Incoming webhooks accept a few messages per second, so this sink posts at most four
+				messages per second by default. Messages that are rejected with a `429` status are
+				retried after the delay given by their `Retry-After` header.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:           components.sources.internal_metrics.output.metrics.component_errors_total
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+	}
+}
@@ -117,12 +117,33 @@ components: sinks: new_relic: {
 			counter:      true
 			distribution: true
 			gauge:        true
-			histogram:    true
-			set:          true
-			summary:      true
+			histogram:    false
+			set:          false
+			summary:      false
 		}
 		traces: false
 	}
 
+	how_it_works: {
+		metric_types: {
+			title: "Metric types"
+			body: """
+				Metrics are sent to the dimensional Metric API, with their tags as attributes. Gauges are sent as
+				`gauge` metrics. Incremental counters with an interval are sent as `count` metrics, and other
+				counters as `gauge` metrics. Incremental distributions with an interval are summarized into
+				`summary` metrics. Other metrics are dropped, as the Metric API requires an interval for counts
+				and summaries.
+				"""
+		}
+		entity_synthesis: {
+			title: "Entity synthesis"
+			body: """
+				To let New Relic attach the data to its entities, the `host.name` and `service.name` attributes
+				are set, unless already present, from the fields of the events with the `host` and `service`
+				meanings, and from the `host` and `service` tags of the metrics.
+				"""
+		}
+	}
+
 	telemetry: components.sinks.http.telemetry
 }
@@ -0,0 +1,224 @@
+package metadata
+
+components: sinks: pagerduty: {
+	title: "PagerDuty"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: false
+		send: {
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.pagerduty
+
+				interface: {
+					socket: {
+						api: {
+							title: "PagerDuty Events API v2"
+							url:   urls.pagerduty_events_api
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		routing_key: {
+			description: "The integration key of the PagerDuty service to send events to."
+			required:    true
+			type: string: {
+				examples: ["${PAGERDUTY_ROUTING_KEY}", "{{ pagerduty_routing_key }}"]
+				syntax: "template"
+			}
+		}
+		endpoint: {
+			common:      false
+			description: "The base URL of the PagerDuty Events API."
+			required:    false
+			type: string: {
+				default: "https://events.pagerduty.com"
+			}
+		}
+		action: {
+			common:      true
+			description: "The action of each event. Events whose action is `trigger`, `triggered` or `firing` trigger an alert, events whose action is `acknowledge`, `acknowledged` or `ack` acknowledge it, and events whose action is `resolve`, `resolved` or `ok` resolve it. Events with another action are dropped."
+			required:    false
+			type: string: {
+				default: "trigger"
+				examples: ["{{ status }}"]
+				syntax: "template"
+			}
+		}
+		dedup_key: {
+			common:      true
+			description: "The key identifying the alert of each event. Events with the same key are grouped in the same alert, which they can acknowledge or resolve. Events that acknowledge or resolve an alert are dropped without it, and each triggering event creates a new alert."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["{{ service }}-{{ check }}"]
+				syntax: "template"
+			}
+		}
+		summary: {
+			common:      true
+			description: "The summary of the alert."
+			required:    false
+			type: string: {
+				default: "{{ message }}"
+				syntax:  "template"
+			}
+		}
+		source: {
+			common:      false
+			description: "The system the alert is about."
+			required:    false
+			type: string: {
+				default: "{{ host }}"
+				syntax:  "template"
+			}
+		}
+		severity: {
+			common:      true
+			description: "The severity of each event. It is mapped to the PagerDuty severity with the closest meaning, so syslog severities and level names such as `fatal`, `err`, `warn` or `debug` can be used. Events whose severity can't be mapped get `default_severity`."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["{{ level }}"]
+				syntax: "template"
+			}
+		}
+		default_severity: {
+			common:      false
+			description: "The severity of events without a severity that can be mapped."
+			required:    false
+			type: string: {
+				default: "error"
+				enum: {
+					critical: "Critical."
+					error:    "Error."
+					warning:  "Warning."
+					info:     "Info."
+				}
+			}
+		}
+		component: {
+			common:      false
+			description: "The component of the source system that is responsible for the alert."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["{{ container_name }}"]
+				syntax: "template"
+			}
+		}
+		group: {
+			common:      false
+			description: "The logical grouping of the components of the source system."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["{{ cluster }}"]
+				syntax: "template"
+			}
+		}
+		class: {
+			common:      false
+			description: "The class or type of the alert."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["disk full"]
+				syntax: "template"
+			}
+		}
+		routing_key_rate_limit_num: {
+			common:      false
+			description: "The most events sent for each routing key within `routing_key_rate_limit_duration_secs`. Events over the limit are dropped rather than retried."
+			required:    false
+			type: uint: {
+				default: 120
+				unit:    "events"
+			}
+		}
+		routing_key_rate_limit_duration_secs: {
+			common:      false
+			description: "The time window used for `routing_key_rate_limit_num`."
+			required:    false
+			type: uint: {
+				default: 60
+				unit:    "seconds"
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		alert_lifecycle: {
+			title: "Alert lifecycle"
+			body: """
+				Each event triggers, acknowledges or resolves an alert depending on its `action`.
+				Triggering events carry a payload with the summary, source and severity of the alert,
+				and the event itself as custom details. Acknowledging and resolving events only
+				refer to the alert by its `dedup_key`.
+				"""
+		}
+		rate_limiting: {
+			title: "Rate limiting"
+			body: """
+				PagerDuty limits how many events can be sent for each routing key, so this sink sends
+				at most `routing_key_rate_limit_num` events per routing key within
+				`routing_key_rate_limit_duration_secs`, and drops the others. Requests that PagerDuty
+				rejects with a `429` status are retried after the delay given by their `Retry-After`
+				header.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_discarded_events_total: components.sources.internal_metrics.output.metrics.component_discarded_events_total
+		component_errors_total:           components.sources.internal_metrics.output.metrics.component_errors_total
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+	}
+}
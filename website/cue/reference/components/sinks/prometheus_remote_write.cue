@@ -193,6 +193,22 @@ components: sinks: prometheus_remote_write: {
 		traces: false
 	}
 
+	how_it_works: {
+		native_histograms: {
+			title: "Native histograms"
+			body: """
+				Exponential histograms, such as those received from OpenTelemetry or from a
+				Prometheus native histogram, are sent as native histograms rather than being
+				converted to fixed buckets. Their resolution is lowered to schema 8 if needed, which
+				is the finest one Prometheus supports. Histograms coarser than schema -4 are sent as
+				regular histograms instead.
+
+				The receiving end must have native histograms enabled, for instance with the
+				`--enable-feature=native-histograms` flag of Prometheus.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
 		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
 		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
@@ -0,0 +1,130 @@
+package metadata
+
+components: sinks: quickwit: {
+	title: "Quickwit"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    10_000_000
+				timeout_secs: 1.0
+			}
+			compression: {
+				enabled: true
+				default: "none"
+				algorithms: ["none", "gzip", "zstd"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+			}
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.quickwit
+
+				interface: {
+					socket: {
+						api: {
+							title: "Quickwit ingest API"
+							url:   urls.quickwit_ingest_api
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		auth: configuration._http_auth & {_args: {
+			password_example: "${QUICKWIT_PASSWORD}"
+			username_example: "${QUICKWIT_USERNAME}"
+		}}
+		commit: {
+			common:      true
+			description: "When the documents ingested are committed, and become searchable."
+			required:    false
+			type: string: {
+				default: "auto"
+				enum: {
+					auto:     "The documents are committed once the commit timeout of the index expires, and the request returns as soon as they are queued for indexing."
+					wait_for: "The request waits until the documents are committed, once the commit timeout of the index expires."
+					force:    "The documents are committed immediately, and the request waits until they are. Forcing commits creates many small splits, so this should only be used with large batches or low volumes of events."
+				}
+			}
+		}
+		endpoint: {
+			description: "The endpoint of the REST API of the Quickwit cluster."
+			required:    true
+			type: string: {
+				examples: ["http://localhost:7280"]
+			}
+		}
+		index: {
+			description: "The index the events are ingested into. The index must already exist. The events are batched by index, so that each request ingests into a single one."
+			required:    true
+			type: string: {
+				examples: ["application-logs", "{{ service }}-logs"]
+				syntax: "template"
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		batching: {
+			title: "Batching"
+			body: """
+				The events are sent as newline delimited JSON documents, in requests of at most 10MB, the largest
+				request the REST API of Quickwit accepts by default. When the index is the same for all of the events,
+				the healthcheck also checks that it exists.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_discarded_events_total: components.sources.internal_metrics.output.metrics.component_discarded_events_total
+		component_errors_total:           components.sources.internal_metrics.output.metrics.component_errors_total
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+	}
+}
@@ -73,13 +73,14 @@ components: sinks: redis: {
 		}
 		data_type: {
 			common:      false
-			description: "The Redis data type (`list` or `channel`) to use."
+			description: "The Redis data type (`list`, `channel` or `stream`) to use."
 			required:    false
 			type: string: {
 				default: "list"
 				enum: {
 					list:    "Use the Redis `list` data type."
 					channel: "Use the Redis `channel` data type."
+					stream:  "Use the Redis `stream` data type, appending messages with `XADD`."
 				}
 			}
 		}
@@ -105,6 +106,43 @@ components: sinks: redis: {
 				}
 			}
 		}
+		stream: {
+			common:      false
+			description: "Options for the Redis `stream` data type."
+			required:    false
+			type: object: {
+				examples: []
+				options: {
+					approximate_maxlen: {
+						common:      false
+						description: "Whether or not to trim the stream approximately. Approximate trimming only removes whole nodes of the stream, which is much more efficient, and leaves the stream at least `maxlen` entries long."
+						required:    false
+						type: bool: default: true
+					}
+					field: {
+						common:      false
+						description: "The field of the stream entries to store messages in."
+						required:    false
+						type: string: default: "message"
+					}
+					maxlen: {
+						common:      false
+						description: "The maximum length of the stream. The oldest entries are trimmed off the stream as new ones are added. If not set, the stream is not trimmed."
+						required:    false
+						type: uint: {
+							default: null
+							unit:    "events"
+						}
+					}
+				}
+			}
+		}
+		cluster: {
+			common:      false
+			description: "Whether or not the Redis deployment is a Redis Cluster. When enabled, `url` is the address of any node of the cluster, and each message is sent to the node serving the hash slot of its key."
+			required:    false
+			type: bool: default: false
+		}
 	}
 
 	input: {
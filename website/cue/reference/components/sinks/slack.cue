@@ -0,0 +1,160 @@
+package metadata
+
+components: sinks: slack: {
+	title: "Slack"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: true
+			request: {
+				enabled:        true
+				rate_limit_num: 1
+				headers:        false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.slack
+
+				interface: {
+					socket: {
+						api: {
+							title: "Slack Web API"
+							url:   urls.slack
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		webhook_url: {
+			common:      true
+			description: "The URL of the incoming webhook to post messages to. Either this or `token` must be set."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["${SLACK_WEBHOOK_URL}"]
+			}
+		}
+		token: {
+			common:      true
+			description: "The token of the Slack app to post messages with. Messages are posted with the `chat.postMessage` method, which requires `channel` to be set. Either this or `webhook_url` must be set."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["${SLACK_TOKEN}"]
+			}
+		}
+		endpoint: {
+			common:      false
+			description: "The base URL of the Slack Web API."
+			required:    false
+			type: string: {
+				default: "https://slack.com/api"
+			}
+		}
+		channel: {
+			common:      true
+			description: "The channel to post each message to."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["#alerts", "{{ team_channel }}"]
+				syntax: "template"
+			}
+		}
+		thread_ts: {
+			common:      false
+			description: "The timestamp of the message to reply to in a thread. Messages are posted to the channel itself by default."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["{{ slack_thread_ts }}"]
+				syntax: "template"
+			}
+		}
+		text: {
+			description: "The text of each message. When `blocks` are set, this is only shown in notifications."
+			required:    true
+			type: string: {
+				examples: ["{{ service }} is down: {{ message }}"]
+				syntax: "template"
+			}
+		}
+		blocks: {
+			common:      false
+			description: "The [Block Kit](\(urls.slack_block_kit)) layout of each message. Each string in the blocks is a template, so that the blocks can refer to fields of the event."
+			required:    false
+			type: array: {
+				default: null
+				items: type: object: {
+					examples: [{
+						type: "section"
+						text: {
+							type: "mrkdwn"
+							text: "*{{ service }}* is down: {{ message }}"
+						}
+					}]
+					options: {}
+				}
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		rate_limiting: {
+			title: "Rate limiting"
+			body: """
+				Slack [limits](\(urls.slack_rate_limits)) how many messages can be posted to each
+				channel, so this sink posts at most one message per second by default. Messages that
+				Slack rejects with a `429` status, or with a `ratelimited` error, are retried after
+				the delay given by their `Retry-After` header.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:           components.sources.internal_metrics.output.metrics.component_errors_total
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+	}
+}
@@ -0,0 +1,204 @@
+package metadata
+
+components: sinks: smtp: {
+	title: "SMTP"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			request: enabled: false
+			tls: enabled:     false
+			to: {
+				service: services.smtp
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		host: {
+			description: "The host name of the SMTP server."
+			required:    true
+			type: string: {
+				examples: ["smtp.example.com"]
+			}
+		}
+		port: {
+			common:      false
+			description: "The port of the SMTP server. Defaults to 587 with `starttls`, 465 with `tls`, and 25 with `none`."
+			required:    false
+			type: uint: {
+				default: null
+				examples: [2525]
+				unit: null
+			}
+		}
+		tls_mode: {
+			common:      true
+			description: "How the connection to the SMTP server is secured."
+			required:    false
+			type: string: {
+				default: "starttls"
+				enum: {
+					starttls: "The connection is upgraded to TLS with `STARTTLS`, which the server must support."
+					tls:      "The connection uses TLS from the start."
+					none:     "The connection is not secured."
+				}
+			}
+		}
+		auth: {
+			common:      true
+			description: "The credentials used to log in to the SMTP server."
+			required:    false
+			type: object: options: {
+				username: {
+					description: "The username."
+					required:    true
+					type: string: examples: ["vector"]
+				}
+				password: {
+					description: "The password."
+					required:    true
+					type: string: examples: ["${SMTP_PASSWORD}"]
+				}
+			}
+		}
+		from: {
+			description: "The sender of the emails."
+			required:    true
+			type: string: {
+				examples: ["Vector <vector@example.com>"]
+			}
+		}
+		to: {
+			description: "The recipients of the emails."
+			required:    true
+			type: array: items: type: string: examples: ["oncall@example.com"]
+		}
+		subject: {
+			description: "The subject of the emails. When an email is a digest of several events, the subject is rendered from the first one."
+			required:    true
+			type: string: {
+				examples: ["{{ service }} is down"]
+				syntax: "template"
+			}
+		}
+		body: {
+			description: "The body of each event in the emails."
+			required:    true
+			type: string: {
+				examples: ["{{ message }}"]
+				syntax: "template"
+			}
+		}
+		key_field: {
+			common:      true
+			description: "The key that events are grouped by. Each key gets its own digests, and its own rate limit. Events are not grouped by default."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["{{ service }}"]
+				syntax: "template"
+			}
+		}
+		digest_secs: {
+			common:      true
+			description: "How long to wait for more events of a key before sending their digest. With `0`, an email is sent for each event."
+			required:    false
+			type: uint: {
+				default: 30
+				unit:    "seconds"
+			}
+		}
+		max_events_per_email: {
+			common:      false
+			description: "The most events whose body is included in a digest. The other events of the digest are only counted."
+			required:    false
+			type: uint: {
+				default: 25
+				unit:    "events"
+			}
+		}
+		rate_limit_num: {
+			common:      true
+			description: "The most emails sent for each key within `rate_limit_duration_secs`. The events of digests over the limit are dropped."
+			required:    false
+			type: uint: {
+				default: 10
+				unit:    null
+			}
+		}
+		rate_limit_duration_secs: {
+			common:      false
+			description: "The time window used for `rate_limit_num`."
+			required:    false
+			type: uint: {
+				default: 3600
+				unit:    "seconds"
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		digests: {
+			title: "Digests"
+			body: """
+				Alerts tend to come in bursts, so rather than sending an email for each event, the
+				events of each key are collected for `digest_secs` and sent as a single email. The
+				body of the email holds the body of each event, up to `max_events_per_email` of
+				them, and a count of the others.
+				"""
+		}
+		rate_limiting: {
+			title: "Rate limiting"
+			body: """
+				At most `rate_limit_num` emails are sent for each key within
+				`rate_limit_duration_secs`, so that a misbehaving pipeline can't flood mailboxes.
+				The events of the emails over the limit are dropped, and counted in
+				`component_discarded_events_total`.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_discarded_events_total: components.sources.internal_metrics.output.metrics.component_discarded_events_total
+		component_errors_total:           components.sources.internal_metrics.output.metrics.component_errors_total
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+	}
+}
@@ -0,0 +1,180 @@
+package metadata
+
+components: sinks: splunk_signalfx: {
+	title: "Splunk Observability (SignalFx)"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["Splunk"]
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    1_000_000
+				max_events:   1000
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: enabled:    false
+			proxy: enabled:       true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.splunk_signalfx
+
+				interface: {
+					socket: {
+						api: {
+							title: "SignalFx ingest API"
+							url:   urls.splunk_signalfx_ingest_api
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		realm: {
+			common:      true
+			description: "The realm of the organization. The events are sent to the ingest endpoint of the realm, `https://ingest.<realm>.signalfx.com`."
+			required:    false
+			type: string: {
+				default: "us0"
+				examples: ["us1", "eu0", "jp0"]
+			}
+		}
+		endpoint: {
+			common:      false
+			description: "The ingest endpoint to send events to, instead of the one of the realm. This is useful to send events through a proxy, such as the OpenTelemetry Collector."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["http://otel-collector:9943"]
+			}
+		}
+		access_token: {
+			description: "An [access token](\(urls.splunk_signalfx_access_tokens)) of the organization, with the `INGEST` scope."
+			required:    true
+			type: string: {
+				examples: ["${SIGNALFX_ACCESS_TOKEN}"]
+			}
+		}
+		default_namespace: {
+			common: true
+			description: """
+				The default namespace for any metrics sent. This namespace is only used if a metric
+				has no existing namespace. When a namespace is present, it is used as a prefix to the
+				metric name, and separated with a period (`.`).
+				"""
+			required: false
+			type: string: {
+				default: null
+				examples: ["service"]
+			}
+		}
+		dimension_mapping: {
+			common: false
+			description: """
+				The names of the dimensions the tags of the metrics are sent as, by tag. Tags that
+				aren't listed are sent as dimensions of the same name. The names of the dimensions
+				can only contain letters, digits, underscores and hyphens, so other characters are
+				replaced with underscores.
+				"""
+			required: false
+			type: object: {
+				examples: [{"host": "host_name", "k8s.pod.name": "kubernetes_pod_name"}]
+				options: {
+					"*": {
+						description: "The name of the dimension the tag is sent as."
+						required:    true
+						type: string: {}
+					}
+				}
+			}
+		}
+		event_type: {
+			common: true
+			description: """
+				The type of the custom events the logs are sent as. The `category`, `dimensions`
+				and `properties` fields of the logs, if any, are sent as the category, dimensions
+				and properties of the events.
+				"""
+			required: false
+			type: string: {
+				default: "{{ event_type }}"
+				examples: ["deployment", "{{ event_type }}"]
+				syntax: "template"
+			}
+		}
+	}
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: false
+			gauge:        true
+			histogram:    false
+			set:          true
+			summary:      false
+		}
+		traces: false
+	}
+
+	how_it_works: {
+		datapoints: {
+			title: "Datapoints"
+			body: """
+				Metrics are sent as datapoints to the `/v2/datapoint` endpoint. Incremental counters
+				are sent as `counter` datapoints, absolute counters as `cumulative_counter`
+				datapoints, and gauges as `gauge` datapoints. Incremental gauges are accumulated
+				first, and sets are sent as gauges of the number of their values. The tags of the
+				metrics are sent as dimensions, renamed with `dimension_mapping`.
+				"""
+		}
+		events: {
+			title: "Custom events"
+			body: """
+				Logs are sent as custom events to the `/v2/event` endpoint. Their type is rendered
+				from `event_type`, and their category defaults to `USER_DEFINED`. Datapoints and
+				events are always sent in separate requests.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		component_errors_total:           components.sources.internal_metrics.output.metrics.component_errors_total
+		component_discarded_events_total: components.sources.internal_metrics.output.metrics.component_discarded_events_total
+	}
+}
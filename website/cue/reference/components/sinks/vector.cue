@@ -82,17 +82,138 @@ components: sinks: vector: {
 				examples: ["92.12.333.224:\(_port)"]
 			}
 		}
+		addresses: {
+			description: "Additional downstream Vector addresses to connect to. Requests are balanced across `address` and these addresses. Each address _must_ include a port."
+			common:      false
+			required:    false
+			type: array: {
+				default: []
+				items: type: string: {
+					examples: ["92.12.333.225:\(_port)"]
+				}
+			}
+		}
 		compression: {
-			description: "Enable gRPC compression with gzip."
-			common:      true
+			description: """
+				The compression of requests: `none`, `gzip` or `zstd`. For backwards compatibility, this
+				can also be a boolean, where `true` is the same as `gzip`. zstd compressed requests can only
+				be received by a downstream Vector whose `vector` source supports zstd.
+				"""
+			common:   true
+			required: false
+			type: string: {
+				default: "none"
+				enum: {
+					none: "No compression."
+					gzip: "[Gzip](\(urls.gzip)) compression."
+					zstd: "[Zstandard](\(urls.zstd)) compression."
+				}
+			}
+		}
+		grpc: {
+			description: "gRPC client connection settings."
+			common:      false
 			required:    false
-			type: bool: default: false
+			type: object: options: {
+				connections: {
+					description: """
+						The number of connections to open to the downstream Vector. The host of the address is
+						resolved, and each connection is pinned to one of the resolved addresses in turn. Requests
+						are balanced across the connections round-robin, which spreads the load across the
+						instances of a headless service.
+						"""
+					common:   false
+					required: false
+					type: uint: {
+						default: 1
+						unit:    null
+					}
+				}
+				keepalive_interval_secs: {
+					description: "The interval between HTTP/2 keepalive pings, which are also sent on idle connections. If not set, no keepalive pings are sent."
+					common:      false
+					required:    false
+					type: uint: {
+						default: null
+						unit:    "seconds"
+					}
+				}
+				keepalive_timeout_secs: {
+					description: "The time to wait for a keepalive ping to be acknowledged before the connection is closed."
+					common:      false
+					required:    false
+					type: uint: {
+						default: null
+						unit:    "seconds"
+					}
+				}
+				max_concurrent_streams: {
+					description: "The maximum number of in-flight requests on each connection."
+					common:      false
+					required:    false
+					type: uint: {
+						default: null
+						unit:    "requests"
+					}
+				}
+			}
+		}
+		load_balancing: {
+			description: """
+				Load balancing settings. Requests are spread across the addresses weighted by their recent
+				success rate, and addresses failing too many requests in a row are ejected for a while.
+				"""
+			common:   false
+			required: false
+			type: object: options: {
+				ejection_consecutive_errors: {
+					description: "The number of consecutive failed requests after which an endpoint is ejected."
+					common:      false
+					required:    false
+					type: uint: {
+						default: 5
+						unit:    "requests"
+					}
+				}
+				ejection_duration_secs: {
+					description: "The time an endpoint is ejected for. An endpoint ejected again right after its ejection ends is ejected for longer each time, up to ten times this duration."
+					common:      false
+					required:    false
+					type: uint: {
+						default: 30
+						unit:    "seconds"
+					}
+				}
+				max_ejection_percent: {
+					description: "The maximum percentage of the endpoints which can be ejected at the same time."
+					common:      false
+					required:    false
+					type: uint: {
+						default: 50
+						unit:    null
+					}
+				}
+				resolve_interval_secs: {
+					description: """
+						The interval at which the hosts of the addresses are resolved again. When set, requests are
+						balanced across all of the addresses the hosts resolve to, such as the instances of a
+						Kubernetes headless service.
+						"""
+					common:   false
+					required: false
+					type: uint: {
+						default: null
+						unit:    "seconds"
+					}
+				}
+			}
 		}
 	}
 
 	how_it_works: components.sources.vector.how_it_works
 
 	telemetry: metrics: {
+		endpoint_ejections_total:         components.sources.internal_metrics.output.metrics.endpoint_ejections_total
 		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
 		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
 		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
@@ -0,0 +1,138 @@
+package metadata
+
+components: sinks: victorialogs: {
+	title: "VictoriaLogs"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    10_000_000
+				timeout_secs: 1.0
+			}
+			compression: {
+				enabled: true
+				default: "gzip"
+				algorithms: ["none", "gzip", "zlib", "zstd"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+			}
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+			to: {
+				service: services.victorialogs
+
+				interface: {
+					socket: {
+						api: {
+							title: "VictoriaLogs JSON stream API"
+							url:   urls.victorialogs_jsonline
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		endpoint: {
+			description: "The base URL of the VictoriaLogs instance. Vector will append `/insert/jsonline` to this."
+			required:    true
+			type: string: {
+				examples: ["http://localhost:9428"]
+			}
+		}
+		message_field: {
+			common:      false
+			description: "The field holding the log message, sent as the `_msg` field. If not set, the global `log_schema.message_key` is used."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["message"]
+			}
+		}
+		timestamp_field: {
+			common:      false
+			description: "The field holding the log timestamp, sent as the `_time` field. If not set, the global `log_schema.timestamp_key` is used. RFC 3339 strings and integer Unix timestamps, in seconds, are converted to timestamps, while other values are sent as is. Events without a timestamp are sent with the current time."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["timestamp"]
+			}
+		}
+		stream_fields: {
+			common:      true
+			description: "The fields identifying the log stream. These should be low-cardinality fields, such as the host or the application name."
+			required:    false
+			type: array: {
+				default: []
+				items: type: string: examples: ["host", "app"]
+			}
+		}
+		account_id: {
+			common:      false
+			description: "The `AccountID` of the tenant to send logs to."
+			required:    false
+			type: uint: {
+				default: null
+				examples: [12]
+				unit: null
+			}
+		}
+		project_id: {
+			common:      false
+			description: "The `ProjectID` of the tenant to send logs to."
+			required:    false
+			type: uint: {
+				default: null
+				examples: [34]
+				unit: null
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	telemetry: metrics: {
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+	}
+}
@@ -0,0 +1,144 @@
+package metadata
+
+components: sinks: victoriametrics: {
+	title: "VictoriaMetrics"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    10_000_000
+				max_events:   10_000
+				timeout_secs: 1.0
+			}
+			compression: {
+				enabled: true
+				default: "gzip"
+				algorithms: ["none", "gzip", "zlib", "zstd"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+			}
+			encoding: enabled: false
+			proxy: enabled: true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+			to: {
+				service: services.victoriametrics
+
+				interface: {
+					socket: {
+						api: {
+							title: "VictoriaMetrics import API"
+							url:   urls.victoriametrics_import
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		endpoint: {
+			description: """
+				The base URL of the VictoriaMetrics instance. Vector will append the import path of
+				the configured `format` to this. For the cluster version, this should point to
+				`vminsert`, including the tenant.
+				"""
+			required: true
+			type: string: {
+				examples: ["http://localhost:8428", "http://vminsert:8480/insert/0/prometheus"]
+			}
+		}
+		format: {
+			common:      true
+			description: "The import format used to send metrics to VictoriaMetrics."
+			required:    false
+			type: string: {
+				default: "jsonl"
+				enum: {
+					jsonl:      "JSON lines, sent to `/api/v1/import`."
+					prometheus: "The Prometheus text exposition format, sent to `/api/v1/import/prometheus`."
+					native:     "The binary format of `/api/v1/export/native`, sent to `/api/v1/import/native`."
+				}
+			}
+		}
+		default_namespace: {
+			common: true
+			description: """
+				The default namespace for any metrics sent. This namespace is only used if a metric
+				has no existing namespace. When a namespace is present, it is used as a prefix to the
+				metric name, and separated with an underscore (`_`).
+				"""
+			required: false
+			type: string: {
+				default: null
+				examples: ["service"]
+			}
+		}
+		buckets: {
+			common:      false
+			description: "Default buckets to use for aggregating distribution metrics into histograms."
+			required:    false
+			type: array: {
+				default: [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0]
+				items: type: float: examples: [0.005, 0.01]
+			}
+		}
+		quantiles: {
+			common:      false
+			description: "Quantiles to use for aggregating distribution metrics into a summary."
+			required:    false
+			type: array: {
+				default: [0.5, 0.75, 0.9, 0.95, 0.99]
+				items: type: float: examples: [0.5, 0.99]
+			}
+		}
+	}
+
+	input: {
+		logs: false
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: false
+	}
+
+	telemetry: metrics: {
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+	}
+}
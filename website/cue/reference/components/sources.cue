@@ -175,6 +175,26 @@ components: sources: [Name=string]: {
 								}
 							}
 						}
+						json: {
+							description:   "Options for the JSON decoder."
+							required:      false
+							common:        false
+							relevant_when: "codec = `json`"
+							type: object: options: {
+								engine: {
+									description: "The engine used to parse JSON."
+									required:    false
+									common:      false
+									type: string: {
+										default: "serde"
+										enum: {
+											serde: "Parse JSON with [serde_json](\(urls.serde_json))."
+											simd:  "Parse JSON with [simd-json](\(urls.simd_json)), which uses SIMD instructions to parse large inputs significantly faster. SIMD instructions are only used when Vector is built for a CPU supporting AVX2 or SSE 4.2 on x86, or NEON on ARM. Inputs it can't parse, such as those containing invalid UTF-8, are parsed again with serde_json."
+										}
+									}
+								}
+							}
+						}
 					}
 				}
 			}
@@ -310,6 +330,24 @@ components: sources: [Name=string]: {
 									examples: [ "CN=localhost,OU=Vector,O=Datadog,L=New York,ST=New York,C=US"]
 								}
 							}
+							common_name: {
+								common:      true
+								description: "The common name (CN) from the subject of the client TLS certificate, if any. Only added if `tls.client_metadata_key` is set."
+								required:    false
+								type: string: {
+									default: null
+									examples: ["localhost"]
+								}
+							}
+							subject_alt_names: {
+								common:      false
+								description: "The DNS names, email addresses, URIs and IP addresses of the subject alternative names (SAN) of the client TLS certificate. Only added if `tls.client_metadata_key` is set."
+								required:    false
+								type: array: {
+									default: []
+									items: type: string: examples: ["client.example.com", "10.0.0.1"]
+								}
+							}
 						}
 					}
 				}
@@ -337,6 +375,11 @@ components: sources: [Name=string]: {
 						checkpoint positions are stored in the data directory which is
 						specified via the global `data_dir` option, but can be overridden
 						via the `data_dir` option in the file source directly.
+
+						Sources with a `state_store` option can store their checkpoints
+						remotely instead, in Redis, S3 or DynamoDB, so that stateless
+						containers and autoscaled aggregators resume where a previous
+						instance left off.
 						"""
 				}
 			}
@@ -367,6 +410,7 @@ components: sources: [Name=string]: {
 
 	telemetry: metrics: {
 		events_out_total:                 components.sources.internal_metrics.output.metrics.events_out_total
+		component_in_flight_event_bytes:  components.sources.internal_metrics.output.metrics.component_in_flight_event_bytes
 		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
 		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
 		source_lag_time_seconds:          components.sources.internal_metrics.output.metrics.source_lag_time_seconds
@@ -0,0 +1,235 @@
+package metadata
+
+components: sources: aws_cloudwatch_logs: components._aws & {
+	title: "AWS CloudWatch Logs"
+
+	features: {
+		acknowledgements: true
+		collect: {
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+				enabled_by_scheme:      true
+			}
+			checkpoint: enabled: true
+			proxy: enabled:      true
+			from: service:       services.aws_cloudwatch_logs
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		state_store: configuration._state_store
+		acknowledgements: configuration._source_acknowledgements
+		log_group_names: {
+			common:      true
+			description: "The names of the log groups to read from. At least one of `log_group_names` or `log_group_prefix` must be set."
+			required:    false
+			type: array: {
+				default: []
+				items: type: string: {
+					examples: ["/aws/lambda/my-function"]
+					syntax: "literal"
+				}
+			}
+		}
+		log_group_prefix: {
+			common:      true
+			description: "The prefix of the names of the log groups to read from. The log groups whose name starts with this prefix are listed with `DescribeLogGroups` every `group_discovery_interval_secs`, and read from along with the log groups of `log_group_names`."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["/ecs/"]
+				syntax: "literal"
+			}
+		}
+		log_stream_prefix: {
+			common:      false
+			description: "The prefix of the names of the log streams to read from, in each log group. By default, all of the log streams of the log groups are read from."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["web/"]
+				syntax: "literal"
+			}
+		}
+		filter_pattern: {
+			common:      false
+			description: "The [filter pattern](\(urls.aws_cloudwatch_logs_filter_pattern)) the log events must match. By default, all of the log events are read."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["ERROR", "{ $.level = \"error\" }"]
+				syntax: "literal"
+			}
+		}
+		start_lookback_secs: {
+			common:      false
+			description: "How far back to start reading a log group that has no checkpoint. By default, only the log events written after the log group is first read from are read."
+			required:    false
+			type: uint: {
+				default: 0
+				unit:    "seconds"
+			}
+		}
+		poll_interval_secs: {
+			common:      false
+			description: "The interval between reads of a log group that has no new log events."
+			required:    false
+			type: uint: {
+				default: 10
+				unit:    "seconds"
+			}
+		}
+		group_discovery_interval_secs: {
+			common:      false
+			description: "The interval between listings of the log groups matching `log_group_prefix`."
+			required:    false
+			type: uint: {
+				default: 300
+				unit:    "seconds"
+			}
+		}
+	}
+
+	output: logs: event: {
+		description: "An individual log event"
+		fields: {
+			id: {
+				description: "The ID of the log event."
+				required:    true
+				type: string: {
+					examples: ["35683658089614582423604394983260738922885519999578275840"]
+					syntax: "literal"
+				}
+			}
+			log_group: {
+				description: "The name of the log group the log event was read from."
+				required:    true
+				type: string: {
+					examples: ["/aws/lambda/my-function"]
+					syntax: "literal"
+				}
+			}
+			log_stream: {
+				description: "The name of the log stream the log event was written to."
+				required:    true
+				type: string: {
+					examples: ["2022/10/01/[$LATEST]8c2a1b0e6d8e4f5a9b7c3d2e1f0a9b8c"]
+					syntax: "literal"
+				}
+			}
+			message: {
+				description: "The message of the log event."
+				required:    true
+				type: string: {
+					examples: ["START RequestId: 8c2a1b0e-6d8e-4f5a-9b7c-3d2e1f0a9b8c Version: $LATEST"]
+					syntax: "literal"
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["aws_cloudwatch_logs"]
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The timestamp of the log event."
+			}
+		}
+	}
+
+	telemetry: metrics: {
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+	}
+
+	permissions: iam: [
+		{
+			platform: "aws"
+			_service: "AmazonCloudWatchLogs"
+
+			policies: [
+				{
+					_action:       "DescribeLogGroups"
+					required_when: "[`log_group_prefix`](#log_group_prefix) is set"
+				},
+				{
+					_action: "FilterLogEvents"
+				},
+			]
+		},
+	]
+
+	how_it_works: {
+		reading: {
+			title: "Reading log groups"
+			body: """
+				Each log group is read with `FilterLogEvents` requests, from the timestamp of the
+				latest log event read, every `poll_interval_secs`. Log events are only filtered by
+				timestamp, so log events ingested with a timestamp older than the latest log event
+				read are not read. Log groups which are deleted stop being read, and are read again
+				if they are recreated and listed.
+
+				Live tailing with `StartLiveTail` is not supported, as it is not available in the
+				version of the AWS SDK Vector is built with.
+				"""
+		}
+		checkpointing: {
+			title: "Checkpointing"
+			body: """
+				The timestamp of the latest log event read from each log group is checkpointed, along
+				with the IDs of the log events read at that timestamp, either once the events are sent,
+				or once they are acknowledged by the sinks when `acknowledgements` are enabled. Vector
+				resumes reading each log group from its checkpoint when restarted, skipping the log
+				events it has already read.
+				"""
+		}
+		subscription_filters: {
+			title: "Subscription filters"
+			body: """
+				Log events delivered by [subscription filters](\(urls.aws_cloudwatch_logs_subscriptions))
+				to Kinesis Data Streams or Kinesis Data Firehose can be unwrapped by the
+				`aws_kinesis_streams` and `aws_kinesis_firehose` sources with their
+				`unwrap_cloudwatch_logs` option, instead of being read with this source.
+				"""
+		}
+	}
+}
@@ -111,6 +111,19 @@ components: sources: aws_kinesis_firehose: {
 				}
 			}
 		}
+		unwrap_cloudwatch_logs: {
+			common:      false
+			description: """
+				Whether or not to unwrap the messages of [AWS CloudWatch Logs subscription
+				filters](\(urls.aws_cloudwatch_logs_subscriptions)). When enabled, each record holding a gzipped
+				subscription filter message is unwrapped into one event per log event, decoded from the log event's
+				message and timestamped with the log event's timestamp. The `id`, `log_group`, `log_stream`, `owner`
+				and `subscription_filters` fields are added to each event, and control messages are dropped. Records
+				holding anything else are decoded as they are.
+				"""
+			required:    false
+			type: bool: default: false
+		}
 	}
 
 	output: logs: {
@@ -192,7 +205,8 @@ components: sources: aws_kinesis_firehose: {
 				set this up:
 
 				1. Deploy vector with a publicly exposed HTTP endpoint using
-				   this source. You will likely also want to use the
+				   this source. You will likely also want to enable the
+				   `unwrap_cloudwatch_logs` option, or use the
 				   [`parse_aws_cloudwatch_log_subscription_message`](\(urls.vrl_functions)/#parse_aws_cloudwatch_log_subscription_message)
 				   function to extract the log events. Make sure to set
 				   the `access_key` to secure this endpoint. Your
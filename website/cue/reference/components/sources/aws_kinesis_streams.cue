@@ -0,0 +1,348 @@
+package metadata
+
+components: sources: aws_kinesis_streams: components._aws & {
+	title: "AWS Kinesis Data Streams"
+
+	features: {
+		acknowledgements: true
+		collect: {
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+				enabled_by_scheme:      true
+			}
+			checkpoint: enabled: true
+			proxy: enabled:      true
+			from: service:       services.aws_kinesis_data_streams
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		acknowledgements: configuration._source_acknowledgements
+		stream_name: {
+			description: "The name of the stream to consume."
+			required:    true
+			type: string: {
+				examples: ["my-stream"]
+				syntax: "literal"
+			}
+		}
+		consumer: {
+			common:      false
+			description: "How records are read from the shards of the stream."
+			required:    false
+			type: object: options: {
+				mode: {
+					common:      true
+					description: "The consumer mode."
+					required:    false
+					type: string: {
+						default: "polling"
+						enum: {
+							polling:          "Poll each shard with `GetRecords` requests. The read throughput of each shard, 2 MB per second, is shared by all of the consumers polling the stream."
+							enhanced_fan_out: "Subscribe to each shard with enhanced fan-out, getting a dedicated read throughput of 2 MB per second for each shard. The consumer is registered on the stream if it doesn't already exist."
+						}
+						syntax: "literal"
+					}
+				}
+				consumer_name: {
+					description:   "The name of the enhanced fan-out consumer. Each Vector instance consuming the same stream with enhanced fan-out should use a different consumer name."
+					relevant_when: "mode = \"enhanced_fan_out\""
+					required:      true
+					type: string: {
+						examples: ["vector"]
+						syntax: "literal"
+					}
+				}
+			}
+		}
+		starting_position: {
+			common:      true
+			description: "The position in a shard to start reading from, when it has no checkpoint."
+			required:    false
+			type: string: {
+				default: "latest"
+				enum: {
+					latest:       "Start reading the records written after the shard is first read."
+					trim_horizon: "Start reading from the oldest record of the shard."
+				}
+				syntax: "literal"
+			}
+		}
+		max_records: {
+			common:      false
+			description: "The maximum number of records returned by each `GetRecords` request, when polling."
+			required:    false
+			type: uint: {
+				default: 10000
+				unit:    null
+			}
+		}
+		poll_interval_secs: {
+			common:      false
+			description: "The interval between `GetRecords` requests to a shard that has no new records, when polling."
+			required:    false
+			type: uint: {
+				default: 1
+				unit:    "seconds"
+			}
+		}
+		shard_discovery_interval_secs: {
+			common:      false
+			description: "The interval between listings of the shards of the stream. New shards, such as the child shards created by a resharding, are only consumed once they have been listed."
+			required:    false
+			type: uint: {
+				default: 60
+				unit:    "seconds"
+			}
+		}
+		unwrap_cloudwatch_logs: {
+			common:      false
+			description: """
+				Whether or not to unwrap the messages of [AWS CloudWatch Logs subscription
+				filters](\(urls.aws_cloudwatch_logs_subscriptions)). When enabled, each record holding a gzipped
+				subscription filter message is unwrapped into one event per log event, decoded from the log event's
+				message and timestamped with the log event's timestamp. The `id`, `log_group`, `log_stream`, `owner`
+				and `subscription_filters` fields are added to each event, and control messages are dropped. Records
+				holding anything else are decoded as they are.
+				"""
+			required:    false
+			type: bool: default: false
+		}
+		checkpoint: {
+			common:      false
+			description: "Where the position of each shard is checkpointed."
+			required:    false
+			type: object: options: {
+				type: {
+					common:      true
+					description: "The checkpoint store."
+					required:    false
+					type: string: {
+						default: "file"
+						enum: {
+							file:     "Checkpoint to a file in the data directory."
+							dynamodb: "Checkpoint to a DynamoDB table, which must have a string partition key named `lease_key`."
+						}
+						syntax: "literal"
+					}
+				}
+				table_name: {
+					description:   "The name of the DynamoDB table."
+					relevant_when: "type = \"dynamodb\""
+					required:      true
+					type: string: {
+						examples: ["vector-checkpoints"]
+						syntax: "literal"
+					}
+				}
+				key_prefix: {
+					common:        false
+					description:   "The prefix of the keys the checkpoints are stored under. Each checkpoint is stored under the key `<key_prefix>/<shard_id>`. By default, the name of the stream is used."
+					relevant_when: "type = \"dynamodb\""
+					required:      false
+					type: string: {
+						default: null
+						examples: ["my-stream"]
+						syntax: "literal"
+					}
+				}
+				endpoint: {
+					common:        false
+					description:   "Custom endpoint for use with DynamoDB-compatible services."
+					relevant_when: "type = \"dynamodb\""
+					required:      false
+					type: string: {
+						default: null
+						examples: ["http://127.0.0.0:8000"]
+						syntax: "literal"
+					}
+				}
+			}
+		}
+	}
+
+	output: logs: record: {
+		description: "An individual Kinesis record"
+		fields: {
+			message: {
+				description: "The data of the Kinesis record."
+				required:    true
+				type: string: {
+					examples: ["53.126.150.246 - - [01/Oct/2020:11:25:58 -0400] \"GET /disintermediate HTTP/2.0\" 401 20308"]
+					syntax: "literal"
+				}
+			}
+			partition_key: {
+				description: "The partition key of the Kinesis record."
+				required:    true
+				type: string: {
+					examples: ["user-1234"]
+					syntax: "literal"
+				}
+			}
+			sequence_number: {
+				description: "The sequence number of the Kinesis record in its shard."
+				required:    true
+				type: string: {
+					examples: ["49590338271490256608559692538361571095921575989136588898"]
+					syntax: "literal"
+				}
+			}
+			shard_id: {
+				description: "The ID of the shard the Kinesis record was read from."
+				required:    true
+				type: string: {
+					examples: ["shardId-000000000000"]
+					syntax: "literal"
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["aws_kinesis_streams"]
+				}
+			}
+			stream: {
+				description: "The name of the stream the Kinesis record was read from."
+				required:    true
+				type: string: {
+					examples: ["my-stream"]
+					syntax: "literal"
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The approximate time the Kinesis record was written to the stream."
+			}
+		}
+	}
+
+	telemetry: metrics: {
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+	}
+
+	permissions: iam: [
+		{
+			platform: "aws"
+			_service: "kinesis"
+
+			policies: [
+				{
+					_action: "ListShards"
+				},
+				{
+					_action:       "GetShardIterator"
+					required_when: "[`consumer.mode`](#consumer.mode) is set to `polling`"
+				},
+				{
+					_action:       "GetRecords"
+					required_when: "[`consumer.mode`](#consumer.mode) is set to `polling`"
+				},
+				{
+					_action:       "DescribeStreamSummary"
+					required_when: "[`consumer.mode`](#consumer.mode) is set to `enhanced_fan_out`"
+				},
+				{
+					_action:       "RegisterStreamConsumer"
+					required_when: "[`consumer.mode`](#consumer.mode) is set to `enhanced_fan_out`"
+				},
+				{
+					_action:       "DescribeStreamConsumer"
+					required_when: "[`consumer.mode`](#consumer.mode) is set to `enhanced_fan_out`"
+				},
+				{
+					_action:       "SubscribeToShard"
+					required_when: "[`consumer.mode`](#consumer.mode) is set to `enhanced_fan_out`"
+				},
+			]
+		},
+		{
+			platform: "aws"
+			_service: "dynamodb"
+
+			policies: [
+				{
+					_action:       "GetItem"
+					required_when: "[`checkpoint.type`](#checkpoint.type) is set to `dynamodb`"
+				},
+				{
+					_action:       "PutItem"
+					required_when: "[`checkpoint.type`](#checkpoint.type) is set to `dynamodb`"
+				},
+			]
+		},
+	]
+
+	how_it_works: {
+		consumers: {
+			title: "Polling and enhanced fan-out"
+			body: """
+				By default, each shard of the stream is polled with `GetRecords` requests. The read
+				throughput of each shard, 2 MB per second and five requests per second, is then shared
+				by all of the consumers polling the stream. With `consumer.mode` set to
+				`enhanced_fan_out`, Vector registers an enhanced fan-out consumer on the stream, and
+				subscribes to each shard over HTTP/2, getting records pushed as soon as they are
+				written, with a read throughput of 2 MB per second dedicated to the consumer.
+				"""
+		}
+		resharding: {
+			title: "Resharding"
+			body: """
+				The shards of the stream are listed every `shard_discovery_interval_secs`, and right
+				away once all of the records of a shard have been read. When the stream is resharded,
+				the child shards are only consumed once all of the records of their parent shards have
+				been read, so that the records sharing a partition key are read in order.
+				"""
+		}
+		checkpointing: {
+			title: "Checkpointing"
+			body: """
+				The sequence number of the last record read from each shard is checkpointed, either
+				once the events of the records are sent, or once they are acknowledged by the sinks
+				when `acknowledgements` are enabled. Vector resumes reading each shard after its
+				checkpoint when restarted. Checkpoints are stored in a file of the data directory by
+				default, or in a DynamoDB table with `checkpoint.type` set to `dynamodb`, which lets
+				several Vector instances share the checkpoints of the stream.
+				"""
+		}
+	}
+}
@@ -33,7 +33,8 @@ components: sources: aws_s3: components._aws & {
 		requirements: [
 			"""
 				The AWS S3 source requires a SQS queue configured to receive S3
-				bucket notifications for the desired S3 buckets.
+				bucket notifications for the desired S3 buckets, unless the
+				`backfill` strategy is used.
 				""",
 		]
 		warnings: []
@@ -53,7 +54,8 @@ components: sources: aws_s3: components._aws & {
 			type: string: {
 				default: "sqs"
 				enum: {
-					sqs: "Consume S3 objects by polling for bucket notifications sent to an [AWS SQS queue](\(urls.aws_sqs))."
+					sqs:      "Consume S3 objects by polling for bucket notifications sent to an [AWS SQS queue](\(urls.aws_sqs))."
+					backfill: "Read the objects already in a bucket once, by prefix and time of last modification, to re-ingest them after an incident. Can be run to completion with `vector --once`."
 				}
 			}
 		}
@@ -71,6 +73,63 @@ components: sources: aws_s3: components._aws & {
 				}
 			}
 		}
+		backfill: {
+			common:      false
+			description: "Backfill strategy options. Required if strategy=`backfill`."
+			required:    false
+			type: object: {
+				examples: []
+				options: {
+					bucket: {
+						description: "The name of the bucket to read the objects of."
+						required:    true
+						type: string: {
+							examples: ["my-bucket"]
+						}
+					}
+					prefix: {
+						common:      true
+						description: "The prefix of the keys of the objects to read. By default, all the objects of the bucket are read."
+						required:    false
+						type: string: {
+							default:  null
+							examples: ["AWSLogs/111111111111/vpcflowlogs/us-east-1/2022/10/"]
+						}
+					}
+					start_time: {
+						common:      true
+						description: "Only the objects last modified at, or after, this time are read."
+						required:    false
+						type: timestamp: default: null
+					}
+					end_time: {
+						common:      true
+						description: "Only the objects last modified before this time are read."
+						required:    false
+						type: timestamp: default: null
+					}
+					concurrency: {
+						common:      false
+						description: "The number of objects read at once. Defaults to the number of available CPUs on the system."
+						required:    false
+						type: uint: {
+							default: null
+							unit:    null
+						}
+					}
+					data_dir: {
+						common:      false
+						description: "The directory used to persist the progress of the backfill, when `state_store` is `file`. By default, the global `data_dir` option is used. Please make sure the user Vector is running as has write permissions to this directory."
+						required:    false
+						type: string: {
+							default:  null
+							examples: ["/var/lib/vector"]
+						}
+					}
+					state_store: configuration._state_store
+				}
+			}
+		}
 		sqs: {
 			common:      true
 			description: "SQS strategy options. Required if strategy=`sqs`."
@@ -208,6 +267,10 @@ components: sources: aws_s3: components._aws & {
 				{
 					_action: "GetObject"
 				},
+				{
+					_action:       "ListBucket"
+					required_when: "[`strategy`](#strategy) is set to `backfill`"
+				},
 			]
 		},
 		{
@@ -236,6 +299,7 @@ components: sources: aws_s3: components._aws & {
 		component_received_bytes_total:         components.sources.internal_metrics.output.metrics.component_received_bytes_total
 		component_received_events_total:        components.sources.internal_metrics.output.metrics.component_received_events_total
 		component_received_event_bytes_total:   components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		s3_backfill_objects_read_total:         components.sources.internal_metrics.output.metrics.s3_backfill_objects_read_total
 		sqs_message_delete_failed_total:        components.sources.internal_metrics.output.metrics.sqs_message_delete_failed_total
 		sqs_message_delete_succeeded_total:     components.sources.internal_metrics.output.metrics.sqs_message_delete_succeeded_total
 		sqs_message_processing_failed_total:    components.sources.internal_metrics.output.metrics.sqs_message_processing_failed_total
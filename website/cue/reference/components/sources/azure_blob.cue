@@ -0,0 +1,236 @@
+package metadata
+
+components: sources: azure_blob: {
+	title: "Azure Blob Storage"
+
+	features: {
+		acknowledgements: true
+		multiline: enabled: true
+		collect: {
+			tls: enabled:        false
+			checkpoint: enabled: false
+			proxy: enabled:      false
+			from: service:       services.azure_blob
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: {
+		requirements: [
+			"""
+				The Azure Blob Storage source requires an Azure Storage queue
+				receiving the `Microsoft.Storage.BlobCreated` events of the
+				desired storage account from an Event Grid subscription.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		acknowledgements: configuration._source_acknowledgements
+		connection_string: {
+			description: "The Azure Blob Storage Account connection string. Only authentication with access key supported. This or storage_account has to be provided."
+			required:    false
+			common:      true
+			type: string: {
+				default: ""
+				examples: ["DefaultEndpointsProtocol=https;AccountName=mylogstorage;AccountKey=storageaccountkeybase64encoded;EndpointSuffix=core.windows.net"]
+			}
+		}
+		storage_account: {
+			description: "The Azure Blob Storage Account name. Credentials are loaded according to `auth`. This or connection_string has to be provided."
+			required:    false
+			common:      true
+			type: string: {
+				default: ""
+				examples: ["mylogstorage"]
+			}
+		}
+		auth: components.sinks.azure_blob.configuration.auth
+		strategy: {
+			common:      false
+			description: "The strategy to use to consume blobs from Azure Blob Storage."
+			required:    false
+			type: string: {
+				default: "storage_queue"
+				enum: {
+					storage_queue: "Consume blobs by polling for the `BlobCreated` events sent by [Event Grid](\(urls.azure_event_grid_blob_events)) to an [Azure Storage queue](\(urls.azure_storage_queues))."
+				}
+			}
+		}
+		compression: {
+			common:      false
+			description: "The compression format of the blobs."
+			required:    false
+			type: string: {
+				default: "auto"
+				enum: {
+					auto: "Vector will try to determine the compression format of the blob from its: `Content-Encoding` property, `Content-Type` property, and name suffix (e.g. `.gz`). It will fallback to 'none' if it cannot determine the compression."
+					gzip: "GZIP format."
+					zstd: "ZSTD format."
+					none: "Uncompressed."
+				}
+			}
+		}
+		queue: {
+			common:      true
+			description: "Storage queue strategy options. Required if strategy=`storage_queue`."
+			required:    false
+			type: object: {
+				examples: []
+				options: {
+					queue_name: {
+						description: "The name of the Azure Storage queue to receive the Event Grid events from. The queue must be in the storage account of the blobs."
+						required:    true
+						type: string: {
+							examples: ["blob-events"]
+						}
+					}
+					poll_secs: {
+						common:      true
+						description: "How long to wait before polling the queue again when it is empty."
+						required:    false
+						type: uint: {
+							default: 15
+							unit:    "seconds"
+						}
+					}
+					visibility_timeout_secs: {
+						common:      false
+						description: "The visibility timeout to use for messages in seconds. This controls how long a message is left unavailable when a Vector receives it. If a `vector` does not delete the message before the timeout expires, it will be made reavailable for another consumer; this can happen if, for example, the `vector` process crashes."
+						required:    false
+						warnings: ["Should be set higher than the length of time it takes to process an individual message to avoid that message being reprocessed."]
+						type: uint: {
+							default: 300
+							unit:    "seconds"
+						}
+					}
+					delete_message: {
+						common:      true
+						description: "Whether to delete the message once Vector processes it. It can be useful to set this to `false` to debug or during initial Vector setup."
+						required:    false
+						type: bool: default: true
+					}
+					client_concurrency: {
+						common:      false
+						description: "Number of concurrent tasks to create for polling the queue for messages. Defaults to the number of available CPUs on the system."
+						required:    false
+						type: uint: {
+							default: null
+							examples: [1, 4]
+							unit: null
+						}
+					}
+				}
+			}
+		}
+	}
+
+	output: logs: object: {
+		description: "A line from a blob."
+		fields: {
+			message: {
+				description: "A line from the blob."
+				required:    true
+				type: string: {
+					examples: ["53.126.150.246 - - [01/Oct/2020:11:25:58 -0400] \"GET /disintermediate HTTP/2.0\" 401 20308"]
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The time of the `BlobCreated` event. Defaults to the Last-Modified time of the blob if this information is missing."
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["azure_blob"]
+				}
+			}
+			container: {
+				description: "The container of the blob the line came from."
+				required:    true
+				type: string: {
+					examples: ["logs"]
+				}
+			}
+			blob: {
+				description: "The blob the line came from."
+				required:    true
+				type: string: {
+					examples: ["app/2022-07-18/out.log.gz"]
+				}
+			}
+			storage_account: {
+				description: "The storage account of the blob."
+				required:    true
+				type: string: {
+					examples: ["mylogstorage"]
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		event_grid: {
+			title: "Receiving blob notifications"
+			body:  """
+				Blobs are read as they are created, from the `Microsoft.Storage.BlobCreated`
+				[events](\(urls.azure_event_grid_blob_events)) that an Event Grid subscription
+				of the storage account delivers to an Azure Storage queue. The events of
+				other types are ignored, so the subscription can be filtered to the
+				`BlobCreated` event type and to the desired containers with a subject filter,
+				such as `/blobServices/default/containers/logs/`.
+
+				The queue must be in the same storage account as the blobs, and the
+				credentials Vector uses must be allowed to read its messages, to delete them
+				and to read the blobs, for example with the `Storage Queue Data Message
+				Processor` and `Storage Blob Data Reader` roles.
+				"""
+		}
+		checkpointing: {
+			title: "Checkpointing"
+			body:  """
+				The queue is the checkpoint of the source: a message is only deleted once
+				all of the lines of its blobs are read, and once they are delivered by the
+				sinks when acknowledgements are enabled. If Vector stops before then, the
+				message becomes visible again after `visibility_timeout_secs` and its blobs
+				are read again, so that no blob is lost but some of its lines may be
+				delivered twice.
+				"""
+		}
+		events: {
+			title: "Handling events from the `azure_blob` source"
+			body:  """
+				This source behaves very similarly to the `file` source in that
+				it will output one event per line (unless the `multiline`
+				configuration option is used).
+
+				Compressed blobs are decompressed as they are read, according to the
+				`compression` option.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_discarded_events_total:            components.sources.internal_metrics.output.metrics.component_discarded_events_total
+		component_errors_total:                      components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:              components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:             components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total:        components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		azure_queue_event_ignored_total:             components.sources.internal_metrics.output.metrics.azure_queue_event_ignored_total
+		azure_queue_message_received_messages_total: components.sources.internal_metrics.output.metrics.azure_queue_message_received_messages_total
+	}
+}
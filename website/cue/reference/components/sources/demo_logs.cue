@@ -44,12 +44,16 @@ components: sources: demo_logs: {
 			required:    true
 			type: string: {
 				enum: {
-					"shuffle":       "Lines are chosen at random from the list specified using `lines`."
-					"apache_common": "Randomly generated logs in [Apache common](\(urls.apache_common)) format."
-					"apache_error":  "Randomly generated logs in [Apache error](\(urls.apache_error)) format."
-					"syslog":        "Randomly generated logs in Syslog format ([RFC 5424](\(urls.syslog_5424)))."
-					"bsd_syslog":    "Randomly generated logs in Syslog format ([RFC 3164](\(urls.syslog_3164)))."
-					"json":          "Randomly generated HTTP server logs in [JSON](\(urls.json)) format."
+					"shuffle":          "Lines are chosen at random from the list specified using `lines`."
+					"apache_common":    "Randomly generated logs in [Apache common](\(urls.apache_common)) format."
+					"apache_error":     "Randomly generated logs in [Apache error](\(urls.apache_error)) format."
+					"syslog":           "Randomly generated logs in Syslog format ([RFC 5424](\(urls.syslog_5424)))."
+					"bsd_syslog":       "Randomly generated logs in Syslog format ([RFC 3164](\(urls.syslog_3164)))."
+					"json":             "Randomly generated HTTP server logs in [JSON](\(urls.json)) format."
+					"kubernetes_audit": "Randomly generated [Kubernetes audit](\(urls.kubernetes_auditing)) events, in JSON format."
+					"nginx_ingress":    "Randomly generated access logs of the [NGINX Ingress Controller](\(urls.nginx_ingress_log_format)), in its default format."
+					"aws_alb":          "Randomly generated [AWS Application Load Balancer](\(urls.aws_elb_access_format)) access logs."
+					"windows_event":    "Randomly generated Windows event log events, in JSON format."
 				}
 			}
 		}
@@ -66,6 +70,36 @@ components: sources: demo_logs: {
 				examples: [1.0, 0.1, 0.01]
 			}
 		}
+		rate: {
+			common: false
+			description: """
+				The rate of the output lines, in lines per second, which takes precedence over
+				`interval`. This is either a constant rate, such as `"1000"`, or a rate ramping up, or
+				down, linearly over a duration before it stays at its final value, such as
+				`"linear:100..10000/60s"`, which is useful to test how a pipeline copes with
+				backpressure. The duration is in milliseconds (`ms`), seconds (`s`), minutes (`m`) or
+				hours (`h`).
+				"""
+			required: false
+			type: string: {
+				default: null
+				examples: ["1000", "linear:100..10000/60s"]
+			}
+		}
+		seed: {
+			common: false
+			description: """
+				The seed of the random generation of the output lines. With a seed, the source
+				outputs the same lines each time it runs, but for their timestamps, which are those
+				of the current time. By default, the lines differ from one run to the next.
+				"""
+			required: false
+			type: uint: {
+				default: null
+				examples: [42]
+				unit: null
+			}
+		}
 		count: {
 			common:      false
 			description: "The total number of lines to output. By default the source continuously prints logs (infinitely)."
@@ -0,0 +1,243 @@
+package metadata
+
+components: sources: ebpf: {
+	title: "eBPF"
+
+	description: """
+		Captures process and TCP connection events from the Linux kernel with BPF programs, as a
+		lightweight security signal of what runs on a host and what it connects to.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["daemon"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		collect: {
+			checkpoint: enabled: false
+			from: service:       services.ebpf
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  false
+			"armv7-unknown-linux-musleabihf": false
+			"x86_64-apple-darwin":            false
+			"x86_64-pc-windows-msv":          false
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+
+		requirements: [
+			"""
+				The kernel must be built with BTF (`CONFIG_DEBUG_INFO_BTF`), which most distributions
+				enable, so that the BPF programs are relocated to its layout.
+				""",
+			"""
+				Vector must run as root, or with the `CAP_BPF` and `CAP_PERFMON` capabilities.
+				""",
+			"""
+				This source is not part of the default build. It is enabled with the `sources-ebpf`
+				feature, which requires `clang` and the `libbpf` headers to compile its BPF programs.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		events: {
+			common:      true
+			description: "The kinds of events to capture."
+			required:    false
+			type: array: {
+				default: ["process_exec", "process_exit", "tcp_connect", "tcp_accept"]
+				items: type: string: {
+					enum: {
+						process_exec: "A process executed a program."
+						process_exit: "A process exited."
+						tcp_connect:  "A TCP connection initiated by this host was established."
+						tcp_accept:   "A TCP connection initiated by a peer was established."
+					}
+				}
+			}
+		}
+		buffer_pages: {
+			common:      false
+			description: "The number of memory pages of the buffer that each CPU sends events through. Events are lost when a buffer fills up faster than it is read, so this may need to be increased on busy hosts. Must be a power of two."
+			required:    false
+			type: uint: {
+				default: 64
+				unit:    null
+			}
+		}
+	}
+
+	output: logs: event: {
+		description: "A process or TCP connection event."
+		fields: {
+			event_type: {
+				description: "The kind of event."
+				required:    true
+				type: string: {
+					enum: {
+						process_exec: "A process executed a program."
+						process_exit: "A process exited."
+						tcp_connect:  "A TCP connection initiated by this host was established."
+						tcp_accept:   "A TCP connection initiated by a peer was established."
+					}
+				}
+			}
+			host: fields._local_host
+			process: {
+				description: "The process of the event. Absent for `tcp_accept` events, as connections are accepted by the kernel."
+				required:    false
+				type: object: options: {
+					pid: {
+						description: "The process ID."
+						required:    true
+						type: uint: {
+							examples: [4242]
+							unit: null
+						}
+					}
+					ppid: {
+						description: "The process ID of the parent."
+						required:    true
+						type: uint: {
+							examples: [1]
+							unit: null
+						}
+					}
+					uid: {
+						description: "The user ID of the process."
+						required:    true
+						type: uint: {
+							examples: [1000]
+							unit: null
+						}
+					}
+					gid: {
+						description: "The group ID of the process."
+						required:    true
+						type: uint: {
+							examples: [1000]
+							unit: null
+						}
+					}
+					comm: {
+						description: "The command name of the process, truncated to 15 characters."
+						required:    true
+						type: string: {
+							examples: ["curl"]
+						}
+					}
+					filename: {
+						description: "The program executed, for `process_exec` events."
+						required:    false
+						type: string: {
+							examples: ["/usr/bin/curl"]
+						}
+					}
+					exit_code: {
+						description: "The exit status, for `process_exit` events of processes that exited normally."
+						required:    false
+						type: uint: {
+							examples: [0]
+							unit: null
+						}
+					}
+					signal: {
+						description: "The signal that terminated the process, for `process_exit` events."
+						required:    false
+						type: uint: {
+							examples: [9]
+							unit: null
+						}
+					}
+				}
+			}
+			connection: {
+				description: "The connection of `tcp_connect` and `tcp_accept` events."
+				required:    false
+				type: object: options: {
+					src_addr: {
+						description: "The local IP address."
+						required:    true
+						type: string: {
+							examples: ["10.0.0.1"]
+						}
+					}
+					src_port: {
+						description: "The local port."
+						required:    true
+						type: uint: {
+							examples: [51234]
+							unit: null
+						}
+					}
+					dst_addr: {
+						description: "The remote IP address."
+						required:    true
+						type: string: {
+							examples: ["93.184.216.34"]
+						}
+					}
+					dst_port: {
+						description: "The remote port."
+						required:    true
+						type: uint: {
+							examples: [443]
+							unit: null
+						}
+					}
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["ebpf"]
+				}
+			}
+			timestamp: fields._current_timestamp
+		}
+	}
+
+	how_it_works: {
+		programs: {
+			title: "BPF programs"
+			body: """
+				The BPF programs are compiled into Vector, and attached to the `sched_process_exec`,
+				`sched_process_exit` and `inet_sock_set_state` tracepoints for the kinds of events
+				that are captured. They are relocated to the layout of the running kernel with
+				[CO-RE](\(urls.ebpf)), so the same build of Vector runs on any kernel with BTF.
+
+				Connections are attributed to the process that initiated them, and events are
+				counted in `component_discarded_events_total` when they are lost because a buffer
+				filled up.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_discarded_events_total:     components.sources.internal_metrics.output.metrics.component_discarded_events_total
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
@@ -49,6 +49,7 @@ components: sources: file: {
 
 	configuration: {
 		acknowledgements: configuration._source_acknowledgements
+		state_store: configuration._state_store
 		exclude: {
 			common:      false
 			description: "Array of file patterns to exclude. [Globbing](#globbing) is supported.*Takes precedence over the [`include` option](#include).*"
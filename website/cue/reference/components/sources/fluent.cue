@@ -69,6 +69,47 @@ components: sources: fluent: {
 				unit:    "concurrency"
 			}
 		}
+		security: {
+			common:      false
+			description: "Authentication of the clients. When set, the clients must authenticate with the shared key, and optionally a username and password, with the handshake of the forward protocol before sending any messages."
+			required:    false
+			type: object: options: {
+				self_hostname: {
+					description: "The hostname the source identifies itself with to the clients. Defaults to the hostname of the machine."
+					required:    false
+					type: string: {
+						default: null
+						examples: ["vector.example.com"]
+					}
+				}
+				shared_key: {
+					description: "The key shared with the clients."
+					required:    true
+					type: string: {
+						examples: ["${FLUENT_SHARED_KEY}"]
+					}
+				}
+				users: {
+					description: "The users the clients authenticate as. When set, the clients must also authenticate with the username and password of one of them."
+					required:    false
+					type: array: {
+						default: []
+						items: type: object: options: {
+							username: {
+								description: "The username of the user."
+								required:    true
+								type: string: examples: ["fluentd"]
+							}
+							password: {
+								description: "The password of the user."
+								required:    true
+								type: string: examples: ["${FLUENT_PASSWORD}"]
+							}
+						}
+					}
+				}
+			}
+		}
 	}
 
 	output: logs: line: {
@@ -181,15 +222,26 @@ components: sources: fluent: {
 		secure_mode: {
 			title: "Secure forward mode support"
 			body:  """
-				The `fluent` source currently supports using TLS, but does not support the authentication part of the
-				Fluent protocol including:
+				The `fluent` source supports the authentication part of the Fluent protocol, configured with the
+				`security` option:
 
-				- Shared key
-				- Username and password
+				- Shared key, which the clients and Vector must both be configured with
+				- Username and password, when `security.users` is set
 
-				And so these options of the secure forward output plugins for Fluent and Fluent Bit cannot be used.
+				Each client then authenticates with a handshake when connecting, before sending any messages, and the
+				connections of the clients that fail to authenticate are closed. This is usually combined with TLS, so
+				that the messages are encrypted as well.
+				"""
+		}
 
-				If you would find this useful, [please let us know](\(urls.vector_repo)/issues/7532).
+		chunk_acknowledgements: {
+			title: "Chunk acknowledgements"
+			body:  """
+				The clients can ask for their messages to be acknowledged, by setting the `chunk` option of the
+				messages, as Fluentd does with `require_ack_response`. Vector responds with the acknowledgement of a
+				chunk once its events are accepted, or, when `acknowledgements` are enabled, once they are delivered
+				by the sinks. Nothing is sent back for the chunks whose events failed to be delivered, and the
+				connection is closed, so that the clients send them again.
 				"""
 		}
 	}
@@ -0,0 +1,128 @@
+package metadata
+
+components: sources: google_workspace: {
+	title: "Google Workspace"
+
+	classes: components._audit_log.classes
+
+	features: {
+		acknowledgements: false
+		collect: {
+			checkpoint: enabled: true
+			from: {
+				service: services.google_workspace
+
+				interface: socket: {
+					api: {
+						title: "Admin SDK Reports API"
+						url:   urls.google_workspace_reports_api
+					}
+					direction: "outgoing"
+					protocols: ["http"]
+					ssl: "required"
+				}
+			}
+			proxy: enabled: true
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+				enabled_by_scheme:      true
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: components._audit_log.support & {
+		requirements: [
+			"""
+				The service account used by the source must be granted
+				[domain-wide delegation](\(urls.google_workspace_domain_wide_delegation)) of the
+				`https://www.googleapis.com/auth/admin.reports.audit.readonly` scope.
+				""",
+		]
+	}
+	installation: components._audit_log.installation
+
+	configuration: {
+		state_store: configuration._state_store
+		credentials_path: {
+			description: "Path to the credentials JSON file of a service account."
+			required:    true
+			type: string: {
+				examples: ["/path/to/credentials.json"]
+			}
+		}
+		subject: {
+			description: "The email of the administrator that the service account acts on behalf of."
+			required:    true
+			type: string: {
+				examples: ["admin@example.com"]
+			}
+		}
+		applications: {
+			common:      true
+			description: "The applications whose activities are pulled."
+			required:    false
+			type: array: {
+				default: ["admin", "drive", "login", "token"]
+				items: type: string: {
+					examples: ["admin", "calendar", "drive", "login", "saml", "token", "user_accounts"]
+				}
+			}
+		}
+		customer_id: {
+			common:      false
+			description: "The ID of the customer whose activities are pulled. By default, the customer of the `subject` is used."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["C01234567"]
+			}
+		}
+		delay_secs: {
+			common:      false
+			description: "How long to wait before pulling activities, in seconds. Google makes some activities available hours after they happened. Activities that become available after the source has pulled the time range they happened in are missed, so a delay trades the freshness of the activities for their completeness."
+			required:    false
+			type: uint: {
+				default: 0
+				unit:    "seconds"
+			}
+		}
+		endpoint: {
+			common:      false
+			description: "The endpoint of the Admin SDK API."
+			required:    false
+			type: string: {
+				default: "https://admin.googleapis.com"
+				examples: ["https://admin.googleapis.com"]
+			}
+		}
+		poll_interval_secs: {
+			common:      true
+			description: "The interval between polls, in seconds."
+			required:    false
+			type: uint: {
+				default: 300
+				unit:    "seconds"
+			}
+		}
+	}
+
+	how_it_works: components._audit_log.how_it_works & {
+		activities: {
+			title: "Activities"
+			body: """
+				The Reports API lists the activities of each application from the most recent, so
+				the cursor of each application is only advanced once all the pages of a time
+				range have been sent. The first poll pulls all the activities that Google still
+				retains. Each activity becomes an event, whose `event.action` is the name of the
+				first event of the activity.
+				"""
+		}
+	}
+
+	output:    components._audit_log.output
+	telemetry: components._audit_log.telemetry
+}
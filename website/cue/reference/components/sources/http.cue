@@ -56,10 +56,10 @@ components: sources: http: {
 	configuration: {
 		acknowledgements: configuration._source_acknowledgements
 		address: {
-			description: "The address to accept connections on. The address _must_ include a port."
+			description: "The address to accept connections on, `systemd#N` to use the Nth socket passed by systemd socket activation, or `fd#N` to use the socket of file descriptor N inherited from the process that started Vector. If an address is used it _must_ include a port."
 			required:    true
 			type: string: {
-				examples: ["0.0.0.0:\(_port)", "localhost:\(_port)"]
+				examples: ["0.0.0.0:\(_port)", "localhost:\(_port)", "systemd", "fd#3"]
 			}
 		}
 		encoding: {
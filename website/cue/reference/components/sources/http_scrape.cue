@@ -19,7 +19,7 @@ components: sources: http_scrape: {
 			default_framing: "`bytes`"
 		}
 		collect: {
-			checkpoint: enabled: false
+			checkpoint: enabled: true
 			from: {
 				service: services.http_scrape
 
@@ -52,10 +52,124 @@ components: sources: http_scrape: {
 	}
 
 	configuration: {
-		auth: configuration._http_auth & {_args: {
-			password_example: "${PASSWORD}"
-			username_example: "${USERNAME}"
-		}}
+		state_store: configuration._state_store
+		auth: {
+			common:      false
+			description: "Configures the authentication strategy."
+			required:    false
+			type: object: options: {
+				strategy: {
+					description: "The authentication strategy to use."
+					required:    true
+					type: string: {
+						enum: {
+							basic:  "The [basic authentication strategy](\(urls.basic_auth))."
+							bearer: "The bearer token authentication strategy."
+							oauth2: "The OAuth2 authentication strategy, with access tokens fetched from the token endpoint with the client credentials grant, or with the refresh token grant if `refresh_token` is set. Tokens are refreshed before they expire, or when a request is rejected with a `401` status."
+							aws:    "The AWS authentication strategy, signing requests with AWS Signature Version 4."
+						}
+					}
+				}
+				user: {
+					description:   "The basic authentication user name."
+					relevant_when: "strategy = \"basic\""
+					required:      true
+					type: string: {
+						examples: ["${USERNAME}", "username"]
+					}
+				}
+				password: {
+					description:   "The basic authentication password."
+					relevant_when: "strategy = \"basic\""
+					required:      true
+					type: string: {
+						examples: ["${PASSWORD}", "password"]
+					}
+				}
+				token: {
+					description:   "The token to use for bearer authentication"
+					relevant_when: "strategy = \"bearer\""
+					required:      true
+					type: string: {
+						examples: ["${API_TOKEN}", "xyz123"]
+					}
+				}
+				token_endpoint: {
+					description:   "The URL of the OAuth2 token endpoint."
+					relevant_when: "strategy = \"oauth2\""
+					required:      true
+					type: string: {
+						examples: ["https://login.microsoftonline.com/${TENANT_ID}/oauth2/v2.0/token"]
+					}
+				}
+				client_id: {
+					description:   "The OAuth2 client ID."
+					relevant_when: "strategy = \"oauth2\""
+					required:      true
+					type: string: {
+						examples: ["${CLIENT_ID}"]
+					}
+				}
+				client_secret: {
+					common:        true
+					description:   "The OAuth2 client secret."
+					relevant_when: "strategy = \"oauth2\""
+					required:      false
+					type: string: {
+						default: null
+						examples: ["${CLIENT_SECRET}"]
+					}
+				}
+				refresh_token: {
+					common:        false
+					description:   "The OAuth2 refresh token. If the token endpoint returns a new refresh token, it is used for the next refreshes."
+					relevant_when: "strategy = \"oauth2\""
+					required:      false
+					type: string: {
+						default: null
+						examples: ["${REFRESH_TOKEN}"]
+					}
+				}
+				scopes: {
+					common:        false
+					description:   "The OAuth2 scopes to request."
+					relevant_when: "strategy = \"oauth2\""
+					required:      false
+					type: array: {
+						default: []
+						items: type: string: {
+							examples: ["https://manage.office.com/.default"]
+						}
+					}
+				}
+				region: {
+					description:   "The AWS region of the API."
+					relevant_when: "strategy = \"aws\""
+					required:      true
+					type: string: {
+						examples: ["us-east-1"]
+					}
+				}
+				service: {
+					description:   "The name of the AWS service of the API."
+					relevant_when: "strategy = \"aws\""
+					required:      true
+					type: string: {
+						examples: ["execute-api"]
+					}
+				}
+			}
+		}
+		body: {
+			common:      false
+			description: "The body of the HTTP requests. It is rendered before each request, and can refer to the checkpoint of `since` as `{{ since }}`, and to the cursor or number of the current page of `pagination` as `{{ cursor }}` or `{{ page }}`."
+			required:    false
+			type: string: {
+				default: null
+				examples: [#"{"query": "level:error", "from": "{{ since }}"}"#]
+				syntax: "template"
+			}
+		}
 		endpoint: {
 			description: "Endpoint to scrape observability data from."
 			required:    true
@@ -72,6 +186,16 @@ components: sources: http_scrape: {
 				examples: [{"Your-Custom-Header": "it's-value"}]
 			}
 		}
+		headers_vrl: {
+			common:      false
+			description: "A [VRL](\(urls.vrl_reference)) program computing headers for each HTTP request, such as signatures or timestamps. The program can read the `url`, `method` and `body` of the request, and the `since`, `cursor` and `page` fields that `body` can refer to. It must resolve to an object whose fields are set as headers, replacing the headers of `headers`."
+			required:    false
+			type: string: {
+				default: null
+				examples: [#"{ "X-Signature": encode_base64(hmac(string!(.method) + "\n" + string!(.url), "${SECRET}")) }"#]
+				syntax: "remap_program"
+			}
+		}
 		method: {
 			common:      false
 			description: "Specifies the action of the HTTP request."
@@ -88,6 +212,61 @@ components: sources: http_scrape: {
 				}
 			}
 		}
+		pagination: {
+			common:      false
+			description: "Pagination of the responses. The pages of a scrape are requested one after the other, until the last one."
+			required:    false
+			type: object: options: {
+				strategy: {
+					description: "How the next page of a response is found."
+					required:    true
+					type: string: {
+						enum: {
+							cursor:      "The cursor of the next page is read from each JSON response."
+							link_header: "The URL of the next page is read from the `next` link of the `Link` header of each response."
+							page:        "The pages are requested by number, until a page has no events."
+						}
+					}
+				}
+				cursor_pointer: {
+					description:   "A JSON pointer to the cursor in the response. The scrape ends with the first response without a cursor."
+					relevant_when: "strategy = \"cursor\""
+					required:      true
+					type: string: {
+						examples: ["/meta/next_cursor"]
+					}
+				}
+				param: {
+					common:        true
+					description:   "The query parameter to send the cursor or page number in. They are also available to `body` as `{{ cursor }}` or `{{ page }}`."
+					relevant_when: "strategy = \"cursor\" or strategy = \"page\""
+					required:      false
+					type: string: {
+						default: null
+						examples: ["cursor", "page"]
+					}
+				}
+				first_page: {
+					common:        false
+					description:   "The number of the first page."
+					relevant_when: "strategy = \"page\""
+					required:      false
+					type: uint: {
+						default: 1
+						unit:    null
+					}
+				}
+				max_pages: {
+					common:      false
+					description: "The most pages requested in a single scrape. Once reached, the scrape ends without requesting the next pages."
+					required:    false
+					type: uint: {
+						default: 100
+						unit:    null
+					}
+				}
+			}
+		}
 		query: {
 			common: false
 			description: """
@@ -119,6 +298,45 @@ components: sources: http_scrape: {
 				}
 			}
 		}
+		since: {
+			common:      false
+			description: "Resuming each scrape from the position of the last event received."
+			required:    false
+			type: object: options: {
+				field: {
+					description: "The field of the events holding their position. After each complete scrape, the greatest value of the field becomes the checkpoint of the next scrape. The checkpoint is persisted across restarts."
+					required:    true
+					type: string: {
+						examples: ["timestamp", "id"]
+					}
+				}
+				initial: {
+					description: "The checkpoint of the first scrape."
+					required:    true
+					type: string: {
+						examples: ["2022-01-01T00:00:00Z"]
+					}
+				}
+				param: {
+					common:      true
+					description: "The query parameter to send the checkpoint in. The checkpoint is also available to `body` as `{{ since }}`."
+					required:    false
+					type: string: {
+						default: null
+						examples: ["since"]
+					}
+				}
+			}
+		}
+		split_pointer: {
+			common:      false
+			description: "A JSON pointer to the array of each response whose elements are decoded as separate events, with `decoding`. By default, the whole response is decoded."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["/data/items"]
+			}
+		}
 		scrape_interval_secs: {
 			common:      true
 			description: "The interval between scrapes, in seconds."
@@ -130,6 +348,22 @@ components: sources: http_scrape: {
 		}
 	}
 
+	how_it_works: {
+		pagination: {
+			title: "Pagination"
+			body: """
+				When `pagination` is set, each scrape requests the pages of the response one
+				after the other, until the last one or `pagination.max_pages`. The next page is
+				found from a cursor in each response, from the `next` link of the `Link` header,
+				or by counting pages until one has no events. Requests that fail end the scrape,
+				and the next scrape starts over from the first page.
+
+				With `since`, the next scrape starts from the checkpoint of the last complete
+				scrape instead, so that only new events are requested.
+				"""
+		}
+	}
+
 	output: {
 		logs: {
 			text: {
@@ -237,6 +237,18 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		audit_log_rate_limited_total: {
+			description:       "The total number of times an audit log source waited for the rate limit of its API to be reset."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				provider: {
+					description: "The audit log API."
+					required:    true
+					examples: ["okta", "microsoft_365", "google_workspace"]
+				}
+			}
+		}
 		checkpoint_write_errors_total: {
 			description:       "The total number of errors writing checkpoints. This metric is deprecated in favor of `component_errors_total`."
 			type:              "counter"
@@ -420,6 +432,14 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags
 		}
+		endpoint_ejections_total: {
+			description:       "The total number of times an endpoint has been ejected from load balancing after consecutive failed requests."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				endpoint: _endpoint
+			}
+		}
 		events_discarded_total: {
 			description:       "The total number of events discarded by this component."
 			type:              "counter"
@@ -466,6 +486,27 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		azure_queue_event_ignored_total: {
+			description:       "The total number of times an Event Grid event in an Azure Storage queue message was ignored (for an event that was not `Microsoft.Storage.BlobCreated`)."
+			type:              "counter"
+			default_namespace: "vector"
+
+			tags: _component_tags & {
+				ignore_type: {
+					description: "The reason for ignoring the Event Grid event"
+					required:    true
+					enum: {
+						"invalid_event_type": "The type of invalid event."
+					}
+				}
+			}
+		}
+		azure_queue_message_received_messages_total: {
+			description:       "The total number of received Azure Storage queue messages."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		buffer_byte_size: {
 			description:       "The number of bytes current in the buffer."
 			type:              "gauge"
@@ -523,6 +564,15 @@ components: sources: internal_metrics: {
 				stage:      _stage
 			}
 		}
+		component_in_flight_event_bytes: {
+			description: """
+				The number of event bytes sent by this source that are still in flight within the
+				topology. Only reported when a global memory budget is set with `memory.limit_bytes`.
+				"""
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		component_received_bytes_total: {
 			description:       string | *"The number of raw bytes accepted by this component from source origins."
 			type:              "counter"
@@ -564,6 +614,23 @@ components: sources: internal_metrics: {
 				mode: _mode
 			}
 		}
+		component_batches_flushed_total: {
+			description:       "The total number of batches flushed by this component."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				reason: {
+					description: "The reason the batch was flushed."
+					required:    true
+					enum: {
+						size:          "The batch reached its `max_bytes` or `max_events` limit."
+						timeout:       "The batch reached its `timeout_secs` limit."
+						total_size:    "The batches of all the partitions together reached their `max_total_bytes` limit, and this one was the largest of them."
+						end_of_stream: "The input of the component ended."
+					}
+				}
+			}
+		}
 		component_received_events_count: {
 			description: """
 				A histogram of the number of events passed in each internal batch in Vector's internal topology.
@@ -645,6 +712,12 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags & {output: _output}
 		}
+		component_request_splits_total: {
+			description:       "The total number of requests split in half because their encoded payload was larger than the limit of the downstream service."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		datadog_logs_received_in_total: {
 			description:       "Number of Datadog logs received."
 			type:              "counter"
@@ -890,6 +963,25 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags
 		}
+		pii_redactions_total: {
+			description:       "The total number of occurrences of personally identifiable information redacted by the `redact_pii` transform."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				detector: {
+					description: "The detector that found the information."
+					required:    true
+					enum: {
+						credit_card:               "Credit card numbers."
+						email:                     "Email addresses."
+						us_social_security_number: "US social security numbers."
+						iban:                      "International bank account numbers."
+						ip_address:                "IPv4 and IPv6 addresses."
+						secret:                    "Secrets, such as API keys and passwords."
+					}
+				}
+			}
+		}
 		processed_bytes_total: {
 			description:       "The number of bytes processed by the component."
 			type:              "counter"
@@ -996,6 +1088,12 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		s3_backfill_objects_read_total: {
+			description:       "The total number of objects read by the backfills of the `aws_s3` source."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		sqs_message_delete_failed_total: {
 			description:       "The total number of failures to delete SQS messages."
 			type:              "counter"
@@ -1074,6 +1172,43 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		tenant_discarded_events_total: {
+			description:       "The total number of events of the tenant discarded for exceeding its limits."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				tenant: {
+					description: "The name of the tenant, set by the `tenancy.tenant_field` global option."
+					required:    true
+				}
+				reason: {
+					description: "The limit of the tenant that was exceeded."
+					required:    true
+				}
+			}
+		}
+		tenant_received_event_bytes_total: {
+			description:       "The total number of bytes of events of the tenant received by the sources."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				tenant: {
+					description: "The name of the tenant, set by the `tenancy.tenant_field` global option."
+					required:    true
+				}
+			}
+		}
+		tenant_received_events_total: {
+			description:       "The total number of events of the tenant received by the sources."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				tenant: {
+					description: "The name of the tenant, set by the `tenancy.tenant_field` global option."
+					required:    true
+				}
+			}
+		}
 		timestamp_parse_errors_total: {
 			description:       "The total number of errors encountered parsing [RFC 3339](\(urls.rfc_3339)) timestamps."
 			type:              "counter"
@@ -1101,11 +1236,17 @@ components: sources: internal_metrics: {
 			}
 		}
 		utilization: {
-			description:       "A ratio from 0 to 1 of the load on a component. A value of 0 would indicate a completely idle component that is simply waiting for input. A value of 1 would indicate a that is never idle. This value is updated every 5 seconds."
+			description:       "A ratio from 0 to 1 of the load on a component. A value of 0 would indicate a completely idle component that is simply waiting for input. A value of 1 would indicate a that is never idle. This value is updated every `utilization.report_interval_secs` seconds, 5 by default, and smoothed as set by `utilization.ewma_alpha`."
 			type:              "gauge"
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		output_utilization: {
+			description:       "A ratio from 0 to 1 of the time a transform with several outputs spends sending events to each output. Sending only takes time when the downstream components apply backpressure, so a high value indicates the output holding the transform back. This value is updated, and smoothed, like `utilization`."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags & {output: _output}
+		}
 		build_info: {
 			description:       "Has a fixed value of 1.0. Contains build information such as Rust and Vector versions."
 			type:              "gauge"
@@ -53,7 +53,22 @@ components: sources: kafka: {
 				examples: ["smallest", "earliest", "beginning", "largest", "latest", "end", "error"]
 			}
 		}
+		backpressure_pause_ms: {
+			common:      false
+			description: "The time after which the assigned partitions are paused while the downstream components apply backpressure. Paused partitions are not fetched from the brokers, which keeps the messages from piling up in the queue of the consumer, and are resumed as soon as the events are accepted again. Set to `0` to never pause the partitions."
+			required:    false
+			type: uint: {
+				default: 1000
+				unit:    "milliseconds"
+			}
+		}
 		bootstrap_servers: components._kafka.configuration.bootstrap_servers
+		cooperative_rebalancing: {
+			common:      false
+			description: "Whether or not to rebalance the partitions of the consumer group incrementally. With incremental cooperative rebalancing, only the partitions moving to another consumer are revoked on a rebalance, instead of all the partitions of every consumer. All the consumers of the group must use it."
+			required:    false
+			type: bool: default: false
+		}
 		commit_interval_ms: {
 			common:      false
 			description: "The frequency that the consumer offsets are committed (written) to offset storage."
@@ -81,6 +96,15 @@ components: sources: kafka: {
 				examples: ["consumer-group-name"]
 			}
 		}
+		group_instance_id: {
+			common:      false
+			description: "The static member ID of the consumer within the consumer group. Static members which leave the group, such as when Vector restarts, get their partitions back without a rebalance of the group if they come back within `session_timeout_ms`. The ID must be unique within the consumer group, and stay the same across restarts."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["vector-0"]
+			}
+		}
 		key_field: {
 			common:      true
 			description: "The log field name to use for the Kafka message key."
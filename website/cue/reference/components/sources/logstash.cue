@@ -318,6 +318,19 @@ components: sources: logstash: {
 				Currently, this source will acknowledge events to the sender once the event has been sent to the next
 				component in the topology. In the future, this source will utilize Vector's support for end-to-end
 				acknowledgements.
+
+				Each window of events sent by the client is acknowledged on its own, with the sequence number of its
+				last event, so that the client can send the next window as soon as the previous one is acknowledged.
+				"""
+		}
+
+		keepalives: {
+			title: "Keepalives"
+			body: """
+				With end-to-end acknowledgements enabled, the events can wait a while before being acknowledged. To keep
+				the clients speaking version 2 of the protocol, used by the recent Elastic Beats, from timing out and
+				sending the events again, this source acknowledges the sequence number 0 every 5 seconds while the
+				events are waiting, as Logstash does.
 				"""
 		}
 	}
@@ -0,0 +1,135 @@
+package metadata
+
+components: sources: microsoft_365: {
+	title: "Microsoft 365"
+
+	classes: components._audit_log.classes
+
+	features: {
+		acknowledgements: false
+		collect: {
+			checkpoint: enabled: true
+			from: {
+				service: services.microsoft_365
+
+				interface: socket: {
+					api: {
+						title: "Office 365 Management Activity API"
+						url:   urls.microsoft_365_management_activity_api
+					}
+					direction: "outgoing"
+					protocols: ["http"]
+					ssl: "required"
+				}
+			}
+			proxy: enabled: true
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+				enabled_by_scheme:      true
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: components._audit_log.support & {
+		requirements: [
+			"""
+				The Azure Active Directory application used by the source must be granted the
+				`ActivityFeed.Read` permission of the Office 365 Management APIs, and the
+				`ActivityFeed.ReadDlp` one to pull the `DLP.All` content type.
+				""",
+		]
+	}
+	installation: components._audit_log.installation
+
+	configuration: {
+		state_store: configuration._state_store
+		tenant_id: {
+			description: "The ID of the Azure Active Directory tenant of the organization."
+			required:    true
+			type: string: {
+				examples: ["${AZURE_TENANT_ID}"]
+			}
+		}
+		client_id: {
+			description: "The client ID of the Azure Active Directory application used to pull the audit logs."
+			required:    true
+			type: string: {
+				examples: ["${AZURE_CLIENT_ID}"]
+			}
+		}
+		client_secret: {
+			description: "The client secret of the Azure Active Directory application."
+			required:    true
+			type: string: {
+				examples: ["${AZURE_CLIENT_SECRET}"]
+			}
+		}
+		content_types: {
+			common:      true
+			description: "The content types pulled. The source subscribes to each of them when it starts, if needed."
+			required:    false
+			type: array: {
+				default: ["Audit.AzureActiveDirectory", "Audit.Exchange", "Audit.SharePoint", "Audit.General", "DLP.All"]
+				items: type: string: {
+					examples: ["Audit.General"]
+				}
+			}
+		}
+		publisher_id: {
+			common:      false
+			description: "The publisher identifier sent with the requests, which Microsoft uses to share the request quota. By default, the tenant ID is used."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["${AZURE_TENANT_ID}"]
+			}
+		}
+		endpoint: {
+			common:      false
+			description: "The endpoint of the Management Activity API. The API of government clouds have different endpoints."
+			required:    false
+			type: string: {
+				default: "https://manage.office.com"
+				examples: ["https://manage-gcc.office.com"]
+			}
+		}
+		login_endpoint: {
+			common:      false
+			description: "The endpoint of the Microsoft identity platform, where access tokens are requested."
+			required:    false
+			type: string: {
+				default: "https://login.microsoftonline.com"
+				examples: ["https://login.microsoftonline.us"]
+			}
+		}
+		poll_interval_secs: {
+			common:      true
+			description: "The interval between polls, in seconds."
+			required:    false
+			type: uint: {
+				default: 60
+				unit:    "seconds"
+			}
+		}
+	}
+
+	how_it_works: components._audit_log.how_it_works & {
+		content_blobs: {
+			title: "Content blobs"
+			body: """
+				The Management Activity API makes the records of each content type available as
+				blobs of content, listed by the time they were created. The cursor of each
+				content type is the end of the last time range whose blobs have all been pulled.
+				The first poll pulls the blobs of the last 24 hours, and the source can catch up
+				with at most the last seven days, after which Microsoft deletes the blobs.
+				"""
+		}
+	}
+
+	output:    components._audit_log.output
+	telemetry: components._audit_log.telemetry
+}
@@ -0,0 +1,200 @@
+package metadata
+
+components: sources: netflow: {
+	_port: 2055
+
+	title: "NetFlow"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.netflow
+				interface: socket: {
+					api: {
+						title: "NetFlow"
+						url:   urls.netflow
+					}
+					direction: "incoming"
+					port:      _port
+					protocols: ["udp"]
+					ssl: "disabled"
+				}
+			}
+			receive_buffer_bytes: {
+				enabled: true
+			}
+			keepalive: enabled: false
+			tls: enabled:       false
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		address: {
+			common:      true
+			description: "The address to listen for flows on. NetFlow v5, NetFlow v9, IPFIX and sFlow v5 datagrams are all accepted on this address, and told apart by their version."
+			required:    false
+			type: string: {
+				default: "0.0.0.0:\(_port)"
+			}
+		}
+		receive_buffer_bytes: {
+			common:      false
+			description: "The size, in bytes, of the receive buffer used for the listening socket."
+			required:    false
+			type: uint: {
+				default: null
+				unit:    "bytes"
+			}
+		}
+	}
+
+	output: logs: flow: {
+		description: "A flow, or a sampled packet for sFlow. Only the fields known for the flow are set."
+		fields: {
+			host: {
+				description: "The IP address of the exporter that sent the flow."
+				required:    true
+				type: string: {
+					examples: ["10.0.0.254"]
+				}
+			}
+			flow_type: {
+				description: "The protocol the flow was exported with."
+				required:    true
+				type: string: {
+					enum: {
+						netflow_v5: "NetFlow v5."
+						netflow_v9: "NetFlow v9."
+						ipfix:      "IPFIX."
+						sflow:      "sFlow v5."
+					}
+				}
+			}
+			src_addr: {
+				description: "The source IP address."
+				required:    false
+				type: string: {
+					examples: ["10.0.0.1"]
+				}
+			}
+			dst_addr: {
+				description: "The destination IP address."
+				required:    false
+				type: string: {
+					examples: ["192.168.1.10"]
+				}
+			}
+			src_port: {
+				description: "The source port."
+				required:    false
+				type: uint: {
+					examples: [51234]
+					unit: null
+				}
+			}
+			dst_port: {
+				description: "The destination port."
+				required:    false
+				type: uint: {
+					examples: [443]
+					unit: null
+				}
+			}
+			protocol: {
+				description: "The IP protocol number."
+				required:    false
+				type: uint: {
+					examples: [6]
+					unit: null
+				}
+			}
+			bytes: {
+				description: "The number of bytes of the flow, or the length of the sampled frame for sFlow."
+				required:    false
+				type: uint: {
+					examples: [1500]
+					unit: "bytes"
+				}
+			}
+			packets: {
+				description: "The number of packets of the flow."
+				required:    false
+				type: uint: {
+					examples: [10]
+					unit: null
+				}
+			}
+			flow_start: {
+				description: "When the flow started."
+				required:    false
+				type: timestamp: {}
+			}
+			flow_end: {
+				description: "When the flow ended."
+				required:    false
+				type: timestamp: {}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["netflow"]
+				}
+			}
+			timestamp: fields._current_timestamp
+		}
+	}
+
+	how_it_works: {
+		fields: {
+			title: "Field names"
+			body: """
+				Flows of every protocol are decoded with the same field names, such as `src_addr`,
+				`dst_port`, `bytes`, `tcp_flags`, `input_interface`, `src_as` or `vlan_id`, whichever
+				are known for the flow. Information elements of NetFlow v9 and IPFIX without a
+				standardized name are kept as `field_<id>`, or `field_<enterprise>_<id>` for
+				enterprise-specific elements.
+
+				sFlow flow samples are decoded from the headers of the sampled packets, with
+				`sampling_interval` set to the sampling rate. Counter samples are not decoded.
+				"""
+		}
+		templates: {
+			title: "Templates"
+			body: """
+				NetFlow v9 and IPFIX records are decoded with the templates that their exporter
+				sent for their observation domain. Templates are kept in memory for as long as Vector
+				runs, so records received before their template are dropped, and counted in
+				`component_errors_total`.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
@@ -0,0 +1,108 @@
+package metadata
+
+components: sources: okta: {
+	title: "Okta"
+
+	classes: components._audit_log.classes
+
+	features: {
+		acknowledgements: false
+		collect: {
+			checkpoint: enabled: true
+			from: {
+				service: services.okta
+
+				interface: socket: {
+					api: {
+						title: "Okta System Log API"
+						url:   urls.okta_system_log_api
+					}
+					direction: "outgoing"
+					protocols: ["http"]
+					ssl: "required"
+				}
+			}
+			proxy: enabled: true
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+				enabled_by_scheme:      true
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support:      components._audit_log.support
+	installation: components._audit_log.installation
+
+	configuration: {
+		state_store: configuration._state_store
+		domain: {
+			description: "The Okta domain of the organization."
+			required:    true
+			type: string: {
+				examples: ["example.okta.com"]
+			}
+		}
+		token: {
+			description: "An [API token](\(urls.okta_api_token)) of the organization."
+			required:    true
+			type: string: {
+				examples: ["${OKTA_API_TOKEN}"]
+			}
+		}
+		since: {
+			common:      false
+			description: "The time of the first records pulled, as an RFC 3339 timestamp. By default, the records of the last seven days are pulled. Once records have been pulled, the source resumes from its cursor instead."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["2022-01-01T00:00:00Z"]
+			}
+		}
+		filter: {
+			common:      false
+			description: "A filter expression on the records pulled."
+			required:    false
+			type: string: {
+				default: null
+				examples: [#"eventType eq "user.session.start""#]
+			}
+		}
+		limit: {
+			common:      false
+			description: "The maximum number of records of each page."
+			required:    false
+			type: uint: {
+				default: 1000
+				unit:    null
+			}
+		}
+		poll_interval_secs: {
+			common:      true
+			description: "The interval between polls, in seconds."
+			required:    false
+			type: uint: {
+				default: 60
+				unit:    "seconds"
+			}
+		}
+	}
+
+	how_it_works: components._audit_log.how_it_works & {
+		polling: {
+			title: "Polling"
+			body: """
+				Okta returns the `next` link of the System Log even once the last record has been
+				pulled, and each poll starts from it. This link is the cursor of the source, so
+				the `since`, `filter` and `limit` options only apply until the first page has
+				been pulled.
+				"""
+		}
+	}
+
+	output:    components._audit_log.output
+	telemetry: components._audit_log.telemetry
+}
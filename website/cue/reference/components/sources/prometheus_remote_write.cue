@@ -79,6 +79,9 @@ components: sources: prometheus_remote_write: {
 				For metrics named with a suffix of `_total`, this source
 				emits the value as a counter metric. All other metrics
 				are emitted as gauges.
+
+				Native histograms carry their own layout, so they are
+				always emitted as exponential histograms.
 				"""
 		}
 	}
@@ -4,7 +4,7 @@ components: sources: redis: {
 	title: "Redis"
 
 	features: {
-		acknowledgements: false
+		acknowledgements: true
 		collect: {
 			checkpoint: enabled: false
 			tls: enabled:        false
@@ -58,6 +58,7 @@ components: sources: redis: {
 	}
 
 	configuration: {
+		acknowledgements: configuration._source_acknowledgements
 		url: {
 			description: "The Redis URL to connect to. The url _must_ take the form of `protocol://server:port/db` where the protocol can either be `redis` or `rediss` for connections secured via TLS."
 			groups: ["tcp"]
@@ -79,13 +80,14 @@ components: sources: redis: {
 		}
 		data_type: {
 			common:      false
-			description: "The Redis data type (`list` or `channel`) to use."
+			description: "The Redis data type (`list`, `channel` or `stream`) to use."
 			required:    false
 			type: string: {
 				default: "list"
 				enum: {
 					list:    "Use the Redis `list` data type."
 					channel: "Use the Redis `channel` data type."
+					stream:  "Use the Redis `stream` data type, reading entries as a member of a consumer group."
 				}
 				syntax: "literal"
 			}
@@ -114,6 +116,87 @@ components: sources: redis: {
 				}
 			}
 		}
+		stream: {
+			common:      false
+			description: "Options for the Redis `stream` data type."
+			required:    false
+			warnings: []
+			type: object: {
+				examples: []
+				options: {
+					block_ms: {
+						common:      false
+						description: "The maximum time to wait for new entries."
+						required:    false
+						type: uint: {
+							default: 1000
+							unit:    "milliseconds"
+						}
+					}
+					claim_min_idle_secs: {
+						common:      false
+						description: "The time after which the pending entries of other consumers are claimed. When set, the entries read but not acknowledged by any consumer of the group for longer than this are claimed when Vector starts, and read again."
+						required:    false
+						type: uint: {
+							default: null
+							unit:    "seconds"
+						}
+					}
+					consumer: {
+						common:      false
+						description: "The name of the consumer within the consumer group. Each Vector instance reading from the same consumer group must use a distinct name, which must also stay the same across restarts for the entries left pending to be read again. Defaults to the hostname."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["vector-0"]
+							syntax: "literal"
+						}
+					}
+					count: {
+						common:      false
+						description: "The maximum number of entries to read at once."
+						required:    false
+						type: uint: {
+							default: 100
+							unit:    "events"
+						}
+					}
+					create_group: {
+						common:      false
+						description: "Whether or not to create the consumer group, and the stream, if they do not exist."
+						required:    false
+						type: bool: default: true
+					}
+					field: {
+						common:      false
+						description: "The field of the entries holding the message to decode."
+						required:    false
+						type: string: {
+							default: "message"
+							syntax:  "literal"
+						}
+					}
+					group: {
+						common:      false
+						description: "The name of the consumer group to read entries as."
+						required:    false
+						type: string: {
+							default: "vector"
+							syntax:  "literal"
+						}
+					}
+					start_id: {
+						common:      false
+						description: "The ID of the entry after which a consumer group created by Vector starts reading. `$` only reads the entries added after the group is created, and `0` reads the whole stream."
+						required:    false
+						type: string: {
+							default: "$"
+							syntax:  "literal"
+						}
+					}
+				}
+			}
+		}
 		redis_key: {
 			common:      false
 			description: "The log field name to use for the redis key. If set to an empty string or null, the key is not added to the log event."
@@ -161,6 +244,16 @@ components: sources: redis: {
 				API.
 				"""
 		}
+		streams: {
+			title: "Streams"
+			body:  """
+				With the `stream` data type, the source reads entries with `XREADGROUP` as the `consumer` of a
+				consumer `group`, so that several Vector instances can share the entries of a stream. When
+				acknowledgements are enabled, an entry is acknowledged with `XACK` only once its events are
+				delivered, and the entries left pending when Vector stops are read again when it restarts.
+				Acknowledgements are only supported by the `stream` data type.
+				"""
+		}
 	}
 
 	telemetry: metrics: {
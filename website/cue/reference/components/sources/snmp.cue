@@ -0,0 +1,359 @@
+package metadata
+
+components: sources: snmp: {
+	_port: 162
+
+	title: "SNMP"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.snmp
+				interface: socket: {
+					api: {
+						title: "SNMP"
+						url:   urls.snmp
+					}
+					direction: "incoming"
+					port:      _port
+					protocols: ["udp"]
+					ssl: "disabled"
+				}
+			}
+			receive_buffer_bytes: {
+				enabled: true
+			}
+			keepalive: enabled: false
+			tls: enabled:       false
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		mib_paths: {
+			common:      false
+			description: "Files or directories of MIB modules, used to resolve object identifiers to names. The objects of `SNMPv2-SMI`, `SNMPv2-MIB` and `IF-MIB` are always known."
+			required:    false
+			type: array: {
+				default: []
+				items: type: string: {
+					examples: ["/usr/share/snmp/mibs"]
+				}
+			}
+		}
+		traps: {
+			common:      true
+			description: "Reception of SNMP notifications, which are traps and informs. At least one of `traps` or `poll` must be set."
+			required:    false
+			type: object: options: {
+				address: {
+					description: "The address to listen for notifications on."
+					required:    false
+					type: string: {
+						default: "0.0.0.0:\(_port)"
+					}
+				}
+				communities: {
+					description: "The communities of the SNMPv2c notifications that are accepted. When empty, SNMPv2c notifications of any community are accepted."
+					required:    false
+					type: array: {
+						default: []
+						items: type: string: {
+							examples: ["public"]
+						}
+					}
+				}
+				receive_buffer_bytes: {
+					description: "The size, in bytes, of the receive buffer used for the listening socket."
+					required:    false
+					type: uint: {
+						default: null
+						unit:    "bytes"
+					}
+				}
+				users: {
+					description: "The users of the SNMPv3 notifications that are accepted. When empty, SNMPv3 notifications are rejected."
+					required:    false
+					type: array: {
+						default: []
+						items: type: object: options: {
+							name: {
+								description: "The name of the user."
+								required:    true
+								type: string: {
+									examples: ["vector"]
+								}
+							}
+							auth_protocol: {
+								description: "The authentication protocol of the user."
+								required:    false
+								type: string: {
+									default: null
+									enum: {
+										md5:    "HMAC-MD5-96."
+										sha:    "HMAC-SHA-96."
+										sha224: "HMAC-SHA-224-128."
+										sha256: "HMAC-SHA-256-192."
+										sha384: "HMAC-SHA-384-256."
+										sha512: "HMAC-SHA-512-384."
+									}
+								}
+							}
+							auth_password: {
+								description: "The authentication password of the user, at least 8 characters long. Required when `auth_protocol` is set."
+								required:    false
+								type: string: {
+									default: null
+									examples: ["${SNMP_AUTH_PASSWORD}"]
+								}
+							}
+							priv_protocol: {
+								description: "The privacy protocol of the user, which requires authentication."
+								required:    false
+								type: string: {
+									default: null
+									enum: {
+										des: "CBC-DES."
+										aes: "CFB128-AES-128."
+									}
+								}
+							}
+							priv_password: {
+								description: "The privacy password of the user, at least 8 characters long. Required when `priv_protocol` is set."
+								required:    false
+								type: string: {
+									default: null
+									examples: ["${SNMP_PRIV_PASSWORD}"]
+								}
+							}
+						}
+					}
+				}
+			}
+		}
+		poll: {
+			common:      true
+			description: "Periodic polling of SNMP agents, with SNMPv2c. At least one of `traps` or `poll` must be set."
+			required:    false
+			type: object: options: {
+				interval_secs: {
+					description: "The interval between polls, in seconds."
+					required:    false
+					type: uint: {
+						default: 60
+						unit:    "seconds"
+					}
+				}
+				timeout_secs: {
+					description: "How long to wait for each response, in seconds."
+					required:    false
+					type: uint: {
+						default: 5
+						unit:    "seconds"
+					}
+				}
+				targets: {
+					description: "The agents to poll."
+					required:    true
+					type: array: items: type: object: options: {
+						address: {
+							description: "The address of the agent, as `host:port`."
+							required:    true
+							type: string: {
+								examples: ["switch1.example.com:161", "10.0.0.1:161"]
+							}
+						}
+						community: {
+							description: "The community of the agent."
+							required:    false
+							type: string: {
+								default: "public"
+							}
+						}
+						get: {
+							description: "The objects to get, either by name or numeric OID."
+							required:    false
+							type: array: {
+								default: []
+								items: type: string: {
+									examples: ["sysName.0", "SNMPv2-MIB::sysUpTime.0", "1.3.6.1.2.1.1.5.0"]
+								}
+							}
+						}
+						walk: {
+							description: "The subtrees to walk, either by name or numeric OID."
+							required:    false
+							type: array: {
+								default: []
+								items: type: string: {
+									examples: ["IF-MIB::ifDescr", "1.3.6.1.2.1.2.2.1.10"]
+								}
+							}
+						}
+					}
+				}
+			}
+		}
+	}
+
+	output: logs: {
+		notification: {
+			description: "An SNMP trap or inform."
+			fields: {
+				host: {
+					description: "The IP address of the agent that sent the notification."
+					required:    true
+					type: string: {
+						examples: ["10.0.0.1"]
+					}
+				}
+				version: {
+					description: "The SNMP version of the notification."
+					required:    true
+					type: string: {
+						enum: {
+							"2c": "SNMPv2c."
+							"3":  "SNMPv3."
+						}
+					}
+				}
+				pdu_type: {
+					description: "The type of the notification."
+					required:    true
+					type: string: {
+						enum: {
+							trap:   "An unacknowledged notification."
+							inform: "An acknowledged notification."
+						}
+					}
+				}
+				user: {
+					description: "The user of an SNMPv3 notification."
+					required:    false
+					type: string: {
+						examples: ["vector"]
+					}
+				}
+				context_name: {
+					description: "The context of an SNMPv3 notification, when not empty."
+					required:    false
+					type: string: {
+						examples: ["vlan100"]
+					}
+				}
+				trap_oid: {
+					description: "The name of the notification, from `snmpTrapOID.0`."
+					required:    false
+					type: string: {
+						examples: ["IF-MIB::linkDown"]
+					}
+				}
+				uptime: {
+					description: "The uptime of the agent, in hundredths of a second, from `sysUpTime.0`."
+					required:    false
+					type: uint: {
+						examples: [12345]
+						unit: null
+					}
+				}
+				varbinds: {
+					description: "The other variables of the notification, keyed by their name."
+					required:    true
+					type: object: {
+						examples: [{"IF-MIB::ifIndex.3": 3, "IF-MIB::ifDescr.3": "eth0"}]
+						options: {}
+					}
+				}
+				source_type: {
+					description: "The name of the source type."
+					required:    true
+					type: string: {
+						examples: ["snmp"]
+					}
+				}
+				timestamp: fields._current_timestamp
+			}
+		}
+		poll: {
+			description: "The values polled from an agent."
+			fields: {
+				host: {
+					description: "The address of the agent, as configured."
+					required:    true
+					type: string: {
+						examples: ["10.0.0.1:161"]
+					}
+				}
+				values: {
+					description: "The values of the objects that were read, keyed by their name."
+					required:    true
+					type: object: {
+						examples: [{"SNMPv2-MIB::sysName.0": "switch1", "IF-MIB::ifDescr.1": "lo"}]
+						options: {}
+					}
+				}
+				source_type: {
+					description: "The name of the source type."
+					required:    true
+					type: string: {
+						examples: ["snmp"]
+					}
+				}
+				timestamp: fields._current_timestamp
+			}
+		}
+	}
+
+	how_it_works: {
+		names: {
+			title: "Object names"
+			body: """
+				Object identifiers are resolved to the name of their longest known prefix, followed by
+				the remaining arcs, such as `IF-MIB::ifDescr.3`. Identifiers without any known prefix
+				are kept in their numeric form. More objects are known by loading MIB modules with
+				`mib_paths`.
+
+				Octet strings are decoded as UTF-8 when they are printable, and written as hexadecimal
+				bytes, such as `00:1b:21:3c:9d:f8`, otherwise.
+				"""
+		}
+		security: {
+			title: "Security"
+			body: """
+				SNMPv2c notifications are accepted when their community is one of `communities`.
+				SNMPv3 notifications are authenticated and decrypted with the keys of their user,
+				and must use the security level that the user is configured with.
+
+				SNMPv2c informs are acknowledged with a response. SNMPv3 informs are received but
+				not acknowledged, and SNMPv1 traps are not supported.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
@@ -70,18 +70,40 @@ components: sources: statsd: {
 				enum: {
 					tcp:  "TCP Socket."
 					udp:  "UDP Socket."
-					unix: "Unix Domain Socket."
+					unix:          "Unix Domain Socket, in stream mode."
+					unix_datagram: "Unix Domain Socket, in datagram mode. This is how DogStatsD clients usually send metrics over a Unix Domain Socket."
 				}
 			}
 		}
 		path: {
 			description:   "The unix socket path. *This should be an absolute path*."
-			relevant_when: "mode = `unix`"
+			relevant_when: "mode = `unix` or `unix_datagram`"
 			required:      true
 			type: string: {
 				examples: ["/path/to/socket"]
 			}
 		}
+		socket_file_mode: {
+			common:        false
+			description:   "Unix file mode bits to be applied to the unix socket file as its designated file permissions."
+			relevant_when: "mode = `unix_datagram`"
+			required:      false
+			type: uint: {
+				default: null
+				unit:    null
+				examples: [0o777, 0o600, 508]
+			}
+		}
+		max_length: {
+			common:        false
+			description:   "The maximum size of a datagram. Each datagram may hold several newline-delimited metrics."
+			relevant_when: "mode = `unix_datagram`"
+			required:      false
+			type: uint: {
+				default: 102400
+				unit:    "bytes"
+			}
+		}
 		shutdown_timeout_secs: {
 			common:        false
 			description:   "The timeout before a connection is forcefully closed during shutdown."
@@ -112,7 +134,72 @@ components: sources: statsd: {
 		set:          output._passthrough_set
 	}
 
+	output: logs: {
+		event: {
+			description: "A DogStatsD event."
+			fields: {
+				dogstatsd_type: {
+					description: "The type of DogStatsD message."
+					required:    true
+					type: string: {
+						examples: ["event"]
+					}
+				}
+				title: {
+					description: "The title of the event."
+					required:    true
+					type: string: {
+						examples: ["Deployment finished"]
+					}
+				}
+				message:   fields._raw_line
+				timestamp: fields._current_timestamp
+			}
+		}
+		service_check: {
+			description: "A DogStatsD service check."
+			fields: {
+				dogstatsd_type: {
+					description: "The type of DogStatsD message."
+					required:    true
+					type: string: {
+						examples: ["service_check"]
+					}
+				}
+				check: {
+					description: "The name of the service check."
+					required:    true
+					type: string: {
+						examples: ["app.is_up"]
+					}
+				}
+				status: {
+					description: "The status of the service check: `0` for OK, `1` for warning, `2` for critical and `3` for unknown."
+					required:    true
+					type: uint: {
+						examples: [0, 2]
+						unit: null
+					}
+				}
+				timestamp: fields._current_timestamp
+			}
+		}
+	}
+
 	how_it_works: {
+		dogstatsd: {
+			title: "DogStatsD extensions"
+			body:  """
+				Besides the plain StatsD metrics, this source accepts the DogStatsD extensions:
+				tags, container IDs (added as the `container_id` tag), timestamps, and several
+				values for the same metric in a single line, such as `latency:12:15:9|ms`.
+				DogStatsD events and service checks are sent as log events.
+
+				When a metric is sampled, counters are scaled by the inverse of the sample rate,
+				and each distribution value is counted that many times, rounded to the nearest
+				integer.
+				"""
+		}
 		timestamps: {
 			title: "Timestamps"
 			body:  """
@@ -122,6 +209,9 @@ components: sources: statsd: {
 				`null` timestamps are substituted with the current time by downstream sinks or
 				third-party services during sending/ingestion. See the
 				[metric data model](\(urls.vector_metric)) page for more info.
+
+				DogStatsD clients can send a timestamp with the `|T<unix seconds>` extension, in
+				which case it is set on the metric.
 				"""
 		}
 	}
@@ -50,11 +50,11 @@ components: sources: syslog: {
 
 	configuration: {
 		address: {
-			description:   "The address to listen for connections on, or `systemd#N` to use the Nth socket passed by systemd socket activation. If an address is used it _must_ include a port."
+			description:   "The address to listen for connections on, `systemd#N` to use the Nth socket passed by systemd socket activation, or `fd#N` to use the socket of file descriptor N inherited from the process that started Vector. If an address is used it _must_ include a port."
 			relevant_when: "mode = `tcp` or `udp`"
 			required:      true
 			type: string: {
-				examples: ["0.0.0.0:\(_port)", "systemd", "systemd#3"]
+				examples: ["0.0.0.0:\(_port)", "systemd", "systemd#3", "fd#3"]
 			}
 		}
 		host_key: {
@@ -63,6 +63,19 @@ components: sources: vector: {
 				examples: ["0.0.0.0:\(_port)"]
 			}
 		}
+		http_fallback: {
+			common: false
+			description: """
+				Whether or not to also accept events over plain HTTP, on the same port.
+
+				The events are `POST`ed in the body of the requests, as a single event or an array of events, in
+				their native JSON encoding (`application/json`, the default) or MessagePack encoding
+				(`application/msgpack`), optionally compressed with `gzip`. The requests whose content type is
+				`application/grpc` are still handled as gRPC requests.
+				"""
+			required: false
+			type: bool: default: false
+		}
 		version: {
 			description: "Source API version. Specifying this version ensures that Vector does not silently break backward compatibility."
 			common:      true
@@ -90,7 +103,7 @@ components: sources: vector: {
 					}
 				}
 				"*": {
-					description: "Vector transparently forwards data from another upstream Vector instance. The `vector` source will not modify or add fields."
+					description: "Vector transparently forwards data from another upstream Vector instance. The `vector` source will not modify or add fields, and keeps the type information and semantic meanings of fields sent along by the upstream `vector` sink."
 					required:    true
 					type: "*": {}
 				}
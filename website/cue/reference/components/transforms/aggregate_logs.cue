@@ -0,0 +1,149 @@
+package metadata
+
+components: transforms: aggregate_logs: {
+	title: "Aggregate Logs"
+
+	description: """
+		Rolls up log events into a single summary log event per group over a
+		defined interval window, counting the events of each group and
+		summarizing their numeric fields. Unlike `log_to_metric`, the
+		summaries are emitted as log events, which makes this transform
+		suitable for pre-aggregating high-volume audit logs.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		aggregate: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		group_by: {
+			common: true
+			description: """
+				An ordered list of fields by which to group events. Each group with matching values for the specified
+				fields is summarized independently, and the values of these fields are kept in the summary event.
+
+				When no fields are specified, all events will be summarized in a single group.
+				"""
+			required: false
+			type: array: {
+				default: []
+				items: type: string: {
+					examples: ["user", "action", "host"]
+				}
+			}
+		}
+		interval_ms: {
+			common: true
+			description: """
+				The interval over which events are aggregated in milliseconds. Over this period events with the same
+				values for the `group_by` fields will be rolled up into a single summary event.
+				"""
+			required: false
+			type: uint: {
+				default: 10000
+				unit:    "milliseconds"
+			}
+		}
+		max: {
+			common:      false
+			description: "A list of numeric fields to track the maximum of over each group, held in the `max` object of the summary event."
+			required:    false
+			type: array: {
+				default: []
+				items: type: string: {
+					examples: ["duration_ms"]
+				}
+			}
+		}
+		min: {
+			common:      false
+			description: "A list of numeric fields to track the minimum of over each group, held in the `min` object of the summary event."
+			required:    false
+			type: array: {
+				default: []
+				items: type: string: {
+					examples: ["duration_ms"]
+				}
+			}
+		}
+		sample: {
+			common:      false
+			description: "Which events of each group are kept, as a sample, in the summary event."
+			required:    false
+			type: string: {
+				default: "none"
+				enum: {
+					none:  "No events are kept."
+					first: "The first event of the group is kept in the `first` field."
+					last:  "The last event of the group is kept in the `last` field."
+					both:  "Both the first and the last events of the group are kept, in the `first` and `last` fields."
+				}
+			}
+		}
+		sum: {
+			common:      true
+			description: "A list of numeric fields to sum over each group, held in the `sum` object of the summary event."
+			required:    false
+			type: array: {
+				default: []
+				items: type: string: {
+					examples: ["bytes"]
+				}
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	examples: [
+		{
+			title: "Roll up audit logs per user and action"
+			input: [
+				{log: {timestamp: "2021-07-12T07:58:44.223543Z", user: "alice", action: "read", bytes:  10, duration_ms: 5}},
+				{log: {timestamp: "2021-07-12T07:58:45.223543Z", user: "alice", action: "read", bytes:  20, duration_ms: 15}},
+				{log: {timestamp: "2021-07-12T07:58:46.223543Z", user: "bob", action:   "write", bytes: 5, duration_ms:  3}},
+			]
+			configuration: {
+				interval_ms: 5000
+				group_by: ["user", "action"]
+				sum: ["bytes"]
+				max: ["duration_ms"]
+			}
+			output: [
+				{log: {timestamp: "2021-07-12T07:58:49.223543Z", user: "alice", action: "read", count:  2, sum: {bytes: 30}, max: {duration_ms: 15}}},
+				{log: {timestamp: "2021-07-12T07:58:49.223543Z", user: "bob", action:   "write", count: 1, sum: {bytes: 5}, max: {duration_ms:  3}}},
+			]
+		},
+	]
+
+	how_it_works: {
+		aggregation_behavior: {
+			title: "Aggregation Behavior"
+			body: """
+				Every event counts towards the `count` of its group. Events without a numeric value for a field
+				configured in `sum`, `min`, or `max` are left out of the summary of that field. Sums of integers
+				remain integers unless they overflow, in which case they are converted to floats.
+
+				All groups are flushed at the end of each interval, and their summary events are timestamped with the
+				time of the flush.
+				"""
+		}
+	}
+}
@@ -0,0 +1,159 @@
+package metadata
+
+components: transforms: encrypt_fields: {
+	title: "Encrypt Fields"
+
+	description: """
+		Encrypts selected fields of log events with AES-256-GCM, using data keys
+		wrapped by AWS KMS or GCP Cloud KMS, and embeds the wrapped data key in
+		the event so that the fields can be decrypted later.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		sanitize: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		data_key_ttl_secs: {
+			common:      false
+			description: "How long a data key is used, in seconds, before a new one is generated and wrapped."
+			required:    false
+			type: uint: {
+				default: 3600
+				unit:    "seconds"
+			}
+		}
+		envelope_field: {
+			common:      false
+			description: "The field holding the envelope of the encrypted fields."
+			required:    false
+			type: string: {
+				default: "encryption"
+			}
+		}
+		fields: {
+			description: "The fields to encrypt. Fields that are missing from an event are skipped."
+			required:    true
+			type: array: items: type: string: {
+				examples: ["message", "user.email"]
+			}
+		}
+		kms: {
+			description: "The service wrapping the data keys."
+			required:    true
+			type: object: options: {
+				type: {
+					description: "The type of the service."
+					required:    true
+					type: string: enum: {
+						aws_kms: "Data keys are wrapped by a key of AWS KMS."
+						gcp_kms: "Data keys are wrapped by a key of GCP Cloud KMS."
+						static:  "Data keys are wrapped, with AES-256-GCM, by a key given in the configuration. This is meant for testing, and for environments without a KMS."
+					}
+				}
+				key_id: {
+					description:   "The ID, ARN, or alias of the AWS KMS key, or the identifier of the static key written in the envelopes of the events."
+					relevant_when: "type = \"aws_kms\" or type = \"static\""
+					required:      true
+					type: string: {
+						examples: ["alias/vector", "arn:aws:kms:us-east-1:123456789012:key/1234abcd-12ab-34cd-56ef-1234567890ab"]
+					}
+				}
+				key_name: {
+					description:   "The resource name of the Cloud KMS key."
+					relevant_when: "type = \"gcp_kms\""
+					required:      true
+					type: string: {
+						examples: ["projects/my-project/locations/global/keyRings/my-ring/cryptoKeys/my-key"]
+					}
+				}
+				key: {
+					description:   "The key encryption key, as 32 bytes encoded with base64."
+					relevant_when: "type = \"static\""
+					required:      true
+					type: string: {
+						examples: ["${ENCRYPTION_KEY}"]
+					}
+				}
+				region: {
+					description:   "The AWS region of the KMS key."
+					relevant_when: "type = \"aws_kms\""
+					required:      false
+					type: string: {
+						default: null
+						examples: ["us-east-1"]
+					}
+				}
+				endpoint: {
+					description: "Custom endpoint for use with KMS-compatible services."
+					required:    false
+					type: string: {
+						default: null
+						examples: ["http://127.0.0.1:4566"]
+					}
+				}
+			}
+		}
+		proxy: configuration._proxy
+	}
+
+	env_vars: {
+		http_proxy:  env_vars._http_proxy
+		HTTP_PROXY:  env_vars._http_proxy
+		https_proxy: env_vars._https_proxy
+		HTTPS_PROXY: env_vars._https_proxy
+		no_proxy:    env_vars._no_proxy
+		NO_PROXY:    env_vars._no_proxy
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		envelope_encryption: {
+			title: "Envelope encryption"
+			body: """
+				Fields are encrypted with a random data key, which is itself encrypted, or wrapped, by a key managed
+				by the KMS and never leaves it. The KMS is only called when a new data key is generated, every
+				`data_key_ttl_secs`, rather than for each event.
+
+				Each field is replaced by its value, encoded as JSON and encrypted with AES-256-GCM with the path of
+				the field as additional authenticated data, so that encrypted values can't be moved between fields.
+				The encrypted value is the 12 bytes nonce, followed by the ciphertext and the 16 bytes tag, encoded
+				with base64.
+
+				The `envelope_field` of each event holds the `algorithm`, the `key_provider` and `key_id` of the
+				wrapping key, the `wrapped_key` encoded with base64, and the list of encrypted `fields`. To decrypt
+				the fields, the wrapped key is decrypted with the KMS, then each field is decrypted with it.
+				"""
+		}
+		failures: {
+			title: "Failures"
+			body: """
+				Events are dropped when a data key can't be wrapped, for example when the KMS can't be reached, so
+				that fields are never sent unencrypted.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:           components.sources.internal_metrics.output.metrics.component_errors_total
+		component_discarded_events_total: components.sources.internal_metrics.output.metrics.component_discarded_events_total
+	}
+}
@@ -33,6 +33,28 @@ components: transforms: filter: {
 			required: true
 			type: condition: {}
 		}
+		condition_name: {
+			common: false
+			description: """
+				A name for the condition, annotated on the events rerouted to the `dropped` output. If left
+				unspecified, the condition itself is annotated: the source of VRL conditions, or the configuration
+				of other conditions, encoded as JSON.
+				"""
+			required: false
+			type: string: {
+				default: null
+				examples: ["errors_only"]
+			}
+		}
+		reroute_dropped: {
+			common:   false
+			required: false
+			description: """
+				Send the events that don't match the condition to the `dropped` output instead of dropping them
+				entirely.
+				"""
+			type: bool: default: false
+		}
 	}
 
 	input: {
@@ -79,6 +101,22 @@ components: transforms: filter: {
 		},
 	]
 
+	outputs: [
+		components._default_output,
+		{
+			name: "dropped"
+			description: """
+				This transform also implements an additional `dropped` output. When `reroute_dropped` is set to
+				`true`, events that don't match the condition are sent to the `dropped` output instead of being
+				dropped. For a transform component named `foo`, this output can be accessed by specifying
+				`foo.dropped` as the input to another component. Events sent to this output are annotated, in the
+				`dropped` metadata field, with the `reason`, the name of the failing `condition`, and the
+				`component_id`, `component_type` and `component_kind` of the transform, so that drop decisions
+				can be audited downstream.
+				"""
+		},
+	]
+
 	telemetry: metrics: {
 		events_discarded_total: components.sources.internal_metrics.output.metrics.events_discarded_total
 	}
@@ -8,7 +8,8 @@ components: transforms: geoip: {
 		GeoLite2-City, GeoIP2-ISP and GeoLite2-ASN databases.
 
 		Note that this transform has been deprecated in-lieu of using a `geoip` enrichment table
-		along with
+		along with the [`ip_enrichment` transform](/docs/reference/configuration/transforms/ip_enrichment/)
+		or
 		[`get_enrichment_table_record`](/docs/reference/vrl/functions/#get_enrichment_table_record).
 		"""
 
@@ -0,0 +1,146 @@
+package metadata
+
+components: transforms: ip_enrichment: {
+	title: "IP Enrichment"
+
+	description: """
+		Enrich events with the data of their IP addresses, looked up in `geoip` or `mmdb`
+		enrichment tables, such as the MaxMind City, ASN, ISP, Connection Type, and Anonymous IP
+		databases, or any database in the MaxMind DB format.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		enrich: {
+			from: service: {
+				name:     "MaxMind DB databases"
+				url:      urls.maxmind_db_file_format
+				versions: ">= 2"
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		lookups: {
+			description: "The lookups to make for each event, in order."
+			required:    true
+			type: array: items: type: object: options: {
+				table: {
+					description: "The name of the enrichment table to look the IP address up in. The table must be a `geoip` or `mmdb` enrichment table."
+					required:    true
+					type: string: {
+						examples: ["geoip_city", "asn"]
+					}
+				}
+				source: {
+					description: "The field name that contains the IP address. This field should contain a valid IPv4 or IPv6 address."
+					required:    true
+					type: string: {
+						examples: ["ip_address", "x-forwarded-for", "parent.child", "array[0]"]
+					}
+				}
+				target: {
+					description: "The field to insert the data found for the IP address into. Nothing is inserted when the IP address isn't found."
+					required:    true
+					type: string: {
+						examples: ["geoip", "geo.{{ direction }}"]
+						syntax: "template"
+					}
+				}
+				fields: {
+					common:      false
+					description: "The fields of the data found to insert. By default, all of the fields are inserted."
+					required:    false
+					type: array: {
+						default: null
+						items: type: string: {
+							examples: ["country_code", "autonomous_system_number"]
+						}
+					}
+				}
+			}
+		}
+		cache: {
+			common:      false
+			description: "Options controlling how the data of recently looked up IP addresses is cached."
+			required:    false
+			type: object: {
+				options: {
+					max_entries: {
+						common:      true
+						description: "The number of IP addresses to cache the data of, for each lookup."
+						required:    false
+						type: uint: {
+							default: 10000
+							unit:    null
+						}
+					}
+					max_bytes: {
+						common:      false
+						description: "The maximum size of the cached data. By default, the size of the cache is only limited by `max_entries`."
+						required:    false
+						type: uint: {
+							default: null
+							unit:    "bytes"
+						}
+					}
+				}
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		supported_databases: {
+			title: "Supported databases"
+			body:  """
+				The lookups are made in `geoip` and `mmdb` [enrichment
+				tables](\(urls.enrichment_tables_concept)). The `geoip` table returns the
+				fields of the MaxMind City, ASN, ISP, Connection Type, and Anonymous IP databases,
+				while the `mmdb` table reads any database in the [MaxMind DB file
+				format](\(urls.maxmind_db_file_format)), returning its records as they are stored.
+				"""
+		}
+
+		cache_behavior: {
+			title: "Cache Behavior"
+			body: """
+				The data found for each IP address, or its absence, is cached in an LRU cache of
+				`cache.max_entries` entries, per lookup, and of `cache.max_bytes` bytes if set. The
+				least recently used IP addresses are evicted first. The cache is cleared when the
+				enrichment tables are reloaded, so that the data of an updated database is used as
+				soon as it is loaded.
+				"""
+		}
+
+		migrating_from_geoip: {
+			title: "Migrating from the `geoip` transform"
+			body: """
+				This transform replaces the deprecated `geoip` transform. The `database` and
+				`locale` of a `geoip` transform become those of a `geoip` enrichment table, and its
+				`source` and `target` those of a lookup in that table.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total: components.sources.internal_metrics.output.metrics.component_errors_total
+	}
+}
@@ -0,0 +1,110 @@
+package metadata
+
+components: transforms: join: {
+	title: "Join"
+
+	description: """
+		Correlates the events of two streams, such as requests and their responses, by joining the events of
+		both sides with the same values for a set of fields, within a time window. The events that aren't
+		joined within the window are sent to separate outputs.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		reduce: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		flush_period_ms: {
+			common:      false
+			description: "The interval to check for and flush any expired events, in milliseconds."
+			required:    false
+			type: uint: {
+				default: 1000
+				unit:    "milliseconds"
+			}
+		}
+		join_by: {
+			description: """
+				An ordered list of fields by which to join events. An event of the left side is joined with the first
+				pending event of the right side with the same values for these fields, and vice versa.
+				"""
+			required: true
+			type: array: items: type: string: {
+				examples: ["request_id", "host"]
+			}
+		}
+		left_when: {
+			description: """
+				A condition telling the events of the left side of the join apart from those of the right side. The
+				events that match it are on the left side, and all the other events are on the right side, so that
+				both streams can be `inputs` of the transform.
+				"""
+			required: true
+			type: condition: {}
+		}
+		window_ms: {
+			common: true
+			description: """
+				The maximum period of time to wait for the matching event of the other side, in milliseconds. The
+				events that aren't joined within this window are sent to the `left_unmatched` or `right_unmatched`
+				output.
+				"""
+			required: false
+			type: uint: {
+				default: 30000
+				unit:    "milliseconds"
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	examples: [
+		{
+			title: "Join requests with their responses"
+			input: [
+				{log: {type: "request", request_id:  "a1", path:   "/"}},
+				{log: {type: "response", request_id: "a1", status: 200}},
+			]
+			configuration: {
+				left_when: #".type == "request""#
+				join_by: ["request_id"]
+			}
+			output: [
+				{log: {type: "request", request_id: "a1", path: "/", status: 200}},
+			]
+		},
+	]
+
+	how_it_works: {
+		join_behavior: {
+			title: "Join Behavior"
+			body: """
+				The joined event holds the fields of the left event, along with the top-level fields of the right
+				event it doesn't have. The fields both events have keep the values of the left event.
+
+				The events of each side are joined in the order they were received. The events that are still
+				pending when the window expires, or when Vector shuts down, are sent unchanged to the
+				`<transform_name>.left_unmatched` or `<transform_name>.right_unmatched` output, depending on their
+				side.
+				"""
+		}
+	}
+}
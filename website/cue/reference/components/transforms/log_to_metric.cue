@@ -32,8 +32,9 @@ components: transforms: log_to_metric: {
 				examples: []
 				options: {
 					field: {
-						description: "The log field to use as the metric."
-						required:    true
+						description:   "The log field to use as the metric."
+						required:      true
+						relevant_when: #"type != "vrl""#
 						type: string: {
 							examples: ["duration", "parent.child"]
 						}
@@ -109,6 +110,30 @@ components: transforms: log_to_metric: {
 							}
 						}
 					}
+					source: {
+						description: """
+							The [Vector Remap Language](\(urls.vrl_reference)) (VRL) program computing the metrics.
+							It can read the event but not modify it, and must resolve to an object
+							describing a metric, to an array of such objects, or to `null`. Each
+							object has a `name`, a `type` (one of `counter`, `gauge`, `set`,
+							`histogram`, or `summary`), a `value`, and optionally a `namespace`, a
+							`kind` (`incremental` or `absolute`), and an object of `tags`.
+							"""
+						required:      true
+						relevant_when: #"type = "vrl""#
+						type: string: {
+							examples: [
+								"""
+									stats = parse_key_value!(.message)
+									[
+									  { "name": "requests_total", "type": "counter", "value": stats.requests },
+									  { "name": "queue_depth", "type": "gauge", "value": stats.queue },
+									]
+									""",
+							]
+							syntax: "remap_program"
+						}
+					}
 					type: {
 						description: "The metric type."
 						required:    true
@@ -119,6 +144,7 @@ components: transforms: log_to_metric: {
 								histogram: "A [distribution metric type](\(urls.vector_metric)#histogram) with histogram statistic."
 								set:       "A [set metric type](\(urls.vector_metric)#set)."
 								summary:   "A [distribution metric type](\(urls.vector_metric)#distribution) with summary statistic."
+								vrl:       "Any number of metrics, computed by the VRL program in `source`."
 							}
 						}
 					}
@@ -419,6 +445,17 @@ components: transforms: log_to_metric: {
 				individual metrics for reduction in the metrics storage itself.
 				"""
 		}
+		vrl_metrics: {
+			title: "Metrics computed with VRL"
+			body: """
+				A `vrl` entry runs its program against each log event, and emits one metric per
+				object the program resolves to. This makes it possible to derive several metrics
+				from a single field, such as the counters of a stats line, or to compute the
+				names, tags, and values of the metrics rather than reading them from fields.
+				Objects that don't describe a valid metric are reported as errors, and no
+				metric is emitted for the program's output on that event.
+				"""
+		}
 		null_fields: {
 			title: "Null Fields"
 			body: """
@@ -12,7 +12,7 @@ components: transforms: metric_to_log: {
 		commonly_used: true
 		development:   "stable"
 		egress_method: "stream"
-		stateful:      false
+		stateful:      true
 	}
 
 	features: {
@@ -35,6 +35,42 @@ components: transforms: metric_to_log: {
 				examples: ["host", "hostname"]
 			}
 		}
+		histogram_layout: {
+			common:      false
+			description: "How the buckets of aggregated and exponential histograms are laid out in logs."
+			required:    false
+			type: string: {
+				default: "nested"
+				enum: {
+					nested:     "A single log holds all the buckets of the histogram."
+					per_bucket: "One log is produced for each bucket, holding the bucket along with the total `count` and `sum` of the histogram."
+				}
+			}
+		}
+		histogram_layout_overrides: {
+			common:      false
+			description: "Overrides of `histogram_layout` for specific metrics, keyed by metric name."
+			required:    false
+			type: object: {
+				examples: [{"http_request_duration_seconds": "per_bucket"}]
+				options: {}
+			}
+		}
+		temporality: {
+			common: false
+			description: """
+				The aggregation temporality to convert metrics to before turning them into logs.
+				If not set, metrics keep their temporality.
+				"""
+			required: false
+			type: string: {
+				default: null
+				enum: {
+					delta:      "Absolute metrics are turned into the change since the previous value of their series."
+					cumulative: "Incremental metrics are turned into the running total of their series."
+				}
+			}
+		}
 		timezone: configuration._timezone
 	}
 
@@ -102,7 +138,19 @@ components: transforms: metric_to_log: {
 		},
 	]
 
-	how_it_works: {}
+	how_it_works: {
+		temporality: {
+			title: "Temporality conversion"
+			body: """
+				When `temporality` is set, the previous value of each series is kept in memory
+				so that absolute metrics can be turned into deltas, and incremental metrics into
+				running totals. Gauges are left as they are. The first absolute value of a series
+				is only used as the baseline of its deltas, so no log is produced for it. Since the
+				conversion relies on seeing every metric of a series in order, the transform is
+				not run concurrently in that case.
+				"""
+		}
+	}
 
 	telemetry: metrics: {
 		processing_errors_total: components.sources.internal_metrics.output.metrics.processing_errors_total
@@ -0,0 +1,124 @@
+package metadata
+
+components: transforms: redact_pii: {
+	title: "Redact PII"
+
+	description: """
+		Redacts personally identifiable information (PII), such as credit card
+		numbers, email addresses, and secrets, from all of the string fields of
+		log events, using a set of built-in detectors.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		sanitize: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		action: {
+			common:      true
+			description: "The action taken on the information that is found."
+			required:    false
+			type: string: {
+				default: "mask"
+				enum: {
+					mask:     "The information is replaced by the `replacement` text."
+					hash:     "The information is replaced by its SHA-256 hash, encoded as hexadecimal."
+					tokenize: "The information is replaced by a token, derived from the information and the `key` with HMAC-SHA256."
+					remove:   "The field, or array element, holding the information is removed from the event."
+				}
+			}
+		}
+		detectors: {
+			common:      true
+			description: "The detectors used to find personally identifiable information. All of the string fields of events are scanned, recursively."
+			required:    false
+			type: array: {
+				default: ["credit_card", "email", "us_social_security_number", "iban", "ip_address", "secret"]
+				items: type: string: {
+					enum: {
+						credit_card:               "Credit card numbers, validated with the Luhn checksum."
+						email:                     "Email addresses."
+						us_social_security_number: "US social security numbers."
+						iban:                      "International bank account numbers, validated with their checksum."
+						ip_address:                "IPv4 and IPv6 addresses."
+						secret:                    "Secrets, such as API keys, access tokens, private keys, and passwords, detected with heuristics."
+					}
+				}
+			}
+		}
+		key: {
+			common: false
+			description: """
+				The key used to derive tokens, required when `action` is `tokenize`. The same information is always
+				replaced by the same token for a given key, so that redacted events can still be correlated.
+				"""
+			required: false
+			type: string: {
+				default: null
+				examples: ["${PII_TOKENIZATION_KEY}"]
+			}
+		}
+		replacement: {
+			common:      false
+			description: "The text replacing the information that is found, when `action` is `mask`."
+			required:    false
+			type: string: {
+				default: "[REDACTED]"
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	examples: [
+		{
+			title: "Mask PII"
+			configuration: {}
+			input: log: {
+				message: "user jane@example.com paid with 4111 1111 1111 1111"
+				client: ip: "10.1.2.3"
+			}
+			output: log: {
+				message: "user [REDACTED] paid with [REDACTED]"
+				client: ip: "[REDACTED]"
+			}
+		},
+	]
+
+	how_it_works: {
+		detectors: {
+			title: "Detectors"
+			body: """
+				Detectors find information with regular expressions, which are complemented by checksums to reduce
+				false positives where the information has one, such as credit card numbers and IBANs. Secrets are
+				detected with heuristics, based on well-known formats of credentials (AWS access keys, GitHub, Slack and
+				Stripe tokens, JSON web tokens, private keys...) and on assignments to fields named like credentials,
+				such as `password=...`, in which case only the assigned value is redacted.
+
+				Unlike the VRL `redact` function, which is applied to specific fields, this transform scans all of the
+				string fields of events, recursively.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		pii_redactions_total: components.sources.internal_metrics.output.metrics.pii_redactions_total
+	}
+}
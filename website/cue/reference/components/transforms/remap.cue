@@ -104,6 +104,35 @@ components: transforms: "remap": {
 				"""
 			type: bool: default: false
 		}
+		persist_state: {
+			common:   false
+			required: false
+			description: """
+				Persist the state set with the `set_state` function in the data directory of the transform, so that
+				it survives restarts of Vector. The state is snapshot every `state_snapshot_interval_secs`, and when
+				the transform is stopped. Otherwise, the state is only kept in memory.
+				"""
+			type: bool: default: false
+		}
+		state_snapshot_interval_secs: {
+			common:      false
+			required:    false
+			description: "The interval between the snapshots of the state, when `persist_state` is enabled."
+			type: uint: {
+				default: 60
+				unit:    "seconds"
+			}
+		}
+		data_dir: {
+			common:      false
+			description: "The directory used to persist the state of the transform, when `persist_state` is enabled. By default, the global `data_dir` option is used. Please make sure the Vector project has write permissions to this dir."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["/var/lib/vector"]
+				syntax: "file_system_path"
+			}
+		}
 	}
 
 	input: {
@@ -206,6 +235,22 @@ components: transforms: "remap": {
 				```
 				"""#
 		}
+		state: {
+			title: "State"
+			body: #"""
+				The events processed by a remap transform can share values, with the `get_state` and `set_state`
+				functions, for logic spanning several events, such as detecting the first time a value is seen, gaps
+				in sequence numbers, or keeping counters. The state is only shared by the events of a single transform,
+				and its values can expire after a `ttl`.
+
+				```coffee
+				.first_seen = get_state(string!(.user_id)) == null
+				set_state(string!(.user_id), now(), ttl: 86400)
+				```
+
+				Values are persisted as JSON with `persist_state`, so timestamps are restored as strings.
+				"""#
+		}
 	}
 
 	outputs: [
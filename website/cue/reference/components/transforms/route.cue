@@ -28,6 +28,18 @@ components: transforms: route: {
 	}
 
 	configuration: {
+		reroute_unmatched: {
+			common: false
+			description: """
+				The route to which events that don't match any route are sent, instead of the `_unmatched` output.
+				This can be either a route or an output of `split`. When set, the `_unmatched` output doesn't exist.
+				"""
+			required: false
+			type: string: {
+				default: null
+				examples: ["stable"]
+			}
+		}
 		route: {
 			description: """
 				A table of route identifiers to logical conditions representing the filter of the route. Each route can
@@ -36,7 +48,7 @@ components: transforms: route: {
 				`_unmatched` is a reserved output name and cannot be used as a route name. `_default` is also reserved
 				for future use.
 				"""
-			required: true
+			required: false
 			type: object: {
 				options: {
 					"*": {
@@ -50,6 +62,52 @@ components: transforms: route: {
 				}
 			}
 		}
+		split: {
+			common: false
+			description: """
+				Splits events between weighted outputs. Each event is sent to exactly one of the outputs of the split,
+				independently of `route`, which enables canary deliveries such as sending 20% of the traffic to a new
+				pipeline. Outputs are referenced like routes, with the name `<transform_name>.<output_id>`.
+				"""
+			required: false
+			type: object: options: {
+				condition: {
+					common:      false
+					description: "A logical condition restricting which events are split. Events that don't match it are handled as if there was no split."
+					required:    false
+					type: condition: {}
+				}
+				key_field: {
+					common: true
+					description: """
+						The name of the field whose value is hashed to pick the output of an event, so that events with
+						the same value are consistently sent to the same output. For metrics, this is the name of a tag.
+						If left unspecified, or if the event doesn't have `key_field`, events are sent to outputs in
+						turn.
+						"""
+					required: false
+					type: string: {
+						default: null
+						examples: ["user_id", "host"]
+					}
+				}
+				weights: {
+					description: """
+						A table of output identifiers to their weight. Each output receives its share of events,
+						relative to the sum of all weights, so weights can be expressed as percentages.
+						"""
+					required: true
+					type: object: options: "*": {
+						description: "The weight of the output."
+						required:    true
+						type: uint: {
+							examples: [80, 20]
+							unit: null
+						}
+					}
+				}
+			}
+		}
 	}
 
 	input: {
@@ -112,6 +170,26 @@ components: transforms: route: {
 				namespace: "host"
 			}
 		},
+		{
+			title: "Canary delivery"
+
+			configuration: {
+				split: {
+					key_field: "user_id"
+					weights: {
+						stable: 80
+						canary: 20
+					}
+				}
+			}
+
+			input: log: {
+				user_id: "jane"
+			}
+			output: log: {
+				user_id: "jane"
+			}
+		},
 	]
 
 	outputs: [
@@ -119,5 +197,13 @@ components: transforms: route: {
 			name:        "<route_id>"
 			description: "Each route can be referenced as an input by other components with the name `<transform_name>.<route_id>`."
 		},
+		{
+			name:        "<split_output_id>"
+			description: "Each output of `split` can be referenced as an input by other components with the name `<transform_name>.<split_output_id>`."
+		},
+		{
+			name:        "_unmatched"
+			description: "Events that don't match any route, unless `reroute_unmatched` is set."
+		},
 	]
 }
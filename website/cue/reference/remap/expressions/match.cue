@@ -0,0 +1,104 @@
+package metadata
+
+remap: expressions: match: {
+	title: "Match"
+	description: """
+		A _match_ expression compares a value against a list of patterns, and evaluates the body of the first arm
+		whose pattern matches the value. Patterns can destructure objects and arrays, binding parts of the value to
+		variables that are only defined within the arm, and arms can be further restricted by a guard.
+		"""
+	return: """
+		The result of the body of the matching arm, or null if no arm matches.
+		"""
+
+	grammar: {
+		source: """
+			"match" ~ expression ~ "{" ~ (pattern ~ ("if" ~ guard)? ~ "=>" ~ body ~ ","?)* ~ "}"
+			"""
+		definitions: {
+			expression: {
+				description: """
+					The `expression` whose value is matched against the patterns of the arms, in order.
+					"""
+			}
+			pattern: {
+				description: """
+					The `pattern` the value is matched against. It can be one of:
+
+					* `_`, matching any value.
+					* A variable name, matching any value and binding it to the variable within the arm.
+					* A literal, matching an equal value. A regular expression literal matches the strings it
+					  matches.
+					* An array of patterns, such as `[first, _]`, matching an array of the same length whose elements
+					  match each of the patterns.
+					* An object of patterns, such as `{ "tag": "http", "status": status }`, matching an object that has
+					  all of the listed fields, with each of them matching its pattern. Other fields are ignored.
+					"""
+			}
+			guard: {
+				description: """
+					The `guard` _must_ be an expression that resolves to a Boolean, and can use the variables bound by
+					the pattern. If it resolves to `false`, the value is matched against the next arms.
+					"""
+			}
+			body: {
+				description: """
+					The `body` is evaluated if the pattern matches. Assignments and `abort` expressions have to be
+					wrapped in a block, such as `{ .severity = 3 }`. Arms are separated by commas or new lines.
+					"""
+			}
+		}
+	}
+
+	characteristics: {
+		exhaustiveness: {
+			title: "Exhaustiveness"
+			description: """
+				The compiler tracks the types of the values left unmatched by the arms without a guard. If values of
+				some type aren't matched by any arm, a warning is raised, as the expression then resolves to null.
+				Likewise, arms following an arm that already matches every remaining value raise a warning, as they
+				are never reached. The variables bound by an arm are typed according to the values that reach it.
+				"""
+		}
+	}
+
+	examples: [
+		{
+			title: "Destructuring objects"
+			input: log: {
+				tag:    "http"
+				status: 404
+				path:   "/index.html"
+			}
+			source: #"""
+				match . {
+					{ "tag": "dns", "query": query } => "dns query for " + string!(query),
+					{ "tag": "http", "status": 404, "path": path } => "not found: " + string!(path),
+					_ => "unknown"
+				}
+				"""#
+			return: "not found: /index.html"
+		},
+		{
+			title: "Guards"
+			source: #"""
+				match 3 {
+					0 => "zero"
+					n if n < 0 => "negative"
+					_ => "positive"
+				}
+				"""#
+			return: "positive"
+		},
+		{
+			title: "Destructuring arrays"
+			source: #"""
+				match split("GET /index.html", " ") {
+					[method, path] if method == "GET" => path,
+					_ => null
+				}
+				"""#
+			return: "/index.html"
+		},
+	]
+}
@@ -0,0 +1,51 @@
+package metadata
+
+remap: functions: all: {
+	category:    "Enumerate"
+	description: """
+		Check whether all elements of a collection match the closure.
+
+		The iteration stops at the first element for which the closure
+		returns `false`, without running the closure for the remaining
+		elements.
+
+		The function uses the "function closure syntax" to allow reading
+		the key/value or index/value combination for each item in the
+		collection.
+
+		The same scoping rules apply to closure blocks as they do for
+		regular blocks, meaning, any variable defined in parent scopes
+		are accessible, and mutations to those variables are preserved,
+		but any new variables instantiated in the closure block are
+		unavailable outside of the block.
+
+		Check out the examples below to learn about the closure syntax.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The array or object to check."
+			required:    true
+			type: ["array", "object"]
+		},
+	]
+	internal_failure_reasons: []
+	return: {
+		types: ["boolean"]
+	}
+	examples: [
+		{
+			title: "Match all elements"
+			input: log: {
+				values: [1, 2, 3]
+			}
+			source: #"""
+				all(array!(.values)) -> |_index, value| {
+				    int!(value) > 0
+				}
+				"""#
+			return: true
+		},
+	]
+}
@@ -0,0 +1,63 @@
+package metadata
+
+remap: functions: any: {
+	category:    "Enumerate"
+	description: """
+		Check whether any element of a collection matches the closure.
+
+		The iteration stops at the first element for which the closure
+		returns `true`, without running the closure for the remaining
+		elements.
+
+		The function uses the "function closure syntax" to allow reading
+		the key/value or index/value combination for each item in the
+		collection.
+
+		The same scoping rules apply to closure blocks as they do for
+		regular blocks, meaning, any variable defined in parent scopes
+		are accessible, and mutations to those variables are preserved,
+		but any new variables instantiated in the closure block are
+		unavailable outside of the block.
+
+		Check out the examples below to learn about the closure syntax.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The array or object to check."
+			required:    true
+			type: ["array", "object"]
+		},
+	]
+	internal_failure_reasons: []
+	return: {
+		types: ["boolean"]
+	}
+	examples: [
+		{
+			title: "Match any element"
+			input: log: {
+				tags: ["foo", "bar", "baz"]
+			}
+			source: #"""
+				any(array!(.tags)) -> |_index, value| {
+				    value == "bar"
+				}
+				"""#
+			return: true
+		},
+		{
+			title: "Match against a list of regular expressions"
+			input: log: {
+				message: "barbaz"
+			}
+			source: #"""
+				any([r'foo', r'bar']) -> |_index, pattern| {
+				    match!(.message, pattern)
+				}
+				"""#
+			return: true
+		},
+	]
+}
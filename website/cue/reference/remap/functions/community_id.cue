@@ -0,0 +1,72 @@
+package metadata
+
+remap: functions: community_id: {
+	category: "String"
+	description: """
+		Generates an ID based on the [Community ID Spec](https://github.com/corelight/community-id-spec),
+		a hash of the 5-tuple of a network flow that is the same for both of its directions. This allows
+		the events of tools like Zeek, Suricata or NetFlow exporters to be correlated.
+		"""
+	notices: [
+		"""
+			For ICMP and ICMPv6 flows, the ports are the ICMP type and code.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "source_ip"
+			description: "The source IP address."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "destination_ip"
+			description: "The destination IP address."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "protocol"
+			description: "The IANA protocol number, such as 6 for TCP or 17 for UDP."
+			required:    true
+			type: ["integer"]
+		},
+		{
+			name:        "source_port"
+			description: "The source port, required for TCP, UDP, SCTP, ICMP and ICMPv6 flows."
+			required:    false
+			type: ["integer"]
+		},
+		{
+			name:        "destination_port"
+			description: "The destination port, required for TCP, UDP, SCTP, ICMP and ICMPv6 flows."
+			required:    false
+			type: ["integer"]
+		},
+		{
+			name:        "seed"
+			description: "The seed of the hash, to tell the flows of different networks apart."
+			required:    false
+			default:     0
+			type: ["integer"]
+		},
+	]
+	internal_failure_reasons: [
+		"`source_ip` or `destination_ip` isn't a valid IP address",
+		"`source_ip` and `destination_ip` aren't of the same IP address family",
+		"`protocol` is a port-based protocol and `source_port` or `destination_port` isn't set",
+		"`protocol`, a port or `seed` is out of range",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "TCP"
+			source: #"""
+				community_id!(source_ip: "1.2.3.4", destination_ip: "5.6.7.8", source_port: 1122, destination_port: 3344, protocol: 6)
+				"""#
+			return: "1:wCb3OG7yAFWelaUydu0D+125CLM="
+		},
+	]
+}
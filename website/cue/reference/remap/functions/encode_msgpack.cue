@@ -0,0 +1,35 @@
+package metadata
+
+remap: functions: encode_msgpack: {
+	category: "Codec"
+	description: """
+		Encodes the `value` to MessagePack.
+		"""
+	notices: [
+		"""
+			Strings that aren't valid UTF-8 are encoded as MessagePack binaries, and timestamps are
+			encoded as RFC 3339 strings.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The value to encode to MessagePack."
+			required:    true
+			type: ["any"]
+		},
+	]
+	internal_failure_reasons: []
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Encode to MessagePack"
+			source: #"""
+				encode_base64(encode_msgpack({"foo": "bar"}))
+				"""#
+			return: "gaNmb2+jYmFy"
+		},
+	]
+}
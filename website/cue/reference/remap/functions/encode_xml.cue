@@ -0,0 +1,79 @@
+package metadata
+
+remap: functions: encode_xml: {
+	category: "Codec"
+	description: """
+		Encodes the `value`, which must have a single key naming the root element, to XML.
+		"""
+	notices: [
+		"""
+			This is the counterpart of [`parse_xml`](#parse_xml): keys starting with `attr_prefix` are
+			encoded as attributes, the `text_key` key as the text of the element, and any other key as a
+			child element, repeated for each item of an array. `null` values are encoded as empty
+			elements.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The object to encode to XML."
+			required:    true
+			type: ["object"]
+		},
+		{
+			name:        "attr_prefix"
+			description: "String prefix of the keys encoded as attributes."
+			required:    false
+			default:     "@"
+			type: ["string"]
+		},
+		{
+			name:        "text_key"
+			description: "Key of the text of elements that have attributes or children."
+			required:    false
+			default:     "text"
+			type: ["string"]
+		},
+		{
+			name: "namespaces"
+			description: """
+				Namespace URIs to declare on the root element, keyed by prefix. The empty prefix declares
+				the default namespace.
+				"""
+			required: false
+			type: ["object"]
+		},
+		{
+			name:        "include_declaration"
+			description: "Start the document with an XML declaration."
+			required:    false
+			default:     false
+			type: ["boolean"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` doesn't have exactly one key",
+		"`value` contains a key that isn't a valid XML name",
+		"`value` contains an attribute or text that is an object or an array",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Encode to XML"
+			source: #"""
+				encode_xml!({"book": {"@category": "CHILDREN", "title": {"@lang": "en", "text": "Harry Potter"}, "year": 2005}})
+				"""#
+			return: #"<book category="CHILDREN"><title lang="en">Harry Potter</title><year>2005</year></book>"#
+		},
+		{
+			title: "Round-trip a SOAP payload"
+			source: #"""
+				payload = parse_xml!(s'<soap:Envelope xmlns:soap="urn:soap"><soap:Body>ok</soap:Body></soap:Envelope>', include_namespaces: true)
+				encode_xml!(payload)
+				"""#
+			return: #"<soap:Envelope xmlns:soap="urn:soap"><soap:Body>ok</soap:Body></soap:Envelope>"#
+		},
+	]
+}
@@ -0,0 +1,51 @@
+package metadata
+
+remap: functions: find_item: {
+	category:    "Enumerate"
+	description: """
+		Find the first element of a collection that matches the closure.
+
+		The iteration stops at the first element for which the closure
+		returns `true`, and that element is returned. If none of the
+		elements match, `null` is returned.
+
+		The function uses the "function closure syntax" to allow reading
+		the key/value or index/value combination for each item in the
+		collection.
+
+		The same scoping rules apply to closure blocks as they do for
+		regular blocks, meaning, any variable defined in parent scopes
+		are accessible, and mutations to those variables are preserved,
+		but any new variables instantiated in the closure block are
+		unavailable outside of the block.
+
+		Check out the examples below to learn about the closure syntax.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The array or object to search."
+			required:    true
+			type: ["array", "object"]
+		},
+	]
+	internal_failure_reasons: []
+	return: {
+		types: ["any"]
+	}
+	examples: [
+		{
+			title: "Find an element"
+			input: log: {
+				users: [{"name": "foo", "admin": false}, {"name": "bar", "admin": true}]
+			}
+			source: #"""
+				find_item(array!(.users)) -> |_index, value| {
+				    value.admin == true
+				}
+				"""#
+			return: {"name": "bar", "admin": true}
+		},
+	]
+}
@@ -0,0 +1,33 @@
+package metadata
+
+remap: functions: get_state: {
+	category: "Event"
+	description: """
+		Returns the value of the given key of the state of the transform, shared by all the events it
+		processes, or `null` if the key has no value or its value expired.
+
+		The state is only available in the `remap` transform.
+		"""
+
+	arguments: [
+		{
+			name:        "key"
+			description: "The key of the value."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+	]
+	return: types: ["any"]
+
+	examples: [
+		{
+			title: "Get a value of the state."
+			source: #"""
+				get_state("last_seen")
+				"""#
+			return: "2022-11-02T10:30:00Z"
+		},
+	]
+}
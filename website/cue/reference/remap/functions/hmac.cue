@@ -0,0 +1,58 @@
+package metadata
+
+remap: functions: hmac: {
+	category: "Cryptography"
+	description: """
+		Calculates a [HMAC](https://en.wikipedia.org/wiki/HMAC) of the `value` using the given `key`.
+		The returned HMAC is raw bytes, which can be encoded with
+		[`encode_base64`](#encode_base64) for instance.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to calculate the HMAC for."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "key"
+			description: "The secret key to calculate the HMAC with."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "algorithm"
+			description: "The hash algorithm to use."
+			enum: {
+				"SHA1":    "SHA1 algorithm"
+				"SHA-224": "SHA-224 algorithm"
+				"SHA-256": "SHA-256 algorithm"
+				"SHA-384": "SHA-384 algorithm"
+				"SHA-512": "SHA-512 algorithm"
+			}
+			required: false
+			default:  "SHA-256"
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: []
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Calculate a HMAC of a value"
+			source: #"""
+				encode_base64(hmac("Hello there", "super-secret-key"))
+				"""#
+			return: "eLGE8YMviv85NPXgISRUZxstBNSU47JQdcXkUWcClmI="
+		},
+		{
+			title: "Calculate a HMAC with the SHA1 algorithm"
+			source: #"""
+				encode_base64(hmac("Hello there", "super-secret-key", algorithm: "SHA1"))
+				"""#
+			return: "MiyBIHO8Set9+6crALiwkS0yFPE="
+		},
+	]
+}
@@ -0,0 +1,56 @@
+package metadata
+
+remap: functions: map_while: {
+	category:    "Enumerate"
+	description: """
+		Map the elements of a collection, until the closure returns `null`.
+
+		The elements are replaced by the return value of the closure. The
+		iteration stops at the first element for which the closure returns
+		`null`, and the remaining elements are dropped.
+
+		Unlike `map_values`, the closure can read the key or index of each
+		element.
+
+		The function uses the "function closure syntax" to allow reading
+		the key/value or index/value combination for each item in the
+		collection.
+
+		The same scoping rules apply to closure blocks as they do for
+		regular blocks, meaning, any variable defined in parent scopes
+		are accessible, and mutations to those variables are preserved,
+		but any new variables instantiated in the closure block are
+		unavailable outside of the block.
+
+		Check out the examples below to learn about the closure syntax.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The array or object to map."
+			required:    true
+			type: ["array", "object"]
+		},
+	]
+	internal_failure_reasons: []
+	return: {
+		types: ["array", "object"]
+	}
+	examples: [
+		{
+			title: "Map elements until one is empty"
+			input: log: {
+				lines: ["foo", "bar", "", "baz"]
+			}
+			source: #"""
+				map_while(array!(.lines)) -> |index, value| {
+				    if value != "" {
+				        to_string(index) + ": " + string!(value)
+				    }
+				}
+				"""#
+			return: ["0: foo", "1: bar"]
+		},
+	]
+}
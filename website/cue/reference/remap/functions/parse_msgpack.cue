@@ -0,0 +1,47 @@
+package metadata
+
+remap: functions: parse_msgpack: {
+	category: "Parse"
+	description: """
+		Parses the `value` as a single MessagePack value.
+		"""
+	notices: [
+		"""
+			MessagePack strings and binaries are both returned as strings. The timestamp extension, and
+			the `EventTime` extension used by the fluentd forward protocol, are returned as timestamps,
+			while the data of any other extension is returned as a string.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The MessagePack payload to parse."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a valid MessagePack payload",
+		"`value` contains data after the parsed value",
+		"`value` contains an unsigned integer greater than the maximum signed 64-bit integer",
+	]
+	return: types: ["boolean", "integer", "float", "string", "timestamp", "object", "array", "null"]
+
+	examples: [
+		{
+			title: "Parse MessagePack"
+			source: #"""
+				parse_msgpack!(decode_base64!("gaNmb2+jYmFy"))
+				"""#
+			return: foo: "bar"
+		},
+		{
+			title: "Parse a fluentd EventTime"
+			source: #"""
+				parse_msgpack!(decode_base64!("1wBZaC8AAAAABQ=="))
+				"""#
+			return: "2017-07-14T02:40:00.000000005Z"
+		},
+	]
+}
@@ -25,6 +25,17 @@ remap: functions: parse_xml: {
 			default:     true
 			type: ["boolean"]
 		},
+		{
+			name: "include_namespaces"
+			description: """
+				Keep the namespace prefixes of element and attribute names, and include the namespace
+				declarations of each element as `xmlns` attributes, so that the document can be encoded
+				back with [`encode_xml`](#encode_xml).
+				"""
+			required: false
+			default:  false
+			type: ["boolean"]
+		},
 		{
 			name:        "attr_prefix"
 			description: "String prefix to use for XML tag attribute keys."
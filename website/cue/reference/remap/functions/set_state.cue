@@ -0,0 +1,46 @@
+package metadata
+
+remap: functions: set_state: {
+	category: "Event"
+	description: """
+		Sets the value of the given key of the state of the transform, shared by all the events it
+		processes. Setting the value to `null` removes the key.
+
+		The state is only available in the `remap` transform.
+		"""
+
+	arguments: [
+		{
+			name:        "key"
+			description: "The key of the value."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "value"
+			description: "The value."
+			required:    true
+			type: ["any"]
+		},
+		{
+			name:        "ttl"
+			description: "The number of seconds after which the value expires. By default, the value never expires."
+			required:    false
+			type: ["integer", "float"]
+		},
+	]
+	internal_failure_reasons: [
+		"`ttl` is not a positive number.",
+	]
+	return: types: ["null"]
+
+	examples: [
+		{
+			title: "Set a value of the state for an hour."
+			source: #"""
+				set_state!("last_seen", "2022-11-02T10:30:00Z", ttl: 3600)
+				"""#
+			return: null
+		},
+	]
+}
@@ -0,0 +1,74 @@
+package metadata
+
+remap: functions: xpath: {
+	category: "Parse"
+	description: """
+		Selects the values matching an XPath-like `path` in the XML document `value`, returning the text
+		of the matching elements, or the matching attribute values, in document order.
+		"""
+	notices: [
+		"""
+			Only a subset of XPath is supported: absolute paths made of `/` and `//` steps, `*`, `.`,
+			`@name` and `text()` steps, and the `[1]`, `[@name]`, `[@name='value']` and `[name='value']`
+			predicates.
+			""",
+		"""
+			Unprefixed names match elements and attributes regardless of their namespace. Prefixed names
+			are resolved against `namespaces`, then against the namespaces declared in the document.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string representation of the XML document to select values from."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "path"
+			description: "The path of the elements or attributes to select."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "namespaces"
+			description: "The namespace URIs of the prefixes used in `path`, keyed by prefix."
+			required:    false
+			type: ["object"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a valid XML document",
+		"`path` isn't a supported path",
+	]
+	return: types: ["array"]
+
+	examples: [
+		{
+			title: "Select elements"
+			source: #"""
+				xpath!(s'<books><book><title>Dune</title></book><book><title>Emma</title></book></books>', "//book/title")
+				"""#
+			return: ["Dune", "Emma"]
+		},
+		{
+			title: "Select attributes"
+			source: #"""
+				xpath!(s'<book category="SF"><title lang="en">Dune</title></book>', "/book/title/@lang")
+				"""#
+			return: ["en"]
+		},
+		{
+			title: "Select with a predicate and namespaces"
+			source: #"""
+				xpath!(
+					s'<s:Envelope xmlns:s="urn:soap"><s:Body><item id="1">a</item><item id="2">b</item></s:Body></s:Envelope>',
+					"/soap:Envelope/soap:Body/item[@id='2']",
+					namespaces: {"soap": "urn:soap"}
+				)
+				"""#
+			return: ["b"]
+		},
+	]
+}
@@ -29,5 +29,8 @@ remap: syntax: keywords: {
 		* `until`
 		* `use`
 		* `while`
+
+		`match` is a contextual keyword: it only starts a match expression when it's followed by the value to match on,
+		and can otherwise be used as a variable name.
 		"""
 }
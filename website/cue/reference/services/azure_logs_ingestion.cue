@@ -0,0 +1,10 @@
+package metadata
+
+services: azure_logs_ingestion: {
+	name:     "Azure Monitor Logs"
+	thing:    "a \(name) workspace"
+	url:      urls.azure_logs_ingestion_api
+	versions: null
+
+	description: "[Azure Monitor Logs](\(urls.azure_monitor)) stores the logs of Azure, other cloud environments and on-premises systems in Log Analytics workspaces, which back [Microsoft Sentinel](\(urls.microsoft_sentinel)). The Logs Ingestion API sends data to these workspaces through Data Collection Rules, which define the schema of the data and the table it is stored in."
+}
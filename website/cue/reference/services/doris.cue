@@ -0,0 +1,10 @@
+package metadata
+
+services: doris: {
+	name:     "Apache Doris"
+	thing:    "an \(name) or StarRocks database"
+	url:      urls.doris
+	versions: null
+
+	description: "[Apache Doris](\(urls.doris)) is an open-source, real-time analytical database based on an MPP architecture, which ingests large volumes of data through its Stream Load HTTP protocol. [StarRocks](\(urls.starrocks)), a fork of Doris, supports the same protocol."
+}
@@ -0,0 +1,8 @@
+package metadata
+
+services: ebpf: {
+	name:     "eBPF"
+	thing:    "the Linux kernel"
+	url:      urls.ebpf
+	versions: ">= 5.8"
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: google_workspace: {
+	name:     "Google Workspace"
+	thing:    "a \(name) account"
+	url:      urls.google_workspace
+	versions: null
+
+	description: "[Google Workspace](\(urls.google_workspace)) is a suite of productivity services. The [Admin SDK Reports API](\(urls.google_workspace_reports_api)) exposes the activities of the users and administrators of an account."
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: microsoft_365: {
+	name:     "Microsoft 365"
+	thing:    "a \(name) tenant"
+	url:      urls.microsoft_365
+	versions: null
+
+	description: "[Microsoft 365](\(urls.microsoft_365)) is a suite of productivity services. The [Office 365 Management Activity API](\(urls.microsoft_365_management_activity_api)) exposes the audit logs of the user, administrator, system and policy actions of a tenant."
+}
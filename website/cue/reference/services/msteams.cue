@@ -0,0 +1,10 @@
+package metadata
+
+services: msteams: {
+	name:     "Microsoft Teams"
+	thing:    "a \(name) channel"
+	url:      urls.msteams
+	versions: null
+
+	description: "[Microsoft Teams](\(urls.msteams)) is the chat and collaboration platform of Microsoft 365, whose channels can receive messages through [incoming webhooks](\(urls.msteams_incoming_webhooks))."
+}
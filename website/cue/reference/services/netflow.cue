@@ -0,0 +1,8 @@
+package metadata
+
+services: netflow: {
+	name:     "NetFlow"
+	thing:    "NetFlow, IPFIX and sFlow exporters"
+	url:      urls.netflow
+	versions: null
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: okta: {
+	name:     "Okta"
+	thing:    "an \(name) organization"
+	url:      urls.okta
+	versions: null
+
+	description: "[Okta](\(urls.okta)) is an identity and access management service. Its [System Log](\(urls.okta_system_log_api)) records the authentication and administration events of an organization."
+}
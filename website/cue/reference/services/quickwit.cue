@@ -0,0 +1,10 @@
+package metadata
+
+services: quickwit: {
+	name:     "Quickwit"
+	thing:    "a \(name) cluster"
+	url:      urls.quickwit
+	versions: null
+
+	description: "[Quickwit](\(urls.quickwit)) is an open-source, cloud-native search engine for logs and traces, which stores its indexes on object storage."
+}
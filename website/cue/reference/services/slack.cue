@@ -0,0 +1,10 @@
+package metadata
+
+services: slack: {
+	name:     "Slack"
+	thing:    "a \(name) workspace"
+	url:      urls.slack
+	versions: null
+
+	description: "[Slack](\(urls.slack)) is a messaging platform for teams, commonly used to deliver alerts to the people on call."
+}
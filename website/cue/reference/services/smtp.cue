@@ -0,0 +1,10 @@
+package metadata
+
+services: smtp: {
+	name:     "SMTP"
+	thing:    "an \(name) server"
+	url:      urls.smtp
+	versions: null
+
+	description: "The [Simple Mail Transfer Protocol](\(urls.smtp)) is the standard protocol for sending email, supported by every mail server and email delivery service."
+}
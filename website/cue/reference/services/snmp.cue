@@ -0,0 +1,8 @@
+package metadata
+
+services: snmp: {
+	name:     "SNMP"
+	thing:    "SNMP agents"
+	url:      urls.snmp
+	versions: null
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: splunk_signalfx: {
+	name:     "Splunk Observability Cloud"
+	thing:    "a \(name) organization"
+	url:      urls.splunk_signalfx
+	versions: null
+
+	description: "[Splunk Observability Cloud](\(urls.splunk_signalfx)), formerly SignalFx, is a SaaS platform for infrastructure monitoring and application performance monitoring, built on streaming analytics of metrics and events."
+}
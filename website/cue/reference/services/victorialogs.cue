@@ -0,0 +1,10 @@
+package metadata
+
+services: victorialogs: {
+	name:     "VictoriaLogs"
+	thing:    "a \(name) database"
+	url:      urls.victorialogs
+	versions: null
+
+	description: "[VictoriaLogs](\(urls.victorialogs)) is a log database from the VictoriaMetrics team, designed for high ingestion rates and low resource usage."
+}
@@ -4,6 +4,7 @@ urls: {
 	apex:                                       "https://apex.sh/logs/"
 	azure_blob_storage:                         "https://azure.microsoft.com/en-us/services/storage/blobs/"
 	affine_type_system:                         "\(wikipedia)/wiki/Substructural_type_system#Affine_type_systems"
+	adaptive_cards:                             "https://adaptivecards.io"
 	adaptive_request_concurrency_post:          "/blog/adaptive-request-concurrency/"
 	amazon_linux:                               "https://aws.amazon.com/amazon-linux-ami/"
 	amqp_protocol:                              "https://www.amqp.org/about/what"
@@ -26,6 +27,7 @@ urls: {
 	aws_cloudwatch_logs:                        "\(aws_docs)/AmazonCloudWatch/latest/logs/WhatIsCloudWatchLogs.html"
 	aws_cloudwatch_logs_api:                    "\(aws_docs)/AmazonCloudWatchLogs/latest/APIReference/Welcome.html"
 	aws_cloudwatch_logs_firehose:               "\(aws_docs)/firehose/latest/dev/writing-with-cloudwatch-logs.html"
+	aws_cloudwatch_logs_filter_pattern:         "\(aws_docs)/AmazonCloudWatch/latest/logs/FilterAndPatternSyntax.html"
 	aws_cloudwatch_logs_group_name:             "\(aws_docs)/AmazonCloudWatch/latest/logs/Working-with-log-groups-and-streams.html"
 	aws_cloudwatch_logs_service_limits:         "\(aws_docs)/AmazonCloudWatch/latest/logs/cloudwatch_limits_cwl.html"
 	aws_cloudwatch_logs_sink_source:            "\(vector_repo)/blob/master/src/sinks/aws_cloudwatch_logs/"
@@ -52,6 +54,7 @@ urls: {
 	aws_iam:                                    "\(aws_docs)/IAM/latest/UserGuide/introduction.html"
 	aws_iam_role:                               "\(aws_docs)/IAM/latest/UserGuide/id_roles.html"
 	aws_imds_v1_security_problems:              "https://aws.amazon.com/blogs/security/defense-in-depth-open-firewalls-reverse-proxies-ssrf-vulnerabilities-ec2-instance-metadata-service/"
+	aws_kinesis_aggregation:                    "https://github.com/awslabs/amazon-kinesis-producer/blob/master/aggregation-format.md"
 	aws_kinesis_firehose:                       "https://aws.amazon.com/kinesis/data-firehose/"
 	aws_kinesis_firehose_http_protocol:         "\(aws_docs)/firehose/latest/dev/create-destination.html#create-destination-http"
 	aws_firehose_http_request_spec:             "\(aws_docs)/firehose/latest/dev/httpdeliveryrequestresponse.html"
@@ -73,6 +76,7 @@ urls: {
 	aws_s3_endpoints:                           "\(aws_docs)/general/latest/gr/rande.html#s3_endpoint"
 	aws_s3_grantee:                             "\(aws_docs)/AmazonS3/latest/dev/acl-overview.html#specifying-grantee"
 	aws_s3_metadata:                            "\(aws_docs)/AmazonS3/latest/dev/UsingMetadata.html#object-metadata"
+	aws_s3_multipart_upload:                    "\(aws_docs)/AmazonS3/latest/userguide/mpuoverview.html"
 	aws_s3_regions:                             "\(aws_docs)/general/latest/gr/rande.html#s3_region"
 	aws_s3_server_access_logs:                  "\(aws_docs)/AmazonS3/latest/dev/ServerLogs.html"
 	aws_s3_service_limits:                      "\(aws_docs)/streams/latest/dev/service-sizes-and-limits.html"
@@ -86,10 +90,16 @@ urls: {
 	aws_vpc_flow_logs:                          "\(aws_docs)/vpc/latest/userguide/flow-logs.html"
 	axiom:                                      "https://axiom.co"
 	axiom_cloud:                                "https://cloud.axiom.co"
+	azure_append_blobs:                         "https://docs.microsoft.com/en-us/rest/api/storageservices/understanding-block-blobs--append-blobs--and-page-blobs#about-append-blobs"
 	azure_blob:                                 "https://azure.microsoft.com/en-us/services/storage/blobs/"
 	azure_blob_endpoints:                       "https://docs.microsoft.com/en-us/rest/api/storageservices/blob-service-rest-api"
+	azure_data_collection_endpoints:            "https://learn.microsoft.com/en-us/azure/azure-monitor/essentials/data-collection-endpoint-overview"
+	azure_data_collection_rules:                "https://learn.microsoft.com/en-us/azure/azure-monitor/essentials/data-collection-rule-overview"
+	azure_event_grid_blob_events:               "https://docs.microsoft.com/en-us/azure/storage/blobs/storage-blob-event-overview"
+	azure_logs_ingestion_api:                   "https://learn.microsoft.com/en-us/azure/azure-monitor/logs/logs-ingestion-api-overview"
 	azure_monitor:                              "https://azure.microsoft.com/en-us/services/monitor/"
 	azure_monitor_logs_endpoints:               "https://docs.microsoft.com/en-us/rest/api/monitor/"
+	azure_storage_queues:                       "https://docs.microsoft.com/en-us/azure/storage/queues/storage-queues-introduction"
 	base64:                                     "\(wikipedia)/wiki/Base64"
 	base64_padding:                             "\(wikipedia)/wiki/Base64#Output_padding"
 	base64_standard:                            "https://tools.ietf.org/html/rfc4648#section-4"
@@ -99,6 +109,7 @@ urls: {
 	bind_dnstap:                                "https://kb.isc.org/docs/aa-01342"
 	b_tree_map:                                 "https://doc.rust-lang.org/std/collections/struct.BTreeMap.html"
 	cargo_audit:                                "\(github)/RustSec/cargo-audit"
+	cel:                                        "\(github)/google/cel-spec"
 	centos:                                     "https://www.centos.org/"
 	chrono_time_formats:                        "https://docs.rs/chrono/latest/chrono/format/strftime/index.html#specifiers"
 	cgroups_limit_resources:                    "https://the.binbashtheory.com/control-resources-cgroups/"
@@ -162,10 +173,13 @@ urls: {
 	docker_setup:                               "\(docker_docs)/get-docker/"
 	dockerfile:                                 "\(vector_repo)/blob/master/Dockerfile"
 	dogstatsd:                                  "\(datadog_docs)/developers/dogstatsd/?tab=hostagent"
+	doris:                                      "https://doris.apache.org/"
+	doris_stream_load:                          "https://doris.apache.org/docs/data-operate/import/import-way/stream-load-manual"
 	dot_format:                                 "https://graphviz.org/doc/info/lang.html"
 	dpkg:                                       "https://wiki.debian.org/dpkg"
 	dry_code:                                   "\(wikipedia)/wiki/Don%27t_repeat_yourself"
 	cidr:                                       "\(wikipedia)/wiki/Classless_Inter-Domain_Routing"
+	ebpf:                                       "https://ebpf.io/"
 	elastic_beats:                              "https://www.elastic.co/beats/"
 	elasticsearch:                              "https://www.elastic.co/products/elasticsearch"
 	elasticsearch_bulk:                         "https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-bulk.html"
@@ -198,8 +212,12 @@ urls: {
 	gcp_authentication_api_key:                 "\(gcp)/docs/authentication/api-keys"
 	gcp_authentication_server_to_server:        "\(gcp)/docs/authentication/production"
 	gcp_authentication_service_account:         "\(gcp)/docs/authentication/production#obtaining_and_providing_service_account_credentials_manually"
+	gcp_bigquery:                               "\(gcp)/bigquery/"
+	gcp_bigquery_write_api:                     "\(gcp)/bigquery/docs/write-api"
 	gcp_cloud_storage:                          "\(gcp)/storage"
 	gcp_chronicle:                              "https://chronicle.security"
+	gcp_chronicle_ingestion_api:                "https://cloud.google.com/chronicle/docs/reference/ingestion-api"
+	gcp_chronicle_udm:                          "https://cloud.google.com/chronicle/docs/reference/udm-field-list"
 	gcp_folders:                                "\(gcp)/resource-manager/docs/creating-managing-folders"
 	gcp_pubsub:                                 "\(gcp)/pubsub/"
 	gcp_pubsub_rest:                            "\(gcp)/pubsub/docs/reference/rest/"
@@ -223,6 +241,9 @@ urls: {
 	github_sign_commits:                        "https://help.github.com/en/github/authenticating-to-github/signing-commits"
 	globbing:                                   "\(wikipedia)/wiki/Glob_(programming)"
 	glog:                                       "\(github)/google/glog"
+	google_workspace:                           "https://workspace.google.com/"
+	google_workspace_domain_wide_delegation:    "https://developers.google.com/admin-sdk/reports/v1/guides/delegation"
+	google_workspace_reports_api:               "https://developers.google.com/admin-sdk/reports/v1/get-start/overview"
 	graphql:                                    "https://graphql.org"
 	graphql_playground:                         "\(github)/graphql/graphql-playground"
 	graphviz:                                   "https://graphviz.org/"
@@ -239,7 +260,9 @@ urls: {
 	homebrew_services:                          "\(github)/Homebrew/homebrew-services"
 	honeycomb:                                  "https://honeycomb.io"
 	honeycomb_batch:                            "https://docs.honeycomb.io/api/events/#batched-events"
+	honeycomb_markers:                          "https://docs.honeycomb.io/working-with-your-data/markers/"
 	honeycomb_signup:                           "https://ui.honeycomb.io/signup"
+	honeycomb_tracing:                          "https://docs.honeycomb.io/getting-data-in/tracing/send-trace-data/#manual-tracing"
 	host:                                       "\(wikipedia)/wiki/Host_(network)"
 	http:                                       "https://www.w3.org/Protocols/"
 	http_client:                                "\(wikipedia)/wiki/Hypertext_Transfer_Protocol#Client_request"
@@ -282,6 +305,7 @@ urls: {
 	kubernetes_accessing_api_from_pod:          "\(kubernetes)/docs/tasks/access-application-cluster/access-cluster/#accessing-the-api-from-a-pod"
 	kubernetes_api:                             "\(kubernetes)/docs/concepts/overview/kubernetes-api/"
 	kubernetes_api_server:                      "\(kubernetes)/docs/reference/command-line-tools-reference/kube-apiserver/"
+	kubernetes_auditing:                        "\(kubernetes)/docs/tasks/debug/debug-cluster/audit/"
 	kubernetes_authorization:                   "\(kubernetes)/docs/reference/access-authn-authz/authorization/"
 	kubernetes_daemonset:                       "\(kubernetes)/docs/concepts/workloads/controllers/daemonset/"
 	kubernetes_example_daemonset:               "\(vector_repo)/blob/master/config/kubernetes/vector-daemonset.yaml"
@@ -340,15 +364,23 @@ urls: {
 	memory_safety:                              "\(wikipedia)/wiki/Memory_safety"
 	memory_safety_bugs:                         "https://thenewstack.io/microsoft-rust-is-the-industrys-best-chance-at-safe-systems-programming/"
 	metric_event_source:                        "\(vector_repo)/blob/master/src/event/metric.rs"
+	microsoft_365:                              "https://www.microsoft.com/microsoft-365"
+	microsoft_365_management_activity_api:      "https://learn.microsoft.com/en-us/office/office-365-management-api/office-365-management-activity-api-reference"
+	microsoft_sentinel:                         "https://learn.microsoft.com/en-us/azure/sentinel/overview"
 	mlua:                                       "\(github)/khvzak/mlua"
 	mongodb:                                    "https://www.mongodb.com"
 	mongodb_command_server_status:              "https://docs.mongodb.com/manual/reference/command/serverStatus/"
 	mongodb_connection_string_uri_format:       "https://docs.mongodb.com/manual/reference/connection-string/"
+	msteams:                                    "https://www.microsoft.com/microsoft-teams"
+	msteams_incoming_webhooks:                  "https://learn.microsoft.com/en-us/microsoftteams/platform/webhooks-and-connectors/how-to/add-incoming-webhook"
 	musl_builder_docker_image:                  "\(vector_repo)/blob/master/scripts/ci-docker-images/builder-x86_64-unknown-linux-musl/Dockerfile"
+	mysql:                                      "https://www.mysql.com/"
+	mysql_gtids:                                "https://dev.mysql.com/doc/refman/8.0/en/replication-gtids-concepts.html"
 	native_proto_schema:                        "\(vector_repo)/blob/master/lib/vector-core/proto/event.proto"
 	native_json_schema:                         "\(vector_repo)/blob/master/lib/codecs/tests/data/native_encoding/schema.cue"
 	nats:                                       "https://nats.io/"
 	nats_rs:                                    "\(github)/nats-io/nats.rs"
+	netflow:                                    "\(wikipedia)/wiki/NetFlow"
 	new_bug_report:                             "\(vector_repo)/issues/new?labels=type%3A+bug"
 	new_feature_request:                        "\(vector_repo)/issues/new?labels=type%3A+new+feature"
 	new_relic:                                  "https://newrelic.com/"
@@ -362,14 +394,20 @@ urls: {
 	nginx:                                      "https://www.nginx.com/"
 	nginx_combined:                             "https://nginx.org/en/docs/http/ngx_http_log_module.html"
 	nginx_error:                                "https://github.com/nginx/nginx/blob/branches/stable-1.18/src/core/ngx_log.c#L102"
+	nginx_ingress_log_format:                   "https://kubernetes.github.io/ingress-nginx/user-guide/nginx-configuration/log-format/"
 	nginx_stub_status_module:                   "http://nginx.org/en/docs/http/ngx_http_stub_status_module.html"
 	nix:                                        "https://nixos.org/nix/"
 	nixos:                                      "https://nixos.org/"
 	nixpkgs_9682:                               "\(github)/NixOS/nixpkgs/issues/9682"
+	okta:                                       "https://www.okta.com/"
+	okta_api_token:                             "https://developer.okta.com/docs/guides/create-an-api-token/main/"
+	okta_system_log_api:                        "https://developer.okta.com/docs/reference/api/system-log/"
 	openssl:                                    "https://www.openssl.org/"
 	opentelemetry:                              "https://opentelemetry.io"
 	opentelemetry_protocol:                     "\(opentelemetry)/docs/reference/specification/protocol/otlp/"
 	order_of_ops:                               "\(wikipedia)/wiki/Order_of_operations"
+	pagerduty:                                  "https://www.pagerduty.com/"
+	pagerduty_events_api:                       "https://developer.pagerduty.com/docs/events-api-v2/overview/"
 	papertrail:                                 "https://www.papertrail.com/"
 	papertrail_syslog:                          "https://help.papertrailapp.com/kb/how-it-works/http-api/#submitting-log-messages"
 	perl_windows:                               "https://www.perl.org/get.html#win32"
@@ -404,6 +442,8 @@ urls: {
 	protobuf:                                   "https://developers.google.com/protocol-buffers"
 	pulsar:                                     "https://pulsar.apache.org/"
 	pulsar_protocol:                            "https://pulsar.apache.org/docs/en/develop-binary-protocol/"
+	quickwit:                                   "https://quickwit.io/"
+	quickwit_ingest_api:                        "https://quickwit.io/docs/reference/rest-api#ingest-data-into-an-index"
 	raspbian:                                   "https://www.raspbian.org/"
 	rdkafka:                                    "\(github)/edenhill/librdkafka"
 	regex:                                      "\(wikipedia)/wiki/Regular_expression"
@@ -445,12 +485,19 @@ urls: {
 	sematext_monitoring:                        "https://sematext.com/docs/monitoring/"
 	sematext_registration:                      "https://apps.sematext.com/ui/registration"
 	semver:                                     "https://semver.org/"
+	serde_json:                                 "https://github.com/serde-rs/json"
 	sha1:                                       "\(wikipedia)/wiki/SHA-1"
 	sha2:                                       "\(wikipedia)/wiki/SHA-2"
 	sha3:                                       "\(wikipedia)/wiki/SHA-3"
 	signal:                                     "\(wikipedia)/wiki/Signal_(IPC)"
+	simd_json:                                  "https://github.com/simd-lite/simd-json"
+	slack:                                      "https://slack.com"
+	slack_block_kit:                            "https://api.slack.com/block-kit"
+	slack_rate_limits:                          "https://api.slack.com/docs/rate-limits"
 	snake_case:                                 "\(wikipedia)/wiki/Snake_case"
+	smtp:                                       "\(wikipedia)/wiki/Simple_Mail_Transfer_Protocol"
 	snappy:                                     "https://google.github.io/snappy/"
+	snmp:                                       "\(wikipedia)/wiki/Simple_Network_Management_Protocol"
 	socket:                                     "\(wikipedia)/wiki/Network_socket"
 	splunk:                                     "https://www.splunk.com"
 	splunk_hec:                                 "https://dev.splunk.com/enterprise/docs/dataapps/httpeventcollector/"
@@ -462,8 +509,12 @@ urls: {
 	splunk_hec_raw_endpoint:                    "https://docs.splunk.com/Documentation/Splunk/8.0.0/RESTREF/RESTinput#services.2Fcollector.2Fraw"
 	splunk_hec_setup:                           "https://docs.splunk.com/Documentation/Splunk/latest/Data/UsetheHTTPEventCollector"
 	splunk_hec_metadata:                        "https://docs.splunk.com/Documentation/Splunk/latest/Data/FormateventsforHTTPEventCollector#Event_metadata"
+	splunk_signalfx:                            "https://www.splunk.com/en_us/products/observability.html"
+	splunk_signalfx_access_tokens:              "https://docs.splunk.com/observability/en/admin/authentication/authentication-tokens/org-tokens.html"
+	splunk_signalfx_ingest_api:                 "https://dev.splunk.com/observability/reference/api/ingest_data/latest"
 	specs_instrumentation:                      "\(vector_repo)/blob/master/docs/specs/instrumentation.md)"
 	standard_streams:                           "\(wikipedia)/wiki/Standard_streams"
+	starrocks:                                  "https://www.starrocks.io/"
 	statsd:                                     "\(github)/statsd/statsd"
 	statsd_multi:                               "\(github)/statsd/statsd/blob/master/docs/metric_types.md#multi-metric-packets"
 	statsd_set:                                 "\(github)/statsd/statsd/blob/master/docs/metric_types.md#sets"
@@ -606,6 +657,10 @@ urls: {
 	vector_twitter:                             "https://twitter.com/vectordotdev"
 	vector_unit_tests:                          "\(vector_configuration)/unit-tests"
 	vector_version_branches:                    "\(vector_repo)/branches/all?query=v"
+	victorialogs:                               "https://docs.victoriametrics.com/victorialogs/"
+	victorialogs_jsonline:                      "https://docs.victoriametrics.com/victorialogs/data-ingestion/#json-stream-api"
+	victoriametrics:                            "https://victoriametrics.com"
+	victoriametrics_import:                     "https://docs.victoriametrics.com/#how-to-import-time-series-data"
 	vrl_announcement:                           "/blog/vector-remap-language"
 	vrl_boolean_expression:                     "\(vrl_reference)#boolean-expressions"
 	vrl_error_handling:                         "\(vrl_errors_reference)#handling"